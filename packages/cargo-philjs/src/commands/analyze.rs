@@ -0,0 +1,905 @@
+//! Bundle analysis for `cargo philjs build --analyze`
+//!
+//! Reads the built wasm module directly, twiggy-style: every function body
+//! is sized from the code section, named from the `name` section and
+//! attributed to the crate its (demangled) path starts with. The report
+//! compares against the previous analysis, and an interactive treemap is
+//! written to `.philjs/analyze/report.html`.
+//!
+//! wasm-opt drops the `name` section, so a copy of the module taken before
+//! optimization supplies function names when the final one has none.
+//!
+//! Size budgets (`--budget`, `--budget-gzip`) fail the build when the wasm
+//! grows past them, with or without `--analyze`.
+
+use anyhow::{bail, Context, Result};
+use bytesize::ByteSize;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::build::gzip_size;
+
+/// Where analyses and the treemap are kept between builds
+const ANALYZE_DIR: &str = ".philjs/analyze";
+
+/// Functions listed per crate in the treemap; the rest are grouped
+const TREEMAP_FUNCTIONS: usize = 200;
+
+// ============================================================================
+// Wasm parsing
+// ============================================================================
+
+/// A section and its size including the id and length prefix
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Section {
+    pub name: String,
+    pub size: u64,
+}
+
+/// A function body in the code section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    pub name: Option<String>,
+    pub size: u64,
+}
+
+/// What the analyzer needs from a module
+#[derive(Debug, Default)]
+pub struct WasmModule {
+    pub sections: Vec<Section>,
+    pub functions: Vec<Function>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, pos: 0 }
+    }
+
+    fn done(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        let byte = *self.bytes.get(self.pos).context("unexpected end of wasm")?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.bytes.len());
+        let end = end.context("unexpected end of wasm")?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn leb(&mut self) -> Result<u64> {
+        let mut result = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.byte()?;
+            if shift < 64 {
+                result |= u64::from(byte & 0x7f) << shift;
+            }
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+            shift += 7;
+            if shift > 70 {
+                bail!("invalid LEB128 in wasm");
+            }
+        }
+    }
+
+    fn len(&mut self) -> Result<usize> {
+        Ok(usize::try_from(self.leb()?)?)
+    }
+
+    fn name(&mut self) -> Result<String> {
+        let len = self.len()?;
+        Ok(String::from_utf8_lossy(self.bytes(len)?).into_owned())
+    }
+
+    fn limits(&mut self) -> Result<()> {
+        let flags = self.byte()?;
+        self.leb()?;
+        if flags & 1 != 0 {
+            self.leb()?;
+        }
+        Ok(())
+    }
+}
+
+const SECTION_NAMES: &[&str] = &[
+    "custom", "type", "import", "function", "table", "memory", "global", "export", "start",
+    "element", "code", "data", "datacount", "tag",
+];
+
+impl WasmModule {
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 8 || &bytes[..4] != b"\0asm" {
+            bail!("not a wasm module");
+        }
+        let mut reader = Reader::new(&bytes[8..]);
+        let mut module = WasmModule::default();
+        let mut imported_functions = 0u64;
+        let mut names: BTreeMap<u64, String> = BTreeMap::new();
+        let mut bodies: Vec<u64> = Vec::new();
+
+        while !reader.done() {
+            let start = reader.pos;
+            let id = reader.byte()?;
+            let len = reader.len()?;
+            let payload = reader.bytes(len)?;
+            let size = (reader.pos - start) as u64;
+            let mut section = Reader::new(payload);
+
+            let name = match id {
+                0 => {
+                    let custom = section.name()?;
+                    if custom == "name" {
+                        names = parse_function_names(&mut section)?;
+                    }
+                    format!("custom \"{}\"", custom)
+                }
+                2 => {
+                    imported_functions = count_imported_functions(&mut section)?;
+                    "import".to_string()
+                }
+                10 => {
+                    let count = section.len()?;
+                    for _ in 0..count {
+                        let body_start = section.pos;
+                        let body_len = section.len()?;
+                        section.bytes(body_len)?;
+                        bodies.push((section.pos - body_start) as u64);
+                    }
+                    "code".to_string()
+                }
+                _ => SECTION_NAMES
+                    .get(usize::from(id))
+                    .map_or_else(|| format!("unknown ({})", id), |n| n.to_string()),
+            };
+            module.sections.push(Section { name, size });
+        }
+
+        module.functions = bodies
+            .into_iter()
+            .enumerate()
+            .map(|(i, size)| Function {
+                name: names.get(&(imported_functions + i as u64)).cloned(),
+                size,
+            })
+            .collect();
+        Ok(module)
+    }
+
+    pub fn has_names(&self) -> bool {
+        self.functions.iter().any(|f| f.name.is_some())
+    }
+
+    pub fn total(&self) -> u64 {
+        // Magic and version
+        8 + self.sections.iter().map(|s| s.size).sum::<u64>()
+    }
+}
+
+fn count_imported_functions(section: &mut Reader) -> Result<u64> {
+    let mut functions = 0;
+    for _ in 0..section.len()? {
+        section.name()?;
+        section.name()?;
+        match section.byte()? {
+            0 => {
+                section.leb()?;
+                functions += 1;
+            }
+            1 => {
+                section.byte()?;
+                section.limits()?;
+            }
+            2 => section.limits()?,
+            3 => {
+                section.byte()?;
+                section.byte()?;
+            }
+            4 => {
+                section.byte()?;
+                section.leb()?;
+            }
+            kind => bail!("unknown import kind {}", kind),
+        }
+    }
+    Ok(functions)
+}
+
+/// Function names from the `name` custom section
+fn parse_function_names(section: &mut Reader) -> Result<BTreeMap<u64, String>> {
+    let mut names = BTreeMap::new();
+    while !section.done() {
+        let id = section.byte()?;
+        let len = section.len()?;
+        let mut sub = Reader::new(section.bytes(len)?);
+        if id == 1 {
+            for _ in 0..sub.len()? {
+                let index = sub.leb()?;
+                names.insert(index, sub.name()?);
+            }
+        }
+    }
+    Ok(names)
+}
+
+// ============================================================================
+// Attribution
+// ============================================================================
+
+/// Demangle a legacy Rust symbol (`_ZN4core3fmt5write17h…E`), dropping
+/// the hash; other names are returned as they are
+pub fn demangle(name: &str) -> String {
+    let Some(mut rest) = name.strip_prefix("_ZN").or_else(|| name.strip_prefix("__ZN")) else {
+        return strip_hash(name);
+    };
+    let mut parts = Vec::new();
+    while !rest.starts_with('E') && !rest.is_empty() {
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        let Ok(len) = rest[..digits].parse::<usize>() else {
+            return name.to_string();
+        };
+        let Some(ident) = rest.get(digits..digits + len) else {
+            return name.to_string();
+        };
+        parts.push(unescape(ident));
+        rest = &rest[digits + len..];
+    }
+    strip_hash(&parts.join("::"))
+}
+
+fn unescape(ident: &str) -> String {
+    const ESCAPES: &[(&str, &str)] = &[
+        ("$SP$", "@"),
+        ("$BP$", "*"),
+        ("$RF$", "&"),
+        ("$LT$", "<"),
+        ("$GT$", ">"),
+        ("$LP$", "("),
+        ("$RP$", ")"),
+        ("$C$", ","),
+        ("$u7e$", "~"),
+        ("$u20$", " "),
+        ("$u22$", "\""),
+        ("$u27$", "'"),
+        ("$u2b$", "+"),
+        ("$u3b$", ";"),
+        ("$u5b$", "["),
+        ("$u5d$", "]"),
+        ("$u7b$", "{"),
+        ("$u7d$", "}"),
+    ];
+    // Identifiers starting with an escape get a leading `_`
+    let ident = ident.strip_prefix("_$").map_or(ident.to_string(), |rest| format!("${}", rest));
+    let mut ident = ident.replace("..", "::");
+    for (from, to) in ESCAPES {
+        ident = ident.replace(from, to);
+    }
+    ident
+}
+
+/// Drop a trailing `::h0123456789abcdef`
+fn strip_hash(path: &str) -> String {
+    match path.rsplit_once("::") {
+        Some((head, hash))
+            if hash.len() == 17
+                && hash.starts_with('h')
+                && hash[1..].chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            head.to_string()
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// The crate a demangled path belongs to
+///
+/// `<alloc::vec::Vec<T> as core::ops::Drop>::drop` belongs to `alloc`;
+/// `<T as serde::Serialize>::serialize` to `serde`.
+pub fn crate_of(path: &str) -> String {
+    if path.starts_with("__wbindgen") || path.starts_with("__wbg") {
+        return "wasm-bindgen".to_string();
+    }
+    if let Some(inner) = path.strip_prefix('<') {
+        let (self_ty, trait_path) = match inner.split_once(" as ") {
+            Some((self_ty, rest)) => (self_ty, Some(rest)),
+            None => (inner, None),
+        };
+        let self_ty = self_ty
+            .trim_start_matches('&')
+            .trim_start_matches("mut ")
+            .trim_start_matches("dyn ");
+        if let Some((krate, _)) = self_ty.split_once("::") {
+            return krate.trim_start_matches('<').to_string();
+        }
+        if let Some((krate, _)) = trait_path.and_then(|t| t.split_once("::")) {
+            return krate.to_string();
+        }
+        return "[other]".to_string();
+    }
+    match path.split_once("::") {
+        Some((krate, _)) => krate.to_string(),
+        None => "[other]".to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrateSize {
+    pub name: String,
+    pub size: u64,
+    pub functions: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionSize {
+    pub name: String,
+    #[serde(rename = "crate")]
+    pub krate: String,
+    pub size: u64,
+}
+
+/// A module's size broken down by section, crate and function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    pub file: String,
+    pub size: u64,
+    pub gzip: u64,
+    pub sections: Vec<Section>,
+    /// Largest first
+    pub crates: Vec<CrateSize>,
+    /// Largest first
+    pub functions: Vec<FunctionSize>,
+    /// Names came from the module before wasm-opt
+    #[serde(default)]
+    pub names_before_opt: bool,
+}
+
+impl Analysis {
+    pub fn new(file: String, bytes: &[u8], module: &WasmModule, names: Option<&WasmModule>) -> Result<Self> {
+        let (named, names_before_opt) = match names {
+            Some(names) if !module.has_names() && names.has_names() => (names, true),
+            _ => (module, false),
+        };
+
+        let mut functions: Vec<FunctionSize> = named
+            .functions
+            .iter()
+            .map(|f| {
+                let (name, krate) = match &f.name {
+                    Some(raw) => {
+                        let name = demangle(raw);
+                        let krate = crate_of(&name);
+                        (name, krate)
+                    }
+                    None => ("[unnamed]".to_string(), "[unnamed]".to_string()),
+                };
+                FunctionSize { name, krate, size: f.size }
+            })
+            .collect();
+        functions.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        let mut crates: BTreeMap<String, CrateSize> = BTreeMap::new();
+        for f in &functions {
+            let entry = crates.entry(f.krate.clone()).or_insert_with(|| CrateSize {
+                name: f.krate.clone(),
+                size: 0,
+                functions: 0,
+            });
+            entry.size += f.size;
+            entry.functions += 1;
+        }
+        let data = module.sections.iter().filter(|s| s.name == "data").map(|s| s.size).sum::<u64>();
+        if data > 0 {
+            crates.insert(
+                "[data]".to_string(),
+                CrateSize {
+                    name: "[data]".to_string(),
+                    size: data,
+                    functions: 0,
+                },
+            );
+        }
+        let mut crates: Vec<CrateSize> = crates.into_values().collect();
+        crates.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(Analysis {
+            file,
+            size: bytes.len() as u64,
+            gzip: gzip_size(bytes)?,
+            sections: module.sections.clone(),
+            crates,
+            functions,
+            names_before_opt,
+        })
+    }
+}
+
+// ============================================================================
+// Entry points
+// ============================================================================
+
+fn analyze_dir() -> PathBuf {
+    PathBuf::from(ANALYZE_DIR)
+}
+
+/// The largest `.wasm` under `dir`
+fn find_wasm(dir: &Path) -> Option<PathBuf> {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "wasm"))
+        .max_by_key(|e| e.metadata().map(|m| m.len()).unwrap_or(0))
+        .map(|e| e.into_path())
+}
+
+/// Keep the module as wasm-bindgen produced it, before wasm-opt strips
+/// its names
+pub fn snapshot_names(pkg: &Path) -> Result<()> {
+    if let Some(wasm) = find_wasm(pkg) {
+        fs::create_dir_all(analyze_dir())?;
+        fs::copy(wasm, analyze_dir().join("names.wasm"))?;
+    }
+    Ok(())
+}
+
+/// Analyze the wasm in `out_dir`, print the report, compare with the
+/// previous build and write the treemap
+pub fn run(out_dir: &Path) -> Result<()> {
+    let wasm = find_wasm(out_dir).context("No .wasm found in the build output")?;
+    let bytes = fs::read(&wasm)?;
+    let module = WasmModule::parse(&bytes).with_context(|| format!("Failed to parse {}", wasm.display()))?;
+    let names = fs::read(analyze_dir().join("names.wasm"))
+        .ok()
+        .and_then(|bytes| WasmModule::parse(&bytes).ok());
+
+    let file = wasm.strip_prefix(out_dir).unwrap_or(&wasm).display().to_string();
+    let analysis = Analysis::new(file, &bytes, &module, names.as_ref())?;
+
+    let dir = analyze_dir();
+    fs::create_dir_all(&dir)?;
+    let last_path = dir.join("last.json");
+    let previous: Option<Analysis> = fs::read_to_string(&last_path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok());
+
+    print_analysis(&analysis, previous.as_ref());
+
+    let report = dir.join("report.html");
+    fs::write(&report, treemap_html(&analysis)?)?;
+    fs::write(&last_path, serde_json::to_string_pretty(&analysis)?)?;
+    println!(
+        "  {}  Treemap: {}\n",
+        "[analyze]".cyan().bold(),
+        report.display().to_string().cyan()
+    );
+    Ok(())
+}
+
+/// Fail when the wasm in `out_dir` exceeds a budget such as `"250 KB"`
+pub fn check_budget(out_dir: &Path, budget: Option<&str>, budget_gzip: Option<&str>) -> Result<()> {
+    if budget.is_none() && budget_gzip.is_none() {
+        return Ok(());
+    }
+    let wasm = find_wasm(out_dir).context("No .wasm found in the build output")?;
+    let bytes = fs::read(&wasm)?;
+
+    let mut over = Vec::new();
+    let checks = [
+        ("size", budget, bytes.len() as u64),
+        ("gzip size", budget_gzip, if budget_gzip.is_some() { gzip_size(&bytes)? } else { 0 }),
+    ];
+    for (label, limit, actual) in checks {
+        let Some(limit) = limit else {
+            continue;
+        };
+        let limit = parse_size(limit)?;
+        if actual > limit {
+            println!(
+                "  {}  wasm {} {} exceeds budget {} by {}",
+                "[budget]".red().bold(),
+                label,
+                ByteSize(actual),
+                ByteSize(limit),
+                ByteSize(actual - limit)
+            );
+            over.push(label);
+        } else {
+            println!(
+                "  {}  wasm {} {} within budget {}",
+                "[budget]".green().bold(),
+                label,
+                ByteSize(actual),
+                ByteSize(limit)
+            );
+        }
+    }
+    println!();
+
+    if !over.is_empty() {
+        bail!("Size budget exceeded ({})", over.join(", "));
+    }
+    Ok(())
+}
+
+/// `250kb`, `1.5 MB` or a plain byte count
+pub fn parse_size(size: &str) -> Result<u64> {
+    let trimmed = size.trim();
+    if let Ok(bytes) = trimmed.parse::<u64>() {
+        return Ok(bytes);
+    }
+    trimmed
+        .parse::<ByteSize>()
+        .map(|b| b.as_u64())
+        .map_err(|e| anyhow::anyhow!("Invalid size '{}': {}", size, e))
+}
+
+// ============================================================================
+// Output
+// ============================================================================
+
+fn delta(now: u64, before: Option<u64>) -> String {
+    match before {
+        Some(before) if before == now => "".to_string(),
+        Some(before) if now > before => format!("+{}", ByteSize(now - before)).red().to_string(),
+        Some(before) => format!("-{}", ByteSize(before - now)).green().to_string(),
+        None => "new".dimmed().to_string(),
+    }
+}
+
+fn print_analysis(analysis: &Analysis, previous: Option<&Analysis>) {
+    println!("  {}", "Bundle Analysis:".white().bold());
+    println!();
+    println!(
+        "    {}  {}  (gzip {})  {}",
+        analysis.file.dimmed(),
+        ByteSize(analysis.size).to_string().green(),
+        ByteSize(analysis.gzip).to_string().cyan(),
+        previous.map(|p| delta(analysis.size, Some(p.size))).unwrap_or_default()
+    );
+    println!();
+
+    println!("    {}", "Sections".white().bold());
+    let mut sections = analysis.sections.clone();
+    sections.sort_by_key(|s| std::cmp::Reverse(s.size));
+    for section in sections.iter().take(6) {
+        println!("      {:<24} {:>10}", section.name, ByteSize(section.size).to_string());
+    }
+    println!();
+
+    let max = analysis.crates.first().map_or(1, |c| c.size.max(1));
+    println!("    {}", "Crates".white().bold());
+    for krate in analysis.crates.iter().take(15) {
+        let before = previous.map(|p| p.crates.iter().find(|c| c.name == krate.name).map(|c| c.size));
+        let bar = "=".repeat(((krate.size as f64 / max as f64) * 20.0) as usize);
+        println!(
+            "      {:<24} {:>10}  {:<20}  {}",
+            krate.name,
+            ByteSize(krate.size).to_string(),
+            bar.cyan(),
+            before.map(|b| delta(krate.size, b)).unwrap_or_default()
+        );
+    }
+    if let Some(previous) = previous {
+        for gone in previous.crates.iter().filter(|p| !analysis.crates.iter().any(|c| c.name == p.name)) {
+            println!(
+                "      {:<24} {:>10}  {:<20}  {}",
+                gone.name.dimmed(),
+                "-",
+                "",
+                "removed".green()
+            );
+        }
+    }
+    println!();
+
+    println!("    {}", "Largest functions".white().bold());
+    for function in analysis.functions.iter().take(10) {
+        println!(
+            "      {:>10}  {}",
+            ByteSize(function.size).to_string(),
+            truncate(&function.name, 72).dimmed()
+        );
+    }
+    if analysis.names_before_opt {
+        println!(
+            "\n      {}",
+            "Function sizes are measured before wasm-opt".dimmed()
+        );
+    } else if analysis.functions.iter().all(|f| f.krate == "[unnamed]") {
+        println!(
+            "\n      {}",
+            "No function names in the module; build with --source-map to keep them".dimmed()
+        );
+    }
+    println!();
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    let mut out: String = s.chars().take(max - 1).collect();
+    out.push('…');
+    out
+}
+
+/// Treemap nodes: crates containing their largest functions
+#[derive(Serialize)]
+struct TreeNode {
+    name: String,
+    size: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<TreeNode>,
+}
+
+fn tree(analysis: &Analysis) -> TreeNode {
+    let mut children: Vec<TreeNode> = analysis
+        .crates
+        .iter()
+        .map(|krate| {
+            let functions: Vec<&FunctionSize> =
+                analysis.functions.iter().filter(|f| f.krate == krate.name).collect();
+            let mut nodes: Vec<TreeNode> = functions
+                .iter()
+                .take(TREEMAP_FUNCTIONS)
+                .map(|f| TreeNode {
+                    name: f.name.clone(),
+                    size: f.size,
+                    children: Vec::new(),
+                })
+                .collect();
+            if functions.len() > TREEMAP_FUNCTIONS {
+                let rest = &functions[TREEMAP_FUNCTIONS..];
+                nodes.push(TreeNode {
+                    name: format!("({} smaller functions)", rest.len()),
+                    size: rest.iter().map(|f| f.size).sum(),
+                    children: Vec::new(),
+                });
+            }
+            TreeNode {
+                name: krate.name.clone(),
+                size: krate.size,
+                children: nodes,
+            }
+        })
+        .collect();
+
+    let attributed: u64 = children.iter().map(|c| c.size).sum();
+    if analysis.size > attributed && !analysis.names_before_opt {
+        children.push(TreeNode {
+            name: "[wasm overhead]".to_string(),
+            size: analysis.size - attributed,
+            children: Vec::new(),
+        });
+    }
+
+    TreeNode {
+        name: analysis.file.clone(),
+        size: children.iter().map(|c| c.size).sum(),
+        children,
+    }
+}
+
+fn treemap_html(analysis: &Analysis) -> Result<String> {
+    let data = serde_json::to_string(&tree(analysis))?.replace("</", "<\\/");
+    Ok(TREEMAP_HTML.replace("__PHILJS_TREEMAP_DATA__", &data))
+}
+
+/// Self-contained squarified treemap; click a crate to zoom in
+const TREEMAP_HTML: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8" />
+<title>PhilJS bundle analysis</title>
+<style>
+  body { margin: 0; font: 13px/1.4 system-ui, sans-serif; background: #111; color: #eee; }
+  header { padding: 10px 16px; display: flex; gap: 12px; align-items: baseline; }
+  header a { color: #7cc4ff; cursor: pointer; }
+  #map { position: absolute; top: 44px; left: 8px; right: 8px; bottom: 8px; }
+  .cell { position: absolute; box-sizing: border-box; border: 1px solid #111; overflow: hidden;
+          padding: 2px 4px; cursor: pointer; color: #111; white-space: nowrap; text-overflow: ellipsis; }
+  .cell:hover { filter: brightness(1.15); }
+</style>
+</head>
+<body>
+<header><strong>Bundle analysis</strong><span id="crumbs"></span><span id="info"></span></header>
+<div id="map"></div>
+<script>
+const root = __PHILJS_TREEMAP_DATA__;
+const map = document.getElementById('map');
+const crumbs = document.getElementById('crumbs');
+const info = document.getElementById('info');
+const fmt = (n) => n >= 1048576 ? (n / 1048576).toFixed(2) + ' MiB' : n >= 1024 ? (n / 1024).toFixed(1) + ' KiB' : n + ' B';
+const color = (i) => `hsl(${(i * 47) % 360}, 60%, 65%)`;
+
+function worst(row, side) {
+  const sum = row.reduce((a, n) => a + n.area, 0);
+  const max = Math.max(...row.map((n) => n.area)), min = Math.min(...row.map((n) => n.area));
+  return Math.max((side * side * max) / (sum * sum), (sum * sum) / (side * side * min));
+}
+
+function layout(nodes, x, y, w, h) {
+  const total = nodes.reduce((a, n) => a + n.size, 0) || 1;
+  const items = nodes.filter((n) => n.size > 0).map((n) => ({ node: n, area: (n.size / total) * w * h }));
+  const out = [];
+  while (items.length) {
+    const side = Math.min(w, h);
+    const row = [items.shift()];
+    while (items.length && worst([...row, items[0]], side) <= worst(row, side)) row.push(items.shift());
+    const sum = row.reduce((a, n) => a + n.area, 0);
+    const thick = sum / side;
+    let offset = 0;
+    for (const item of row) {
+      const len = item.area / thick;
+      out.push(w >= h
+        ? { node: item.node, x, y: y + offset, w: thick, h: len }
+        : { node: item.node, x: x + offset, y, w: len, h: thick });
+      offset += len;
+    }
+    if (w >= h) { x += thick; w -= thick; } else { y += thick; h -= thick; }
+  }
+  return out;
+}
+
+function show(path) {
+  const node = path[path.length - 1];
+  map.innerHTML = '';
+  crumbs.innerHTML = '';
+  path.forEach((p, i) => {
+    const a = document.createElement('a');
+    a.textContent = p.name;
+    a.onclick = () => show(path.slice(0, i + 1));
+    crumbs.append(i ? ' / ' : '', a);
+  });
+  info.textContent = fmt(node.size);
+  const children = node.children && node.children.length ? node.children : [node];
+  layout(children, 0, 0, map.clientWidth, map.clientHeight).forEach((r, i) => {
+    const cell = document.createElement('div');
+    cell.className = 'cell';
+    Object.assign(cell.style, { left: r.x + 'px', top: r.y + 'px', width: r.w + 'px', height: r.h + 'px', background: color(i) });
+    const pct = ((r.node.size / root.size) * 100).toFixed(2);
+    cell.title = `${r.node.name}\n${fmt(r.node.size)} (${pct}%)`;
+    if (r.w > 60 && r.h > 16) cell.textContent = `${r.node.name} ${fmt(r.node.size)}`;
+    if (r.node.children && r.node.children.length) cell.onclick = () => show([...path, r.node]);
+    map.append(cell);
+  });
+}
+
+window.addEventListener('resize', () => show([root]));
+show([root]);
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leb(mut value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return out;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn string(s: &str) -> Vec<u8> {
+        let mut out = leb(s.len() as u64);
+        out.extend(s.as_bytes());
+        out
+    }
+
+    fn section(id: u8, payload: Vec<u8>) -> Vec<u8> {
+        let mut out = vec![id];
+        out.extend(leb(payload.len() as u64));
+        out.extend(payload);
+        out
+    }
+
+    /// One imported function and two defined ones, with names
+    fn module() -> Vec<u8> {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+
+        let mut import = leb(1);
+        import.extend(string("env"));
+        import.extend(string("log"));
+        import.extend([0, 0]);
+        wasm.extend(section(2, import));
+
+        let mut code = leb(2);
+        for body in [vec![0u8; 10], vec![0u8; 300]] {
+            code.extend(leb(body.len() as u64));
+            code.extend(body);
+        }
+        wasm.extend(section(10, code));
+
+        let mut names = leb(3);
+        for (index, name) in [
+            (0, "log"),
+            (1, "_ZN4core3fmt5write17h0123456789abcdefE"),
+            (2, "_ZN60_$LT$alloc..vec..Vec$LT$T$GT$$u20$as$u20$core..ops..Drop$GT$4drop17h0123456789abcdefE"),
+        ] {
+            names.extend(leb(index));
+            names.extend(string(name));
+        }
+        let mut custom = string("name");
+        custom.push(1);
+        custom.extend(leb(names.len() as u64));
+        custom.extend(names);
+        wasm.extend(section(0, custom));
+        wasm
+    }
+
+    #[test]
+    fn test_parse_module() {
+        let bytes = module();
+        let module = WasmModule::parse(&bytes).unwrap();
+        assert_eq!(module.total(), bytes.len() as u64);
+        assert_eq!(module.functions.len(), 2);
+        assert_eq!(module.functions[0].size, 11);
+        assert_eq!(module.functions[1].size, 302);
+        assert_eq!(module.functions[0].name.as_deref(), Some("_ZN4core3fmt5write17h0123456789abcdefE"));
+        let names: Vec<_> = module.sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["import", "code", "custom \"name\""]);
+    }
+
+    #[test]
+    fn test_demangle() {
+        assert_eq!(demangle("_ZN4core3fmt5write17h0123456789abcdefE"), "core::fmt::write");
+        assert_eq!(
+            demangle("_ZN60_$LT$alloc..vec..Vec$LT$T$GT$$u20$as$u20$core..ops..Drop$GT$4drop17h0123456789abcdefE"),
+            "<alloc::vec::Vec<T> as core::ops::Drop>::drop"
+        );
+        assert_eq!(demangle("memcpy"), "memcpy");
+    }
+
+    #[test]
+    fn test_crate_of() {
+        assert_eq!(crate_of("core::fmt::write"), "core");
+        assert_eq!(crate_of("<alloc::vec::Vec<T> as core::ops::Drop>::drop"), "alloc");
+        assert_eq!(crate_of("<T as serde::ser::Serialize>::serialize"), "serde");
+        assert_eq!(crate_of("<&T as core::fmt::Debug>::fmt"), "core");
+        assert_eq!(crate_of("__wbindgen_malloc"), "wasm-bindgen");
+        assert_eq!(crate_of("memcpy"), "[other]");
+    }
+
+    #[test]
+    fn test_analysis() {
+        let bytes = module();
+        let module = WasmModule::parse(&bytes).unwrap();
+        let analysis = Analysis::new("app_bg.wasm".to_string(), &bytes, &module, None).unwrap();
+        let crates: Vec<_> = analysis.crates.iter().map(|c| (c.name.as_str(), c.size)).collect();
+        assert_eq!(crates, [("alloc", 302), ("core", 11)]);
+        assert_eq!(analysis.functions[0].name, "<alloc::vec::Vec<T> as core::ops::Drop>::drop");
+        assert!(treemap_html(&analysis).unwrap().contains("\"name\":\"alloc\""));
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+        assert_eq!(parse_size("250kb").unwrap(), 250_000);
+        assert_eq!(parse_size("1 KiB").unwrap(), 1024);
+        assert!(parse_size("big").is_err());
+    }
+}
@@ -1,40 +1,165 @@
 //! Production build command
 //!
 //! Optimized production builds with:
-//! - WASM size optimization (wasm-opt)
-//! - Tree shaking
-//! - Source map generation
-//! - Bundle analysis
+//! - WASM size optimization (wasm-opt, configurable passes)
+//! - Content-hashed asset file names
+//! - `manifest.json` mapping source paths to hashed files
+//! - Separate client and server output for SSR
+//! - Build-time rendering of ssg/isr routes (`--prerender`, see [`super::prerender`])
+//! - Cloudflare Workers bundle and `wrangler.toml` (`--target=cloudflare`)
+//! - Per-artifact size report, `--analyze` breakdown and size budgets
+//!
+//! Output layout:
+//!
+//! ```text
+//! dist/                    dist/            (--ssr)
+//!   index.html               client/
+//!   styles.1a2b3c4d.css        index.html
+//!   pkg/app.5e6f7a8b.js        manifest.json
+//!   manifest.json              pkg/...
+//!                            server/
+//!                              my-app
+//! ```
+//!
+//! `--target=cloudflare` splits the same way, with `worker/` in place of
+//! `server/` (see [`super::worker`]).
+//!
+//! Defaults come from `[build]`, `[ssr]` and `[assets]` in `philjs.toml`,
+//! with the `prod` profile for `--release` builds and `dev` otherwise.
+//!
+//! The manifest is the flat source-to-file format read by
+//! `philjs::meta::AssetManifest`, so the server integrations resolve
+//! `pkg/app.js` to its hashed name.
 
+use super::{analyze, prerender, worker};
+use crate::config::Config;
 use crate::BuildTarget;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle, MultiProgress};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use bytesize::ByteSize;
 
-/// Build result with metrics
-struct BuildResult {
-    wasm_size: u64,
-    wasm_size_gzip: u64,
-    total_size: u64,
-    build_time: std::time::Duration,
+/// Name of the asset manifest written to the client output directory
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Production build options
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+    pub release: bool,
+    pub out_dir: String,
+    pub target: BuildTarget,
+    /// Also build the server, splitting output into `client/` and `server/`
+    pub ssr: bool,
+    /// Render ssg/isr routes to HTML with the server; implies `ssr`
+    pub prerender: bool,
+    pub source_map: bool,
+    pub no_optimize: bool,
+    pub analyze: bool,
+    pub minify: bool,
+    /// wasm-opt optimization level: `0`-`4`, `s` or `z`
+    pub opt_level: String,
+    /// Extra wasm-opt passes, e.g. `strip-debug` or `dce`
+    pub wasm_opt_passes: Vec<String>,
+    /// Keep original asset file names
+    pub no_hash: bool,
+    /// Fail the build when the wasm exceeds this size, e.g. `250kb`
+    pub budget: Option<String>,
+    /// Fail the build when the gzipped wasm exceeds this size
+    pub budget_gzip: Option<String>,
+    /// Copied to the root of the client output
+    pub static_dir: String,
+    /// Copied to `assets/` in the client output
+    pub assets_dir: String,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        Self {
+            release: false,
+            out_dir: "dist".to_string(),
+            target: BuildTarget::Browser,
+            ssr: false,
+            prerender: false,
+            source_map: false,
+            no_optimize: false,
+            analyze: false,
+            minify: false,
+            opt_level: "z".to_string(),
+            wasm_opt_passes: Vec::new(),
+            no_hash: false,
+            budget: None,
+            budget_gzip: None,
+            static_dir: "static".to_string(),
+            assets_dir: "assets".to_string(),
+        }
+    }
+}
+
+impl BuildOptions {
+    /// Options from `[build]`, `[ssr]` and `[assets]` in `philjs.toml`
+    pub fn from_config(config: &Config, release: bool) -> Self {
+        let build = &config.build;
+        Self {
+            release,
+            out_dir: build.out_dir.clone(),
+            target: config.build_target(),
+            ssr: config.ssr.enabled,
+            prerender: build.prerender,
+            source_map: build.source_maps,
+            minify: build.minify,
+            opt_level: build.opt_level.clone(),
+            wasm_opt_passes: build.wasm_opt_passes.clone(),
+            no_hash: !build.hash,
+            budget: build.budget.clone(),
+            budget_gzip: build.budget_gzip.clone(),
+            static_dir: config.assets.static_dir.clone(),
+            assets_dir: config.assets.assets_dir.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A file in `manifest.json`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestEntry {
+    /// Output path, relative to the manifest
+    file: String,
+    size: u64,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    is_entry: bool,
+}
+
+/// A built file in the size report
+struct Artifact {
+    name: String,
+    size: u64,
+    gzip: Option<u64>,
 }
 
 /// Run production build
-pub async fn run(
-    release: bool,
-    out_dir: &str,
-    target: BuildTarget,
-    ssr: bool,
-    source_map: bool,
-    no_optimize: bool,
-    analyze: bool,
-    minify: bool,
-) -> Result<()> {
+pub async fn run(options: BuildOptions) -> Result<()> {
+    let BuildOptions {
+        release,
+        ref out_dir,
+        target,
+        ssr,
+        prerender,
+        source_map,
+        no_optimize,
+        analyze,
+        minify,
+        ..
+    } = options;
+    let ssr = ssr || prerender;
     let start = Instant::now();
 
     print_build_header(release, target, ssr);
@@ -45,6 +170,9 @@ pub async fn run(
         fs::remove_dir_all(out_path)?;
     }
     fs::create_dir_all(out_path)?;
+    let edge = matches!(target, BuildTarget::Cloudflare);
+    let client_dir = if ssr || edge { out_path.join("client") } else { out_path.to_path_buf() };
+    fs::create_dir_all(&client_dir)?;
 
     let mp = MultiProgress::new();
 
@@ -54,36 +182,64 @@ pub async fn run(
     pb1.set_message("Compiling Rust to WASM...");
     pb1.set_position(0);
 
-    build_wasm(release, target)?;
+    let should_minify = minify || (release && !no_optimize);
+    build_wasm(release, target, should_minify)?;
     pb1.set_position(40);
     pb1.set_message("WASM compilation complete");
 
     // Step 2: Optimize WASM (if release and not skipped)
-    let should_minify = minify || (release && !no_optimize);
     if should_minify {
         pb1.set_message("Optimizing and minifying WASM bundle...");
-        optimize_wasm()?;
+        if analyze {
+            analyze::snapshot_names(Path::new("pkg"))?;
+        }
+        optimize_wasm(&options.opt_level, &options.wasm_opt_passes, source_map)?;
         pb1.set_position(60);
     }
 
     // Step 3: Copy static files
     pb1.set_message("Copying static assets...");
-    copy_static_files(out_dir)?;
-    pb1.set_position(75);
+    copy_static_files(&options, &client_dir)?;
+    emit_liveview_client(&client_dir)?;
+    pb1.set_position(70);
 
     // Step 4: Copy WASM bundle
     pb1.set_message("Bundling output...");
-    copy_wasm_bundle(out_dir)?;
+    copy_wasm_bundle(&client_dir)?;
+    pb1.set_position(80);
+
+    // Step 5: Fingerprint assets and write the manifest
+    pb1.set_message("Hashing assets...");
+    let manifest = if options.no_hash {
+        list_assets(&client_dir)?
+    } else {
+        fingerprint_assets(&client_dir)?
+    };
+    write_manifest(&client_dir, &manifest)?;
     pb1.set_position(90);
 
-    // Step 5: Build SSR if enabled
+    // Step 6: Build SSR if enabled
     if ssr {
         pb1.set_message("Building SSR bundle...");
-        build_ssr(release)?;
+        let server = build_ssr(release)?;
+        let server_dir = out_path.join("server");
+        fs::create_dir_all(&server_dir)?;
+        fs::copy(&server, server_dir.join(server.file_name().unwrap()))
+            .context("Failed to copy server binary")?;
+        pb1.set_position(95);
+
+        if prerender {
+            pb1.set_message("Prerendering pages...");
+            prerender::run(&server, &client_dir, Path::new(".")).await?;
+        }
+    }
+    if edge {
+        pb1.set_message("Building Cloudflare Worker...");
+        worker::build(release, &out_path.join("worker"))?;
         pb1.set_position(95);
     }
 
-    // Step 6: Generate source maps if requested
+    // Step 7: Generate source maps if requested
     if source_map {
         pb1.set_message("Generating source maps...");
         generate_source_maps(out_dir)?;
@@ -93,16 +249,22 @@ pub async fn run(
     pb1.finish_with_message("Build complete!");
 
     // Calculate sizes
-    let result = calculate_build_metrics(out_dir, start.elapsed())?;
+    let artifacts = collect_artifacts(out_path)?;
 
     // Print summary
-    print_build_summary(&result, out_dir, analyze)?;
+    print_build_summary(&artifacts, out_dir, start.elapsed());
+    if edge {
+        worker::write_wrangler_toml(Path::new("."), out_path)?;
+    }
 
     // Bundle analysis
     if analyze {
-        print_bundle_analysis(out_dir)?;
+        analyze::run(&client_dir)?;
     }
 
+    // Size budgets
+    analyze::check_budget(&client_dir, options.budget.as_deref(), options.budget_gzip.as_deref())?;
+
     Ok(())
 }
 
@@ -137,7 +299,9 @@ fn print_build_header(release: bool, target: BuildTarget, ssr: bool) {
 }
 
 /// Build WASM
-fn build_wasm(release: bool, target: BuildTarget) -> Result<()> {
+///
+/// wasm-pack's own wasm-opt run is skipped when we optimize afterwards.
+fn build_wasm(release: bool, target: BuildTarget, optimize: bool) -> Result<()> {
     let target_flag = match target {
         BuildTarget::Browser => "web",
         BuildTarget::Node => "nodejs",
@@ -157,6 +321,9 @@ fn build_wasm(release: bool, target: BuildTarget) -> Result<()> {
     } else {
         args.push("--dev");
     }
+    if optimize {
+        args.push("--no-opt");
+    }
 
     let output = Command::new("wasm-pack")
         .args(&args)
@@ -174,7 +341,9 @@ fn build_wasm(release: bool, target: BuildTarget) -> Result<()> {
 }
 
 /// Optimize WASM with wasm-opt
-fn optimize_wasm() -> Result<()> {
+///
+/// `level` is passed as `-O<level>`; each of `passes` as `--<pass>`.
+fn optimize_wasm(level: &str, passes: &[String], keep_names: bool) -> Result<()> {
     // Check if wasm-opt is available
     if which::which("wasm-opt").is_err() {
         println!(
@@ -188,6 +357,11 @@ fn optimize_wasm() -> Result<()> {
         return Ok(());
     }
 
+    let level = level.trim_start_matches("-O").trim_start_matches('O');
+    if !matches!(level, "0" | "1" | "2" | "3" | "4" | "s" | "z") {
+        anyhow::bail!("Invalid wasm-opt level `{}` (expected 0-4, s or z)", level);
+    }
+
     let wasm_files: Vec<_> = walkdir::WalkDir::new("pkg")
         .into_iter()
         .filter_map(|e| e.ok())
@@ -198,38 +372,52 @@ fn optimize_wasm() -> Result<()> {
         let path = entry.path();
         let temp_path = path.with_extension("wasm.opt");
 
-        Command::new("wasm-opt")
-            .args([
-                "-Oz",                      // Optimize for size
-                "--enable-mutable-globals", // Enable mutable globals
-                "--enable-simd",            // Enable SIMD
-                "-o", temp_path.to_str().unwrap(),
-                path.to_str().unwrap(),
-            ])
+        let mut command = Command::new("wasm-opt");
+        command
+            .arg(format!("-O{}", level))
+            .args(["--enable-mutable-globals", "--enable-simd"]);
+        for pass in passes {
+            command.arg(format!("--{}", pass.trim_start_matches('-')));
+        }
+        if keep_names {
+            command.arg("-g");
+        }
+        let output = command
+            .arg("-o")
+            .arg(&temp_path)
+            .arg(path)
             .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()?;
+            .stderr(Stdio::piped())
+            .output()
+            .context("Failed to run wasm-opt")?;
+
+        if !output.status.success() {
+            let _ = fs::remove_file(&temp_path);
+            anyhow::bail!(
+                "wasm-opt failed on {}:\n{}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
 
         // Replace original with optimized
-        if temp_path.exists() {
-            fs::rename(&temp_path, path)?;
-        }
+        fs::rename(&temp_path, path)?;
     }
 
     Ok(())
 }
 
 /// Copy static files to output
-fn copy_static_files(out_dir: &str) -> Result<()> {
-    let static_dir = Path::new("static");
+fn copy_static_files(options: &BuildOptions, out_dir: &Path) -> Result<()> {
+    let static_dir = Path::new(&options.static_dir);
     if static_dir.exists() {
         let options = fs_extra::dir::CopyOptions::new().content_only(true);
         fs_extra::dir::copy(static_dir, out_dir, &options)?;
     }
 
-    let assets_dir = Path::new("assets");
+    let assets_dir = Path::new(&options.assets_dir);
     if assets_dir.exists() {
-        let dest = Path::new(out_dir).join("assets");
+        let dest = out_dir.join("assets");
         fs::create_dir_all(&dest)?;
         let options = fs_extra::dir::CopyOptions::new().content_only(true);
         fs_extra::dir::copy(assets_dir, &dest, &options)?;
@@ -238,10 +426,71 @@ fn copy_static_files(out_dir: &str) -> Result<()> {
     Ok(())
 }
 
+/// Emit `philjs-live.js` for projects using LiveView, unless the project ships its own
+fn emit_liveview_client(out_dir: &Path) -> Result<()> {
+    let manifest = fs::read_to_string("Cargo.toml").unwrap_or_default();
+    let uses_liveview = manifest
+        .parse::<toml::Table>()
+        .map(|manifest| uses_liveview(&manifest))
+        .unwrap_or(false);
+    if !uses_liveview {
+        return Ok(());
+    }
+
+    let dest = out_dir.join("philjs-live.js");
+    if dest.exists() {
+        return Ok(());
+    }
+
+    let js = philjs::liveview::client_js(&philjs::liveview::LiveClientConfig::default());
+    fs::write(&dest, js).context("Failed to write LiveView client")?;
+    Ok(())
+}
+
+/// Whether a manifest enables the `liveview` feature of a philjs dependency,
+/// either on the dependency itself or through one of the package's features
+fn uses_liveview(manifest: &toml::Table) -> bool {
+    let tables = std::iter::once(manifest).chain(
+        manifest
+            .get("target")
+            .and_then(|targets| targets.as_table())
+            .into_iter()
+            .flat_map(|targets| targets.values().filter_map(|target| target.as_table())),
+    );
+    let mut philjs_deps = Vec::new();
+    for table in tables {
+        let Some(deps) = table.get("dependencies").and_then(|deps| deps.as_table()) else {
+            continue;
+        };
+        for (name, dep) in deps {
+            let package = dep.get("package").and_then(|p| p.as_str()).unwrap_or(name);
+            if !super::doctor::is_philjs_crate(package) {
+                continue;
+            }
+            let features = dep.get("features").and_then(|f| f.as_array());
+            if features.is_some_and(|f| f.iter().any(|f| f.as_str() == Some("liveview"))) {
+                return true;
+            }
+            philjs_deps.push(name.as_str());
+        }
+    }
+
+    // e.g. `server = ["philjs-axum/liveview"]`
+    let Some(features) = manifest.get("features").and_then(|f| f.as_table()) else {
+        return false;
+    };
+    features
+        .values()
+        .filter_map(|enables| enables.as_array())
+        .flatten()
+        .filter_map(|enable| enable.as_str()?.split_once('/'))
+        .any(|(dep, feature)| feature == "liveview" && philjs_deps.contains(&dep.trim_end_matches('?')))
+}
+
 /// Copy WASM bundle to output
-fn copy_wasm_bundle(out_dir: &str) -> Result<()> {
+fn copy_wasm_bundle(out_dir: &Path) -> Result<()> {
     let pkg_dir = Path::new("pkg");
-    let dest_pkg = Path::new(out_dir).join("pkg");
+    let dest_pkg = out_dir.join("pkg");
     fs::create_dir_all(&dest_pkg)?;
 
     // Copy only necessary files
@@ -265,22 +514,35 @@ fn copy_wasm_bundle(out_dir: &str) -> Result<()> {
     Ok(())
 }
 
-/// Build SSR bundle
-fn build_ssr(release: bool) -> Result<()> {
-    let mut args = vec!["build"];
+/// Build SSR bundle, returning the server executable
+fn build_ssr(release: bool) -> Result<PathBuf> {
+    let mut args = vec!["build", "--message-format=json-render-diagnostics"];
     if release {
         args.push("--release");
     }
     args.extend(["--features", "ssr"]);
 
-    Command::new("cargo")
+    let output = Command::new("cargo")
         .args(&args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .status()
+        .output()
         .context("Failed to build SSR")?;
 
-    Ok(())
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("SSR build failed:\n{}", stderr);
+    }
+
+    cargo_metadata::Message::parse_stream(output.stdout.as_slice())
+        .flatten()
+        .filter_map(|message| match message {
+            cargo_metadata::Message::CompilerArtifact(artifact) => artifact.executable,
+            _ => None,
+        })
+        .last()
+        .map(|path| path.into_std_path_buf())
+        .context("SSR build produced no server binary; add a [[bin]] target")
 }
 
 /// Generate source maps
@@ -290,36 +552,223 @@ fn generate_source_maps(out_dir: &str) -> Result<()> {
     Ok(())
 }
 
-/// Calculate build metrics
-fn calculate_build_metrics(out_dir: &str, build_time: std::time::Duration) -> Result<BuildResult> {
-    let mut total_size = 0u64;
-    let mut wasm_size = 0u64;
+/// Files whose URL is fixed, so they are never renamed
+const FIXED_NAMES: &[&str] = &[
+    "philjs-live.js",
+    "sw.js",
+    "service-worker.js",
+    "favicon.ico",
+    "robots.txt",
+];
+
+/// Whether `path` (relative to the output directory) gets a content hash
+fn is_fingerprintable(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    if path.starts_with(".well-known/") || FIXED_NAMES.contains(&name) {
+        return false;
+    }
+    matches!(
+        extension(path),
+        "js" | "mjs" | "css" | "wasm" | "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "avif"
+            | "woff" | "woff2" | "ttf" | "otf"
+    )
+}
 
-    for entry in walkdir::WalkDir::new(out_dir) {
+/// Whether file references inside `path` are rewritten
+fn is_text(path: &str) -> bool {
+    matches!(extension(path), "js" | "mjs" | "css" | "html" | "htm" | "json" | "webmanifest" | "svg")
+}
+
+fn extension(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rsplit_once('.').map_or("", |(_, ext)| ext)
+}
+
+/// Files under `dir` as `/`-separated relative paths
+fn relative_files(dir: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(dir) {
         let entry = entry?;
         if entry.file_type().is_file() {
-            let size = entry.metadata()?.len();
-            total_size += size;
+            let relative = entry.path().strip_prefix(dir)?;
+            let parts: Vec<_> = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+            files.push(parts.join("/"));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// `pkg/app.js` with hash `1a2b3c4d` becomes `pkg/app.1a2b3c4d.js`
+fn hashed_name(path: &str, hash: &str) -> String {
+    let (dir, name) = match path.rsplit_once('/') {
+        Some((dir, name)) => (Some(dir), name),
+        None => (None, path),
+    };
+    let name = match name.split_once('.') {
+        Some((stem, ext)) => format!("{}.{}.{}", stem, hash, ext),
+        None => format!("{}.{}", name, hash),
+    };
+    match dir {
+        Some(dir) => format!("{}/{}", dir, name),
+        None => name,
+    }
+}
 
-            if entry.path().extension().map_or(false, |ext| ext == "wasm") {
-                wasm_size = size;
+fn content_hash(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    digest[..4].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Replace whole-name occurrences of `from` in `text`
+///
+/// A match must not be part of a longer file name, so `app.js` leaves
+/// `myapp.js` and `app.js.map` alone.
+fn replace_reference(text: &str, from: &str, to: &str) -> String {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.');
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(from) {
+        let before = rest[..index].chars().next_back();
+        let after = rest[index + from.len()..].chars().next();
+        out.push_str(&rest[..index]);
+        if !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char) {
+            out.push_str(to);
+        } else {
+            out.push_str(from);
+        }
+        rest = &rest[index + from.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Point references in the file at `path` to renamed files
+///
+/// Tries the full relative path, the path relative to the referencing
+/// file's directory, and the bare file name when it's unambiguous.
+fn rewrite_references(text: &str, path: &str, renames: &[(String, String)]) -> String {
+    let dir = path.rsplit_once('/').map(|(dir, _)| format!("{}/", dir));
+    let base = |p: &str| p.rsplit('/').next().unwrap_or(p).to_string();
+
+    let mut text = text.to_string();
+    for (from, to) in renames {
+        text = replace_reference(&text, from, to);
+        if let Some(dir) = &dir {
+            if let (Some(from), Some(to)) = (from.strip_prefix(dir.as_str()), to.strip_prefix(dir.as_str())) {
+                text = replace_reference(&text, from, to);
             }
         }
+        let name = base(from);
+        if renames.iter().filter(|(other, _)| base(other) == name).count() == 1 {
+            text = replace_reference(&text, &name, &base(to));
+        }
+    }
+    text
+}
+
+/// Rename assets under `dir` to content-hashed names, rewriting references
+/// to them, and return the manifest entries
+///
+/// Files are hashed after the files they reference, so a changed image
+/// also changes the hash of the stylesheet that uses it.
+fn fingerprint_assets(dir: &Path) -> Result<BTreeMap<String, ManifestEntry>> {
+    let files = relative_files(dir)?;
+
+    let mut hashable: Vec<&String> = files.iter().filter(|f| is_fingerprintable(f)).collect();
+    hashable.sort_by_key(|f| {
+        let rank = match extension(f) {
+            "css" => 1,
+            "js" | "mjs" => 2,
+            _ => 0,
+        };
+        // Nested modules (wasm-bindgen snippets) before their importers
+        (rank, std::cmp::Reverse(f.matches('/').count()))
+    });
+
+    let mut renames: Vec<(String, String)> = Vec::new();
+    for file in hashable {
+        let path = dir.join(file);
+        let mut bytes = fs::read(&path)?;
+        if is_text(file) {
+            let text = String::from_utf8_lossy(&bytes);
+            bytes = rewrite_references(&text, file, &renames).into_bytes();
+        }
+        let hashed = hashed_name(file, &content_hash(&bytes));
+        fs::write(dir.join(&hashed), &bytes)?;
+        fs::remove_file(&path)?;
+        renames.push((file.clone(), hashed));
+    }
+
+    // HTML and other fixed-name files keep their names but follow renames
+    for file in files.iter().filter(|f| is_text(f) && !is_fingerprintable(f)) {
+        let path = dir.join(file);
+        let text = fs::read_to_string(&path)?;
+        let rewritten = rewrite_references(&text, file, &renames);
+        if rewritten != text {
+            fs::write(&path, rewritten)?;
+        }
+    }
+
+    let mut manifest = BTreeMap::new();
+    for (source, file) in renames {
+        let size = fs::metadata(dir.join(&file))?.len();
+        manifest.insert(source.clone(), manifest_entry(&source, file, size));
+    }
+    Ok(manifest)
+}
+
+/// Manifest entries for unhashed assets, mapping each file to itself
+fn list_assets(dir: &Path) -> Result<BTreeMap<String, ManifestEntry>> {
+    let mut manifest = BTreeMap::new();
+    for file in relative_files(dir)?.into_iter().filter(|f| is_fingerprintable(f)) {
+        let size = fs::metadata(dir.join(&file))?.len();
+        manifest.insert(file.clone(), manifest_entry(&file, file, size));
     }
+    Ok(manifest)
+}
+
+fn manifest_entry(source: &str, file: String, size: u64) -> ManifestEntry {
+    ManifestEntry {
+        file,
+        size,
+        is_entry: source == "pkg/app.js",
+    }
+}
 
-    // Estimate gzip size (typically 30-40% of original for WASM)
-    let wasm_size_gzip = (wasm_size as f64 * 0.35) as u64;
+fn write_manifest(dir: &Path, manifest: &BTreeMap<String, ManifestEntry>) -> Result<()> {
+    let json = serde_json::to_string_pretty(manifest)?;
+    fs::write(dir.join(MANIFEST_FILE), json).context("Failed to write manifest.json")
+}
+
+/// Every file under `out_dir`, largest first
+fn collect_artifacts(out_dir: &Path) -> Result<Vec<Artifact>> {
+    let mut artifacts = Vec::new();
+    for name in relative_files(out_dir)? {
+        let bytes = fs::read(out_dir.join(&name))?;
+        let gzip = if is_text(&name) || extension(&name) == "wasm" {
+            Some(gzip_size(&bytes)?)
+        } else {
+            None
+        };
+        artifacts.push(Artifact {
+            name,
+            size: bytes.len() as u64,
+            gzip,
+        });
+    }
+    artifacts.sort_by(|a, b| b.size.cmp(&a.size));
+    Ok(artifacts)
+}
 
-    Ok(BuildResult {
-        wasm_size,
-        wasm_size_gzip,
-        total_size,
-        build_time,
-    })
+pub(super) fn gzip_size(bytes: &[u8]) -> Result<u64> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(bytes)?;
+    Ok(encoder.finish()?.len() as u64)
 }
 
 /// Print build summary
-fn print_build_summary(result: &BuildResult, out_dir: &str, analyze: bool) -> Result<()> {
+fn print_build_summary(artifacts: &[Artifact], out_dir: &str, build_time: Duration) {
     println!();
     println!("  {}  Build completed successfully!", "[done]".green().bold());
     println!();
@@ -327,70 +776,110 @@ fn print_build_summary(result: &BuildResult, out_dir: &str, analyze: bool) -> Re
     println!(
         "  {}  {:.2}s",
         "Time:".white().bold(),
-        result.build_time.as_secs_f64()
+        build_time.as_secs_f64()
     );
     println!();
 
     // Size breakdown
-    println!("  {}", "Bundle Size:".white().bold());
-    println!(
-        "    {}  {} (gzip: ~{})",
-        "WASM:".dimmed(),
-        ByteSize(result.wasm_size).to_string().green(),
-        ByteSize(result.wasm_size_gzip).to_string().cyan(),
-    );
+    println!("  {}", "Artifacts:".white().bold());
+    let width = artifacts.iter().map(|a| a.name.len()).max().unwrap_or(0);
+    for artifact in artifacts {
+        let gzip = artifact
+            .gzip
+            .map(|size| format!("gzip {}", ByteSize(size)))
+            .unwrap_or_default();
+        println!(
+            "    {:<width$}  {:>10}  {}",
+            artifact.name.dimmed(),
+            ByteSize(artifact.size).to_string().green(),
+            gzip.cyan(),
+            width = width,
+        );
+    }
+
+    let total: u64 = artifacts.iter().map(|a| a.size).sum();
+    let wasm_gzip: u64 = artifacts
+        .iter()
+        .filter(|a| extension(&a.name) == "wasm")
+        .filter_map(|a| a.gzip)
+        .sum();
     println!(
-        "    {}  {}",
-        "Total:".dimmed(),
-        ByteSize(result.total_size).to_string().green(),
+        "    {:<width$}  {:>10}",
+        "Total".white().bold(),
+        ByteSize(total).to_string().green(),
+        width = width,
     );
     println!();
 
     // Size comparison to popular frameworks
-    if result.wasm_size < 100_000 {
+    if wasm_gzip > 0 && wasm_gzip < 100_000 {
         println!(
             "  {}  Smaller than most JavaScript frameworks!",
             "[perf]".green().bold()
         );
         println!();
     }
-
-    Ok(())
 }
 
-/// Print bundle analysis
-fn print_bundle_analysis(out_dir: &str) -> Result<()> {
-    println!("  {}", "Bundle Analysis:".white().bold());
-    println!();
-
-    let mut files: Vec<(String, u64)> = Vec::new();
-
-    for entry in walkdir::WalkDir::new(out_dir) {
-        let entry = entry?;
-        if entry.file_type().is_file() {
-            let size = entry.metadata()?.len();
-            let name = entry.path().strip_prefix(out_dir)?.display().to_string();
-            files.push((name, size));
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uses_liveview() {
+        let uses = |manifest: &str| uses_liveview(&manifest.parse().unwrap());
+
+        assert!(uses("[dependencies]\nphiljs-axum = { version = \"0.1\", features = [\"liveview\"] }"));
+        assert!(uses("[dependencies]\nweb = { package = \"philjs-axum\", features = [\"liveview\"] }"));
+        assert!(uses(
+            "[dependencies]\nphiljs-axum = { version = \"0.1\", optional = true }\n\
+             [features]\nserver = [\"philjs-axum?/liveview\"]"
+        ));
+        assert!(uses("[target.'cfg(not(target_arch = \"wasm32\"))'.dependencies]\nphiljs-axum = { version = \"0.1\", features = [\"liveview\"] }"));
+
+        // Mentions of "liveview" that don't enable the feature
+        assert!(!uses("[package]\nname = \"liveview-demo\"\n[dependencies]\nphiljs = \"0.1\""));
+        assert!(!uses("[dependencies]\nother = { version = \"1\", features = [\"liveview\"] }"));
+        assert!(!uses("[features]\nliveview = [\"other/liveview\"]\n[dependencies]\nother = \"1\""));
     }
 
-    // Sort by size descending
-    files.sort_by(|a, b| b.1.cmp(&a.1));
-
-    // Print top files
-    for (name, size) in files.iter().take(10) {
-        let bar_len = (((*size as f64) / (files[0].1 as f64)) * 30.0) as usize;
-        let bar = "=".repeat(bar_len);
+    #[test]
+    fn test_hashed_name() {
+        assert_eq!(hashed_name("pkg/app.js", "1a2b3c4d"), "pkg/app.1a2b3c4d.js");
+        assert_eq!(hashed_name("styles.css", "1a2b3c4d"), "styles.1a2b3c4d.css");
+        assert_eq!(hashed_name("pkg/app_bg.wasm", "1a2b3c4d"), "pkg/app_bg.1a2b3c4d.wasm");
+    }
 
-        println!(
-            "    {} {:>10}  {}",
-            bar.cyan(),
-            ByteSize(*size).to_string(),
-            name.dimmed()
+    #[test]
+    fn test_replace_reference() {
+        assert_eq!(
+            replace_reference("import('./app.js'); load('myapp.js', 'app.js.map')", "app.js", "app.1a.js"),
+            "import('./app.1a.js'); load('myapp.js', 'app.js.map')"
         );
     }
 
-    println!();
+    #[test]
+    fn test_rewrite_references() {
+        let renames = vec![
+            ("pkg/app_bg.wasm".to_string(), "pkg/app_bg.1a2b3c4d.wasm".to_string()),
+            ("pkg/app.js".to_string(), "pkg/app.5e6f7a8b.js".to_string()),
+        ];
+        assert_eq!(
+            rewrite_references("new URL('app_bg.wasm', import.meta.url)", "pkg/app.js", &renames),
+            "new URL('app_bg.1a2b3c4d.wasm', import.meta.url)"
+        );
+        assert_eq!(
+            rewrite_references(r#"<script src="/pkg/app.js"></script>"#, "index.html", &renames),
+            r#"<script src="/pkg/app.5e6f7a8b.js"></script>"#
+        );
+    }
 
-    Ok(())
+    #[test]
+    fn test_is_fingerprintable() {
+        assert!(is_fingerprintable("pkg/app.js"));
+        assert!(is_fingerprintable("styles.css"));
+        assert!(!is_fingerprintable("index.html"));
+        assert!(!is_fingerprintable("philjs-live.js"));
+        assert!(!is_fingerprintable(".well-known/assetlinks.json"));
+    }
 }
@@ -1,28 +1,32 @@
 //! Clean build artifacts
 //!
-//! Removes build outputs and caches.
+//! Removes build outputs and caches, including the `out_dir` of every
+//! profile in `philjs.toml`.
 
 use anyhow::Result;
 use colored::Colorize;
 use std::fs;
 use std::path::Path;
 
+use crate::config::Config;
+
 /// Run clean command
 pub fn run(all: bool) -> Result<()> {
     println!("\n{}  Cleaning build artifacts...\n", "[clean]".cyan().bold());
 
-    let dirs_to_clean = if all {
+    let mut dirs_to_clean = if all {
         vec![
             "target",
-            "dist",
             "pkg",
             "node_modules",
             ".philjs",
             ".wasm-pack-cache",
         ]
     } else {
-        vec!["target", "dist", "pkg", ".philjs"]
+        vec!["target", "pkg", ".philjs"]
     };
+    let out_dirs = out_dirs()?;
+    dirs_to_clean.extend(out_dirs.iter().map(String::as_str));
 
     let mut total_freed = 0u64;
 
@@ -70,6 +74,20 @@ pub fn run(all: bool) -> Result<()> {
     Ok(())
 }
 
+/// Build output directory of the config and of each of its profiles
+fn out_dirs() -> Result<Vec<String>> {
+    let root = Path::new(".");
+    let config = Config::load_profile(root, "")?;
+    let mut dirs = vec![config.build.out_dir.clone()];
+    for profile in config.profile.keys() {
+        let out_dir = Config::load_profile(root, profile)?.build.out_dir;
+        if !dirs.contains(&out_dir) {
+            dirs.push(out_dir);
+        }
+    }
+    Ok(dirs)
+}
+
 /// Calculate directory size
 fn dir_size(path: &Path) -> Result<u64> {
     let mut total = 0u64;
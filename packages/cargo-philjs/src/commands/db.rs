@@ -0,0 +1,489 @@
+//! Database commands
+//!
+//! `cargo philjs db migrate|rollback|status|reset` run the project's
+//! migration package (by convention a workspace member named `migration`),
+//! so every project migrates the same way regardless of how its migrations
+//! are defined. Its `main` hands the command line to the ORM:
+//!
+//! - philjs-seaorm: `Migrator::new().discover()?.run_cli(&db).await`
+//! - philjs-sqlx: `MigrationRunner::new(&pool, "migrations").run_cli().await`
+//!
+//! `db seed` runs the project's `seed` binary and `db new` writes a
+//! timestamped pair of `.up.sql`/`.down.sql` files both ORMs understand.
+//!
+//! The database URL comes from `--database-url` or `DATABASE_URL`, unless
+//! an environment is selected with `--env` or `PHILJS_ENV`:
+//!
+//! ```toml
+//! # philjs.toml
+//! [database]
+//! url = "postgres://localhost/app_dev"
+//!
+//! [database.environments]
+//! test = "postgres://localhost/app_test"
+//! ```
+//!
+//! An environment missing from the config falls back to
+//! `DATABASE_URL_<ENV>`. The environment also selects the config profile,
+//! so `[profile.test.database]` applies with `--env test`.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::Config;
+use crate::utils::{project_root, to_snake_case};
+use crate::{DbArgs, MigrateAction};
+
+/// ORM integration the project migrates with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    SeaOrm,
+    Sqlx,
+}
+
+impl Backend {
+    /// How the migration package's `main` should look
+    fn run_cli_hint(self) -> &'static str {
+        match self {
+            Backend::SeaOrm => "`philjs_seaorm::Migrator::new().discover()?.run_cli(&db).await`",
+            Backend::Sqlx => {
+                "`philjs_sqlx::migrate::MigrationRunner::new(&pool, \"migrations\").run_cli().await`"
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Backend::SeaOrm => write!(f, "philjs-seaorm"),
+            Backend::Sqlx => write!(f, "philjs-sqlx"),
+        }
+    }
+}
+
+/// How to invoke the migration package
+enum MigrationPackage {
+    /// A workspace member, run with `-p`
+    Member(String),
+    /// A standalone crate, run with `--manifest-path`
+    Manifest(PathBuf),
+}
+
+/// The project and the database a command runs against
+struct Project {
+    root: PathBuf,
+    metadata: Option<cargo_metadata::Metadata>,
+    config: Config,
+    env: Option<String>,
+    database_url: Option<String>,
+}
+
+impl Project {
+    fn load(args: &DbArgs) -> Result<Self> {
+        let root = project_root().context("Not inside a Cargo project")?;
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .current_dir(&root)
+            .no_deps()
+            .exec()
+            .ok();
+        let env = args
+            .env
+            .clone()
+            .or_else(|| std::env::var("PHILJS_ENV").ok())
+            .filter(|env| !env.is_empty());
+        let config = Config::load(&root, env.as_deref().unwrap_or("dev"))?;
+        let database_url = resolve_database_url(
+            &config,
+            env.as_deref(),
+            args.database_url.as_deref(),
+            |name| std::env::var(name).ok(),
+        )?;
+        Ok(Project {
+            root,
+            metadata,
+            config,
+            env,
+            database_url,
+        })
+    }
+
+    fn backend(&self) -> Option<Backend> {
+        let metadata = self.metadata.as_ref()?;
+        let depends_on = |name: &str| {
+            metadata
+                .workspace_packages()
+                .iter()
+                .any(|p| p.dependencies.iter().any(|d| d.name == name))
+        };
+        if depends_on("philjs-seaorm") {
+            Some(Backend::SeaOrm)
+        } else if depends_on("philjs-sqlx") {
+            Some(Backend::Sqlx)
+        } else {
+            None
+        }
+    }
+
+    fn has_bin(&self, name: &str) -> bool {
+        self.metadata.as_ref().is_some_and(|metadata| {
+            metadata
+                .workspace_packages()
+                .iter()
+                .flat_map(|p| &p.targets)
+                .any(|t| t.name == name && t.kind.iter().any(|k| k == "bin"))
+        })
+    }
+
+    /// `cargo run` in the project root with the database selected
+    fn cargo_run(&self) -> Command {
+        let mut cmd = Command::new("cargo");
+        cmd.current_dir(&self.root).args(["run", "--quiet"]);
+        if let Some(url) = &self.database_url {
+            cmd.env("DATABASE_URL", url);
+        }
+        if let Some(env) = &self.env {
+            cmd.env("PHILJS_ENV", env);
+        }
+        cmd
+    }
+
+    fn describe(&self) -> String {
+        let backend = self
+            .backend()
+            .map(|b| b.to_string())
+            .unwrap_or_else(|| "unknown ORM".to_string());
+        match &self.env {
+            Some(env) => format!("{}, {} environment", backend, env),
+            None => backend,
+        }
+    }
+}
+
+/// Pick the database URL for `env`, or the default one
+fn resolve_database_url(
+    config: &Config,
+    env: Option<&str>,
+    explicit: Option<&str>,
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<Option<String>> {
+    let Some(env) = env else {
+        return Ok(explicit
+            .map(str::to_string)
+            .or_else(|| var("DATABASE_URL"))
+            .or_else(|| config.database.url.clone()));
+    };
+
+    if let Some(url) = config.database.environments.get(env) {
+        return Ok(Some(url.clone()));
+    }
+    let name = format!("DATABASE_URL_{}", env.to_uppercase().replace('-', "_"));
+    match var(&name) {
+        Some(url) => Ok(Some(url)),
+        None => bail!(
+            "No database configured for environment '{}'. Add it under \
+             [database.environments] in philjs.toml or set {}",
+            env,
+            name
+        ),
+    }
+}
+
+/// Run database migrations
+pub fn migrate(action: MigrateAction, steps: Option<usize>, args: &DbArgs) -> Result<()> {
+    let project = Project::load(args)?;
+    let target = find_migration_package(&project, &args.package)?;
+
+    if !args.json {
+        println!(
+            "\n{}  Running migrations ({}, {})...\n",
+            "[db]".cyan().bold(),
+            action,
+            project.describe()
+        );
+    }
+
+    let mut cmd = project.cargo_run();
+    match &target {
+        MigrationPackage::Member(name) => cmd.args(["-p", name]),
+        MigrationPackage::Manifest(path) => cmd.arg("--manifest-path").arg(path),
+    };
+    cmd.arg("--").args(migrate_args(action, steps, args.json));
+
+    let status = cmd.status().context("Failed to run the migration package")?;
+    if !status.success() {
+        bail!("Migration {} failed", action);
+    }
+
+    if !args.json && action != MigrateAction::Status {
+        println!("\n{}  Migrations finished\n", "[done]".green().bold());
+    }
+    Ok(())
+}
+
+/// Roll back the last `steps` migrations
+pub fn rollback(steps: usize, args: &DbArgs) -> Result<()> {
+    migrate(MigrateAction::Down, Some(steps), args)
+}
+
+/// Show applied and pending migrations
+pub fn status(args: &DbArgs) -> Result<()> {
+    migrate(MigrateAction::Status, None, args)
+}
+
+/// Roll back every migration, apply them all again, then seed
+pub fn reset(args: &DbArgs, seed_bin: &str, no_seed: bool) -> Result<()> {
+    migrate(MigrateAction::Fresh, None, args)?;
+    if no_seed || args.json {
+        return Ok(());
+    }
+    let project = Project::load(args)?;
+    if project.has_bin(seed_bin) {
+        seed(args, seed_bin)
+    } else {
+        Ok(())
+    }
+}
+
+/// Run the project's seed binary
+pub fn seed(args: &DbArgs, bin: &str) -> Result<()> {
+    let project = Project::load(args)?;
+    if !project.has_bin(bin) {
+        bail!(
+            "No `{}` binary found. Add src/bin/{}.rs that connects with DATABASE_URL \
+             and inserts the seed data, or pass --bin",
+            bin,
+            bin
+        );
+    }
+
+    println!(
+        "\n{}  Seeding database ({})...\n",
+        "[db]".cyan().bold(),
+        project.describe()
+    );
+    let status = project
+        .cargo_run()
+        .args(["--bin", bin])
+        .status()
+        .context("Failed to run the seed binary")?;
+    if !status.success() {
+        bail!("Seeding failed");
+    }
+    println!("\n{}  Database seeded\n", "[done]".green().bold());
+    Ok(())
+}
+
+/// Write a timestamped migration from the template matching its name
+pub fn new_migration(name: &str, dir: Option<&str>) -> Result<()> {
+    let root = project_root().context("Not inside a Cargo project")?;
+    let config = Config::load(&root, "dev")?;
+    let dir = root.join(dir.unwrap_or(&config.database.migrations_dir));
+    fs::create_dir_all(&dir)?;
+
+    let name = to_snake_case(name);
+    let version = timestamp();
+    if let Some(existing) = find_existing(&dir, &name)? {
+        bail!("A migration named '{}' already exists: {}", name, existing.display());
+    }
+
+    let (up, down) = migration_template(&name);
+    let up_path = dir.join(format!("{}_{}.up.sql", version, name));
+    let down_path = dir.join(format!("{}_{}.down.sql", version, name));
+    fs::write(&up_path, up)?;
+    fs::write(&down_path, down)?;
+
+    println!("\n{}  Created migration:\n", "[db]".cyan().bold());
+    for path in [&up_path, &down_path] {
+        let shown = path.strip_prefix(&root).unwrap_or(path);
+        println!("  {}", shown.display().to_string().green());
+    }
+    println!("\n  Apply it with {}\n", "cargo philjs db migrate".cyan());
+    Ok(())
+}
+
+/// Arguments understood by both ORMs' `run_cli`
+fn migrate_args(action: MigrateAction, steps: Option<usize>, json: bool) -> Vec<String> {
+    let mut args = vec![action.to_string()];
+    if let Some(steps) = steps {
+        args.push("--steps".to_string());
+        args.push(steps.to_string());
+    }
+    if json {
+        args.push("--json".to_string());
+    }
+    args
+}
+
+/// Find the migration package in the workspace or next to it
+fn find_migration_package(project: &Project, package: &str) -> Result<MigrationPackage> {
+    let is_member = project
+        .metadata
+        .as_ref()
+        .is_some_and(|metadata| metadata.workspace_packages().iter().any(|p| p.name == package));
+    if is_member {
+        return Ok(MigrationPackage::Member(package.to_string()));
+    }
+
+    let manifest = project.root.join(package).join("Cargo.toml");
+    if manifest.is_file() {
+        return Ok(MigrationPackage::Manifest(manifest));
+    }
+
+    let hint = match project.backend() {
+        Some(backend) => backend.run_cli_hint().to_string(),
+        None => format!(
+            "{} or {}",
+            Backend::SeaOrm.run_cli_hint(),
+            Backend::Sqlx.run_cli_hint()
+        ),
+    };
+    bail!(
+        "No migration package '{}' found. Create one with a main that calls {}, \
+         or pass --package",
+        package,
+        hint
+    )
+}
+
+/// UTC `YYYYMMDDHHMMSS`, the version prefix both ORMs sort by
+fn timestamp() -> String {
+    humantime::format_rfc3339_seconds(std::time::SystemTime::now())
+        .to_string()
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect()
+}
+
+fn find_existing(dir: &Path, name: &str) -> Result<Option<PathBuf>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let stem = file_name.split('.').next().unwrap_or("");
+        if stem.split_once('_').is_some_and(|(_, rest)| rest == name) {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+const CREATE_TABLE_UP: &str = "-- Create {{table}}
+CREATE TABLE {{table}} (
+    id BIGINT PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+";
+
+const CREATE_TABLE_DOWN: &str = "DROP TABLE {{table}};
+";
+
+const ADD_COLUMN_UP: &str = "-- Add {{column}} to {{table}}
+ALTER TABLE {{table}} ADD COLUMN {{column}} TEXT;
+";
+
+const ADD_COLUMN_DOWN: &str = "ALTER TABLE {{table}} DROP COLUMN {{column}};
+";
+
+const BLANK_UP: &str = "-- {{name}}
+";
+
+const BLANK_DOWN: &str = "-- Revert {{name}}
+";
+
+/// Up and down SQL for a migration name: `create_<table>`,
+/// `add_<column>_to_<table>`, or anything else for blank files
+fn migration_template(name: &str) -> (String, String) {
+    let fill = |template: &str, table: &str, column: &str| {
+        template
+            .replace("{{table}}", table)
+            .replace("{{column}}", column)
+            .replace("{{name}}", name)
+    };
+
+    if let Some(table) = name.strip_prefix("create_").filter(|t| !t.is_empty()) {
+        return (fill(CREATE_TABLE_UP, table, ""), fill(CREATE_TABLE_DOWN, table, ""));
+    }
+    if let Some((column, table)) = name
+        .strip_prefix("add_")
+        .and_then(|rest| rest.split_once("_to_"))
+        .filter(|(column, table)| !column.is_empty() && !table.is_empty())
+    {
+        return (fill(ADD_COLUMN_UP, table, column), fill(ADD_COLUMN_DOWN, table, column));
+    }
+    (fill(BLANK_UP, "", ""), fill(BLANK_DOWN, "", ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_args() {
+        assert_eq!(migrate_args(MigrateAction::Up, None, false), ["up"]);
+        assert_eq!(
+            migrate_args(MigrateAction::Down, Some(2), true),
+            ["down", "--steps", "2", "--json"]
+        );
+    }
+
+    #[test]
+    fn test_migration_template() {
+        let (up, down) = migration_template("create_users");
+        assert!(up.contains("CREATE TABLE users ("));
+        assert_eq!(down, "DROP TABLE users;\n");
+
+        let (up, down) = migration_template("add_email_to_users");
+        assert!(up.contains("ALTER TABLE users ADD COLUMN email TEXT;"));
+        assert!(down.contains("DROP COLUMN email"));
+
+        let (up, _) = migration_template("backfill_slugs");
+        assert_eq!(up, "-- backfill_slugs\n");
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let version = timestamp();
+        assert_eq!(version.len(), 14);
+        assert!(version.starts_with("20"));
+    }
+
+    #[test]
+    fn test_resolve_database_url() {
+        let mut config = Config::default();
+        config.database.url = Some("sqlite://dev.db".to_string());
+        config
+            .database
+            .environments
+            .insert("test".to_string(), "sqlite::memory:".to_string());
+        let no_vars = |_: &str| None;
+
+        assert_eq!(
+            resolve_database_url(&config, None, None, no_vars).unwrap().as_deref(),
+            Some("sqlite://dev.db")
+        );
+        assert_eq!(
+            resolve_database_url(&config, None, Some("postgres://x"), no_vars)
+                .unwrap()
+                .as_deref(),
+            Some("postgres://x")
+        );
+        assert_eq!(
+            resolve_database_url(&config, Some("test"), Some("postgres://x"), no_vars)
+                .unwrap()
+                .as_deref(),
+            Some("sqlite::memory:")
+        );
+        let staging = |name: &str| (name == "DATABASE_URL_STAGING").then(|| "postgres://staging".to_string());
+        assert_eq!(
+            resolve_database_url(&config, Some("staging"), None, staging)
+                .unwrap()
+                .as_deref(),
+            Some("postgres://staging")
+        );
+        assert!(resolve_database_url(&config, Some("prod"), None, no_vars).is_err());
+    }
+}
@@ -1,11 +1,18 @@
 //! Deployment commands
 //!
-//! Deploy PhilJS applications to various platforms.
+//! Deploy PhilJS applications to various platforms. Docker images are
+//! built from a generated Dockerfile, see [`super::docker`].
+//!
+//! The platform, previews and the build come from `[deploy]` and `[build]`
+//! in `philjs.toml` with the `prod` profile applied.
 
+use super::docker::{self, DockerOptions};
+use crate::config::Config;
 use crate::DeployPlatform;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::path::Path;
 use std::process::Command;
 
 /// Run deployment
@@ -13,11 +20,19 @@ pub async fn run(
     platform: Option<DeployPlatform>,
     preview: bool,
     no_build: bool,
+    docker: DockerOptions,
 ) -> Result<()> {
-    let platform = match platform {
+    let config = Config::load(Path::new("."), "prod")?;
+    let platform = match platform.or(config.deploy_platform()) {
         Some(p) => p,
         None => select_platform()?,
     };
+    let preview = preview || config.deploy.preview;
+    let mut options = crate::commands::build::BuildOptions::from_config(&config, true);
+    if platform == DeployPlatform::Cloudflare {
+        options.target = crate::BuildTarget::Cloudflare;
+    }
+    let out_dir = options.out_dir.clone();
 
     println!(
         "\n{}  Deploying to {:?}...\n",
@@ -25,22 +40,22 @@ pub async fn run(
         platform
     );
 
-    // Build first if needed
-    if !no_build {
+    // Build first if needed; Docker images build inside the container
+    if !no_build && platform != DeployPlatform::Docker {
         println!("{}  Building for production...", "[build]".cyan().bold());
-        crate::commands::build::run(true, "dist", crate::BuildTarget::Browser, false, false, false, false).await?;
+        crate::commands::build::run(options).await?;
         println!();
     }
 
     // Deploy based on platform
     match platform {
-        DeployPlatform::Vercel => deploy_vercel(preview)?,
-        DeployPlatform::Netlify => deploy_netlify(preview)?,
-        DeployPlatform::Cloudflare => deploy_cloudflare(preview)?,
+        DeployPlatform::Vercel => deploy_vercel(&out_dir, preview)?,
+        DeployPlatform::Netlify => deploy_netlify(&out_dir, preview)?,
+        DeployPlatform::Cloudflare => deploy_cloudflare(&out_dir, preview)?,
         DeployPlatform::Railway => deploy_railway()?,
         DeployPlatform::Fly => deploy_fly(preview)?,
         DeployPlatform::Aws => deploy_aws()?,
-        DeployPlatform::Docker => build_docker()?,
+        DeployPlatform::Docker => docker::run(docker)?,
     }
 
     Ok(())
@@ -51,7 +66,7 @@ fn select_platform() -> Result<DeployPlatform> {
     let options = vec![
         "Vercel (Recommended for SPAs)",
         "Netlify",
-        "Cloudflare Pages",
+        "Cloudflare Workers",
         "Railway (Full-stack)",
         "Fly.io (Full-stack)",
         "AWS Lambda",
@@ -77,10 +92,10 @@ fn select_platform() -> Result<DeployPlatform> {
 }
 
 /// Deploy to Vercel
-fn deploy_vercel(preview: bool) -> Result<()> {
+fn deploy_vercel(out_dir: &str, preview: bool) -> Result<()> {
     check_cli("vercel", "npm i -g vercel")?;
 
-    let mut args = vec!["deploy", "dist"];
+    let mut args = vec!["deploy", out_dir];
     if !preview {
         args.push("--prod");
     }
@@ -103,10 +118,11 @@ fn deploy_vercel(preview: bool) -> Result<()> {
 }
 
 /// Deploy to Netlify
-fn deploy_netlify(preview: bool) -> Result<()> {
+fn deploy_netlify(out_dir: &str, preview: bool) -> Result<()> {
     check_cli("netlify", "npm i -g netlify-cli")?;
 
-    let mut args = vec!["deploy", "--dir=dist"];
+    let dir = format!("--dir={}", out_dir);
+    let mut args = vec!["deploy", dir.as_str()];
     if !preview {
         args.push("--prod");
     }
@@ -128,23 +144,33 @@ fn deploy_netlify(preview: bool) -> Result<()> {
     Ok(())
 }
 
-/// Deploy to Cloudflare Pages
-fn deploy_cloudflare(preview: bool) -> Result<()> {
+/// Deploy to Cloudflare Workers
+///
+/// Uploads the `--target=cloudflare` build with the project's
+/// `wrangler.toml`; previews are uploaded as a new version without
+/// being deployed.
+fn deploy_cloudflare(out_dir: &str, preview: bool) -> Result<()> {
     check_cli("wrangler", "npm i -g wrangler")?;
 
+    if !Path::new("wrangler.toml").exists() {
+        crate::commands::worker::write_wrangler_toml(Path::new("."), Path::new(out_dir))?;
+    }
+
+    let args: &[&str] = if preview { &["versions", "upload"] } else { &["deploy"] };
     println!(
-        "{}  Running wrangler pages deploy...\n",
-        "[cloudflare]".cyan().bold()
+        "{}  Running wrangler {}...\n",
+        "[cloudflare]".cyan().bold(),
+        args.join(" ")
     );
 
     let status = Command::new("wrangler")
-        .args(["pages", "deploy", "dist"])
+        .args(args)
         .status()
         .context("Failed to run wrangler deploy")?;
 
     if status.success() {
         println!(
-            "\n{}  Deployed to Cloudflare Pages successfully!",
+            "\n{}  Deployed to Cloudflare Workers successfully!",
             "[done]".green().bold()
         );
     }
@@ -250,81 +276,6 @@ Resources:
     Ok(())
 }
 
-/// Build Docker image
-fn build_docker() -> Result<()> {
-    // Create Dockerfile if it doesn't exist
-    if !std::path::Path::new("Dockerfile").exists() {
-        let dockerfile = r#"# Build stage
-FROM rust:1.75 as builder
-
-# Install wasm-pack
-RUN cargo install wasm-pack
-
-WORKDIR /app
-COPY . .
-
-# Build WASM
-RUN wasm-pack build --target web --release
-
-# Production stage
-FROM nginx:alpine
-
-# Copy built files
-COPY --from=builder /app/dist /usr/share/nginx/html
-COPY --from=builder /app/pkg /usr/share/nginx/html/pkg
-
-# Copy nginx config
-COPY nginx.conf /etc/nginx/conf.d/default.conf
-
-EXPOSE 80
-CMD ["nginx", "-g", "daemon off;"]
-"#;
-        std::fs::write("Dockerfile", dockerfile)?;
-
-        let nginx_conf = r#"server {
-    listen 80;
-    server_name localhost;
-    root /usr/share/nginx/html;
-    index index.html;
-
-    location / {
-        try_files $uri $uri/ /index.html;
-    }
-
-    location ~* \.(wasm)$ {
-        add_header Content-Type application/wasm;
-    }
-
-    gzip on;
-    gzip_types text/plain text/css application/json application/javascript application/wasm;
-}
-"#;
-        std::fs::write("nginx.conf", nginx_conf)?;
-
-        println!(
-            "{}  Created Dockerfile and nginx.conf",
-            "[done]".green().bold()
-        );
-    }
-
-    println!("{}  Building Docker image...\n", "[docker]".cyan().bold());
-
-    let status = Command::new("docker")
-        .args(["build", "-t", "philjs-app", "."])
-        .status()
-        .context("Failed to build Docker image")?;
-
-    if status.success() {
-        println!(
-            "\n{}  Docker image built successfully!",
-            "[done]".green().bold()
-        );
-        println!("\n  Run with: {}", "docker run -p 8080:80 philjs-app".cyan());
-    }
-
-    Ok(())
-}
-
 /// Check if CLI tool is installed
 fn check_cli(cmd: &str, install_hint: &str) -> Result<()> {
     if which::which(cmd).is_err() {
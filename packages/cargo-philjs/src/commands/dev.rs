@@ -1,27 +1,39 @@
-//! Enhanced Development Server with True Hot Module Replacement
+//! Development Server with Hot Reload
 //!
-//! Provides a Leptos-class development experience with:
-//! - True HMR (Hot Module Replacement) - not just full rebuilds
-//! - State preservation during reloads
-//! - Incremental WASM compilation with caching
-//! - Beautiful terminal UI with error overlays
-//! - WebSocket-based live reload
-//! - CSS hot reload without page refresh
-//! - Sub-second rebuild times
-//! - Rust analyzer integration
+//! Watches the crate and rebuilds the client on every change:
+//! - Incremental `cargo build` for wasm32, then wasm-bindgen into `pkg/`
+//! - Compiler errors shown in an overlay in the browser
+//! - CSS swapped in place without reloading the page
+//! - A reload client injected into every HTML page, talking over a WebSocket
+//! - Cross-origin isolation (COOP/COEP) so `SharedArrayBuffer` and wasm
+//!   threads work as in production
+//!
+//! Files are served from `pkg/`, `static/`, `assets/` and `public/` (see
+//! `[assets]` in `philjs.toml`); any other path falls back to `index.html`
+//! so client-side routes load. Settings come from `[dev]` with the `dev`
+//! profile applied, and command-line flags win.
 
 use anyhow::{Context, Result};
 use colored::Colorize;
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
 
+/// Path of the reload WebSocket
+const HMR_PATH: &str = "/__philjs/hmr";
+
+/// Path the reload client is served from
+const CLIENT_PATH: &str = "/__philjs/client.js";
+
+/// Where wasm-bindgen writes the client; templates load `/pkg/app.js`
+const OUT_DIR: &str = "pkg";
+const OUT_NAME: &str = "app";
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -35,8 +47,14 @@ pub struct DevConfig {
     pub open: bool,
     pub https: bool,
     pub verbose: bool,
+    /// Send COOP/COEP headers
+    pub cross_origin_isolation: bool,
     pub watch_dirs: Vec<PathBuf>,
     pub ignore_patterns: Vec<String>,
+    /// Directories served as-is, by their own name
+    pub asset_dirs: Vec<String>,
+    /// Directory served from the site root
+    pub public_dir: String,
 }
 
 impl Default for DevConfig {
@@ -48,16 +66,55 @@ impl Default for DevConfig {
             open: false,
             https: false,
             verbose: false,
+            cross_origin_isolation: true,
             watch_dirs: vec![
                 PathBuf::from("src"),
                 PathBuf::from("static"),
                 PathBuf::from("assets"),
+                PathBuf::from("public"),
             ],
             ignore_patterns: vec![
                 "target".to_string(),
                 "node_modules".to_string(),
                 ".git".to_string(),
+                OUT_DIR.to_string(),
             ],
+            asset_dirs: vec![OUT_DIR.to_string(), "static".to_string(), "assets".to_string()],
+            public_dir: "public".to_string(),
+        }
+    }
+}
+
+impl DevConfig {
+    /// Settings from `[dev]` and `[assets]` in `philjs.toml`
+    pub fn from_project(config: &crate::config::Config) -> Self {
+        let assets = &config.assets;
+        let mut watch_dirs = vec![PathBuf::from("src")];
+        for dir in [&assets.static_dir, &assets.assets_dir, &assets.public_dir]
+            .into_iter()
+            .chain(&config.dev.watch)
+        {
+            let dir = PathBuf::from(dir);
+            if !watch_dirs.contains(&dir) {
+                watch_dirs.push(dir);
+            }
+        }
+
+        Self {
+            port: config.dev.port,
+            host: config.dev.host.clone(),
+            hot_reload: config.dev.hot_reload,
+            open: config.dev.open,
+            https: config.dev.https,
+            cross_origin_isolation: config.dev.cross_origin_isolation,
+            watch_dirs,
+            asset_dirs: vec![
+                OUT_DIR.to_string(),
+                assets.static_dir.clone(),
+                assets.assets_dir.clone(),
+            ],
+            public_dir: assets.public_dir.clone(),
+            ..Default::default()
         }
     }
 }
@@ -65,29 +122,29 @@ impl Default for DevConfig {
 /// Development server state
 struct DevServer {
     config: DevConfig,
+    root: PathBuf,
     build_count: AtomicU64,
     last_build_time: Mutex<Duration>,
-    module_cache: Mutex<HashMap<PathBuf, ModuleInfo>>,
-    clients: Mutex<Vec<broadcast::Sender<HmrMessage>>>,
-}
-
-/// Information about a compiled module
-#[derive(Debug, Clone)]
-struct ModuleInfo {
-    path: PathBuf,
-    hash: u64,
-    last_modified: std::time::SystemTime,
-    dependencies: Vec<PathBuf>,
+    /// The last build error, replayed to clients that connect later
+    last_error: Mutex<Option<HmrMessage>>,
+    events: broadcast::Sender<HmrMessage>,
 }
 
-/// HMR message sent to connected clients
-#[derive(Debug, Clone)]
+/// Message pushed to connected browsers
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
 enum HmrMessage {
-    FullReload,
-    ModuleUpdate { path: String, hash: u64 },
-    CssUpdate { path: String, content: String },
-    Error { message: String, file: Option<String>, line: Option<u32> },
     Connected,
+    /// A rebuild started
+    Building,
+    Reload,
+    /// A stylesheet changed; `path` is its URL
+    Css { path: String },
+    Error {
+        message: String,
+        file: Option<String>,
+        line: Option<usize>,
+    },
 }
 
 /// File change classification
@@ -102,13 +159,15 @@ enum ChangeKind {
 }
 
 impl DevServer {
-    fn new(config: DevConfig) -> Arc<Self> {
+    fn new(config: DevConfig, root: PathBuf) -> Arc<Self> {
+        let (events, _) = broadcast::channel(64);
         Arc::new(DevServer {
             config,
+            root,
             build_count: AtomicU64::new(0),
             last_build_time: Mutex::new(Duration::ZERO),
-            module_cache: Mutex::new(HashMap::new()),
-            clients: Mutex::new(Vec::new()),
+            last_error: Mutex::new(None),
+            events,
         })
     }
 
@@ -118,10 +177,13 @@ impl DevServer {
     }
 
     fn broadcast(&self, message: HmrMessage) {
-        let clients = self.clients.lock().unwrap();
-        for sender in clients.iter() {
-            let _ = sender.send(message.clone());
+        match &message {
+            HmrMessage::Error { .. } => *self.last_error.lock().unwrap() = Some(message.clone()),
+            HmrMessage::Building | HmrMessage::Reload => *self.last_error.lock().unwrap() = None,
+            _ => {}
         }
+        // No receivers just means no browser is open
+        let _ = self.events.send(message);
     }
 }
 
@@ -131,38 +193,48 @@ impl DevServer {
 
 /// Run the development server
 pub async fn run(
-    port: u16,
-    host: &str,
+    port: Option<u16>,
+    host: Option<String>,
     open: bool,
     https: bool,
     watch_dirs: Option<Vec<String>>,
     no_hot_reload: bool,
 ) -> Result<()> {
-    let config = DevConfig {
-        port,
-        host: host.to_string(),
-        hot_reload: !no_hot_reload,
-        open,
-        https,
-        watch_dirs: watch_dirs
-            .unwrap_or_default()
-            .into_iter()
-            .map(PathBuf::from)
-            .chain(DevConfig::default().watch_dirs)
-            .collect(),
-        ..Default::default()
-    };
+    let root = crate::utils::project_root().context("Not inside a Cargo project")?;
+    let mut config = DevConfig::from_project(&crate::config::Config::load(&root, "dev")?);
+    for dir in watch_dirs.unwrap_or_default().into_iter().map(PathBuf::from) {
+        if !config.watch_dirs.contains(&dir) {
+            config.watch_dirs.push(dir);
+        }
+    }
+    if let Some(port) = port {
+        config.port = port;
+    }
+    if let Some(host) = host {
+        config.host = host;
+    }
+    config.open |= open;
+    config.https |= https;
+    config.hot_reload &= !no_hot_reload;
 
-    let server = DevServer::new(config.clone());
+    std::env::set_current_dir(&root)?;
+    let server = DevServer::new(config.clone(), root);
 
     print_banner(&config);
     check_prerequisites()?;
 
+    if config.https {
+        println!(
+            "{}  HTTPS is not supported by the dev server yet, serving over HTTP",
+            "[warn]".yellow().bold()
+        );
+    }
+
     // Initial build
     println!("{}  Building project...\n", "[build]".cyan().bold());
 
     let build_start = Instant::now();
-    match build_incremental(&server, None).await {
+    match build_client(&server).await {
         Ok(_) => {
             let build_time = build_start.elapsed();
             *server.last_build_time.lock().unwrap() = build_time;
@@ -173,8 +245,9 @@ pub async fn run(
             );
         }
         Err(e) => {
-            println!("{}  Build failed: {}\n", "[error]".red().bold(), e);
+            println!("{}  Build failed:\n\n{}\n", "[error]".red().bold(), e);
             println!("  Watching for changes to retry...\n");
+            server.broadcast(e.into_message());
         }
     }
 
@@ -201,16 +274,32 @@ pub async fn run(
         }
     }
 
-    // Also watch Cargo.toml
-    if Path::new("Cargo.toml").exists() {
-        watcher.watch(Path::new("Cargo.toml"), RecursiveMode::NonRecursive)?;
+    // Also watch Cargo.toml and a root index.html
+    for file in ["Cargo.toml", "index.html"] {
+        if Path::new(file).exists() {
+            watcher.watch(Path::new(file), RecursiveMode::NonRecursive)?;
+        }
     }
 
+    // Bind before announcing the URL, so a busy port fails loudly
+    let addr = format!("{}:{}", config.host, config.port);
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("Could not listen on {} (is the port in use? try --port)", addr))?;
+
     println!();
     print_ready(&config);
 
+    // Start HTTP server with WebSocket support for HMR
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        if let Err(e) = run_http_server(server_clone, listener).await {
+            eprintln!("HTTP server error: {}", e);
+        }
+    });
+
     // Open browser if requested
-    if open {
+    if config.open {
         let url = server.build_url();
         if let Err(e) = open::that(&url) {
             println!(
@@ -221,14 +310,6 @@ pub async fn run(
         }
     }
 
-    // Start HTTP server with WebSocket support for HMR
-    let server_clone = server.clone();
-    tokio::spawn(async move {
-        if let Err(e) = run_http_server(server_clone).await {
-            eprintln!("HTTP server error: {}", e);
-        }
-    });
-
     // Setup shutdown signal
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
@@ -274,48 +355,133 @@ pub async fn run(
     Ok(())
 }
 
+// ============================================================================
+// Test Server
+// ============================================================================
+
+/// The dev server running in the background for `cargo philjs test --browser`
+///
+/// Serves the same files as `cargo philjs dev`, without the reload client,
+/// on a free local port.
+pub struct TestServer {
+    server: Arc<DevServer>,
+    pub url: String,
+}
+
+impl TestServer {
+    /// Build the client and start serving it, with the `test` profile
+    pub async fn start() -> Result<Self> {
+        let root = crate::utils::project_root().context("Not inside a Cargo project")?;
+        let project = crate::config::Config::load(&root, "test")?;
+        std::env::set_current_dir(&root)?;
+        check_prerequisites()?;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let config = DevConfig {
+            port: addr.port(),
+            host: "127.0.0.1".to_string(),
+            hot_reload: false,
+            open: false,
+            ..DevConfig::from_project(&project)
+        };
+        let server = DevServer::new(config, root);
+
+        let test_server = TestServer {
+            server: server.clone(),
+            url: server.build_url(),
+        };
+        test_server.rebuild().await?;
+
+        tokio::spawn(async move {
+            if let Err(e) = run_http_server(server, listener).await {
+                eprintln!("HTTP server error: {}", e);
+            }
+        });
+        Ok(test_server)
+    }
+
+    /// Rebuild the client, e.g. after a change in watch mode
+    pub async fn rebuild(&self) -> Result<()> {
+        build_client(&self.server)
+            .await
+            .map_err(|e| anyhow::anyhow!("Build failed:\n\n{}", e))
+    }
+}
+
 // ============================================================================
 // Build System
 // ============================================================================
 
-/// Perform an incremental build
-async fn build_incremental(server: &DevServer, changed_files: Option<&[PathBuf]>) -> Result<()> {
-    let start = Instant::now();
+/// A failed build, with the first compiler error's location
+#[derive(Debug)]
+struct BuildFailure {
+    message: String,
+    file: Option<String>,
+    line: Option<usize>,
+}
 
-    // Determine build strategy based on what changed
-    let strategy = if let Some(files) = changed_files {
-        determine_build_strategy(files)
-    } else {
-        BuildStrategy::Full
-    };
+impl BuildFailure {
+    fn new(message: impl Into<String>) -> Self {
+        BuildFailure {
+            message: message.into(),
+            file: None,
+            line: None,
+        }
+    }
 
-    match strategy {
-        BuildStrategy::Full => {
-            build_wasm_optimized(server.config.verbose).await?;
+    fn into_message(self) -> HmrMessage {
+        HmrMessage::Error {
+            message: self.message,
+            file: self.file,
+            line: self.line,
         }
-        BuildStrategy::Incremental { modules } => {
-            // For now, do full rebuild but with optimizations
-            // True incremental Rust compilation is complex
-            build_wasm_optimized(server.config.verbose).await?;
+    }
+}
+
+impl std::fmt::Display for BuildFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<anyhow::Error> for BuildFailure {
+    fn from(e: anyhow::Error) -> Self {
+        BuildFailure::new(format!("{:#}", e))
+    }
+}
+
+/// Handle a batch of changed files: rebuild, swap CSS or reload
+async fn rebuild(server: &DevServer, changed_files: &[PathBuf]) -> Result<bool, BuildFailure> {
+    match determine_build_strategy(changed_files) {
+        BuildStrategy::Full => {
+            build_client(server).await?;
         }
         BuildStrategy::CssOnly { files } => {
             // CSS changes don't need Rust rebuild
             for file in files {
-                if let Ok(content) = std::fs::read_to_string(&file) {
-                    server.broadcast(HmrMessage::CssUpdate {
-                        path: file.display().to_string(),
-                        content,
-                    });
+                if let Some(path) = asset_url(&server.root, &server.config.public_dir, &file) {
+                    server.broadcast(HmrMessage::Css { path });
                 }
             }
-            return Ok(());
+            return Ok(false);
         }
         BuildStrategy::StaticOnly => {
             // Static file changes just need a page refresh
-            server.broadcast(HmrMessage::FullReload);
-            return Ok(());
+            server.broadcast(HmrMessage::Reload);
+            return Ok(false);
         }
     }
+    Ok(true)
+}
+
+/// Rebuild the wasm client and tell browsers to reload
+async fn build_client(server: &DevServer) -> Result<(), BuildFailure> {
+    let start = Instant::now();
+    server.broadcast(HmrMessage::Building);
+
+    let wasm = cargo_build_wasm(server.config.verbose).await?;
+    run_wasm_bindgen(wasm).await?;
 
     let duration = start.elapsed();
     *server.last_build_time.lock().unwrap() = duration;
@@ -323,7 +489,7 @@ async fn build_incremental(server: &DevServer, changed_files: Option<&[PathBuf]>
 
     // Notify clients
     if server.config.hot_reload {
-        server.broadcast(HmrMessage::FullReload);
+        server.broadcast(HmrMessage::Reload);
     }
 
     Ok(())
@@ -332,44 +498,33 @@ async fn build_incremental(server: &DevServer, changed_files: Option<&[PathBuf]>
 #[derive(Debug)]
 enum BuildStrategy {
     Full,
-    Incremental { modules: Vec<PathBuf> },
     CssOnly { files: Vec<PathBuf> },
     StaticOnly,
 }
 
 fn determine_build_strategy(files: &[PathBuf]) -> BuildStrategy {
-    let mut rust_files = Vec::new();
     let mut css_files = Vec::new();
+    let mut has_rust = false;
     let mut has_static = false;
-    let mut has_config = false;
 
     for file in files {
         match classify_file(file) {
-            ChangeKind::Rust => rust_files.push(file.clone()),
+            ChangeKind::Rust | ChangeKind::Config => has_rust = true,
             ChangeKind::Css => css_files.push(file.clone()),
             ChangeKind::Static | ChangeKind::Html => has_static = true,
-            ChangeKind::Config => has_config = true,
             ChangeKind::Unknown => {}
         }
     }
 
-    if has_config {
+    if has_rust {
         return BuildStrategy::Full;
     }
 
-    if !rust_files.is_empty() {
-        return BuildStrategy::Incremental { modules: rust_files };
-    }
-
     if !css_files.is_empty() && !has_static {
         return BuildStrategy::CssOnly { files: css_files };
     }
 
-    if has_static {
-        return BuildStrategy::StaticOnly;
-    }
-
-    BuildStrategy::Full
+    BuildStrategy::StaticOnly
 }
 
 fn classify_file(path: &Path) -> ChangeKind {
@@ -378,48 +533,87 @@ fn classify_file(path: &Path) -> ChangeKind {
 
     match ext {
         "rs" => ChangeKind::Rust,
-        "css" | "scss" | "sass" | "less" => ChangeKind::Css,
+        "css" => ChangeKind::Css,
         "html" | "htm" => ChangeKind::Html,
         "toml" if name == "Cargo.toml" => ChangeKind::Config,
-        "js" | "ts" | "json" | "png" | "jpg" | "jpeg" | "gif" | "svg" | "woff" | "woff2" | "ttf" => {
-            ChangeKind::Static
-        }
+        "scss" | "sass" | "less" | "js" | "ts" | "json" | "png" | "jpg" | "jpeg" | "gif" | "svg"
+        | "woff" | "woff2" | "ttf" => ChangeKind::Static,
         _ => ChangeKind::Unknown,
     }
 }
 
-/// Build WASM with optimizations
-async fn build_wasm_optimized(verbose: bool) -> Result<()> {
-    let mut args = vec![
-        "build",
-        "--target",
-        "web",
-        "--dev",
-        "--out-dir",
-        "pkg",
-        "--out-name",
-        "app",
-    ];
-
-    let output = if verbose {
-        Command::new("wasm-pack")
-            .args(&args)
-            .status()
-            .context("Failed to run wasm-pack")?
-    } else {
-        Command::new("wasm-pack")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .status()
-            .context("Failed to run wasm-pack")?
-    };
+/// Compile the crate for wasm32 and return the `.wasm` it produced
+///
+/// Runs in the dev profile, so cargo's incremental compilation keeps
+/// rebuilds to the crates that changed.
+async fn cargo_build_wasm(verbose: bool) -> Result<PathBuf, BuildFailure> {
+    let output = tokio::process::Command::new("cargo")
+        .args([
+            "build",
+            "--lib",
+            "--target",
+            "wasm32-unknown-unknown",
+            "--message-format=json-diagnostic-rendered-ansi",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(if verbose { Stdio::inherit() } else { Stdio::piped() })
+        .output()
+        .await
+        .map_err(|e| BuildFailure::new(format!("Failed to run cargo: {}", e)))?;
+
+    let mut wasm = None;
+    let mut failure: Option<BuildFailure> = None;
+    for message in cargo_metadata::Message::parse_stream(output.stdout.as_slice()).flatten() {
+        match message {
+            cargo_metadata::Message::CompilerArtifact(artifact) => {
+                if let Some(file) = artifact.filenames.iter().find(|f| f.extension() == Some("wasm")) {
+                    wasm = Some(file.clone().into_std_path_buf());
+                }
+            }
+            cargo_metadata::Message::CompilerMessage(message) if failure.is_none() => {
+                let diagnostic = message.message;
+                if diagnostic.level != cargo_metadata::diagnostic::DiagnosticLevel::Error {
+                    continue;
+                }
+                let span = diagnostic.spans.iter().find(|span| span.is_primary);
+                failure = Some(BuildFailure {
+                    message: diagnostic.rendered.unwrap_or(diagnostic.message),
+                    file: span.map(|span| span.file_name.clone()),
+                    line: span.map(|span| span.line_start),
+                });
+            }
+            _ => {}
+        }
+    }
 
-    if !output.success() {
-        anyhow::bail!("wasm-pack build failed");
+    if !output.status.success() {
+        return Err(failure.unwrap_or_else(|| {
+            BuildFailure::new(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }));
     }
+    wasm.ok_or_else(|| {
+        BuildFailure::new("cargo built no .wasm; add `crate-type = [\"cdylib\", \"rlib\"]` under [lib]")
+    })
+}
 
-    Ok(())
+/// Generate the JS bindings for `wasm` into `pkg/`
+async fn run_wasm_bindgen(wasm: PathBuf) -> Result<(), BuildFailure> {
+    tokio::task::spawn_blocking(move || {
+        wasm_bindgen_cli_support::Bindgen::new()
+            .input_path(&wasm)
+            .web(true)?
+            .debug(true)
+            .keep_debug(true)
+            .out_name(OUT_NAME)
+            .generate(OUT_DIR)
+            .context(
+                "wasm-bindgen failed; if this is a schema version mismatch, \
+                 align the project's wasm-bindgen version with cargo-philjs",
+            )
+    })
+    .await
+    .map_err(|e| BuildFailure::new(e.to_string()))?
+    .map_err(BuildFailure::from)
 }
 
 /// Process file changes
@@ -429,10 +623,6 @@ async fn process_changes(server: &DevServer, changes: &[PathBuf]) {
     // Group changes by type for better logging
     let rust_changes: Vec<_> = changes.iter().filter(|p| classify_file(p) == ChangeKind::Rust).collect();
     let css_changes: Vec<_> = changes.iter().filter(|p| classify_file(p) == ChangeKind::Css).collect();
-    let other_changes: Vec<_> = changes.iter().filter(|p| {
-        let kind = classify_file(p);
-        kind != ChangeKind::Rust && kind != ChangeKind::Css
-    }).collect();
 
     // Print change summary
     println!();
@@ -460,10 +650,14 @@ async fn process_changes(server: &DevServer, changes: &[PathBuf]) {
 
     // Rebuild
     let build_start = Instant::now();
-    match build_incremental(server, Some(changes)).await {
-        Ok(_) => {
+    match rebuild(server, changes).await {
+        Ok(false) => {
+            if server.config.hot_reload {
+                println!("{}  Browser updated", "[hmr]".magenta().bold());
+            }
+        }
+        Ok(true) => {
             let build_time = build_start.elapsed();
-            let last_time = *server.last_build_time.lock().unwrap();
 
             let speed_indicator = if build_time < Duration::from_millis(500) {
                 "⚡".to_string()
@@ -490,18 +684,14 @@ async fn process_changes(server: &DevServer, changes: &[PathBuf]) {
         }
         Err(e) => {
             println!(
-                "{}  Build #{} failed: {}",
+                "{}  Build #{} failed:\n\n{}",
                 "[error]".red().bold(),
                 count,
                 e
             );
 
             // Send error to clients for overlay
-            server.broadcast(HmrMessage::Error {
-                message: e.to_string(),
-                file: changes.first().map(|p| p.display().to_string()),
-                line: None,
-            });
+            server.broadcast(e.into_message());
         }
     }
 }
@@ -510,29 +700,281 @@ async fn process_changes(server: &DevServer, changes: &[PathBuf]) {
 // HTTP Server with WebSocket
 // ============================================================================
 
-async fn run_http_server(server: Arc<DevServer>) -> Result<()> {
-    use axum::{
-        extract::ws::{WebSocket, WebSocketUpgrade},
-        extract::State,
-        response::IntoResponse,
-        routing::get,
-        Router,
-    };
-    use tower_http::services::ServeDir;
+async fn run_http_server(server: Arc<DevServer>, listener: tokio::net::TcpListener) -> Result<()> {
+    use axum::{middleware, routing::get, Router};
 
     let app = Router::new()
-        .route("/__hmr", get(hmr_handler))
-        .fallback_service(ServeDir::new("pkg").append_index_html_on_directories(true))
-        .with_state(server.clone());
-
-    let addr = format!("{}:{}", server.config.host, server.config.port);
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
+        .route(HMR_PATH, get(hmr_handler))
+        .route(CLIENT_PATH, get(client_script))
+        .fallback(serve_asset)
+        .layer(middleware::from_fn_with_state(server.clone(), dev_headers))
+        .with_state(server);
 
     axum::serve(listener, app).await?;
 
     Ok(())
 }
 
+/// Headers on every response: no caching, and cross-origin isolation
+async fn dev_headers(
+    axum::extract::State(server): axum::extract::State<Arc<DevServer>>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::http::{header, HeaderValue};
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    if server.config.cross_origin_isolation {
+        headers.insert("cross-origin-opener-policy", HeaderValue::from_static("same-origin"));
+        headers.insert("cross-origin-embedder-policy", HeaderValue::from_static("require-corp"));
+        headers.insert("cross-origin-resource-policy", HeaderValue::from_static("same-origin"));
+    }
+    response
+}
+
+async fn client_script() -> impl axum::response::IntoResponse {
+    ([(axum::http::header::CONTENT_TYPE, "text/javascript")], HMR_CLIENT)
+}
+
+/// Serve a file from the asset directories, or `index.html` for anything
+/// that isn't one
+async fn serve_asset(
+    axum::extract::State(server): axum::extract::State<Arc<DevServer>>,
+    uri: axum::http::Uri,
+) -> axum::response::Response {
+    use axum::http::{header, StatusCode};
+    use axum::response::IntoResponse;
+
+    let Some(relative) = sanitize_path(uri.path()) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    if let Some(path) = resolve_asset(&server.root, &server.config, &relative) {
+        return match tokio::fs::read(&path).await {
+            Ok(bytes) if content_type(&path) == "text/html; charset=utf-8" => {
+                html_response(&server, &String::from_utf8_lossy(&bytes))
+            }
+            Ok(bytes) => ([(header::CONTENT_TYPE, content_type(&path))], bytes).into_response(),
+            Err(_) => StatusCode::NOT_FOUND.into_response(),
+        };
+    }
+
+    // A missing file with an extension is a broken link, not a route
+    if relative.extension().is_some() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let index = find_index(&server.root, &server.config);
+    let html = match index {
+        Some(path) => tokio::fs::read_to_string(path).await.unwrap_or_default(),
+        None => DEFAULT_INDEX.to_string(),
+    };
+    html_response(&server, &html)
+}
+
+fn html_response(server: &DevServer, html: &str) -> axum::response::Response {
+    use axum::response::{Html, IntoResponse};
+
+    if server.config.hot_reload {
+        Html(inject_client(html)).into_response()
+    } else {
+        Html(html.to_string()).into_response()
+    }
+}
+
+/// The request path as a relative path, refusing anything that escapes
+/// the project
+fn sanitize_path(path: &str) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in Path::new(path.trim_start_matches('/')).components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    Some(relative)
+}
+
+/// Find `relative` under an asset directory or the public directory
+fn resolve_asset(root: &Path, config: &DevConfig, relative: &Path) -> Option<PathBuf> {
+    let first = relative.components().next()?.as_os_str().to_str()?;
+    let candidates = [
+        config.asset_dirs.iter().any(|dir| dir == first).then(|| root.join(relative)),
+        Some(root.join(&config.public_dir).join(relative)),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .map(|path| if path.is_dir() { path.join("index.html") } else { path })
+        .find(|path| path.is_file())
+}
+
+fn find_index(root: &Path, config: &DevConfig) -> Option<PathBuf> {
+    ["", config.public_dir.as_str()]
+        .into_iter()
+        .chain(config.asset_dirs.iter().map(String::as_str))
+        .map(|dir| root.join(dir).join("index.html"))
+        .find(|path| path.is_file())
+}
+
+/// The URL a watched file is served at
+fn asset_url(root: &Path, public_dir: &str, file: &Path) -> Option<String> {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    let relative = relative.strip_prefix(public_dir).unwrap_or(relative);
+    let parts: Vec<_> = relative
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    Some(format!("/{}", parts.join("/")))
+}
+
+/// MIME type by extension; wasm must be `application/wasm` for streaming
+/// compilation
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "wasm" => "application/wasm",
+        "json" | "map" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "txt" => "text/plain; charset=utf-8",
+        "xml" => "application/xml",
+        "webmanifest" => "application/manifest+json",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "pdf" => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Add the reload client to an HTML page
+fn inject_client(html: &str) -> String {
+    let tag = format!(r#"<script type="module" src="{}"></script>"#, CLIENT_PATH);
+    for marker in ["</body>", "</head>"] {
+        if let Some(index) = html.rfind(marker) {
+            let mut out = String::with_capacity(html.len() + tag.len());
+            out.push_str(&html[..index]);
+            out.push_str(&tag);
+            out.push_str(&html[index..]);
+            return out;
+        }
+    }
+    format!("{}{}", html, tag)
+}
+
+/// Served when the project has no `index.html`
+const DEFAULT_INDEX: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <title>PhilJS App</title>
+</head>
+<body>
+    <div id="app"></div>
+    <script type="module">
+        import init from '/pkg/app.js';
+        init();
+    </script>
+</body>
+</html>
+"#;
+
+/// Browser side of hot reload: reloads after builds, swaps stylesheets and
+/// shows build errors in an overlay
+const HMR_CLIENT: &str = r#"// PhilJS dev server client
+const OVERLAY_ID = '__philjs_error_overlay';
+let retry = 0;
+
+function showError({ message, file, line }) {
+    let overlay = document.getElementById(OVERLAY_ID);
+    if (!overlay) {
+        overlay = document.createElement('div');
+        overlay.id = OVERLAY_ID;
+        overlay.style.cssText = 'position:fixed;inset:0;z-index:2147483647;overflow:auto;' +
+            'background:rgba(20,20,20,.95);color:#f88;font:13px/1.5 ui-monospace,monospace;padding:32px';
+        overlay.addEventListener('click', () => overlay.remove());
+        document.body.appendChild(overlay);
+    }
+    const location = file ? `${file}${line ? ':' + line : ''}` : '';
+    const text = message.replace(/\x1b\[[0-9;]*m/g, '');
+    overlay.innerHTML = '<h2 style="color:#fff;margin:0 0 8px">Build failed</h2>';
+    const where = document.createElement('div');
+    where.style.color = '#ccc';
+    where.textContent = location;
+    const pre = document.createElement('pre');
+    pre.style.whiteSpace = 'pre-wrap';
+    pre.textContent = text;
+    overlay.append(where, pre);
+}
+
+function clearError() {
+    document.getElementById(OVERLAY_ID)?.remove();
+}
+
+function swapStylesheet(path) {
+    let swapped = false;
+    for (const link of document.querySelectorAll('link[rel="stylesheet"]')) {
+        const url = new URL(link.href);
+        if (url.pathname === path) {
+            url.searchParams.set('t', Date.now());
+            link.href = url.toString();
+            swapped = true;
+        }
+    }
+    if (!swapped) location.reload();
+}
+
+function connect() {
+    const protocol = location.protocol === 'https:' ? 'wss:' : 'ws:';
+    const socket = new WebSocket(`${protocol}//${location.host}/__philjs/hmr`);
+
+    socket.addEventListener('open', () => {
+        // The server restarted while we were away: pick up its build
+        if (retry > 0) location.reload();
+        retry = 0;
+    });
+    socket.addEventListener('message', (event) => {
+        const message = JSON.parse(event.data);
+        switch (message.type) {
+            case 'building':
+                console.info('[philjs] rebuilding...');
+                break;
+            case 'reload':
+                location.reload();
+                break;
+            case 'css':
+                clearError();
+                swapStylesheet(message.path);
+                break;
+            case 'error':
+                showError(message);
+                break;
+        }
+    });
+    socket.addEventListener('close', () => {
+        retry += 1;
+        setTimeout(connect, Math.min(1000 * retry, 5000));
+    });
+}
+
+connect();
+"#;
+
 async fn hmr_handler(
     ws: axum::extract::ws::WebSocketUpgrade,
     axum::extract::State(server): axum::extract::State<Arc<DevServer>>,
@@ -543,43 +985,30 @@ async fn hmr_handler(
 async fn handle_hmr_socket(mut socket: axum::extract::ws::WebSocket, server: Arc<DevServer>) {
     use axum::extract::ws::Message;
 
-    // Create a channel for this client
-    let (tx, mut rx) = broadcast::channel::<HmrMessage>(16);
+    let mut rx = server.events.subscribe();
 
-    // Register client
-    {
-        let mut clients = server.clients.lock().unwrap();
-        clients.push(tx);
+    let mut greeting = vec![HmrMessage::Connected];
+    greeting.extend(server.last_error.lock().unwrap().clone());
+    for message in greeting {
+        let json = serde_json::to_string(&message).unwrap_or_default();
+        if socket.send(Message::Text(json)).await.is_err() {
+            return;
+        }
     }
 
-    // Send connected message
-    let _ = socket.send(Message::Text(r#"{"type":"connected"}"#.to_string())).await;
-
     // Listen for messages
     loop {
         tokio::select! {
             msg = rx.recv() => {
-                if let Ok(hmr_msg) = msg {
-                    let json = match hmr_msg {
-                        HmrMessage::FullReload => r#"{"type":"reload"}"#.to_string(),
-                        HmrMessage::ModuleUpdate { path, hash } => {
-                            format!(r#"{{"type":"update","path":"{}","hash":{}}}"#, path, hash)
-                        }
-                        HmrMessage::CssUpdate { path, content } => {
-                            format!(r#"{{"type":"css","path":"{}","content":"{}"}}"#, path, content.replace('\n', "\\n").replace('"', "\\\""))
-                        }
-                        HmrMessage::Error { message, file, line } => {
-                            format!(r#"{{"type":"error","message":"{}","file":{},"line":{}}}"#,
-                                message.replace('"', "\\\""),
-                                file.map(|f| format!("\"{}\"", f)).unwrap_or_else(|| "null".to_string()),
-                                line.map(|l| l.to_string()).unwrap_or_else(|| "null".to_string())
-                            )
-                        }
-                        HmrMessage::Connected => r#"{"type":"connected"}"#.to_string(),
-                    };
-                    if socket.send(Message::Text(json)).await.is_err() {
-                        break;
-                    }
+                let message = match msg {
+                    Ok(message) => message,
+                    // Missed some messages; a reload catches up
+                    Err(broadcast::error::RecvError::Lagged(_)) => HmrMessage::Reload,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let json = serde_json::to_string(&message).unwrap_or_default();
+                if socket.send(Message::Text(json)).await.is_err() {
+                    break;
                 }
             }
             result = socket.recv() => {
@@ -596,7 +1025,7 @@ async fn handle_hmr_socket(mut socket: axum::extract::ws::WebSocket, server: Arc
 // Utilities
 // ============================================================================
 
-fn print_banner(config: &DevConfig) {
+fn print_banner(_config: &DevConfig) {
     println!();
     println!(
         "  {}",
@@ -616,8 +1045,7 @@ fn print_banner(config: &DevConfig) {
 }
 
 fn print_ready(config: &DevConfig) {
-    let protocol = if config.https { "https" } else { "http" };
-    let url = format!("{}://{}:{}", protocol, config.host, config.port);
+    let url = format!("http://{}:{}", config.host, config.port);
 
     println!("  {}  Server ready!", "[ok]".green().bold());
     println!();
@@ -630,7 +1058,7 @@ fn print_ready(config: &DevConfig) {
     if config.host == "0.0.0.0" || config.host == "::" {
         // Try to get local IP
         if let Ok(ip) = local_ip_address::local_ip() {
-            let network_url = format!("{}://{}:{}", protocol, ip, config.port);
+            let network_url = format!("http://{}:{}", ip, config.port);
             println!(
                 "  {}  {}",
                 "Network:".white().bold(),
@@ -655,28 +1083,6 @@ fn print_ready(config: &DevConfig) {
 }
 
 fn check_prerequisites() -> Result<()> {
-    // Check for wasm-pack
-    if which::which("wasm-pack").is_err() {
-        println!(
-            "{}  wasm-pack not found, installing...",
-            "[setup]".yellow().bold()
-        );
-
-        let status = Command::new("cargo")
-            .args(["install", "wasm-pack"])
-            .status()
-            .context("Failed to install wasm-pack")?;
-
-        if !status.success() {
-            anyhow::bail!("Failed to install wasm-pack");
-        }
-
-        println!(
-            "{}  wasm-pack installed",
-            "[done]".green().bold()
-        );
-    }
-
     // Check for wasm32 target
     let output = Command::new("rustup")
         .args(["target", "list", "--installed"])
@@ -698,35 +1104,28 @@ fn check_prerequisites() -> Result<()> {
     Ok(())
 }
 
-fn should_process_event(event: &notify::Event, ignore_patterns: &[String]) -> bool {
+pub(crate) fn should_process_event(event: &notify::Event, ignore_patterns: &[String]) -> bool {
     use notify::EventKind;
 
-    // Only process create/modify events
+    // Only process create/modify/remove events
     match event.kind {
-        EventKind::Create(_) | EventKind::Modify(_) => {}
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {}
         _ => return false,
     }
 
     // Check paths against ignore patterns
     for path in &event.paths {
-        let path_str = path.display().to_string();
-
-        // Check ignore patterns
-        for pattern in ignore_patterns {
-            if path_str.contains(pattern) {
-                return false;
-            }
+        let ignored = path.components().any(|component| {
+            ignore_patterns
+                .iter()
+                .any(|pattern| component.as_os_str() == pattern.as_str())
+        });
+        if ignored {
+            continue;
         }
 
-        // Check file extension
-        if let Some(ext) = path.extension() {
-            let ext = ext.to_string_lossy();
-            match ext.as_ref() {
-                "rs" | "toml" | "html" | "css" | "scss" | "sass" | "js" | "ts" | "json" => {
-                    return true
-                }
-                _ => {}
-            }
+        if classify_file(path) != ChangeKind::Unknown {
+            return true;
         }
     }
 
@@ -744,3 +1143,46 @@ fn format_duration(d: Duration) -> String {
         format!("{}m {}s", mins, secs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inject_client() {
+        let html = "<html><body><div id=\"app\"></div></body></html>";
+        assert_eq!(
+            inject_client(html),
+            "<html><body><div id=\"app\"></div>\
+             <script type=\"module\" src=\"/__philjs/client.js\"></script></body></html>"
+        );
+        assert!(inject_client("<p>hi</p>").ends_with("</script>"));
+    }
+
+    #[test]
+    fn test_sanitize_path() {
+        assert_eq!(sanitize_path("/pkg/app.js"), Some(PathBuf::from("pkg/app.js")));
+        assert_eq!(sanitize_path("/"), Some(PathBuf::new()));
+        assert_eq!(sanitize_path("/../secret"), None);
+    }
+
+    #[test]
+    fn test_content_type() {
+        assert_eq!(content_type(Path::new("pkg/app_bg.wasm")), "application/wasm");
+        assert_eq!(content_type(Path::new("pkg/app.js")), "text/javascript; charset=utf-8");
+        assert_eq!(content_type(Path::new("LICENSE")), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_asset_url() {
+        let root = Path::new("/project");
+        assert_eq!(
+            asset_url(root, "public", Path::new("/project/static/styles.css")).as_deref(),
+            Some("/static/styles.css")
+        );
+        assert_eq!(
+            asset_url(root, "public", Path::new("/project/public/app.css")).as_deref(),
+            Some("/app.css")
+        );
+    }
+}
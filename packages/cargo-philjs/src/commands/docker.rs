@@ -0,0 +1,457 @@
+//! Container images for `cargo philjs deploy --platform=docker`
+//!
+//! Generates a multi-stage Dockerfile for the project's template, builds
+//! the image, tags it from the package name and version and optionally
+//! pushes it:
+//!
+//! - `spa`/`minimal`: `cargo philjs build` in the build stage, static
+//!   files served by nginx
+//! - `ssr`/`fullstack`: `cargo philjs build --ssr`, the server binary
+//!   and client assets on a slim Debian image
+//! - `liveview`: the `server` feature binary and `static/`
+//!
+//! The template comes from `[project] template` in `philjs.toml`, or from
+//! the package's features. Everything else can be overridden, with the
+//! `prod` profile applied:
+//!
+//! ```toml
+//! [deploy.docker]
+//! registry = "ghcr.io/acme"
+//! port = 3000
+//! runtime_steps = ["RUN apt-get update && apt-get install -y libpq5"]
+//!
+//! [deploy.docker.env]
+//! RUST_LOG = "info"
+//! ```
+//!
+//! Generated files carry a marker comment and are regenerated on every
+//! deploy; remove the marker to take ownership of the Dockerfile.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::{Config, DockerConfig};
+use crate::utils::{command_exists, project_root};
+
+/// First line of every Dockerfile written by this module
+const GENERATED_MARKER: &str = "# Generated by cargo-philjs";
+
+const BUILDER_IMAGE: &str = "rust:1-bookworm";
+const SERVER_IMAGE: &str = "debian:bookworm-slim";
+const STATIC_IMAGE: &str = "nginx:alpine";
+
+/// Image options from the command line
+#[derive(Debug, Clone, Default)]
+pub struct DockerOptions {
+    pub push: bool,
+    /// Overrides `[deploy.docker] registry`
+    pub registry: Option<String>,
+    /// Extra tags
+    pub tags: Vec<String>,
+}
+
+/// How the image serves the app
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageKind {
+    /// Static files behind nginx
+    Static,
+    /// Server-rendered app with a hydrated client
+    Ssr,
+    /// LiveView server
+    LiveView,
+}
+
+impl ImageKind {
+    pub fn from_template(template: &str) -> Option<Self> {
+        match template.to_lowercase().as_str() {
+            "spa" | "minimal" | "static" => Some(ImageKind::Static),
+            "ssr" | "fullstack" => Some(ImageKind::Ssr),
+            "liveview" => Some(ImageKind::LiveView),
+            _ => None,
+        }
+    }
+
+    /// Guess from the package's features when no template is configured
+    fn detect(package: Option<&cargo_metadata::Package>) -> Self {
+        match package {
+            Some(p) if p.features.contains_key("server") => ImageKind::LiveView,
+            Some(p) if p.features.contains_key("ssr") => ImageKind::Ssr,
+            _ => ImageKind::Static,
+        }
+    }
+
+    fn default_port(self) -> u16 {
+        match self {
+            ImageKind::Static => 80,
+            ImageKind::Ssr | ImageKind::LiveView => 8080,
+        }
+    }
+}
+
+/// Everything the Dockerfile is rendered from
+#[derive(Debug, Clone)]
+pub struct DockerContext<'a> {
+    pub kind: ImageKind,
+    /// Server binary name
+    pub bin: String,
+    pub port: u16,
+    pub config: &'a DockerConfig,
+}
+
+/// Generate the Dockerfile, build the image and push it if asked
+pub fn run(options: DockerOptions) -> Result<()> {
+    let root = project_root().context("Not inside a Cargo project")?;
+    let config = Config::load(&root, "prod")?;
+    let docker = &config.deploy.docker;
+
+    let metadata = cargo_metadata::MetadataCommand::new()
+        .current_dir(&root)
+        .no_deps()
+        .exec()
+        .context("Failed to read Cargo metadata")?;
+    let package = metadata.root_package();
+
+    let kind = match config.project.template.as_deref() {
+        Some(template) => ImageKind::from_template(template)
+            .with_context(|| format!("Unknown template `{}` in philjs.toml", template))?,
+        None => ImageKind::detect(package),
+    };
+    let bin = package
+        .and_then(|p| p.targets.iter().find(|t| t.is_bin()).map(|t| t.name.clone()))
+        .or_else(|| package.map(|p| p.name.to_string()))
+        .unwrap_or_else(|| "app".to_string());
+    let ctx = DockerContext {
+        kind,
+        bin,
+        port: docker.port.unwrap_or(kind.default_port()),
+        config: docker,
+    };
+
+    let dockerfile = match &docker.dockerfile {
+        Some(path) => {
+            println!("{}  Using {}", "[docker]".cyan().bold(), path);
+            path.clone()
+        }
+        None => {
+            write_generated(&root.join("Dockerfile"), &render_dockerfile(&ctx))?;
+            "Dockerfile".to_string()
+        }
+    };
+    write_if_missing(&root.join(".dockerignore"), DOCKERIGNORE)?;
+    if kind == ImageKind::Static {
+        write_if_missing(&root.join("nginx.conf"), &nginx_conf(ctx.port))?;
+    }
+
+    let name = docker
+        .image
+        .clone()
+        .or_else(|| package.map(|p| p.name.to_string()))
+        .unwrap_or_else(|| "philjs-app".to_string());
+    let version = package.map(|p| p.version.to_string());
+    let registry = options.registry.as_deref().or(docker.registry.as_deref());
+    let tags: Vec<String> = docker.tags.iter().chain(&options.tags).cloned().collect();
+    let images = image_refs(registry, &name, version.as_deref(), &tags);
+
+    if !command_exists("docker") {
+        bail!("docker not found. Install with:\n  https://docs.docker.com/get-docker/");
+    }
+
+    println!("{}  Building {}...\n", "[docker]".cyan().bold(), images[0]);
+    let mut command = Command::new("docker");
+    command.current_dir(&root).arg("build").args(["-f", &dockerfile]);
+    for image in &images {
+        command.args(["-t", image]);
+    }
+    if let Some(platform) = &docker.platform {
+        command.args(["--platform", platform]);
+    }
+    for (key, value) in &docker.build_args {
+        command.arg("--build-arg").arg(format!("{}={}", key, value));
+    }
+    let status = command.arg(".").status().context("Failed to run docker build")?;
+    if !status.success() {
+        bail!("docker build failed");
+    }
+
+    println!("\n{}  Built image:", "[done]".green().bold());
+    for image in &images {
+        println!("    {}", image.cyan());
+    }
+
+    if options.push {
+        for image in &images {
+            println!("\n{}  Pushing {}...", "[docker]".cyan().bold(), image);
+            let status = Command::new("docker")
+                .args(["push", image])
+                .status()
+                .context("Failed to run docker push")?;
+            if !status.success() {
+                bail!("docker push {} failed", image);
+            }
+        }
+        println!("\n{}  Pushed {} tag(s)", "[done]".green().bold(), images.len());
+    } else {
+        println!(
+            "\n  Run with: {}",
+            format!("docker run -p {0}:{0} {1}", ctx.port, images[0]).cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// `registry/name:tag` for the version, `latest` and any extra tags
+pub fn image_refs(registry: Option<&str>, name: &str, version: Option<&str>, tags: &[String]) -> Vec<String> {
+    let repository = match registry {
+        Some(registry) => format!("{}/{}", registry.trim_end_matches('/'), name),
+        None => name.to_string(),
+    };
+    let mut refs: Vec<String> = Vec::new();
+    for tag in version.into_iter().chain(["latest"]).chain(tags.iter().map(String::as_str)) {
+        let image = format!("{}:{}", repository, tag);
+        if !refs.contains(&image) {
+            refs.push(image);
+        }
+    }
+    refs
+}
+
+/// Render the multi-stage Dockerfile for `ctx`
+pub fn render_dockerfile(ctx: &DockerContext) -> String {
+    let config = ctx.config;
+    let builder = config.builder_image.as_deref().unwrap_or(BUILDER_IMAGE);
+    let runtime = config.runtime_image.as_deref().unwrap_or(match ctx.kind {
+        ImageKind::Static => STATIC_IMAGE,
+        ImageKind::Ssr | ImageKind::LiveView => SERVER_IMAGE,
+    });
+    let bin = &ctx.bin;
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# syntax=docker/dockerfile:1");
+    let _ = writeln!(out, "{}; configure with [deploy.docker] in philjs.toml", GENERATED_MARKER);
+    let _ = writeln!(out, "# or delete this line to keep your own changes.");
+    let _ = writeln!(out);
+
+    // Build stage
+    let _ = writeln!(out, "FROM {} AS builder", builder);
+    if ctx.kind != ImageKind::LiveView {
+        let _ = writeln!(
+            out,
+            "RUN rustup target add wasm32-unknown-unknown \\\n    && cargo install --locked wasm-pack wasm-opt cargo-philjs@{}",
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+    let _ = writeln!(out, "WORKDIR /app");
+    for key in config.build_args.keys() {
+        let _ = writeln!(out, "ARG {}", key);
+    }
+    for step in &config.builder_steps {
+        let _ = writeln!(out, "{}", step);
+    }
+    let _ = writeln!(out, "COPY . .");
+    let _ = writeln!(
+        out,
+        "RUN --mount=type=cache,target=/usr/local/cargo/registry \\\n    --mount=type=cache,target=/app/target \\"
+    );
+    match ctx.kind {
+        ImageKind::Static => {
+            let _ = writeln!(out, "    cargo philjs build --release --out-dir dist");
+        }
+        ImageKind::Ssr => {
+            let _ = writeln!(out, "    cargo philjs build --release --ssr --out-dir dist");
+        }
+        ImageKind::LiveView => {
+            let _ = writeln!(
+                out,
+                "    cargo build --release --features server --bin {0} \\\n    && cp target/release/{0} /app/{0}",
+                bin
+            );
+        }
+    }
+    let _ = writeln!(out);
+
+    // Runtime stage
+    let _ = writeln!(out, "FROM {}", runtime);
+    match ctx.kind {
+        ImageKind::Static => {
+            let _ = writeln!(out, "COPY nginx.conf /etc/nginx/conf.d/default.conf");
+            let _ = writeln!(out, "COPY --from=builder /app/dist /usr/share/nginx/html");
+        }
+        ImageKind::Ssr | ImageKind::LiveView => {
+            let _ = writeln!(
+                out,
+                "RUN apt-get update \\\n    && apt-get install -y --no-install-recommends ca-certificates \\\n    && rm -rf /var/lib/apt/lists/* \\\n    && useradd --system --uid 10001 philjs"
+            );
+            let _ = writeln!(out, "WORKDIR /app");
+            if ctx.kind == ImageKind::Ssr {
+                let _ = writeln!(out, "COPY --from=builder /app/dist/client ./");
+                let _ = writeln!(out, "COPY --from=builder /app/dist/server/{0} /usr/local/bin/{0}", bin);
+            } else {
+                let _ = writeln!(out, "COPY --from=builder /app/static ./static");
+                let _ = writeln!(out, "COPY --from=builder /app/{0} /usr/local/bin/{0}", bin);
+            }
+            let _ = writeln!(out, "ENV HOST=0.0.0.0 PORT={}", ctx.port);
+        }
+    }
+    for (key, value) in &config.env {
+        let _ = writeln!(out, "ENV {}={:?}", key, value);
+    }
+    for step in &config.runtime_steps {
+        let _ = writeln!(out, "{}", step);
+    }
+    if ctx.kind != ImageKind::Static {
+        let _ = writeln!(out, "USER philjs");
+    }
+    let _ = writeln!(out, "EXPOSE {}", ctx.port);
+    match ctx.kind {
+        ImageKind::Static => {
+            let _ = writeln!(out, "CMD [\"nginx\", \"-g\", \"daemon off;\"]");
+        }
+        ImageKind::Ssr | ImageKind::LiveView => {
+            let _ = writeln!(out, "CMD [\"{}\"]", bin);
+        }
+    }
+    out
+}
+
+/// Write a generated file unless the user has taken it over
+fn write_generated(path: &Path, content: &str) -> Result<()> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    match fs::read_to_string(path) {
+        Ok(existing) if !existing.contains(GENERATED_MARKER) => {
+            println!("{}  Using existing {}", "[docker]".cyan().bold(), name);
+            Ok(())
+        }
+        Ok(existing) if existing == content => Ok(()),
+        existing => {
+            fs::write(path, content)?;
+            let verb = if existing.is_ok() { "Updated" } else { "Created" };
+            println!("{}  {} {}", "[done]".green().bold(), verb, name);
+            Ok(())
+        }
+    }
+}
+
+fn write_if_missing(path: &Path, content: &str) -> Result<()> {
+    if !path.exists() {
+        fs::write(path, content)?;
+        println!(
+            "{}  Created {}",
+            "[done]".green().bold(),
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+    }
+    Ok(())
+}
+
+const DOCKERIGNORE: &str = "target
+dist
+pkg
+.philjs
+node_modules
+.git
+.env
+.env.*
+";
+
+fn nginx_conf(port: u16) -> String {
+    format!(
+        r#"server {{
+    listen {port};
+    server_name localhost;
+    root /usr/share/nginx/html;
+    index index.html;
+
+    location / {{
+        try_files $uri $uri/ /index.html;
+        add_header Cache-Control "no-cache";
+    }}
+
+    # Content-hashed assets never change
+    location /pkg/ {{
+        add_header Cache-Control "public, max-age=31536000, immutable";
+    }}
+
+    location ~* \.wasm$ {{
+        types {{ application/wasm wasm; }}
+    }}
+
+    gzip on;
+    gzip_types text/plain text/css application/json application/javascript application/wasm;
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(kind: ImageKind, config: &DockerConfig) -> DockerContext<'_> {
+        DockerContext {
+            kind,
+            bin: "my-app".to_string(),
+            port: config.port.unwrap_or(kind.default_port()),
+            config,
+        }
+    }
+
+    #[test]
+    fn test_from_template() {
+        assert_eq!(ImageKind::from_template("spa"), Some(ImageKind::Static));
+        assert_eq!(ImageKind::from_template("Fullstack"), Some(ImageKind::Ssr));
+        assert_eq!(ImageKind::from_template("liveview"), Some(ImageKind::LiveView));
+        assert_eq!(ImageKind::from_template("desktop"), None);
+    }
+
+    #[test]
+    fn test_image_refs() {
+        assert_eq!(
+            image_refs(Some("ghcr.io/acme/"), "my-app", Some("0.2.0"), &["staging".to_string()]),
+            ["ghcr.io/acme/my-app:0.2.0", "ghcr.io/acme/my-app:latest", "ghcr.io/acme/my-app:staging"]
+        );
+        assert_eq!(image_refs(None, "my-app", None, &["latest".to_string()]), ["my-app:latest"]);
+    }
+
+    #[test]
+    fn test_static_dockerfile() {
+        let config = DockerConfig::default();
+        let dockerfile = render_dockerfile(&context(ImageKind::Static, &config));
+        assert!(dockerfile.contains(GENERATED_MARKER));
+        assert!(dockerfile.contains("cargo philjs build --release --out-dir dist"));
+        assert!(dockerfile.contains("FROM nginx:alpine"));
+        assert!(dockerfile.contains("EXPOSE 80\n"));
+    }
+
+    #[test]
+    fn test_ssr_dockerfile() {
+        let config = DockerConfig::default();
+        let dockerfile = render_dockerfile(&context(ImageKind::Ssr, &config));
+        assert!(dockerfile.contains("cargo philjs build --release --ssr"));
+        assert!(dockerfile.contains("COPY --from=builder /app/dist/server/my-app /usr/local/bin/my-app"));
+        assert!(dockerfile.contains("USER philjs"));
+        assert!(dockerfile.ends_with("CMD [\"my-app\"]\n"));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut config = DockerConfig {
+            runtime_image: Some("gcr.io/distroless/cc".to_string()),
+            port: Some(3000),
+            runtime_steps: vec!["RUN echo ready".to_string()],
+            ..Default::default()
+        };
+        config.build_args.insert("FEATURES".to_string(), "postgres".to_string());
+        config.env.insert("RUST_LOG".to_string(), "info".to_string());
+        let dockerfile = render_dockerfile(&context(ImageKind::LiveView, &config));
+        assert!(!dockerfile.contains("wasm-pack"));
+        assert!(dockerfile.contains("ARG FEATURES\n"));
+        assert!(dockerfile.contains("FROM gcr.io/distroless/cc\n"));
+        assert!(dockerfile.contains("ENV RUST_LOG=\"info\"\nRUN echo ready\n"));
+        assert!(dockerfile.contains("EXPOSE 3000\n"));
+    }
+}
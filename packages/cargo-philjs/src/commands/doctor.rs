@@ -0,0 +1,773 @@
+//! Environment diagnostics
+//!
+//! `cargo philjs doctor` checks everything a PhilJS project needs to build
+//! and run, and prints a fix for each problem:
+//!
+//! - that `philjs.toml` parses and its settings are valid
+//! - Rust toolchain version and the `wasm32-unknown-unknown` target
+//! - wasm-pack and wasm-opt (binaryen)
+//! - philjs crate versions and features across the dependency graph
+//! - whether the dev and server ports are free
+//! - what the project's template needs (features, database, deploy tools)
+//!
+//! Warnings don't fail the command; failures exit non-zero.
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::{Config, CONFIG_FILE, LEGACY_CONFIG_FILE};
+use crate::utils::{command_exists, project_root};
+
+/// Oldest rustc the PhilJS crates build with
+const MIN_RUST: (u64, u64) = (1, 75);
+
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Framework integrations that render on the server
+const SERVER_CRATES: &[&str] = &["philjs-axum", "philjs-actix", "philjs-poem", "philjs-rocket"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// Result of one check
+#[derive(Debug, Serialize)]
+pub struct Check {
+    pub section: &'static str,
+    pub name: String,
+    pub status: Status,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Report {
+    checks: Vec<Check>,
+}
+
+impl Report {
+    fn ok(&mut self, section: &'static str, name: impl Into<String>, detail: impl Into<String>) {
+        self.push(section, name, Status::Ok, detail, None::<String>);
+    }
+
+    fn warn(
+        &mut self,
+        section: &'static str,
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) {
+        self.push(section, name, Status::Warn, detail, Some(fix));
+    }
+
+    fn fail(
+        &mut self,
+        section: &'static str,
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) {
+        self.push(section, name, Status::Fail, detail, Some(fix));
+    }
+
+    fn push(
+        &mut self,
+        section: &'static str,
+        name: impl Into<String>,
+        status: Status,
+        detail: impl Into<String>,
+        fix: Option<impl Into<String>>,
+    ) {
+        self.checks.push(Check {
+            section,
+            name: name.into(),
+            status,
+            detail: detail.into(),
+            fix: fix.map(Into::into),
+        });
+    }
+
+    fn count(&self, status: Status) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+}
+
+/// What the checks know about the project
+struct Project {
+    root: PathBuf,
+    config: Config,
+    template: Option<String>,
+    metadata: Option<cargo_metadata::Metadata>,
+}
+
+/// Run doctor command
+pub fn run(json: bool) -> Result<()> {
+    if !json {
+        println!(
+            "\n{}  Checking your PhilJS environment\n",
+            "[doctor]".cyan().bold()
+        );
+    }
+
+    let mut report = Report::default();
+    check_toolchain(&mut report);
+    check_tools(&mut report);
+
+    match load_project(&mut report) {
+        Some(project) => {
+            check_dependencies(&mut report, &project);
+            check_ports(&mut report, &project);
+            check_template(&mut report, &project);
+        }
+        None => report.warn(
+            "Project",
+            "PhilJS project",
+            "no Cargo.toml found; only the toolchain was checked",
+            "Run inside a project, or create one with `cargo philjs new <name>`",
+        ),
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    let failed = report.count(Status::Fail);
+    if failed > 0 {
+        bail!("{} check(s) failed", failed);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Toolchain
+// ============================================================================
+
+fn check_toolchain(report: &mut Report) {
+    const SECTION: &str = "Toolchain";
+
+    match tool_version("rustc", &["--version"]) {
+        Some(output) => match parse_version(&output) {
+            Some(version) if (version.0, version.1) < MIN_RUST => report.fail(
+                SECTION,
+                "rustc",
+                format!(
+                    "{} is older than the required {}.{}",
+                    output, MIN_RUST.0, MIN_RUST.1
+                ),
+                "rustup update stable",
+            ),
+            _ => report.ok(SECTION, "rustc", output),
+        },
+        None => report.fail(
+            SECTION,
+            "rustc",
+            "not found",
+            "Install Rust from https://rustup.rs",
+        ),
+    }
+
+    match tool_version("cargo", &["--version"]) {
+        Some(output) => report.ok(SECTION, "cargo", output),
+        None => report.fail(
+            SECTION,
+            "cargo",
+            "not found",
+            "Install Rust from https://rustup.rs",
+        ),
+    }
+
+    if !command_exists("rustup") {
+        report.warn(
+            SECTION,
+            WASM_TARGET,
+            "rustup not found, can't check installed targets",
+            format!("Make sure the {} target is installed for your toolchain", WASM_TARGET),
+        );
+        return;
+    }
+    let installed = Command::new("rustup")
+        .args(["target", "list", "--installed"])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).lines().any(|l| l.trim() == WASM_TARGET))
+        .unwrap_or(false);
+    if installed {
+        report.ok(SECTION, WASM_TARGET, "installed");
+    } else {
+        report.fail(
+            SECTION,
+            WASM_TARGET,
+            "target not installed",
+            format!("rustup target add {}", WASM_TARGET),
+        );
+    }
+}
+
+// ============================================================================
+// WASM tools
+// ============================================================================
+
+fn check_tools(report: &mut Report) {
+    const SECTION: &str = "WASM tools";
+
+    match tool_version("wasm-pack", &["--version"]) {
+        Some(version) => report.ok(SECTION, "wasm-pack", version),
+        None => report.fail(SECTION, "wasm-pack", "not found", "cargo install wasm-pack"),
+    }
+
+    match tool_version("wasm-opt", &["--version"]) {
+        Some(version) => report.ok(SECTION, "wasm-opt", version),
+        None => report.warn(
+            SECTION,
+            "wasm-opt",
+            "not found; release builds skip size optimization",
+            "cargo install wasm-opt (or install binaryen from your package manager)",
+        ),
+    }
+}
+
+// ============================================================================
+// Config
+// ============================================================================
+
+/// Load the config with the `dev` profile, reporting whether it is valid
+fn check_config(report: &mut Report, root: &Path) -> Config {
+    const SECTION: &str = "Config";
+
+    let Some(path) = Config::path(root) else {
+        report.ok(SECTION, CONFIG_FILE, "not found, using defaults");
+        return Config::default();
+    };
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    match Config::load(root, "dev") {
+        Ok(config) if name == LEGACY_CONFIG_FILE => {
+            report.warn(
+                SECTION,
+                name,
+                "valid, but the file name is deprecated",
+                format!("Rename it to {}", CONFIG_FILE),
+            );
+            config
+        }
+        Ok(config) => {
+            report.ok(SECTION, name, format!("valid ({} profile)", config.active_profile));
+            config
+        }
+        Err(err) => {
+            let message = err.to_string();
+            let problems = message.split_once("\n\n").map_or(message.as_str(), |(_, p)| p);
+            report.fail(SECTION, name, "invalid", problems.trim());
+            Config::default()
+        }
+    }
+}
+
+// ============================================================================
+// Dependencies
+// ============================================================================
+
+fn load_project(report: &mut Report) -> Option<Project> {
+    let root = project_root()?;
+    let config = check_config(report, &root);
+
+    let metadata = match cargo_metadata::MetadataCommand::new()
+        .current_dir(&root)
+        .exec()
+    {
+        Ok(metadata) => Some(metadata),
+        Err(err) => {
+            report.fail(
+                "Dependencies",
+                "cargo metadata",
+                first_line(&err.to_string()),
+                "Fix Cargo.toml, then run `cargo fetch`",
+            );
+            None
+        }
+    };
+
+    let template = config.project.template.clone().or_else(|| {
+        let package = metadata.as_ref()?.root_package()?;
+        let template = if package.features.contains_key("server") {
+            "liveview"
+        } else if package.features.contains_key("ssr") {
+            "ssr"
+        } else {
+            "spa"
+        };
+        Some(template.to_string())
+    });
+
+    Some(Project {
+        root,
+        config,
+        template,
+        metadata,
+    })
+}
+
+/// A philjs crate as resolved in the dependency graph
+#[derive(Debug, Clone)]
+struct Resolved {
+    name: String,
+    version: semver::Version,
+    features: BTreeSet<String>,
+}
+
+fn resolved_crates(metadata: &cargo_metadata::Metadata) -> Vec<Resolved> {
+    let Some(resolve) = &metadata.resolve else {
+        return Vec::new();
+    };
+    let root = metadata.root_package().map(|p| &p.id);
+    let mut crates: Vec<Resolved> = resolve
+        .nodes
+        .iter()
+        .filter(|node| Some(&node.id) != root)
+        .filter_map(|node| {
+            let package = metadata.packages.iter().find(|p| p.id == node.id)?;
+            is_philjs_crate(&package.name).then(|| Resolved {
+                name: package.name.to_string(),
+                version: package.version.clone(),
+                features: node.features.iter().map(|f| f.to_string()).collect(),
+            })
+        })
+        .collect();
+    crates.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    crates
+}
+
+pub(crate) fn is_philjs_crate(name: &str) -> bool {
+    (name == "philjs" || name.starts_with("philjs-")) && name != "cargo-philjs"
+}
+
+fn check_dependencies(report: &mut Report, project: &Project) {
+    const SECTION: &str = "Dependencies";
+
+    let Some(metadata) = &project.metadata else {
+        return;
+    };
+    let crates = resolved_crates(metadata);
+    if crates.is_empty() {
+        report.fail(
+            SECTION,
+            "philjs",
+            "not a dependency of this project",
+            "cargo add philjs",
+        );
+        return;
+    }
+
+    for problem in feature_problems(&crates) {
+        match problem {
+            Problem::Duplicate { name, versions } => report.fail(
+                SECTION,
+                name.clone(),
+                format!("{} versions in the dependency graph: {}", versions.len(), versions.join(", ")),
+                format!("Depend on a single version of {0}, then `cargo update -p {0}`", name),
+            ),
+            Problem::VersionSkew { versions } => report.warn(
+                SECTION,
+                "philjs versions",
+                format!("crates from different releases: {}", versions.join(", ")),
+                "Use the same version for every philjs crate in Cargo.toml",
+            ),
+            Problem::MissingFeature { name, feature, needed_by } => report.fail(
+                SECTION,
+                format!("{}/{}", name, feature),
+                format!("{} enables `{}` but {} doesn't", needed_by, feature, name),
+                format!("{} = {{ version = \"...\", features = [\"{}\"] }}", name, feature),
+            ),
+        }
+    }
+
+    let worker = metadata
+        .root_package()
+        .and_then(|p| p.features.get("worker"));
+    if let Some(enables) = worker {
+        if !enables.iter().any(|f| f == "philjs/worker") {
+            report.fail(
+                SECTION,
+                "worker feature",
+                "the `worker` feature doesn't enable philjs/worker",
+                "[features]\nworker = [\"philjs/worker\"]",
+            );
+        }
+    }
+
+    if report.checks.iter().all(|c| c.section != SECTION) {
+        let summary: Vec<String> = crates.iter().map(|c| format!("{} {}", c.name, c.version)).collect();
+        report.ok(SECTION, "philjs crates", summary.join(", "));
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Problem {
+    /// The same crate resolved at more than one version
+    Duplicate { name: String, versions: Vec<String> },
+    /// philjs crates from different minor releases
+    VersionSkew { versions: Vec<String> },
+    /// A feature enabled on an integration but not on the crate it relies on
+    MissingFeature {
+        name: String,
+        feature: &'static str,
+        needed_by: String,
+    },
+}
+
+fn feature_problems(crates: &[Resolved]) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    let mut by_name: BTreeMap<&str, Vec<&Resolved>> = BTreeMap::new();
+    for c in crates {
+        by_name.entry(&c.name).or_default().push(c);
+    }
+    for (name, versions) in &by_name {
+        if versions.len() > 1 {
+            problems.push(Problem::Duplicate {
+                name: name.to_string(),
+                versions: versions.iter().map(|c| c.version.to_string()).collect(),
+            });
+        }
+    }
+
+    let releases: BTreeSet<(u64, u64)> = crates
+        .iter()
+        .map(|c| (c.version.major, c.version.minor))
+        .collect();
+    if releases.len() > 1 {
+        problems.push(Problem::VersionSkew {
+            versions: crates
+                .iter()
+                .map(|c| format!("{} {}", c.name, c.version))
+                .collect(),
+        });
+    }
+
+    if let Some(philjs) = by_name.get("philjs").and_then(|v| v.first()) {
+        for server in crates.iter().filter(|c| SERVER_CRATES.contains(&c.name.as_str())) {
+            if server.features.contains("ssr") && !philjs.features.contains("ssr") {
+                problems.push(Problem::MissingFeature {
+                    name: "philjs".to_string(),
+                    feature: "ssr",
+                    needed_by: server.name.clone(),
+                });
+            }
+        }
+    }
+
+    problems
+}
+
+// ============================================================================
+// Ports
+// ============================================================================
+
+fn check_ports(report: &mut Report, project: &Project) {
+    const SECTION: &str = "Ports";
+
+    let mut ports = vec![("dev server", project.config.dev.port, "cargo philjs dev --port")];
+    if let Some(port) = project.config.server.port {
+        if port != project.config.dev.port {
+            ports.push(("server", port, "set [server] port in philjs.toml to"));
+        }
+    }
+
+    for (name, port, fix) in ports {
+        if port_free(port) {
+            report.ok(SECTION, name, format!("{} is free", port));
+        } else {
+            let suggestion = (port + 1..port.saturating_add(100))
+                .find(|&p| port_free(p))
+                .map(|p| format!("Stop the process using {} or use another port: {} {}", port, fix, p))
+                .unwrap_or_else(|| format!("Stop the process using port {}", port));
+            report.warn(SECTION, name, format!("{} is in use", port), suggestion);
+        }
+    }
+}
+
+fn port_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+// ============================================================================
+// Template
+// ============================================================================
+
+fn check_template(report: &mut Report, project: &Project) {
+    const SECTION: &str = "Template";
+
+    let Some(template) = project.template.as_deref() else {
+        return;
+    };
+    let package = project.metadata.as_ref().and_then(|m| m.root_package());
+    let features = package.map(|p| &p.features);
+    let has_feature = |feature: &str, enables: &str| {
+        features
+            .and_then(|f| f.get(feature))
+            .is_some_and(|list| list.iter().any(|f| f == enables))
+    };
+
+    match template {
+        "spa" | "minimal" => {
+            let has_index = ["index.html", "static/index.html"]
+                .iter()
+                .any(|file| project.root.join(file).exists());
+            if has_index {
+                report.ok(SECTION, template, "index.html found");
+            } else {
+                report.fail(
+                    SECTION,
+                    template,
+                    "no index.html or static/index.html",
+                    "Create static/index.html that loads /pkg/app.js",
+                );
+            }
+        }
+        "ssr" | "fullstack" => {
+            if has_feature("ssr", "philjs/ssr") {
+                report.ok(SECTION, "ssr feature", "enables philjs/ssr");
+            } else {
+                report.fail(
+                    SECTION,
+                    "ssr feature",
+                    "no `ssr` feature enabling philjs/ssr",
+                    "[features]\nssr = [\"philjs/ssr\"]",
+                );
+            }
+            if has_feature("hydration", "philjs/hydration") {
+                report.ok(SECTION, "hydration feature", "enables philjs/hydration");
+            } else {
+                report.warn(
+                    SECTION,
+                    "hydration feature",
+                    "no `hydration` feature; server-rendered pages won't become interactive",
+                    "[features]\nhydration = [\"philjs/hydration\"]",
+                );
+            }
+            if !project.config.ssr.enabled {
+                report.warn(
+                    SECTION,
+                    "[ssr]",
+                    "SSR is disabled in philjs.toml",
+                    "[ssr]\nenabled = true",
+                );
+            }
+        }
+        "liveview" => {
+            if features.is_some_and(|f| f.contains_key("server")) {
+                report.ok(SECTION, "server feature", "present");
+            } else {
+                report.fail(
+                    SECTION,
+                    "server feature",
+                    "LiveView apps run with a `server` feature",
+                    "[features]\nserver = [\"axum\", \"tokio\"]",
+                );
+            }
+        }
+        other => report.warn(
+            SECTION,
+            other,
+            "unknown template, nothing to check",
+            "Set [project] template to spa, ssr, fullstack, liveview or minimal",
+        ),
+    }
+
+    check_database(report, project);
+    check_deploy_tools(report, project);
+}
+
+fn check_database(report: &mut Report, project: &Project) {
+    const SECTION: &str = "Template";
+
+    let migrations = project.root.join(&project.config.database.migrations_dir);
+    if !migrations.is_dir() {
+        return;
+    }
+    let has_url = std::env::var("DATABASE_URL").is_ok() || project.config.database.url.is_some();
+    if has_url {
+        report.ok(SECTION, "database", "URL configured");
+    } else {
+        report.warn(
+            SECTION,
+            "database",
+            format!("{} exists but no database URL is set", migrations.display()),
+            "Set DATABASE_URL or add [database] url to philjs.toml",
+        );
+    }
+}
+
+fn check_deploy_tools(report: &mut Report, project: &Project) {
+    const SECTION: &str = "Template";
+
+    let docker = &project.config.deploy.docker;
+    let uses_docker = docker.image.is_some()
+        || docker.registry.is_some()
+        || project.root.join("Dockerfile").exists();
+    if uses_docker && !command_exists("docker") {
+        report.warn(
+            SECTION,
+            "docker",
+            "Docker deploys are configured but docker isn't installed",
+            "Install Docker from https://docs.docker.com/get-docker/",
+        );
+    }
+
+    if project.root.join("wrangler.toml").exists() {
+        if !command_exists("wrangler") {
+            report.warn(
+                SECTION,
+                "wrangler",
+                "wrangler.toml found but wrangler isn't installed",
+                "npm install -g wrangler",
+            );
+        }
+        let has_worker = project
+            .metadata
+            .as_ref()
+            .and_then(|m| m.root_package())
+            .is_some_and(|p| p.features.contains_key("worker"));
+        if !has_worker {
+            report.fail(
+                SECTION,
+                "worker feature",
+                "Cloudflare builds need a `worker` feature",
+                "[features]\nworker = [\"philjs/worker\"]",
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Output
+// ============================================================================
+
+fn print_report(report: &Report) {
+    let mut section = "";
+    for check in &report.checks {
+        if check.section != section {
+            section = check.section;
+            println!("  {}", section.white().bold());
+        }
+        let tag = match check.status {
+            Status::Ok => format!("{:<6}", "[ok]").green().bold(),
+            Status::Warn => "[warn]".yellow().bold(),
+            Status::Fail => "[fail]".red().bold(),
+        };
+        println!("    {}  {}  {}", tag, check.name, check.detail.dimmed());
+        if let Some(fix) = &check.fix {
+            if check.status != Status::Ok {
+                for (i, line) in fix.lines().enumerate() {
+                    let label = if i == 0 { "fix:" } else { "    " };
+                    println!("              {} {}", label.dimmed(), line.cyan());
+                }
+            }
+        }
+    }
+
+    let (warnings, failures) = (report.count(Status::Warn), report.count(Status::Fail));
+    println!();
+    if failures == 0 && warnings == 0 {
+        println!("{}  Everything looks good", "[done]".green().bold());
+    } else {
+        println!(
+            "  {} failed, {} warning(s)",
+            failures.to_string().red().bold(),
+            warnings.to_string().yellow().bold()
+        );
+    }
+    println!();
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn tool_version(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(first_line(&stdout).to_string())
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default().trim()
+}
+
+/// First `major.minor[.patch]` in a `--version` line
+fn parse_version(output: &str) -> Option<(u64, u64, u64)> {
+    let word = output
+        .split_whitespace()
+        .find(|w| w.chars().next().is_some_and(|c| c.is_ascii_digit()))?;
+    let mut parts = word
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|p| !p.is_empty())
+        .map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next()?.ok()?;
+    let patch = parts.next().and_then(|p| p.ok()).unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(name: &str, version: &str, features: &[&str]) -> Resolved {
+        Resolved {
+            name: name.to_string(),
+            version: version.parse().unwrap(),
+            features: features.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("rustc 1.78.0 (9b00956e5 2024-04-29)"), Some((1, 78, 0)));
+        assert_eq!(parse_version("rustc 1.80.0-nightly (abc 2024-05-01)"), Some((1, 80, 0)));
+        assert_eq!(parse_version("wasm-pack 0.12.1"), Some((0, 12, 1)));
+        assert_eq!(parse_version("wasm-opt version 116"), None);
+    }
+
+    #[test]
+    fn test_consistent_crates() {
+        let crates = [
+            resolved("philjs", "2.0.1", &["ssr", "wasm"]),
+            resolved("philjs-axum", "2.0.0", &["ssr"]),
+        ];
+        assert!(feature_problems(&crates).is_empty());
+    }
+
+    #[test]
+    fn test_feature_problems() {
+        let crates = [
+            resolved("philjs", "2.0.0", &["wasm"]),
+            resolved("philjs", "1.4.0", &["wasm"]),
+            resolved("philjs-axum", "2.0.0", &["ssr"]),
+        ];
+        let problems = feature_problems(&crates);
+        assert!(problems.contains(&Problem::Duplicate {
+            name: "philjs".to_string(),
+            versions: vec!["2.0.0".to_string(), "1.4.0".to_string()],
+        }));
+        assert!(problems.iter().any(|p| matches!(p, Problem::VersionSkew { .. })));
+        assert!(problems.contains(&Problem::MissingFeature {
+            name: "philjs".to_string(),
+            feature: "ssr",
+            needed_by: "philjs-axum".to_string(),
+        }));
+    }
+}
@@ -2,13 +2,54 @@
 //!
 //! Provides scaffolding for components, pages, server functions, and more.
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use std::fs;
 use std::path::Path;
 
+use super::scaffold::{self, Names, BUILTIN_KINDS};
+use crate::ComponentStyle;
+
+/// Options for [`component_enhanced`]
+#[derive(Debug, Clone)]
+pub struct ComponentOptions {
+    /// Directory to create in, `src/components` by default
+    pub dir: Option<String>,
+    pub tests: bool,
+    /// Generate a props struct
+    pub props: bool,
+    /// Typed props as `(name, type)`; implies `props`
+    pub fields: Vec<(String, String)>,
+    pub style: ComponentStyle,
+}
+
+impl Default for ComponentOptions {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            tests: true,
+            props: false,
+            fields: Vec::new(),
+            style: ComponentStyle::default(),
+        }
+    }
+}
+
+/// Options for [`page`]
+#[derive(Debug, Clone, Default)]
+pub struct PageOptions {
+    /// Route path, `/<kebab-name>` by default
+    pub route: Option<String>,
+    pub loader: bool,
+}
+
 /// Generate a component
 pub fn component(name: &str, dir: Option<&str>, include_tests: bool) -> Result<()> {
+    if from_template("component", &Names::new(name, None))? {
+        return Ok(());
+    }
+
     let dir = dir.unwrap_or("src/components");
     let path = Path::new(dir);
     fs::create_dir_all(path)?;
@@ -77,7 +118,7 @@ pub fn {name}(props: {name}Props) -> impl IntoView {{
     fs::write(&file_path, content)?;
 
     // Update mod.rs if it exists
-    update_mod_rs(path, &snake_name)?;
+    scaffold::declare_module(path, &snake_name, false)?;
 
     println!(
         "{}  Created component: {}",
@@ -93,14 +134,25 @@ pub fn {name}(props: {name}Props) -> impl IntoView {{
 }
 
 /// Generate a page/route
-pub fn page(name: &str, include_loader: bool) -> Result<()> {
+///
+/// The page is added to the project's `routes![...]` list when there is
+/// one, otherwise it registers itself with `#[route]`.
+pub fn page(name: &str, options: &PageOptions) -> Result<()> {
+    let root = Path::new("");
+    let names = Names::new(name, options.route.as_deref());
+    let router = scaffold::find_router(root);
+
+    if from_template("page", &names)? {
+        return register_page(root, &names, router.as_deref());
+    }
+
     let path = Path::new("src/pages");
     fs::create_dir_all(path)?;
 
-    let snake_name = to_snake_case(name);
+    let snake_name = &names.snake_name;
     let file_path = path.join(format!("{}.rs", snake_name));
 
-    let loader_section = if include_loader {
+    let loader_section = if options.loader {
         format!(
             r#"
 
@@ -125,15 +177,20 @@ pub struct {name}Data {{
         String::new()
     };
 
+    let attribute = match router {
+        Some(_) => "#[component]".to_string(),
+        None => format!("#[route(\"{}\")]", names.route),
+    };
+
     let content = format!(
         r#"//! {name} Page
 //!
-//! Route: /{route}
+//! Route: {route}
 
 use philjs::prelude::*;
 
 /// {name} page component
-#[component]
+{attribute}
 pub fn {name}Page() -> impl IntoView {{
     view! {{
         <main class="page-{snake_name}">
@@ -153,7 +210,8 @@ pub fn meta() -> Vec<(&'static str, &'static str)> {{
 "#,
         name = name,
         snake_name = snake_name,
-        route = snake_name.replace('_', "-"),
+        route = names.route,
+        attribute = attribute,
         loader_section = loader_section
     );
 
@@ -165,11 +223,51 @@ pub fn meta() -> Vec<(&'static str, &'static str)> {{
         file_path.display().to_string().cyan()
     );
 
+    register_page(root, &names, router.as_deref())
+}
+
+/// Declare the page module and add it to the router
+fn register_page(root: &Path, names: &Names, router: Option<&Path>) -> Result<()> {
+    let pages = root.join("src/pages");
+    if !pages.join(format!("{}.rs", names.snake_name)).exists() {
+        return Ok(());
+    }
+
+    scaffold::declare_module(&pages, &names.snake_name, true)?;
+    if let Some(crate_root) = scaffold::declare_in_crate_root(root, "pages")? {
+        println!(
+            "{}  Declared mod pages in {}",
+            "[done]".green().bold(),
+            crate_root.display().to_string().cyan()
+        );
+    }
+
+    match router {
+        Some(router) => {
+            let module = format!("pages::{}", names.snake_name);
+            let component = format!("{}Page", names.name);
+            if scaffold::register_route(router, &names.route, &module, &component)? {
+                println!(
+                    "{}  Registered {} in {}",
+                    "[done]".green().bold(),
+                    names.route.cyan(),
+                    router.display().to_string().cyan()
+                );
+            }
+        }
+        None => println!("  Route {} registered with #[route]", names.route.cyan()),
+    }
+    println!();
+
     Ok(())
 }
 
 /// Generate a server function
 pub fn server_fn(name: &str) -> Result<()> {
+    if from_template("server", &Names::new(name, None))? {
+        return Ok(());
+    }
+
     let path = Path::new("src/server");
     fs::create_dir_all(path)?;
 
@@ -195,7 +293,7 @@ pub struct {name}Request {{
 pub struct {name}Response {{
     pub success: bool,
     pub message: String,
-}
+}}
 
 /// {name} server function
 ///
@@ -256,6 +354,10 @@ mod tests {{
 
 /// Generate an API route
 pub fn api(name: &str) -> Result<()> {
+    if from_template("api", &Names::new(name, None))? {
+        return Ok(());
+    }
+
     let path = Path::new("src/api");
     fs::create_dir_all(path)?;
 
@@ -358,6 +460,10 @@ pub async fn delete(id: String) -> ServerResult<{name}Response> {{
 
 /// Generate a store/state module
 pub fn store(name: &str) -> Result<()> {
+    if from_template("store", &Names::new(name, None))? {
+        return Ok(());
+    }
+
     let path = Path::new("src/stores");
     fs::create_dir_all(path)?;
 
@@ -486,6 +592,10 @@ mod tests {{
 
 /// Generate a custom hook
 pub fn hook(name: &str) -> Result<()> {
+    if from_template("hook", &Names::new(name, None))? {
+        return Ok(());
+    }
+
     let path = Path::new("src/hooks");
     fs::create_dir_all(path)?;
 
@@ -607,50 +717,41 @@ mod tests {{
     Ok(())
 }
 
-/// Update mod.rs to include new module
-fn update_mod_rs(dir: &Path, module_name: &str) -> Result<()> {
-    let mod_path = dir.join("mod.rs");
-
-    if mod_path.exists() {
-        let content = fs::read_to_string(&mod_path)?;
-        let module_line = format!("pub mod {};", module_name);
-
-        if !content.contains(&module_line) {
-            let new_content = format!("{}\n{}", content.trim(), module_line);
-            fs::write(mod_path, new_content)?;
-        }
+/// Generate a component with enhanced options
+pub fn component_enhanced(name: &str, options: &ComponentOptions) -> Result<()> {
+    if from_template("component", &Names::new(name, None))? {
+        return Ok(());
     }
 
-    Ok(())
-}
-
-/// Generate a component with enhanced options
-pub fn component_enhanced(
-    name: &str,
-    dir: Option<&str>,
-    include_tests: bool,
-    with_props: bool,
-    with_styles: bool,
-) -> Result<()> {
-    let dir = dir.unwrap_or("src/components");
+    let dir = options.dir.as_deref().unwrap_or("src/components");
     let path = Path::new(dir);
     fs::create_dir_all(path)?;
 
     let snake_name = to_snake_case(name);
     let file_path = path.join(format!("{}.rs", snake_name));
+    let with_props = options.props || !options.fields.is_empty();
+    let with_styles = options.style == ComponentStyle::Css;
 
     // Generate props section if requested
     let props_section = if with_props {
+        let fields = if options.fields.is_empty() {
+            "    /// Add your props here\n    pub children: Option<Children>,\n".to_string()
+        } else {
+            options
+                .fields
+                .iter()
+                .map(|(field, ty)| format!("    pub {}: {},\n", field, ty))
+                .collect()
+        };
         format!(
             r#"
 /// Props for the {name} component
 #[derive(Clone, Default)]
 pub struct {name}Props {{
-    /// Add your props here
-    pub children: Option<Children>,
-}}
+{fields}}}
 "#,
-            name = name
+            name = name,
+            fields = fields
         )
     } else {
         String::new()
@@ -676,7 +777,24 @@ const STYLES: &str = include_str!("{snake_name}.css");
         String::new()
     };
 
-    let tests_section = if include_tests {
+    let (root_class, heading_class) = match options.style {
+        ComponentStyle::Tailwind => (
+            "flex flex-col gap-4 p-4".to_string(),
+            r#" class="text-2xl font-semibold""#,
+        ),
+        _ => (snake_name.clone(), ""),
+    };
+
+    // Only the placeholder `children` prop is rendered
+    let children_render = if with_props && options.fields.is_empty() {
+        "\n            {props.children}"
+    } else {
+        ""
+    };
+
+    let example_props = if with_props { "..Default::default()" } else { "" };
+
+    let tests_section = if options.tests {
         format!(
             r#"
 
@@ -687,14 +805,14 @@ mod tests {{
     #[test]
     fn test_{snake_name}_renders() {{
         let html = render_to_string(|| view! {{
-            <{name} {}/>
+            <{name} {example_props}/>
         }});
         assert!(html.contains("{name}"));
     }}
 }}"#,
             name = name,
             snake_name = snake_name,
-            if with_props { "..Default::default()" } else { "" }
+            example_props = example_props
         )
     } else {
         String::new()
@@ -711,26 +829,26 @@ use philjs::prelude::*;{style_import}{props_section}
 /// # Example
 /// ```rust
 /// view! {{
-///     <{name} {}/>
+///     <{name} {example_props}/>
 /// }}
 /// ```
 #[component]
 {component_sig} {{
     view! {{
-        <div class="{snake_name}">
-            <h2>"{name}"</h2>
-            {{{children_render}}}
+        <div class="{root_class}">
+            <h2{heading_class}>"{name}"</h2>{children_render}
         </div>
     }}
 }}{tests_section}
 "#,
         name = name,
-        snake_name = snake_name,
         style_import = style_import,
         props_section = props_section,
         component_sig = component_sig,
-        children_render = if with_props { "{props.children}" } else { "" },
-        if with_props { "..Default::default()" } else { "" },
+        root_class = root_class,
+        heading_class = heading_class,
+        children_render = children_render,
+        example_props = example_props,
         tests_section = tests_section
     );
 
@@ -763,7 +881,7 @@ use philjs::prelude::*;{style_import}{props_section}
     }
 
     // Update mod.rs if it exists
-    update_mod_rs(path, &snake_name)?;
+    scaffold::declare_module(path, &snake_name, false)?;
 
     println!(
         "{}  Created component: {}",
@@ -789,6 +907,201 @@ use philjs::prelude::*;{style_import}{props_section}
     Ok(())
 }
 
+/// Generate from a `.philjs/templates` directory
+pub fn custom(kind: &str, name: &str) -> Result<()> {
+    if !from_template(kind, &Names::new(name, None))? {
+        bail!(
+            "Unknown generator '{}'. Add a template in {}/{}/",
+            kind,
+            scaffold::TEMPLATES_DIR,
+            kind
+        );
+    }
+    Ok(())
+}
+
+/// Use the project's template for `kind` instead of the built-in one, if it has one
+fn from_template(kind: &str, names: &Names) -> Result<bool> {
+    let root = Path::new("");
+    if scaffold::template_dir(root, kind).is_none() {
+        return Ok(false);
+    }
+
+    let written = scaffold::generate(root, kind, names)?;
+    for path in &written {
+        println!(
+            "{}  Created {}: {}",
+            "[done]".green().bold(),
+            kind,
+            path.display().to_string().cyan()
+        );
+    }
+    if written.is_empty() {
+        println!("  {}  Nothing generated", "[warn]".yellow().bold());
+    }
+    Ok(true)
+}
+
+/// Parse `title: String, count: i32` into prop fields; a bare name is a `String`
+pub fn parse_props(spec: &str) -> Result<Vec<(String, String)>> {
+    let mut fields = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    for c in spec.chars().chain(std::iter::once(',')) {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                let part = std::mem::take(&mut current);
+                let part = part.trim();
+                if part.is_empty() {
+                    continue;
+                }
+                let (field, ty) = part.split_once(':').unwrap_or((part, "String"));
+                let (field, ty) = (field.trim(), ty.trim());
+                let valid = field.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+                    && field.chars().all(|c| c.is_alphanumeric() || c == '_');
+                if !valid || ty.is_empty() {
+                    bail!("Invalid prop '{}', expected name: Type", part);
+                }
+                fields.push((field.to_string(), ty.to_string()));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    Ok(fields)
+}
+
+// ============================================================================
+// Interactive prompts
+// ============================================================================
+
+/// Ask what to generate, then prompt for its options
+pub fn interactive() -> Result<()> {
+    let mut kinds: Vec<String> = BUILTIN_KINDS.iter().map(|kind| kind.to_string()).collect();
+    for kind in scaffold::project_kinds(Path::new("")) {
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("What do you want to generate?")
+        .items(&kinds)
+        .default(0)
+        .interact()?;
+
+    match kinds[choice].as_str() {
+        "component" => {
+            let mut options = ComponentOptions::default();
+            let name = prompt_component(None, &mut options)?;
+            component_enhanced(&name, &options)
+        }
+        "page" => {
+            let mut options = PageOptions::default();
+            let name = prompt_page(None, &mut options)?;
+            page(&name, &options)
+        }
+        "server" => server_fn(&prompt_name("Server function", None)?),
+        "api" => api(&prompt_name("API route", None)?),
+        "store" => store(&prompt_name("Store", None)?),
+        "hook" => hook(&prompt_name("Hook", None)?),
+        kind => custom(kind, &prompt_name(kind, None)?),
+    }
+}
+
+/// Prompt for a name, offering `name` as the default
+pub fn prompt_name(what: &str, name: Option<String>) -> Result<String> {
+    let theme = ColorfulTheme::default();
+    let mut input = Input::<String>::with_theme(&theme)
+        .with_prompt(format!("{} name", what))
+        .validate_with(|input: &String| -> Result<(), &str> {
+            let valid = input.chars().next().is_some_and(char::is_alphabetic)
+                && input.chars().all(|c| c.is_alphanumeric() || c == '_');
+            if valid {
+                Ok(())
+            } else {
+                Err("Use letters, digits and underscores, starting with a letter")
+            }
+        });
+    if let Some(name) = name {
+        input = input.default(name);
+    }
+    Ok(input.interact_text()?)
+}
+
+/// Prompt for a component's name, props, styling and tests
+pub fn prompt_component(name: Option<String>, options: &mut ComponentOptions) -> Result<String> {
+    let theme = ColorfulTheme::default();
+    let name = prompt_name("Component", name)?;
+
+    options.dir = Some(
+        Input::<String>::with_theme(&theme)
+            .with_prompt("Directory")
+            .default(options.dir.clone().unwrap_or_else(|| "src/components".to_string()))
+            .interact_text()?,
+    );
+
+    let current: Vec<String> = options
+        .fields
+        .iter()
+        .map(|(field, ty)| format!("{}: {}", field, ty))
+        .collect();
+    let props: String = Input::with_theme(&theme)
+        .with_prompt("Props (name: Type, comma separated; empty for none)")
+        .with_initial_text(current.join(", "))
+        .allow_empty(true)
+        .validate_with(|input: &String| parse_props(input).map(|_| ()).map_err(|e| e.to_string()))
+        .interact_text()?;
+    options.fields = parse_props(&props)?;
+
+    let styles = [
+        (ComponentStyle::None, "None"),
+        (ComponentStyle::Css, "CSS module"),
+        (ComponentStyle::Tailwind, "Tailwind classes"),
+    ];
+    let labels: Vec<&str> = styles.iter().map(|(_, label)| *label).collect();
+    let selected = Select::with_theme(&theme)
+        .with_prompt("Styling")
+        .items(&labels)
+        .default(styles.iter().position(|(style, _)| *style == options.style).unwrap_or(0))
+        .interact()?;
+    options.style = styles[selected].0;
+
+    options.tests = Confirm::with_theme(&theme)
+        .with_prompt("Include tests?")
+        .default(options.tests)
+        .interact()?;
+
+    Ok(name)
+}
+
+/// Prompt for a page's name, route path and loader
+pub fn prompt_page(name: Option<String>, options: &mut PageOptions) -> Result<String> {
+    let theme = ColorfulTheme::default();
+    let name = prompt_name("Page", name)?;
+
+    let default_route = options
+        .route
+        .clone()
+        .unwrap_or_else(|| Names::new(&name, None).route);
+    options.route = Some(
+        Input::<String>::with_theme(&theme)
+            .with_prompt("Route path")
+            .default(default_route)
+            .interact_text()?,
+    );
+
+    options.loader = Confirm::with_theme(&theme)
+        .with_prompt("Include a data loader?")
+        .default(options.loader)
+        .interact()?;
+
+    Ok(name)
+}
+
 /// Convert PascalCase to snake_case
 fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
@@ -800,3 +1113,23 @@ fn to_snake_case(s: &str) -> String {
     }
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_props() {
+        let fields = parse_props("title: String, items: HashMap<String, i32>, label").unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                ("title".to_string(), "String".to_string()),
+                ("items".to_string(), "HashMap<String, i32>".to_string()),
+                ("label".to_string(), "String".to_string()),
+            ]
+        );
+        assert!(parse_props("").unwrap().is_empty());
+        assert!(parse_props("1st: u8").is_err());
+    }
+}
@@ -0,0 +1,918 @@
+//! Translation catalogs
+//!
+//! `extract` scans the source for `t!("key", name = ...)`, including calls
+//! inside `view!` and other macros, and adds the keys each locale's catalog
+//! is missing. `status` reports how much of every locale is translated and
+//! which keys are missing or no longer used, and fails with `--strict` so CI
+//! catches untranslated text.
+//!
+//! ```bash
+//! cargo philjs i18n extract --locale fr
+//! cargo philjs i18n status --strict
+//! ```
+//!
+//! Catalogs are `locales/<locale>/messages.ftl` (Fluent) or `messages.po`
+//! (gettext), see `[i18n]` in philjs.toml. New Fluent keys are added as
+//! comments at the end of the file, `# key = { $arg }`, for translators to
+//! fill in; new gettext keys get an empty `msgstr`. Existing translations,
+//! comments and formatting are kept.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use proc_macro2::{TokenStream, TokenTree};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+use super::routes::rust_files;
+use crate::config::{is_locale, Config};
+use crate::utils::project_root;
+use crate::CatalogFormat;
+
+/// Starts the section of keys waiting for a Fluent translation
+const FLUENT_MARKER: &str = "## Untranslated (added by cargo philjs i18n extract)";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A key passed to `t!`
+#[derive(Debug, Default, Serialize)]
+struct Key {
+    /// `file:line` of every use
+    locations: Vec<String>,
+    /// Argument names, e.g. `name` in `t!("welcome", name = ...)`
+    args: BTreeSet<String>,
+}
+
+/// Keys by name
+type Keys = BTreeMap<String, Key>;
+
+#[derive(Debug, Serialize)]
+struct LocaleStatus {
+    locale: String,
+    catalog: String,
+    exists: bool,
+    translated: usize,
+    missing: Vec<String>,
+    unused: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    format: String,
+    default_locale: String,
+    keys: usize,
+    locales: Vec<LocaleStatus>,
+    warnings: Vec<String>,
+}
+
+impl CatalogFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            CatalogFormat::Fluent => "ftl",
+            CatalogFormat::Gettext => "po",
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Add new keys to every locale's catalog
+pub fn extract(src: &str, new_locales: &[String], format: Option<CatalogFormat>, prune: bool) -> Result<()> {
+    let root = project_root().context("Not inside a Cargo project")?;
+    let config = Config::load(&root, "dev")?;
+    let format = format.unwrap_or_else(|| config.catalog_format());
+    for locale in new_locales {
+        if !is_locale(locale) {
+            bail!("`{}` is not a locale, e.g. `fr` or `pt-BR`", locale);
+        }
+    }
+
+    let (keys, warnings) = scan(&root, src, format)?;
+    println!(
+        "\n{}  {} key(s) used with t!() in {}\n",
+        "[i18n]".cyan().bold(),
+        keys.len(),
+        src
+    );
+    print_warnings(&warnings);
+
+    for locale in locales(&root, &config, format, new_locales) {
+        let path = catalog_path(&root, &config, &locale, format);
+        let content = fs::read_to_string(&path).unwrap_or_default();
+        let update = match format {
+            CatalogFormat::Fluent => update_fluent(&content, &keys, prune),
+            CatalogFormat::Gettext => update_gettext(&content, &locale, &keys, prune),
+        };
+
+        if update.content != content || !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, &update.content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+
+        let mut changes = Vec::new();
+        if !update.added.is_empty() {
+            changes.push(format!("{} new", update.added.len()).green().to_string());
+        }
+        if !update.removed.is_empty() {
+            changes.push(format!("{} removed", update.removed.len()).red().to_string());
+        }
+        if update.untranslated > 0 {
+            changes.push(format!("{} untranslated", update.untranslated).yellow().to_string());
+        }
+        if changes.is_empty() {
+            changes.push("up to date".dimmed().to_string());
+        }
+        println!(
+            "  {}  {:<32} {}",
+            "[ok]".green(),
+            display(&root, &path),
+            changes.join(", ")
+        );
+    }
+
+    println!("\n{}  Catalogs updated\n", "[done]".green().bold());
+    Ok(())
+}
+
+/// Report translated, missing and unused keys per locale
+pub fn status(src: &str, json: bool, strict: bool) -> Result<()> {
+    let root = project_root().context("Not inside a Cargo project")?;
+    let config = Config::load(&root, "dev")?;
+    let format = config.catalog_format();
+
+    let (keys, warnings) = scan(&root, src, format)?;
+    let mut report = StatusReport {
+        format: config.i18n.format.clone(),
+        default_locale: config.i18n.default_locale.clone(),
+        keys: keys.len(),
+        locales: Vec::new(),
+        warnings,
+    };
+    for locale in locales(&root, &config, format, &[]) {
+        let path = catalog_path(&root, &config, &locale, format);
+        let content = fs::read_to_string(&path).ok();
+        let translated = match (&content, format) {
+            (Some(content), CatalogFormat::Fluent) => fluent_translated(content),
+            (Some(content), CatalogFormat::Gettext) => gettext_translated(content),
+            (None, _) => BTreeSet::new(),
+        };
+        report.locales.push(LocaleStatus {
+            catalog: display(&root, &path),
+            exists: content.is_some(),
+            translated: keys.keys().filter(|k| translated.contains(*k)).count(),
+            missing: keys.keys().filter(|k| !translated.contains(*k)).cloned().collect(),
+            unused: translated.iter().filter(|k| !is_used(k, &keys)).cloned().collect(),
+            locale,
+        });
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_status(&report, &keys);
+    }
+
+    let incomplete: Vec<&str> = report
+        .locales
+        .iter()
+        .filter(|l| !l.missing.is_empty())
+        .map(|l| l.locale.as_str())
+        .collect();
+    if strict && !incomplete.is_empty() {
+        bail!(
+            "Untranslated keys in {}; run `cargo philjs i18n extract` and translate them",
+            incomplete.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// The default locale, configured locales, locales with a catalog and
+/// `extra`, in that order
+fn locales(root: &Path, config: &Config, format: CatalogFormat, extra: &[String]) -> Vec<String> {
+    let mut locales = vec![config.i18n.default_locale.clone()];
+    locales.extend(config.i18n.locales.iter().cloned());
+
+    let file = format!("messages.{}", format.extension());
+    if let Ok(entries) = fs::read_dir(root.join(&config.i18n.dir)) {
+        let mut found: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().join(&file).is_file())
+            .filter_map(|e| e.file_name().to_str().map(str::to_string))
+            .filter(|name| is_locale(name))
+            .collect();
+        found.sort();
+        locales.extend(found);
+    }
+    locales.extend(extra.iter().cloned());
+
+    let mut seen = BTreeSet::new();
+    locales.retain(|l| seen.insert(l.clone()));
+    locales
+}
+
+fn catalog_path(root: &Path, config: &Config, locale: &str, format: CatalogFormat) -> PathBuf {
+    root.join(&config.i18n.dir)
+        .join(locale)
+        .join(format!("messages.{}", format.extension()))
+}
+
+fn display(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).display().to_string()
+}
+
+/// Whether a catalog key belongs to a key in the source; a message is used
+/// when only its attributes are
+fn is_used(catalog_key: &str, keys: &Keys) -> bool {
+    keys.contains_key(catalog_key)
+        || keys
+            .keys()
+            .any(|k| k.split_once('.').is_some_and(|(id, _)| id == catalog_key))
+}
+
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        println!("  {}  {}", "[warn]".yellow().bold(), warning);
+    }
+    if !warnings.is_empty() {
+        println!();
+    }
+}
+
+fn print_status(report: &StatusReport, keys: &Keys) {
+    println!(
+        "\n{}  {} key(s), {} catalogs\n",
+        "[i18n]".cyan().bold(),
+        report.keys,
+        report.format
+    );
+    print_warnings(&report.warnings);
+
+    let width = report.locales.iter().map(|l| l.locale.len()).max().unwrap_or(0);
+    for locale in &report.locales {
+        let percent = (locale.translated * 100).checked_div(report.keys).unwrap_or(100);
+        let summary = format!("{:>4}/{:<4} {:>3}%", locale.translated, report.keys, percent);
+        let state = if !locale.exists {
+            "no catalog".red().to_string()
+        } else if locale.missing.is_empty() {
+            "[ok]".green().to_string()
+        } else {
+            format!("{} missing", locale.missing.len()).yellow().to_string()
+        };
+        let default = if locale.locale == report.default_locale {
+            " (default)".dimmed().to_string()
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:<width$} {}  {}{}",
+            locale.locale.white().bold(),
+            summary,
+            state,
+            default,
+            width = width
+        );
+
+        let key_width = locale.missing.iter().map(|k| k.len()).max().unwrap_or(0);
+        for key in &locale.missing {
+            let location = keys[key].locations.first().map(String::as_str).unwrap_or_default();
+            println!(
+                "      {:<key_width$}  {}",
+                key,
+                location.dimmed(),
+                key_width = key_width
+            );
+        }
+        if !locale.unused.is_empty() {
+            println!(
+                "      {} {}",
+                "unused:".dimmed(),
+                locale.unused.join(", ").dimmed()
+            );
+        }
+    }
+    println!();
+}
+
+// ============================================================================
+// Scanning
+// ============================================================================
+
+/// Every key used with `t!` under `root/src`, with warnings for calls that
+/// can't be extracted
+fn scan(root: &Path, src: &str, format: CatalogFormat) -> Result<(Keys, Vec<String>)> {
+    let src_dir = root.join(src);
+    if !src_dir.is_dir() {
+        bail!("Source directory {} not found", src_dir.display());
+    }
+
+    let mut keys = Keys::new();
+    let mut warnings = Vec::new();
+    for path in rust_files(&src_dir) {
+        let file = display(root, &path);
+        let source = fs::read_to_string(&path)?;
+        match syn::parse_file(&source) {
+            Ok(ast) => KeyCollector {
+                file: &file,
+                keys: &mut keys,
+                warnings: &mut warnings,
+            }
+            .visit_file(&ast),
+            Err(e) => warnings.push(format!("{}: skipped, failed to parse: {}", file, e)),
+        }
+    }
+
+    if format == CatalogFormat::Fluent {
+        keys.retain(|key, usage| {
+            let valid = is_fluent_key(key);
+            if !valid {
+                warnings.push(format!(
+                    "{}: `{}` is not a Fluent message id (letters, digits, `-` and `_`, \
+                     optionally `.attribute`); skipped",
+                    usage.locations[0], key
+                ));
+            }
+            valid
+        });
+    }
+    Ok((keys, warnings))
+}
+
+struct KeyCollector<'a> {
+    file: &'a str,
+    keys: &'a mut Keys,
+    warnings: &'a mut Vec<String>,
+}
+
+/// `t!` arguments: a key, then `name = value` pairs
+struct TArgs {
+    key: syn::LitStr,
+    args: Vec<syn::Ident>,
+}
+
+impl Parse for TArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        let mut args = Vec::new();
+        while !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            args.push(input.parse()?);
+            input.parse::<syn::Token![=]>()?;
+            input.parse::<syn::Expr>()?;
+        }
+        Ok(TArgs { key, args })
+    }
+}
+
+impl<'ast> Visit<'ast> for KeyCollector<'_> {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let is_t = mac.path.segments.last().is_some_and(|s| s.ident == "t");
+        if is_t {
+            self.add(mac.tokens.clone(), mac.path.span().start().line);
+        } else {
+            // view! and other macros aren't parsed by syn
+            self.find_calls(mac.tokens.clone());
+        }
+    }
+}
+
+impl KeyCollector<'_> {
+    /// Find `t!(...)` in a macro's tokens
+    fn find_calls(&mut self, tokens: TokenStream) {
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        let mut i = 0;
+        while i < tokens.len() {
+            if let [TokenTree::Ident(ident), TokenTree::Punct(bang), TokenTree::Group(group), ..] = &tokens[i..] {
+                if ident == "t" && bang.as_char() == '!' {
+                    self.add(group.stream(), ident.span().start().line);
+                    i += 3;
+                    continue;
+                }
+            }
+            if let TokenTree::Group(group) = &tokens[i] {
+                self.find_calls(group.stream());
+            }
+            i += 1;
+        }
+    }
+
+    fn add(&mut self, tokens: TokenStream, line: usize) {
+        let location = format!("{}:{}", self.file, line);
+        match syn::parse2::<TArgs>(tokens) {
+            Ok(call) => {
+                let key = self.keys.entry(call.key.value()).or_default();
+                key.locations.push(location);
+                key.args.extend(call.args.iter().map(|a| a.to_string()));
+            }
+            Err(_) => self.warnings.push(format!(
+                "{}: t!() needs a string literal key and `name = value` arguments to be extracted",
+                location
+            )),
+        }
+    }
+}
+
+fn is_fluent_key(key: &str) -> bool {
+    let is_identifier = |s: &str| {
+        let mut chars = s.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    };
+    match key.split_once('.') {
+        Some((id, attribute)) => is_identifier(id) && is_identifier(attribute),
+        None => is_identifier(key),
+    }
+}
+
+// ============================================================================
+// Catalogs
+// ============================================================================
+
+/// A rewritten catalog
+struct CatalogUpdate {
+    content: String,
+    /// Keys added for translation
+    added: Vec<String>,
+    /// Unused keys removed by `--prune`
+    removed: Vec<String>,
+    /// Keys still waiting for a translation
+    untranslated: usize,
+}
+
+/// A Fluent message and the keys it provides
+struct FluentMessage {
+    id: String,
+    /// Including the comment lines directly above it
+    lines: Range<usize>,
+    /// The id when it has a value, and `id.attribute` for each attribute
+    keys: Vec<String>,
+}
+
+fn fluent_messages(lines: &[&str]) -> Vec<FluentMessage> {
+    let mut messages: Vec<FluentMessage> = Vec::new();
+    let mut current: Option<FluentMessage> = None;
+    let mut comment_start = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let continues = line.starts_with([' ', '\t', '}']);
+        if continues || (line.trim().is_empty() && current.is_some()) {
+            if let Some(message) = current.as_mut() {
+                let text = line.trim();
+                if let Some((attribute, _)) = text.strip_prefix('.').and_then(|a| a.split_once('=')) {
+                    message.keys.push(format!("{}.{}", message.id, attribute.trim()));
+                } else if !text.is_empty() && !message.keys.contains(&message.id) {
+                    message.keys.insert(0, message.id.clone());
+                }
+                if !text.is_empty() {
+                    message.lines.end = i + 1;
+                }
+            }
+            continue;
+        }
+
+        messages.extend(current.take());
+        if line.starts_with("# ") || *line == "#" {
+            comment_start.get_or_insert(i);
+            continue;
+        }
+        let start = comment_start.take().unwrap_or(i);
+        let Some((id, value)) = line.split_once('=') else {
+            continue;
+        };
+        let id = id.trim();
+        if id.starts_with('-') || line.starts_with('#') || !is_fluent_key(id) {
+            continue;
+        }
+        current = Some(FluentMessage {
+            id: id.to_string(),
+            lines: start..i + 1,
+            keys: if value.trim().is_empty() {
+                Vec::new()
+            } else {
+                vec![id.to_string()]
+            },
+        });
+    }
+    messages.extend(current);
+    messages
+}
+
+fn fluent_translated(content: &str) -> BTreeSet<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let end = lines.iter().position(|l| *l == FLUENT_MARKER).unwrap_or(lines.len());
+    let mut keys: BTreeSet<String> = fluent_messages(&lines[..end])
+        .into_iter()
+        .flat_map(|m| m.keys)
+        .collect();
+    // Translations written below the marker
+    keys.extend(
+        fluent_messages(&fluent_tail(&lines[end..]))
+            .into_iter()
+            .flat_map(|m| m.keys),
+    );
+    keys
+}
+
+/// `# key =` placeholder for an untranslated key
+fn fluent_placeholder(line: &str) -> Option<&str> {
+    let key = line.strip_prefix("# ")?.split_once(" =")?.0;
+    is_fluent_key(key).then_some(key)
+}
+
+/// Lines below the marker that aren't placeholders
+fn fluent_tail<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    lines
+        .iter()
+        .skip(1)
+        .filter(|l| fluent_placeholder(l).is_none())
+        .copied()
+        .collect()
+}
+
+fn update_fluent(content: &str, keys: &Keys, prune: bool) -> CatalogUpdate {
+    let lines: Vec<&str> = content.lines().collect();
+    let marker = lines.iter().position(|l| *l == FLUENT_MARKER).unwrap_or(lines.len());
+    let placeholders: BTreeSet<&str> = lines[marker..]
+        .iter()
+        .filter_map(|l| fluent_placeholder(l))
+        .collect();
+
+    // Translations filled in below the marker move up with the rest
+    let mut body: Vec<&str> = lines[..marker].to_vec();
+    let tail = fluent_tail(&lines[marker..]);
+    if tail.iter().any(|l| !l.trim().is_empty()) {
+        if body.last().is_some_and(|l| !l.trim().is_empty()) {
+            body.push("");
+        }
+        body.extend(tail);
+    }
+
+    let mut removed = Vec::new();
+    if prune {
+        let unused: Vec<FluentMessage> = fluent_messages(&body)
+            .into_iter()
+            .filter(|m| !is_used(&m.id, keys) && !m.keys.iter().any(|k| keys.contains_key(k)))
+            .collect();
+        for message in unused.iter().rev() {
+            let mut end = message.lines.end;
+            // Drop the blank line that separated it from the next entry
+            if body.get(end).is_some_and(|l| l.trim().is_empty()) {
+                end += 1;
+            }
+            body.drain(message.lines.start..end);
+        }
+        removed = unused.into_iter().map(|m| m.id).collect();
+        removed.sort();
+    }
+    while body.last().is_some_and(|l| l.trim().is_empty()) {
+        body.pop();
+    }
+
+    let translated: BTreeSet<String> = fluent_messages(&body).into_iter().flat_map(|m| m.keys).collect();
+    let missing: Vec<(&String, &Key)> = keys.iter().filter(|(k, _)| !translated.contains(*k)).collect();
+
+    let mut out = body.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    if !missing.is_empty() {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(FLUENT_MARKER);
+        out.push('\n');
+        for (key, usage) in &missing {
+            let args: String = usage.args.iter().map(|a| format!(" {{ ${} }}", a)).collect();
+            out.push_str(&format!("# {} ={}\n", key, args));
+        }
+    }
+
+    CatalogUpdate {
+        content: out,
+        added: missing
+            .iter()
+            .filter(|(k, _)| !placeholders.contains(k.as_str()))
+            .map(|(k, _)| (*k).clone())
+            .collect(),
+        removed,
+        untranslated: missing.len(),
+    }
+}
+
+/// A `.po` entry: the lines between blank lines
+struct PoEntry<'a> {
+    lines: Vec<&'a str>,
+    msgid: Option<String>,
+    translated: bool,
+}
+
+impl PoEntry<'_> {
+    fn is_header(&self) -> bool {
+        self.msgid.as_deref() == Some("")
+    }
+}
+
+fn po_entries(content: &str) -> Vec<PoEntry<'_>> {
+    let mut blocks: Vec<Vec<&str>> = vec![Vec::new()];
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            blocks.push(Vec::new());
+        } else if let Some(block) = blocks.last_mut() {
+            block.push(line);
+        }
+    }
+
+    blocks
+        .into_iter()
+        .filter(|b| !b.is_empty())
+        .map(|lines| {
+            let mut msgid: Option<String> = None;
+            let mut strings = Vec::new();
+            let mut in_msgid = false;
+            let mut fuzzy = false;
+            for line in &lines {
+                let line = line.trim();
+                if let Some(flags) = line.strip_prefix("#,") {
+                    fuzzy |= flags.split(',').any(|f| f.trim() == "fuzzy");
+                } else if let Some(rest) = line.strip_prefix("msgid ") {
+                    msgid = Some(po_unquote(rest));
+                    in_msgid = true;
+                } else if line.starts_with("msgstr") || line.starts_with("msgid_plural") {
+                    in_msgid = false;
+                    if line.starts_with("msgstr") {
+                        let rest = line.split_once(' ').map(|(_, r)| r).unwrap_or_default();
+                        strings.push(po_unquote(rest));
+                    }
+                } else if line.starts_with('"') {
+                    if in_msgid {
+                        if let Some(id) = msgid.as_mut() {
+                            id.push_str(&po_unquote(line));
+                        }
+                    } else if let Some(last) = strings.last_mut() {
+                        last.push_str(&po_unquote(line));
+                    }
+                }
+            }
+            let translated = !fuzzy && !strings.is_empty() && strings.iter().all(|s| !s.is_empty());
+            PoEntry { lines, msgid, translated }
+        })
+        .collect()
+}
+
+fn po_unquote(s: &str) -> String {
+    let inner = s.trim().trim_start_matches('"').trim_end_matches('"');
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+fn po_quote(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+fn gettext_translated(content: &str) -> BTreeSet<String> {
+    po_entries(content)
+        .into_iter()
+        .filter(|e| e.translated && !e.is_header())
+        .filter_map(|e| e.msgid)
+        .collect()
+}
+
+fn update_gettext(content: &str, locale: &str, keys: &Keys, prune: bool) -> CatalogUpdate {
+    let entries = po_entries(content);
+    let mut blocks: Vec<String> = Vec::new();
+    let mut seen = BTreeSet::new();
+    let mut removed = Vec::new();
+    let mut untranslated = 0;
+
+    if !entries.iter().any(PoEntry::is_header) {
+        blocks.push(format!(
+            "msgid \"\"\nmsgstr \"\"\n\"Language: {}\\n\"\n\"MIME-Version: 1.0\\n\"\n\
+             \"Content-Type: text/plain; charset=UTF-8\\n\"\n\"Content-Transfer-Encoding: 8bit\\n\"",
+            locale
+        ));
+    }
+    for entry in &entries {
+        let obsolete = entry.lines.iter().all(|l| l.starts_with("#~"));
+        let key = entry.msgid.as_deref().filter(|_| !obsolete && !entry.is_header());
+        let Some(key) = key else {
+            blocks.push(entry.lines.join("\n"));
+            continue;
+        };
+        let Some(usage) = keys.get(key) else {
+            if prune {
+                removed.push(key.to_string());
+            } else {
+                blocks.push(entry.lines.join("\n"));
+            }
+            continue;
+        };
+        seen.insert(key);
+        if !entry.translated {
+            untranslated += 1;
+        }
+
+        // Replace the references with where the key is used now
+        let mut lines: Vec<String> = entry
+            .lines
+            .iter()
+            .filter(|l| !l.starts_with("#:"))
+            .map(|l| l.to_string())
+            .collect();
+        let at = lines
+            .iter()
+            .position(|l| l.starts_with("#,") || !l.starts_with('#'))
+            .unwrap_or(lines.len());
+        for (i, location) in usage.locations.iter().enumerate() {
+            lines.insert(at + i, format!("#: {}", location));
+        }
+        blocks.push(lines.join("\n"));
+    }
+
+    let mut added = Vec::new();
+    for (key, usage) in keys {
+        if seen.contains(key.as_str()) {
+            continue;
+        }
+        let mut lines = Vec::new();
+        if !usage.args.is_empty() {
+            let args: Vec<String> = usage.args.iter().map(|a| format!("{{{}}}", a)).collect();
+            lines.push(format!("#. Arguments: {}", args.join(", ")));
+        }
+        lines.extend(usage.locations.iter().map(|l| format!("#: {}", l)));
+        lines.push(format!("msgid {}", po_quote(key)));
+        lines.push("msgstr \"\"".to_string());
+        blocks.push(lines.join("\n"));
+        added.push(key.clone());
+        untranslated += 1;
+    }
+
+    CatalogUpdate {
+        content: format!("{}\n", blocks.join("\n\n")),
+        added,
+        removed,
+        untranslated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(source: &str) -> (Keys, Vec<String>) {
+        let file = syn::parse_file(source).unwrap();
+        let mut keys = Keys::new();
+        let mut warnings = Vec::new();
+        KeyCollector {
+            file: "src/app.rs",
+            keys: &mut keys,
+            warnings: &mut warnings,
+        }
+        .visit_file(&file);
+        (keys, warnings)
+    }
+
+    fn keys(names: &[(&str, &[&str])]) -> Keys {
+        names
+            .iter()
+            .map(|(name, args)| {
+                let key = Key {
+                    locations: vec!["src/app.rs:1".to_string()],
+                    args: args.iter().map(|a| a.to_string()).collect(),
+                };
+                (name.to_string(), key)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_keys() {
+        let (keys, warnings) = collect(
+            r#"
+fn App() -> impl IntoView {
+    let title = t!("page-title");
+    view! {
+        <h1>{move || t!("welcome", name = user.name())}</h1>
+        <p>{move || philjs::t!("emails", count = unread.get(),)}</p>
+        <input placeholder=t!("search.placeholder") />
+    }
+}
+
+fn dynamic(key: &str) -> String {
+    format!("{}", t!(key))
+}
+"#,
+        );
+
+        let names: Vec<&str> = keys.keys().map(String::as_str).collect();
+        assert_eq!(names, ["emails", "page-title", "search.placeholder", "welcome"]);
+        assert_eq!(keys["welcome"].args.iter().collect::<Vec<_>>(), ["name"]);
+        assert_eq!(keys["page-title"].locations, ["src/app.rs:3"]);
+        assert_eq!(keys["welcome"].locations, ["src/app.rs:5"]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("src/app.rs:12:"));
+    }
+
+    #[test]
+    fn test_update_fluent() {
+        let keys = keys(&[("welcome", &["name"]), ("login", &[]), ("login.hint", &[]), ("new-key", &[])]);
+        let existing = "\
+# Greeting on the home page
+welcome = Welcome, { $name }!
+
+login =
+    .hint = Email address
+
+# No longer used
+old = Old
+
+## Untranslated (added by cargo philjs i18n extract)
+# new-key =
+# gone =
+";
+
+        let update = update_fluent(existing, &keys, false);
+        assert_eq!(update.added, ["login"]);
+        assert_eq!(update.untranslated, 2);
+        assert!(update.content.starts_with("# Greeting on the home page\nwelcome ="));
+        assert!(update.content.contains("old = Old"));
+        assert!(update.content.ends_with(&format!("{}\n# login =\n# new-key =\n", FLUENT_MARKER)));
+
+        // A translation filled in below the marker moves up
+        let filled = update.content.replace("# new-key =", "new-key = New");
+        let update = update_fluent(&filled, &keys, true);
+        assert_eq!(update.removed, ["old"]);
+        assert!(!update.content.contains("No longer used"));
+        assert!(update.content.contains("\nnew-key = New\n"));
+        assert!(update.content.ends_with(&format!("{}\n# login =\n", FLUENT_MARKER)));
+        assert_eq!(
+            fluent_translated(&update.content).into_iter().collect::<Vec<_>>(),
+            ["login.hint", "new-key", "welcome"]
+        );
+    }
+
+    #[test]
+    fn test_update_gettext() {
+        let keys = keys(&[("welcome", &["name"]), ("new-key", &[])]);
+        let update = update_gettext("", "fr", &keys, false);
+        assert_eq!(update.added, ["new-key", "welcome"]);
+        assert!(update.content.contains("\"Language: fr\\n\""));
+        assert!(update.content.contains("#. Arguments: {name}\n#: src/app.rs:1\nmsgid \"welcome\"\nmsgstr \"\""));
+
+        let translated = update
+            .content
+            .replace("msgid \"welcome\"\nmsgstr \"\"", "msgid \"welcome\"\nmsgstr \"Bienvenue, {name}\"")
+            + "\n#: src/old.rs:9\nmsgid \"old\"\nmsgstr \"Vieux\"\n";
+        assert_eq!(
+            gettext_translated(&translated).into_iter().collect::<Vec<_>>(),
+            ["old", "welcome"]
+        );
+
+        let update = update_gettext(&translated, "fr", &keys, true);
+        assert!(update.added.is_empty());
+        assert_eq!(update.removed, ["old"]);
+        assert_eq!(update.untranslated, 1);
+        assert_eq!(update.content.matches("Language: fr").count(), 1);
+        assert!(update.content.contains("msgstr \"Bienvenue, {name}\""));
+    }
+
+    #[test]
+    fn test_fluent_keys() {
+        assert!(is_fluent_key("welcome"));
+        assert!(is_fluent_key("login.placeholder"));
+        assert!(!is_fluent_key("Hello, world"));
+        assert!(!is_fluent_key("a.b.c"));
+    }
+}
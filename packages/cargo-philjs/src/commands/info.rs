@@ -6,8 +6,11 @@ use anyhow::Result;
 use colored::Colorize;
 use serde::Serialize;
 use std::fs;
+use std::path::Path;
 use std::process::Command;
 
+use crate::config::Config;
+
 #[derive(Serialize)]
 struct ProjectInfo {
     name: Option<String>,
@@ -30,6 +33,7 @@ struct ToolsInfo {
 struct FilesInfo {
     rust_files: usize,
     has_config: bool,
+    config_file: Option<String>,
     has_static: bool,
 }
 
@@ -57,12 +61,13 @@ fn gather_info() -> Result<ProjectInfo> {
         (None, None)
     };
 
-    // Read template from config
-    let template = if let Ok(content) = fs::read_to_string("philjs.config.toml") {
-        extract_field(&content, "template")
-    } else {
-        None
-    };
+    // Read template from config; `doctor` reports an invalid one
+    let root = Path::new(".");
+    let template = Config::load(root, "dev")
+        .ok()
+        .and_then(|config| config.project.template);
+    let config_file = Config::path(root)
+        .and_then(|path| Some(path.file_name()?.to_string_lossy().to_string()));
 
     // Get tool versions
     let tools = ToolsInfo {
@@ -85,7 +90,8 @@ fn gather_info() -> Result<ProjectInfo> {
 
     let files = FilesInfo {
         rust_files,
-        has_config: std::path::Path::new("philjs.config.toml").exists(),
+        has_config: config_file.is_some(),
+        config_file,
         has_static: std::path::Path::new("static").exists(),
     };
 
@@ -139,10 +145,9 @@ fn print_info(info: &ProjectInfo) {
     println!(
         "    {}  {}",
         "Config:".dimmed(),
-        if info.files.has_config {
-            "philjs.config.toml".green()
-        } else {
-            "not found".red()
+        match &info.files.config_file {
+            Some(file) => file.green(),
+            None => "not found".red(),
         }
     );
     println!(
@@ -2,6 +2,7 @@
 //!
 //! Adds PhilJS to an existing Rust project.
 
+use crate::config::{Config, CONFIG_FILE};
 use crate::ProjectTemplate;
 use anyhow::{Context, Result};
 use colored::Colorize;
@@ -99,12 +100,14 @@ fn add_philjs_dependency(template: ProjectTemplate) -> Result<()> {
     Ok(())
 }
 
-/// Create philjs.config.toml
+/// Create philjs.toml
 fn create_config_file(template: ProjectTemplate) -> Result<()> {
-    let config_path = Path::new("philjs.config.toml");
-
-    if config_path.exists() {
-        println!("  {}  philjs.config.toml already exists", "[ok]".green());
+    if let Some(existing) = Config::path(Path::new(".")) {
+        println!(
+            "  {}  {} already exists",
+            "[ok]".green(),
+            existing.file_name().unwrap_or_default().to_string_lossy()
+        );
         return Ok(());
     }
 
@@ -162,8 +165,8 @@ tree_shake = true
         }
     };
 
-    fs::write(config_path, config)?;
-    println!("  {}  Created philjs.config.toml", "[ok]".green());
+    fs::write(CONFIG_FILE, config)?;
+    println!("  {}  Created {}", "[ok]".green(), CONFIG_FILE);
 
     Ok(())
 }
@@ -7,14 +7,23 @@ pub mod new;
 pub mod init;
 pub mod dev;
 pub mod build;
+pub mod analyze;
 pub mod check;
 pub mod generate;
 pub mod test;
 pub mod deploy;
+pub mod docker;
+pub mod worker;
 pub mod add;
 pub mod update;
 pub mod info;
 pub mod clean;
+pub mod db;
+pub mod i18n;
+pub mod doctor;
+pub mod routes;
+pub mod prerender;
+pub mod scaffold;
 
 // Re-export common utilities for commands
 pub use crate::utils::{command_exists, project_root, is_philjs_project};
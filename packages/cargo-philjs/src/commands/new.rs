@@ -2,6 +2,7 @@
 //!
 //! Scaffolds a new project with the selected template.
 
+use crate::config::CONFIG_FILE;
 use crate::templates;
 use crate::ProjectTemplate;
 use anyhow::{Context, Result};
@@ -299,7 +300,7 @@ fn create_source_files(path: &Path, name: &str, template: ProjectTemplate) -> Re
 }
 
 fn create_config_files(path: &Path, template: ProjectTemplate) -> Result<()> {
-    // philjs.config.toml
+    // philjs.toml
     let config = match template {
         ProjectTemplate::Liveview => {
             r#"[project]
@@ -358,7 +359,7 @@ tree_shake = true
 "#
         }
     };
-    fs::write(path.join("philjs.config.toml"), config)?;
+    fs::write(path.join(CONFIG_FILE), config)?;
 
     // .gitignore
     let gitignore = r#"# Build outputs
@@ -0,0 +1,473 @@
+//! Build-time prerendering for `cargo philjs build --prerender`
+//!
+//! Pages marked `#[route(ssr = "ssg")]` or `#[route(ssr = "isr")]` are
+//! rendered once at build time by the SSR server binary, started from the
+//! project root on a free port with `HOST`, `PORT` and `PHILJS_PRERENDER=1`
+//! set:
+//!
+//! - static routes are requested as they are
+//! - dynamic routes (`/posts/:slug`) are expanded from
+//!   `GET /_philjs/static-paths`, a JSON object mapping each route pattern to
+//!   the paths its `get_static_paths` returns
+//!
+//! ```rust,ignore
+//! async fn static_paths() -> Json<BTreeMap<&'static str, Vec<String>>> {
+//!     Json(BTreeMap::from([("/posts/:slug", posts::get_static_paths().await)]))
+//! }
+//! ```
+//!
+//! Each page is written to `client/<path>/index.html`, and the data from its
+//! `<script type="application/json" data-philjs>` blocks to
+//! `client/<path>/__data.json` for client-side navigation. Alongside:
+//!
+//! - `client/_philjs/prerender.json` lists every page with its route, mode
+//!   and revalidate interval, for servers that regenerate ISR pages
+//! - `client/_headers` (Netlify and Cloudflare Pages) gets a cache rule per
+//!   page, letting the edge serve ISR pages for their revalidate interval
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Seek};
+use std::path::Path;
+use std::process::Stdio;
+use std::time::{Duration, Instant, SystemTime};
+
+use super::routes::{self, Route, RouteKind, DEFAULT_REVALIDATE};
+
+/// Lists the concrete paths of each dynamic route
+pub const STATIC_PATHS_ENDPOINT: &str = "/_philjs/static-paths";
+
+/// Prerender manifest, relative to the client output
+pub const PRERENDER_MANIFEST: &str = "_philjs/prerender.json";
+
+/// Hydration data next to each page's `index.html`
+const DATA_FILE: &str = "__data.json";
+
+const HEADERS_FILE: &str = "_headers";
+const HEADERS_MARKER: &str = "# Prerendered pages (generated by cargo-philjs)";
+
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A route to prerender
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Target {
+    route: String,
+    mode: &'static str,
+    revalidate: Option<u32>,
+}
+
+/// A page written to the client output
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrerenderedPage {
+    pub path: String,
+    /// Route pattern the path was generated from
+    pub route: String,
+    /// `ssg` or `isr`
+    pub mode: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revalidate: Option<u32>,
+    /// HTML file, relative to the client output
+    pub file: String,
+    /// Hydration data file, relative to the client output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PrerenderManifest<'a> {
+    version: u32,
+    generated_at: String,
+    pages: &'a [PrerenderedPage],
+}
+
+/// Prerender the project's ssg and isr pages with `server` into `client_dir`
+pub async fn run(server: &Path, client_dir: &Path, root: &Path) -> Result<Vec<PrerenderedPage>> {
+    let (routes, _) = routes::discover(root, "src")?;
+    let targets = targets(&routes);
+    if targets.is_empty() {
+        println!(
+            "  {}  No routes with ssr = \"ssg\" or \"isr\", nothing to prerender",
+            "[warn]".yellow()
+        );
+        return Ok(Vec::new());
+    }
+
+    let server = Server::start(server, root).await?;
+    let client = reqwest::Client::new();
+
+    let static_paths = if targets.iter().any(|t| is_dynamic(&t.route)) {
+        fetch_static_paths(&client, &server.url).await?
+    } else {
+        BTreeMap::new()
+    };
+
+    let mut pages = Vec::new();
+    let mut failures = Vec::new();
+    for target in &targets {
+        let paths = if is_dynamic(&target.route) {
+            match static_paths.get(&target.route) {
+                Some(paths) => paths.clone(),
+                None => {
+                    println!(
+                        "  {}  {} has no static paths, skipped (list them at {})",
+                        "[warn]".yellow(),
+                        target.route,
+                        STATIC_PATHS_ENDPOINT
+                    );
+                    continue;
+                }
+            }
+        } else {
+            vec![target.route.clone()]
+        };
+
+        for path in paths {
+            if !matches_route(&target.route, &path) {
+                failures.push(format!("{}: static path {} doesn't match the route", target.route, path));
+                continue;
+            }
+            match render(&client, &server.url, &path).await {
+                Ok(html) => pages.push(write_page(client_dir, target, &path, &html)?),
+                Err(e) => failures.push(format!("{}: {}", path, e)),
+            }
+        }
+    }
+    drop(server);
+
+    if !failures.is_empty() {
+        bail!("Prerendering failed:\n  {}", failures.join("\n  "));
+    }
+
+    write_manifest(client_dir, &pages)?;
+    write_headers(client_dir, &pages)?;
+
+    println!(
+        "\n{}  Prerendered {} page(s)",
+        "[done]".green().bold(),
+        pages.len()
+    );
+    for page in &pages {
+        let mode = match page.revalidate {
+            Some(seconds) => format!("isr {}s", seconds),
+            None => page.mode.to_string(),
+        };
+        println!("    {:<40} {}", page.path, mode.dimmed());
+    }
+    Ok(pages)
+}
+
+/// Pages that render at build time, in route order
+fn targets(routes: &[Route]) -> Vec<Target> {
+    let mut targets: Vec<Target> = routes
+        .iter()
+        .filter(|route| route.kind == RouteKind::Page)
+        .filter_map(|route| {
+            let (mode, revalidate) = match route.ssr.as_deref() {
+                Some("ssg") => ("ssg", None),
+                Some("isr") => ("isr", Some(route.revalidate.unwrap_or(DEFAULT_REVALIDATE))),
+                _ => return None,
+            };
+            Some(Target {
+                route: route.path.clone(),
+                mode,
+                revalidate,
+            })
+        })
+        .collect();
+    targets.sort_by(|a, b| a.route.cmp(&b.route));
+    targets.dedup_by(|a, b| a.route == b.route);
+    targets
+}
+
+fn is_dynamic(route: &str) -> bool {
+    route.split('/').any(|s| s.starts_with(':') || s.starts_with('*'))
+}
+
+/// Whether `path` is one of the URLs `route` matches
+fn matches_route(route: &str, path: &str) -> bool {
+    let route: Vec<&str> = route.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if path.iter().any(|s| *s == "." || *s == "..") {
+        return false;
+    }
+    for (i, segment) in route.iter().enumerate() {
+        if segment.starts_with('*') {
+            return path.len() > i;
+        }
+        match path.get(i) {
+            Some(_) if segment.starts_with(':') => {}
+            Some(p) if p == segment => {}
+            _ => return false,
+        }
+    }
+    path.len() == route.len()
+}
+
+/// `/` is `index.html`, `/about` is `about/index.html`
+fn page_file(path: &str) -> String {
+    let path = path.split(['?', '#']).next().unwrap_or_default().trim_matches('/');
+    if path.is_empty() {
+        "index.html".to_string()
+    } else {
+        format!("{}/index.html", path)
+    }
+}
+
+/// The SSR server, killed when dropped
+struct Server {
+    _child: tokio::process::Child,
+    url: String,
+}
+
+impl Server {
+    async fn start(binary: &Path, root: &Path) -> Result<Self> {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+        let mut log = tempfile::tempfile()?;
+
+        let mut child = tokio::process::Command::new(binary)
+            .current_dir(root)
+            .env("HOST", "127.0.0.1")
+            .env("PORT", port.to_string())
+            .env("PHILJS_PRERENDER", "1")
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log.try_clone()?)
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start {}", binary.display()))?;
+
+        let start = Instant::now();
+        loop {
+            if let Some(status) = child.try_wait()? {
+                let mut output = String::new();
+                log.rewind()?;
+                log.read_to_string(&mut output)?;
+                bail!("The server exited with {} before prerendering:\n{}", status, output.trim_end());
+            }
+            if tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_ok() {
+                break;
+            }
+            if start.elapsed() > STARTUP_TIMEOUT {
+                bail!(
+                    "The server didn't listen on port {} within {}s; it must bind to HOST and PORT",
+                    port,
+                    STARTUP_TIMEOUT.as_secs()
+                );
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        Ok(Self {
+            _child: child,
+            url: format!("http://127.0.0.1:{}", port),
+        })
+    }
+}
+
+async fn fetch_static_paths(
+    client: &reqwest::Client,
+    base: &str,
+) -> Result<BTreeMap<String, Vec<String>>> {
+    let response = client
+        .get(format!("{}{}", base, STATIC_PATHS_ENDPOINT))
+        .header("x-philjs-prerender", "1")
+        .send()
+        .await
+        .context("Failed to request static paths")?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(BTreeMap::new());
+    }
+    if !response.status().is_success() {
+        bail!("{} returned {}", STATIC_PATHS_ENDPOINT, response.status());
+    }
+    response
+        .json()
+        .await
+        .with_context(|| format!("{} must return {{\"/route/:param\": [\"/route/value\"]}}", STATIC_PATHS_ENDPOINT))
+}
+
+async fn render(client: &reqwest::Client, base: &str, path: &str) -> Result<String> {
+    let response = client
+        .get(format!("{}{}", base, path))
+        .header("x-philjs-prerender", "1")
+        .send()
+        .await?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("server returned {}", status);
+    }
+    Ok(response.text().await?)
+}
+
+fn write_page(client_dir: &Path, target: &Target, path: &str, html: &str) -> Result<PrerenderedPage> {
+    let file = page_file(path);
+    let html_path = client_dir.join(&file);
+    if let Some(parent) = html_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&html_path, html)?;
+
+    let data = match hydration_data(html) {
+        Some(data) => {
+            let data_file = match file.strip_suffix("index.html") {
+                Some(dir) => format!("{}{}", dir, DATA_FILE),
+                None => DATA_FILE.to_string(),
+            };
+            fs::write(client_dir.join(&data_file), serde_json::to_vec(&data)?)?;
+            Some(data_file)
+        }
+        None => None,
+    };
+
+    Ok(PrerenderedPage {
+        path: path.to_string(),
+        route: target.route.clone(),
+        mode: target.mode,
+        revalidate: target.revalidate,
+        file,
+        data,
+        size: html.len() as u64,
+    })
+}
+
+/// Collect `<script type="application/json" data-philjs="id">` blocks by id
+fn hydration_data(html: &str) -> Option<serde_json::Value> {
+    let mut data = serde_json::Map::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<script") {
+        let tag_end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let tag = &rest[start..tag_end];
+        let body_end = match rest[tag_end..].find("</script>") {
+            Some(end) => tag_end + end,
+            None => break,
+        };
+        if tag.contains("application/json") {
+            if let Some(id) = attribute(tag, "data-philjs") {
+                let body = &rest[tag_end + 1..body_end];
+                if let Ok(value) = serde_json::from_str(body.trim()) {
+                    data.insert(id.to_string(), value);
+                }
+            }
+        }
+        rest = &rest[body_end + "</script>".len()..];
+    }
+    (!data.is_empty()).then_some(serde_json::Value::Object(data))
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{}=", name))? + name.len() + 1;
+    let value = &tag[start..];
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &value[1..];
+    Some(&value[..value.find(quote)?])
+}
+
+fn write_manifest(client_dir: &Path, pages: &[PrerenderedPage]) -> Result<()> {
+    let manifest = PrerenderManifest {
+        version: 1,
+        generated_at: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+        pages,
+    };
+    let path = client_dir.join(PRERENDER_MANIFEST);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&manifest)?)?;
+    Ok(())
+}
+
+/// Append cache rules to `_headers`, keeping any the project ships
+fn write_headers(client_dir: &Path, pages: &[PrerenderedPage]) -> Result<()> {
+    if pages.is_empty() {
+        return Ok(());
+    }
+    let path = client_dir.join(HEADERS_FILE);
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let existing = match existing.find(HEADERS_MARKER) {
+        Some(at) => existing[..at].trim_end().to_string(),
+        None => existing.trim_end().to_string(),
+    };
+
+    let mut out = existing;
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+    out.push_str(&cache_rules(pages));
+    fs::write(path, out)?;
+    Ok(())
+}
+
+fn cache_rules(pages: &[PrerenderedPage]) -> String {
+    let mut out = format!("{}\n", HEADERS_MARKER);
+    for page in pages {
+        let cache = match page.revalidate {
+            Some(seconds) => format!(
+                "public, max-age=0, s-maxage={}, stale-while-revalidate=31536000",
+                seconds
+            ),
+            None => "public, max-age=0, must-revalidate".to_string(),
+        };
+        out.push_str(&format!("{}\n  Cache-Control: {}\n", page.path, cache));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_route() {
+        assert!(matches_route("/posts/:slug", "/posts/hello"));
+        assert!(matches_route("/users/:id<i32>", "/users/42"));
+        assert!(matches_route("/docs/*path", "/docs/a/b"));
+        assert!(!matches_route("/posts/:slug", "/posts"));
+        assert!(!matches_route("/posts/:slug", "/blog/hello"));
+        assert!(!matches_route("/posts/:slug", "/posts/../secret"));
+        assert!(!matches_route("/posts/:slug", "/posts/a/b"));
+    }
+
+    #[test]
+    fn test_page_file() {
+        assert_eq!(page_file("/"), "index.html");
+        assert_eq!(page_file("/about"), "about/index.html");
+        assert_eq!(page_file("/posts/hello/"), "posts/hello/index.html");
+    }
+
+    #[test]
+    fn test_hydration_data() {
+        let html = r#"<html><body><div id="app"></div>
+<script type="application/json" data-philjs="user">{"name":"Ada"}</script>
+<script>window.x = 1;</script>
+<script type="application/json" data-philjs='posts'>[1, 2]</script></body></html>"#;
+        let data = hydration_data(html).unwrap();
+        assert_eq!(data["user"]["name"], "Ada");
+        assert_eq!(data["posts"][1], 2);
+        assert!(hydration_data("<p>static</p>").is_none());
+    }
+
+    #[test]
+    fn test_cache_rules() {
+        let page = |path: &str, revalidate: Option<u32>| PrerenderedPage {
+            path: path.to_string(),
+            route: path.to_string(),
+            mode: if revalidate.is_some() { "isr" } else { "ssg" },
+            revalidate,
+            file: page_file(path),
+            data: None,
+            size: 0,
+        };
+        let rules = cache_rules(&[page("/about", None), page("/news", Some(120))]);
+        assert!(rules.contains("/about\n  Cache-Control: public, max-age=0, must-revalidate\n"));
+        assert!(rules.contains("/news\n  Cache-Control: public, max-age=0, s-maxage=120,"));
+    }
+}
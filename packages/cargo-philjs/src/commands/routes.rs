@@ -0,0 +1,729 @@
+//! Route inspection
+//!
+//! Reads `#[route]`, `#[layout]` and `#[api]` attributes from the source
+//! tree and prints the route tree as the macros resolve it: default paths,
+//! SSR modes, guards and the layout each page renders in. Routes that
+//! match the same URLs are reported as conflicts, and the command fails so
+//! CI catches them.
+//!
+//! ```bash
+//! cargo philjs routes
+//! cargo philjs routes --json | jq '.conflicts'
+//! ```
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+use crate::utils::project_root;
+
+/// Revalidation interval `#[route(ssr = "isr")]` uses when none is given
+pub(crate) const DEFAULT_REVALIDATE: u32 = 60;
+
+const SSR_MODES: &[&str] = &["ssr", "csr", "ssg", "isr"];
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum RouteKind {
+    Page,
+    Layout,
+    Api,
+}
+
+/// One annotated function
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Route {
+    pub(crate) kind: RouteKind,
+    pub(crate) path: String,
+    /// Function name
+    pub(crate) component: String,
+    pub(crate) file: String,
+    pub(crate) line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ssr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) revalidate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) guard: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) title: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) preload: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) parent: Option<String>,
+}
+
+impl Route {
+    fn location(&self) -> String {
+        format!("{}:{}", self.file, self.line)
+    }
+}
+
+/// A page or layout and the routes rendered inside it
+#[derive(Debug, Serialize)]
+struct RouteNode {
+    #[serde(flatten)]
+    route: Route,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<RouteNode>,
+}
+
+/// Routes that match the same URLs
+#[derive(Debug, Serialize)]
+struct Conflict {
+    path: String,
+    routes: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct RouteReport {
+    routes: Vec<RouteNode>,
+    api: Vec<Route>,
+    conflicts: Vec<Conflict>,
+    warnings: Vec<String>,
+}
+
+// ============================================================================
+// Main Entry Point
+// ============================================================================
+
+/// Every annotated function under `root/src`, with files that failed to parse
+pub(crate) fn discover(root: &Path, src: &str) -> Result<(Vec<Route>, Vec<String>)> {
+    let src_dir = root.join(src);
+    if !src_dir.is_dir() {
+        bail!("Source directory {} not found", src_dir.display());
+    }
+
+    let mut warnings = Vec::new();
+    let mut routes = Vec::new();
+    for path in rust_files(&src_dir) {
+        let display = path.strip_prefix(root).unwrap_or(&path).display().to_string();
+        let source = fs::read_to_string(&path)?;
+        match syn::parse_file(&source) {
+            Ok(file) => {
+                let mut collector = RouteCollector {
+                    file: display,
+                    routes: &mut routes,
+                    warnings: &mut warnings,
+                };
+                collector.visit_file(&file);
+            }
+            Err(e) => warnings.push(format!("{}: skipped, failed to parse: {}", display, e)),
+        }
+    }
+    Ok((routes, warnings))
+}
+
+/// Print the route tree
+pub fn run(src: &str, json: bool) -> Result<()> {
+    let root = project_root().context("Not inside a Cargo project")?;
+    let (routes, warnings) = discover(&root, src)?;
+
+    let report = build_report(routes, warnings);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    if !report.conflicts.is_empty() {
+        bail!("{} route conflict(s) found", report.conflicts.len());
+    }
+    Ok(())
+}
+
+pub(crate) fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|ext| ext == "rs"))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// An attribute argument: a bare string literal or `name`, `name = value`
+enum AttrArg {
+    Lit(syn::LitStr),
+    Meta(Box<syn::Meta>),
+}
+
+impl Parse for AttrArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitStr) {
+            input.parse().map(AttrArg::Lit)
+        } else {
+            input.parse().map(|meta| AttrArg::Meta(Box::new(meta)))
+        }
+    }
+}
+
+struct RouteCollector<'a> {
+    file: String,
+    routes: &'a mut Vec<Route>,
+    warnings: &'a mut Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for RouteCollector<'_> {
+    fn visit_item_fn(&mut self, item: &'ast syn::ItemFn) {
+        for attr in &item.attrs {
+            let Some(kind) = route_kind(attr) else {
+                continue;
+            };
+            match parse_route(kind, attr, &item.sig.ident, &self.file) {
+                Ok(route) => self.routes.push(route),
+                Err(e) => self.warnings.push(format!(
+                    "{}:{}: invalid #[{}] on {}: {}",
+                    self.file,
+                    attr.span().start().line,
+                    attr_name(attr),
+                    item.sig.ident,
+                    e
+                )),
+            }
+        }
+        syn::visit::visit_item_fn(self, item);
+    }
+}
+
+fn attr_name(attr: &syn::Attribute) -> String {
+    attr.path()
+        .segments
+        .last()
+        .map(|s| s.ident.to_string())
+        .unwrap_or_default()
+}
+
+/// `#[route]`, `#[philjs::route]` and so on
+fn route_kind(attr: &syn::Attribute) -> Option<RouteKind> {
+    match attr_name(attr).as_str() {
+        "route" => Some(RouteKind::Page),
+        "layout" => Some(RouteKind::Layout),
+        "api" => Some(RouteKind::Api),
+        _ => None,
+    }
+}
+
+fn parse_args(attr: &syn::Attribute) -> syn::Result<Vec<AttrArg>> {
+    match &attr.meta {
+        syn::Meta::List(list) => Ok(list
+            .parse_args_with(Punctuated::<AttrArg, syn::Token![,]>::parse_terminated)?
+            .into_iter()
+            .collect()),
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn lit_string(expr: &syn::Expr) -> syn::Result<String> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Ok(s.value()),
+        syn::Expr::Path(p) => Ok(quote_path(&p.path)),
+        other => Err(syn::Error::new(other.span(), "expected a string")),
+    }
+}
+
+fn quote_path(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Resolve a route the way the attribute macros do
+fn parse_route(kind: RouteKind, attr: &syn::Attribute, ident: &syn::Ident, file: &str) -> syn::Result<Route> {
+    let name = ident.to_string();
+    let mut route = Route {
+        kind,
+        path: String::new(),
+        component: name.clone(),
+        file: file.to_string(),
+        line: ident.span().start().line,
+        method: None,
+        ssr: None,
+        revalidate: None,
+        guard: None,
+        title: None,
+        preload: false,
+        parent: None,
+    };
+    let args = parse_args(attr)?;
+
+    match kind {
+        RouteKind::Layout => {
+            route.path = args
+                .iter()
+                .find_map(|arg| match arg {
+                    AttrArg::Lit(lit) => Some(lit.value()),
+                    _ => None,
+                })
+                .unwrap_or_else(|| "/".to_string());
+        }
+        RouteKind::Api => {
+            // `#[api(POST)]`; the path always comes from the function name
+            let method = match args.as_slice() {
+                [AttrArg::Meta(meta)] => meta
+                    .require_path_only()
+                    .ok()
+                    .and_then(|path| path.get_ident())
+                    .map(|i| i.to_string().to_uppercase()),
+                _ => None,
+            };
+            route.method = Some(method.unwrap_or_else(|| "GET".to_string()));
+            route.path = format!("/api/{}", name.replace('_', "-"));
+        }
+        RouteKind::Page => {
+            let mut path = None;
+            for arg in &args {
+                let meta = match arg {
+                    AttrArg::Lit(lit) => {
+                        path = Some(lit.value());
+                        continue;
+                    }
+                    AttrArg::Meta(meta) => meta.as_ref(),
+                };
+                match meta {
+                    syn::Meta::Path(flag) => {
+                        if flag.is_ident("preload") {
+                            route.preload = true;
+                        }
+                    }
+                    syn::Meta::NameValue(nv) => {
+                        let key = quote_path(&nv.path);
+                        match key.as_str() {
+                            "path" => path = Some(lit_string(&nv.value)?),
+                            "ssr" => route.ssr = Some(lit_string(&nv.value)?),
+                            "guard" => route.guard = Some(lit_string(&nv.value)?),
+                            "title" => route.title = Some(lit_string(&nv.value)?),
+                            "parent" => route.parent = Some(lit_string(&nv.value)?),
+                            "method" => route.method = Some(lit_string(&nv.value)?.to_uppercase()),
+                            "preload" => route.preload = lit_bool(&nv.value)?,
+                            "revalidate" => route.revalidate = Some(lit_int(&nv.value)?),
+                            _ => {}
+                        }
+                    }
+                    syn::Meta::List(_) => {}
+                }
+            }
+            route.path = path.unwrap_or_else(|| format!("/{}", name.replace('_', "-")));
+            if route.ssr.is_none() {
+                route.ssr = Some("ssr".to_string());
+            }
+            if route.ssr.as_deref() == Some("isr") && route.revalidate.is_none() {
+                route.revalidate = Some(DEFAULT_REVALIDATE);
+            }
+        }
+    }
+    Ok(route)
+}
+
+fn lit_bool(expr: &syn::Expr) -> syn::Result<bool> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Bool(b), .. }) => Ok(b.value),
+        other => Err(syn::Error::new(other.span(), "expected true or false")),
+    }
+}
+
+fn lit_int(expr: &syn::Expr) -> syn::Result<u32> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(i), .. }) => i.base10_parse(),
+        other => Err(syn::Error::new(other.span(), "expected an integer")),
+    }
+}
+
+// ============================================================================
+// Analysis
+// ============================================================================
+
+/// Path segments
+fn segments(path: &str) -> Vec<&str> {
+    path.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+/// The URL shapes a path matches, with parameter names erased
+///
+/// `/users/:id?` matches both `/users` and `/users/:`.
+fn patterns(path: &str) -> Vec<String> {
+    let mut patterns = vec![String::new()];
+    for segment in segments(path) {
+        if let Some(rest) = segment.strip_prefix(':') {
+            if rest.ends_with('?') {
+                let with: Vec<String> = patterns.iter().map(|p| format!("{}/:", p)).collect();
+                patterns.extend(with);
+                continue;
+            }
+            patterns.iter_mut().for_each(|p| p.push_str("/:"));
+        } else if segment.starts_with('*') {
+            patterns.iter_mut().for_each(|p| p.push_str("/*"));
+        } else {
+            patterns.iter_mut().for_each(|p| {
+                p.push('/');
+                p.push_str(segment);
+            });
+        }
+    }
+    for pattern in &mut patterns {
+        if pattern.is_empty() {
+            pattern.push('/');
+        }
+    }
+    patterns
+}
+
+/// Whether `layout` wraps `path`, segment by segment
+fn is_under(path: &str, layout: &str) -> bool {
+    let path = segments(path);
+    let layout = segments(layout);
+    path.len() >= layout.len() && path.iter().zip(&layout).all(|(a, b)| a == b)
+}
+
+/// Routes that match the same URLs, keyed by pattern
+fn find_conflicts(routes: &[Route]) -> Vec<Conflict> {
+    let mut by_pattern: BTreeMap<String, Vec<&Route>> = BTreeMap::new();
+    for route in routes {
+        let key_prefix = match route.kind {
+            RouteKind::Layout => "layout ".to_string(),
+            // Pages answer GET, so they collide with GET API routes
+            RouteKind::Page => "GET ".to_string(),
+            RouteKind::Api => format!("{} ", route.method.as_deref().unwrap_or("GET")),
+        };
+        for pattern in patterns(&route.path) {
+            let entry = by_pattern.entry(format!("{}{}", key_prefix, pattern)).or_default();
+            if !entry.iter().any(|r| r.component == route.component && r.file == route.file) {
+                entry.push(route);
+            }
+        }
+    }
+
+    let mut conflicts: Vec<Conflict> = Vec::new();
+    for (key, found) in by_pattern {
+        if found.len() < 2 {
+            continue;
+        }
+        let routes: Vec<String> = found
+            .iter()
+            .map(|r| format!("{} {} ({})", r.component, r.path, r.location()))
+            .collect();
+        // An optional parameter reports the same pair twice
+        if conflicts.iter().any(|c| c.routes == routes) {
+            continue;
+        }
+        let path = key.split_once(' ').map_or(key.as_str(), |(_, p)| p).to_string();
+        conflicts.push(Conflict { path, routes });
+    }
+    conflicts
+}
+
+fn check_route(route: &Route, layouts: &[&Route], warnings: &mut Vec<String>) {
+    let at = route.location();
+    if let Some(ssr) = route.ssr.as_deref() {
+        if !SSR_MODES.contains(&ssr) {
+            warnings.push(format!(
+                "{}: {} has unknown ssr mode \"{}\" (expected {}); it renders as ssr",
+                at,
+                route.component,
+                ssr,
+                SSR_MODES.join(", ")
+            ));
+        } else if ssr != "isr" && route.revalidate.is_some() {
+            warnings.push(format!(
+                "{}: {} sets revalidate but ssr = \"{}\"; revalidate only applies to isr",
+                at, route.component, ssr
+            ));
+        }
+    }
+    if let Some(parent) = &route.parent {
+        if !layouts.iter().any(|l| &l.path == parent || &l.component == parent) {
+            warnings.push(format!("{}: {} names unknown parent layout \"{}\"", at, route.component, parent));
+        }
+    }
+}
+
+/// The layout `route` renders in: its `parent`, or the deepest layout
+/// whose path contains it
+fn parent_layout<'a>(route: &Route, layouts: &[&'a Route]) -> Option<&'a Route> {
+    if let Some(parent) = &route.parent {
+        if let Some(layout) = layouts.iter().find(|l| &l.path == parent || &l.component == parent) {
+            return Some(*layout);
+        }
+    }
+    layouts
+        .iter()
+        .filter(|l| {
+            !(l.component == route.component && l.file == route.file)
+                && is_under(&route.path, &l.path)
+                // A layout sits under a strictly shorter layout
+                && (route.kind != RouteKind::Layout || segments(&l.path).len() < segments(&route.path).len())
+        })
+        .max_by_key(|l| segments(&l.path).len())
+        .copied()
+}
+
+fn build_report(routes: Vec<Route>, mut warnings: Vec<String>) -> RouteReport {
+    let conflicts = find_conflicts(&routes);
+
+    let (mut api, mut views): (Vec<Route>, Vec<Route>) = routes.into_iter().partition(|r| r.kind == RouteKind::Api);
+    api.sort_by(|a, b| (&a.path, &a.method).cmp(&(&b.path, &b.method)));
+    views.sort_by(|a, b| {
+        (&a.path, a.kind != RouteKind::Layout).cmp(&(&b.path, b.kind != RouteKind::Layout))
+    });
+
+    let layouts: Vec<&Route> = views.iter().filter(|r| r.kind == RouteKind::Layout).collect();
+    for route in &views {
+        check_route(route, &layouts, &mut warnings);
+    }
+
+    // Index of each view's parent layout among `views`
+    let parents: Vec<Option<usize>> = views
+        .iter()
+        .map(|route| {
+            parent_layout(route, &layouts).and_then(|layout| views.iter().position(|v| std::ptr::eq(v, layout)))
+        })
+        .collect();
+
+    fn build(index: Option<usize>, views: &[Route], parents: &[Option<usize>]) -> Vec<RouteNode> {
+        (0..views.len())
+            .filter(|&i| parents[i] == index)
+            .map(|i| RouteNode {
+                route: views[i].clone(),
+                children: build(Some(i), views, parents),
+            })
+            .collect()
+    }
+
+    RouteReport {
+        routes: build(None, &views, &parents),
+        api,
+        conflicts,
+        warnings,
+    }
+}
+
+// ============================================================================
+// Output
+// ============================================================================
+
+fn count(nodes: &[RouteNode], kind: RouteKind) -> usize {
+    nodes
+        .iter()
+        .map(|n| usize::from(n.route.kind == kind) + count(&n.children, kind))
+        .sum()
+}
+
+fn describe(route: &Route) -> String {
+    let mut parts = Vec::new();
+    match route.kind {
+        RouteKind::Layout => parts.push("layout".magenta().to_string()),
+        _ => {
+            let mode = route.ssr.as_deref().unwrap_or("ssr");
+            let mode = match route.revalidate {
+                Some(seconds) if mode == "isr" => format!("isr {}s", seconds),
+                _ => mode.to_string(),
+            };
+            parts.push(mode.cyan().to_string());
+        }
+    }
+    if let Some(guard) = &route.guard {
+        parts.push(format!("guard: {}", guard).yellow().to_string());
+    }
+    if route.preload {
+        parts.push("preload".dimmed().to_string());
+    }
+    parts.join("  ")
+}
+
+fn print_nodes(nodes: &[RouteNode], prefix: &str, width: usize) {
+    for (i, node) in nodes.iter().enumerate() {
+        let last = i + 1 == nodes.len();
+        let branch = if last { "└── " } else { "├── " };
+        let label = format!("{}{}{}", prefix, branch, node.route.path);
+        println!(
+            "  {:<width$}  {}  {}  {}",
+            label,
+            format!("{:<24}", node.route.component).white().bold(),
+            describe(&node.route),
+            node.route.location().dimmed(),
+            width = width
+        );
+        let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        print_nodes(&node.children, &child_prefix, width);
+    }
+}
+
+fn label_width(nodes: &[RouteNode], depth: usize) -> usize {
+    nodes
+        .iter()
+        .map(|n| (depth * 4 + 4 + n.route.path.chars().count()).max(label_width(&n.children, depth + 1)))
+        .max()
+        .unwrap_or(0)
+}
+
+fn print_report(report: &RouteReport) {
+    println!(
+        "\n{}  Routes ({} pages, {} layouts, {} API)\n",
+        "[routes]".cyan().bold(),
+        count(&report.routes, RouteKind::Page),
+        count(&report.routes, RouteKind::Layout),
+        report.api.len()
+    );
+
+    if report.routes.is_empty() && report.api.is_empty() {
+        println!("  No #[route], #[layout] or #[api] functions found.\n");
+    }
+
+    print_nodes(&report.routes, "", label_width(&report.routes, 0));
+
+    if !report.api.is_empty() {
+        println!("\n  {}", "API".white().bold());
+        let width = report.api.iter().map(|r| r.path.len()).max().unwrap_or(0);
+        for route in &report.api {
+            println!(
+                "    {} {:<width$}  {}  {}",
+                format!("{:<7}", route.method.as_deref().unwrap_or("GET")).green(),
+                route.path,
+                format!("{:<24}", route.component).white().bold(),
+                route.location().dimmed(),
+                width = width
+            );
+        }
+    }
+    println!();
+
+    for warning in &report.warnings {
+        println!("  {}  {}", "[warn]".yellow().bold(), warning);
+    }
+    for conflict in &report.conflicts {
+        println!(
+            "  {}  {} is matched by more than one route:",
+            "[conflict]".red().bold(),
+            conflict.path
+        );
+        for route in &conflict.routes {
+            println!("               {}", route);
+        }
+    }
+    if !report.warnings.is_empty() || !report.conflicts.is_empty() {
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(source: &str) -> (Vec<Route>, Vec<String>) {
+        let file = syn::parse_file(source).unwrap();
+        let mut routes = Vec::new();
+        let mut warnings = Vec::new();
+        RouteCollector {
+            file: "src/lib.rs".to_string(),
+            routes: &mut routes,
+            warnings: &mut warnings,
+        }
+        .visit_file(&file);
+        (routes, warnings)
+    }
+
+    #[test]
+    fn test_parse_routes() {
+        let (routes, warnings) = collect(
+            r#"
+            #[layout("/dashboard")]
+            fn DashboardLayout(children: Children) -> impl IntoView {}
+
+            #[route("/dashboard/:id<i32>", ssr = "isr", guard = "require_auth", preload)]
+            fn Report(params: ReportParams) -> impl IntoView {}
+
+            #[route]
+            fn about_us() -> impl IntoView {}
+
+            #[api(POST)]
+            async fn create_user(req: Request) -> Response {}
+            "#,
+        );
+        assert!(warnings.is_empty());
+        assert_eq!(routes.len(), 4);
+
+        assert_eq!(routes[0].kind, RouteKind::Layout);
+        assert_eq!(routes[0].path, "/dashboard");
+
+        assert_eq!(routes[1].path, "/dashboard/:id<i32>");
+        assert_eq!(routes[1].ssr.as_deref(), Some("isr"));
+        assert_eq!(routes[1].revalidate, Some(DEFAULT_REVALIDATE));
+        assert_eq!(routes[1].guard.as_deref(), Some("require_auth"));
+        assert!(routes[1].preload);
+
+        assert_eq!(routes[2].path, "/about-us");
+        assert_eq!(routes[2].ssr.as_deref(), Some("ssr"));
+
+        assert_eq!(routes[3].method.as_deref(), Some("POST"));
+        assert_eq!(routes[3].path, "/api/create-user");
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert_eq!(patterns("/"), ["/"]);
+        assert_eq!(patterns("/users/:id<i32>"), ["/users/:"]);
+        assert_eq!(patterns("/users/:id?"), ["/users", "/users/:"]);
+        assert_eq!(patterns("/files/*path"), ["/files/*"]);
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let (routes, _) = collect(
+            r#"
+            #[route("/users/:id")]
+            fn User() {}
+            #[route("/users/:name")]
+            fn Profile() {}
+            #[route("/users/new")]
+            fn NewUser() {}
+            "#,
+        );
+        let conflicts = find_conflicts(&routes);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].path, "/users/:");
+        assert_eq!(conflicts[0].routes.len(), 2);
+    }
+
+    #[test]
+    fn test_tree() {
+        let (routes, _) = collect(
+            r#"
+            #[layout]
+            fn Root() {}
+            #[layout("/dashboard")]
+            fn Dashboard() {}
+            #[route("/")]
+            fn Home() {}
+            #[route("/dashboard/stats")]
+            fn Stats() {}
+            "#,
+        );
+        let report = build_report(routes, Vec::new());
+        assert_eq!(report.routes.len(), 1);
+        let root = &report.routes[0];
+        assert_eq!(root.route.component, "Root");
+        let children: Vec<_> = root.children.iter().map(|n| n.route.component.as_str()).collect();
+        assert_eq!(children, ["Home", "Dashboard"]);
+        assert_eq!(root.children[1].children[0].route.component, "Stats");
+    }
+}
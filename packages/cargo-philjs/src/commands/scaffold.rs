@@ -0,0 +1,431 @@
+//! Project templates and post-generation wiring for `cargo philjs generate`
+//!
+//! Templates live in `.philjs/templates/<kind>/`. Every file in the
+//! directory is copied into the project with placeholders filled in, in
+//! both file contents and paths:
+//!
+//! | Placeholder      | `UserCard`   |
+//! |------------------|--------------|
+//! | `{{name}}`       | `UserCard`   |
+//! | `{{snake_name}}` | `user_card`  |
+//! | `{{kebab_name}}` | `user-card`  |
+//! | `{{route}}`      | `/user-card` |
+//!
+//! A directory named after a built-in kind (`component`, `page`, `server`,
+//! `api`, `store`, `hook`) replaces that scaffold; any other name adds a
+//! kind, e.g. `.philjs/templates/widget/` for `cargo philjs generate widget`.
+//!
+//! New pages are registered with the router: appended to the project's
+//! `routes![...]` list if it has one, otherwise given a `#[route]` attribute
+//! so they register themselves.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::to_snake_case;
+
+/// Where project templates are looked up, relative to the project root
+pub const TEMPLATES_DIR: &str = ".philjs/templates";
+
+/// Kinds with a built-in scaffold
+pub const BUILTIN_KINDS: &[&str] = &["component", "page", "server", "api", "store", "hook"];
+
+/// Files checked, in order, for a `routes![...]` list
+const ROUTER_FILES: &[&str] = &[
+    "src/router.rs",
+    "src/routes.rs",
+    "src/app.rs",
+    "src/components/app.rs",
+    "src/lib.rs",
+    "src/main.rs",
+];
+
+/// Name forms available to templates
+#[derive(Debug, Clone)]
+pub struct Names {
+    pub name: String,
+    pub snake_name: String,
+    pub kebab_name: String,
+    pub route: String,
+}
+
+impl Names {
+    pub fn new(name: &str, route: Option<&str>) -> Self {
+        let snake_name = to_snake_case(name);
+        let kebab_name = snake_name.replace('_', "-");
+        let route = route
+            .map(normalize_route)
+            .unwrap_or_else(|| format!("/{}", kebab_name));
+        Self {
+            name: name.to_string(),
+            snake_name,
+            kebab_name,
+            route,
+        }
+    }
+
+    fn vars(&self) -> [(&'static str, &str); 4] {
+        [
+            ("name", &self.name),
+            ("snake_name", &self.snake_name),
+            ("kebab_name", &self.kebab_name),
+            ("route", &self.route),
+        ]
+    }
+}
+
+fn normalize_route(route: &str) -> String {
+    let route = route.trim();
+    if route.starts_with('/') {
+        route.to_string()
+    } else {
+        format!("/{}", route)
+    }
+}
+
+/// Template directory for `kind`, if the project has one
+pub fn template_dir(root: &Path, kind: &str) -> Option<PathBuf> {
+    let dir = root.join(TEMPLATES_DIR).join(kind);
+    dir.is_dir().then_some(dir)
+}
+
+/// Names of all project templates, sorted
+pub fn project_kinds(root: &Path) -> Vec<String> {
+    let mut kinds: Vec<String> = fs::read_dir(root.join(TEMPLATES_DIR))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    kinds.sort();
+    kinds
+}
+
+/// Fill `{{placeholder}}`s; unknown ones are left as they are
+pub fn render(text: &str, names: &Names) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let key = after[..end].trim();
+        match names.vars().iter().find(|(k, _)| *k == key) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Render the template for `kind` into `root`, returning the files written
+///
+/// Existing files are never overwritten. Generated `.rs` files are added to
+/// their directory's `mod.rs` when there is one.
+pub fn generate(root: &Path, kind: &str, names: &Names) -> Result<Vec<PathBuf>> {
+    let dir = template_dir(root, kind)
+        .with_context(|| format!("No template at {}/{}", TEMPLATES_DIR, kind))?;
+
+    let mut written = Vec::new();
+    for entry in walkdir::WalkDir::new(&dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(&dir)?;
+        let target = root.join(render(&relative.to_string_lossy(), names));
+        if target.exists() {
+            println!(
+                "  {}  {} already exists, skipped",
+                "[warn]".yellow().bold(),
+                target.display()
+            );
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let bytes = fs::read(entry.path())?;
+        match String::from_utf8(bytes) {
+            Ok(text) => fs::write(&target, render(&text, names))?,
+            Err(err) => fs::write(&target, err.into_bytes())?,
+        }
+
+        if target.extension().is_some_and(|ext| ext == "rs") {
+            let stem = target.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+            if !matches!(stem, "mod" | "lib" | "main") {
+                if let Some(parent) = target.parent() {
+                    declare_module(parent, stem, false)?;
+                }
+            }
+        }
+        written.push(target);
+    }
+    Ok(written)
+}
+
+/// Add `pub mod <module>;` to `dir/mod.rs`, creating the file if `create` is set
+pub fn declare_module(dir: &Path, module: &str, create: bool) -> Result<()> {
+    let mod_path = dir.join("mod.rs");
+    if !mod_path.exists() {
+        if create {
+            fs::write(&mod_path, format!("pub mod {};\n", module))?;
+        }
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(&mod_path)?;
+    if !declares_module(&content, module) {
+        let content = content.trim_end();
+        let separator = if content.is_empty() { "" } else { "\n" };
+        fs::write(&mod_path, format!("{}{}pub mod {};\n", content, separator, module))?;
+    }
+    Ok(())
+}
+
+fn declares_module(source: &str, module: &str) -> bool {
+    source.lines().any(|line| {
+        let line = line.trim();
+        let line = line
+            .strip_prefix("pub(crate) ")
+            .or_else(|| line.strip_prefix("pub "))
+            .unwrap_or(line);
+        line == format!("mod {};", module)
+    })
+}
+
+/// Make sure the crate root declares `mod <module>;`
+///
+/// Returns the file changed, if any.
+pub fn declare_in_crate_root(root: &Path, module: &str) -> Result<Option<PathBuf>> {
+    let Some(crate_root) = ["src/lib.rs", "src/main.rs"]
+        .iter()
+        .map(|file| root.join(file))
+        .find(|path| path.exists())
+    else {
+        return Ok(None);
+    };
+
+    let source = fs::read_to_string(&crate_root)?;
+    match insert_mod_declaration(&source, module) {
+        Some(updated) => {
+            fs::write(&crate_root, updated)?;
+            Ok(Some(crate_root))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Insert `mod <module>;` after the last top-level `mod` item, or after the
+/// crate docs if there is none
+fn insert_mod_declaration(source: &str, module: &str) -> Option<String> {
+    if declares_module(source, module) {
+        return None;
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let is_mod = |line: &str| {
+        let line = line.strip_prefix("pub ").unwrap_or(line);
+        line.starts_with("mod ") && line.ends_with(';')
+    };
+    let at = match lines.iter().rposition(|line| is_mod(line)) {
+        Some(last) => last + 1,
+        None => lines
+            .iter()
+            .take_while(|line| line.starts_with("//!") || line.starts_with("#!["))
+            .count(),
+    };
+
+    let mut out: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    let declaration = format!("mod {};", module);
+    if at > 0 && !is_mod(lines[at - 1]) {
+        // Keep a blank line between the crate docs and the new item
+        out.insert(at, declaration);
+        out.insert(at, String::new());
+    } else {
+        out.insert(at, declaration);
+    }
+    Some(out.join("\n") + "\n")
+}
+
+/// First file holding a `routes![...]` list
+pub fn find_router(root: &Path) -> Option<PathBuf> {
+    ROUTER_FILES
+        .iter()
+        .map(|file| root.join(file))
+        .find(|path| {
+            fs::read_to_string(path)
+                .map(|source| source.contains("routes!["))
+                .unwrap_or(false)
+        })
+}
+
+/// Append `(route => component)` to the `routes![...]` list in `router`
+///
+/// Returns `false` if the route was already listed.
+pub fn register_route(router: &Path, route: &str, module: &str, component: &str) -> Result<bool> {
+    let source = fs::read_to_string(router)?;
+    match insert_route(&source, route, module, component) {
+        Some(updated) => {
+            fs::write(router, updated)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// `routes!` only takes identifiers, so the component is imported as well
+fn insert_route(source: &str, route: &str, module: &str, component: &str) -> Option<String> {
+    let start = source.find("routes![")? + "routes![".len();
+    let end = start + matching_bracket(&source[start..])?;
+    let list = &source[start..end];
+    if list.contains(&format!("\"{}\"", route)) {
+        return None;
+    }
+
+    let entry = format!("(\"{}\" => {})", route, component);
+    let mut updated = String::with_capacity(source.len() + 128);
+    updated.push_str(&source[..start]);
+    if list.contains('\n') {
+        // One entry per line: match the last entry's indentation
+        let body = list.trim_end();
+        let indent = body
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .map(|line| &line[..line.len() - line.trim_start().len()])
+            .unwrap_or("    ");
+        updated.push_str(body);
+        if !body.trim().is_empty() && !body.ends_with(',') {
+            updated.push(',');
+        }
+        updated.push('\n');
+        updated.push_str(indent);
+        updated.push_str(&entry);
+        updated.push(',');
+        updated.push_str(&list[body.len()..]);
+    } else {
+        let body = list.trim().trim_end_matches(',');
+        if !body.is_empty() {
+            updated.push_str(body);
+            updated.push_str(", ");
+        }
+        updated.push_str(&entry);
+    }
+    updated.push_str(&source[end..]);
+
+    let import = format!("use crate::{}::{};", module, component);
+    if !updated.contains(&import) {
+        updated = insert_use(&updated, &import);
+    }
+    Some(updated)
+}
+
+/// Offset of the `]` closing a list whose `[` was just consumed
+fn matching_bracket(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '[' | '(' | '{' => depth += 1,
+            ']' if depth == 0 => return Some(i),
+            ']' | ')' | '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Insert a `use` item after the last top-level one
+fn insert_use(source: &str, import: &str) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let at = match lines.iter().rposition(|line| line.starts_with("use ")) {
+        // A grouped import may span several lines
+        Some(last) => (last..lines.len())
+            .find(|&i| lines[i].trim_end().ends_with(';'))
+            .map_or(last + 1, |i| i + 1),
+        None => lines
+            .iter()
+            .take_while(|line| {
+                line.starts_with("//!")
+                    || line.starts_with("#![")
+                    || line.trim().is_empty()
+                    || (line.starts_with("mod ") || line.starts_with("pub mod "))
+            })
+            .count(),
+    };
+
+    let mut out: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    out.insert(at, import.to_string());
+    out.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_placeholders() {
+        let names = Names::new("UserCard", None);
+        assert_eq!(
+            render("{{name}} {{ snake_name }} {{kebab_name}} {{route}} {{other}}", &names),
+            "UserCard user_card user-card /user-card {{other}}"
+        );
+        assert_eq!(Names::new("About", Some("info/about")).route, "/info/about");
+    }
+
+    #[test]
+    fn test_insert_mod_declaration() {
+        let source = "//! App\n\nmod components;\n\nuse philjs::prelude::*;\n";
+        let updated = insert_mod_declaration(source, "pages").unwrap();
+        assert_eq!(updated, "//! App\n\nmod components;\nmod pages;\n\nuse philjs::prelude::*;\n");
+        assert!(insert_mod_declaration(&updated, "pages").is_none());
+
+        let updated = insert_mod_declaration("//! App\nfn main() {}\n", "pages").unwrap();
+        assert_eq!(updated, "//! App\n\nmod pages;\nfn main() {}\n");
+    }
+
+    #[test]
+    fn test_insert_route_multiline() {
+        let source = r#"use philjs::prelude::*;
+use crate::pages::home::HomePage;
+
+pub fn app_routes() -> Vec<Route> {
+    routes![
+        ("/" => HomePage),
+    ]
+}
+"#;
+        let updated = insert_route(source, "/about", "pages::about", "AboutPage").unwrap();
+        assert!(updated.contains("        (\"/\" => HomePage),\n        (\"/about\" => AboutPage),\n    ]"));
+        assert!(updated.contains("use crate::pages::home::HomePage;\nuse crate::pages::about::AboutPage;\n"));
+        assert!(insert_route(&updated, "/about", "pages::about", "AboutPage").is_none());
+    }
+
+    #[test]
+    fn test_insert_route_inline() {
+        let source = "fn routes() -> Vec<Route> { routes![(\"/\" => HomePage)] }\n";
+        let updated = insert_route(source, "/about", "pages::about", "AboutPage").unwrap();
+        assert!(updated.contains("routes![(\"/\" => HomePage), (\"/about\" => AboutPage)]"));
+        assert!(updated.starts_with("use crate::pages::about::AboutPage;\n"));
+    }
+}
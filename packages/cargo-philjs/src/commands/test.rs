@@ -1,17 +1,68 @@
 //! Test commands
 //!
 //! Run tests for PhilJS projects with support for WASM testing.
+//!
+//! `--browser` runs two kinds of suite:
+//!
+//! - wasm-bindgen-test suites, in headless Chrome (or Firefox) via
+//!   `wasm-pack test`
+//! - end-to-end tests: integration tests named `e2e*` (`tests/e2e.rs`,
+//!   `tests/e2e_checkout.rs`, ...), run natively while the dev server and a
+//!   headless Chromium are up. They connect with any CDP client:
+//!
+//! ```rust,ignore
+//! let (browser, mut handler) =
+//!     Browser::connect(std::env::var("PHILJS_BROWSER_WS")?).await?;
+//! let page = browser.new_page(std::env::var("PHILJS_BASE_URL")?).await?;
+//! ```
+//!
+//! When a suite fails, its output, the browser console and screenshots of
+//! the pages still open are saved under `target/philjs-test/`.
+//!
+//! The dev server and watch mode use the `test` profile of `philjs.toml`.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use chromiumoxide::cdp::js_protocol::runtime::{EventConsoleApiCalled, EventExceptionThrown};
+use chromiumoxide::page::ScreenshotParams;
+use chromiumoxide::{Browser, BrowserConfig};
 use colored::Colorize;
+use futures::StreamExt;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use super::dev::TestServer;
+use crate::config::Config;
+
+/// Base URL of the dev server, for E2E tests
+const BASE_URL_VAR: &str = "PHILJS_BASE_URL";
+
+/// DevTools WebSocket of the browser, for E2E tests
+const BROWSER_WS_VAR: &str = "PHILJS_BROWSER_WS";
+
+/// Where a failing run's output goes; tests may save their own files there
+const ARTIFACTS_VAR: &str = "PHILJS_ARTIFACTS_DIR";
+
+const ARTIFACTS_DIR: &str = "target/philjs-test";
+
+/// Integration tests whose name starts with this are E2E tests
+const E2E_PREFIX: &str = "e2e";
 
 /// Run tests
-pub fn run(watch: bool, browser: bool, coverage: bool, pattern: Option<&str>) -> Result<()> {
+pub async fn run(
+    watch: bool,
+    browser: bool,
+    coverage: bool,
+    headed: bool,
+    pattern: Option<&str>,
+) -> Result<()> {
     println!("\n{}  Running tests...\n", "[test]".cyan().bold());
 
     if browser {
-        run_browser_tests(pattern)?;
+        run_browser_tests(watch, headed, pattern).await?;
     } else {
         run_cargo_tests(watch, coverage, pattern)?;
     }
@@ -110,9 +161,331 @@ fn run_regular_tests(pattern: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-/// Run browser/WASM tests
-fn run_browser_tests(pattern: Option<&str>) -> Result<()> {
-    // Check for wasm-pack
+// ============================================================================
+// Browser tests
+// ============================================================================
+
+/// The project's browser test suites
+#[derive(Debug, Default)]
+struct Suites {
+    /// Has wasm-bindgen-test as a dev-dependency
+    wasm: bool,
+    /// Integration tests that are not E2E tests, built for wasm
+    wasm_tests: Vec<String>,
+    e2e_tests: Vec<String>,
+}
+
+impl Suites {
+    fn discover() -> Result<Self> {
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .no_deps()
+            .exec()
+            .context("Failed to read Cargo metadata")?;
+        let package = metadata.root_package().context("No package in this directory")?;
+
+        let mut suites = Suites {
+            wasm: package.dependencies.iter().any(|d| d.name == "wasm-bindgen-test"),
+            ..Default::default()
+        };
+        for target in package.targets.iter().filter(|t| t.is_test()) {
+            if is_e2e(&target.name) {
+                suites.e2e_tests.push(target.name.clone());
+            } else {
+                suites.wasm_tests.push(target.name.clone());
+            }
+        }
+        Ok(suites)
+    }
+}
+
+fn is_e2e(test: &str) -> bool {
+    test.strip_prefix(E2E_PREFIX)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['_', '-']))
+}
+
+/// Browser `wasm-pack test` drives
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Driver {
+    Chrome,
+    Firefox,
+    Node,
+}
+
+impl Driver {
+    fn detect() -> Self {
+        if ["chromedriver", "chrome", "chromium", "google-chrome"].iter().any(|b| which::which(b).is_ok()) {
+            Driver::Chrome
+        } else if ["geckodriver", "firefox"].iter().any(|b| which::which(b).is_ok()) {
+            Driver::Firefox
+        } else {
+            Driver::Node
+        }
+    }
+}
+
+fn wasm_pack_args(driver: Driver, headed: bool, tests: &[String], pattern: Option<&str>) -> Vec<String> {
+    let mut args = vec!["test".to_string()];
+    match driver {
+        Driver::Chrome => args.push("--chrome".to_string()),
+        Driver::Firefox => args.push("--firefox".to_string()),
+        Driver::Node => args.push("--node".to_string()),
+    }
+    if !headed && driver != Driver::Node {
+        args.push("--headless".to_string());
+    }
+    // E2E tests are native, so the wasm build names every other target
+    args.extend(["--".to_string(), "--lib".to_string()]);
+    for test in tests {
+        args.extend(["--test".to_string(), test.clone()]);
+    }
+    args.extend(pattern.map(String::from));
+    args
+}
+
+/// Headless Chromium shared by the E2E tests, recording every page's console
+struct BrowserSession {
+    server: TestServer,
+    browser: Browser,
+    handler: tokio::task::JoinHandle<()>,
+    console: Arc<Mutex<Vec<String>>>,
+    watched: HashSet<String>,
+}
+
+impl BrowserSession {
+    async fn start(headed: bool) -> Result<Self> {
+        println!("{}  Building and starting the dev server...", "[test]".cyan().bold());
+        let server = TestServer::start().await?;
+
+        let mut config = BrowserConfig::builder();
+        if headed {
+            config = config.with_head();
+        }
+        let config = config.build().map_err(|e| anyhow::anyhow!("{}", e))?;
+        let (browser, mut handler) = Browser::launch(config)
+            .await
+            .context("Failed to launch Chromium (is Chrome or Chromium installed?)")?;
+        let handler = tokio::spawn(async move {
+            while let Some(event) = handler.next().await {
+                if event.is_err() {
+                    break;
+                }
+            }
+        });
+
+        println!("  Serving {}\n", server.url.cyan());
+        Ok(BrowserSession {
+            server,
+            browser,
+            handler,
+            console: Arc::default(),
+            watched: HashSet::new(),
+        })
+    }
+
+    /// Record the console of pages opened since the last call
+    async fn watch_new_pages(&mut self) {
+        let Ok(pages) = self.browser.pages().await else {
+            return;
+        };
+        for page in pages {
+            let id = format!("{:?}", page.target_id());
+            if !self.watched.insert(id) {
+                continue;
+            }
+            let url = page.url().await.ok().flatten().unwrap_or_default();
+
+            if let Ok(mut events) = page.event_listener::<EventConsoleApiCalled>().await {
+                let console = self.console.clone();
+                let url = url.clone();
+                tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        let parts: Vec<String> = event
+                            .args
+                            .iter()
+                            .map(|arg| match (&arg.value, &arg.description) {
+                                (Some(serde_json::Value::String(s)), _) => s.clone(),
+                                (Some(value), _) => value.to_string(),
+                                (None, Some(description)) => description.clone(),
+                                (None, None) => String::new(),
+                            })
+                            .collect();
+                        let level = format!("{:?}", event.r#type).to_lowercase();
+                        console.lock().unwrap().push(console_line(&url, &level, &parts));
+                    }
+                });
+            }
+            if let Ok(mut events) = page.event_listener::<EventExceptionThrown>().await {
+                let console = self.console.clone();
+                tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        let details = &event.exception_details;
+                        let text = details
+                            .exception
+                            .as_ref()
+                            .and_then(|e| e.description.clone())
+                            .unwrap_or_else(|| details.text.clone());
+                        console.lock().unwrap().push(console_line(&url, "exception", &[text]));
+                    }
+                });
+            }
+        }
+    }
+
+    /// Save the console and a screenshot of every open page
+    async fn save_artifacts(&self, dir: &Path) -> Result<()> {
+        let console = self.console.lock().unwrap().join("\n");
+        std::fs::write(dir.join("console.log"), console)?;
+
+        for (i, page) in self.browser.pages().await?.into_iter().enumerate() {
+            let params = ScreenshotParams::builder().full_page(true).build();
+            let path = dir.join(format!("page-{}.png", i + 1));
+            if page.save_screenshot(params, &path).await.is_ok() {
+                let url = page.url().await.ok().flatten().unwrap_or_default();
+                println!("    {}  {}", path.display().to_string().cyan(), url.dimmed());
+            }
+        }
+        Ok(())
+    }
+
+    async fn close(mut self) {
+        let _ = self.browser.close().await;
+        let _ = self.browser.wait().await;
+        self.handler.abort();
+    }
+}
+
+fn console_line(url: &str, level: &str, parts: &[String]) -> String {
+    format!("[{}] {}: {}", url, level, parts.join(" "))
+}
+
+/// Run every browser suite once; `Ok(false)` when one failed
+async fn run_browser_suites(
+    suites: &Suites,
+    session: Option<&mut BrowserSession>,
+    headed: bool,
+    pattern: Option<&str>,
+) -> Result<bool> {
+    let artifacts = artifacts_dir();
+    let mut passed = true;
+
+    if suites.wasm {
+        let driver = Driver::detect();
+        if driver == Driver::Node {
+            println!(
+                "{}  No browser found, running wasm tests in Node.js",
+                "[warn]".yellow().bold()
+            );
+        }
+        println!("{}  Running wasm-bindgen tests ({:?})...\n", "[test]".cyan().bold(), driver);
+        let mut command = tokio::process::Command::new("wasm-pack");
+        command.args(wasm_pack_args(driver, headed, &suites.wasm_tests, pattern));
+        let (ok, output) = run_captured(command, None).await?;
+        if !ok {
+            std::fs::create_dir_all(&artifacts)?;
+            std::fs::write(artifacts.join("wasm-bindgen-test.log"), output)?;
+            println!("\n{}  wasm-bindgen tests failed", "[fail]".red().bold());
+            println!("    {}", artifacts.join("wasm-bindgen-test.log").display().to_string().cyan());
+            passed = false;
+        }
+    }
+
+    if let Some(session) = session {
+        println!("\n{}  Running E2E tests...\n", "[test]".cyan().bold());
+        let mut command = tokio::process::Command::new("cargo");
+        command
+            .arg("test")
+            .env(BASE_URL_VAR, &session.server.url)
+            .env(BROWSER_WS_VAR, session.browser.websocket_address())
+            .env(ARTIFACTS_VAR, &artifacts);
+        for test in &suites.e2e_tests {
+            command.args(["--test", test]);
+        }
+        command.args(pattern);
+        session.console.lock().unwrap().clear();
+
+        let (ok, output) = run_captured(command, Some(&mut *session)).await?;
+        if !ok {
+            std::fs::create_dir_all(&artifacts)?;
+            std::fs::write(artifacts.join("e2e.log"), output)?;
+            println!("\n{}  E2E tests failed, saved:", "[fail]".red().bold());
+            println!("    {}", artifacts.join("e2e.log").display().to_string().cyan());
+            println!("    {}", artifacts.join("console.log").display().to_string().cyan());
+            session.save_artifacts(&artifacts).await?;
+            passed = false;
+        }
+    }
+
+    if passed {
+        println!("\n{}  Browser tests passed!\n", "[done]".green().bold());
+    }
+    Ok(passed)
+}
+
+/// Run a command, echoing and collecting its output; with a session, pages
+/// opened meanwhile are watched
+async fn run_captured(
+    mut command: tokio::process::Command,
+    mut session: Option<&mut BrowserSession>,
+) -> Result<(bool, String)> {
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to start test command")?;
+
+    let output = Arc::new(Mutex::new(String::new()));
+    let stdout = BufReader::new(child.stdout.take().context("no stdout")?);
+    let stderr = BufReader::new(child.stderr.take().context("no stderr")?);
+    let echo_stdout = tokio::spawn(echo_lines(stdout, output.clone(), false));
+    let echo_stderr = tokio::spawn(echo_lines(stderr, output.clone(), true));
+
+    let status = loop {
+        tokio::select! {
+            status = child.wait() => break status?,
+            _ = tokio::time::sleep(Duration::from_millis(200)) => {
+                if let Some(session) = session.as_deref_mut() {
+                    session.watch_new_pages().await;
+                }
+            }
+        }
+    };
+    let _ = echo_stdout.await;
+    let _ = echo_stderr.await;
+
+    let output = output.lock().unwrap().clone();
+    Ok((status.success(), output))
+}
+
+async fn echo_lines<R: tokio::io::AsyncRead + Unpin>(
+    reader: BufReader<R>,
+    output: Arc<Mutex<String>>,
+    stderr: bool,
+) {
+    let mut lines = reader.lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if stderr {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+        let mut output = output.lock().unwrap();
+        output.push_str(&line);
+        output.push('\n');
+    }
+}
+
+/// `target/philjs-test/<timestamp>`
+fn artifacts_dir() -> PathBuf {
+    let stamp: String = humantime::format_rfc3339_seconds(SystemTime::now())
+        .to_string()
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect();
+    Path::new(ARTIFACTS_DIR).join(stamp)
+}
+
+/// Run browser/WASM tests, re-running on changes with `--watch`
+async fn run_browser_tests(watch: bool, headed: bool, pattern: Option<&str>) -> Result<()> {
     if which::which("wasm-pack").is_err() {
         println!(
             "{}  Installing wasm-pack...",
@@ -124,36 +497,117 @@ fn run_browser_tests(pattern: Option<&str>) -> Result<()> {
             .context("Failed to install wasm-pack")?;
     }
 
-    println!("{}  Running browser tests...\n", "[test]".cyan().bold());
-
-    let mut args = vec!["test", "--headless"];
+    let suites = Suites::discover()?;
+    if !suites.wasm && suites.e2e_tests.is_empty() {
+        bail!(
+            "No browser tests found: add wasm-bindgen-test to [dev-dependencies] or an E2E test in tests/{}.rs",
+            E2E_PREFIX
+        );
+    }
 
-    // Try Chrome first, then Firefox
-    if which::which("chromedriver").is_ok() || which::which("chrome").is_ok() {
-        args.push("--chrome");
-        println!("  Using Chrome...");
-    } else if which::which("geckodriver").is_ok() || which::which("firefox").is_ok() {
-        args.push("--firefox");
-        println!("  Using Firefox...");
+    let mut session = if suites.e2e_tests.is_empty() {
+        None
     } else {
-        println!(
-            "{}  No browser found. Install Chrome or Firefox for browser tests.",
-            "[warn]".yellow().bold()
-        );
-        args.push("--node");
-        println!("  Falling back to Node.js...");
+        Some(BrowserSession::start(headed).await?)
+    };
+
+    let passed = run_browser_suites(&suites, session.as_mut(), headed, pattern).await;
+    if !watch {
+        if let Some(session) = session {
+            session.close().await;
+        }
+        if !passed? {
+            bail!("Browser tests failed");
+        }
+        return Ok(());
+    }
+    if let Err(e) = passed {
+        println!("{}  {:#}", "[error]".red().bold(), e);
     }
 
-    let status = Command::new("wasm-pack")
-        .args(&args)
-        .status()
-        .context("Failed to run wasm-pack test")?;
+    // Watch mode: rebuild and re-run on every change until Ctrl+C
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })?;
+    let dev = super::dev::DevConfig::from_project(&Config::load(Path::new("."), "test")?);
+    let ignore = dev.ignore_patterns;
+    for dir in dev.watch_dirs.iter().map(PathBuf::as_path).chain([Path::new("tests")]) {
+        if dir.exists() {
+            notify::Watcher::watch(&mut watcher, dir, notify::RecursiveMode::Recursive)?;
+        }
+    }
 
-    if status.success() {
-        println!("\n{}  Browser tests passed!\n", "[done]".green().bold());
-    } else {
-        println!("\n{}  Browser tests failed.\n", "[fail]".red().bold());
+    loop {
+        println!("{}  Watching for changes (Ctrl+C to stop)...\n", "[watch]".cyan().bold());
+        let changed = loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => break false,
+                Some(event) = rx.recv() => {
+                    if event.paths.iter().any(|p| p.extension().is_some_and(|e| e == "rs"))
+                        || super::dev::should_process_event(&event, &ignore)
+                    {
+                        break true;
+                    }
+                }
+            }
+        };
+        if !changed {
+            break;
+        }
+
+        // Let a burst of saves settle
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        while rx.try_recv().is_ok() {}
+
+        if let Some(session) = session.as_ref() {
+            if let Err(e) = session.server.rebuild().await {
+                println!("{}  {:#}\n", "[error]".red().bold(), e);
+                continue;
+            }
+        }
+        if let Err(e) = run_browser_suites(&suites, session.as_mut(), headed, pattern).await {
+            println!("{}  {:#}", "[error]".red().bold(), e);
+        }
     }
 
+    if let Some(session) = session {
+        session.close().await;
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_e2e() {
+        assert!(is_e2e("e2e"));
+        assert!(is_e2e("e2e_checkout"));
+        assert!(is_e2e("e2e-login"));
+        assert!(!is_e2e("e2eish"));
+        assert!(!is_e2e("integration"));
+    }
+
+    #[test]
+    fn test_wasm_pack_args() {
+        let args = wasm_pack_args(Driver::Chrome, false, &["web".to_string()], Some("counter"));
+        assert_eq!(
+            args,
+            ["test", "--chrome", "--headless", "--", "--lib", "--test", "web", "counter"]
+        );
+        let args = wasm_pack_args(Driver::Node, false, &[], None);
+        assert_eq!(args, ["test", "--node", "--", "--lib"]);
+    }
+
+    #[test]
+    fn test_console_line() {
+        assert_eq!(
+            console_line("http://127.0.0.1:4000/", "error", &["boom".to_string(), "42".to_string()]),
+            "[http://127.0.0.1:4000/] error: boom 42"
+        );
+    }
+}
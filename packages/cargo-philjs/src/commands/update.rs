@@ -1,27 +1,482 @@
 //! Update dependencies
 //!
-//! Updates PhilJS and related dependencies to the latest versions.
+//! Moves every philjs crate in the project's manifests to the same release.
+//! The crate versions that belong together come from the release matrix in
+//! `releases.toml`, embedded in the CLI, so core, macros and integrations
+//! never end up on mismatched versions:
+//!
+//! ```bash
+//! cargo philjs update             # newest release
+//! cargo philjs update --to 2.0    # newest 2.0.x
+//! cargo philjs update --check     # show the plan without changing anything
+//! ```
+//!
+//! Requirements are rewritten in place (formatting and features are kept),
+//! then `cargo update` refreshes `Cargo.lock`. Path, git and
+//! `workspace = true` dependencies are left alone. Migration notes are
+//! printed for every breaking release between the current and the target
+//! version.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use toml_edit::{DocumentMut, Item, TableLike};
+
+use super::doctor::is_philjs_crate;
+use crate::utils::project_root;
+
+/// Compatible crate versions per release
+const RELEASES: &str = include_str!("releases.toml");
+
+/// Dependency tables at the top level and under `[target.'cfg(..)']`
+const DEPENDENCY_TABLES: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Updated along with philjs unless `--all` updates everything
+const WASM_PACKAGES: &[&str] = &["wasm-bindgen", "wasm-bindgen-futures", "web-sys", "js-sys"];
+
+// ============================================================================
+// Release matrix
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct Matrix {
+    release: Vec<Release>,
+}
+
+/// A set of philjs crates released and tested together
+#[derive(Debug, Deserialize)]
+pub struct Release {
+    pub version: String,
+    #[serde(default)]
+    pub date: Option<String>,
+    /// Upgrading to this release needs changes to the project
+    #[serde(default)]
+    pub breaking: bool,
+    pub crates: BTreeMap<String, String>,
+    #[serde(default)]
+    pub migration: Vec<MigrationNote>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MigrationNote {
+    pub summary: String,
+    /// Only relevant to projects using one of these crates
+    #[serde(default)]
+    pub crates: Vec<String>,
+    #[serde(default)]
+    pub fix: Option<String>,
+}
+
+impl Release {
+    fn semver(&self) -> Version {
+        Version::parse(&self.version).expect("release versions are checked on load")
+    }
+
+    /// The release's version of `name`
+    fn crate_version(&self, name: &str) -> Option<Version> {
+        self.crates.get(name).and_then(|v| Version::parse(v).ok())
+    }
+}
+
+/// The embedded releases, oldest first
+fn releases() -> Result<Vec<Release>> {
+    let matrix: Matrix = toml::from_str(RELEASES).context("Invalid embedded releases.toml")?;
+    let mut releases = matrix.release;
+    for release in &releases {
+        Version::parse(&release.version)
+            .with_context(|| format!("Invalid release version `{}`", release.version))?;
+        for (name, version) in &release.crates {
+            Version::parse(version)
+                .with_context(|| format!("Invalid version `{}` of {} in {}", version, name, release.version))?;
+        }
+    }
+    releases.sort_by_key(Release::semver);
+    Ok(releases)
+}
+
+/// The newest release matching `target`; `2.0` means the newest 2.0.x
+fn select_release<'a>(releases: &'a [Release], target: Option<&str>) -> Result<&'a Release> {
+    let target = target.filter(|t| *t != "latest");
+    let Some(target) = target else {
+        return releases.last().context("No releases are known");
+    };
+    let requirement = if target.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("~{}", target)
+    } else {
+        target.to_string()
+    };
+    let requirement = VersionReq::parse(&requirement)
+        .with_context(|| format!("`{}` is not a version, e.g. 2.0 or 2.0.1", target))?;
+    releases
+        .iter()
+        .rev()
+        .find(|r| requirement.matches(&r.semver()))
+        .with_context(|| {
+            let known: Vec<&str> = releases.iter().map(|r| r.version.as_str()).collect();
+            format!("No PhilJS release matches `{}`; known releases: {}", target, known.join(", "))
+        })
+}
+
+// ============================================================================
+// Manifests
+// ============================================================================
+
+/// A philjs dependency found in a manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Dependency {
+    /// Table it was found in, e.g. `dev-dependencies`
+    table: String,
+    /// Crate name, after `package = "..."` renames
+    name: String,
+    requirement: Option<String>,
+    /// Why it can't be updated, e.g. a path dependency
+    skipped: Option<&'static str>,
+}
+
+/// Call `f` with the table name, key and item of every dependency
+fn visit_dependencies(doc: &mut DocumentMut, mut f: impl FnMut(&str, &str, &mut Item)) {
+    fn visit(table: &str, deps: &mut dyn TableLike, f: &mut impl FnMut(&str, &str, &mut Item)) {
+        for (key, item) in deps.iter_mut() {
+            f(table, key.get(), item);
+        }
+    }
+
+    for table in DEPENDENCY_TABLES {
+        if let Some(deps) = doc.get_mut(table).and_then(Item::as_table_like_mut) {
+            visit(table, deps, &mut f);
+        }
+    }
+    if let Some(targets) = doc.get_mut("target").and_then(Item::as_table_like_mut) {
+        for (target, item) in targets.iter_mut() {
+            let target = target.get().to_string();
+            for table in DEPENDENCY_TABLES {
+                if let Some(deps) = item.get_mut(table).and_then(Item::as_table_like_mut) {
+                    visit(&format!("target.{}.{}", target, table), deps, &mut f);
+                }
+            }
+        }
+    }
+    let workspace = doc.get_mut("workspace").and_then(|w| w.get_mut("dependencies"));
+    if let Some(deps) = workspace.and_then(Item::as_table_like_mut) {
+        visit("workspace.dependencies", deps, &mut f);
+    }
+}
+
+/// The crate a dependency entry refers to
+fn crate_name(key: &str, item: &Item) -> String {
+    item.get("package")
+        .and_then(Item::as_str)
+        .unwrap_or(key)
+        .to_string()
+}
+
+fn philjs_dependencies(doc: &mut DocumentMut) -> Vec<Dependency> {
+    let mut found = Vec::new();
+    visit_dependencies(doc, |table, key, item| {
+        let name = crate_name(key, item);
+        if !is_philjs_crate(&name) {
+            return;
+        }
+        let skipped = if item.get("workspace").and_then(Item::as_bool) == Some(true) {
+            Some("inherited from the workspace")
+        } else if item.get("path").is_some() {
+            Some("path dependency")
+        } else if item.get("git").is_some() {
+            Some("git dependency")
+        } else {
+            None
+        };
+        let requirement = item
+            .as_str()
+            .or_else(|| item.get("version").and_then(Item::as_str))
+            .map(str::to_string);
+        found.push(Dependency {
+            table: table.to_string(),
+            name,
+            requirement,
+            skipped,
+        });
+    });
+    found
+}
+
+/// Set the requirement of every updatable philjs dependency in `doc`
+fn apply_release(doc: &mut DocumentMut, release: &Release) {
+    visit_dependencies(doc, |_, key, item| {
+        let name = crate_name(key, item);
+        let Some(version) = release.crates.get(&name) else {
+            return;
+        };
+        if !is_philjs_crate(&name)
+            || item.get("workspace").is_some()
+            || item.get("path").is_some()
+            || item.get("git").is_some()
+        {
+            return;
+        }
+        let value = if item.is_str() {
+            item.as_value_mut()
+        } else {
+            item.get_mut("version").and_then(Item::as_value_mut)
+        };
+        match value {
+            Some(value) => {
+                let decor = value.decor().clone();
+                *value = version.as_str().into();
+                *value.decor_mut() = decor;
+            }
+            // A table without a version, e.g. only `features`
+            None => {
+                if let Some(table) = item.as_table_like_mut() {
+                    table.insert("version", toml_edit::value(version.as_str()));
+                }
+            }
+        }
+    });
+}
+
+/// The root manifest and those of the workspace members
+fn manifests(root: &Path) -> Vec<PathBuf> {
+    let mut manifests = vec![root.join("Cargo.toml")];
+    let metadata = cargo_metadata::MetadataCommand::new()
+        .current_dir(root)
+        .no_deps()
+        .exec();
+    if let Ok(metadata) = metadata {
+        for package in metadata.workspace_packages() {
+            let path = PathBuf::from(package.manifest_path.as_std_path());
+            if !manifests.contains(&path) {
+                manifests.push(path);
+            }
+        }
+    }
+    manifests
+}
+
+// ============================================================================
+// Planning
+// ============================================================================
+
+/// What updating one manifest changes
+struct ManifestPlan {
+    path: PathBuf,
+    doc: DocumentMut,
+    dependencies: Vec<Dependency>,
+}
+
+/// The oldest release any dependency is currently on
+fn current_release<'a>(releases: &'a [Release], deps: &[Dependency]) -> Option<&'a Release> {
+    deps.iter()
+        .filter(|d| d.skipped.is_none())
+        .filter_map(|d| {
+            let requirement = VersionReq::parse(d.requirement.as_deref()?).ok()?;
+            releases
+                .iter()
+                .rev()
+                .find(|r| r.crate_version(&d.name).is_some_and(|v| requirement.matches(&v)))
+        })
+        .min_by_key(|r| r.semver())
+}
+
+/// Dependencies the target release has no version of
+fn incompatible(release: &Release, deps: &[Dependency]) -> Vec<String> {
+    let mut missing: Vec<String> = deps
+        .iter()
+        .filter(|d| d.skipped.is_none() && !release.crates.contains_key(&d.name))
+        .map(|d| d.name.clone())
+        .collect();
+    missing.sort();
+    missing.dedup();
+    missing
+}
+
+/// Migration notes of breaking releases after `from` up to `to`, relevant
+/// to a project using `crates`
+fn migration_notes<'a>(
+    releases: &'a [Release],
+    from: Option<&Release>,
+    to: &Release,
+    crates: &[String],
+) -> Vec<(&'a Release, &'a MigrationNote)> {
+    let from = from.map(Release::semver);
+    let to = to.semver();
+    releases
+        .iter()
+        .filter(|r| r.breaking)
+        .filter(|r| from.as_ref().is_none_or(|from| r.semver() > *from) && r.semver() <= to)
+        .flat_map(|r| r.migration.iter().map(move |note| (r, note)))
+        .filter(|(_, note)| note.crates.is_empty() || note.crates.iter().any(|c| crates.contains(c)))
+        .collect()
+}
+
+// ============================================================================
+// Command
+// ============================================================================
 
 /// Run update command
-pub fn run(all: bool, check_only: bool) -> Result<()> {
+pub fn run(all: bool, check_only: bool, to: Option<&str>) -> Result<()> {
     println!("\n{}  Checking for updates...\n", "[update]".cyan().bold());
 
+    let root = project_root().context("Not inside a Cargo project")?;
+    let releases = releases()?;
+    let target = select_release(&releases, to)?;
+
+    let mut plans = Vec::new();
+    for path in manifests(&root) {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut doc: DocumentMut = content
+            .parse()
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        let dependencies = philjs_dependencies(&mut doc);
+        if !dependencies.is_empty() {
+            apply_release(&mut doc, target);
+            plans.push(ManifestPlan { path, doc, dependencies });
+        }
+    }
+
+    let all_deps: Vec<Dependency> = plans.iter().flat_map(|p| p.dependencies.clone()).collect();
+    if all_deps.is_empty() {
+        bail!("No philjs dependencies found; add one with `cargo add philjs`");
+    }
+    let missing = incompatible(target, &all_deps);
+    if !missing.is_empty() {
+        bail!(
+            "PhilJS {} has no compatible version of {}; remove {} or pin {} with a path dependency",
+            target.version,
+            missing.join(", "),
+            if missing.len() == 1 { "it" } else { "them" },
+            if missing.len() == 1 { "it" } else { "them" },
+        );
+    }
+
+    let current = current_release(&releases, &all_deps);
+    print_plan(&root, &plans, current, target);
+
+    let mut crates: Vec<String> = all_deps.iter().map(|d| d.name.clone()).collect();
+    crates.sort();
+    crates.dedup();
+
     if check_only {
-        check_updates(all)?;
+        print_migration_notes(&migration_notes(&releases, current, target, &crates));
+        if all {
+            check_outdated()?;
+        }
+        return Ok(());
+    }
+
+    for plan in &plans {
+        fs::write(&plan.path, plan.doc.to_string())
+            .with_context(|| format!("Failed to write {}", plan.path.display()))?;
+    }
+    refresh_lockfile(&root, all, &crates)?;
+    update_tools()?;
+
+    print_migration_notes(&migration_notes(&releases, current, target, &crates));
+    println!(
+        "\n{}  PhilJS dependencies are on {}\n",
+        "[done]".green().bold(),
+        target.version.cyan()
+    );
+
+    Ok(())
+}
+
+fn print_plan(root: &Path, plans: &[ManifestPlan], current: Option<&Release>, target: &Release) {
+    let from = current.map_or("an unknown release".to_string(), |r| r.version.clone());
+    let direction = match current.map(|r| r.semver().cmp(&target.semver())) {
+        Some(std::cmp::Ordering::Greater) => " (downgrade)".yellow().to_string(),
+        _ => String::new(),
+    };
+    println!(
+        "  PhilJS {} {} {}{}\n",
+        from,
+        "->".dimmed(),
+        target.version.cyan().bold(),
+        direction
+    );
+
+    for plan in plans {
+        let shown = plan.path.strip_prefix(root).unwrap_or(&plan.path);
+        println!("  {}", shown.display().to_string().white().bold());
+        for dep in &plan.dependencies {
+            let from = dep.requirement.as_deref().unwrap_or("*");
+            let line = match (dep.skipped, target.crates.get(&dep.name)) {
+                (Some(reason), _) => format!("{} ({}, skipped)", from, reason).dimmed().to_string(),
+                (None, Some(to)) if to == from => format!("{} (up to date)", from).dimmed().to_string(),
+                (None, Some(to)) => format!("{} {} {}", from, "->".dimmed(), to.green()),
+                (None, None) => from.to_string(),
+            };
+            let table = if dep.table == "dependencies" {
+                String::new()
+            } else {
+                format!(" [{}]", dep.table).dimmed().to_string()
+            };
+            println!("    {:<16} {}{}", dep.name, line, table);
+        }
+        println!();
+    }
+}
+
+fn print_migration_notes(notes: &[(&Release, &MigrationNote)]) {
+    let mut last = None;
+    for (release, note) in notes {
+        if last != Some(release.version.as_str()) {
+            let date = release.date.as_deref().map(|d| format!(" ({})", d)).unwrap_or_default();
+            println!(
+                "{}  Migrating to {}{}:",
+                "[notes]".yellow().bold(),
+                release.version.cyan(),
+                date
+            );
+            last = Some(release.version.as_str());
+        }
+        println!("  - {}", note.summary);
+        if let Some(fix) = &note.fix {
+            println!("    {} {}", "fix:".dimmed(), fix.cyan());
+        }
+    }
+    if !notes.is_empty() {
+        println!();
+    }
+}
+
+/// Bring `Cargo.lock` in line with the new requirements
+fn refresh_lockfile(root: &Path, all: bool, crates: &[String]) -> Result<()> {
+    let mut cmd = Command::new("cargo");
+    cmd.current_dir(root).arg("update");
+    if all {
+        println!("{}  Updating all dependencies...\n", "[update]".cyan().bold());
     } else {
-        perform_update(all)?;
+        println!("{}  Updating PhilJS dependencies...\n", "[update]".cyan().bold());
+        for package in crates.iter().map(String::as_str).chain(WASM_PACKAGES.iter().copied()) {
+            // Only packages in the lockfile can be named
+            let locked = Command::new("cargo")
+                .current_dir(root)
+                .args(["pkgid", package])
+                .output()
+                .is_ok_and(|o| o.status.success());
+            if locked {
+                cmd.args(["-p", package]);
+            }
+        }
     }
 
+    let status = cmd.status().context("Failed to run cargo update")?;
+    if !status.success() {
+        bail!("cargo update failed; the new requirements are in Cargo.toml");
+    }
+    println!();
     Ok(())
 }
 
-/// Check for available updates
-fn check_updates(all: bool) -> Result<()> {
-    // Check cargo-outdated
+/// Check every dependency with cargo-outdated
+fn check_outdated() -> Result<()> {
     if which::which("cargo-outdated").is_err() {
         println!(
             "{}  Installing cargo-outdated for update checks...",
@@ -33,15 +488,10 @@ fn check_updates(all: bool) -> Result<()> {
             .context("Failed to install cargo-outdated")?;
     }
 
-    let mut args = vec!["outdated"];
-    if !all {
-        args.extend(["--root-deps-only"]);
-    }
-
     println!("{}  Checking for outdated dependencies...\n", "[check]".cyan().bold());
 
     let output = Command::new("cargo")
-        .args(&args)
+        .args(["outdated", "--root-deps-only"])
         .output()
         .context("Failed to run cargo outdated")?;
 
@@ -57,7 +507,7 @@ fn check_updates(all: bool) -> Result<()> {
             println!();
             println!(
                 "  Run {} to update.",
-                "cargo philjs update".cyan()
+                "cargo philjs update --all".cyan()
             );
             println!();
         }
@@ -73,58 +523,6 @@ fn check_updates(all: bool) -> Result<()> {
     Ok(())
 }
 
-/// Perform the update
-fn perform_update(all: bool) -> Result<()> {
-    if all {
-        println!("{}  Updating all dependencies...\n", "[update]".cyan().bold());
-
-        // Update Cargo.lock
-        Command::new("cargo")
-            .args(["update"])
-            .status()
-            .context("Failed to run cargo update")?;
-    } else {
-        println!("{}  Updating PhilJS dependencies...\n", "[update]".cyan().bold());
-
-        // Update only philjs-related packages
-        let packages = [
-            "philjs",
-            "philjs-macros",
-            "wasm-bindgen",
-            "wasm-bindgen-futures",
-            "web-sys",
-            "js-sys",
-        ];
-
-        for pkg in packages {
-            let result = Command::new("cargo")
-                .args(["update", "-p", pkg])
-                .output();
-
-            match result {
-                Ok(output) if output.status.success() => {
-                    println!("  {}  Updated {}", "[ok]".green(), pkg);
-                }
-                _ => {
-                    // Package might not be in dependencies, that's okay
-                }
-            }
-        }
-    }
-
-    println!();
-
-    // Check for new tools
-    update_tools()?;
-
-    println!(
-        "\n{}  Dependencies updated successfully!\n",
-        "[done]".green().bold()
-    );
-
-    Ok(())
-}
-
 /// Update CLI tools
 fn update_tools() -> Result<()> {
     println!("{}  Checking tools...", "[tools]".cyan().bold());
@@ -155,3 +553,82 @@ fn update_tools() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[package]
+name = "app"
+
+[dependencies]
+philjs = { version = "0.1", features = ["ssr"] } # core
+philjs-axum = "0.1.0"
+serde = "1"
+db = { package = "philjs-sqlx", version = "0.1" }
+philjs-macros = { path = "../philjs-macros" }
+
+[target.'cfg(target_arch = "wasm32")'.dev-dependencies]
+philjs-tauri = "0.1"
+"#;
+
+    #[test]
+    fn test_embedded_releases() {
+        let releases = releases().unwrap();
+        assert!(!releases.is_empty());
+        for release in &releases {
+            assert!(release.crates.contains_key("philjs"), "{}", release.version);
+            assert!(release.crates.contains_key("philjs-macros"), "{}", release.version);
+        }
+        assert!(releases.windows(2).all(|w| w[0].semver() < w[1].semver()));
+    }
+
+    #[test]
+    fn test_select_release() {
+        let releases = releases().unwrap();
+        let latest = releases.last().unwrap();
+        assert_eq!(select_release(&releases, None).unwrap().version, latest.version);
+        assert_eq!(select_release(&releases, Some("latest")).unwrap().version, latest.version);
+        assert_eq!(select_release(&releases, Some("0.1")).unwrap().version, "0.1.0");
+        assert!(select_release(&releases, Some("99")).is_err());
+        assert!(select_release(&releases, Some("two")).is_err());
+    }
+
+    #[test]
+    fn test_plan_and_apply() {
+        let releases = releases().unwrap();
+        let mut doc: DocumentMut = MANIFEST.parse().unwrap();
+        let deps = philjs_dependencies(&mut doc);
+
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["philjs", "philjs-axum", "philjs-sqlx", "philjs-macros", "philjs-tauri"]);
+        assert_eq!(deps[3].skipped, Some("path dependency"));
+        assert_eq!(deps[4].table, "target.cfg(target_arch = \"wasm32\").dev-dependencies");
+        assert_eq!(current_release(&releases, &deps).unwrap().version, "0.1.0");
+
+        let target = select_release(&releases, Some("2.0")).unwrap();
+        assert!(incompatible(target, &deps).is_empty());
+        apply_release(&mut doc, target);
+        let updated = doc.to_string();
+        assert!(updated.contains(r#"philjs = { version = "2.0.0", features = ["ssr"] } # core"#));
+        assert!(updated.contains(r#"philjs-axum = "2.0.0""#));
+        assert!(updated.contains(r#"db = { package = "philjs-sqlx", version = "2.0.0" }"#));
+        assert!(updated.contains(r#"philjs-macros = { path = "../philjs-macros" }"#));
+        assert!(updated.contains(r#"philjs-tauri = "2.0.0""#));
+        assert!(updated.contains(r#"serde = "1""#));
+    }
+
+    #[test]
+    fn test_migration_notes() {
+        let releases = releases().unwrap();
+        let from = select_release(&releases, Some("0.1")).unwrap();
+        let to = select_release(&releases, Some("2.0")).unwrap();
+
+        let axum = migration_notes(&releases, Some(from), to, &["philjs".into(), "philjs-axum".into()]);
+        assert!(axum.iter().any(|(_, n)| n.summary.contains("philjs/ssr")));
+
+        let spa = migration_notes(&releases, Some(from), to, &["philjs".into()]);
+        assert!(!spa.iter().any(|(_, n)| n.summary.contains("philjs/ssr")));
+        assert!(migration_notes(&releases, Some(to), to, &["philjs".into()]).is_empty());
+    }
+}
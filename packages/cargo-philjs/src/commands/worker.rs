@@ -0,0 +1,160 @@
+//! Cloudflare Workers output for `cargo philjs build --target=cloudflare`
+//!
+//! The client is built as for the browser into `dist/client/`, served by
+//! the Workers static assets binding. The crate is built a second time with
+//! its `worker` feature into `dist/worker/`, next to a JS entry that answers
+//! fetch events:
+//!
+//! - `/api/_sf/*` goes to `handle_server_fn`, exported by `philjs` with its
+//!   `worker` feature
+//! - everything else goes to the crate's exported `fetch(request, env, ctx)`,
+//!   or to `render(url)` whose HTML is returned as is
+//!
+//! ```toml
+//! [features]
+//! worker = ["philjs/worker"]
+//! ```
+//!
+//! A `wrangler.toml` pointing at both is written if the project has none.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+/// Cargo feature the Worker build enables
+const WORKER_FEATURE: &str = "worker";
+
+const WORKER_ENTRY: &str = "index.js";
+
+/// Build the Worker bundle into `worker_dir`
+pub fn build(release: bool, worker_dir: &Path) -> Result<()> {
+    let metadata = cargo_metadata::MetadataCommand::new()
+        .no_deps()
+        .exec()
+        .context("Failed to read Cargo metadata")?;
+    let has_feature = metadata
+        .root_package()
+        .is_some_and(|p| p.features.contains_key(WORKER_FEATURE));
+    if !has_feature {
+        bail!(
+            "--target=cloudflare needs a `{0}` feature in Cargo.toml:\n\n  [features]\n  {0} = [\"philjs/worker\"]",
+            WORKER_FEATURE
+        );
+    }
+
+    fs::create_dir_all(worker_dir)?;
+    let out_dir = worker_dir
+        .canonicalize()
+        .context("Failed to resolve the worker output directory")?;
+
+    let mut command = Command::new("wasm-pack");
+    command
+        .args(["build", "--target", "web", "--out-name", "server", "--no-pack"])
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg(if release { "--release" } else { "--dev" })
+        .args(["--", "--features", WORKER_FEATURE]);
+    let output = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .context("Failed to run wasm-pack")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Worker build failed:\n{}", stderr);
+    }
+
+    // wasm-pack leaves a `*` .gitignore behind
+    let _ = fs::remove_file(out_dir.join(".gitignore"));
+    fs::write(out_dir.join(WORKER_ENTRY), WORKER_SHIM)?;
+    Ok(())
+}
+
+/// Write `wrangler.toml` unless the project already has one
+pub fn write_wrangler_toml(root: &Path, out_dir: &Path) -> Result<bool> {
+    let path = root.join("wrangler.toml");
+    if path.exists() {
+        return Ok(false);
+    }
+    let name = cargo_metadata::MetadataCommand::new()
+        .current_dir(root)
+        .no_deps()
+        .exec()
+        .ok()
+        .and_then(|m| m.root_package().map(|p| p.name.to_string()))
+        .unwrap_or_else(|| "philjs-app".to_string());
+    fs::write(&path, wrangler_toml(&name, &out_dir.display().to_string(), &today()))?;
+    println!("  {}  Created wrangler.toml", "[done]".green().bold());
+    Ok(true)
+}
+
+fn today() -> String {
+    humantime::format_rfc3339(SystemTime::now()).to_string()[..10].to_string()
+}
+
+pub fn wrangler_toml(name: &str, out_dir: &str, compatibility_date: &str) -> String {
+    let out_dir = out_dir.trim_end_matches('/');
+    format!(
+        r#"name = "{name}"
+main = "{out_dir}/worker/{WORKER_ENTRY}"
+compatibility_date = "{compatibility_date}"
+
+# Static files from the client build; requests that match none reach the Worker
+[assets]
+directory = "{out_dir}/client"
+binding = "ASSETS"
+"#
+    )
+}
+
+/// Fetch-event entry wrapping the wasm-bindgen output
+const WORKER_SHIM: &str = r#"// Generated by cargo-philjs: Cloudflare Workers entry
+import init, * as app from './server.js';
+import wasm from './server_bg.wasm';
+
+const SERVER_FN_PREFIX = '/api/_sf/';
+
+let ready;
+
+export default {
+  async fetch(request, env, ctx) {
+    ready ??= init({ module_or_path: wasm });
+    await ready;
+
+    const url = new URL(request.url);
+    if (url.pathname.startsWith(SERVER_FN_PREFIX) && app.handle_server_fn) {
+      return app.handle_server_fn(request);
+    }
+    if (app.fetch) {
+      return app.fetch(request, env, ctx);
+    }
+    if (app.render) {
+      const html = await app.render(url.pathname + url.search);
+      return new Response(html, {
+        headers: { 'Content-Type': 'text/html; charset=utf-8' },
+      });
+    }
+    if (env.ASSETS) {
+      return env.ASSETS.fetch(request);
+    }
+    return new Response('Not found', { status: 404 });
+  },
+};
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrangler_toml() {
+        let toml = wrangler_toml("my-app", "dist/", "2026-01-01");
+        assert!(toml.contains("name = \"my-app\""));
+        assert!(toml.contains("main = \"dist/worker/index.js\""));
+        assert!(toml.contains("directory = \"dist/client\""));
+        assert!(toml.contains("compatibility_date = \"2026-01-01\""));
+    }
+}
@@ -1,27 +1,116 @@
 //! Configuration handling
+//!
+//! Project settings live in `philjs.toml` at the crate root
+//! (`philjs.config.toml` is still read by older projects). Every section is
+//! optional and falls back to the defaults below; command-line flags win
+//! over the file.
+//!
+//! ```toml
+//! [dev]
+//! port = 3000
+//! watch = ["content"]
+//!
+//! [build]
+//! out_dir = "dist"
+//! budget_gzip = "150kb"
+//!
+//! [deploy]
+//! platform = "fly"
+//!
+//! [deploy.docker]
+//! registry = "${REGISTRY:-ghcr.io/acme}"
+//!
+//! # Applied over the sections above when the profile is active
+//! [profile.prod.build]
+//! minify = true
+//! source_maps = false
+//! ```
+//!
+//! The active profile is `--profile` or `PHILJS_PROFILE`, otherwise the
+//! command's own: `dev` for `dev`, `prod` for release builds and deploys,
+//! `test` for `test`.
+//!
+//! String values may reference environment variables as `${NAME}` or
+//! `${NAME:-default}`; `$$` is a literal `$`. Unknown keys, values of the
+//! wrong type and invalid settings are rejected with the offending key.
 
+use anyhow::{anyhow, bail, Context, Result};
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::{BuildTarget, CatalogFormat, DeployPlatform, ProjectTemplate};
+
+/// Config file written by `new` and `init`
+pub const CONFIG_FILE: &str = "philjs.toml";
+
+/// Config file name used before `philjs.toml`
+pub const LEGACY_CONFIG_FILE: &str = "philjs.config.toml";
+
+/// Profile selected with `--profile` or `PHILJS_PROFILE`
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Select the profile for this run, overriding each command's default
+pub fn set_profile(profile: &str) {
+    let _ = PROFILE.set(profile.to_string());
+}
 
 #[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     pub project: ProjectConfig,
     pub build: BuildConfig,
     pub dev: DevConfig,
     pub ssr: SsrConfig,
+    #[serde(skip_serializing_if = "ServerConfig::is_empty")]
+    pub server: ServerConfig,
+    #[serde(skip_serializing_if = "LiveViewConfig::is_empty")]
+    pub liveview: LiveViewConfig,
     pub optimization: OptimizationConfig,
+    pub assets: AssetsConfig,
+    #[serde(skip_serializing_if = "I18nConfig::is_default")]
+    pub i18n: I18nConfig,
+    pub database: DatabaseConfig,
+    pub deploy: DeployConfig,
+    /// Overrides per profile, e.g. `[profile.prod.build]`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profile: BTreeMap<String, toml::Table>,
+    /// Profile applied when loading
+    #[serde(skip)]
+    pub active_profile: String,
 }
 
 #[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
 pub struct ProjectConfig {
     pub name: String,
+    /// Template the project was created from (`spa`, `ssr`, `fullstack`, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
 }
 
+/// Defaults for `cargo philjs build`
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct BuildConfig {
     pub target: String,
     pub out_dir: String,
+    pub minify: bool,
+    pub source_maps: bool,
+    /// wasm-opt optimization level: `0`-`4`, `s` or `z`
+    pub opt_level: String,
+    pub wasm_opt_passes: Vec<String>,
+    /// Content-hash asset file names
+    pub hash: bool,
+    /// Render ssg/isr routes at build time
+    pub prerender: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_gzip: Option<String>,
 }
 
 impl Default for BuildConfig {
@@ -29,31 +118,89 @@ impl Default for BuildConfig {
         BuildConfig {
             target: "browser".to_string(),
             out_dir: "dist".to_string(),
+            minify: false,
+            source_maps: false,
+            opt_level: "z".to_string(),
+            wasm_opt_passes: Vec::new(),
+            hash: true,
+            prerender: false,
+            budget: None,
+            budget_gzip: None,
         }
     }
 }
 
+/// Defaults for `cargo philjs dev`
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct DevConfig {
     pub port: u16,
+    pub host: String,
     pub open: bool,
+    pub https: bool,
+    pub hot_reload: bool,
+    /// Send COOP/COEP headers
+    pub cross_origin_isolation: bool,
+    /// Directories watched in addition to `src` and the asset directories
+    pub watch: Vec<String>,
 }
 
 impl Default for DevConfig {
     fn default() -> Self {
         DevConfig {
             port: 3000,
-            open: true,
+            host: "127.0.0.1".to_string(),
+            open: false,
+            https: false,
+            hot_reload: true,
+            cross_origin_isolation: true,
+            watch: Vec::new(),
         }
     }
 }
 
 #[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
 pub struct SsrConfig {
     pub enabled: bool,
 }
 
+/// The app's own server, for `ssr`, `fullstack` and `liveview` projects
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_prefix: Option<String>,
+}
+
+impl ServerConfig {
+    fn is_empty(&self) -> bool {
+        self.port.is_none() && self.host.is_none() && self.api_prefix.is_none()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct LiveViewConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub websocket_path: Option<String>,
+    /// Milliseconds between reconnect attempts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconnect_interval: Option<u64>,
+}
+
+impl LiveViewConfig {
+    fn is_empty(&self) -> bool {
+        self.websocket_path.is_none() && self.reconnect_interval.is_none()
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct OptimizationConfig {
     pub minify: bool,
     pub tree_shake: bool,
@@ -68,20 +215,500 @@ impl Default for OptimizationConfig {
     }
 }
 
+/// Where static files come from
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AssetsConfig {
+    /// Copied to the root of the build output; served as `/static/...` in dev
+    pub static_dir: String,
+    /// Copied to `assets/` in the build output
+    pub assets_dir: String,
+    /// Served from the site root in dev
+    pub public_dir: String,
+}
+
+impl Default for AssetsConfig {
+    fn default() -> Self {
+        AssetsConfig {
+            static_dir: "static".to_string(),
+            assets_dir: "assets".to_string(),
+            public_dir: "public".to_string(),
+        }
+    }
+}
+
+/// Translation catalogs managed by `cargo philjs i18n`
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct I18nConfig {
+    pub default_locale: String,
+    /// Locales besides the default; catalogs already in `dir` are included
+    pub locales: Vec<String>,
+    /// Catalogs are `<dir>/<locale>/messages.ftl` (or `.po`)
+    pub dir: String,
+    pub format: String,
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        I18nConfig {
+            default_locale: "en".to_string(),
+            locales: Vec::new(),
+            dir: "locales".to_string(),
+            format: "fluent".to_string(),
+        }
+    }
+}
+
+impl I18nConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// Default database URL
+    pub url: Option<String>,
+    pub migrations_dir: String,
+    /// Database URL per environment, selected with `--env` or `PHILJS_ENV`
+    pub environments: BTreeMap<String, String>,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        DatabaseConfig {
+            url: None,
+            migrations_dir: "migrations".to_string(),
+            environments: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeployConfig {
+    /// Platform used when `--platform` is not given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    /// Deploy previews instead of production
+    pub preview: bool,
+    pub docker: DockerConfig,
+}
+
+/// Overrides for `deploy --platform=docker`
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct DockerConfig {
+    /// Image name, defaults to the package name
+    pub image: Option<String>,
+    /// Registry prefix, e.g. `ghcr.io/acme`
+    pub registry: Option<String>,
+    /// Tags added to the version and `latest`
+    pub tags: Vec<String>,
+    /// Use this Dockerfile instead of generating one
+    pub dockerfile: Option<String>,
+    pub builder_image: Option<String>,
+    pub runtime_image: Option<String>,
+    /// Port the container listens on
+    pub port: Option<u16>,
+    /// Target platform passed to `docker build --platform`
+    pub platform: Option<String>,
+    /// `--build-arg`s, also declared with `ARG` in the build stage
+    pub build_args: BTreeMap<String, String>,
+    /// Environment of the runtime stage
+    pub env: BTreeMap<String, String>,
+    /// Dockerfile instructions run in the build stage before building
+    pub builder_steps: Vec<String>,
+    /// Dockerfile instructions run in the runtime stage before `CMD`
+    pub runtime_steps: Vec<String>,
+}
+
 impl Config {
-    pub fn load() -> Option<Self> {
-        let path = Path::new("philjs.config.toml");
-        if path.exists() {
-            let content = fs::read_to_string(path).ok()?;
-            toml::from_str(&content).ok()
-        } else {
-            None
+    /// The config file in `dir`, preferring `philjs.toml`
+    pub fn path(dir: &Path) -> Option<PathBuf> {
+        [CONFIG_FILE, LEGACY_CONFIG_FILE]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.exists())
+    }
+
+    /// Load the config in `dir` with `--profile`, or `default_profile`
+    pub fn load(dir: &Path, default_profile: &str) -> Result<Self> {
+        let profile = PROFILE.get().map(String::as_str).unwrap_or(default_profile);
+        Self::load_profile(dir, profile)
+    }
+
+    /// Load the config in `dir` with `profile` applied; defaults without a file
+    pub fn load_profile(dir: &Path, profile: &str) -> Result<Self> {
+        let Some(path) = Self::path(dir) else {
+            return Ok(Config {
+                active_profile: profile.to_string(),
+                ..Default::default()
+            });
+        };
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        Self::parse(&content, profile, |name| std::env::var(name).ok())
+            .map_err(|e| anyhow!("Invalid {}\n\n{}", name, e))
+    }
+
+    /// Parse, apply `profile`, interpolate and validate
+    fn parse(content: &str, profile: &str, var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        // Checked as written first, so errors point at a line
+        let base: Config = toml::from_str(content)?;
+
+        let mut value: toml::Table = toml::from_str(content)?;
+        value.remove("profile");
+
+        // Overrides of every profile must fit the schema, not just the active one
+        for (name, overrides) in &base.profile {
+            let mut merged = value.clone();
+            merge(&mut merged, overrides.clone());
+            toml::Value::Table(merged)
+                .try_into::<Config>()
+                .map_err(|e| anyhow!("[profile.{}] {}", name, e))?;
+        }
+        if let Some(overrides) = base.profile.get(profile) {
+            merge(&mut value, overrides.clone());
+        }
+
+        let mut problems = Vec::new();
+        let mut value = toml::Value::Table(value);
+        interpolate(&mut value, "", &var, &mut problems);
+        if !problems.is_empty() {
+            bail!("{}", format_problems(&problems));
+        }
+
+        let mut config: Config = value.try_into()?;
+        config.profile = base.profile;
+        config.active_profile = profile.to_string();
+
+        let problems = config.validate();
+        if !problems.is_empty() {
+            bail!("{}", format_problems(&problems));
         }
+        Ok(config)
     }
 
-    pub fn save(&self) -> anyhow::Result<()> {
+    /// Settings that parse but can't work, as `key: problem`
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if let Some(template) = &self.project.template {
+            check_enum::<ProjectTemplate>("project.template", template, &mut problems);
+        }
+        check_enum::<BuildTarget>("build.target", &self.build.target, &mut problems);
+        if let Some(platform) = &self.deploy.platform {
+            check_enum::<DeployPlatform>("deploy.platform", platform, &mut problems);
+        }
+        check_enum::<CatalogFormat>("i18n.format", &self.i18n.format, &mut problems);
+
+        // `build` empties the output directory first
+        let protected = ["src", "tests", "target", "migrations"];
+        if !is_relative_subdir(&self.build.out_dir)
+            || protected.iter().any(|dir| Path::new(&self.build.out_dir) == Path::new(dir))
+        {
+            problems.push(format!(
+                "build.out_dir: `{}` must be a subdirectory of the project that \
+                 is safe to delete, e.g. `dist`",
+                self.build.out_dir
+            ));
+        }
+        if !["0", "1", "2", "3", "4", "s", "z"].contains(&self.build.opt_level.as_str()) {
+            problems.push(format!(
+                "build.opt_level: `{}` is not a wasm-opt level, expected 0-4, s or z",
+                self.build.opt_level
+            ));
+        }
+        for (key, budget) in [
+            ("build.budget", &self.build.budget),
+            ("build.budget_gzip", &self.build.budget_gzip),
+        ] {
+            if let Some(budget) = budget {
+                if crate::commands::analyze::parse_size(budget).is_err() {
+                    problems.push(format!(
+                        "{}: `{}` is not a size, e.g. `250kb` or `1.5 MB`",
+                        key, budget
+                    ));
+                }
+            }
+        }
+
+        if self.dev.port == 0 {
+            problems.push("dev.port: must be between 1 and 65535".to_string());
+        }
+        if self.dev.host.trim().is_empty() {
+            problems.push("dev.host: must not be empty".to_string());
+        }
+        if self.server.port == Some(0) {
+            problems.push("server.port: must be between 1 and 65535".to_string());
+        }
+
+        for (key, dir) in [
+            ("assets.static_dir", &self.assets.static_dir),
+            ("assets.assets_dir", &self.assets.assets_dir),
+            ("assets.public_dir", &self.assets.public_dir),
+            ("i18n.dir", &self.i18n.dir),
+            ("database.migrations_dir", &self.database.migrations_dir),
+        ] {
+            if !is_relative_subdir(dir) {
+                problems.push(format!(
+                    "{}: `{}` must be a directory inside the project",
+                    key, dir
+                ));
+            }
+        }
+        for locale in std::iter::once(&self.i18n.default_locale).chain(&self.i18n.locales) {
+            if !is_locale(locale) {
+                problems.push(format!(
+                    "i18n: `{}` is not a locale, e.g. `en` or `pt-BR`",
+                    locale
+                ));
+            }
+        }
+        for dir in &self.dev.watch {
+            if Path::new(dir).is_absolute() {
+                problems.push(format!("dev.watch: `{}` must be relative to the project", dir));
+            }
+        }
+
+        problems
+    }
+
+    /// `build.target`, once validated
+    pub fn build_target(&self) -> BuildTarget {
+        BuildTarget::from_str(&self.build.target, true).unwrap_or_default()
+    }
+
+    /// `deploy.platform`, once validated
+    pub fn deploy_platform(&self) -> Option<DeployPlatform> {
+        let platform = self.deploy.platform.as_deref()?;
+        DeployPlatform::from_str(platform, true).ok()
+    }
+
+    /// `i18n.format`, once validated
+    pub fn catalog_format(&self) -> CatalogFormat {
+        CatalogFormat::from_str(&self.i18n.format, true).unwrap_or_default()
+    }
+
+    /// Write to `philjs.toml` in `dir`
+    pub fn save(&self, dir: &Path) -> Result<()> {
         let content = toml::to_string_pretty(self)?;
-        fs::write("philjs.config.toml", content)?;
+        fs::write(dir.join(CONFIG_FILE), content)?;
         Ok(())
     }
 }
+
+/// Merge `overrides` into `base`; tables merge, everything else is replaced
+fn merge(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(value)) => merge(base, value),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Expand `${NAME}` and `${NAME:-default}` in every string under `value`
+fn interpolate(
+    value: &mut toml::Value,
+    key: &str,
+    var: &impl Fn(&str) -> Option<String>,
+    problems: &mut Vec<String>,
+) {
+    match value {
+        toml::Value::String(s) => match expand(s, var) {
+            Ok(expanded) => *s = expanded,
+            Err(e) => problems.push(format!("{}: {}", key, e)),
+        },
+        toml::Value::Array(items) => {
+            for item in items {
+                interpolate(item, key, var, problems);
+            }
+        }
+        toml::Value::Table(table) => {
+            for (name, item) in table.iter_mut() {
+                let key = if key.is_empty() { name.clone() } else { format!("{}.{}", key, name) };
+                interpolate(item, &key, var, problems);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn expand(s: &str, var: &impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(at) = rest.find('$') {
+        out.push_str(&rest[..at]);
+        rest = &rest[at..];
+        if let Some(after) = rest.strip_prefix("$$") {
+            out.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("${") {
+            let end = after
+                .find('}')
+                .ok_or_else(|| format!("unclosed `${{` in `{}`", s))?;
+            let (name, default) = match after[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&after[..end], None),
+            };
+            if name.is_empty()
+                || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                return Err(format!("`{}` is not an environment variable name", name));
+            }
+            match var(name).or_else(|| default.map(str::to_string)) {
+                Some(value) => out.push_str(&value),
+                None => {
+                    return Err(format!(
+                        "environment variable `{}` is not set; set it or use `${{{}:-default}}`",
+                        name, name
+                    ))
+                }
+            }
+            rest = &after[end + 1..];
+        } else {
+            out.push('$');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn check_enum<T: ValueEnum>(key: &str, value: &str, problems: &mut Vec<String>) {
+    if T::from_str(value, true).is_err() {
+        let expected: Vec<String> = T::value_variants()
+            .iter()
+            .filter_map(|v| v.to_possible_value())
+            .map(|v| v.get_name().to_string())
+            .collect();
+        problems.push(format!(
+            "{}: unknown value `{}`, expected one of {}",
+            key,
+            value,
+            expected.join(", ")
+        ));
+    }
+}
+
+fn is_relative_subdir(dir: &str) -> bool {
+    let components: Vec<_> = Path::new(dir).components().collect();
+    !components.is_empty() && components.iter().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// A BCP 47 language tag such as `en`, `pt-BR` or `zh-Hant-TW`
+pub(crate) fn is_locale(tag: &str) -> bool {
+    let mut subtags = tag.split(['-', '_']);
+    let language = subtags.next().unwrap_or_default();
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|s| (2..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+fn format_problems(problems: &[String]) -> String {
+    problems
+        .iter()
+        .map(|p| format!("  - {}", p))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str, profile: &str) -> Result<Config> {
+        Config::parse(content, profile, |name| match name {
+            "REGISTRY" => Some("ghcr.io/acme".to_string()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_profile_overrides_base() {
+        let content = r#"
+[build]
+out_dir = "dist"
+minify = false
+
+[dev]
+port = 4000
+
+[profile.prod.build]
+minify = true
+out_dir = "build"
+"#;
+        let dev = parse(content, "dev").unwrap();
+        assert_eq!(dev.build.out_dir, "dist");
+        assert!(!dev.build.minify);
+        assert_eq!(dev.dev.port, 4000);
+
+        let prod = parse(content, "prod").unwrap();
+        assert_eq!(prod.build.out_dir, "build");
+        assert!(prod.build.minify);
+        assert_eq!(prod.dev.port, 4000);
+        assert_eq!(prod.active_profile, "prod");
+    }
+
+    #[test]
+    fn test_env_interpolation() {
+        let content = r#"
+[deploy.docker]
+registry = "${REGISTRY}"
+image = "${IMAGE:-app}"
+tags = ["v$${VERSION}"]
+"#;
+        let config = parse(content, "dev").unwrap();
+        assert_eq!(config.deploy.docker.registry.as_deref(), Some("ghcr.io/acme"));
+        assert_eq!(config.deploy.docker.image.as_deref(), Some("app"));
+        assert_eq!(config.deploy.docker.tags, vec!["v${VERSION}"]);
+
+        let err = parse("[database]\nurl = \"${DATABASE_URL}\"\n", "dev").unwrap_err();
+        assert!(err.to_string().contains("database.url"));
+        assert!(err.to_string().contains("DATABASE_URL"));
+    }
+
+    #[test]
+    fn test_schema_errors_name_the_key() {
+        let err = parse("[dev]\nprot = 3000\n", "dev").unwrap_err().to_string();
+        assert!(err.contains("unknown field `prot`"), "{}", err);
+        assert!(err.contains("line 2"), "{}", err);
+
+        let err = parse("[profile.prod.build]\nminify = \"yes\"\n", "dev")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("[profile.prod]"), "{}", err);
+        assert!(err.contains("build.minify"), "{}", err);
+    }
+
+    #[test]
+    fn test_validation() {
+        let err = parse(
+            "[build]\ntarget = \"web\"\nout_dir = \".\"\nopt_level = \"9\"\n",
+            "dev",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("build.target: unknown value `web`"), "{}", err);
+        assert!(err.contains("build.out_dir"), "{}", err);
+        assert!(err.contains("build.opt_level"), "{}", err);
+
+        let err = parse("[i18n]\nformat = \"xliff\"\nlocales = [\"fr\", \"french!\"]\n", "dev")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("i18n.format: unknown value `xliff`"), "{}", err);
+        assert!(err.contains("`french!` is not a locale"), "{}", err);
+        assert!(is_locale("pt-BR") && is_locale("zh-Hant-TW") && !is_locale("e"));
+
+        let config = parse("[deploy]\nplatform = \"Fly\"\n", "dev").unwrap();
+        assert_eq!(config.deploy_platform(), Some(DeployPlatform::Fly));
+        assert!(Config::default().validate().is_empty());
+    }
+}
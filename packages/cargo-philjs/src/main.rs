@@ -28,6 +28,19 @@
 //! # Add components/pages
 //! cargo philjs add component Button
 //! cargo philjs add page Dashboard
+//!
+//! # Inspect routes
+//! cargo philjs routes --json
+//!
+//! # Run database migrations
+//! cargo philjs db migrate
+//! cargo philjs db migrate down --steps 2
+//! cargo philjs db rollback --env staging
+//! cargo philjs db new create_users
+//!
+//! # Manage translations
+//! cargo philjs i18n extract --locale fr
+//! cargo philjs i18n status
 //! ```
 
 mod commands;
@@ -35,7 +48,7 @@ mod config;
 mod templates;
 mod utils;
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
 use miette::Result;
 use std::process::ExitCode;
@@ -73,6 +86,10 @@ struct Cli {
     /// Suppress non-essential output
     #[arg(short, long, global = true)]
     quiet: bool,
+
+    /// philjs.toml profile to apply (default: per command, e.g. prod for release builds)
+    #[arg(long, global = true, env = "PHILJS_PROFILE", value_name = "NAME")]
+    profile: Option<String>,
 }
 
 fn get_styles() -> clap::builder::Styles {
@@ -132,13 +149,13 @@ enum Commands {
     /// Start development server with hot reload
     #[command(alias = "d", visible_alias = "serve")]
     Dev {
-        /// Port to run on
-        #[arg(short, long, default_value = "3000", env = "PHILJS_PORT")]
-        port: u16,
+        /// Port to run on [default: 3000]
+        #[arg(short, long, env = "PHILJS_PORT")]
+        port: Option<u16>,
 
-        /// Host to bind to
-        #[arg(long, default_value = "127.0.0.1", env = "PHILJS_HOST")]
-        host: String,
+        /// Host to bind to [default: 127.0.0.1]
+        #[arg(long, env = "PHILJS_HOST")]
+        host: Option<String>,
 
         /// Open browser automatically
         #[arg(short, long)]
@@ -164,18 +181,22 @@ enum Commands {
         #[arg(long, short)]
         release: bool,
 
-        /// Output directory
-        #[arg(short, long, default_value = "dist")]
-        out_dir: String,
+        /// Output directory [default: dist]
+        #[arg(short, long)]
+        out_dir: Option<String>,
 
-        /// Build target
-        #[arg(long, value_enum, default_value = "browser")]
-        target: BuildTarget,
+        /// Build target [default: browser]
+        #[arg(long, value_enum)]
+        target: Option<BuildTarget>,
 
         /// Enable SSR (server-side rendering)
         #[arg(long)]
         ssr: bool,
 
+        /// Prerender ssg/isr routes to HTML at build time (implies --ssr)
+        #[arg(long)]
+        prerender: bool,
+
         /// Enable source maps
         #[arg(long)]
         source_map: bool,
@@ -184,13 +205,33 @@ enum Commands {
         #[arg(long)]
         no_optimize: bool,
 
-        /// Analyze bundle size
+        /// Break down WASM size by crate and function, with an HTML treemap
         #[arg(long)]
         analyze: bool,
 
         /// Minify output (default for release)
         #[arg(long)]
         minify: bool,
+
+        /// wasm-opt optimization level (0-4, s, z) [default: z]
+        #[arg(long)]
+        opt_level: Option<String>,
+
+        /// Extra wasm-opt pass, e.g. strip-debug (repeatable)
+        #[arg(long = "wasm-opt-pass", value_name = "PASS")]
+        wasm_opt_passes: Vec<String>,
+
+        /// Keep original asset file names instead of content-hashed ones
+        #[arg(long)]
+        no_hash: bool,
+
+        /// Fail if the WASM exceeds this size, e.g. 250kb
+        #[arg(long, value_name = "SIZE")]
+        budget: Option<String>,
+
+        /// Fail if the gzipped WASM exceeds this size
+        #[arg(long, value_name = "SIZE")]
+        budget_gzip: Option<String>,
     },
 
     /// Type check and lint your project
@@ -216,10 +257,14 @@ enum Commands {
         #[arg(long)]
         watch: bool,
 
-        /// Run browser/WASM tests
+        /// Run browser tests: wasm-bindgen-test suites and e2e* tests against the dev server
         #[arg(long)]
         browser: bool,
 
+        /// Show the browser window (with --browser)
+        #[arg(long)]
+        headed: bool,
+
         /// Run with coverage
         #[arg(long)]
         coverage: bool,
@@ -231,8 +276,12 @@ enum Commands {
     /// Generate code (components, pages, etc.)
     #[command(alias = "g", visible_alias = "gen")]
     Generate {
+        /// Prompt for options not given on the command line
+        #[arg(short, long, global = true)]
+        interactive: bool,
+
         #[command(subcommand)]
-        what: GenerateCommand,
+        what: Option<GenerateCommand>,
     },
 
     /// Add a component or page to your project
@@ -254,6 +303,18 @@ enum Commands {
         /// Skip build step
         #[arg(long)]
         no_build: bool,
+
+        /// Push the image after building (docker)
+        #[arg(long)]
+        push: bool,
+
+        /// Registry to tag and push to, e.g. ghcr.io/acme (docker)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Extra image tag (docker, repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
     },
 
     /// Update PhilJS and dependencies
@@ -265,6 +326,10 @@ enum Commands {
         /// Check for updates without installing
         #[arg(long)]
         check: bool,
+
+        /// PhilJS release to move to, e.g. 2.0 (default: latest)
+        #[arg(long, value_name = "VERSION")]
+        to: Option<String>,
     },
 
     /// Show project info and diagnostics
@@ -274,6 +339,36 @@ enum Commands {
         json: bool,
     },
 
+    /// Check the toolchain, WASM tools, dependencies and ports
+    Doctor {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the route tree with SSR modes, guards and conflicts
+    Routes {
+        /// Source directory to scan
+        #[arg(long, default_value = "src")]
+        src: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Database tasks such as migrations
+    Db {
+        #[command(subcommand)]
+        what: DbCommand,
+    },
+
+    /// Extract t!() keys into translation catalogs and report coverage
+    I18n {
+        #[command(subcommand)]
+        what: I18nCommand,
+    },
+
     /// Clean build artifacts
     Clean {
         /// Also clean node_modules and .philjs cache
@@ -309,7 +404,7 @@ impl std::fmt::Display for ProjectTemplate {
     }
 }
 
-#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum BuildTarget {
     /// Browser (WebAssembly)
     #[default]
@@ -322,13 +417,13 @@ pub enum BuildTarget {
     Cloudflare,
 }
 
-#[derive(ValueEnum, Clone, Copy, Debug)]
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DeployPlatform {
     /// Vercel
     Vercel,
     /// Netlify
     Netlify,
-    /// Cloudflare Pages
+    /// Cloudflare Workers
     Cloudflare,
     /// Railway
     Railway,
@@ -340,13 +435,188 @@ pub enum DeployPlatform {
     Docker,
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ComponentStyle {
+    /// No styling
+    #[default]
+    None,
+    /// CSS module next to the component
+    Css,
+    /// Tailwind utility classes
+    Tailwind,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MigrateAction {
+    /// Apply pending migrations
+    #[default]
+    Up,
+    /// Roll back migrations
+    Down,
+    /// Roll back and re-apply the last migration
+    Redo,
+    /// Show applied and pending migrations
+    Status,
+    /// Roll back every migration
+    Reset,
+    /// Roll back everything, then apply all
+    Fresh,
+}
+
+impl std::fmt::Display for MigrateAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrateAction::Up => write!(f, "up"),
+            MigrateAction::Down => write!(f, "down"),
+            MigrateAction::Redo => write!(f, "redo"),
+            MigrateAction::Status => write!(f, "status"),
+            MigrateAction::Reset => write!(f, "reset"),
+            MigrateAction::Fresh => write!(f, "fresh"),
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CatalogFormat {
+    /// Fluent, `<locale>/messages.ftl`
+    #[default]
+    Fluent,
+    /// gettext, `<locale>/messages.po`
+    Gettext,
+}
+
+/// Database selection shared by the `db` commands
+#[derive(Args, Clone, Debug)]
+pub struct DbArgs {
+    /// Environment whose database to use (e.g. test, staging)
+    #[arg(short, long)]
+    pub env: Option<String>,
+
+    /// Database URL
+    #[arg(long, env = "DATABASE_URL")]
+    pub database_url: Option<String>,
+
+    /// Migration package name
+    #[arg(short, long, default_value = "migration")]
+    pub package: String,
+
+    /// Print progress as JSON lines
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Subcommand)]
+enum DbCommand {
+    /// Run migrations through the project's migration package
+    Migrate {
+        /// Migration action
+        #[arg(value_enum, default_value = "up")]
+        action: MigrateAction,
+
+        /// Number of migrations to apply or roll back
+        #[arg(short = 'n', long)]
+        steps: Option<usize>,
+
+        #[command(flatten)]
+        db: DbArgs,
+    },
+
+    /// Roll back the most recent migrations
+    Rollback {
+        /// Number of migrations to roll back
+        #[arg(short = 'n', long, default_value = "1")]
+        steps: usize,
+
+        #[command(flatten)]
+        db: DbArgs,
+    },
+
+    /// Show applied and pending migrations
+    Status {
+        #[command(flatten)]
+        db: DbArgs,
+    },
+
+    /// Roll back every migration, re-apply them and seed
+    Reset {
+        /// Seed binary to run afterwards
+        #[arg(long, default_value = "seed")]
+        bin: String,
+
+        /// Don't seed after migrating
+        #[arg(long)]
+        no_seed: bool,
+
+        #[command(flatten)]
+        db: DbArgs,
+    },
+
+    /// Run the project's seed binary
+    Seed {
+        /// Seed binary name
+        #[arg(long, default_value = "seed")]
+        bin: String,
+
+        #[command(flatten)]
+        db: DbArgs,
+    },
+
+    /// Create a timestamped migration (create_<table> and
+    /// add_<column>_to_<table> names get SQL templates)
+    New {
+        /// Migration name
+        name: String,
+
+        /// Migrations directory (default from philjs.toml, or migrations)
+        #[arg(short, long)]
+        dir: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum I18nCommand {
+    /// Add the keys used with t!() to every locale's catalog
+    Extract {
+        /// Also create a catalog for this locale (repeatable)
+        #[arg(short, long = "locale", value_name = "LOCALE")]
+        locales: Vec<String>,
+
+        /// Source directory to scan
+        #[arg(long, default_value = "src")]
+        src: String,
+
+        /// Catalog format (default from philjs.toml, or fluent)
+        #[arg(long, value_enum)]
+        format: Option<CatalogFormat>,
+
+        /// Remove keys no longer used in the source
+        #[arg(long)]
+        prune: bool,
+    },
+
+    /// Show untranslated and unused keys per locale
+    Status {
+        /// Source directory to scan
+        #[arg(long, default_value = "src")]
+        src: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Fail when a locale has untranslated keys
+        #[arg(long)]
+        strict: bool,
+    },
+}
+
 #[derive(Subcommand)]
 enum GenerateCommand {
     /// Generate a component
     #[command(alias = "c")]
     Component {
         /// Component name (PascalCase)
-        name: String,
+        name: Option<String>,
 
         /// Directory to create in
         #[arg(short, long)]
@@ -360,7 +630,15 @@ enum GenerateCommand {
         #[arg(long)]
         props: bool,
 
-        /// Include CSS module
+        /// Typed prop, e.g. --prop title:String (repeatable)
+        #[arg(long = "prop", value_name = "NAME:TYPE")]
+        prop: Vec<String>,
+
+        /// Styling for the component
+        #[arg(long, value_enum)]
+        style: Option<ComponentStyle>,
+
+        /// Include CSS module (same as --style css)
         #[arg(long)]
         styled: bool,
     },
@@ -369,7 +647,11 @@ enum GenerateCommand {
     #[command(alias = "p")]
     Page {
         /// Page name/path
-        name: String,
+        name: Option<String>,
+
+        /// Route path (defaults to /<page-name>)
+        #[arg(long)]
+        route: Option<String>,
 
         /// Include loader function
         #[arg(long)]
@@ -380,27 +662,31 @@ enum GenerateCommand {
     #[command(alias = "s")]
     Server {
         /// Function name
-        name: String,
+        name: Option<String>,
     },
 
     /// Generate an API route
     #[command(alias = "a")]
     Api {
         /// API route name
-        name: String,
+        name: Option<String>,
     },
 
     /// Generate a store/state module
     Store {
         /// Store name
-        name: String,
+        name: Option<String>,
     },
 
     /// Generate a custom hook
     Hook {
         /// Hook name
-        name: String,
+        name: Option<String>,
     },
+
+    /// Generate from a template in .philjs/templates/<kind>
+    #[command(external_subcommand)]
+    Custom(Vec<String>),
 }
 
 #[derive(Subcommand)]
@@ -418,6 +704,14 @@ enum AddCommand {
     },
 }
 
+/// Name from the command line, prompted for when missing or with --interactive
+fn generate_name(what: &str, name: Option<String>, interactive: bool) -> anyhow::Result<String> {
+    match name {
+        Some(name) if !interactive => Ok(name),
+        name => commands::generate::prompt_name(what, name),
+    }
+}
+
 fn print_banner() {
     println!("{}", BANNER.cyan().bold());
 }
@@ -479,6 +773,10 @@ async fn main() -> ExitCode {
 }
 
 async fn run_command(cli: Cli) -> anyhow::Result<()> {
+    if let Some(profile) = &cli.profile {
+        config::set_profile(profile);
+    }
+
     match cli.command {
         Commands::New {
             name,
@@ -505,51 +803,89 @@ async fn run_command(cli: Cli) -> anyhow::Result<()> {
             watch,
             no_hot_reload,
         } => {
-            commands::dev::run(port, &host, open, https, watch, no_hot_reload).await
+            commands::dev::run(port, host, open, https, watch, no_hot_reload).await
         }
         Commands::Build {
             release,
             out_dir,
             target,
             ssr,
+            prerender,
             source_map,
             no_optimize,
             analyze,
             minify,
+            opt_level,
+            wasm_opt_passes,
+            no_hash,
+            budget,
+            budget_gzip,
         } => {
-            commands::build::run(
+            let profile = if release { "prod" } else { "dev" };
+            let config = config::Config::load(std::path::Path::new("."), profile)?;
+            let defaults = commands::build::BuildOptions::from_config(&config, release);
+            commands::build::run(commands::build::BuildOptions {
                 release,
-                &out_dir,
-                target,
-                ssr,
-                source_map,
+                out_dir: out_dir.unwrap_or(defaults.out_dir),
+                target: target.unwrap_or(defaults.target),
+                ssr: ssr || defaults.ssr,
+                prerender: prerender || defaults.prerender,
+                source_map: source_map || defaults.source_map,
                 no_optimize,
                 analyze,
-                minify,
-            )
+                minify: minify || defaults.minify,
+                opt_level: opt_level.unwrap_or(defaults.opt_level),
+                wasm_opt_passes: [defaults.wasm_opt_passes, wasm_opt_passes].concat(),
+                no_hash: no_hash || defaults.no_hash,
+                budget: budget.or(defaults.budget),
+                budget_gzip: budget_gzip.or(defaults.budget_gzip),
+                ..defaults
+            })
             .await
         }
         Commands::Check { clippy, fmt, fix } => {
             commands::check::run(clippy, fmt, fix)
         }
-        Commands::Generate { what } => match what {
-            GenerateCommand::Component { name, dir, tests, props, styled } => {
-                commands::generate::component_enhanced(&name, dir.as_deref(), tests, props, styled)
+        Commands::Generate { what: None, .. } => commands::generate::interactive(),
+        Commands::Generate { interactive, what: Some(what) } => match what {
+            GenerateCommand::Component { name, dir, tests, props, prop, style, styled } => {
+                let mut options = commands::generate::ComponentOptions {
+                    dir,
+                    tests,
+                    props,
+                    fields: commands::generate::parse_props(&prop.join(","))?,
+                    style: if styled { ComponentStyle::Css } else { style.unwrap_or_default() },
+                };
+                let name = match name {
+                    Some(name) if !interactive => name,
+                    name => commands::generate::prompt_component(name, &mut options)?,
+                };
+                commands::generate::component_enhanced(&name, &options)
             }
-            GenerateCommand::Page { name, loader } => {
-                commands::generate::page(&name, loader)
+            GenerateCommand::Page { name, route, loader } => {
+                let mut options = commands::generate::PageOptions { route, loader };
+                let name = match name {
+                    Some(name) if !interactive => name,
+                    name => commands::generate::prompt_page(name, &mut options)?,
+                };
+                commands::generate::page(&name, &options)
             }
             GenerateCommand::Server { name } => {
-                commands::generate::server_fn(&name)
+                commands::generate::server_fn(&generate_name("Server function", name, interactive)?)
             }
             GenerateCommand::Api { name } => {
-                commands::generate::api(&name)
+                commands::generate::api(&generate_name("API route", name, interactive)?)
             }
             GenerateCommand::Store { name } => {
-                commands::generate::store(&name)
+                commands::generate::store(&generate_name("Store", name, interactive)?)
             }
             GenerateCommand::Hook { name } => {
-                commands::generate::hook(&name)
+                commands::generate::hook(&generate_name("Hook", name, interactive)?)
+            }
+            GenerateCommand::Custom(args) => {
+                let kind = &args[0];
+                let name = generate_name(kind, args.get(1).cloned(), interactive)?;
+                commands::generate::custom(kind, &name)
             }
         },
         Commands::Add { what } => match what {
@@ -557,26 +893,53 @@ async fn run_command(cli: Cli) -> anyhow::Result<()> {
                 commands::generate::component(&name, None, true)
             }
             AddCommand::Page { name } => {
-                commands::generate::page(&name, true)
+                let options = commands::generate::PageOptions {
+                    loader: true,
+                    ..Default::default()
+                };
+                commands::generate::page(&name, &options)
             }
         },
         Commands::Test {
             watch,
             browser,
+            headed,
             coverage,
             pattern,
         } => {
-            commands::test::run(watch, browser, coverage, pattern.as_deref())
+            commands::test::run(watch, browser, coverage, headed, pattern.as_deref()).await
         }
         Commands::Deploy {
             platform,
             preview,
             no_build,
-        } => commands::deploy::run(platform, preview, no_build).await,
-        Commands::Update { all, check } => {
-            commands::update::run(all, check)
+            push,
+            registry,
+            tags,
+        } => {
+            let docker = commands::docker::DockerOptions { push, registry, tags };
+            commands::deploy::run(platform, preview, no_build, docker).await
+        }
+        Commands::Update { all, check, to } => {
+            commands::update::run(all, check, to.as_deref())
         }
         Commands::Info { json } => commands::info::run(json),
+        Commands::Doctor { json } => commands::doctor::run(json),
+        Commands::Routes { src, json } => commands::routes::run(&src, json),
+        Commands::Db { what } => match what {
+            DbCommand::Migrate { action, steps, db } => commands::db::migrate(action, steps, &db),
+            DbCommand::Rollback { steps, db } => commands::db::rollback(steps, &db),
+            DbCommand::Status { db } => commands::db::status(&db),
+            DbCommand::Reset { bin, no_seed, db } => commands::db::reset(&db, &bin, no_seed),
+            DbCommand::Seed { bin, db } => commands::db::seed(&db, &bin),
+            DbCommand::New { name, dir } => commands::db::new_migration(&name, dir.as_deref()),
+        },
+        Commands::I18n { what } => match what {
+            I18nCommand::Extract { locales, src, format, prune } => {
+                commands::i18n::extract(&src, &locales, format, prune)
+            }
+            I18nCommand::Status { src, json, strict } => commands::i18n::status(&src, json, strict),
+        },
         Commands::Clean { all } => commands::clean::run(all),
     }
 }
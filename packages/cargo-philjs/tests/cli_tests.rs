@@ -70,6 +70,76 @@ fn test_build_with_minify_flag() {
         .stdout(predicate::str::contains("--minify"));
 }
 
+#[test]
+fn test_build_wasm_opt_flags() {
+    cargo_philjs()
+        .arg("build")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--opt-level"))
+        .stdout(predicate::str::contains("--wasm-opt-pass"))
+        .stdout(predicate::str::contains("--no-hash"));
+}
+
+#[test]
+fn test_build_prerender_flag() {
+    cargo_philjs()
+        .arg("build")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--prerender"));
+}
+
+#[test]
+fn test_build_budget_flags() {
+    cargo_philjs()
+        .arg("build")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--budget"))
+        .stdout(predicate::str::contains("--budget-gzip"));
+}
+
+#[test]
+fn test_routes_command_help() {
+    cargo_philjs()
+        .arg("routes")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--json"));
+}
+
+#[test]
+fn test_i18n_subcommands() {
+    cargo_philjs()
+        .args(["i18n", "extract", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--locale"))
+        .stdout(predicate::str::contains("--prune"));
+    cargo_philjs()
+        .args(["i18n", "status", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--strict"));
+}
+
+#[test]
+fn test_db_subcommands() {
+    for command in ["migrate", "rollback", "status", "reset", "seed", "new"] {
+        cargo_philjs()
+            .arg("db")
+            .arg(command)
+            .arg("--help")
+            .assert()
+            .success();
+    }
+}
+
 #[test]
 fn test_generate_command_help() {
     cargo_philjs()
@@ -93,6 +163,24 @@ fn test_generate_component_help() {
         .stdout(predicate::str::contains("--styled"));
 }
 
+#[test]
+fn test_generate_interactive_flags() {
+    cargo_philjs()
+        .arg("generate")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--interactive"));
+
+    cargo_philjs()
+        .arg("generate")
+        .arg("page")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--route"));
+}
+
 #[test]
 fn test_invalid_command() {
     cargo_philjs()
@@ -130,6 +218,17 @@ fn test_test_command_exists() {
         .stdout(predicate::str::contains("Run tests"));
 }
 
+#[test]
+fn test_test_browser_flags() {
+    cargo_philjs()
+        .arg("test")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--browser"))
+        .stdout(predicate::str::contains("--headed"));
+}
+
 #[test]
 fn test_deploy_command_exists() {
     cargo_philjs()
@@ -140,6 +239,18 @@ fn test_deploy_command_exists() {
         .stdout(predicate::str::contains("Deploy to"));
 }
 
+#[test]
+fn test_deploy_docker_flags() {
+    cargo_philjs()
+        .arg("deploy")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--push"))
+        .stdout(predicate::str::contains("--registry"))
+        .stdout(predicate::str::contains("--tag"));
+}
+
 #[test]
 fn test_clean_command_exists() {
     cargo_philjs()
@@ -160,6 +271,26 @@ fn test_info_command_exists() {
         .stdout(predicate::str::contains("project info"));
 }
 
+#[test]
+fn test_doctor_command_exists() {
+    cargo_philjs()
+        .arg("doctor")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--json"));
+}
+
+#[test]
+fn test_profile_flag() {
+    cargo_philjs()
+        .arg("build")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--profile"));
+}
+
 #[test]
 fn test_update_command_exists() {
     cargo_philjs()
@@ -167,7 +298,8 @@ fn test_update_command_exists() {
         .arg("--help")
         .assert()
         .success()
-        .stdout(predicate::str::contains("Update"));
+        .stdout(predicate::str::contains("Update"))
+        .stdout(predicate::str::contains("--to"));
 }
 
 #[test]
@@ -355,6 +487,98 @@ fn test_generate_component_with_props() {
     assert!(content.contains("pub fn Card(props: CardProps)"));
 }
 
+// Integration test: Generate from a project template
+#[test]
+fn test_generate_custom_template() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join(".philjs/templates/widget/src/widgets");
+    fs::create_dir_all(&template_dir).unwrap();
+    fs::write(
+        template_dir.join("{{snake_name}}.rs"),
+        "pub struct {{name}}; // {{kebab_name}}\n",
+    )
+    .unwrap();
+
+    cargo_philjs()
+        .current_dir(temp_dir.path())
+        .arg("generate")
+        .arg("widget")
+        .arg("FancyBox")
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(temp_dir.path().join("src/widgets/fancy_box.rs")).unwrap();
+    assert_eq!(content, "pub struct FancyBox; // fancy-box\n");
+}
+
+// Integration test: Invalid philjs.toml is rejected before building
+#[test]
+fn test_invalid_config_is_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("philjs.toml"),
+        "[build]\ntarget = \"web\"\n\n[profile.prod.dev]\nprot = 3000\n",
+    )
+    .unwrap();
+
+    cargo_philjs()
+        .current_dir(temp_dir.path())
+        .arg("build")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid philjs.toml"))
+        .stderr(predicate::str::contains("unknown field `prot`"));
+}
+
+#[test]
+fn test_update_unknown_release() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nphiljs = \"0.1\"\n",
+    )
+    .unwrap();
+
+    cargo_philjs()
+        .current_dir(temp_dir.path())
+        .args(["update", "--check", "--to", "99"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No PhilJS release matches `99`"));
+}
+
+#[test]
+fn test_i18n_extract_and_status() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+    fs::write(
+        temp_dir.path().join("src/main.rs"),
+        "fn main() { view! { <h1>{t!(\"welcome\", name = user)}</h1> } }\n",
+    )
+    .unwrap();
+
+    cargo_philjs()
+        .current_dir(temp_dir.path())
+        .args(["i18n", "extract", "--locale", "fr"])
+        .assert()
+        .success();
+
+    let catalog = fs::read_to_string(temp_dir.path().join("locales/fr/messages.ftl")).unwrap();
+    assert!(catalog.contains("# welcome = { $name }"));
+
+    cargo_philjs()
+        .current_dir(temp_dir.path())
+        .args(["i18n", "status", "--strict"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Untranslated keys in en, fr"));
+}
+
 // Integration test: Generate component with styles
 #[test]
 fn test_generate_component_with_styles() {
@@ -0,0 +1,727 @@
+//! SSR response caching for PhilJS Actix
+//!
+//! [`SsrCacheMiddleware`] stores successful `GET` responses and serves them
+//! until their TTL expires or one of their tags is invalidated.
+//!
+//! ```rust,ignore
+//! let cache = SsrCache::memory(10_000);
+//!
+//! App::new()
+//!     .app_data(web::Data::new(cache.clone()))
+//!     .wrap(
+//!         SsrCacheMiddleware::new(cache)
+//!             .ttl(Duration::from_secs(60))
+//!             .vary_header("accept-language")
+//!             .bypass_cookie("philjs_session"),
+//!     )
+//! ```
+//!
+//! Handlers tag responses with the [`CACHE_TAGS_HEADER`] header (stripped
+//! before the response is sent), and invalidate them through [`SsrCache`]:
+//!
+//! ```rust,ignore
+//! async fn post(id: web::Path<u64>) -> HttpResponse {
+//!     HttpResponse::Ok()
+//!         .insert_header((CACHE_TAGS_HEADER, format!("posts, post:{}", id)))
+//!         .body(render_post(*id))
+//! }
+//!
+//! async fn update_post(cache: web::Data<SsrCache>, id: web::Path<u64>) -> HttpResponse {
+//!     cache.invalidate_tag(&format!("post:{}", id)).await;
+//!     HttpResponse::NoContent().finish()
+//! }
+//! ```
+//!
+//! Requests carrying the bypass header or cookie (e.g. logged-in users) are
+//! never served from or stored in the cache.
+
+use actix_web::{
+    body::{BodySize, BoxBody, EitherBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{self, HeaderName, HeaderValue},
+        Method, StatusCode,
+    },
+    Error, HttpMessage, HttpResponse,
+};
+use async_trait::async_trait;
+use futures::future::{ok, LocalBoxFuture, Ready};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Response header listing cache tags, comma-separated
+pub const CACHE_TAGS_HEADER: &str = "x-philjs-cache-tags";
+
+/// Response header reporting `HIT`, `MISS`, or `BYPASS`
+pub const CACHE_STATUS_HEADER: &str = "x-philjs-cache";
+
+/// Default request header that skips the cache
+pub const DEFAULT_BYPASS_HEADER: &str = "x-philjs-cache-bypass";
+
+/// Default time a response stays cached
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Largest body stored by default
+pub const DEFAULT_MAX_BODY: usize = 1024 * 1024;
+
+/// A cached response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    /// Status code
+    pub status: u16,
+    /// Response headers
+    pub headers: Vec<(String, String)>,
+    /// Response body
+    pub body: String,
+}
+
+impl CachedResponse {
+    fn to_response(&self) -> HttpResponse {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+        let mut builder = HttpResponse::build(status);
+        for (name, value) in &self.headers {
+            builder.append_header((name.as_str(), value.as_str()));
+        }
+        builder.body(self.body.clone())
+    }
+}
+
+/// Errors from cache backends
+#[derive(Debug, thiserror::Error)]
+pub enum CacheError {
+    /// The backend failed
+    #[error("Cache store error: {0}")]
+    Store(String),
+    /// An entry could not be (de)serialized
+    #[error("Cache serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Storage backend for [`SsrCache`]
+#[async_trait]
+pub trait SsrCacheStore: Send + Sync + 'static {
+    /// Load a live entry
+    async fn get(&self, key: &str) -> Result<Option<CachedResponse>, CacheError>;
+
+    /// Store an entry under `key`, indexed by `tags`
+    async fn put(
+        &self,
+        key: &str,
+        response: &CachedResponse,
+        tags: &[String],
+        ttl: Duration,
+    ) -> Result<(), CacheError>;
+
+    /// Remove one entry
+    async fn remove(&self, key: &str) -> Result<(), CacheError>;
+
+    /// Remove every entry carrying `tag`, returning how many were removed
+    async fn invalidate_tag(&self, tag: &str) -> Result<usize, CacheError>;
+
+    /// Remove every entry
+    async fn clear(&self) -> Result<(), CacheError>;
+}
+
+/// Shared handle to an SSR cache backend
+///
+/// Cheap to clone; register it as app data to invalidate from handlers.
+#[derive(Clone)]
+pub struct SsrCache {
+    store: Arc<dyn SsrCacheStore>,
+}
+
+impl SsrCache {
+    /// Wrap a backend
+    pub fn new(store: impl SsrCacheStore) -> Self {
+        Self {
+            store: Arc::new(store),
+        }
+    }
+
+    /// In-process cache holding at most `capacity` entries
+    pub fn memory(capacity: usize) -> Self {
+        Self::new(MemoryCacheStore::new(capacity))
+    }
+
+    /// The backend
+    pub fn store(&self) -> &dyn SsrCacheStore {
+        self.store.as_ref()
+    }
+
+    /// Drop every response tagged `tag`
+    pub async fn invalidate_tag(&self, tag: &str) -> usize {
+        self.store.invalidate_tag(tag).await.unwrap_or_else(|err| {
+            tracing::warn!(error = %err, tag, "SSR cache invalidation failed");
+            0
+        })
+    }
+
+    /// Drop every cached response
+    pub async fn clear(&self) {
+        if let Err(err) = self.store.clear().await {
+            tracing::warn!(error = %err, "SSR cache clear failed");
+        }
+    }
+}
+
+struct MemoryEntry {
+    response: CachedResponse,
+    tags: Vec<String>,
+    expires: Instant,
+}
+
+#[derive(Default)]
+struct MemoryInner {
+    entries: HashMap<String, MemoryEntry>,
+    tags: HashMap<String, HashSet<String>>,
+    /// Insertion order, for evicting the oldest entry when full
+    order: VecDeque<String>,
+}
+
+impl MemoryInner {
+    fn remove(&mut self, key: &str) -> bool {
+        let Some(entry) = self.entries.remove(key) else {
+            return false;
+        };
+        for tag in entry.tags {
+            if let Some(keys) = self.tags.get_mut(&tag) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.tags.remove(&tag);
+                }
+            }
+        }
+        true
+    }
+}
+
+/// In-process cache backend
+pub struct MemoryCacheStore {
+    inner: Mutex<MemoryInner>,
+    capacity: usize,
+}
+
+impl MemoryCacheStore {
+    /// Create a store holding at most `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(MemoryInner::default()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Number of stored entries, including expired ones not yet evicted
+    pub fn len(&self) -> usize {
+        self.inner.lock().entries.len()
+    }
+
+    /// Whether the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl SsrCacheStore for MemoryCacheStore {
+    async fn get(&self, key: &str) -> Result<Option<CachedResponse>, CacheError> {
+        let mut inner = self.inner.lock();
+        match inner.entries.get(key) {
+            Some(entry) if entry.expires > Instant::now() => Ok(Some(entry.response.clone())),
+            Some(_) => {
+                inner.remove(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        response: &CachedResponse,
+        tags: &[String],
+        ttl: Duration,
+    ) -> Result<(), CacheError> {
+        let mut inner = self.inner.lock();
+        inner.remove(key);
+
+        while inner.entries.len() >= self.capacity {
+            let Some(oldest) = inner.order.pop_front() else { break };
+            inner.remove(&oldest);
+        }
+
+        for tag in tags {
+            inner.tags.entry(tag.clone()).or_default().insert(key.to_string());
+        }
+        inner.entries.insert(
+            key.to_string(),
+            MemoryEntry {
+                response: response.clone(),
+                tags: tags.to_vec(),
+                expires: Instant::now() + ttl,
+            },
+        );
+        inner.order.retain(|k| k != key);
+        inner.order.push_back(key.to_string());
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), CacheError> {
+        let mut inner = self.inner.lock();
+        inner.remove(key);
+        inner.order.retain(|k| k != key);
+        Ok(())
+    }
+
+    async fn invalidate_tag(&self, tag: &str) -> Result<usize, CacheError> {
+        let mut inner = self.inner.lock();
+        let keys = inner.tags.remove(tag).unwrap_or_default();
+        let removed = keys.iter().filter(|key| inner.remove(key)).count();
+        inner.order.retain(|k| !keys.contains(k));
+        Ok(removed)
+    }
+
+    async fn clear(&self) -> Result<(), CacheError> {
+        *self.inner.lock() = MemoryInner::default();
+        Ok(())
+    }
+}
+
+/// Redis cache backend
+///
+/// Entries are stored as JSON with `SET EX`; each tag is a set of keys.
+#[cfg(feature = "redis-cache")]
+pub struct RedisCacheStore {
+    conn: redis::aio::ConnectionManager,
+    prefix: String,
+}
+
+#[cfg(feature = "redis-cache")]
+impl RedisCacheStore {
+    /// Connect to Redis at `url`
+    pub async fn connect(url: &str) -> Result<Self, CacheError> {
+        let client = redis::Client::open(url).map_err(|e| CacheError::Store(e.to_string()))?;
+        let conn = redis::aio::ConnectionManager::new(client)
+            .await
+            .map_err(|e| CacheError::Store(e.to_string()))?;
+        Ok(Self {
+            conn,
+            prefix: "philjs:ssr:".to_string(),
+        })
+    }
+
+    /// Set the key prefix (default `philjs:ssr:`)
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn entry_key(&self, key: &str) -> String {
+        format!("{}entry:{}", self.prefix, key)
+    }
+
+    fn tag_key(&self, tag: &str) -> String {
+        format!("{}tag:{}", self.prefix, tag)
+    }
+}
+
+#[cfg(feature = "redis-cache")]
+fn redis_error(err: redis::RedisError) -> CacheError {
+    CacheError::Store(err.to_string())
+}
+
+#[cfg(feature = "redis-cache")]
+#[async_trait]
+impl SsrCacheStore for RedisCacheStore {
+    async fn get(&self, key: &str) -> Result<Option<CachedResponse>, CacheError> {
+        use redis::AsyncCommands;
+
+        let mut conn = self.conn.clone();
+        let json: Option<String> = conn.get(self.entry_key(key)).await.map_err(redis_error)?;
+        json.map(|json| serde_json::from_str(&json).map_err(CacheError::from))
+            .transpose()
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        response: &CachedResponse,
+        tags: &[String],
+        ttl: Duration,
+    ) -> Result<(), CacheError> {
+        let json = serde_json::to_string(response)?;
+        let seconds = ttl.as_secs().max(1);
+        let entry_key = self.entry_key(key);
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().cmd("SET").arg(&entry_key).arg(json).arg("EX").arg(seconds).ignore();
+        for tag in tags {
+            let tag_key = self.tag_key(tag);
+            pipe.cmd("SADD").arg(&tag_key).arg(&entry_key).ignore();
+            pipe.cmd("EXPIRE").arg(&tag_key).arg(seconds).ignore();
+        }
+        let mut conn = self.conn.clone();
+        pipe.query_async::<_, ()>(&mut conn).await.map_err(redis_error)
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), CacheError> {
+        use redis::AsyncCommands;
+
+        let mut conn = self.conn.clone();
+        conn.del::<_, ()>(self.entry_key(key)).await.map_err(redis_error)
+    }
+
+    async fn invalidate_tag(&self, tag: &str) -> Result<usize, CacheError> {
+        use redis::AsyncCommands;
+
+        let mut conn = self.conn.clone();
+        let tag_key = self.tag_key(tag);
+        let keys: Vec<String> = conn.smembers(&tag_key).await.map_err(redis_error)?;
+        let removed: usize = if keys.is_empty() {
+            0
+        } else {
+            conn.del(&keys).await.map_err(redis_error)?
+        };
+        conn.del::<_, ()>(&tag_key).await.map_err(redis_error)?;
+        Ok(removed)
+    }
+
+    async fn clear(&self) -> Result<(), CacheError> {
+        use redis::AsyncCommands;
+
+        let mut conn = self.conn.clone();
+        let keys: Vec<String> = {
+            let mut iter = conn
+                .scan_match::<_, String>(format!("{}*", self.prefix))
+                .await
+                .map_err(redis_error)?;
+            let mut keys = Vec::new();
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            keys
+        };
+        if !keys.is_empty() {
+            conn.del::<_, ()>(keys).await.map_err(redis_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Middleware caching rendered responses
+///
+/// Only `GET` requests answered with `200 OK`, a fixed-size body, no
+/// `Set-Cookie`, and no `Cache-Control: private`/`no-store` are stored.
+/// Keys combine the path, query, and the configured request headers.
+#[derive(Clone)]
+pub struct SsrCacheMiddleware {
+    cache: SsrCache,
+    ttl: Duration,
+    vary_headers: Vec<HeaderName>,
+    bypass_header: Option<HeaderName>,
+    bypass_cookies: Vec<String>,
+    max_body: usize,
+}
+
+impl SsrCacheMiddleware {
+    /// Cache responses in `cache`
+    pub fn new(cache: SsrCache) -> Self {
+        Self {
+            cache,
+            ttl: DEFAULT_CACHE_TTL,
+            vary_headers: Vec::new(),
+            bypass_header: Some(HeaderName::from_static(DEFAULT_BYPASS_HEADER)),
+            bypass_cookies: Vec::new(),
+            max_body: DEFAULT_MAX_BODY,
+        }
+    }
+
+    /// Set how long responses stay cached
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Include a request header in the cache key, e.g. `accept-language`
+    pub fn vary_header(mut self, name: &str) -> Self {
+        if let Ok(name) = HeaderName::try_from(name) {
+            self.vary_headers.push(name);
+        }
+        self
+    }
+
+    /// Skip the cache for requests carrying this header
+    pub fn bypass_header(mut self, name: &str) -> Self {
+        self.bypass_header = HeaderName::try_from(name).ok();
+        self
+    }
+
+    /// Skip the cache for requests carrying this cookie, e.g. a session cookie
+    pub fn bypass_cookie(mut self, name: impl Into<String>) -> Self {
+        self.bypass_cookies.push(name.into());
+        self
+    }
+
+    /// Set the largest body that will be stored
+    pub fn max_body(mut self, bytes: usize) -> Self {
+        self.max_body = bytes;
+        self
+    }
+
+    fn bypassed(&self, req: &ServiceRequest) -> bool {
+        if let Some(name) = &self.bypass_header {
+            if req.headers().contains_key(name) {
+                return true;
+            }
+        }
+        self.bypass_cookies.iter().any(|name| req.cookie(name).is_some())
+    }
+
+    fn key(&self, req: &ServiceRequest) -> String {
+        let mut key = req.path().to_string();
+        if !req.query_string().is_empty() {
+            key.push('?');
+            key.push_str(req.query_string());
+        }
+        for name in &self.vary_headers {
+            let value = req.headers().get(name).and_then(|v| v.to_str().ok()).unwrap_or("");
+            key.push('|');
+            key.push_str(name.as_str());
+            key.push('=');
+            key.push_str(value);
+        }
+        key
+    }
+
+    fn cacheable<B: MessageBody>(&self, res: &HttpResponse<B>) -> bool {
+        if res.status() != StatusCode::OK || res.headers().contains_key(header::SET_COOKIE) {
+            return false;
+        }
+        let private = res
+            .headers()
+            .get(header::CACHE_CONTROL)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("private") || v.contains("no-store"));
+        if private {
+            return false;
+        }
+        matches!(res.body().size(), BodySize::Sized(len) if len as usize <= self.max_body)
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for SsrCacheMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = SsrCacheMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(SsrCacheMiddlewareService {
+            service: Rc::new(service),
+            settings: Rc::new(self.clone()),
+        })
+    }
+}
+
+/// SSR cache middleware service
+pub struct SsrCacheMiddlewareService<S> {
+    service: Rc<S>,
+    settings: Rc<SsrCacheMiddleware>,
+}
+
+fn set_status<B>(res: &mut ServiceResponse<B>, status: &'static str) {
+    res.headers_mut().insert(
+        HeaderName::from_static(CACHE_STATUS_HEADER),
+        HeaderValue::from_static(status),
+    );
+}
+
+fn take_tags<B>(res: &mut ServiceResponse<B>) -> Vec<String> {
+    let name = HeaderName::from_static(CACHE_TAGS_HEADER);
+    let tags = res
+        .headers()
+        .get_all(&name)
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect();
+    res.headers_mut().remove(name);
+    tags
+}
+
+impl<S, B> Service<ServiceRequest> for SsrCacheMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let settings = self.settings.clone();
+        req.extensions_mut().insert(settings.cache.clone());
+
+        Box::pin(async move {
+            if req.method() != Method::GET {
+                let mut res = service.call(req).await?;
+                take_tags(&mut res);
+                return Ok(res.map_into_left_body());
+            }
+            if settings.bypassed(&req) {
+                let mut res = service.call(req).await?;
+                take_tags(&mut res);
+                set_status(&mut res, "BYPASS");
+                return Ok(res.map_into_left_body());
+            }
+
+            let key = settings.key(&req);
+            match settings.cache.store.get(&key).await {
+                Ok(Some(cached)) => {
+                    let mut res = req.into_response(cached.to_response());
+                    set_status(&mut res, "HIT");
+                    return Ok(res.map_into_right_body());
+                }
+                Ok(None) => {}
+                Err(err) => tracing::warn!(error = %err, "SSR cache lookup failed"),
+            }
+
+            let mut res = service.call(req).await?;
+            let tags = take_tags(&mut res);
+            set_status(&mut res, "MISS");
+            if !settings.cacheable(res.response()) {
+                return Ok(res.map_into_left_body());
+            }
+
+            let (req, res) = res.into_parts();
+            let (res, body) = res.into_parts();
+            let body = match actix_web::body::to_bytes(body).await {
+                Ok(body) => body,
+                Err(err) => {
+                    let err: Box<dyn std::error::Error> = err.into();
+                    return Err(actix_web::error::ErrorInternalServerError(err.to_string()));
+                }
+            };
+
+            if let Ok(text) = std::str::from_utf8(&body) {
+                let cached = CachedResponse {
+                    status: res.status().as_u16(),
+                    headers: res
+                        .headers()
+                        .iter()
+                        .filter(|(name, _)| {
+                            *name != header::CONTENT_LENGTH
+                                && *name != header::DATE
+                                && name.as_str() != CACHE_STATUS_HEADER
+                        })
+                        .filter_map(|(name, value)| {
+                            Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+                        })
+                        .collect(),
+                    body: text.to_string(),
+                };
+                if let Err(err) = settings.cache.store.put(&key, &cached, &tags, settings.ttl).await {
+                    tracing::warn!(error = %err, "SSR cache store failed");
+                }
+            }
+
+            let res = res.set_body(BoxBody::new(body));
+            Ok(ServiceResponse::new(req, res).map_into_right_body())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, web, App};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[actix_rt::test]
+    async fn test_memory_store_tags_and_capacity() {
+        let store = MemoryCacheStore::new(2);
+        let response = CachedResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: "<p>hi</p>".to_string(),
+        };
+        let ttl = Duration::from_secs(60);
+        store.put("/a", &response, &["posts".to_string()], ttl).await.unwrap();
+        store.put("/b", &response, &["posts".to_string()], ttl).await.unwrap();
+        store.put("/c", &response, &[], ttl).await.unwrap();
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get("/a").await.unwrap().is_none());
+        assert_eq!(store.invalidate_tag("posts").await.unwrap(), 1);
+        assert!(store.get("/c").await.unwrap().is_some());
+
+        store.put("/d", &response, &[], Duration::ZERO).await.unwrap();
+        assert!(store.get("/d").await.unwrap().is_none());
+    }
+
+    #[actix_rt::test]
+    async fn test_middleware_hit_miss_bypass_and_invalidate() {
+        let renders = Arc::new(AtomicUsize::new(0));
+        let cache = SsrCache::memory(100);
+        let counter = renders.clone();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(cache.clone()))
+                .wrap(SsrCacheMiddleware::new(cache.clone()).bypass_cookie("session"))
+                .route(
+                    "/posts",
+                    web::get().to(move || {
+                        let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                        async move {
+                            HttpResponse::Ok()
+                                .content_type("text/html; charset=utf-8")
+                                .insert_header((CACHE_TAGS_HEADER, "posts"))
+                                .body(format!("<p>render {}</p>", n))
+                        }
+                    }),
+                ),
+        )
+        .await;
+
+        let get = || test::TestRequest::get().uri("/posts");
+
+        let res = test::call_service(&app, get().to_request()).await;
+        assert_eq!(res.headers().get(CACHE_STATUS_HEADER).unwrap(), "MISS");
+        assert!(res.headers().get(CACHE_TAGS_HEADER).is_none());
+        assert_eq!(&test::read_body(res).await[..], b"<p>render 1</p>");
+
+        let res = test::call_service(&app, get().to_request()).await;
+        assert_eq!(res.headers().get(CACHE_STATUS_HEADER).unwrap(), "HIT");
+        assert_eq!(res.headers().get(header::CONTENT_TYPE).unwrap(), "text/html; charset=utf-8");
+        assert_eq!(&test::read_body(res).await[..], b"<p>render 1</p>");
+
+        let req = get().cookie(actix_web::cookie::Cookie::new("session", "abc")).to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.headers().get(CACHE_STATUS_HEADER).unwrap(), "BYPASS");
+
+        assert_eq!(cache.invalidate_tag("posts").await, 1);
+        let res = test::call_service(&app, get().to_request()).await;
+        assert_eq!(res.headers().get(CACHE_STATUS_HEADER).unwrap(), "MISS");
+        assert_eq!(renders.load(Ordering::SeqCst), 3);
+    }
+}
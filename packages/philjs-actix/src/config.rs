@@ -10,7 +10,7 @@ use parking_lot::RwLock;
 pub struct PhilJsConfig {
     /// Static files directory
     pub static_dir: Option<PathBuf>,
-    /// Enable compression
+    /// Enable compression (requires the `compression` feature)
     pub compression: bool,
     /// Enable tracing
     pub tracing: bool,
@@ -147,11 +147,19 @@ impl PhilJsConfig {
         #[cfg(feature = "static-files")]
         if let Some(ref dir) = self.static_dir {
             use actix_files::Files;
+            let scope = web::scope("/static");
+            #[cfg(feature = "compression")]
+            let scope = scope.wrap(actix_web::middleware::Condition::new(
+                self.compression,
+                crate::middleware::CompressionMiddleware::default(),
+            ));
             cfg.service(
-                Files::new("/static", dir)
-                    .show_files_listing()
-                    .use_last_modified(true)
-                    .prefer_utf8(true),
+                scope.service(
+                    Files::new("", dir)
+                        .show_files_listing()
+                        .use_last_modified(true)
+                        .prefer_utf8(true),
+                ),
             );
         }
     }
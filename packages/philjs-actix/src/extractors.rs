@@ -364,6 +364,321 @@ impl FromRequest for ConnectionInfo {
     }
 }
 
+#[cfg(feature = "session")]
+pub use auth::*;
+
+#[cfg(feature = "session")]
+mod auth {
+    use super::*;
+    use crate::error::PhilJsError;
+    use crate::session::UserSession;
+    use actix_session::SessionExt;
+    use actix_web::{http::{header, StatusCode}, web, HttpResponse, ResponseError};
+    use philjs::prelude::{render_to_string, IntoView};
+    use std::marker::PhantomData;
+    use std::sync::Arc;
+
+    /// Role required by [`RequireRole`]
+    ///
+    /// ```rust
+    /// use philjs_actix::extractors::Role;
+    ///
+    /// struct Admin;
+    ///
+    /// impl Role for Admin {
+    ///     const NAME: &'static str = "admin";
+    /// }
+    /// ```
+    pub trait Role: 'static {
+        /// Role name as stored in [`UserSession::roles`](crate::session::UserSession)
+        const NAME: &'static str;
+    }
+
+    /// Why an auth extractor rejected a request
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum AuthFailure {
+        /// No signed-in user; `return_to` is the requested path and query
+        Unauthenticated {
+            /// Where to send the user after signing in
+            return_to: String,
+        },
+        /// The user lacks a required role
+        Forbidden {
+            /// The missing role
+            role: &'static str,
+        },
+    }
+
+    type LoginPage = Arc<dyn Fn(AuthFailure) -> String + Send + Sync>;
+
+    /// How auth extractors respond to HTML requests
+    ///
+    /// Register as app data; without it every rejection is a JSON error.
+    ///
+    /// ```rust,ignore
+    /// App::new().app_data(web::Data::new(
+    ///     AuthConfig::new().login_page(|failure| view! { <LoginPage failure=failure /> }),
+    /// ))
+    /// ```
+    #[derive(Clone, Default)]
+    pub struct AuthConfig {
+        login_page: Option<LoginPage>,
+        login_url: Option<String>,
+    }
+
+    impl AuthConfig {
+        /// Create a config answering every rejection with JSON
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Render a PhilJS view for HTML requests that fail authentication or authorization
+        pub fn login_page<F, V>(mut self, page: F) -> Self
+        where
+            F: Fn(AuthFailure) -> V + Send + Sync + 'static,
+            V: IntoView,
+        {
+            self.login_page = Some(Arc::new(move |failure| render_to_string(|| page(failure))));
+            self
+        }
+
+        /// Redirect unauthenticated HTML requests to `url`, with a `next` query parameter
+        pub fn login_url(mut self, url: impl Into<String>) -> Self {
+            self.login_url = Some(url.into());
+            self
+        }
+
+        fn reject(&self, req: &HttpRequest, failure: AuthFailure) -> AuthRejection {
+            let mut rejection = AuthRejection {
+                failure: failure.clone(),
+                redirect: None,
+                html: None,
+            };
+            if !wants_html(req) {
+                return rejection;
+            }
+            match (&failure, &self.login_url) {
+                (AuthFailure::Unauthenticated { return_to }, Some(url)) => {
+                    let separator = if url.contains('?') { '&' } else { '?' };
+                    rejection.redirect = Some(format!("{}{}next={}", url, separator, encode_component(return_to)));
+                }
+                _ => rejection.html = self.login_page.as_ref().map(|page| page(failure)),
+            }
+            rejection
+        }
+    }
+
+    /// Whether the client prefers HTML over JSON
+    fn wants_html(req: &HttpRequest) -> bool {
+        let accept = req
+            .headers()
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+        match (accept.find("text/html"), accept.find("application/json")) {
+            (Some(html), Some(json)) => html < json,
+            (Some(_), None) => true,
+            _ => false,
+        }
+    }
+
+    fn encode_component(value: &str) -> String {
+        let mut encoded = String::with_capacity(value.len());
+        for byte in value.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                    encoded.push(byte as char)
+                }
+                _ => encoded.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        encoded
+    }
+
+    /// Rejection from [`AuthUser`] or [`RequireRole`]
+    ///
+    /// JSON clients get a 401/403 error body; HTML clients get the configured
+    /// login page or a redirect to the login URL.
+    #[derive(Debug)]
+    pub struct AuthRejection {
+        failure: AuthFailure,
+        redirect: Option<String>,
+        html: Option<String>,
+    }
+
+    impl AuthRejection {
+        /// Why the request was rejected
+        pub fn failure(&self) -> &AuthFailure {
+            &self.failure
+        }
+    }
+
+    impl std::fmt::Display for AuthRejection {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match &self.failure {
+                AuthFailure::Unauthenticated { .. } => write!(f, "Authentication required"),
+                AuthFailure::Forbidden { role } => write!(f, "Missing role: {}", role),
+            }
+        }
+    }
+
+    impl ResponseError for AuthRejection {
+        fn status_code(&self) -> StatusCode {
+            match self.failure {
+                AuthFailure::Unauthenticated { .. } => StatusCode::UNAUTHORIZED,
+                AuthFailure::Forbidden { .. } => StatusCode::FORBIDDEN,
+            }
+        }
+
+        fn error_response(&self) -> HttpResponse {
+            if let Some(location) = &self.redirect {
+                return HttpResponse::SeeOther()
+                    .insert_header((header::LOCATION, location.as_str()))
+                    .finish();
+            }
+            if let Some(html) = &self.html {
+                return HttpResponse::build(self.status_code())
+                    .content_type("text/html; charset=utf-8")
+                    .body(html.clone());
+            }
+            match &self.failure {
+                AuthFailure::Unauthenticated { .. } => PhilJsError::Unauthorized(self.to_string()),
+                AuthFailure::Forbidden { .. } => PhilJsError::Forbidden(self.to_string()),
+            }
+            .error_response()
+        }
+    }
+
+    fn load_user(req: &HttpRequest) -> Option<UserSession> {
+        UserSession::load(&req.get_session()).filter(UserSession::is_authenticated)
+    }
+
+    fn unauthenticated(req: &HttpRequest) -> AuthRejection {
+        let return_to = match req.query_string() {
+            "" => req.path().to_string(),
+            query => format!("{}?{}", req.path(), query),
+        };
+        auth_config(req).reject(req, AuthFailure::Unauthenticated { return_to })
+    }
+
+    fn auth_config(req: &HttpRequest) -> AuthConfig {
+        req.app_data::<web::Data<AuthConfig>>()
+            .map(|config| config.get_ref().clone())
+            .unwrap_or_default()
+    }
+
+    /// Extract the signed-in user, rejecting anonymous requests
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use philjs_actix::extractors::AuthUser;
+    ///
+    /// async fn profile(user: AuthUser) -> impl Responder {
+    ///     HttpResponse::Ok().body(format!("Hello, {:?}", user.username))
+    /// }
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct AuthUser(pub UserSession);
+
+    impl Deref for AuthUser {
+        type Target = UserSession;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl FromRequest for AuthUser {
+        type Error = AuthRejection;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+            ready(load_user(req).map(AuthUser).ok_or_else(|| unauthenticated(req)))
+        }
+    }
+
+    /// Extract the signed-in user if there is one; never rejects
+    #[derive(Debug, Clone)]
+    pub struct OptionalUser(pub Option<UserSession>);
+
+    impl Deref for OptionalUser {
+        type Target = Option<UserSession>;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl FromRequest for OptionalUser {
+        type Error = actix_web::Error;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+            ready(Ok(OptionalUser(load_user(req))))
+        }
+    }
+
+    /// Extract a signed-in user holding role `R`
+    ///
+    /// Anonymous requests are rejected with 401, users without the role with 403.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// async fn dashboard(admin: RequireRole<Admin>) -> impl Responder {
+    ///     HttpResponse::Ok().body(format!("Welcome, {:?}", admin.username))
+    /// }
+    /// ```
+    pub struct RequireRole<R: Role> {
+        user: UserSession,
+        _role: PhantomData<R>,
+    }
+
+    impl<R: Role> RequireRole<R> {
+        /// Take the user session
+        pub fn into_inner(self) -> UserSession {
+            self.user
+        }
+    }
+
+    impl<R: Role> Deref for RequireRole<R> {
+        type Target = UserSession;
+
+        fn deref(&self) -> &Self::Target {
+            &self.user
+        }
+    }
+
+    impl<R: Role> std::fmt::Debug for RequireRole<R> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("RequireRole")
+                .field("role", &R::NAME)
+                .field("user", &self.user)
+                .finish()
+        }
+    }
+
+    impl<R: Role> FromRequest for RequireRole<R> {
+        type Error = AuthRejection;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+            let result = match load_user(req) {
+                None => Err(unauthenticated(req)),
+                Some(user) if !user.has_role(R::NAME) => {
+                    Err(auth_config(req).reject(req, AuthFailure::Forbidden { role: R::NAME }))
+                }
+                Some(user) => Ok(RequireRole {
+                    user,
+                    _role: PhantomData,
+                }),
+            };
+            ready(result)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -397,4 +712,74 @@ mod tests {
 
         assert_eq!(conn.scheme(), "http");
     }
+
+    #[cfg(feature = "session")]
+    mod auth {
+        use super::super::*;
+        use crate::session::UserSession;
+        use actix_session::Session;
+        use actix_web::{test, web, ResponseError};
+
+        struct Admin;
+
+        impl Role for Admin {
+            const NAME: &'static str = "admin";
+        }
+
+        fn request_with_user(user: Option<UserSession>, accept: &str) -> HttpRequest {
+            let mut req = test::TestRequest::default()
+                .uri("/admin?tab=users")
+                .insert_header(("accept", accept))
+                .app_data(web::Data::new(
+                    AuthConfig::new().login_page(|failure| match failure {
+                        AuthFailure::Unauthenticated { .. } => "<h1>Sign in</h1>",
+                        AuthFailure::Forbidden { .. } => "<h1>No access</h1>",
+                    }),
+                ))
+                .to_srv_request();
+            if let Some(user) = user {
+                let json = serde_json::to_string(&user).unwrap();
+                Session::set_session(&mut req, [("user_session".to_string(), json)]);
+            }
+            req.request().clone()
+        }
+
+        #[actix_rt::test]
+        async fn test_auth_user_rejects_json_with_401() {
+            let req = request_with_user(None, "application/json");
+            let err = AuthUser::from_request(&req, &mut Payload::None).await.unwrap_err();
+            let res = err.error_response();
+            assert_eq!(res.status(), 401);
+            assert_eq!(res.headers().get("content-type").unwrap(), "application/json");
+            assert_eq!(
+                err.failure(),
+                &AuthFailure::Unauthenticated { return_to: "/admin?tab=users".to_string() }
+            );
+        }
+
+        #[actix_rt::test]
+        async fn test_require_role_renders_login_page_for_html() {
+            let req = request_with_user(Some(UserSession::new().with_user_id("1")), "text/html");
+            let err = RequireRole::<Admin>::from_request(&req, &mut Payload::None).await.unwrap_err();
+            let res = err.error_response();
+            assert_eq!(res.status(), 403);
+            assert_eq!(res.headers().get("content-type").unwrap(), "text/html; charset=utf-8");
+
+            let req = request_with_user(None, "text/html");
+            let err = RequireRole::<Admin>::from_request(&req, &mut Payload::None).await.unwrap_err();
+            assert_eq!(err.error_response().status(), 401);
+        }
+
+        #[actix_rt::test]
+        async fn test_role_and_optional_user_extraction() {
+            let user = UserSession::new().with_user_id("1").with_role("admin");
+            let req = request_with_user(Some(user), "text/html");
+            let admin = RequireRole::<Admin>::from_request(&req, &mut Payload::None).await.unwrap();
+            assert_eq!(admin.user_id.as_deref(), Some("1"));
+
+            let anonymous = request_with_user(None, "text/html");
+            let optional = OptionalUser::from_request(&anonymous, &mut Payload::None).await.unwrap();
+            assert!(optional.is_none());
+        }
+    }
 }
@@ -62,6 +62,8 @@ pub mod extractors;
 pub mod handlers;
 pub mod ssr;
 pub mod cors;
+pub mod cache;
+pub mod server_fn;
 
 #[cfg(feature = "websocket")]
 pub mod websocket;
@@ -72,26 +74,36 @@ pub mod session;
 // Re-exports
 pub use config::PhilJsConfig;
 pub use error::PhilJsError;
-pub use middleware::{SsrMiddleware, CompressionMiddleware, TracingMiddleware};
+pub use middleware::{SsrMiddleware, TracingMiddleware};
 pub use service::PhilJsService;
+pub use server_fn::PhilJsServerFns;
+pub use cache::{SsrCache, SsrCacheMiddleware};
 
 #[cfg(feature = "websocket")]
-pub use websocket::{LiveViewSocket, WebSocketHandler};
+pub use websocket::{LiveViewHub, LiveViewSocket, WebSocketHandler};
 
 #[cfg(feature = "session")]
 pub use session::{SessionManager, SessionConfig};
 
+#[cfg(feature = "compression")]
+pub use middleware::CompressionMiddleware;
+
 /// Prelude - import commonly used items
 pub mod prelude {
     pub use crate::config::PhilJsConfig;
     pub use crate::error::PhilJsError;
-    pub use crate::middleware::{SsrMiddleware, CompressionMiddleware, TracingMiddleware};
+    pub use crate::middleware::{SsrMiddleware, TracingMiddleware};
     pub use crate::service::PhilJsService;
-    pub use crate::{render_to_response, render_with_data, render_stream, api_response};
+    pub use crate::server_fn::PhilJsServerFns;
+    pub use crate::cache::{SsrCache, SsrCacheMiddleware, CACHE_TAGS_HEADER};
+    pub use crate::{render_to_response, render_with_data, render_stream, render_stream_with, api_response};
 
     // Re-export extractors
     pub use crate::extractors::{Json, Form, Path, Query, SsrContext, ConnectionInfo};
 
+    #[cfg(feature = "session")]
+    pub use crate::extractors::{AuthConfig, AuthUser, OptionalUser, RequireRole, Role};
+
     // Re-export handlers
     pub use crate::handlers::{
         health_check, not_found, cors_preflight, redirect,
@@ -104,11 +116,14 @@ pub mod prelude {
     };
 
     #[cfg(feature = "websocket")]
-    pub use crate::websocket::{LiveViewSocket, WebSocketHandler};
+    pub use crate::websocket::{LiveViewHub, LiveViewSocket, WebSocketHandler};
 
     #[cfg(feature = "session")]
     pub use crate::session::{SessionManager, SessionConfig};
 
+    #[cfg(feature = "compression")]
+    pub use crate::middleware::CompressionMiddleware;
+
     // Re-export Actix essentials
     pub use actix_web::{
         web, App, HttpServer, HttpRequest, HttpResponse,
@@ -161,17 +176,35 @@ window.__PHILJS_DATA__ = JSON.parse(document.getElementById('__PHILJS_DATA__').t
 }
 
 /// Render a streaming response
+///
+/// The shell is flushed as soon as it renders; suspense boundaries follow
+/// as they resolve.
 pub fn render_stream<F, V>(f: F) -> HttpResponse
 where
     F: FnOnce() -> V,
     V: IntoView,
 {
-    let html = render_to_string(f);
+    render_stream_with(f, philjs::StreamingConfig::default())
+}
+
+/// Render a streaming response with custom streaming options
+pub fn render_stream_with<F, V>(f: F, config: philjs::StreamingConfig) -> HttpResponse
+where
+    F: FnOnce() -> V,
+    V: IntoView,
+{
+    use futures::StreamExt;
+
+    let chunks = philjs::render_to_stream_async(f, config)
+        .filter(|chunk| futures::future::ready(!chunk.is_empty()))
+        .map(|chunk| Ok::<_, std::convert::Infallible>(bytes::Bytes::from(chunk)));
 
     HttpResponse::Ok()
         .content_type("text/html; charset=utf-8")
-        .insert_header((header::TRANSFER_ENCODING, "chunked"))
-        .body(html)
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        // Ask reverse proxies not to buffer, so the shell is flushed early
+        .insert_header(("x-accel-buffering", "no"))
+        .streaming(chunks)
 }
 
 /// Create a JSON API response
@@ -3,15 +3,21 @@
 use actix_web::{
     body::{BoxBody, EitherBody, MessageBody},
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
-    http::header::{self, ContentEncoding, HeaderValue},
-    Error, HttpResponse,
+    http::header,
+    Error,
 };
 use futures::future::{ok, Ready, LocalBoxFuture};
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::task::{Context, Poll};
 use std::time::Instant;
-use tracing::{info, warn, span, Level};
+use tracing::{info, Level};
+
+#[cfg(feature = "compression")]
+mod compression;
+
+#[cfg(feature = "compression")]
+pub use compression::{CompressionMiddleware, CompressionMiddlewareService};
 
 /// SSR Middleware for server-side rendering
 pub struct SsrMiddleware {
@@ -116,92 +122,6 @@ where
     }
 }
 
-/// Compression middleware with configurable settings
-pub struct CompressionMiddleware {
-    /// Minimum size to compress (bytes)
-    pub min_size: usize,
-    /// Compression level (1-9)
-    pub level: u32,
-}
-
-impl Default for CompressionMiddleware {
-    fn default() -> Self {
-        Self {
-            min_size: 1024,
-            level: 6,
-        }
-    }
-}
-
-impl CompressionMiddleware {
-    /// Create new compression middleware
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    /// Set minimum size to compress
-    pub fn min_size(mut self, size: usize) -> Self {
-        self.min_size = size;
-        self
-    }
-
-    /// Set compression level
-    pub fn level(mut self, level: u32) -> Self {
-        self.level = level.clamp(1, 9);
-        self
-    }
-}
-
-impl<S, B> Transform<S, ServiceRequest> for CompressionMiddleware
-where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
-    S::Future: 'static,
-    B: MessageBody + 'static,
-{
-    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
-    type Error = Error;
-    type InitError = ();
-    type Transform = CompressionMiddlewareService<S>;
-    type Future = Ready<Result<Self::Transform, Self::InitError>>;
-
-    fn new_transform(&self, service: S) -> Self::Future {
-        ok(CompressionMiddlewareService {
-            service: Rc::new(RefCell::new(service)),
-            min_size: self.min_size,
-        })
-    }
-}
-
-/// Compression middleware service
-pub struct CompressionMiddlewareService<S> {
-    service: Rc<RefCell<S>>,
-    min_size: usize,
-}
-
-impl<S, B> Service<ServiceRequest> for CompressionMiddlewareService<S>
-where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
-    S::Future: 'static,
-    B: MessageBody + 'static,
-{
-    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
-    type Error = Error;
-    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
-
-    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.service.borrow_mut().poll_ready(ctx)
-    }
-
-    fn call(&self, req: ServiceRequest) -> Self::Future {
-        let service = self.service.clone();
-
-        Box::pin(async move {
-            let res = service.borrow_mut().call(req).await?;
-            Ok(res.map_into_left_body())
-        })
-    }
-}
-
 /// Tracing middleware for request logging
 pub struct TracingMiddleware {
     /// Log level for requests
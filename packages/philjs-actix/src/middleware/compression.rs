@@ -0,0 +1,371 @@
+//! Response compression (`compression` feature)
+
+use actix_web::{
+    body::{BodySize, BoxBody, EitherBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{self, ContentEncoding, HeaderValue},
+    http::{Method, StatusCode},
+    Error, HttpResponse,
+};
+use bytes::Bytes;
+use futures::future::{ok, Ready, LocalBoxFuture};
+use std::io::{self, Write};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::task::{ready, Context, Poll};
+use tracing::warn;
+
+/// Content types that are already compressed and skipped by default
+///
+/// Entries ending in `/` match a whole top-level type.
+const DEFAULT_EXCLUDED_TYPES: &[&str] = &[
+    "image/",
+    "video/",
+    "audio/",
+    "font/woff",
+    "font/woff2",
+    "application/zip",
+    "application/gzip",
+    "application/x-gzip",
+    "application/zstd",
+    "application/x-brotli",
+    "application/x-7z-compressed",
+    "application/vnd.rar",
+    "text/event-stream",
+];
+
+/// Compression middleware with configurable settings
+///
+/// Negotiates brotli, zstd, or gzip from `Accept-Encoding` (honouring
+/// q-values, ties broken by [`encodings`](Self::encodings) order) and
+/// compresses responses incrementally, so streamed SSR keeps flushing.
+/// Responses below `min_size`, already-encoded responses, and excluded
+/// content types pass through untouched.
+#[derive(Clone, Debug)]
+pub struct CompressionMiddleware {
+    /// Minimum size to compress (bytes)
+    pub min_size: usize,
+    /// Compression level (1-9)
+    pub level: u32,
+    /// Offered encodings, in order of preference
+    pub encodings: Vec<ContentEncoding>,
+    /// Content types never compressed
+    pub excluded_types: Vec<String>,
+}
+
+impl Default for CompressionMiddleware {
+    fn default() -> Self {
+        Self {
+            min_size: 1024,
+            level: 6,
+            encodings: vec![ContentEncoding::Brotli, ContentEncoding::Zstd, ContentEncoding::Gzip],
+            excluded_types: DEFAULT_EXCLUDED_TYPES.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+}
+
+impl CompressionMiddleware {
+    /// Create new compression middleware
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set minimum size to compress
+    pub fn min_size(mut self, size: usize) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    /// Set compression level
+    ///
+    /// Used as the gzip level, brotli quality, and zstd level.
+    pub fn level(mut self, level: u32) -> Self {
+        self.level = level.clamp(1, 9);
+        self
+    }
+
+    /// Set the offered encodings, in order of preference
+    ///
+    /// Only brotli, zstd, and gzip are supported; other values are ignored.
+    pub fn encodings(mut self, encodings: impl IntoIterator<Item = ContentEncoding>) -> Self {
+        self.encodings = encodings
+            .into_iter()
+            .filter(|e| matches!(e, ContentEncoding::Brotli | ContentEncoding::Zstd | ContentEncoding::Gzip))
+            .collect();
+        self
+    }
+
+    /// Never compress a content type (or a `type/` prefix)
+    pub fn exclude(mut self, content_type: impl Into<String>) -> Self {
+        self.excluded_types.push(content_type.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Whether a response body may be compressed
+    fn compressible<B: MessageBody>(&self, res: &HttpResponse<B>) -> bool {
+        let status = res.status();
+        if status.is_informational()
+            || status == StatusCode::NO_CONTENT
+            || status == StatusCode::NOT_MODIFIED
+            || status == StatusCode::PARTIAL_CONTENT
+        {
+            return false;
+        }
+
+        let encoded = res
+            .headers()
+            .get(header::CONTENT_ENCODING)
+            .is_some_and(|v| v.as_bytes() != b"identity");
+        if encoded {
+            return false;
+        }
+
+        let large_enough = match res.body().size() {
+            BodySize::Sized(len) => len as usize >= self.min_size,
+            BodySize::Stream => true,
+            BodySize::None => false,
+        };
+        if !large_enough {
+            return false;
+        }
+
+        let Some(content_type) = res.headers().get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()) else {
+            return false;
+        };
+        let essence = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+        if essence == "image/svg+xml" {
+            return true;
+        }
+        !self.excluded_types.iter().any(|excluded| {
+            if excluded.ends_with('/') {
+                essence.starts_with(excluded.as_str())
+            } else {
+                essence == *excluded
+            }
+        })
+    }
+}
+
+/// Pick the best offered encoding for an `Accept-Encoding` header
+fn negotiate(accept: &str, offered: &[ContentEncoding]) -> Option<ContentEncoding> {
+    let mut wildcard = None;
+    let mut qualities: Vec<(String, f32)> = Vec::new();
+    for part in accept.split(',') {
+        let mut params = part.split(';');
+        let name = params.next().unwrap_or("").trim().to_ascii_lowercase();
+        let q = params
+            .filter_map(|p| p.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if name == "*" {
+            wildcard = Some(q);
+        } else if !name.is_empty() {
+            qualities.push((name, q));
+        }
+    }
+
+    let mut best: Option<(ContentEncoding, f32)> = None;
+    for encoding in offered {
+        let q = qualities
+            .iter()
+            .find(|(name, _)| name == encoding.as_str())
+            .map(|(_, q)| *q)
+            .or(wildcard)
+            .unwrap_or(0.0);
+        if q > 0.0 && !matches!(best, Some((_, best_q)) if q <= best_q) {
+            best = Some((*encoding, q));
+        }
+    }
+    best.map(|(encoding, _)| encoding)
+}
+
+impl<S, B> Transform<S, ServiceRequest> for CompressionMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = CompressionMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(CompressionMiddlewareService {
+            service: Rc::new(RefCell::new(service)),
+            settings: Rc::new(self.clone()),
+        })
+    }
+}
+
+/// Compression middleware service
+pub struct CompressionMiddlewareService<S> {
+    service: Rc<RefCell<S>>,
+    settings: Rc<CompressionMiddleware>,
+}
+
+impl<S, B> Service<ServiceRequest> for CompressionMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.borrow_mut().poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let settings = self.settings.clone();
+        let encoding = if req.method() == Method::HEAD {
+            None
+        } else {
+            req.headers()
+                .get(header::ACCEPT_ENCODING)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|accept| negotiate(accept, &settings.encodings))
+        };
+
+        Box::pin(async move {
+            let mut res = service.borrow_mut().call(req).await?;
+            if !settings.compressible(res.response()) {
+                return Ok(res.map_into_left_body());
+            }
+
+            // Caches must key on Accept-Encoding whether or not this client got compression
+            res.headers_mut()
+                .append(header::VARY, HeaderValue::from_static("accept-encoding"));
+
+            let Some(encoding) = encoding else {
+                return Ok(res.map_into_left_body());
+            };
+            let encoder = match ContentEncoder::new(encoding, settings.level) {
+                Ok(encoder) => encoder,
+                Err(err) => {
+                    warn!(error = %err, "failed to create {} encoder", encoding.as_str());
+                    return Ok(res.map_into_left_body());
+                }
+            };
+
+            let res = res.map_body(|head, body| {
+                head.headers_mut()
+                    .insert(header::CONTENT_ENCODING, encoding.to_header_value());
+                head.headers_mut().remove(header::CONTENT_LENGTH);
+                BoxBody::new(EncodedBody {
+                    body: Box::pin(body),
+                    encoder: Some(encoder),
+                })
+            });
+            Ok(res.map_into_right_body())
+        })
+    }
+}
+
+/// Incremental encoder writing compressed output into a buffer
+enum ContentEncoder {
+    Brotli(Box<brotli::CompressorWriter<Vec<u8>>>),
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+    Gzip(flate2::write::GzEncoder<Vec<u8>>),
+}
+
+impl ContentEncoder {
+    fn new(encoding: ContentEncoding, level: u32) -> io::Result<Self> {
+        match encoding {
+            ContentEncoding::Brotli => Ok(Self::Brotli(Box::new(brotli::CompressorWriter::new(
+                Vec::new(),
+                4096,
+                level.min(11),
+                22,
+            )))),
+            ContentEncoding::Zstd => Ok(Self::Zstd(zstd::stream::write::Encoder::new(
+                Vec::new(),
+                level as i32,
+            )?)),
+            ContentEncoding::Gzip => Ok(Self::Gzip(flate2::write::GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::new(level),
+            ))),
+            _ => Err(io::Error::new(io::ErrorKind::Unsupported, "unsupported encoding")),
+        }
+    }
+
+    /// Compress a chunk and flush, so each input chunk reaches the client
+    fn write(&mut self, chunk: &[u8]) -> io::Result<Bytes> {
+        let buffer = match self {
+            Self::Brotli(encoder) => {
+                encoder.write_all(chunk)?;
+                encoder.flush()?;
+                encoder.get_mut()
+            }
+            Self::Zstd(encoder) => {
+                encoder.write_all(chunk)?;
+                encoder.flush()?;
+                encoder.get_mut()
+            }
+            Self::Gzip(encoder) => {
+                encoder.write_all(chunk)?;
+                encoder.flush()?;
+                encoder.get_mut()
+            }
+        };
+        Ok(Bytes::from(std::mem::take(buffer)))
+    }
+
+    fn finish(self) -> io::Result<Bytes> {
+        let buffer = match self {
+            Self::Brotli(encoder) => encoder.into_inner(),
+            Self::Zstd(encoder) => encoder.finish()?,
+            Self::Gzip(encoder) => encoder.finish()?,
+        };
+        Ok(Bytes::from(buffer))
+    }
+}
+
+/// Response body compressed on the fly
+struct EncodedBody<B> {
+    body: Pin<Box<B>>,
+    encoder: Option<ContentEncoder>,
+}
+
+impl<B: MessageBody> MessageBody for EncodedBody<B> {
+    type Error = Box<dyn std::error::Error>;
+
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let this = self.get_mut();
+        loop {
+            let Some(encoder) = this.encoder.as_mut() else {
+                return Poll::Ready(None);
+            };
+            match ready!(this.body.as_mut().poll_next(cx)) {
+                Some(Ok(chunk)) => {
+                    let compressed = encoder.write(&chunk)?;
+                    if !compressed.is_empty() {
+                        return Poll::Ready(Some(Ok(compressed)));
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                None => {
+                    let encoder = this.encoder.take().expect("encoder present");
+                    let trailer = encoder.finish()?;
+                    if trailer.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    return Poll::Ready(Some(Ok(trailer)));
+                }
+            }
+        }
+    }
+}
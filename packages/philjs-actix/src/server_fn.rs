@@ -0,0 +1,526 @@
+//! Server function service for PhilJS Actix
+//!
+//! [`PhilJsServerFns`] mounts every function registered with
+//! `philjs::server::register_server_fn` (which `#[server]`, `#[action]`, and
+//! `#[loader]` do for you) at its own path. The request is available to the
+//! function through `philjs::server::server_context()`.
+//!
+//! ```rust,ignore
+//! App::new()
+//!     .service(PhilJsServerFns::new())
+//!     .route("/", web::get().to(index))
+//! ```
+//!
+//! Input is read according to the request:
+//!
+//! - `GET` functions (`#[loader]`) read the query string
+//! - `application/x-www-form-urlencoded` bodies (`#[action]`) are decoded
+//!   with `philjs::server::form`: values stay strings and repeated names
+//!   become arrays
+//! - `multipart/form-data` bodies (with the `multipart` feature) become a JSON
+//!   object in the same shape; file fields deserialize into [`UploadedFile`]
+//!
+//! Plain HTML form posts, URL-encoded or multipart, are redirected back to
+//! the referring page with `303 See Other`.
+//! - everything else is read as JSON
+//!
+//! Streaming server functions respond with newline-delimited JSON.
+
+use actix_web::{
+    dev::{AppService, HttpServiceFactory},
+    http::{header, Method, StatusCode},
+    web, HttpRequest, HttpResponse,
+};
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use philjs::server::{self, form, ServerContext, ServerError};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Default maximum request body size
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Content type of streaming server function responses
+pub const NDJSON: &str = "application/x-ndjson";
+
+/// A file field from a multipart submission
+///
+/// Server function inputs can use this type (or any struct with the same
+/// fields) for file inputs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadedFile {
+    /// Client-supplied file name
+    pub filename: Option<String>,
+    /// Declared content type
+    pub content_type: Option<String>,
+    /// File contents
+    pub data: Vec<u8>,
+}
+
+/// Service exposing every registered server function
+///
+/// Functions must be registered before the app is built.
+#[derive(Debug, Clone)]
+pub struct PhilJsServerFns {
+    body_limit: usize,
+}
+
+impl PhilJsServerFns {
+    /// Create the service with default limits
+    pub fn new() -> Self {
+        Self {
+            body_limit: DEFAULT_BODY_LIMIT,
+        }
+    }
+
+    /// Set the maximum request body size, including multipart uploads
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.body_limit = limit;
+        self
+    }
+
+    /// Register the routes on a `web::ServiceConfig`
+    pub fn configure(self, cfg: &mut web::ServiceConfig) {
+        cfg.service(self);
+    }
+}
+
+impl Default for PhilJsServerFns {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpServiceFactory for PhilJsServerFns {
+    fn register(self, config: &mut AppService) {
+        let functions = server::registered_server_fns();
+        let streaming = server::registered_streaming_server_fns();
+        info!(count = functions.len() + streaming.len(), "Mounting server functions");
+
+        let routes = functions
+            .into_iter()
+            .map(|path| (path, false))
+            .chain(streaming.into_iter().map(|path| (path, true)));
+
+        for (path, streaming) in routes {
+            let limit = self.body_limit;
+            let method = route_method(path);
+            web::resource(path)
+                .route(web::method(method).to(move |req: HttpRequest, payload: web::Payload| {
+                    handle(path, req, payload, streaming, limit)
+                }))
+                .register(config);
+        }
+    }
+}
+
+fn route_method(path: &str) -> Method {
+    match server::server_fn_method(path).unwrap_or("POST") {
+        "GET" => Method::GET,
+        "PUT" => Method::PUT,
+        "PATCH" => Method::PATCH,
+        "DELETE" => Method::DELETE,
+        _ => Method::POST,
+    }
+}
+
+/// Build the server function context from a request
+pub fn server_context(req: &HttpRequest) -> ServerContext {
+    let mut context = ServerContext::new();
+    for (name, value) in req.headers() {
+        if let Ok(value) = value.to_str() {
+            context.headers.insert(name.as_str().to_lowercase(), value.to_string());
+        }
+    }
+    if let Ok(cookies) = req.cookies() {
+        for cookie in cookies.iter() {
+            context.cookies.insert(cookie.name().to_string(), cookie.value().to_string());
+        }
+    }
+    context.client_ip = req
+        .connection_info()
+        .realip_remote_addr()
+        .map(|addr| {
+            addr.parse::<std::net::SocketAddr>()
+                .map(|addr| addr.ip().to_string())
+                .unwrap_or_else(|_| addr.to_string())
+        });
+    if let Some(id) = req.headers().get("x-request-id").and_then(|v| v.to_str().ok()) {
+        context.request_id = id.to_string();
+    }
+    context
+}
+
+fn content_type(req: &HttpRequest) -> &str {
+    req.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+}
+
+fn wants_redirect(req: &HttpRequest) -> bool {
+    let accept = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    form::wants_redirect(content_type(req), accept)
+}
+
+fn too_large() -> ServerError {
+    ServerError::new("Request body too large").with_status(413)
+}
+
+async fn read_body(mut payload: web::Payload, limit: usize) -> Result<Bytes, ServerError> {
+    let mut body = BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(|e| ServerError::bad_request(format!("Failed to read body: {}", e)))?;
+        if body.len() + chunk.len() > limit {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body.freeze())
+}
+
+#[cfg(feature = "multipart")]
+async fn multipart_to_json(req: &HttpRequest, payload: web::Payload, limit: usize) -> Result<String, ServerError> {
+    let mut multipart = actix_multipart::Multipart::new(req.headers(), payload);
+    let mut object = serde_json::Map::new();
+    let mut total = 0;
+
+    while let Some(field) = multipart.next().await {
+        let mut field = field.map_err(|e| ServerError::bad_request(format!("Invalid multipart data: {}", e)))?;
+        let name = field.content_disposition().get_name().unwrap_or_default().to_string();
+        let filename = field.content_disposition().get_filename().map(str::to_string);
+        let field_type = field.content_type().map(|mime| mime.to_string());
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| ServerError::bad_request(format!("Invalid multipart data: {}", e)))?;
+            total += chunk.len();
+            if total > limit {
+                return Err(too_large());
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        let value = if filename.is_some() {
+            serde_json::to_value(UploadedFile {
+                filename,
+                content_type: field_type,
+                data,
+            })
+            .map_err(|e| ServerError::new(e.to_string()))?
+        } else {
+            let text = String::from_utf8(data)
+                .map_err(|_| ServerError::bad_request(format!("Field `{}` is not valid UTF-8", name)))?;
+            serde_json::Value::String(text)
+        };
+        form::insert_field(&mut object, name, value);
+    }
+    Ok(serde_json::Value::Object(object).to_string())
+}
+
+#[cfg(not(feature = "multipart"))]
+async fn multipart_to_json(_req: &HttpRequest, _payload: web::Payload, _limit: usize) -> Result<String, ServerError> {
+    Err(ServerError::new("Multipart server function input requires the `multipart` feature").with_status(415))
+}
+
+async fn read_input(req: &HttpRequest, payload: web::Payload, limit: usize) -> Result<String, ServerError> {
+    if req.method() == Method::GET {
+        return match req.query_string() {
+            "" => Ok("null".to_string()),
+            query => form::url_encoded_to_json(query.as_bytes()),
+        };
+    }
+    if content_type(req).starts_with(form::MULTIPART) {
+        return multipart_to_json(req, payload, limit).await;
+    }
+
+    let body = read_body(payload, limit).await?;
+    if content_type(req).starts_with(form::URL_ENCODED) {
+        return form::url_encoded_to_json(&body);
+    }
+    let body = std::str::from_utf8(&body)
+        .map_err(|_| ServerError::bad_request("Request body is not valid UTF-8"))?;
+    Ok(if body.trim().is_empty() { "null".to_string() } else { body.to_string() })
+}
+
+fn json_response(status: StatusCode, body: String) -> HttpResponse {
+    HttpResponse::build(status)
+        .content_type("application/json")
+        .body(body)
+}
+
+fn error_response(error: ServerError) -> HttpResponse {
+    let status = StatusCode::from_u16(error.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    json_response(status, serde_json::to_string(&error).unwrap_or_default())
+}
+
+fn redirect_back(req: &HttpRequest) -> HttpResponse {
+    let location = req
+        .headers()
+        .get(header::REFERER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("/")
+        .to_string();
+    HttpResponse::SeeOther()
+        .insert_header((header::LOCATION, location))
+        .finish()
+}
+
+async fn handle(
+    path: &'static str,
+    req: HttpRequest,
+    payload: web::Payload,
+    streaming: bool,
+    limit: usize,
+) -> HttpResponse {
+    let input = match read_input(&req, payload, limit).await {
+        Ok(input) => input,
+        Err(error) => return error_response(error),
+    };
+    let context = server_context(&req);
+
+    if streaming {
+        return match server::with_server_context(context, server::call_streaming_server_fn(path, input)).await {
+            Ok(stream) => ndjson_response(stream),
+            Err(error) => error_response(error),
+        };
+    }
+
+    match server::with_server_context(context, server::call_server_fn(path, input)).await {
+        Ok(_) if wants_redirect(&req) => redirect_back(&req),
+        Ok(output) => json_response(StatusCode::OK, output),
+        Err(error) => error_response(error),
+    }
+}
+
+/// One JSON value per line; an error ends the stream with `{"error": ...}`
+fn ndjson_response(stream: server::ServerFnStream) -> HttpResponse {
+    let lines = stream.scan(false, |failed, item| {
+        if *failed {
+            return futures::future::ready(None);
+        }
+        let line = match item {
+            Ok(json) => json,
+            Err(error) => {
+                *failed = true;
+                serde_json::json!({ "error": error }).to_string()
+            }
+        };
+        futures::future::ready(Some(Ok::<_, std::convert::Infallible>(Bytes::from(format!("{}\n", line)))))
+    });
+
+    HttpResponse::Ok()
+        .content_type(NDJSON)
+        .insert_header(("x-accel-buffering", "no"))
+        .streaming(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, App};
+    use philjs::server::{ServerFn, ServerResult, StreamingServerFn};
+    use std::future::Future;
+    use std::pin::Pin;
+
+    struct Greet;
+
+    impl ServerFn for Greet {
+        type Input = serde_json::Value;
+        type Output = String;
+        const PATH: &'static str = "/api/actix_greet";
+
+        fn run(input: serde_json::Value) -> Pin<Box<dyn Future<Output = ServerResult<String>> + Send>> {
+            let ip = server::server_context().and_then(|ctx| ctx.client_ip).unwrap_or_default();
+            Box::pin(async move {
+                match input["name"].as_str() {
+                    Some(name) => Ok(format!("hi {} from {}", name, ip)),
+                    None => Err(ServerError::bad_request("name required")),
+                }
+            })
+        }
+    }
+
+    struct Search;
+
+    impl ServerFn for Search {
+        type Input = serde_json::Value;
+        type Output = serde_json::Value;
+        const PATH: &'static str = "/api/actix_search";
+        const METHOD: &'static str = "GET";
+
+        fn run(input: serde_json::Value) -> Pin<Box<dyn Future<Output = ServerResult<Self::Output>> + Send>> {
+            Box::pin(async move { Ok(input) })
+        }
+    }
+
+    struct Echo;
+
+    impl ServerFn for Echo {
+        type Input = serde_json::Value;
+        type Output = serde_json::Value;
+        const PATH: &'static str = "/api/actix_echo";
+
+        fn run(input: serde_json::Value) -> Pin<Box<dyn Future<Output = ServerResult<Self::Output>> + Send>> {
+            Box::pin(async move { Ok(input) })
+        }
+    }
+
+    struct Count;
+
+    impl StreamingServerFn for Count {
+        type Input = u32;
+        type Item = u32;
+        const PATH: &'static str = "/api/actix_count";
+
+        fn run(to: u32) -> Pin<Box<dyn Future<Output = ServerResult<Pin<Box<dyn futures::Stream<Item = ServerResult<u32>> + Send>>>> + Send>> {
+            Box::pin(async move {
+                let items = (1..=to).map(Ok);
+                Ok(Box::pin(futures::stream::iter(items)) as Pin<Box<dyn futures::Stream<Item = _> + Send>>)
+            })
+        }
+    }
+
+    fn register() {
+        server::register_server_fn::<Greet>();
+        server::register_server_fn::<Search>();
+        server::register_server_fn::<Echo>();
+        server::register_streaming_server_fn::<Count>();
+    }
+
+    #[actix_rt::test]
+    async fn test_json_and_query_server_fns() {
+        register();
+        let app = test::init_service(App::new().service(PhilJsServerFns::new())).await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/actix_greet")
+            .insert_header(("x-forwarded-for", "10.0.0.7"))
+            .set_payload(r#"{"name":"ada"}"#)
+            .to_request();
+        let body = test::call_and_read_body(&app, req).await;
+        assert_eq!(&body[..], b"\"hi ada from 10.0.0.7\"");
+
+        let req = test::TestRequest::get()
+            .uri("/api/actix_search?q=rust&page=2&tag=a&tag=b")
+            .to_request();
+        let body = test::call_and_read_body(&app, req).await;
+        assert_eq!(&body[..], br#"{"page":"2","q":"rust","tag":["a","b"]}"#);
+
+        let req = test::TestRequest::post().uri("/api/actix_search").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[actix_rt::test]
+    async fn test_form_post_redirects_back() {
+        register();
+        let app = test::init_service(App::new().service(PhilJsServerFns::new())).await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/actix_greet")
+            .insert_header((header::CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .insert_header((header::ACCEPT, "text/html"))
+            .insert_header((header::REFERER, "/signup"))
+            .set_payload("name=ada")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::SEE_OTHER);
+        assert_eq!(res.headers().get(header::LOCATION).unwrap(), "/signup");
+    }
+
+    #[actix_rt::test]
+    async fn test_form_values_stay_strings() {
+        register();
+        let app = test::init_service(App::new().service(PhilJsServerFns::new())).await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/actix_echo")
+            .insert_header((header::CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .set_payload("zip=90210&name=true&color=red&color=blue")
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(
+            body,
+            serde_json::json!({ "zip": "90210", "name": "true", "color": ["red", "blue"] })
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_streaming_server_fn() {
+        register();
+        let app = test::init_service(App::new().service(PhilJsServerFns::new())).await;
+
+        let req = test::TestRequest::post().uri("/api/actix_count").set_payload("3").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.headers().get(header::CONTENT_TYPE).unwrap(), NDJSON);
+        assert_eq!(&test::read_body(res).await[..], b"1\n2\n3\n");
+    }
+
+    #[cfg(feature = "multipart")]
+    #[actix_rt::test]
+    async fn test_multipart_upload() {
+        register();
+        let app = test::init_service(App::new().service(PhilJsServerFns::new())).await;
+
+        let body = "--XX\r\n\
+            Content-Disposition: form-data; name=\"name\"\r\n\r\n\
+            ada\r\n\
+            --XX\r\n\
+            Content-Disposition: form-data; name=\"avatar\"; filename=\"a.txt\"\r\n\
+            Content-Type: text/plain\r\n\r\n\
+            hi\r\n\
+            --XX--\r\n";
+        let req = test::TestRequest::post()
+            .uri("/api/actix_echo")
+            .insert_header((header::CONTENT_TYPE, "multipart/form-data; boundary=XX"))
+            .set_payload(body)
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["name"], "ada");
+        let avatar: UploadedFile = serde_json::from_value(body["avatar"].clone()).unwrap();
+        assert_eq!(avatar.filename.as_deref(), Some("a.txt"));
+        assert_eq!(avatar.content_type.as_deref(), Some("text/plain"));
+        assert_eq!(avatar.data, b"hi");
+    }
+
+    #[cfg(feature = "multipart")]
+    #[actix_rt::test]
+    async fn test_multipart_form_post_redirects_back() {
+        register();
+        let app = test::init_service(App::new().service(PhilJsServerFns::new())).await;
+
+        let body = "--XX\r\n\
+            Content-Disposition: form-data; name=\"zip\"\r\n\r\n\
+            02139\r\n\
+            --XX\r\n\
+            Content-Disposition: form-data; name=\"tag\"\r\n\r\n\
+            a\r\n\
+            --XX\r\n\
+            Content-Disposition: form-data; name=\"tag\"\r\n\r\n\
+            b\r\n\
+            --XX--\r\n";
+        let req = test::TestRequest::post()
+            .uri("/api/actix_echo")
+            .insert_header((header::CONTENT_TYPE, "multipart/form-data; boundary=XX"))
+            .set_payload(body)
+            .to_request();
+        let echoed: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(echoed, serde_json::json!({ "zip": "02139", "tag": ["a", "b"] }));
+
+        let req = test::TestRequest::post()
+            .uri("/api/actix_echo")
+            .insert_header((header::CONTENT_TYPE, "multipart/form-data; boundary=XX"))
+            .insert_header((header::ACCEPT, "text/html"))
+            .insert_header((header::REFERER, "/profile"))
+            .set_payload(body)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::SEE_OTHER);
+        assert_eq!(res.headers().get(header::LOCATION).unwrap(), "/profile");
+    }
+}
@@ -6,6 +6,10 @@ use actix_web::{
     body::BoxBody,
     Error,
 };
+#[cfg(feature = "compression")]
+use actix_web::middleware::Condition;
+#[cfg(feature = "compression")]
+use crate::middleware::CompressionMiddleware;
 use philjs::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -18,6 +22,11 @@ pub struct PhilJsService {
     routes: Arc<RwLock<HashMap<String, RouteHandler>>>,
     /// Fallback handler
     fallback: Option<Box<dyn Fn() -> HttpResponse + Send + Sync>>,
+    /// Compression applied to rendered pages and static files
+    #[cfg(feature = "compression")]
+    compression: Option<CompressionMiddleware>,
+    /// Static file mounts (URL prefix, directory)
+    static_dirs: Vec<(String, std::path::PathBuf)>,
 }
 
 type RouteHandler = Box<dyn Fn(HttpRequest) -> HttpResponse + Send + Sync>;
@@ -28,9 +37,47 @@ impl PhilJsService {
         Self {
             routes: Arc::new(RwLock::new(HashMap::new())),
             fallback: None,
+            #[cfg(feature = "compression")]
+            compression: Some(CompressionMiddleware::default()),
+            static_dirs: Vec::new(),
         }
     }
 
+    /// Configure response compression (enabled with defaults)
+    #[cfg(feature = "compression")]
+    pub fn compression(mut self, compression: CompressionMiddleware) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Disable response compression, e.g. behind a compressing proxy
+    #[cfg(feature = "compression")]
+    pub fn no_compression(mut self) -> Self {
+        self.compression = None;
+        self
+    }
+
+    /// Serve static files from `dir` under `mount`
+    ///
+    /// Requires the `static-files` feature.
+    pub fn static_files(mut self, mount: impl Into<String>, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.static_dirs.push((mount.into(), dir.into()));
+        self
+    }
+
+    #[cfg(feature = "compression")]
+    fn compression_middleware(&self) -> Condition<CompressionMiddleware> {
+        Condition::new(
+            self.compression.is_some(),
+            self.compression.clone().unwrap_or_default(),
+        )
+    }
+
+    #[cfg(not(feature = "compression"))]
+    fn compression_middleware(&self) -> actix_web::middleware::Identity {
+        actix_web::middleware::Identity::default()
+    }
+
     /// Add a route handler
     pub fn route<F, V>(self, path: &str, handler: F) -> Self
     where
@@ -107,14 +154,31 @@ window.__PHILJS_DATA__ = JSON.parse(document.getElementById('__PHILJS_DATA__').t
             let routes_clone = routes.clone();
             let path_clone = path.clone();
 
-            cfg.route(path, web::get().to(move |req: HttpRequest| {
-                let routes = routes_clone.read();
-                if let Some(handler) = routes.get(&path_clone) {
-                    handler(req)
-                } else {
-                    HttpResponse::NotFound().finish()
-                }
-            }));
+            cfg.service(
+                web::resource(path)
+                    .wrap(self.compression_middleware())
+                    .route(web::get().to(move |req: HttpRequest| {
+                        let routes = routes_clone.read();
+                        if let Some(handler) = routes.get(&path_clone) {
+                            handler(req)
+                        } else {
+                            HttpResponse::NotFound().finish()
+                        }
+                    })),
+            );
+        }
+
+        #[cfg(feature = "static-files")]
+        for (mount, dir) in &self.static_dirs {
+            cfg.service(
+                web::scope(mount)
+                    .wrap(self.compression_middleware())
+                    .service(actix_files::Files::new("", dir).use_last_modified(true).prefer_utf8(true)),
+            );
+        }
+        #[cfg(not(feature = "static-files"))]
+        if !self.static_dirs.is_empty() {
+            tracing::warn!("PhilJsService::static_files requires the `static-files` feature");
         }
     }
 }
@@ -369,31 +369,31 @@ impl SeoBuilder {
     }
 
     /// Build meta tags
+    ///
+    /// Delegates to [`philjs::meta::Seo`] so every integration emits the same
+    /// Open Graph and Twitter card set.
     pub fn build(self) -> Vec<MetaTag> {
-        let mut tags = vec![MetaTag::name("title", &self.title)];
-
-        if let Some(desc) = &self.description {
-            tags.push(MetaTag::name("description", desc));
-        }
-
-        if !self.keywords.is_empty() {
-            tags.push(MetaTag::name("keywords", self.keywords.join(", ")));
-        }
-
-        // Open Graph
-        tags.push(MetaTag::property("og:title", &self.title));
-        if let Some(desc) = &self.description {
-            tags.push(MetaTag::property("og:description", desc));
+        let mut seo = philjs::meta::Seo::new()
+            .title(self.title.clone())
+            .keywords(self.keywords);
+        if let Some(desc) = self.description {
+            seo = seo.description(desc);
         }
         for (property, content) in self.og_tags {
-            tags.push(MetaTag::property(property, content));
+            seo = seo.og(property, content);
         }
-
-        // Twitter
         for (name, content) in self.twitter_tags {
-            tags.push(MetaTag::name(format!("twitter:{}", name), content));
+            seo = seo.twitter(name, content);
         }
 
+        let mut tags = vec![MetaTag::name("title", &self.title)];
+        for tag in seo.meta_tags(None) {
+            if let Some(name) = tag.name {
+                tags.push(MetaTag::name(name, tag.content));
+            } else if let Some(property) = tag.property {
+                tags.push(MetaTag::property(property, tag.content));
+            }
+        }
         tags
     }
 }
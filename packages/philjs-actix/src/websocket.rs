@@ -1,10 +1,13 @@
 //! WebSocket support for PhilJS LiveView
 
+use actix::{Actor, ActorContext, ActorFutureExt, AsyncContext, Handler, Recipient, StreamHandler, WrapFuture};
 use actix_web::{web, HttpRequest, HttpResponse, Error};
-use actix_ws::{Message, MessageStream, Session};
+use actix_ws::{Message, MessageStream, ProtocolError, Session};
 use futures_util::StreamExt;
+use philjs::liveview::{self as core, LiveConnection, LiveView, LiveViewRegistry, Reply};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use parking_lot::RwLock;
 use tokio::sync::mpsc;
 use uuid::Uuid;
@@ -231,6 +234,220 @@ pub enum LiveViewPatch {
     Navigate { to: String },
 }
 
+pub use philjs::liveview::LiveViewFactory;
+
+/// Published `handle_info` payload delivered to a connection's mailbox
+#[derive(Debug, Clone, actix::Message)]
+#[rtype(result = "()")]
+pub struct LiveViewInfo(pub serde_json::Value);
+
+/// WebSocket endpoint serving `philjs::liveview` views, one actor per socket
+///
+/// Adapts `actix-ws` sessions to the core [`core::LiveViewHub`], which
+/// speaks the `WsMessage` protocol used by the LiveView client.
+/// [`publish`](Self::publish) delivers events to the mailbox of every
+/// socket subscribed to a topic, which calls `handle_info` and pushes a
+/// re-render.
+///
+/// ```rust,ignore
+/// let hub = web::Data::new(LiveViewHub::new().route("/counter", || Box::new(Counter::default())));
+///
+/// async fn live(req: HttpRequest, stream: web::Payload, hub: web::Data<LiveViewHub>) -> Result<HttpResponse, Error> {
+///     hub.into_inner().upgrade(req, stream)
+/// }
+///
+/// App::new().app_data(hub.clone()).route("/live", web::get().to(live))
+/// ```
+pub struct LiveViewHub {
+    hub: core::LiveViewHub,
+    heartbeat_interval: Duration,
+}
+
+impl LiveViewHub {
+    /// Create a hub with its own registry and pub/sub
+    pub fn new() -> Self {
+        Self {
+            hub: core::LiveViewHub::new(),
+            heartbeat_interval: Duration::from_secs(15),
+        }
+    }
+
+    /// Share an existing registry (e.g. one with a running sweeper)
+    pub fn with_registry(mut self, registry: Arc<LiveViewRegistry>) -> Self {
+        self.hub = self.hub.with_registry(registry);
+        self
+    }
+
+    /// How often each socket is pinged and checked for liveness
+    pub fn heartbeat_interval(mut self, interval: Duration) -> Self {
+        self.heartbeat_interval = interval;
+        self
+    }
+
+    /// Close sockets that have not been heard from within `timeout`
+    pub fn heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.hub = self.hub.with_heartbeat_timeout(timeout);
+        self
+    }
+
+    /// Serve a view for joins on `topic` (usually the page path)
+    pub fn route<F>(mut self, topic: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn() -> Box<dyn LiveView> + Send + Sync + 'static,
+    {
+        self.hub = self.hub.route(topic, factory);
+        self
+    }
+
+    /// The view registry
+    pub fn registry(&self) -> &Arc<LiveViewRegistry> {
+        self.hub.registry()
+    }
+
+    /// Number of open connections
+    pub fn connection_count(&self) -> usize {
+        self.hub.connection_count()
+    }
+
+    /// Subscribe a connected socket to an additional topic
+    pub fn subscribe(&self, topic: &str, socket_id: &str) {
+        self.hub.subscribe(topic, socket_id);
+    }
+
+    /// Deliver an event to every socket subscribed to `topic`
+    ///
+    /// Returns the number of mailboxes the event was delivered to.
+    pub fn publish(&self, topic: &str, event: &str, payload: serde_json::Value) -> usize {
+        self.hub.publish(topic, event, payload)
+    }
+
+    /// Upgrade a request into a LiveView connection
+    pub fn upgrade(
+        self: Arc<Self>,
+        req: HttpRequest,
+        stream: web::Payload,
+    ) -> Result<HttpResponse, Error> {
+        let (response, session, msg_stream) = actix_ws::handle(&req, stream)?;
+
+        LiveViewActor::create(|ctx| {
+            ctx.add_stream(msg_stream);
+            let mailbox: Recipient<LiveViewInfo> = ctx.address().recipient();
+            let conn = self.hub.connect(Uuid::new_v4().to_string(), move |info| {
+                mailbox.do_send(LiveViewInfo(info));
+            });
+            LiveViewActor {
+                hub: self,
+                session,
+                conn,
+                last_seen: Instant::now(),
+            }
+        });
+
+        Ok(response)
+    }
+}
+
+impl Default for LiveViewHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Actor owning one LiveView connection; dropping it terminates the view
+struct LiveViewActor {
+    hub: Arc<LiveViewHub>,
+    session: Session,
+    conn: LiveConnection,
+    last_seen: Instant,
+}
+
+impl LiveViewActor {
+    /// Send a text frame, holding the mailbox until it is written so
+    /// frames keep their order; stops the actor if the socket is gone
+    fn send(&mut self, ctx: &mut actix::Context<Self>, text: String) {
+        let mut session = self.session.clone();
+        ctx.wait(
+            async move { session.text(text).await }
+                .into_actor(self)
+                .map(|result, _act, ctx| {
+                    if result.is_err() {
+                        ctx.stop();
+                    }
+                }),
+        );
+    }
+
+    fn reply(&mut self, ctx: &mut actix::Context<Self>, reply: Reply) {
+        match reply {
+            Reply::None => {}
+            Reply::Send(frame) | Reply::Joined { frame, .. } => self.send(ctx, frame),
+            Reply::Close(frame) => {
+                if let Some(frame) = frame {
+                    self.send(ctx, frame);
+                }
+                ctx.stop();
+            }
+        }
+    }
+}
+
+impl Actor for LiveViewActor {
+    type Context = actix::Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(self.hub.heartbeat_interval, |act, ctx| {
+            if act.last_seen.elapsed() > act.hub.hub.heartbeat_timeout() || !act.conn.is_alive() {
+                ctx.stop();
+                return;
+            }
+            let mut session = act.session.clone();
+            actix::spawn(async move {
+                let _ = session.ping(b"").await;
+            });
+        });
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        let session = self.session.clone();
+        actix::spawn(async move {
+            let _ = session.close(None).await;
+        });
+    }
+}
+
+impl StreamHandler<Result<Message, ProtocolError>> for LiveViewActor {
+    fn handle(&mut self, msg: Result<Message, ProtocolError>, ctx: &mut Self::Context) {
+        self.last_seen = Instant::now();
+        match msg {
+            Ok(Message::Text(text)) => {
+                let reply = self.conn.handle_text(&text);
+                self.reply(ctx, reply);
+            }
+            Ok(Message::Ping(bytes)) => {
+                let mut session = self.session.clone();
+                actix::spawn(async move {
+                    let _ = session.pong(&bytes).await;
+                });
+            }
+            Ok(Message::Close(_)) | Err(_) => ctx.stop(),
+            Ok(_) => {}
+        }
+    }
+
+    fn finished(&mut self, ctx: &mut Self::Context) {
+        ctx.stop();
+    }
+}
+
+impl Handler<LiveViewInfo> for LiveViewActor {
+    type Result = ();
+
+    fn handle(&mut self, info: LiveViewInfo, ctx: &mut Self::Context) {
+        let reply = self.conn.handle_info(info.0);
+        self.reply(ctx, reply);
+    }
+}
+
 /// Generic WebSocket handler
 pub struct WebSocketHandler {
     /// Handler function
@@ -397,6 +614,14 @@ mod tests {
         assert!(socket.assigns().contains_key("name"));
     }
 
+    #[test]
+    fn test_hub_publish_without_connections() {
+        let hub = LiveViewHub::new().route("/counter", || unreachable!());
+        hub.subscribe("/counter", "socket-1");
+        assert_eq!(hub.connection_count(), 0);
+        assert_eq!(hub.publish("/counter", "tick", serde_json::json!({})), 0);
+    }
+
     #[test]
     fn test_live_view_patch_serialization() {
         let patch = LiveViewPatch::Replace {
@@ -290,3 +290,80 @@ fn test_pagination_offset_calculation() {
     assert_eq!(params.offset(), 50);
     assert_eq!(params.limit(), 25);
 }
+
+#[actix_rt::test]
+async fn test_render_stream_is_chunked() {
+    use actix_web::body::MessageBody;
+
+    let response = philjs_actix::render_stream(|| "<h1>Streamed</h1>");
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "text/html; charset=utf-8"
+    );
+    assert!(matches!(response.body().size(), actix_web::body::BodySize::Stream));
+
+    let body = actix_web::body::to_bytes(response.into_body()).await.unwrap();
+    assert!(String::from_utf8_lossy(&body).contains("Streamed"));
+}
+
+#[cfg(feature = "compression")]
+#[actix_rt::test]
+async fn test_compression_negotiates_gzip() {
+    use std::io::Read;
+
+    let page = "<p>PhilJS</p>".repeat(200);
+    let body = page.clone();
+    let app = test::init_service(
+        App::new()
+            .wrap(CompressionMiddleware::new())
+            .route("/", web::get().to(move || {
+                let body = body.clone();
+                async move { HttpResponse::Ok().content_type("text/html; charset=utf-8").body(body) }
+            })),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header(("accept-encoding", "br;q=0.5, gzip"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.headers().get("content-encoding").unwrap(), "gzip");
+    assert_eq!(resp.headers().get("vary").unwrap(), "accept-encoding");
+
+    let compressed = test::read_body(resp).await;
+    assert!(compressed.len() < page.len());
+    let mut decoded = String::new();
+    flate2::read::GzDecoder::new(&compressed[..])
+        .read_to_string(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, page);
+}
+
+#[cfg(feature = "compression")]
+#[actix_rt::test]
+async fn test_compression_skips_small_and_excluded() {
+    let app = test::init_service(
+        App::new()
+            .wrap(CompressionMiddleware::new().min_size(64))
+            .route("/small", web::get().to(|| async {
+                HttpResponse::Ok().content_type("text/plain").body("tiny")
+            }))
+            .route("/image", web::get().to(|| async {
+                HttpResponse::Ok().content_type("image/png").body(vec![0u8; 4096])
+            })),
+    )
+    .await;
+
+    for uri in ["/small", "/image"] {
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .insert_header(("accept-encoding", "br, zstd, gzip"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.headers().get("content-encoding").is_none(), "{} was compressed", uri);
+    }
+}
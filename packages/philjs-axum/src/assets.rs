@@ -0,0 +1,383 @@
+//! Static asset serving for PhilJS Axum
+//!
+//! [`PhilJsAssets`] serves the build output directory, resolves entry files
+//! through the build manifest so [`render_document`](crate::render_document)
+//! links the hashed bundles, and falls back to `index.html` for client-side
+//! routes.
+//!
+//! ```rust,ignore
+//! let app = Router::new()
+//!     .route("/", get(index))
+//!     .layer(PhilJsAssets::new("dist").mount("/static").spa_fallback(true));
+//! ```
+
+use axum::{
+    body::Body,
+    http::{header, HeaderValue, Method, Request, Response, StatusCode, Uri},
+};
+use philjs::meta::{set_asset_manifest, AssetManifest};
+use parking_lot::RwLock;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tower_http::services::ServeDir;
+use tracing::{debug, warn};
+
+/// Manifest file names looked up in the asset directory, in order
+pub const MANIFEST_FILES: &[&str] = &["manifest.json", ".vite/manifest.json", "asset-manifest.json"];
+
+static DOCUMENT_ASSETS: RwLock<Option<DocumentAssets>> = RwLock::new(None);
+
+/// Resolved entry URLs used by [`render_document`](crate::render_document)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentAssets {
+    /// Stylesheet URLs
+    pub styles: Vec<String>,
+    /// Module script URLs
+    pub scripts: Vec<String>,
+}
+
+impl Default for DocumentAssets {
+    fn default() -> Self {
+        Self {
+            styles: vec!["/static/styles.css".to_string()],
+            scripts: vec!["/static/app.js".to_string()],
+        }
+    }
+}
+
+impl DocumentAssets {
+    /// The entries installed by [`PhilJsAssets`], or the unhashed defaults
+    pub fn current() -> Self {
+        DOCUMENT_ASSETS.read().clone().unwrap_or_default()
+    }
+
+    /// `<link>` tags for the `<head>`
+    pub fn head_tags(&self) -> String {
+        self.styles
+            .iter()
+            .map(|href| format!(r#"<link rel="stylesheet" href="{}">"#, escape_attr(href)))
+            .collect::<Vec<_>>()
+            .join("\n    ")
+    }
+
+    /// `<script>` tags for the end of the `<body>`
+    pub fn body_tags(&self) -> String {
+        self.scripts
+            .iter()
+            .map(|src| format!(r#"<script type="module" src="{}"></script>"#, escape_attr(src)))
+            .collect::<Vec<_>>()
+            .join("\n    ")
+    }
+}
+
+fn escape_attr(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;")
+}
+
+/// Layer serving build assets with SPA fallback
+#[derive(Clone)]
+pub struct PhilJsAssets {
+    dir: PathBuf,
+    mount: String,
+    index: String,
+    spa_fallback: bool,
+    immutable_max_age: u64,
+    manifest: AssetManifest,
+    scripts: Vec<String>,
+    styles: Vec<String>,
+}
+
+impl PhilJsAssets {
+    /// Serve `dir`, reading its build manifest if present
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let manifest = load_manifest(&dir).unwrap_or_default();
+        Self {
+            dir,
+            mount: "/static".to_string(),
+            index: "index.html".to_string(),
+            spa_fallback: false,
+            immutable_max_age: 31_536_000,
+            manifest,
+            scripts: vec!["app.js".to_string()],
+            styles: vec!["styles.css".to_string()],
+        }
+    }
+
+    /// URL prefix the assets are served under (default: `/static`)
+    pub fn mount(mut self, mount: impl Into<String>) -> Self {
+        let mount = mount.into();
+        self.mount = format!("/{}", mount.trim_matches('/'));
+        self
+    }
+
+    /// Serve the index file for unmatched HTML navigations
+    pub fn spa_fallback(mut self, enabled: bool) -> Self {
+        self.spa_fallback = enabled;
+        self
+    }
+
+    /// Index file used for the SPA fallback (default: `index.html`)
+    pub fn index(mut self, index: impl Into<String>) -> Self {
+        self.index = index.into();
+        self
+    }
+
+    /// `max-age` for content-hashed files (default: one year)
+    pub fn immutable_max_age(mut self, seconds: u64) -> Self {
+        self.immutable_max_age = seconds;
+        self
+    }
+
+    /// Replace the entry scripts (default: `app.js`)
+    pub fn scripts(mut self, scripts: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.scripts = scripts.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Replace the entry stylesheets (default: `styles.css`)
+    pub fn styles(mut self, styles: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.styles = styles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The manifest with the mount point as its base
+    pub fn manifest(&self) -> AssetManifest {
+        self.manifest.clone().base(self.mount.clone())
+    }
+
+    /// Resolved entry URLs
+    pub fn document_assets(&self) -> DocumentAssets {
+        let manifest = self.manifest();
+        DocumentAssets {
+            styles: self.styles.iter().map(|s| manifest.resolve(s)).collect(),
+            scripts: self.scripts.iter().map(|s| manifest.resolve(s)).collect(),
+        }
+    }
+
+    /// Install the manifest for `Stylesheet` and the entry tags for
+    /// `render_document`; done automatically when the layer is applied
+    pub fn install(&self) {
+        set_asset_manifest(self.manifest());
+        *DOCUMENT_ASSETS.write() = Some(self.document_assets());
+    }
+}
+
+fn load_manifest(dir: &Path) -> Option<AssetManifest> {
+    for name in MANIFEST_FILES {
+        let path = dir.join(name);
+        let Ok(json) = std::fs::read_to_string(&path) else { continue };
+        match AssetManifest::from_json(&json) {
+            Ok(manifest) => {
+                debug!(path = %path.display(), "Loaded asset manifest");
+                return Some(manifest);
+            }
+            Err(e) => warn!(path = %path.display(), "Invalid asset manifest: {}", e),
+        }
+    }
+    None
+}
+
+/// Whether a file name contains a content hash, e.g. `app.3f2a1c9b.js`
+fn is_content_hashed(name: &str) -> bool {
+    let stem = match name.rsplit_once('.') {
+        Some((stem, _)) => stem,
+        None => return false,
+    };
+    stem.split(['.', '-'])
+        .skip(1)
+        .any(|part| {
+            part.len() >= 8
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && part.chars().any(|c| c.is_ascii_digit())
+        })
+}
+
+/// Whether a request is a browser navigation that may fall back to the SPA
+fn is_navigation<B>(req: &Request<B>) -> bool {
+    let accepts_html = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("text/html"));
+    let has_extension = req
+        .uri()
+        .path()
+        .rsplit('/')
+        .next()
+        .is_some_and(|segment| segment.contains('.'));
+    req.method() == Method::GET && accepts_html && !has_extension
+}
+
+impl<S> Layer<S> for PhilJsAssets {
+    type Service = PhilJsAssetsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        self.install();
+        PhilJsAssetsService {
+            inner,
+            serve_dir: ServeDir::new(&self.dir)
+                .precompressed_br()
+                .precompressed_gzip()
+                .append_index_html_on_directories(false),
+            assets: self.clone(),
+        }
+    }
+}
+
+/// Asset serving service
+#[derive(Clone)]
+pub struct PhilJsAssetsService<S> {
+    inner: S,
+    serve_dir: ServeDir,
+    assets: PhilJsAssets,
+}
+
+impl<S> Service<Request<Body>> for PhilJsAssetsService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let is_read = matches!(*req.method(), Method::GET | Method::HEAD);
+        let asset_path = req
+            .uri()
+            .path()
+            .strip_prefix(self.assets.mount.as_str())
+            .filter(|rest| rest.starts_with('/'))
+            .map(str::to_string);
+
+        if let (true, Some(path)) = (is_read, asset_path) {
+            let mut serve_dir = self.serve_dir.clone();
+            let immutable_max_age = self.assets.immutable_max_age;
+            if let Ok(uri) = path.parse::<Uri>() {
+                *req.uri_mut() = uri;
+            }
+            return Box::pin(async move {
+                let res = match serve_dir.try_call(req).await {
+                    Ok(res) => res.map(Body::new),
+                    Err(e) => {
+                        warn!("Failed to serve asset: {}", e);
+                        return Ok(Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::empty())
+                            .unwrap());
+                    }
+                };
+                Ok(with_cache_control(res, &path, immutable_max_age))
+            });
+        }
+
+        let fallback = (self.assets.spa_fallback && is_navigation(&req))
+            .then(|| self.assets.dir.join(&self.assets.index));
+        let fut = self.inner.call(req);
+
+        Box::pin(async move {
+            let res = fut.await?;
+            if res.status() != StatusCode::NOT_FOUND {
+                return Ok(res);
+            }
+            let Some(index) = fallback else { return Ok(res) };
+            match tokio::fs::read(&index).await {
+                Ok(html) => Ok(Response::builder()
+                    .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+                    .header(header::CACHE_CONTROL, "no-cache")
+                    .body(Body::from(html))
+                    .unwrap()),
+                Err(_) => Ok(res),
+            }
+        })
+    }
+}
+
+fn with_cache_control(mut res: Response<Body>, path: &str, immutable_max_age: u64) -> Response<Body> {
+    if !res.status().is_success() && res.status() != StatusCode::NOT_MODIFIED {
+        return res;
+    }
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let value = if is_content_hashed(name) {
+        format!("public, max-age={}, immutable", immutable_max_age)
+    } else if name.ends_with(".html") {
+        "no-cache".to_string()
+    } else {
+        "public, max-age=0, must-revalidate".to_string()
+    };
+    if let Ok(value) = HeaderValue::from_str(&value) {
+        res.headers_mut().insert(header::CACHE_CONTROL, value);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    fn dist() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("philjs-axum-assets-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("assets")).unwrap();
+        std::fs::write(dir.join("manifest.json"), r#"{"app.js": {"file": "assets/app.3f2a1c9b.js"}}"#).unwrap();
+        std::fs::write(dir.join("assets/app.3f2a1c9b.js"), "console.log(1)").unwrap();
+        std::fs::write(dir.join("assets/app.3f2a1c9b.js.gz"), "gzipped").unwrap();
+        std::fs::write(dir.join("index.html"), "<div id=app></div>").unwrap();
+        dir
+    }
+
+    async fn get_path(app: &Router, path: &str, headers: &[(&str, &str)]) -> Response<Body> {
+        let mut req = Request::get(path);
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        app.clone().oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[test]
+    fn test_content_hash_detection() {
+        assert!(is_content_hashed("app.3f2a1c9b.js"));
+        assert!(is_content_hashed("chunk-a1b2c3d4.css"));
+        assert!(!is_content_hashed("styles.css"));
+        assert!(!is_content_hashed("jquery.min.js"));
+    }
+
+    #[test]
+    fn test_document_assets_resolve_through_manifest() {
+        let assets = PhilJsAssets::new(dist()).mount("static/");
+        let resolved = assets.document_assets();
+        assert_eq!(resolved.scripts, vec!["/static/assets/app.3f2a1c9b.js"]);
+        assert_eq!(resolved.styles, vec!["/static/styles.css"]);
+        assert!(resolved.body_tags().contains(r#"src="/static/assets/app.3f2a1c9b.js""#));
+    }
+
+    #[tokio::test]
+    async fn test_serves_assets_and_spa_fallback() {
+        let app = Router::new()
+            .route("/api", get(|| async { "api" }))
+            .layer(PhilJsAssets::new(dist()).spa_fallback(true));
+
+        let res = get_path(&app, "/static/assets/app.3f2a1c9b.js", &[("accept-encoding", "gzip")]).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res.headers()[header::CACHE_CONTROL].to_str().unwrap().contains("immutable"));
+        assert_eq!(res.headers()[header::CONTENT_ENCODING], "gzip");
+
+        let res = get_path(&app, "/dashboard/settings", &[("accept", "text/html")]).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"<div id=app></div>");
+
+        assert_eq!(get_path(&app, "/missing.png", &[("accept", "text/html")]).await.status(), StatusCode::NOT_FOUND);
+        assert_eq!(get_path(&app, "/api", &[]).await.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,226 @@
+//! Request-scoped PhilJS context for Axum
+//!
+//! [`ContextLayer`] makes shared values and request extensions available
+//! through `philjs::use_context` while the request is handled, so handlers,
+//! `#[server]` functions, and SSR components can reach them without threading
+//! state through every call.
+//!
+//! ```rust,ignore
+//! #[derive(Clone)]
+//! struct Db(PgPool);
+//!
+//! #[component]
+//! fn Posts() -> impl IntoView {
+//!     let db = use_context::<Db>().expect("Db provided by ContextLayer");
+//!     ...
+//! }
+//!
+//! let app = Router::new()
+//!     .route("/posts", get(posts))
+//!     .layer(ContextLayer::new().provide(Db(pool)).extension::<CurrentUser>());
+//! ```
+//!
+//! The context is installed on every poll of the request future, so it stays
+//! available across `.await` points even when the task moves between threads.
+
+use axum::{
+    body::Body,
+    http::{Request, Response},
+};
+use philjs::reactive::context::with_context_scope;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// Installs one value into the current context scope
+type Provider = Arc<dyn Fn() + Send + Sync>;
+
+/// Produces a provider for a request, if the value is available
+type ProviderSource = Arc<dyn Fn(&Request<Body>) -> Option<Provider> + Send + Sync>;
+
+/// Layer providing values to `use_context` for the duration of a request
+#[derive(Clone, Default)]
+pub struct ContextLayer {
+    sources: Vec<ProviderSource>,
+}
+
+impl ContextLayer {
+    /// Create a layer providing nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Provide a value shared by every request, such as a database pool
+    pub fn provide<T>(mut self, value: T) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let provider: Provider = Arc::new(move || philjs::provide_context(value.clone()));
+        self.sources.push(Arc::new(move |_| Some(provider.clone())));
+        self
+    }
+
+    /// Provide a request extension, when present
+    ///
+    /// Extensions are read when the request reaches this layer, so add this
+    /// layer inside (before) the layers inserting them.
+    pub fn extension<T>(self) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.with(|req| req.extensions().get::<T>().cloned())
+    }
+
+    /// Provide a value computed from each request
+    pub fn with<T, F>(mut self, f: F) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Fn(&Request<Body>) -> Option<T> + Send + Sync + 'static,
+    {
+        self.sources.push(Arc::new(move |req| {
+            let value = f(req)?;
+            Some(Arc::new(move || philjs::provide_context(value.clone())) as Provider)
+        }));
+        self
+    }
+}
+
+impl<S> Layer<S> for ContextLayer {
+    type Service = ContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ContextService {
+            inner,
+            sources: Arc::new(self.sources.clone()),
+        }
+    }
+}
+
+/// Context middleware service
+#[derive(Clone)]
+pub struct ContextService<S> {
+    inner: S,
+    sources: Arc<Vec<ProviderSource>>,
+}
+
+impl<S> Service<Request<Body>> for ContextService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = ContextScoped<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let providers: Vec<Provider> = self.sources.iter().filter_map(|source| source(&req)).collect();
+        let providers = Arc::new(providers);
+        // Run the handler's synchronous part (e.g. SSR) inside the scope too
+        let inner = with_context_scope(|| {
+            provide_all(&providers);
+            self.inner.call(req)
+        });
+        ContextScoped {
+            inner: Box::pin(inner),
+            providers,
+        }
+    }
+}
+
+fn provide_all(providers: &[Provider]) {
+    for provide in providers {
+        provide();
+    }
+}
+
+/// Future running with request context installed on every poll
+pub struct ContextScoped<F> {
+    inner: Pin<Box<F>>,
+    providers: Arc<Vec<Provider>>,
+}
+
+impl<F: Future> Future for ContextScoped<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let providers = self.providers.clone();
+        with_context_scope(|| {
+            provide_all(&providers);
+            self.inner.as_mut().poll(cx)
+        })
+    }
+}
+
+/// Run a future with values provided to `use_context` on every poll
+///
+/// Useful outside the layer, e.g. in background tasks spawned by a handler.
+pub fn scope_context<F, T>(value: T, future: F) -> ContextScoped<F>
+where
+    F: Future,
+    T: Clone + Send + Sync + 'static,
+{
+    let provider: Provider = Arc::new(move || philjs::provide_context(value.clone()));
+    ContextScoped {
+        inner: Box::pin(future),
+        providers: Arc::new(vec![provider]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Extension, Router};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    #[derive(Clone)]
+    struct Db(&'static str);
+
+    #[derive(Clone)]
+    struct RequestPath(String);
+
+    async fn handler() -> String {
+        tokio::task::yield_now().await;
+        let db = philjs::use_context::<Db>().map(|db| db.0).unwrap_or("none");
+        let path = philjs::use_context::<RequestPath>().map(|p| p.0).unwrap_or_default();
+        let user = philjs::use_context::<u32>().unwrap_or_default();
+        format!("{} {} {}", db, path, user)
+    }
+
+    #[tokio::test]
+    async fn test_context_available_in_handler() {
+        let app = Router::new()
+            .route("/posts", get(handler))
+            .layer(
+                ContextLayer::new()
+                    .provide(Db("pool"))
+                    .with(|req| Some(RequestPath(req.uri().path().to_string())))
+                    .extension::<u32>(),
+            )
+            .layer(Extension(7u32));
+
+        let res = app
+            .oneshot(Request::get("/posts").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"pool /posts 7");
+        assert!(philjs::use_context::<Db>().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_scope_context() {
+        let value = scope_context(Db("scoped"), async {
+            tokio::task::yield_now().await;
+            philjs::use_context::<Db>().map(|db| db.0)
+        })
+        .await;
+        assert_eq!(value, Some("scoped"));
+    }
+}
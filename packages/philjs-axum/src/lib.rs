@@ -8,6 +8,10 @@
 //! - **Extractors**: Type-safe request data extraction
 //! - **Handlers**: Common handler patterns
 //! - **WebSocket**: LiveView support for real-time updates
+//! - **Assets**: Manifest-aware static serving with SPA fallback
+//! - **Context**: Request-scoped values for `use_context`
+//! - **Server Functions**: `#[server]` functions mounted with `server_fn_routes()`
+//! - **Sessions**: Signed-cookie sessions with pluggable stores
 //! - **Tower Middleware**: Compatible middleware layers
 //!
 //! ## Quick Start
@@ -35,31 +39,44 @@
 
 #![warn(missing_docs)]
 
+pub mod assets;
+pub mod context;
 pub mod extractors;
 pub mod handlers;
 pub mod middleware;
+pub mod server_fn;
+pub mod session;
 pub mod ssr;
 pub mod state;
 pub mod websocket;
 pub mod tower;
 
+pub use assets::{DocumentAssets, PhilJsAssets};
+pub use context::{scope_context, ContextLayer};
 pub use extractors::{PhilJsJson, PhilJsQuery, SsrContext};
 pub use handlers::{health_check, not_found, ApiResponse};
 pub use middleware::PhilJsLayer;
-pub use ssr::{HtmlDocument, MetaTag, Script};
+pub use server_fn::server_fn_routes;
+pub use session::{Key, MemoryStore, Session, SessionData, SessionError, SessionLayer, SessionStore};
+pub use ssr::{HtmlDocument, HtmlStream, MetaTag, Script, render_stream, render_stream_document};
 pub use state::{AppState, AppStateBuilder, Environment, CacheStats};
-pub use websocket::{LiveViewSocket, LiveViewHandler, BroadcastChannel, PresenceTracker};
-pub use tower::{TracingLayer, TimeoutLayer, RequestIdLayer, SecurityHeadersLayer, RateLimitLayer};
+pub use websocket::{LiveViewSocket, LiveViewHandler, LiveViewHub, live_view_handler, BroadcastChannel, PresenceTracker};
+pub use tower::{ETagLayer, TracingLayer, TimeoutLayer, RequestIdLayer, SecurityHeadersLayer, RateLimitLayer};
 
 /// Prelude - import commonly used items
 pub mod prelude {
+    pub use crate::assets::PhilJsAssets;
+    pub use crate::context::ContextLayer;
     pub use crate::extractors::{PhilJsJson, PhilJsQuery, SsrContext};
     pub use crate::handlers::{health_check, not_found, ApiResponse, PaginationParams};
     pub use crate::middleware::PhilJsLayer;
-    pub use crate::ssr::{HtmlDocument, MetaTag, Script, SeoBuilder};
+    pub use crate::server_fn::server_fn_routes;
+    pub use crate::session::{Key, MemoryStore, Session, SessionLayer, SessionStore};
+    pub use crate::ssr::{HtmlDocument, HtmlStream, MetaTag, Script, SeoBuilder, render_stream, render_stream_document};
+    pub use philjs::StreamingConfig;
     pub use crate::state::{AppState, AppStateBuilder, Environment};
-    pub use crate::websocket::{LiveViewSocket, LiveViewHandler, BroadcastChannel, PresenceTracker};
-    pub use crate::tower::{TracingLayer, TimeoutLayer, RequestIdLayer, SecurityHeadersLayer, RateLimitLayer};
+    pub use crate::websocket::{LiveViewSocket, LiveViewHandler, LiveViewHub, BroadcastChannel, PresenceTracker};
+    pub use crate::tower::{ETagLayer, TracingLayer, TimeoutLayer, RequestIdLayer, SecurityHeadersLayer, RateLimitLayer};
     pub use axum::{Router, routing::{get, post, put, patch, delete}, response::{Html, Json, IntoResponse}, http::StatusCode};
     pub use axum::extract::{State, Path, Query};
     pub use philjs::prelude::*;
@@ -107,6 +124,7 @@ where
     V: philjs::IntoView,
 {
     let body_html = philjs::render_to_string(f);
+    let assets = assets::DocumentAssets::current();
 
     let html = format!(
         r#"<!DOCTYPE html>
@@ -115,14 +133,17 @@ where
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
     <title>{}</title>
-    <link rel="stylesheet" href="/static/styles.css">
+    {}
 </head>
 <body>
     <div id="app">{}</div>
-    <script type="module" src="/static/app.js"></script>
+    {}
 </body>
 </html>"#,
-        title, body_html
+        title,
+        assets.head_tags(),
+        body_html,
+        assets.body_tags()
     );
 
     Html(html)
@@ -0,0 +1,347 @@
+//! Server function routes for PhilJS Axum
+//!
+//! [`server_fn_routes`] mounts every function registered with
+//! `philjs::server::register_server_fn` (which `#[server]`, `#[action]`, and
+//! `#[loader]` do for you) at its own path. The request is available to the
+//! function through `philjs::server::server_context()`, and the session
+//! through [`Session::current`](crate::session::Session::current) when a
+//! `SessionLayer` is applied.
+//!
+//! ```rust,ignore
+//! let app = Router::new()
+//!     .route("/", get(index))
+//!     .merge(philjs_axum::server_fn_routes())
+//!     .layer(sessions);
+//! ```
+//!
+//! Input is read according to the function's encoding:
+//!
+//! - `GET` functions (`#[loader]`) read the query string
+//! - `application/x-www-form-urlencoded` bodies (`#[action]`) are decoded
+//!   with `philjs::server::form`: values stay strings and repeated names
+//!   become arrays; plain HTML form posts are redirected back to the
+//!   referring page with `303 See Other`
+//! - everything else is read as JSON
+//!
+//! Streaming server functions respond with newline-delimited JSON.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{ConnectInfo, Request},
+    http::{header, request::Parts, HeaderMap, Method, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{on, MethodFilter},
+    Router,
+};
+use futures::StreamExt;
+use philjs::server::{self, form, ServerContext, ServerError};
+use std::net::SocketAddr;
+use tracing::info;
+
+/// Default maximum request body size
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Content type of streaming server function responses
+pub const NDJSON: &str = "application/x-ndjson";
+
+/// Router serving every registered server function
+///
+/// Functions must be registered before this is called.
+pub fn server_fn_routes<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let mut router = Router::new();
+
+    let functions = server::registered_server_fns();
+    let streaming = server::registered_streaming_server_fns();
+    info!(count = functions.len() + streaming.len(), "Mounting server functions");
+
+    for path in functions {
+        router = router.route(
+            path,
+            on(method_filter(path), move |req: Request| handle(path, req, false)),
+        );
+    }
+    for path in streaming {
+        router = router.route(
+            path,
+            on(method_filter(path), move |req: Request| handle(path, req, true)),
+        );
+    }
+    router
+}
+
+fn method_filter(path: &str) -> MethodFilter {
+    match server::server_fn_method(path).unwrap_or("POST") {
+        "GET" => MethodFilter::GET,
+        "PUT" => MethodFilter::PUT,
+        "PATCH" => MethodFilter::PATCH,
+        "DELETE" => MethodFilter::DELETE,
+        _ => MethodFilter::POST,
+    }
+}
+
+/// Build the server function context from request parts
+pub fn server_context(parts: &Parts) -> ServerContext {
+    let mut context = ServerContext::new();
+    for (name, value) in &parts.headers {
+        if let Ok(value) = value.to_str() {
+            context.headers.insert(name.as_str().to_lowercase(), value.to_string());
+        }
+    }
+    for value in parts.headers.get_all(header::COOKIE) {
+        let Ok(value) = value.to_str() else { continue };
+        for pair in value.split(';') {
+            if let Some((name, value)) = pair.trim().split_once('=') {
+                context.cookies.insert(name.to_string(), value.to_string());
+            }
+        }
+    }
+    context.client_ip = client_ip(parts);
+    if let Some(id) = parts.headers.get("x-request-id").and_then(|v| v.to_str().ok()) {
+        context.request_id = id.to_string();
+    }
+    context
+}
+
+fn client_ip(parts: &Parts) -> Option<String> {
+    parts
+        .headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .map(|ip| ip.trim().to_string())
+        .or_else(|| {
+            parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip().to_string())
+        })
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: header::HeaderName) -> &'a str {
+    headers.get(name).and_then(|v| v.to_str().ok()).unwrap_or_default()
+}
+
+fn wants_redirect(headers: &HeaderMap) -> bool {
+    form::wants_redirect(
+        header_str(headers, header::CONTENT_TYPE),
+        header_str(headers, header::ACCEPT),
+    )
+}
+
+fn read_input(parts: &Parts, body: &Bytes) -> Result<String, ServerError> {
+    if parts.method == Method::GET {
+        return match parts.uri.query() {
+            Some(query) if !query.is_empty() => form::url_encoded_to_json(query.as_bytes()),
+            _ => Ok("null".to_string()),
+        };
+    }
+    if header_str(&parts.headers, header::CONTENT_TYPE).starts_with(form::URL_ENCODED) {
+        return form::url_encoded_to_json(body);
+    }
+    let body = std::str::from_utf8(body)
+        .map_err(|_| ServerError::bad_request("Request body is not valid UTF-8"))?;
+    Ok(if body.trim().is_empty() { "null".to_string() } else { body.to_string() })
+}
+
+fn json_response(status: StatusCode, body: String) -> Response {
+    (status, [(header::CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+fn error_response(error: ServerError) -> Response {
+    let status = StatusCode::from_u16(error.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    json_response(status, serde_json::to_string(&error).unwrap_or_default())
+}
+
+fn redirect_back(parts: &Parts) -> Response {
+    let location = parts
+        .headers
+        .get(header::REFERER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("/")
+        .to_string();
+    (StatusCode::SEE_OTHER, [(header::LOCATION, location)]).into_response()
+}
+
+async fn handle(path: &'static str, req: Request, streaming: bool) -> Response {
+    let (parts, body) = req.into_parts();
+
+    let body = match axum::body::to_bytes(body, DEFAULT_BODY_LIMIT).await {
+        Ok(body) => body,
+        Err(_) => return error_response(ServerError::new("Request body too large").with_status(413)),
+    };
+    let input = match read_input(&parts, &body) {
+        Ok(input) => input,
+        Err(error) => return error_response(error),
+    };
+    let context = server_context(&parts);
+
+    if streaming {
+        return match server::with_server_context(context, server::call_streaming_server_fn(path, input)).await {
+            Ok(stream) => ndjson_response(stream),
+            Err(error) => error_response(error),
+        };
+    }
+
+    match server::with_server_context(context, server::call_server_fn(path, input)).await {
+        Ok(_) if wants_redirect(&parts.headers) => redirect_back(&parts),
+        Ok(output) => json_response(StatusCode::OK, output),
+        Err(error) => error_response(error),
+    }
+}
+
+/// One JSON value per line; an error ends the stream with `{"error": ...}`
+fn ndjson_response(stream: server::ServerFnStream) -> Response {
+    let lines = stream
+        .scan(false, |failed, item| {
+            if *failed {
+                return futures::future::ready(None);
+            }
+            let line = match item {
+                Ok(json) => json,
+                Err(error) => {
+                    *failed = true;
+                    serde_json::json!({ "error": error }).to_string()
+                }
+            };
+            futures::future::ready(Some(Ok::<_, std::convert::Infallible>(format!("{}\n", line))))
+        });
+
+    (
+        [
+            (header::CONTENT_TYPE, NDJSON),
+            (header::HeaderName::from_static("x-accel-buffering"), "no"),
+        ],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+    use philjs::server::{ServerFn, ServerResult, StreamingServerFn};
+    use std::future::Future;
+    use std::pin::Pin;
+    use tower::ServiceExt;
+
+    struct Greet;
+
+    impl ServerFn for Greet {
+        type Input = serde_json::Value;
+        type Output = String;
+        const PATH: &'static str = "/api/axum_greet";
+
+        fn run(input: serde_json::Value) -> Pin<Box<dyn Future<Output = ServerResult<String>> + Send>> {
+            let ip = server::server_context().and_then(|ctx| ctx.client_ip).unwrap_or_default();
+            Box::pin(async move {
+                match input["name"].as_str() {
+                    Some(name) => Ok(format!("hi {} from {}", name, ip)),
+                    None => Err(ServerError::bad_request("name required")),
+                }
+            })
+        }
+    }
+
+    struct Search;
+
+    impl ServerFn for Search {
+        type Input = serde_json::Value;
+        type Output = serde_json::Value;
+        const PATH: &'static str = "/api/axum_search";
+        const METHOD: &'static str = "GET";
+
+        fn run(input: serde_json::Value) -> Pin<Box<dyn Future<Output = ServerResult<Self::Output>> + Send>> {
+            Box::pin(async move { Ok(input) })
+        }
+    }
+
+    struct Count;
+
+    impl StreamingServerFn for Count {
+        type Input = u32;
+        type Item = u32;
+        const PATH: &'static str = "/api/axum_count";
+
+        fn run(to: u32) -> Pin<Box<dyn Future<Output = ServerResult<Pin<Box<dyn futures::Stream<Item = ServerResult<u32>> + Send>>>> + Send>> {
+            Box::pin(async move {
+                let items = (1..=to).map(Ok);
+                Ok(Box::pin(futures::stream::iter(items)) as Pin<Box<dyn futures::Stream<Item = _> + Send>>)
+            })
+        }
+    }
+
+    fn app() -> Router {
+        server::register_server_fn::<Greet>();
+        server::register_server_fn::<Search>();
+        server::register_streaming_server_fn::<Count>();
+        server_fn_routes()
+    }
+
+    async fn send(req: axum::http::Request<Body>) -> (StatusCode, HeaderMap, String) {
+        let res = app().oneshot(req).await.unwrap();
+        let status = res.status();
+        let headers = res.headers().clone();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        (status, headers, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_json_server_fn_with_context() {
+        let req = axum::http::Request::post("/api/axum_greet")
+            .header("x-forwarded-for", "10.0.0.7")
+            .body(Body::from(r#"{"name":"ada"}"#))
+            .unwrap();
+        let (status, _, body) = send(req).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "\"hi ada from 10.0.0.7\"");
+
+        let req = axum::http::Request::post("/api/axum_greet").body(Body::from("{}")).unwrap();
+        let (status, _, body) = send(req).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("name required"));
+    }
+
+    #[tokio::test]
+    async fn test_encodings() {
+        let req = axum::http::Request::get("/api/axum_search?q=rust&page=2&tag=a&tag=b").body(Body::empty()).unwrap();
+        let (status, _, body) = send(req).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, r#"{"page":"2","q":"rust","tag":["a","b"]}"#);
+
+        let req = axum::http::Request::post("/api/axum_search").body(Body::empty()).unwrap();
+        assert_eq!(send(req).await.0, StatusCode::METHOD_NOT_ALLOWED);
+
+        let req = axum::http::Request::post("/api/axum_greet")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(header::ACCEPT, "text/html")
+            .header(header::REFERER, "/signup")
+            .body(Body::from("name=ada"))
+            .unwrap();
+        let (status, headers, _) = send(req).await;
+        assert_eq!(status, StatusCode::SEE_OTHER);
+        assert_eq!(headers[header::LOCATION], "/signup");
+
+        // Scripted form posts get JSON, with values kept as strings
+        let req = axum::http::Request::post("/api/axum_greet")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(header::ACCEPT, "application/json")
+            .body(Body::from("name=42"))
+            .unwrap();
+        let (status, _, body) = send(req).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.starts_with("\"hi 42 from"));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_server_fn() {
+        let req = axum::http::Request::post("/api/axum_count").body(Body::from("3")).unwrap();
+        let (status, headers, body) = send(req).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[header::CONTENT_TYPE], NDJSON);
+        assert_eq!(body, "1\n2\n3\n");
+    }
+}
@@ -0,0 +1,765 @@
+//! Sessions for PhilJS Axum
+//!
+//! [`SessionLayer`] loads the session named by a signed cookie from a
+//! [`SessionStore`], exposes it to handlers through the [`Session`] extractor,
+//! and persists changes once the response is ready. The session is also
+//! available to `#[server]` functions via [`Session::current`].
+//!
+//! ```rust,ignore
+//! let sessions = SessionLayer::new(MemoryStore::new(), Key::generate())
+//!     .ttl(Duration::from_secs(60 * 60 * 24))
+//!     .secure(true);
+//!
+//! let state = AppState::builder().with_sessions(sessions.clone()).build();
+//!
+//! async fn visits(session: Session) -> String {
+//!     let visits = session.get::<u32>("visits").unwrap_or(0) + 1;
+//!     session.insert("visits", visits);
+//!     format!("{} visits", visits)
+//! }
+//!
+//! let app = Router::new()
+//!     .route("/", get(visits))
+//!     .layer(sessions)
+//!     .with_state(state);
+//! ```
+
+use axum::{
+    async_trait,
+    body::Body,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderValue, Request, Response, StatusCode},
+    response::{IntoResponse, Response as AxumResponse},
+};
+use cookie::{Cookie, CookieJar, SameSite};
+use parking_lot::{Mutex, RwLock};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+use tracing::warn;
+
+pub use cookie::Key;
+
+/// Default session cookie name
+pub const SESSION_COOKIE: &str = "philjs_session";
+
+/// Default session lifetime
+pub const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(60 * 60 * 24 * 7);
+
+/// Values stored in a session
+pub type SessionData = HashMap<String, serde_json::Value>;
+
+/// Session store errors
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    /// The backing store failed
+    #[error("session store error: {0}")]
+    Store(String),
+    /// Session data could not be (de)serialized
+    #[error("session serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Backend persisting session data
+#[async_trait]
+pub trait SessionStore: Send + Sync + 'static {
+    /// Load a session; expired sessions load as `None`
+    async fn load(&self, id: &str) -> Result<Option<SessionData>, SessionError>;
+
+    /// Save a session, replacing its data and expiry
+    async fn save(&self, id: &str, data: &SessionData, ttl: Duration) -> Result<(), SessionError>;
+
+    /// Delete a session
+    async fn delete(&self, id: &str) -> Result<(), SessionError>;
+}
+
+// ============================================================================
+// Memory Store
+// ============================================================================
+
+/// In-process session store, for development and single-instance deployments
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    sessions: Arc<RwLock<HashMap<String, (SessionData, Instant)>>>,
+}
+
+impl MemoryStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored sessions, including expired ones not yet cleaned up
+    pub fn len(&self) -> usize {
+        self.sessions.read().len()
+    }
+
+    /// Whether the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.sessions.read().is_empty()
+    }
+
+    /// Remove expired sessions
+    pub fn cleanup(&self) {
+        let now = Instant::now();
+        self.sessions.write().retain(|_, (_, expires_at)| *expires_at > now);
+    }
+}
+
+#[async_trait]
+impl SessionStore for MemoryStore {
+    async fn load(&self, id: &str) -> Result<Option<SessionData>, SessionError> {
+        Ok(self
+            .sessions
+            .read()
+            .get(id)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(data, _)| data.clone()))
+    }
+
+    async fn save(&self, id: &str, data: &SessionData, ttl: Duration) -> Result<(), SessionError> {
+        self.sessions
+            .write()
+            .insert(id.to_string(), (data.clone(), Instant::now() + ttl));
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), SessionError> {
+        self.sessions.write().remove(id);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Redis Store
+// ============================================================================
+
+/// Redis-backed session store; expiry is handled by Redis key TTLs
+#[cfg(feature = "redis-store")]
+#[derive(Clone)]
+pub struct RedisStore {
+    connection: redis::aio::ConnectionManager,
+    prefix: String,
+}
+
+#[cfg(feature = "redis-store")]
+impl RedisStore {
+    /// Create a store from a connection manager
+    pub fn new(connection: redis::aio::ConnectionManager) -> Self {
+        Self {
+            connection,
+            prefix: "philjs:session:".to_string(),
+        }
+    }
+
+    /// Connect to a Redis URL
+    pub async fn connect(url: &str) -> Result<Self, SessionError> {
+        let client = redis::Client::open(url).map_err(|e| SessionError::Store(e.to_string()))?;
+        let connection = redis::aio::ConnectionManager::new(client)
+            .await
+            .map_err(|e| SessionError::Store(e.to_string()))?;
+        Ok(Self::new(connection))
+    }
+
+    /// Set the key prefix
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn key(&self, id: &str) -> String {
+        format!("{}{}", self.prefix, id)
+    }
+}
+
+#[cfg(feature = "redis-store")]
+#[async_trait]
+impl SessionStore for RedisStore {
+    async fn load(&self, id: &str) -> Result<Option<SessionData>, SessionError> {
+        use redis::AsyncCommands;
+        let mut connection = self.connection.clone();
+        let value: Option<String> = connection
+            .get(self.key(id))
+            .await
+            .map_err(|e| SessionError::Store(e.to_string()))?;
+        value.map(|v| serde_json::from_str(&v)).transpose().map_err(Into::into)
+    }
+
+    async fn save(&self, id: &str, data: &SessionData, ttl: Duration) -> Result<(), SessionError> {
+        use redis::AsyncCommands;
+        let mut connection = self.connection.clone();
+        let value = serde_json::to_string(data)?;
+        connection
+            .set_ex::<_, _, ()>(self.key(id), value, ttl.as_secs().max(1))
+            .await
+            .map_err(|e| SessionError::Store(e.to_string()))
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), SessionError> {
+        use redis::AsyncCommands;
+        let mut connection = self.connection.clone();
+        connection
+            .del::<_, ()>(self.key(id))
+            .await
+            .map_err(|e| SessionError::Store(e.to_string()))
+    }
+}
+
+// ============================================================================
+// SQLx Store
+// ============================================================================
+
+/// PostgreSQL session store built on SQLx
+///
+/// Call [`SqlxStore::migrate`] once at startup to create the table.
+#[cfg(feature = "sqlx-store")]
+#[derive(Clone)]
+pub struct SqlxStore {
+    pool: sqlx::PgPool,
+    table: String,
+}
+
+#[cfg(feature = "sqlx-store")]
+impl SqlxStore {
+    /// Create a store using the `philjs_sessions` table
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self {
+            pool,
+            table: "philjs_sessions".to_string(),
+        }
+    }
+
+    /// Use a different table name
+    pub fn table(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    /// Create the sessions table if it does not exist
+    pub async fn migrate(&self) -> Result<(), SessionError> {
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {} (id TEXT PRIMARY KEY, data TEXT NOT NULL, expires_at BIGINT NOT NULL)",
+            self.table
+        );
+        sqlx::query(&sql)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| SessionError::Store(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Delete expired sessions
+    pub async fn cleanup(&self) -> Result<u64, SessionError> {
+        let sql = format!("DELETE FROM {} WHERE expires_at <= $1", self.table);
+        let result = sqlx::query(&sql)
+            .bind(unix_now())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| SessionError::Store(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(feature = "sqlx-store")]
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(feature = "sqlx-store")]
+#[async_trait]
+impl SessionStore for SqlxStore {
+    async fn load(&self, id: &str) -> Result<Option<SessionData>, SessionError> {
+        let sql = format!("SELECT data FROM {} WHERE id = $1 AND expires_at > $2", self.table);
+        let row: Option<(String,)> = sqlx::query_as(&sql)
+            .bind(id)
+            .bind(unix_now())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| SessionError::Store(e.to_string()))?;
+        row.map(|(data,)| serde_json::from_str(&data)).transpose().map_err(Into::into)
+    }
+
+    async fn save(&self, id: &str, data: &SessionData, ttl: Duration) -> Result<(), SessionError> {
+        let sql = format!(
+            "INSERT INTO {} (id, data, expires_at) VALUES ($1, $2, $3) \
+             ON CONFLICT (id) DO UPDATE SET data = EXCLUDED.data, expires_at = EXCLUDED.expires_at",
+            self.table
+        );
+        sqlx::query(&sql)
+            .bind(id)
+            .bind(serde_json::to_string(data)?)
+            .bind(unix_now() + ttl.as_secs() as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| SessionError::Store(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), SessionError> {
+        let sql = format!("DELETE FROM {} WHERE id = $1", self.table);
+        sqlx::query(&sql)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| SessionError::Store(e.to_string()))?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Session
+// ============================================================================
+
+tokio::task_local! {
+    static CURRENT_SESSION: Session;
+}
+
+/// The current request's session
+///
+/// Cheap to clone; all clones share the same data. Changes are saved by
+/// [`SessionLayer`] after the handler returns.
+#[derive(Clone)]
+pub struct Session {
+    inner: Arc<Mutex<SessionInner>>,
+}
+
+struct SessionInner {
+    id: String,
+    data: SessionData,
+    is_new: bool,
+    modified: bool,
+    destroyed: bool,
+    previous_id: Option<String>,
+}
+
+fn new_session_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+impl Session {
+    fn new(id: String, data: SessionData, is_new: bool) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SessionInner {
+                id,
+                data,
+                is_new,
+                modified: false,
+                destroyed: false,
+                previous_id: None,
+            })),
+        }
+    }
+
+    /// The session of the request being handled, inside [`SessionLayer`]
+    ///
+    /// Use this from `#[server]` functions, which have no extractors.
+    pub fn current() -> Option<Session> {
+        CURRENT_SESSION.try_with(Clone::clone).ok()
+    }
+
+    /// Session ID
+    pub fn id(&self) -> String {
+        self.inner.lock().id.clone()
+    }
+
+    /// Whether the session was created by this request
+    pub fn is_new(&self) -> bool {
+        self.inner.lock().is_new
+    }
+
+    /// Get a value
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.inner
+            .lock()
+            .data
+            .get(key)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// Set a value
+    pub fn insert<T: Serialize>(&self, key: impl Into<String>, value: T) {
+        match serde_json::to_value(value) {
+            Ok(value) => {
+                let mut inner = self.inner.lock();
+                inner.data.insert(key.into(), value);
+                inner.modified = true;
+            }
+            Err(e) => warn!("Failed to serialize session value: {}", e),
+        }
+    }
+
+    /// Remove a value, returning it
+    pub fn remove<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let mut inner = self.inner.lock();
+        let value = inner.data.remove(key)?;
+        inner.modified = true;
+        serde_json::from_value(value).ok()
+    }
+
+    /// Remove all values
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.data.clear();
+        inner.modified = true;
+    }
+
+    /// Whether the session holds no values
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().data.is_empty()
+    }
+
+    /// Issue a new ID for the same data; call on login to prevent fixation
+    pub fn regenerate(&self) {
+        let mut inner = self.inner.lock();
+        let old = std::mem::replace(&mut inner.id, new_session_id());
+        if !inner.is_new && inner.previous_id.is_none() {
+            inner.previous_id = Some(old);
+        }
+        inner.modified = true;
+    }
+
+    /// Delete the session and expire its cookie
+    pub fn destroy(&self) {
+        let mut inner = self.inner.lock();
+        inner.data.clear();
+        inner.destroyed = true;
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Session
+where
+    S: Send + Sync,
+{
+    type Rejection = AxumResponse;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<Session>().cloned().ok_or_else(|| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Session extractor used without SessionLayer",
+            )
+                .into_response()
+        })
+    }
+}
+
+// ============================================================================
+// Session Layer
+// ============================================================================
+
+/// Layer loading and saving sessions around each request
+#[derive(Clone)]
+pub struct SessionLayer {
+    store: Arc<dyn SessionStore>,
+    key: Key,
+    cookie_name: String,
+    ttl: Duration,
+    rolling: bool,
+    secure: bool,
+    same_site: SameSite,
+    path: String,
+}
+
+impl SessionLayer {
+    /// Create a layer; `key` signs the session cookie
+    pub fn new(store: impl SessionStore, key: Key) -> Self {
+        Self {
+            store: Arc::new(store),
+            key,
+            cookie_name: SESSION_COOKIE.to_string(),
+            ttl: DEFAULT_SESSION_TTL,
+            rolling: true,
+            secure: false,
+            same_site: SameSite::Lax,
+            path: "/".to_string(),
+        }
+    }
+
+    /// Set the cookie name
+    pub fn cookie_name(mut self, name: impl Into<String>) -> Self {
+        self.cookie_name = name.into();
+        self
+    }
+
+    /// Set the session lifetime
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Extend the expiry on every request (default: on)
+    pub fn rolling(mut self, rolling: bool) -> Self {
+        self.rolling = rolling;
+        self
+    }
+
+    /// Only send the cookie over HTTPS
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Set the cookie `SameSite` attribute (default: `Lax`)
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = same_site;
+        self
+    }
+
+    /// Set the cookie path
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// The session store
+    pub fn store(&self) -> &Arc<dyn SessionStore> {
+        &self.store
+    }
+
+    /// Verify the signed session cookie and return the session ID
+    fn session_id(&self, headers: &axum::http::HeaderMap) -> Option<String> {
+        let mut jar = CookieJar::new();
+        for value in headers.get_all(header::COOKIE) {
+            let Ok(value) = value.to_str() else { continue };
+            for cookie in Cookie::split_parse_encoded(value.to_string()).flatten() {
+                jar.add_original(cookie);
+            }
+        }
+        jar.signed(&self.key)
+            .get(&self.cookie_name)
+            .map(|c| c.value().to_string())
+    }
+
+    async fn load(&self, headers: &axum::http::HeaderMap) -> Session {
+        if let Some(id) = self.session_id(headers) {
+            match self.store.load(&id).await {
+                Ok(Some(data)) => return Session::new(id, data, false),
+                Ok(None) => {}
+                Err(e) => warn!("Failed to load session: {}", e),
+            }
+        }
+        Session::new(new_session_id(), SessionData::new(), true)
+    }
+
+    fn cookie(&self, value: String, max_age: Duration) -> HeaderValue {
+        let cookie = Cookie::build((self.cookie_name.clone(), value))
+            .path(self.path.clone())
+            .http_only(true)
+            .secure(self.secure)
+            .same_site(self.same_site)
+            .max_age(cookie::time::Duration::seconds(max_age.as_secs() as i64))
+            .build();
+        let mut jar = CookieJar::new();
+        jar.signed_mut(&self.key).add(cookie);
+        let signed = jar.get(&self.cookie_name).map(|c| c.encoded().to_string());
+        HeaderValue::from_str(&signed.unwrap_or_default()).unwrap_or_else(|_| HeaderValue::from_static(""))
+    }
+
+    fn removal_cookie(&self) -> HeaderValue {
+        let mut cookie = Cookie::build((self.cookie_name.clone(), "")).path(self.path.clone()).build();
+        cookie.make_removal();
+        HeaderValue::from_str(&cookie.to_string()).unwrap_or_else(|_| HeaderValue::from_static(""))
+    }
+
+    /// Persist the session and return the `Set-Cookie` value, if any
+    async fn commit(&self, session: &Session) -> Option<HeaderValue> {
+        let (id, data, is_new, modified, destroyed, previous_id) = {
+            let mut inner = session.inner.lock();
+            (
+                inner.id.clone(),
+                inner.data.clone(),
+                inner.is_new,
+                inner.modified,
+                inner.destroyed,
+                inner.previous_id.take(),
+            )
+        };
+
+        if let Some(previous_id) = previous_id {
+            if let Err(e) = self.store.delete(&previous_id).await {
+                warn!("Failed to delete rotated session: {}", e);
+            }
+        }
+
+        if destroyed {
+            if !is_new {
+                if let Err(e) = self.store.delete(&id).await {
+                    warn!("Failed to delete session: {}", e);
+                }
+            }
+            return Some(self.removal_cookie());
+        }
+
+        // Empty new sessions are never stored, so anonymous traffic creates no state
+        if is_new && data.is_empty() {
+            return None;
+        }
+        if !(modified || self.rolling) {
+            return None;
+        }
+
+        match self.store.save(&id, &data, self.ttl).await {
+            Ok(()) => Some(self.cookie(id, self.ttl)),
+            Err(e) => {
+                warn!("Failed to save session: {}", e);
+                None
+            }
+        }
+    }
+}
+
+impl<S> Layer<S> for SessionLayer {
+    type Service = SessionMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SessionMiddleware {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Session middleware service
+#[derive(Clone)]
+pub struct SessionMiddleware<S> {
+    inner: S,
+    layer: SessionLayer,
+}
+
+impl<S> Service<Request<Body>> for SessionMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        // Swap in the clone so the ready service handles this request
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+
+        Box::pin(async move {
+            let session = layer.load(req.headers()).await;
+            req.extensions_mut().insert(session.clone());
+
+            let mut res = CURRENT_SESSION.scope(session.clone(), inner.call(req)).await?;
+
+            if let Some(cookie) = layer.commit(&session).await {
+                res.headers_mut().append(header::SET_COOKIE, cookie);
+            }
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn visits(session: Session) -> String {
+        let visits = session.get::<u32>("visits").unwrap_or(0) + 1;
+        session.insert("visits", visits);
+        visits.to_string()
+    }
+
+    async fn logout(session: Session) -> &'static str {
+        session.destroy();
+        "bye"
+    }
+
+    async fn from_task_local() -> String {
+        Session::current().map(|s| s.id()).unwrap_or_default()
+    }
+
+    fn app(store: MemoryStore, key: Key) -> Router {
+        Router::new()
+            .route("/", get(visits))
+            .route("/logout", get(logout))
+            .route("/current", get(from_task_local))
+            .layer(SessionLayer::new(store, key))
+    }
+
+    async fn send(app: &Router, uri: &str, cookie: Option<&str>) -> (String, Option<String>) {
+        let mut req = Request::builder().uri(uri);
+        if let Some(cookie) = cookie {
+            req = req.header(header::COOKIE, cookie);
+        }
+        let res = app.clone().oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+        let set_cookie = res
+            .headers()
+            .get(header::SET_COOKIE)
+            .map(|v| v.to_str().unwrap().split(';').next().unwrap().to_string());
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        (String::from_utf8(body.to_vec()).unwrap(), set_cookie)
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_expiry() {
+        let store = MemoryStore::new();
+        let mut data = SessionData::new();
+        data.insert("a".to_string(), serde_json::json!(1));
+
+        store.save("live", &data, Duration::from_secs(60)).await.unwrap();
+        store.save("dead", &data, Duration::ZERO).await.unwrap();
+
+        assert_eq!(store.load("live").await.unwrap(), Some(data));
+        assert_eq!(store.load("dead").await.unwrap(), None);
+        store.cleanup();
+        assert_eq!(store.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_round_trip() {
+        let store = MemoryStore::new();
+        let app = app(store.clone(), Key::generate());
+
+        let (body, cookie) = send(&app, "/", None).await;
+        assert_eq!(body, "1");
+        let cookie = cookie.expect("session cookie");
+
+        let (body, _) = send(&app, "/", Some(&cookie)).await;
+        assert_eq!(body, "2");
+
+        let (_, cleared) = send(&app, "/logout", Some(&cookie)).await;
+        assert_eq!(cleared.as_deref(), Some(format!("{}=", SESSION_COOKIE).as_str()));
+        assert!(store.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tampered_cookie_starts_new_session() {
+        let app = app(MemoryStore::new(), Key::generate());
+        let (_, cookie) = send(&app, "/", None).await;
+        let forged = format!("{}x", cookie.unwrap());
+
+        let (body, _) = send(&app, "/", Some(&forged)).await;
+        assert_eq!(body, "1");
+    }
+
+    #[tokio::test]
+    async fn test_session_available_to_server_fns() {
+        let app = app(MemoryStore::new(), Key::generate());
+        let (id, cookie) = send(&app, "/current", None).await;
+        assert_eq!(id.len(), 32);
+        // Untouched new sessions are not persisted
+        assert!(cookie.is_none());
+    }
+}
@@ -1,5 +1,14 @@
 //! SSR utilities for Axum
 
+use std::convert::Infallible;
+use std::pin::Pin;
+
+use axum::body::{Body, Bytes};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use futures::stream::{self, Stream, StreamExt};
+use philjs::StreamingConfig;
+
 /// HTML document builder
 pub struct HtmlDocument {
     title: String,
@@ -95,3 +104,120 @@ impl SeoBuilder {
         tags
     }
 }
+
+/// Streaming HTML response
+///
+/// Each chunk is written to the socket as it is produced, so the shell
+/// reaches the browser before suspense boundaries resolve.
+pub struct HtmlStream {
+    body: Pin<Box<dyn Stream<Item = String> + Send>>,
+    status: StatusCode,
+}
+
+impl HtmlStream {
+    /// Create a response from a stream of HTML chunks
+    pub fn from_stream<S>(stream: S) -> Self
+    where
+        S: Stream<Item = String> + Send + 'static,
+    {
+        Self {
+            body: Box::pin(stream),
+            status: StatusCode::OK,
+        }
+    }
+
+    /// Wrap the stream with a document prefix and suffix
+    pub fn wrap(self, prefix: impl Into<String>, suffix: impl Into<String>) -> Self {
+        let head = stream::once(futures::future::ready(prefix.into()));
+        let tail = stream::once(futures::future::ready(suffix.into()));
+        Self {
+            body: Box::pin(head.chain(self.body).chain(tail)),
+            status: self.status,
+        }
+    }
+
+    /// Set the status code
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+impl IntoResponse for HtmlStream {
+    fn into_response(self) -> Response {
+        let body = Body::from_stream(
+            self.body
+                .filter(|chunk| futures::future::ready(!chunk.is_empty()))
+                .map(|chunk| Ok::<_, Infallible>(Bytes::from(chunk))),
+        );
+        let mut response = (self.status, body).into_response();
+        let headers = response.headers_mut();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/html; charset=utf-8"));
+        headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+        // Ask reverse proxies not to buffer, so the shell is flushed early
+        headers.insert("x-accel-buffering", HeaderValue::from_static("no"));
+        response
+    }
+}
+
+/// Stream a PhilJS view with `philjs::render_to_stream_async`
+pub fn render_stream<F, V>(f: F, config: StreamingConfig) -> HtmlStream
+where
+    F: FnOnce() -> V,
+    V: philjs::IntoView,
+{
+    HtmlStream::from_stream(philjs::render_to_stream_async(f, config))
+}
+
+/// Stream a PhilJS view inside a full HTML document
+pub fn render_stream_document<F, V>(title: &str, f: F, config: StreamingConfig) -> HtmlStream
+where
+    F: FnOnce() -> V,
+    V: philjs::IntoView,
+{
+    let assets = crate::assets::DocumentAssets::current();
+    let head = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>{}</title>
+    {}
+</head>
+<body>
+    <div id="app">"#,
+        title,
+        assets.head_tags()
+    );
+    let tail = format!(
+        r#"</div>
+    {}
+</body>
+</html>"#,
+        assets.body_tags()
+    );
+
+    render_stream(f, config).wrap(head, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_html_stream_response() {
+        let chunks = stream::iter(vec!["<p>a</p>".to_string(), String::new(), "<p>b</p>".to_string()]);
+        let response = HtmlStream::from_stream(chunks)
+            .wrap("<body>", "</body>")
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"<body><p>a</p><p>b</p></body>");
+    }
+}
@@ -8,6 +8,8 @@ use parking_lot::RwLock;
 use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 
+use crate::session::SessionLayer;
+
 /// Application state shared across handlers
 #[derive(Clone)]
 pub struct AppState {
@@ -26,6 +28,8 @@ struct AppStateInner {
     config: HashMap<String, serde_json::Value>,
     /// In-memory cache
     cache: RwLock<HashMap<String, CacheEntry>>,
+    /// Session configuration shared by handlers and server functions
+    sessions: Option<SessionLayer>,
 }
 
 /// Cache entry with expiration
@@ -71,6 +75,7 @@ impl Default for AppState {
                 environment: Environment::Development,
                 config: HashMap::new(),
                 cache: RwLock::new(HashMap::new()),
+                sessions: None,
             }),
         }
     }
@@ -119,6 +124,11 @@ impl AppState {
             .and_then(|v| serde_json::from_value(v.clone()).ok())
     }
 
+    /// Session layer configured with [`AppStateBuilder::with_sessions`]
+    pub fn sessions(&self) -> Option<&SessionLayer> {
+        self.inner.sessions.as_ref()
+    }
+
     /// Get a cached value
     pub fn cache_get<T: for<'de> Deserialize<'de>>(&self, key: &str) -> Option<T> {
         let cache = self.inner.cache.read();
@@ -206,6 +216,7 @@ pub struct AppStateBuilder {
     version: Option<String>,
     environment: Option<Environment>,
     config: HashMap<String, serde_json::Value>,
+    sessions: Option<SessionLayer>,
 }
 
 impl AppStateBuilder {
@@ -250,6 +261,12 @@ impl AppStateBuilder {
         self
     }
 
+    /// Configure sessions; apply the same layer to the router
+    pub fn with_sessions(mut self, sessions: SessionLayer) -> Self {
+        self.sessions = Some(sessions);
+        self
+    }
+
     /// Build the application state
     pub fn build(self) -> AppState {
         AppState {
@@ -259,6 +276,7 @@ impl AppStateBuilder {
                 environment: self.environment.unwrap_or_default(),
                 config: self.config,
                 cache: RwLock::new(HashMap::new()),
+                sessions: self.sessions,
             }),
         }
     }
@@ -303,6 +321,17 @@ mod tests {
         assert_eq!(state.cache_get::<String>("key1"), None);
     }
 
+    #[test]
+    fn test_app_state_sessions() {
+        use crate::session::{Key, MemoryStore};
+
+        assert!(AppState::new().sessions().is_none());
+        let state = AppStateBuilder::new()
+            .with_sessions(SessionLayer::new(MemoryStore::new(), Key::generate()))
+            .build();
+        assert!(state.sessions().is_some());
+    }
+
     #[test]
     fn test_environment_display() {
         assert_eq!(Environment::Development.to_string(), "development");
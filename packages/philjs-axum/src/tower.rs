@@ -547,6 +547,256 @@ where
     }
 }
 
+// ============================================================================
+// ETag Layer
+// ============================================================================
+
+/// Default largest body hashed by [`ETagLayer`]
+pub const DEFAULT_ETAG_MAX_BODY: usize = 5 * 1024 * 1024;
+
+/// Conditional GET layer for SSR responses
+///
+/// Hashes successful HTML responses of known size, sets a strong `ETag`, and
+/// answers a matching `If-None-Match` with `304 Not Modified`. Streaming
+/// responses pass through untouched.
+///
+/// With [`route_cache`](Self::route_cache), the latest ETag of each URL is
+/// remembered for a while so revalidations within that window skip rendering.
+/// The route cache is only for public pages; see its docs for what is left
+/// out of it.
+#[derive(Clone)]
+pub struct ETagLayer {
+    max_body: usize,
+    route_cache: Option<std::sync::Arc<ETagCache>>,
+}
+
+impl ETagLayer {
+    /// Create a new ETag layer
+    pub fn new() -> Self {
+        Self {
+            max_body: DEFAULT_ETAG_MAX_BODY,
+            route_cache: None,
+        }
+    }
+
+    /// Skip hashing bodies larger than `bytes`
+    pub fn max_body(mut self, bytes: usize) -> Self {
+        self.max_body = bytes;
+        self
+    }
+
+    /// Remember up to `capacity` URLs' ETags for `ttl`
+    ///
+    /// Only use this for public pages that do not change within `ttl`, since
+    /// a hit answers `304` without calling the handler, skipping its auth
+    /// checks. As a guard, requests carrying `Cookie` or `Authorization`
+    /// neither read nor fill the cache, and responses with `Set-Cookie`,
+    /// `Vary` or `Cache-Control: private`/`no-store` are never stored.
+    pub fn route_cache(mut self, capacity: usize, ttl: Duration) -> Self {
+        self.route_cache = Some(std::sync::Arc::new(ETagCache::new(capacity, ttl)));
+        self
+    }
+}
+
+impl Default for ETagLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for ETagLayer {
+    type Service = ETagMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ETagMiddleware {
+            inner,
+            max_body: self.max_body,
+            route_cache: self.route_cache.clone(),
+        }
+    }
+}
+
+/// Bounded per-URL ETag cache; the oldest entry is evicted first
+struct ETagCache {
+    capacity: usize,
+    ttl: Duration,
+    entries: parking_lot::Mutex<(
+        std::collections::HashMap<String, (String, Instant)>,
+        std::collections::VecDeque<String>,
+    )>,
+}
+
+impl ETagCache {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            ttl,
+            entries: parking_lot::Mutex::new(Default::default()),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        let entries = self.entries.lock();
+        entries.0
+            .get(key)
+            .filter(|(_, stored)| stored.elapsed() < self.ttl)
+            .map(|(etag, _)| etag.clone())
+    }
+
+    fn insert(&self, key: String, etag: String) {
+        let mut guard = self.entries.lock();
+        let (map, order) = &mut *guard;
+        if map.insert(key.clone(), (etag, Instant::now())).is_none() {
+            order.push_back(key);
+        }
+        while map.len() > self.capacity {
+            match order.pop_front() {
+                Some(oldest) => {
+                    map.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Strong ETag from the body length and its 64-bit FNV-1a hash
+pub fn etag_for(body: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in body {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("\"{:x}-{:016x}\"", body.len(), hash)
+}
+
+/// Whether an `If-None-Match` header matches `etag` (weak comparison)
+fn if_none_match(header_value: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    header_value.split(',').any(|candidate| {
+        let candidate = candidate.trim();
+        candidate == "*" || candidate.trim_start_matches("W/") == etag
+    })
+}
+
+/// Whether a request may be answered from, or fill, the route cache
+fn is_anonymous(headers: &axum::http::HeaderMap) -> bool {
+    !headers.contains_key(header::COOKIE) && !headers.contains_key(header::AUTHORIZATION)
+}
+
+/// Whether a response is the same for everyone and may fill the route cache
+fn is_public(headers: &axum::http::HeaderMap) -> bool {
+    let private = headers
+        .get_all(header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|directive| {
+            let directive = directive.trim();
+            directive.eq_ignore_ascii_case("private") || directive.eq_ignore_ascii_case("no-store")
+        });
+    !private && !headers.contains_key(header::SET_COOKIE) && !headers.contains_key(header::VARY)
+}
+
+fn not_modified(etag: &str) -> Response<Body> {
+    let mut res = Response::new(Body::empty());
+    *res.status_mut() = StatusCode::NOT_MODIFIED;
+    if let Ok(value) = etag.parse() {
+        res.headers_mut().insert(header::ETAG, value);
+    }
+    res
+}
+
+/// ETag middleware service
+#[derive(Clone)]
+pub struct ETagMiddleware<S> {
+    inner: S,
+    max_body: usize,
+    route_cache: Option<std::sync::Arc<ETagCache>>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for ETagMiddleware<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send,
+    ReqBody: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        use axum::body::HttpBody;
+
+        let conditional = matches!(*req.method(), axum::http::Method::GET | axum::http::Method::HEAD);
+        let condition = req
+            .headers()
+            .get(header::IF_NONE_MATCH)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let cache_key = req.uri().to_string();
+        let route_cache = self.route_cache.clone().filter(|_| is_anonymous(req.headers()));
+
+        if let (true, Some(cache), Some(candidate)) = (conditional, &route_cache, &condition) {
+            if let Some(etag) = cache.get(&cache_key) {
+                if if_none_match(candidate, &etag) {
+                    return Box::pin(async move { Ok(not_modified(&etag)) });
+                }
+            }
+        }
+
+        let max_body = self.max_body;
+        let fut = self.inner.call(req);
+
+        Box::pin(async move {
+            let res = fut.await?;
+
+            let is_html = res
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.starts_with("text/html"));
+            let size = res.body().size_hint().exact();
+            let hashable = size.is_some_and(|size| size as usize <= max_body);
+            if !conditional || res.status() != StatusCode::OK || !is_html || !hashable {
+                return Ok(res);
+            }
+
+            let (mut parts, body) = res.into_parts();
+            let bytes = match axum::body::to_bytes(body, max_body).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    warn!("Failed to buffer response for ETag: {}", e);
+                    return Ok((StatusCode::INTERNAL_SERVER_ERROR, "Failed to read response").into_response());
+                }
+            };
+
+            let etag = parts
+                .headers
+                .get(header::ETAG)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+                .unwrap_or_else(|| etag_for(&bytes));
+            if let Some(cache) = route_cache.filter(|_| is_public(&parts.headers)) {
+                cache.insert(cache_key, etag.clone());
+            }
+
+            if condition.is_some_and(|candidate| if_none_match(&candidate, &etag)) {
+                return Ok(not_modified(&etag));
+            }
+
+            if let Ok(value) = etag.parse() {
+                parts.headers.insert(header::ETAG, value);
+            }
+            Ok(Response::from_parts(parts, Body::from(bytes)))
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -578,6 +828,103 @@ mod tests {
         assert!(layer.hsts.is_none());
     }
 
+    #[test]
+    fn test_etag_matching() {
+        let etag = etag_for(b"<h1>Hi</h1>");
+        assert_eq!(etag, etag_for(b"<h1>Hi</h1>"));
+        assert_ne!(etag, etag_for(b"<h1>Bye</h1>"));
+        assert!(if_none_match(&format!("\"x\", W/{}", etag), &etag));
+        assert!(if_none_match("*", &etag));
+        assert!(!if_none_match("\"x\"", &etag));
+    }
+
+    #[test]
+    fn test_etag_cache_is_bounded() {
+        let cache = ETagCache::new(2, Duration::from_secs(60));
+        cache.insert("/a".into(), "1".into());
+        cache.insert("/b".into(), "2".into());
+        cache.insert("/c".into(), "3".into());
+        assert_eq!(cache.get("/a"), None);
+        assert_eq!(cache.get("/c"), Some("3".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_etag_conditional_get() {
+        use axum::{response::Html, routing::get, Router};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tower::ServiceExt;
+
+        static RENDERS: AtomicUsize = AtomicUsize::new(0);
+        let app = Router::new()
+            .route("/", get(|| async {
+                RENDERS.fetch_add(1, Ordering::SeqCst);
+                Html("<h1>Home</h1>")
+            }))
+            .layer(ETagLayer::new().route_cache(16, Duration::from_secs(60)));
+
+        let res = app.clone().oneshot(Request::get("/").body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let etag = res.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        let res = app
+            .oneshot(Request::get("/").header(header::IF_NONE_MATCH, &etag).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_etag_route_cache_is_public_only() {
+        use axum::{response::Html, routing::get, Router};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tower::ServiceExt;
+
+        static RENDERS: AtomicUsize = AtomicUsize::new(0);
+        async fn page() -> Html<&'static str> {
+            RENDERS.fetch_add(1, Ordering::SeqCst);
+            Html("<h1>Page</h1>")
+        }
+        let app = Router::new()
+            .route("/account", get(page))
+            .route("/session", get(|| async { ([(header::SET_COOKIE, "sid=1")], page().await) }))
+            .route("/private", get(|| async { ([(header::CACHE_CONTROL, "max-age=0, Private")], page().await) }))
+            .route("/vary", get(|| async { ([(header::VARY, "Accept-Language")], page().await) }))
+            .layer(ETagLayer::new().route_cache(16, Duration::from_secs(60)));
+        let request = |uri: &str, etag: &str, cookie: Option<&str>| {
+            let mut req = Request::get(uri).header(header::IF_NONE_MATCH, etag);
+            if let Some(cookie) = cookie {
+                req = req.header(header::COOKIE, cookie);
+            }
+            req.body(Body::empty()).unwrap()
+        };
+        let etag = etag_for(b"<h1>Page</h1>");
+
+        // Signed-in requests always reach the handler and aren't remembered
+        for _ in 0..2 {
+            let res = app.clone().oneshot(request("/account", &etag, Some("sid=1"))).await.unwrap();
+            assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        }
+        assert_eq!(RENDERS.load(Ordering::SeqCst), 2);
+        let res = app.clone().oneshot(request("/account", &etag, None)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(RENDERS.load(Ordering::SeqCst), 3);
+
+        // Per-user responses aren't stored either
+        for uri in ["/session", "/private", "/vary"] {
+            let before = RENDERS.load(Ordering::SeqCst);
+            for _ in 0..2 {
+                app.clone().oneshot(request(uri, &etag, None)).await.unwrap();
+            }
+            assert_eq!(RENDERS.load(Ordering::SeqCst), before + 2, "{}", uri);
+        }
+
+        // Public pages are
+        let res = app.clone().oneshot(request("/account", &etag, None)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(RENDERS.load(Ordering::SeqCst), 9);
+    }
+
     #[test]
     fn test_rate_limit_layer() {
         let layer = RateLimitLayer::per_minute(100);
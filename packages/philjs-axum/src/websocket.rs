@@ -8,10 +8,14 @@ use axum::{
         State,
     },
     response::Response,
+    routing::get,
+    Router,
 };
 use futures_util::{SinkExt, StreamExt};
+use philjs::liveview::{self as core, LiveView, LiveViewRegistry, Reply};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
 use parking_lot::RwLock;
 use tokio::sync::{broadcast, mpsc};
 use uuid::Uuid;
@@ -401,6 +405,189 @@ pub async fn echo_handler(socket: WebSocket) {
     }
 }
 
+// ============================================================================
+// Registry-backed LiveView endpoint
+// ============================================================================
+
+pub use philjs::liveview::LiveViewFactory;
+
+/// WebSocket endpoint serving `philjs::liveview` views
+///
+/// Adapts axum's WebSocket to the core [`core::LiveViewHub`], which speaks
+/// the `WsMessage` protocol used by the LiveView client. Events published
+/// with [`broadcast`](Self::broadcast) reach `handle_info` of every view
+/// subscribed to their topic and push a re-render.
+///
+/// ```rust,ignore
+/// let hub = Arc::new(LiveViewHub::new().route("/counter", || Box::new(Counter::default())));
+/// let app = Router::new().merge(hub.clone().router("/live"));
+///
+/// // Later, from any handler:
+/// hub.broadcast("/counter", "tick", json!({ "n": 1 }));
+/// ```
+pub struct LiveViewHub {
+    hub: core::LiveViewHub,
+    broadcast: Arc<BroadcastChannel>,
+    presence: Arc<PresenceTracker>,
+}
+
+impl LiveViewHub {
+    /// Create a hub with its own registry, broadcast channel, and presence tracking
+    pub fn new() -> Self {
+        Self {
+            hub: core::LiveViewHub::new(),
+            broadcast: Arc::new(BroadcastChannel::default()),
+            presence: Arc::new(PresenceTracker::new()),
+        }
+    }
+
+    /// Share an existing registry (e.g. one with a running sweeper)
+    pub fn with_registry(mut self, registry: Arc<LiveViewRegistry>) -> Self {
+        self.hub = self.hub.with_registry(registry);
+        self
+    }
+
+    /// Mirror broadcasts to an existing channel
+    pub fn with_broadcast(mut self, broadcast: Arc<BroadcastChannel>) -> Self {
+        self.broadcast = broadcast;
+        self
+    }
+
+    /// Share an existing presence tracker
+    pub fn with_presence(mut self, presence: Arc<PresenceTracker>) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    /// Close sockets that have not sent a heartbeat within `timeout`
+    pub fn heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.hub = self.hub.with_heartbeat_timeout(timeout);
+        self
+    }
+
+    /// Serve a view for joins on `topic` (usually the page path)
+    pub fn route<F>(mut self, topic: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn() -> Box<dyn LiveView> + Send + Sync + 'static,
+    {
+        self.hub = self.hub.route(topic, factory);
+        self
+    }
+
+    /// The view registry
+    pub fn registry(&self) -> &Arc<LiveViewRegistry> {
+        self.hub.registry()
+    }
+
+    /// The presence tracker
+    pub fn presence(&self) -> &Arc<PresenceTracker> {
+        &self.presence
+    }
+
+    /// Subscribe a connected socket to an additional topic
+    pub fn subscribe(&self, topic: &str, socket_id: &str) {
+        self.hub.subscribe(topic, socket_id);
+    }
+
+    /// Publish a server-side event to every view subscribed to `topic`,
+    /// mirroring it to [`BroadcastChannel`] subscribers.
+    ///
+    /// Returns the number of local views notified.
+    pub fn broadcast(&self, topic: &str, event: &str, payload: serde_json::Value) -> usize {
+        let delivered = self.hub.publish(topic, event, payload.clone());
+        let _ = self.broadcast.broadcast(BroadcastMessage {
+            topic: topic.to_string(),
+            event: event.to_string(),
+            payload,
+        });
+        delivered
+    }
+
+    /// A router serving the hub's WebSocket endpoint at `path`
+    pub fn router<S>(self: Arc<Self>, path: &str) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        Router::new()
+            .route(path, get(live_view_handler))
+            .with_state(self)
+    }
+
+    /// Upgrade a request into a LiveView connection
+    pub fn upgrade(self: Arc<Self>, ws: WebSocketUpgrade) -> Response {
+        ws.on_upgrade(move |socket| self.serve(socket))
+    }
+
+    async fn serve(self: Arc<Self>, socket: WebSocket) {
+        let (mut sender, mut receiver) = socket.split();
+        let (tx, mut info_rx) = mpsc::unbounded_channel();
+        let mut conn = self.hub.connect(Uuid::new_v4().to_string(), move |info| {
+            let _ = tx.send(info);
+        });
+
+        let mut heartbeat_check = tokio::time::interval(self.hub.heartbeat_interval());
+        loop {
+            let reply = tokio::select! {
+                incoming = receiver.next() => match incoming {
+                    Some(Ok(Message::Text(text))) => conn.handle_text(&text),
+                    Some(Ok(Message::Ping(data))) => {
+                        let _ = sender.send(Message::Pong(data)).await;
+                        continue;
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                },
+                Some(info) = info_rx.recv() => conn.handle_info(info),
+                _ = heartbeat_check.tick() => {
+                    if conn.is_alive() {
+                        continue;
+                    }
+                    break;
+                }
+            };
+            let frame = match reply {
+                Reply::None => continue,
+                Reply::Send(frame) => frame,
+                Reply::Joined { topic, payload, frame } => {
+                    self.presence.track(&topic, conn.socket_id(), serde_json::json!({ "url": payload.url }));
+                    frame
+                }
+                Reply::Close(frame) => {
+                    if let Some(frame) = frame {
+                        let _ = sender.send(Message::Text(frame)).await;
+                    }
+                    break;
+                }
+            };
+            if sender.send(Message::Text(frame)).await.is_err() {
+                break;
+            }
+        }
+
+        if let Some(topic) = conn.topic() {
+            self.presence.untrack(topic, conn.socket_id());
+        }
+    }
+}
+
+impl Default for LiveViewHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Axum handler upgrading to a [`LiveViewHub`] connection
+///
+/// ```rust,ignore
+/// Router::new().route("/live", get(live_view_handler)).with_state(hub)
+/// ```
+pub async fn live_view_handler(
+    ws: WebSocketUpgrade,
+    State(hub): State<Arc<LiveViewHub>>,
+) -> Response {
+    hub.upgrade(ws)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -443,6 +630,18 @@ mod tests {
         // Would need tokio runtime to actually receive
     }
 
+    #[tokio::test]
+    async fn test_hub_broadcast_reaches_channel() {
+        let hub = LiveViewHub::new();
+        let mut rx = hub.broadcast.subscribe();
+
+        // No views are connected, but the event is still mirrored
+        assert_eq!(hub.broadcast("/counter", "tick", serde_json::json!({ "n": 1 })), 0);
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.topic, "/counter");
+        assert_eq!(event.payload["n"], 1);
+    }
+
     #[test]
     fn test_presence_tracker() {
         let tracker = PresenceTracker::new();
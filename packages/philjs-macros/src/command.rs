@@ -0,0 +1,682 @@
+//! Tauri Command Macro Implementation
+//!
+//! Turns a plain Rust function into a Tauri command plus a typed client:
+//! - On native targets, a `#[tauri::command]` that deserializes each argument
+//!   itself so failures name the offending field
+//! - On `wasm32`, an async stub with the same arguments that invokes the
+//!   command through the Tauri IPC bridge
+//! - On both, a `{NAME}_COMMAND` spec used to write TypeScript bindings
+//!
+//! Arguments whose type is injected by Tauri (`State`, `AppHandle`, `Window`,
+//! ...) are passed through on native targets and left out of the client.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Attribute, FnArg, GenericArgument, ItemFn, Pat, PathArguments, ReturnType,
+    Type,
+};
+use darling::FromMeta;
+
+#[derive(Debug, FromMeta, Default)]
+pub struct CommandArgs {
+    /// Command name seen by the frontend (defaults to the function name)
+    #[darling(default)]
+    pub rename: Option<String>,
+}
+
+/// Types Tauri injects into commands rather than reading from the payload
+const INJECTED_TYPES: &[&str] = &[
+    "State",
+    "AppHandle",
+    "Window",
+    "WebviewWindow",
+    "Webview",
+    "Channel",
+    "Request",
+];
+
+/// Implementation of the #[philjs_command] macro
+pub fn philjs_command_impl(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = match darling::ast::NestedMeta::parse_meta_list(args.into()) {
+        Ok(v) => v,
+        Err(e) => return TokenStream::from(darling::Error::from(e).write_errors()),
+    };
+
+    let args = match CommandArgs::from_list(&args) {
+        Ok(v) => v,
+        Err(e) => return TokenStream::from(e.write_errors()),
+    };
+
+    let input_fn = parse_macro_input!(input as ItemFn);
+
+    match expand(args, input_fn) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(e) => TokenStream::from(e.to_compile_error()),
+    }
+}
+
+fn expand(args: CommandArgs, input_fn: ItemFn) -> syn::Result<TokenStream2> {
+    let fn_name = &input_fn.sig.ident;
+    let fn_vis = &input_fn.vis;
+    let fn_generics = &input_fn.sig.generics;
+    let fn_where = &input_fn.sig.generics.where_clause;
+    let fn_async = &input_fn.sig.asyncness;
+    let fn_inputs = &input_fn.sig.inputs;
+    let fn_output = &input_fn.sig.output;
+    let fn_block = &input_fn.block;
+    let fn_attrs = &input_fn.attrs;
+    let doc_attrs: Vec<&Attribute> = fn_attrs.iter().filter(|a| a.path().is_ident("doc")).collect();
+
+    let command_name = args.rename.unwrap_or_else(|| fn_name.to_string());
+    let impl_name = format_ident!("__philjs_command_{}", fn_name);
+    let spec_name = format_ident!("{}_COMMAND", fn_name.to_string().to_uppercase());
+
+    // Split the arguments into payload arguments and Tauri-injected ones
+    let mut call_args = Vec::new();
+    let mut injected = Vec::new();
+    let mut payload_names = Vec::new();
+    let mut payload_types = Vec::new();
+    let mut payload_keys = Vec::new();
+
+    for arg in fn_inputs {
+        let pat_type = match arg {
+            FnArg::Typed(pat_type) => pat_type,
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new_spanned(
+                    receiver,
+                    "#[philjs_command] cannot be used on methods",
+                ))
+            }
+        };
+        let arg_name = match &*pat_type.pat {
+            Pat::Ident(pat_ident) => &pat_ident.ident,
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "#[philjs_command] arguments must be plain identifiers",
+                ))
+            }
+        };
+        let arg_type = &*pat_type.ty;
+        call_args.push(arg_name.clone());
+
+        if is_injected(arg_type) {
+            injected.push(quote! { #arg_name: #arg_type });
+            continue;
+        }
+        if let Type::Reference(_) = arg_type {
+            return Err(syn::Error::new_spanned(
+                arg_type,
+                "#[philjs_command] arguments must be owned, e.g. `String` instead of `&str`",
+            ));
+        }
+
+        payload_keys.push(to_camel_case(&arg_name.to_string()));
+        payload_names.push(arg_name.clone());
+        payload_types.push(arg_type.clone());
+    }
+
+    // `T` and `Result<T, E>` both become `Result<T, ErrorEnvelope>`
+    let (ok_type, returns_result) = match fn_output {
+        ReturnType::Default => (quote! { () }, false),
+        ReturnType::Type(_, ty) => match result_ok_type(ty) {
+            Some(ok) => (quote! { #ok }, true),
+            None => (quote! { #ty }, false),
+        },
+    };
+    let await_call = fn_async.map(|_| quote! { .await });
+    let wrap_result = if returns_result {
+        quote! { __philjs_result.map_err(::std::convert::Into::into) }
+    } else {
+        quote! { ::std::result::Result::Ok(__philjs_result) }
+    };
+
+    let ts_args: Vec<TokenStream2> = payload_keys
+        .iter()
+        .zip(&payload_types)
+        .map(|(key, ty)| {
+            let ts = ts_type(ty);
+            quote! { (#key, #ts) }
+        })
+        .collect();
+    let ts_returns = match fn_output {
+        ReturnType::Default => "void".to_string(),
+        ReturnType::Type(_, ty) => match result_ok_type(ty) {
+            Some(ok) => ts_type(ok),
+            None => ts_type(ty),
+        },
+    };
+    let docs = extract_doc_comment(fn_attrs);
+
+    Ok(quote! {
+        // The original function, called by the native command
+        #[cfg(not(target_arch = "wasm32"))]
+        #[doc(hidden)]
+        #[allow(clippy::too_many_arguments)]
+        #fn_vis #fn_async fn #impl_name #fn_generics(#fn_inputs) #fn_output #fn_where #fn_block
+
+        // Native command: read each argument from the payload by name
+        #[cfg(not(target_arch = "wasm32"))]
+        #(#fn_attrs)*
+        #[::tauri::command]
+        #fn_vis #fn_async fn #fn_name #fn_generics(
+            #(#injected,)*
+            __philjs_request: ::tauri::ipc::Request<'_>,
+        ) -> ::std::result::Result<#ok_type, ::philjs_tauri::commands::ErrorEnvelope> #fn_where {
+            #[allow(unused_mut)]
+            let mut __philjs_args = ::philjs_tauri::commands::CommandArgs::from_request(
+                #command_name,
+                &__philjs_request,
+            )?;
+            #(let #payload_names: #payload_types = __philjs_args.take(#payload_keys)?;)*
+            let __philjs_result = #impl_name(#(#call_args),*) #await_call;
+            #wrap_result
+        }
+
+        // Client stub for PhilJS wasm frontends
+        #[cfg(target_arch = "wasm32")]
+        #(#doc_attrs)*
+        #fn_vis async fn #fn_name(
+            #(#payload_names: #payload_types),*
+        ) -> ::std::result::Result<#ok_type, ::philjs_tauri::commands::ErrorEnvelope> {
+            ::philjs_tauri::client::invoke(
+                #command_name,
+                ::std::vec![#((#payload_keys, ::philjs_tauri::client::arg(#payload_keys, &#payload_names)?)),*],
+            )
+            .await
+        }
+
+        /// Signature of this command, for TypeScript bindings
+        #[allow(dead_code)]
+        #fn_vis const #spec_name: ::philjs_tauri::commands::CommandSpec =
+            ::philjs_tauri::commands::CommandSpec {
+                name: #command_name,
+                docs: #docs,
+                args: &[#(#ts_args),*],
+                returns: #ts_returns,
+            };
+    })
+}
+
+/// Whether Tauri provides this argument instead of the frontend
+fn is_injected(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|seg| INJECTED_TYPES.iter().any(|name| seg.ident == name))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// The `T` of `Result<T, E>` or `CommandResult<T>`
+fn result_ok_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let seg = type_path.path.segments.last()?;
+    if seg.ident != "Result" && seg.ident != "CommandResult" {
+        return None;
+    }
+    first_type_arg(&seg.arguments)
+}
+
+fn first_type_arg(args: &PathArguments) -> Option<&Type> {
+    match args {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+fn type_args(args: &PathArguments) -> Vec<&Type> {
+    match args {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Map a Rust type to the TypeScript type of its JSON form
+///
+/// User-defined types keep their name and are expected to be declared on
+/// the frontend.
+fn ts_type(ty: &Type) -> String {
+    match ty {
+        Type::Reference(reference) => ts_type(&reference.elem),
+        Type::Paren(paren) => ts_type(&paren.elem),
+        Type::Group(group) => ts_type(&group.elem),
+        Type::Slice(slice) => format!("{}[]", ts_element(&slice.elem)),
+        Type::Array(array) => format!("{}[]", ts_element(&array.elem)),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => "null".to_string(),
+        Type::Tuple(tuple) => format!(
+            "[{}]",
+            tuple.elems.iter().map(ts_type).collect::<Vec<_>>().join(", ")
+        ),
+        Type::Path(type_path) => {
+            let Some(seg) = type_path.path.segments.last() else {
+                return "unknown".to_string();
+            };
+            let args = type_args(&seg.arguments);
+            match seg.ident.to_string().as_str() {
+                "String" | "str" | "char" | "PathBuf" | "Path" => "string".to_string(),
+                "bool" => "boolean".to_string(),
+                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32"
+                | "i64" | "i128" | "isize" | "f32" | "f64" => "number".to_string(),
+                "Value" => "unknown".to_string(),
+                "Option" => match args.first() {
+                    Some(inner) => format!("{} | null", ts_type(inner)),
+                    None => "unknown".to_string(),
+                },
+                "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => match args.first() {
+                    Some(inner) => format!("{}[]", ts_element(inner)),
+                    None => "unknown[]".to_string(),
+                },
+                "HashMap" | "BTreeMap" => match (args.first(), args.get(1)) {
+                    (Some(key), Some(value)) => {
+                        format!("Record<{}, {}>", ts_type(key), ts_type(value))
+                    }
+                    _ => "Record<string, unknown>".to_string(),
+                },
+                "Box" | "Arc" | "Rc" | "Cow" => match args.first() {
+                    Some(inner) => ts_type(inner),
+                    None => "unknown".to_string(),
+                },
+                name if args.is_empty() => name.to_string(),
+                name => format!(
+                    "{}<{}>",
+                    name,
+                    args.into_iter().map(ts_type).collect::<Vec<_>>().join(", ")
+                ),
+            }
+        }
+        _ => "unknown".to_string(),
+    }
+}
+
+/// An array element type, parenthesized if it's a union
+fn ts_element(ty: &Type) -> String {
+    let ts = ts_type(ty);
+    if ts.contains(" | ") {
+        format!("({})", ts)
+    } else {
+        ts
+    }
+}
+
+/// Tauri's convention: `user_id` is sent as `userId`
+fn to_camel_case(s: &str) -> String {
+    let mut result = String::new();
+    let mut capitalize_next = false;
+
+    for c in s.trim_start_matches('_').chars() {
+        if c == '_' {
+            capitalize_next = true;
+        } else if capitalize_next {
+            result.push(c.to_ascii_uppercase());
+            capitalize_next = false;
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Extract doc comments from attributes
+fn extract_doc_comment(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter_map(|attr| {
+            if attr.path().is_ident("doc") {
+                attr.meta.require_name_value().ok().and_then(|nv| {
+                    if let syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(s),
+                        ..
+                    }) = &nv.value
+                    {
+                        Some(s.value().trim().to_string())
+                    } else {
+                        None
+                    }
+                })
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::ToTokens;
+    use syn::{parse_quote, Expr, Item, ItemConst, Lit, Signature};
+
+    /// The four items a command expands to: the original function, the
+    /// native command, the wasm client and the spec
+    fn expand_items(args: CommandArgs, input: ItemFn) -> (ItemFn, ItemFn, ItemFn, ItemConst) {
+        let file: syn::File = syn::parse2(expand(args, input).unwrap()).unwrap();
+        match <[Item; 4]>::try_from(file.items) {
+            Ok([Item::Fn(original), Item::Fn(native), Item::Fn(client), Item::Const(spec)]) => {
+                (original, native, client, spec)
+            }
+            _ => panic!("unexpected expansion"),
+        }
+    }
+
+    fn tokens(tokens: impl ToTokens) -> String {
+        tokens.to_token_stream().to_string()
+    }
+
+    fn arg_names(sig: &Signature) -> Vec<String> {
+        sig.inputs
+            .iter()
+            .map(|arg| match arg {
+                FnArg::Typed(pat_type) => tokens(&pat_type.pat),
+                FnArg::Receiver(_) => "self".to_string(),
+            })
+            .collect()
+    }
+
+    fn has_attr(item: &ItemFn, path: &[&str]) -> bool {
+        item.attrs.iter().any(|attr| {
+            let segments = attr.path().segments.iter().map(|seg| seg.ident.to_string());
+            segments.eq(path.iter().copied())
+        })
+    }
+
+    fn cfg(item: &ItemFn) -> String {
+        let attr = item.attrs.iter().find(|attr| attr.path().is_ident("cfg")).unwrap();
+        tokens(&attr.meta)
+    }
+
+    fn string(expr: &Expr) -> String {
+        match expr {
+            Expr::Lit(syn::ExprLit { lit: Lit::Str(s), .. }) => s.value(),
+            other => panic!("expected a string literal, got `{}`", tokens(other)),
+        }
+    }
+
+    fn spec_field<'a>(spec: &'a ItemConst, name: &str) -> &'a Expr {
+        let Expr::Struct(spec) = &*spec.expr else {
+            panic!("spec is not a struct literal");
+        };
+        &spec.fields.iter().find(|f| tokens(&f.member) == name).unwrap().expr
+    }
+
+    /// `(name, docs, args, returns)` of a spec
+    fn spec_values(spec: &ItemConst) -> (String, String, Vec<(String, String)>, String) {
+        let Expr::Reference(args) = spec_field(spec, "args") else {
+            panic!("args is not a reference");
+        };
+        let Expr::Array(args) = &*args.expr else {
+            panic!("args is not an array");
+        };
+        let args = args
+            .elems
+            .iter()
+            .map(|arg| match arg {
+                Expr::Tuple(pair) => (string(&pair.elems[0]), string(&pair.elems[1])),
+                other => panic!("expected a pair, got `{}`", tokens(other)),
+            })
+            .collect();
+        (
+            string(spec_field(spec, "name")),
+            string(spec_field(spec, "docs")),
+            args,
+            string(spec_field(spec, "returns")),
+        )
+    }
+
+    fn stmts(item: &ItemFn) -> Vec<String> {
+        item.block.stmts.iter().map(tokens).collect()
+    }
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_expands_sync_command() {
+        let (original, native, client, spec) = expand_items(
+            CommandArgs::default(),
+            parse_quote! {
+                /// Greets someone
+                ///
+                /// Politely.
+                pub fn greet(user_name: String, excited: Option<bool>) -> String {
+                    format!("Hello, {}", user_name)
+                }
+            },
+        );
+
+        // The body moves to a hidden native-only function
+        assert_eq!(original.sig.ident, "__philjs_command_greet");
+        assert_eq!(arg_names(&original.sig), ["user_name", "excited"]);
+        assert_eq!(cfg(&original), tokens(quote! { cfg(not(target_arch = "wasm32")) }));
+        assert_eq!(tokens(&original.block), tokens(quote! { { format!("Hello, {}", user_name) } }));
+
+        // The command reads each argument from the request by its camelCase name
+        assert_eq!(native.sig.ident, "greet");
+        assert!(has_attr(&native, &["tauri", "command"]));
+        assert!(has_attr(&native, &["doc"]));
+        assert!(native.sig.asyncness.is_none());
+        assert_eq!(arg_names(&native.sig), ["__philjs_request"]);
+        assert_eq!(
+            tokens(&native.sig.output),
+            tokens(quote! { -> ::std::result::Result<String, ::philjs_tauri::commands::ErrorEnvelope> })
+        );
+        assert_eq!(
+            stmts(&native)[1..],
+            [
+                tokens(quote! { let user_name: String = __philjs_args.take("userName")?; }),
+                tokens(quote! { let excited: Option<bool> = __philjs_args.take("excited")?; }),
+                tokens(quote! { let __philjs_result = __philjs_command_greet(user_name, excited); }),
+                tokens(quote! { ::std::result::Result::Ok(__philjs_result) }),
+            ]
+        );
+        assert!(stmts(&native)[0].contains("from_request (\"greet\""));
+
+        // The client takes the same arguments and invokes the command by name
+        assert_eq!(client.sig.ident, "greet");
+        assert_eq!(cfg(&client), tokens(quote! { cfg(target_arch = "wasm32") }));
+        assert!(client.sig.asyncness.is_some());
+        assert!(has_attr(&client, &["doc"]));
+        assert_eq!(arg_names(&client.sig), ["user_name", "excited"]);
+        assert_eq!(tokens(&client.sig.output), tokens(&native.sig.output));
+        let body = tokens(&client.block);
+        assert!(body.contains("invoke (\"greet\""));
+        assert!(body.contains("arg (\"userName\" , & user_name)"));
+        assert!(body.contains("arg (\"excited\" , & excited)"));
+
+        assert_eq!(spec.ident, "GREET_COMMAND");
+        assert_eq!(
+            spec_values(&spec),
+            (
+                "greet".to_string(),
+                "Greets someone\n\nPolitely.".to_string(),
+                pairs(&[("userName", "string"), ("excited", "boolean | null")]),
+                "string".to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_expands_async_command_with_injected_args() {
+        let (original, native, client, spec) = expand_items(
+            CommandArgs { rename: Some("save".to_string()) },
+            parse_quote! {
+                async fn save_note(
+                    state: State<'_, Db>,
+                    window: tauri::Window,
+                    note_id: u64,
+                    tags: Vec<String>,
+                ) -> CommandResult<Vec<Note>> {
+                    todo!()
+                }
+            },
+        );
+
+        assert!(original.sig.asyncness.is_some());
+        assert_eq!(arg_names(&original.sig), ["state", "window", "note_id", "tags"]);
+
+        // Injected arguments stay on the command, ahead of the request
+        assert!(native.sig.asyncness.is_some());
+        assert_eq!(arg_names(&native.sig), ["state", "window", "__philjs_request"]);
+        assert_eq!(
+            tokens(&native.sig.output),
+            tokens(quote! { -> ::std::result::Result<Vec<Note>, ::philjs_tauri::commands::ErrorEnvelope> })
+        );
+        assert_eq!(
+            stmts(&native)[1..],
+            [
+                tokens(quote! { let note_id: u64 = __philjs_args.take("noteId")?; }),
+                tokens(quote! { let tags: Vec<String> = __philjs_args.take("tags")?; }),
+                tokens(quote! {
+                    let __philjs_result = __philjs_command_save_note(state, window, note_id, tags).await;
+                }),
+                tokens(quote! { __philjs_result.map_err(::std::convert::Into::into) }),
+            ]
+        );
+        assert!(stmts(&native)[0].contains("from_request (\"save\""));
+
+        // ...and are left out of the client
+        assert_eq!(arg_names(&client.sig), ["note_id", "tags"]);
+        assert!(!has_attr(&client, &["doc"]));
+        assert!(tokens(&client.block).contains("invoke (\"save\""));
+
+        assert_eq!(spec.ident, "SAVE_NOTE_COMMAND");
+        assert_eq!(
+            spec_values(&spec),
+            (
+                "save".to_string(),
+                String::new(),
+                pairs(&[("noteId", "number"), ("tags", "string[]")]),
+                "Note[]".to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_expands_command_without_args_or_result() {
+        let (_, native, client, spec) = expand_items(CommandArgs::default(), parse_quote! { fn ping() {} });
+
+        assert_eq!(arg_names(&native.sig), ["__philjs_request"]);
+        assert_eq!(
+            tokens(&native.sig.output),
+            tokens(quote! { -> ::std::result::Result<(), ::philjs_tauri::commands::ErrorEnvelope> })
+        );
+        assert!(arg_names(&client.sig).is_empty());
+        assert_eq!(
+            spec_values(&spec),
+            ("ping".to_string(), String::new(), Vec::new(), "void".to_string())
+        );
+    }
+
+    #[test]
+    fn test_plain_result_return() {
+        let (_, native, _, spec) = expand_items(
+            CommandArgs::default(),
+            parse_quote! { fn load(path: PathBuf) -> Result<Option<String>, std::io::Error> { todo!() } },
+        );
+        assert!(stmts(&native).last().unwrap().contains("map_err"));
+        assert_eq!(spec_values(&spec).3, "string | null");
+    }
+
+    #[test]
+    fn test_rejects_unsupported_signatures() {
+        let error = |input: ItemFn| expand(CommandArgs::default(), input).unwrap_err().to_string();
+
+        assert_eq!(
+            error(parse_quote! { fn get(&self) {} }),
+            "#[philjs_command] cannot be used on methods"
+        );
+        assert_eq!(
+            error(parse_quote! { fn add((a, b): (u8, u8)) -> u8 { a + b } }),
+            "#[philjs_command] arguments must be plain identifiers"
+        );
+        assert_eq!(
+            error(parse_quote! { fn greet(name: &str) {} }),
+            "#[philjs_command] arguments must be owned, e.g. `String` instead of `&str`"
+        );
+    }
+
+    #[test]
+    fn test_ts_types() {
+        let cases: Vec<(Type, &str)> = vec![
+            (parse_quote!(String), "string"),
+            (parse_quote!(&'static str), "string"),
+            (parse_quote!(char), "string"),
+            (parse_quote!(std::path::PathBuf), "string"),
+            (parse_quote!(bool), "boolean"),
+            (parse_quote!(u64), "number"),
+            (parse_quote!(f32), "number"),
+            (parse_quote!(()), "null"),
+            (parse_quote!(serde_json::Value), "unknown"),
+            (parse_quote!(Option<String>), "string | null"),
+            (parse_quote!(Vec<u8>), "number[]"),
+            (parse_quote!(Vec<Option<i32>>), "(number | null)[]"),
+            (parse_quote!(Vec<Vec<bool>>), "boolean[][]"),
+            (parse_quote!([u8; 4]), "number[]"),
+            (parse_quote!(&[String]), "string[]"),
+            (parse_quote!(HashSet<String>), "string[]"),
+            (parse_quote!(HashMap<String, Vec<bool>>), "Record<string, boolean[]>"),
+            (parse_quote!(BTreeMap<u32, Note>), "Record<number, Note>"),
+            (parse_quote!((String, u8)), "[string, number]"),
+            (parse_quote!(Box<Note>), "Note"),
+            (parse_quote!(Arc<str>), "string"),
+            (parse_quote!(Cow<'static, str>), "string"),
+            (parse_quote!(Note), "Note"),
+            (parse_quote!(Page<Note>), "Page<Note>"),
+            (parse_quote!(Pair<String, u8>), "Pair<string, number>"),
+            (parse_quote!(fn() -> u8), "unknown"),
+        ];
+        for (ty, expected) in cases {
+            assert_eq!(ts_type(&ty), expected, "for `{}`", tokens(&ty));
+        }
+    }
+
+    #[test]
+    fn test_result_ok_type() {
+        let ok = |ty: Type| result_ok_type(&ty).map(tokens);
+        assert_eq!(ok(parse_quote!(Result<u8, String>)), Some("u8".to_string()));
+        assert_eq!(ok(parse_quote!(std::result::Result<Vec<Note>, E>)), Some(tokens(quote!(Vec<Note>))));
+        assert_eq!(ok(parse_quote!(CommandResult<()>)), Some("()".to_string()));
+        assert_eq!(ok(parse_quote!(Option<u8>)), None);
+        assert_eq!(ok(parse_quote!(u8)), None);
+    }
+
+    #[test]
+    fn test_injected_types() {
+        let injected = |ty: Type| is_injected(&ty);
+        assert!(injected(parse_quote!(State<'_, Db>)));
+        assert!(injected(parse_quote!(tauri::AppHandle)));
+        assert!(injected(parse_quote!(tauri::ipc::Channel<u8>)));
+        assert!(injected(parse_quote!(WebviewWindow)));
+        assert!(!injected(parse_quote!(String)));
+        assert!(!injected(parse_quote!(Vec<State>)));
+    }
+
+    #[test]
+    fn test_camel_case() {
+        assert_eq!(to_camel_case("user_id"), "userId");
+        assert_eq!(to_camel_case("_private_arg"), "privateArg");
+        assert_eq!(to_camel_case("a_b_c"), "aBC");
+        assert_eq!(to_camel_case("already"), "already");
+        assert_eq!(to_camel_case("trailing_"), "trailing");
+    }
+}
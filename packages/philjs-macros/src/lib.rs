@@ -18,6 +18,9 @@
 //! - `#[action]` - Create form actions (URL-encoded server functions)
 //! - `#[loader]` - Create data loaders (GET server functions)
 //!
+//! ## Desktop Macros
+//! - `#[philjs_command]` - Tauri commands with typed wasm/TypeScript clients
+//!
 //! ## Routing Macros
 //! - `#[route("/path/:param")]` - Define type-safe routes
 //! - `#[layout]` - Define layout components
@@ -77,6 +80,7 @@
 
 use proc_macro::TokenStream;
 
+mod command;
 mod component;
 mod props;
 mod server;
@@ -544,6 +548,56 @@ pub fn redirect(input: TokenStream) -> TokenStream {
     routing::redirect_impl(input)
 }
 
+// ============================================================================
+// Desktop Macros
+// ============================================================================
+
+/// Register a function as a Tauri command with a typed client.
+///
+/// On native targets this expands to a `#[tauri::command]` that reads each
+/// argument from the invoke payload and reports the first one that fails to
+/// deserialize as an `invalid_args` error naming the field. The function may
+/// return `T` or `Result<T, E>` for any `E: Into<ErrorEnvelope>`.
+///
+/// Compiled for `wasm32`, the same item becomes an async client stub with
+/// the same arguments, so a PhilJS frontend sharing the crate calls the
+/// command like a local function. Arguments injected by Tauri (`State`,
+/// `AppHandle`, `Window`, ...) are left out of the stub.
+///
+/// Each command also gets a `{NAME}_COMMAND` constant describing its
+/// signature, which `philjs_tauri::commands::export_typescript` turns into
+/// TypeScript bindings.
+///
+/// # Attributes
+///
+/// - `rename = "name"` - Command name seen by the frontend
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use philjs_tauri::prelude::*;
+///
+/// /// Save a note and return its id
+/// #[philjs_command]
+/// async fn save_note(
+///     store: State<'_, NoteStore>,
+///     title: String,
+///     body: Option<String>,
+/// ) -> Result<u64, CommandError> {
+///     store.insert(title, body.unwrap_or_default()).await
+/// }
+///
+/// // Backend
+/// tauri::Builder::default().invoke_handler(tauri::generate_handler![save_note]);
+///
+/// // PhilJS wasm frontend
+/// let id = save_note("Groceries".into(), None).await?;
+/// ```
+#[proc_macro_attribute]
+pub fn philjs_command(args: TokenStream, input: TokenStream) -> TokenStream {
+    command::philjs_command_impl(args, input)
+}
+
 // ============================================================================
 // Reactivity Macros (Additional)
 // ============================================================================
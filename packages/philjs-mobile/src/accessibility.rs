@@ -0,0 +1,436 @@
+//! PhilJS Mobile Accessibility
+//!
+//! Screen-reader metadata for any component, mapped to `UIAccessibility` on
+//! iOS and `AccessibilityNodeInfo` on Android.
+//!
+//! ```rust,ignore
+//! Image::symbol("trash")
+//!     .accessibility_label("Delete")
+//!     .accessibility_hint("Removes the message")
+//!     .accessibility_role(AccessibilityRole::Button)
+//! ```
+//!
+//! In debug builds the renderer tracks touch targets (buttons, text inputs
+//! and views with gestures) and [`audit`] lists those a screen reader would
+//! announce without a name.
+
+use crate::layout::FlexItem;
+use crate::{Constraints, NativeComponent, NativeView, RenderContext, Size};
+use std::cell::RefCell;
+use std::ops::{BitOr, BitOrAssign};
+
+// ============================================================================
+// Attributes
+// ============================================================================
+
+/// What kind of element a view is, which decides how it is announced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AccessibilityRole {
+    /// No particular role; announced by label only
+    #[default]
+    None,
+    Button,
+    Link,
+    Header,
+    Text,
+    Image,
+    TextField,
+    SearchField,
+    Switch,
+    Checkbox,
+    /// Slider or stepper; swipe up/down changes the value
+    Adjustable,
+    Tab,
+    TabBar,
+    List,
+    ProgressBar,
+    Alert,
+}
+
+/// State flags announced alongside the role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AccessibilityTraits(u32);
+
+impl AccessibilityTraits {
+    pub const NONE: Self = AccessibilityTraits(0);
+    pub const SELECTED: Self = AccessibilityTraits(1 << 0);
+    pub const DISABLED: Self = AccessibilityTraits(1 << 1);
+    pub const CHECKED: Self = AccessibilityTraits(1 << 2);
+    /// Content is loading
+    pub const BUSY: Self = AccessibilityTraits(1 << 3);
+    pub const EXPANDED: Self = AccessibilityTraits(1 << 4);
+    pub const COLLAPSED: Self = AccessibilityTraits(1 << 5);
+    /// Value changes often, e.g. a timer; polled rather than announced
+    pub const UPDATES_FREQUENTLY: Self = AccessibilityTraits(1 << 6);
+    /// Touches go straight to the view, e.g. a drawing canvas
+    pub const DIRECT_INTERACTION: Self = AccessibilityTraits(1 << 7);
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for AccessibilityTraits {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        AccessibilityTraits(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for AccessibilityTraits {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// How changes to a view's label or value are announced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LiveRegion {
+    #[default]
+    Off,
+    /// Announce after current speech finishes
+    Polite,
+    /// Interrupt current speech
+    Assertive,
+}
+
+/// Accessibility attributes for a view
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Accessibility {
+    /// Short name, e.g. "Delete"
+    pub label: Option<String>,
+    /// What activating the element does, e.g. "Removes the message"
+    pub hint: Option<String>,
+    /// Current value, e.g. "50%" for a slider
+    pub value: Option<String>,
+    pub role: AccessibilityRole,
+    pub traits: AccessibilityTraits,
+    pub live_region: LiveRegion,
+    /// Hide the view and its children from screen readers
+    pub hidden: bool,
+}
+
+impl Accessibility {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn role(mut self, role: AccessibilityRole) -> Self {
+        self.role = role;
+        self
+    }
+
+    pub fn traits(mut self, traits: AccessibilityTraits) -> Self {
+        self.traits |= traits;
+        self
+    }
+
+    pub fn live_region(mut self, live_region: LiveRegion) -> Self {
+        self.live_region = live_region;
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Whether a screen reader has something to call the view
+    pub fn is_named(&self) -> bool {
+        self.hidden || self.label.as_deref().map_or(false, |label| !label.trim().is_empty())
+    }
+}
+
+// ============================================================================
+// Component Modifiers
+// ============================================================================
+
+/// A component with accessibility attributes applied to its root view
+pub struct Accessible<C> {
+    child: C,
+    props: Accessibility,
+    view: RefCell<Option<NativeView>>,
+}
+
+impl<C> Accessible<C> {
+    pub fn new(child: C, props: Accessibility) -> Self {
+        Accessible {
+            child,
+            props,
+            view: RefCell::new(None),
+        }
+    }
+
+    pub fn props(&self) -> &Accessibility {
+        &self.props
+    }
+
+    // Inherent so chained calls edit these attributes instead of wrapping again
+    pub fn accessibility_label(mut self, label: impl Into<String>) -> Self {
+        self.props.label = Some(label.into());
+        self
+    }
+
+    pub fn accessibility_hint(mut self, hint: impl Into<String>) -> Self {
+        self.props.hint = Some(hint.into());
+        self
+    }
+
+    pub fn accessibility_value(mut self, value: impl Into<String>) -> Self {
+        self.props.value = Some(value.into());
+        self
+    }
+
+    pub fn accessibility_role(mut self, role: AccessibilityRole) -> Self {
+        self.props.role = role;
+        self
+    }
+
+    pub fn accessibility_traits(mut self, traits: AccessibilityTraits) -> Self {
+        self.props.traits |= traits;
+        self
+    }
+
+    pub fn live_region(mut self, live_region: LiveRegion) -> Self {
+        self.props.live_region = live_region;
+        self
+    }
+
+    pub fn accessibility_hidden(mut self, hidden: bool) -> Self {
+        self.props.hidden = hidden;
+        self
+    }
+}
+
+impl<C: NativeComponent> NativeComponent for Accessible<C> {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        let view = self.child.render(ctx);
+        ctx.set_accessibility(&view, &self.props);
+        *self.view.borrow_mut() = Some(view.clone());
+        view
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        self.child.update(ctx);
+        // Re-applied so live regions announce a changed label or value
+        if let Some(view) = &*self.view.borrow() {
+            ctx.set_accessibility(view, &self.props);
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.child.measure(constraints)
+    }
+
+    fn flex(&self) -> FlexItem {
+        self.child.flex()
+    }
+}
+
+/// Accessibility modifiers, available on every component
+pub trait AccessibilityExt: NativeComponent + Sized {
+    fn accessibility(self, props: Accessibility) -> Accessible<Self> {
+        Accessible::new(self, props)
+    }
+
+    fn accessibility_label(self, label: impl Into<String>) -> Accessible<Self> {
+        Accessible::new(self, Accessibility::new().label(label))
+    }
+
+    fn accessibility_hint(self, hint: impl Into<String>) -> Accessible<Self> {
+        Accessible::new(self, Accessibility::new().hint(hint))
+    }
+
+    fn accessibility_value(self, value: impl Into<String>) -> Accessible<Self> {
+        Accessible::new(self, Accessibility::new().value(value))
+    }
+
+    fn accessibility_role(self, role: AccessibilityRole) -> Accessible<Self> {
+        Accessible::new(self, Accessibility::new().role(role))
+    }
+
+    fn accessibility_traits(self, traits: AccessibilityTraits) -> Accessible<Self> {
+        Accessible::new(self, Accessibility::new().traits(traits))
+    }
+
+    fn live_region(self, live_region: LiveRegion) -> Accessible<Self> {
+        Accessible::new(self, Accessibility::new().live_region(live_region))
+    }
+
+    fn accessibility_hidden(self, hidden: bool) -> Accessible<Self> {
+        Accessible::new(self, Accessibility::new().hidden(hidden))
+    }
+}
+
+impl<C: NativeComponent> AccessibilityExt for C {}
+
+// ============================================================================
+// Screen Reader
+// ============================================================================
+
+/// Speak a message, e.g. "Message sent" after a background action
+pub fn announce(message: &str) {
+    // iOS: UIAccessibility.post(notification: .announcement, argument: message)
+    // Android: rootView.announceForAccessibility(message)
+    let _ = message;
+}
+
+/// Whether VoiceOver or TalkBack is running
+pub fn is_screen_reader_enabled() -> bool {
+    #[cfg(target_os = "ios")]
+    {
+        // Would return UIAccessibility.isVoiceOverRunning
+        false
+    }
+    #[cfg(target_os = "android")]
+    {
+        // Would return accessibilityManager.isTouchExplorationEnabled
+        false
+    }
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        false
+    }
+}
+
+// ============================================================================
+// Dev-mode Audit
+// ============================================================================
+
+/// Kind of touch target the audit tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchTargetKind {
+    Button,
+    TextInput,
+    /// A view with gesture recognizers attached
+    GestureTarget,
+}
+
+/// A problem found by [`audit`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessibilityIssue {
+    pub target: TouchTargetKind,
+    pub message: String,
+}
+
+impl std::fmt::Display for AccessibilityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Touch targets in the rendered tree that a screen reader can't name
+///
+/// Always empty in release builds, where nothing is tracked.
+pub fn audit() -> Vec<AccessibilityIssue> {
+    tracking::issues()
+}
+
+/// Print [`audit`] results to stderr
+pub fn report_issues() {
+    for issue in audit() {
+        eprintln!("PhilJS Mobile accessibility: {}", issue);
+    }
+}
+
+/// Views seen by the renderer in debug builds, keyed by handle address. Entries
+/// hold the handle weakly and are dropped once the view is gone.
+pub(crate) mod tracking {
+    use super::{AccessibilityIssue, TouchTargetKind};
+    use crate::NativeView;
+    use std::any::Any;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex, Weak};
+
+    struct Entry {
+        view: Weak<dyn Any + Send + Sync>,
+        target: Option<TouchTargetKind>,
+        /// Named by the platform, e.g. a button title
+        intrinsic_label: bool,
+        /// Named through accessibility attributes
+        labeled: bool,
+    }
+
+    static VIEWS: Mutex<Option<HashMap<usize, Entry>>> = Mutex::new(None);
+
+    fn key(view: &NativeView) -> usize {
+        Arc::as_ptr(&view.handle) as *const () as usize
+    }
+
+    fn with_entry(view: &NativeView, f: impl FnOnce(&mut Entry)) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        let mut views = VIEWS.lock().unwrap();
+        let views = views.get_or_insert_with(HashMap::new);
+        views.retain(|_, entry| entry.view.strong_count() > 0);
+        let entry = views.entry(key(view)).or_insert_with(|| Entry {
+            view: Arc::downgrade(&view.handle),
+            target: None,
+            intrinsic_label: false,
+            labeled: false,
+        });
+        f(entry);
+    }
+
+    /// Record a touch target, and whether the platform already names it
+    pub fn track_target(view: &NativeView, target: TouchTargetKind, has_label: bool) {
+        with_entry(view, |entry| {
+            // A button with gestures attached is still a button
+            entry.target.get_or_insert(target);
+            entry.intrinsic_label |= has_label;
+        });
+    }
+
+    /// Record accessibility attributes applied to a view
+    pub fn track_named(view: &NativeView, named: bool) {
+        with_entry(view, |entry| entry.labeled = named);
+    }
+
+    pub fn issues() -> Vec<AccessibilityIssue> {
+        let views = VIEWS.lock().unwrap();
+        let Some(views) = views.as_ref() else {
+            return Vec::new();
+        };
+        views
+            .values()
+            .filter(|entry| entry.view.strong_count() > 0)
+            .filter(|entry| !entry.labeled && !entry.intrinsic_label)
+            .filter_map(|entry| entry.target)
+            .map(|target| AccessibilityIssue {
+                target,
+                message: match target {
+                    TouchTargetKind::Button => {
+                        "button has no title or accessibility label".to_string()
+                    }
+                    TouchTargetKind::TextInput => {
+                        "text input has no placeholder or accessibility label".to_string()
+                    }
+                    TouchTargetKind::GestureTarget => {
+                        "view with gestures has no accessibility label".to_string()
+                    }
+                },
+            })
+            .collect()
+    }
+}
@@ -6,9 +6,15 @@
 
 use crate::{
     IntoView, MobileConfig, NativeView, RenderContext, Color, Rect, Size, Point,
-    renderer::{PlatformRenderer, TextStyle, ButtonConfig, TextInputConfig, ScrollConfig, ContainerStyle, ImageSource},
+    renderer::{PlatformRenderer, TextStyle, ButtonConfig, TextInputConfig, ScrollConfig, ContainerStyle, ImageSource, ListConfig},
+    navigation::NavigationTransition,
+    camera::CameraSession,
+    gestures::GestureArena,
+    animation::AnimatedStyle,
+    accessibility::Accessibility,
+    webview::{WebViewController, BRIDGE_SCRIPT},
 };
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 // ============================================================================
 // Android Application Entry Point
@@ -28,6 +34,23 @@ where
     // 2. Create the main Activity
     // 3. Set up the view hierarchy
     // 4. Start the message loop
+    // 5. Register an OnBackPressedCallback that calls
+    //    crate::navigation::handle_back_pressed() and finishes the activity
+    //    when it returns false
+    // 6. Pass the launch intent and every onNewIntent to handle_intent()
+    // 7. Route FirebaseMessagingService.onNewToken to
+    //    crate::notifications::handle_fcm_token and onMessageReceived, as
+    //    RemoteMessage JSON, to crate::notifications::handle_remote_notification
+    // 8. Observe ProcessLifecycleOwner and call
+    //    crate::lifecycle::handle_app_state_change with Active on ON_START,
+    //    Background on ON_STOP, and Suspended from onDestroy when finishing
+    // 9. Ship a PhilJsWorker (ListenableWorker) whose startWork calls
+    //    crate::lifecycle::handle_background_task(inputData["id"]) and whose
+    //    onStopped calls crate::lifecycle::handle_background_task_expired
+    // 10. In onCreate and onConfigurationChanged (with uiMode|fontScale in
+    //     android:configChanges), call crate::theme::handle_appearance_change
+    //     with uiMode & UI_MODE_NIGHT_MASK == UI_MODE_NIGHT_YES and
+    //     crate::theme::handle_font_scale_change(configuration.fontScale)
 
     let _ = (app, config);
 }
@@ -140,6 +163,42 @@ impl PlatformRenderer for AndroidRenderer {
         }
     }
 
+    fn create_list(&self, config: ListConfig) -> NativeView {
+        // Would create RecyclerView via JNI, wrapped in SwipeRefreshLayout when refreshable
+        // val recycler = RecyclerView(context)
+        // recycler.layoutManager = LinearLayoutManager(context, orientation, false)
+        // if (config.separator) recycler.addItemDecoration(DividerItemDecoration(...))
+        // if (config.refresh_control) SwipeRefreshLayout(context).addView(recycler)
+
+        NativeView {
+            handle: Arc::new(format!("RecyclerView: {:?}", config.direction)),
+            children: Vec::new(),
+        }
+    }
+
+    fn create_list_cell(&self, list: &NativeView, reuse_id: &str) -> NativeView {
+        // Would take a ViewHolder from the shared pool, keyed by view type
+        // val holder = recycler.recycledViewPool.getRecycledView(reuse_id.hashCode())
+        //     ?: adapter.createViewHolder(recycler, reuse_id.hashCode())
+        let _ = list;
+
+        NativeView {
+            handle: Arc::new(format!("ViewHolder: {}", reuse_id)),
+            children: Vec::new(),
+        }
+    }
+
+    fn set_content_size(&self, view: &NativeView, size: Size) {
+        // RecyclerView sizes its content from the adapter; plain scroll
+        // views would resize their single child
+        let _ = (view, size);
+    }
+
+    fn set_refreshing(&self, list: &NativeView, refreshing: bool) {
+        // Would set swipeRefreshLayout.isRefreshing = refreshing
+        let _ = (list, refreshing);
+    }
+
     fn add_child(&self, parent: &NativeView, child: NativeView) {
         // Would call parent.addView(child)
         let _ = (parent, child);
@@ -160,6 +219,112 @@ impl PlatformRenderer for AndroidRenderer {
         let _ = (view, frame);
     }
 
+    fn create_navigation_host(&self) -> NativeView {
+        // Would create a FragmentContainerView managed by the activity's FragmentManager
+        // val container = FragmentContainerView(context)
+        // container.id = View.generateViewId()
+
+        NativeView {
+            handle: Arc::new("FragmentContainerView"),
+            children: Vec::new(),
+        }
+    }
+
+    fn transition(
+        &self,
+        host: &NativeView,
+        from: Option<&NativeView>,
+        to: &NativeView,
+        transition: NavigationTransition,
+        reverse: bool,
+    ) {
+        // Would wrap `to` in a Fragment and commit a transaction:
+        // val tx = fragmentManager.beginTransaction().setReorderingAllowed(true)
+        // when (transition) {
+        //     Push -> tx.setCustomAnimations(R.anim.slide_in_right, R.anim.slide_out_left,
+        //                                    R.anim.slide_in_left, R.anim.slide_out_right)
+        //     Modal -> tx.setCustomAnimations(R.anim.slide_in_bottom, R.anim.fade_out,
+        //                                     R.anim.fade_in, R.anim.slide_out_bottom)
+        //     Fade -> tx.setTransition(FragmentTransaction.TRANSIT_FRAGMENT_FADE)
+        //     None, Custom -> {}
+        // }
+        // if (reverse) fragmentManager.popBackStack()
+        // else tx.replace(container.id, fragment).addToBackStack(null).commit()
+        let _ = (host, from, to, transition, reverse);
+    }
+
+    fn create_camera_preview(&self, session: &CameraSession) -> NativeView {
+        // Would create a CameraX PreviewView and attach it to the session's Preview use case
+        // val previewView = PreviewView(context)
+        // previewView.scaleType = PreviewView.ScaleType.FILL_CENTER
+        // preview.setSurfaceProvider(previewView.surfaceProvider)
+        let _ = session;
+
+        NativeView {
+            handle: Arc::new("PreviewView"),
+            children: Vec::new(),
+        }
+    }
+
+    fn create_web_view(&self, web_view: &WebViewController) -> NativeView {
+        // Would build an android.webkit.WebView with the bridge installed:
+        // webView.settings.javaScriptEnabled = true
+        // webView.addJavascriptInterface(PhilJsBridge(id), "PhilJsBridge"), whose
+        //     @JavascriptInterface postMessage(json) calls crate::webview::handle_webview_message
+        // WebViewCompat.addDocumentStartJavaScript(webView, BRIDGE_SCRIPT, setOf("*"))
+        // WebViewClient.shouldOverrideUrlLoading(request)
+        //     -> crate::webview::handle_navigation_request(id, request) == Cancel
+        // onPageStarted / onPageFinished / onReceivedError
+        //     -> crate::webview::handle_load_event(id, event)
+        // then load web_view.source() with loadUrl or loadDataWithBaseURL
+        let _ = (web_view.id(), BRIDGE_SCRIPT);
+
+        NativeView {
+            handle: Arc::new("android.webkit.WebView"),
+            children: Vec::new(),
+        }
+    }
+
+    fn attach_gestures(&self, view: &NativeView, arena: Arc<Mutex<GestureArena>>) {
+        // Would set an OnTouchListener that converts each MotionEvent:
+        // ACTION_DOWN / ACTION_POINTER_DOWN -> Began, ACTION_MOVE -> Moved,
+        // ACTION_UP / ACTION_POINTER_UP -> Ended, ACTION_CANCEL -> Cancelled,
+        // with every pointer as a Touch and getEventTime() as the timestamp
+        // A Choreographer frame callback calls arena.lock().tick(now) while arena.needs_tick()
+        // view.parent.requestDisallowInterceptTouchEvent(arena.is_active()) keeps an
+        // enclosing ScrollView from stealing an active gesture
+        let _ = (view, arena);
+    }
+
+    fn apply_animated_style(&self, view: &NativeView, style: &AnimatedStyle) {
+        // Would set View properties directly; translations and padding are in
+        // pixels, so scale by density:
+        // style.opacity -> view.setAlpha(opacity)
+        // style.transform -> setTranslationX/Y(t.translate_* * density),
+        //     setScaleX/Y(t.scale_*), setRotation(Math.toDegrees(t.rotation))
+        // style.background -> (view.background as GradientDrawable).setColor(argb)
+        // style.corner_radius -> drawable.cornerRadius = radius * density
+        // style.padding -> view.setPadding(left, top, right, bottom) in pixels
+        let _ = (view, style);
+    }
+
+    fn set_accessibility(&self, view: &NativeView, props: &Accessibility) {
+        // Would map onto AccessibilityNodeInfo via JNI:
+        // view.contentDescription = props.label (plus value, e.g. "Volume, 50%")
+        // view.importantForAccessibility = if hidden NO_HIDE_DESCENDANTS else YES
+        // view.accessibilityLiveRegion = NONE / POLITE / ASSERTIVE
+        // ViewCompat.setAccessibilityHeading(view, role == Header)
+        // ViewCompat.setAccessibilityDelegate(view) with onInitializeAccessibilityNodeInfo:
+        //     info.className = role (Button -> "android.widget.Button",
+        //         Switch -> "android.widget.Switch", Checkbox -> "android.widget.CheckBox",
+        //         Adjustable -> "android.widget.SeekBar", TextField -> "android.widget.EditText",
+        //         Image -> "android.widget.ImageView", ProgressBar -> "android.widget.ProgressBar")
+        //     info.hintText = props.hint (API 26+; otherwise tooltipText)
+        //     info.isSelected / isEnabled / isChecked / isCheckable from traits
+        //     EXPANDED / COLLAPSED -> add ACTION_COLLAPSE / ACTION_EXPAND
+        let _ = (view, props);
+    }
+
     fn measure_text(&self, text: &str, style: &TextStyle, max_width: f32) -> Size {
         // Would use Paint.measureText or StaticLayout
         // val paint = TextPaint()
@@ -404,6 +569,23 @@ impl Intent {
     }
 }
 
+/// Route a VIEW intent's URI to the installed navigator, or a notification
+/// tap to the notification handler
+///
+/// Returns whether the intent was handled.
+pub fn handle_intent(intent: &Intent) -> bool {
+    // FCM notification taps launch the app with the message data as extras
+    if intent.extras.contains_key("google.message_id") {
+        let payload = serde_json::to_string(&intent.extras).unwrap_or_default();
+        return crate::notifications::handle_notification_response(&payload, None);
+    }
+
+    match (&intent.action[..], &intent.data) {
+        ("android.intent.action.VIEW", Some(uri)) => crate::navigation::handle_open_url(uri),
+        _ => false,
+    }
+}
+
 // ============================================================================
 // Android Toast
 // ============================================================================
@@ -1,57 +1,493 @@
 //! PhilJS Mobile Animation
 //!
 //! High-performance animations for mobile applications.
-
+//!
+//! Animated values are stepped by a frame driver on the UI thread
+//! (CADisplayLink on iOS, Choreographer on Android, a timer thread in the
+//! desktop simulation). Business logic on any thread starts animations with
+//! [`Animated::animate_to`]; the driver steps them each frame and pushes
+//! [`use_animated_style`] results straight to the native views, without a
+//! re-render.
+//!
+//! ```rust,ignore
+//! let offset = AnimatedValue::new(0.0);
+//! let opacity = AnimatedValue::new(1.0);
+//!
+//! let style = {
+//!     let (offset, opacity) = (offset.clone(), opacity.clone());
+//!     use_animated_style(move || {
+//!         AnimatedStyle::new()
+//!             .opacity(opacity.get())
+//!             .transform(Transform::translate(0.0, offset.get()))
+//!     })
+//! };
+//!
+//! offset.animate_to(300.0, Animation::spring()).then(0.0, Animation::snappy());
+//! opacity.animate_to(0.0, Animation::ease_out(Duration::from_millis(200)));
+//!
+//! view! { {style.view(Card::new(content))} }
+//! ```
+
+use crate::renderer::PlatformRenderer;
+use crate::layout::FlexItem;
+use crate::{Color, Constraints, EdgeInsets, NativeComponent, NativeView, Point, RenderContext, Size};
+use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-/// Animated value that smoothly transitions between states
-pub struct AnimatedValue {
-    current: Arc<Mutex<f32>>,
-    target: f32,
-    animation: Option<Animation>,
+/// Longest physics step; longer frames are split into substeps
+const MAX_SPRING_STEP: f32 = 1.0 / 240.0;
+/// Longest frame stepped at once, so a stall doesn't fling springs
+const MAX_FRAME: Duration = Duration::from_millis(64);
+/// Springs settle once closer than this to the target...
+const REST_DISTANCE: f32 = 0.001;
+/// ...and slower than this
+const REST_VELOCITY: f32 = 0.01;
+
+// ============================================================================
+// Animatable Values
+// ============================================================================
+
+/// A value that can be animated component by component
+///
+/// Springs and curves work on each component independently; colors animate
+/// their RGBA channels, transforms their translation, scale and rotation.
+pub trait Animatable: Clone + Send + Sync + 'static {
+    fn components(&self) -> Vec<f32>;
+    fn from_components(components: &[f32]) -> Self;
+
+    /// Linear interpolation, `t` from 0.0 to 1.0
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        let components: Vec<f32> = self
+            .components()
+            .iter()
+            .zip(to.components())
+            .map(|(from, to)| from + (to - from) * t)
+            .collect();
+        Self::from_components(&components)
+    }
+}
+
+impl Animatable for f32 {
+    fn components(&self) -> Vec<f32> {
+        vec![*self]
+    }
+
+    fn from_components(c: &[f32]) -> Self {
+        c[0]
+    }
+}
+
+impl Animatable for Point {
+    fn components(&self) -> Vec<f32> {
+        vec![self.x, self.y]
+    }
+
+    fn from_components(c: &[f32]) -> Self {
+        Point::new(c[0], c[1])
+    }
+}
+
+impl Animatable for Size {
+    fn components(&self) -> Vec<f32> {
+        vec![self.width, self.height]
+    }
+
+    fn from_components(c: &[f32]) -> Self {
+        Size::new(c[0], c[1])
+    }
+}
+
+impl Animatable for Color {
+    fn components(&self) -> Vec<f32> {
+        vec![self.r, self.g, self.b, self.a]
+    }
+
+    fn from_components(c: &[f32]) -> Self {
+        // Springs overshoot; keep channels displayable
+        Color::rgba(c[0].clamp(0.0, 1.0), c[1].clamp(0.0, 1.0), c[2].clamp(0.0, 1.0), c[3].clamp(0.0, 1.0))
+    }
+}
+
+impl Animatable for EdgeInsets {
+    fn components(&self) -> Vec<f32> {
+        vec![self.top, self.right, self.bottom, self.left]
+    }
+
+    fn from_components(c: &[f32]) -> Self {
+        EdgeInsets {
+            top: c[0],
+            right: c[1],
+            bottom: c[2],
+            left: c[3],
+        }
+    }
+}
+
+/// 2D view transform, applied as translate, then rotate, then scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub translate_x: f32,
+    pub translate_y: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    /// Radians, clockwise
+    pub rotation: f32,
 }
 
-impl AnimatedValue {
-    pub fn new(initial: f32) -> Self {
-        AnimatedValue {
-            current: Arc::new(Mutex::new(initial)),
-            target: initial,
-            animation: None,
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        translate_x: 0.0,
+        translate_y: 0.0,
+        scale_x: 1.0,
+        scale_y: 1.0,
+        rotation: 0.0,
+    };
+
+    pub fn translate(x: f32, y: f32) -> Self {
+        Transform {
+            translate_x: x,
+            translate_y: y,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn scale(scale: f32) -> Self {
+        Transform {
+            scale_x: scale,
+            scale_y: scale,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn rotate(radians: f32) -> Self {
+        Transform {
+            rotation: radians,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn translated(mut self, x: f32, y: f32) -> Self {
+        self.translate_x += x;
+        self.translate_y += y;
+        self
+    }
+
+    pub fn scaled(mut self, x: f32, y: f32) -> Self {
+        self.scale_x *= x;
+        self.scale_y *= y;
+        self
+    }
+
+    pub fn rotated(mut self, radians: f32) -> Self {
+        self.rotation += radians;
+        self
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Animatable for Transform {
+    fn components(&self) -> Vec<f32> {
+        vec![self.translate_x, self.translate_y, self.scale_x, self.scale_y, self.rotation]
+    }
+
+    fn from_components(c: &[f32]) -> Self {
+        Transform {
+            translate_x: c[0],
+            translate_y: c[1],
+            scale_x: c[2],
+            scale_y: c[3],
+            rotation: c[4],
+        }
+    }
+}
+
+// ============================================================================
+// Animated Values
+// ============================================================================
+
+/// Animated number, e.g. an offset or opacity
+pub type AnimatedValue = Animated<f32>;
+
+type Completion = Box<dyn FnOnce(bool) + Send>;
+type Subscriber<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
+/// Value that smoothly transitions between states
+///
+/// Clones share the value. Starting a new animation interrupts the running
+/// one: springs carry their velocity into the new target and curves start
+/// from wherever the value is, so interruptions never jump.
+pub struct Animated<T: Animatable> {
+    inner: Arc<Mutex<AnimatedState<T>>>,
+}
+
+struct AnimatedState<T> {
+    current: Vec<f32>,
+    velocity: Vec<f32>,
+    running: Option<Segment>,
+    queue: VecDeque<Segment>,
+    subscribers: Vec<Subscriber<T>>,
+}
+
+/// One step of a chain
+struct Segment {
+    target: Vec<f32>,
+    animation: Animation,
+    from: Vec<f32>,
+    started: Option<Duration>,
+    last: Option<Duration>,
+    on_complete: Vec<Completion>,
+}
+
+impl Segment {
+    fn new(target: Vec<f32>, animation: Animation) -> Self {
+        Segment {
+            target,
+            animation,
+            from: Vec::new(),
+            started: None,
+            last: None,
+            on_complete: Vec::new(),
+        }
+    }
+}
+
+impl<T: Animatable> Animated<T> {
+    pub fn new(initial: T) -> Self {
+        let current = initial.components();
+        Animated {
+            inner: Arc::new(Mutex::new(AnimatedState {
+                velocity: vec![0.0; current.len()],
+                current,
+                running: None,
+                queue: VecDeque::new(),
+                subscribers: Vec::new(),
+            })),
         }
     }
 
     /// Get current value
-    pub fn get(&self) -> f32 {
-        *self.current.lock().unwrap()
+    pub fn get(&self) -> T {
+        T::from_components(&self.inner.lock().unwrap().current)
+    }
+
+    /// Value the animation chain will end at
+    pub fn target(&self) -> T {
+        let state = self.inner.lock().unwrap();
+        let target = state
+            .queue
+            .back()
+            .or(state.running.as_ref())
+            .map(|segment| &segment.target)
+            .unwrap_or(&state.current);
+        T::from_components(target)
+    }
+
+    /// Animate to `target`, interrupting any running animation
+    pub fn animate_to(&self, target: T, animation: Animation) -> &Self {
+        let interrupted = {
+            let mut state = self.inner.lock().unwrap();
+            let mut interrupted: Vec<Completion> = Vec::new();
+            for segment in state.running.take().into_iter().chain(state.queue.drain(..)) {
+                interrupted.extend(segment.on_complete);
+            }
+            state.queue.push_back(Segment::new(target.components(), animation));
+            interrupted
+        };
+
+        for f in interrupted {
+            f(false);
+        }
+        self.schedule();
+        self
     }
 
-    /// Set target value with animation
-    pub fn animate_to(&mut self, target: f32, animation: Animation) {
-        self.target = target;
-        self.animation = Some(animation);
+    /// Animate to `target` once the current chain finishes
+    pub fn then(&self, target: T, animation: Animation) -> &Self {
+        self.inner
+            .lock()
+            .unwrap()
+            .queue
+            .push_back(Segment::new(target.components(), animation));
+        self.schedule();
+        self
+    }
+
+    /// Called when the last animation in the chain finishes (`true`) or is
+    /// interrupted (`false`); immediately if nothing is animating
+    pub fn on_complete<F: FnOnce(bool) + Send + 'static>(&self, f: F) -> &Self {
+        let idle = {
+            let mut state = self.inner.lock().unwrap();
+            let state = &mut *state;
+            match state.queue.back_mut().or(state.running.as_mut()) {
+                Some(segment) => {
+                    segment.on_complete.push(Box::new(f));
+                    None
+                }
+                None => Some(f),
+            }
+        };
+
+        if let Some(f) = idle {
+            f(true);
+        }
+        self
     }
 
     /// Set value immediately without animation
-    pub fn set(&mut self, value: f32) {
-        self.target = value;
-        *self.current.lock().unwrap() = value;
-        self.animation = None;
+    pub fn set(&self, value: T) {
+        let (interrupted, subscribers) = {
+            let mut state = self.inner.lock().unwrap();
+            let mut interrupted: Vec<Completion> = Vec::new();
+            for segment in state.running.take().into_iter().chain(state.queue.drain(..)) {
+                interrupted.extend(segment.on_complete);
+            }
+            state.current = value.components();
+            state.velocity = vec![0.0; state.current.len()];
+            (interrupted, state.subscribers.clone())
+        };
+
+        for subscriber in subscribers {
+            subscriber(&value);
+        }
+        for f in interrupted {
+            f(false);
+        }
+        driver::invalidate_styles();
+    }
+
+    /// Stop where the value is now
+    pub fn stop(&self) {
+        let value = self.get();
+        self.set(value);
     }
 
     /// Check if animation is in progress
     pub fn is_animating(&self) -> bool {
-        self.animation.is_some() && self.get() != self.target
+        let state = self.inner.lock().unwrap();
+        state.running.is_some() || !state.queue.is_empty()
+    }
+
+    /// Called with the new value after every frame that changes it
+    pub fn subscribe<F: Fn(&T) + Send + Sync + 'static>(&self, f: F) {
+        self.inner.lock().unwrap().subscribers.push(Arc::new(f));
+    }
+
+    fn schedule(&self) {
+        let tick: Arc<dyn Tick> = self.inner.clone();
+        driver::schedule(&tick);
     }
 }
 
-impl Clone for AnimatedValue {
+impl<T: Animatable> Clone for Animated<T> {
     fn clone(&self) -> Self {
-        AnimatedValue {
-            current: Arc::clone(&self.current),
-            target: self.target,
-            animation: self.animation.clone(),
+        Animated {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+/// Work to run once a frame's locks are released
+type AfterFrame = Vec<Box<dyn FnOnce() + Send>>;
+
+/// A value the driver can step
+trait Tick: Send + Sync {
+    /// Step to `now`, returning whether it's still animating
+    fn tick(&self, now: Duration, after: &mut AfterFrame) -> bool;
+}
+
+impl<T: Animatable> Tick for Mutex<AnimatedState<T>> {
+    fn tick(&self, now: Duration, after: &mut AfterFrame) -> bool {
+        let mut state = self.lock().unwrap();
+        let state = &mut *state;
+        let mut changed = false;
+        let mut completions: Vec<Completion> = Vec::new();
+
+        loop {
+            let mut segment = match state.running.take() {
+                Some(segment) => segment,
+                None => match state.queue.pop_front() {
+                    Some(mut segment) => {
+                        segment.from = state.current.clone();
+                        if let Animation::Spring(spring) = &segment.animation {
+                            if spring.initial_velocity != 0.0 {
+                                state.velocity = vec![spring.initial_velocity; state.current.len()];
+                            }
+                        }
+                        segment
+                    }
+                    None => break,
+                },
+            };
+
+            let started = *segment.started.get_or_insert(now);
+            let dt = segment.last.map(|last| now.saturating_sub(last)).unwrap_or_default().min(MAX_FRAME);
+            segment.last = Some(now);
+
+            let done = match &segment.animation {
+                Animation::Spring(spring) => {
+                    spring.step(&mut state.current, &mut state.velocity, &segment.target, dt.as_secs_f32())
+                }
+                Animation::Timing(timing) => {
+                    let (value, done) = timing.sample(now.saturating_sub(started));
+                    set_frame(state, &segment.from, &segment.target, value, dt);
+                    done
+                }
+                Animation::Keyframe(keyframes) => {
+                    // Keyframe values are absolute and set every component
+                    let (value, done) = keyframes.sample(now.saturating_sub(started));
+                    let values = vec![value; state.current.len()];
+                    set_frame(state, &values, &values, 1.0, dt);
+                    if done {
+                        segment.target = values;
+                    }
+                    done
+                }
+            };
+            changed = true;
+
+            if !done {
+                state.running = Some(segment);
+                break;
+            }
+            // Finished at rest; the next in the chain starts this frame
+            state.velocity.iter_mut().for_each(|v| *v = 0.0);
+            completions.extend(segment.on_complete);
+        }
+
+        // Subscribers see the new value before completions run
+        if changed {
+            let value = T::from_components(&state.current);
+            let subscribers = state.subscribers.clone();
+            after.push(Box::new(move || {
+                for subscriber in subscribers {
+                    subscriber(&value);
+                }
+            }));
+        }
+        for f in completions {
+            after.push(Box::new(move || f(true)));
         }
+
+        state.running.is_some() || !state.queue.is_empty()
+    }
+}
+
+/// Move to `from + (to - from) * t`, tracking velocity for interruptions
+fn set_frame<T>(state: &mut AnimatedState<T>, from: &[f32], to: &[f32], t: f32, dt: Duration) {
+    let dt = dt.as_secs_f32();
+    for (i, (from, to)) in from.iter().zip(to).enumerate() {
+        let value = from + (to - from) * t;
+        if dt > 0.0 {
+            state.velocity[i] = (value - state.current[i]) / dt;
+        }
+        state.current[i] = value;
     }
 }
 
@@ -177,6 +613,35 @@ impl SpringAnimation {
     pub fn damping_ratio(&self) -> f32 {
         self.damping / (2.0 * (self.stiffness * self.mass).sqrt())
     }
+
+    /// Advance each component by `dt` seconds, returning whether all are at rest
+    ///
+    /// Semi-implicit Euler in substeps of at most 1/240 s, which stays stable
+    /// for the stiffest presets.
+    pub fn step(&self, position: &mut [f32], velocity: &mut [f32], target: &[f32], dt: f32) -> bool {
+        let steps = (dt / MAX_SPRING_STEP).ceil().max(1.0) as usize;
+        let h = dt / steps as f32;
+        let mass = self.mass.max(f32::EPSILON);
+
+        for _ in 0..steps {
+            for ((x, v), target) in position.iter_mut().zip(velocity.iter_mut()).zip(target) {
+                let force = -self.stiffness * (*x - target) - self.damping * *v;
+                *v += force / mass * h;
+                *x += *v * h;
+            }
+        }
+
+        let at_rest = position
+            .iter()
+            .zip(velocity.iter())
+            .zip(target)
+            .all(|((x, v), target)| (x - target).abs() < REST_DISTANCE && v.abs() < REST_VELOCITY);
+        if at_rest {
+            position.copy_from_slice(target);
+            velocity.iter_mut().for_each(|v| *v = 0.0);
+        }
+        at_rest
+    }
 }
 
 impl Default for SpringAnimation {
@@ -225,6 +690,18 @@ impl TimingAnimation {
         self.delay = delay;
         self
     }
+
+    /// Eased progress after `elapsed`, and whether the animation is done
+    pub fn sample(&self, elapsed: Duration) -> (f32, bool) {
+        let Some(elapsed) = elapsed.checked_sub(self.delay) else {
+            return (0.0, false);
+        };
+        if self.duration.is_zero() {
+            return (1.0, true);
+        }
+        let t = (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0);
+        (self.curve.evaluate(t), t >= 1.0)
+    }
 }
 
 /// Timing curve types
@@ -297,20 +774,58 @@ impl TimingCurve {
                 }
             }
             TimingCurve::CubicBezier { x1, y1, x2, y2 } => {
-                // Simplified cubic bezier evaluation
-                // Full implementation would use Newton-Raphson
-                let t2 = t * t;
-                let t3 = t2 * t;
-                let mt = 1.0 - t;
-                let mt2 = mt * mt;
-                let mt3 = mt2 * mt;
-
-                mt3 * 0.0 + 3.0 * mt2 * t * y1 + 3.0 * mt * t2 * y2 + t3 * 1.0
+                // `t` is progress along x; find the curve parameter with that x
+                let s = solve_bezier_x(t.clamp(0.0, 1.0), *x1, *x2);
+                bezier(s, *y1, *y2)
             }
         }
     }
 }
 
+/// One axis of a cubic bezier from (0, 0) to (1, 1)
+fn bezier(s: f32, p1: f32, p2: f32) -> f32 {
+    let ms = 1.0 - s;
+    3.0 * ms * ms * s * p1 + 3.0 * ms * s * s * p2 + s * s * s
+}
+
+fn bezier_slope(s: f32, p1: f32, p2: f32) -> f32 {
+    let ms = 1.0 - s;
+    3.0 * ms * ms * p1 + 6.0 * ms * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+}
+
+/// Parameter `s` where the curve's x equals `x`: Newton-Raphson, falling
+/// back to bisection where the slope is flat
+fn solve_bezier_x(x: f32, x1: f32, x2: f32) -> f32 {
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(s, x1, x2) - x;
+        if error.abs() < 1e-5 {
+            return s;
+        }
+        let slope = bezier_slope(s, x1, x2);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s = (s - error / slope).clamp(0.0, 1.0);
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    s = x;
+    for _ in 0..32 {
+        let value = bezier(s, x1, x2);
+        if (value - x).abs() < 1e-5 {
+            break;
+        }
+        if value < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+    s
+}
+
 /// Keyframe animation
 #[derive(Debug, Clone)]
 pub struct KeyframeAnimation {
@@ -358,6 +873,56 @@ impl KeyframeAnimation {
         self.repeat = mode;
         self
     }
+
+    /// Value after `elapsed`, and whether every repeat has played
+    pub fn sample(&self, elapsed: Duration) -> (f32, bool) {
+        let (cycles, autoreverse) = match self.repeat {
+            RepeatMode::None => (Some(1), false),
+            RepeatMode::Repeat(n) => (Some(n.max(1)), false),
+            RepeatMode::Forever => (None, false),
+            RepeatMode::Autoreverse(n) => (Some(n.max(1)), true),
+            RepeatMode::AutoreverseForever => (None, true),
+        };
+
+        let position = if self.duration.is_zero() {
+            f32::INFINITY
+        } else {
+            elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        };
+        let (progress, done) = match cycles {
+            Some(cycles) if position >= cycles as f32 => {
+                // Autoreverse ends back at the start after an even count
+                let end = if autoreverse && cycles % 2 == 0 { 0.0 } else { 1.0 };
+                (end, true)
+            }
+            _ => {
+                let cycle = position.floor();
+                let t = position - cycle;
+                let reversed = autoreverse && cycle as u64 % 2 == 1;
+                (if reversed { 1.0 - t } else { t }, false)
+            }
+        };
+        (self.value_at(progress), done)
+    }
+
+    /// Value at `progress` from 0.0 to 1.0 through the keyframes
+    pub fn value_at(&self, progress: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.keyframes.first(), self.keyframes.last()) else {
+            return 0.0;
+        };
+        if progress <= first.time {
+            return first.value;
+        }
+        if progress >= last.time {
+            return last.value;
+        }
+
+        let next = self.keyframes.partition_point(|k| k.time <= progress);
+        let (a, b) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let span = b.time - a.time;
+        let t = if span > 0.0 { (progress - a.time) / span } else { 1.0 };
+        a.value + (b.value - a.value) * a.easing.evaluate(t)
+    }
 }
 
 /// Animation repeat mode
@@ -463,23 +1028,41 @@ pub fn animate<F: FnOnce()>(f: F) {
 }
 
 /// Create a repeating animation
+///
+/// `f` is called each frame with a value running from 0.0 to 1.0 along the
+/// animation's curve, restarting until the handle is dropped. Springs have
+/// no fixed duration and run a single time.
 pub fn repeat_forever<F: Fn(f32) + Send + Sync + 'static>(
     animation: Animation,
     f: F,
 ) -> AnimationHandle {
-    // Would start the animation loop
-    AnimationHandle { id: 0 }
+    let value = AnimatedValue::new(0.0);
+    value.subscribe(move |v| f(*v));
+
+    let animation = match animation {
+        Animation::Timing(timing) => Animation::Keyframe(
+            KeyframeAnimation::new(timing.duration)
+                .keyframe_eased(0.0, 0.0, timing.curve)
+                .keyframe(1.0, 1.0)
+                .repeat(RepeatMode::Forever),
+        ),
+        Animation::Keyframe(keyframes) => Animation::Keyframe(keyframes.repeat(RepeatMode::Forever)),
+        spring => spring,
+    };
+    value.animate_to(1.0, animation);
+
+    AnimationHandle { value }
 }
 
 /// Handle to a running animation
 pub struct AnimationHandle {
-    id: u64,
+    value: AnimatedValue,
 }
 
 impl AnimationHandle {
     /// Stop the animation
     pub fn stop(&self) {
-        // Would stop the animation
+        self.value.stop();
     }
 }
 
@@ -488,3 +1071,574 @@ impl Drop for AnimationHandle {
         self.stop();
     }
 }
+
+// ============================================================================
+// Animated Styles
+// ============================================================================
+
+/// View properties set directly by the frame driver
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnimatedStyle {
+    pub opacity: Option<f32>,
+    pub transform: Option<Transform>,
+    pub background: Option<Color>,
+    pub corner_radius: Option<f32>,
+    pub padding: Option<EdgeInsets>,
+}
+
+impl AnimatedStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = Some(opacity.clamp(0.0, 1.0));
+        self
+    }
+
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = Some(radius.max(0.0));
+        self
+    }
+
+    pub fn padding(mut self, padding: EdgeInsets) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+}
+
+type StyleFn = Arc<dyn Fn() -> AnimatedStyle + Send + Sync>;
+
+/// A style computed from animated values
+///
+/// Created by [`use_animated_style`]; wrap components with
+/// [`view`](Self::view) to have it applied.
+#[derive(Clone)]
+pub struct AnimatedStyleHook {
+    style: StyleFn,
+}
+
+/// Compute a view style from animated values on every frame
+///
+/// The closure runs on the UI thread during frames where a value changed,
+/// and its result goes straight to the native view. It should only read
+/// animated values and captured constants.
+pub fn use_animated_style<F>(f: F) -> AnimatedStyleHook
+where
+    F: Fn() -> AnimatedStyle + Send + Sync + 'static,
+{
+    AnimatedStyleHook { style: Arc::new(f) }
+}
+
+impl AnimatedStyleHook {
+    /// The style for the current values
+    pub fn current(&self) -> AnimatedStyle {
+        (self.style)()
+    }
+
+    /// Apply this style to a component
+    pub fn view(&self, child: impl NativeComponent + 'static) -> AnimatedView {
+        AnimatedView {
+            child: Box::new(child),
+            style: Arc::clone(&self.style),
+            binding: RefCell::new(None),
+        }
+    }
+}
+
+/// A component whose native view follows an [`AnimatedStyleHook`]
+pub struct AnimatedView {
+    child: Box<dyn NativeComponent>,
+    style: StyleFn,
+    binding: RefCell<Option<Arc<StyleBinding>>>,
+}
+
+impl NativeComponent for AnimatedView {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        let view = self.child.render(ctx);
+        let binding = Arc::new(StyleBinding {
+            view: view.clone(),
+            renderer: ctx.clone_renderer(),
+            style: Arc::clone(&self.style),
+            last: Mutex::new(None),
+        });
+        binding.apply();
+
+        // The driver holds a weak reference; the binding lives with this component
+        driver::bind_style(&binding);
+        *self.binding.borrow_mut() = Some(binding);
+        view
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        self.child.update(ctx);
+        if let Some(binding) = &*self.binding.borrow() {
+            binding.apply();
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        // Transforms don't affect layout
+        self.child.measure(constraints)
+    }
+
+    fn flex(&self) -> FlexItem {
+        self.child.flex()
+    }
+}
+
+struct StyleBinding {
+    view: NativeView,
+    renderer: Box<dyn PlatformRenderer>,
+    style: StyleFn,
+    last: Mutex<Option<AnimatedStyle>>,
+}
+
+impl StyleBinding {
+    /// Push the style to the view if it changed
+    fn apply(&self) {
+        let style = (self.style)();
+        let mut last = self.last.lock().unwrap();
+        if last.as_ref() != Some(&style) {
+            self.renderer.apply_animated_style(&self.view, &style);
+            *last = Some(style);
+        }
+    }
+}
+
+// ============================================================================
+// Frame Driver
+// ============================================================================
+
+/// Step all running animations to `now` and apply animated styles
+///
+/// Called by the platform's frame callback on the UI thread. `now` is the
+/// frame timestamp, measured from any fixed point. Returns whether another
+/// frame is needed.
+pub fn drive_frame(now: Duration) -> bool {
+    driver::frame(now)
+}
+
+/// Whether any animation is waiting for a frame
+pub fn needs_frame() -> bool {
+    driver::needs_frame()
+}
+
+mod driver {
+    use super::{AfterFrame, StyleBinding, Tick};
+    use std::sync::{Arc, Mutex, Weak};
+    use std::time::Duration;
+
+    struct Driver {
+        values: Vec<Weak<dyn Tick>>,
+        styles: Vec<Weak<StyleBinding>>,
+        styles_dirty: bool,
+    }
+
+    static DRIVER: Mutex<Driver> = Mutex::new(Driver {
+        values: Vec::new(),
+        styles: Vec::new(),
+        styles_dirty: false,
+    });
+
+    fn same(a: &Weak<dyn Tick>, b: &Weak<dyn Tick>) -> bool {
+        a.as_ptr() as *const () == b.as_ptr() as *const ()
+    }
+
+    pub(super) fn schedule(value: &Arc<dyn Tick>) {
+        let weak = Arc::downgrade(value);
+        let mut driver = DRIVER.lock().unwrap();
+        if !driver.values.iter().any(|v| same(v, &weak)) {
+            driver.values.push(weak);
+        }
+        drop(driver);
+        request_frame();
+    }
+
+    pub(super) fn bind_style(binding: &Arc<StyleBinding>) {
+        DRIVER.lock().unwrap().styles.push(Arc::downgrade(binding));
+    }
+
+    /// A value jumped; restyle on the next frame
+    pub(super) fn invalidate_styles() {
+        DRIVER.lock().unwrap().styles_dirty = true;
+        request_frame();
+    }
+
+    pub(super) fn needs_frame() -> bool {
+        let driver = DRIVER.lock().unwrap();
+        !driver.values.is_empty() || driver.styles_dirty
+    }
+
+    pub(super) fn frame(now: Duration) -> bool {
+        let values = {
+            let mut driver = DRIVER.lock().unwrap();
+            driver.styles_dirty = false;
+            driver.values.clone()
+        };
+
+        let mut after: AfterFrame = Vec::new();
+        let finished: Vec<Weak<dyn Tick>> = values
+            .iter()
+            .filter(|weak| match weak.upgrade() {
+                Some(value) => !value.tick(now, &mut after),
+                None => true,
+            })
+            .cloned()
+            .collect();
+
+        let styles = {
+            let mut driver = DRIVER.lock().unwrap();
+            // Values scheduled during the frame aren't in `finished` and stay
+            driver.values.retain(|v| !finished.iter().any(|f| same(f, v)));
+            driver.styles.retain(|s| s.strong_count() > 0);
+            driver.styles.clone()
+        };
+
+        for binding in styles.iter().filter_map(Weak::upgrade) {
+            binding.apply();
+        }
+
+        // Subscribers and completions may start new animations
+        for f in after {
+            f();
+        }
+        needs_frame()
+    }
+
+    /// Ask the platform for a frame callback
+    fn request_frame() {
+        #[cfg(target_os = "ios")]
+        {
+            // Would unpause the main thread's CADisplayLink; its callback calls
+            // drive_frame(Duration::from_secs_f64(link.targetTimestamp)) and
+            // pauses the link again once it returns false
+        }
+        #[cfg(target_os = "android")]
+        {
+            // Would post a Choreographer.FrameCallback on the main looper; it calls
+            // drive_frame(Duration::from_nanos(frameTimeNanos)) and reposts itself
+            // while it returns true
+        }
+        #[cfg(not(any(target_os = "ios", target_os = "android")))]
+        {
+            simulated_display_link();
+        }
+    }
+
+    /// A 60 Hz timer thread standing in for the display link
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    fn simulated_display_link() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Instant;
+
+        static RUNNING: AtomicBool = AtomicBool::new(false);
+        if RUNNING.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        std::thread::spawn(|| {
+            let epoch = Instant::now();
+            loop {
+                std::thread::sleep(Duration::from_millis(16));
+                if !frame(epoch.elapsed()) {
+                    RUNNING.store(false, Ordering::Release);
+                    // A frame requested between the check and the store
+                    if !needs_frame() || RUNNING.swap(true, Ordering::AcqRel) {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One 60 Hz frame
+    const FRAME: f32 = 1.0 / 60.0;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    /// Step a spring at 60 Hz until it rests, returning the frame count and
+    /// the furthest position reached
+    fn settle(spring: &SpringAnimation, from: f32, to: f32, max_frames: usize) -> (usize, f32) {
+        let (mut x, mut v) = ([from], [spring.initial_velocity]);
+        let mut peak = from;
+        for frame in 1..=max_frames {
+            if spring.step(&mut x, &mut v, &[to], FRAME) {
+                assert_eq!((x[0], v[0]), (to, 0.0), "snaps to the target at rest");
+                return (frame, peak);
+            }
+            peak = peak.max(x[0]);
+        }
+        panic!("spring did not settle in {} frames", max_frames);
+    }
+
+    /// Queue a segment without scheduling it on the frame driver
+    fn queue(value: &AnimatedValue, target: f32, animation: Animation) {
+        value
+            .inner
+            .lock()
+            .unwrap()
+            .queue
+            .push_back(Segment::new(vec![target], animation));
+    }
+
+    /// Step a value to `now` as the driver would, running subscribers and completions
+    fn frame(value: &AnimatedValue, now: u64) -> bool {
+        let mut after: AfterFrame = Vec::new();
+        let running = value.inner.tick(ms(now), &mut after);
+        for f in after {
+            f();
+        }
+        running
+    }
+
+    #[test]
+    fn test_spring_presets_converge() {
+        let (frames, peak) = settle(&SpringAnimation::default_spring(), 0.0, 100.0, 600);
+        assert!(frames < 120, "default spring took {} frames", frames);
+        // Close to critically damped: barely overshoots
+        assert!(peak < 100.5);
+
+        let (_, peak) = settle(&SpringAnimation::bouncy(), 0.0, 100.0, 600);
+        assert!(peak > 110.0, "bouncy spring should overshoot, peaked at {}", peak);
+
+        for spring in [SpringAnimation::smooth(), SpringAnimation::snappy(), SpringAnimation::gentle()] {
+            settle(&spring, 0.0, 100.0, 600);
+            settle(&spring, 100.0, -50.0, 600);
+        }
+    }
+
+    #[test]
+    fn test_spring_initial_velocity() {
+        // Flung away from the target first
+        let spring = SpringAnimation::default_spring().velocity(-500.0);
+        let (mut x, mut v) = ([0.0], [spring.initial_velocity]);
+        spring.step(&mut x, &mut v, &[100.0], FRAME);
+        assert!(x[0] < 0.0);
+        settle(&spring, 0.0, 100.0, 600);
+    }
+
+    #[test]
+    fn test_spring_substeps_are_frame_rate_independent() {
+        let spring = SpringAnimation::snappy();
+        let (mut a, mut va) = ([0.0], [0.0]);
+        let (mut b, mut vb) = ([0.0], [0.0]);
+
+        spring.step(&mut a, &mut va, &[100.0], 0.064);
+        for _ in 0..4 {
+            spring.step(&mut b, &mut vb, &[100.0], 0.016);
+        }
+        assert!(close(a[0], b[0]));
+        assert!((va[0] - vb[0]).abs() < va[0].abs() * 1e-4);
+    }
+
+    #[test]
+    fn test_spring_steps_components_independently() {
+        let spring = SpringAnimation::default_spring();
+        let (mut x, mut v) = ([0.0, 50.0], [0.0, 0.0]);
+        spring.step(&mut x, &mut v, &[10.0, 50.0], FRAME);
+        assert!(x[0] > 0.0);
+        assert_eq!((x[1], v[1]), (50.0, 0.0));
+    }
+
+    #[test]
+    fn test_timing_endpoints_and_delay() {
+        let linear = TimingAnimation::linear(ms(200));
+        assert_eq!(linear.sample(ms(0)), (0.0, false));
+        assert_eq!(linear.sample(ms(100)), (0.5, false));
+        assert_eq!(linear.sample(ms(200)), (1.0, true));
+        assert_eq!(linear.sample(ms(900)), (1.0, true));
+
+        let delayed = TimingAnimation::ease_in(ms(100)).delay(ms(50));
+        assert_eq!(delayed.sample(ms(25)), (0.0, false));
+        assert_eq!(delayed.sample(ms(50)), (0.0, false));
+        assert!(close(delayed.sample(ms(100)).0, 0.25));
+        assert_eq!(delayed.sample(ms(150)), (1.0, true));
+
+        assert_eq!(TimingAnimation::linear(Duration::ZERO).sample(ms(0)), (1.0, true));
+    }
+
+    #[test]
+    fn test_curves() {
+        let curves = [
+            TimingCurve::Linear,
+            TimingCurve::EaseIn,
+            TimingCurve::EaseOut,
+            TimingCurve::EaseInOut,
+            TimingCurve::ios_default(),
+            TimingCurve::material_standard(),
+            TimingCurve::material_decelerate(),
+            TimingCurve::material_accelerate(),
+        ];
+        for curve in curves {
+            assert!(close(curve.evaluate(0.0), 0.0), "{:?} starts at 0", curve);
+            assert!(close(curve.evaluate(1.0), 1.0), "{:?} ends at 1", curve);
+            let samples: Vec<f32> = (0..=20).map(|i| curve.evaluate(i as f32 / 20.0)).collect();
+            assert!(samples.windows(2).all(|w| w[1] >= w[0] - 1e-4), "{:?} is monotonic", curve);
+        }
+
+        assert!(TimingCurve::EaseIn.evaluate(0.25) < 0.25);
+        assert!(TimingCurve::EaseOut.evaluate(0.25) > 0.25);
+        assert!(close(TimingCurve::EaseInOut.evaluate(0.5), 0.5));
+
+        // A bezier along the diagonal is linear
+        let diagonal = TimingCurve::CubicBezier { x1: 0.25, y1: 0.25, x2: 0.75, y2: 0.75 };
+        for t in [0.1, 0.3, 0.5, 0.9] {
+            assert!(close(diagonal.evaluate(t), t));
+        }
+    }
+
+    #[test]
+    fn test_keyframes() {
+        let keyframes = KeyframeAnimation::new(ms(1000))
+            .keyframe_eased(1.0, 50.0, TimingCurve::Linear)
+            .keyframe_eased(0.0, 0.0, TimingCurve::Linear)
+            .keyframe_eased(0.5, 100.0, TimingCurve::Linear);
+
+        // Sorted by time regardless of insertion order
+        assert_eq!(keyframes.value_at(0.0), 0.0);
+        assert!(close(keyframes.value_at(0.25), 50.0));
+        assert_eq!(keyframes.value_at(0.5), 100.0);
+        assert!(close(keyframes.value_at(0.75), 75.0));
+        assert_eq!(keyframes.value_at(1.0), 50.0);
+        assert_eq!(keyframes.value_at(-1.0), 0.0);
+        assert_eq!(keyframes.value_at(2.0), 50.0);
+
+        assert!(close(keyframes.sample(ms(250)).0, 50.0));
+        assert_eq!(keyframes.sample(ms(1000)), (50.0, true));
+        assert_eq!(KeyframeAnimation::new(ms(100)).value_at(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_keyframe_repeats() {
+        let keyframes = KeyframeAnimation::new(ms(1000))
+            .keyframe_eased(0.0, 0.0, TimingCurve::Linear)
+            .keyframe_eased(1.0, 100.0, TimingCurve::Linear);
+
+        let twice = keyframes.clone().repeat(RepeatMode::Repeat(2));
+        let (value, done) = twice.sample(ms(1500));
+        assert!(close(value, 50.0) && !done);
+        assert_eq!(twice.sample(ms(2000)), (100.0, true));
+
+        let reversing = keyframes.clone().repeat(RepeatMode::Autoreverse(2));
+        let (value, done) = reversing.sample(ms(1250));
+        assert!(close(value, 75.0) && !done);
+        // An even number of plays ends back at the start
+        assert_eq!(reversing.sample(ms(2000)), (0.0, true));
+        assert_eq!(keyframes.clone().repeat(RepeatMode::Autoreverse(3)).sample(ms(3000)), (100.0, true));
+
+        let forever = keyframes.repeat(RepeatMode::Forever);
+        let (value, done) = forever.sample(ms(100_500));
+        assert!(close(value, 50.0) && !done);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let point = Point::new(0.0, 0.0).interpolate(&Point::new(10.0, 20.0), 0.5);
+        assert_eq!(point, Point::new(5.0, 10.0));
+
+        // Overshooting springs keep colors displayable
+        let color = Color::from_components(&[1.5, -0.2, 0.5, 1.0]);
+        assert_eq!((color.r, color.g, color.b, color.a), (1.0, 0.0, 0.5, 1.0));
+    }
+
+    #[test]
+    fn test_chain_runs_in_order() {
+        let value = AnimatedValue::new(0.0);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let completed = Arc::new(Mutex::new(Vec::new()));
+        {
+            let seen = seen.clone();
+            value.subscribe(move |v| seen.lock().unwrap().push(*v));
+        }
+
+        queue(&value, 10.0, Animation::linear(ms(100)));
+        let done = completed.clone();
+        value.on_complete(move |finished| done.lock().unwrap().push(("first", finished)));
+        queue(&value, 20.0, Animation::linear(ms(100)));
+        let done = completed.clone();
+        value.on_complete(move |finished| done.lock().unwrap().push(("second", finished)));
+        assert_eq!(value.target(), 20.0);
+        assert!(value.is_animating());
+
+        assert!(frame(&value, 1000));
+        assert!(frame(&value, 1050));
+        assert!(close(value.get(), 5.0));
+        // The first segment ends and the second starts in the same frame
+        assert!(frame(&value, 1100));
+        assert_eq!(value.get(), 10.0);
+        assert_eq!(*completed.lock().unwrap(), [("first", true)]);
+        assert!(frame(&value, 1150));
+        assert!(close(value.get(), 15.0));
+        assert!(!frame(&value, 1200));
+
+        assert_eq!(value.get(), 20.0);
+        assert!(!value.is_animating());
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 5);
+        assert!(seen.iter().zip([0.0, 5.0, 10.0, 15.0, 20.0]).all(|(a, b)| close(*a, b)));
+        assert_eq!(*completed.lock().unwrap(), [("first", true), ("second", true)]);
+    }
+
+    #[test]
+    fn test_spring_segment_then_timing() {
+        let value = AnimatedValue::new(0.0);
+        queue(&value, 100.0, Animation::spring());
+        queue(&value, 0.0, Animation::linear(ms(100)));
+
+        let mut now = 0;
+        loop {
+            assert!(frame(&value, now));
+            if value.get() == 100.0 {
+                break;
+            }
+            now += 16;
+            assert!(now < 2000, "spring segment did not finish");
+        }
+        // The timing segment starts from where the spring came to rest
+        assert!(frame(&value, now + 50));
+        assert!(close(value.get(), 50.0));
+        assert!(!frame(&value, now + 100));
+        assert_eq!(value.get(), 0.0);
+    }
+
+    #[test]
+    fn test_set_interrupts_chain() {
+        let value = AnimatedValue::new(0.0);
+        let result = Arc::new(Mutex::new(None));
+        queue(&value, 10.0, Animation::linear(ms(100)));
+        let slot = result.clone();
+        value.on_complete(move |finished| *slot.lock().unwrap() = Some(finished));
+
+        value.set(3.0);
+        assert_eq!(*result.lock().unwrap(), Some(false));
+        assert_eq!(value.get(), 3.0);
+        assert!(!value.is_animating());
+
+        // Nothing queued: completes right away
+        let slot = result.clone();
+        value.on_complete(move |finished| *slot.lock().unwrap() = Some(finished));
+        assert_eq!(*result.lock().unwrap(), Some(true));
+    }
+}
@@ -3,9 +3,13 @@
 //! Core UI components for building mobile applications.
 
 use crate::{
-    Color, Constraints, EdgeInsets, FontWeight, IntoView, NativeComponent, NativeView,
+    Color, Constraints, EdgeInsets, FontWeight, IntoView, NativeComponent, NativeView, Rect,
     RenderContext, Size, TextAlign,
 };
+use crate::layout::{flex_layout, overlay_layout, AlignItems, FlexItem, FlexStyle, Layout};
+use crate::theme::{self, token, Typography};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 // ============================================================================
@@ -24,11 +28,18 @@ pub struct Text {
 
 impl Text {
     pub fn new(content: impl Into<String>) -> Self {
+        Text::styled(content, Typography::Body)
+    }
+
+    /// Text in a type scale role, sized for the system text size
+    pub fn styled(content: impl Into<String>, style: Typography) -> Self {
+        let theme = theme::current();
+        let font = theme.font(style);
         Text {
             content: content.into(),
-            font_size: 17.0,
-            font_weight: FontWeight::Regular,
-            color: Color::BLACK,
+            font_size: font.size,
+            font_weight: font.weight,
+            color: theme.color(token::LABEL),
             alignment: TextAlign::Left,
             max_lines: None,
         }
@@ -66,23 +77,23 @@ impl Text {
 
     // Semantic constructors
     pub fn title(content: impl Into<String>) -> Self {
-        Text::new(content).font_size(28.0).font_weight(FontWeight::Bold)
+        Text::styled(content, Typography::Title)
     }
 
     pub fn headline(content: impl Into<String>) -> Self {
-        Text::new(content).font_size(22.0).font_weight(FontWeight::SemiBold)
+        Text::styled(content, Typography::Title2)
     }
 
     pub fn subheadline(content: impl Into<String>) -> Self {
-        Text::new(content).font_size(15.0).color(Color::rgba(0.4, 0.4, 0.4, 1.0))
+        Text::styled(content, Typography::Subheadline).color(Color::semantic(token::SECONDARY_LABEL))
     }
 
     pub fn body(content: impl Into<String>) -> Self {
-        Text::new(content).font_size(17.0)
+        Text::styled(content, Typography::Body)
     }
 
     pub fn caption(content: impl Into<String>) -> Self {
-        Text::new(content).font_size(12.0).color(Color::rgba(0.5, 0.5, 0.5, 1.0))
+        Text::styled(content, Typography::Caption).color(Color::semantic(token::TERTIARY_LABEL))
     }
 }
 
@@ -139,10 +150,11 @@ pub struct ButtonStyle {
 
 impl Default for ButtonStyle {
     fn default() -> Self {
+        let theme = theme::current();
         ButtonStyle {
-            background: Color::from_hex(0x007AFF),
-            text_color: Color::WHITE,
-            font_size: 17.0,
+            background: theme.color(token::PRIMARY),
+            text_color: theme.color(token::ON_PRIMARY),
+            font_size: theme.font(Typography::Headline).size,
             font_weight: FontWeight::SemiBold,
             corner_radius: 10.0,
             padding: EdgeInsets::symmetric(20.0, 14.0),
@@ -187,13 +199,13 @@ impl<F> Button<F> {
     pub fn secondary(title: impl Into<String>) -> Self {
         let mut btn = Button::new(title);
         btn.style.background = Color::TRANSPARENT;
-        btn.style.text_color = Color::from_hex(0x007AFF);
+        btn.style.text_color = Color::semantic(token::PRIMARY);
         btn
     }
 
     pub fn destructive(title: impl Into<String>) -> Self {
         let mut btn = Button::new(title);
-        btn.style.background = Color::from_hex(0xFF3B30);
+        btn.style.background = Color::semantic(token::DESTRUCTIVE);
         btn
     }
 }
@@ -359,7 +371,7 @@ impl<F> Switch<F> {
             value,
             on_change: None,
             enabled: true,
-            on_tint: Color::from_hex(0x34C759), // iOS green
+            on_tint: Color::semantic(token::SUCCESS),
         }
     }
 
@@ -485,6 +497,42 @@ impl Default for VStack {
     }
 }
 
+impl VStack {
+    fn style(&self) -> FlexStyle {
+        FlexStyle::column()
+            .gap(self.spacing)
+            .padding(self.padding)
+            .align(self.alignment.into())
+    }
+}
+
+impl NativeComponent for VStack {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        let layout = flex_layout(&self.style(), &self.children, ctx.constraints);
+        render_layout(ctx, crate::renderer::ContainerStyle::default(), &self.children, &layout)
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        for child in &self.children {
+            child.update(ctx);
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        flex_layout(&self.style(), &self.children, constraints).size
+    }
+}
+
+impl From<HAlignment> for AlignItems {
+    fn from(alignment: HAlignment) -> Self {
+        match alignment {
+            HAlignment::Leading => AlignItems::Start,
+            HAlignment::Center => AlignItems::Center,
+            HAlignment::Trailing => AlignItems::End,
+        }
+    }
+}
+
 /// Horizontal stack layout
 pub struct HStack {
     pub children: Vec<Box<dyn NativeComponent>>,
@@ -523,6 +571,11 @@ impl HStack {
         self
     }
 
+    pub fn padding(mut self, padding: EdgeInsets) -> Self {
+        self.padding = padding;
+        self
+    }
+
     pub fn child(mut self, component: impl NativeComponent + 'static) -> Self {
         self.children.push(Box::new(component));
         self
@@ -535,6 +588,43 @@ impl Default for HStack {
     }
 }
 
+impl HStack {
+    fn style(&self) -> FlexStyle {
+        FlexStyle::row()
+            .gap(self.spacing)
+            .padding(self.padding)
+            .align(self.alignment.into())
+    }
+}
+
+impl NativeComponent for HStack {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        let layout = flex_layout(&self.style(), &self.children, ctx.constraints);
+        render_layout(ctx, crate::renderer::ContainerStyle::default(), &self.children, &layout)
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        for child in &self.children {
+            child.update(ctx);
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        flex_layout(&self.style(), &self.children, constraints).size
+    }
+}
+
+impl From<VAlignment> for AlignItems {
+    fn from(alignment: VAlignment) -> Self {
+        match alignment {
+            // Baselines need text metrics the layout pass doesn't have yet
+            VAlignment::Top | VAlignment::FirstTextBaseline | VAlignment::LastTextBaseline => AlignItems::Start,
+            VAlignment::Center => AlignItems::Center,
+            VAlignment::Bottom => AlignItems::End,
+        }
+    }
+}
+
 /// Overlay stack (Z-axis)
 pub struct ZStack {
     pub children: Vec<Box<dyn NativeComponent>>,
@@ -580,6 +670,121 @@ impl Default for ZStack {
     }
 }
 
+impl NativeComponent for ZStack {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        let (horizontal, vertical) = self.alignment.axes();
+        let layout = overlay_layout(horizontal, vertical, &self.children, ctx.constraints);
+        render_layout(ctx, crate::renderer::ContainerStyle::default(), &self.children, &layout)
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        for child in &self.children {
+            child.update(ctx);
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        let (horizontal, vertical) = self.alignment.axes();
+        overlay_layout(horizontal, vertical, &self.children, constraints).size
+    }
+}
+
+impl Alignment {
+    /// Horizontal and vertical alignment
+    pub fn axes(self) -> (AlignItems, AlignItems) {
+        use AlignItems::{Center, End, Start};
+        match self {
+            Alignment::TopLeading => (Start, Start),
+            Alignment::Top => (Center, Start),
+            Alignment::TopTrailing => (End, Start),
+            Alignment::Leading => (Start, Center),
+            Alignment::Center => (Center, Center),
+            Alignment::Trailing => (End, Center),
+            Alignment::BottomLeading => (Start, End),
+            Alignment::Bottom => (Center, End),
+            Alignment::BottomTrailing => (End, End),
+        }
+    }
+}
+
+/// Controls how a child grows and shrinks inside a stack
+///
+/// ```rust,ignore
+/// HStack::new()
+///     .child(Text::new("Name"))
+///     .child(Flexible::new(TextInput::new()))
+/// ```
+pub struct Flexible {
+    pub child: Box<dyn NativeComponent>,
+    pub item: FlexItem,
+}
+
+impl Flexible {
+    /// Wrap a child that takes an equal share of the free space
+    pub fn new(child: impl NativeComponent + 'static) -> Self {
+        Flexible {
+            child: Box::new(child),
+            item: FlexItem::default().grow(1.0),
+        }
+    }
+
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.item = self.item.grow(grow);
+        self
+    }
+
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.item = self.item.shrink(shrink);
+        self
+    }
+
+    pub fn basis(mut self, basis: f32) -> Self {
+        self.item = self.item.basis(basis);
+        self
+    }
+
+    pub fn align_self(mut self, align: AlignItems) -> Self {
+        self.item = self.item.align_self(align);
+        self
+    }
+}
+
+impl NativeComponent for Flexible {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        self.child.render(ctx)
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        self.child.update(ctx);
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.child.measure(constraints)
+    }
+
+    fn flex(&self) -> FlexItem {
+        self.item
+    }
+}
+
+/// Render children into a new container at the frames from `layout`
+fn render_layout(
+    ctx: &mut RenderContext,
+    style: crate::renderer::ContainerStyle,
+    children: &[Box<dyn NativeComponent>],
+    layout: &Layout,
+) -> NativeView {
+    let container = ctx.create_container(style);
+    for (child, frame) in children.iter().zip(&layout.frames) {
+        let mut child_ctx = ctx.with_constraints(Constraints::tight(frame.size));
+        child_ctx.parent_frame = *frame;
+        let view = child.render(&mut child_ctx);
+        ctx.set_frame(&view, *frame);
+        ctx.add_child(&container, view);
+    }
+    container
+}
+
 /// Scrollable content container
 pub struct ScrollView {
     pub content: Option<Box<dyn NativeComponent>>,
@@ -664,6 +869,415 @@ where
     }
 }
 
+/// Virtualized list for long or unbounded datasets
+///
+/// Only rows within the viewport (plus `overscan` viewports on each side)
+/// are rendered. Rows leaving the window return their cell to a pool keyed
+/// by reuse id, and rows entering take one from it, so the platform's
+/// recycling list (UITableView / RecyclerView) reuses its native cells.
+/// Rows are identified by key, so measurements and mounted cells follow
+/// items across inserts and reorders.
+///
+/// ```rust,ignore
+/// FlatList::new(messages, |msg, _| MessageRow::new(msg))
+///     .key(|msg, _| msg.id.to_string())
+///     .estimated_item_height(72.0)
+///     .on_refresh(move || reload())
+///     .on_end_reached(move || load_more())
+/// ```
+pub struct FlatList<T, F> {
+    pub items: Vec<T>,
+    pub render_item: F,
+    pub key_extractor: Option<Box<dyn Fn(&T, usize) -> String>>,
+    pub reuse_id: Option<Box<dyn Fn(&T, usize) -> &'static str>>,
+    pub item_extent: ItemExtent,
+    pub direction: ScrollDirection,
+    pub overscan: f32,
+    pub separator: bool,
+    pub refreshing: bool,
+    pub on_refresh: Option<Box<dyn Fn()>>,
+    pub on_end_reached: Option<Box<dyn Fn()>>,
+    pub end_reached_threshold: f32,
+    state: RefCell<FlatListState>,
+}
+
+/// Row size along the scroll axis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemExtent {
+    /// Every row has this size; no measurement needed
+    Fixed(f32),
+    /// Rows are measured when first rendered, assuming this size until then
+    Estimated(f32),
+}
+
+#[derive(Default)]
+struct FlatListState {
+    list: Option<NativeView>,
+    viewport: Size,
+    offset: f32,
+    measured: HashMap<String, f32>,
+    mounted: Vec<MountedCell>,
+    pool: HashMap<&'static str, Vec<NativeView>>,
+    end_reached_at: Option<usize>,
+}
+
+struct MountedCell {
+    key: String,
+    index: usize,
+    reuse_id: &'static str,
+    cell: NativeView,
+    content: NativeView,
+}
+
+const DEFAULT_REUSE_ID: &str = "cell";
+
+impl<T, F, V> FlatList<T, F>
+where
+    F: Fn(&T, usize) -> V,
+    V: NativeComponent,
+{
+    pub fn new(items: Vec<T>, render_item: F) -> Self {
+        FlatList {
+            items,
+            render_item,
+            key_extractor: None,
+            reuse_id: None,
+            item_extent: ItemExtent::Estimated(44.0),
+            direction: ScrollDirection::Vertical,
+            overscan: 1.0,
+            separator: true,
+            refreshing: false,
+            on_refresh: None,
+            on_end_reached: None,
+            end_reached_threshold: 0.5,
+            state: RefCell::new(FlatListState::default()),
+        }
+    }
+
+    /// Stable identity for each item; defaults to its index
+    pub fn key(mut self, f: impl Fn(&T, usize) -> String + 'static) -> Self {
+        self.key_extractor = Some(Box::new(f));
+        self
+    }
+
+    /// Cell type for each item, for lists mixing differently built rows
+    pub fn reuse_id(mut self, f: impl Fn(&T, usize) -> &'static str + 'static) -> Self {
+        self.reuse_id = Some(Box::new(f));
+        self
+    }
+
+    pub fn item_height(mut self, height: f32) -> Self {
+        self.item_extent = ItemExtent::Fixed(height);
+        self
+    }
+
+    pub fn estimated_item_height(mut self, height: f32) -> Self {
+        self.item_extent = ItemExtent::Estimated(height);
+        self
+    }
+
+    pub fn horizontal(mut self) -> Self {
+        self.direction = ScrollDirection::Horizontal;
+        self
+    }
+
+    /// Viewports rendered ahead of and behind the visible area
+    pub fn overscan(mut self, viewports: f32) -> Self {
+        self.overscan = viewports.max(0.0);
+        self
+    }
+
+    pub fn separator(mut self, show: bool) -> Self {
+        self.separator = show;
+        self
+    }
+
+    pub fn refreshing(mut self, refreshing: bool) -> Self {
+        self.refreshing = refreshing;
+        self
+    }
+
+    /// Enable pull-to-refresh
+    pub fn on_refresh(mut self, handler: impl Fn() + 'static) -> Self {
+        self.on_refresh = Some(Box::new(handler));
+        self
+    }
+
+    /// Called once per list length when scrolling nears the end
+    pub fn on_end_reached(mut self, handler: impl Fn() + 'static) -> Self {
+        self.on_end_reached = Some(Box::new(handler));
+        self
+    }
+
+    /// Distance from the end, in viewports, that triggers `on_end_reached`
+    pub fn end_reached_threshold(mut self, viewports: f32) -> Self {
+        self.end_reached_threshold = viewports.max(0.0);
+        self
+    }
+
+    fn item_key(&self, index: usize) -> String {
+        match &self.key_extractor {
+            Some(f) => f(&self.items[index], index),
+            None => index.to_string(),
+        }
+    }
+
+    fn item_reuse_id(&self, index: usize) -> &'static str {
+        match &self.reuse_id {
+            Some(f) => f(&self.items[index], index),
+            None => DEFAULT_REUSE_ID,
+        }
+    }
+
+    fn main_axis(&self, size: Size) -> f32 {
+        match self.direction {
+            ScrollDirection::Horizontal => size.width,
+            _ => size.height,
+        }
+    }
+
+    /// Start offset of every item, plus the total length at the end
+    fn offsets(&self, state: &FlatListState) -> Vec<f32> {
+        let mut offsets = Vec::with_capacity(self.items.len() + 1);
+        let mut offset = 0.0;
+        offsets.push(offset);
+        for index in 0..self.items.len() {
+            offset += match self.item_extent {
+                ItemExtent::Fixed(extent) => extent,
+                ItemExtent::Estimated(estimate) => {
+                    state.measured.get(&self.item_key(index)).copied().unwrap_or(estimate)
+                }
+            };
+            offsets.push(offset);
+        }
+        offsets
+    }
+
+    /// Items to render for a scroll offset and viewport length
+    pub fn visible_range(&self, offset: f32, viewport: f32) -> std::ops::Range<usize> {
+        let offsets = self.offsets(&self.state.borrow());
+        Self::range_in(&offsets, offset, viewport, self.overscan)
+    }
+
+    fn range_in(offsets: &[f32], offset: f32, viewport: f32, overscan: f32) -> std::ops::Range<usize> {
+        let count = offsets.len() - 1;
+        let start = offset - viewport * overscan;
+        let end = offset + viewport * (1.0 + overscan);
+
+        let first = offsets[1..].partition_point(|&item_end| item_end <= start);
+        let last = offsets[..count].partition_point(|&item_start| item_start < end);
+        first.min(count)..last.max(first)
+    }
+
+    fn frame(&self, offsets: &[f32], index: usize, cross: f32) -> Rect {
+        let (start, extent) = (offsets[index], offsets[index + 1] - offsets[index]);
+        match self.direction {
+            ScrollDirection::Horizontal => Rect::new(start, 0.0, extent, cross),
+            _ => Rect::new(0.0, start, cross, extent),
+        }
+    }
+
+    fn cross_axis(&self, state: &FlatListState) -> f32 {
+        match self.direction {
+            ScrollDirection::Horizontal => state.viewport.height,
+            _ => state.viewport.width,
+        }
+    }
+
+    /// Render an item's content into a cell, measuring it if needed
+    fn fill_cell(&self, ctx: &mut RenderContext, state: &mut FlatListState, cell: &NativeView, index: usize, key: &str) -> NativeView {
+        let component = (self.render_item)(&self.items[index], index);
+        let content = component.render(ctx);
+
+        if let ItemExtent::Estimated(_) = self.item_extent {
+            let cross = self.cross_axis(state);
+            let constraints = match self.direction {
+                ScrollDirection::Horizontal => Constraints::loose(Size::new(f32::INFINITY, cross)),
+                _ => Constraints::loose(Size::new(cross, f32::INFINITY)),
+            };
+            let extent = self.main_axis(component.measure(constraints));
+            state.measured.insert(key.to_string(), extent);
+        }
+
+        ctx.add_child(cell, content.clone());
+        content
+    }
+
+    fn recycle(ctx: &mut RenderContext, state: &mut FlatListState, mounted: MountedCell) {
+        ctx.remove_child(&mounted.cell, &mounted.content);
+        state.pool.entry(mounted.reuse_id).or_default().push(mounted.cell);
+    }
+
+    /// Mount, recycle, and position cells for the current scroll window
+    fn sync(&self, ctx: &mut RenderContext) {
+        let mut guard = self.state.borrow_mut();
+        let state = &mut *guard;
+        let Some(list) = state.list.clone() else { return };
+
+        let viewport = self.main_axis(state.viewport);
+        let range = Self::range_in(&self.offsets(state), state.offset, viewport, self.overscan);
+
+        // Recycle cells that left the window
+        let (keep, leave): (Vec<_>, Vec<_>) = std::mem::take(&mut state.mounted)
+            .into_iter()
+            .partition(|mounted| range.contains(&mounted.index));
+        for mounted in leave {
+            Self::recycle(ctx, state, mounted);
+        }
+        state.mounted = keep;
+
+        // Mount cells that entered it
+        for index in range {
+            if state.mounted.iter().any(|mounted| mounted.index == index) {
+                continue;
+            }
+            let key = self.item_key(index);
+            let reuse_id = self.item_reuse_id(index);
+            let cell = match state.pool.get_mut(reuse_id).and_then(|pool| pool.pop()) {
+                Some(cell) => cell,
+                None => {
+                    let cell = ctx.create_list_cell(&list, reuse_id);
+                    ctx.add_child(&list, cell.clone());
+                    cell
+                }
+            };
+            let content = self.fill_cell(ctx, state, &cell, index, &key);
+            state.mounted.push(MountedCell { key, index, reuse_id, cell, content });
+        }
+
+        // Measurements may have moved rows, so lay out everything mounted
+        let offsets = self.offsets(state);
+        let cross = self.cross_axis(state);
+        for mounted in &state.mounted {
+            ctx.set_frame(&mounted.cell, self.frame(&offsets, mounted.index, cross));
+        }
+        let total = offsets[self.items.len()];
+        let content_size = match self.direction {
+            ScrollDirection::Horizontal => Size::new(total, cross),
+            _ => Size::new(cross, total),
+        };
+        ctx.set_content_size(&list, content_size);
+
+        // Infinite scroll
+        let remaining = total - (state.offset + viewport);
+        let count = self.items.len();
+        let end_reached = remaining <= viewport * self.end_reached_threshold && state.end_reached_at != Some(count);
+        if end_reached {
+            state.end_reached_at = Some(count);
+        }
+        drop(guard);
+
+        if end_reached {
+            if let Some(handler) = &self.on_end_reached {
+                handler();
+            }
+        }
+    }
+
+    /// Handle a scroll event from the native list
+    pub fn on_scroll(&self, ctx: &mut RenderContext, offset: f32) {
+        self.state.borrow_mut().offset = offset.max(0.0);
+        self.sync(ctx);
+    }
+
+    /// Handle a change in the list's visible size
+    pub fn on_resize(&self, ctx: &mut RenderContext, viewport: Size) {
+        self.state.borrow_mut().viewport = viewport;
+        self.sync(ctx);
+    }
+
+    /// Handle a pull-to-refresh gesture
+    pub fn refresh(&self, ctx: &mut RenderContext) {
+        if let Some(handler) = &self.on_refresh {
+            if let Some(list) = &self.state.borrow().list {
+                ctx.set_refreshing(list, true);
+            }
+            handler();
+        }
+    }
+
+    /// Update the refresh indicator, e.g. once reloaded data arrives
+    pub fn set_refreshing(&mut self, ctx: &mut RenderContext, refreshing: bool) {
+        self.refreshing = refreshing;
+        if let Some(list) = &self.state.borrow().list {
+            ctx.set_refreshing(list, refreshing);
+        }
+    }
+
+    /// Replace the items, keeping cells and measurements for unchanged keys
+    pub fn set_items(&mut self, ctx: &mut RenderContext, items: Vec<T>) {
+        self.items = items;
+
+        let indices: HashMap<String, usize> = (0..self.items.len()).map(|index| (self.item_key(index), index)).collect();
+        {
+            let mut state = self.state.borrow_mut();
+            let state = &mut *state;
+            for mut mounted in std::mem::take(&mut state.mounted) {
+                match indices.get(&mounted.key) {
+                    Some(&index) if self.item_reuse_id(index) == mounted.reuse_id => {
+                        ctx.remove_child(&mounted.cell, &mounted.content);
+                        mounted.index = index;
+                        mounted.content = self.fill_cell(ctx, state, &mounted.cell, index, &mounted.key);
+                        state.mounted.push(mounted);
+                    }
+                    _ => Self::recycle(ctx, state, mounted),
+                }
+            }
+            state.measured.retain(|key, _| indices.contains_key(key));
+            // New items may need another page
+            state.end_reached_at = None;
+        }
+
+        self.sync(ctx);
+    }
+}
+
+impl<T, F, V> NativeComponent for FlatList<T, F>
+where
+    F: Fn(&T, usize) -> V,
+    V: NativeComponent,
+{
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        let list = ctx.create_list(crate::renderer::ListConfig {
+            direction: match self.direction {
+                ScrollDirection::Horizontal => crate::renderer::ScrollDirection::Horizontal,
+                _ => crate::renderer::ScrollDirection::Vertical,
+            },
+            separator: self.separator,
+            refresh_control: self.on_refresh.is_some(),
+            refreshing: self.refreshing,
+            ..Default::default()
+        });
+
+        {
+            let mut state = self.state.borrow_mut();
+            state.list = Some(list.clone());
+            // Until the platform reports the real viewport, assume the constraints
+            let max = ctx.constraints;
+            state.viewport = Size::new(
+                if max.max_width.is_finite() { max.max_width } else { 390.0 },
+                if max.max_height.is_finite() { max.max_height } else { 844.0 },
+            );
+        }
+
+        self.sync(ctx);
+        list
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        self.sync(ctx);
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        // Lists fill the cross axis and grow along the scroll axis up to the limit
+        let total = *self.offsets(&self.state.borrow()).last().unwrap_or(&0.0);
+        match self.direction {
+            ScrollDirection::Horizontal => Size::new(total.min(constraints.max_width), constraints.max_height),
+            _ => Size::new(constraints.max_width, total.min(constraints.max_height)),
+        }
+    }
+}
+
 // ============================================================================
 // Utility Components
 // ============================================================================
@@ -700,6 +1314,11 @@ impl NativeComponent for Spacer {
     fn measure(&self, constraints: Constraints) -> Size {
         Size::new(self.min_length, self.min_length)
     }
+
+    fn flex(&self) -> FlexItem {
+        // Expands to push its siblings apart
+        FlexItem::default().grow(1.0).basis(self.min_length)
+    }
 }
 
 /// Visual separator line
@@ -711,7 +1330,7 @@ pub struct Divider {
 impl Divider {
     pub fn new() -> Self {
         Divider {
-            color: Color::rgba(0.8, 0.8, 0.8, 1.0),
+            color: Color::semantic(token::SEPARATOR),
             thickness: 1.0,
         }
     }
@@ -793,6 +1412,34 @@ impl Default for Container {
     }
 }
 
+impl NativeComponent for Container {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        let style = crate::renderer::ContainerStyle {
+            background_color: self.background,
+            corner_radius: self.corner_radius,
+            shadow: self.shadow.as_ref().map(|shadow| crate::renderer::Shadow {
+                color: shadow.color,
+                offset: crate::Point::new(shadow.x, shadow.y),
+                radius: shadow.radius,
+                opacity: shadow.color.a,
+            }),
+            ..Default::default()
+        };
+        let layout = flex_layout(&FlexStyle::column().padding(self.padding), self.child.as_slice(), ctx.constraints);
+        render_layout(ctx, style, self.child.as_slice(), &layout)
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        if let Some(child) = &self.child {
+            child.update(ctx);
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        flex_layout(&FlexStyle::column().padding(self.padding), self.child.as_slice(), constraints).size
+    }
+}
+
 /// Pre-styled card container
 pub struct Card {
     pub child: Option<Box<dyn NativeComponent>>,
@@ -823,3 +1470,252 @@ impl Default for Card {
         Self::new()
     }
 }
+
+impl NativeComponent for Card {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        let style = crate::renderer::ContainerStyle {
+            background_color: Color::semantic(token::SURFACE),
+            corner_radius: 12.0,
+            shadow: Some(crate::renderer::Shadow::default()),
+            ..Default::default()
+        };
+        let layout = flex_layout(&self.style(), self.child.as_slice(), ctx.constraints);
+        render_layout(ctx, style, self.child.as_slice(), &layout)
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        if let Some(child) = &self.child {
+            child.update(ctx);
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        flex_layout(&self.style(), self.child.as_slice(), constraints).size
+    }
+}
+
+impl Card {
+    fn style(&self) -> FlexStyle {
+        // Cards span the width they're given
+        FlexStyle::column().padding(self.padding).align(AlignItems::Stretch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{ContainerStyle, NativeRenderer};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Row of a fixed height
+    struct Row(f32);
+
+    impl NativeComponent for Row {
+        fn render(&self, ctx: &mut RenderContext) -> NativeView {
+            ctx.create_container(ContainerStyle::default())
+        }
+
+        fn update(&self, _ctx: &mut RenderContext) {}
+
+        fn measure(&self, _constraints: Constraints) -> Size {
+            Size::new(0.0, self.0)
+        }
+    }
+
+    fn rows(count: u32, height: f32) -> FlatList<u32, impl Fn(&u32, usize) -> Row> {
+        FlatList::new((0..count).collect(), move |_, _| Row(height))
+    }
+
+    /// Context whose viewport is `width` by `height`
+    fn context(width: f32, height: f32) -> RenderContext {
+        let mut ctx = NativeRenderer::new().create_context();
+        ctx.constraints = Constraints::loose(Size::new(width, height));
+        ctx
+    }
+
+    /// Mounted rows as `(index, key, cell)`, in index order
+    fn mounted<T, F>(list: &FlatList<T, F>) -> Vec<(usize, String, NativeView)> {
+        let mut mounted: Vec<_> = list
+            .state
+            .borrow()
+            .mounted
+            .iter()
+            .map(|m| (m.index, m.key.clone(), m.cell.clone()))
+            .collect();
+        mounted.sort_by_key(|(index, _, _)| *index);
+        mounted
+    }
+
+    fn indices<T, F>(list: &FlatList<T, F>) -> Vec<usize> {
+        mounted(list).into_iter().map(|(index, _, _)| index).collect()
+    }
+
+    fn same(a: &NativeView, b: &NativeView) -> bool {
+        Arc::ptr_eq(&a.handle, &b.handle)
+    }
+
+    #[test]
+    fn test_visible_range() {
+        let list = rows(100, 50.0).item_height(50.0).overscan(0.0);
+        assert_eq!(list.visible_range(0.0, 200.0), 0..4);
+        // Partly visible rows at either edge count
+        assert_eq!(list.visible_range(25.0, 200.0), 0..5);
+        assert_eq!(list.visible_range(100.0, 200.0), 2..6);
+        assert_eq!(list.visible_range(4800.0, 200.0), 96..100);
+        assert_eq!(list.visible_range(6000.0, 200.0), 100..100);
+
+        let list = rows(100, 50.0).item_height(50.0).overscan(1.0);
+        assert_eq!(list.visible_range(0.0, 200.0), 0..8);
+        assert_eq!(list.visible_range(1000.0, 200.0), 16..28);
+        assert_eq!(list.visible_range(4800.0, 200.0), 92..100);
+
+        assert_eq!(rows(0, 50.0).visible_range(0.0, 200.0), 0..0);
+    }
+
+    #[test]
+    fn test_visible_range_uses_measured_heights() {
+        let mut ctx = context(390.0, 200.0);
+        let list = rows(100, 100.0).estimated_item_height(40.0).overscan(0.0);
+        assert_eq!(list.visible_range(0.0, 200.0), 0..5);
+
+        // Rows mounted on the estimate are measured at 100
+        list.render(&mut ctx);
+        assert_eq!(indices(&list), [0, 1, 2, 3, 4]);
+        assert_eq!(list.visible_range(0.0, 200.0), 0..2);
+        assert_eq!(list.visible_range(500.0, 200.0), 5..10);
+        assert_eq!(list.measure(Constraints::unbounded()).height, 500.0 + 95.0 * 40.0);
+    }
+
+    #[test]
+    fn test_scrolling_recycles_cells() {
+        let mut ctx = context(390.0, 200.0);
+        let list = rows(100, 50.0).item_height(50.0).overscan(0.0);
+        list.render(&mut ctx);
+        let before = mounted(&list);
+        assert_eq!(indices(&list), [0, 1, 2, 3]);
+
+        list.on_scroll(&mut ctx, 100.0);
+        let after = mounted(&list);
+        assert_eq!(indices(&list), [2, 3, 4, 5]);
+        // Rows that stayed keep their cells; rows that entered took the
+        // cells of the rows that left
+        assert!(same(&before[2].2, &after[0].2) && same(&before[3].2, &after[1].2));
+        assert!(after[2..].iter().all(|(_, _, cell)| same(cell, &before[0].2) || same(cell, &before[1].2)));
+        assert!(list.state.borrow().pool.values().all(Vec::is_empty));
+
+        // Far jumps reuse the same four cells
+        list.on_scroll(&mut ctx, 3000.0);
+        assert_eq!(indices(&list), [60, 61, 62, 63]);
+        assert!(mounted(&list).iter().all(|(_, _, cell)| before.iter().any(|(_, _, b)| same(cell, b))));
+    }
+
+    #[test]
+    fn test_cells_are_pooled_by_reuse_id() {
+        let mut ctx = context(390.0, 200.0);
+        let list = rows(100, 50.0)
+            .item_height(50.0)
+            .overscan(0.0)
+            .reuse_id(|item, _| if *item < 3 { "header" } else { "row" });
+        list.render(&mut ctx);
+
+        list.on_scroll(&mut ctx, 150.0);
+        assert_eq!(indices(&list), [3, 4, 5, 6]);
+        let state = list.state.borrow();
+        // Header cells wait in their own pool; rows needed new cells
+        assert_eq!(state.pool["header"].len(), 3);
+        assert!(!state.pool.contains_key("row"));
+        assert!(state.mounted.iter().all(|m| m.reuse_id == "row"));
+    }
+
+    #[test]
+    fn test_keys_follow_items() {
+        let mut ctx = context(390.0, 200.0);
+        let mut list = FlatList::new(vec![10, 20, 30, 40, 50], |_: &u32, _| Row(50.0))
+            .key(|item, _| item.to_string())
+            .item_height(50.0)
+            .overscan(0.0);
+        list.render(&mut ctx);
+        let before = mounted(&list);
+        let keys: Vec<_> = before.iter().map(|(_, key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["10", "20", "30", "40"]);
+
+        // Insert at the front: rows keep their cells at their new indices,
+        // and the row pushed out lends its cell to the new one
+        list.set_items(&mut ctx, vec![5, 10, 20, 30, 40, 50]);
+        let after = mounted(&list);
+        let keys: Vec<_> = after.iter().map(|(index, key, _)| (*index, key.as_str())).collect();
+        assert_eq!(keys, [(0, "5"), (1, "10"), (2, "20"), (3, "30")]);
+        assert!(same(&after[1].2, &before[0].2));
+        assert!(same(&after[3].2, &before[2].2));
+        assert!(same(&after[0].2, &before[3].2));
+
+        assert_eq!(rows(3, 50.0).item_key(2), "2");
+    }
+
+    #[test]
+    fn test_measurements_follow_keys() {
+        let mut ctx = context(390.0, 200.0);
+        let mut list = FlatList::new(vec![1, 2, 3], |item: &u32, _| Row(*item as f32 * 10.0))
+            .key(|item, _| item.to_string())
+            .overscan(0.0);
+        list.render(&mut ctx);
+        assert_eq!(list.measure(Constraints::unbounded()).height, 60.0);
+
+        list.set_items(&mut ctx, vec![3, 1]);
+        assert_eq!(list.measure(Constraints::unbounded()).height, 40.0);
+        assert_eq!(list.state.borrow().measured.len(), 2);
+    }
+
+    #[test]
+    fn test_end_reached_fires_once_per_length() {
+        let fired = Rc::new(Cell::new(0));
+        let count = fired.clone();
+        let mut ctx = context(390.0, 200.0);
+        let mut list = rows(20, 50.0)
+            .item_height(50.0)
+            .on_end_reached(move || count.set(count.get() + 1));
+
+        // 1000 tall; fires within half a viewport of the end
+        list.render(&mut ctx);
+        list.on_scroll(&mut ctx, 600.0);
+        assert_eq!(fired.get(), 0);
+        list.on_scroll(&mut ctx, 700.0);
+        assert_eq!(fired.get(), 1);
+        list.on_scroll(&mut ctx, 800.0);
+        list.on_scroll(&mut ctx, 0.0);
+        list.on_scroll(&mut ctx, 800.0);
+        assert_eq!(fired.get(), 1);
+
+        // A new page re-arms it
+        list.set_items(&mut ctx, (0..30).collect());
+        assert_eq!(fired.get(), 1);
+        list.on_scroll(&mut ctx, 1200.0);
+        assert_eq!(fired.get(), 2);
+    }
+
+    #[test]
+    fn test_end_reached_threshold() {
+        let fired = Rc::new(Cell::new(0));
+        let count = fired.clone();
+        let mut ctx = context(390.0, 200.0);
+        let list = rows(20, 50.0)
+            .item_height(50.0)
+            .end_reached_threshold(0.0)
+            .on_end_reached(move || count.set(count.get() + 1));
+        list.render(&mut ctx);
+        list.on_scroll(&mut ctx, 799.0);
+        assert_eq!(fired.get(), 0);
+        list.on_scroll(&mut ctx, 800.0);
+        assert_eq!(fired.get(), 1);
+
+        // A list shorter than its viewport is already at the end
+        let fired = Rc::new(Cell::new(0));
+        let count = fired.clone();
+        let list = rows(2, 50.0)
+            .item_height(50.0)
+            .on_end_reached(move || count.set(count.get() + 1));
+        list.render(&mut ctx);
+        assert_eq!(fired.get(), 1);
+    }
+}
@@ -1,11 +1,140 @@
 //! PhilJS Mobile Gestures
 //!
 //! Touch gesture recognition and handling.
+//!
+//! Recognizers are plain state machines fed [`TouchEvent`]s, so gestures
+//! behave the same on iOS and Android. A [`GestureDetector`] wraps a
+//! component, and its [`GestureArena`] decides which recognizers win when
+//! several want the same touches: higher priority goes first, and a
+//! recognizer that starts cancels the lower-priority ones it conflicts with
+//! unless both are marked simultaneous.
+//!
+//! ```rust,ignore
+//! let scale = AnimatedValue::new(1.0);
+//! let offset = AnimatedValue::new(0.0);
+//!
+//! GestureDetector::new(photo)
+//!     .gesture(pinch_to_zoom(&scale, 1.0, 4.0).simultaneous())
+//!     .gesture(drag_to_dismiss(&offset, 150.0, move || navigator.dismiss()))
+//!     .gesture(Recognize::new(TapGesture::double_tap()).priority(1).on_end(|_| reset_zoom()))
+//! ```
 
-use crate::Point;
-use std::sync::Arc;
+use crate::animation::{AnimatedValue, Animation, SpringAnimation};
+use crate::runtime::Signal;
+use crate::{Constraints, NativeComponent, NativeView, Point, RenderContext, Size};
+use crate::layout::FlexItem;
+use std::any::Any;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+/// Movement before a touch stops counting as a tap
+const TAP_SLOP: f32 = 10.0;
+/// Longest press, and longest gap between taps, in a tap gesture
+const TAP_INTERVAL: Duration = Duration::from_millis(300);
+/// Movement before a pan begins
+const PAN_SLOP: f32 = 10.0;
+/// Scale change before a pinch begins
+const PINCH_THRESHOLD: f32 = 0.05;
+/// Rotation before a rotation gesture begins (about 5 degrees)
+const ROTATION_THRESHOLD: f32 = 0.09;
+/// Shortest swipe, in points
+const SWIPE_MIN_DISTANCE: f32 = 50.0;
+/// Slowest swipe, in points per second
+const SWIPE_MIN_VELOCITY: f32 = 300.0;
+
+// ============================================================================
+// Touch Events
+// ============================================================================
+
+/// Phase of a touch event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    /// A finger went down
+    Began,
+    /// One or more fingers moved
+    Moved,
+    /// A finger lifted
+    Ended,
+    /// The system took the touches away, e.g. for an incoming call
+    Cancelled,
+}
+
+/// A finger on the screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+    /// Stable for the life of the touch
+    pub id: u64,
+    /// Location in the view's coordinates
+    pub location: Point,
+}
+
+/// A touch event delivered by the platform
+///
+/// `touches` holds every finger down during the event, including one that
+/// is lifting in an `Ended` event.
+#[derive(Debug, Clone)]
+pub struct TouchEvent {
+    pub phase: TouchPhase,
+    pub touches: Vec<Touch>,
+    /// Time since an arbitrary fixed point, e.g. `MotionEvent.getEventTime`
+    pub timestamp: Duration,
+}
+
+impl TouchEvent {
+    pub fn new(phase: TouchPhase, touches: Vec<Touch>, timestamp: Duration) -> Self {
+        TouchEvent { phase, touches, timestamp }
+    }
+
+    /// Center of all touches
+    pub fn centroid(&self) -> Point {
+        let count = self.touches.len().max(1) as f32;
+        let (x, y) = self
+            .touches
+            .iter()
+            .fold((0.0, 0.0), |(x, y), touch| (x + touch.location.x, y + touch.location.y));
+        Point::new(x / count, y / count)
+    }
+
+    /// Whether this event lifts the last finger
+    pub fn is_final(&self) -> bool {
+        matches!(self.phase, TouchPhase::Ended | TouchPhase::Cancelled) && self.touches.len() <= 1
+    }
+
+    /// Distance and angle between the first two touches
+    fn span(&self) -> Option<(f32, f32)> {
+        match &self.touches[..] {
+            [a, b, ..] => {
+                let (dx, dy) = (b.location.x - a.location.x, b.location.y - a.location.y);
+                Some(((dx * dx + dy * dy).sqrt(), dy.atan2(dx)))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    ((b.x - a.x).powi(2) + (b.y - a.y).powi(2)).sqrt()
+}
+
+/// Rate of change per second between two samples
+fn rate(delta: f32, from: Duration, to: Duration) -> f32 {
+    let dt = to.saturating_sub(from).as_secs_f32();
+    if dt > 0.0 { delta / dt } else { 0.0 }
+}
+
+fn is_active(state: GestureState) -> bool {
+    matches!(state, GestureState::Began | GestureState::Changed)
+}
+
+/// State after losing to another recognizer
+fn lost(state: GestureState) -> GestureState {
+    if is_active(state) {
+        GestureState::Cancelled
+    } else {
+        GestureState::Failed
+    }
+}
+
 /// Gesture recognizer state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GestureState {
@@ -24,10 +153,32 @@ pub enum GestureState {
 }
 
 /// Base trait for gesture recognizers
+///
+/// Discrete gestures (tap, swipe) go straight from `Possible` to `Ended`;
+/// continuous ones go through `Began` and `Changed` first.
 pub trait GestureRecognizer: Send + Sync {
     fn state(&self) -> GestureState;
     fn location(&self) -> Point;
     fn reset(&mut self);
+
+    /// Feed a touch event, returning the new state
+    fn handle_touch(&mut self, event: &TouchEvent) -> GestureState;
+
+    /// Stop because another recognizer won the touches
+    fn cancel(&mut self);
+
+    /// Whether the recognizer is waiting on time rather than touches
+    fn needs_tick(&self) -> bool {
+        false
+    }
+
+    /// Advance to `now` without a touch event, for time-based gestures
+    fn tick(&mut self, now: Duration) -> GestureState {
+        let _ = now;
+        self.state()
+    }
+
+    fn as_any(&self) -> &dyn Any;
 }
 
 /// Recognized gesture types
@@ -57,6 +208,10 @@ pub struct TapGesture {
     pub location: Point,
     /// Current state
     pub state: GestureState,
+    taps: u32,
+    touches_seen: u32,
+    down: Option<(Point, Duration)>,
+    last_up: Option<Duration>,
 }
 
 impl TapGesture {
@@ -66,6 +221,10 @@ impl TapGesture {
             touches_required: 1,
             location: Point::zero(),
             state: GestureState::Possible,
+            taps: 0,
+            touches_seen: 0,
+            down: None,
+            last_up: None,
         }
     }
 
@@ -91,6 +250,79 @@ impl Default for TapGesture {
     }
 }
 
+impl GestureRecognizer for TapGesture {
+    fn state(&self) -> GestureState {
+        self.state
+    }
+
+    fn location(&self) -> Point {
+        self.location
+    }
+
+    fn reset(&mut self) {
+        *self = TapGesture::new().taps(self.taps_required).touches(self.touches_required);
+    }
+
+    fn handle_touch(&mut self, event: &TouchEvent) -> GestureState {
+        if self.state != GestureState::Possible {
+            return self.state;
+        }
+        let location = event.centroid();
+
+        match event.phase {
+            TouchPhase::Began => {
+                // Too long since the last tap to continue the sequence
+                if let Some(up) = self.last_up {
+                    if event.timestamp.saturating_sub(up) > TAP_INTERVAL {
+                        self.taps = 0;
+                    }
+                }
+                self.touches_seen = self.touches_seen.max(event.touches.len() as u32);
+                if self.touches_seen > self.touches_required {
+                    self.state = GestureState::Failed;
+                } else if self.down.is_none() {
+                    self.down = Some((location, event.timestamp));
+                }
+            }
+            TouchPhase::Moved => {
+                if let Some((start, _)) = self.down {
+                    if distance(start, location) > TAP_SLOP {
+                        self.state = GestureState::Failed;
+                    }
+                }
+            }
+            TouchPhase::Ended if event.is_final() => {
+                let Some((_, at)) = self.down.take() else {
+                    return self.state;
+                };
+                if self.touches_seen != self.touches_required || event.timestamp.saturating_sub(at) > TAP_INTERVAL {
+                    self.state = GestureState::Failed;
+                    return self.state;
+                }
+
+                self.taps += 1;
+                self.touches_seen = 0;
+                self.last_up = Some(event.timestamp);
+                self.location = location;
+                if self.taps >= self.taps_required {
+                    self.state = GestureState::Ended;
+                }
+            }
+            TouchPhase::Ended => {}
+            TouchPhase::Cancelled => self.state = GestureState::Failed,
+        }
+        self.state
+    }
+
+    fn cancel(&mut self) {
+        self.state = lost(self.state);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 /// Tap gesture handler builder
 pub struct TapHandler<F> {
     gesture: TapGesture,
@@ -128,6 +360,7 @@ pub struct LongPressGesture {
     pub location: Point,
     /// Current state
     pub state: GestureState,
+    down: Option<(Point, Duration)>,
 }
 
 impl LongPressGesture {
@@ -138,6 +371,7 @@ impl LongPressGesture {
             touches_required: 1,
             location: Point::zero(),
             state: GestureState::Possible,
+            down: None,
         }
     }
 
@@ -158,6 +392,83 @@ impl Default for LongPressGesture {
     }
 }
 
+impl GestureRecognizer for LongPressGesture {
+    fn state(&self) -> GestureState {
+        self.state
+    }
+
+    fn location(&self) -> Point {
+        self.location
+    }
+
+    fn reset(&mut self) {
+        self.state = GestureState::Possible;
+        self.down = None;
+    }
+
+    fn handle_touch(&mut self, event: &TouchEvent) -> GestureState {
+        let location = event.centroid();
+
+        match (event.phase, self.state) {
+            (TouchPhase::Began, GestureState::Possible) => {
+                if event.touches.len() as u32 == self.touches_required {
+                    self.down = Some((location, event.timestamp));
+                    self.location = location;
+                } else {
+                    self.down = None;
+                }
+            }
+            (TouchPhase::Moved, GestureState::Possible) => {
+                if let Some((start, _)) = self.down {
+                    if distance(start, location) > self.allowable_movement {
+                        self.state = GestureState::Failed;
+                    } else {
+                        self.tick(event.timestamp);
+                    }
+                }
+            }
+            (TouchPhase::Moved, GestureState::Began | GestureState::Changed) => {
+                self.location = location;
+                self.state = GestureState::Changed;
+            }
+            (TouchPhase::Ended, GestureState::Possible) => {
+                // Lifted before the press was long enough
+                if self.tick(event.timestamp) == GestureState::Possible {
+                    self.state = GestureState::Failed;
+                }
+            }
+            (TouchPhase::Ended, GestureState::Began | GestureState::Changed) => {
+                self.location = location;
+                self.state = GestureState::Ended;
+            }
+            (TouchPhase::Cancelled, state) => self.state = lost(state),
+            _ => {}
+        }
+        self.state
+    }
+
+    fn cancel(&mut self) {
+        self.state = lost(self.state);
+    }
+
+    fn needs_tick(&self) -> bool {
+        self.state == GestureState::Possible && self.down.is_some()
+    }
+
+    fn tick(&mut self, now: Duration) -> GestureState {
+        if let (GestureState::Possible, Some((_, at))) = (self.state, self.down) {
+            if now.saturating_sub(at) >= self.minimum_duration {
+                self.state = GestureState::Began;
+            }
+        }
+        self.state
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 // ============================================================================
 // Pan Gesture
 // ============================================================================
@@ -177,6 +488,8 @@ pub struct PanGesture {
     pub velocity: Point,
     /// Current state
     pub state: GestureState,
+    start: Option<Point>,
+    last: Option<(Point, Duration)>,
 }
 
 impl PanGesture {
@@ -188,6 +501,8 @@ impl PanGesture {
             translation: Point::zero(),
             velocity: Point::zero(),
             state: GestureState::Possible,
+            start: None,
+            last: None,
         }
     }
 
@@ -204,6 +519,74 @@ impl Default for PanGesture {
     }
 }
 
+impl GestureRecognizer for PanGesture {
+    fn state(&self) -> GestureState {
+        self.state
+    }
+
+    fn location(&self) -> Point {
+        self.location
+    }
+
+    fn reset(&mut self) {
+        *self = PanGesture::new().touches(self.min_touches, self.max_touches);
+    }
+
+    fn handle_touch(&mut self, event: &TouchEvent) -> GestureState {
+        let location = event.centroid();
+        let touches = event.touches.len() as u32;
+
+        match event.phase {
+            TouchPhase::Began | TouchPhase::Moved => {
+                // Re-anchor when fingers are added so the centroid doesn't jump
+                if event.phase == TouchPhase::Began || self.start.is_none() {
+                    let offset = self.translation;
+                    self.start = Some(Point::new(location.x - offset.x, location.y - offset.y));
+                }
+                let start = self.start.unwrap_or(location);
+                if let Some((last, at)) = self.last {
+                    self.velocity = Point::new(
+                        rate(location.x - last.x, at, event.timestamp),
+                        rate(location.y - last.y, at, event.timestamp),
+                    );
+                }
+                self.last = Some((location, event.timestamp));
+                self.location = location;
+                self.translation = Point::new(location.x - start.x, location.y - start.y);
+
+                let in_range = (self.min_touches..=self.max_touches).contains(&touches);
+                self.state = match self.state {
+                    GestureState::Possible if in_range && distance(start, location) > PAN_SLOP => GestureState::Began,
+                    GestureState::Began | GestureState::Changed => GestureState::Changed,
+                    state => state,
+                };
+            }
+            TouchPhase::Ended if event.is_final() => {
+                self.state = match self.state {
+                    GestureState::Began | GestureState::Changed => GestureState::Ended,
+                    GestureState::Possible => GestureState::Failed,
+                    state => state,
+                };
+            }
+            TouchPhase::Ended => {
+                // A finger lifted; re-anchor on the next move
+                self.start = None;
+                self.last = None;
+            }
+            TouchPhase::Cancelled => self.state = lost(self.state),
+        }
+        self.state
+    }
+
+    fn cancel(&mut self) {
+        self.state = lost(self.state);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 /// Pan gesture handler
 pub struct PanHandler<F> {
     gesture: PanGesture,
@@ -234,6 +617,8 @@ pub struct PinchGesture {
     pub location: Point,
     /// Current state
     pub state: GestureState,
+    initial_distance: Option<f32>,
+    last: Option<(f32, Duration)>,
 }
 
 impl PinchGesture {
@@ -243,10 +628,68 @@ impl PinchGesture {
             velocity: 0.0,
             location: Point::zero(),
             state: GestureState::Possible,
+            initial_distance: None,
+            last: None,
         }
     }
 }
 
+impl GestureRecognizer for PinchGesture {
+    fn state(&self) -> GestureState {
+        self.state
+    }
+
+    fn location(&self) -> Point {
+        self.location
+    }
+
+    fn reset(&mut self) {
+        *self = PinchGesture::new();
+    }
+
+    fn handle_touch(&mut self, event: &TouchEvent) -> GestureState {
+        match (event.phase, event.span()) {
+            (TouchPhase::Began | TouchPhase::Moved, Some((span, _))) => {
+                let initial = *self.initial_distance.get_or_insert(span.max(1.0));
+                let scale = span / initial;
+                if let Some((last, at)) = self.last {
+                    self.velocity = rate(scale - last, at, event.timestamp);
+                }
+                self.last = Some((scale, event.timestamp));
+                self.scale = scale;
+                self.location = event.centroid();
+
+                self.state = match self.state {
+                    GestureState::Possible if (scale - 1.0).abs() > PINCH_THRESHOLD => GestureState::Began,
+                    GestureState::Began | GestureState::Changed => GestureState::Changed,
+                    state => state,
+                };
+            }
+            // Down to one finger
+            (TouchPhase::Ended, _) if event.touches.len() <= 2 => {
+                self.state = match self.state {
+                    GestureState::Began | GestureState::Changed => GestureState::Ended,
+                    GestureState::Possible if event.is_final() => GestureState::Failed,
+                    state => state,
+                };
+                self.initial_distance = None;
+                self.last = None;
+            }
+            (TouchPhase::Cancelled, _) => self.state = lost(self.state),
+            _ => {}
+        }
+        self.state
+    }
+
+    fn cancel(&mut self) {
+        self.state = lost(self.state);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 impl Default for PinchGesture {
     fn default() -> Self {
         Self::new()
@@ -268,6 +711,8 @@ pub struct RotationGesture {
     pub location: Point,
     /// Current state
     pub state: GestureState,
+    initial_angle: Option<f32>,
+    last: Option<(f32, Duration)>,
 }
 
 impl RotationGesture {
@@ -277,7 +722,72 @@ impl RotationGesture {
             velocity: 0.0,
             location: Point::zero(),
             state: GestureState::Possible,
+            initial_angle: None,
+            last: None,
+        }
+    }
+}
+
+impl GestureRecognizer for RotationGesture {
+    fn state(&self) -> GestureState {
+        self.state
+    }
+
+    fn location(&self) -> Point {
+        self.location
+    }
+
+    fn reset(&mut self) {
+        *self = RotationGesture::new();
+    }
+
+    fn handle_touch(&mut self, event: &TouchEvent) -> GestureState {
+        use std::f32::consts::PI;
+
+        match (event.phase, event.span()) {
+            (TouchPhase::Began | TouchPhase::Moved, Some((_, angle))) => {
+                let initial = *self.initial_angle.get_or_insert(angle);
+                // Keep within -PI..PI so crossing the axis doesn't jump a full turn
+                let mut rotation = angle - initial;
+                if rotation > PI {
+                    rotation -= 2.0 * PI;
+                } else if rotation < -PI {
+                    rotation += 2.0 * PI;
+                }
+                if let Some((last, at)) = self.last {
+                    self.velocity = rate(rotation - last, at, event.timestamp);
+                }
+                self.last = Some((rotation, event.timestamp));
+                self.rotation = rotation;
+                self.location = event.centroid();
+
+                self.state = match self.state {
+                    GestureState::Possible if rotation.abs() > ROTATION_THRESHOLD => GestureState::Began,
+                    GestureState::Began | GestureState::Changed => GestureState::Changed,
+                    state => state,
+                };
+            }
+            (TouchPhase::Ended, _) if event.touches.len() <= 2 => {
+                self.state = match self.state {
+                    GestureState::Began | GestureState::Changed => GestureState::Ended,
+                    GestureState::Possible if event.is_final() => GestureState::Failed,
+                    state => state,
+                };
+                self.initial_angle = None;
+                self.last = None;
+            }
+            (TouchPhase::Cancelled, _) => self.state = lost(self.state),
+            _ => {}
         }
+        self.state
+    }
+
+    fn cancel(&mut self) {
+        self.state = lost(self.state);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
@@ -311,6 +821,7 @@ pub struct SwipeGesture {
     pub location: Point,
     /// Current state
     pub state: GestureState,
+    down: Option<(Point, Duration)>,
 }
 
 impl SwipeGesture {
@@ -320,6 +831,7 @@ impl SwipeGesture {
             touches_required: 1,
             location: Point::zero(),
             state: GestureState::Possible,
+            down: None,
         }
     }
 
@@ -345,6 +857,67 @@ impl SwipeGesture {
     }
 }
 
+impl GestureRecognizer for SwipeGesture {
+    fn state(&self) -> GestureState {
+        self.state
+    }
+
+    fn location(&self) -> Point {
+        self.location
+    }
+
+    fn reset(&mut self) {
+        self.state = GestureState::Possible;
+        self.down = None;
+    }
+
+    fn handle_touch(&mut self, event: &TouchEvent) -> GestureState {
+        if self.state != GestureState::Possible {
+            return self.state;
+        }
+        let location = event.centroid();
+
+        match event.phase {
+            TouchPhase::Began => {
+                if event.touches.len() as u32 > self.touches_required {
+                    self.state = GestureState::Failed;
+                } else {
+                    self.down = Some((location, event.timestamp));
+                }
+            }
+            TouchPhase::Moved => {}
+            TouchPhase::Ended => {
+                let Some((start, at)) = self.down.take() else {
+                    return self.state;
+                };
+                let (dx, dy) = (location.x - start.x, location.y - start.y);
+                // Distance along the swipe direction, which must also be the dominant axis
+                let (along, across) = match self.direction {
+                    SwipeDirection::Left => (-dx, dy),
+                    SwipeDirection::Right => (dx, dy),
+                    SwipeDirection::Up => (-dy, dx),
+                    SwipeDirection::Down => (dy, dx),
+                };
+                let fast = rate(along, at, event.timestamp) >= SWIPE_MIN_VELOCITY;
+                let recognized = along >= SWIPE_MIN_DISTANCE && along > across.abs() && fast;
+
+                self.location = location;
+                self.state = if recognized { GestureState::Ended } else { GestureState::Failed };
+            }
+            TouchPhase::Cancelled => self.state = GestureState::Failed,
+        }
+        self.state
+    }
+
+    fn cancel(&mut self) {
+        self.state = lost(self.state);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 // ============================================================================
 // Edge Pan Gesture
 // ============================================================================
@@ -398,6 +971,327 @@ impl EdgePanGesture {
     }
 }
 
+// ============================================================================
+// Gesture Arena
+// ============================================================================
+
+type Listener = Box<dyn Fn(&dyn GestureRecognizer) + Send + Sync>;
+
+struct Member {
+    recognizer: Box<dyn GestureRecognizer>,
+    priority: i32,
+    simultaneous: bool,
+    listeners: Vec<Listener>,
+}
+
+impl Member {
+    fn conflicts(&self, other: &Member) -> bool {
+        !(self.simultaneous && other.simultaneous)
+    }
+}
+
+/// The recognizers competing for one view's touches
+#[derive(Default)]
+pub struct GestureArena {
+    /// Highest priority first
+    members: Vec<Member>,
+}
+
+impl GestureArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a recognizer
+    pub fn add<G: GestureRecognizer + 'static>(&mut self, recognize: Recognize<G>) {
+        let member = recognize.into_member();
+        // After existing members of the same priority, so earlier ones win ties
+        let index = self.members.partition_point(|m| m.priority >= member.priority);
+        self.members.insert(index, member);
+    }
+
+    /// Feed a touch event from the platform
+    pub fn handle_touch(&mut self, event: &TouchEvent) {
+        self.advance(|recognizer| recognizer.handle_touch(event));
+
+        // Start fresh for the next touch sequence; a tap waiting for its
+        // second tap stays Possible and keeps its count
+        if event.is_final() {
+            for member in &mut self.members {
+                if !matches!(member.recognizer.state(), GestureState::Possible) {
+                    member.recognizer.reset();
+                }
+            }
+        }
+    }
+
+    /// Whether the platform should call [`tick`](Self::tick) each frame
+    pub fn needs_tick(&self) -> bool {
+        self.members.iter().any(|m| m.recognizer.needs_tick())
+    }
+
+    /// Advance time-based recognizers, e.g. from a display link
+    pub fn tick(&mut self, now: Duration) {
+        self.advance(|recognizer| recognizer.tick(now));
+    }
+
+    /// Whether a recognizer currently owns the touches
+    ///
+    /// Platforms use this to stop an enclosing scroll view from taking over.
+    pub fn is_active(&self) -> bool {
+        self.members.iter().any(|m| is_active(m.recognizer.state()))
+    }
+
+    fn advance(&mut self, mut step: impl FnMut(&mut dyn GestureRecognizer) -> GestureState) {
+        let mut changed = vec![false; self.members.len()];
+        let mut won = vec![false; self.members.len()];
+
+        for i in 0..self.members.len() {
+            let before = self.members[i].recognizer.state();
+            let mut after = step(&mut *self.members[i].recognizer);
+
+            let starting = before == GestureState::Possible && matches!(after, GestureState::Began | GestureState::Ended);
+            if starting {
+                let member = &self.members[i];
+                let blocked = self.members[..i]
+                    .iter()
+                    .zip(&won)
+                    .any(|(other, &other_won)| {
+                        other.conflicts(member) && (other_won || is_active(other.recognizer.state()))
+                    });
+
+                if blocked {
+                    self.members[i].recognizer.cancel();
+                    after = self.members[i].recognizer.state();
+                } else {
+                    won[i] = true;
+                    let (winner, rest) = self.members.split_at_mut(i + 1);
+                    for (offset, other) in rest.iter_mut().enumerate() {
+                        let state = other.recognizer.state();
+                        if winner[i].conflicts(other) && matches!(state, GestureState::Possible | GestureState::Began | GestureState::Changed) {
+                            other.recognizer.cancel();
+                            changed[i + 1 + offset] = is_active(state);
+                        }
+                    }
+                }
+            }
+
+            changed[i] |= after != before || after == GestureState::Changed;
+        }
+
+        // Listeners run with the arena locked and must not add gestures
+        for (member, _) in self.members.iter().zip(&changed).filter(|(_, changed)| **changed) {
+            for listener in &member.listeners {
+                listener(&*member.recognizer);
+            }
+        }
+    }
+}
+
+/// A recognizer and how it takes part in its arena
+///
+/// Updates are typed: listeners, signals and driven values all see the
+/// concrete gesture, e.g. a [`PanGesture`] with its translation.
+pub struct Recognize<G> {
+    gesture: G,
+    priority: i32,
+    simultaneous: bool,
+    listeners: Vec<Box<dyn Fn(&G) + Send + Sync>>,
+}
+
+impl<G: GestureRecognizer + 'static> Recognize<G> {
+    pub fn new(gesture: G) -> Self {
+        Recognize {
+            gesture,
+            priority: 0,
+            simultaneous: false,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// Higher priorities get the touches first
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Run alongside other simultaneous recognizers, e.g. pinch with rotation
+    pub fn simultaneous(mut self) -> Self {
+        self.simultaneous = true;
+        self
+    }
+
+    /// Called on every state change and every `Changed` update
+    pub fn on_change<F: Fn(&G) + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.listeners.push(Box::new(f));
+        self
+    }
+
+    /// Called when the gesture is recognized (discrete) or finishes (continuous)
+    pub fn on_end<F: Fn(&G) + Send + Sync + 'static>(self, f: F) -> Self {
+        self.on_change(move |gesture| {
+            if gesture.state() == GestureState::Ended {
+                f(gesture);
+            }
+        })
+    }
+
+    /// Keep a signal set to the latest gesture
+    pub fn signal(self, signal: &Signal<G>) -> Self
+    where
+        G: Clone,
+    {
+        let signal = signal.clone();
+        self.on_change(move |gesture| signal.set(gesture.clone()))
+    }
+
+    /// Keep a signal set to the gesture's state
+    pub fn state_signal(self, signal: &Signal<GestureState>) -> Self {
+        let signal = signal.clone();
+        self.on_change(move |gesture| signal.set(gesture.state()))
+    }
+
+    /// Set an animated value from the gesture while it's active
+    pub fn drive<F: Fn(&G) -> f32 + Send + Sync + 'static>(self, value: &AnimatedValue, f: F) -> Self {
+        let value = value.clone();
+        self.on_change(move |gesture| {
+            if is_active(gesture.state()) {
+                value.set(f(gesture));
+            }
+        })
+    }
+
+    fn into_member(self) -> Member {
+        let listeners = self
+            .listeners
+            .into_iter()
+            .map(|listener| -> Listener {
+                Box::new(move |recognizer: &dyn GestureRecognizer| {
+                    if let Some(gesture) = recognizer.as_any().downcast_ref::<G>() {
+                        listener(gesture);
+                    }
+                })
+            })
+            .collect();
+
+        Member {
+            recognizer: Box::new(self.gesture),
+            priority: self.priority,
+            simultaneous: self.simultaneous,
+            listeners,
+        }
+    }
+}
+
+// ============================================================================
+// Interactions
+// ============================================================================
+
+/// Drag down to dismiss
+///
+/// `offset` follows the finger downwards. On release the sheet is dismissed
+/// if it was dragged past `threshold` or flung down, and springs back
+/// otherwise.
+pub fn drag_to_dismiss<F>(offset: &AnimatedValue, threshold: f32, on_dismiss: F) -> Recognize<PanGesture>
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    const FLING_VELOCITY: f32 = 1000.0;
+
+    let value = offset.clone();
+    Recognize::new(PanGesture::new()).on_change(move |pan| {
+        match pan.state {
+            GestureState::Began | GestureState::Changed => value.set(pan.translation.y.max(0.0)),
+            GestureState::Ended if pan.translation.y > threshold || pan.velocity.y > FLING_VELOCITY => on_dismiss(),
+            GestureState::Ended | GestureState::Cancelled => {
+                let spring = SpringAnimation::default().velocity(pan.velocity.y);
+                value.animate_to(0.0, Animation::Spring(spring));
+            }
+            _ => {}
+        }
+    })
+}
+
+/// Pinch to zoom between `min` and `max`
+///
+/// The scale can overshoot the limits a little while pinching and springs
+/// back within them on release.
+pub fn pinch_to_zoom(scale: &AnimatedValue, min: f32, max: f32) -> Recognize<PinchGesture> {
+    const OVERSHOOT: f32 = 0.25;
+
+    let value = scale.clone();
+    let base = Mutex::new(scale.get());
+    Recognize::new(PinchGesture::new()).on_change(move |pinch| {
+        let mut base = base.lock().unwrap();
+        if pinch.state == GestureState::Began {
+            *base = value.get();
+        }
+        match pinch.state {
+            GestureState::Began | GestureState::Changed => {
+                let scaled = *base * pinch.scale;
+                value.set(scaled.clamp(min * (1.0 - OVERSHOOT), max * (1.0 + OVERSHOOT)));
+            }
+            GestureState::Ended | GestureState::Cancelled => {
+                let current = value.get();
+                if current < min || current > max {
+                    value.animate_to(current.clamp(min, max), Animation::spring());
+                }
+            }
+            _ => {}
+        }
+    })
+}
+
+// ============================================================================
+// Gesture Detector
+// ============================================================================
+
+/// Recognizes gestures on a child component
+pub struct GestureDetector {
+    child: Box<dyn NativeComponent>,
+    arena: Arc<Mutex<GestureArena>>,
+}
+
+impl GestureDetector {
+    pub fn new(child: impl NativeComponent + 'static) -> Self {
+        GestureDetector {
+            child: Box::new(child),
+            arena: Arc::new(Mutex::new(GestureArena::new())),
+        }
+    }
+
+    /// Add a recognizer
+    pub fn gesture<G: GestureRecognizer + 'static>(self, recognize: Recognize<G>) -> Self {
+        self.arena.lock().unwrap().add(recognize);
+        self
+    }
+
+    /// The arena receiving this view's touches
+    pub fn arena(&self) -> Arc<Mutex<GestureArena>> {
+        Arc::clone(&self.arena)
+    }
+}
+
+impl NativeComponent for GestureDetector {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        let view = self.child.render(ctx);
+        ctx.attach_gestures(&view, self.arena());
+        view
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        self.child.update(ctx);
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.child.measure(constraints)
+    }
+
+    fn flex(&self) -> FlexItem {
+        self.child.flex()
+    }
+}
+
 // ============================================================================
 // Gesture Modifiers
 // ============================================================================
@@ -475,3 +1369,273 @@ pub enum DropOperation {
     Move,
     Cancel,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn at(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn event(phase: TouchPhase, touches: &[(u64, f32, f32)], ms: u64) -> TouchEvent {
+        let touches = touches
+            .iter()
+            .map(|&(id, x, y)| Touch { id, location: Point::new(x, y) })
+            .collect();
+        TouchEvent::new(phase, touches, at(ms))
+    }
+
+    /// Feed one-finger events: `(phase, x, y, ms)`
+    fn drive<G: GestureRecognizer>(gesture: &mut G, events: &[(TouchPhase, f32, f32, u64)]) -> Vec<GestureState> {
+        events
+            .iter()
+            .map(|&(phase, x, y, ms)| gesture.handle_touch(&event(phase, &[(1, x, y)], ms)))
+            .collect()
+    }
+
+    type Log = Arc<Mutex<Vec<(&'static str, GestureState)>>>;
+
+    fn logged<G: GestureRecognizer + 'static>(name: &'static str, gesture: G, log: &Log) -> Recognize<G> {
+        let log = log.clone();
+        Recognize::new(gesture).on_change(move |g| log.lock().unwrap().push((name, g.state())))
+    }
+
+    fn taken(log: &Log) -> Vec<(&'static str, GestureState)> {
+        std::mem::take(&mut *log.lock().unwrap())
+    }
+
+    use GestureState::*;
+    use TouchPhase::{Began as Down, Cancelled as Cancel, Ended as Up, Moved as Move};
+
+    #[test]
+    fn test_tap() {
+        let mut tap = TapGesture::new();
+        assert_eq!(drive(&mut tap, &[(Down, 5.0, 5.0, 0), (Up, 6.0, 5.0, 80)]), [Possible, Ended]);
+        assert_eq!(tap.location(), Point::new(6.0, 5.0));
+
+        // Moved past the slop
+        let mut tap = TapGesture::new();
+        assert_eq!(drive(&mut tap, &[(Down, 0.0, 0.0, 0), (Move, 20.0, 0.0, 30)]), [Possible, Failed]);
+
+        // Held too long
+        let mut tap = TapGesture::new();
+        assert_eq!(drive(&mut tap, &[(Down, 0.0, 0.0, 0), (Up, 0.0, 0.0, 400)]), [Possible, Failed]);
+
+        // Too many fingers
+        let mut tap = TapGesture::new();
+        assert_eq!(tap.handle_touch(&event(Down, &[(1, 0.0, 0.0), (2, 50.0, 0.0)], 0)), Failed);
+    }
+
+    #[test]
+    fn test_double_tap() {
+        let mut tap = TapGesture::double_tap();
+        let states = drive(&mut tap, &[(Down, 0.0, 0.0, 0), (Up, 0.0, 0.0, 50), (Down, 2.0, 0.0, 150), (Up, 2.0, 0.0, 200)]);
+        assert_eq!(states, [Possible, Possible, Possible, Ended]);
+
+        // A second tap after the interval starts the count over
+        let mut tap = TapGesture::double_tap();
+        let states = drive(&mut tap, &[(Down, 0.0, 0.0, 0), (Up, 0.0, 0.0, 50), (Down, 0.0, 0.0, 450), (Up, 0.0, 0.0, 500)]);
+        assert_eq!(states, [Possible, Possible, Possible, Possible]);
+    }
+
+    #[test]
+    fn test_long_press() {
+        let mut press = LongPressGesture::new();
+        assert_eq!(drive(&mut press, &[(Down, 0.0, 0.0, 0)]), [Possible]);
+        assert!(press.needs_tick());
+        assert_eq!(press.tick(at(499)), Possible);
+        assert_eq!(press.tick(at(500)), Began);
+        assert!(!press.needs_tick());
+        assert_eq!(drive(&mut press, &[(Move, 30.0, 0.0, 600), (Up, 30.0, 0.0, 700)]), [Changed, Ended]);
+
+        // Lifted early
+        let mut press = LongPressGesture::new();
+        assert_eq!(drive(&mut press, &[(Down, 0.0, 0.0, 0), (Up, 0.0, 0.0, 200)]), [Possible, Failed]);
+
+        // Moved before it began
+        let mut press = LongPressGesture::new();
+        assert_eq!(drive(&mut press, &[(Down, 0.0, 0.0, 0), (Move, 15.0, 0.0, 100)]), [Possible, Failed]);
+
+        // A move after the minimum duration begins it without a tick
+        let mut press = LongPressGesture::new().duration(at(100));
+        assert_eq!(drive(&mut press, &[(Down, 0.0, 0.0, 0), (Move, 1.0, 0.0, 150)]), [Possible, Began]);
+    }
+
+    #[test]
+    fn test_pan() {
+        let mut pan = PanGesture::new();
+        let states = drive(
+            &mut pan,
+            &[(Down, 0.0, 0.0, 0), (Move, 5.0, 0.0, 10), (Move, 20.0, 0.0, 20), (Move, 40.0, 10.0, 30)],
+        );
+        assert_eq!(states, [Possible, Possible, Began, Changed]);
+        assert_eq!(pan.translation, Point::new(40.0, 10.0));
+        assert!((pan.velocity.x - 2000.0).abs() < 1.0);
+        assert!((pan.velocity.y - 1000.0).abs() < 1.0);
+        assert_eq!(drive(&mut pan, &[(Up, 40.0, 10.0, 40)]), [Ended]);
+
+        // Never moved far enough
+        let mut pan = PanGesture::new();
+        assert_eq!(drive(&mut pan, &[(Down, 0.0, 0.0, 0), (Up, 3.0, 0.0, 50)]), [Possible, Failed]);
+
+        // Taken away by the system while active
+        let mut pan = PanGesture::new();
+        drive(&mut pan, &[(Down, 0.0, 0.0, 0), (Move, 30.0, 0.0, 10)]);
+        assert_eq!(drive(&mut pan, &[(Cancel, 30.0, 0.0, 20)]), [Cancelled]);
+    }
+
+    #[test]
+    fn test_pan_touch_range() {
+        let mut pan = PanGesture::new().touches(2, 2);
+        assert_eq!(drive(&mut pan, &[(Down, 0.0, 0.0, 0), (Move, 50.0, 0.0, 10)]), [Possible, Possible]);
+        assert_eq!(drive(&mut pan, &[(Up, 50.0, 0.0, 20)]), [Failed]);
+    }
+
+    #[test]
+    fn test_pinch_and_rotation() {
+        let down = event(Down, &[(1, 0.0, 0.0), (2, 100.0, 0.0)], 0);
+        // Twice as far apart and turned a quarter turn
+        let moved = event(Move, &[(1, 0.0, 0.0), (2, 0.0, 200.0)], 100);
+        let lift = event(Up, &[(1, 0.0, 0.0), (2, 0.0, 200.0)], 150);
+
+        let mut pinch = PinchGesture::new();
+        assert_eq!(pinch.handle_touch(&down), Possible);
+        assert_eq!(pinch.handle_touch(&moved), Began);
+        assert!((pinch.scale - 2.0).abs() < 1e-4);
+        assert!((pinch.velocity - 10.0).abs() < 1e-2);
+        assert_eq!(pinch.location, Point::new(0.0, 100.0));
+        assert_eq!(pinch.handle_touch(&lift), Ended);
+
+        let mut rotation = RotationGesture::new();
+        assert_eq!(rotation.handle_touch(&down), Possible);
+        assert_eq!(rotation.handle_touch(&moved), Began);
+        assert!((rotation.rotation - FRAC_PI_2).abs() < 1e-4);
+        assert_eq!(rotation.handle_touch(&lift), Ended);
+
+        // One finger never pinches
+        let mut pinch = PinchGesture::new();
+        assert_eq!(drive(&mut pinch, &[(Down, 0.0, 0.0, 0), (Move, 80.0, 0.0, 10), (Up, 80.0, 0.0, 20)]), [Possible, Possible, Failed]);
+    }
+
+    #[test]
+    fn test_rotation_wraps_across_axis() {
+        let mut rotation = RotationGesture::new();
+        // Just above the negative x axis, then just below it: a small turn, not a full one
+        rotation.handle_touch(&event(Down, &[(1, 0.0, 0.0), (2, -100.0, 1.0)], 0));
+        rotation.handle_touch(&event(Move, &[(1, 0.0, 0.0), (2, -100.0, -1.0)], 10));
+        assert!(rotation.rotation.abs() < 0.1);
+        assert_eq!(rotation.state(), Possible);
+    }
+
+    #[test]
+    fn test_swipe() {
+        let mut swipe = SwipeGesture::right();
+        assert_eq!(drive(&mut swipe, &[(Down, 0.0, 0.0, 0), (Move, 50.0, 2.0, 50), (Up, 100.0, 5.0, 100)]), [Possible, Possible, Ended]);
+
+        // Too slow
+        let mut swipe = SwipeGesture::right();
+        assert_eq!(drive(&mut swipe, &[(Down, 0.0, 0.0, 0), (Up, 100.0, 0.0, 1000)]), [Possible, Failed]);
+
+        // Wrong direction, and mostly across the swipe axis
+        let mut swipe = SwipeGesture::left();
+        assert_eq!(drive(&mut swipe, &[(Down, 0.0, 0.0, 0), (Up, 100.0, 0.0, 100)]), [Possible, Failed]);
+        let mut swipe = SwipeGesture::up();
+        assert_eq!(drive(&mut swipe, &[(Down, 0.0, 0.0, 0), (Up, 90.0, -60.0, 100)]), [Possible, Failed]);
+        let mut swipe = SwipeGesture::up();
+        assert_eq!(drive(&mut swipe, &[(Down, 0.0, 0.0, 0), (Up, 10.0, -80.0, 100)]), [Possible, Ended]);
+    }
+
+    #[test]
+    fn test_arena_started_gesture_cancels_lower_priority() {
+        let log = Log::default();
+        let mut arena = GestureArena::new();
+        arena.add(logged("swipe", SwipeGesture::right(), &log));
+        arena.add(logged("pan", PanGesture::new(), &log).priority(1));
+
+        arena.handle_touch(&event(Down, &[(1, 0.0, 0.0)], 0));
+        assert!(!arena.is_active());
+        arena.handle_touch(&event(Move, &[(1, 30.0, 0.0)], 30));
+        assert!(arena.is_active());
+        arena.handle_touch(&event(Up, &[(1, 100.0, 0.0)], 100));
+
+        // The swipe lost while still Possible, so its listeners never saw it
+        assert_eq!(taken(&log), [("pan", Began), ("pan", Ended)]);
+        assert!(!arena.is_active());
+    }
+
+    #[test]
+    fn test_arena_long_press_beats_tap() {
+        let log = Log::default();
+        let mut arena = GestureArena::new();
+        arena.add(logged("tap", TapGesture::new(), &log));
+        arena.add(logged("press", LongPressGesture::new(), &log).priority(1));
+
+        arena.handle_touch(&event(Down, &[(1, 0.0, 0.0)], 0));
+        assert!(arena.needs_tick());
+        arena.tick(at(500));
+        assert!(arena.is_active());
+        arena.handle_touch(&event(Up, &[(1, 0.0, 0.0)], 600));
+        // The tap was cancelled while Possible, so its listeners never saw it
+        assert_eq!(taken(&log), [("press", Began), ("press", Ended)]);
+
+        // Recognizers are reset for the next sequence: a quick tap wins
+        arena.handle_touch(&event(Down, &[(1, 0.0, 0.0)], 1000));
+        arena.handle_touch(&event(Up, &[(1, 0.0, 0.0)], 1050));
+        assert_eq!(taken(&log), [("press", Failed), ("tap", Ended)]);
+    }
+
+    #[test]
+    fn test_arena_simultaneous_gestures() {
+        let down = event(Down, &[(1, 0.0, 0.0), (2, 100.0, 0.0)], 0);
+        let moved = event(Move, &[(1, 0.0, 0.0), (2, 0.0, 200.0)], 100);
+
+        let log = Log::default();
+        let mut arena = GestureArena::new();
+        arena.add(logged("pinch", PinchGesture::new(), &log).simultaneous());
+        arena.add(logged("rotate", RotationGesture::new(), &log).simultaneous());
+        arena.handle_touch(&down);
+        arena.handle_touch(&moved);
+        assert_eq!(taken(&log), [("pinch", Began), ("rotate", Began)]);
+
+        // Without both opting in, the first to start wins
+        let mut arena = GestureArena::new();
+        arena.add(logged("pinch", PinchGesture::new(), &log).simultaneous());
+        arena.add(logged("rotate", RotationGesture::new(), &log));
+        arena.handle_touch(&down);
+        arena.handle_touch(&moved);
+        assert_eq!(taken(&log), [("pinch", Began)]);
+    }
+
+    #[test]
+    fn test_equal_priority_goes_in_order_added() {
+        let log = Log::default();
+        let mut arena = GestureArena::new();
+        arena.add(logged("first", PanGesture::new(), &log));
+        arena.add(logged("second", PanGesture::new(), &log));
+
+        arena.handle_touch(&event(Down, &[(1, 0.0, 0.0)], 0));
+        arena.handle_touch(&event(Move, &[(1, 30.0, 0.0)], 10));
+        assert_eq!(taken(&log), [("first", Began)]);
+    }
+
+    #[test]
+    fn test_drag_to_dismiss() {
+        let offset = AnimatedValue::new(0.0);
+        let dismissed = Arc::new(Mutex::new(false));
+        let flag = dismissed.clone();
+
+        let mut arena = GestureArena::new();
+        arena.add(drag_to_dismiss(&offset, 150.0, move || *flag.lock().unwrap() = true));
+
+        arena.handle_touch(&event(Down, &[(1, 0.0, 0.0)], 0));
+        arena.handle_touch(&event(Move, &[(1, 0.0, -40.0)], 100));
+        // Dragging up is clamped to zero
+        assert_eq!(offset.get(), 0.0);
+        arena.handle_touch(&event(Move, &[(1, 0.0, 200.0)], 1000));
+        assert_eq!(offset.get(), 200.0);
+        arena.handle_touch(&event(Up, &[(1, 0.0, 200.0)], 1100));
+        assert!(*dismissed.lock().unwrap());
+    }
+}
@@ -0,0 +1,744 @@
+//! PhilJS Mobile Hot Reload
+//!
+//! Dev-mode client for `cargo philjs dev`. It connects to the dev server's
+//! `/__hmr` WebSocket and turns its messages into [`HotUpdate`]s the runtime
+//! applies by swapping the root view, without restarting the app.
+//!
+//! Two kinds of payload can replace the root:
+//!
+//! - **Bundles**: a rebuilt `cdylib` exporting its root with [`hot_root!`].
+//!   Bundles are loaded from disk when the server shares the filesystem
+//!   (desktop simulation, iOS simulator) and downloaded from the dev server
+//!   otherwise. Local bundles must live inside the configured
+//!   [`output_dir`](HotReloadConfig::output_dir); anything else is rejected
+//!   rather than loaded. WebAssembly bundles are for the web runtime and are
+//!   ignored.
+//! - **View trees**: a serialized [`ViewNode`] for layout-only edits, which
+//!   needs no rebuild at all.
+//!
+//! State created with [`hot_signal`] lives in the host and is handed to each
+//! bundle, so counters, form input, and so on survive a swap as long as the
+//! key and type are unchanged.
+//!
+//! ```rust,ignore
+//! // In the app crate, built as a cdylib in dev
+//! philjs_mobile::hot_root!(App);
+//!
+//! fn App() -> impl NativeComponent {
+//!     let count = hot_signal("count", || 0);
+//!     VStack::new().child(Text::new(format!("Count: {}", count.get())))
+//! }
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::components::{Card, Container, Flexible, HAlignment, HStack, Spacer, Text, VAlignment, VStack, ZStack};
+use crate::runtime::Signal;
+use crate::{Color, EdgeInsets, NativeComponent};
+
+/// Default dev server endpoint
+pub const DEFAULT_DEV_SERVER: &str = "ws://127.0.0.1:3000/__hmr";
+
+/// Symbol a hot bundle exports its root under
+pub const HOT_ROOT_SYMBOL: &[u8] = b"philjs_hot_root\0";
+
+/// Signature of the exported root function
+pub type HotRootFn = unsafe extern "C" fn(state: *const HotState) -> *mut Box<dyn NativeComponent>;
+
+/// Hot reload client configuration
+#[derive(Debug, Clone)]
+pub struct HotReloadConfig {
+    /// WebSocket URL of the dev server's HMR endpoint
+    pub url: String,
+    /// Delay before reconnecting after the server goes away
+    pub reconnect_delay: Duration,
+    /// Where bundles are staged before loading
+    pub bundle_dir: PathBuf,
+    /// The dev server's build output; local bundles outside it are refused
+    pub output_dir: PathBuf,
+}
+
+impl Default for HotReloadConfig {
+    fn default() -> Self {
+        HotReloadConfig {
+            url: DEFAULT_DEV_SERVER.to_string(),
+            reconnect_delay: Duration::from_secs(1),
+            bundle_dir: std::env::temp_dir().join("philjs-hot"),
+            output_dir: PathBuf::from("target"),
+        }
+    }
+}
+
+impl HotReloadConfig {
+    /// Connect to the given dev server, e.g. `ws://10.0.2.2:3000/__hmr` from
+    /// the Android emulator
+    pub fn new(url: impl Into<String>) -> Self {
+        HotReloadConfig {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    pub fn bundle_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.bundle_dir = dir.into();
+        self
+    }
+
+    pub fn output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = dir.into();
+        self
+    }
+
+    /// HTTP origin of the dev server, derived from the WebSocket URL
+    fn http_origin(&self) -> Option<String> {
+        let (scheme, rest) = match self.url.split_once("://")? {
+            ("ws", rest) => ("http", rest),
+            ("wss", rest) => ("https", rest),
+            _ => return None,
+        };
+        Some(format!("{}://{}", scheme, rest.split('/').next().unwrap_or(rest)))
+    }
+}
+
+/// Messages sent by the dev server
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DevMessage {
+    Connected,
+    /// Restart from scratch
+    Reload,
+    /// A rebuilt module
+    Update { path: String, hash: u64 },
+    /// A serialized view tree to render as the root
+    Tree { root: ViewNode },
+    Error {
+        message: String,
+        file: Option<String>,
+        line: Option<u32>,
+    },
+    /// Messages for other runtimes, e.g. CSS updates
+    #[serde(other)]
+    Other,
+}
+
+/// A change for the runtime to apply
+pub enum HotUpdate {
+    /// Replace the root view
+    Root(Box<dyn NativeComponent>),
+    /// The change can't be applied in place; restart the app
+    Reload,
+    /// The build failed
+    Error {
+        message: String,
+        file: Option<String>,
+        line: Option<u32>,
+    },
+}
+
+/// What the connection thread hands to the UI thread
+enum Payload {
+    Bundle(PathBuf),
+    Tree(ViewNode),
+    Reload,
+    Error {
+        message: String,
+        file: Option<String>,
+        line: Option<u32>,
+    },
+}
+
+/// Connection to the dev server
+///
+/// Messages are read on a background thread. Call [`poll`](Self::poll) from
+/// the UI thread, e.g. once per frame, since views are built there.
+pub struct HotReloadClient {
+    rx: mpsc::Receiver<Payload>,
+    state: Arc<HotState>,
+    // Kept loaded: views built from a bundle point into its code
+    libraries: Vec<libloading::Library>,
+}
+
+impl HotReloadClient {
+    /// Start connecting in the background
+    pub fn connect(config: HotReloadConfig) -> Self {
+        let (tx, rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("philjs-hot-reload".to_string())
+            .spawn(move || run_connection(config, tx))
+            .expect("failed to spawn hot reload thread");
+
+        HotReloadClient {
+            rx,
+            state: HotState::global(),
+            libraries: Vec::new(),
+        }
+    }
+
+    /// The next update, if one has arrived
+    pub fn poll(&mut self) -> Option<HotUpdate> {
+        let payload = self.rx.try_recv().ok()?;
+        Some(self.apply(payload))
+    }
+
+    /// Wait for the next update; `None` once the connection thread exits
+    pub fn recv(&mut self) -> Option<HotUpdate> {
+        let payload = self.rx.recv().ok()?;
+        Some(self.apply(payload))
+    }
+
+    fn apply(&mut self, payload: Payload) -> HotUpdate {
+        match payload {
+            Payload::Tree(node) => match node.into_component() {
+                Ok(root) => HotUpdate::Root(root),
+                Err(message) => HotUpdate::Error {
+                    message,
+                    file: None,
+                    line: None,
+                },
+            },
+            Payload::Bundle(path) => match self.load_bundle(&path) {
+                Ok(root) => HotUpdate::Root(root),
+                Err(e) => HotUpdate::Error {
+                    message: format!("failed to load bundle: {}", e),
+                    file: Some(path.display().to_string()),
+                    line: None,
+                },
+            },
+            Payload::Reload => HotUpdate::Reload,
+            Payload::Error { message, file, line } => HotUpdate::Error { message, file, line },
+        }
+    }
+
+    fn load_bundle(&mut self, path: &Path) -> Result<Box<dyn NativeComponent>, libloading::Error> {
+        // Safety: bundles are staged from the dev server's output directory
+        // or downloaded from the dev server itself, and are built against
+        // this crate with `hot_root!`
+        unsafe {
+            let library = libloading::Library::new(path)?;
+            let root = {
+                let root_fn: libloading::Symbol<HotRootFn> = library.get(HOT_ROOT_SYMBOL)?;
+                *Box::from_raw(root_fn(Arc::as_ptr(&self.state)))
+            };
+            self.libraries.push(library);
+            Ok(root)
+        }
+    }
+}
+
+/// Read messages until the client is dropped, reconnecting as needed
+fn run_connection(config: HotReloadConfig, tx: mpsc::Sender<Payload>) {
+    let mut connected_before = false;
+
+    loop {
+        let mut socket = match tungstenite::connect(config.url.as_str()) {
+            Ok((socket, _)) => socket,
+            Err(_) => {
+                std::thread::sleep(config.reconnect_delay);
+                continue;
+            }
+        };
+
+        // Changes made while disconnected were missed
+        if connected_before && tx.send(Payload::Reload).is_err() {
+            return;
+        }
+        connected_before = true;
+
+        while let Ok(message) = socket.read() {
+            let tungstenite::Message::Text(text) = message else { continue };
+            let Some(payload) = parse_message(&config, &text) else { continue };
+            if tx.send(payload).is_err() {
+                return;
+            }
+        }
+
+        std::thread::sleep(config.reconnect_delay);
+    }
+}
+
+fn parse_message(config: &HotReloadConfig, text: &str) -> Option<Payload> {
+    match serde_json::from_str::<DevMessage>(text) {
+        Ok(message) => to_payload(config, message),
+        Err(e) => Some(Payload::Error {
+            message: format!("unrecognized dev server message: {}", e),
+            file: None,
+            line: None,
+        }),
+    }
+}
+
+fn to_payload(config: &HotReloadConfig, message: DevMessage) -> Option<Payload> {
+    match message {
+        DevMessage::Tree { root } => Some(Payload::Tree(root)),
+        DevMessage::Update { path, hash } => {
+            if Path::new(&path).extension().and_then(|ext| ext.to_str()) != Some(std::env::consts::DLL_EXTENSION) {
+                return None;
+            }
+            match stage_bundle(config, &path, hash) {
+                Ok(bundle) => Some(Payload::Bundle(bundle)),
+                Err(e) => Some(Payload::Error {
+                    message: format!("failed to fetch bundle {}: {}", path, e),
+                    file: None,
+                    line: None,
+                }),
+            }
+        }
+        DevMessage::Reload => Some(Payload::Reload),
+        DevMessage::Error { message, file, line } => Some(Payload::Error { message, file, line }),
+        DevMessage::Connected | DevMessage::Other => None,
+    }
+}
+
+/// Where a bundle named by the dev server comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BundleSource {
+    /// A file inside the output directory
+    Local(PathBuf),
+    /// A URL on the dev server
+    Remote(String),
+}
+
+/// Resolve the path in an update message, refusing anything outside the
+/// dev server's output directory
+///
+/// Paths that exist locally are canonicalized, so neither `..` nor symlinks
+/// can escape the directory. Other paths are fetched from the dev server
+/// and must be plain relative URL paths.
+fn bundle_source(config: &HotReloadConfig, path: &str) -> Result<BundleSource, String> {
+    let candidate = Path::new(path);
+    if candidate.components().any(|component| component == Component::ParentDir) {
+        return Err(format!("bundle path {} leaves the output directory", path));
+    }
+
+    let local = if candidate.is_absolute() {
+        candidate.to_path_buf()
+    } else {
+        config.output_dir.join(candidate)
+    };
+    if local.is_file() {
+        let root = config
+            .output_dir
+            .canonicalize()
+            .map_err(|e| format!("output directory {}: {}", config.output_dir.display(), e))?;
+        let local = local.canonicalize().map_err(|e| format!("bundle {}: {}", path, e))?;
+        return if local.starts_with(&root) {
+            Ok(BundleSource::Local(local))
+        } else {
+            Err(format!("bundle {} is outside {}", path, config.output_dir.display()))
+        };
+    }
+    if path.contains(':') || path.contains('\\') {
+        return Err(format!("bundle {} is not a dev server path", path));
+    }
+
+    let origin = config
+        .http_origin()
+        .ok_or_else(|| "dev server URL must use ws:// or wss://".to_string())?;
+    Ok(BundleSource::Remote(format!("{}/{}", origin, path.trim_start_matches('/'))))
+}
+
+/// Copy or download a bundle to a path unique to its hash
+///
+/// The dynamic loader caches libraries by path, so loading a rebuilt bundle
+/// from the same path would return the old code.
+fn stage_bundle(config: &HotReloadConfig, path: &str, hash: u64) -> Result<PathBuf, String> {
+    let source = bundle_source(config, path)?;
+    std::fs::create_dir_all(&config.bundle_dir).map_err(|e| e.to_string())?;
+    let target = config
+        .bundle_dir
+        .join(format!("bundle-{:016x}.{}", hash, std::env::consts::DLL_EXTENSION));
+
+    match source {
+        BundleSource::Local(file) => {
+            std::fs::copy(file, &target).map_err(|e| e.to_string())?;
+        }
+        BundleSource::Remote(url) => {
+            let bytes = reqwest::blocking::get(url)
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.bytes())
+                .map_err(|e| e.to_string())?;
+            std::fs::write(&target, bytes).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(target)
+}
+
+// ============================================================================
+// State Preservation
+// ============================================================================
+
+/// Signals that outlive bundle swaps
+pub struct HotState {
+    signals: Mutex<HashMap<(String, TypeId), Box<dyn Any + Send>>>,
+}
+
+static INSTALLED_STATE: AtomicPtr<HotState> = AtomicPtr::new(std::ptr::null_mut());
+
+impl HotState {
+    /// The state shared by this process, or by the host when inside a bundle
+    pub fn global() -> Arc<HotState> {
+        static GLOBAL: std::sync::OnceLock<Arc<HotState>> = std::sync::OnceLock::new();
+        GLOBAL
+            .get_or_init(|| {
+                Arc::new(HotState {
+                    signals: Mutex::new(HashMap::new()),
+                })
+            })
+            .clone()
+    }
+
+    fn with_current<R>(f: impl FnOnce(&HotState) -> R) -> R {
+        let installed = INSTALLED_STATE.load(Ordering::Acquire);
+        if installed.is_null() {
+            f(&HotState::global())
+        } else {
+            // Safety: installed by `hot_root!` from the host's state, which
+            // the host keeps alive for the life of the process
+            f(unsafe { &*installed })
+        }
+    }
+}
+
+/// Use the host's state inside a bundle; called by [`hot_root!`]
+#[doc(hidden)]
+pub fn install_state(state: *const HotState) {
+    INSTALLED_STATE.store(state as *mut HotState, Ordering::Release);
+}
+
+/// A signal that keeps its value across hot reloads
+///
+/// Returns the signal created under `key` before the last swap, or a new one
+/// from `init`. Changing the value's type starts over with `init`.
+pub fn hot_signal<T>(key: &str, init: impl FnOnce() -> T) -> Signal<T>
+where
+    T: Clone + Send + 'static,
+{
+    HotState::with_current(|state| {
+        let mut signals = state.signals.lock().unwrap();
+        let slot = signals
+            .entry((key.to_string(), TypeId::of::<T>()))
+            .or_insert_with(|| Box::new(Signal::new(init())));
+        slot.downcast_ref::<Signal<T>>().expect("keyed by type").clone()
+    })
+}
+
+/// Export a component constructor as a hot reload bundle's root
+#[macro_export]
+macro_rules! hot_root {
+    ($root:expr) => {
+        #[no_mangle]
+        pub unsafe extern "C" fn philjs_hot_root(
+            state: *const $crate::hot_reload::HotState,
+        ) -> *mut ::std::boxed::Box<dyn $crate::NativeComponent> {
+            $crate::hot_reload::install_state(state);
+            let root: ::std::boxed::Box<dyn $crate::NativeComponent> = ::std::boxed::Box::new($root());
+            ::std::boxed::Box::into_raw(::std::boxed::Box::new(root))
+        }
+    };
+}
+
+// ============================================================================
+// Serialized View Trees
+// ============================================================================
+
+/// A view tree sent by the dev server
+#[derive(Debug, Clone, Deserialize)]
+pub struct ViewNode {
+    /// Component name, e.g. `"VStack"`
+    pub kind: String,
+    #[serde(default)]
+    pub props: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub children: Vec<ViewNode>,
+}
+
+impl ViewNode {
+    fn f32_prop(&self, name: &str) -> Option<f32> {
+        self.props.get(name).and_then(|value| value.as_f64()).map(|value| value as f32)
+    }
+
+    fn str_prop(&self, name: &str) -> Option<&str> {
+        self.props.get(name).and_then(|value| value.as_str())
+    }
+
+    fn padding(&self) -> Option<EdgeInsets> {
+        self.f32_prop("padding").map(EdgeInsets::all)
+    }
+
+    fn color_prop(&self, name: &str) -> Option<Color> {
+        let hex = self.str_prop(name)?.trim_start_matches('#');
+        u32::from_str_radix(hex, 16).ok().map(Color::from_hex)
+    }
+
+    fn children(self) -> Result<Vec<Box<dyn NativeComponent>>, String> {
+        self.children.into_iter().map(ViewNode::into_component).collect()
+    }
+
+    fn single_child(self) -> Result<Option<Box<dyn NativeComponent>>, String> {
+        let kind = self.kind.clone();
+        let mut children = self.children()?;
+        if children.len() > 1 {
+            return Err(format!("{} takes one child, got {}", kind, children.len()));
+        }
+        Ok(children.pop())
+    }
+
+    /// Build the component this node describes
+    pub fn into_component(self) -> Result<Box<dyn NativeComponent>, String> {
+        let kind = self.kind.clone();
+        let component: Box<dyn NativeComponent> = match kind.as_str() {
+            "Text" => {
+                let mut text = Text::new(self.str_prop("content").unwrap_or_default());
+                if let Some(size) = self.f32_prop("font_size") {
+                    text = text.font_size(size);
+                }
+                if let Some(color) = self.color_prop("color") {
+                    text = text.color(color);
+                }
+                Box::new(text)
+            }
+            "VStack" => {
+                let mut stack = VStack::new();
+                if let Some(spacing) = self.f32_prop("spacing") {
+                    stack = stack.spacing(spacing);
+                }
+                if let Some(padding) = self.padding() {
+                    stack = stack.padding(padding);
+                }
+                stack = stack.alignment(match self.str_prop("alignment") {
+                    Some("leading") => HAlignment::Leading,
+                    Some("trailing") => HAlignment::Trailing,
+                    _ => HAlignment::Center,
+                });
+                stack.children = self.children()?;
+                Box::new(stack)
+            }
+            "HStack" => {
+                let mut stack = HStack::new();
+                if let Some(spacing) = self.f32_prop("spacing") {
+                    stack = stack.spacing(spacing);
+                }
+                if let Some(padding) = self.padding() {
+                    stack = stack.padding(padding);
+                }
+                stack = stack.alignment(match self.str_prop("alignment") {
+                    Some("top") => VAlignment::Top,
+                    Some("bottom") => VAlignment::Bottom,
+                    _ => VAlignment::Center,
+                });
+                stack.children = self.children()?;
+                Box::new(stack)
+            }
+            "ZStack" => {
+                let mut stack = ZStack::new();
+                stack.children = self.children()?;
+                Box::new(stack)
+            }
+            "Spacer" => Box::new(Spacer::new().min_length(self.f32_prop("min_length").unwrap_or(0.0))),
+            "Flexible" => {
+                let grow = self.f32_prop("grow").unwrap_or(1.0);
+                let child = self.single_child()?.ok_or("Flexible needs a child")?;
+                Box::new(Flexible {
+                    child,
+                    item: crate::FlexItem::default().grow(grow),
+                })
+            }
+            "Container" => {
+                let mut container = Container::new();
+                if let Some(padding) = self.padding() {
+                    container = container.padding(padding);
+                }
+                if let Some(color) = self.color_prop("background") {
+                    container = container.background(color);
+                }
+                if let Some(radius) = self.f32_prop("corner_radius") {
+                    container = container.corner_radius(radius);
+                }
+                container.child = self.single_child()?;
+                Box::new(container)
+            }
+            "Card" => {
+                let mut card = Card::new();
+                if let Some(padding) = self.padding() {
+                    card = card.padding(padding);
+                }
+                card.child = self.single_child()?;
+                Box::new(card)
+            }
+            other => return Err(format!("unknown component in view tree: {}", other)),
+        };
+        Ok(component)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory under the system temp dir
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("philjs-hot-test-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn bundle_name(stem: &str) -> String {
+        format!("{}.{}", stem, std::env::consts::DLL_EXTENSION)
+    }
+
+    fn config(output: &Path) -> HotReloadConfig {
+        HotReloadConfig::new("ws://127.0.0.1:3000/__hmr")
+            .output_dir(output)
+            .bundle_dir(output.join("staged"))
+    }
+
+    #[test]
+    fn test_parse_messages() {
+        let config = config(&scratch("parse"));
+
+        let tree = r#"{"type":"tree","root":{"kind":"Text","props":{"content":"hi"}}}"#;
+        assert!(matches!(
+            parse_message(&config, tree),
+            Some(Payload::Tree(ViewNode { ref kind, .. })) if kind == "Text"
+        ));
+        assert!(matches!(parse_message(&config, r#"{"type":"reload"}"#), Some(Payload::Reload)));
+        assert!(matches!(
+            parse_message(&config, r#"{"type":"error","message":"E0308","file":"src/app.rs","line":12}"#),
+            Some(Payload::Error { ref message, file: Some(ref file), line: Some(12) })
+                if message == "E0308" && file == "src/app.rs"
+        ));
+
+        // Other runtimes' messages and wasm bundles are ignored
+        assert!(parse_message(&config, r#"{"type":"connected"}"#).is_none());
+        assert!(parse_message(&config, r#"{"type":"css","path":"/app.css"}"#).is_none());
+        assert!(parse_message(&config, r#"{"type":"update","path":"pkg/app.wasm","hash":1}"#).is_none());
+    }
+
+    #[test]
+    fn test_malformed_messages_become_errors() {
+        let config = config(&scratch("malformed"));
+        assert!(matches!(
+            parse_message(&config, "not json"),
+            Some(Payload::Error { ref message, .. }) if message.starts_with("unrecognized dev server message")
+        ));
+        assert!(matches!(
+            parse_message(&config, r#"{"type":"update","path":"x.so"}"#),
+            Some(Payload::Error { .. })
+        ));
+    }
+
+    #[test]
+    fn test_local_bundles_inside_output_dir() {
+        let output = scratch("inside");
+        std::fs::create_dir_all(output.join("debug")).unwrap();
+        let bundle = output.join("debug").join(bundle_name("libapp"));
+        std::fs::write(&bundle, b"bundle").unwrap();
+        let config = config(&output);
+
+        let expected = BundleSource::Local(bundle.canonicalize().unwrap());
+        let relative = format!("debug/{}", bundle_name("libapp"));
+        assert_eq!(bundle_source(&config, &relative), Ok(expected.clone()));
+        assert_eq!(bundle_source(&config, bundle.to_str().unwrap()), Ok(expected));
+
+        let staged = stage_bundle(&config, &relative, 0xabc).unwrap();
+        assert!(staged.starts_with(output.join("staged")));
+        assert_eq!(std::fs::read(staged).unwrap(), b"bundle");
+    }
+
+    #[test]
+    fn test_rejects_paths_outside_output_dir() {
+        let root = scratch("outside");
+        let output = root.join("target");
+        std::fs::create_dir_all(&output).unwrap();
+        let secret = root.join(bundle_name("libsecret"));
+        std::fs::write(&secret, b"secret").unwrap();
+        let config = config(&output);
+
+        assert!(bundle_source(&config, secret.to_str().unwrap()).is_err());
+        assert!(bundle_source(&config, &format!("../{}", bundle_name("libsecret"))).is_err());
+        assert!(bundle_source(&config, &format!("debug/../../{}", bundle_name("libsecret"))).is_err());
+        assert!(bundle_source(&config, &format!("http://evil.test/{}", bundle_name("libapp"))).is_err());
+
+        // Nothing is staged for a refused bundle
+        let message = format!(r#"{{"type":"update","path":{:?},"hash":7}}"#, secret.to_str().unwrap());
+        assert!(matches!(parse_message(&config, &message), Some(Payload::Error { .. })));
+        assert!(!output.join("staged").join(bundle_name("bundle-0000000000000007")).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rejects_symlinks_out_of_output_dir() {
+        let root = scratch("symlink");
+        let output = root.join("target");
+        std::fs::create_dir_all(&output).unwrap();
+        let secret = root.join(bundle_name("libsecret"));
+        std::fs::write(&secret, b"secret").unwrap();
+        std::os::unix::fs::symlink(&secret, output.join(bundle_name("libapp"))).unwrap();
+
+        assert!(bundle_source(&config(&output), &bundle_name("libapp")).is_err());
+    }
+
+    #[test]
+    fn test_remote_bundles_resolve_against_dev_server() {
+        let output = scratch("remote");
+        let name = bundle_name("libapp");
+
+        assert_eq!(
+            bundle_source(&config(&output), &format!("/debug/{}", name)),
+            Ok(BundleSource::Remote(format!("http://127.0.0.1:3000/debug/{}", name)))
+        );
+
+        let secure = HotReloadConfig::new("wss://dev.example.com/__hmr").output_dir(&output);
+        assert_eq!(
+            bundle_source(&secure, &name),
+            Ok(BundleSource::Remote(format!("https://dev.example.com/{}", name)))
+        );
+
+        let plain = HotReloadConfig::new("tcp://dev.example.com").output_dir(&output);
+        assert!(bundle_source(&plain, &name).is_err());
+    }
+
+    #[test]
+    fn test_unloadable_bundle_reports_error() {
+        let output = scratch("unloadable");
+        let bundle = output.join(bundle_name("libbroken"));
+        std::fs::write(&bundle, b"not a library").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let mut client = HotReloadClient {
+            rx,
+            state: HotState::global(),
+            libraries: Vec::new(),
+        };
+        tx.send(Payload::Bundle(bundle.clone())).unwrap();
+
+        match client.poll() {
+            Some(HotUpdate::Error { file, .. }) => assert_eq!(file, Some(bundle.display().to_string())),
+            _ => panic!("expected an error update"),
+        }
+        assert!(client.poll().is_none());
+    }
+
+    #[test]
+    fn test_hot_signal_survives_lookup() {
+        let first = hot_signal("hot_reload_test_count", || 1);
+        first.set(5);
+        assert_eq!(hot_signal("hot_reload_test_count", || 1).get(), 5);
+        // A different type under the same key starts over
+        assert_eq!(hot_signal("hot_reload_test_count", || "fresh").get(), "fresh");
+    }
+}
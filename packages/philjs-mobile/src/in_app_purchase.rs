@@ -1,9 +1,26 @@
 //! PhilJS Mobile In-App Purchases
 //!
 //! StoreKit (iOS) and Google Play Billing integration.
-
+//!
+//! A [`Store`] fetches the catalog, runs purchases and publishes each
+//! product's [`PurchaseState`] as a signal. Completed transactions go
+//! through an optional server-side validator before they are finished and
+//! granted.
+//!
+//! Store calls finish asynchronously; the platform glue reports them with
+//! [`complete_request`] and delivers transactions that arrive on their own
+//! with [`handle_transaction_update`]. Until that glue exists, every store
+//! call fails at once with [`PurchaseError::StoreNotAvailable`].
+
+use crate::runtime::Signal;
+use futures::channel::oneshot;
+use futures::future::BoxFuture;
+use futures::FutureExt;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
 
 /// Product types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -103,6 +120,13 @@ pub struct Transaction {
     pub original_id: String,
     /// Product identifier
     pub product_id: String,
+    /// Type of the product bought, set by the platform glue
+    ///
+    /// StoreKit reports it with the transaction. Play only tells in-app
+    /// products from subscriptions, so the glue fills in consumables from
+    /// the app's own catalog. Decides whether the transaction is consumed
+    /// or acknowledged, and whether it is kept as an entitlement.
+    pub product_type: ProductType,
     /// Purchase date
     pub purchase_date: String,
     /// Expiration date (for subscriptions)
@@ -122,168 +146,479 @@ pub enum PurchaseResult {
     Failed(PurchaseError),
 }
 
+/// Where a product's purchase currently stands, as seen through
+/// [`Store::purchase_state`]
+#[derive(Debug, Clone)]
+pub enum PurchaseState {
+    Idle,
+    /// The store's purchase sheet is showing
+    Purchasing,
+    /// Waiting on approval, e.g. Ask to Buy or a cash payment on Play
+    Pending,
+    /// Waiting on the receipt validator
+    Validating,
+    Purchased(Transaction),
+    Cancelled,
+    Failed(PurchaseError),
+}
+
+impl PurchaseState {
+    /// Whether a purchase is under way and the buy button should be disabled
+    pub fn is_busy(&self) -> bool {
+        matches!(self, PurchaseState::Purchasing | PurchaseState::Pending | PurchaseState::Validating)
+    }
+}
+
+// ============================================================================
+// Receipt Validation
+// ============================================================================
+
+/// Which store a receipt came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorePlatform {
+    AppStore,
+    PlayStore,
+}
+
+/// A transaction to check on the server before granting it
+///
+/// `transaction.receipt_data` is the StoreKit 2 JWS signed transaction on
+/// iOS and the purchase token on Android.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptRequest {
+    pub platform: StorePlatform,
+    pub product_type: ProductType,
+    pub transaction: Transaction,
+}
+
+/// The server's verdict on a receipt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReceiptValidation {
+    /// Grant the purchase; the server may correct the expiration date
+    Valid { expiration_date: Option<String> },
+    /// Don't grant it, e.g. a forged or refunded receipt
+    Invalid(String),
+}
+
+type Validator = Arc<dyn Fn(ReceiptRequest) -> BoxFuture<'static, Result<ReceiptValidation, String>> + Send + Sync>;
+
+// ============================================================================
+// Store
+// ============================================================================
+
 /// Store manager for in-app purchases
+///
+/// Clones share the catalog, purchase states and validator. The most recently
+/// created store receives transactions that arrive outside a purchase call
+/// (approved Ask to Buy requests, renewals, purchases on other devices).
+///
+/// ```rust,ignore
+/// #[server]
+/// async fn validate_receipt(receipt: ReceiptRequest) -> Result<ReceiptValidation, ServerFnError> {
+///     app_store_server_api::verify(&receipt.transaction).await
+/// }
+///
+/// let store = Store::new();
+/// store.validate_with(validate_receipt);
+/// store.fetch_products(&["pro_monthly"]).await?;
+///
+/// let state = store.purchase_state("pro_monthly");
+/// Button::new("Subscribe").disabled(state.get().is_busy());
+/// store.purchase("pro_monthly").await;
+/// ```
+#[derive(Clone)]
 pub struct Store {
-    products: Vec<Product>,
-    on_transaction: Option<Arc<dyn Fn(Transaction) + Send + Sync>>,
+    inner: Arc<StoreInner>,
+}
+
+struct StoreInner {
+    products: Mutex<HashMap<String, Product>>,
+    states: Mutex<HashMap<String, Signal<PurchaseState>>>,
+    entitlements: Signal<Vec<Transaction>>,
+    validator: Mutex<Option<Validator>>,
+    on_transaction: Mutex<Option<Arc<dyn Fn(Transaction) + Send + Sync>>>,
 }
 
+/// Receiver for transactions outside a purchase call
+static CURRENT_STORE: Mutex<Option<Weak<StoreInner>>> = Mutex::new(None);
+/// Transactions that arrived before any store existed
+static QUEUED_TRANSACTIONS: Mutex<Vec<Transaction>> = Mutex::new(Vec::new());
+
 impl Store {
     pub fn new() -> Self {
-        Store {
-            products: Vec::new(),
-            on_transaction: None,
+        let store = Store {
+            inner: Arc::new(StoreInner {
+                products: Mutex::new(HashMap::new()),
+                states: Mutex::new(HashMap::new()),
+                entitlements: Signal::new(Vec::new()),
+                validator: Mutex::new(None),
+                on_transaction: Mutex::new(None),
+            }),
+        };
+        *CURRENT_STORE.lock().unwrap() = Some(Arc::downgrade(&store.inner));
+        native::observe_transactions();
+
+        let queued = std::mem::take(&mut *QUEUED_TRANSACTIONS.lock().unwrap());
+        if !queued.is_empty() {
+            let store = store.clone();
+            crate::runtime::spawn_async(async move {
+                for transaction in queued {
+                    store.process(transaction).await;
+                }
+            });
         }
+        store
+    }
+
+    /// Check every transaction with a server before it's granted
+    ///
+    /// Takes any async function of a [`ReceiptRequest`], including a
+    /// `#[server]` function. An `Err` (e.g. no network) fails the purchase
+    /// but leaves the transaction unfinished, so the store delivers it again
+    /// on the next launch.
+    pub fn validate_with<F, Fut, E>(&self, validator: F)
+    where
+        F: Fn(ReceiptRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ReceiptValidation, E>> + Send + 'static,
+        E: std::fmt::Display,
+    {
+        let validator: Validator = Arc::new(move |request| {
+            let result = validator(request);
+            async move { result.await.map_err(|e| e.to_string()) }.boxed()
+        });
+        *self.inner.validator.lock().unwrap() = Some(validator);
     }
 
     /// Fetch products from the store
-    pub async fn fetch_products(&mut self, product_ids: &[&str]) -> Result<Vec<Product>, PurchaseError> {
-        #[cfg(target_os = "ios")]
-        {
-            self.fetch_products_ios(product_ids).await
-        }
-        #[cfg(target_os = "android")]
-        {
-            self.fetch_products_android(product_ids).await
-        }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            let _ = product_ids;
-            Err(PurchaseError::StoreNotAvailable)
+    pub async fn fetch_products(&self, product_ids: &[&str]) -> Result<Vec<Product>, PurchaseError> {
+        let ids: Vec<String> = product_ids.iter().map(|id| id.to_string()).collect();
+        let products = match request(|id| native::fetch_products(id, &ids)).await? {
+            StoreResponse::Products(products) => products,
+            _ => return Err(PurchaseError::Unknown("unexpected store response".to_string())),
+        };
+
+        let mut cache = self.inner.products.lock().unwrap();
+        for product in &products {
+            cache.insert(product.id.clone(), product.clone());
         }
+        Ok(products)
+    }
+
+    /// A product from the last fetch
+    pub fn product(&self, product_id: &str) -> Option<Product> {
+        self.inner.products.lock().unwrap().get(product_id).cloned()
+    }
+
+    /// The purchase state of a product, as a signal
+    pub fn purchase_state(&self, product_id: &str) -> Signal<PurchaseState> {
+        self.inner
+            .states
+            .lock()
+            .unwrap()
+            .entry(product_id.to_string())
+            .or_insert_with(|| Signal::new(PurchaseState::Idle))
+            .clone()
+    }
+
+    /// Validated transactions the user is entitled to, as a signal
+    pub fn entitlements(&self) -> Signal<Vec<Transaction>> {
+        self.inner.entitlements.clone()
+    }
+
+    /// Whether the user owns a product or has an active subscription to it
+    pub fn is_entitled(&self, product_id: &str) -> bool {
+        self.inner.entitlements.get().iter().any(|t| t.product_id == product_id)
     }
 
     /// Purchase a product
+    ///
+    /// The product must have been fetched first. Progress is published
+    /// through [`Store::purchase_state`].
     pub async fn purchase(&self, product_id: &str) -> PurchaseResult {
-        #[cfg(target_os = "ios")]
-        {
-            self.purchase_ios(product_id).await
+        let state = self.purchase_state(product_id);
+        if self.product(product_id).is_none() {
+            state.set(PurchaseState::Failed(PurchaseError::ProductNotFound));
+            return PurchaseResult::Failed(PurchaseError::ProductNotFound);
         }
-        #[cfg(target_os = "android")]
-        {
-            self.purchase_android(product_id).await
+        if state.get().is_busy() {
+            return PurchaseResult::Failed(PurchaseError::PurchaseFailed("purchase already in progress".to_string()));
         }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            let _ = product_id;
-            PurchaseResult::Failed(PurchaseError::StoreNotAvailable)
+
+        state.set(PurchaseState::Purchasing);
+        let outcome = match request(|id| native::purchase(id, product_id)).await {
+            Ok(StoreResponse::Purchase(outcome)) => outcome,
+            Ok(_) => PurchaseOutcome::Failed(PurchaseError::Unknown("unexpected store response".to_string())),
+            Err(e) => PurchaseOutcome::Failed(e),
+        };
+
+        match outcome {
+            PurchaseOutcome::Completed(transaction) => self.process(transaction).await,
+            PurchaseOutcome::Pending => {
+                // Completes later through handle_transaction_update
+                state.set(PurchaseState::Pending);
+                PurchaseResult::Pending
+            }
+            PurchaseOutcome::Cancelled => {
+                state.set(PurchaseState::Cancelled);
+                PurchaseResult::Cancelled
+            }
+            PurchaseOutcome::Failed(e) => {
+                state.set(PurchaseState::Failed(e.clone()));
+                PurchaseResult::Failed(e)
+            }
         }
     }
 
     /// Restore previous purchases
+    ///
+    /// Restored transactions go through the validator like new ones; the
+    /// ones it accepts are returned.
     pub async fn restore_purchases(&self) -> Result<Vec<Transaction>, PurchaseError> {
-        #[cfg(target_os = "ios")]
-        {
-            self.restore_ios().await
-        }
-        #[cfg(target_os = "android")]
-        {
-            self.restore_android().await
-        }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            Err(PurchaseError::StoreNotAvailable)
+        let transactions = match request(native::restore).await? {
+            StoreResponse::Transactions(transactions) => transactions,
+            _ => return Err(PurchaseError::Unknown("unexpected store response".to_string())),
+        };
+
+        let mut restored = Vec::new();
+        for mut transaction in transactions {
+            transaction.is_restored = true;
+            if let PurchaseResult::Success(transaction) = self.process(transaction).await {
+                restored.push(transaction);
+            }
         }
+        Ok(restored)
     }
 
-    /// Get current entitlements
+    /// Get current entitlements from the store
+    ///
+    /// Replaces [`Store::entitlements`] with the store's answer, without
+    /// revalidating.
     pub async fn current_entitlements(&self) -> Result<Vec<Transaction>, PurchaseError> {
-        #[cfg(target_os = "ios")]
-        {
-            self.entitlements_ios().await
-        }
-        #[cfg(target_os = "android")]
-        {
-            self.entitlements_android().await
-        }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            Err(PurchaseError::StoreNotAvailable)
-        }
+        let transactions = match request(native::current_entitlements).await? {
+            StoreResponse::Transactions(transactions) => transactions,
+            _ => return Err(PurchaseError::Unknown("unexpected store response".to_string())),
+        };
+        self.inner.entitlements.set(transactions.clone());
+        Ok(transactions)
     }
 
-    /// Finish a transaction (acknowledge)
+    /// Finish a transaction (acknowledge, or consume for consumables)
+    ///
+    /// [`Store::purchase`] does this after validation; call it yourself
+    /// only for transactions from [`Store::current_entitlements`].
     pub async fn finish_transaction(&self, transaction: &Transaction) -> Result<(), PurchaseError> {
-        #[cfg(target_os = "ios")]
-        {
-            // Would call finish() on SKPaymentTransaction
-            let _ = transaction;
-            Ok(())
+        let consume = transaction.product_type == ProductType::Consumable;
+        match request(|id| native::finish(id, transaction, consume)).await? {
+            StoreResponse::Done => Ok(()),
+            _ => Err(PurchaseError::Unknown("unexpected store response".to_string())),
+        }
+    }
+
+    /// Listen for validated transactions, from purchases and from updates
+    /// delivered by the store
+    pub fn on_transaction<F: Fn(Transaction) + Send + Sync + 'static>(&self, callback: F) {
+        *self.inner.on_transaction.lock().unwrap() = Some(Arc::new(callback));
+    }
+
+    /// Validate, finish and grant a completed transaction
+    async fn process(&self, transaction: Transaction) -> PurchaseResult {
+        let state = self.purchase_state(&transaction.product_id);
+        let product_type = transaction.product_type;
+
+        let validator = self.inner.validator.lock().unwrap().clone();
+        let mut transaction = transaction;
+        if let Some(validator) = validator {
+            state.set(PurchaseState::Validating);
+            let receipt = ReceiptRequest {
+                platform: StorePlatform::current(),
+                product_type,
+                transaction: transaction.clone(),
+            };
+            match validator(receipt).await {
+                Ok(ReceiptValidation::Valid { expiration_date }) => {
+                    if expiration_date.is_some() {
+                        transaction.expiration_date = expiration_date;
+                    }
+                }
+                Ok(ReceiptValidation::Invalid(reason)) => {
+                    // Finished so the store stops redelivering it, but never granted
+                    let _ = self.finish_transaction(&transaction).await;
+                    let error = PurchaseError::ValidationFailed(reason);
+                    state.set(PurchaseState::Failed(error.clone()));
+                    return PurchaseResult::Failed(error);
+                }
+                Err(message) => {
+                    let error = PurchaseError::ValidationFailed(message);
+                    state.set(PurchaseState::Failed(error.clone()));
+                    return PurchaseResult::Failed(error);
+                }
+            }
         }
-        #[cfg(target_os = "android")]
-        {
-            // Would call acknowledgePurchase or consumePurchase
-            let _ = transaction;
-            Ok(())
+
+        if let Err(e) = self.finish_transaction(&transaction).await {
+            state.set(PurchaseState::Failed(e.clone()));
+            return PurchaseResult::Failed(e);
         }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            let _ = transaction;
-            Err(PurchaseError::StoreNotAvailable)
+
+        if product_type != ProductType::Consumable {
+            self.inner.entitlements.update(|entitlements| {
+                entitlements.retain(|t| t.original_id != transaction.original_id);
+                entitlements.push(transaction.clone());
+            });
+        }
+        state.set(PurchaseState::Purchased(transaction.clone()));
+
+        let callback = self.inner.on_transaction.lock().unwrap().clone();
+        if let Some(callback) = callback {
+            callback(transaction.clone());
         }
+        PurchaseResult::Success(transaction)
     }
+}
 
-    /// Listen for transaction updates
-    pub fn on_transaction<F: Fn(Transaction) + Send + Sync + 'static>(&mut self, callback: F) {
-        self.on_transaction = Some(Arc::new(callback));
+impl Default for Store {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    #[cfg(target_os = "ios")]
-    async fn fetch_products_ios(&mut self, product_ids: &[&str]) -> Result<Vec<Product>, PurchaseError> {
-        // Would use StoreKit 2 Product.products(for:)
-        let _ = product_ids;
-        Ok(Vec::new())
+impl StorePlatform {
+    fn current() -> Self {
+        if cfg!(target_os = "android") {
+            StorePlatform::PlayStore
+        } else {
+            StorePlatform::AppStore
+        }
     }
+}
 
-    #[cfg(target_os = "ios")]
-    async fn purchase_ios(&self, product_id: &str) -> PurchaseResult {
-        // Would use product.purchase()
-        let _ = product_id;
-        PurchaseResult::Cancelled
+// ============================================================================
+// Platform Callbacks
+// ============================================================================
+
+/// How the store sheet ended
+#[derive(Debug, Clone)]
+pub enum PurchaseOutcome {
+    Completed(Transaction),
+    Pending,
+    Cancelled,
+    Failed(PurchaseError),
+}
+
+/// Result of a store request, reported by the platform glue
+#[derive(Debug, Clone)]
+pub enum StoreResponse {
+    Products(Vec<Product>),
+    Purchase(PurchaseOutcome),
+    Transactions(Vec<Transaction>),
+    Done,
+}
+
+type Pending = oneshot::Sender<Result<StoreResponse, PurchaseError>>;
+
+static NEXT_REQUEST: AtomicU64 = AtomicU64::new(1);
+static PENDING: Mutex<Option<HashMap<u64, Pending>>> = Mutex::new(None);
+
+/// Start a native request and wait for [`complete_request`]
+async fn request(start: impl FnOnce(u64) -> Result<(), PurchaseError>) -> Result<StoreResponse, PurchaseError> {
+    let id = NEXT_REQUEST.fetch_add(1, Ordering::Relaxed);
+    let (tx, rx) = oneshot::channel();
+    PENDING.lock().unwrap().get_or_insert_with(HashMap::new).insert(id, tx);
+
+    if let Err(e) = start(id) {
+        complete_request(id, Err(e));
     }
+    rx.await.unwrap_or(Err(PurchaseError::StoreNotAvailable))
+}
 
-    #[cfg(target_os = "ios")]
-    async fn restore_ios(&self) -> Result<Vec<Transaction>, PurchaseError> {
-        // Would use AppStore.sync()
-        Ok(Vec::new())
+/// Resolve a store request
+///
+/// Called by the platform glue when a StoreKit 2 call returns on iOS, and
+/// from the BillingClient listeners (ProductDetailsResponseListener,
+/// PurchasesUpdatedListener, AcknowledgePurchaseResponseListener,
+/// ConsumeResponseListener) on Android.
+pub fn complete_request(id: u64, result: Result<StoreResponse, PurchaseError>) {
+    let tx = PENDING.lock().unwrap().as_mut().and_then(|pending| pending.remove(&id));
+    if let Some(tx) = tx {
+        let _ = tx.send(result);
     }
+}
 
-    #[cfg(target_os = "ios")]
-    async fn entitlements_ios(&self) -> Result<Vec<Transaction>, PurchaseError> {
-        // Would iterate Transaction.currentEntitlements
-        Ok(Vec::new())
+/// A transaction the store delivered outside a purchase call
+///
+/// Called by the platform glue from `Transaction.updates` on iOS, and on
+/// Android from PurchasesUpdatedListener when no purchase is in flight and
+/// for unacknowledged purchases found at startup.
+pub fn handle_transaction_update(transaction: Transaction) {
+    let store = CURRENT_STORE.lock().unwrap().as_ref().and_then(Weak::upgrade);
+    match store {
+        Some(inner) => {
+            let store = Store { inner };
+            crate::runtime::spawn_async(async move {
+                store.process(transaction).await;
+            });
+        }
+        None => QUEUED_TRANSACTIONS.lock().unwrap().push(transaction),
     }
+}
 
-    #[cfg(target_os = "android")]
-    async fn fetch_products_android(&mut self, product_ids: &[&str]) -> Result<Vec<Product>, PurchaseError> {
-        // Would use BillingClient.queryProductDetailsAsync
-        let _ = product_ids;
-        Ok(Vec::new())
+/// Native calls. Each reports back through [`complete_request`] with the id
+/// it was given.
+///
+/// None is wired up yet, so each fails right away instead of leaving its
+/// request waiting.
+mod native {
+    use super::{PurchaseError, Transaction};
+
+    pub fn observe_transactions() {
+        // iOS: Task { for await update in Transaction.updates {
+        //     handle_transaction_update(transaction from update.payloadValue, jwsRepresentation) } }
+        // Android: BillingClient.newBuilder(context).setListener(purchasesUpdatedListener)
+        //     .enablePendingPurchases().build().startConnection(...), then
+        //     queryPurchasesAsync and pass unacknowledged purchases to handle_transaction_update
     }
 
-    #[cfg(target_os = "android")]
-    async fn purchase_android(&self, product_id: &str) -> PurchaseResult {
-        // Would use BillingClient.launchBillingFlow
-        let _ = product_id;
-        PurchaseResult::Cancelled
+    pub fn fetch_products(request: u64, ids: &[String]) -> Result<(), PurchaseError> {
+        // iOS: would use StoreKit 2: let products = try await Product.products(for: ids)
+        //   mapping type, displayName, displayPrice, price * 100, subscription.subscriptionPeriod
+        // Android: would call BillingClient.queryProductDetailsAsync for INAPP and SUBS products,
+        //   mapping oneTimePurchaseOfferDetails / subscriptionOfferDetails pricing phases
+        let _ = (request, ids);
+        Err(PurchaseError::StoreNotAvailable)
     }
 
-    #[cfg(target_os = "android")]
-    async fn restore_android(&self) -> Result<Vec<Transaction>, PurchaseError> {
-        // Would use BillingClient.queryPurchasesAsync
-        Ok(Vec::new())
+    pub fn purchase(request: u64, product_id: &str) -> Result<(), PurchaseError> {
+        // iOS: would call product.purchase() and map the result:
+        //   .success(.verified(t)) -> Completed(t with receipt_data = jwsRepresentation)
+        //   .success(.unverified) -> Failed(PurchaseFailed), .pending -> Pending,
+        //   .userCancelled -> Cancelled
+        // Android: would call BillingClient.launchBillingFlow(activity, params); the
+        //   PurchasesUpdatedListener maps OK + PURCHASED -> Completed(purchaseToken),
+        //   OK + PENDING -> Pending, USER_CANCELED -> Cancelled, ITEM_ALREADY_OWNED -> AlreadyPurchased
+        let _ = (request, product_id);
+        Err(PurchaseError::StoreNotAvailable)
     }
 
-    #[cfg(target_os = "android")]
-    async fn entitlements_android(&self) -> Result<Vec<Transaction>, PurchaseError> {
-        // Would use BillingClient.queryPurchasesAsync
-        Ok(Vec::new())
+    pub fn restore(request: u64) -> Result<(), PurchaseError> {
+        // iOS: would call AppStore.sync(), then collect Transaction.currentEntitlements
+        // Android: would call queryPurchasesAsync for INAPP and SUBS
+        let _ = request;
+        Err(PurchaseError::StoreNotAvailable)
     }
-}
 
-impl Default for Store {
-    fn default() -> Self {
-        Self::new()
+    pub fn current_entitlements(request: u64) -> Result<(), PurchaseError> {
+        // iOS: would collect verified transactions from Transaction.currentEntitlements
+        // Android: would call queryPurchasesAsync, keeping PURCHASED ones
+        let _ = request;
+        Err(PurchaseError::StoreNotAvailable)
+    }
+
+    pub fn finish(request: u64, transaction: &Transaction, consume: bool) -> Result<(), PurchaseError> {
+        // iOS: would call await transaction.finish(); consumables need nothing more
+        // Android: would call consumeAsync(purchaseToken) when `consume`, otherwise
+        //   acknowledgePurchase(purchaseToken); unacknowledged purchases are refunded after 3 days
+        let _ = (request, transaction, consume);
+        Err(PurchaseError::StoreNotAvailable)
     }
 }
 
@@ -352,6 +687,8 @@ pub enum PurchaseError {
     AlreadyPurchased,
     /// Subscription expired
     SubscriptionExpired,
+    /// The receipt validator rejected the transaction or couldn't be reached
+    ValidationFailed(String),
     /// Unknown error
     Unknown(String),
 }
@@ -368,9 +705,58 @@ impl std::fmt::Display for PurchaseError {
             PurchaseError::NotAuthorized => write!(f, "Not authorized"),
             PurchaseError::AlreadyPurchased => write!(f, "Already purchased"),
             PurchaseError::SubscriptionExpired => write!(f, "Subscription expired"),
+            PurchaseError::ValidationFailed(e) => write!(f, "Receipt validation failed: {}", e),
             PurchaseError::Unknown(e) => write!(f, "Unknown error: {}", e),
         }
     }
 }
 
 impl std::error::Error for PurchaseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    fn transaction(product_id: &str, product_type: ProductType) -> Transaction {
+        Transaction {
+            id: "t1".to_string(),
+            original_id: "t1".to_string(),
+            product_id: product_id.to_string(),
+            product_type,
+            purchase_date: "2026-01-01T00:00:00Z".to_string(),
+            expiration_date: None,
+            is_restored: false,
+            receipt_data: None,
+        }
+    }
+
+    #[test]
+    fn test_store_calls_answer_without_glue() {
+        let store = Store::new();
+        assert!(matches!(block_on(store.fetch_products(&["coins"])), Err(PurchaseError::StoreNotAvailable)));
+        assert!(matches!(block_on(store.restore_purchases()), Err(PurchaseError::StoreNotAvailable)));
+        assert!(matches!(block_on(store.current_entitlements()), Err(PurchaseError::StoreNotAvailable)));
+        assert!(matches!(
+            block_on(store.finish_transaction(&transaction("coins", ProductType::Consumable))),
+            Err(PurchaseError::StoreNotAvailable)
+        ));
+    }
+
+    #[test]
+    fn test_unfetched_consumable_keeps_its_type() {
+        let store = Store::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = seen.clone();
+        store.validate_with(move |receipt: ReceiptRequest| {
+            log.lock().unwrap().push(receipt.product_type);
+            async { Ok::<_, String>(ReceiptValidation::Invalid("test".to_string())) }
+        });
+
+        let result = block_on(store.process(transaction("coins", ProductType::Consumable)));
+        assert!(matches!(result, PurchaseResult::Failed(PurchaseError::ValidationFailed(_))));
+        assert_eq!(*seen.lock().unwrap(), [ProductType::Consumable]);
+        assert!(!store.is_entitled("coins"));
+        assert!(!store.purchase_state("coins").get().is_busy());
+    }
+}
@@ -6,9 +6,15 @@
 
 use crate::{
     IntoView, MobileConfig, NativeView, RenderContext, Color, Rect, Size, Point,
-    renderer::{PlatformRenderer, TextStyle, ButtonConfig, TextInputConfig, ScrollConfig, ContainerStyle, ImageSource},
+    renderer::{PlatformRenderer, TextStyle, ButtonConfig, TextInputConfig, ScrollConfig, ContainerStyle, ImageSource, ListConfig},
+    navigation::NavigationTransition,
+    camera::CameraSession,
+    gestures::GestureArena,
+    animation::AnimatedStyle,
+    accessibility::Accessibility,
+    webview::{WebViewController, BRIDGE_SCRIPT},
 };
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 // ============================================================================
 // iOS Application Entry Point
@@ -36,6 +42,44 @@ where
     // Set up the window and root view controller
     // Start the run loop
 
+    // The app delegate forwards links to the installed navigator:
+    // application(_:open:options:) -> crate::navigation::handle_open_url(url)
+    // application(_:continue:restorationHandler:) with a web page activity
+    //     -> crate::navigation::handle_open_url(activity.webpageURL)
+    // The navigation controller's interactivePopGestureRecognizer, once it
+    // completes, calls crate::navigation::handle_back_gesture()
+
+    // Push notifications, with the delegate also set as the
+    // UNUserNotificationCenter delegate:
+    // didRegisterForRemoteNotificationsWithDeviceToken
+    //     -> crate::notifications::handle_apns_token(token.bytes)
+    // didFailToRegisterForRemoteNotificationsWithError
+    //     -> crate::notifications::handle_push_registration_failed(error.localizedDescription)
+    // userNotificationCenter(_:willPresent:) with userInfo as JSON
+    //     -> crate::notifications::handle_remote_notification(json, true)
+    // didReceiveRemoteNotification:fetchCompletionHandler:
+    //     -> crate::notifications::handle_remote_notification(json, false)
+    // userNotificationCenter(_:didReceive:) with response.actionIdentifier
+    //     -> crate::notifications::handle_notification_response(json, Some(action))
+
+    // Lifecycle, from the scene delegate:
+    // sceneDidBecomeActive -> crate::lifecycle::handle_app_state_change(Active)
+    // sceneWillResignActive -> handle_app_state_change(Inactive)
+    // sceneDidEnterBackground -> handle_app_state_change(Background)
+    // applicationWillTerminate -> handle_app_state_change(Suspended)
+    // In didFinishLaunching, for each BGTaskSchedulerPermittedIdentifiers entry:
+    // BGTaskScheduler.shared.register(forTaskWithIdentifier: id, using: nil) { task in
+    //     task.expirationHandler = { crate::lifecycle::handle_background_task_expired(id) }
+    //     keep `task` for setTaskCompleted, then crate::lifecycle::handle_background_task(id)
+    // }
+
+    // Appearance, from the root view controller:
+    // traitCollectionDidChange(_:) when userInterfaceStyle or
+    // preferredContentSizeCategory changed, and once at launch
+    //     -> crate::theme::handle_appearance_change(style == .dark)
+    //     -> crate::theme::handle_font_scale_change(
+    //            UIFontMetrics.default.scaledValue(for: 17) / 17)
+
     let _ = (app, config);
 }
 
@@ -150,6 +194,43 @@ impl PlatformRenderer for IOSRenderer {
         }
     }
 
+    fn create_list(&self, config: ListConfig) -> NativeView {
+        // Would create UITableView (or UICollectionView for horizontal lists)
+        // let tableView = UITableView::new(style: .plain);
+        // tableView.setSeparatorStyle(if config.separator { .singleLine } else { .none });
+        // tableView.setShowsVerticalScrollIndicator(config.show_indicators);
+        // if config.refresh_control {
+        //     tableView.setRefreshControl(UIRefreshControl::new());
+        // }
+
+        NativeView {
+            handle: Arc::new(format!("UITableView: {:?}", config.direction)),
+            children: Vec::new(),
+        }
+    }
+
+    fn create_list_cell(&self, list: &NativeView, reuse_id: &str) -> NativeView {
+        // Would dequeue from the table's reuse queue
+        // tableView.register(UITableViewCell.self, forCellReuseIdentifier: reuse_id);
+        // let cell = tableView.dequeueReusableCell(withIdentifier: reuse_id);
+        let _ = list;
+
+        NativeView {
+            handle: Arc::new(format!("UITableViewCell: {}", reuse_id)),
+            children: Vec::new(),
+        }
+    }
+
+    fn set_content_size(&self, view: &NativeView, size: Size) {
+        // Would call scrollView.setContentSize(CGSize::from(size))
+        let _ = (view, size);
+    }
+
+    fn set_refreshing(&self, list: &NativeView, refreshing: bool) {
+        // Would call refreshControl.beginRefreshing() / endRefreshing()
+        let _ = (list, refreshing);
+    }
+
     fn add_child(&self, parent: &NativeView, child: NativeView) {
         // Would call parent.addSubview(child)
         let _ = (parent, child);
@@ -165,6 +246,116 @@ impl PlatformRenderer for IOSRenderer {
         let _ = (view, frame);
     }
 
+    fn create_navigation_host(&self) -> NativeView {
+        // Would create UINavigationController and embed its view
+        // let nav = UINavigationController::new();
+        // nav.setNavigationBarHidden(false, animated: false);
+        // parentViewController.addChild(nav);
+
+        NativeView {
+            handle: Arc::new("UINavigationController"),
+            children: Vec::new(),
+        }
+    }
+
+    fn transition(
+        &self,
+        host: &NativeView,
+        from: Option<&NativeView>,
+        to: &NativeView,
+        transition: NavigationTransition,
+        reverse: bool,
+    ) {
+        // Would wrap `to` in a UIViewController and drive the controller:
+        // match (transition, reverse) {
+        //     (Push, false) => nav.pushViewController(vc, animated: true),
+        //     (Push, true) => nav.popToViewController(vc, animated: true),
+        //     (Modal, false) => nav.topViewController.present(vc, animated: true),
+        //     (Modal, true) => presented.dismiss(animated: true),
+        //     (Fade, _) => UIView.transition(with: nav.view, options: .transitionCrossDissolve) {
+        //         nav.setViewControllers(stack, animated: false)
+        //     },
+        //     (None | Custom, _) => nav.setViewControllers(stack, animated: false),
+        // }
+        let _ = (host, from, to, transition, reverse);
+    }
+
+    fn create_camera_preview(&self, session: &CameraSession) -> NativeView {
+        // Would host the session's preview layer in a UIView
+        // let layer = AVCaptureVideoPreviewLayer(session: sessions[session.id()])
+        // layer.videoGravity = .resizeAspectFill
+        // layer.connection.isVideoMirrored = front && config.mirror_front
+        // view.layer.addSublayer(layer)
+        let _ = session;
+
+        NativeView {
+            handle: Arc::new("AVCaptureVideoPreviewLayer"),
+            children: Vec::new(),
+        }
+    }
+
+    fn create_web_view(&self, web_view: &WebViewController) -> NativeView {
+        // Would build a WKWebView with the bridge installed:
+        // let config = WKWebViewConfiguration()
+        // config.userContentController.addUserScript(WKUserScript(
+        //     source: BRIDGE_SCRIPT, injectionTime: .atDocumentStart, forMainFrameOnly: true))
+        // config.userContentController.add(handler, name: "philjs")
+        //     -> crate::webview::handle_webview_message(id, message.body as String)
+        // navigationDelegate.decidePolicyFor(navigationAction)
+        //     -> crate::webview::handle_navigation_request(id, request) == Allow ? .allow : .cancel
+        // didStartProvisionalNavigation / didFinish / didFail
+        //     -> crate::webview::handle_load_event(id, event)
+        // then load web_view.source() with load(URLRequest) or loadHTMLString
+        let _ = (web_view.id(), BRIDGE_SCRIPT);
+
+        NativeView {
+            handle: Arc::new("WKWebView"),
+            children: Vec::new(),
+        }
+    }
+
+    fn attach_gestures(&self, view: &NativeView, arena: Arc<Mutex<GestureArena>>) {
+        // Would add a UIGestureRecognizer subclass that forwards raw touches:
+        // touchesBegan/Moved/Ended/Cancelled build a TouchEvent from event.allTouches,
+        //     with timestamp event.timestamp, and call arena.lock().handle_touch(&event)
+        // A CADisplayLink calls arena.lock().tick(now) while arena.needs_tick()
+        // cancelsTouchesInView follows arena.is_active() so buttons underneath
+        // don't fire once a gesture has begun
+        let _ = (view, arena);
+    }
+
+    fn apply_animated_style(&self, view: &NativeView, style: &AnimatedStyle) {
+        // Would set layer properties with implicit animations disabled, since
+        // the frame driver already interpolates:
+        // CATransaction.begin(); CATransaction.setDisableActions(true)
+        // if let Some(opacity) = style.opacity { view.alpha = opacity }
+        // if let Some(t) = style.transform {
+        //     view.transform = CGAffineTransform(translationX: t.translate_x, y: t.translate_y)
+        //         .rotated(by: t.rotation).scaledBy(x: t.scale_x, y: t.scale_y)
+        // }
+        // if let Some(color) = style.background { view.backgroundColor = UIColor(color) }
+        // if let Some(radius) = style.corner_radius { view.layer.cornerRadius = radius }
+        // if let Some(padding) = style.padding { view.layoutMargins = UIEdgeInsets(padding) }
+        // CATransaction.commit()
+        let _ = (view, style);
+    }
+
+    fn set_accessibility(&self, view: &NativeView, props: &Accessibility) {
+        // Would map onto UIAccessibility properties:
+        // view.isAccessibilityElement = props.label.is_some() && !props.hidden
+        // view.accessibilityElementsHidden = props.hidden
+        // view.accessibilityLabel / accessibilityHint / accessibilityValue = props.*
+        // view.accessibilityTraits = role (Button -> .button, Link -> .link,
+        //     Header -> .header, Image -> .image, Text -> .staticText,
+        //     SearchField -> .searchField, Adjustable -> .adjustable, Tab -> .tabBar on
+        //     the container) | traits (SELECTED -> .selected, DISABLED -> .notEnabled,
+        //     UPDATES_FREQUENTLY -> .updatesFrequently, DIRECT_INTERACTION -> .allowsDirectInteraction)
+        // CHECKED/EXPANDED/COLLAPSED/BUSY have no trait; append them to accessibilityValue
+        // iOS has no live regions: when live_region != Off and the label or value
+        // differs from the last call, UIAccessibility.post(.announcement, label)
+        let _ = (view, props);
+    }
+
     fn measure_text(&self, text: &str, style: &TextStyle, max_width: f32) -> Size {
         // Would use NSAttributedString.boundingRect
         // let attributes = [.font: UIFont.systemFont(ofSize: style.font_size)]
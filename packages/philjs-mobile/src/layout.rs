@@ -0,0 +1,543 @@
+//! PhilJS Mobile Layout
+//!
+//! Flexbox layout over [`Constraints`]. Stacks and containers compute child
+//! frames here rather than in the platform renderers, so iOS and Android
+//! place views identically.
+//!
+//! Each child is measured at its natural size, free space along the main
+//! axis is handed out by `grow` (or taken back by `shrink`), and children
+//! are then aligned on the cross axis. With [`FlexStyle::wrap`], children
+//! that don't fit start a new line; each line grows, shrinks, and aligns on
+//! its own, and lines stack along the cross axis `gap` apart.
+
+use crate::{Constraints, EdgeInsets, NativeComponent, Rect, Size};
+use std::ops::Range;
+
+/// Main axis of a flex container
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlexDirection {
+    Row,
+    #[default]
+    Column,
+}
+
+/// Distribution of leftover space along the main axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JustifyContent {
+    #[default]
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+/// Placement of children on the cross axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlignItems {
+    #[default]
+    Start,
+    Center,
+    End,
+    Stretch,
+}
+
+/// Flex container style
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlexStyle {
+    pub direction: FlexDirection,
+    pub justify: JustifyContent,
+    pub align: AlignItems,
+    pub gap: f32,
+    pub padding: EdgeInsets,
+    /// Start a new line when the next child doesn't fit
+    pub wrap: bool,
+}
+
+impl FlexStyle {
+    pub fn row() -> Self {
+        FlexStyle {
+            direction: FlexDirection::Row,
+            ..Default::default()
+        }
+    }
+
+    pub fn column() -> Self {
+        FlexStyle {
+            direction: FlexDirection::Column,
+            ..Default::default()
+        }
+    }
+
+    pub fn justify(mut self, justify: JustifyContent) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    pub fn align(mut self, align: AlignItems) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn padding(mut self, padding: EdgeInsets) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+}
+
+/// How a child takes part in its parent's flex layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlexItem {
+    /// Share of free space taken when the container has room to spare
+    pub grow: f32,
+    /// Share of the overflow given up, weighted by basis, when space is short
+    pub shrink: f32,
+    /// Main axis size before growing or shrinking; measured when `None`
+    pub basis: Option<f32>,
+    /// Overrides the container's `align`
+    pub align_self: Option<AlignItems>,
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        FlexItem {
+            grow: 0.0,
+            shrink: 1.0,
+            basis: None,
+            align_self: None,
+        }
+    }
+}
+
+impl FlexItem {
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow.max(0.0);
+        self
+    }
+
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink.max(0.0);
+        self
+    }
+
+    pub fn basis(mut self, basis: f32) -> Self {
+        self.basis = Some(basis.max(0.0));
+        self
+    }
+
+    pub fn align_self(mut self, align: AlignItems) -> Self {
+        self.align_self = Some(align);
+        self
+    }
+}
+
+/// Result of a layout pass
+#[derive(Debug, Clone, Default)]
+pub struct Layout {
+    /// Size of the container
+    pub size: Size,
+    /// Frame of each child, relative to the container
+    pub frames: Vec<Rect>,
+}
+
+/// Main/cross axis view of a size, so the algorithm is written once
+#[derive(Debug, Clone, Copy)]
+struct Axes {
+    main: f32,
+    cross: f32,
+}
+
+impl FlexDirection {
+    fn axes(self, size: Size) -> Axes {
+        match self {
+            FlexDirection::Row => Axes { main: size.width, cross: size.height },
+            FlexDirection::Column => Axes { main: size.height, cross: size.width },
+        }
+    }
+
+    fn size(self, main: f32, cross: f32) -> Size {
+        match self {
+            FlexDirection::Row => Size::new(main, cross),
+            FlexDirection::Column => Size::new(cross, main),
+        }
+    }
+
+    fn rect(self, main: f32, cross: f32, main_size: f32, cross_size: f32) -> Rect {
+        match self {
+            FlexDirection::Row => Rect::new(main, cross, main_size, cross_size),
+            FlexDirection::Column => Rect::new(cross, main, cross_size, main_size),
+        }
+    }
+
+    fn constraints(self, min_main: f32, max_main: f32, min_cross: f32, max_cross: f32) -> Constraints {
+        match self {
+            FlexDirection::Row => Constraints {
+                min_width: min_main,
+                max_width: max_main,
+                min_height: min_cross,
+                max_height: max_cross,
+            },
+            FlexDirection::Column => Constraints {
+                min_width: min_cross,
+                max_width: max_cross,
+                min_height: min_main,
+                max_height: max_main,
+            },
+        }
+    }
+}
+
+/// Lay out children along one axis
+pub fn flex_layout(style: &FlexStyle, children: &[Box<dyn NativeComponent>], constraints: Constraints) -> Layout {
+    let dir = style.direction;
+    let padding = dir.axes(Size::new(
+        style.padding.left + style.padding.right,
+        style.padding.top + style.padding.bottom,
+    ));
+    let (lead_main, lead_cross) = match dir {
+        FlexDirection::Row => (style.padding.left, style.padding.top),
+        FlexDirection::Column => (style.padding.top, style.padding.left),
+    };
+
+    let max = dir.axes(Size::new(constraints.max_width, constraints.max_height));
+    let min = dir.axes(Size::new(constraints.min_width, constraints.min_height));
+    let avail_main = (max.main - padding.main).max(0.0);
+    let avail_cross = (max.cross - padding.cross).max(0.0);
+
+    let items: Vec<FlexItem> = children.iter().map(|child| child.flex()).collect();
+
+    // Natural main size of each child
+    let bases: Vec<f32> = children
+        .iter()
+        .zip(&items)
+        .map(|(child, item)| match item.basis {
+            Some(basis) => basis,
+            None => dir.axes(child.measure(dir.constraints(0.0, f32::INFINITY, 0.0, avail_cross))).main,
+        })
+        .collect();
+
+    let lines = break_lines(style, &bases, avail_main);
+
+    // Grow into free space or shrink out of overflow, line by line
+    let mut mains = bases.clone();
+    if avail_main.is_finite() {
+        for line in &lines {
+            let gaps = style.gap * line.len().saturating_sub(1) as f32;
+            let free = avail_main - bases[line.clone()].iter().sum::<f32>() - gaps;
+            let total_grow: f32 = items[line.clone()].iter().map(|item| item.grow).sum();
+            let total_shrink: f32 = items[line.clone()]
+                .iter()
+                .zip(&bases[line.clone()])
+                .map(|(item, basis)| item.shrink * basis)
+                .sum();
+
+            for i in line.clone() {
+                if free > 0.0 && total_grow > 0.0 {
+                    mains[i] += free * items[i].grow / total_grow;
+                } else if free < 0.0 && total_shrink > 0.0 {
+                    mains[i] = (mains[i] + free * items[i].shrink * bases[i] / total_shrink).max(0.0);
+                }
+            }
+        }
+    }
+
+    // Cross size of each child at its final main size
+    let aligns: Vec<AlignItems> = items.iter().map(|item| item.align_self.unwrap_or(style.align)).collect();
+    let mut crosses: Vec<f32> = children
+        .iter()
+        .zip(&mains)
+        .map(|(child, &main)| dir.axes(child.measure(dir.constraints(main, main, 0.0, avail_cross))).cross)
+        .collect();
+
+    let line_main = |line: &Range<usize>| {
+        mains[line.clone()].iter().sum::<f32>() + style.gap * line.len().saturating_sub(1) as f32
+    };
+    let mut line_crosses: Vec<f32> = lines
+        .iter()
+        .map(|line| crosses[line.clone()].iter().copied().fold(0.0, f32::max))
+        .collect();
+    let used_main = lines.iter().map(line_main).fold(0.0, f32::max);
+    let content_cross = line_crosses.iter().sum::<f32>() + style.gap * lines.len().saturating_sub(1) as f32;
+
+    // Containers hug their content unless a child grows or space is justified
+    let fills_main = items.iter().any(|item| item.grow > 0.0) || style.justify != JustifyContent::Start;
+    let inner_main = if fills_main && avail_main.is_finite() { avail_main } else { used_main };
+    let inner_cross = if style.align == AlignItems::Stretch && avail_cross.is_finite() && lines.len() == 1 {
+        avail_cross
+    } else {
+        content_cross
+    };
+    let main_size = (inner_main + padding.main).clamp(min.main, max.main.max(min.main));
+    let cross_size = (inner_cross + padding.cross).clamp(min.cross, max.cross.max(min.cross));
+    let inner_main = main_size - padding.main;
+    let inner_cross = cross_size - padding.cross;
+
+    // A single line spans the container's cross axis
+    if lines.len() == 1 {
+        line_crosses[0] = inner_cross;
+    }
+    for (line, &line_cross) in lines.iter().zip(&line_crosses) {
+        for i in line.clone() {
+            if aligns[i] == AlignItems::Stretch {
+                crosses[i] = line_cross;
+            }
+        }
+    }
+
+    // Place children
+    let mut frames = Vec::with_capacity(children.len());
+    let mut line_offset = 0.0;
+    for (line, &line_cross) in lines.iter().zip(&line_crosses) {
+        let count = line.len() as f32;
+        let leftover = (inner_main - line_main(line)).max(0.0);
+        let (mut cursor, between) = match style.justify {
+            JustifyContent::Start => (0.0, 0.0),
+            JustifyContent::Center => (leftover / 2.0, 0.0),
+            JustifyContent::End => (leftover, 0.0),
+            JustifyContent::SpaceBetween if line.len() > 1 => (0.0, leftover / (count - 1.0)),
+            JustifyContent::SpaceBetween => (0.0, 0.0),
+            JustifyContent::SpaceAround => (leftover / count / 2.0, leftover / count),
+            JustifyContent::SpaceEvenly => (leftover / (count + 1.0), leftover / (count + 1.0)),
+        };
+
+        for i in line.clone() {
+            let offset = match aligns[i] {
+                AlignItems::Start | AlignItems::Stretch => 0.0,
+                AlignItems::Center => (line_cross - crosses[i]) / 2.0,
+                AlignItems::End => line_cross - crosses[i],
+            };
+            frames.push(dir.rect(lead_main + cursor, lead_cross + line_offset + offset, mains[i], crosses[i]));
+            cursor += mains[i] + style.gap + between;
+        }
+        line_offset += line_cross + style.gap;
+    }
+
+    Layout {
+        size: dir.size(main_size, cross_size),
+        frames,
+    }
+}
+
+/// Split children into lines of at most `avail_main`
+///
+/// Without wrapping everything is one line; a child wider than the whole
+/// line still gets a line to itself.
+fn break_lines(style: &FlexStyle, bases: &[f32], avail_main: f32) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    if style.wrap && avail_main.is_finite() {
+        let mut used = 0.0;
+        for (i, &basis) in bases.iter().enumerate() {
+            let needed = if i > start { used + style.gap + basis } else { basis };
+            if i > start && needed > avail_main {
+                lines.push(start..i);
+                start = i;
+                used = basis;
+            } else {
+                used = needed;
+            }
+        }
+    }
+    lines.push(start..bases.len());
+    lines
+}
+
+/// Lay out children on top of each other, aligned within the largest
+pub fn overlay_layout(
+    horizontal: AlignItems,
+    vertical: AlignItems,
+    children: &[Box<dyn NativeComponent>],
+    constraints: Constraints,
+) -> Layout {
+    let sizes: Vec<Size> = children
+        .iter()
+        .map(|child| child.measure(Constraints::loose(Size::new(constraints.max_width, constraints.max_height))))
+        .collect();
+
+    let width = sizes.iter().map(|size| size.width).fold(0.0, f32::max);
+    let height = sizes.iter().map(|size| size.height).fold(0.0, f32::max);
+    let size = Size::new(
+        width.clamp(constraints.min_width, constraints.max_width.max(constraints.min_width)),
+        height.clamp(constraints.min_height, constraints.max_height.max(constraints.min_height)),
+    );
+
+    let place = |align: AlignItems, outer: f32, inner: f32| match align {
+        AlignItems::Start | AlignItems::Stretch => 0.0,
+        AlignItems::Center => (outer - inner) / 2.0,
+        AlignItems::End => outer - inner,
+    };
+
+    let frames = sizes
+        .iter()
+        .map(|child| {
+            let w = if horizontal == AlignItems::Stretch { size.width } else { child.width };
+            let h = if vertical == AlignItems::Stretch { size.height } else { child.height };
+            Rect::new(place(horizontal, size.width, w), place(vertical, size.height, h), w, h)
+        })
+        .collect();
+
+    Layout { size, frames }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NativeView, RenderContext};
+
+    /// Fixed-size leaf that fills a tight main axis
+    struct Leaf {
+        size: Size,
+        flex: FlexItem,
+    }
+
+    impl NativeComponent for Leaf {
+        fn render(&self, _ctx: &mut RenderContext) -> NativeView {
+            unreachable!("layout tests only measure")
+        }
+
+        fn update(&self, _ctx: &mut RenderContext) {}
+
+        fn measure(&self, constraints: Constraints) -> Size {
+            Size::new(
+                self.size.width.clamp(constraints.min_width, constraints.max_width),
+                self.size.height.clamp(constraints.min_height, constraints.max_height),
+            )
+        }
+
+        fn flex(&self) -> FlexItem {
+            self.flex
+        }
+    }
+
+    fn leaf(width: f32, height: f32, flex: FlexItem) -> Box<dyn NativeComponent> {
+        Box::new(Leaf {
+            size: Size::new(width, height),
+            flex,
+        })
+    }
+
+    fn frames(layout: &Layout) -> Vec<(f32, f32, f32, f32)> {
+        layout
+            .frames
+            .iter()
+            .map(|r| (r.origin.x, r.origin.y, r.size.width, r.size.height))
+            .collect()
+    }
+
+    fn bounds(width: f32, height: f32) -> Constraints {
+        Constraints::loose(Size::new(width, height))
+    }
+
+    #[test]
+    fn test_grow_shares_free_space() {
+        let children = vec![
+            leaf(50.0, 10.0, FlexItem::default().grow(1.0)),
+            leaf(50.0, 10.0, FlexItem::default().grow(3.0)),
+            leaf(100.0, 10.0, FlexItem::default()),
+        ];
+        let layout = flex_layout(&FlexStyle::row(), &children, bounds(300.0, 100.0));
+
+        assert_eq!(layout.size.width, 300.0);
+        assert_eq!(layout.size.height, 10.0);
+        assert_eq!(
+            frames(&layout),
+            vec![(0.0, 0.0, 75.0, 10.0), (75.0, 0.0, 125.0, 10.0), (200.0, 0.0, 100.0, 10.0)]
+        );
+    }
+
+    #[test]
+    fn test_shrink_weighted_by_basis() {
+        let children = vec![leaf(120.0, 10.0, FlexItem::default()), leaf(60.0, 10.0, FlexItem::default())];
+        let layout = flex_layout(&FlexStyle::row(), &children, bounds(120.0, 100.0));
+        assert_eq!(frames(&layout), vec![(0.0, 0.0, 80.0, 10.0), (80.0, 0.0, 40.0, 10.0)]);
+
+        // A child that won't shrink keeps its basis; the rest give up the overflow
+        let children = vec![
+            leaf(60.0, 10.0, FlexItem::default().shrink(0.0)),
+            leaf(80.0, 10.0, FlexItem::default()),
+        ];
+        let layout = flex_layout(&FlexStyle::row(), &children, bounds(100.0, 100.0));
+        assert_eq!(frames(&layout), vec![(0.0, 0.0, 60.0, 10.0), (60.0, 0.0, 40.0, 10.0)]);
+    }
+
+    #[test]
+    fn test_justify_with_gap_and_padding() {
+        let children = vec![leaf(50.0, 10.0, FlexItem::default()), leaf(50.0, 10.0, FlexItem::default())];
+
+        let style = FlexStyle::row().gap(10.0).justify(JustifyContent::Center);
+        let layout = flex_layout(&style, &children, bounds(300.0, 100.0));
+        assert_eq!(frames(&layout), vec![(95.0, 0.0, 50.0, 10.0), (155.0, 0.0, 50.0, 10.0)]);
+
+        // Without growth or justification the container hugs its content
+        let style = FlexStyle::column().gap(10.0).padding(EdgeInsets::all(5.0));
+        let layout = flex_layout(&style, &children, bounds(300.0, 300.0));
+        assert_eq!((layout.size.width, layout.size.height), (60.0, 40.0));
+        assert_eq!(frames(&layout), vec![(5.0, 5.0, 50.0, 10.0), (5.0, 25.0, 50.0, 10.0)]);
+    }
+
+    #[test]
+    fn test_cross_axis_alignment() {
+        let children = vec![leaf(10.0, 20.0, FlexItem::default()), leaf(10.0, 40.0, FlexItem::default())];
+
+        let layout = flex_layout(&FlexStyle::row().align(AlignItems::Center), &children, bounds(100.0, 100.0));
+        assert_eq!(layout.size.height, 40.0);
+        assert_eq!(frames(&layout), vec![(0.0, 10.0, 10.0, 20.0), (10.0, 0.0, 10.0, 40.0)]);
+
+        let layout = flex_layout(&FlexStyle::row().align(AlignItems::End), &children, bounds(100.0, 100.0));
+        assert_eq!(frames(&layout)[0], (0.0, 20.0, 10.0, 20.0));
+
+        // Stretch fills the container; align_self opts a child out
+        let children = vec![
+            leaf(10.0, 20.0, FlexItem::default()),
+            leaf(10.0, 20.0, FlexItem::default().align_self(AlignItems::End)),
+        ];
+        let layout = flex_layout(&FlexStyle::row().align(AlignItems::Stretch), &children, bounds(100.0, 100.0));
+        assert_eq!(layout.size.height, 100.0);
+        assert_eq!(frames(&layout), vec![(0.0, 0.0, 10.0, 100.0), (10.0, 80.0, 10.0, 20.0)]);
+    }
+
+    #[test]
+    fn test_wrap_breaks_lines() {
+        let children = vec![
+            leaf(40.0, 20.0, FlexItem::default()),
+            leaf(40.0, 30.0, FlexItem::default()),
+            leaf(40.0, 20.0, FlexItem::default()),
+        ];
+
+        let layout = flex_layout(&FlexStyle::row().gap(10.0).wrap(true), &children, bounds(100.0, 200.0));
+        assert_eq!((layout.size.width, layout.size.height), (90.0, 60.0));
+        assert_eq!(
+            frames(&layout),
+            vec![(0.0, 0.0, 40.0, 20.0), (50.0, 0.0, 40.0, 30.0), (0.0, 40.0, 40.0, 20.0)]
+        );
+
+        // Without wrap the same children shrink onto one line
+        let layout = flex_layout(&FlexStyle::row().gap(10.0), &children, bounds(100.0, 200.0));
+        assert_eq!(layout.size.height, 30.0);
+        assert!(layout.frames.iter().all(|frame| frame.origin.y == 0.0));
+        assert!((layout.frames[2].origin.x + layout.frames[2].size.width - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_wrap_oversized_child_gets_own_line() {
+        let children = vec![
+            leaf(150.0, 10.0, FlexItem::default()),
+            leaf(30.0, 10.0, FlexItem::default().grow(1.0)),
+        ];
+        let layout = flex_layout(&FlexStyle::row().wrap(true), &children, bounds(100.0, 100.0));
+
+        // The wide child shrinks to the line; growth stays within each line
+        assert_eq!(frames(&layout), vec![(0.0, 0.0, 100.0, 10.0), (0.0, 10.0, 100.0, 10.0)]);
+    }
+}
@@ -54,16 +54,21 @@
 
 pub mod prelude;
 pub mod runtime;
+pub mod lifecycle;
 pub mod renderer;
 pub mod components;
+pub mod layout;
 pub mod platform;
 pub mod navigation;
 pub mod gestures;
 pub mod animation;
+pub mod accessibility;
+pub mod theme;
 pub mod storage;
 pub mod permissions;
 pub mod notifications;
 pub mod camera;
+pub mod webview;
 pub mod location;
 pub mod sensors;
 pub mod haptics;
@@ -71,6 +76,9 @@ pub mod share;
 pub mod biometrics;
 pub mod in_app_purchase;
 
+#[cfg(feature = "hot-reload")]
+pub mod hot_reload;
+
 #[cfg(target_os = "ios")]
 pub mod ios;
 
@@ -81,16 +89,51 @@ use std::sync::Arc;
 
 // Re-exports
 pub use runtime::{MobileApp, MobileConfig, run, run_with_config};
+pub use lifecycle::{
+    app_state, current_app_state, register_background_task, schedule_background_task, cancel_background_task,
+    BackgroundTask, BackgroundTaskRequest, BackgroundTaskKind, BackgroundTaskError,
+};
 pub use renderer::{NativeRenderer, RenderContext};
 pub use components::*;
+pub use layout::{FlexDirection, FlexItem, FlexStyle, JustifyContent, AlignItems, Layout};
 pub use platform::{Platform, PlatformInfo, DeviceInfo};
-pub use navigation::{Navigator, Route, NavigationStack};
-pub use gestures::{GestureRecognizer, Gesture, GestureState};
-pub use animation::{AnimatedValue, SpringAnimation, TimingAnimation};
-pub use storage::{SecureStorage, AsyncStorage, FileSystem};
+pub use navigation::{Navigator, Route, NavigationStack, NavigationHost, NavigationTransition, DeepLinks, RouteParams, RouteError};
+pub use gestures::{
+    GestureRecognizer, Gesture, GestureState, GestureDetector, GestureArena, Recognize, TouchEvent, TouchPhase, Touch,
+};
+pub use animation::{
+    Animated, AnimatedValue, Animatable, Animation, SpringAnimation, TimingAnimation, Transform, AnimatedStyle,
+    AnimatedView, use_animated_style,
+};
+pub use accessibility::{
+    Accessibility, AccessibilityExt, AccessibilityRole, AccessibilityTraits, Accessible, LiveRegion,
+    AccessibilityIssue, announce,
+};
+pub use theme::{
+    Theme, ThemeState, Palette, TypeScale, Typography, Font, Appearance, ColorSchemePreference, set_theme,
+    set_color_scheme,
+};
+pub use storage::{
+    SecureStorage, AsyncStorage, FileSystem, StorageBackend, MemoryBackend, StorageMigration, StorageError,
+};
+#[cfg(feature = "sqlite")]
+pub use storage::SqliteBackend;
 pub use permissions::{Permission, PermissionStatus, request_permission};
-pub use notifications::{LocalNotification, PushNotification, NotificationHandler};
+pub use notifications::{
+    LocalNotification, PushNotification, PushToken, NotificationHandler, NotificationResponse, NotificationError,
+    register_for_push,
+};
 pub use haptics::{HapticFeedback, HapticStyle};
+pub use in_app_purchase::{
+    Store, Product, PurchaseState, PurchaseResult, PurchaseError, ReceiptRequest, ReceiptValidation,
+};
+pub use webview::{
+    WebView, WebViewController, WebViewSource, WebMessage, NavigationRequest, NavigationDecision, LoadEvent,
+    WebViewError,
+};
+pub use camera::{
+    Camera, CameraConfig, CameraSession, CameraView, CameraError, ImagePicker, MediaFile, Photo, Video, PickedMedia,
+};
 
 /// Application entry point
 pub fn run<F, V>(app: F)
@@ -121,6 +164,11 @@ pub trait NativeComponent {
     fn render(&self, ctx: &mut RenderContext) -> NativeView;
     fn update(&self, ctx: &mut RenderContext);
     fn measure(&self, constraints: Constraints) -> Size;
+
+    /// How this component sizes itself inside a stack
+    fn flex(&self) -> FlexItem {
+        FlexItem::default()
+    }
 }
 
 /// A native view handle
@@ -186,7 +234,7 @@ impl Size {
 }
 
 /// Point in logical pixels
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -223,7 +271,7 @@ impl Rect {
 }
 
 /// Color representation
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -257,7 +305,7 @@ impl Color {
 }
 
 /// Edge insets
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct EdgeInsets {
     pub top: f32,
     pub right: f32,
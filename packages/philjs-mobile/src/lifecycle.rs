@@ -0,0 +1,401 @@
+//! PhilJS Mobile Lifecycle
+//!
+//! App state transitions and background work.
+//!
+//! The platform glue reports transitions through [`handle_app_state_change`]
+//! and launches scheduled work through [`handle_background_task`]. Whenever
+//! the app moves to `Background` or `Suspended`, queued [`AsyncStorage`]
+//! writes are flushed before the call returns, so the OS can suspend or kill
+//! the process without losing them.
+
+use crate::runtime::Signal;
+use crate::storage::AsyncStorage;
+use crate::AppState;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// Longest the app will block on storage when leaving the foreground; iOS
+/// allows about five seconds in `sceneDidEnterBackground`
+pub const FLUSH_TIMEOUT: Duration = Duration::from_secs(3);
+
+// ============================================================================
+// App State
+// ============================================================================
+
+/// The app's lifecycle state as a signal
+///
+/// Subscribers run on the thread that reported the transition, before
+/// storage is flushed, so writes they queue are flushed too.
+///
+/// ```rust,ignore
+/// app_state().subscribe(|state| {
+///     if *state == AppState::Background {
+///         drafts.save();
+///     }
+/// });
+/// ```
+pub fn app_state() -> Signal<AppState> {
+    static STATE: OnceLock<Signal<AppState>> = OnceLock::new();
+    STATE.get_or_init(|| Signal::new(AppState::Active)).clone()
+}
+
+/// Current lifecycle state
+pub fn current_app_state() -> AppState {
+    app_state().get()
+}
+
+/// Report a lifecycle transition (called by the platform glue)
+pub fn handle_app_state_change(state: AppState) {
+    let signal = app_state();
+    if signal.get() == state {
+        return;
+    }
+    signal.set(state);
+
+    if matches!(state, AppState::Background | AppState::Suspended) {
+        flush_storage();
+    }
+}
+
+/// Flush every open store, holding off suspension while it runs
+fn flush_storage() {
+    // iOS would bracket this so the flush isn't cut short:
+    // let id = UIApplication.shared.beginBackgroundTask(withName: "philjs-flush")
+    // ... UIApplication.shared.endBackgroundTask(id)
+    if !AsyncStorage::flush_all(FLUSH_TIMEOUT) {
+        eprintln!("PhilJS Mobile: storage flush did not finish within {:?}", FLUSH_TIMEOUT);
+    }
+}
+
+// ============================================================================
+// Background Tasks
+// ============================================================================
+
+/// How the OS should treat a background task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundTaskKind {
+    /// Short, frequent work such as fetching new content
+    /// (`BGAppRefreshTask` / periodic WorkManager request)
+    Refresh,
+    /// Longer maintenance, run when the device is idle
+    /// (`BGProcessingTask` / one-time WorkManager request)
+    Processing,
+}
+
+/// A request for the OS to run a registered task later
+#[derive(Debug, Clone)]
+pub struct BackgroundTaskRequest {
+    /// Identifier passed to [`register_background_task`]; on iOS it must also
+    /// be listed under `BGTaskSchedulerPermittedIdentifiers` in Info.plist
+    pub identifier: String,
+    pub kind: BackgroundTaskKind,
+    /// Don't start before this much time has passed
+    pub earliest_begin: Option<Duration>,
+    /// Run again this long after each run
+    pub repeat_interval: Option<Duration>,
+    pub requires_network: bool,
+    pub requires_charging: bool,
+}
+
+impl BackgroundTaskRequest {
+    pub fn refresh(identifier: impl Into<String>) -> Self {
+        Self::new(identifier.into(), BackgroundTaskKind::Refresh)
+    }
+
+    pub fn processing(identifier: impl Into<String>) -> Self {
+        Self::new(identifier.into(), BackgroundTaskKind::Processing)
+    }
+
+    fn new(identifier: String, kind: BackgroundTaskKind) -> Self {
+        BackgroundTaskRequest {
+            identifier,
+            kind,
+            earliest_begin: None,
+            repeat_interval: None,
+            requires_network: false,
+            requires_charging: false,
+        }
+    }
+
+    pub fn earliest_begin(mut self, delay: Duration) -> Self {
+        self.earliest_begin = Some(delay);
+        self
+    }
+
+    /// Repeat the task; Android enforces a minimum of 15 minutes
+    pub fn repeat_every(mut self, interval: Duration) -> Self {
+        self.repeat_interval = Some(interval);
+        self
+    }
+
+    pub fn requires_network(mut self) -> Self {
+        self.requires_network = true;
+        self
+    }
+
+    pub fn requires_charging(mut self) -> Self {
+        self.requires_charging = true;
+        self
+    }
+}
+
+/// A running background task, passed to its handler
+#[derive(Clone)]
+pub struct BackgroundTask {
+    identifier: String,
+    expired: Arc<AtomicBool>,
+}
+
+impl BackgroundTask {
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// Whether the OS has asked the task to stop
+    ///
+    /// Long handlers should check this between steps and return early;
+    /// the OS kills the process soon after.
+    pub fn is_expired(&self) -> bool {
+        self.expired.load(Ordering::SeqCst)
+    }
+}
+
+type TaskHandler = Arc<dyn Fn(BackgroundTask) -> BoxFuture<'static, bool> + Send + Sync>;
+
+static HANDLERS: Mutex<Option<HashMap<String, TaskHandler>>> = Mutex::new(None);
+static SCHEDULED: Mutex<Option<HashMap<String, BackgroundTaskRequest>>> = Mutex::new(None);
+static RUNNING: Mutex<Option<HashMap<String, BackgroundTask>>> = Mutex::new(None);
+
+/// Register the handler for a background task identifier
+///
+/// Register every identifier at startup, before the first frame; iOS may
+/// launch the app in the background just to run one. The handler resolves
+/// to whether the work succeeded.
+///
+/// ```rust,ignore
+/// register_background_task("com.example.refresh", |task| async move {
+///     sync_inbox(&task).await.is_ok()
+/// });
+/// schedule_background_task(
+///     BackgroundTaskRequest::refresh("com.example.refresh")
+///         .repeat_every(Duration::from_secs(3600))
+///         .requires_network(),
+/// )?;
+/// ```
+pub fn register_background_task<F, Fut>(identifier: impl Into<String>, handler: F)
+where
+    F: Fn(BackgroundTask) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = bool> + Send + 'static,
+{
+    let handler: TaskHandler = Arc::new(move |task| handler(task).boxed());
+    HANDLERS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(identifier.into(), handler);
+}
+
+/// Ask the OS to run a registered task, replacing any earlier request for it
+pub fn schedule_background_task(request: BackgroundTaskRequest) -> Result<(), BackgroundTaskError> {
+    let registered = HANDLERS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map_or(false, |handlers| handlers.contains_key(&request.identifier));
+    if !registered {
+        return Err(BackgroundTaskError::NotRegistered(request.identifier));
+    }
+
+    native::schedule(&request)?;
+    SCHEDULED
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(request.identifier.clone(), request);
+    Ok(())
+}
+
+/// Cancel pending runs of a task; a run already in progress continues
+pub fn cancel_background_task(identifier: &str) {
+    if let Some(scheduled) = SCHEDULED.lock().unwrap().as_mut() {
+        scheduled.remove(identifier);
+    }
+    native::cancel(identifier);
+}
+
+/// Cancel pending runs of every task
+pub fn cancel_all_background_tasks() {
+    let identifiers: Vec<String> = SCHEDULED
+        .lock()
+        .unwrap()
+        .take()
+        .map(|scheduled| scheduled.into_keys().collect())
+        .unwrap_or_default();
+    for identifier in identifiers {
+        native::cancel(&identifier);
+    }
+}
+
+/// Run a task the OS launched (called by the platform glue)
+///
+/// Returns false for an unknown identifier, which the glue should report
+/// as a failed run. The handler runs on its own thread; when it finishes,
+/// storage is flushed, repeating tasks are rescheduled and the OS is told
+/// the outcome.
+pub fn handle_background_task(identifier: &str) -> bool {
+    let handler = HANDLERS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|handlers| handlers.get(identifier).cloned());
+    let Some(handler) = handler else {
+        return false;
+    };
+
+    let task = BackgroundTask {
+        identifier: identifier.to_string(),
+        expired: Arc::new(AtomicBool::new(false)),
+    };
+    RUNNING
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(task.identifier.clone(), task.clone());
+
+    std::thread::Builder::new()
+        .name(format!("philjs-task-{}", identifier))
+        .spawn(move || {
+            let identifier = task.identifier.clone();
+            let success = futures::executor::block_on(handler(task));
+            finish_task(&identifier, success);
+        })
+        .is_ok()
+}
+
+/// Tell a running task the OS wants it to stop (called by the platform glue)
+pub fn handle_background_task_expired(identifier: &str) {
+    if let Some(task) = RUNNING.lock().unwrap().as_ref().and_then(|running| running.get(identifier)) {
+        task.expired.store(true, Ordering::SeqCst);
+    }
+}
+
+fn finish_task(identifier: &str, success: bool) {
+    if let Some(running) = RUNNING.lock().unwrap().as_mut() {
+        running.remove(identifier);
+    }
+    flush_storage();
+
+    let repeat = SCHEDULED
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|scheduled| scheduled.get(identifier))
+        .filter(|request| request.repeat_interval.is_some())
+        .cloned();
+    if let Some(request) = repeat {
+        native::reschedule(&request);
+    }
+    native::complete(identifier, success);
+}
+
+/// Background task error
+#[derive(Debug, Clone)]
+pub enum BackgroundTaskError {
+    /// No handler was registered for the identifier
+    NotRegistered(String),
+    /// The OS refused the request, e.g. an identifier missing from Info.plist
+    Rejected(String),
+    NotAvailable,
+}
+
+impl std::fmt::Display for BackgroundTaskError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackgroundTaskError::NotRegistered(id) => write!(f, "No handler registered for background task {}", id),
+            BackgroundTaskError::Rejected(msg) => write!(f, "Background task rejected: {}", msg),
+            BackgroundTaskError::NotAvailable => write!(f, "Background tasks not available"),
+        }
+    }
+}
+
+impl std::error::Error for BackgroundTaskError {}
+
+// ============================================================================
+// Platform Scheduling
+// ============================================================================
+
+/// Native calls. iOS uses `BGTaskScheduler`; Android uses WorkManager with a
+/// `PhilJsWorker` that forwards `doWork` to [`handle_background_task`].
+mod native {
+    use super::{BackgroundTaskError, BackgroundTaskRequest};
+
+    pub fn schedule(request: &BackgroundTaskRequest) -> Result<(), BackgroundTaskError> {
+        #[cfg(target_os = "ios")]
+        {
+            // Would submit a request of the matching class:
+            // let r = kind == Refresh ? BGAppRefreshTaskRequest(identifier: id)
+            //                         : BGProcessingTaskRequest(identifier: id)
+            // r.earliestBeginDate = Date(timeIntervalSinceNow: earliest_begin)
+            // for processing: r.requiresNetworkConnectivity, r.requiresExternalPower
+            // try BGTaskScheduler.shared.submit(r), mapping errors to Rejected
+            let _ = request;
+            Ok(())
+        }
+        #[cfg(target_os = "android")]
+        {
+            // Would enqueue unique work under the identifier via JNI:
+            // val constraints = Constraints.Builder()
+            //     .setRequiredNetworkType(if (requires_network) CONNECTED else NOT_REQUIRED)
+            //     .setRequiresCharging(requires_charging).build()
+            // repeat_interval: PeriodicWorkRequestBuilder<PhilJsWorker>(interval)
+            //     -> enqueueUniquePeriodicWork(id, UPDATE, r)
+            // otherwise: OneTimeWorkRequestBuilder<PhilJsWorker>()
+            //     -> enqueueUniqueWork(id, REPLACE, r)
+            // with setInitialDelay(earliest_begin) and inputData { "id": identifier }
+            let _ = request;
+            Ok(())
+        }
+        #[cfg(not(any(target_os = "ios", target_os = "android")))]
+        {
+            // Nothing runs on its own in simulation; call
+            // handle_background_task to exercise a handler
+            let _ = request;
+            Ok(())
+        }
+    }
+
+    /// Queue the next run of a repeating task after one finishes
+    pub fn reschedule(request: &BackgroundTaskRequest) {
+        // iOS: BGTaskScheduler requests run once, so submit again with
+        //     earliestBeginDate = now + repeat_interval
+        // Android: periodic work repeats on its own
+        #[cfg(target_os = "ios")]
+        {
+            let request = BackgroundTaskRequest {
+                earliest_begin: request.repeat_interval,
+                ..request.clone()
+            };
+            let _ = schedule(&request);
+        }
+        let _ = request;
+    }
+
+    pub fn cancel(identifier: &str) {
+        // iOS: BGTaskScheduler.shared.cancel(taskRequestWithIdentifier: identifier)
+        // Android: WorkManager.getInstance(context).cancelUniqueWork(identifier)
+        let _ = identifier;
+    }
+
+    /// Report a finished run to the OS
+    pub fn complete(identifier: &str, success: bool) {
+        // iOS: task.setTaskCompleted(success: success) on the BGTask kept by the glue
+        // Android: resolve the worker's ListenableFuture with
+        //     if (success) Result.success() else Result.retry()
+        let _ = (identifier, success);
+    }
+}
@@ -1,8 +1,16 @@
 //! PhilJS Mobile Navigation
 //!
 //! Navigation stack and routing for mobile applications.
+//!
+//! [`Navigator`] owns the route stack; [`NavigationHost`] renders it with
+//! the platform's transitions (UINavigationController on iOS, fragment
+//! transactions on Android). Deep links map `myapp://` URLs and universal
+//! links to routes through [`DeepLinks`].
 
+use crate::{Constraints, NativeComponent, NativeView, RenderContext, Size};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 /// Navigation route
@@ -59,26 +67,55 @@ impl Route {
 /// Navigation stack
 pub struct NavigationStack {
     routes: Vec<Route>,
+    entries: Vec<StackEntry>,
+    next_id: u64,
     listeners: Vec<Box<dyn Fn(&[Route]) + Send + Sync>>,
+    back_handlers: Vec<Arc<dyn Fn() -> bool + Send + Sync>>,
+}
+
+/// Identity and presentation of one screen in the stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StackEntry {
+    /// Unique for the life of the stack, so hosts can tell screens apart
+    pub id: u64,
+    /// How the screen was shown; played in reverse when it is removed
+    pub transition: NavigationTransition,
 }
 
 impl NavigationStack {
     pub fn new(initial: Route) -> Self {
         NavigationStack {
             routes: vec![initial],
+            entries: vec![StackEntry {
+                id: 0,
+                transition: NavigationTransition::None,
+            }],
+            next_id: 1,
             listeners: Vec::new(),
+            back_handlers: Vec::new(),
         }
     }
 
     /// Push a new route onto the stack
     pub fn push(&mut self, route: Route) {
+        self.push_with(route, NavigationTransition::Push);
+    }
+
+    /// Push a route shown with the given transition
+    pub fn push_with(&mut self, route: Route, transition: NavigationTransition) {
         self.routes.push(route);
+        self.entries.push(StackEntry {
+            id: self.next_id,
+            transition,
+        });
+        self.next_id += 1;
         self.notify();
     }
 
     /// Pop the top route from the stack
     pub fn pop(&mut self) -> Option<Route> {
         if self.routes.len() > 1 {
+            self.entries.pop();
             let route = self.routes.pop();
             self.notify();
             route
@@ -91,18 +128,67 @@ impl NavigationStack {
     pub fn pop_to_root(&mut self) {
         if self.routes.len() > 1 {
             self.routes.truncate(1);
+            self.entries.truncate(1);
             self.notify();
         }
     }
 
     /// Replace the current route
+    ///
+    /// The new screen appears without a transition.
     pub fn replace(&mut self, route: Route) {
         if let Some(last) = self.routes.last_mut() {
             *last = route;
+            if let Some(entry) = self.entries.last_mut() {
+                *entry = StackEntry {
+                    id: self.next_id,
+                    transition: NavigationTransition::None,
+                };
+                self.next_id += 1;
+            }
             self.notify();
         }
     }
 
+    /// Replace the whole stack with a single route
+    pub fn reset(&mut self, route: Route) {
+        self.routes = vec![route];
+        self.entries = vec![StackEntry {
+            id: self.next_id,
+            transition: NavigationTransition::None,
+        }];
+        self.next_id += 1;
+        self.notify();
+    }
+
+    /// Dismiss the topmost modal and everything pushed above it
+    ///
+    /// Returns false if no modal is presented.
+    pub fn dismiss(&mut self) -> bool {
+        match self.modal_index() {
+            Some(index) => {
+                self.routes.truncate(index);
+                self.entries.truncate(index);
+                self.notify();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if a modal is presented
+    pub fn is_modal_presented(&self) -> bool {
+        self.modal_index().is_some()
+    }
+
+    fn modal_index(&self) -> Option<usize> {
+        // The root can't be dismissed
+        self.entries
+            .iter()
+            .rposition(|entry| entry.transition == NavigationTransition::Modal)
+            .filter(|&index| index > 0)
+    }
+
     /// Get the current route
     pub fn current(&self) -> Option<&Route> {
         self.routes.last()
@@ -113,6 +199,11 @@ impl NavigationStack {
         &self.routes
     }
 
+    /// Get the identity of each route in the stack
+    pub fn entries(&self) -> &[StackEntry] {
+        &self.entries
+    }
+
     /// Check if can go back
     pub fn can_go_back(&self) -> bool {
         self.routes.len() > 1
@@ -133,12 +224,18 @@ impl NavigationStack {
     pub fn on_change<F: Fn(&[Route]) + Send + Sync + 'static>(&mut self, f: F) {
         self.listeners.push(Box::new(f));
     }
+
+    /// Intercept back navigation; return true to consume it
+    pub fn on_back<F: Fn() -> bool + Send + Sync + 'static>(&mut self, f: F) {
+        self.back_handlers.push(Arc::new(f));
+    }
 }
 
 /// Navigator - the main navigation interface
 pub struct Navigator {
     stack: Arc<Mutex<NavigationStack>>,
     transition: NavigationTransition,
+    deep_links: Option<Arc<DeepLinks>>,
 }
 
 impl Navigator {
@@ -146,13 +243,14 @@ impl Navigator {
         Navigator {
             stack: Arc::new(Mutex::new(NavigationStack::new(initial))),
             transition: NavigationTransition::Push,
+            deep_links: None,
         }
     }
 
     /// Navigate to a new route
     pub fn navigate(&self, route: Route) {
         if let Ok(mut stack) = self.stack.lock() {
-            stack.push(route);
+            stack.push_with(route, self.transition);
         }
     }
 
@@ -161,6 +259,23 @@ impl Navigator {
         self.navigate(Route::new(path));
     }
 
+    /// Navigate to a route built from typed params
+    pub fn navigate_with<P: RouteParams>(&self, params: P) {
+        self.navigate(params.into_route());
+    }
+
+    /// Present a route modally
+    pub fn present(&self, route: Route) {
+        if let Ok(mut stack) = self.stack.lock() {
+            stack.push_with(route, NavigationTransition::Modal);
+        }
+    }
+
+    /// Dismiss the topmost modal
+    pub fn dismiss(&self) -> bool {
+        self.stack.lock().map(|mut s| s.dismiss()).unwrap_or(false)
+    }
+
     /// Go back
     pub fn back(&self) -> bool {
         if let Ok(mut stack) = self.stack.lock() {
@@ -184,6 +299,52 @@ impl Navigator {
         }
     }
 
+    /// Replace the whole stack, e.g. after signing in
+    pub fn reset(&self, route: Route) {
+        if let Ok(mut stack) = self.stack.lock() {
+            stack.reset(route);
+        }
+    }
+
+    /// Handle the Android back button or gesture
+    ///
+    /// Back handlers run first, then the topmost modal is dismissed, then
+    /// the stack is popped. Returns false at the root so the platform can
+    /// close the app.
+    pub fn handle_back(&self) -> bool {
+        // Handlers run unlocked so they can navigate themselves
+        let handlers = match self.stack.lock() {
+            Ok(stack) => stack.back_handlers.clone(),
+            Err(_) => return false,
+        };
+        if handlers.iter().rev().any(|handler| handler()) {
+            return true;
+        }
+
+        let Ok(mut stack) = self.stack.lock() else { return false };
+        stack.dismiss() || stack.pop().is_some()
+    }
+
+    /// Record a pop the platform already animated, e.g. the iOS swipe back
+    pub fn handle_back_gesture(&self) -> bool {
+        let Ok(mut stack) = self.stack.lock() else { return false };
+        if let Some(entry) = stack.entries.last_mut() {
+            entry.transition = NavigationTransition::None;
+        }
+        stack.pop().is_some()
+    }
+
+    /// Navigate to the route a deep link resolves to
+    pub fn open_url(&self, url: &str) -> Result<(), RouteError> {
+        let links = self
+            .deep_links
+            .as_ref()
+            .ok_or_else(|| RouteError::NoMatch(url.to_string()))?;
+        let route = links.resolve(url)?;
+        self.navigate(route);
+        Ok(())
+    }
+
     /// Get current route
     pub fn current(&self) -> Option<Route> {
         self.stack.lock().ok()?.current().cloned()
@@ -200,12 +361,43 @@ impl Navigator {
         self
     }
 
+    /// Resolve deep links with the given configuration
+    pub fn with_deep_links(mut self, links: DeepLinks) -> Self {
+        self.deep_links = Some(Arc::new(links));
+        self
+    }
+
     /// Add navigation listener
     pub fn on_change<F: Fn(&[Route]) + Send + Sync + 'static>(&self, f: F) {
         if let Ok(mut stack) = self.stack.lock() {
             stack.on_change(f);
         }
     }
+
+    /// Intercept back navigation, e.g. to confirm discarding a form
+    pub fn on_back<F: Fn() -> bool + Send + Sync + 'static>(&self, f: F) {
+        if let Ok(mut stack) = self.stack.lock() {
+            stack.on_back(f);
+        }
+    }
+
+    /// Make this the navigator that receives platform back presses and
+    /// opened URLs
+    ///
+    /// A URL that launched the app before this was called is opened now.
+    pub fn install(&self) {
+        *ROOT_NAVIGATOR.lock().unwrap() = Some(self.clone());
+        if let Some(url) = PENDING_URL.lock().unwrap().take() {
+            let _ = self.open_url(&url);
+        }
+    }
+
+    fn snapshot(&self) -> Vec<(StackEntry, Route)> {
+        self.stack
+            .lock()
+            .map(|stack| stack.entries.iter().copied().zip(stack.routes.iter().cloned()).collect())
+            .unwrap_or_default()
+    }
 }
 
 impl Clone for Navigator {
@@ -213,7 +405,415 @@ impl Clone for Navigator {
         Navigator {
             stack: Arc::clone(&self.stack),
             transition: self.transition,
+            deep_links: self.deep_links.clone(),
+        }
+    }
+}
+
+static ROOT_NAVIGATOR: Mutex<Option<Navigator>> = Mutex::new(None);
+static PENDING_URL: Mutex<Option<String>> = Mutex::new(None);
+
+/// Open a URL delivered by the platform in the installed navigator
+///
+/// URLs that arrive before [`Navigator::install`] are held until then.
+pub fn handle_open_url(url: &str) -> bool {
+    let navigator = ROOT_NAVIGATOR.lock().unwrap().clone();
+    match navigator {
+        Some(navigator) => navigator.open_url(url).is_ok(),
+        None => {
+            *PENDING_URL.lock().unwrap() = Some(url.to_string());
+            true
+        }
+    }
+}
+
+/// Handle a platform back press; false means the app should close
+pub fn handle_back_pressed() -> bool {
+    let navigator = ROOT_NAVIGATOR.lock().unwrap().clone();
+    navigator.map(|navigator| navigator.handle_back()).unwrap_or(false)
+}
+
+/// Handle a completed platform back gesture
+pub fn handle_back_gesture() -> bool {
+    let navigator = ROOT_NAVIGATOR.lock().unwrap().clone();
+    navigator.map(|navigator| navigator.handle_back_gesture()).unwrap_or(false)
+}
+
+// ============================================================================
+// Typed Routes
+// ============================================================================
+
+/// Route error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    /// No registered route matches the URL or path
+    NoMatch(String),
+    /// A required parameter is absent
+    MissingParam(String),
+    /// A parameter didn't parse as the expected type
+    InvalidParam { key: String, value: String },
+}
+
+impl std::fmt::Display for RouteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouteError::NoMatch(path) => write!(f, "No route matches {}", path),
+            RouteError::MissingParam(key) => write!(f, "Missing route parameter: {}", key),
+            RouteError::InvalidParam { key, value } => {
+                write!(f, "Invalid value for route parameter {}: {}", key, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+/// Route parameters as a typed struct
+///
+/// ```rust,ignore
+/// struct UserRoute { id: u64 }
+///
+/// impl RouteParams for UserRoute {
+///     const PATH: &'static str = "/users/:id";
+///
+///     fn from_route(route: &Route) -> Result<Self, RouteError> {
+///         Ok(UserRoute { id: route.param_as("id")? })
+///     }
+///
+///     fn into_route(self) -> Route {
+///         Route::new(Self::PATH).param("id", self.id.to_string())
+///     }
+/// }
+///
+/// navigator.navigate_with(UserRoute { id: 42 });
+/// ```
+pub trait RouteParams: Sized {
+    /// Path pattern, with `:name` segments for parameters
+    const PATH: &'static str;
+
+    fn from_route(route: &Route) -> Result<Self, RouteError>;
+
+    fn into_route(self) -> Route;
+}
+
+impl Route {
+    /// Parse a parameter
+    pub fn param_as<T: FromStr>(&self, key: &str) -> Result<T, RouteError> {
+        let value = self
+            .params
+            .get(key)
+            .or_else(|| self.query.get(key))
+            .ok_or_else(|| RouteError::MissingParam(key.to_string()))?;
+        value.parse().map_err(|_| RouteError::InvalidParam {
+            key: key.to_string(),
+            value: value.clone(),
+        })
+    }
+
+    /// Read typed params, if this route is for `P`
+    pub fn params_as<P: RouteParams>(&self) -> Result<P, RouteError> {
+        if self.path != P::PATH {
+            return Err(RouteError::NoMatch(self.path.clone()));
         }
+        P::from_route(self)
+    }
+
+    /// Match a concrete path like `/users/42` against a pattern like
+    /// `/users/:id`, returning a route for the pattern
+    ///
+    /// A trailing `*` segment matches any remainder.
+    pub fn match_path(pattern: &str, path: &str) -> Option<Route> {
+        let mut params = HashMap::new();
+        let mut segments = path.split('/').filter(|s| !s.is_empty());
+
+        for part in pattern.split('/').filter(|s| !s.is_empty()) {
+            if part == "*" {
+                return Some(Route { params, ..Route::new(pattern) });
+            }
+            let segment = segments.next()?;
+            match part.strip_prefix(':') {
+                Some(name) => {
+                    params.insert(name.to_string(), percent_decode(segment));
+                }
+                None if part == segment => {}
+                None => return None,
+            }
+        }
+
+        if segments.next().is_some() {
+            return None;
+        }
+        Some(Route { params, ..Route::new(pattern) })
+    }
+}
+
+// ============================================================================
+// Deep Linking
+// ============================================================================
+
+/// Deep link configuration
+///
+/// Maps `myapp://users/42` and universal links like
+/// `https://example.com/users/42` to registered route patterns.
+///
+/// ```rust,ignore
+/// let navigator = Navigator::new(Route::new("/"))
+///     .with_deep_links(
+///         DeepLinks::new()
+///             .scheme("philjs")
+///             .universal_host("example.com")
+///             .route("/users/:id")
+///             .route_for::<SettingsRoute>(),
+///     );
+/// navigator.install();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DeepLinks {
+    pub schemes: Vec<String>,
+    pub hosts: Vec<String>,
+    pub patterns: Vec<String>,
+}
+
+impl DeepLinks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept a custom URL scheme, e.g. `"philjs"` for `philjs://...`
+    pub fn scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.schemes.push(scheme.into());
+        self
+    }
+
+    /// Accept universal links (iOS) / app links (Android) for a domain
+    pub fn universal_host(mut self, host: impl Into<String>) -> Self {
+        self.hosts.push(host.into());
+        self
+    }
+
+    /// Register a route pattern; earlier patterns win
+    pub fn route(mut self, pattern: impl Into<String>) -> Self {
+        self.patterns.push(pattern.into());
+        self
+    }
+
+    /// Register a typed route's pattern
+    pub fn route_for<P: RouteParams>(self) -> Self {
+        self.route(P::PATH)
+    }
+
+    /// Resolve a URL to a route
+    pub fn resolve(&self, url: &str) -> Result<Route, RouteError> {
+        let no_match = || RouteError::NoMatch(url.to_string());
+
+        let (scheme, rest) = url.split_once("://").ok_or_else(no_match)?;
+        let rest = rest.split('#').next().unwrap_or_default();
+        let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (host, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+
+        let path = if self.schemes.iter().any(|s| s.eq_ignore_ascii_case(scheme)) {
+            // In `philjs://users/42` the first segment is in the host position
+            format!("/{}{}", host, path)
+        } else if matches!(scheme, "https" | "http") && self.hosts.iter().any(|h| h.eq_ignore_ascii_case(host)) {
+            path.to_string()
+        } else {
+            return Err(no_match());
+        };
+
+        let mut route = self
+            .patterns
+            .iter()
+            .find_map(|pattern| Route::match_path(pattern, &path))
+            .ok_or_else(no_match)?;
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            route
+                .query
+                .insert(percent_decode(&key.replace('+', " ")), percent_decode(&value.replace('+', " ")));
+        }
+        Ok(route)
+    }
+
+    /// `CFBundleURLTypes` entry for the iOS Info.plist
+    pub fn info_plist(&self) -> String {
+        let schemes: String = self
+            .schemes
+            .iter()
+            .map(|scheme| format!("            <string>{}</string>\n", scheme))
+            .collect();
+        format!(
+            "<key>CFBundleURLTypes</key>\n<array>\n    <dict>\n        <key>CFBundleURLSchemes</key>\n        <array>\n{}        </array>\n    </dict>\n</array>\n",
+            schemes
+        )
+    }
+
+    /// Associated domains for the iOS entitlements file
+    pub fn associated_domains(&self) -> Vec<String> {
+        self.hosts.iter().map(|host| format!("applinks:{}", host)).collect()
+    }
+
+    /// Intent filters for the Android manifest's main activity
+    pub fn android_intent_filters(&self) -> String {
+        let mut xml = String::new();
+        let filter = |auto_verify: bool, data: String| {
+            format!(
+                "<intent-filter{}>\n    <action android:name=\"android.intent.action.VIEW\" />\n    <category android:name=\"android.intent.category.DEFAULT\" />\n    <category android:name=\"android.intent.category.BROWSABLE\" />\n{}</intent-filter>\n",
+                if auto_verify { " android:autoVerify=\"true\"" } else { "" },
+                data
+            )
+        };
+
+        for scheme in &self.schemes {
+            xml.push_str(&filter(false, format!("    <data android:scheme=\"{}\" />\n", scheme)));
+        }
+        for host in &self.hosts {
+            xml.push_str(&filter(
+                true,
+                format!("    <data android:scheme=\"https\" android:host=\"{}\" />\n", host),
+            ));
+        }
+        xml
+    }
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let hex = |byte: u8| (byte as char).to_digit(16).map(|digit| digit as u8);
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let decoded = match bytes[i] {
+            b'%' if i + 2 < bytes.len() => hex(bytes[i + 1]).zip(hex(bytes[i + 2])).map(|(hi, lo)| hi << 4 | lo),
+            _ => None,
+        };
+        match (decoded, bytes[i]) {
+            (Some(byte), _) => {
+                out.push(byte);
+                i += 3;
+            }
+            (None, byte) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// ============================================================================
+// Navigation Host
+// ============================================================================
+
+/// Renders a navigator's stack, animating between screens as it changes
+///
+/// Call [`update`](NativeComponent::update) after navigating (the runtime
+/// does this each frame) to apply the change with the platform transition.
+pub struct NavigationHost {
+    navigator: Navigator,
+    screen: Box<dyn Fn(&Route) -> Box<dyn NativeComponent>>,
+    state: RefCell<HostState>,
+}
+
+#[derive(Default)]
+struct HostState {
+    host: Option<NativeView>,
+    screens: Vec<(StackEntry, NativeView)>,
+}
+
+impl NavigationHost {
+    pub fn new<F, V>(navigator: Navigator, screen: F) -> Self
+    where
+        F: Fn(&Route) -> V + 'static,
+        V: NativeComponent + 'static,
+    {
+        NavigationHost {
+            navigator,
+            screen: Box::new(move |route| Box::new(screen(route))),
+            state: RefCell::new(HostState::default()),
+        }
+    }
+
+    pub fn navigator(&self) -> &Navigator {
+        &self.navigator
+    }
+
+    /// Bring the mounted screens in line with the stack
+    fn sync(&self, ctx: &mut RenderContext) {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+        let Some(host) = state.host.clone() else { return };
+        let entries = self.navigator.snapshot();
+
+        let kept = state
+            .screens
+            .iter()
+            .zip(&entries)
+            .take_while(|((mounted, _), (entry, _))| mounted.id == entry.id)
+            .count();
+        if kept == state.screens.len() && kept == entries.len() {
+            return;
+        }
+
+        let removed = state.screens.split_off(kept);
+        let added = &entries[kept..];
+        let from = removed.last().or(state.screens.last()).map(|(_, view)| view.clone());
+
+        for (index, (entry, route)) in added.iter().enumerate() {
+            let view = (self.screen)(route).render(ctx);
+            // Screens passed over on the way to the new top are attached directly
+            if index + 1 < added.len() {
+                ctx.add_child(&host, view.clone());
+            }
+            state.screens.push((*entry, view));
+        }
+        for (_, view) in removed.iter().rev().skip(1) {
+            ctx.remove_child(&host, view);
+        }
+
+        // Going forward plays the new top's transition; going back reverses
+        // the transition of the first screen removed
+        let (transition, reverse) = match (added.last(), removed.first()) {
+            (Some((entry, _)), _) => (entry.transition, false),
+            (None, Some((entry, _))) => (entry.transition, true),
+            (None, None) => return,
+        };
+        if let Some((_, to)) = state.screens.last() {
+            ctx.transition(&host, from.as_ref(), to, transition, reverse);
+        }
+        if let Some((_, view)) = removed.last() {
+            ctx.remove_child(&host, view);
+        }
+    }
+}
+
+impl NativeComponent for NavigationHost {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        let host = ctx.create_navigation_host();
+        let mut screens = Vec::new();
+        for (entry, route) in self.navigator.snapshot() {
+            let view = (self.screen)(&route).render(ctx);
+            ctx.add_child(&host, view.clone());
+            screens.push((entry, view));
+        }
+
+        *self.state.borrow_mut() = HostState {
+            host: Some(host.clone()),
+            screens,
+        };
+        host
+    }
+
+    fn update(&self, ctx: &mut RenderContext) {
+        self.sync(ctx);
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        // Screens fill the space they're given
+        Size::new(constraints.max_width, constraints.max_height)
     }
 }
 
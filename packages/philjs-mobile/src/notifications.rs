@@ -1,10 +1,18 @@
 //! PhilJS Mobile Notifications
 //!
 //! Local and push notification handling.
-
+//!
+//! Push tokens and incoming pushes reach Rust through the `handle_*`
+//! functions, which the platform glue calls from the app delegate
+//! (iOS) or `FirebaseMessagingService` (Android). They are delivered to the
+//! installed [`NotificationHandler`].
+
+use crate::permissions::{request_permission, Permission};
+use futures::channel::oneshot;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 
 // ============================================================================
@@ -208,16 +216,29 @@ pub fn clear_badge() {
 // ============================================================================
 
 /// Push notification payload
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Built from the platform payload by [`PushNotification::parse`]: APNs
+/// `aps` dictionaries and FCM `RemoteMessage` JSON both land here, with any
+/// custom keys in `data`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PushNotification {
+    /// Message id, when the service provides one
+    pub id: Option<String>,
     /// Notification title
     pub title: Option<String>,
+    /// Notification subtitle (iOS)
+    pub subtitle: Option<String>,
     /// Notification body
     pub body: Option<String>,
     /// Badge count
     pub badge: Option<u32>,
     /// Sound
     pub sound: Option<String>,
+    /// Category for actions
+    pub category: Option<String>,
+    /// Thread ID for grouping
+    pub thread_id: Option<String>,
     /// Custom data
     pub data: HashMap<String, serde_json::Value>,
     /// Whether shown in foreground
@@ -226,8 +247,139 @@ pub struct PushNotification {
     pub mutable_content: bool,
 }
 
-/// Push notification token
+impl PushNotification {
+    /// Parse an APNs or FCM payload
+    pub fn parse(payload: &str) -> Result<Self, NotificationError> {
+        let value: serde_json::Value =
+            serde_json::from_str(payload).map_err(|e| NotificationError::InvalidPayload(e.to_string()))?;
+        let serde_json::Value::Object(map) = value else {
+            return Err(NotificationError::InvalidPayload("payload is not an object".into()));
+        };
+
+        if map.contains_key("aps") {
+            Ok(Self::from_apns(map))
+        } else {
+            Ok(Self::from_fcm(map))
+        }
+    }
+
+    /// `{"aps": {...}, ...custom}` from `userInfo`
+    fn from_apns(mut map: serde_json::Map<String, serde_json::Value>) -> Self {
+        use serde_json::Value;
+
+        let aps = match map.remove("aps") {
+            Some(Value::Object(aps)) => aps,
+            _ => Default::default(),
+        };
+        let text = |value: Option<&Value>| value.and_then(Value::as_str).map(String::from);
+
+        let mut notification = PushNotification {
+            badge: aps.get("badge").and_then(Value::as_u64).map(|b| b as u32),
+            category: text(aps.get("category")),
+            thread_id: text(aps.get("thread-id")),
+            content_available: aps.get("content-available").and_then(Value::as_i64) == Some(1),
+            mutable_content: aps.get("mutable-content").and_then(Value::as_i64) == Some(1),
+            ..Default::default()
+        };
+
+        match aps.get("alert") {
+            Some(Value::String(body)) => notification.body = Some(body.clone()),
+            Some(Value::Object(alert)) => {
+                notification.title = text(alert.get("title"));
+                notification.subtitle = text(alert.get("subtitle"));
+                notification.body = text(alert.get("body"));
+            }
+            _ => {}
+        }
+
+        // Critical alerts carry a dictionary with the sound name inside
+        notification.sound = match aps.get("sound") {
+            Some(Value::Object(sound)) => text(sound.get("name")),
+            sound => text(sound),
+        };
+
+        notification.id = text(map.get("gcm.message_id")).or_else(|| text(map.get("id")));
+        notification.data = map.into_iter().collect();
+        notification
+    }
+
+    /// `RemoteMessage` as `{"messageId", "notification": {...}, "data": {...}}`
+    ///
+    /// FCM data values are always strings; each is decoded as JSON when it
+    /// parses so typed payloads can hold numbers, booleans and objects.
+    fn from_fcm(mut map: serde_json::Map<String, serde_json::Value>) -> Self {
+        use serde_json::Value;
+
+        let text = |value: Option<&Value>| value.and_then(Value::as_str).map(String::from);
+        let content = match map.remove("notification") {
+            Some(Value::Object(content)) => content,
+            _ => Default::default(),
+        };
+        let data = match map.remove("data") {
+            Some(Value::Object(data)) => data,
+            // Intent extras arrive flattened, without a data object
+            _ => map
+                .iter()
+                .filter(|(key, _)| !key.starts_with("google.") && !key.starts_with("gcm."))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        };
+
+        PushNotification {
+            id: text(map.get("messageId")).or_else(|| text(map.get("google.message_id"))),
+            title: text(content.get("title")),
+            body: text(content.get("body")),
+            sound: text(content.get("sound")),
+            badge: content
+                .get("notificationCount")
+                .and_then(Value::as_u64)
+                .map(|b| b as u32),
+            category: text(content.get("clickAction")),
+            thread_id: text(content.get("tag")),
+            data: data
+                .into_iter()
+                .map(|(key, value)| match value {
+                    Value::String(s) => (key, serde_json::from_str(&s).unwrap_or(Value::String(s))),
+                    value => (key, value),
+                })
+                .collect(),
+            content_available: content.is_empty(),
+            mutable_content: false,
+        }
+    }
+
+    /// Deserialize the custom data into a typed payload
+    ///
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// struct ChatMessage { thread: String, unread: u32 }
+    ///
+    /// let message: ChatMessage = notification.payload()?;
+    /// ```
+    pub fn payload<T: DeserializeOwned>(&self) -> Result<T, NotificationError> {
+        let data = serde_json::Value::Object(self.data.clone().into_iter().collect());
+        serde_json::from_value(data).map_err(|e| NotificationError::InvalidPayload(e.to_string()))
+    }
+
+    /// A single custom data value
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.data
+            .get(key)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+/// A tap on a notification or one of its actions
 #[derive(Debug, Clone)]
+pub struct NotificationResponse {
+    /// The notification that was tapped
+    pub notification: PushNotification,
+    /// The action button, or `None` for the notification itself
+    pub action_id: Option<String>,
+}
+
+/// Push notification token
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PushToken {
     /// Token string
     pub token: String,
@@ -235,6 +387,24 @@ pub struct PushToken {
     pub token_type: PushTokenType,
 }
 
+impl PushToken {
+    /// Token from the raw APNs device token, hex encoded as APNs expects
+    pub fn apns(device_token: &[u8]) -> Self {
+        PushToken {
+            token: device_token.iter().map(|b| format!("{:02x}", b)).collect(),
+            token_type: PushTokenType::APNS,
+        }
+    }
+
+    /// Token from FirebaseMessaging
+    pub fn fcm(token: impl Into<String>) -> Self {
+        PushToken {
+            token: token.into(),
+            token_type: PushTokenType::FCM,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PushTokenType {
     /// Apple Push Notification service
@@ -243,17 +413,64 @@ pub enum PushTokenType {
     FCM,
 }
 
+static CURRENT_TOKEN: Mutex<Option<PushToken>> = Mutex::new(None);
+static REGISTRATIONS: Mutex<Vec<oneshot::Sender<Result<PushToken, NotificationError>>>> = Mutex::new(Vec::new());
+
 /// Register for push notifications
+///
+/// Prompts for [`Permission::Notifications`] first, then resolves once the
+/// platform hands back a token. Call on every launch: tokens change, and
+/// the installed handler's `on_token` fires whenever they do.
 pub async fn register_for_push() -> Result<PushToken, NotificationError> {
+    if !request_permission(Permission::Notifications).await.is_granted() {
+        return Err(NotificationError::PermissionDenied);
+    }
+
+    let (tx, rx) = oneshot::channel();
+    REGISTRATIONS.lock().unwrap().push(tx);
+
+    if let Err(e) = request_push_token() {
+        resolve_registrations(Err(e.clone()));
+        return Err(e);
+    }
+
+    rx.await
+        .unwrap_or_else(|_| Err(NotificationError::TokenError("registration cancelled".into())))
+}
+
+/// Stop receiving push notifications and forget the token
+pub fn unregister_for_push() {
+    CURRENT_TOKEN.lock().unwrap().take();
+
     #[cfg(target_os = "ios")]
     {
-        // Would use UIApplication.registerForRemoteNotifications
-        Err(NotificationError::NotSupported)
+        // Would use UIApplication.unregisterForRemoteNotifications
     }
     #[cfg(target_os = "android")]
     {
-        // Would use FirebaseMessaging.getToken
-        Err(NotificationError::NotSupported)
+        // Would use FirebaseMessaging.getInstance().deleteToken()
+    }
+}
+
+/// The last token delivered by the platform
+pub fn current_push_token() -> Option<PushToken> {
+    CURRENT_TOKEN.lock().unwrap().clone()
+}
+
+fn request_push_token() -> Result<(), NotificationError> {
+    #[cfg(target_os = "ios")]
+    {
+        // Would use UIApplication.registerForRemoteNotifications; the app
+        // delegate forwards the result to handle_apns_token or
+        // handle_push_registration_failed
+        Ok(())
+    }
+    #[cfg(target_os = "android")]
+    {
+        // Would use FirebaseMessaging.getInstance().getToken() with an
+        // OnCompleteListener that calls handle_fcm_token or
+        // handle_push_registration_failed
+        Ok(())
     }
     #[cfg(not(any(target_os = "ios", target_os = "android")))]
     {
@@ -261,23 +478,118 @@ pub async fn register_for_push() -> Result<PushToken, NotificationError> {
     }
 }
 
+fn resolve_registrations(result: Result<PushToken, NotificationError>) {
+    let pending = std::mem::take(&mut *REGISTRATIONS.lock().unwrap());
+    for tx in pending {
+        let _ = tx.send(result.clone());
+    }
+}
+
+// ============================================================================
+// Platform Entry Points
+// ============================================================================
+
+/// Called from `didRegisterForRemoteNotificationsWithDeviceToken`
+pub fn handle_apns_token(device_token: &[u8]) {
+    handle_push_token(PushToken::apns(device_token));
+}
+
+/// Called from `getToken()` and `FirebaseMessagingService.onNewToken`
+pub fn handle_fcm_token(token: &str) {
+    handle_push_token(PushToken::fcm(token));
+}
+
+/// Record a new token, notifying the handler only if it changed
+pub fn handle_push_token(token: PushToken) {
+    let changed = {
+        let mut current = CURRENT_TOKEN.lock().unwrap();
+        let changed = current.as_ref() != Some(&token);
+        *current = Some(token.clone());
+        changed
+    };
+
+    resolve_registrations(Ok(token.clone()));
+    if changed {
+        dispatch(NotificationEvent::Token(token));
+    }
+}
+
+/// Called from `didFailToRegisterForRemoteNotificationsWithError` or a failed `getToken()`
+pub fn handle_push_registration_failed(message: &str) {
+    let error = NotificationError::TokenError(message.to_string());
+    resolve_registrations(Err(error.clone()));
+    dispatch(NotificationEvent::TokenError(error));
+}
+
+/// Called when a push arrives
+///
+/// `foreground` is whether the app was active: iOS `willPresent` and FCM
+/// `onMessageReceived` while resumed are foreground; silent pushes and
+/// `onMessageReceived` in the background are not. Returns false if the
+/// payload couldn't be parsed.
+pub fn handle_remote_notification(payload: &str, foreground: bool) -> bool {
+    match PushNotification::parse(payload) {
+        Ok(notification) if foreground => dispatch(NotificationEvent::Received(notification)),
+        Ok(notification) => dispatch(NotificationEvent::Background(notification)),
+        Err(_) => return false,
+    }
+    true
+}
+
+/// Called when the user taps a notification or one of its actions
+///
+/// Taps that launch the app arrive before the handler is installed and are
+/// delivered once it is.
+pub fn handle_notification_response(payload: &str, action_id: Option<&str>) -> bool {
+    // The tap on the notification body itself
+    const IOS_DEFAULT_ACTION: &str = "com.apple.UNNotificationDefaultActionIdentifier";
+
+    let Ok(notification) = PushNotification::parse(payload) else {
+        return false;
+    };
+    let action_id = action_id.filter(|id| *id != IOS_DEFAULT_ACTION).map(String::from);
+    dispatch(NotificationEvent::Opened(NotificationResponse { notification, action_id }));
+    true
+}
+
 // ============================================================================
 // Notification Handler
 // ============================================================================
 
 /// Notification event handler
+///
+/// Built once at startup and [installed](Self::install). Events that arrive
+/// before then, such as the tap that launched the app, are queued.
+///
+/// ```rust,ignore
+/// NotificationHandler::new()
+///     .on_token(|token| upload_token(token))
+///     .on_received(|n| show_banner(n))
+///     .on_opened(|n| {
+///         if let Ok(chat) = n.payload::<ChatMessage>() {
+///             navigator.navigate(&format!("/chats/{}", chat.thread));
+///         }
+///     })
+///     .install();
+/// ```
 pub struct NotificationHandler {
     on_received: Option<Arc<dyn Fn(PushNotification) + Send + Sync>>,
+    on_background: Option<Arc<dyn Fn(PushNotification) + Send + Sync>>,
     on_opened: Option<Arc<dyn Fn(PushNotification) + Send + Sync>>,
+    on_action: Option<Arc<dyn Fn(&str, PushNotification) + Send + Sync>>,
     on_token: Option<Arc<dyn Fn(PushToken) + Send + Sync>>,
+    on_token_error: Option<Arc<dyn Fn(NotificationError) + Send + Sync>>,
 }
 
 impl NotificationHandler {
     pub fn new() -> Self {
         NotificationHandler {
             on_received: None,
+            on_background: None,
             on_opened: None,
+            on_action: None,
             on_token: None,
+            on_token_error: None,
         }
     }
 
@@ -287,17 +599,79 @@ impl NotificationHandler {
         self
     }
 
+    /// Called when a push arrives while the app is in the background
+    pub fn on_background<F: Fn(PushNotification) + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.on_background = Some(Arc::new(f));
+        self
+    }
+
     /// Called when user taps on notification
     pub fn on_opened<F: Fn(PushNotification) + Send + Sync + 'static>(mut self, f: F) -> Self {
         self.on_opened = Some(Arc::new(f));
         self
     }
 
+    /// Called when user taps an action button, with the action id
+    ///
+    /// Without this, action taps go to `on_opened`.
+    pub fn on_action<F: Fn(&str, PushNotification) + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.on_action = Some(Arc::new(f));
+        self
+    }
+
     /// Called when push token is received/updated
     pub fn on_token<F: Fn(PushToken) + Send + Sync + 'static>(mut self, f: F) -> Self {
         self.on_token = Some(Arc::new(f));
         self
     }
+
+    /// Called when registration for a token fails
+    pub fn on_token_error<F: Fn(NotificationError) + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.on_token_error = Some(Arc::new(f));
+        self
+    }
+
+    /// Make this the app's handler and deliver any queued events
+    pub fn install(self) {
+        *HANDLER.lock().unwrap() = Some(Arc::new(self));
+
+        let pending = std::mem::take(&mut *PENDING_EVENTS.lock().unwrap());
+        for event in pending {
+            dispatch(event);
+        }
+    }
+
+    fn handle(&self, event: NotificationEvent) {
+        match event {
+            NotificationEvent::Received(n) => {
+                if let Some(f) = &self.on_received {
+                    f(n);
+                }
+            }
+            NotificationEvent::Background(n) => {
+                if let Some(f) = &self.on_background {
+                    f(n);
+                }
+            }
+            NotificationEvent::Opened(NotificationResponse { notification, action_id }) => {
+                match (action_id, &self.on_action, &self.on_opened) {
+                    (Some(action), Some(f), _) => f(&action, notification),
+                    (_, _, Some(f)) => f(notification),
+                    _ => {}
+                }
+            }
+            NotificationEvent::Token(token) => {
+                if let Some(f) = &self.on_token {
+                    f(token);
+                }
+            }
+            NotificationEvent::TokenError(error) => {
+                if let Some(f) = &self.on_token_error {
+                    f(error);
+                }
+            }
+        }
+    }
 }
 
 impl Default for NotificationHandler {
@@ -306,6 +680,27 @@ impl Default for NotificationHandler {
     }
 }
 
+enum NotificationEvent {
+    Received(PushNotification),
+    Background(PushNotification),
+    Opened(NotificationResponse),
+    Token(PushToken),
+    TokenError(NotificationError),
+}
+
+static HANDLER: Mutex<Option<Arc<NotificationHandler>>> = Mutex::new(None);
+static PENDING_EVENTS: Mutex<Vec<NotificationEvent>> = Mutex::new(Vec::new());
+
+/// Deliver to the installed handler, or queue until one is installed
+fn dispatch(event: NotificationEvent) {
+    let handler = HANDLER.lock().unwrap().clone();
+    match handler {
+        // Called unlocked so callbacks can register or install again
+        Some(handler) => handler.handle(event),
+        None => PENDING_EVENTS.lock().unwrap().push(event),
+    }
+}
+
 // ============================================================================
 // Notification Actions
 // ============================================================================
@@ -392,6 +787,7 @@ pub enum NotificationError {
     NotSupported,
     SchedulingFailed(String),
     TokenError(String),
+    InvalidPayload(String),
 }
 
 impl std::fmt::Display for NotificationError {
@@ -401,6 +797,7 @@ impl std::fmt::Display for NotificationError {
             NotificationError::NotSupported => write!(f, "Notifications not supported"),
             NotificationError::SchedulingFailed(e) => write!(f, "Failed to schedule: {}", e),
             NotificationError::TokenError(e) => write!(f, "Token error: {}", e),
+            NotificationError::InvalidPayload(e) => write!(f, "Invalid payload: {}", e),
         }
     }
 }
@@ -13,40 +13,64 @@ pub use crate::{
     // Runtime
     run, run_with_config, MobileApp, MobileConfig,
 
+    // Lifecycle
+    app_state, register_background_task, schedule_background_task, BackgroundTask, BackgroundTaskRequest,
+
     // Renderer
     NativeRenderer, RenderContext,
 
     // Components
     components::{
         Text, Button, Image, TextInput, Switch, Slider,
-        VStack, HStack, ZStack, ScrollView, ListView,
-        Spacer, Divider, Container, Card,
+        VStack, HStack, ZStack, ScrollView, ListView, FlatList,
+        Flexible, Spacer, Divider, Container, Card,
     },
 
+    // Layout
+    layout::{FlexStyle, FlexItem, FlexDirection, JustifyContent, AlignItems},
+
     // Platform
     Platform, PlatformInfo, DeviceInfo,
 
     // Navigation
-    Navigator, Route, NavigationStack,
+    Navigator, Route, NavigationStack, NavigationHost, NavigationTransition,
+    DeepLinks, RouteParams, RouteError,
 
     // Gestures
-    GestureRecognizer, Gesture, GestureState,
+    GestureRecognizer, Gesture, GestureState, GestureDetector, Recognize,
 
     // Animation
-    AnimatedValue, SpringAnimation, TimingAnimation,
+    Animated, AnimatedValue, Animation, SpringAnimation, TimingAnimation, Transform, AnimatedStyle,
+    use_animated_style,
+
+    // Accessibility
+    Accessibility, AccessibilityExt, AccessibilityRole, AccessibilityTraits, LiveRegion,
+
+    // Theming
+    Theme, Typography, Appearance, ColorSchemePreference, set_theme, set_color_scheme,
+    theme::token,
 
     // Storage
-    SecureStorage, AsyncStorage, FileSystem,
+    SecureStorage, AsyncStorage, FileSystem, StorageBackend, MemoryBackend, StorageMigration,
 
     // Permissions
     Permission, PermissionStatus, request_permission,
 
     // Notifications
-    LocalNotification, PushNotification, NotificationHandler,
+    LocalNotification, PushNotification, NotificationHandler, register_for_push,
 
     // Haptics
     HapticFeedback, HapticStyle,
+
+    // Camera
+    Camera, CameraSession, CameraView, ImagePicker, MediaFile,
+
+    // WebView
+    WebView, WebViewController, WebViewSource, WebMessage, NavigationDecision,
 };
 
+#[cfg(feature = "hot-reload")]
+pub use crate::hot_reload::hot_signal;
+
 // Re-export macros
 pub use philjs_macros::{component, view, rsx};
@@ -5,8 +5,15 @@
 use crate::{
     Color, Constraints, EdgeInsets, FontWeight, NativeView, Point, Rect, Size, TextAlign,
 };
+use crate::accessibility::{tracking, Accessibility, TouchTargetKind};
+use crate::animation::AnimatedStyle;
+use crate::camera::CameraSession;
+use crate::gestures::GestureArena;
+use crate::navigation::NavigationTransition;
+use crate::theme::{self, token, Typography};
+use crate::webview::WebViewController;
 use std::any::Any;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 /// Render context passed to components during rendering
 pub struct RenderContext {
@@ -24,11 +31,13 @@ pub struct RenderContext {
 
 impl RenderContext {
     pub fn new(renderer: Box<dyn PlatformRenderer>) -> Self {
+        // The system appearance, unless the app forces light or dark
+        theme::handle_appearance_change(renderer.is_dark_mode());
         RenderContext {
             constraints: Constraints::unbounded(),
             parent_frame: Rect::default(),
             scale_factor: renderer.scale_factor(),
-            dark_mode: renderer.is_dark_mode(),
+            dark_mode: theme::is_dark(),
             renderer,
         }
     }
@@ -56,12 +65,18 @@ impl RenderContext {
 
     /// Create a button
     pub fn create_button(&mut self, config: ButtonConfig) -> NativeView {
-        self.renderer.create_button(config)
+        let titled = config.title.as_deref().map_or(false, |title| !title.trim().is_empty());
+        let view = self.renderer.create_button(config);
+        tracking::track_target(&view, TouchTargetKind::Button, titled);
+        view
     }
 
     /// Create a text input
     pub fn create_text_input(&mut self, config: TextInputConfig) -> NativeView {
-        self.renderer.create_text_input(config)
+        let placeholder = config.placeholder.is_some();
+        let view = self.renderer.create_text_input(config);
+        tracking::track_target(&view, TouchTargetKind::TextInput, placeholder);
+        view
     }
 
     /// Create a scroll view
@@ -89,6 +104,77 @@ impl RenderContext {
         self.renderer.set_frame(view, frame)
     }
 
+    /// Create a virtualized list view
+    pub fn create_list(&mut self, config: ListConfig) -> NativeView {
+        self.renderer.create_list(config)
+    }
+
+    /// Create a reusable list cell
+    pub fn create_list_cell(&mut self, list: &NativeView, reuse_id: &str) -> NativeView {
+        self.renderer.create_list_cell(list, reuse_id)
+    }
+
+    /// Set the scrollable content size of a scroll or list view
+    pub fn set_content_size(&mut self, view: &NativeView, size: Size) {
+        self.renderer.set_content_size(view, size)
+    }
+
+    /// Show or hide a list's refresh indicator
+    pub fn set_refreshing(&mut self, list: &NativeView, refreshing: bool) {
+        self.renderer.set_refreshing(list, refreshing)
+    }
+
+    /// Create the view that hosts a navigation stack's screens
+    pub fn create_navigation_host(&mut self) -> NativeView {
+        self.renderer.create_navigation_host()
+    }
+
+    /// Animate from one screen to another inside a navigation host
+    ///
+    /// `reverse` is set when going back, so a push plays as a pop.
+    pub fn transition(
+        &mut self,
+        host: &NativeView,
+        from: Option<&NativeView>,
+        to: &NativeView,
+        transition: NavigationTransition,
+        reverse: bool,
+    ) {
+        self.renderer.transition(host, from, to, transition, reverse)
+    }
+
+    /// Create a live preview surface for a camera session
+    pub fn create_camera_preview(&mut self, session: &CameraSession) -> NativeView {
+        self.renderer.create_camera_preview(session)
+    }
+
+    /// Create a web view for a controller and load its source
+    pub fn create_web_view(&mut self, web_view: &WebViewController) -> NativeView {
+        self.renderer.create_web_view(web_view)
+    }
+
+    /// Route a view's touches to a gesture arena
+    pub fn attach_gestures(&mut self, view: &NativeView, arena: Arc<Mutex<GestureArena>>) {
+        tracking::track_target(view, TouchTargetKind::GestureTarget, false);
+        self.renderer.attach_gestures(view, arena)
+    }
+
+    /// Set screen-reader attributes on a view
+    pub fn set_accessibility(&mut self, view: &NativeView, props: &Accessibility) {
+        tracking::track_named(view, props.is_named());
+        self.renderer.set_accessibility(view, props)
+    }
+
+    /// Set animated properties on a view
+    pub fn apply_animated_style(&mut self, view: &NativeView, style: &AnimatedStyle) {
+        self.renderer.apply_animated_style(view, style)
+    }
+
+    /// The platform renderer, for work done outside a render pass
+    pub(crate) fn clone_renderer(&self) -> Box<dyn PlatformRenderer> {
+        self.renderer.clone_box()
+    }
+
     /// Measure text size
     pub fn measure_text(&self, text: &str, style: &TextStyle, max_width: f32) -> Size {
         self.renderer.measure_text(text, style, max_width)
@@ -103,9 +189,27 @@ pub trait PlatformRenderer: Send + Sync {
     fn create_text_input(&self, config: TextInputConfig) -> NativeView;
     fn create_scroll_view(&self, config: ScrollConfig) -> NativeView;
     fn create_container(&self, style: ContainerStyle) -> NativeView;
+    fn create_list(&self, config: ListConfig) -> NativeView;
+    fn create_list_cell(&self, list: &NativeView, reuse_id: &str) -> NativeView;
+    fn set_content_size(&self, view: &NativeView, size: Size);
+    fn set_refreshing(&self, list: &NativeView, refreshing: bool);
     fn add_child(&self, parent: &NativeView, child: NativeView);
     fn remove_child(&self, parent: &NativeView, child: &NativeView);
     fn set_frame(&self, view: &NativeView, frame: Rect);
+    fn create_navigation_host(&self) -> NativeView;
+    fn transition(
+        &self,
+        host: &NativeView,
+        from: Option<&NativeView>,
+        to: &NativeView,
+        transition: NavigationTransition,
+        reverse: bool,
+    );
+    fn create_camera_preview(&self, session: &CameraSession) -> NativeView;
+    fn create_web_view(&self, web_view: &WebViewController) -> NativeView;
+    fn attach_gestures(&self, view: &NativeView, arena: Arc<Mutex<GestureArena>>);
+    fn apply_animated_style(&self, view: &NativeView, style: &AnimatedStyle);
+    fn set_accessibility(&self, view: &NativeView, props: &Accessibility);
     fn measure_text(&self, text: &str, style: &TextStyle, max_width: f32) -> Size;
     fn scale_factor(&self) -> f32;
     fn is_dark_mode(&self) -> bool;
@@ -173,10 +277,12 @@ pub struct TextStyle {
 
 impl Default for TextStyle {
     fn default() -> Self {
+        let theme = theme::current();
+        let body = theme.font(Typography::Body);
         TextStyle {
-            font_size: 17.0, // iOS default
-            font_weight: FontWeight::Regular,
-            color: Color::BLACK,
+            font_size: body.size,
+            font_weight: body.weight,
+            color: theme.color(token::LABEL),
             alignment: TextAlign::Left,
             line_height: None,
             letter_spacing: None,
@@ -239,10 +345,11 @@ pub struct ButtonStyle {
 
 impl Default for ButtonStyle {
     fn default() -> Self {
+        let theme = theme::current();
         ButtonStyle {
-            background_color: Color::from_hex(0x007AFF), // iOS blue
-            text_color: Color::WHITE,
-            font_size: 17.0,
+            background_color: theme.color(token::PRIMARY),
+            text_color: theme.color(token::ON_PRIMARY),
+            font_size: theme.font(Typography::Headline).size,
             font_weight: FontWeight::SemiBold,
             corner_radius: 8.0,
             padding: EdgeInsets::symmetric(16.0, 12.0),
@@ -305,15 +412,16 @@ pub struct TextInputStyle {
 
 impl Default for TextInputStyle {
     fn default() -> Self {
+        let theme = theme::current();
         TextInputStyle {
-            font_size: 17.0,
-            text_color: Color::BLACK,
-            placeholder_color: Color::rgba(0.6, 0.6, 0.6, 1.0),
-            background_color: Color::WHITE,
+            font_size: theme.font(Typography::Body).size,
+            text_color: theme.color(token::LABEL),
+            placeholder_color: theme.color(token::PLACEHOLDER),
+            background_color: theme.color(token::FILL),
             corner_radius: 8.0,
             padding: EdgeInsets::symmetric(12.0, 10.0),
             border_width: 1.0,
-            border_color: Color::rgba(0.8, 0.8, 0.8, 1.0),
+            border_color: theme.color(token::SEPARATOR),
         }
     }
 }
@@ -348,6 +456,29 @@ pub enum ScrollDirection {
     Both,
 }
 
+/// Virtualized list configuration
+#[derive(Debug, Clone)]
+pub struct ListConfig {
+    pub direction: ScrollDirection,
+    pub show_indicators: bool,
+    pub separator: bool,
+    /// Attach a pull-to-refresh control
+    pub refresh_control: bool,
+    pub refreshing: bool,
+}
+
+impl Default for ListConfig {
+    fn default() -> Self {
+        ListConfig {
+            direction: ScrollDirection::Vertical,
+            show_indicators: true,
+            separator: true,
+            refresh_control: false,
+            refreshing: false,
+        }
+    }
+}
+
 /// Container styling
 #[derive(Debug, Clone)]
 pub struct ContainerStyle {
@@ -453,10 +584,62 @@ impl PlatformRenderer for SimulationRenderer {
         }
     }
 
+    fn create_list(&self, _config: ListConfig) -> NativeView {
+        NativeView {
+            handle: Arc::new("List"),
+            children: Vec::new(),
+        }
+    }
+
+    fn create_list_cell(&self, _list: &NativeView, reuse_id: &str) -> NativeView {
+        NativeView {
+            handle: Arc::new(format!("ListCell: {}", reuse_id)),
+            children: Vec::new(),
+        }
+    }
+
+    fn set_content_size(&self, _view: &NativeView, _size: Size) {}
+    fn set_refreshing(&self, _list: &NativeView, _refreshing: bool) {}
+
     fn add_child(&self, _parent: &NativeView, _child: NativeView) {}
     fn remove_child(&self, _parent: &NativeView, _child: &NativeView) {}
     fn set_frame(&self, _view: &NativeView, _frame: Rect) {}
 
+    fn create_navigation_host(&self) -> NativeView {
+        NativeView {
+            handle: Arc::new("NavigationHost"),
+            children: Vec::new(),
+        }
+    }
+
+    fn transition(
+        &self,
+        _host: &NativeView,
+        _from: Option<&NativeView>,
+        _to: &NativeView,
+        _transition: NavigationTransition,
+        _reverse: bool,
+    ) {
+    }
+
+    fn create_camera_preview(&self, session: &CameraSession) -> NativeView {
+        NativeView {
+            handle: Arc::new(format!("CameraPreview: {}", session.id())),
+            children: Vec::new(),
+        }
+    }
+
+    fn create_web_view(&self, web_view: &WebViewController) -> NativeView {
+        NativeView {
+            handle: Arc::new(format!("WebView: {}", web_view.id())),
+            children: Vec::new(),
+        }
+    }
+
+    fn attach_gestures(&self, _view: &NativeView, _arena: Arc<Mutex<GestureArena>>) {}
+    fn apply_animated_style(&self, _view: &NativeView, _style: &AnimatedStyle) {}
+    fn set_accessibility(&self, _view: &NativeView, _props: &Accessibility) {}
+
     fn measure_text(&self, text: &str, style: &TextStyle, max_width: f32) -> Size {
         // Rough estimation for simulation
         let char_width = style.font_size * 0.5;
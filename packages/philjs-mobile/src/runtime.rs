@@ -23,6 +23,9 @@ pub struct MobileConfig {
     pub background_color: crate::Color,
     /// Enable debug features
     pub debug: bool,
+    /// Dev server to hot reload from (`hot-reload` feature); defaults to
+    /// `PHILJS_DEV_SERVER` in debug builds
+    pub dev_server: Option<String>,
 }
 
 impl Default for MobileConfig {
@@ -33,8 +36,11 @@ impl Default for MobileConfig {
             orientation: OrientationConfig::default(),
             status_bar: StatusBarConfig::default(),
             edge_to_edge: true,
-            background_color: crate::Color::WHITE,
+            background_color: crate::Color::semantic(crate::theme::token::BACKGROUND),
             debug: cfg!(debug_assertions),
+            dev_server: std::env::var("PHILJS_DEV_SERVER")
+                .ok()
+                .filter(|_| cfg!(debug_assertions)),
         }
     }
 }
@@ -159,6 +165,21 @@ impl MobileApp {
         }
     }
 
+    /// Replace the root view with a newly built component
+    ///
+    /// Used by hot reload; signals the component reads are not reset. With
+    /// `debug` set, accessibility problems in the new tree are printed.
+    pub fn swap_root(&self, root: &dyn crate::NativeComponent, ctx: &mut RenderContext) -> NativeView {
+        let view = root.render(ctx);
+        if let Ok(mut state) = self.state.lock() {
+            state.root_view = Some(view.clone());
+        }
+        if self.config.debug {
+            crate::accessibility::report_issues();
+        }
+        view
+    }
+
     /// Get current app state
     pub fn current_state(&self) -> AppState {
         self.state.lock().map(|s| s.current_state).unwrap_or(AppState::Active)
@@ -234,6 +255,36 @@ where
     println!("Starting {} in simulation mode", config.name);
     let _root = app();
     // In real implementation, this would create a window simulating a mobile device
+
+    #[cfg(feature = "hot-reload")]
+    if let Some(url) = &config.dev_server {
+        run_hot_reload(url, &config);
+    }
+}
+
+/// Apply dev server updates to the simulated app until the connection ends
+#[cfg(all(feature = "hot-reload", not(any(target_os = "ios", target_os = "android"))))]
+fn run_hot_reload(url: &str, config: &MobileConfig) {
+    use crate::hot_reload::{HotReloadClient, HotReloadConfig, HotUpdate};
+
+    println!("Hot reload: connecting to {}", url);
+    let app = MobileApp::new(config.clone());
+    let mut ctx = crate::NativeRenderer::new().create_context();
+    let mut client = HotReloadClient::connect(HotReloadConfig::new(url.to_string()));
+
+    while let Some(update) = client.recv() {
+        match update {
+            HotUpdate::Root(root) => {
+                app.swap_root(root.as_ref(), &mut ctx);
+                println!("Hot reload: root view replaced");
+            }
+            HotUpdate::Reload => println!("Hot reload: change needs a restart"),
+            HotUpdate::Error { message, file, line } => match (file, line) {
+                (Some(file), Some(line)) => eprintln!("Hot reload: {}:{}: {}", file, line, message),
+                _ => eprintln!("Hot reload: {}", message),
+            },
+        }
+    }
 }
 
 /// Signal for reactive state
@@ -1,152 +1,787 @@
 //! PhilJS Mobile Storage
 //!
 //! Persistent storage solutions for mobile applications.
+//!
+//! Both stores sit on a [`StorageBackend`]:
+//!
+//! - [`AsyncStorage`] keeps non-sensitive data in SQLite (with the `sqlite`
+//!   feature) and runs every operation on a background thread, so the UI
+//!   thread only awaits.
+//! - [`SecureStorage`] keeps secrets in the iOS Keychain, or on Android in
+//!   SQLite encrypted with an AES-GCM key held by the Android Keystore. On
+//!   other targets every operation fails with [`StorageError::Unsupported`].
+//!
+//! [`MemoryBackend`] stands in for either in tests and desktop simulation.
 
 use serde::{de::DeserializeOwned, Serialize};
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "sqlite")]
+use std::path::Path;
 use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex, OnceLock, Weak};
+use std::time::{Duration, Instant};
+
+// ============================================================================
+// Backends
+// ============================================================================
+
+/// String key-value store behind [`AsyncStorage`] and [`SecureStorage`]
+pub trait StorageBackend: Send + Sync {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError>;
+    fn set(&self, key: &str, value: &str) -> Result<(), StorageError>;
+    fn remove(&self, key: &str) -> Result<(), StorageError>;
+    fn clear(&self) -> Result<(), StorageError>;
+    fn keys(&self) -> Result<Vec<String>, StorageError>;
+
+    /// Write several values; backends that can do so atomically should
+    fn set_many(&self, entries: &[(String, String)]) -> Result<(), StorageError> {
+        for (key, value) in entries {
+            self.set(key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Make completed writes durable before the process may be killed
+    fn flush(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
+}
+
+/// In-memory backend for tests and desktop simulation
+#[derive(Clone, Default)]
+pub struct MemoryBackend {
+    values: Arc<Mutex<BTreeMap<String, String>>>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A backend shared by every caller using the same name in this process
+    pub fn shared(name: &str) -> Self {
+        static SHARED: OnceLock<Mutex<HashMap<String, MemoryBackend>>> = OnceLock::new();
+        SHARED
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_default()
+            .clone()
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        Ok(self.values.lock().unwrap().get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        self.values.lock().unwrap().insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        self.values.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), StorageError> {
+        self.values.lock().unwrap().clear();
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        Ok(self.values.lock().unwrap().keys().cloned().collect())
+    }
+
+    fn set_many(&self, entries: &[(String, String)]) -> Result<(), StorageError> {
+        let mut values = self.values.lock().unwrap();
+        for (key, value) in entries {
+            values.insert(key.clone(), value.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Schema migrations for [`SqliteBackend`], applied in order by `user_version`
+#[cfg(feature = "sqlite")]
+const SQLITE_SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS kv (key TEXT PRIMARY KEY NOT NULL, value TEXT NOT NULL)",
+    "ALTER TABLE kv ADD COLUMN updated_at INTEGER NOT NULL DEFAULT 0",
+];
+
+/// SQLite backend
+#[cfg(feature = "sqlite")]
+pub struct SqliteBackend {
+    conn: Mutex<rusqlite::Connection>,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteBackend {
+    /// Open or create a database file, upgrading its schema
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| StorageError::IoError(e.to_string()))?;
+        }
+        let conn = rusqlite::Connection::open(path)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        Self::with_connection(conn)
+    }
+
+    /// A database that lives only as long as this backend
+    pub fn memory() -> Result<Self, StorageError> {
+        Self::with_connection(rusqlite::Connection::open_in_memory()?)
+    }
+
+    fn with_connection(mut conn: rusqlite::Connection) -> Result<Self, StorageError> {
+        let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        let version = version.max(0) as usize;
+        if version < SQLITE_SCHEMA.len() {
+            let tx = conn.transaction()?;
+            for statement in &SQLITE_SCHEMA[version..] {
+                tx.execute_batch(statement)?;
+            }
+            tx.pragma_update(None, "user_version", SQLITE_SCHEMA.len() as i64)?;
+            tx.commit()?;
+        }
+        Ok(SqliteBackend { conn: Mutex::new(conn) })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+const SQLITE_UPSERT: &str = "INSERT INTO kv (key, value, updated_at) VALUES (?1, ?2, strftime('%s', 'now')) \
+     ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at";
+
+#[cfg(feature = "sqlite")]
+impl StorageBackend for SqliteBackend {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        use rusqlite::OptionalExtension;
+        let conn = self.conn.lock().unwrap();
+        Ok(conn
+            .query_row("SELECT value FROM kv WHERE key = ?1", [key], |row| row.get(0))
+            .optional()?)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        self.conn.lock().unwrap().execute(SQLITE_UPSERT, [key, value])?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        self.conn.lock().unwrap().execute("DELETE FROM kv WHERE key = ?1", [key])?;
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), StorageError> {
+        self.conn.lock().unwrap().execute("DELETE FROM kv", [])?;
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT key FROM kv ORDER BY key")?;
+        let keys: Vec<String> = stmt.query_map([], |row| row.get(0))?.collect::<Result<_, _>>()?;
+        Ok(keys)
+    }
+
+    fn set_many(&self, entries: &[(String, String)]) -> Result<(), StorageError> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(SQLITE_UPSERT)?;
+            for (key, value) in entries {
+                stmt.execute([key, value])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<(), StorageError> {
+        // Fold the WAL into the database file so a killed process loses nothing
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        Ok(())
+    }
+}
 
 // ============================================================================
 // Secure Storage (Keychain/Keystore)
 // ============================================================================
 
 /// Secure storage using iOS Keychain or Android Keystore
+///
+/// Off iOS and Android there is nowhere safe to keep secrets, so every
+/// operation fails with [`StorageError::Unsupported`]; tests and desktop
+/// simulation can pass a [`MemoryBackend`] to [`with_backend`](Self::with_backend).
 pub struct SecureStorage {
     service_name: String,
+    backend: Box<dyn StorageBackend>,
 }
 
 impl SecureStorage {
     pub fn new(service_name: impl Into<String>) -> Self {
+        let service_name = service_name.into();
+
+        #[cfg(target_os = "ios")]
+        let backend: Box<dyn StorageBackend> = Box::new(KeychainBackend::new(&service_name));
+        #[cfg(all(target_os = "android", feature = "android"))]
+        let backend: Box<dyn StorageBackend> = Box::new(KeystoreBackend::new(&service_name));
+        #[cfg(not(any(target_os = "ios", all(target_os = "android", feature = "android"))))]
+        let backend: Box<dyn StorageBackend> = Box::new(UnsupportedBackend);
+
+        SecureStorage { service_name, backend }
+    }
+
+    /// Secure storage over a specific backend, e.g. [`MemoryBackend`] in tests
+    pub fn with_backend(service_name: impl Into<String>, backend: impl StorageBackend + 'static) -> Self {
         SecureStorage {
             service_name: service_name.into(),
+            backend: Box::new(backend),
         }
     }
 
+    pub fn service_name(&self) -> &str {
+        &self.service_name
+    }
+
     /// Store a string value securely
     pub fn set(&self, key: &str, value: &str) -> Result<(), StorageError> {
-        #[cfg(target_os = "ios")]
-        {
-            self.set_ios_keychain(key, value)
-        }
-        #[cfg(target_os = "android")]
-        {
-            self.set_android_keystore(key, value)
-        }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            // Simulation - in-memory storage
-            Ok(())
-        }
+        self.backend.set(key, value)
     }
 
     /// Retrieve a string value
     pub fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
-        #[cfg(target_os = "ios")]
-        {
-            self.get_ios_keychain(key)
-        }
-        #[cfg(target_os = "android")]
-        {
-            self.get_android_keystore(key)
-        }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            Ok(None)
-        }
+        self.backend.get(key)
     }
 
     /// Delete a value
     pub fn delete(&self, key: &str) -> Result<(), StorageError> {
-        #[cfg(target_os = "ios")]
-        {
-            self.delete_ios_keychain(key)
-        }
-        #[cfg(target_os = "android")]
-        {
-            self.delete_android_keystore(key)
-        }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            Ok(())
-        }
+        self.backend.remove(key)
+    }
+
+    /// Delete every value stored under this service
+    pub fn clear(&self) -> Result<(), StorageError> {
+        self.backend.clear()
+    }
+
+    /// Get all keys stored under this service
+    pub fn keys(&self) -> Result<Vec<String>, StorageError> {
+        self.backend.keys()
     }
 
     /// Store data with biometric protection
-    pub fn set_with_biometrics(&self, key: &str, value: &str) -> Result<(), StorageError> {
-        // Would use LAContext on iOS, BiometricPrompt on Android
-        self.set(key, value)
+    ///
+    /// Not supported yet: items are not bound to an access control, so this
+    /// always fails rather than storing the value unprotected.
+    pub fn set_with_biometrics(&self, key: &str, _value: &str) -> Result<(), StorageError> {
+        Err(StorageError::Unsupported(format!(
+            "biometric protection for {:?} is not implemented",
+            key
+        )))
     }
 
     /// Retrieve data requiring biometric authentication
+    ///
+    /// Not supported yet; see [`set_with_biometrics`](Self::set_with_biometrics).
     pub fn get_with_biometrics(&self, key: &str) -> Result<Option<String>, StorageError> {
-        // Would prompt for biometric auth first
-        self.get(key)
+        Err(StorageError::Unsupported(format!(
+            "biometric protection for {:?} is not implemented",
+            key
+        )))
     }
+}
 
-    #[cfg(target_os = "ios")]
-    fn set_ios_keychain(&self, _key: &str, _value: &str) -> Result<(), StorageError> {
-        // Would use Security framework
-        Ok(())
+/// Backend for targets without a platform secure store
+#[cfg(not(any(target_os = "ios", all(target_os = "android", feature = "android"))))]
+struct UnsupportedBackend;
+
+#[cfg(not(any(target_os = "ios", all(target_os = "android", feature = "android"))))]
+impl UnsupportedBackend {
+    fn error() -> StorageError {
+        StorageError::Unsupported("secure storage is only available on iOS and Android".to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "ios", all(target_os = "android", feature = "android"))))]
+impl StorageBackend for UnsupportedBackend {
+    fn get(&self, _key: &str) -> Result<Option<String>, StorageError> {
+        Err(Self::error())
     }
 
-    #[cfg(target_os = "ios")]
-    fn get_ios_keychain(&self, _key: &str) -> Result<Option<String>, StorageError> {
-        Ok(None)
+    fn set(&self, _key: &str, _value: &str) -> Result<(), StorageError> {
+        Err(Self::error())
     }
 
-    #[cfg(target_os = "ios")]
-    fn delete_ios_keychain(&self, _key: &str) -> Result<(), StorageError> {
-        Ok(())
+    fn remove(&self, _key: &str) -> Result<(), StorageError> {
+        Err(Self::error())
+    }
+
+    fn clear(&self) -> Result<(), StorageError> {
+        Err(Self::error())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        Err(Self::error())
+    }
+}
+
+#[cfg(target_os = "ios")]
+const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+
+/// Keychain generic passwords, one per key, under the service name
+#[cfg(target_os = "ios")]
+pub struct KeychainBackend {
+    service: String,
+}
+
+#[cfg(target_os = "ios")]
+impl KeychainBackend {
+    pub fn new(service: &str) -> Self {
+        KeychainBackend {
+            service: service.to_string(),
+        }
     }
+}
 
-    #[cfg(target_os = "android")]
-    fn set_android_keystore(&self, _key: &str, _value: &str) -> Result<(), StorageError> {
-        // Would use Android Keystore
+#[cfg(target_os = "ios")]
+impl StorageBackend for KeychainBackend {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        match security_framework::passwords::get_generic_password(&self.service, key) {
+            Ok(bytes) => String::from_utf8(bytes)
+                .map(Some)
+                .map_err(|e| StorageError::DeserializationError(e.to_string())),
+            Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => Ok(None),
+            Err(e) => Err(StorageError::KeychainError(e.to_string())),
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        security_framework::passwords::set_generic_password(&self.service, key, value.as_bytes())
+            .map_err(|e| StorageError::KeychainError(e.to_string()))
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        match security_framework::passwords::delete_generic_password(&self.service, key) {
+            Err(e) if e.code() != ERR_SEC_ITEM_NOT_FOUND => Err(StorageError::KeychainError(e.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    fn clear(&self) -> Result<(), StorageError> {
+        for key in self.keys()? {
+            self.remove(&key)?;
+        }
         Ok(())
     }
 
-    #[cfg(target_os = "android")]
-    fn get_android_keystore(&self, _key: &str) -> Result<Option<String>, StorageError> {
-        Ok(None)
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        use security_framework::item::{ItemClass, ItemSearchOptions, Limit};
+
+        let results = match ItemSearchOptions::new()
+            .class(ItemClass::generic_password())
+            .service(&self.service)
+            .load_attributes(true)
+            .limit(Limit::All)
+            .search()
+        {
+            Ok(results) => results,
+            Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => return Ok(Vec::new()),
+            Err(e) => return Err(StorageError::KeychainError(e.to_string())),
+        };
+
+        Ok(results
+            .iter()
+            .filter_map(|result| result.simplify_dict())
+            .filter_map(|attributes| attributes.get("acct").cloned())
+            .collect())
+    }
+}
+
+/// SQLite values encrypted with an AES-GCM key that never leaves the
+/// Android Keystore
+///
+/// Each value is sealed with its storage key as associated data, so a
+/// ciphertext copied to another key fails to decrypt.
+#[cfg(all(target_os = "android", feature = "android"))]
+pub struct KeystoreBackend {
+    alias: String,
+    store: OnceLock<Result<SqliteBackend, StorageError>>,
+}
+
+#[cfg(all(target_os = "android", feature = "android"))]
+impl KeystoreBackend {
+    pub fn new(service: &str) -> Self {
+        KeystoreBackend {
+            alias: format!("philjs.secure.{}", service),
+            store: OnceLock::new(),
+        }
+    }
+
+    fn store(&self) -> Result<&SqliteBackend, StorageError> {
+        self.store
+            .get_or_init(|| SqliteBackend::open(FileSystem::documents_dir().join(format!("{}.db", self.alias))))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+}
+
+#[cfg(all(target_os = "android", feature = "android"))]
+impl StorageBackend for KeystoreBackend {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        let Some(sealed) = self.store()?.get(key)? else { return Ok(None) };
+        let (iv, ciphertext) = sealed
+            .split_once(':')
+            .and_then(|(iv, ciphertext)| Some((hex_decode(iv)?, hex_decode(ciphertext)?)))
+            .ok_or_else(|| StorageError::DeserializationError("corrupt secure storage entry".to_string()))?;
+        let plaintext = keystore::decrypt(&self.alias, key, &iv, &ciphertext)?;
+        String::from_utf8(plaintext)
+            .map(Some)
+            .map_err(|e| StorageError::DeserializationError(e.to_string()))
     }
 
-    #[cfg(target_os = "android")]
-    fn delete_android_keystore(&self, _key: &str) -> Result<(), StorageError> {
+    fn set(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        let (iv, ciphertext) = keystore::encrypt(&self.alias, key, value.as_bytes())?;
+        self.store()?.set(key, &format!("{}:{}", hex_encode(&iv), hex_encode(&ciphertext)))
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        self.store()?.remove(key)
+    }
+
+    fn clear(&self) -> Result<(), StorageError> {
+        self.store()?.clear()
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        self.store()?.keys()
+    }
+}
+
+#[cfg(all(target_os = "android", feature = "android"))]
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(all(target_os = "android", feature = "android"))]
+fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// AES-GCM through `javax.crypto` with a key generated inside the Keystore
+#[cfg(all(target_os = "android", feature = "android"))]
+mod keystore {
+    use super::StorageError;
+    use jni::objects::{JByteArray, JObject, JValue};
+    use jni::JNIEnv;
+
+    const ENCRYPT_MODE: i32 = 1;
+    const DECRYPT_MODE: i32 = 2;
+    const PURPOSE_ENCRYPT_DECRYPT: i32 = 1 | 2;
+    const TAG_BITS: i32 = 128;
+
+    /// Seal `plaintext`, authenticating `aad` alongside it
+    pub fn encrypt(alias: &str, aad: &str, plaintext: &[u8]) -> Result<(Vec<u8>, Vec<u8>), StorageError> {
+        with_env(|env| {
+            let key = secret_key(env, alias)?;
+            let cipher = cipher(env)?;
+            env.call_method(&cipher, "init", "(ILjava/security/Key;)V", &[JValue::Int(ENCRYPT_MODE), JValue::Object(&key)])?;
+            update_aad(env, &cipher, aad)?;
+
+            let iv = JByteArray::from(env.call_method(&cipher, "getIV", "()[B", &[])?.l()?);
+            let input = env.byte_array_from_slice(plaintext)?;
+            let output = JByteArray::from(env.call_method(&cipher, "doFinal", "([B)[B", &[JValue::Object(&input)])?.l()?);
+            Ok((env.convert_byte_array(iv)?, env.convert_byte_array(output)?))
+        })
+    }
+
+    /// Open a value sealed by [`encrypt`] with the same `aad`
+    pub fn decrypt(alias: &str, aad: &str, iv: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, StorageError> {
+        with_env(|env| {
+            let key = secret_key(env, alias)?;
+            let cipher = cipher(env)?;
+            let iv = env.byte_array_from_slice(iv)?;
+            let spec = env.new_object("javax/crypto/spec/GCMParameterSpec", "(I[B)V", &[JValue::Int(TAG_BITS), JValue::Object(&iv)])?;
+            env.call_method(
+                &cipher,
+                "init",
+                "(ILjava/security/Key;Ljava/security/spec/AlgorithmParameterSpec;)V",
+                &[JValue::Int(DECRYPT_MODE), JValue::Object(&key), JValue::Object(&spec)],
+            )?;
+            update_aad(env, &cipher, aad)?;
+
+            let input = env.byte_array_from_slice(ciphertext)?;
+            let output = JByteArray::from(env.call_method(&cipher, "doFinal", "([B)[B", &[JValue::Object(&input)])?.l()?);
+            env.convert_byte_array(output)
+        })
+    }
+
+    fn with_env<R>(f: impl FnOnce(&mut JNIEnv) -> jni::errors::Result<R>) -> Result<R, StorageError> {
+        let activity = ndk_glue::native_activity();
+        // Safety: the VM pointer comes from the running activity
+        let vm = unsafe { jni::JavaVM::from_raw(activity.vm().cast()) }.map_err(keystore_error)?;
+        let mut env = vm.attach_current_thread().map_err(keystore_error)?;
+
+        let result = f(&mut env);
+        if env.exception_check().unwrap_or(false) {
+            let _ = env.exception_clear();
+        }
+        result.map_err(keystore_error)
+    }
+
+    fn update_aad(env: &mut JNIEnv, cipher: &JObject, aad: &str) -> jni::errors::Result<()> {
+        let aad = env.byte_array_from_slice(aad.as_bytes())?;
+        env.call_method(cipher, "updateAAD", "([B)V", &[JValue::Object(&aad)])?;
         Ok(())
     }
+
+    fn cipher<'a>(env: &mut JNIEnv<'a>) -> jni::errors::Result<JObject<'a>> {
+        let transformation = env.new_string("AES/GCM/NoPadding")?;
+        env.call_static_method(
+            "javax/crypto/Cipher",
+            "getInstance",
+            "(Ljava/lang/String;)Ljavax/crypto/Cipher;",
+            &[JValue::Object(&transformation)],
+        )?
+        .l()
+    }
+
+    /// The Keystore key for `alias`, generated on first use
+    fn secret_key<'a>(env: &mut JNIEnv<'a>, alias: &str) -> jni::errors::Result<JObject<'a>> {
+        let provider = env.new_string("AndroidKeyStore")?;
+        let alias = env.new_string(alias)?;
+
+        let store = env
+            .call_static_method(
+                "java/security/KeyStore",
+                "getInstance",
+                "(Ljava/lang/String;)Ljava/security/KeyStore;",
+                &[JValue::Object(&provider)],
+            )?
+            .l()?;
+        env.call_method(&store, "load", "(Ljava/security/KeyStore$LoadStoreParameter;)V", &[JValue::Object(&JObject::null())])?;
+
+        let exists = env
+            .call_method(&store, "containsAlias", "(Ljava/lang/String;)Z", &[JValue::Object(&alias)])?
+            .z()?;
+        if !exists {
+            let builder = env.new_object(
+                "android/security/keystore/KeyGenParameterSpec$Builder",
+                "(Ljava/lang/String;I)V",
+                &[JValue::Object(&alias), JValue::Int(PURPOSE_ENCRYPT_DECRYPT)],
+            )?;
+            let string_class = env.find_class("java/lang/String")?;
+            let gcm = env.new_string("GCM")?;
+            let modes = env.new_object_array(1, &string_class, &gcm)?;
+            env.call_method(
+                &builder,
+                "setBlockModes",
+                "([Ljava/lang/String;)Landroid/security/keystore/KeyGenParameterSpec$Builder;",
+                &[JValue::Object(&modes)],
+            )?;
+            let no_padding = env.new_string("NoPadding")?;
+            let paddings = env.new_object_array(1, &string_class, &no_padding)?;
+            env.call_method(
+                &builder,
+                "setEncryptionPaddings",
+                "([Ljava/lang/String;)Landroid/security/keystore/KeyGenParameterSpec$Builder;",
+                &[JValue::Object(&paddings)],
+            )?;
+            let spec = env
+                .call_method(&builder, "build", "()Landroid/security/keystore/KeyGenParameterSpec;", &[])?
+                .l()?;
+
+            let algorithm = env.new_string("AES")?;
+            let generator = env
+                .call_static_method(
+                    "javax/crypto/KeyGenerator",
+                    "getInstance",
+                    "(Ljava/lang/String;Ljava/lang/String;)Ljavax/crypto/KeyGenerator;",
+                    &[JValue::Object(&algorithm), JValue::Object(&provider)],
+                )?
+                .l()?;
+            env.call_method(
+                &generator,
+                "init",
+                "(Ljava/security/spec/AlgorithmParameterSpec;)V",
+                &[JValue::Object(&spec)],
+            )?;
+            env.call_method(&generator, "generateKey", "()Ljavax/crypto/SecretKey;", &[])?;
+        }
+
+        env.call_method(
+            &store,
+            "getKey",
+            "(Ljava/lang/String;[C)Ljava/security/Key;",
+            &[JValue::Object(&alias), JValue::Object(&JObject::null())],
+        )?
+        .l()
+    }
+
+    fn keystore_error(e: jni::errors::Error) -> StorageError {
+        StorageError::KeychainError(format!("Android Keystore: {}", e))
+    }
 }
 
 // ============================================================================
-// Async Storage (UserDefaults/SharedPreferences)
+// Async Storage (SQLite)
 // ============================================================================
 
+/// Key used to record which data migrations have run
+const DATA_VERSION_KEY: &str = "__philjs_data_version";
+
+type Job = Box<dyn FnOnce(&Result<Box<dyn StorageBackend>, StorageError>) + Send>;
+
+/// Storage threads, for [`AsyncStorage::flush_all`]. The `Weak` dies with
+/// the last clone of its store, so the entry stops keeping the thread alive.
+static OPEN_STORES: Mutex<Vec<(Weak<()>, mpsc::Sender<Job>)>> = Mutex::new(Vec::new());
+
 /// Key-value storage for non-sensitive data
+///
+/// Operations run in order on a background thread that owns the backend.
+/// Clones share that thread.
+///
+/// ```rust,ignore
+/// let storage = AsyncStorage::standard();
+/// storage.set("settings", &settings).await?;
+/// let settings: Option<Settings> = storage.get("settings").await?;
+/// ```
+#[derive(Clone)]
 pub struct AsyncStorage {
-    suite_name: Option<String>,
+    jobs: mpsc::Sender<Job>,
+    open: Arc<()>,
 }
 
 impl AsyncStorage {
-    /// Default storage
+    /// Default storage, in the app's documents directory
+    #[cfg(feature = "sqlite")]
     pub fn standard() -> Self {
-        AsyncStorage { suite_name: None }
+        Self::open(FileSystem::documents_dir().join("philjs-storage.db"))
     }
 
     /// App group storage (for sharing with extensions)
+    #[cfg(feature = "sqlite")]
     pub fn group(suite_name: impl Into<String>) -> Self {
-        AsyncStorage {
-            suite_name: Some(suite_name.into()),
+        // iOS would resolve containerURL(forSecurityApplicationGroupIdentifier:)
+        let suite_name = suite_name.into();
+        Self::open(FileSystem::documents_dir().join(format!("{}.db", suite_name)))
+    }
+
+    /// Storage in a SQLite database at `path`
+    ///
+    /// The database is opened on the storage thread; if that fails, every
+    /// operation returns the error.
+    #[cfg(feature = "sqlite")]
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self::spawn(move || Ok(Box::new(SqliteBackend::open(&path)?)))
+    }
+
+    /// Storage that forgets everything when dropped, for tests
+    pub fn memory() -> Self {
+        Self::with_backend(MemoryBackend::new())
+    }
+
+    /// Storage over a specific backend
+    pub fn with_backend(backend: impl StorageBackend + 'static) -> Self {
+        Self::spawn(move || Ok(Box::new(backend)))
+    }
+
+    fn spawn(open: impl FnOnce() -> Result<Box<dyn StorageBackend>, StorageError> + Send + 'static) -> Self {
+        let (jobs, rx) = mpsc::channel::<Job>();
+        std::thread::Builder::new()
+            .name("philjs-storage".to_string())
+            .spawn(move || {
+                let backend = open();
+                for job in rx {
+                    job(&backend);
+                }
+            })
+            .expect("failed to spawn storage thread");
+
+        let open = Arc::new(());
+        let mut stores = OPEN_STORES.lock().unwrap();
+        stores.retain(|(open, _)| open.strong_count() > 0);
+        stores.push((Arc::downgrade(&open), jobs.clone()));
+        AsyncStorage { jobs, open }
+    }
+
+    /// Wait for queued operations in every open store, then flush them
+    ///
+    /// Blocks for at most `timeout`; returns whether every store finished.
+    /// Called by [`crate::lifecycle`] when the app leaves the foreground.
+    pub fn flush_all(timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let (done, finished) = mpsc::channel();
+        let mut waiting = 0;
+        {
+            let mut stores = OPEN_STORES.lock().unwrap();
+            stores.retain(|(open, _)| open.strong_count() > 0);
+            for (_, jobs) in stores.iter() {
+                let done = done.clone();
+                let job: Job = Box::new(move |backend| {
+                    if let Ok(backend) = backend {
+                        let _ = backend.flush();
+                    }
+                    let _ = done.send(());
+                });
+                if jobs.send(job).is_ok() {
+                    waiting += 1;
+                }
+            }
         }
+
+        (0..waiting).all(|_| {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            finished.recv_timeout(remaining).is_ok()
+        })
+    }
+
+    /// Run an operation on the storage thread
+    async fn run<R, F>(&self, f: F) -> Result<R, StorageError>
+    where
+        R: Send + 'static,
+        F: FnOnce(&dyn StorageBackend) -> Result<R, StorageError> + Send + 'static,
+    {
+        let (tx, rx) = futures::channel::oneshot::channel();
+        let job: Job = Box::new(move |backend| {
+            let result = match backend {
+                Ok(backend) => f(backend.as_ref()),
+                Err(e) => Err(e.clone()),
+            };
+            let _ = tx.send(result);
+        });
+
+        let stopped = || StorageError::IoError("storage thread stopped".to_string());
+        self.jobs.send(job).map_err(|_| stopped())?;
+        rx.await.map_err(|_| stopped())?
+    }
+
+    /// Wait for earlier operations, then make them durable
+    pub async fn flush(&self) -> Result<(), StorageError> {
+        self.run(|backend| backend.flush()).await
     }
 
     /// Store a serializable value
-    pub fn set<T: Serialize>(&self, key: &str, value: &T) -> Result<(), StorageError> {
+    pub async fn set<T: Serialize>(&self, key: &str, value: &T) -> Result<(), StorageError> {
         let json = serde_json::to_string(value)
             .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-        self.set_string(key, &json)
+        self.set_string(key, &json).await
     }
 
     /// Retrieve a deserializable value
-    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, StorageError> {
-        match self.get_string(key)? {
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, StorageError> {
+        match self.get_string(key).await? {
             Some(json) => {
                 let value = serde_json::from_str(&json)
                     .map_err(|e| StorageError::DeserializationError(e.to_string()))?;
@@ -157,95 +792,93 @@ impl AsyncStorage {
     }
 
     /// Store a string value
-    pub fn set_string(&self, key: &str, value: &str) -> Result<(), StorageError> {
-        #[cfg(target_os = "ios")]
-        {
-            self.set_user_defaults(key, value)
-        }
-        #[cfg(target_os = "android")]
-        {
-            self.set_shared_prefs(key, value)
-        }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            let _ = (key, value);
-            Ok(())
-        }
+    pub async fn set_string(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        let (key, value) = (key.to_string(), value.to_string());
+        self.run(move |backend| backend.set(&key, &value)).await
     }
 
     /// Retrieve a string value
-    pub fn get_string(&self, key: &str) -> Result<Option<String>, StorageError> {
-        #[cfg(target_os = "ios")]
-        {
-            self.get_user_defaults(key)
-        }
-        #[cfg(target_os = "android")]
-        {
-            self.get_shared_prefs(key)
-        }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            let _ = key;
-            Ok(None)
-        }
+    pub async fn get_string(&self, key: &str) -> Result<Option<String>, StorageError> {
+        let key = key.to_string();
+        self.run(move |backend| backend.get(&key)).await
     }
 
-    /// Delete a value
-    pub fn remove(&self, key: &str) -> Result<(), StorageError> {
-        #[cfg(target_os = "ios")]
-        {
-            self.remove_user_defaults(key)
-        }
-        #[cfg(target_os = "android")]
-        {
-            self.remove_shared_prefs(key)
-        }
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
-        {
-            let _ = key;
-            Ok(())
-        }
+    /// Store several string values in one write
+    pub async fn multi_set(&self, entries: Vec<(String, String)>) -> Result<(), StorageError> {
+        self.run(move |backend| backend.set_many(&entries)).await
     }
 
-    /// Clear all values
-    pub fn clear(&self) -> Result<(), StorageError> {
-        // Would clear all keys
-        Ok(())
+    /// Retrieve several string values, in the order of `keys`
+    pub async fn multi_get(&self, keys: Vec<String>) -> Result<Vec<Option<String>>, StorageError> {
+        self.run(move |backend| keys.iter().map(|key| backend.get(key)).collect()).await
     }
 
-    /// Get all keys
-    pub fn all_keys(&self) -> Result<Vec<String>, StorageError> {
-        Ok(Vec::new())
+    /// Delete a value
+    pub async fn remove(&self, key: &str) -> Result<(), StorageError> {
+        let key = key.to_string();
+        self.run(move |backend| backend.remove(&key)).await
     }
 
-    #[cfg(target_os = "ios")]
-    fn set_user_defaults(&self, _key: &str, _value: &str) -> Result<(), StorageError> {
-        Ok(())
+    /// Clear all values
+    pub async fn clear(&self) -> Result<(), StorageError> {
+        self.run(|backend| backend.clear()).await
     }
 
-    #[cfg(target_os = "ios")]
-    fn get_user_defaults(&self, _key: &str) -> Result<Option<String>, StorageError> {
-        Ok(None)
+    /// Get all keys
+    pub async fn all_keys(&self) -> Result<Vec<String>, StorageError> {
+        self.run(|backend| {
+            Ok(backend
+                .keys()?
+                .into_iter()
+                .filter(|key| key != DATA_VERSION_KEY)
+                .collect())
+        })
+        .await
     }
 
-    #[cfg(target_os = "ios")]
-    fn remove_user_defaults(&self, _key: &str) -> Result<(), StorageError> {
-        Ok(())
-    }
+    /// Run the data migrations newer than the stored data version
+    ///
+    /// Returns the data version afterwards. A failed migration stops the
+    /// run, leaving the version at the last one that succeeded.
+    pub async fn migrate(&self, mut migrations: Vec<StorageMigration>) -> Result<u32, StorageError> {
+        migrations.sort_by_key(|migration| migration.version);
+        self.run(move |backend| {
+            let mut current: u32 = backend
+                .get(DATA_VERSION_KEY)?
+                .and_then(|version| version.parse().ok())
+                .unwrap_or(0);
 
-    #[cfg(target_os = "android")]
-    fn set_shared_prefs(&self, _key: &str, _value: &str) -> Result<(), StorageError> {
-        Ok(())
+            for migration in migrations {
+                if migration.version <= current {
+                    continue;
+                }
+                (migration.run)(backend)?;
+                current = migration.version;
+                backend.set(DATA_VERSION_KEY, &current.to_string())?;
+            }
+            Ok(current)
+        })
+        .await
     }
+}
 
-    #[cfg(target_os = "android")]
-    fn get_shared_prefs(&self, _key: &str) -> Result<Option<String>, StorageError> {
-        Ok(None)
-    }
+type MigrationFn = Box<dyn FnOnce(&dyn StorageBackend) -> Result<(), StorageError> + Send>;
 
-    #[cfg(target_os = "android")]
-    fn remove_shared_prefs(&self, _key: &str) -> Result<(), StorageError> {
-        Ok(())
+/// A one-time change to stored data, e.g. renaming a key
+pub struct StorageMigration {
+    pub version: u32,
+    run: MigrationFn,
+}
+
+impl StorageMigration {
+    pub fn new(
+        version: u32,
+        run: impl FnOnce(&dyn StorageBackend) -> Result<(), StorageError> + Send + 'static,
+    ) -> Self {
+        StorageMigration {
+            version,
+            run: Box::new(run),
+        }
     }
 }
 
@@ -385,6 +1018,8 @@ pub enum StorageError {
     DeserializationError(String),
     KeychainError(String),
     BiometricError(String),
+    DatabaseError(String),
+    Unsupported(String),
 }
 
 impl std::fmt::Display for StorageError {
@@ -397,8 +1032,17 @@ impl std::fmt::Display for StorageError {
             StorageError::DeserializationError(e) => write!(f, "Deserialization error: {}", e),
             StorageError::KeychainError(e) => write!(f, "Keychain error: {}", e),
             StorageError::BiometricError(e) => write!(f, "Biometric error: {}", e),
+            StorageError::DatabaseError(e) => write!(f, "Database error: {}", e),
+            StorageError::Unsupported(e) => write!(f, "Unsupported: {}", e),
         }
     }
 }
 
 impl std::error::Error for StorageError {}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for StorageError {
+    fn from(e: rusqlite::Error) -> Self {
+        StorageError::DatabaseError(e.to_string())
+    }
+}
@@ -0,0 +1,452 @@
+//! PhilJS Mobile Theming
+//!
+//! Semantic color tokens and a type scale that follow the system appearance
+//! and text size.
+//!
+//! Components ask for roles instead of RGB values:
+//!
+//! ```rust,ignore
+//! Container::new().background(Color::semantic(token::SURFACE))
+//! Text::styled("Settings", Typography::Title)
+//! ```
+//!
+//! Tokens resolve against the installed [`Theme`] for the current
+//! [`Appearance`]. The default theme matches the platform: iOS system colors
+//! and Dynamic Type sizes on iOS (and in desktop simulation), Material 3
+//! baseline colors and type on Android.
+//!
+//! The platform glue reports system changes through
+//! [`handle_appearance_change`] and [`handle_font_scale_change`]. Both
+//! update the [`theme`] signal, which the app subscribes to and re-renders on.
+
+use crate::runtime::Signal;
+use crate::{Color, FontWeight};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+/// Names of the built-in color tokens
+pub mod token {
+    /// Accent for buttons, links and selection
+    pub const PRIMARY: &str = "primary";
+    /// Text and icons on `primary`
+    pub const ON_PRIMARY: &str = "on_primary";
+    /// Screen background
+    pub const BACKGROUND: &str = "background";
+    /// Cards, sheets and grouped rows
+    pub const SURFACE: &str = "surface";
+    /// Primary text
+    pub const LABEL: &str = "label";
+    pub const SECONDARY_LABEL: &str = "secondary_label";
+    pub const TERTIARY_LABEL: &str = "tertiary_label";
+    /// Placeholder text in inputs
+    pub const PLACEHOLDER: &str = "placeholder";
+    /// Hairlines and dividers
+    pub const SEPARATOR: &str = "separator";
+    /// Text input fill
+    pub const FILL: &str = "fill";
+    pub const DESTRUCTIVE: &str = "destructive";
+    pub const SUCCESS: &str = "success";
+    pub const WARNING: &str = "warning";
+}
+
+/// Light or dark appearance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+
+/// Which appearance the app uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSchemePreference {
+    /// Follow the system setting
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+// ============================================================================
+// Palette and Type Scale
+// ============================================================================
+
+/// Colors for one appearance, by token
+#[derive(Debug, Clone, Default)]
+pub struct Palette {
+    colors: HashMap<String, Color>,
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn color(mut self, token: impl Into<String>, color: Color) -> Self {
+        self.colors.insert(token.into(), color);
+        self
+    }
+
+    pub fn get(&self, token: &str) -> Option<Color> {
+        self.colors.get(token).copied()
+    }
+
+    fn from_hex(entries: &[(&str, u32)]) -> Self {
+        entries
+            .iter()
+            .fold(Palette::new(), |palette, &(token, hex)| palette.color(token, Color::from_hex(hex)))
+    }
+}
+
+/// Text roles in the type scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Typography {
+    LargeTitle,
+    Title,
+    Title2,
+    Headline,
+    Body,
+    Callout,
+    Subheadline,
+    Footnote,
+    Caption,
+}
+
+/// A resolved font
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Font {
+    pub size: f32,
+    pub weight: FontWeight,
+    pub line_height: f32,
+}
+
+impl Font {
+    pub const fn new(size: f32, weight: FontWeight, line_height: f32) -> Self {
+        Font { size, weight, line_height }
+    }
+
+    fn scaled(self, scale: f32) -> Self {
+        Font {
+            size: (self.size * scale).round(),
+            line_height: (self.line_height * scale).round(),
+            ..self
+        }
+    }
+}
+
+/// Fonts for each [`Typography`] role at the default text size
+#[derive(Debug, Clone)]
+pub struct TypeScale {
+    fonts: HashMap<Typography, Font>,
+}
+
+impl TypeScale {
+    /// iOS Dynamic Type sizes at the Large (default) setting
+    pub fn cupertino() -> Self {
+        use FontWeight::*;
+        TypeScale::from_entries(&[
+            (Typography::LargeTitle, Font::new(34.0, Bold, 41.0)),
+            (Typography::Title, Font::new(28.0, Bold, 34.0)),
+            (Typography::Title2, Font::new(22.0, SemiBold, 28.0)),
+            (Typography::Headline, Font::new(17.0, SemiBold, 22.0)),
+            (Typography::Body, Font::new(17.0, Regular, 22.0)),
+            (Typography::Callout, Font::new(16.0, Regular, 21.0)),
+            (Typography::Subheadline, Font::new(15.0, Regular, 20.0)),
+            (Typography::Footnote, Font::new(13.0, Regular, 18.0)),
+            (Typography::Caption, Font::new(12.0, Regular, 16.0)),
+        ])
+    }
+
+    /// Material 3 type scale
+    pub fn material() -> Self {
+        use FontWeight::*;
+        TypeScale::from_entries(&[
+            (Typography::LargeTitle, Font::new(36.0, Regular, 44.0)),
+            (Typography::Title, Font::new(28.0, Regular, 36.0)),
+            (Typography::Title2, Font::new(22.0, Regular, 28.0)),
+            (Typography::Headline, Font::new(16.0, Medium, 24.0)),
+            (Typography::Body, Font::new(16.0, Regular, 24.0)),
+            (Typography::Callout, Font::new(14.0, Medium, 20.0)),
+            (Typography::Subheadline, Font::new(14.0, Regular, 20.0)),
+            (Typography::Footnote, Font::new(12.0, Regular, 16.0)),
+            (Typography::Caption, Font::new(11.0, Medium, 16.0)),
+        ])
+    }
+
+    fn from_entries(entries: &[(Typography, Font)]) -> Self {
+        TypeScale {
+            fonts: entries.iter().copied().collect(),
+        }
+    }
+
+    pub fn font(mut self, style: Typography, font: Font) -> Self {
+        self.fonts.insert(style, font);
+        self
+    }
+
+    pub fn get(&self, style: Typography) -> Font {
+        self.fonts
+            .get(&style)
+            .copied()
+            .unwrap_or(Font::new(17.0, FontWeight::Regular, 22.0))
+    }
+}
+
+// ============================================================================
+// Theme
+// ============================================================================
+
+/// Colors for both appearances plus a type scale
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub light: Palette,
+    pub dark: Palette,
+    pub type_scale: TypeScale,
+    /// Upper bound on the system text size multiplier, so large accessibility
+    /// sizes don't break fixed layouts
+    pub max_font_scale: f32,
+}
+
+impl Theme {
+    /// The default theme for the platform the app runs on
+    pub fn platform() -> Self {
+        if cfg!(target_os = "android") {
+            Self::material()
+        } else {
+            Self::cupertino()
+        }
+    }
+
+    /// iOS system colors and Dynamic Type
+    pub fn cupertino() -> Self {
+        Theme {
+            light: Palette::from_hex(&[
+                (token::PRIMARY, 0x007AFF),
+                (token::ON_PRIMARY, 0xFFFFFF),
+                (token::BACKGROUND, 0xFFFFFF),
+                (token::SURFACE, 0xF2F2F7),
+                (token::LABEL, 0x000000),
+                (token::SECONDARY_LABEL, 0x6C6C70),
+                (token::TERTIARY_LABEL, 0x8E8E93),
+                (token::PLACEHOLDER, 0xA3A3A8),
+                (token::SEPARATOR, 0xC6C6C8),
+                (token::FILL, 0xFFFFFF),
+                (token::DESTRUCTIVE, 0xFF3B30),
+                (token::SUCCESS, 0x34C759),
+                (token::WARNING, 0xFF9500),
+            ]),
+            dark: Palette::from_hex(&[
+                (token::PRIMARY, 0x0A84FF),
+                (token::ON_PRIMARY, 0xFFFFFF),
+                (token::BACKGROUND, 0x000000),
+                (token::SURFACE, 0x1C1C1E),
+                (token::LABEL, 0xFFFFFF),
+                (token::SECONDARY_LABEL, 0xAEAEB2),
+                (token::TERTIARY_LABEL, 0x8E8E93),
+                (token::PLACEHOLDER, 0x636366),
+                (token::SEPARATOR, 0x38383A),
+                (token::FILL, 0x1C1C1E),
+                (token::DESTRUCTIVE, 0xFF453A),
+                (token::SUCCESS, 0x30D158),
+                (token::WARNING, 0xFF9F0A),
+            ]),
+            type_scale: TypeScale::cupertino(),
+            max_font_scale: 3.0,
+        }
+    }
+
+    /// Material 3 baseline colors and type
+    pub fn material() -> Self {
+        Theme {
+            light: Palette::from_hex(&[
+                (token::PRIMARY, 0x6750A4),
+                (token::ON_PRIMARY, 0xFFFFFF),
+                (token::BACKGROUND, 0xFFFBFE),
+                (token::SURFACE, 0xF3EDF7),
+                (token::LABEL, 0x1C1B1F),
+                (token::SECONDARY_LABEL, 0x49454F),
+                (token::TERTIARY_LABEL, 0x79747E),
+                (token::PLACEHOLDER, 0x79747E),
+                (token::SEPARATOR, 0xCAC4D0),
+                (token::FILL, 0xE7E0EC),
+                (token::DESTRUCTIVE, 0xB3261E),
+                (token::SUCCESS, 0x386A20),
+                (token::WARNING, 0x7D5700),
+            ]),
+            dark: Palette::from_hex(&[
+                (token::PRIMARY, 0xD0BCFF),
+                (token::ON_PRIMARY, 0x381E72),
+                (token::BACKGROUND, 0x1C1B1F),
+                (token::SURFACE, 0x2B2930),
+                (token::LABEL, 0xE6E1E5),
+                (token::SECONDARY_LABEL, 0xCAC4D0),
+                (token::TERTIARY_LABEL, 0x938F99),
+                (token::PLACEHOLDER, 0x938F99),
+                (token::SEPARATOR, 0x49454F),
+                (token::FILL, 0x49454F),
+                (token::DESTRUCTIVE, 0xF2B8B5),
+                (token::SUCCESS, 0x9CD67D),
+                (token::WARNING, 0xF8BD42),
+            ]),
+            type_scale: TypeScale::material(),
+            max_font_scale: 2.0,
+        }
+    }
+
+    /// Set a token for both appearances
+    pub fn color(mut self, token: &str, light: Color, dark: Color) -> Self {
+        self.light = self.light.color(token, light);
+        self.dark = self.dark.color(token, dark);
+        self
+    }
+
+    pub fn font(mut self, style: Typography, font: Font) -> Self {
+        self.type_scale = self.type_scale.font(style, font);
+        self
+    }
+
+    /// A token's color; dark palettes fall back to the light one
+    pub fn resolve(&self, token: &str, appearance: Appearance) -> Option<Color> {
+        match appearance {
+            Appearance::Light => self.light.get(token),
+            Appearance::Dark => self.dark.get(token).or_else(|| self.light.get(token)),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::platform()
+    }
+}
+
+// ============================================================================
+// Current Theme
+// ============================================================================
+
+/// The installed theme and the environment it resolves against
+#[derive(Debug, Clone)]
+pub struct ThemeState {
+    pub theme: Arc<Theme>,
+    pub preference: ColorSchemePreference,
+    /// What the system is set to, regardless of `preference`
+    pub system_appearance: Appearance,
+    /// System text size multiplier, 1.0 at the default size
+    pub system_font_scale: f32,
+}
+
+impl ThemeState {
+    /// The appearance in effect
+    pub fn appearance(&self) -> Appearance {
+        match self.preference {
+            ColorSchemePreference::System => self.system_appearance,
+            ColorSchemePreference::Light => Appearance::Light,
+            ColorSchemePreference::Dark => Appearance::Dark,
+        }
+    }
+
+    pub fn is_dark(&self) -> bool {
+        self.appearance() == Appearance::Dark
+    }
+
+    /// Text size multiplier in effect, capped by the theme
+    pub fn font_scale(&self) -> f32 {
+        self.system_font_scale.clamp(0.5, self.theme.max_font_scale)
+    }
+
+    /// Resolve a color token
+    ///
+    /// Unknown tokens come back magenta in debug builds, so they stand out,
+    /// and black in release builds.
+    pub fn color(&self, token: &str) -> Color {
+        self.theme.resolve(token, self.appearance()).unwrap_or(if cfg!(debug_assertions) {
+            Color::rgb(1.0, 0.0, 1.0)
+        } else {
+            Color::BLACK
+        })
+    }
+
+    /// A type scale role at the current text size
+    pub fn font(&self, style: Typography) -> Font {
+        self.theme.type_scale.get(style).scaled(self.font_scale())
+    }
+}
+
+/// The theme and environment, as a signal
+///
+/// Resolved colors and fonts are plain values, so subscribe and re-render
+/// to follow appearance and text size changes.
+pub fn theme() -> Signal<ThemeState> {
+    static STATE: OnceLock<Signal<ThemeState>> = OnceLock::new();
+    STATE
+        .get_or_init(|| {
+            Signal::new(ThemeState {
+                theme: Arc::new(Theme::platform()),
+                preference: ColorSchemePreference::System,
+                system_appearance: Appearance::Light,
+                system_font_scale: 1.0,
+            })
+        })
+        .clone()
+}
+
+/// Current theme state
+pub fn current() -> ThemeState {
+    theme().get()
+}
+
+/// Whether dark colors are in effect
+pub fn is_dark() -> bool {
+    current().is_dark()
+}
+
+/// Install a theme
+pub fn set_theme(new_theme: Theme) {
+    theme().update(|state| state.theme = Arc::new(new_theme));
+}
+
+/// Force light or dark, or follow the system again
+///
+/// Native controls and system UI follow the override too.
+pub fn set_color_scheme(preference: ColorSchemePreference) {
+    theme().update(|state| state.preference = preference);
+
+    #[cfg(target_os = "ios")]
+    crate::ios::set_override_user_interface_style(match preference {
+        ColorSchemePreference::System => crate::ios::UserInterfaceStyle::Unspecified,
+        ColorSchemePreference::Light => crate::ios::UserInterfaceStyle::Light,
+        ColorSchemePreference::Dark => crate::ios::UserInterfaceStyle::Dark,
+    });
+
+    #[cfg(target_os = "android")]
+    crate::android::set_night_mode(match preference {
+        ColorSchemePreference::System => crate::android::NightMode::FollowSystem,
+        ColorSchemePreference::Light => crate::android::NightMode::No,
+        ColorSchemePreference::Dark => crate::android::NightMode::Yes,
+    });
+}
+
+/// Report the system appearance (called by the platform glue)
+pub fn handle_appearance_change(dark: bool) {
+    let appearance = if dark { Appearance::Dark } else { Appearance::Light };
+    let signal = theme();
+    if signal.get().system_appearance != appearance {
+        signal.update(|state| state.system_appearance = appearance);
+    }
+}
+
+/// Report the system text size multiplier (called by the platform glue)
+pub fn handle_font_scale_change(scale: f32) {
+    let signal = theme();
+    if scale > 0.0 && signal.get().system_font_scale != scale {
+        signal.update(|state| state.system_font_scale = scale);
+    }
+}
+
+impl Color {
+    /// A color token from the current theme, e.g. `Color::semantic("surface")`
+    ///
+    /// See [`token`] for the built-in names.
+    pub fn semantic(token: &str) -> Color {
+        current().color(token)
+    }
+}
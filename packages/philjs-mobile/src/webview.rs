@@ -0,0 +1,643 @@
+//! PhilJS Mobile WebView
+//!
+//! Embedded web content (WKWebView on iOS, android.webkit.WebView on
+//! Android) with a message bridge between the page and Rust.
+//!
+//! Every page gets [`BRIDGE_SCRIPT`], which defines `window.philjs`:
+//!
+//! ```js
+//! philjs.postMessage({ type: "checkout", total: 42 });
+//! philjs.onMessage(data => render(data));
+//!
+//! const theme = philjs.signal("theme");
+//! theme.subscribe(value => document.body.className = value);
+//! theme.set("dark");
+//! ```
+//!
+//! On the Rust side, [`WebView::on_message`] receives what the page posts,
+//! [`WebViewController::post_message`] sends data back, and
+//! [`WebView::bind_signal`] keeps a page signal and a [`Signal`] in sync in
+//! both directions.
+//!
+//! The platform glue reports page events through the `handle_*` functions,
+//! keyed by [`WebViewController::id`].
+
+use crate::runtime::Signal;
+use crate::{Constraints, NativeComponent, NativeView, RenderContext, Size};
+use futures::channel::oneshot;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Script injected at document start in the main frame of every page
+pub const BRIDGE_SCRIPT: &str = r#"(function () {
+  if (window.philjs) return;
+  var listeners = [];
+  var signals = {};
+  function send(envelope) {
+    var json = JSON.stringify(envelope);
+    if (window.webkit && window.webkit.messageHandlers && window.webkit.messageHandlers.philjs) {
+      window.webkit.messageHandlers.philjs.postMessage(json);
+    } else if (window.PhilJsBridge) {
+      window.PhilJsBridge.postMessage(json);
+    }
+  }
+  function state(name) {
+    return signals[name] || (signals[name] = { value: undefined, subscribers: [] });
+  }
+  function notify(s) {
+    s.subscribers.forEach(function (cb) { cb(s.value); });
+  }
+  window.philjs = {
+    postMessage: function (data) { send({ kind: "message", data: data }); },
+    onMessage: function (cb) { listeners.push(cb); },
+    signal: function (name) {
+      var s = state(name);
+      return {
+        get: function () { return s.value; },
+        set: function (value) {
+          s.value = value;
+          notify(s);
+          send({ kind: "signal", name: name, value: value });
+        },
+        subscribe: function (cb) { s.subscribers.push(cb); cb(s.value); }
+      };
+    },
+    __receive: function (envelope) {
+      if (envelope.kind === "message") {
+        listeners.forEach(function (cb) { cb(envelope.data); });
+      } else if (envelope.kind === "signal") {
+        var s = state(envelope.name);
+        s.value = envelope.value;
+        notify(s);
+      }
+    }
+  };
+  send({ kind: "ready" });
+})();"#;
+
+// ============================================================================
+// Content and Navigation
+// ============================================================================
+
+/// What a web view shows
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebViewSource {
+    Url(String),
+    /// Inline HTML; relative links and same-origin checks use `base_url`
+    Html { html: String, base_url: Option<String> },
+}
+
+/// What started a navigation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationType {
+    LinkActivated,
+    FormSubmitted,
+    BackForward,
+    Reload,
+    Redirect,
+    Other,
+}
+
+/// A navigation the page is about to make
+#[derive(Debug, Clone)]
+pub struct NavigationRequest {
+    pub url: String,
+    /// False for iframes
+    pub main_frame: bool,
+    pub navigation_type: NavigationType,
+}
+
+/// Whether a navigation may go ahead
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationDecision {
+    Allow,
+    Cancel,
+}
+
+/// Page load progress
+#[derive(Debug, Clone)]
+pub enum LoadEvent {
+    Started { url: String },
+    Finished { url: String },
+    Failed { url: String, message: String },
+}
+
+/// Data posted by the page with `philjs.postMessage`
+#[derive(Debug, Clone)]
+pub struct WebMessage {
+    pub data: Value,
+}
+
+impl WebMessage {
+    /// Decode the message into a typed value
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, WebViewError> {
+        serde_json::from_value(self.data.clone()).map_err(|e| WebViewError::Serialization(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Controller
+// ============================================================================
+
+type MessageHandler = Arc<dyn Fn(WebMessage) + Send + Sync>;
+type NavigationHandler = Arc<dyn Fn(&NavigationRequest) -> NavigationDecision + Send + Sync>;
+type InterceptHandler = Arc<dyn Fn(&str) + Send + Sync>;
+type LoadHandler = Arc<dyn Fn(&LoadEvent) + Send + Sync>;
+
+/// A page signal kept in sync with a Rust [`Signal`]
+struct SignalBinding {
+    name: String,
+    /// Set while a page update is applied, so it isn't echoed back
+    applying: Arc<AtomicBool>,
+    apply: Box<dyn Fn(&Value) + Send + Sync>,
+    current: Box<dyn Fn() -> Option<Value> + Send + Sync>,
+}
+
+#[derive(Default)]
+struct Handlers {
+    on_message: Option<MessageHandler>,
+    on_navigation: Option<NavigationHandler>,
+    intercepts: Vec<(String, InterceptHandler)>,
+    on_load: Option<LoadHandler>,
+}
+
+struct ControllerState {
+    source: WebViewSource,
+    url: Option<String>,
+    /// The current page has loaded the bridge
+    ready: bool,
+    /// Envelopes posted before the bridge was ready
+    outbox: Vec<Value>,
+    handlers: Handlers,
+    bindings: Vec<Arc<SignalBinding>>,
+}
+
+struct ControllerInner {
+    id: u64,
+    state: Mutex<ControllerState>,
+}
+
+/// Handle for driving a web view from Rust
+///
+/// Clones refer to the same web view.
+#[derive(Clone)]
+pub struct WebViewController {
+    inner: Arc<ControllerInner>,
+}
+
+static NEXT_WEB_VIEW: AtomicU64 = AtomicU64::new(1);
+static WEB_VIEWS: Mutex<Option<HashMap<u64, Weak<ControllerInner>>>> = Mutex::new(None);
+
+impl WebViewController {
+    fn new(source: WebViewSource) -> Self {
+        let inner = Arc::new(ControllerInner {
+            id: NEXT_WEB_VIEW.fetch_add(1, Ordering::Relaxed),
+            state: Mutex::new(ControllerState {
+                source,
+                url: None,
+                ready: false,
+                outbox: Vec::new(),
+                handlers: Handlers::default(),
+                bindings: Vec::new(),
+            }),
+        });
+
+        let mut views = WEB_VIEWS.lock().unwrap();
+        let views = views.get_or_insert_with(HashMap::new);
+        views.retain(|_, view| view.strong_count() > 0);
+        views.insert(inner.id, Arc::downgrade(&inner));
+        WebViewController { inner }
+    }
+
+    fn find(id: u64) -> Option<Self> {
+        let views = WEB_VIEWS.lock().unwrap();
+        let inner = views.as_ref()?.get(&id)?.upgrade()?;
+        Some(WebViewController { inner })
+    }
+
+    /// Identifier the platform glue passes to the `handle_*` functions
+    pub fn id(&self) -> u64 {
+        self.inner.id
+    }
+
+    /// Content to load when the view is created
+    pub fn source(&self) -> WebViewSource {
+        self.inner.state.lock().unwrap().source.clone()
+    }
+
+    /// URL of the last page that finished loading
+    pub fn url(&self) -> Option<String> {
+        self.inner.state.lock().unwrap().url.clone()
+    }
+
+    /// Send data to the page's `philjs.onMessage` listeners
+    ///
+    /// Messages sent while a page is loading are delivered once its bridge
+    /// is ready.
+    pub fn post_message<T: Serialize>(&self, data: &T) -> Result<(), WebViewError> {
+        let data = serde_json::to_value(data).map_err(|e| WebViewError::Serialization(e.to_string()))?;
+        self.send(json!({ "kind": "message", "data": data }));
+        Ok(())
+    }
+
+    /// Run a script in the page and return its result as JSON
+    pub fn evaluate_js(&self, script: &str) -> impl Future<Output = Result<Value, WebViewError>> {
+        let (request, rx) = begin_script();
+        native::evaluate(self.inner.id, script, Some(request));
+        async move {
+            let result = rx.await.map_err(|_| WebViewError::Closed)??;
+            // WKWebView and evaluateJavascript both hand back JSON text
+            Ok(serde_json::from_str(&result).unwrap_or(Value::String(result)))
+        }
+    }
+
+    /// Navigate to new content
+    pub fn load(&self, source: WebViewSource) {
+        {
+            let mut state = self.inner.state.lock().unwrap();
+            state.source = source.clone();
+            state.ready = false;
+        }
+        native::load(self.inner.id, &source);
+    }
+
+    pub fn reload(&self) {
+        native::reload(self.inner.id);
+    }
+
+    pub fn go_back(&self) {
+        native::go_back(self.inner.id);
+    }
+
+    pub fn go_forward(&self) {
+        native::go_forward(self.inner.id);
+    }
+
+    /// Deliver an envelope now, or once the bridge is ready
+    fn send(&self, envelope: Value) {
+        let ready = {
+            let mut state = self.inner.state.lock().unwrap();
+            if !state.ready {
+                state.outbox.push(envelope.clone());
+            }
+            state.ready
+        };
+        if ready {
+            deliver(self.inner.id, &envelope);
+        }
+    }
+
+    fn bind<T>(&self, name: String, signal: &Signal<T>)
+    where
+        T: Clone + Serialize + DeserializeOwned + Send + 'static,
+    {
+        let applying = Arc::new(AtomicBool::new(false));
+        let binding = Arc::new(SignalBinding {
+            name: name.clone(),
+            applying: Arc::clone(&applying),
+            apply: {
+                let signal = signal.clone();
+                Box::new(move |value| {
+                    if let Ok(value) = serde_json::from_value::<T>(value.clone()) {
+                        signal.set(value);
+                    }
+                })
+            },
+            current: {
+                let signal = signal.clone();
+                Box::new(move || serde_json::to_value(signal.get()).ok())
+            },
+        });
+        self.inner.state.lock().unwrap().bindings.push(binding);
+
+        // Weak so a long-lived signal doesn't keep the web view alive
+        let controller = Arc::downgrade(&self.inner);
+        signal.subscribe(move |value| {
+            if applying.load(Ordering::SeqCst) {
+                return;
+            }
+            let (Some(inner), Ok(value)) = (controller.upgrade(), serde_json::to_value(value)) else {
+                return;
+            };
+            WebViewController { inner }.send(json!({ "kind": "signal", "name": name, "value": value }));
+        });
+    }
+
+    fn handlers<R>(&self, f: impl FnOnce(&Handlers) -> R) -> R {
+        f(&self.inner.state.lock().unwrap().handlers)
+    }
+}
+
+fn deliver(webview: u64, envelope: &Value) {
+    native::evaluate(webview, &format!("window.philjs && window.philjs.__receive({})", envelope), None);
+}
+
+// ============================================================================
+// Component
+// ============================================================================
+
+/// Embedded web content
+///
+/// ```rust,ignore
+/// let token = create_signal(None::<String>);
+/// WebView::url("https://example.com/login")
+///     .intercept("myapp://oauth", move |url| token.set(parse_code(url)))
+///     .on_message(|message| println!("page says {:?}", message.data))
+/// ```
+pub struct WebView {
+    controller: WebViewController,
+}
+
+impl WebView {
+    pub fn new(source: WebViewSource) -> Self {
+        WebView {
+            controller: WebViewController::new(source),
+        }
+    }
+
+    pub fn url(url: impl Into<String>) -> Self {
+        Self::new(WebViewSource::Url(url.into()))
+    }
+
+    pub fn html(html: impl Into<String>) -> Self {
+        Self::new(WebViewSource::Html {
+            html: html.into(),
+            base_url: None,
+        })
+    }
+
+    /// Base URL for HTML content
+    pub fn base_url(self, base: impl Into<String>) -> Self {
+        if let WebViewSource::Html { base_url, .. } = &mut self.controller.inner.state.lock().unwrap().source {
+            *base_url = Some(base.into());
+        }
+        self
+    }
+
+    /// Handle data the page posts with `philjs.postMessage`
+    pub fn on_message(self, handler: impl Fn(WebMessage) + Send + Sync + 'static) -> Self {
+        self.controller.inner.state.lock().unwrap().handlers.on_message = Some(Arc::new(handler));
+        self
+    }
+
+    /// Decide whether each navigation may go ahead
+    pub fn on_navigation(
+        self,
+        handler: impl Fn(&NavigationRequest) -> NavigationDecision + Send + Sync + 'static,
+    ) -> Self {
+        self.controller.inner.state.lock().unwrap().handlers.on_navigation = Some(Arc::new(handler));
+        self
+    }
+
+    /// Cancel navigations to URLs starting with `prefix` and pass them to
+    /// `handler`, e.g. an OAuth redirect URI carrying the authorization code
+    pub fn intercept(self, prefix: impl Into<String>, handler: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.controller
+            .inner
+            .state
+            .lock()
+            .unwrap()
+            .handlers
+            .intercepts
+            .push((prefix.into(), Arc::new(handler)));
+        self
+    }
+
+    /// Observe page loads
+    pub fn on_load(self, handler: impl Fn(&LoadEvent) + Send + Sync + 'static) -> Self {
+        self.controller.inner.state.lock().unwrap().handlers.on_load = Some(Arc::new(handler));
+        self
+    }
+
+    /// Keep the page's `philjs.signal(name)` and `signal` in sync
+    ///
+    /// The page receives the current value once its bridge is ready; after
+    /// that a change on either side is sent to the other.
+    pub fn bind_signal<T>(self, name: impl Into<String>, signal: &Signal<T>) -> Self
+    where
+        T: Clone + Serialize + DeserializeOwned + Send + 'static,
+    {
+        self.controller.bind(name.into(), signal);
+        self
+    }
+
+    pub fn controller(&self) -> WebViewController {
+        self.controller.clone()
+    }
+}
+
+impl NativeComponent for WebView {
+    fn render(&self, ctx: &mut RenderContext) -> NativeView {
+        ctx.create_web_view(&self.controller)
+    }
+
+    fn update(&self, _ctx: &mut RenderContext) {
+        // The page owns its content; changes go through the controller
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        // Web content scrolls inside the view, so take all the space offered
+        let width = if constraints.max_width.is_finite() { constraints.max_width } else { constraints.min_width };
+        let height = if constraints.max_height.is_finite() { constraints.max_height } else { constraints.min_height };
+        Size::new(width, height)
+    }
+}
+
+// ============================================================================
+// Platform Callbacks
+// ============================================================================
+
+/// A message from the page's bridge (called by the platform glue)
+pub fn handle_webview_message(webview: u64, json: &str) {
+    let Some(controller) = WebViewController::find(webview) else {
+        return;
+    };
+    let Ok(envelope) = serde_json::from_str::<Value>(json) else {
+        return;
+    };
+
+    match envelope["kind"].as_str() {
+        Some("ready") => {
+            let (outbox, bindings) = {
+                let mut state = controller.inner.state.lock().unwrap();
+                state.ready = true;
+                (std::mem::take(&mut state.outbox), state.bindings.clone())
+            };
+            for binding in bindings {
+                if let Some(value) = (binding.current)() {
+                    deliver(webview, &json!({ "kind": "signal", "name": binding.name, "value": value }));
+                }
+            }
+            for envelope in outbox {
+                deliver(webview, &envelope);
+            }
+        }
+        Some("message") => {
+            if let Some(handler) = controller.handlers(|h| h.on_message.clone()) {
+                handler(WebMessage {
+                    data: envelope["data"].clone(),
+                });
+            }
+        }
+        Some("signal") => {
+            let name = envelope["name"].as_str().unwrap_or_default();
+            let binding = {
+                let state = controller.inner.state.lock().unwrap();
+                state.bindings.iter().find(|binding| binding.name == name).cloned()
+            };
+            if let Some(binding) = binding {
+                binding.applying.store(true, Ordering::SeqCst);
+                (binding.apply)(&envelope["value"]);
+                binding.applying.store(false, Ordering::SeqCst);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Ask whether a navigation may go ahead (called by the platform glue)
+///
+/// Unknown web views allow everything.
+pub fn handle_navigation_request(webview: u64, request: &NavigationRequest) -> NavigationDecision {
+    let Some(controller) = WebViewController::find(webview) else {
+        return NavigationDecision::Allow;
+    };
+
+    let intercept = controller.handlers(|h| {
+        h.intercepts
+            .iter()
+            .find(|(prefix, _)| request.url.starts_with(prefix.as_str()))
+            .map(|(_, handler)| Arc::clone(handler))
+    });
+    if let Some(handler) = intercept {
+        handler(&request.url);
+        return NavigationDecision::Cancel;
+    }
+
+    match controller.handlers(|h| h.on_navigation.clone()) {
+        Some(handler) => handler(request),
+        None => NavigationDecision::Allow,
+    }
+}
+
+/// Report page load progress (called by the platform glue)
+pub fn handle_load_event(webview: u64, event: LoadEvent) {
+    let Some(controller) = WebViewController::find(webview) else {
+        return;
+    };
+    {
+        let mut state = controller.inner.state.lock().unwrap();
+        match &event {
+            // The new page has to load the bridge again
+            LoadEvent::Started { .. } => state.ready = false,
+            LoadEvent::Finished { url } => state.url = Some(url.clone()),
+            LoadEvent::Failed { .. } => {}
+        }
+    }
+    if let Some(handler) = controller.handlers(|h| h.on_load.clone()) {
+        handler(&event);
+    }
+}
+
+static NEXT_SCRIPT: AtomicU64 = AtomicU64::new(1);
+static SCRIPTS: Mutex<Option<HashMap<u64, oneshot::Sender<Result<String, WebViewError>>>>> = Mutex::new(None);
+
+fn begin_script() -> (u64, oneshot::Receiver<Result<String, WebViewError>>) {
+    let id = NEXT_SCRIPT.fetch_add(1, Ordering::Relaxed);
+    let (tx, rx) = oneshot::channel();
+    SCRIPTS.lock().unwrap().get_or_insert_with(HashMap::new).insert(id, tx);
+    (id, rx)
+}
+
+/// Finish a [`WebViewController::evaluate_js`] call (called by the platform
+/// glue with the result as JSON text, or the script error)
+pub fn handle_script_result(request: u64, result: Result<String, String>) {
+    finish_script(request, result.map_err(WebViewError::Script));
+}
+
+fn finish_script(request: u64, result: Result<String, WebViewError>) {
+    let tx = SCRIPTS.lock().unwrap().as_mut().and_then(|scripts| scripts.remove(&request));
+    if let Some(tx) = tx {
+        let _ = tx.send(result);
+    }
+}
+
+/// WebView error
+#[derive(Debug, Clone)]
+pub enum WebViewError {
+    /// The script threw or couldn't be run
+    Script(String),
+    Serialization(String),
+    /// The web view went away before answering
+    Closed,
+    NotAvailable,
+}
+
+impl std::fmt::Display for WebViewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebViewError::Script(msg) => write!(f, "Script error: {}", msg),
+            WebViewError::Serialization(msg) => write!(f, "Serialization error: {}", msg),
+            WebViewError::Closed => write!(f, "Web view closed"),
+            WebViewError::NotAvailable => write!(f, "Web view not available"),
+        }
+    }
+}
+
+impl std::error::Error for WebViewError {}
+
+// ============================================================================
+// Platform Calls
+// ============================================================================
+
+/// Native calls, keyed by web view id. The glue keeps the platform view
+/// created by the renderer for each id.
+mod native {
+    use super::{WebViewError, WebViewSource};
+
+    /// Run a script; with a request id, report the result through
+    /// `handle_script_result`
+    pub fn evaluate(webview: u64, script: &str, request: Option<u64>) {
+        #[cfg(any(target_os = "ios", target_os = "android"))]
+        {
+            // iOS: webView.evaluateJavaScript(script) { result, error in
+            //     handle_script_result(request, JSONSerialization(result) or error) }
+            // Android: webView.evaluateJavascript(script) { json -> handle_script_result(request, Ok(json)) }
+            let _ = (webview, script, request);
+        }
+        #[cfg(not(any(target_os = "ios", target_os = "android")))]
+        {
+            let _ = (webview, script);
+            if let Some(request) = request {
+                super::finish_script(request, Err(WebViewError::NotAvailable));
+            }
+        }
+    }
+
+    pub fn load(webview: u64, source: &WebViewSource) {
+        // iOS: webView.load(URLRequest(url)) / loadHTMLString(html, baseURL: base_url)
+        // Android: webView.loadUrl(url) / loadDataWithBaseURL(base_url, html, "text/html", "utf-8", null)
+        let _ = (webview, source);
+    }
+
+    pub fn reload(webview: u64) {
+        // iOS: webView.reload(); Android: webView.reload()
+        let _ = webview;
+    }
+
+    pub fn go_back(webview: u64) {
+        // iOS: if webView.canGoBack { webView.goBack() }
+        // Android: if (webView.canGoBack()) webView.goBack()
+        let _ = webview;
+    }
+
+    pub fn go_forward(webview: u64) {
+        // iOS: webView.goForward(); Android: webView.goForward()
+        let _ = webview;
+    }
+}
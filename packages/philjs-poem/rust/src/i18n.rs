@@ -0,0 +1,282 @@
+//! Locale negotiation for PhilJS Poem
+//!
+//! [`LocaleMiddleware`] resolves each request's locale with
+//! [`Translations::resolve`]: a path prefix (`/fr/about`), then a cookie,
+//! then `Accept-Language`. The resulting [`RequestLocale`] is provided to
+//! components, so `t!` and `philjs::i18n::current_locale` follow it, and to
+//! handlers through `Data<&RequestLocale>`.
+//!
+//! ```rust,ignore
+//! let translations = Translations::new("en")
+//!     .with_fluent("en", include_str!("../locales/en/messages.ftl"))?
+//!     .with_fluent("fr", include_str!("../locales/fr/messages.ftl"))?;
+//!
+//! #[component]
+//! fn Nav() -> impl IntoView {
+//!     let locale = current_locale().unwrap_or_default();
+//!     view! { <a href={locale_url(&locale, "/about")}>{t!("about")}</a> }
+//! }
+//!
+//! #[handler]
+//! fn about(Data(locale): Data<&RequestLocale>) -> PhilJsHtml {
+//!     let title = locale.translate("about", &[]);
+//!     render_document(&title, || view! { <Nav /> })
+//! }
+//!
+//! let app = Route::new()
+//!     .at("/about", get(about))
+//!     .with(LocaleMiddleware::new(translations));
+//! ```
+//!
+//! With path prefixes enabled, `/fr/about` is routed as `/about`.
+
+use philjs::i18n::{strip_locale_prefix, LocaleRequest, LOCALE_COOKIE};
+use poem::http::{header, Uri};
+use poem::{Endpoint, Middleware, Request};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pub use philjs::i18n::{RequestLocale, Translations};
+
+/// `path` prefixed with `locale`, e.g. `locale_url("fr", "/about")` is `/fr/about`
+pub fn locale_url(locale: &str, path: &str) -> String {
+    let path = path.trim_start_matches('/');
+    if path.is_empty() {
+        format!("/{}", locale)
+    } else {
+        format!("/{}/{}", locale, path)
+    }
+}
+
+/// Middleware negotiating the locale and providing it to components
+#[derive(Debug, Clone)]
+pub struct LocaleMiddleware {
+    translations: Translations,
+    cookie: Option<String>,
+    path_prefix: bool,
+}
+
+impl LocaleMiddleware {
+    /// Negotiate among the locales `translations` has catalogs for
+    pub fn new(translations: Translations) -> Self {
+        Self {
+            translations,
+            cookie: Some(LOCALE_COOKIE.to_string()),
+            path_prefix: true,
+        }
+    }
+
+    /// Read the chosen locale from this cookie (default `philjs-locale`)
+    pub fn cookie(mut self, name: impl Into<String>) -> Self {
+        self.cookie = Some(name.into());
+        self
+    }
+
+    /// Ignore cookies
+    pub fn no_cookie(mut self) -> Self {
+        self.cookie = None;
+        self
+    }
+
+    /// Whether a leading `/{locale}` path segment selects the locale
+    pub fn path_prefix(mut self, enabled: bool) -> Self {
+        self.path_prefix = enabled;
+        self
+    }
+
+    /// The catalogs shared by every request
+    pub fn translations(&self) -> &Translations {
+        &self.translations
+    }
+
+    /// Negotiate a request's locale: path prefix, cookie, `Accept-Language`, default
+    pub fn resolve(&self, req: &Request) -> RequestLocale {
+        let cookie = self.cookie.as_deref().and_then(|name| {
+            req.headers()
+                .get_all(header::COOKIE)
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .flat_map(|v| v.split(';'))
+                .filter_map(|pair| pair.trim().split_once('='))
+                .find_map(|(key, value)| (key == name).then_some(value))
+        });
+        self.translations.resolve(&LocaleRequest {
+            path: if self.path_prefix { req.uri().path() } else { "" },
+            cookie,
+            accept_language: req
+                .headers()
+                .get(header::ACCEPT_LANGUAGE)
+                .and_then(|v| v.to_str().ok()),
+        })
+    }
+
+    /// `path` in every locale, e.g. for `hreflang` links
+    pub fn alternates(&self, path: &str) -> Vec<(String, String)> {
+        self.translations
+            .locales()
+            .into_iter()
+            .map(|locale| {
+                let url = locale_url(&locale, path);
+                (locale, url)
+            })
+            .collect()
+    }
+
+    /// The path and query without a locale prefix, if the path has one
+    fn strip_prefix(&self, uri: &Uri) -> Option<String> {
+        if !self.path_prefix {
+            return None;
+        }
+        let locales = self.translations.locales();
+        let (Some(_), rest) = strip_locale_prefix(uri.path(), &locales) else {
+            return None;
+        };
+        Some(match uri.query() {
+            Some(query) => format!("{}?{}", rest, query),
+            None => rest.to_string(),
+        })
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for LocaleMiddleware {
+    type Output = LocaleEndpoint<E>;
+
+    fn transform(&self, inner: E) -> Self::Output {
+        LocaleEndpoint {
+            inner,
+            config: self.clone(),
+        }
+    }
+}
+
+/// Endpoint wrapped by [`LocaleMiddleware`]
+pub struct LocaleEndpoint<E> {
+    inner: E,
+    config: LocaleMiddleware,
+}
+
+impl<E: Endpoint> Endpoint for LocaleEndpoint<E> {
+    type Output = E::Output;
+
+    async fn call(&self, mut req: Request) -> poem::Result<Self::Output> {
+        let locale = self.config.resolve(&req);
+
+        // Route `/fr/about` as `/about`
+        if let Some(path_and_query) = self.config.strip_prefix(req.uri()) {
+            let mut parts = req.uri().clone().into_parts();
+            if let Ok(pq) = path_and_query.parse() {
+                parts.path_and_query = Some(pq);
+                if let Ok(uri) = Uri::from_parts(parts) {
+                    req.set_uri(uri);
+                }
+            }
+        }
+
+        req.extensions_mut().insert(locale.clone());
+        let inner = philjs::reactive::context::with_context_scope(|| {
+            philjs::provide_context(locale.clone());
+            self.inner.call(req)
+        });
+        LocaleScoped {
+            inner: Box::pin(inner),
+            locale,
+        }
+        .await
+    }
+}
+
+/// Future with the locale provided to `use_context` on every poll
+struct LocaleScoped<F> {
+    inner: Pin<Box<F>>,
+    locale: RequestLocale,
+}
+
+impl<F: Future> Future for LocaleScoped<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let locale = self.locale.clone();
+        philjs::reactive::context::with_context_scope(|| {
+            philjs::provide_context(locale);
+            self.inner.as_mut().poll(cx)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poem::web::Data;
+    use poem::{handler, EndpointExt};
+
+    fn middleware() -> LocaleMiddleware {
+        let translations = Translations::new("en")
+            .with_fluent("en", "hello = Hello")
+            .unwrap()
+            .with_fluent("fr", "hello = Bonjour")
+            .unwrap()
+            .with_fluent("pt-BR", "hello = Olá")
+            .unwrap();
+        LocaleMiddleware::new(translations)
+    }
+
+    #[test]
+    fn test_locale_urls() {
+        assert_eq!(locale_url("fr", "/about"), "/fr/about");
+        assert_eq!(locale_url("fr", "/"), "/fr");
+        assert_eq!(
+            middleware().alternates("/a")[1],
+            ("fr".to_string(), "/fr/a".to_string())
+        );
+    }
+
+    #[test]
+    fn test_path_prefix_toggle() {
+        let uri: Uri = "/fr/about?x=1".parse().unwrap();
+        assert_eq!(middleware().strip_prefix(&uri).as_deref(), Some("/about?x=1"));
+        assert_eq!(middleware().path_prefix(false).strip_prefix(&uri), None);
+
+        let req = Request::builder().uri(uri).finish();
+        assert_eq!(middleware().path_prefix(false).resolve(&req).locale(), "en");
+    }
+
+    #[handler]
+    async fn page(req: &Request, locale: Data<&RequestLocale>) -> String {
+        tokio::task::yield_now().await;
+        format!(
+            "{} {} {} {}",
+            locale.locale(),
+            philjs::i18n::current_locale().unwrap_or_default(),
+            philjs::t!("hello"),
+            req.uri()
+        )
+    }
+
+    #[tokio::test]
+    async fn test_middleware_negotiates_and_strips_prefix() {
+        let app = page.with(middleware());
+
+        let req = Request::builder()
+            .uri_str("/fr/about?x=1")
+            .header(header::ACCEPT_LANGUAGE, "en")
+            .finish();
+        let body = app.call(req).await.unwrap().into_body().into_string().await.unwrap();
+        assert_eq!(body, "fr fr Bonjour /about?x=1");
+
+        let req = Request::builder()
+            .uri_str("/about")
+            .header(header::COOKIE, "theme=dark; philjs-locale=pt-BR")
+            .header(header::ACCEPT_LANGUAGE, "fr")
+            .finish();
+        let body = app.call(req).await.unwrap().into_body().into_string().await.unwrap();
+        assert_eq!(body, "pt-BR pt-BR Olá /about");
+
+        let req = Request::builder()
+            .uri_str("/about")
+            .header(header::ACCEPT_LANGUAGE, "de, pt;q=0.9")
+            .finish();
+        let body = app.call(req).await.unwrap().into_body().into_string().await.unwrap();
+        assert_eq!(body, "pt-BR pt-BR Olá /about");
+    }
+}
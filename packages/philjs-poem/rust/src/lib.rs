@@ -42,7 +42,9 @@
 pub mod config;
 pub mod error;
 pub mod extractors;
+pub mod i18n;
 pub mod middleware;
+pub mod rate_limit;
 pub mod responses;
 pub mod ssr;
 
@@ -56,25 +58,37 @@ pub mod openapi;
 pub use config::PhilJsConfig;
 pub use error::PhilJsError;
 pub use extractors::{SsrContext, AuthUser, PhilJsJson, PhilJsQuery};
-pub use middleware::{SsrMiddleware, CorsMiddleware, SecurityMiddleware, RateLimitMiddleware};
-pub use responses::{PhilJsHtml, PhilJsJson as JsonResponse, PhilJsStream, PhilJsError as ErrorResponse};
-pub use ssr::{render, render_document, render_with_data, render_stream};
+pub use i18n::{LocaleMiddleware, locale_url};
+pub use middleware::{SsrMiddleware, CorsMiddleware, SecurityMiddleware};
+pub use rate_limit::{RateLimit, RateLimitKey, RateLimitMiddleware, RateLimitStrategy};
+pub use responses::{PhilJsHtml, PhilJsJson as JsonResponse, PhilJsStream, PhilJsError as ErrorResponse, FlushPolicy};
+pub use ssr::{render, render_document, render_with_data, render_stream, render_stream_document};
 
 #[cfg(feature = "websocket")]
 pub use websocket::{LiveViewSocket, LiveViewHandler, BroadcastManager, PresenceTracker};
 
+#[cfg(feature = "openapi")]
+pub use openapi::PhilJsOpenApi;
+
 /// Prelude - import commonly used items
 pub mod prelude {
     pub use crate::config::PhilJsConfig;
     pub use crate::error::PhilJsError;
     pub use crate::extractors::{SsrContext, AuthUser, PhilJsJson, PhilJsQuery};
+    pub use crate::i18n::LocaleMiddleware;
     pub use crate::middleware::{SsrMiddleware, CorsMiddleware, SecurityMiddleware};
-    pub use crate::responses::{PhilJsHtml, PhilJsStream};
+    pub use crate::rate_limit::{RateLimit, RateLimitMiddleware};
+    pub use crate::responses::{PhilJsHtml, PhilJsStream, FlushPolicy};
+    pub use crate::ssr::{render_stream, render_stream_document};
+    pub use philjs::StreamingConfig;
     pub use crate::ssr::{render, render_document, render_with_data};
 
     #[cfg(feature = "websocket")]
     pub use crate::websocket::{LiveViewSocket, LiveViewHandler};
 
+    #[cfg(feature = "openapi")]
+    pub use crate::openapi::PhilJsOpenApi;
+
     // Re-export Poem essentials
     pub use poem::{
         Route, Server, Endpoint, IntoResponse, Response,
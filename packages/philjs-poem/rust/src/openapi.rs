@@ -0,0 +1,413 @@
+//! OpenAPI documentation for PhilJS API routes
+//!
+//! [`PhilJsOpenApi`] builds one OpenAPI 3 document from PhilJS server
+//! functions and `#[api]` handlers, optionally merged with specs produced by
+//! `poem-openapi` services, and mounts it with Swagger UI and Redoc.
+//!
+//! Every server function registered with `philjs::server` is documented
+//! automatically. Registering its type with
+//! [`server_fn`](PhilJsOpenApi::server_fn) adds request and response schemas
+//! generated by `schemars`; types used by several operations are emitted once
+//! under `components/schemas` and referenced everywhere else.
+//!
+//! ```rust,ignore
+//! let docs = PhilJsOpenApi::new("Blog API", "1.0.0")
+//!     .server_fn::<CreatePost>("Create a post")
+//!     .api::<(), Vec<Post>>("GET", "/api/posts", "List posts")
+//!     .merge_spec(&users_service.spec());
+//!
+//! let app = Route::new()
+//!     .nest("/docs", docs.endpoint());
+//! // GET /docs/openapi.json, /docs/swagger, /docs/redoc
+//! ```
+
+use philjs::server::{self, ServerFn};
+use poem::{endpoint::make_sync, http::StatusCode, Response, Route};
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Component name of the shared server function error schema
+const SERVER_ERROR_SCHEMA: &str = "ServerError";
+
+/// Builder for the PhilJS OpenAPI document and its UI routes
+pub struct PhilJsOpenApi {
+    title: String,
+    version: String,
+    description: Option<String>,
+    generator: SchemaGenerator,
+    /// path -> method -> operation
+    operations: BTreeMap<String, BTreeMap<String, Value>>,
+    merged: Vec<Value>,
+    include_registered: bool,
+}
+
+impl PhilJsOpenApi {
+    /// Create a document with the given title and API version
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            generator: SchemaSettings::openapi3().into_generator(),
+            operations: BTreeMap::new(),
+            merged: Vec::new(),
+            include_registered: true,
+        }
+    }
+
+    /// Set the API description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Only document operations added explicitly
+    ///
+    /// By default every registered server function is listed, with untyped
+    /// bodies for those not added through [`server_fn`](Self::server_fn).
+    pub fn explicit_only(mut self) -> Self {
+        self.include_registered = false;
+        self
+    }
+
+    /// Document a server function with schemas for its input and output
+    pub fn server_fn<F>(self, summary: impl Into<String>) -> Self
+    where
+        F: ServerFn,
+        F::Input: JsonSchema + 'static,
+        F::Output: JsonSchema,
+    {
+        self.api::<F::Input, F::Output>(F::METHOD, F::PATH, summary)
+    }
+
+    /// Document an `#[api]` handler or any other JSON route
+    ///
+    /// Use `()` for `I` when the route takes no input.
+    pub fn api<I: JsonSchema + 'static, O: JsonSchema>(
+        mut self,
+        method: &str,
+        path: &str,
+        summary: impl Into<String>,
+    ) -> Self {
+        let input = self.generator.subschema_for::<I>();
+        let output = self.generator.subschema_for::<O>();
+        let input = serde_json::to_value(input).unwrap_or(Value::Null);
+        let output = serde_json::to_value(output).unwrap_or(Value::Null);
+        let has_input = TypeId::of::<I>() != TypeId::of::<()>();
+
+        let operation = operation(method, path, summary.into(), has_input.then_some(input), output);
+        self.operations
+            .entry(openapi_path(path))
+            .or_default()
+            .insert(method.to_ascii_lowercase(), operation);
+        self
+    }
+
+    /// Merge a spec produced elsewhere, e.g. `OpenApiService::spec()`
+    ///
+    /// Paths and component schemas from the merged spec are added to the
+    /// document; PhilJS operations win on conflicts.
+    pub fn merge_spec(mut self, spec: &str) -> Self {
+        match serde_json::from_str(spec) {
+            Ok(spec) => self.merged.push(spec),
+            Err(err) => tracing::warn!(error = %err, "Ignoring invalid OpenAPI spec"),
+        }
+        self
+    }
+
+    /// Build the OpenAPI document
+    pub fn spec(&self) -> Value {
+        let mut paths: Map<String, Value> = Map::new();
+        let mut schemas: Map<String, Value> = Map::new();
+
+        for spec in &self.merged {
+            if let Some(merged) = spec.get("paths").and_then(Value::as_object) {
+                for (path, item) in merged {
+                    paths.insert(path.clone(), item.clone());
+                }
+            }
+            if let Some(merged) = spec.pointer("/components/schemas").and_then(Value::as_object) {
+                for (name, schema) in merged {
+                    schemas.insert(name.clone(), schema.clone());
+                }
+            }
+        }
+
+        let mut operations = self.operations.clone();
+        if self.include_registered {
+            let registered = server::registered_server_fns()
+                .into_iter()
+                .chain(server::registered_streaming_server_fns());
+            for path in registered {
+                let method = server::server_fn_method(path).unwrap_or("POST");
+                operations
+                    .entry(openapi_path(path))
+                    .or_default()
+                    .entry(method.to_ascii_lowercase())
+                    .or_insert_with(|| operation(method, path, String::new(), Some(json!({})), json!({})));
+            }
+        }
+
+        for (path, methods) in operations {
+            let item = paths.entry(path).or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(item) = item {
+                for (method, operation) in methods {
+                    item.insert(method, operation);
+                }
+            }
+        }
+
+        for (name, schema) in self.generator.definitions() {
+            schemas.insert(name.clone(), serde_json::to_value(schema).unwrap_or(Value::Null));
+        }
+        schemas.insert(
+            SERVER_ERROR_SCHEMA.to_string(),
+            json!({
+                "type": "object",
+                "required": ["message", "status"],
+                "properties": {
+                    "message": { "type": "string" },
+                    "code": { "type": "string", "nullable": true },
+                    "status": { "type": "integer", "format": "uint16" }
+                }
+            }),
+        );
+
+        let mut info = json!({ "title": self.title, "version": self.version });
+        if let Some(description) = &self.description {
+            info["description"] = json!(description);
+        }
+
+        json!({
+            "openapi": "3.0.3",
+            "info": info,
+            "paths": paths,
+            "components": { "schemas": schemas },
+        })
+    }
+
+    /// Routes serving `/openapi.json`, `/swagger`, and `/redoc`
+    ///
+    /// Nest the returned route where the docs should live; the UIs load the
+    /// spec relative to that prefix.
+    pub fn endpoint(&self) -> Route {
+        let spec: Arc<str> = self.spec().to_string().into();
+        let swagger = Arc::<str>::from(swagger_html(&self.title));
+        let redoc = Arc::<str>::from(redoc_html(&self.title));
+
+        Route::new()
+            .at(
+                "/openapi.json",
+                make_sync(move |_| {
+                    Response::builder()
+                        .status(StatusCode::OK)
+                        .content_type("application/json")
+                        .body(spec.to_string())
+                }),
+            )
+            .at("/swagger", make_sync(move |_| html(&swagger)))
+            .at("/redoc", make_sync(move |_| html(&redoc)))
+    }
+}
+
+fn html(body: &str) -> Response {
+    Response::builder()
+        .status(StatusCode::OK)
+        .content_type("text/html; charset=utf-8")
+        .body(body.to_string())
+}
+
+/// Convert `/users/:id` to `/users/{id}`
+fn openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => format!("{{{}}}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn operation(method: &str, path: &str, summary: String, input: Option<Value>, output: Value) -> Value {
+    let mut operation = json!({
+        "operationId": operation_id(method, path),
+        "responses": {
+            "200": {
+                "description": "Success",
+                "content": { "application/json": { "schema": output } }
+            },
+            "default": {
+                "description": "Server function error",
+                "content": {
+                    "application/json": {
+                        "schema": { "$ref": format!("#/components/schemas/{}", SERVER_ERROR_SCHEMA) }
+                    }
+                }
+            }
+        }
+    });
+    if !summary.is_empty() {
+        operation["summary"] = json!(summary);
+    }
+
+    let parameters: Vec<Value> = path
+        .split('/')
+        .filter_map(|segment| segment.strip_prefix(':'))
+        .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }))
+        .collect();
+
+    match input {
+        Some(schema) if method.eq_ignore_ascii_case("GET") => {
+            let mut parameters = parameters;
+            parameters.push(json!({
+                "name": "input",
+                "in": "query",
+                "style": "form",
+                "explode": true,
+                "schema": schema,
+            }));
+            operation["parameters"] = json!(parameters);
+        }
+        Some(schema) => {
+            if !parameters.is_empty() {
+                operation["parameters"] = json!(parameters);
+            }
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": {
+                    "application/json": { "schema": schema },
+                    "application/x-www-form-urlencoded": { "schema": schema }
+                }
+            });
+        }
+        None if !parameters.is_empty() => operation["parameters"] = json!(parameters),
+        None => {}
+    }
+    operation
+}
+
+fn operation_id(method: &str, path: &str) -> String {
+    let mut id = method.to_ascii_lowercase();
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        id.push('_');
+        id.push_str(&segment.trim_start_matches(':').replace(['-', '.'], "_"));
+    }
+    id
+}
+
+fn swagger_html(title: &str) -> String {
+    format!(
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <title>{}</title>
+    <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+    <script>
+        SwaggerUIBundle({{ url: "openapi.json", dom_id: "#swagger-ui" }});
+    </script>
+</body>
+</html>"##,
+        escape(title)
+    )
+}
+
+fn redoc_html(title: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <title>{}</title>
+</head>
+<body>
+    <redoc spec-url="openapi.json"></redoc>
+    <script src="https://cdn.redoc.ly/redoc/latest/bundles/redoc.standalone.js"></script>
+</body>
+</html>"#,
+        escape(title)
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use philjs::server::ServerResult;
+    use serde::{Deserialize, Serialize};
+    use std::future::Future;
+    use std::pin::Pin;
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    struct Post {
+        id: u64,
+        title: String,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    struct CreatePost {
+        title: String,
+    }
+
+    impl ServerFn for CreatePost {
+        type Input = CreatePost;
+        type Output = Post;
+        const PATH: &'static str = "/api/posts";
+
+        fn run(input: CreatePost) -> Pin<Box<dyn Future<Output = ServerResult<Post>> + Send>> {
+            Box::pin(async move { Ok(Post { id: 1, title: input.title }) })
+        }
+    }
+
+    #[test]
+    fn test_spec_reuses_schemas() {
+        let spec = PhilJsOpenApi::new("Blog", "1.0.0")
+            .explicit_only()
+            .server_fn::<CreatePost>("Create a post")
+            .api::<(), Vec<Post>>("GET", "/api/posts", "List posts")
+            .api::<(), Post>("GET", "/api/posts/:id", "Get a post")
+            .spec();
+
+        let create = &spec["paths"]["/api/posts"]["post"];
+        assert_eq!(create["summary"], "Create a post");
+        assert_eq!(
+            create["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/CreatePost"
+        );
+        assert_eq!(
+            spec["paths"]["/api/posts/{id}"]["get"]["parameters"][0]["name"],
+            "id"
+        );
+        assert!(spec["paths"]["/api/posts"]["get"].get("requestBody").is_none());
+
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        assert!(schemas.contains_key("Post"));
+        assert!(schemas.contains_key(SERVER_ERROR_SCHEMA));
+    }
+
+    #[test]
+    fn test_merge_spec() {
+        let merged = r##"{
+            "paths": { "/users": { "get": { "operationId": "list_users" } } },
+            "components": { "schemas": { "User": { "type": "object" } } }
+        }"##;
+        let spec = PhilJsOpenApi::new("Blog", "1.0.0")
+            .explicit_only()
+            .merge_spec(merged)
+            .spec();
+
+        assert_eq!(spec["paths"]["/users"]["get"]["operationId"], "list_users");
+        assert!(spec["components"]["schemas"]["User"].is_object());
+    }
+}
@@ -0,0 +1,728 @@
+//! Rate limiting for PhilJS Poem
+//!
+//! [`RateLimitMiddleware`] limits requests per client with a token bucket or
+//! a sliding window, in memory or (with the `redis` feature) shared across
+//! instances through Redis.
+//!
+//! ```rust,ignore
+//! let app = Route::new()
+//!     .at("/", get(index))
+//!     .at("/api/login", post(login))
+//!     .with(
+//!         RateLimitMiddleware::new(RateLimit::per_minute(120))
+//!             .route("/api/login", RateLimit::per_minute(5).token_bucket())
+//!             .exempt("/static")
+//!             .key_by(RateLimitKey::Principal),
+//!     );
+//! ```
+//!
+//! Every limited response carries `X-RateLimit-Limit`,
+//! `X-RateLimit-Remaining`, and `X-RateLimit-Reset` (seconds); rejected
+//! requests get `429 Too Many Requests` with `Retry-After`.
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use poem::http::{header, HeaderValue, StatusCode};
+use poem::{Endpoint, IntoResponse, Middleware, Request, Response};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Limiting algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitStrategy {
+    /// Bursts up to the limit, refilling evenly over the window
+    TokenBucket,
+    /// Weighted count over the current and previous window
+    #[default]
+    SlidingWindow,
+}
+
+/// A limit of `limit` requests per `window`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Requests allowed per window
+    pub limit: u32,
+    /// Window length
+    pub window: Duration,
+    /// Algorithm
+    pub strategy: RateLimitStrategy,
+}
+
+impl RateLimit {
+    /// Allow `limit` requests per `window` using a sliding window
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit: limit.max(1),
+            window: window.max(Duration::from_millis(1)),
+            strategy: RateLimitStrategy::default(),
+        }
+    }
+
+    /// Requests per second
+    pub fn per_second(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(1))
+    }
+
+    /// Requests per minute
+    pub fn per_minute(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(60))
+    }
+
+    /// Requests per hour
+    pub fn per_hour(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(3600))
+    }
+
+    /// Use a token bucket
+    pub fn token_bucket(mut self) -> Self {
+        self.strategy = RateLimitStrategy::TokenBucket;
+        self
+    }
+
+    /// Use a sliding window
+    pub fn sliding_window(mut self) -> Self {
+        self.strategy = RateLimitStrategy::SlidingWindow;
+        self
+    }
+
+    fn window_ms(&self) -> u64 {
+        self.window.as_millis().max(1) as u64
+    }
+
+    /// Tokens refilled per millisecond
+    fn refill_rate(&self) -> f64 {
+        self.limit as f64 / self.window_ms() as f64
+    }
+}
+
+/// Outcome of counting one request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    /// Whether the request may proceed
+    pub allowed: bool,
+    /// The configured limit
+    pub limit: u32,
+    /// Requests left before limiting
+    pub remaining: u32,
+    /// Time until the limit fully resets
+    pub reset_after: Duration,
+    /// Time until a request would be allowed, when rejected
+    pub retry_after: Option<Duration>,
+}
+
+/// Token bucket state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketState {
+    /// Tokens left
+    pub tokens: f64,
+    /// Last refill, in milliseconds since the epoch
+    pub updated_ms: u64,
+}
+
+impl BucketState {
+    fn full(rule: &RateLimit, now_ms: u64) -> Self {
+        Self {
+            tokens: rule.limit as f64,
+            updated_ms: now_ms,
+        }
+    }
+
+    /// Refill, then take a token if one is available
+    fn take(&mut self, rule: &RateLimit, now_ms: u64) -> bool {
+        let elapsed = now_ms.saturating_sub(self.updated_ms) as f64;
+        self.tokens = (self.tokens + elapsed * rule.refill_rate()).min(rule.limit as f64);
+        self.updated_ms = now_ms;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn decision(&self, rule: &RateLimit, allowed: bool) -> RateLimitDecision {
+        let rate = rule.refill_rate();
+        let missing = rule.limit as f64 - self.tokens;
+        RateLimitDecision {
+            allowed,
+            limit: rule.limit,
+            remaining: self.tokens.floor() as u32,
+            reset_after: Duration::from_millis((missing / rate).ceil() as u64),
+            retry_after: (!allowed).then(|| Duration::from_millis(((1.0 - self.tokens) / rate).ceil() as u64)),
+        }
+    }
+}
+
+/// Sliding window state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowState {
+    /// Start of the current window, in milliseconds since the epoch
+    pub start_ms: u64,
+    /// Requests counted in the current window
+    pub current: u64,
+    /// Requests counted in the previous window
+    pub previous: u64,
+}
+
+impl WindowState {
+    fn new(rule: &RateLimit, now_ms: u64) -> Self {
+        Self {
+            start_ms: now_ms - now_ms % rule.window_ms(),
+            current: 0,
+            previous: 0,
+        }
+    }
+
+    /// Roll the window forward to `now_ms`
+    fn advance(&mut self, rule: &RateLimit, now_ms: u64) {
+        let window = rule.window_ms();
+        let start = now_ms - now_ms % window;
+        if start != self.start_ms {
+            self.previous = if self.start_ms + window == start { self.current } else { 0 };
+            self.current = 0;
+            self.start_ms = start;
+        }
+    }
+
+    fn estimate(&self, rule: &RateLimit, now_ms: u64) -> f64 {
+        let window = rule.window_ms() as f64;
+        let elapsed = now_ms.saturating_sub(self.start_ms) as f64;
+        self.previous as f64 * (window - elapsed) / window + self.current as f64
+    }
+
+    /// Count a request if it fits under the limit
+    fn hit(&mut self, rule: &RateLimit, now_ms: u64) -> bool {
+        self.advance(rule, now_ms);
+        if self.estimate(rule, now_ms) + 1.0 <= rule.limit as f64 {
+            self.current += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn decision(&self, rule: &RateLimit, now_ms: u64, allowed: bool) -> RateLimitDecision {
+        let window = rule.window_ms();
+        let elapsed = now_ms.saturating_sub(self.start_ms);
+        let estimate = self.estimate(rule, now_ms);
+        let retry_after = (!allowed).then(|| {
+            // When the previous window's weight has decayed enough for one more request
+            let wait = if self.current + 1 > rule.limit as u64 || self.previous == 0 {
+                window - elapsed
+            } else {
+                let free = (rule.limit as u64 - 1 - self.current) as f64;
+                let decayed_at = window as f64 - free * window as f64 / self.previous as f64;
+                (decayed_at - elapsed as f64).max(0.0).ceil() as u64
+            };
+            Duration::from_millis(wait.max(1))
+        });
+        RateLimitDecision {
+            allowed,
+            limit: rule.limit,
+            remaining: (rule.limit as f64 - estimate).max(0.0).floor() as u32,
+            reset_after: Duration::from_millis(window - elapsed + if self.current > 0 { window } else { 0 }),
+            retry_after,
+        }
+    }
+}
+
+/// Errors from rate limit stores
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimitError {
+    /// The backend failed
+    #[error("Rate limit store error: {0}")]
+    Store(String),
+}
+
+/// Storage for rate limit counters
+#[async_trait]
+pub trait RateLimitStore: Send + Sync + 'static {
+    /// Count one request for `key` under `rule`
+    async fn hit(&self, key: &str, rule: &RateLimit, now_ms: u64) -> Result<RateLimitDecision, RateLimitError>;
+}
+
+enum Counter {
+    Bucket(BucketState),
+    Window(WindowState),
+}
+
+/// In-process counter store
+#[derive(Default)]
+pub struct MemoryRateLimitStore {
+    counters: Mutex<HashMap<String, (Counter, u64)>>,
+}
+
+impl MemoryRateLimitStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of tracked keys
+    pub fn len(&self) -> usize {
+        self.counters.lock().len()
+    }
+
+    /// Whether no keys are tracked
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop counters that have fully reset by `now_ms`
+    pub fn cleanup(&self, now_ms: u64) {
+        self.counters.lock().retain(|_, (_, expires)| *expires > now_ms);
+    }
+}
+
+/// Keys tracked before idle counters are swept
+const MEMORY_SWEEP_THRESHOLD: usize = 10_000;
+
+#[async_trait]
+impl RateLimitStore for MemoryRateLimitStore {
+    async fn hit(&self, key: &str, rule: &RateLimit, now_ms: u64) -> Result<RateLimitDecision, RateLimitError> {
+        let mut counters = self.counters.lock();
+        if counters.len() >= MEMORY_SWEEP_THRESHOLD && !counters.contains_key(key) {
+            counters.retain(|_, (_, expires)| *expires > now_ms);
+        }
+
+        let (counter, expires) = counters.entry(key.to_string()).or_insert_with(|| {
+            let counter = match rule.strategy {
+                RateLimitStrategy::TokenBucket => Counter::Bucket(BucketState::full(rule, now_ms)),
+                RateLimitStrategy::SlidingWindow => Counter::Window(WindowState::new(rule, now_ms)),
+            };
+            (counter, now_ms)
+        });
+
+        let decision = match (counter, rule.strategy) {
+            (Counter::Bucket(state), RateLimitStrategy::TokenBucket) => {
+                let allowed = state.take(rule, now_ms);
+                state.decision(rule, allowed)
+            }
+            (Counter::Window(state), RateLimitStrategy::SlidingWindow) => {
+                let allowed = state.hit(rule, now_ms);
+                state.decision(rule, now_ms, allowed)
+            }
+            // The rule for this key changed strategy; start over
+            (counter, RateLimitStrategy::TokenBucket) => {
+                let mut state = BucketState::full(rule, now_ms);
+                let allowed = state.take(rule, now_ms);
+                *counter = Counter::Bucket(state);
+                state.decision(rule, allowed)
+            }
+            (counter, RateLimitStrategy::SlidingWindow) => {
+                let mut state = WindowState::new(rule, now_ms);
+                let allowed = state.hit(rule, now_ms);
+                *counter = Counter::Window(state);
+                state.decision(rule, now_ms, allowed)
+            }
+        };
+        *expires = now_ms + decision.reset_after.as_millis() as u64;
+        Ok(decision)
+    }
+}
+
+/// Redis counter store, shared by every instance
+///
+/// Each check is a single Lua script, so concurrent instances never race.
+#[cfg(feature = "redis")]
+pub struct RedisRateLimitStore {
+    conn: redis::aio::ConnectionManager,
+    prefix: String,
+}
+
+#[cfg(feature = "redis")]
+const TOKEN_BUCKET_SCRIPT: &str = r"
+local capacity = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(state[1]) or capacity
+local ts = tonumber(state[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * rate)
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now)
+redis.call('PEXPIRE', KEYS[1], math.ceil((capacity - tokens) / rate) + 1000)
+return {allowed, tostring(tokens), now}
+";
+
+#[cfg(feature = "redis")]
+const SLIDING_WINDOW_SCRIPT: &str = r"
+local window = tonumber(ARGV[1])
+local limit = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+local start = now - (now % window)
+local state = redis.call('HMGET', KEYS[1], 'start', 'current', 'previous')
+local s = tonumber(state[1]) or start
+local current = tonumber(state[2]) or 0
+local previous = tonumber(state[3]) or 0
+if s ~= start then
+    if s + window == start then previous = current else previous = 0 end
+    current = 0
+end
+local estimated = previous * (window - (now - start)) / window + current
+local allowed = 0
+if estimated + 1 <= limit then
+    current = current + 1
+    allowed = 1
+end
+redis.call('HSET', KEYS[1], 'start', start, 'current', current, 'previous', previous)
+redis.call('PEXPIRE', KEYS[1], window * 2)
+return {allowed, start, current, previous}
+";
+
+#[cfg(feature = "redis")]
+impl RedisRateLimitStore {
+    /// Connect to Redis at `url`
+    pub async fn connect(url: &str) -> Result<Self, RateLimitError> {
+        let client = redis::Client::open(url).map_err(|e| RateLimitError::Store(e.to_string()))?;
+        let conn = redis::aio::ConnectionManager::new(client)
+            .await
+            .map_err(|e| RateLimitError::Store(e.to_string()))?;
+        Ok(Self {
+            conn,
+            prefix: "philjs:ratelimit:".to_string(),
+        })
+    }
+
+    /// Set the key prefix (default `philjs:ratelimit:`)
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl RateLimitStore for RedisRateLimitStore {
+    async fn hit(&self, key: &str, rule: &RateLimit, now_ms: u64) -> Result<RateLimitDecision, RateLimitError> {
+        let mut conn = self.conn.clone();
+        let key = format!("{}{}", self.prefix, key);
+        let store_error = |e: redis::RedisError| RateLimitError::Store(e.to_string());
+
+        match rule.strategy {
+            RateLimitStrategy::TokenBucket => {
+                let (allowed, tokens, updated_ms): (u8, String, u64) = redis::Script::new(TOKEN_BUCKET_SCRIPT)
+                    .key(&key)
+                    .arg(rule.limit)
+                    .arg(rule.refill_rate())
+                    .arg(now_ms)
+                    .invoke_async(&mut conn)
+                    .await
+                    .map_err(store_error)?;
+                let state = BucketState {
+                    tokens: tokens.parse().unwrap_or(0.0),
+                    updated_ms,
+                };
+                Ok(state.decision(rule, allowed == 1))
+            }
+            RateLimitStrategy::SlidingWindow => {
+                let (allowed, start_ms, current, previous): (u8, u64, u64, u64) =
+                    redis::Script::new(SLIDING_WINDOW_SCRIPT)
+                        .key(&key)
+                        .arg(rule.window_ms())
+                        .arg(rule.limit)
+                        .arg(now_ms)
+                        .invoke_async(&mut conn)
+                        .await
+                        .map_err(store_error)?;
+                let state = WindowState {
+                    start_ms,
+                    current,
+                    previous,
+                };
+                Ok(state.decision(rule, now_ms, allowed == 1))
+            }
+        }
+    }
+}
+
+/// Authenticated principal used by [`RateLimitKey::Principal`]
+///
+/// Insert it into request extensions from authentication middleware that
+/// runs before rate limiting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitPrincipal(pub String);
+
+type KeyFn = Arc<dyn Fn(&Request) -> Option<String> + Send + Sync>;
+
+/// What identifies a client
+#[derive(Clone, Default)]
+pub enum RateLimitKey {
+    /// Client IP address
+    #[default]
+    Ip,
+    /// [`RateLimitPrincipal`] extension, falling back to the IP
+    Principal,
+    /// Custom key, falling back to the IP when `None`
+    Custom(KeyFn),
+}
+
+struct RateLimitConfig {
+    default: RateLimit,
+    /// Path prefix overrides; `None` exempts the prefix
+    routes: Vec<(String, Option<RateLimit>)>,
+    key: RateLimitKey,
+    store: Arc<dyn RateLimitStore>,
+    trust_forwarded_for: bool,
+}
+
+impl RateLimitConfig {
+    /// The most specific rule for `path`, with the prefix it was configured for
+    fn rule_for(&self, path: &str) -> Option<(&str, RateLimit)> {
+        let route = self
+            .routes
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len());
+        match route {
+            Some((prefix, Some(rule))) => Some((prefix.as_str(), *rule)),
+            Some((_, None)) => None,
+            None => Some(("*", self.default)),
+        }
+    }
+
+    fn client_ip(&self, req: &Request) -> String {
+        if self.trust_forwarded_for {
+            let forwarded = req
+                .headers()
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .map(str::trim)
+                .filter(|ip| !ip.is_empty());
+            if let Some(ip) = forwarded {
+                return ip.to_string();
+            }
+        }
+        req.remote_addr()
+            .as_socket_addr()
+            .map(|addr| addr.ip().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
+    fn client_key(&self, req: &Request) -> String {
+        let key = match &self.key {
+            RateLimitKey::Ip => None,
+            RateLimitKey::Principal => req
+                .extensions()
+                .get::<RateLimitPrincipal>()
+                .map(|principal| format!("principal:{}", principal.0)),
+            RateLimitKey::Custom(f) => f(req).map(|key| format!("custom:{}", key)),
+        };
+        key.unwrap_or_else(|| format!("ip:{}", self.client_ip(req)))
+    }
+}
+
+/// Rate limiting middleware
+#[derive(Clone)]
+pub struct RateLimitMiddleware {
+    config: Arc<RateLimitConfig>,
+}
+
+impl RateLimitMiddleware {
+    /// Limit every route by `rule`, counting in memory
+    pub fn new(rule: RateLimit) -> Self {
+        Self {
+            config: Arc::new(RateLimitConfig {
+                default: rule,
+                routes: Vec::new(),
+                key: RateLimitKey::default(),
+                store: Arc::new(MemoryRateLimitStore::new()),
+                trust_forwarded_for: false,
+            }),
+        }
+    }
+
+    fn config_mut(&mut self) -> &mut RateLimitConfig {
+        Arc::get_mut(&mut self.config).expect("RateLimitMiddleware configured after being shared")
+    }
+
+    /// Use a different counter store, e.g. Redis
+    pub fn store(mut self, store: impl RateLimitStore) -> Self {
+        self.config_mut().store = Arc::new(store);
+        self
+    }
+
+    /// Choose what identifies a client
+    pub fn key_by(mut self, key: RateLimitKey) -> Self {
+        self.config_mut().key = key;
+        self
+    }
+
+    /// Identify clients with a custom function, e.g. by API key header
+    pub fn key_with<F>(self, f: F) -> Self
+    where
+        F: Fn(&Request) -> Option<String> + Send + Sync + 'static,
+    {
+        self.key_by(RateLimitKey::Custom(Arc::new(f)))
+    }
+
+    /// Apply `rule` to paths starting with `prefix`
+    ///
+    /// The longest matching prefix wins; each prefix counts separately.
+    pub fn route(mut self, prefix: impl Into<String>, rule: RateLimit) -> Self {
+        self.config_mut().routes.push((prefix.into(), Some(rule)));
+        self
+    }
+
+    /// Never limit paths starting with `prefix`
+    pub fn exempt(mut self, prefix: impl Into<String>) -> Self {
+        self.config_mut().routes.push((prefix.into(), None));
+        self
+    }
+
+    /// Key by the first `X-Forwarded-For` address (only behind a trusted proxy)
+    pub fn trust_forwarded_for(mut self, trust: bool) -> Self {
+        self.config_mut().trust_forwarded_for = trust;
+        self
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for RateLimitMiddleware {
+    type Output = RateLimitEndpoint<E>;
+
+    fn transform(&self, inner: E) -> Self::Output {
+        RateLimitEndpoint {
+            inner,
+            config: self.config.clone(),
+        }
+    }
+}
+
+/// Endpoint wrapped by [`RateLimitMiddleware`]
+pub struct RateLimitEndpoint<E> {
+    inner: E,
+    config: Arc<RateLimitConfig>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    (duration.as_millis() as u64).div_ceil(1000)
+}
+
+fn apply_headers(res: &mut Response, decision: &RateLimitDecision) {
+    let headers = res.headers_mut();
+    headers.insert("x-ratelimit-limit", HeaderValue::from(decision.limit));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from(decision.remaining));
+    headers.insert("x-ratelimit-reset", HeaderValue::from(ceil_secs(decision.reset_after)));
+    if let Some(retry_after) = decision.retry_after {
+        headers.insert(header::RETRY_AFTER, HeaderValue::from(ceil_secs(retry_after).max(1)));
+    }
+}
+
+impl<E: Endpoint> Endpoint for RateLimitEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> poem::Result<Self::Output> {
+        let Some((scope, rule)) = self.config.rule_for(req.uri().path()) else {
+            return Ok(self.inner.call(req).await?.into_response());
+        };
+        let key = format!("{}|{}", scope, self.config.client_key(&req));
+
+        let decision = match self.config.store.hit(&key, &rule, now_ms()).await {
+            Ok(decision) => decision,
+            Err(err) => {
+                // Fail open: an unavailable store should not take the site down
+                tracing::warn!(error = %err, "Rate limit check failed");
+                return Ok(self.inner.call(req).await?.into_response());
+            }
+        };
+
+        let mut res = if decision.allowed {
+            self.inner.call(req).await?.into_response()
+        } else {
+            let retry_after = decision.retry_after.map(ceil_secs).unwrap_or(1);
+            Response::builder()
+                .status(StatusCode::TOO_MANY_REQUESTS)
+                .content_type("application/json")
+                .body(
+                    serde_json::json!({
+                        "error": "Too many requests",
+                        "retry_after": retry_after,
+                    })
+                    .to_string(),
+                )
+        };
+        apply_headers(&mut res, &decision);
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poem::{handler, EndpointExt};
+
+    #[handler]
+    fn ok() -> &'static str {
+        "ok"
+    }
+
+    #[test]
+    fn test_token_bucket_refills() {
+        let rule = RateLimit::new(2, Duration::from_secs(2)).token_bucket();
+        let mut bucket = BucketState::full(&rule, 0);
+
+        assert!(bucket.take(&rule, 0));
+        assert!(bucket.take(&rule, 0));
+        assert!(!bucket.take(&rule, 0));
+        assert_eq!(bucket.decision(&rule, false).retry_after, Some(Duration::from_secs(1)));
+
+        assert!(bucket.take(&rule, 1000));
+        assert!(!bucket.take(&rule, 1000));
+    }
+
+    #[test]
+    fn test_sliding_window_weights_previous_window() {
+        let rule = RateLimit::new(4, Duration::from_secs(10));
+        let mut window = WindowState::new(&rule, 0);
+
+        for _ in 0..4 {
+            assert!(window.hit(&rule, 1000));
+        }
+        assert!(!window.hit(&rule, 9000));
+
+        // Halfway through the next window, half of the previous 4 still count
+        assert!(window.hit(&rule, 15_000));
+        assert!(window.hit(&rule, 15_000));
+        assert!(!window.hit(&rule, 15_000));
+        assert_eq!(window.decision(&rule, 15_000, true).remaining, 0);
+    }
+
+    #[tokio::test]
+    async fn test_middleware_limits_with_headers() {
+        let app = ok.with(
+            RateLimitMiddleware::new(RateLimit::per_minute(2))
+                .route("/login", RateLimit::per_minute(1))
+                .exempt("/static"),
+        );
+
+        for remaining in ["1", "0"] {
+            let res = app.call(Request::builder().uri_str("/").finish()).await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            assert_eq!(res.headers().get("x-ratelimit-remaining").unwrap(), remaining);
+        }
+        let res = app.call(Request::builder().uri_str("/").finish()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(res.headers().contains_key(header::RETRY_AFTER));
+
+        // Route overrides count separately
+        let res = app.call(Request::builder().uri_str("/login").finish()).await.unwrap();
+        assert_eq!(res.headers().get("x-ratelimit-limit").unwrap(), "1");
+
+        let res = app.call(Request::builder().uri_str("/static/app.js").finish()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(!res.headers().contains_key("x-ratelimit-limit"));
+    }
+}
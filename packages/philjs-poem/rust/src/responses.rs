@@ -0,0 +1,167 @@
+//! Response types for PhilJS Poem
+
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, StreamExt};
+use poem::http::{header, HeaderValue, StatusCode};
+use poem::{Body, IntoResponse, Response};
+use philjs::StreamingConfig;
+
+/// When streamed HTML is written to the connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlushPolicy {
+    /// Write every rendered chunk as soon as it is produced
+    #[default]
+    EveryChunk,
+    /// Coalesce chunks until at least `min_bytes` are pending
+    Buffered {
+        /// Bytes to collect before writing
+        min_bytes: usize,
+    },
+    /// Collect the whole document and write it once, e.g. for crawlers
+    End,
+}
+
+/// Streaming HTML response
+///
+/// Backed by `poem::Body::from_bytes_stream`, so the shell reaches the
+/// browser before suspense boundaries resolve.
+pub struct PhilJsStream {
+    body: BoxStream<'static, String>,
+    status: StatusCode,
+    flush: FlushPolicy,
+}
+
+impl PhilJsStream {
+    /// Stream HTML chunks
+    pub fn from_stream<S>(stream: S) -> Self
+    where
+        S: futures::Stream<Item = String> + Send + 'static,
+    {
+        Self {
+            body: stream.boxed(),
+            status: StatusCode::OK,
+            flush: FlushPolicy::default(),
+        }
+    }
+
+    /// Stream a PhilJS view with `philjs::render_to_stream_async`
+    ///
+    /// [`StreamingConfig::chunk_size`] becomes the minimum flush size when
+    /// `flush_on_suspense` is off.
+    pub fn render<F, V>(f: F, config: StreamingConfig) -> Self
+    where
+        F: FnOnce() -> V,
+        V: philjs::IntoView,
+    {
+        let flush = if config.flush_on_suspense {
+            FlushPolicy::EveryChunk
+        } else {
+            FlushPolicy::Buffered {
+                min_bytes: config.chunk_size,
+            }
+        };
+        Self::from_stream(philjs::render_to_stream_async(f, config)).flush(flush)
+    }
+
+    /// Wrap the stream with a document prefix and suffix
+    pub fn wrap(self, prefix: impl Into<String>, suffix: impl Into<String>) -> Self {
+        let head = stream::once(futures::future::ready(prefix.into()));
+        let tail = stream::once(futures::future::ready(suffix.into()));
+        Self {
+            body: head.chain(self.body).chain(tail).boxed(),
+            ..self
+        }
+    }
+
+    /// Set the flush policy
+    pub fn flush(mut self, flush: FlushPolicy) -> Self {
+        self.flush = flush;
+        self
+    }
+
+    /// Set the status code
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+/// Join chunks until `min_bytes` are pending or the stream ends
+fn coalesce(body: BoxStream<'static, String>, min_bytes: usize) -> BoxStream<'static, String> {
+    stream::unfold(Some(body), move |body| async move {
+        let mut body = body?;
+        let mut pending = String::new();
+        while let Some(chunk) = body.next().await {
+            pending.push_str(&chunk);
+            if pending.len() >= min_bytes {
+                return Some((pending, Some(body)));
+            }
+        }
+        if pending.is_empty() {
+            None
+        } else {
+            Some((pending, None))
+        }
+    })
+    .boxed()
+}
+
+impl IntoResponse for PhilJsStream {
+    fn into_response(self) -> Response {
+        let chunks = match self.flush {
+            FlushPolicy::EveryChunk => self.body,
+            FlushPolicy::Buffered { min_bytes } => coalesce(self.body, min_bytes),
+            FlushPolicy::End => coalesce(self.body, usize::MAX),
+        };
+        let body = Body::from_bytes_stream(
+            chunks
+                .filter(|chunk| futures::future::ready(!chunk.is_empty()))
+                .map(|chunk| Ok::<_, std::io::Error>(Bytes::from(chunk))),
+        );
+
+        let mut response = Response::builder()
+            .status(self.status)
+            .content_type("text/html; charset=utf-8")
+            .body(body);
+        let headers = response.headers_mut();
+        headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+        if self.flush != FlushPolicy::End {
+            // Ask reverse proxies not to buffer, so the shell is flushed early
+            headers.insert("x-accel-buffering", HeaderValue::from_static("no"));
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunks() -> PhilJsStream {
+        let parts = ["<p>a</p>", "", "<p>b</p>", "<p>c</p>"];
+        PhilJsStream::from_stream(stream::iter(parts.map(String::from))).wrap("<body>", "</body>")
+    }
+
+    #[tokio::test]
+    async fn test_stream_response() {
+        let response = chunks().into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(response.headers().get("x-accel-buffering").unwrap(), "no");
+        let body = response.into_body().into_string().await.unwrap();
+        assert_eq!(body, "<body><p>a</p><p>b</p><p>c</p></body>");
+    }
+
+    #[tokio::test]
+    async fn test_buffered_flush_coalesces_chunks() {
+        let collected: Vec<String> = coalesce(chunks().body, 12).collect().await;
+        assert_eq!(collected, ["<body><p>a</p>", "<p>b</p><p>c</p>", "</body>"]);
+
+        let response = chunks().flush(FlushPolicy::End).into_response();
+        assert!(response.headers().get("x-accel-buffering").is_none());
+    }
+}
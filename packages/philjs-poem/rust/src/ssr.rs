@@ -0,0 +1,45 @@
+//! Server-side rendering helpers for PhilJS Poem
+
+use crate::responses::PhilJsStream;
+use philjs::StreamingConfig;
+
+/// Stream a PhilJS view
+///
+/// The shell is flushed immediately and suspense boundaries stream in as
+/// they resolve.
+pub fn render_stream<F, V>(f: F, config: StreamingConfig) -> PhilJsStream
+where
+    F: FnOnce() -> V,
+    V: philjs::IntoView,
+{
+    PhilJsStream::render(f, config)
+}
+
+/// Stream a PhilJS view inside a full HTML document
+///
+/// The document head and the view shell go out in the first flush.
+pub fn render_stream_document<F, V>(title: &str, f: F, config: StreamingConfig) -> PhilJsStream
+where
+    F: FnOnce() -> V,
+    V: philjs::IntoView,
+{
+    let head = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>{}</title>
+    <link rel="stylesheet" href="/static/styles.css">
+</head>
+<body>
+    <div id="app">"#,
+        title
+    );
+    let tail = r#"</div>
+    <script type="module" src="/static/app.js"></script>
+</body>
+</html>"#;
+
+    render_stream(f, config).wrap(head, tail)
+}
@@ -0,0 +1,360 @@
+//! WebSocket support for PhilJS Poem
+//!
+//! [`LiveViewSocket`] serves `philjs::liveview` views over
+//! `poem::web::websocket` through the shared `philjs::liveview::LiveViewHub`,
+//! like the Rocket, Axum, and Actix integrations.
+//!
+//! ```rust,ignore
+//! let live = Arc::new(
+//!     LiveViewSocket::new().route("/counter", || Box::new(Counter::default())),
+//! );
+//!
+//! let app = Route::new()
+//!     .at("/counter", get(counter_page))
+//!     .at("/live", live.clone().endpoint());
+//!
+//! // Elsewhere: push a server-side event to every counter view
+//! live.broadcast("/counter", "tick", json!({ "n": 1 }));
+//! ```
+
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::RwLock;
+use philjs::liveview::{self as core, LiveView, LiveViewRegistry, Reply};
+use poem::web::websocket::{Message, WebSocket, WebSocketStream};
+use poem::{Endpoint, FromRequest, IntoResponse, Request, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use uuid::Uuid;
+
+pub use philjs::liveview::LiveViewFactory;
+
+/// Broadcast message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BroadcastMessage {
+    /// Topic
+    pub topic: String,
+    /// Event type
+    pub event: String,
+    /// Payload
+    pub payload: serde_json::Value,
+}
+
+/// Fan-out of server-side events to subscribers outside the LiveView hub
+pub struct BroadcastManager {
+    tx: broadcast::Sender<BroadcastMessage>,
+}
+
+impl BroadcastManager {
+    /// Create a manager buffering up to `capacity` undelivered messages
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    /// Subscribe to every broadcast message
+    pub fn subscribe(&self) -> broadcast::Receiver<BroadcastMessage> {
+        self.tx.subscribe()
+    }
+
+    /// Broadcast a message, returning how many sockets received it
+    pub fn broadcast(&self, msg: BroadcastMessage) -> usize {
+        self.tx.send(msg).unwrap_or(0)
+    }
+
+    /// Get subscriber count
+    pub fn subscriber_count(&self) -> usize {
+        self.tx.receiver_count()
+    }
+}
+
+impl Default for BroadcastManager {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+/// Presence entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceEntry {
+    /// User/connection ID
+    pub id: String,
+    /// Phoenix-style reference
+    pub phx_ref: String,
+    /// Metadata
+    pub meta: serde_json::Value,
+    /// Join timestamp
+    pub joined_at: u64,
+}
+
+/// Presence tracking for connected users
+pub struct PresenceTracker {
+    state: RwLock<HashMap<String, Vec<PresenceEntry>>>,
+}
+
+impl PresenceTracker {
+    /// Create a new presence tracker
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Track a user joining
+    pub fn track(&self, key: &str, id: &str, meta: serde_json::Value) -> PresenceEntry {
+        let entry = PresenceEntry {
+            id: id.to_string(),
+            phx_ref: Uuid::new_v4().to_string(),
+            meta,
+            joined_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        };
+
+        self.state
+            .write()
+            .entry(key.to_string())
+            .or_default()
+            .push(entry.clone());
+        entry
+    }
+
+    /// Untrack a user leaving
+    pub fn untrack(&self, key: &str, id: &str) -> Option<PresenceEntry> {
+        let mut state = self.state.write();
+        let entries = state.get_mut(key)?;
+        let index = entries.iter().position(|e| e.id == id)?;
+        let entry = entries.remove(index);
+        if entries.is_empty() {
+            state.remove(key);
+        }
+        Some(entry)
+    }
+
+    /// Get presences for a key
+    pub fn get(&self, key: &str) -> Vec<PresenceEntry> {
+        self.state.read().get(key).cloned().unwrap_or_default()
+    }
+
+    /// List all presences
+    pub fn list(&self) -> HashMap<String, Vec<PresenceEntry>> {
+        self.state.read().clone()
+    }
+}
+
+impl Default for PresenceTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// LiveView WebSocket server
+///
+/// Adapts poem's WebSocket to the core [`core::LiveViewHub`], which speaks
+/// the `WsMessage` protocol used by the LiveView client. Messages published
+/// with [`broadcast`](Self::broadcast) reach `handle_info` on every view
+/// subscribed to the topic.
+pub struct LiveViewSocket {
+    hub: core::LiveViewHub,
+    broadcast: Arc<BroadcastManager>,
+    presence: Arc<PresenceTracker>,
+}
+
+impl LiveViewSocket {
+    /// Create a server with its own registry, broadcast manager, and presence tracking
+    pub fn new() -> Self {
+        Self {
+            hub: core::LiveViewHub::new(),
+            broadcast: Arc::new(BroadcastManager::default()),
+            presence: Arc::new(PresenceTracker::new()),
+        }
+    }
+
+    /// Share an existing registry (e.g. one with a running sweeper)
+    pub fn with_registry(mut self, registry: Arc<LiveViewRegistry>) -> Self {
+        self.hub = self.hub.with_registry(registry);
+        self
+    }
+
+    /// Mirror broadcasts to an existing manager
+    pub fn with_broadcast(mut self, broadcast: Arc<BroadcastManager>) -> Self {
+        self.broadcast = broadcast;
+        self
+    }
+
+    /// Share an existing presence tracker
+    pub fn with_presence(mut self, presence: Arc<PresenceTracker>) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    /// Close sockets that have not sent a heartbeat within `timeout`
+    pub fn heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.hub = self.hub.with_heartbeat_timeout(timeout);
+        self
+    }
+
+    /// Serve a view for joins on `topic` (usually the page path)
+    pub fn route<F>(mut self, topic: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn() -> Box<dyn LiveView> + Send + Sync + 'static,
+    {
+        self.hub = self.hub.route(topic, factory);
+        self
+    }
+
+    /// The view registry
+    pub fn registry(&self) -> &Arc<LiveViewRegistry> {
+        self.hub.registry()
+    }
+
+    /// The presence tracker
+    pub fn presence(&self) -> &Arc<PresenceTracker> {
+        &self.presence
+    }
+
+    /// Subscribe a connected socket to an additional topic
+    pub fn subscribe(&self, topic: &str, socket_id: &str) {
+        self.hub.subscribe(topic, socket_id);
+    }
+
+    /// Publish a server-side event to every view subscribed to `topic`,
+    /// mirroring it to [`BroadcastManager`] subscribers.
+    ///
+    /// Returns the number of local views notified.
+    pub fn broadcast(&self, topic: &str, event: &str, payload: serde_json::Value) -> usize {
+        let delivered = self.hub.publish(topic, event, payload.clone());
+        self.broadcast.broadcast(BroadcastMessage {
+            topic: topic.to_string(),
+            event: event.to_string(),
+            payload,
+        });
+        delivered
+    }
+
+    /// An endpoint serving this socket, for `Route::at`
+    pub fn endpoint(self: Arc<Self>) -> LiveViewHandler {
+        LiveViewHandler { socket: self }
+    }
+
+    /// Upgrade a request into a LiveView connection
+    pub fn upgrade(self: Arc<Self>, ws: WebSocket) -> impl IntoResponse {
+        ws.on_upgrade(move |stream| self.serve(stream))
+    }
+
+    async fn serve(self: Arc<Self>, stream: WebSocketStream) {
+        let (mut sender, mut receiver) = stream.split();
+        let (tx, mut info_rx) = mpsc::unbounded_channel();
+        let mut conn = self.hub.connect(Uuid::new_v4().to_string(), move |info| {
+            let _ = tx.send(info);
+        });
+
+        let mut heartbeat_check = tokio::time::interval(self.hub.heartbeat_interval());
+        loop {
+            let reply = tokio::select! {
+                incoming = receiver.next() => match incoming {
+                    Some(Ok(Message::Text(text))) => conn.handle_text(&text),
+                    Some(Ok(Message::Ping(data))) => {
+                        let _ = sender.send(Message::Pong(data)).await;
+                        continue;
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                },
+                Some(info) = info_rx.recv() => conn.handle_info(info),
+                _ = heartbeat_check.tick() => {
+                    if conn.is_alive() {
+                        continue;
+                    }
+                    break;
+                }
+            };
+            let frame = match reply {
+                Reply::None => continue,
+                Reply::Send(frame) => frame,
+                Reply::Joined { topic, payload, frame } => {
+                    self.presence.track(&topic, conn.socket_id(), serde_json::json!({ "url": payload.url }));
+                    frame
+                }
+                Reply::Close(frame) => {
+                    if let Some(frame) = frame {
+                        let _ = sender.send(Message::Text(frame)).await;
+                    }
+                    break;
+                }
+            };
+            if sender.send(Message::Text(frame)).await.is_err() {
+                break;
+            }
+        }
+
+        if let Some(topic) = conn.topic() {
+            self.presence.untrack(topic, conn.socket_id());
+        }
+    }
+}
+
+impl Default for LiveViewSocket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Poem endpoint upgrading requests to a [`LiveViewSocket`] connection
+///
+/// ```rust,ignore
+/// Route::new().at("/live", LiveViewHandler::new(live))
+/// ```
+#[derive(Clone)]
+pub struct LiveViewHandler {
+    socket: Arc<LiveViewSocket>,
+}
+
+impl LiveViewHandler {
+    /// Create an endpoint for `socket`
+    pub fn new(socket: Arc<LiveViewSocket>) -> Self {
+        Self { socket }
+    }
+}
+
+impl Endpoint for LiveViewHandler {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> poem::Result<Self::Output> {
+        let ws = WebSocket::from_request_without_body(&req).await?;
+        Ok(self.socket.clone().upgrade(ws).into_response())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_broadcast_reaches_subscribers() {
+        let socket = LiveViewSocket::new();
+        let mut rx = socket.broadcast.subscribe();
+
+        // No views are connected, but the event is still mirrored
+        assert_eq!(socket.broadcast("/counter", "tick", serde_json::json!({ "n": 1 })), 0);
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.topic, "/counter");
+        assert_eq!(event.payload["n"], 1);
+    }
+
+    #[test]
+    fn test_presence_tracker() {
+        let tracker = PresenceTracker::new();
+
+        let entry = tracker.track("room:lobby", "user1", serde_json::json!({"name": "Alice"}));
+        assert_eq!(entry.id, "user1");
+        assert_eq!(tracker.get("room:lobby").len(), 1);
+
+        assert!(tracker.untrack("room:lobby", "user1").is_some());
+        assert!(tracker.get("room:lobby").is_empty());
+        assert!(tracker.list().is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+//! Pluggable authentication for PhilJS Rocket integration
+//!
+//! Implement [`AuthBackend`] to load users from your database, manage an
+//! [`Auth`] instance, and use [`AuthUser`] as a request guard.
+//!
+//! ```rust,ignore
+//! struct Users(DbPool);
+//!
+//! #[rocket::async_trait]
+//! impl AuthBackend for Users {
+//!     async fn load_user(&self, user_id: &str) -> Option<AuthUser> {
+//!         self.0.find_user(user_id).await.map(|u| AuthUser::new(u.id).role(u.role))
+//!     }
+//! }
+//!
+//! #[post("/login", data = "<form>")]
+//! async fn login(auth: &State<Auth>, cookies: &CookieJar<'_>, form: Form<Login>) -> Redirect {
+//!     let user = check_password(&form).await?;
+//!     auth.login(cookies, &user);
+//!     Redirect::to("/")
+//! }
+//!
+//! #[get("/admin")]
+//! fn admin(user: AuthUser<Admin>) -> String {
+//!     format!("Hello, {}", user.id)
+//! }
+//!
+//! rocket::build().manage(Auth::new(Users(pool)))
+//! ```
+
+use rocket::http::CookieJar;
+use rocket::Request;
+use std::sync::Arc;
+
+use crate::guards::AuthUser;
+use crate::state::SessionState;
+
+/// Session key holding the logged-in user ID
+pub const SESSION_USER_KEY: &str = "auth_user_id";
+
+/// Source of users for the [`AuthUser`] guard
+#[rocket::async_trait]
+pub trait AuthBackend: Send + Sync + 'static {
+    /// Load the user stored in a session by [`Auth::login`]
+    async fn load_user(&self, user_id: &str) -> Option<AuthUser>;
+
+    /// Authenticate a bearer token; tokens are rejected by default
+    async fn verify_token(&self, _token: &str) -> Option<AuthUser> {
+        None
+    }
+}
+
+/// Role requirement for [`AuthUser`]
+pub trait Role: Send + Sync + 'static {
+    /// Whether a user holding `roles` satisfies this requirement
+    fn permits(roles: &[String]) -> bool;
+}
+
+/// Any authenticated user
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnyRole;
+
+impl Role for AnyRole {
+    fn permits(_roles: &[String]) -> bool {
+        true
+    }
+}
+
+/// Define a marker type requiring a named role
+///
+/// ```rust,ignore
+/// define_role!(Editor, "editor");
+///
+/// #[post("/posts")]
+/// fn create(user: AuthUser<Editor>) { ... }
+/// ```
+#[macro_export]
+macro_rules! define_role {
+    ($(#[$meta:meta])* $name:ident, $role:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $name;
+
+        impl $crate::auth::Role for $name {
+            fn permits(roles: &[String]) -> bool {
+                roles.iter().any(|r| r == $role)
+            }
+        }
+    };
+}
+
+define_role!(
+    /// Users holding the `admin` role
+    Admin,
+    "admin"
+);
+
+define_role!(
+    /// Users holding the `user` role
+    User,
+    "user"
+);
+
+/// Managed authentication state
+#[derive(Clone)]
+pub struct Auth {
+    backend: Arc<dyn AuthBackend>,
+    sessions: SessionState,
+}
+
+impl Auth {
+    /// Create auth state with the default session store
+    pub fn new(backend: impl AuthBackend) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            sessions: SessionState::default(),
+        }
+    }
+
+    /// Use a custom session store
+    pub fn sessions(mut self, sessions: SessionState) -> Self {
+        self.sessions = sessions;
+        self
+    }
+
+    /// The session store
+    pub fn session_state(&self) -> &SessionState {
+        &self.sessions
+    }
+
+    /// Log a user in, starting a session if needed
+    pub fn login<R: Role>(&self, cookies: &CookieJar<'_>, user: &AuthUser<R>) {
+        // Rotate the session ID on login to prevent fixation
+        self.sessions.end(cookies);
+        let session_id = self.sessions.start(cookies);
+        self.sessions
+            .set(&session_id, SESSION_USER_KEY, serde_json::Value::String(user.id.clone()));
+    }
+
+    /// Log the current user out and destroy the session
+    pub fn logout(&self, cookies: &CookieJar<'_>) {
+        self.sessions.end(cookies);
+    }
+
+    /// The user ID stored in the current session
+    pub fn session_user_id(&self, cookies: &CookieJar<'_>) -> Option<String> {
+        let session_id = self.sessions.from_cookies(cookies)?;
+        self.sessions
+            .get(&session_id, SESSION_USER_KEY)
+            .and_then(|v| v.as_str().map(str::to_string))
+    }
+
+    /// Resolve the user for a request from its bearer token or session
+    pub async fn authenticate(&self, request: &Request<'_>) -> Option<AuthUser> {
+        if let Some(token) = request
+            .headers()
+            .get_one("Authorization")
+            .and_then(|h| h.strip_prefix("Bearer "))
+        {
+            return self.backend.verify_token(token.trim()).await;
+        }
+
+        let user_id = self.session_user_id(request.cookies())?;
+        self.backend.load_user(&user_id).await
+    }
+}
+
+/// Resolve the request's user through the managed [`Auth`], if any
+pub(crate) async fn authenticate(request: &Request<'_>) -> Option<AuthUser> {
+    match request.rocket().state::<Auth>() {
+        Some(auth) => auth.authenticate(request).await,
+        None => {
+            tracing::warn!("AuthUser guard used without a managed `Auth` state");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+    use rocket::{get, post, routes};
+
+    struct StaticUsers;
+
+    #[rocket::async_trait]
+    impl AuthBackend for StaticUsers {
+        async fn load_user(&self, user_id: &str) -> Option<AuthUser> {
+            match user_id {
+                "1" => Some(AuthUser::new("1").role("admin")),
+                "2" => Some(AuthUser::new("2").role("user")),
+                _ => None,
+            }
+        }
+
+        async fn verify_token(&self, token: &str) -> Option<AuthUser> {
+            (token == "secret").then(|| AuthUser::new("2").role("user"))
+        }
+    }
+
+    #[post("/login/<id>")]
+    fn login(id: &str, auth: &rocket::State<Auth>, cookies: &CookieJar<'_>) {
+        auth.login(cookies, &AuthUser::new(id));
+    }
+
+    #[post("/logout")]
+    fn logout(auth: &rocket::State<Auth>, cookies: &CookieJar<'_>) {
+        auth.logout(cookies);
+    }
+
+    #[get("/me")]
+    fn me(user: AuthUser) -> String {
+        user.id
+    }
+
+    #[get("/admin")]
+    fn admin(user: AuthUser<Admin>) -> String {
+        user.id
+    }
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .manage(Auth::new(StaticUsers))
+            .mount("/", routes![login, logout, me, admin]);
+        Client::tracked(rocket).unwrap()
+    }
+
+    #[test]
+    fn test_role_permits() {
+        let roles = vec!["admin".to_string()];
+        assert!(AnyRole::permits(&[]));
+        assert!(Admin::permits(&roles));
+        assert!(!User::permits(&roles));
+        assert!(AuthUser::new("1").role("admin").into_role::<Admin>().is_some());
+        assert!(AuthUser::new("1").into_role::<Admin>().is_none());
+    }
+
+    #[test]
+    fn test_session_login_and_roles() {
+        let client = client();
+        assert_eq!(client.get("/me").dispatch().status(), rocket::http::Status::Unauthorized);
+
+        client.post("/login/2").dispatch();
+        assert_eq!(client.get("/me").dispatch().into_string().unwrap(), "2");
+        assert_eq!(client.get("/admin").dispatch().status(), rocket::http::Status::Forbidden);
+
+        client.post("/login/1").dispatch();
+        assert_eq!(client.get("/admin").dispatch().into_string().unwrap(), "1");
+
+        client.post("/logout").dispatch();
+        assert_eq!(client.get("/me").dispatch().status(), rocket::http::Status::Unauthorized);
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let client = client();
+        let response = client
+            .get("/me")
+            .header(rocket::http::Header::new("Authorization", "Bearer secret"))
+            .dispatch();
+        assert_eq!(response.into_string().unwrap(), "2");
+    }
+}
@@ -4,13 +4,18 @@
 //! similar to middleware in other frameworks.
 
 use rocket::fairing::{Fairing, Info, Kind};
-use rocket::http::Header;
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::route::{Handler, Outcome as RouteOutcome, Route};
 use rocket::{Request, Response, Data, Build, Rocket};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::{info, span, Level};
 
 use crate::config::{PhilJsConfig, SsrConfig};
+use crate::metrics::Metrics;
+use crate::guards::{CsrfIssued, CsrfSubmitted, PageCacheStatus};
+use crate::state::CacheState;
 
 /// SSR Fairing for server-side rendering support
 pub struct PhilJsSsrFairing {
@@ -144,12 +149,21 @@ pub struct LiveViewContext {
     pub heartbeat_interval: u64,
 }
 
-/// Metrics fairing for request logging and timing
+/// Metrics fairing for request logging, timing, and Prometheus export
+///
+/// Records request counts, latency, and in-flight requests into
+/// [`Metrics::global`] (or a custom registry), alongside the SSR render and
+/// LiveView socket metrics recorded elsewhere. With [`endpoint`](Self::endpoint)
+/// the registry is served in Prometheus text format.
 pub struct PhilJsMetricsFairing {
     /// Log level
     level: Level,
     /// Include response body size
     log_body_size: bool,
+    /// Registry to record into
+    metrics: Metrics,
+    /// Path serving the Prometheus exposition
+    endpoint: Option<String>,
 }
 
 impl PhilJsMetricsFairing {
@@ -158,6 +172,8 @@ impl PhilJsMetricsFairing {
         Self {
             level: Level::INFO,
             log_body_size: true,
+            metrics: Metrics::global().clone(),
+            endpoint: None,
         }
     }
 
@@ -172,6 +188,18 @@ impl PhilJsMetricsFairing {
         self.log_body_size = enabled;
         self
     }
+
+    /// Record into a custom registry instead of the global one
+    pub fn metrics(mut self, metrics: Metrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Serve Prometheus metrics at `path` (e.g. `/metrics`)
+    pub fn endpoint(mut self, path: impl Into<String>) -> Self {
+        self.endpoint = Some(path.into());
+        self
+    }
 }
 
 impl Default for PhilJsMetricsFairing {
@@ -180,27 +208,49 @@ impl Default for PhilJsMetricsFairing {
     }
 }
 
+/// Start time recorded by the metrics fairing
+struct RequestStart(Instant);
+
 #[rocket::async_trait]
 impl Fairing for PhilJsMetricsFairing {
     fn info(&self) -> Info {
         Info {
             name: "PhilJS Metrics Fairing",
-            kind: Kind::Request | Kind::Response,
+            kind: Kind::Ignite | Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        let rocket = rocket.manage(self.metrics.clone());
+        match &self.endpoint {
+            Some(path) => {
+                let route = Route::new(Method::Get, "/", MetricsHandler(self.metrics.clone()));
+                Ok(rocket.mount(path.clone(), vec![route]))
+            }
+            None => Ok(rocket),
         }
     }
 
     async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
         // Store request start time
-        request.local_cache(|| Instant::now());
+        request.local_cache(|| RequestStart(Instant::now()));
+        self.metrics.request_started();
     }
 
     async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
-        let start = request.local_cache(|| Instant::now());
-        let duration = start.elapsed();
+        let start = request.local_cache(|| RequestStart(Instant::now()));
+        let duration = start.0.elapsed();
 
         let method = request.method();
         let uri = request.uri();
         let status = response.status();
+        let route = request
+            .route()
+            .map(|r| r.uri.to_string())
+            .unwrap_or_else(|| "unmatched".to_string());
+
+        self.metrics
+            .request_finished(method.as_str(), &route, status.code, duration);
 
         info!(
             method = %method,
@@ -212,6 +262,22 @@ impl Fairing for PhilJsMetricsFairing {
     }
 }
 
+/// Route handler serving a [`Metrics`] registry in Prometheus format
+#[derive(Clone)]
+pub struct MetricsHandler(pub Metrics);
+
+#[rocket::async_trait]
+impl Handler for MetricsHandler {
+    async fn handle<'r>(&self, _request: &'r Request<'_>, _data: Data<'r>) -> RouteOutcome<'r> {
+        let body = self.0.to_prometheus();
+        let response = Response::build()
+            .header(ContentType::new("text", "plain").with_params([("version", "0.0.4")]))
+            .sized_body(body.len(), std::io::Cursor::new(body))
+            .finalize();
+        RouteOutcome::Success(response)
+    }
+}
+
 /// CORS fairing for cross-origin requests
 pub struct PhilJsCorsFairing {
     /// Allowed origins
@@ -391,6 +457,481 @@ impl Fairing for PhilJsSecurityFairing {
     }
 }
 
+/// Page cache fairing
+///
+/// Manages a [`CacheState`] (unless one is already managed) and the settings
+/// used by the [`PageCache`](crate::guards::PageCache) guard, and reports
+/// hits and misses in an `X-Cache` response header. Pages that were not
+/// cached because they are per-user are reported as `BYPASS`, and a page
+/// whose response sets cookies is evicted before it is sent.
+pub struct PhilJsPageCacheFairing {
+    config: PageCacheConfig,
+}
+
+/// Page cache settings shared with the `PageCache` guard
+#[derive(Clone, Debug)]
+pub struct PageCacheConfig {
+    /// Default TTL in seconds
+    pub ttl: u64,
+    /// Request headers that produce separate cache entries
+    pub vary: Vec<String>,
+}
+
+impl PhilJsPageCacheFairing {
+    /// Create a page cache fairing with a 60 second TTL
+    pub fn new() -> Self {
+        Self {
+            config: PageCacheConfig {
+                ttl: 60,
+                vary: Vec::new(),
+            },
+        }
+    }
+
+    /// Set the default TTL in seconds
+    pub fn ttl(mut self, seconds: u64) -> Self {
+        self.config.ttl = seconds;
+        self
+    }
+
+    /// Cache separately per value of a request header (e.g. `Accept-Language`)
+    pub fn vary(mut self, header: impl Into<String>) -> Self {
+        self.config.vary.push(header.into());
+        self
+    }
+}
+
+impl Default for PhilJsPageCacheFairing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for PhilJsPageCacheFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "PhilJS Page Cache Fairing",
+            kind: Kind::Ignite | Kind::Response,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        let rocket = if rocket.state::<CacheState>().is_none() {
+            rocket.manage(CacheState::new(self.config.ttl))
+        } else {
+            rocket
+        };
+        Ok(rocket.manage(self.config.clone()))
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let status = request.local_cache(PageCacheStatus::default);
+
+        // A page rendered alongside a `Set-Cookie` belongs to this visitor
+        if response.headers().contains("Set-Cookie") {
+            if let Some(key) = status.stored.lock().take() {
+                if let Some(cache) = request.rocket().state::<CacheState>() {
+                    cache.remove(&key);
+                }
+                *status.outcome.lock() = Some("BYPASS");
+            }
+        }
+
+        if let Some(outcome) = *status.outcome.lock() {
+            response.set_header(Header::new("X-Cache", outcome));
+            if !self.config.vary.is_empty() {
+                response.set_header(Header::new("Vary", self.config.vary.join(", ")));
+            }
+        }
+    }
+}
+
+/// CSRF protection fairing
+///
+/// Mints a token per browser session into a `SameSite=Strict` cookie and
+/// captures the `_csrf` field of submitted forms, so the
+/// [`CsrfToken`](crate::guards::CsrfToken) guard can verify `POST`, `PUT`,
+/// `PATCH`, and `DELETE` requests against either the `X-CSRF-Token` header
+/// or the form field. Use [`render_with_csrf`](crate::ssr::render_with_csrf)
+/// to embed the token in pages.
+///
+/// The form field is read without consuming the body, from Rocket's peek
+/// buffer, so only the first 512 bytes of a urlencoded form are searched.
+/// That covers the hidden input `render_with_csrf` puts at the top of each
+/// form; forms that carry `_csrf` further down, and multipart forms, must
+/// send the token in the `X-CSRF-Token` header instead.
+pub struct PhilJsCsrfFairing {
+    config: CsrfConfig,
+}
+
+/// CSRF settings shared with the `CsrfToken` guard
+#[derive(Clone, Debug)]
+pub struct CsrfConfig {
+    /// Cookie holding the token
+    pub cookie_name: String,
+    /// Header checked on unsafe requests
+    pub header_name: String,
+    /// Form field checked on unsafe requests
+    pub field_name: String,
+    /// Path prefixes exempt from verification (e.g. webhooks)
+    pub exempt: Vec<String>,
+}
+
+impl CsrfConfig {
+    /// Whether `path` is exempt from verification
+    ///
+    /// Prefixes match whole segments: `/hooks` exempts `/hooks` and
+    /// `/hooks/github`, but not `/hooksevil`.
+    pub fn is_exempt(&self, path: &str) -> bool {
+        self.exempt.iter().any(|prefix| {
+            path.strip_prefix(prefix.trim_end_matches('/'))
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
+
+impl Default for CsrfConfig {
+    fn default() -> Self {
+        Self {
+            cookie_name: "csrf_token".to_string(),
+            header_name: "X-CSRF-Token".to_string(),
+            field_name: crate::ssr::CSRF_FIELD.to_string(),
+            exempt: Vec::new(),
+        }
+    }
+}
+
+impl PhilJsCsrfFairing {
+    /// Create a CSRF fairing with default names
+    pub fn new() -> Self {
+        Self {
+            config: CsrfConfig::default(),
+        }
+    }
+
+    /// Exempt a path prefix (whole segments) from verification
+    pub fn exempt(mut self, prefix: impl Into<String>) -> Self {
+        self.config.exempt.push(prefix.into());
+        self
+    }
+
+    /// Set the token cookie name
+    pub fn cookie_name(mut self, name: impl Into<String>) -> Self {
+        self.config.cookie_name = name.into();
+        self
+    }
+
+    /// Set the token header name
+    pub fn header_name(mut self, name: impl Into<String>) -> Self {
+        self.config.header_name = name.into();
+        self
+    }
+}
+
+impl Default for PhilJsCsrfFairing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Bytes of a form body searched for the CSRF field; Rocket peeks at most 512
+const CSRF_PEEK_BYTES: usize = 512;
+
+/// Extract a field from an `application/x-www-form-urlencoded` body
+///
+/// When `body` is only the start of the form, its last pair may be cut off
+/// and is ignored.
+fn form_field(body: &[u8], field: &str, complete: bool) -> Option<String> {
+    let body = String::from_utf8_lossy(body);
+    let body = if complete {
+        &body[..]
+    } else {
+        body.rsplit_once('&').map_or("", |(pairs, _)| pairs)
+    };
+    body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| *name == field)
+        .and_then(|(_, value)| {
+            rocket::http::RawStr::new(value)
+                .url_decode()
+                .ok()
+                .map(|v| v.into_owned())
+        })
+}
+
+#[rocket::async_trait]
+impl Fairing for PhilJsCsrfFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "PhilJS CSRF Fairing",
+            kind: Kind::Ignite | Kind::Request,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        Ok(rocket.manage(self.config.clone()))
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, data: &mut Data<'_>) {
+        let token = match request.cookies().get(&self.config.cookie_name) {
+            Some(cookie) => cookie.value().to_string(),
+            None => {
+                let token = uuid::Uuid::new_v4().simple().to_string();
+                request.cookies().add(
+                    rocket::http::Cookie::build((self.config.cookie_name.clone(), token.clone()))
+                        .path("/")
+                        .http_only(true)
+                        .same_site(rocket::http::SameSite::Strict),
+                );
+                token
+            }
+        };
+        request.local_cache(|| CsrfIssued(Some(token)));
+
+        // The hidden field is emitted first in each form, so the peek buffer holds it
+        if request.content_type() == Some(&ContentType::Form) {
+            let body = data.peek(CSRF_PEEK_BYTES).await.to_vec();
+            let submitted = form_field(&body, &self.config.field_name, data.peek_complete());
+            request.local_cache(|| CsrfSubmitted(submitted));
+        }
+    }
+}
+
+/// Static asset fairing for the built wasm/js/css bundle
+///
+/// Mounts a handler serving files from `dir` that:
+/// - marks content-hashed files (`app.3f2a1c9e.js`) as `immutable` for a year
+///   and everything else as revalidate-on-use
+/// - serves precompressed `.br`/`.gz` siblings when the client accepts them
+/// - answers `If-None-Match` with `304 Not Modified`
+/// - falls back to `index.html` for extension-less HTML navigations, so
+///   client-side routes survive a reload
+pub struct PhilJsStaticFairing {
+    mount: String,
+    handler: StaticAssetHandler,
+}
+
+impl PhilJsStaticFairing {
+    /// Serve `dir` at `/`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mount: "/".to_string(),
+            handler: StaticAssetHandler {
+                root: dir.into(),
+                spa_fallback: true,
+                index: "index.html".to_string(),
+                immutable_max_age: 31_536_000,
+                rank: 20,
+            },
+        }
+    }
+
+    /// Mount point for the assets
+    pub fn mount(mut self, path: impl Into<String>) -> Self {
+        self.mount = path.into();
+        self
+    }
+
+    /// Enable/disable the `index.html` fallback for client-routed paths
+    pub fn spa_fallback(mut self, enabled: bool) -> Self {
+        self.handler.spa_fallback = enabled;
+        self
+    }
+
+    /// Set the fallback document (relative to the asset directory)
+    pub fn index(mut self, file: impl Into<String>) -> Self {
+        self.handler.index = file.into();
+        self
+    }
+
+    /// Set `max-age` for content-hashed files in seconds
+    pub fn immutable_max_age(mut self, seconds: u64) -> Self {
+        self.handler.immutable_max_age = seconds;
+        self
+    }
+
+    /// Set the route rank; app routes with a lower rank win
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.handler.rank = rank;
+        self
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for PhilJsStaticFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "PhilJS Static Assets Fairing",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        info!(dir = %self.handler.root.display(), mount = %self.mount, "Serving static assets");
+        let rank = self.handler.rank;
+        let routes = vec![
+            Route::ranked(rank, Method::Get, "/<path..>", self.handler.clone()),
+            Route::ranked(rank, Method::Head, "/<path..>", self.handler.clone()),
+        ];
+        Ok(rocket.mount(self.mount.clone(), routes))
+    }
+}
+
+/// Route handler behind [`PhilJsStaticFairing`]
+#[derive(Clone)]
+pub struct StaticAssetHandler {
+    root: PathBuf,
+    spa_fallback: bool,
+    index: String,
+    immutable_max_age: u64,
+    rank: isize,
+}
+
+impl StaticAssetHandler {
+    fn locate(&self, request: &Request<'_>) -> Option<PathBuf> {
+        // `PathBuf` segments reject `..` and hidden files
+        let relative = request.segments::<PathBuf>(0..).ok()?;
+        let path = self.root.join(&relative);
+        if path.is_file() {
+            return Some(path);
+        }
+        if path.is_dir() && path.join(&self.index).is_file() {
+            return Some(path.join(&self.index));
+        }
+
+        let wants_html = request
+            .headers()
+            .get_one("Accept")
+            .map(|accept| accept.contains("text/html"))
+            .unwrap_or(false);
+        let index = self.root.join(&self.index);
+        if self.spa_fallback && wants_html && relative.extension().is_none() && index.is_file() {
+            return Some(index);
+        }
+        None
+    }
+
+    fn cache_control(&self, path: &Path) -> String {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if is_content_hashed(name) {
+            format!("public, max-age={}, immutable", self.immutable_max_age)
+        } else if name.ends_with(".html") {
+            "no-cache".to_string()
+        } else {
+            "public, max-age=0, must-revalidate".to_string()
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for StaticAssetHandler {
+    async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> RouteOutcome<'r> {
+        let Some(path) = self.locate(request) else {
+            return RouteOutcome::forward(data, Status::NotFound);
+        };
+
+        let accept_encoding = request.headers().get_one("Accept-Encoding").unwrap_or("");
+        let (file_path, encoding) = precompressed_variant(&path, accept_encoding);
+
+        let Ok(metadata) = tokio::fs::metadata(&file_path).await else {
+            return RouteOutcome::forward(data, Status::NotFound);
+        };
+        let etag = etag_for(&metadata, encoding);
+
+        let mut response = Response::build();
+        response
+            .header(Header::new("Cache-Control", self.cache_control(&path)))
+            .header(Header::new("ETag", etag.clone()))
+            .header(Header::new("Vary", "Accept-Encoding"));
+        if let Some(content_type) = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(ContentType::from_extension)
+        {
+            response.header(content_type);
+        }
+
+        let not_modified = request
+            .headers()
+            .get_one("If-None-Match")
+            .map(|value| etag_matches(value, &etag))
+            .unwrap_or(false);
+        if not_modified {
+            return RouteOutcome::Success(response.status(Status::NotModified).finalize());
+        }
+
+        if let Some(encoding) = encoding {
+            response.header(Header::new("Content-Encoding", encoding));
+        }
+        match tokio::fs::File::open(&file_path).await {
+            Ok(file) => RouteOutcome::Success(response.sized_body(None, file).finalize()),
+            Err(_) => RouteOutcome::forward(data, Status::NotFound),
+        }
+    }
+}
+
+/// Whether a file name carries a build content hash, e.g. `app.3f2a1c9e.js`
+/// or `index-BxT3k9aQ.css`
+fn is_content_hashed(name: &str) -> bool {
+    let stem = match name.rsplit_once('.') {
+        Some((stem, _)) => stem,
+        None => return false,
+    };
+    stem.split(|c| c == '.' || c == '-')
+        .skip(1)
+        .any(|part| {
+            part.len() >= 8
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && part.chars().any(|c| c.is_ascii_digit())
+        })
+}
+
+/// Pick a `.br`/`.gz` sibling the client accepts, preferring brotli
+fn precompressed_variant(path: &Path, accept_encoding: &str) -> (PathBuf, Option<&'static str>) {
+    let accepts = |coding: &str| {
+        accept_encoding.split(',').any(|part| {
+            let mut params = part.trim().split(';');
+            let name = params.next().unwrap_or("").trim();
+            let disabled = params.any(|p| matches!(p.trim(), "q=0" | "q=0.0" | "q=0.00" | "q=0.000"));
+            name.eq_ignore_ascii_case(coding) && !disabled
+        })
+    };
+
+    for (coding, ext) in [("br", "br"), ("gzip", "gz")] {
+        if accepts(coding) {
+            let mut candidate = path.as_os_str().to_owned();
+            candidate.push(".");
+            candidate.push(ext);
+            let candidate = PathBuf::from(candidate);
+            if candidate.is_file() {
+                return (candidate, Some(coding));
+            }
+        }
+    }
+    (path.to_path_buf(), None)
+}
+
+fn etag_for(metadata: &std::fs::Metadata, encoding: Option<&str>) -> String {
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    match encoding {
+        Some(encoding) => format!("\"{:x}-{:x}-{}\"", metadata.len(), modified, encoding),
+        None => format!("\"{:x}-{:x}\"", metadata.len(), modified),
+    }
+}
+
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == "*" || tag == etag)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -426,4 +967,254 @@ mod tests {
         assert!(!fairing.credentials);
         assert_eq!(fairing.max_age, 7200);
     }
+
+    #[test]
+    fn test_csrf_exempt_matches_whole_segments() {
+        let config = PhilJsCsrfFairing::new().exempt("/hooks").exempt("/api/public/").config;
+
+        assert!(config.is_exempt("/hooks"));
+        assert!(config.is_exempt("/hooks/github"));
+        assert!(!config.is_exempt("/hooksevil"));
+        assert!(!config.is_exempt("/hook"));
+        assert!(config.is_exempt("/api/public"));
+        assert!(config.is_exempt("/api/public/feed"));
+        assert!(!config.is_exempt("/api/publicity"));
+        assert!(!config.is_exempt("/"));
+    }
+
+    #[test]
+    fn test_form_field() {
+        assert_eq!(form_field(b"_csrf=abc%20d&name=x", "_csrf", true).as_deref(), Some("abc d"));
+        assert_eq!(form_field(b"name=x&_csrf=abc", "_csrf", true).as_deref(), Some("abc"));
+        assert_eq!(form_field(b"name=x", "_csrf", true), None);
+
+        // A cut-off last pair would be a truncated token
+        assert_eq!(form_field(b"_csrf=abc&name=x", "_csrf", false).as_deref(), Some("abc"));
+        assert_eq!(form_field(b"name=x&_csrf=ab", "_csrf", false), None);
+    }
+
+    fn asset_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("philjs-static-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("assets")).unwrap();
+        std::fs::write(dir.join("index.html"), "<div id=app></div>").unwrap();
+        std::fs::write(dir.join("assets/app.3f2a1c9e.js"), "console.log(1)").unwrap();
+        std::fs::write(dir.join("assets/app.3f2a1c9e.js.gz"), "gzipped").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_content_hash_detection() {
+        assert!(is_content_hashed("app.3f2a1c9e.js"));
+        assert!(is_content_hashed("index-BxT3k9aQ.css"));
+        assert!(!is_content_hashed("app.js"));
+        assert!(!is_content_hashed("favicon.ico"));
+        assert!(!is_content_hashed("my-component.css"));
+    }
+
+    #[test]
+    fn test_static_fairing_serves_assets() {
+        use rocket::local::blocking::Client;
+
+        let dir = asset_dir();
+        let client = Client::tracked(rocket::build().attach(PhilJsStaticFairing::new(&dir))).unwrap();
+
+        let response = client
+            .get("/assets/app.3f2a1c9e.js")
+            .header(Header::new("Accept-Encoding", "gzip, deflate"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+        assert!(response.headers().get_one("Cache-Control").unwrap().contains("immutable"));
+        let etag = response.headers().get_one("ETag").unwrap().to_string();
+
+        let cached = client
+            .get("/assets/app.3f2a1c9e.js")
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .header(Header::new("If-None-Match", etag))
+            .dispatch();
+        assert_eq!(cached.status(), Status::NotModified);
+
+        let spa = client
+            .get("/dashboard/settings")
+            .header(Header::new("Accept", "text/html"))
+            .dispatch();
+        assert_eq!(spa.headers().get_one("Cache-Control"), Some("no-cache"));
+        assert_eq!(spa.into_string().unwrap(), "<div id=app></div>");
+
+        assert_eq!(client.get("/missing.js").dispatch().status(), Status::NotFound);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_page_cache_fairing() {
+        use crate::guards::PageCache;
+        use crate::responders::PhilJsHtml;
+        use rocket::local::blocking::Client;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+        #[rocket::get("/page")]
+        async fn page(cache: PageCache<'_>) -> PhilJsHtml {
+            cache
+                .tag("pages")
+                .render(|| async {
+                    let n = RENDERS.fetch_add(1, Ordering::SeqCst);
+                    PhilJsHtml::new(format!("<p>{}</p>", n))
+                })
+                .await
+        }
+
+        #[rocket::post("/purge")]
+        fn purge(cache: &rocket::State<CacheState>) -> String {
+            cache.invalidate_tag("pages").to_string()
+        }
+
+        let rocket = rocket::build()
+            .attach(PhilJsPageCacheFairing::new().vary("Accept-Language"))
+            .mount("/", rocket::routes![page, purge]);
+        let client = Client::tracked(rocket).unwrap();
+
+        let first = client.get("/page").dispatch();
+        assert_eq!(first.headers().get_one("X-Cache"), Some("MISS"));
+        assert_eq!(first.into_string().unwrap(), "<p>0</p>");
+
+        let second = client.get("/page").dispatch();
+        assert_eq!(second.headers().get_one("X-Cache"), Some("HIT"));
+        assert_eq!(second.into_string().unwrap(), "<p>0</p>");
+
+        let french = client
+            .get("/page")
+            .header(Header::new("Accept-Language", "fr"))
+            .dispatch();
+        assert_eq!(french.headers().get_one("X-Cache"), Some("MISS"));
+
+        assert_eq!(client.post("/purge").dispatch().into_string().unwrap(), "2");
+        assert_eq!(client.get("/page").dispatch().into_string().unwrap(), "<p>2</p>");
+    }
+
+    #[test]
+    fn test_page_cache_skips_per_user_pages() {
+        use crate::guards::{CsrfToken, PageCache};
+        use crate::ssr::render_with_csrf;
+        use rocket::http::{Cookie, CookieJar};
+        use rocket::local::blocking::Client;
+
+        #[rocket::get("/signup")]
+        async fn signup(cache: PageCache<'_>, csrf: CsrfToken) -> PhilJsHtml {
+            cache
+                .render(|| async move { render_with_csrf("Sign up", &csrf, || "Join") })
+                .await
+        }
+
+        #[rocket::get("/welcome")]
+        async fn welcome(cache: PageCache<'_>, cookies: &CookieJar<'_>) -> PhilJsHtml {
+            cookies.add(Cookie::new("seen", "1"));
+            cache.render(|| async { PhilJsHtml::new("<p>welcome</p>") }).await
+        }
+
+        let rocket = rocket::build()
+            .attach(PhilJsCsrfFairing::new())
+            .attach(PhilJsPageCacheFairing::new())
+            .mount("/", rocket::routes![signup, welcome]);
+        // Untracked: every request is a new visitor without cookies
+        let client = Client::untracked(rocket).unwrap();
+
+        let first = client.get("/signup").dispatch();
+        assert_eq!(first.headers().get_one("X-Cache"), Some("BYPASS"));
+        let first = first.into_string().unwrap();
+        let second = client.get("/signup").dispatch();
+        assert_eq!(second.headers().get_one("X-Cache"), Some("BYPASS"));
+        let second = second.into_string().unwrap();
+        assert!(first.contains("name=\"csrf-token\""));
+        assert_ne!(first, second, "each visitor gets their own token");
+
+        for _ in 0..2 {
+            let page = client.get("/welcome").dispatch();
+            assert_eq!(page.headers().get_one("X-Cache"), Some("BYPASS"));
+            assert!(page.headers().get_one("Set-Cookie").is_some());
+        }
+        let cache = client.rocket().state::<CacheState>().unwrap();
+        assert!(cache.get("GET /welcome").is_none());
+    }
+
+    #[test]
+    fn test_csrf_fairing_verifies_forms() {
+        use crate::guards::CsrfToken;
+        use rocket::local::blocking::Client;
+
+        #[rocket::get("/form")]
+        fn form(csrf: CsrfToken) -> String {
+            csrf.token().to_string()
+        }
+
+        #[rocket::post("/submit")]
+        fn submit(_csrf: CsrfToken) -> &'static str {
+            "ok"
+        }
+
+        #[rocket::post("/hooks/stripe")]
+        fn hook(_csrf: CsrfToken) -> &'static str {
+            "ok"
+        }
+
+        let rocket = rocket::build()
+            .attach(PhilJsCsrfFairing::new().exempt("/hooks"))
+            .mount("/", rocket::routes![form, submit, hook]);
+        let client = Client::tracked(rocket).unwrap();
+
+        let token = client.get("/form").dispatch().into_string().unwrap();
+        assert_eq!(client.get("/form").dispatch().into_string().unwrap(), token);
+
+        let ok = client
+            .post("/submit")
+            .header(ContentType::Form)
+            .body(format!("_csrf={}&name=x", token))
+            .dispatch();
+        assert_eq!(ok.status(), Status::Ok);
+
+        let via_header = client
+            .post("/submit")
+            .header(Header::new("X-CSRF-Token", token))
+            .dispatch();
+        assert_eq!(via_header.status(), Status::Ok);
+
+        let forged = client
+            .post("/submit")
+            .header(ContentType::Form)
+            .body("_csrf=forged")
+            .dispatch();
+        assert_eq!(forged.status(), Status::Forbidden);
+
+        assert_eq!(client.post("/hooks/stripe").dispatch().status(), Status::Ok);
+    }
+
+    #[test]
+    fn test_metrics_endpoint() {
+        use rocket::local::blocking::Client;
+
+        #[rocket::get("/hello")]
+        fn hello() -> &'static str {
+            "hi"
+        }
+
+        let metrics = Metrics::new();
+        let rocket = rocket::build()
+            .attach(PhilJsMetricsFairing::new().metrics(metrics.clone()).endpoint("/metrics"))
+            .mount("/", rocket::routes![hello]);
+        let client = Client::tracked(rocket).unwrap();
+
+        client.get("/hello").dispatch();
+        client.get("/nope").dispatch();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.total_requests(), 2);
+        assert_eq!(snapshot.in_flight, 0);
+        assert!(snapshot.requests.iter().any(|r| r.route == "/hello" && r.status == 200));
+
+        let body = client.get("/metrics").dispatch().into_string().unwrap();
+        assert!(body.contains("philjs_http_requests_total{method=\"GET\",route=\"/hello\",status=\"200\"} 1"));
+        assert!(body.contains("# TYPE philjs_http_request_duration_seconds histogram"));
+    }
 }
@@ -3,9 +3,16 @@
 //! Guards are Rocket's mechanism for validating and extracting data from requests.
 
 use rocket::request::{FromRequest, Outcome, Request};
-use rocket::http::Status;
+use rocket::http::{Method, Status};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use crate::auth::{authenticate, AnyRole, Role};
+use crate::fairing::{CsrfConfig, PageCacheConfig};
+use crate::responders::PhilJsHtml;
+use crate::ssr::CSRF_FIELD;
+use crate::state::CacheState;
 
 /// SSR context guard providing request information for server-side rendering
 #[derive(Debug, Clone)]
@@ -119,8 +126,13 @@ impl<'r> FromRequest<'r> for SsrContext {
 }
 
 /// Authenticated user guard
+///
+/// Resolved through the managed [`Auth`](crate::auth::Auth) backend from a
+/// bearer token or the session cookie. The role parameter restricts access:
+/// `AuthUser<Admin>` only succeeds for users holding the `admin` role and
+/// fails with `403 Forbidden` otherwise.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AuthUser {
+pub struct AuthUser<R: Role = AnyRole> {
     /// User ID
     pub id: String,
     /// Username
@@ -129,6 +141,8 @@ pub struct AuthUser {
     pub email: Option<String>,
     /// User roles
     pub roles: Vec<String>,
+    #[serde(skip)]
+    role: PhantomData<R>,
 }
 
 impl AuthUser {
@@ -139,9 +153,30 @@ impl AuthUser {
             username: None,
             email: None,
             roles: Vec::new(),
+            role: PhantomData,
         }
     }
 
+    /// Set the username
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Set the email
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Add a role
+    pub fn role(mut self, role: impl Into<String>) -> Self {
+        self.roles.push(role.into());
+        self
+    }
+}
+
+impl<R: Role> AuthUser<R> {
     /// Check if user has a specific role
     pub fn has_role(&self, role: &str) -> bool {
         self.roles.iter().any(|r| r == role)
@@ -156,44 +191,37 @@ impl AuthUser {
     pub fn has_all_roles(&self, roles: &[&str]) -> bool {
         roles.iter().all(|r| self.has_role(r))
     }
+
+    /// Re-check the user against another role, returning `None` if it is not permitted
+    pub fn into_role<S: Role>(self) -> Option<AuthUser<S>> {
+        if !S::permits(&self.roles) {
+            return None;
+        }
+        Some(AuthUser {
+            id: self.id,
+            username: self.username,
+            email: self.email,
+            roles: self.roles,
+            role: PhantomData,
+        })
+    }
 }
 
 #[rocket::async_trait]
-impl<'r> FromRequest<'r> for AuthUser {
+impl<'r, R: Role> FromRequest<'r> for AuthUser<R> {
     type Error = ();
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        // Check for authorization header
-        let auth_header = request.headers().get_one("Authorization");
-
-        match auth_header {
-            Some(token) if token.starts_with("Bearer ") => {
-                // In a real implementation, validate the JWT token here
-                // For now, we'll create a placeholder user
-                let _token_value = &token[7..];
-
-                // Placeholder - in production, decode and validate JWT
-                Outcome::Success(AuthUser {
-                    id: "user-123".to_string(),
-                    username: Some("demo".to_string()),
-                    email: Some("demo@example.com".to_string()),
-                    roles: vec!["user".to_string()],
-                })
-            }
-            _ => {
-                // Check for session cookie
-                if let Some(_session) = request.cookies().get("session_id") {
-                    // Placeholder - in production, validate session
-                    Outcome::Success(AuthUser {
-                        id: "user-456".to_string(),
-                        username: Some("session_user".to_string()),
-                        email: None,
-                        roles: vec!["user".to_string()],
-                    })
-                } else {
-                    Outcome::Forward(Status::Unauthorized)
-                }
-            }
+        let user = request
+            .local_cache_async(async { authenticate(request).await })
+            .await;
+
+        match user {
+            Some(user) => match user.clone().into_role::<R>() {
+                Some(user) => Outcome::Success(user),
+                None => Outcome::Error((Status::Forbidden, ())),
+            },
+            None => Outcome::Forward(Status::Unauthorized),
         }
     }
 }
@@ -213,6 +241,144 @@ impl<'r> FromRequest<'r> for MaybeAuthUser {
     }
 }
 
+/// Page cache guard
+///
+/// Keys the current route by method, path, query, and the vary headers
+/// configured on [`PhilJsPageCacheFairing`](crate::fairing::PhilJsPageCacheFairing).
+///
+/// A cached page is served to every visitor with the same key, so only
+/// cache pages that look the same for everyone. Two kinds of per-user page
+/// are never cached: pages embedding a CSRF token (e.g. rendered with
+/// [`render_with_csrf`](crate::ssr::render_with_csrf)), and responses that
+/// set cookies, which are evicted again before the response is sent (a
+/// request for the same key arriving in between may still be served it).
+/// Other per-user content, such as a signed-in user's name, must not be
+/// rendered through this guard.
+///
+/// ```rust,ignore
+/// #[get("/posts/<id>")]
+/// async fn post(id: u64, cache: PageCache<'_>) -> PhilJsHtml {
+///     cache
+///         .tag("posts")
+///         .tag(format!("post:{}", id))
+///         .render(|| async move { render_document("Post", move || post_view(id)) })
+///         .await
+/// }
+///
+/// #[post("/posts/<id>")]
+/// fn update(id: u64, cache: &State<CacheState>) {
+///     cache.invalidate_tag(&format!("post:{}", id));
+/// }
+/// ```
+pub struct PageCache<'r> {
+    cache: &'r CacheState,
+    status: &'r PageCacheStatus,
+    key: String,
+    ttl: u64,
+    tags: Vec<String>,
+}
+
+/// Cache outcome recorded for the `X-Cache` response header
+#[derive(Default)]
+pub(crate) struct PageCacheStatus {
+    pub(crate) outcome: parking_lot::Mutex<Option<&'static str>>,
+    /// Key this request stored, evicted if the response sets cookies
+    pub(crate) stored: parking_lot::Mutex<Option<String>>,
+}
+
+/// Whether rendered HTML embeds a per-session CSRF token
+fn carries_csrf_token(html: &str) -> bool {
+    html.contains(r#"<meta name="csrf-token""#) || html.contains(&format!(r#"name="{}""#, CSRF_FIELD))
+}
+
+impl<'r> PageCache<'r> {
+    /// The cache key for this request
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Override the TTL in seconds
+    pub fn ttl(mut self, seconds: u64) -> Self {
+        self.ttl = seconds;
+        self
+    }
+
+    /// Tag the cached page for later invalidation
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Serve the cached page, or render, cache, and serve it.
+    ///
+    /// Only `200 OK` responses without a CSRF token are cached; concurrent
+    /// misses render once.
+    pub async fn render<F, Fut>(self, render: F) -> PhilJsHtml
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = PhilJsHtml>,
+    {
+        let mut uncached = None;
+        let mut rendered = false;
+        let (uncached_slot, rendered_flag) = (&mut uncached, &mut rendered);
+        let cached = self
+            .cache
+            .get_or_insert_with(&self.key, self.ttl, &self.tags, move || async move {
+                *rendered_flag = true;
+                let page = render().await;
+                if page.status_code() == Status::Ok && !carries_csrf_token(page.html()) {
+                    Some(page.html().to_string())
+                } else {
+                    *uncached_slot = Some(page);
+                    None
+                }
+            })
+            .await;
+
+        let outcome = match (rendered, &cached) {
+            (false, _) => "HIT",
+            (true, Some(_)) => "MISS",
+            (true, None) => "BYPASS",
+        };
+        *self.status.outcome.lock() = Some(outcome);
+        if outcome == "MISS" {
+            *self.status.stored.lock() = Some(self.key.clone());
+        }
+
+        match (cached, uncached) {
+            (Some(html), _) => PhilJsHtml::new(html),
+            (None, Some(page)) => page,
+            (None, None) => PhilJsHtml::server_error("Page cache error"),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for PageCache<'r> {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(cache) = request.rocket().state::<CacheState>() else {
+            return Outcome::Error((Status::InternalServerError, ()));
+        };
+        let config = request.rocket().state::<PageCacheConfig>();
+
+        let mut key = format!("{} {}", request.method(), request.uri());
+        for name in config.map(|c| c.vary.as_slice()).unwrap_or_default() {
+            let value = request.headers().get_one(name).unwrap_or("");
+            key.push_str(&format!("|{}={}", name.to_lowercase(), value));
+        }
+
+        Outcome::Success(PageCache {
+            cache,
+            status: request.local_cache(PageCacheStatus::default),
+            key,
+            ttl: config.map(|c| c.ttl).unwrap_or_else(|| cache.default_ttl()),
+            tags: Vec::new(),
+        })
+    }
+}
+
 /// CSRF token guard
 #[derive(Debug, Clone)]
 pub struct CsrfToken {
@@ -239,29 +405,53 @@ impl CsrfToken {
     }
 }
 
+/// Token minted by `PhilJsCsrfFairing` for the current request
+pub(crate) struct CsrfIssued(pub(crate) Option<String>);
+
+/// Token found in a submitted form body by `PhilJsCsrfFairing`
+pub(crate) struct CsrfSubmitted(pub(crate) Option<String>);
+
+/// Compare tokens without short-circuiting on the first differing byte
+fn tokens_match(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for CsrfToken {
     type Error = ();
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        // Check for CSRF token in header or cookie
-        let header_token = request.headers().get_one("X-CSRF-Token");
-        let cookie_token = request.cookies().get("csrf_token").map(|c| c.value());
-
-        match (header_token, cookie_token) {
-            (Some(header), Some(cookie)) if header == cookie => {
-                Outcome::Success(CsrfToken {
-                    token: header.to_string(),
-                })
-            }
-            _ => {
-                // For GET requests, generate a new token
-                if request.method() == rocket::http::Method::Get {
-                    Outcome::Success(CsrfToken::generate())
-                } else {
-                    Outcome::Forward(Status::Forbidden)
-                }
+        let default_config = CsrfConfig::default();
+        let config = request.rocket().state::<CsrfConfig>().unwrap_or(&default_config);
+
+        let issued = request
+            .local_cache(|| CsrfIssued(None))
+            .0
+            .clone()
+            .or_else(|| request.cookies().get(&config.cookie_name).map(|c| c.value().to_string()));
+
+        let safe_method = matches!(
+            request.method(),
+            Method::Get | Method::Head | Method::Options | Method::Trace
+        );
+        if safe_method || config.is_exempt(request.uri().path().as_str()) {
+            return match issued {
+                Some(token) => Outcome::Success(CsrfToken { token }),
+                None => Outcome::Success(CsrfToken::generate()),
+            };
+        }
+
+        let submitted = request
+            .headers()
+            .get_one(&config.header_name)
+            .map(str::to_string)
+            .or_else(|| request.local_cache(|| CsrfSubmitted(None)).0.clone());
+
+        match (issued, submitted) {
+            (Some(issued), Some(submitted)) if tokens_match(&issued, &submitted) => {
+                Outcome::Success(CsrfToken { token: issued })
             }
+            _ => Outcome::Error((Status::Forbidden, ())),
         }
     }
 }
@@ -429,12 +619,10 @@ mod tests {
 
     #[test]
     fn test_auth_user_roles() {
-        let user = AuthUser {
-            id: "123".to_string(),
-            username: Some("test".to_string()),
-            email: None,
-            roles: vec!["admin".to_string(), "user".to_string()],
-        };
+        let user = AuthUser::new("123")
+            .username("test")
+            .role("admin")
+            .role("user");
 
         assert!(user.has_role("admin"));
         assert!(user.has_role("user"));
@@ -455,6 +643,13 @@ mod tests {
         assert_eq!(params.limit(), 20);
     }
 
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc", "abc"));
+        assert!(!tokens_match("abc", "abd"));
+        assert!(!tokens_match("abc", "abcd"));
+    }
+
     #[test]
     fn test_csrf_token() {
         let token = CsrfToken::generate();
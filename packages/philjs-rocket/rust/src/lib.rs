@@ -35,13 +35,16 @@
 #![warn(missing_docs)]
 
 pub mod app;
+pub mod auth;
 pub mod config;
 pub mod error;
 pub mod fairing;
 pub mod guards;
 pub mod handlers;
+pub mod metrics;
 pub mod middleware;
 pub mod responders;
+pub mod server_fn;
 pub mod ssr;
 pub mod state;
 
@@ -53,17 +56,20 @@ pub mod templates;
 
 // Re-exports
 pub use app::{PhilJsApp, PhilJsPresets};
+pub use auth::{Auth, AuthBackend, Role, AnyRole, Admin};
 pub use config::PhilJsConfig;
 pub use error::PhilJsError;
-pub use fairing::{PhilJsSsrFairing, PhilJsLiveViewFairing, PhilJsMetricsFairing, PhilJsCorsFairing, PhilJsSecurityFairing};
-pub use guards::{SsrContext, AuthUser, CsrfToken, ConnectionInfo, PaginationParams, QueryParams};
+pub use fairing::{PhilJsSsrFairing, PhilJsLiveViewFairing, PhilJsMetricsFairing, PhilJsCorsFairing, PhilJsSecurityFairing, PhilJsStaticFairing, PhilJsPageCacheFairing, PhilJsCsrfFairing};
+pub use guards::{SsrContext, AuthUser, MaybeAuthUser, PageCache, CsrfToken, ConnectionInfo, PaginationParams, QueryParams};
 pub use handlers::{health_check, PaginationParams as HandlerPaginationParams, ErrorHandler};
+pub use metrics::{Metrics, MetricsSnapshot};
 pub use responders::{PhilJsHtml, PhilJsJson, PhilJsStream, PhilJsRedirect, PhilJsEmpty, PhilJsError as ErrorResponse, ApiResponse, PaginatedResponse};
-pub use ssr::{render, render_document, render_with_data, render_stream, HtmlDocument, MetaTag, Script, SeoBuilder};
-pub use state::{AppState, AppStateBuilder, CacheState, SessionState};
+pub use ssr::{render, render_document, render_with_data, render_stream, render_stream_document, render_with_csrf, HtmlDocument, MetaTag, Script, SeoBuilder};
+pub use server_fn::{PhilJsServerFnRoutes, ServerFnMiddleware};
+pub use state::{AppState, AppStateBuilder, CacheState, SessionState, SESSION_COOKIE};
 
 #[cfg(feature = "websocket")]
-pub use websocket::{LiveViewSocket, LiveViewHandler, BroadcastManager, PresenceTracker};
+pub use websocket::{LiveViewSocket, LiveViewHandler, LiveViewHub, LiveViewFactory, BroadcastManager, PresenceTracker};
 
 #[cfg(feature = "templates")]
 pub use templates::{TemplateContext, render_template, template_fairing};
@@ -73,6 +79,9 @@ pub mod prelude {
     // App builder
     pub use crate::app::{PhilJsApp, PhilJsPresets};
 
+    // Authentication
+    pub use crate::auth::{Auth, AuthBackend, Role, AnyRole, Admin};
+
     // Configuration
     pub use crate::config::PhilJsConfig;
 
@@ -80,10 +89,10 @@ pub mod prelude {
     pub use crate::error::PhilJsError;
 
     // Fairings
-    pub use crate::fairing::{PhilJsSsrFairing, PhilJsLiveViewFairing, PhilJsMetricsFairing, PhilJsCorsFairing, PhilJsSecurityFairing};
+    pub use crate::fairing::{PhilJsSsrFairing, PhilJsLiveViewFairing, PhilJsMetricsFairing, PhilJsCorsFairing, PhilJsSecurityFairing, PhilJsStaticFairing, PhilJsPageCacheFairing, PhilJsCsrfFairing};
 
     // Guards
-    pub use crate::guards::{SsrContext, AuthUser, CsrfToken, ConnectionInfo, PaginationParams, QueryParams};
+    pub use crate::guards::{SsrContext, AuthUser, MaybeAuthUser, PageCache, CsrfToken, ConnectionInfo, PaginationParams, QueryParams};
 
     // Handlers
     pub use crate::handlers::{health_check, ErrorHandler, api_success, api_error, paginated};
@@ -95,13 +104,17 @@ pub mod prelude {
     pub use crate::responders::{PhilJsHtml, PhilJsJson, PhilJsStream, PhilJsRedirect, PhilJsEmpty, ApiResponse, PaginatedResponse};
 
     // SSR
-    pub use crate::ssr::{render, render_document, render_with_data, HtmlDocument, MetaTag, Script, SeoBuilder};
+    pub use crate::ssr::{render, render_document, render_with_data, render_stream, render_stream_document, render_with_csrf, HtmlDocument, MetaTag, Script, SeoBuilder};
+    pub use philjs::StreamingConfig;
+
+    // Server functions
+    pub use crate::server_fn::PhilJsServerFnRoutes;
 
     // State
     pub use crate::state::{AppState, AppStateBuilder, CacheState, SessionState};
 
     #[cfg(feature = "websocket")]
-    pub use crate::websocket::{LiveViewSocket, LiveViewHandler, BroadcastManager, PresenceTracker};
+    pub use crate::websocket::{LiveViewSocket, LiveViewHandler, LiveViewHub, BroadcastManager, PresenceTracker};
 
     #[cfg(feature = "templates")]
     pub use crate::templates::{TemplateContext, render_template};
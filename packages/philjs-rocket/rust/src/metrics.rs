@@ -0,0 +1,301 @@
+//! Request, SSR, and LiveView metrics for PhilJS Rocket integration
+//!
+//! [`PhilJsMetricsFairing`](crate::fairing::PhilJsMetricsFairing) records
+//! into [`Metrics::global`]; render helpers and `LiveViewHub` do the same, so
+//! a single `/metrics` endpoint covers the whole app.
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Histogram bucket upper bounds in seconds
+pub const DEFAULT_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Latency histogram with cumulative buckets
+#[derive(Debug)]
+struct Histogram {
+    bounds: &'static [f64],
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, seconds: f64) {
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter_mut()) {
+            if seconds <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+
+    fn snapshot(&self) -> HistogramSnapshot {
+        HistogramSnapshot {
+            buckets: self.bounds.iter().copied().zip(self.counts.iter().copied()).collect(),
+            sum: self.sum,
+            count: self.count,
+        }
+    }
+}
+
+/// Point-in-time copy of a histogram
+#[derive(Debug, Clone, Serialize)]
+pub struct HistogramSnapshot {
+    /// `(upper bound in seconds, cumulative count)` pairs
+    pub buckets: Vec<(f64, u64)>,
+    /// Sum of all observations in seconds
+    pub sum: f64,
+    /// Number of observations
+    pub count: u64,
+}
+
+/// Completed request count for one method, route, and status
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestCount {
+    /// HTTP method
+    pub method: String,
+    /// Matched route URI, or `unmatched`
+    pub route: String,
+    /// Response status code
+    pub status: u16,
+    /// Number of requests
+    pub count: u64,
+}
+
+/// Point-in-time copy of all metrics, for custom dashboards
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsSnapshot {
+    /// Completed requests by method, route, and status
+    pub requests: Vec<RequestCount>,
+    /// Requests currently being handled
+    pub in_flight: i64,
+    /// Request latency
+    pub request_duration: HistogramSnapshot,
+    /// SSR render duration
+    pub ssr_render_duration: HistogramSnapshot,
+    /// Connected LiveView sockets
+    pub liveview_sockets: i64,
+}
+
+impl MetricsSnapshot {
+    /// Total completed requests
+    pub fn total_requests(&self) -> u64 {
+        self.requests.iter().map(|r| r.count).sum()
+    }
+}
+
+/// Shared metrics registry
+#[derive(Clone)]
+pub struct Metrics {
+    inner: Arc<MetricsInner>,
+}
+
+struct MetricsInner {
+    requests: Mutex<BTreeMap<(String, String, u16), u64>>,
+    in_flight: AtomicI64,
+    request_duration: Mutex<Histogram>,
+    ssr_render_duration: Mutex<Histogram>,
+    liveview_sockets: AtomicI64,
+}
+
+impl Metrics {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(MetricsInner {
+                requests: Mutex::new(BTreeMap::new()),
+                in_flight: AtomicI64::new(0),
+                request_duration: Mutex::new(Histogram::new(DEFAULT_BUCKETS)),
+                ssr_render_duration: Mutex::new(Histogram::new(DEFAULT_BUCKETS)),
+                liveview_sockets: AtomicI64::new(0),
+            }),
+        }
+    }
+
+    /// The process-wide registry
+    pub fn global() -> &'static Metrics {
+        static GLOBAL: OnceLock<Metrics> = OnceLock::new();
+        GLOBAL.get_or_init(Metrics::new)
+    }
+
+    /// Mark a request as started
+    pub fn request_started(&self) {
+        self.inner.in_flight.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a completed request
+    pub fn request_finished(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        self.inner.in_flight.fetch_sub(1, Ordering::Relaxed);
+        *self
+            .inner
+            .requests
+            .lock()
+            .entry((method.to_string(), route.to_string(), status))
+            .or_insert(0) += 1;
+        self.inner.request_duration.lock().observe(duration.as_secs_f64());
+    }
+
+    /// Record an SSR render
+    pub fn observe_render(&self, duration: Duration) {
+        self.inner.ssr_render_duration.lock().observe(duration.as_secs_f64());
+    }
+
+    /// Time `f` as an SSR render
+    pub fn time_render<T>(&self, f: impl FnOnce() -> T) -> T {
+        let start = std::time::Instant::now();
+        let output = f();
+        self.observe_render(start.elapsed());
+        output
+    }
+
+    /// Record a LiveView socket joining
+    pub fn liveview_connected(&self) {
+        self.inner.liveview_sockets.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a LiveView socket leaving
+    pub fn liveview_disconnected(&self) {
+        self.inner.liveview_sockets.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Copy the current values
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            requests: self
+                .inner
+                .requests
+                .lock()
+                .iter()
+                .map(|((method, route, status), count)| RequestCount {
+                    method: method.clone(),
+                    route: route.clone(),
+                    status: *status,
+                    count: *count,
+                })
+                .collect(),
+            in_flight: self.inner.in_flight.load(Ordering::Relaxed),
+            request_duration: self.inner.request_duration.lock().snapshot(),
+            ssr_render_duration: self.inner.ssr_render_duration.lock().snapshot(),
+            liveview_sockets: self.inner.liveview_sockets.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Render in the Prometheus text exposition format (version 0.0.4)
+    pub fn to_prometheus(&self) -> String {
+        let snapshot = self.snapshot();
+        let mut out = String::new();
+
+        out.push_str("# HELP philjs_http_requests_total Completed HTTP requests\n");
+        out.push_str("# TYPE philjs_http_requests_total counter\n");
+        for request in &snapshot.requests {
+            let _ = writeln!(
+                out,
+                "philjs_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                escape_label(&request.method),
+                escape_label(&request.route),
+                request.status,
+                request.count
+            );
+        }
+
+        out.push_str("# HELP philjs_http_requests_in_flight Requests currently being handled\n");
+        out.push_str("# TYPE philjs_http_requests_in_flight gauge\n");
+        let _ = writeln!(out, "philjs_http_requests_in_flight {}", snapshot.in_flight);
+
+        write_histogram(
+            &mut out,
+            "philjs_http_request_duration_seconds",
+            "HTTP request latency",
+            &snapshot.request_duration,
+        );
+        write_histogram(
+            &mut out,
+            "philjs_ssr_render_duration_seconds",
+            "SSR render duration",
+            &snapshot.ssr_render_duration,
+        );
+
+        out.push_str("# HELP philjs_liveview_sockets Connected LiveView sockets\n");
+        out.push_str("# TYPE philjs_liveview_sockets gauge\n");
+        let _ = writeln!(out, "philjs_liveview_sockets {}", snapshot.liveview_sockets);
+
+        out
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn write_histogram(out: &mut String, name: &str, help: &str, histogram: &HistogramSnapshot) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} histogram", name);
+    for (bound, count) in &histogram.buckets {
+        let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, count);
+    }
+    let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, histogram.count);
+    let _ = writeln!(out, "{}_sum {}", name, histogram.sum);
+    let _ = writeln!(out, "{}_count {}", name, histogram.count);
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let mut histogram = Histogram::new(&[0.1, 1.0]);
+        histogram.observe(0.05);
+        histogram.observe(0.5);
+        histogram.observe(2.0);
+
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.buckets, vec![(0.1, 1), (1.0, 2)]);
+        assert_eq!(snapshot.count, 3);
+    }
+
+    #[test]
+    fn test_prometheus_output() {
+        let metrics = Metrics::new();
+        metrics.request_started();
+        metrics.request_finished("GET", "/posts/<id>", 200, Duration::from_millis(12));
+        metrics.observe_render(Duration::from_millis(3));
+        metrics.liveview_connected();
+
+        let text = metrics.to_prometheus();
+        assert!(text.contains(
+            "philjs_http_requests_total{method=\"GET\",route=\"/posts/<id>\",status=\"200\"} 1"
+        ));
+        assert!(text.contains("philjs_http_requests_in_flight 0"));
+        assert!(text.contains("philjs_http_request_duration_seconds_bucket{le=\"0.025\"} 1"));
+        assert!(text.contains("philjs_ssr_render_duration_seconds_count 1"));
+        assert!(text.contains("philjs_liveview_sockets 1"));
+        assert_eq!(metrics.snapshot().total_requests(), 1);
+    }
+}
@@ -2,11 +2,16 @@
 //!
 //! Responders are Rocket's way of creating HTTP responses.
 
+use futures::stream::{self, Stream, StreamExt};
+use futures::future;
+use philjs::StreamingConfig;
 use rocket::http::{ContentType, Status};
+use rocket::response::stream::ReaderStream;
 use rocket::response::{Responder, Response};
 use rocket::request::Request;
 use serde::Serialize;
 use std::io::Cursor;
+use std::pin::Pin;
 
 /// HTML response responder
 pub struct PhilJsHtml {
@@ -44,6 +49,16 @@ impl PhilJsHtml {
             status: Status::InternalServerError,
         }
     }
+
+    /// The HTML body
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+
+    /// The HTTP status
+    pub fn status_code(&self) -> Status {
+        self.status
+    }
 }
 
 impl<'r> Responder<'r, 'static> for PhilJsHtml {
@@ -101,23 +116,66 @@ impl<'r, T: Serialize> Responder<'r, 'static> for PhilJsJson<T> {
 
 /// Streaming response responder
 pub struct PhilJsStream {
-    html: String,
+    body: Pin<Box<dyn Stream<Item = String> + Send>>,
+    status: Status,
 }
 
 impl PhilJsStream {
-    /// Create a new streaming response
+    /// Create a streaming response from already rendered HTML (sent as one chunk)
     pub fn new(html: impl Into<String>) -> Self {
-        Self { html: html.into() }
+        Self::from_stream(stream::once(future::ready(html.into())))
+    }
+
+    /// Create a streaming response from a stream of HTML chunks
+    pub fn from_stream<S>(chunks: S) -> Self
+    where
+        S: Stream<Item = String> + Send + 'static,
+    {
+        Self {
+            body: Box::pin(chunks),
+            status: Status::Ok,
+        }
+    }
+
+    /// Stream a PhilJS view with `philjs::ssr::render_to_stream_async`.
+    ///
+    /// The shell is flushed immediately and each suspense boundary is sent as
+    /// its content resolves.
+    pub fn render<F, V>(f: F, config: StreamingConfig) -> Self
+    where
+        F: FnOnce() -> V,
+        V: philjs::IntoView,
+    {
+        Self::from_stream(philjs::render_to_stream_async(f, config))
+    }
+
+    /// Wrap the stream with a document prefix and suffix
+    pub fn wrap(self, prefix: impl Into<String>, suffix: impl Into<String>) -> Self {
+        let status = self.status;
+        let head = stream::once(future::ready(prefix.into()));
+        let tail = stream::once(future::ready(suffix.into()));
+        Self {
+            body: Box::pin(head.chain(self.body).chain(tail)),
+            status,
+        }
+    }
+
+    /// Set the HTTP status
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = status;
+        self
     }
 }
 
 impl<'r> Responder<'r, 'static> for PhilJsStream {
     fn respond_to(self, _request: &'r Request<'_>) -> rocket::response::Result<'static> {
+        // Every chunk becomes its own read so Rocket flushes it as soon as it is produced
+        let reader = ReaderStream::from(self.body.map(Cursor::new));
         Response::build()
-            .status(Status::Ok)
+            .status(self.status)
             .header(ContentType::HTML)
-            .raw_header("Transfer-Encoding", "chunked")
-            .sized_body(self.html.len(), Cursor::new(self.html))
+            .raw_header("X-Content-Type-Options", "nosniff")
+            .streamed_body(reader)
             .ok()
     }
 }
@@ -385,6 +443,16 @@ mod tests {
         assert_eq!(not_found.status, Status::NotFound);
     }
 
+    #[tokio::test]
+    async fn test_philjs_stream_wraps_chunks() {
+        let chunks = stream::iter(vec!["<p>a</p>".to_string(), "<p>b</p>".to_string()]);
+        let response = PhilJsStream::from_stream(chunks).wrap("<body>", "</body>");
+
+        let body: Vec<String> = response.body.collect().await;
+        assert_eq!(body, vec!["<body>", "<p>a</p>", "<p>b</p>", "</body>"]);
+        assert_eq!(response.status, Status::Ok);
+    }
+
     #[test]
     fn test_philjs_error() {
         let error = PhilJsError::not_found("Resource not found");
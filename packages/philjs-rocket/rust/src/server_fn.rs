@@ -0,0 +1,259 @@
+//! Server function endpoints for PhilJS Rocket integration
+//!
+//! Exposes every function registered with `philjs::server::register_server_fn`
+//! as a `POST` route, with the request available to the function through
+//! `philjs::server::server_context()`.
+//!
+//! ```rust,ignore
+//! philjs::server::register_server_fn::<GetUser>();
+//!
+//! rocket::build().attach(
+//!     PhilJsServerFnRoutes::new()
+//!         .middleware(|ctx| match ctx.bearer_token() {
+//!             Some(_) => Ok(()),
+//!             None => Err(ServerError::unauthorized("Missing token")),
+//!         }),
+//! )
+//! ```
+
+use philjs::server::{self, ServerContext, ServerError};
+use rocket::data::ToByteUnit;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Method, Status};
+use rocket::route::{Handler, Outcome as RouteOutcome, Route};
+use rocket::{Build, Data, Request, Response, Rocket};
+use std::io::Cursor;
+use std::sync::Arc;
+use tracing::info;
+
+/// Middleware run before every server function call
+///
+/// May enrich the context (e.g. resolve a user from a token) or reject the
+/// call with a [`ServerError`], whose status is returned to the client.
+pub type ServerFnMiddleware = Arc<dyn Fn(&mut ServerContext) -> Result<(), ServerError> + Send + Sync>;
+
+/// Fairing mounting the server function registry under `/api`
+pub struct PhilJsServerFnRoutes {
+    base: String,
+    handler: ServerFnHandler,
+}
+
+impl PhilJsServerFnRoutes {
+    /// Mount server functions under `/api`
+    pub fn new() -> Self {
+        Self {
+            base: "/api".to_string(),
+            handler: ServerFnHandler {
+                base: "/api".to_string(),
+                middleware: Vec::new(),
+                body_limit: 2 * 1024 * 1024,
+            },
+        }
+    }
+
+    /// Set the mount point; function paths are resolved relative to it
+    pub fn base(mut self, base: impl Into<String>) -> Self {
+        let base = base.into();
+        self.handler.base = base.clone();
+        self.base = base;
+        self
+    }
+
+    /// Add a middleware; middleware runs in the order added
+    pub fn middleware<F>(mut self, middleware: F) -> Self
+    where
+        F: Fn(&mut ServerContext) -> Result<(), ServerError> + Send + Sync + 'static,
+    {
+        self.handler.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Maximum request body size in bytes
+    pub fn body_limit(mut self, bytes: usize) -> Self {
+        self.handler.body_limit = bytes;
+        self
+    }
+}
+
+impl Default for PhilJsServerFnRoutes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for PhilJsServerFnRoutes {
+    fn info(&self) -> Info {
+        Info {
+            name: "PhilJS Server Functions",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        let functions = server::registered_server_fns();
+        info!(base = %self.base, count = functions.len(), "Mounting server functions");
+        let route = Route::new(Method::Post, "/<path..>", self.handler.clone());
+        Ok(rocket.mount(self.base.clone(), vec![route]))
+    }
+}
+
+/// Route handler behind [`PhilJsServerFnRoutes`]
+#[derive(Clone)]
+pub struct ServerFnHandler {
+    base: String,
+    middleware: Vec<ServerFnMiddleware>,
+    body_limit: usize,
+}
+
+impl ServerFnHandler {
+    /// Registry key for the request: the full path if registered, otherwise
+    /// the path below the mount point
+    fn function_path(&self, request: &Request<'_>) -> Option<&'static str> {
+        let full = request.uri().path().as_str().to_string();
+        let registered = server::registered_server_fns();
+        let relative = full
+            .strip_prefix(self.base.trim_end_matches('/'))
+            .unwrap_or(&full)
+            .to_string();
+        registered
+            .iter()
+            .find(|path| **path == full)
+            .or_else(|| registered.iter().find(|path| **path == relative))
+            .copied()
+    }
+}
+
+/// Build the server function context from a Rocket request
+pub fn server_context(request: &Request<'_>) -> ServerContext {
+    let mut context = ServerContext::new();
+    for header in request.headers().iter() {
+        context
+            .headers
+            .insert(header.name().as_str().to_lowercase(), header.value().to_string());
+    }
+    for cookie in request.cookies().iter() {
+        context
+            .cookies
+            .insert(cookie.name().to_string(), cookie.value().to_string());
+    }
+    context.client_ip = request.client_ip().map(|ip| ip.to_string());
+    if let Some(id) = request.headers().get_one("X-Request-Id") {
+        context.request_id = id.to_string();
+    }
+    context
+}
+
+fn json_response<'r>(status: Status, body: String) -> Response<'r> {
+    Response::build()
+        .status(status)
+        .header(ContentType::JSON)
+        .sized_body(body.len(), Cursor::new(body))
+        .finalize()
+}
+
+fn error_response<'r>(error: ServerError) -> Response<'r> {
+    let status = Status::from_code(error.status).unwrap_or(Status::InternalServerError);
+    json_response(status, serde_json::to_string(&error).unwrap_or_default())
+}
+
+#[rocket::async_trait]
+impl Handler for ServerFnHandler {
+    async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> RouteOutcome<'r> {
+        let Some(path) = self.function_path(request) else {
+            return RouteOutcome::forward(data, Status::NotFound);
+        };
+
+        let mut context = server_context(request);
+        for middleware in &self.middleware {
+            if let Err(error) = middleware(&mut context) {
+                return RouteOutcome::Success(error_response(error));
+            }
+        }
+
+        let body = match data.open(self.body_limit.bytes()).into_string().await {
+            Ok(body) if body.is_complete() => body.into_inner(),
+            Ok(_) => {
+                return RouteOutcome::Success(error_response(
+                    ServerError::new("Request body too large").with_status(413),
+                ))
+            }
+            Err(_) => {
+                return RouteOutcome::Success(error_response(ServerError::bad_request(
+                    "Invalid request body",
+                )))
+            }
+        };
+        let input = if body.trim().is_empty() { "null".to_string() } else { body };
+
+        let response = match server::with_server_context(context, server::call_server_fn(path, input)).await {
+            Ok(output) => json_response(Status::Ok, output),
+            Err(error) => error_response(error),
+        };
+        RouteOutcome::Success(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use philjs::server::{register_server_fn, ServerFn, ServerResult};
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+    use std::future::Future;
+    use std::pin::Pin;
+
+    struct Echo;
+
+    impl ServerFn for Echo {
+        type Input = String;
+        type Output = String;
+        const PATH: &'static str = "/api/echo";
+
+        fn run(input: String) -> Pin<Box<dyn Future<Output = ServerResult<String>> + Send>> {
+            let user = server::server_context().and_then(|ctx| ctx.header("x-user").cloned());
+            Box::pin(async move {
+                match user {
+                    Some(user) => Ok(format!("{}: {}", user, input)),
+                    None => Err(ServerError::not_found("no user")),
+                }
+            })
+        }
+    }
+
+    fn client() -> Client {
+        register_server_fn::<Echo>();
+        let routes = PhilJsServerFnRoutes::new().middleware(|ctx| match ctx.header("x-blocked") {
+            Some(_) => Err(ServerError::unauthorized("blocked")),
+            None => Ok(()),
+        });
+        Client::tracked(rocket::build().attach(routes)).unwrap()
+    }
+
+    #[test]
+    fn test_server_fn_receives_context() {
+        let client = client();
+        let response = client
+            .post("/api/echo")
+            .header(Header::new("X-User", "ada"))
+            .body("\"hi\"")
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "\"ada: hi\"");
+    }
+
+    #[test]
+    fn test_server_error_status_codes() {
+        let client = client();
+        assert_eq!(client.post("/api/echo").body("\"hi\"").dispatch().status(), Status::NotFound);
+        assert_eq!(client.post("/api/echo").body("42").dispatch().status(), Status::BadRequest);
+
+        let blocked = client
+            .post("/api/echo")
+            .header(Header::new("X-Blocked", "1"))
+            .body("\"hi\"")
+            .dispatch();
+        assert_eq!(blocked.status(), Status::Unauthorized);
+        assert!(blocked.into_string().unwrap().contains("UNAUTHORIZED"));
+    }
+}
@@ -1,6 +1,9 @@
 //! Server-side rendering utilities for PhilJS Rocket integration
 
-use crate::responders::PhilJsHtml;
+use crate::guards::CsrfToken;
+use crate::metrics::Metrics;
+use crate::responders::{PhilJsHtml, PhilJsStream};
+use philjs::StreamingConfig;
 use serde::Serialize;
 
 /// SSR configuration options
@@ -84,7 +87,7 @@ where
     F: FnOnce() -> V,
     V: philjs::IntoView,
 {
-    philjs::render_to_string(f)
+    Metrics::global().time_render(|| philjs::render_to_string(f))
 }
 
 /// Render a PhilJS view to an HTML response
@@ -93,7 +96,7 @@ where
     F: FnOnce() -> V,
     V: philjs::IntoView,
 {
-    let body_html = philjs::render_to_string(f);
+    let body_html = Metrics::global().time_render(|| philjs::render_to_string(f));
 
     let html = format!(
         r#"<!DOCTYPE html>
@@ -122,7 +125,7 @@ where
     V: philjs::IntoView,
     D: Serialize,
 {
-    let body_html = philjs::render_to_string(f);
+    let body_html = Metrics::global().time_render(|| philjs::render_to_string(f));
     let data_json = serde_json::to_string(&data).unwrap_or_default();
 
     let html = format!(
@@ -149,15 +152,91 @@ where
     PhilJsHtml::new(html)
 }
 
+/// Form field carrying the CSRF token
+pub const CSRF_FIELD: &str = "_csrf";
+
+/// Render a PhilJS view to an HTML response with the CSRF token embedded
+///
+/// Adds `<meta name="csrf-token">` for `fetch` calls and a hidden `_csrf`
+/// input to every `POST` form.
+pub fn render_with_csrf<F, V>(title: &str, csrf: &CsrfToken, f: F) -> PhilJsHtml
+where
+    F: FnOnce() -> V,
+    V: philjs::IntoView,
+{
+    let page = render_document(title, f);
+    PhilJsHtml::new(inject_csrf(page.html(), csrf.token()))
+}
+
+/// Embed a CSRF token into rendered HTML
+pub fn inject_csrf(html: &str, token: &str) -> String {
+    let token = philjs::liveview::escape_html(token);
+    let mut out = String::with_capacity(html.len() + 128);
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<form") {
+        let Some(end) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        out.push_str(&rest[..end]);
+        let tag = rest[start..end].to_ascii_lowercase();
+        if tag.contains("method=\"post\"") || tag.contains("method='post'") || tag.contains("method=post") {
+            out.push_str(&format!(
+                r#"<input type="hidden" name="{}" value="{}">"#,
+                CSRF_FIELD, token
+            ));
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+
+    let meta = format!(r#"<meta name="csrf-token" content="{}">"#, token);
+    match out.find("</head>") {
+        Some(at) => out.insert_str(at, &meta),
+        None => out.insert_str(0, &meta),
+    }
+    out
+}
+
 /// Render a streaming response
-pub fn render_stream<F, V>(f: F) -> PhilJsHtml
+///
+/// The shell is flushed immediately and suspense boundaries stream in as
+/// they resolve, according to `config`.
+pub fn render_stream<F, V>(f: F, config: StreamingConfig) -> PhilJsStream
 where
     F: FnOnce() -> V,
     V: philjs::IntoView,
 {
-    let html = philjs::render_to_string(f);
-    // Note: True streaming would require async stream response
-    PhilJsHtml::new(html)
+    PhilJsStream::render(f, config)
+}
+
+/// Render a streaming response wrapped in a full HTML document
+///
+/// The document head and the view shell go out in the first flush.
+pub fn render_stream_document<F, V>(title: &str, f: F, config: StreamingConfig) -> PhilJsStream
+where
+    F: FnOnce() -> V,
+    V: philjs::IntoView,
+{
+    let head = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>{}</title>
+    <link rel="stylesheet" href="/static/styles.css">
+</head>
+<body>
+    <div id="app">"#,
+        title
+    );
+    let tail = r#"</div>
+    <script type="module" src="/static/app.js"></script>
+</body>
+</html>"#;
+
+    PhilJsStream::render(f, config).wrap(head, tail)
 }
 
 /// HTML document builder for SSR
@@ -429,31 +508,31 @@ impl SeoBuilder {
     }
 
     /// Build meta tags
+    ///
+    /// Delegates to [`philjs::meta::Seo`] so every integration emits the same
+    /// Open Graph and Twitter card set.
     pub fn build(self) -> Vec<MetaTag> {
-        let mut tags = vec![MetaTag::name("title", &self.title)];
-
-        if let Some(desc) = &self.description {
-            tags.push(MetaTag::name("description", desc));
-        }
-
-        if !self.keywords.is_empty() {
-            tags.push(MetaTag::name("keywords", self.keywords.join(", ")));
-        }
-
-        // Open Graph
-        tags.push(MetaTag::property("og:title", &self.title));
-        if let Some(desc) = &self.description {
-            tags.push(MetaTag::property("og:description", desc));
+        let mut seo = philjs::meta::Seo::new()
+            .title(self.title.clone())
+            .keywords(self.keywords);
+        if let Some(desc) = self.description {
+            seo = seo.description(desc);
         }
         for (property, content) in self.og_tags {
-            tags.push(MetaTag::property(property, content));
+            seo = seo.og(property, content);
         }
-
-        // Twitter
         for (name, content) in self.twitter_tags {
-            tags.push(MetaTag::name(format!("twitter:{}", name), content));
+            seo = seo.twitter(name, content);
         }
 
+        let mut tags = vec![MetaTag::name("title", &self.title)];
+        for tag in seo.meta_tags(None) {
+            if let Some(name) = tag.name {
+                tags.push(MetaTag::name(name, tag.content));
+            } else if let Some(property) = tag.property {
+                tags.push(MetaTag::property(property, tag.content));
+            }
+        }
         tags
     }
 }
@@ -462,6 +541,16 @@ impl SeoBuilder {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_inject_csrf() {
+        let html = r#"<html><head></head><body><form method="POST" action="/a"><input name="x"></form><form action="/search"></form></body></html>"#;
+        let out = inject_csrf(html, "tok");
+
+        assert!(out.contains(r#"<meta name="csrf-token" content="tok"></head>"#));
+        assert!(out.contains(r#"action="/a"><input type="hidden" name="_csrf" value="tok">"#));
+        assert_eq!(out.matches("name=\"_csrf\"").count(), 1);
+    }
+
     #[test]
     fn test_html_document() {
         let doc = HtmlDocument::new("Test Page")
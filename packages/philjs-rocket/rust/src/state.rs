@@ -3,6 +3,7 @@
 use std::sync::Arc;
 use std::collections::HashMap;
 use parking_lot::RwLock;
+use rocket::http::{Cookie, CookieJar, SameSite};
 
 /// Application state shared across handlers
 #[derive(Clone)]
@@ -173,11 +174,17 @@ impl DbPool {
     }
 }
 
-/// Cache state (placeholder for integration)
+/// Response cache with TTLs, tag-based invalidation, and single-flight
+/// regeneration
+///
+/// Used directly as a key/value cache or, through the
+/// [`PageCache`](crate::guards::PageCache) guard, as an SSR page cache.
 #[derive(Clone)]
 pub struct CacheState {
     /// Cache entries
     entries: Arc<RwLock<HashMap<String, CacheEntry>>>,
+    /// Per-key locks held while a value is being regenerated
+    in_flight: Arc<parking_lot::Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>,
     /// Default TTL in seconds
     default_ttl: u64,
 }
@@ -186,6 +193,35 @@ pub struct CacheState {
 struct CacheEntry {
     value: String,
     expires_at: u64,
+    tags: Vec<String>,
+}
+
+/// Clears a key's [`CacheState::get_or_insert_with`] lock once its last user
+/// finishes, even if that user was cancelled or panicked
+struct InFlight<'a> {
+    locks: &'a parking_lot::Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+    key: &'a str,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        let mut locks = self.locks.lock();
+        // Clones are only taken under `locks`, so two means the map and us
+        let last = locks
+            .get(self.key)
+            .is_some_and(|lock| Arc::ptr_eq(lock, &self.lock) && Arc::strong_count(&self.lock) == 2);
+        if last {
+            locks.remove(self.key);
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
 }
 
 impl CacheState {
@@ -193,21 +229,22 @@ impl CacheState {
     pub fn new(default_ttl: u64) -> Self {
         Self {
             entries: Arc::new(RwLock::new(HashMap::new())),
+            in_flight: Arc::new(parking_lot::Mutex::new(HashMap::new())),
             default_ttl,
         }
     }
 
+    /// Default TTL in seconds
+    pub fn default_ttl(&self) -> u64 {
+        self.default_ttl
+    }
+
     /// Get a value from the cache
     pub fn get(&self, key: &str) -> Option<String> {
         let entries = self.entries.read();
         let entry = entries.get(key)?;
 
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        if entry.expires_at > now {
+        if entry.expires_at > now_secs() {
             Some(entry.value.clone())
         } else {
             None
@@ -221,32 +258,100 @@ impl CacheState {
 
     /// Set a value with custom TTL
     pub fn set_with_ttl(&self, key: impl Into<String>, value: impl Into<String>, ttl: u64) {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        self.set_tagged(key, value, ttl, Vec::<String>::new());
+    }
 
+    /// Set a value with a TTL and invalidation tags
+    pub fn set_tagged(
+        &self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+        ttl: u64,
+        tags: impl IntoIterator<Item = impl Into<String>>,
+    ) {
         self.entries.write().insert(
             key.into(),
             CacheEntry {
                 value: value.into(),
-                expires_at: now + ttl,
+                expires_at: now_secs() + ttl,
+                tags: tags.into_iter().map(Into::into).collect(),
             },
         );
     }
 
+    /// Get a cached value, or generate and store it.
+    ///
+    /// Concurrent misses for the same key wait for a single generation
+    /// instead of all rendering at once. Values for which `generate` returns
+    /// `None` are passed through uncached.
+    pub async fn get_or_insert_with<F, Fut>(
+        &self,
+        key: &str,
+        ttl: u64,
+        tags: &[String],
+        generate: F,
+    ) -> Option<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Option<String>>,
+    {
+        if let Some(value) = self.get(key) {
+            return Some(value);
+        }
+
+        let flight = InFlight {
+            locks: &self.in_flight,
+            key,
+            lock: self
+                .in_flight
+                .lock()
+                .entry(key.to_string())
+                .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
+                .clone(),
+        };
+        let _guard = flight.lock.lock().await;
+
+        // Another request may have filled the entry while we waited
+        if let Some(value) = self.get(key) {
+            return Some(value);
+        }
+
+        let value = generate().await;
+        if let Some(value) = &value {
+            self.set_tagged(key, value.clone(), ttl, tags.iter().cloned());
+        }
+        value
+    }
+
+    /// Remove every entry carrying `tag`, returning how many were removed
+    pub fn invalidate_tag(&self, tag: &str) -> usize {
+        let mut entries = self.entries.write();
+        let before = entries.len();
+        entries.retain(|_, entry| !entry.tags.iter().any(|t| t == tag));
+        before - entries.len()
+    }
+
+    /// Remove every entry whose key starts with `prefix`
+    pub fn invalidate_prefix(&self, prefix: &str) -> usize {
+        let mut entries = self.entries.write();
+        let before = entries.len();
+        entries.retain(|key, _| !key.starts_with(prefix));
+        before - entries.len()
+    }
+
     /// Remove a value from the cache
     pub fn remove(&self, key: &str) -> bool {
         self.entries.write().remove(key).is_some()
     }
 
+    /// Remove all entries
+    pub fn clear(&self) {
+        self.entries.write().clear();
+    }
+
     /// Clear all expired entries
     pub fn cleanup(&self) {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
+        let now = now_secs();
         self.entries.write().retain(|_, v| v.expires_at > now);
     }
 }
@@ -257,13 +362,24 @@ impl Default for CacheState {
     }
 }
 
-/// Session state (placeholder for integration)
+/// Default name of the session cookie
+pub const SESSION_COOKIE: &str = "philjs_session";
+
+/// Server-side session store keyed by a session cookie.
+///
+/// With the `secrets` feature the cookie is a Rocket private cookie
+/// (encrypted and signed with `secret_key`); otherwise it is a plain
+/// `HttpOnly` cookie holding the random session ID.
 #[derive(Clone)]
 pub struct SessionState {
     /// Sessions
     sessions: Arc<RwLock<HashMap<String, SessionData>>>,
     /// Session TTL in seconds
     ttl: u64,
+    /// Cookie name
+    cookie_name: String,
+    /// Mark the cookie `Secure`
+    secure: bool,
 }
 
 #[derive(Clone)]
@@ -278,9 +394,23 @@ impl SessionState {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             ttl,
+            cookie_name: SESSION_COOKIE.to_string(),
+            secure: false,
         }
     }
 
+    /// Set the session cookie name
+    pub fn cookie_name(mut self, name: impl Into<String>) -> Self {
+        self.cookie_name = name.into();
+        self
+    }
+
+    /// Only send the session cookie over HTTPS
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
     /// Create a new session
     pub fn create(&self) -> String {
         let id = uuid::Uuid::new_v4().to_string();
@@ -334,6 +464,67 @@ impl SessionState {
         self.sessions.write().remove(session_id).is_some()
     }
 
+    /// Whether a session exists and has not expired
+    pub fn is_active(&self, session_id: &str) -> bool {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        self.sessions
+            .read()
+            .get(session_id)
+            .map(|s| s.expires_at > now)
+            .unwrap_or(false)
+    }
+
+    /// Read the session ID from the request cookies, if it names a live session
+    pub fn from_cookies(&self, cookies: &CookieJar<'_>) -> Option<String> {
+        #[cfg(feature = "secrets")]
+        let cookie = cookies.get_private(&self.cookie_name);
+        #[cfg(not(feature = "secrets"))]
+        let cookie = cookies.get(&self.cookie_name).cloned();
+
+        cookie
+            .map(|c| c.value().to_string())
+            .filter(|id| self.is_active(id))
+    }
+
+    /// Return the current session ID, creating a session and cookie if needed
+    pub fn start(&self, cookies: &CookieJar<'_>) -> String {
+        if let Some(id) = self.from_cookies(cookies) {
+            return id;
+        }
+
+        let id = self.create();
+        let cookie = Cookie::build((self.cookie_name.clone(), id.clone()))
+            .path("/")
+            .http_only(true)
+            .secure(self.secure)
+            .same_site(SameSite::Lax)
+            .max_age(rocket::time::Duration::seconds(self.ttl as i64));
+
+        #[cfg(feature = "secrets")]
+        cookies.add_private(cookie);
+        #[cfg(not(feature = "secrets"))]
+        cookies.add(cookie);
+
+        id
+    }
+
+    /// Destroy the current session and remove its cookie
+    pub fn end(&self, cookies: &CookieJar<'_>) {
+        if let Some(id) = self.from_cookies(cookies) {
+            self.destroy(&id);
+        }
+
+        let cookie = Cookie::build(self.cookie_name.clone()).path("/");
+        #[cfg(feature = "secrets")]
+        cookies.remove_private(cookie);
+        #[cfg(not(feature = "secrets"))]
+        cookies.remove(cookie);
+    }
+
     /// Cleanup expired sessions
     pub fn cleanup(&self) {
         let now = std::time::SystemTime::now()
@@ -386,6 +577,71 @@ mod tests {
         assert!(cache.get("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_cache_tag_invalidation() {
+        let cache = CacheState::new(300);
+        cache.set_tagged("GET /posts/1", "<p>1</p>", 60, ["posts", "post:1"]);
+        cache.set_tagged("GET /posts/2", "<p>2</p>", 60, ["posts", "post:2"]);
+        cache.set("GET /about", "<p>about</p>");
+
+        assert_eq!(cache.invalidate_tag("post:1"), 1);
+        assert!(cache.get("GET /posts/1").is_none());
+        assert_eq!(cache.invalidate_tag("posts"), 1);
+        assert_eq!(cache.invalidate_prefix("GET /"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_single_flight() {
+        let cache = CacheState::new(300);
+        let renders = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let cache = cache.clone();
+                let renders = renders.clone();
+                tokio::spawn(async move {
+                    cache
+                        .get_or_insert_with("GET /", 60, &[], || async move {
+                            renders.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                            Some("<html></html>".to_string())
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            assert_eq!(task.await.unwrap().as_deref(), Some("<html></html>"));
+        }
+        assert_eq!(renders.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(cache.in_flight.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cache_single_flight_survives_cancel_and_panic() {
+        let cache = CacheState::new(300);
+
+        let stalled = cache.get_or_insert_with("GET /slow", 60, &[], std::future::pending::<Option<String>>);
+        let timeout = std::time::Duration::from_millis(10);
+        assert!(tokio::time::timeout(timeout, stalled).await.is_err());
+        assert!(cache.in_flight.lock().is_empty());
+
+        async fn render_failed() -> Option<String> {
+            panic!("render failed")
+        }
+        let panicking = cache.clone();
+        let task = tokio::spawn(async move { panicking.get_or_insert_with("GET /broken", 60, &[], render_failed).await });
+        assert!(task.await.unwrap_err().is_panic());
+        assert!(cache.in_flight.lock().is_empty());
+
+        // The keys are usable again
+        let value = cache
+            .get_or_insert_with("GET /slow", 60, &[], || async { Some("ok".to_string()) })
+            .await;
+        assert_eq!(value.as_deref(), Some("ok"));
+    }
+
     #[test]
     fn test_session_state() {
         let sessions = SessionState::new(3600);
@@ -1,15 +1,21 @@
 //! WebSocket support for PhilJS Rocket LiveView
 
+use rocket::futures::{SinkExt, StreamExt};
 use rocket::http::Status;
 use rocket::request::{FromRequest, Outcome, Request};
 use rocket_ws::{WebSocket, Message, Channel};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::collections::HashMap;
+use std::time::Duration;
 use parking_lot::RwLock;
-use tokio::sync::broadcast;
+use philjs::liveview::{self as core, LiveViewRegistry, LiveView};
+use tokio::sync::{broadcast, mpsc};
 use uuid::Uuid;
 
+use crate::guards::AuthUser;
+use crate::metrics::Metrics;
+
 /// LiveView WebSocket handler
 pub struct LiveViewSocket<T> {
     /// The LiveView component
@@ -356,6 +362,189 @@ impl Default for PresenceTracker {
     }
 }
 
+// ============================================================================
+// Registry-backed LiveView endpoint
+// ============================================================================
+
+pub use philjs::liveview::LiveViewFactory;
+
+/// WebSocket endpoint serving `philjs::liveview` views over `rocket_ws`.
+///
+/// Adapts `rocket_ws` to the core [`core::LiveViewHub`], which speaks the
+/// `WsMessage` protocol used by the generated LiveView client. Sockets that
+/// stop sending heartbeats are closed.
+///
+/// ```rust,ignore
+/// #[get("/live")]
+/// fn live(ws: WebSocket, user: MaybeAuthUser, hub: &State<Arc<LiveViewHub>>) -> Channel<'static> {
+///     hub.inner().clone().handle_as(ws, user.0)
+/// }
+///
+/// let hub = Arc::new(LiveViewHub::new().route("/counter", || Box::new(Counter::default())));
+/// rocket::build().manage(hub).mount("/", routes![live])
+/// ```
+pub struct LiveViewHub {
+    hub: core::LiveViewHub,
+    broadcast: Arc<BroadcastManager>,
+    presence: Arc<PresenceTracker>,
+}
+
+impl LiveViewHub {
+    /// Create a hub with its own registry, pub/sub, and presence tracking
+    pub fn new() -> Self {
+        Self {
+            hub: core::LiveViewHub::new(),
+            broadcast: Arc::new(BroadcastManager::default()),
+            presence: Arc::new(PresenceTracker::new()),
+        }
+    }
+
+    /// Share an existing registry (e.g. one with a running sweeper)
+    pub fn with_registry(mut self, registry: Arc<LiveViewRegistry>) -> Self {
+        self.hub = self.hub.with_registry(registry);
+        self
+    }
+
+    /// Share an existing broadcast manager
+    pub fn with_broadcast(mut self, broadcast: Arc<BroadcastManager>) -> Self {
+        self.broadcast = broadcast;
+        self
+    }
+
+    /// Share an existing presence tracker
+    pub fn with_presence(mut self, presence: Arc<PresenceTracker>) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    /// Close sockets that have not sent a heartbeat within `timeout`
+    pub fn heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.hub = self.hub.with_heartbeat_timeout(timeout);
+        self
+    }
+
+    /// Serve a view for joins on `topic` (usually the page path)
+    pub fn route<F>(mut self, topic: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn() -> Box<dyn LiveView> + Send + Sync + 'static,
+    {
+        self.hub = self.hub.route(topic, factory);
+        self
+    }
+
+    /// The view registry
+    pub fn registry(&self) -> &Arc<LiveViewRegistry> {
+        self.hub.registry()
+    }
+
+    /// The presence tracker
+    pub fn presence(&self) -> &Arc<PresenceTracker> {
+        &self.presence
+    }
+
+    /// Deliver `payload` to `handle_info` of every view subscribed to `topic`,
+    /// and to external `BroadcastManager` subscribers.
+    ///
+    /// Returns the number of local views notified.
+    pub fn publish(&self, topic: &str, payload: serde_json::Value) -> usize {
+        let delivered = self.hub.publish_info(topic, payload.clone());
+        let _ = self.broadcast.broadcast(topic, payload.to_string());
+        delivered
+    }
+
+    /// Subscribe a connected socket to an additional topic
+    pub fn subscribe(&self, topic: &str, socket_id: &str) {
+        self.hub.subscribe(topic, socket_id);
+    }
+
+    /// Handle an upgraded WebSocket until the client leaves or times out
+    ///
+    /// The socket shows up in presence lists under its socket id; use
+    /// [`handle_as`](Self::handle_as) to list signed-in users by id.
+    pub fn handle(self: Arc<Self>, ws: WebSocket) -> Channel<'static> {
+        self.handle_as(ws, None)
+    }
+
+    /// Like [`handle`](Self::handle), listing the socket in presence under
+    /// the authenticated `user`'s id
+    pub fn handle_as(self: Arc<Self>, ws: WebSocket, user: Option<AuthUser>) -> Channel<'static> {
+        let user_id = user.map(|user| user.id);
+        ws.channel(move |mut stream| Box::pin(async move {
+            let (tx, mut info_rx) = mpsc::unbounded_channel();
+            let mut conn = self.hub.connect(Uuid::new_v4().to_string(), move |info| {
+                let _ = tx.send(info);
+            });
+
+            let mut heartbeat_check = tokio::time::interval(self.hub.heartbeat_interval());
+            let result = loop {
+                let reply = tokio::select! {
+                    incoming = stream.next() => match incoming {
+                        Some(Ok(Message::Text(text))) => conn.handle_text(&text),
+                        Some(Ok(Message::Close(_))) | None => break Ok(()),
+                        Some(Ok(_)) => continue,
+                        Some(Err(err)) => break Err(err),
+                    },
+                    Some(info) = info_rx.recv() => conn.handle_info(info),
+                    _ = heartbeat_check.tick() => {
+                        if conn.is_alive() {
+                            continue;
+                        }
+                        break Ok(());
+                    }
+                };
+                let frame = match reply {
+                    core::Reply::None => continue,
+                    core::Reply::Send(frame) => frame,
+                    core::Reply::Joined { topic, payload, frame } => {
+                        self.track_presence(&topic, user_id.as_deref(), conn.socket_id(), &payload.url);
+                        Metrics::global().liveview_connected();
+                        frame
+                    }
+                    core::Reply::Close(frame) => {
+                        if let Some(frame) = frame {
+                            let _ = stream.send(Message::Text(frame)).await;
+                        }
+                        break Ok(());
+                    }
+                };
+                if stream.send(Message::Text(frame)).await.is_err() {
+                    break Ok(());
+                }
+            };
+
+            // Cleanup; dropping `conn` terminates the view
+            if let Some(topic) = conn.topic() {
+                self.presence.untrack(topic, conn.socket_id());
+                self.publish_presence(topic);
+                Metrics::global().liveview_disconnected();
+            }
+
+            result
+        }))
+    }
+
+    /// List a joined socket in `topic`'s presence
+    ///
+    /// Keyed on what the server knows about the socket, never on the join
+    /// payload, so clients can't pose as other users.
+    fn track_presence(&self, topic: &str, user_id: Option<&str>, socket_id: &str, url: &str) {
+        let id = user_id.unwrap_or(socket_id);
+        self.presence.track(topic, id, socket_id, serde_json::json!({ "url": url }));
+        self.publish_presence(topic);
+    }
+
+    fn publish_presence(&self, topic: &str) {
+        let list = serde_json::to_string(&self.presence.list(topic)).unwrap_or_default();
+        let _ = self.broadcast.broadcast(&format!("presence:{}", topic), list);
+    }
+}
+
+impl Default for LiveViewHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -396,6 +585,42 @@ mod tests {
         assert!(json.contains("#app"));
     }
 
+    struct Counter(i32);
+
+    impl LiveView for Counter {
+        fn mount(&mut self, _socket: &mut core::LiveSocket) {}
+        fn handle_event(&mut self, _event: &core::LiveEvent, _socket: &mut core::LiveSocket) {}
+        fn handle_info(&mut self, _info: serde_json::Value, _socket: &mut core::LiveSocket) {
+            self.0 += 1;
+        }
+        fn render(&self) -> String {
+            format!("<p>{}</p>", self.0)
+        }
+    }
+
+    #[test]
+    fn test_hub_publish_reaches_subscribers() {
+        let hub = LiveViewHub::new().route("/counter", || Box::new(Counter(0)));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut conn = hub.hub.connect("s1", move |info| {
+            let _ = tx.send(info);
+        });
+        hub.subscribe("room", "s1");
+
+        assert_eq!(hub.publish("room", serde_json::json!({ "n": 1 })), 1);
+        let info = rx.try_recv().unwrap();
+        assert_eq!(info["n"], 1);
+        assert_eq!(hub.publish("other", serde_json::json!({})), 0);
+
+        // Views get the published payload unchanged
+        let join = r#"{"type":"Join","topic":"/counter","payload":{"url":"/counter","params":{},"session":""}}"#;
+        assert!(matches!(conn.handle_text(join), core::Reply::Joined { .. }));
+        match conn.handle_info(info) {
+            core::Reply::Send(frame) => assert!(frame.contains("<p>1</p>")),
+            other => panic!("unexpected reply: {:?}", other),
+        }
+    }
+
     #[test]
     fn test_presence_tracker() {
         let tracker = PresenceTracker::new();
@@ -408,4 +633,24 @@ mod tests {
         tracker.untrack("room:1", "conn-1");
         assert_eq!(tracker.count("room:1"), 1);
     }
+
+    #[test]
+    fn test_presence_ignores_join_payload() {
+        let hub = LiveViewHub::new().route("/counter", || Box::new(Counter(0)));
+        let mut conn = hub.hub.connect("s1", |_| {});
+        let join = r#"{"type":"Join","topic":"/counter","payload":{"url":"/counter","params":{},"session":"admin"}}"#;
+        let core::Reply::Joined { topic, payload, .. } = conn.handle_text(join) else {
+            panic!("join failed");
+        };
+
+        hub.track_presence(&topic, None, conn.socket_id(), &payload.url);
+        hub.track_presence(&topic, Some("user-7"), "s2", "/counter");
+
+        let mut ids: Vec<_> = hub.presence().list("/counter").into_iter().map(|p| (p.id, p.conn_id)).collect();
+        ids.sort();
+        assert_eq!(
+            ids,
+            [("s1".to_string(), "s1".to_string()), ("user-7".to_string(), "s2".to_string())]
+        );
+    }
 }
@@ -1,12 +1,13 @@
 //! PhilJS Procedural Macros
 //!
-//! Provides the `view!`, `component`, and `signal` macros for
+//! Provides the `view!`, `live!`, `component`, and `signal` macros for
 //! writing reactive UI components in pure Rust.
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, format_ident};
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::Punctuated,
@@ -197,13 +198,17 @@ impl ElementNode {
         for attr in &self.attrs {
             match attr {
                 ElementAttr::Static { name, value } => {
-                    let name_str = name.to_string().replace('_', "-");
+                    let name_str = name.html_name();
                     static_attrs.push(quote! { (#name_str, #value) });
                 }
                 ElementAttr::Dynamic { name, expr } => {
-                    let name_str = name.to_string().replace('_', "-");
+                    let name_str = name.html_name();
                     dynamic_attrs.push(quote! { (#name_str, move || #expr) });
                 }
+                ElementAttr::Live { event, value } => {
+                    let name_str = format!("live:{}", event.name);
+                    static_attrs.push(quote! { (#name_str, #value) });
+                }
                 ElementAttr::Event { name, handler } => {
                     let event_name = name.to_string();
                     event_handlers.push(quote! { (#event_name, ::std::boxed::Box::new(#handler)) });
@@ -267,15 +272,39 @@ impl ElementNode {
 }
 
 enum ElementAttr {
-    Static { name: Ident, value: LitStr },
-    Dynamic { name: Ident, expr: Expr },
+    Static { name: AttrName, value: LitStr },
+    Dynamic { name: AttrName, expr: Expr },
     Event { name: Ident, handler: Expr },
+    Live { event: AttrName, value: LitStr },
     Class(Expr),
     Style(Expr),
     Ref(Expr),
     Spread(Expr),
 }
 
+/// Attribute name, possibly hyphenated (`phx-change`, `data-id`)
+struct AttrName {
+    name: String,
+}
+
+impl AttrName {
+    fn parse_rest(ident: Ident, input: ParseStream) -> syn::Result<Self> {
+        let mut name = ident.to_string();
+        while input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            let part: Ident = input.call(Ident::parse_any)?;
+            name.push('-');
+            name.push_str(&part.to_string());
+        }
+        Ok(AttrName { name })
+    }
+
+    /// HTML attribute name; `snake_case` segments become `kebab-case`
+    fn html_name(&self) -> String {
+        self.name.replace('_', "-")
+    }
+}
+
 impl Parse for ElementAttr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         // Check for spread: {..props}
@@ -289,8 +318,8 @@ impl Parse for ElementAttr {
             }
         }
 
-        let name: Ident = input.parse()?;
-        let name_str = name.to_string();
+        let ident: Ident = input.call(Ident::parse_any)?;
+        let name_str = ident.to_string();
 
         // Check for event handler: on:click, on:input, etc.
         if name_str == "on" {
@@ -301,6 +330,16 @@ impl Parse for ElementAttr {
             return Ok(ElementAttr::Event { name: event_name, handler });
         }
 
+        // Check for LiveView binding: live:click="increment"
+        if name_str == "live" && input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            let event_ident: Ident = input.call(Ident::parse_any)?;
+            let event = AttrName::parse_rest(event_ident, input)?;
+            input.parse::<Token![=]>()?;
+            let value: LitStr = input.parse()?;
+            return Ok(ElementAttr::Live { event, value });
+        }
+
         // Check for special attributes
         if name_str == "class" || name_str == "style" || name_str == "node_ref" {
             input.parse::<Token![=]>()?;
@@ -313,6 +352,7 @@ impl Parse for ElementAttr {
             };
         }
 
+        let name = AttrName::parse_rest(ident, input)?;
         input.parse::<Token![=]>()?;
 
         // Dynamic or static value
@@ -434,6 +474,202 @@ impl Parse for ComponentProp {
     }
 }
 
+// ============================================================================
+// LIVE MACRO - Server-rendered LiveView templates
+// ============================================================================
+
+/// Void elements that never have children or a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param",
+    "source", "track", "wbr",
+];
+
+/// The `live!` macro renders the `view!` syntax to an HTML `String` for LiveViews.
+///
+/// Every element is stamped with a `data-live-id` that only depends on its
+/// position in the template, so ids stay stable across renders. The client
+/// reports the id as the `target` of events raised on an element, and a
+/// `DomPatch` may name it as its target; `diff_html` does not key on the
+/// ids and morphs the whole view. `{expr}` blocks and dynamic attribute
+/// values are interpolated through `Display` and HTML-escaped, which means
+/// signals can be dropped in directly.
+///
+/// # Example
+/// ```rust
+/// use philjs::liveview::*;
+/// use philjs::Signal;
+///
+/// fn render(count: &Signal<i32>, query: &str) -> String {
+///     live! {
+///         <div>
+///             <h1>"Count: " {count}</h1>
+///             <button live:click="increment">"+"</button>
+///             <form phx-change="validate">
+///                 <input name="query" value={query} />
+///             </form>
+///         </div>
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn live(input: TokenStream) -> TokenStream {
+    let view_input = parse_macro_input!(input as ViewMacroInput);
+    let mut builder = LiveBuilder::default();
+    for node in &view_input.nodes {
+        if let Err(err) = builder.node(node) {
+            return err.to_compile_error().into();
+        }
+    }
+    builder.finish().into()
+}
+
+#[derive(Default)]
+struct LiveBuilder {
+    stmts: Vec<TokenStream2>,
+    pending: String,
+    next_id: usize,
+}
+
+impl LiveBuilder {
+    fn push_static(&mut self, s: &str) {
+        self.pending.push_str(s);
+    }
+
+    fn push_dynamic(&mut self, expr: &Expr) {
+        self.flush();
+        self.stmts.push(quote! {
+            __html.push_str(&::philjs::liveview::escape_html(
+                &::std::string::ToString::to_string(&(#expr))
+            ));
+        });
+    }
+
+    fn flush(&mut self) {
+        if !self.pending.is_empty() {
+            let lit = std::mem::take(&mut self.pending);
+            self.stmts.push(quote! { __html.push_str(#lit); });
+        }
+    }
+
+    fn node(&mut self, node: &ViewNode) -> syn::Result<()> {
+        match node {
+            ViewNode::Element(el) => self.element(el),
+            ViewNode::Text(lit) => {
+                self.push_static(&escape_html_static(&lit.value()));
+                Ok(())
+            }
+            ViewNode::Block(expr) => {
+                self.push_dynamic(expr);
+                Ok(())
+            }
+            ViewNode::Component(comp) => Err(syn::Error::new(
+                comp.name.span(),
+                "components are not supported in live!; interpolate `{component.render()}` instead",
+            )),
+            ViewNode::Fragment(nodes) => {
+                for node in nodes {
+                    self.node(node)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn element(&mut self, el: &ElementNode) -> syn::Result<()> {
+        let tag = el.tag.to_string();
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.push_static(&format!("<{} data-live-id=\"{}\"", tag, id));
+        for attr in &el.attrs {
+            match attr {
+                ElementAttr::Static { name, value } => {
+                    self.push_static(&format!(
+                        " {}=\"{}\"",
+                        name.html_name(),
+                        escape_html_static(&value.value())
+                    ));
+                }
+                ElementAttr::Live { event, value } => {
+                    self.push_static(&format!(
+                        " live:{}=\"{}\"",
+                        event.name,
+                        escape_html_static(&value.value())
+                    ));
+                }
+                ElementAttr::Dynamic { name, expr } => {
+                    self.push_static(&format!(" {}=\"", name.html_name()));
+                    self.push_dynamic(expr);
+                    self.push_static("\"");
+                }
+                ElementAttr::Class(expr) => {
+                    self.push_static(" class=\"");
+                    self.push_dynamic(expr);
+                    self.push_static("\"");
+                }
+                ElementAttr::Style(expr) => {
+                    self.push_static(" style=\"");
+                    self.push_dynamic(expr);
+                    self.push_static("\"");
+                }
+                ElementAttr::Event { name, .. } => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!(
+                            "closures cannot run in a LiveView; use live:{}=\"event\" instead",
+                            name
+                        ),
+                    ));
+                }
+                ElementAttr::Ref(_) | ElementAttr::Spread(_) => {
+                    return Err(syn::Error::new(
+                        el.tag.span(),
+                        "node_ref and spread attributes are not supported in live!",
+                    ));
+                }
+            }
+        }
+
+        if VOID_ELEMENTS.contains(&tag.as_str()) {
+            if !el.children.is_empty() {
+                return Err(syn::Error::new(
+                    el.tag.span(),
+                    format!("<{}> is a void element and cannot have children", tag),
+                ));
+            }
+            self.push_static(" />");
+            return Ok(());
+        }
+
+        self.push_static(">");
+        for child in &el.children {
+            self.node(child)?;
+        }
+        self.push_static(&format!("</{}>", tag));
+        Ok(())
+    }
+
+    fn finish(mut self) -> TokenStream2 {
+        self.flush();
+        let stmts = self.stmts;
+        quote! {
+            {
+                let mut __html = ::std::string::String::new();
+                #(#stmts)*
+                __html
+            }
+        }
+    }
+}
+
+fn escape_html_static(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#039;")
+}
+
 // ============================================================================
 // COMPONENT MACRO - Define reactive components
 // ============================================================================
@@ -0,0 +1,169 @@
+//! Screen reader announcements through ARIA live regions
+//!
+//! ```rust
+//! let announcer = use_announce();
+//! announcer.polite("3 results");
+//! announcer.assertive("Connection lost");
+//! ```
+
+use crate::reactive::{provide_context, use_context, Signal};
+use crate::view::{Element, Fragment, View};
+
+/// Inline style that hides an element visually but keeps it readable by
+/// screen readers
+pub const VISUALLY_HIDDEN: &str = "position:absolute;width:1px;height:1px;padding:0;margin:-1px;\
+    overflow:hidden;clip:rect(0,0,0,0);white-space:nowrap;border:0";
+
+/// How urgently screen readers read an announcement
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Politeness {
+    /// After the user is idle, e.g. search result counts
+    #[default]
+    Polite,
+    /// Immediately, interrupting the user, e.g. errors
+    Assertive,
+}
+
+impl Politeness {
+    /// The `aria-live` value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Politeness::Polite => "polite",
+            Politeness::Assertive => "assertive",
+        }
+    }
+
+    fn region_id(&self) -> &'static str {
+        match self {
+            Politeness::Polite => "philjs-live-polite",
+            Politeness::Assertive => "philjs-live-assertive",
+        }
+    }
+
+    fn region_attrs(&self) -> [(&'static str, &'static str); 5] {
+        [
+            ("id", self.region_id()),
+            ("aria-live", self.as_str()),
+            ("role", if *self == Politeness::Assertive { "alert" } else { "status" }),
+            ("aria-atomic", "true"),
+            ("style", VISUALLY_HIDDEN),
+        ]
+    }
+}
+
+/// Sends messages to the page's live regions; from [`use_announce`].
+#[derive(Clone)]
+pub struct Announcer {
+    last: Signal<Option<(Politeness, String)>>,
+}
+
+impl Announcer {
+    /// Read `message` to screen reader users.
+    ///
+    /// Repeating a message announces it again.
+    pub fn announce(&self, message: impl Into<String>, politeness: Politeness) {
+        let message = message.into();
+        #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+        web::write(politeness, message.clone());
+        self.last.set(Some((politeness, message)));
+    }
+
+    /// Announce when the user is idle.
+    pub fn polite(&self, message: impl Into<String>) {
+        self.announce(message, Politeness::Polite);
+    }
+
+    /// Announce immediately.
+    pub fn assertive(&self, message: impl Into<String>) {
+        self.announce(message, Politeness::Assertive);
+    }
+
+    /// The most recent announcement.
+    pub fn last(&self) -> Option<(Politeness, String)> {
+        self.last.get()
+    }
+}
+
+/// The page's announcer, shared through context.
+///
+/// In the browser the live regions are created on first use unless
+/// [`live_regions`] already rendered them.
+pub fn use_announce() -> Announcer {
+    use_context::<Announcer>().unwrap_or_else(|| {
+        let announcer = Announcer {
+            last: Signal::new(None),
+        };
+        provide_context(announcer.clone());
+        announcer
+    })
+}
+
+/// Empty polite and assertive live regions, for the app shell.
+///
+/// Some screen readers ignore regions added just before they change, so
+/// rendering them up front makes the first announcement reliable.
+pub fn live_regions() -> View {
+    let region = |politeness: Politeness| Element::new("div").attrs(&politeness.region_attrs()).into();
+    View::Fragment(Fragment::new(vec![region(Politeness::Polite), region(Politeness::Assertive)]))
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod web {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    use super::Politeness;
+
+    pub(super) fn write(politeness: Politeness, message: String) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let Some(document) = window.document() else {
+            return;
+        };
+        let region = match document.get_element_by_id(politeness.region_id()) {
+            Some(region) => region,
+            None => {
+                let Ok(region) = document.create_element("div") else {
+                    return;
+                };
+                for (name, value) in politeness.region_attrs() {
+                    let _ = region.set_attribute(name, value);
+                }
+                if let Some(body) = document.body() {
+                    let _ = body.append_child(&region);
+                }
+                region
+            }
+        };
+
+        // Clear first so the same message is read again
+        region.set_text_content(Some(""));
+        let callback = Closure::once_into_js(move || region.set_text_content(Some(&message)));
+        let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(callback.unchecked_ref(), 50);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_announce() {
+        let announcer = use_announce();
+        assert_eq!(announcer.last(), None);
+
+        use_announce().assertive("Saved");
+        assert_eq!(announcer.last(), Some((Politeness::Assertive, "Saved".to_string())));
+        announcer.polite("3 results");
+        assert_eq!(announcer.last(), Some((Politeness::Polite, "3 results".to_string())));
+    }
+
+    #[test]
+    fn test_live_regions() {
+        let html = live_regions().to_html();
+        assert!(html.contains(r#"id="philjs-live-polite""#));
+        assert!(html.contains(r#"aria-live="assertive""#));
+        assert!(html.contains(r#"role="alert""#));
+        assert!(html.contains("clip:rect(0,0,0,0)"));
+    }
+}
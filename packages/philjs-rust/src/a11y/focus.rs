@@ -0,0 +1,426 @@
+//! Focus management: [`FocusTrap`] and [`RovingTabIndex`]
+
+use crate::dom::NodeRef;
+use crate::reactive::Signal;
+use crate::view::{Element, View};
+
+/// Selector for elements reachable with Tab
+pub const TABBABLE: &str = "a[href], area[href], button:not([disabled]), \
+    input:not([disabled]):not([type=hidden]), select:not([disabled]), textarea:not([disabled]), \
+    iframe, [contenteditable=true], [tabindex]:not([tabindex='-1'])";
+
+// =============================================================================
+// Focus Trap
+// =============================================================================
+
+/// Props for [`FocusTrap`]
+#[derive(Default)]
+pub struct FocusTrapProps {
+    /// Content to keep focus in
+    pub children: Option<Box<dyn Fn() -> View>>,
+    /// Whether the trap holds focus; always when `None`
+    pub active: Option<Signal<bool>>,
+    /// Selector of the element to focus on activation; the first tabbable
+    /// element when empty
+    pub initial_focus: String,
+    /// Classes for the wrapper
+    pub class: String,
+}
+
+/// Keeps keyboard focus inside its content while active, as modal dialogs
+/// and drawers need.
+///
+/// On activation it focuses `initial_focus` or the first tabbable element,
+/// Tab and Shift+Tab wrap around at either end, and on deactivation focus
+/// returns to the element that had it before. Renders a `div` with a
+/// `data-philjs-focus-trap` attribute.
+///
+/// # Example
+/// ```rust
+/// view! {
+///     <FocusTrap active=Some(open.clone()) initial_focus="#name">
+///         <form role="dialog" aria-modal="true">...</form>
+///     </FocusTrap>
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn FocusTrap(props: FocusTrapProps) -> View {
+    let container = NodeRef::new();
+    let active = props.active.unwrap_or_else(|| Signal::new(true));
+    let children = props.children.as_ref().map(|c| c()).unwrap_or(View::Empty);
+
+    let mut wrapper = Element::new("div")
+        .attr("data-philjs-focus-trap", "")
+        .node_ref(container.clone())
+        .child(children);
+    if !props.class.is_empty() {
+        wrapper = wrapper.attr("class", props.class);
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        let (keys, enabled) = (container.clone(), active.clone());
+        wrapper = wrapper.on("keydown", move |event| {
+            if enabled.get_untracked() {
+                web::trap_tab(&keys, &event);
+            }
+        });
+        web::watch_trap(container, active, props.initial_focus);
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    let _ = (container, active, props.initial_focus);
+
+    wrapper.into()
+}
+
+/// Where Tab should send focus in a trap of `count` tabbable elements when
+/// the one at `current` has it; `None` lets the browser move focus as usual.
+pub fn trap_target(count: usize, current: Option<usize>, backwards: bool) -> Option<usize> {
+    let last = count.checked_sub(1)?;
+    match (current, backwards) {
+        (None, false) => Some(0),
+        (None, true) | (Some(0), true) => Some(last),
+        (Some(index), false) if index >= last => Some(0),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// Roving Tab Index
+// =============================================================================
+
+/// Arrow keys that move focus in a [`RovingTabIndex`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Orientation {
+    /// Left and right
+    #[default]
+    Horizontal,
+    /// Up and down
+    Vertical,
+    /// All four, for grids of one row or column read either way
+    Both,
+}
+
+impl Orientation {
+    /// The `aria-orientation` value, if there is one.
+    pub fn aria(&self) -> Option<&'static str> {
+        match self {
+            Orientation::Horizontal => Some("horizontal"),
+            Orientation::Vertical => Some("vertical"),
+            Orientation::Both => None,
+        }
+    }
+}
+
+/// Props for [`RovingTabIndex`]
+#[derive(Default)]
+pub struct RovingTabIndexProps {
+    /// The items; each top-level element is one item
+    pub children: Option<Box<dyn Fn() -> View>>,
+    /// Index of the item in the tab order, updated as focus moves
+    pub active: Signal<usize>,
+    /// Which arrow keys move between items
+    pub orientation: Orientation,
+    /// Whether moving past the last item returns to the first, and back
+    pub wrap: bool,
+    /// The container role, e.g. `toolbar`, `tablist`, `radiogroup` or `listbox`
+    pub role: String,
+    /// Accessible name for the container
+    pub label: String,
+    /// Classes for the container
+    pub class: String,
+}
+
+/// A group with a single tab stop whose items are reached with the arrow
+/// keys, Home and End, as toolbars, tab lists and radio groups expect.
+///
+/// The item at `active` has `tabindex="0"` and the others `tabindex="-1"`;
+/// moving focus, by keyboard or pointer, updates both.
+///
+/// # Example
+/// ```rust
+/// view! {
+///     <RovingTabIndex role="toolbar" label="Formatting">
+///         <button>"Bold"</button>
+///         <button>"Italic"</button>
+///         <button>"Underline"</button>
+///     </RovingTabIndex>
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn RovingTabIndex(props: RovingTabIndexProps) -> View {
+    let container = NodeRef::new();
+    let content = props.children.as_ref().map(|c| c()).unwrap_or(View::Empty);
+    let active = props.active.get_untracked();
+
+    let items = flatten(content)
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| match item {
+            View::Element(el) => View::Element(el.attr("tabindex", if index == active { "0" } else { "-1" })),
+            other => other,
+        })
+        .collect();
+
+    let mut wrapper = Element::new("div")
+        .attr("data-philjs-roving", "")
+        .node_ref(container.clone())
+        .children(items);
+    if !props.role.is_empty() {
+        wrapper = wrapper.attr("role", props.role);
+        if let Some(orientation) = props.orientation.aria() {
+            wrapper = wrapper.attr("aria-orientation", orientation);
+        }
+    }
+    if !props.label.is_empty() {
+        wrapper = wrapper.attr("aria-label", props.label);
+    }
+    if !props.class.is_empty() {
+        wrapper = wrapper.attr("class", props.class);
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        let (keys, focus) = (container.clone(), container);
+        let (active_keys, active_focus) = (props.active.clone(), props.active);
+        let (orientation, wrap) = (props.orientation, props.wrap);
+        wrapper = wrapper
+            .on("keydown", move |event| web::rove(&keys, &active_keys, orientation, wrap, &event))
+            .on("focusin", move |event| web::follow_focus(&focus, &active_focus, &event));
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    let _ = container;
+
+    wrapper.into()
+}
+
+/// Top-level views with fragments flattened, so each element is an item
+fn flatten(view: View) -> Vec<View> {
+    match view {
+        View::Fragment(fragment) => fragment.children().iter().cloned().flat_map(flatten).collect(),
+        View::Empty => Vec::new(),
+        other => vec![other],
+    }
+}
+
+/// The item `key` moves focus to from `current` among `count` items, for
+/// custom composite widgets. Keys that don't navigate give `None`; at
+/// either end without `wrap`, focus stays put.
+pub fn roving_target(key: &str, orientation: Orientation, wrap: bool, current: usize, count: usize) -> Option<usize> {
+    let last = count.checked_sub(1)?;
+    let current = current.min(last);
+    let (back, forward) = match orientation {
+        Orientation::Horizontal => (key == "ArrowLeft", key == "ArrowRight"),
+        Orientation::Vertical => (key == "ArrowUp", key == "ArrowDown"),
+        Orientation::Both => (
+            key == "ArrowLeft" || key == "ArrowUp",
+            key == "ArrowRight" || key == "ArrowDown",
+        ),
+    };
+    match key {
+        "Home" => Some(0),
+        "End" => Some(last),
+        _ if forward && current == last => Some(if wrap { 0 } else { last }),
+        _ if forward => Some(current + 1),
+        _ if back && current == 0 => Some(if wrap { last } else { 0 }),
+        _ if back => Some(current - 1),
+        _ => None,
+    }
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod web {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{HtmlElement, KeyboardEvent};
+
+    use super::*;
+    use crate::dom::Event;
+    use crate::reactive::effect::watch;
+
+    fn tabbable(container: &web_sys::Element) -> Vec<HtmlElement> {
+        let Ok(nodes) = container.query_selector_all(TABBABLE) else {
+            return Vec::new();
+        };
+        (0..nodes.length())
+            .filter_map(|i| nodes.item(i)?.dyn_into::<HtmlElement>().ok())
+            .collect()
+    }
+
+    fn focused() -> Option<web_sys::Element> {
+        web_sys::window()?.document()?.active_element()
+    }
+
+    pub(super) fn trap_tab(container: &NodeRef, event: &Event) {
+        let Some(key) = event.inner().dyn_ref::<KeyboardEvent>() else {
+            return;
+        };
+        let Some(container) = container.get() else {
+            return;
+        };
+        if key.key() != "Tab" {
+            return;
+        }
+        let items = tabbable(&container);
+        if items.is_empty() {
+            event.prevent_default();
+            return;
+        }
+        let current = focused().and_then(|el| items.iter().position(|item| item.is_same_node(Some(&el))));
+        if let Some(target) = trap_target(items.len(), current, key.shift_key()) {
+            event.prevent_default();
+            let _ = items[target].focus();
+        }
+    }
+
+    pub(super) fn watch_trap(container: NodeRef, active: Signal<bool>, initial_focus: String) {
+        let previous: Rc<RefCell<Option<HtmlElement>>> = Rc::default();
+        let effect = watch(
+            move || active.get(),
+            move |on, _| {
+                let (container, previous, initial_focus) = (container.clone(), previous.clone(), initial_focus.clone());
+                // Wait a frame so a trap that was just rendered is mounted
+                let callback = Closure::once_into_js(move || {
+                    if !on {
+                        if let Some(el) = previous.borrow_mut().take() {
+                            let _ = el.focus();
+                        }
+                        return;
+                    }
+                    let Some(container) = container.get() else {
+                        return;
+                    };
+                    *previous.borrow_mut() = focused().and_then(|el| el.dyn_into().ok());
+                    let initial = (!initial_focus.is_empty())
+                        .then(|| container.query_selector(&initial_focus).ok().flatten())
+                        .flatten()
+                        .and_then(|el| el.dyn_into::<HtmlElement>().ok());
+                    if let Some(el) = initial.or_else(|| tabbable(&container).into_iter().next()) {
+                        let _ = el.focus();
+                    }
+                });
+                if let Some(window) = web_sys::window() {
+                    let _ = window.request_animation_frame(callback.unchecked_ref());
+                }
+            },
+        );
+        // Lives as long as the page, like the DOM it manages
+        std::mem::forget(effect);
+    }
+
+    /// The container's items and the index of the one holding `target`
+    fn locate(container: &NodeRef, event: &Event) -> Option<(Vec<HtmlElement>, Option<usize>)> {
+        let container = container.get()?;
+        let children = container.children();
+        let items: Vec<HtmlElement> = (0..children.length())
+            .filter_map(|i| children.item(i)?.dyn_into().ok())
+            .collect();
+        let target = event.target().and_then(|t| t.dyn_into::<web_sys::Node>().ok());
+        let current = items.iter().position(|item| item.contains(target.as_ref()));
+        Some((items, current))
+    }
+
+    fn set_active(items: &[HtmlElement], active: &Signal<usize>, index: usize) {
+        for (i, item) in items.iter().enumerate() {
+            let _ = item.set_attribute("tabindex", if i == index { "0" } else { "-1" });
+        }
+        if active.get_untracked() != index {
+            active.set(index);
+        }
+    }
+
+    pub(super) fn rove(container: &NodeRef, active: &Signal<usize>, orientation: Orientation, wrap: bool, event: &Event) {
+        let Some(key) = event.inner().dyn_ref::<KeyboardEvent>() else {
+            return;
+        };
+        let Some((items, current)) = locate(container, event) else {
+            return;
+        };
+        let current = current.unwrap_or_else(|| active.get_untracked());
+        if let Some(next) = roving_target(&key.key(), orientation, wrap, current, items.len()) {
+            event.prevent_default();
+            set_active(&items, active, next);
+            let _ = items[next].focus();
+        }
+    }
+
+    pub(super) fn follow_focus(container: &NodeRef, active: &Signal<usize>, event: &Event) {
+        if let Some((items, Some(current))) = locate(container, event) {
+            set_active(&items, active, current);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::Fragment;
+
+    #[test]
+    fn test_trap_target() {
+        assert_eq!(trap_target(3, Some(2), false), Some(0));
+        assert_eq!(trap_target(3, Some(0), true), Some(2));
+        assert_eq!(trap_target(3, Some(1), false), None);
+        assert_eq!(trap_target(3, Some(1), true), None);
+        // Focus outside the trap is pulled back in
+        assert_eq!(trap_target(3, None, false), Some(0));
+        assert_eq!(trap_target(3, None, true), Some(2));
+        assert_eq!(trap_target(0, None, false), None);
+    }
+
+    #[test]
+    fn test_roving_target() {
+        use Orientation::*;
+        assert_eq!(roving_target("ArrowRight", Horizontal, false, 0, 3), Some(1));
+        assert_eq!(roving_target("ArrowRight", Horizontal, false, 2, 3), Some(2));
+        assert_eq!(roving_target("ArrowRight", Horizontal, true, 2, 3), Some(0));
+        assert_eq!(roving_target("ArrowLeft", Horizontal, true, 0, 3), Some(2));
+        assert_eq!(roving_target("ArrowDown", Horizontal, false, 0, 3), None);
+        assert_eq!(roving_target("ArrowDown", Vertical, false, 0, 3), Some(1));
+        assert_eq!(roving_target("ArrowUp", Both, false, 2, 3), Some(1));
+        assert_eq!(roving_target("End", Vertical, false, 0, 3), Some(2));
+        assert_eq!(roving_target("Home", Vertical, false, 2, 3), Some(0));
+        assert_eq!(roving_target("a", Both, false, 0, 3), None);
+        assert_eq!(roving_target("Home", Both, false, 0, 0), None);
+    }
+
+    #[test]
+    fn test_roving_tab_index_renders() {
+        let active = Signal::new(1);
+        let html = RovingTabIndex(RovingTabIndexProps {
+            children: Some(Box::new(|| {
+                View::Fragment(Fragment::new(vec![
+                    Element::new("button").child("Bold").into(),
+                    Element::new("button").child("Italic").into(),
+                ]))
+            })),
+            active,
+            role: "toolbar".to_string(),
+            label: "Formatting".to_string(),
+            ..Default::default()
+        })
+        .to_html();
+        assert!(html.contains(r#"role="toolbar""#));
+        assert!(html.contains(r#"aria-orientation="horizontal""#));
+        assert!(html.contains(r#"aria-label="Formatting""#));
+        assert!(html.contains(r#"<button tabindex="-1">Bold</button>"#));
+        assert!(html.contains(r#"<button tabindex="0">Italic</button>"#));
+    }
+
+    #[test]
+    fn test_focus_trap_renders() {
+        let html = FocusTrap(FocusTrapProps {
+            children: Some(Box::new(|| Element::new("button").child("OK").into())),
+            class: "dialog".to_string(),
+            ..Default::default()
+        })
+        .to_html();
+        assert!(html.contains("data-philjs-focus-trap"));
+        assert!(html.contains(r#"class="dialog""#));
+        assert!(html.contains("<button>OK</button>"));
+    }
+}
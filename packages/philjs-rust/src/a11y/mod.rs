@@ -0,0 +1,148 @@
+//! Accessibility primitives for PhilJS
+//!
+//! Building blocks for accessible widgets:
+//! - [`FocusTrap`] keeps keyboard focus inside dialogs and drawers
+//! - [`RovingTabIndex`] gives toolbars, tab lists and radio groups a single
+//!   tab stop with arrow-key navigation
+//! - [`use_announce`] reads messages to screen readers through a visually
+//!   hidden ARIA live region
+//! - [`use_id`] and [`AriaIds`] generate ids for `aria-labelledby` and
+//!   `aria-describedby`
+//!
+//! # Example
+//!
+//! ```rust
+//! use philjs::a11y::*;
+//! use philjs::prelude::*;
+//!
+//! #[component]
+//! fn ConfirmDialog(open: Signal<bool>) -> impl IntoView {
+//!     let ids = use_aria_ids("dialog");
+//!     let announcer = use_announce();
+//!
+//!     view! {
+//!         <FocusTrap active=Some(open.clone())>
+//!             <div role="dialog" aria-modal="true"
+//!                  aria-labelledby=ids.label() aria-describedby=ids.description()>
+//!                 <h2 id=ids.label()>"Delete project?"</h2>
+//!                 <p id=ids.description()>"This cannot be undone."</p>
+//!                 <button on:click=move |_| {
+//!                     open.set(false);
+//!                     announcer.polite("Project deleted");
+//!                 }>"Delete"</button>
+//!             </div>
+//!         </FocusTrap>
+//!     }
+//! }
+//! ```
+
+use std::cell::Cell;
+
+pub mod announce;
+pub mod focus;
+
+pub use announce::{live_regions, use_announce, Announcer, Politeness, VISUALLY_HIDDEN};
+pub use focus::{
+    FocusTrap, FocusTrapProps, Orientation, RovingTabIndex, RovingTabIndexProps, TABBABLE,
+};
+
+// =============================================================================
+// Ids
+// =============================================================================
+
+thread_local! {
+    static NEXT_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A new id, `"{prefix}-{n}"`, unique within the render.
+///
+/// Ids count up from zero in each server render and hydration, so the
+/// server and the client agree as long as they render the same tree.
+pub fn use_id(prefix: &str) -> String {
+    let n = NEXT_ID.with(|next| next.replace(next.get() + 1));
+    format!("{}-{}", prefix, n)
+}
+
+/// Restart [`use_id`] numbering; called at the start of every render.
+pub fn reset_ids() {
+    NEXT_ID.with(|next| next.set(0));
+}
+
+/// Related ids for an element, its label and its description.
+///
+/// # Example
+/// ```rust
+/// let ids = use_aria_ids("email");
+/// view! {
+///     <label id=ids.label() for=ids.id()>"Email"</label>
+///     <input id=ids.id() aria-labelledby=ids.label() aria-describedby=ids.description() />
+///     <p id=ids.description()>"We never share it."</p>
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AriaIds {
+    id: String,
+}
+
+impl AriaIds {
+    /// Generate a new set of ids.
+    pub fn new(prefix: &str) -> Self {
+        AriaIds { id: use_id(prefix) }
+    }
+
+    /// The element's own id.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The id for its label, for `aria-labelledby`.
+    pub fn label(&self) -> String {
+        self.part("label")
+    }
+
+    /// The id for its description, for `aria-describedby`.
+    pub fn description(&self) -> String {
+        self.part("description")
+    }
+
+    /// The id for another related element, e.g. `part("error")`.
+    pub fn part(&self, name: &str) -> String {
+        format!("{}-{}", self.id, name)
+    }
+}
+
+/// Generate ids for an element, its label and its description.
+pub fn use_aria_ids(prefix: &str) -> AriaIds {
+    AriaIds::new(prefix)
+}
+
+/// Join ids into an `aria-labelledby` or `aria-describedby` value,
+/// skipping empty ones.
+pub fn id_list<S: AsRef<str>>(ids: &[S]) -> String {
+    ids.iter()
+        .map(AsRef::as_ref)
+        .filter(|id| !id.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids() {
+        reset_ids();
+        assert_eq!(use_id("menu"), "menu-0");
+        let ids = use_aria_ids("dialog");
+        assert_eq!(ids.id(), "dialog-1");
+        assert_eq!(ids.label(), "dialog-1-label");
+        assert_eq!(ids.description(), "dialog-1-description");
+        assert_eq!(ids.part("error"), "dialog-1-error");
+
+        reset_ids();
+        assert_eq!(use_id("menu"), "menu-0");
+
+        assert_eq!(id_list(&[ids.label(), String::new(), ids.part("error")]), "dialog-1-label dialog-1-error");
+    }
+}
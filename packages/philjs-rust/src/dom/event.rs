@@ -54,7 +54,34 @@ impl Event {
         self.inner.current_target()
     }
 
+    #[cfg(feature = "wasm")]
+    /// The value of the input, textarea or select that fired the event;
+    /// `"true"` or `"false"` for checkboxes.
+    pub fn target_value(&self) -> Option<String> {
+        use wasm_bindgen::JsCast;
+
+        let target = self.inner.target()?;
+        if let Some(input) = target.dyn_ref::<web_sys::HtmlInputElement>() {
+            return Some(if input.type_() == "checkbox" {
+                input.checked().to_string()
+            } else {
+                input.value()
+            });
+        }
+        if let Some(textarea) = target.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+            return Some(textarea.value());
+        }
+        target
+            .dyn_ref::<web_sys::HtmlSelectElement>()
+            .map(|select| select.value())
+    }
+
     // Non-WASM stubs
+    #[cfg(not(feature = "wasm"))]
+    pub fn target_value(&self) -> Option<String> {
+        None
+    }
+
     #[cfg(not(feature = "wasm"))]
     pub fn prevent_default(&self) {}
 
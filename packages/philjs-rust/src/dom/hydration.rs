@@ -264,6 +264,7 @@ where
     }
 
     // Create the view (this will use hydration context)
+    crate::a11y::reset_ids();
     let view = f().into_view();
 
     // Walk the DOM and attach handlers without re-rendering
@@ -325,6 +326,9 @@ fn hydrate_view_full(view: &View, parent: &Element, mut index: usize) -> usize {
                     if dom_element.tag_name().to_lowercase() == el.tag().to_lowercase() {
                         // Attach event handlers
                         attach_element_handlers(&dom_element, el);
+                        if let Some(node_ref) = el.get_node_ref() {
+                            node_ref.set(dom_element.clone());
+                        }
 
                         // Recursively hydrate children
                         let mut child_index = 0;
@@ -142,7 +142,7 @@ where
 
 /// Render a view to a parent element.
 #[cfg(feature = "wasm")]
-fn render_view(view: &View, parent: &Element) {
+pub(crate) fn render_view(view: &View, parent: &Element) {
     let document = web_sys::window()
         .expect("no window")
         .document()
@@ -161,6 +161,10 @@ fn render_view(view: &View, parent: &Element) {
                     .expect("failed to set attribute");
             }
 
+            if let Some(node_ref) = el.get_node_ref() {
+                node_ref.set(element.clone());
+            }
+
             // Render children
             for child in el.get_children() {
                 render_view(child, &element);
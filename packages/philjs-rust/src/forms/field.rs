@@ -0,0 +1,169 @@
+//! `<Field>` component wiring an input to a [`FormField`]
+
+use serde_json::Value;
+
+use super::FormField;
+use crate::view::{Dynamic, Element, View};
+
+/// Props for [`Field`]
+#[derive(Clone, Default)]
+pub struct FieldProps {
+    /// The field to edit, from [`FormState::field`](super::FormState::field)
+    pub field: FormField,
+    /// Label text; no `<label>` when empty
+    pub label: String,
+    /// The input `type`, or `textarea`; `text` when empty, `checkbox` for
+    /// boolean fields
+    pub input_type: String,
+    /// Placeholder text
+    pub placeholder: String,
+    /// Extra classes for the wrapper
+    pub class: String,
+    /// The input `id`; `field-<name>` when empty
+    pub id: String,
+}
+
+/// A labelled input bound to a form field, followed by its error.
+///
+/// The input shows the field's value and updates it on `input`, marks it
+/// touched on `blur`, and gets `aria-invalid` and `aria-describedby` for
+/// the error. The wrapper has `data-invalid`, `data-dirty` and
+/// `data-touched` attributes for styling.
+///
+/// # Example
+/// ```rust
+/// view! {
+///     <Field field=form.field("email") label="Email" input_type="email" />
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Field(props: FieldProps) -> View {
+    let field = props.field;
+    let name = field.name().to_string();
+    let id = if props.id.is_empty() {
+        format!("field-{}", name)
+    } else {
+        props.id
+    };
+    let error_id = format!("{}-error", id);
+    let input_type = match props.input_type.as_str() {
+        "" if matches!(field.inner.initial.get_untracked(), Value::Bool(_)) => "checkbox".to_string(),
+        "" => "text".to_string(),
+        other => other.to_string(),
+    };
+    let checkbox = input_type == "checkbox";
+
+    let mut input = if input_type == "textarea" {
+        let text = field.clone();
+        Element::new("textarea").child(Dynamic::new(move || text.text()))
+    } else {
+        let mut input = Element::new("input").attr("type", &input_type);
+        if checkbox {
+            if field.inner.value.get_untracked() == Value::Bool(true) {
+                input = input.attr("checked", "");
+            }
+        } else {
+            let value = field.clone();
+            input = input.dynamic_attrs(vec![("value", Box::new(move || value.text()))]);
+        }
+        input
+    };
+    input = input
+        .attr("id", &id)
+        .attr("name", &name)
+        .attr("aria-describedby", &error_id);
+    if !props.placeholder.is_empty() {
+        input = input.attr("placeholder", props.placeholder);
+    }
+
+    let invalid = field.clone();
+    let on_input = field.clone();
+    let on_blur = field.clone();
+    input = input
+        .dynamic_attrs(vec![(
+            "aria-invalid",
+            Box::new(move || (!invalid.is_valid()).to_string()),
+        )])
+        .on(if checkbox { "change" } else { "input" }, move |event| {
+            if let Some(value) = event.target_value() {
+                on_input.set_input(&value);
+            }
+        })
+        .on("blur", move |_| on_blur.blur());
+
+    let mut children = Vec::new();
+    if !props.label.is_empty() {
+        children.push(Element::new("label").attr("for", &id).child(props.label).into());
+    }
+    children.push(input.into());
+    let error = field.error();
+    children.push(
+        Element::new("p")
+            .attr("id", error_id)
+            .attr("class", "philjs-field-error")
+            .attr("role", "alert")
+            .child(Dynamic::new(move || error.get().unwrap_or_default()))
+            .into(),
+    );
+
+    let class = match props.class.as_str() {
+        "" => "philjs-field".to_string(),
+        extra => format!("philjs-field {}", extra),
+    };
+    let (invalid, dirty, touched) = (field.clone(), field.clone(), field);
+    Element::new("div")
+        .attr("class", class)
+        .attr("data-field", name)
+        .dynamic_attrs(vec![
+            ("data-invalid", Box::new(move || (!invalid.is_valid()).to_string())),
+            ("data-dirty", Box::new(move || dirty.is_dirty().to_string())),
+            ("data-touched", Box::new(move || touched.touched().get().to_string())),
+        ])
+        .children(children)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forms::{use_form, validators::required};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Default, Serialize, Deserialize)]
+    struct Profile {
+        name: String,
+        bio: String,
+        public: bool,
+    }
+
+    #[test]
+    fn test_field_renders() {
+        let form = use_form::<Profile>().validate("name", required("Enter a name"));
+        form.field("name").set_input("Ada & co");
+
+        let field = |name: &str, input_type: &str| {
+            Field(FieldProps {
+                field: form.field(name),
+                label: "Label".to_string(),
+                input_type: input_type.to_string(),
+                ..Default::default()
+            })
+        };
+
+        let html = field("name", "").to_html();
+        assert!(html.contains(r#"<label for="field-name">Label</label>"#));
+        assert!(html.contains(r#"type="text""#));
+        assert!(html.contains(r#"value="Ada &amp; co""#));
+        assert!(html.contains(r#"aria-invalid="false""#));
+        assert!(html.contains(r#"data-dirty="true""#));
+
+        form.field("name").set_input("");
+        form.field("name").blur();
+        let html = field("name", "").to_html();
+        assert!(html.contains(r#"aria-invalid="true""#));
+        assert!(html.contains(">Enter a name</p>"));
+
+        assert!(field("bio", "textarea").to_html().contains("<textarea"));
+        assert!(field("public", "").to_html().contains(r#"type="checkbox""#));
+    }
+}
@@ -0,0 +1,805 @@
+//! Form state management
+//!
+//! [`use_form`] turns the fields of a serde struct into signals with
+//! validation, error, dirty and touched state, and collects them back into
+//! the struct on submit.
+//!
+//! # Example
+//!
+//! ```rust
+//! use philjs::prelude::*;
+//! use philjs::forms::validators::{email, min_length, required};
+//!
+//! #[derive(Clone, Default, Serialize, Deserialize)]
+//! struct Signup {
+//!     email: String,
+//!     password: String,
+//!     age: u32,
+//! }
+//!
+//! #[action]
+//! async fn signup(input: Signup) -> Result<User, ActionError> { ... }
+//!
+//! #[component]
+//! fn SignupForm() -> impl IntoView {
+//!     let form = use_form::<Signup>()
+//!         .validate("email", required("Enter your email"))
+//!         .validate("email", email("Enter a valid email"))
+//!         .validate_async("email", |email: String| async move {
+//!             match email_taken(email).await {
+//!                 Ok(false) => Ok(()),
+//!                 _ => Err("That email is already registered".to_string()),
+//!             }
+//!         })
+//!         .validate("password", min_length(8, "Use at least 8 characters"))
+//!         .validate("age", |age: &u32| if *age >= 13 { Ok(()) } else { Err("Too young".into()) });
+//!     let action = create_server_action(signup);
+//!
+//!     view! {
+//!         <form on:submit=form.on_submit_action(action.clone())>
+//!             <Field field=form.field("email") label="Email" input_type="email" />
+//!             <Field field=form.field("password") label="Password" input_type="password" />
+//!             <Field field=form.field("age") label="Age" input_type="number" />
+//!             <button type="submit" disabled=move || !form.is_valid() || action.pending().get()>
+//!                 "Sign up"
+//!             </button>
+//!         </form>
+//!     }
+//! }
+//! ```
+//!
+//! Errors show once a field is touched (blurred) or the form is submitted,
+//! and then follow every change.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::dom::Event;
+use crate::reactive::{Action, Signal};
+
+mod field;
+pub mod validators;
+
+pub use field::{Field, FieldProps};
+
+/// Error shown for a number field holding something else
+const NOT_A_NUMBER: &str = "Enter a number";
+
+type Validator = Rc<dyn Fn(&Value) -> Result<(), String>>;
+type AsyncValidator = Rc<dyn Fn(&Value) -> Option<Pin<Box<dyn Future<Output = Result<(), String>>>>>>;
+type Check<T> = Rc<dyn Fn(&T) -> Result<(), FieldError>>;
+
+/// A validation error for one field, returned by [`FormState::check`]
+/// rules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldError {
+    /// The field the error is shown on; empty for the whole form
+    pub field: String,
+    /// What is wrong
+    pub message: String,
+}
+
+impl FieldError {
+    /// An error on `field`.
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        FieldError {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+// =============================================================================
+// Fields
+// =============================================================================
+
+struct FieldInner {
+    name: String,
+    value: Signal<Value>,
+    initial: Signal<Value>,
+    error: Signal<Option<String>>,
+    touched: Signal<bool>,
+    validating: Signal<bool>,
+    validators: RefCell<Vec<Validator>>,
+    async_validators: RefCell<Vec<AsyncValidator>>,
+}
+
+/// One field of a [`FormState`].
+///
+/// Cloning is cheap and clones share state.
+#[derive(Clone)]
+pub struct FormField {
+    inner: Rc<FieldInner>,
+}
+
+impl Default for FormField {
+    /// A field not attached to any form.
+    fn default() -> Self {
+        Self::new(String::new(), Value::Null)
+    }
+}
+
+impl FormField {
+    fn new(name: String, initial: Value) -> Self {
+        FormField {
+            inner: Rc::new(FieldInner {
+                name,
+                value: Signal::new(initial.clone()),
+                initial: Signal::new(initial),
+                error: Signal::new(None),
+                touched: Signal::new(false),
+                validating: Signal::new(false),
+                validators: RefCell::new(Vec::new()),
+                async_validators: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// The field name, as in the struct.
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// The raw value (tracked).
+    pub fn value(&self) -> Value {
+        self.inner.value.get()
+    }
+
+    /// The value as `V`, or `None` if it doesn't currently fit (tracked).
+    pub fn get<V: DeserializeOwned>(&self) -> Option<V> {
+        self.inner.value.with(|value| V::deserialize(value).ok())
+    }
+
+    /// The value as an input shows it (tracked).
+    pub fn text(&self) -> String {
+        self.inner.value.with(|value| match value {
+            Value::Null => String::new(),
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    /// Set the value; re-validates once the field has been touched.
+    pub fn set(&self, value: impl Serialize) {
+        self.inner.value.set(serde_json::to_value(value).unwrap_or(Value::Null));
+        if self.inner.touched.get_untracked() {
+            self.validate();
+        }
+    }
+
+    /// Set the value from an input's text, converting it to the field's
+    /// type: numbers for number fields, `true`/`on` for booleans.
+    ///
+    /// Text that isn't a number is kept so it can be corrected, and fails
+    /// validation.
+    pub fn set_input(&self, input: &str) {
+        let value = match self.inner.initial.get_untracked() {
+            Value::Bool(_) => Value::Bool(matches!(input, "true" | "on" | "1")),
+            Value::Number(_) if input.trim().is_empty() => Value::Null,
+            Value::Number(_) => parse_number(input.trim()).unwrap_or_else(|| Value::String(input.to_string())),
+            Value::Null if input.is_empty() => Value::Null,
+            _ => Value::String(input.to_string()),
+        };
+        self.set(value);
+    }
+
+    /// The error to show, if any.
+    pub fn error(&self) -> Signal<Option<String>> {
+        self.inner.error.clone()
+    }
+
+    /// Whether the field currently has no error (tracked).
+    pub fn is_valid(&self) -> bool {
+        self.inner.error.with(Option::is_none)
+    }
+
+    /// Whether the field has lost focus or the form was submitted.
+    pub fn touched(&self) -> Signal<bool> {
+        self.inner.touched.clone()
+    }
+
+    /// Whether an async validator is running.
+    pub fn validating(&self) -> Signal<bool> {
+        self.inner.validating.clone()
+    }
+
+    /// Whether the value differs from the initial one (tracked).
+    pub fn is_dirty(&self) -> bool {
+        self.inner.value.get() != self.inner.initial.get()
+    }
+
+    /// Mark as touched and validate; call from `on:blur`.
+    pub fn blur(&self) {
+        self.inner.touched.set(true);
+        self.validate();
+    }
+
+    /// Run the sync validators, updating [`error`](Self::error).
+    pub fn validate(&self) -> bool {
+        let value = self.inner.value.get_untracked();
+        let error = self.sync_error(&value);
+        let valid = error.is_none();
+        self.inner.error.set(error);
+        valid
+    }
+
+    /// Run the sync validators, then the async ones if those pass.
+    ///
+    /// Results for a value that changed while validating are dropped.
+    pub async fn validate_async(&self) -> bool {
+        if !self.validate() {
+            return false;
+        }
+        let value = self.inner.value.get_untracked();
+        let validators = self.inner.async_validators.borrow().clone();
+        for validator in validators {
+            let Some(pending) = validator(&value) else {
+                continue;
+            };
+            self.inner.validating.set(true);
+            let result = pending.await;
+            self.inner.validating.set(false);
+            if self.inner.value.get_untracked() != value {
+                return false;
+            }
+            if let Err(message) = result {
+                self.inner.error.set(Some(message));
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Restore the initial value and clear the error and touched state.
+    pub fn reset(&self) {
+        self.inner.value.set(self.inner.initial.get_untracked());
+        self.inner.error.set(None);
+        self.inner.touched.set(false);
+    }
+
+    fn sync_error(&self, value: &Value) -> Option<String> {
+        for validator in self.inner.validators.borrow().iter() {
+            if let Err(message) = validator(value) {
+                return Some(message);
+            }
+        }
+        let expects_number = self.inner.initial.with(Value::is_number);
+        (expects_number && !value.is_number()).then(|| NOT_A_NUMBER.to_string())
+    }
+
+    /// Make the current value the initial one, so the field is clean
+    fn commit(&self) {
+        self.inner.initial.set(self.inner.value.get_untracked());
+    }
+}
+
+impl fmt::Debug for FormField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormField")
+            .field("name", &self.inner.name)
+            .field("value", &self.inner.value.get_untracked())
+            .field("error", &self.inner.error.get_untracked())
+            .finish()
+    }
+}
+
+fn parse_number(text: &str) -> Option<Value> {
+    if let Ok(n) = text.parse::<i64>() {
+        return Some(n.into());
+    }
+    text.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number)
+}
+
+// =============================================================================
+// Forms
+// =============================================================================
+
+struct FormInner<T> {
+    fields: Vec<FormField>,
+    checks: RefCell<Vec<Check<T>>>,
+    error: Signal<Option<String>>,
+    submitting: Signal<bool>,
+    submit_count: Signal<u32>,
+}
+
+/// The state of a form over the serde struct `T`, created by [`use_form`].
+///
+/// Cloning is cheap and clones share state, so a clone can be moved into
+/// event handlers.
+pub struct FormState<T> {
+    inner: Rc<FormInner<T>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for FormState<T> {
+    fn clone(&self) -> Self {
+        FormState {
+            inner: self.inner.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Create a form with a field for each field of `T::default()`.
+pub fn use_form<T>() -> FormState<T>
+where
+    T: Serialize + DeserializeOwned + Default + 'static,
+{
+    use_form_with(T::default())
+}
+
+/// Create a form starting from `initial`, e.g. a record being edited.
+///
+/// # Panics
+///
+/// If `T` doesn't serialize to a map, as structs with named fields do.
+pub fn use_form_with<T>(initial: T) -> FormState<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let fields = match serde_json::to_value(&initial) {
+        Ok(Value::Object(map)) => map.into_iter().map(|(name, value)| FormField::new(name, value)).collect(),
+        _ => panic!(
+            "use_form needs a struct with named fields, got {}",
+            std::any::type_name::<T>()
+        ),
+    };
+    FormState {
+        inner: Rc::new(FormInner {
+            fields,
+            checks: RefCell::new(Vec::new()),
+            error: Signal::new(None),
+            submitting: Signal::new(false),
+            submit_count: Signal::new(0),
+        }),
+        _marker: PhantomData,
+    }
+}
+
+impl<T> FormState<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    /// The field called `name`.
+    ///
+    /// # Panics
+    ///
+    /// If `T` has no such field.
+    pub fn field(&self, name: &str) -> FormField {
+        self.try_field(name).unwrap_or_else(|| {
+            panic!("{} has no field `{}`", std::any::type_name::<T>(), name)
+        })
+    }
+
+    /// The field called `name`, if `T` has one.
+    pub fn try_field(&self, name: &str) -> Option<FormField> {
+        self.inner.fields.iter().find(|f| f.name() == name).cloned()
+    }
+
+    /// All fields, sorted by name.
+    pub fn fields(&self) -> &[FormField] {
+        &self.inner.fields
+    }
+
+    /// Validate `name` with `rule`, which gets the value as `V`.
+    ///
+    /// Rules run in the order they are added and the first error is shown.
+    /// They are skipped while the value doesn't fit `V`.
+    pub fn validate<V, F>(self, name: &str, rule: F) -> Self
+    where
+        V: DeserializeOwned,
+        F: Fn(&V) -> Result<(), String> + 'static,
+    {
+        let validator: Validator = Rc::new(move |value| match V::deserialize(value) {
+            Ok(value) => rule(&value),
+            Err(_) => Ok(()),
+        });
+        self.field(name).inner.validators.borrow_mut().push(validator);
+        self
+    }
+
+    /// Validate `name` asynchronously, e.g. checking availability on the
+    /// server. Runs on submit once the sync rules pass.
+    pub fn validate_async<V, F, Fut>(self, name: &str, rule: F) -> Self
+    where
+        V: DeserializeOwned,
+        F: Fn(V) -> Fut + 'static,
+        Fut: Future<Output = Result<(), String>> + 'static,
+    {
+        let validator: AsyncValidator = Rc::new(move |value| {
+            let value = V::deserialize(value).ok()?;
+            Some(Box::pin(rule(value)) as Pin<Box<dyn Future<Output = Result<(), String>>>>)
+        });
+        self.field(name).inner.async_validators.borrow_mut().push(validator);
+        self
+    }
+
+    /// Validate the whole value, e.g. that two fields match. Runs on submit
+    /// once every field is valid.
+    pub fn check<F>(self, rule: F) -> Self
+    where
+        F: Fn(&T) -> Result<(), FieldError> + 'static,
+    {
+        self.inner.checks.borrow_mut().push(Rc::new(rule));
+        self
+    }
+
+    /// The current values (tracked).
+    ///
+    /// Fails when a field holds a value of the wrong type, such as text in a
+    /// number field.
+    pub fn values(&self) -> Result<T, String> {
+        let map: Map<String, Value> = self
+            .inner
+            .fields
+            .iter()
+            .map(|field| (field.name().to_string(), field.value()))
+            .collect();
+        serde_json::from_value(Value::Object(map)).map_err(|e| e.to_string())
+    }
+
+    /// Replace every value and make them the new initial values, e.g. after
+    /// loading a record.
+    pub fn set_values(&self, values: &T) {
+        let Ok(Value::Object(mut map)) = serde_json::to_value(values) else {
+            return;
+        };
+        for field in &self.inner.fields {
+            if let Some(value) = map.remove(field.name()) {
+                field.inner.initial.set(value);
+                field.reset();
+            }
+        }
+        self.inner.error.set(None);
+    }
+
+    /// Whether no field shows an error (tracked).
+    pub fn is_valid(&self) -> bool {
+        self.inner.fields.iter().all(FormField::is_valid) && self.inner.error.with(Option::is_none)
+    }
+
+    /// Whether any field differs from its initial value (tracked).
+    pub fn is_dirty(&self) -> bool {
+        self.inner.fields.iter().any(FormField::is_dirty)
+    }
+
+    /// Whether any field has been touched (tracked).
+    pub fn is_touched(&self) -> bool {
+        self.inner.fields.iter().any(|f| f.inner.touched.get())
+    }
+
+    /// `(field, message)` for every field with an error (tracked).
+    pub fn errors(&self) -> Vec<(String, String)> {
+        self.inner
+            .fields
+            .iter()
+            .filter_map(|f| f.inner.error.get().map(|e| (f.name().to_string(), e)))
+            .collect()
+    }
+
+    /// An error for the whole form: a failed submission or a
+    /// [`check`](Self::check) not tied to a field.
+    pub fn error(&self) -> Signal<Option<String>> {
+        self.inner.error.clone()
+    }
+
+    /// Whether a submission is running.
+    pub fn submitting(&self) -> Signal<bool> {
+        self.inner.submitting.clone()
+    }
+
+    /// Number of submission attempts.
+    pub fn submit_count(&self) -> Signal<u32> {
+        self.inner.submit_count.clone()
+    }
+
+    /// Touch every field and run the sync validators and checks.
+    pub fn validate_all(&self) -> bool {
+        let mut valid = true;
+        for field in &self.inner.fields {
+            field.inner.touched.set(true);
+            valid &= field.validate();
+        }
+        self.inner.error.set(None);
+        valid && self.run_checks()
+    }
+
+    /// [`validate_all`](Self::validate_all), then the async validators of
+    /// every field.
+    pub async fn validate_all_async(&self) -> bool {
+        if !self.validate_all() {
+            return false;
+        }
+        let mut valid = true;
+        for field in &self.inner.fields {
+            valid &= field.validate_async().await;
+        }
+        valid
+    }
+
+    /// Validate and, when valid, pass the values to `handler`.
+    ///
+    /// While `handler` runs, [`submitting`](Self::submitting) is `true`. An
+    /// error it returns becomes the form [`error`](Self::error); on success
+    /// the submitted values become the initial ones, so the form is clean.
+    pub async fn submit<F, Fut, O, E>(&self, handler: F) -> Option<O>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<O, E>>,
+        E: fmt::Display,
+    {
+        self.inner.submit_count.update(|count| *count += 1);
+        if !self.validate_all_async().await {
+            return None;
+        }
+        let values = match self.values() {
+            Ok(values) => values,
+            Err(error) => {
+                self.inner.error.set(Some(error));
+                return None;
+            }
+        };
+
+        self.inner.submitting.set(true);
+        let result = handler(values).await;
+        self.inner.submitting.set(false);
+        match result {
+            Ok(output) => {
+                self.inner.fields.iter().for_each(FormField::commit);
+                Some(output)
+            }
+            Err(error) => {
+                self.inner.error.set(Some(error.to_string()));
+                None
+            }
+        }
+    }
+
+    /// Validate and, when valid, dispatch the values to `action`, such as
+    /// one created from an `#[action]` server function. Returns whether it
+    /// was dispatched; the action's signals track the result.
+    pub async fn submit_action<O>(&self, action: &Action<T, O>) -> bool
+    where
+        T: Clone,
+        O: Clone + 'static,
+    {
+        let action = action.clone();
+        self.submit(|values| async move {
+            action.dispatch(values);
+            Ok::<_, std::convert::Infallible>(())
+        })
+        .await
+        .is_some()
+    }
+
+    /// An `on:submit` handler that prevents the browser submission and
+    /// calls [`submit`](Self::submit) with `handler`.
+    pub fn on_submit<F, Fut, O, E>(&self, handler: F) -> impl Fn(Event) + 'static
+    where
+        F: Fn(T) -> Fut + 'static,
+        Fut: Future<Output = Result<O, E>> + 'static,
+        E: fmt::Display + 'static,
+        O: 'static,
+    {
+        let form = self.clone();
+        let handler = Rc::new(handler);
+        move |event: Event| {
+            event.prevent_default();
+            let form = form.clone();
+            let handler = handler.clone();
+            spawn(async move {
+                form.submit(|values| handler(values)).await;
+            });
+        }
+    }
+
+    /// An `on:submit` handler that prevents the browser submission and
+    /// calls [`submit_action`](Self::submit_action).
+    pub fn on_submit_action<O>(&self, action: Action<T, O>) -> impl Fn(Event) + 'static
+    where
+        T: Clone,
+        O: Clone + 'static,
+    {
+        let form = self.clone();
+        move |event: Event| {
+            event.prevent_default();
+            let form = form.clone();
+            let action = action.clone();
+            spawn(async move {
+                form.submit_action(&action).await;
+            });
+        }
+    }
+
+    /// Restore every field's initial value and clear all errors.
+    pub fn reset(&self) {
+        self.inner.fields.iter().for_each(FormField::reset);
+        self.inner.error.set(None);
+    }
+
+    fn run_checks(&self) -> bool {
+        let Ok(values) = self.values() else {
+            return true;
+        };
+        let checks = self.inner.checks.borrow().clone();
+        for check in checks {
+            if let Err(error) = check(&values) {
+                match self.try_field(&error.field) {
+                    Some(field) => field.inner.error.set(Some(error.message)),
+                    None => self.inner.error.set(Some(error.message)),
+                }
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn spawn(future: impl Future<Output = ()> + 'static) {
+    #[cfg(target_arch = "wasm32")]
+    wasm_bindgen_futures::spawn_local(future);
+
+    // Non-WASM: run to completion, e.g. in tests
+    #[cfg(not(target_arch = "wasm32"))]
+    futures::executor::block_on(future);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validators::{email, required};
+    use super::*;
+    use futures::executor::block_on;
+    use serde::Deserialize;
+
+    #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Signup {
+        email: String,
+        age: u32,
+        password: String,
+        confirm: String,
+        newsletter: bool,
+    }
+
+    fn form() -> FormState<Signup> {
+        use_form::<Signup>()
+            .validate("email", required("Enter your email"))
+            .validate("email", email("Enter a valid email"))
+            .validate("age", |age: &u32| if *age >= 13 { Ok(()) } else { Err("Too young".to_string()) })
+            .check(|v: &Signup| validators::matches("confirm", &v.password, &v.confirm, "Passwords differ"))
+    }
+
+    #[test]
+    fn test_fields_and_values() {
+        let form = form();
+        let names: Vec<&str> = form.fields().iter().map(FormField::name).collect();
+        assert_eq!(names, ["age", "confirm", "email", "newsletter", "password"]);
+
+        form.field("email").set_input("ada@example.com");
+        form.field("age").set_input("36");
+        form.field("newsletter").set_input("on");
+        assert_eq!(form.field("age").get::<u32>(), Some(36));
+        assert_eq!(form.field("age").text(), "36");
+        assert!(form.is_dirty());
+        assert!(form.field("email").is_dirty());
+        assert!(!form.field("password").is_dirty());
+
+        let values = form.values().unwrap();
+        assert_eq!(values.email, "ada@example.com");
+        assert_eq!(values.age, 36);
+        assert!(values.newsletter);
+
+        form.reset();
+        assert!(!form.is_dirty());
+        assert_eq!(form.values().unwrap(), Signup::default());
+    }
+
+    #[test]
+    fn test_validation() {
+        let form = form();
+        let email_field = form.field("email");
+
+        // No errors before the field is touched
+        email_field.set_input("ada");
+        assert!(email_field.error().get().is_none());
+
+        email_field.blur();
+        assert!(email_field.touched().get());
+        assert_eq!(email_field.error().get().as_deref(), Some("Enter a valid email"));
+        email_field.set_input("");
+        assert_eq!(email_field.error().get().as_deref(), Some("Enter your email"));
+        email_field.set_input("ada@example.com");
+        assert!(email_field.is_valid());
+
+        let age = form.field("age");
+        age.set_input("abc");
+        assert!(form.values().is_err());
+        age.blur();
+        assert_eq!(age.error().get().as_deref(), Some(NOT_A_NUMBER));
+        age.set_input("12");
+        assert_eq!(age.error().get().as_deref(), Some("Too young"));
+
+        assert!(!form.validate_all());
+        assert_eq!(form.errors(), [("age".to_string(), "Too young".to_string())]);
+
+        age.set_input("20");
+        form.field("password").set_input("secret");
+        assert!(!form.validate_all());
+        assert_eq!(form.field("confirm").error().get().as_deref(), Some("Passwords differ"));
+        form.field("confirm").set_input("secret");
+        assert!(form.validate_all());
+        assert!(form.is_valid());
+    }
+
+    #[test]
+    fn test_submit() {
+        let taken = Rc::new(RefCell::new(vec!["taken@example.com".to_string()]));
+        let form = form().validate_async("email", {
+            let taken = taken.clone();
+            move |email: String| {
+                let taken = taken.clone();
+                async move {
+                    if taken.borrow().contains(&email) {
+                        Err("Already registered".to_string())
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+        });
+
+        // Invalid: the handler doesn't run
+        assert_eq!(block_on(form.submit(|_| async { Ok::<_, String>(1) })), None);
+        assert!(form.field("email").touched().get());
+        assert_eq!(form.submit_count().get(), 1);
+
+        form.field("email").set_input("taken@example.com");
+        form.field("age").set_input("30");
+        assert_eq!(block_on(form.submit(|_| async { Ok::<_, String>(1) })), None);
+        assert_eq!(form.field("email").error().get().as_deref(), Some("Already registered"));
+
+        form.field("email").set_input("new@example.com");
+        let failed = block_on(form.submit(|_| async { Err::<(), _>("Server unavailable") }));
+        assert_eq!(failed, None);
+        assert_eq!(form.error().get().as_deref(), Some("Server unavailable"));
+        assert!(form.is_dirty());
+
+        let submitted = block_on(form.submit(|values: Signup| async move { Ok::<_, String>(values.email) }));
+        assert_eq!(submitted.as_deref(), Some("new@example.com"));
+        assert!(form.error().get().is_none());
+        assert!(!form.is_dirty());
+        assert!(!form.submitting().get());
+    }
+
+    #[test]
+    fn test_set_values() {
+        let form = use_form_with(Signup {
+            email: "ada@example.com".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(form.field("email").text(), "ada@example.com");
+
+        form.set_values(&Signup {
+            age: 40,
+            ..Default::default()
+        });
+        assert_eq!(form.field("age").text(), "40");
+        assert!(!form.is_dirty());
+        assert!(form.try_field("missing").is_none());
+    }
+}
@@ -0,0 +1,154 @@
+//! Built-in field validators
+//!
+//! Each returns a closure for [`FormState::validate`](super::FormState::validate).
+//! Except for [`required`], they accept empty values, so combine them with
+//! `required` for mandatory fields.
+//!
+//! # Example
+//!
+//! ```rust
+//! use philjs::forms::validators::*;
+//!
+//! let form = use_form::<Signup>()
+//!     .validate("email", required("Enter your email"))
+//!     .validate("email", email("Enter a valid email"))
+//!     .validate("password", min_length(8, "Use at least 8 characters"));
+//! ```
+
+use serde_json::Value;
+
+/// A validator over a field's raw value
+pub type Rule = Box<dyn Fn(&Value) -> Result<(), String>>;
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+fn check(ok: bool, message: &str) -> Result<(), String> {
+    if ok {
+        Ok(())
+    } else {
+        Err(message.to_string())
+    }
+}
+
+/// Fails for empty strings, `None`, empty lists and unchecked checkboxes.
+pub fn required(message: impl Into<String>) -> Rule {
+    let message = message.into();
+    Box::new(move |value| check(!is_empty(value) && *value != Value::Bool(false), &message))
+}
+
+/// At least `min` characters, or `min` items for lists.
+pub fn min_length(min: usize, message: impl Into<String>) -> Rule {
+    let message = message.into();
+    Box::new(move |value| match value {
+        Value::String(s) if !s.is_empty() => check(s.chars().count() >= min, &message),
+        Value::Array(items) if !items.is_empty() => check(items.len() >= min, &message),
+        _ => Ok(()),
+    })
+}
+
+/// At most `max` characters, or `max` items for lists.
+pub fn max_length(max: usize, message: impl Into<String>) -> Rule {
+    let message = message.into();
+    Box::new(move |value| match value {
+        Value::String(s) => check(s.chars().count() <= max, &message),
+        Value::Array(items) => check(items.len() <= max, &message),
+        _ => Ok(()),
+    })
+}
+
+/// A number no smaller than `min`.
+pub fn min(min: f64, message: impl Into<String>) -> Rule {
+    let message = message.into();
+    Box::new(move |value| match value.as_f64() {
+        Some(n) => check(n >= min, &message),
+        None => Ok(()),
+    })
+}
+
+/// A number no larger than `max`.
+pub fn max(max: f64, message: impl Into<String>) -> Rule {
+    let message = message.into();
+    Box::new(move |value| match value.as_f64() {
+        Some(n) => check(n <= max, &message),
+        None => Ok(()),
+    })
+}
+
+/// Looks like an email address: `name@domain.tld` without spaces.
+pub fn email(message: impl Into<String>) -> Rule {
+    let message = message.into();
+    Box::new(move |value| match value {
+        Value::String(s) if !s.trim().is_empty() => {
+            let valid = s.split_once('@').is_some_and(|(local, domain)| {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && domain.split('.').count() >= 2
+                    && domain.split('.').all(|label| !label.is_empty())
+            });
+            check(valid && !s.contains(char::is_whitespace), &message)
+        }
+        _ => Ok(()),
+    })
+}
+
+/// The same value as the field `other`, e.g. a password confirmation.
+///
+/// Use with [`FormState::check`](super::FormState::check), which sees all
+/// values:
+///
+/// ```rust
+/// form.check(|v: &Signup| matches("confirm", &v.password, &v.confirm, "Passwords differ"))
+/// ```
+pub fn matches<V: PartialEq>(
+    field: &str,
+    expected: &V,
+    actual: &V,
+    message: impl Into<String>,
+) -> Result<(), super::FieldError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(super::FieldError::new(field, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validators() {
+        let required = required("required");
+        assert!(required(&json!("")).is_err());
+        assert!(required(&json!("  ")).is_err());
+        assert!(required(&Value::Null).is_err());
+        assert!(required(&json!(false)).is_err());
+        assert!(required(&json!(0)).is_ok());
+        assert!(required(&json!("x")).is_ok());
+
+        let short = min_length(3, "short");
+        assert!(short(&json!("ab")).is_err());
+        assert!(short(&json!("")).is_ok());
+        assert!(short(&json!("héé")).is_ok());
+
+        let range = |n| min(1.0, "low")(&n).and(max(10.0, "high")(&n));
+        assert_eq!(range(json!(0)), Err("low".to_string()));
+        assert_eq!(range(json!(11)), Err("high".to_string()));
+        assert!(range(json!(5)).is_ok());
+
+        let email = email("email");
+        assert!(email(&json!("ada@example.com")).is_ok());
+        assert!(email(&json!("")).is_ok());
+        for bad in ["ada", "ada@", "@example.com", "ada@example", "a da@example.com", "a@b@c.com"] {
+            assert!(email(&json!(bad)).is_err(), "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,448 @@
+//! Fluent (`.ftl`) catalog parsing
+
+use super::{Catalog, DateStyle, I18nError, NumberOptions, Part, Pattern};
+
+/// A message or term being read, with its continuation lines
+struct Entry {
+    id: String,
+    line: usize,
+    value: Vec<String>,
+    attributes: Vec<(String, usize, Vec<String>)>,
+}
+
+pub(super) fn parse(source: &str) -> Result<Catalog, I18nError> {
+    let mut catalog = Catalog::new();
+    let mut entry: Option<Entry> = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let number = index + 1;
+        let continues = line.starts_with([' ', '\t', '}']) || line.trim().is_empty();
+        if continues {
+            let Some(current) = entry.as_mut() else {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Err(I18nError::new(number, "indented line outside of a message"));
+            };
+            let text = line.trim_start();
+            if let Some((name, value)) = text.strip_prefix('.').and_then(|a| a.split_once('=')) {
+                let name = name.trim();
+                if !is_identifier(name) {
+                    return Err(I18nError::new(number, format!("invalid attribute name `{}`", name)));
+                }
+                current
+                    .attributes
+                    .push((name.to_string(), number, vec![value.trim().to_string()]));
+            } else if let Some((_, _, lines)) = current.attributes.last_mut() {
+                lines.push(text.to_string());
+            } else {
+                current.value.push(text.to_string());
+            }
+            continue;
+        }
+
+        if let Some(done) = entry.take() {
+            finish(done, &mut catalog)?;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((id, value)) = line.split_once('=') else {
+            return Err(I18nError::new(number, "expected `key = value`"));
+        };
+        let id = id.trim();
+        if !is_identifier(id.strip_prefix('-').unwrap_or(id)) {
+            return Err(I18nError::new(number, format!("invalid message id `{}`", id)));
+        }
+        entry = Some(Entry {
+            id: id.to_string(),
+            line: number,
+            value: vec![value.trim().to_string()],
+            attributes: Vec::new(),
+        });
+    }
+    if let Some(done) = entry {
+        finish(done, &mut catalog)?;
+    }
+    Ok(catalog)
+}
+
+fn finish(entry: Entry, catalog: &mut Catalog) -> Result<(), I18nError> {
+    let value = join(&entry.value);
+    if value.is_empty() && (entry.attributes.is_empty() || entry.id.starts_with('-')) {
+        return Err(I18nError::new(entry.line, format!("`{}` has no value", entry.id)));
+    }
+    if !value.is_empty() {
+        catalog.insert(entry.id.clone(), parse_pattern(&value, entry.line)?);
+    }
+    for (name, line, lines) in entry.attributes {
+        let pattern = parse_pattern(&join(&lines), line)?;
+        catalog.insert(format!("{}.{}", entry.id, name), pattern);
+    }
+    Ok(())
+}
+
+/// Lines of a multiline value, without leading and trailing blank lines
+fn join(lines: &[String]) -> String {
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// =============================================================================
+// Patterns
+// =============================================================================
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    source: &'a str,
+}
+
+fn parse_pattern(text: &str, line: usize) -> Result<Pattern, I18nError> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+        line,
+        source: text,
+    };
+    let pattern = parser.pattern(None)?;
+    if parser.pos < parser.chars.len() {
+        return Err(parser.error("unexpected `}`"));
+    }
+    Ok(pattern)
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> I18nError {
+        let line = self.line + self.chars[..self.pos.min(self.chars.len())]
+            .iter()
+            .filter(|c| **c == '\n')
+            .count();
+        I18nError::new(line, format!("{} in `{}`", message, self.source.trim()))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        let matches = expected
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.chars.get(self.pos + i) == Some(&c));
+        if matches {
+            self.pos += expected.chars().count();
+        }
+        matches
+    }
+
+    fn identifier(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// Text and placeables up to `end` (not consumed) or the end of input
+    fn pattern(&mut self, end: Option<char>) -> Result<Pattern, I18nError> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            if Some(c) == end || c == '}' {
+                break;
+            }
+            if c == '{' {
+                if !text.is_empty() {
+                    parts.push(Part::Text(std::mem::take(&mut text)));
+                }
+                self.pos += 1;
+                parts.push(self.placeable()?);
+            } else {
+                text.push(c);
+                self.pos += 1;
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Ok(parts)
+    }
+
+    /// After `{`, through the closing `}`
+    fn placeable(&mut self) -> Result<Part, I18nError> {
+        self.skip_whitespace();
+        let part = match self.peek() {
+            Some('$') => {
+                self.pos += 1;
+                let var = self.identifier();
+                if var.is_empty() {
+                    return Err(self.error("expected a variable name after `$`"));
+                }
+                self.skip_whitespace();
+                if self.eat("->") {
+                    return self.select(var);
+                }
+                Part::Var(var)
+            }
+            Some('"') => {
+                self.pos += 1;
+                let mut literal = String::new();
+                loop {
+                    match self.peek() {
+                        Some('"') => break,
+                        Some('\\') => {
+                            self.pos += 1;
+                            literal.extend(self.peek());
+                        }
+                        Some(c) => literal.push(c),
+                        None => return Err(self.error("unterminated string literal")),
+                    }
+                    self.pos += 1;
+                }
+                self.pos += 1;
+                Part::Text(literal)
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let start = self.pos;
+                self.pos += 1;
+                let rest = self.identifier();
+                let token: String = self.chars[start..self.pos].iter().collect();
+                if c == '-' && !rest.starts_with(|c: char| c.is_ascii_digit()) {
+                    Part::Ref(token)
+                } else {
+                    while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+                        self.pos += 1;
+                    }
+                    Part::Text(self.chars[start..self.pos].iter().collect())
+                }
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let id = self.identifier();
+                self.skip_whitespace();
+                if self.eat("(") {
+                    let part = self.function(&id)?;
+                    self.skip_whitespace();
+                    if self.eat("->") {
+                        return match part {
+                            Part::Number { var, .. } => self.select(var),
+                            _ => Err(self.error("only `NUMBER()` can be selected on")),
+                        };
+                    }
+                    part
+                } else {
+                    Part::Ref(id)
+                }
+            }
+            _ => return Err(self.error("expected a variable, literal or reference")),
+        };
+        self.skip_whitespace();
+        if !self.eat("}") {
+            return Err(self.error("expected `}`"));
+        }
+        Ok(part)
+    }
+
+    /// After `NAME(`, through the closing `)`
+    fn function(&mut self, name: &str) -> Result<Part, I18nError> {
+        self.skip_whitespace();
+        if !self.eat("$") {
+            return Err(self.error(&format!("`{}()` expects a variable like `$amount`", name)));
+        }
+        let var = self.identifier();
+        let mut options = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.eat(")") {
+                break;
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected `,` or `)`"));
+            }
+            self.skip_whitespace();
+            let option = self.identifier();
+            self.skip_whitespace();
+            if option.is_empty() || !self.eat(":") {
+                return Err(self.error("expected an option like `name: value`"));
+            }
+            self.skip_whitespace();
+            let quoted = self.eat("\"");
+            let start = self.pos;
+            while self.peek().is_some_and(|c| {
+                if quoted {
+                    c != '"'
+                } else {
+                    c.is_ascii_alphanumeric() || c == '.' || c == '-'
+                }
+            }) {
+                self.pos += 1;
+            }
+            let value: String = self.chars[start..self.pos].iter().collect();
+            if quoted && !self.eat("\"") {
+                return Err(self.error("unterminated string literal"));
+            }
+            options.push((option, value));
+        }
+
+        match name {
+            "NUMBER" => {
+                let mut number = NumberOptions::default();
+                for (option, value) in options {
+                    let digits = || {
+                        value
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|d| *d <= 20)
+                            .ok_or_else(|| self.error(&format!("invalid `{}` `{}`", option, value)))
+                    };
+                    match option.as_str() {
+                        "minimumFractionDigits" => number.min_fraction_digits = digits()?,
+                        "maximumFractionDigits" => number.max_fraction_digits = digits()?,
+                        "useGrouping" => number.grouping = value != "false",
+                        _ => return Err(self.error(&format!("unsupported `NUMBER()` option `{}`", option))),
+                    }
+                }
+                if number.max_fraction_digits < number.min_fraction_digits {
+                    number.max_fraction_digits = number.min_fraction_digits;
+                }
+                Ok(Part::Number { var, options: number })
+            }
+            "DATETIME" => {
+                let mut style = DateStyle::default();
+                for (option, value) in options {
+                    match option.as_str() {
+                        "dateStyle" => {
+                            style = DateStyle::from_name(&value)
+                                .ok_or_else(|| self.error(&format!("invalid `dateStyle` `{}`", value)))?
+                        }
+                        _ => return Err(self.error(&format!("unsupported `DATETIME()` option `{}`", option))),
+                    }
+                }
+                Ok(Part::Date { var, style })
+            }
+            _ => Err(self.error(&format!("unknown function `{}()`", name))),
+        }
+    }
+
+    /// After `$var ->`, through the closing `}`
+    fn select(&mut self, var: String) -> Result<Part, I18nError> {
+        let mut variants = Vec::new();
+        let mut default = None;
+        loop {
+            self.skip_whitespace();
+            if self.eat("}") {
+                break;
+            }
+            if self.eat("*") {
+                if default.is_some() {
+                    return Err(self.error("more than one default variant"));
+                }
+                default = Some(variants.len());
+            }
+            if !self.eat("[") {
+                return Err(self.error("expected a variant like `[one]`"));
+            }
+            let start = self.pos;
+            while self.peek().is_some_and(|c| c != ']' && c != '\n') {
+                self.pos += 1;
+            }
+            let key: String = self.chars[start..self.pos].iter().collect();
+            if !self.eat("]") {
+                return Err(self.error("expected `]`"));
+            }
+            while self.peek().is_some_and(|c| c == ' ' || c == '\t') {
+                self.pos += 1;
+            }
+            let value = self.pattern(Some('\n'))?;
+            variants.push((key.trim().to_string(), trim_pattern(value)));
+        }
+        let Some(default) = default else {
+            return Err(self.error("select expression needs a default variant like `*[other]`"));
+        };
+        Ok(Part::Select { var, variants, default })
+    }
+}
+
+fn trim_pattern(mut pattern: Pattern) -> Pattern {
+    if let Some(Part::Text(text)) = pattern.last_mut() {
+        let trimmed = text.trim_end().to_string();
+        *text = trimmed;
+    }
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fluent() {
+        let catalog = parse(
+            "# Comment\n\
+             hello = Hello\n\
+             multi =\n    First line\n    second line\n\
+             login = Log in\n    .placeholder = Email\n\
+             -brand = PhilJS\n\
+             quoted = { \"{\" }braces{ \"}\" }\n",
+        )
+        .unwrap();
+
+        assert_eq!(catalog.format("hello", &[], "en").unwrap(), "Hello");
+        assert_eq!(catalog.format("multi", &[], "en").unwrap(), "First line\nsecond line");
+        assert_eq!(catalog.format("login.placeholder", &[], "en").unwrap(), "Email");
+        assert_eq!(catalog.format("quoted", &[], "en").unwrap(), "{braces}");
+        assert!(!catalog.contains("-brand"));
+        assert_eq!(catalog.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse("a = ok\nb\n").unwrap_err().line, 2);
+        assert_eq!(parse("a = { $x ->\n  [one] x\n}\n").unwrap_err().line, 3);
+        assert!(parse("a = { $x \n").is_err());
+        assert!(parse("9a = x\n").is_err());
+        assert!(parse("a =\n").is_err());
+        assert!(parse("a = { UPPER($x) }\n").is_err());
+        assert!(parse("a = { NUMBER($x, style: \"percent\") }\n").is_err());
+        assert!(parse("a = { DATETIME($x, dateStyle: \"huge\") }\n").is_err());
+    }
+
+    #[test]
+    fn test_functions() {
+        use crate::i18n::Date;
+
+        let catalog = parse(
+            "total = { NUMBER($amount, minimumFractionDigits: 2) }\n\
+             id = #{ NUMBER($id, useGrouping: \"false\") }\n\
+             shipped = { DATETIME($date, dateStyle: \"long\") }\n\
+             items = { NUMBER($count) ->\n    [one] one item\n   *[other] { $count } items\n}\n",
+        )
+        .unwrap();
+
+        let format = |key, args: &[(&str, crate::i18n::Arg)], locale| catalog.format(key, args, locale).unwrap();
+        assert_eq!(format("total", &[("amount", 1234.5.into())], "en"), "1,234.50");
+        assert_eq!(format("total", &[("amount", 1234.5.into())], "de"), "1.234,50");
+        assert_eq!(format("id", &[("id", 123456.into())], "en"), "#123456");
+        assert_eq!(
+            format("shipped", &[("date", Date::new(2026, 3, 1).into())], "fr"),
+            "1 mars 2026"
+        );
+        assert_eq!(format("items", &[("count", 1.into())], "en"), "one item");
+        assert_eq!(format("items", &[("count", 1200.into())], "en"), "1,200 items");
+    }
+}
@@ -0,0 +1,297 @@
+//! Locale-aware number and date formatting
+//!
+//! Covers the separators, date orders and month names of the most common
+//! locales without pulling in CLDR data; other locales get ISO-style output.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::language;
+
+// =============================================================================
+// Numbers
+// =============================================================================
+
+/// How [`format_number`] writes a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberOptions {
+    /// Fraction digits always shown, padded with zeros
+    pub min_fraction_digits: usize,
+    /// Fraction digits shown at most, rounding the rest
+    pub max_fraction_digits: usize,
+    /// Separate thousands
+    pub grouping: bool,
+}
+
+impl Default for NumberOptions {
+    fn default() -> Self {
+        NumberOptions {
+            min_fraction_digits: 0,
+            max_fraction_digits: 3,
+            grouping: true,
+        }
+    }
+}
+
+impl NumberOptions {
+    /// Exactly `digits` fraction digits, e.g. 2 for prices.
+    pub fn fixed(digits: usize) -> Self {
+        NumberOptions {
+            min_fraction_digits: digits,
+            max_fraction_digits: digits,
+            grouping: true,
+        }
+    }
+}
+
+/// Group and decimal separators, and the smallest number of integer digits
+/// that gets grouped
+fn separators(locale: &str) -> (&'static str, &'static str, usize) {
+    if locale.eq_ignore_ascii_case("de-CH") || locale.eq_ignore_ascii_case("it-CH") {
+        return ("\u{2019}", ".", 4);
+    }
+    match language(locale).as_str() {
+        "de" | "it" | "nl" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl" | "sr" | "vi" => (".", ",", 4),
+        "es" | "pt" => (".", ",", 5),
+        "fr" => ("\u{202f}", ",", 4),
+        "pl" => ("\u{a0}", ",", 5),
+        "ru" | "uk" | "cs" | "sk" | "sv" | "nb" | "no" | "fi" | "hu" | "bg" | "lt" | "lv" | "et" => {
+            ("\u{a0}", ",", 4)
+        }
+        _ => (",", ".", 4),
+    }
+}
+
+/// Write `value` the way `locale` does, e.g. `1,234.5` in `en` and
+/// `1.234,5` in `de`.
+pub fn format_number(locale: &str, value: f64, options: &NumberOptions) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let (group, decimal, min_grouped) = separators(locale);
+    let max = options.max_fraction_digits.max(options.min_fraction_digits);
+    let fixed = format!("{:.*}", max, value.abs());
+    let (integer, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+    let mut fraction = fraction.trim_end_matches('0').to_string();
+    while fraction.len() < options.min_fraction_digits {
+        fraction.push('0');
+    }
+
+    let mut out = String::new();
+    if value < 0.0 && (integer.bytes().any(|b| b != b'0') || !fraction.is_empty()) {
+        out.push('-');
+    }
+    if options.grouping && integer.len() >= min_grouped {
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                out.push_str(group);
+            }
+            out.push(digit);
+        }
+    } else {
+        out.push_str(integer);
+    }
+    if !fraction.is_empty() {
+        out.push_str(decimal);
+        out.push_str(&fraction);
+    }
+    out
+}
+
+// =============================================================================
+// Dates
+// =============================================================================
+
+/// A calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// Year, e.g. 2026
+    pub year: i32,
+    /// Month, 1-12
+    pub month: u8,
+    /// Day of the month, 1-31
+    pub day: u8,
+}
+
+impl Date {
+    /// Create a date; `month` and `day` are 1-based.
+    pub fn new(year: i32, month: u8, day: u8) -> Self {
+        Date { year, month, day }
+    }
+
+    /// The UTC date of a Unix timestamp in seconds.
+    pub fn from_unix(seconds: i64) -> Self {
+        // Howard Hinnant's days-to-civil algorithm
+        let days = seconds.div_euclid(86_400) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+        Date { year, month, day }
+    }
+
+    /// Today's UTC date.
+    pub fn today() -> Self {
+        SystemTime::now().into()
+    }
+}
+
+impl From<SystemTime> for Date {
+    fn from(time: SystemTime) -> Self {
+        let seconds = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_secs() as i64,
+            Err(before) => -(before.duration().as_secs() as i64) - 1,
+        };
+        Date::from_unix(seconds)
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// How much of a date [`format_date`] spells out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateStyle {
+    /// Numeric, e.g. `1/15/2026` or `15.01.2026`
+    #[default]
+    Short,
+    /// Abbreviated month where the locale has one, e.g. `Jan 15, 2026`
+    Medium,
+    /// Full month name, e.g. `January 15, 2026` or `15. Januar 2026`
+    Long,
+}
+
+impl DateStyle {
+    /// Parse Fluent's `dateStyle` option: `short`, `medium`, `long` or `full`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "short" => Some(DateStyle::Short),
+            "medium" => Some(DateStyle::Medium),
+            "long" | "full" => Some(DateStyle::Long),
+            _ => None,
+        }
+    }
+}
+
+fn month_names(lang: &str) -> Option<[&'static str; 12]> {
+    Some(match lang {
+        "en" => [
+            "January", "February", "March", "April", "May", "June", "July", "August", "September", "October",
+            "November", "December",
+        ],
+        "fr" => [
+            "janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre",
+            "novembre", "décembre",
+        ],
+        "de" => [
+            "Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober",
+            "November", "Dezember",
+        ],
+        "es" => [
+            "enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre",
+            "noviembre", "diciembre",
+        ],
+        "it" => [
+            "gennaio", "febbraio", "marzo", "aprile", "maggio", "giugno", "luglio", "agosto", "settembre",
+            "ottobre", "novembre", "dicembre",
+        ],
+        "pt" => [
+            "janeiro", "fevereiro", "março", "abril", "maio", "junho", "julho", "agosto", "setembro", "outubro",
+            "novembro", "dezembro",
+        ],
+        "nl" => [
+            "januari", "februari", "maart", "april", "mei", "juni", "juli", "augustus", "september", "oktober",
+            "november", "december",
+        ],
+        _ => return None,
+    })
+}
+
+/// English as written in the US (and by default) puts the month first
+fn is_us_english(locale: &str) -> bool {
+    let mut subtags = locale.split(['-', '_']);
+    subtags.next().is_some_and(|l| l.eq_ignore_ascii_case("en"))
+        && subtags.next().is_none_or(|region| region.eq_ignore_ascii_case("US"))
+}
+
+/// Write `date` the way `locale` does.
+pub fn format_date(locale: &str, date: Date, style: DateStyle) -> String {
+    let Date { year, month, day } = date;
+    let lang = language(locale);
+
+    if style != DateStyle::Short {
+        match lang.as_str() {
+            "ja" | "zh" => return format!("{}年{}月{}日", year, month, day),
+            "ko" => return format!("{}년 {}월 {}일", year, month, day),
+            _ => {}
+        }
+        if let Some(names) = month_names(&lang) {
+            let name = names[usize::from(month.clamp(1, 12)) - 1];
+            return match lang.as_str() {
+                "en" => {
+                    let name = if style == DateStyle::Medium { &name[..3] } else { name };
+                    if is_us_english(locale) {
+                        format!("{} {}, {}", name, day, year)
+                    } else {
+                        format!("{} {} {}", day, name, year)
+                    }
+                }
+                "de" => format!("{}. {} {}", day, name, year),
+                "es" | "pt" => format!("{} de {} de {}", day, name, year),
+                _ => format!("{} {} {}", day, name, year),
+            };
+        }
+    }
+
+    match lang.as_str() {
+        "en" if is_us_english(locale) => format!("{}/{}/{}", month, day, year),
+        "en" | "fr" | "es" | "it" | "pt" | "el" | "id" | "vi" => format!("{:02}/{:02}/{}", day, month, year),
+        "de" | "ru" | "uk" | "pl" | "cs" | "sk" | "fi" | "nb" | "no" | "da" | "tr" | "ro" | "bg" => {
+            format!("{:02}.{:02}.{}", day, month, year)
+        }
+        "nl" => format!("{:02}-{:02}-{}", day, month, year),
+        "ja" | "zh" => format!("{}/{:02}/{:02}", year, month, day),
+        "ko" => format!("{}. {}. {}.", year, month, day),
+        _ => date.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number() {
+        let default = NumberOptions::default();
+        assert_eq!(format_number("en", 1234567.891, &default), "1,234,567.891");
+        assert_eq!(format_number("de", 1234.5, &default), "1.234,5");
+        assert_eq!(format_number("fr", -1234.5, &default), "-1\u{202f}234,5");
+        assert_eq!(format_number("es", 1234.0, &default), "1234");
+        assert_eq!(format_number("es", 12345.0, &default), "12.345");
+        assert_eq!(format_number("en", 2.0, &NumberOptions::fixed(2)), "2.00");
+        assert_eq!(format_number("en", -0.0001, &default), "0");
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(Date::from_unix(0), Date::new(1970, 1, 1));
+        assert_eq!(Date::from_unix(1_768_435_200), Date::new(2026, 1, 15));
+        assert_eq!(Date::from_unix(-86_400), Date::new(1969, 12, 31));
+
+        let date = Date::new(2026, 1, 15);
+        assert_eq!(format_date("en", date, DateStyle::Short), "1/15/2026");
+        assert_eq!(format_date("en-GB", date, DateStyle::Short), "15/01/2026");
+        assert_eq!(format_date("de", date, DateStyle::Short), "15.01.2026");
+        assert_eq!(format_date("en-US", date, DateStyle::Medium), "Jan 15, 2026");
+        assert_eq!(format_date("de-AT", date, DateStyle::Long), "15. Januar 2026");
+        assert_eq!(format_date("es", date, DateStyle::Long), "15 de enero de 2026");
+        assert_eq!(format_date("ja", date, DateStyle::Long), "2026年1月15日");
+        assert_eq!(format_date("xx", date, DateStyle::Long), "2026-01-15");
+    }
+}
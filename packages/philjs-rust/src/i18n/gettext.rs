@@ -0,0 +1,237 @@
+//! gettext (`.po`) catalog parsing
+
+use super::{Catalog, I18nError, Part, Pattern};
+
+/// The string a continuation line (`"..."`) appends to
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Context,
+    Id,
+    IdPlural,
+    Str(usize),
+}
+
+#[derive(Default)]
+struct Entry {
+    line: usize,
+    fuzzy: bool,
+    id: Option<String>,
+    plural: bool,
+    strings: Vec<String>,
+}
+
+pub(super) fn parse(source: &str) -> Result<Catalog, I18nError> {
+    let mut catalog = Catalog::new();
+    let mut entry = Entry::default();
+    let mut field = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("#~") {
+            continue;
+        }
+        if let Some(flags) = line.strip_prefix("#,") {
+            if matches!(field, Some(Field::Str(_))) {
+                finish(std::mem::take(&mut entry), &mut catalog)?;
+                field = None;
+            }
+            entry.fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let (keyword, rest) = match line.find(|c: char| c.is_whitespace()) {
+            Some(end) if !line.starts_with('"') => (&line[..end], line[end..].trim()),
+            _ => ("", line),
+        };
+        let next = match keyword {
+            "" => match field {
+                Some(field) => field,
+                None => return Err(I18nError::new(number, "string outside of an entry")),
+            },
+            "msgctxt" => Field::Context,
+            "msgid" => Field::Id,
+            "msgid_plural" => Field::IdPlural,
+            "msgstr" => Field::Str(0),
+            _ => match keyword
+                .strip_prefix("msgstr[")
+                .and_then(|k| k.strip_suffix(']'))
+                .and_then(|n| n.parse().ok())
+            {
+                Some(n) => Field::Str(n),
+                None => return Err(I18nError::new(number, format!("unknown keyword `{}`", keyword))),
+            },
+        };
+
+        // A new entry starts with `msgctxt` or `msgid` after a `msgstr`
+        if matches!(next, Field::Context | Field::Id) && matches!(field, Some(Field::Str(_))) {
+            finish(std::mem::take(&mut entry), &mut catalog)?;
+        }
+        if entry.line == 0 {
+            entry.line = number;
+        }
+
+        let text = unquote(rest).ok_or_else(|| I18nError::new(number, "expected a quoted string"))?;
+        match next {
+            Field::Context => {}
+            Field::Id => entry.id.get_or_insert_with(String::new).push_str(&text),
+            Field::IdPlural => entry.plural = true,
+            Field::Str(n) => {
+                if entry.strings.len() <= n {
+                    entry.strings.resize(n + 1, String::new());
+                }
+                entry.strings[n].push_str(&text);
+            }
+        }
+        field = Some(next);
+    }
+    finish(entry, &mut catalog)?;
+    Ok(catalog)
+}
+
+fn finish(entry: Entry, catalog: &mut Catalog) -> Result<(), I18nError> {
+    let Some(id) = entry.id else {
+        if entry.strings.is_empty() {
+            return Ok(());
+        }
+        return Err(I18nError::new(entry.line, "`msgstr` without a `msgid`"));
+    };
+    // The header, and entries still waiting for a translation
+    if id.is_empty() || entry.fuzzy || entry.strings.iter().all(String::is_empty) {
+        return Ok(());
+    }
+    if entry.plural {
+        if entry.strings.iter().any(String::is_empty) {
+            return Ok(());
+        }
+        let forms = entry.strings.iter().map(|s| parse_braces(s)).collect();
+        catalog.insert(id, vec![Part::Plural { var: "count".to_string(), forms }]);
+    } else {
+        catalog.insert(id, parse_braces(&entry.strings[0]));
+    }
+    Ok(())
+}
+
+/// The contents of a `"..."` string with escapes resolved
+fn unquote(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            other => out.push(other),
+        }
+    }
+    Some(out)
+}
+
+/// `{name}` placeholders; `{{` and `}}` are literal braces
+fn parse_braces(text: &str) -> Pattern {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(['{', '}']) {
+        literal.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            literal.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+        let name = rest[1..].find('}').map(|end| &rest[1..end + 1]);
+        match name {
+            Some(name)
+                if !name.is_empty()
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+            {
+                if !literal.is_empty() {
+                    parts.push(Part::Text(std::mem::take(&mut literal)));
+                }
+                parts.push(Part::Var(name.to_string()));
+                rest = &rest[name.len() + 2..];
+            }
+            _ => {
+                literal.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        parts.push(Part::Text(literal));
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PO: &str = r#"
+msgid ""
+msgstr ""
+"Language: ru\n"
+
+#: src/app.rs:3
+msgid "welcome"
+msgstr "Привет, {name}!"
+
+msgid "files"
+msgid_plural "files"
+msgstr[0] "{count} файл"
+msgstr[1] "{count} файла"
+msgstr[2] "{count} "
+"файлов"
+
+#, fuzzy
+msgid "draft"
+msgstr "Черновик"
+
+msgid "untranslated"
+msgstr ""
+
+#~ msgid "old"
+#~ msgstr "Старый"
+"#;
+
+    #[test]
+    fn test_parse_gettext() {
+        let catalog = parse(PO).unwrap();
+        let mut keys: Vec<&str> = catalog.keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["files", "welcome"]);
+
+        assert_eq!(
+            catalog.format("welcome", &[("name", "Ада".into())], "ru").unwrap(),
+            "Привет, Ада!"
+        );
+        let files = |n: u32| catalog.format("files", &[("count", n.into())], "ru").unwrap();
+        assert_eq!(files(1), "1 файл");
+        assert_eq!(files(3), "3 файла");
+        assert_eq!(files(5), "5 файлов");
+    }
+
+    #[test]
+    fn test_parse_braces() {
+        assert_eq!(
+            parse_braces("{{x}} {x} {not valid}"),
+            vec![
+                Part::Text("{x} ".to_string()),
+                Part::Var("x".to_string()),
+                Part::Text(" {not valid}".to_string()),
+            ]
+        );
+        assert!(parse("msgstr \"x\"\n").is_err());
+        assert_eq!(parse("msgid \"a\"\nmsgstr x\n").unwrap_err().line, 2);
+    }
+}
@@ -0,0 +1,812 @@
+//! Internationalization
+//!
+//! Translations are looked up by key in per-locale catalogs written as
+//! Fluent (`.ftl`) or gettext (`.po`) files, usually embedded with
+//! `include_str!`. The active locale is a signal, so text produced by
+//! [`t!`](crate::t) inside a reactive closure updates when it changes.
+//!
+//! # Example
+//!
+//! ```rust
+//! use philjs::i18n::*;
+//! use philjs::t;
+//!
+//! let i18n = I18n::new("en")
+//!     .with_fluent("en", include_str!("../locales/en/messages.ftl"))?
+//!     .with_fluent("fr", include_str!("../locales/fr/messages.ftl"))?;
+//! provide_i18n(i18n.clone());
+//!
+//! view! {
+//!     <h1>{move || t!("welcome", name = user.name())}</h1>
+//!     <p>{move || t!("unread-emails", count = unread.get())}</p>
+//!     <button on:click=move |_| i18n.set_locale("fr")>"Français"</button>
+//! }
+//! ```
+//!
+//! ```ftl
+//! welcome = Welcome, { $name }!
+//! unread-emails = { $count ->
+//!     [one] You have one new email
+//!    *[other] You have { $count } new emails
+//! }
+//! ```
+//!
+//! Lookups fall back from `fr-CA` to `fr`, then to the default locale, and
+//! finally to the key itself. `cargo philjs i18n extract` adds the keys used
+//! with `t!` to the catalogs and `cargo philjs i18n status` lists the ones
+//! still untranslated.
+//!
+//! Numbers and [`Date`]s passed to `t!` are formatted for the locale, and
+//! Fluent's `NUMBER()` and `DATETIME()` functions pick other styles:
+//!
+//! ```ftl
+//! total = Total: { NUMBER($amount, minimumFractionDigits: 2) }
+//! shipped = Shipped on { DATETIME($date, dateStyle: "long") }
+//! ```
+//!
+//! On the server, share one [`Translations`] between threads and provide a
+//! [`RequestLocale`] per request; see [`Translations::resolve`].
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::reactive::context::{provide_context, use_context};
+use crate::reactive::Signal;
+
+mod fluent;
+mod format;
+mod gettext;
+mod negotiate;
+
+pub use format::{format_date, format_number, Date, DateStyle, NumberOptions};
+pub use negotiate::{
+    negotiate_locale, strip_locale_prefix, LocaleRequest, RequestLocale, Translations, LOCALE_COOKIE,
+};
+
+/// References between messages deeper than this are treated as cycles
+const MAX_DEPTH: usize = 8;
+
+// =============================================================================
+// Arguments
+// =============================================================================
+
+/// A value interpolated into a message.
+///
+/// Numbers also select plural variants, strings select variants by name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Arg {
+    /// Text
+    Str(String),
+    /// Number, formatted for the locale
+    Num(f64),
+    /// Date, formatted for the locale
+    Date(Date),
+}
+
+impl Arg {
+    /// The value as `locale` writes it.
+    pub fn format(&self, locale: &str) -> String {
+        match self {
+            Arg::Str(s) => s.clone(),
+            Arg::Num(n) => format_number(locale, *n, &NumberOptions::default()),
+            Arg::Date(date) => format_date(locale, *date, DateStyle::Short),
+        }
+    }
+}
+
+impl fmt::Display for Arg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arg::Str(s) => write!(f, "{}", s),
+            Arg::Num(n) if n.fract() == 0.0 && n.abs() < 1e15 => write!(f, "{}", *n as i64),
+            Arg::Num(n) => write!(f, "{}", n),
+            Arg::Date(date) => write!(f, "{}", date),
+        }
+    }
+}
+
+impl From<Date> for Arg {
+    fn from(value: Date) -> Self {
+        Arg::Date(value)
+    }
+}
+
+impl From<&str> for Arg {
+    fn from(value: &str) -> Self {
+        Arg::Str(value.to_string())
+    }
+}
+
+impl From<String> for Arg {
+    fn from(value: String) -> Self {
+        Arg::Str(value)
+    }
+}
+
+impl From<&String> for Arg {
+    fn from(value: &String) -> Self {
+        Arg::Str(value.clone())
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Arg {
+                fn from(value: $ty) -> Self {
+                    Arg::Num(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+// =============================================================================
+// Catalogs
+// =============================================================================
+
+/// A piece of a parsed message
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    /// `{ $name }`
+    Var(String),
+    /// `{ NUMBER($name, ...) }`
+    Number { var: String, options: NumberOptions },
+    /// `{ DATETIME($name, ...) }`
+    Date { var: String, style: DateStyle },
+    /// `{ other-message }` or `{ -term }`
+    Ref(String),
+    /// `{ $var -> [key] ... *[other] ... }`
+    Select {
+        var: String,
+        variants: Vec<(String, Pattern)>,
+        default: usize,
+    },
+    /// gettext `msgstr[n]` forms, in the locale's plural category order
+    Plural { var: String, forms: Vec<Pattern> },
+}
+
+type Pattern = Vec<Part>;
+
+/// Error from parsing a catalog
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct I18nError {
+    /// 1-based line in the catalog source
+    pub line: usize,
+    /// What is wrong
+    pub message: String,
+}
+
+impl I18nError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        I18nError {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for I18nError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for I18nError {}
+
+/// The translated messages of one locale.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    /// Messages by key; Fluent terms are kept with their leading `-`
+    messages: HashMap<String, Pattern>,
+}
+
+impl Catalog {
+    /// Create an empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a Fluent catalog.
+    ///
+    /// Supports messages, attributes (looked up as `message.attribute`),
+    /// terms, variables, message and term references, string and number
+    /// literals, `NUMBER()` and `DATETIME()`, and select expressions whose
+    /// variants fit on one line.
+    pub fn parse_fluent(source: &str) -> Result<Self, I18nError> {
+        fluent::parse(source)
+    }
+
+    /// Parse a gettext `.po` catalog.
+    ///
+    /// The `msgid` is the key and `{name}` in a `msgstr` is replaced by the
+    /// `name` argument. Plural forms are selected by the `count` argument.
+    /// Fuzzy and untranslated entries are skipped.
+    pub fn parse_gettext(source: &str) -> Result<Self, I18nError> {
+        gettext::parse(source)
+    }
+
+    /// Whether `key` has a translation.
+    pub fn contains(&self, key: &str) -> bool {
+        !key.starts_with('-') && self.messages.contains_key(key)
+    }
+
+    /// Translated keys, in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.messages
+            .keys()
+            .map(String::as_str)
+            .filter(|k| !k.starts_with('-'))
+    }
+
+    /// Number of translated keys.
+    pub fn len(&self) -> usize {
+        self.keys().count()
+    }
+
+    /// Whether the catalog has no translations.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add the messages of `other`, replacing those with the same key.
+    pub fn extend(&mut self, other: Catalog) {
+        self.messages.extend(other.messages);
+    }
+
+    /// Format `key` for `locale`, or `None` when it has no translation.
+    pub fn format(&self, key: &str, args: &[(&str, Arg)], locale: &str) -> Option<String> {
+        if !self.contains(key) {
+            return None;
+        }
+        let mut out = String::new();
+        self.write_pattern(&self.messages[key], args, locale, 0, &mut out);
+        Some(out)
+    }
+
+    fn insert(&mut self, key: String, pattern: Pattern) {
+        self.messages.insert(key, pattern);
+    }
+
+    fn write_pattern(&self, pattern: &Pattern, args: &[(&str, Arg)], locale: &str, depth: usize, out: &mut String) {
+        for part in pattern {
+            match part {
+                Part::Text(text) => out.push_str(text),
+                Part::Var(name) => match arg(args, name) {
+                    Some(value) => out.push_str(&value.format(locale)),
+                    None => out.push_str(&format!("{{${}}}", name)),
+                },
+                Part::Number { var, options } => match arg(args, var) {
+                    Some(Arg::Num(n)) => out.push_str(&format_number(locale, *n, options)),
+                    Some(value) => out.push_str(&value.format(locale)),
+                    None => out.push_str(&format!("{{${}}}", var)),
+                },
+                Part::Date { var, style } => match arg(args, var) {
+                    Some(Arg::Date(date)) => out.push_str(&format_date(locale, *date, *style)),
+                    Some(value) => out.push_str(&value.format(locale)),
+                    None => out.push_str(&format!("{{${}}}", var)),
+                },
+                Part::Ref(id) => match self.messages.get(id) {
+                    Some(referenced) if depth < MAX_DEPTH => {
+                        self.write_pattern(referenced, args, locale, depth + 1, out)
+                    }
+                    _ => out.push_str(&format!("{{{}}}", id)),
+                },
+                Part::Select { var, variants, default } => {
+                    let index = select_variant(arg(args, var), variants, locale).unwrap_or(*default);
+                    self.write_pattern(&variants[index].1, args, locale, depth, out);
+                }
+                Part::Plural { var, forms } => {
+                    let category = match arg(args, var) {
+                        Some(Arg::Num(n)) => plural_category(locale, *n),
+                        _ => "other",
+                    };
+                    let index = plural_categories(locale)
+                        .iter()
+                        .position(|c| *c == category)
+                        .unwrap_or(forms.len() - 1)
+                        .min(forms.len() - 1);
+                    self.write_pattern(&forms[index], args, locale, depth, out);
+                }
+            }
+        }
+    }
+}
+
+fn arg<'a>(args: &'a [(&str, Arg)], name: &str) -> Option<&'a Arg> {
+    args.iter().find(|(n, _)| *n == name).map(|(_, value)| value)
+}
+
+/// Exact matches win over plural categories
+fn select_variant(value: Option<&Arg>, variants: &[(String, Pattern)], locale: &str) -> Option<usize> {
+    match value? {
+        Arg::Str(s) => variants.iter().position(|(key, _)| key == s),
+        Arg::Num(n) => variants
+            .iter()
+            .position(|(key, _)| key.parse::<f64>().ok() == Some(*n))
+            .or_else(|| {
+                let category = plural_category(locale, *n);
+                variants.iter().position(|(key, _)| key == category)
+            }),
+        Arg::Date(_) => None,
+    }
+}
+
+fn language(locale: &str) -> String {
+    locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// The CLDR plural categories a language uses, in gettext form order
+fn plural_categories(locale: &str) -> &'static [&'static str] {
+    match language(locale).as_str() {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => &["other"],
+        "ru" | "uk" | "be" | "pl" | "hr" | "sr" | "bs" => &["one", "few", "many"],
+        "cs" | "sk" => &["one", "few", "other"],
+        _ => &["one", "other"],
+    }
+}
+
+/// The plural category of `n` in `locale`, e.g. `one` or `other`.
+///
+/// Covers the rules of the most common languages; others follow English.
+pub fn plural_category(locale: &str, n: f64) -> &'static str {
+    let integer = n.fract() == 0.0 && n >= 0.0;
+    let i = n.abs() as u64;
+    match language(locale).as_str() {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => "other",
+        "fr" | "pt" if (0.0..2.0).contains(&n) => "one",
+        "fr" | "pt" => "other",
+        "ru" | "uk" | "be" | "hr" | "sr" | "bs" if integer => {
+            if i % 10 == 1 && i % 100 != 11 {
+                "one"
+            } else if (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)) {
+                "few"
+            } else {
+                "many"
+            }
+        }
+        "pl" if integer => {
+            if i == 1 {
+                "one"
+            } else if (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)) {
+                "few"
+            } else {
+                "many"
+            }
+        }
+        "cs" | "sk" if integer => match i {
+            1 => "one",
+            2..=4 => "few",
+            _ => "other",
+        },
+        _ if n == 1.0 => "one",
+        _ => "other",
+    }
+}
+
+/// `fr-CA`, `fr`, then `default_locale`
+fn fallbacks(locale: &str, default_locale: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut tag = locale;
+    loop {
+        chain.push(tag.to_string());
+        match tag.rfind(['-', '_']) {
+            Some(end) => tag = &tag[..end],
+            None => break,
+        }
+    }
+    if !chain.iter().any(|l| l == default_locale) {
+        chain.push(default_locale.to_string());
+    }
+    chain
+}
+
+/// `rtl` for languages written right to left, otherwise `ltr`
+fn text_direction(locale: &str) -> &'static str {
+    match language(locale).as_str() {
+        "ar" | "he" | "fa" | "ur" | "ps" | "sd" | "yi" | "dv" | "ug" => "rtl",
+        _ => "ltr",
+    }
+}
+
+// =============================================================================
+// Locale
+// =============================================================================
+
+/// The active locale, provided as context by [`provide_i18n`].
+///
+/// Reading it with [`get`](Locale::get) inside an effect or a reactive view
+/// closure re-runs that code when the locale changes.
+///
+/// # Example
+/// ```rust
+/// let locale = use_locale().expect("provide_i18n was called");
+/// view! {
+///     <html lang=move || locale.get() dir=move || locale.dir()>...</html>
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Locale(Signal<String>);
+
+impl Locale {
+    /// Create with `locale` active.
+    pub fn new(locale: impl Into<String>) -> Self {
+        Locale(Signal::new(locale.into()))
+    }
+
+    /// The locale tag, e.g. `fr-CA` (tracked).
+    pub fn get(&self) -> String {
+        self.0.get()
+    }
+
+    /// The locale tag without tracking.
+    pub fn get_untracked(&self) -> String {
+        self.0.get_untracked()
+    }
+
+    /// Switch the locale.
+    pub fn set(&self, locale: impl Into<String>) {
+        self.0.set(locale.into());
+    }
+
+    /// The language subtag, e.g. `fr` for `fr-CA` (tracked).
+    pub fn language(&self) -> String {
+        language(&self.0.get())
+    }
+
+    /// `rtl` or `ltr`, for the `dir` attribute (tracked).
+    pub fn dir(&self) -> &'static str {
+        text_direction(&self.0.get())
+    }
+
+    /// Re-run code that read the locale without changing it
+    fn notify(&self) {
+        self.0.update(|_| {});
+    }
+}
+
+impl fmt::Debug for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Locale").field(&self.0.get_untracked()).finish()
+    }
+}
+
+// =============================================================================
+// I18n
+// =============================================================================
+
+/// Catalogs for every locale and the active locale.
+///
+/// Cloning is cheap and clones share state, so a clone can switch the locale
+/// from an event handler.
+#[derive(Clone)]
+pub struct I18n {
+    locale: Locale,
+    translations: Rc<RefCell<Translations>>,
+}
+
+impl I18n {
+    /// Create with `default_locale` active and no catalogs.
+    pub fn new(default_locale: impl Into<String>) -> Self {
+        let default_locale = default_locale.into();
+        I18n {
+            locale: Locale::new(default_locale.clone()),
+            translations: Rc::new(RefCell::new(Translations::new(default_locale))),
+        }
+    }
+
+    /// Use catalogs loaded elsewhere, with `locale` active.
+    ///
+    /// During SSR, pass the [`RequestLocale`]'s catalogs and locale so the
+    /// render matches what the client hydrates with.
+    pub fn from_translations(translations: Translations, locale: impl Into<String>) -> Self {
+        I18n {
+            locale: Locale::new(locale),
+            translations: Rc::new(RefCell::new(translations)),
+        }
+    }
+
+    /// Add a catalog for `locale`.
+    pub fn with_catalog(self, locale: impl Into<String>, catalog: Catalog) -> Self {
+        self.add_catalog(locale, catalog);
+        self
+    }
+
+    /// Add a Fluent catalog for `locale`.
+    pub fn with_fluent(self, locale: impl Into<String>, source: &str) -> Result<Self, I18nError> {
+        Ok(self.with_catalog(locale, Catalog::parse_fluent(source)?))
+    }
+
+    /// Add a gettext catalog for `locale`.
+    pub fn with_gettext(self, locale: impl Into<String>, source: &str) -> Result<Self, I18nError> {
+        Ok(self.with_catalog(locale, Catalog::parse_gettext(source)?))
+    }
+
+    /// Add messages for `locale`, e.g. from a catalog fetched on demand.
+    ///
+    /// Messages already present for the locale are replaced.
+    pub fn add_catalog(&self, locale: impl Into<String>, catalog: Catalog) {
+        self.translations.borrow_mut().add_catalog(locale, catalog);
+        // Re-run translations that may have fallen back before
+        self.locale.notify();
+    }
+
+    /// The active locale (tracked).
+    pub fn locale(&self) -> String {
+        self.locale.get()
+    }
+
+    /// The active locale as a signal, shared by all clones.
+    pub fn locale_signal(&self) -> Locale {
+        self.locale.clone()
+    }
+
+    /// Switch the active locale.
+    pub fn set_locale(&self, locale: impl Into<String>) {
+        self.locale.set(locale);
+    }
+
+    /// The locale used when a message is missing.
+    pub fn default_locale(&self) -> String {
+        self.translations.borrow().default_locale().to_string()
+    }
+
+    /// Locales with a catalog, sorted.
+    pub fn locales(&self) -> Vec<String> {
+        self.translations.borrow().locales()
+    }
+
+    /// A snapshot of the catalogs, e.g. to share with other threads.
+    pub fn translations(&self) -> Translations {
+        self.translations.borrow().clone()
+    }
+
+    /// Translate `key` in the active locale.
+    pub fn translate(&self, key: &str, args: &[(&str, Arg)]) -> String {
+        let locale = self.locale.get();
+        self.translations.borrow().translate(&locale, key, args)
+    }
+
+    /// Whether `key` has a translation in the active locale or a fallback.
+    pub fn has(&self, key: &str) -> bool {
+        self.translations.borrow().has(&self.locale.get_untracked(), key)
+    }
+
+    /// Format a number for the active locale.
+    pub fn number(&self, value: f64, options: &NumberOptions) -> String {
+        format_number(&self.locale.get(), value, options)
+    }
+
+    /// Format a date for the active locale.
+    pub fn date(&self, date: Date, style: DateStyle) -> String {
+        format_date(&self.locale.get(), date, style)
+    }
+
+    /// The best locale with a catalog for an `Accept-Language` header,
+    /// falling back to the default locale.
+    ///
+    /// # Example
+    /// ```rust
+    /// let locale = i18n.negotiate(headers.get("accept-language").unwrap_or(""));
+    /// i18n.set_locale(locale);
+    /// ```
+    pub fn negotiate(&self, accept_language: &str) -> String {
+        self.translations.borrow().negotiate(accept_language)
+    }
+}
+
+/// Make `i18n` available to [`use_i18n`], [`use_locale`] and [`t!`](crate::t).
+pub fn provide_i18n(i18n: I18n) {
+    provide_context(i18n.locale_signal());
+    provide_context(i18n);
+}
+
+/// The provided [`I18n`], if any.
+pub fn use_i18n() -> Option<I18n> {
+    use_context::<I18n>()
+}
+
+/// The active [`Locale`] of the provided [`I18n`], if any.
+pub fn use_locale() -> Option<Locale> {
+    use_context::<Locale>()
+}
+
+/// The locale [`t!`](crate::t) translates into: the provided [`I18n`]'s
+/// (tracked), else the provided [`RequestLocale`]'s.
+pub fn current_locale() -> Option<String> {
+    use_i18n()
+        .map(|i18n| i18n.locale())
+        .or_else(|| use_context::<RequestLocale>().map(|request| request.locale().to_string()))
+}
+
+/// Translate `key` with the provided [`I18n`], else the provided
+/// [`RequestLocale`]; the key itself when neither is provided. Used by
+/// [`t!`](crate::t).
+pub fn translate(key: &str, args: &[(&str, Arg)]) -> String {
+    if let Some(i18n) = use_i18n() {
+        return i18n.translate(key, args);
+    }
+    match use_context::<RequestLocale>() {
+        Some(request) => request.translate(key, args),
+        None => key.to_string(),
+    }
+}
+
+/// The `lang` attribute of the page's `<html>` element, which SSR sets to
+/// the request's locale; use it to start the client in the same locale.
+///
+/// # Example
+/// ```rust
+/// let i18n = I18n::new("en").with_fluent("en", EN)?.with_fluent("fr", FR)?;
+/// if let Some(locale) = document_locale() {
+///     i18n.set_locale(locale);
+/// }
+/// ```
+#[cfg(feature = "wasm")]
+pub fn document_locale() -> Option<String> {
+    web_sys::window()?
+        .document()?
+        .document_element()?
+        .get_attribute("lang")
+        .filter(|lang| !lang.is_empty())
+}
+
+/// Translate a key in the active locale.
+///
+/// The key must be a string literal so `cargo philjs i18n extract` can find
+/// it. Named arguments are interpolated and numbers select plural variants.
+/// Inside `view!`, wrap it in a closure so the text follows the locale.
+///
+/// # Example
+/// ```rust
+/// let title = t!("page-title");
+/// let greeting = t!("welcome", name = user.name);
+/// let unread = t!("unread-emails", count = emails.len());
+///
+/// view! { <p>{move || t!("unread-emails", count = unread.get())}</p> }
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($key:literal $(,)?) => {
+        $crate::i18n::translate($key, &[])
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate(
+            $key,
+            &[$((stringify!($name), $crate::i18n::Arg::from($value))),+],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EN: &str = "\
+welcome = Welcome, { $name }!
+-brand = PhilJS
+about = About { -brand }
+emails = { $count ->
+    [0] No emails
+    [one] One email
+   *[other] { $count } emails
+}
+";
+
+    const FR: &str = "\
+welcome = Bienvenue, { $name } !
+emails = { $count ->
+    [one] { $count } e-mail
+   *[other] { $count } e-mails
+}
+";
+
+    fn i18n() -> I18n {
+        I18n::new("en")
+            .with_fluent("en", EN)
+            .unwrap()
+            .with_fluent("fr", FR)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_translate_with_fallback() {
+        let i18n = i18n();
+        assert_eq!(i18n.translate("welcome", &[("name", "Ada".into())]), "Welcome, Ada!");
+        assert_eq!(i18n.translate("about", &[]), "About PhilJS");
+
+        i18n.set_locale("fr-CA");
+        assert_eq!(i18n.translate("welcome", &[("name", "Ada".into())]), "Bienvenue, Ada !");
+        assert_eq!(i18n.translate("about", &[]), "About PhilJS");
+        assert_eq!(i18n.translate("missing", &[]), "missing");
+        assert!(i18n.has("about"));
+        assert!(!i18n.has("missing"));
+    }
+
+    #[test]
+    fn test_plurals() {
+        let i18n = i18n();
+        let emails = |n: usize| i18n.translate("emails", &[("count", n.into())]);
+        assert_eq!(emails(0), "No emails");
+        assert_eq!(emails(1), "One email");
+        assert_eq!(emails(5), "5 emails");
+
+        i18n.set_locale("fr");
+        assert_eq!(emails(0), "0 e-mail");
+        assert_eq!(emails(2), "2 e-mails");
+
+        assert_eq!(plural_category("ru", 21.0), "one");
+        assert_eq!(plural_category("ru", 22.0), "few");
+        assert_eq!(plural_category("ru", 11.0), "many");
+        assert_eq!(plural_category("ja", 1.0), "other");
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let i18n = i18n();
+        assert_eq!(i18n.negotiate("fr-CH, fr;q=0.9, en;q=0.8"), "fr");
+        assert_eq!(i18n.negotiate("de;q=1.0, en;q=0.5"), "en");
+        assert_eq!(i18n.negotiate("de"), "en");
+        assert_eq!(i18n.negotiate(""), "en");
+    }
+
+    #[test]
+    fn test_t_macro() {
+        assert_eq!(crate::t!("welcome"), "welcome");
+
+        provide_i18n(i18n());
+        assert_eq!(crate::t!("welcome", name = "Ada"), "Welcome, Ada!");
+        assert_eq!(crate::t!("emails", count = 3), "3 emails");
+    }
+
+    #[test]
+    fn test_locale_formatting() {
+        let i18n = i18n();
+        let emails = |n: usize| i18n.translate("emails", &[("count", n.into())]);
+        assert_eq!(emails(2500), "2,500 emails");
+
+        i18n.set_locale("fr");
+        assert_eq!(emails(2500), "2\u{202f}500 e-mails");
+        assert_eq!(i18n.number(0.5, &NumberOptions::fixed(2)), "0,50");
+        assert_eq!(i18n.date(Date::new(2026, 7, 14), DateStyle::Long), "14 juillet 2026");
+        assert_eq!(Arg::from(Date::new(2026, 7, 14)).format("en"), "7/14/2026");
+    }
+
+    #[test]
+    fn test_locale_context() {
+        assert!(use_locale().is_none());
+        assert_eq!(current_locale(), None);
+
+        let i18n = i18n();
+        provide_i18n(i18n.clone());
+        let locale = use_locale().unwrap();
+        assert_eq!(locale.get(), "en");
+
+        locale.set("ar-EG");
+        assert_eq!(i18n.locale(), "ar-EG");
+        assert_eq!(locale.language(), "ar");
+        assert_eq!(locale.dir(), "rtl");
+        assert_eq!(current_locale().as_deref(), Some("ar-EG"));
+    }
+
+    #[test]
+    fn test_request_locale_context() {
+        let translations = i18n().translations();
+        provide_context(translations.resolve(&LocaleRequest {
+            path: "/",
+            cookie: None,
+            accept_language: Some("fr-FR,fr;q=0.9"),
+        }));
+        assert_eq!(current_locale().as_deref(), Some("fr"));
+        assert_eq!(crate::t!("welcome", name = "Ada"), "Bienvenue, Ada !");
+    }
+}
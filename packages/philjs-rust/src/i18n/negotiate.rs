@@ -0,0 +1,285 @@
+//! Catalogs shared across threads and per-request locale selection
+//!
+//! Server integrations build one [`Translations`] at startup, resolve each
+//! request's locale with [`Translations::resolve`] and provide the resulting
+//! [`RequestLocale`] as context for the render. [`t!`](crate::t) uses it when
+//! no [`I18n`](super::I18n) is provided.
+//!
+//! ```rust
+//! let translations = Translations::new("en")
+//!     .with_fluent("en", include_str!("../locales/en/messages.ftl"))?
+//!     .with_fluent("fr", include_str!("../locales/fr/messages.ftl"))?;
+//!
+//! // philjs-axum
+//! let app = Router::new()
+//!     .fallback(render_app)
+//!     .layer(ContextLayer::new().with(move |req| {
+//!         Some(translations.resolve(&LocaleRequest {
+//!             path: req.uri().path(),
+//!             cookie: cookie(req, LOCALE_COOKIE),
+//!             accept_language: header(req, "accept-language"),
+//!         }))
+//!     }));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::{fallbacks, language, text_direction, Arg, Catalog, I18nError};
+
+/// Cookie remembering a visitor's chosen locale
+pub const LOCALE_COOKIE: &str = "philjs-locale";
+
+/// Catalogs for every locale; cheap to clone and `Send + Sync`.
+#[derive(Debug, Clone)]
+pub struct Translations {
+    default_locale: Arc<str>,
+    catalogs: Arc<HashMap<String, Catalog>>,
+}
+
+impl Translations {
+    /// Create with no catalogs.
+    pub fn new(default_locale: impl Into<String>) -> Self {
+        Translations {
+            default_locale: default_locale.into().into(),
+            catalogs: Arc::new(HashMap::new()),
+        }
+    }
+
+    /// Add a catalog for `locale`.
+    pub fn with_catalog(mut self, locale: impl Into<String>, catalog: Catalog) -> Self {
+        self.add_catalog(locale, catalog);
+        self
+    }
+
+    /// Add a Fluent catalog for `locale`.
+    pub fn with_fluent(self, locale: impl Into<String>, source: &str) -> Result<Self, I18nError> {
+        Ok(self.with_catalog(locale, Catalog::parse_fluent(source)?))
+    }
+
+    /// Add a gettext catalog for `locale`.
+    pub fn with_gettext(self, locale: impl Into<String>, source: &str) -> Result<Self, I18nError> {
+        Ok(self.with_catalog(locale, Catalog::parse_gettext(source)?))
+    }
+
+    /// Add messages for `locale`, replacing those with the same key.
+    pub fn add_catalog(&mut self, locale: impl Into<String>, catalog: Catalog) {
+        Arc::make_mut(&mut self.catalogs)
+            .entry(locale.into())
+            .or_default()
+            .extend(catalog);
+    }
+
+    /// The locale used when a message is missing.
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    /// Locales with a catalog, sorted.
+    pub fn locales(&self) -> Vec<String> {
+        let mut locales: Vec<String> = self.catalogs.keys().cloned().collect();
+        locales.sort();
+        locales
+    }
+
+    /// Format `key` in `locale` or a fallback, `None` when no catalog has it.
+    pub fn format(&self, locale: &str, key: &str, args: &[(&str, Arg)]) -> Option<String> {
+        fallbacks(locale, &self.default_locale).into_iter().find_map(|candidate| {
+            self.catalogs
+                .get(&candidate)
+                .and_then(|catalog| catalog.format(key, args, &candidate))
+        })
+    }
+
+    /// Translate `key` in `locale`; the key itself when nothing has it.
+    pub fn translate(&self, locale: &str, key: &str, args: &[(&str, Arg)]) -> String {
+        self.format(locale, key, args).unwrap_or_else(|| key.to_string())
+    }
+
+    /// Whether `key` has a translation in `locale` or a fallback.
+    pub fn has(&self, locale: &str, key: &str) -> bool {
+        fallbacks(locale, &self.default_locale)
+            .iter()
+            .any(|l| self.catalogs.get(l).is_some_and(|c| c.contains(key)))
+    }
+
+    /// The best locale with a catalog for an `Accept-Language` header.
+    pub fn negotiate(&self, accept_language: &str) -> String {
+        negotiate_locale(accept_language, &self.locales(), &self.default_locale)
+    }
+
+    /// Pick the locale for a request: a locale prefix in the path, then
+    /// the [`LOCALE_COOKIE`], then `Accept-Language`, then the default.
+    pub fn resolve(&self, request: &LocaleRequest<'_>) -> RequestLocale {
+        let locales = self.locales();
+        let locale = strip_locale_prefix(request.path, &locales)
+            .0
+            .map(str::to_string)
+            .or_else(|| {
+                let cookie = request.cookie?.trim();
+                locales.iter().find(|l| l.eq_ignore_ascii_case(cookie)).cloned()
+            })
+            .unwrap_or_else(|| {
+                negotiate_locale(request.accept_language.unwrap_or_default(), &locales, &self.default_locale)
+            });
+        RequestLocale {
+            locale,
+            translations: self.clone(),
+        }
+    }
+
+    /// Use `locale` for this thread's renders without a request, e.g. when
+    /// prerendering one page per locale.
+    pub fn for_locale(&self, locale: impl Into<String>) -> RequestLocale {
+        RequestLocale {
+            locale: locale.into(),
+            translations: self.clone(),
+        }
+    }
+}
+
+/// What a request says about the visitor's locale.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocaleRequest<'a> {
+    /// Request path; a first segment naming a locale, as in `/fr/about`, wins
+    pub path: &'a str,
+    /// Value of the [`LOCALE_COOKIE`] cookie
+    pub cookie: Option<&'a str>,
+    /// The `Accept-Language` header
+    pub accept_language: Option<&'a str>,
+}
+
+/// The locale chosen for one request, with the catalogs to translate in it.
+///
+/// `Send + Sync`, so integrations can provide it as request context.
+#[derive(Debug, Clone)]
+pub struct RequestLocale {
+    locale: String,
+    translations: Translations,
+}
+
+impl RequestLocale {
+    /// The chosen locale, for `<html lang=...>`.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// `rtl` for right-to-left languages, otherwise `ltr`, for `<html dir=...>`.
+    pub fn dir(&self) -> &'static str {
+        text_direction(&self.locale)
+    }
+
+    /// The catalogs shared by every request.
+    pub fn translations(&self) -> &Translations {
+        &self.translations
+    }
+
+    /// Translate `key` in the chosen locale.
+    pub fn translate(&self, key: &str, args: &[(&str, Arg)]) -> String {
+        self.translations.translate(&self.locale, key, args)
+    }
+}
+
+/// The best of `available` for an `Accept-Language` header, or `default`.
+///
+/// Exact matches win; otherwise a locale of the same language is used, so
+/// `fr-CH` gets `fr` and `pt` gets `pt-BR`.
+pub fn negotiate_locale<S: AsRef<str>>(accept_language: &str, available: &[S], default: &str) -> String {
+    let mut requested: Vec<(&str, f32)> = accept_language
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .and_then(|q| q.parse().ok())
+                .unwrap_or(1.0);
+            (!tag.is_empty() && tag != "*" && quality > 0.0).then_some((tag, quality))
+        })
+        .collect();
+    requested.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    for (tag, _) in requested {
+        if let Some(exact) = available.iter().find(|l| l.as_ref().eq_ignore_ascii_case(tag)) {
+            return exact.as_ref().to_string();
+        }
+        let lang = language(tag);
+        if let Some(same_language) = available.iter().find(|l| language(l.as_ref()) == lang) {
+            return same_language.as_ref().to_string();
+        }
+    }
+    default.to_string()
+}
+
+/// Split a locale prefix off `path`: `/fr/about` gives `(Some("fr"), "/about")`
+/// when `fr` is available, otherwise `(None, path)`.
+pub fn strip_locale_prefix<'a, S: AsRef<str>>(path: &'a str, available: &'a [S]) -> (Option<&'a str>, &'a str) {
+    let trimmed = path.strip_prefix('/').unwrap_or(path);
+    let (first, rest) = match trimmed.find('/') {
+        Some(end) => (&trimmed[..end], &trimmed[end..]),
+        None => (trimmed, "/"),
+    };
+    match available.iter().find(|l| l.as_ref().eq_ignore_ascii_case(first)) {
+        Some(locale) => (Some(locale.as_ref()), rest),
+        None => (None, path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translations() -> Translations {
+        Translations::new("en")
+            .with_fluent("en", "hello = Hello")
+            .unwrap()
+            .with_fluent("fr", "hello = Bonjour")
+            .unwrap()
+            .with_fluent("pt-BR", "hello = Olá")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let available = ["en", "fr", "pt-BR"];
+        assert_eq!(negotiate_locale("fr-CH, fr;q=0.9, en;q=0.8", &available, "en"), "fr");
+        assert_eq!(negotiate_locale("de;q=1.0, en;q=0.5", &available, "en"), "en");
+        assert_eq!(negotiate_locale("pt", &available, "en"), "pt-BR");
+        assert_eq!(negotiate_locale("de, *;q=0.1", &available, "en"), "en");
+        assert_eq!(negotiate_locale("", &available, "en"), "en");
+    }
+
+    #[test]
+    fn test_resolve_request() {
+        let translations = translations();
+        let resolve = |path, cookie, accept_language| {
+            translations
+                .resolve(&LocaleRequest { path, cookie, accept_language })
+                .locale()
+                .to_string()
+        };
+        assert_eq!(resolve("/fr/about", Some("pt-BR"), Some("en")), "fr");
+        assert_eq!(resolve("/about", Some("pt-br"), Some("fr")), "pt-BR");
+        assert_eq!(resolve("/about", Some("de"), Some("fr-CA")), "fr");
+        assert_eq!(resolve("/", None, None), "en");
+
+        let request = translations.for_locale("fr-CA");
+        assert_eq!(request.translate("hello", &[]), "Bonjour");
+        assert_eq!(request.dir(), "ltr");
+    }
+
+    #[test]
+    fn test_strip_locale_prefix() {
+        let available = ["en", "fr"];
+        assert_eq!(strip_locale_prefix("/fr/blog/1", &available), (Some("fr"), "/blog/1"));
+        assert_eq!(strip_locale_prefix("/fr", &available), (Some("fr"), "/"));
+        assert_eq!(strip_locale_prefix("/french", &available), (None, "/french"));
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Translations>();
+        assert_send_sync::<RequestLocale>();
+    }
+}
@@ -13,6 +13,9 @@
 //! - **Type-safe Routing**: File-based routing with compile-time safety
 //! - **Server Functions**: RPC-style server/client communication
 //! - **Data Fetching**: TanStack Query-style caching and mutations
+//! - **Internationalization**: Fluent and gettext catalogs, reactive locale switching and SSR locale negotiation
+//! - **Forms**: Field signals from serde structs with sync and async validation
+//! - **Accessibility**: Focus traps, roving tab index, live region announcements and ARIA id helpers
 //!
 //! ## Quick Start
 //!
@@ -108,6 +111,9 @@
 #![warn(missing_docs)]
 #![allow(clippy::type_complexity)]
 
+// Lets `live!` expansions name `::philjs` from inside this crate
+extern crate self as philjs;
+
 pub mod reactive;
 pub mod view;
 pub mod dom;
@@ -119,12 +125,15 @@ pub mod query;
 pub mod liveview;
 pub mod meta;
 pub mod store;
+pub mod i18n;
+pub mod forms;
+pub mod a11y;
 
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
 // Re-export macros
-pub use philjs_macros::{component, effect, memo, resource, signal, view, Store};
+pub use philjs_macros::{component, effect, live, memo, resource, signal, view, Store};
 
 // Re-export core types
 pub use reactive::{
@@ -154,6 +163,7 @@ pub use dom::{
 
 pub use ssr::{
     render_to_string,
+    render_to_string_with_meta,
     render_to_stream,
     render_to_stream_async,
     StreamingConfig,
@@ -206,6 +216,13 @@ pub mod prelude {
 
     pub use crate::ssr::{render_to_string, render_to_stream};
 
+    pub use crate::i18n::{I18n, Locale, provide_i18n, use_i18n, use_locale};
+    pub use crate::t;
+
+    pub use crate::forms::{use_form, use_form_with, FormState, FormField, Field, FieldProps};
+
+    pub use crate::a11y::{FocusTrap, FocusTrapProps, RovingTabIndex, RovingTabIndexProps, use_announce, use_id};
+
     pub use philjs_macros::{component, effect, memo, resource, signal, view, Store};
 }
 
@@ -225,13 +242,28 @@ pub fn spread_attrs<T>(_attrs: T) -> Vec<(&'static str, Box<dyn Fn() -> String>)
 
 // Meta/Head management (leptos_meta equivalent)
 pub use meta::{
-    Title, TitleTemplate, Meta, Link, Style, Script, Html, Body,
-    MetaContext, use_meta_context, with_meta_context,
+    Title, TitleTemplate, Meta, Link, Style, Script, Html, Body, Seo, JsonLd, Stylesheet,
+    MetaContext, MetaValue, use_meta_context, with_meta_context, with_meta_context_async,
 };
 
 // Store for deep reactive updates
 pub use store::{Store, StoreField, StoreVec, StoreMap, create_store, produce};
 
+// Internationalization
+pub use i18n::{
+    I18n, Locale, Catalog, I18nError, Translations, RequestLocale, LocaleRequest,
+    provide_i18n, use_i18n, use_locale,
+};
+
+// Form state management
+pub use forms::{use_form, use_form_with, FormState, FormField, FieldError, Field, FieldProps};
+
+// Accessibility primitives
+pub use a11y::{
+    FocusTrap, FocusTrapProps, RovingTabIndex, RovingTabIndexProps, Orientation,
+    Announcer, Politeness, AriaIds, use_announce, use_aria_ids, use_id,
+};
+
 // Action exports
 pub use reactive::{
     Action, MultiAction, ActionError,
@@ -245,8 +277,9 @@ pub use reactive::{
 // Transition and Animation
 pub use view::{
     Transition, TransitionConfig, use_transition, use_deferred_value,
-    AnimatedShow, AnimatedShowConfig, AnimationState,
+    AnimatedShow, AnimatedShowConfig, AnimationState, Keyframes,
     fade, slide, scale,
+    For, Reorder, FlipAnimation, FlipConfig, use_flip,
 };
 
 // Router Form components
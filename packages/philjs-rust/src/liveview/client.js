@@ -0,0 +1,373 @@
+// PhilJS LiveView client runtime
+//
+// Generated by `philjs::liveview::client_js`. Speaks the `WsMessage` protocol:
+// joins the view over a WebSocket, forwards `live:*` / `phx-*` events, applies
+// `DomPatch` diffs, sends heartbeats, and reconnects with exponential backoff.
+(function (global) {
+  'use strict';
+
+  var config = __PHILJS_LIVE_CONFIG__;
+  var socket = null;
+  var heartbeatTimer = null;
+  var reconnectTimer = null;
+  var attempts = 0;
+  var closedByUser = false;
+
+  // ---------------------------------------------------------------------------
+  // Connection management
+  // ---------------------------------------------------------------------------
+
+  function socketUrl() {
+    var protocol = global.location.protocol === 'https:' ? 'wss:' : 'ws:';
+    return protocol + '//' + global.location.host + config.path;
+  }
+
+  function send(message) {
+    if (socket && socket.readyState === WebSocket.OPEN) {
+      socket.send(JSON.stringify(message));
+      return true;
+    }
+    return false;
+  }
+
+  function queryParams() {
+    var params = {};
+    new URLSearchParams(global.location.search).forEach(function (value, key) {
+      params[key] = value;
+    });
+    return params;
+  }
+
+  function root() {
+    return document.querySelector(config.root) || document.body;
+  }
+
+  function connect() {
+    closedByUser = false;
+    socket = new WebSocket(socketUrl());
+
+    socket.onopen = function () {
+      attempts = 0;
+      send({
+        type: 'Join',
+        topic: config.topic,
+        payload: {
+          url: global.location.href,
+          params: queryParams(),
+          session: root().getAttribute('data-live-session') || ''
+        }
+      });
+      startHeartbeat();
+    };
+
+    socket.onmessage = function (event) {
+      var message;
+      try {
+        message = JSON.parse(event.data);
+      } catch (err) {
+        console.error('[philjs-live] invalid message', err);
+        return;
+      }
+      handleMessage(message);
+    };
+
+    socket.onclose = function () {
+      stopHeartbeat();
+      if (!closedByUser) {
+        scheduleReconnect();
+      }
+    };
+
+    socket.onerror = function () {
+      socket.close();
+    };
+  }
+
+  function disconnect() {
+    closedByUser = true;
+    stopHeartbeat();
+    clearTimeout(reconnectTimer);
+    if (socket) {
+      send({ type: 'Leave', topic: config.topic });
+      socket.close();
+    }
+  }
+
+  function startHeartbeat() {
+    stopHeartbeat();
+    heartbeatTimer = setInterval(function () {
+      send({ type: 'Heartbeat' });
+    }, config.heartbeatIntervalMs);
+  }
+
+  function stopHeartbeat() {
+    clearInterval(heartbeatTimer);
+    heartbeatTimer = null;
+  }
+
+  function scheduleReconnect() {
+    var delay = Math.min(config.reconnectMaxMs, config.reconnectBaseMs * Math.pow(2, attempts));
+    // Full jitter so a server restart doesn't get a thundering herd.
+    delay = Math.floor(Math.random() * delay);
+    attempts += 1;
+    clearTimeout(reconnectTimer);
+    reconnectTimer = setTimeout(connect, delay);
+  }
+
+  // ---------------------------------------------------------------------------
+  // Incoming messages
+  // ---------------------------------------------------------------------------
+
+  function handleMessage(message) {
+    switch (message.type) {
+      case 'Diff':
+        applyDiff(message.diff);
+        break;
+      case 'Reply':
+        break;
+      default:
+        break;
+    }
+  }
+
+  function applyDiff(diff) {
+    (diff.patches || []).forEach(applyPatch);
+    if (diff.title) {
+      document.title = diff.title;
+    }
+    (diff.events || []).forEach(function (push) {
+      global.dispatchEvent(new CustomEvent('phx:' + push.event, { detail: push.payload }));
+    });
+    if (diff.redirect) {
+      global.location.assign(diff.redirect);
+    }
+  }
+
+  function findTarget(target) {
+    if (target === 'body') {
+      return root();
+    }
+    var selector = '[data-live-id="' + String(target).replace(/"/g, '\\"') + '"]';
+    var el = document.querySelector(selector) || document.getElementById(target);
+    if (el) {
+      return el;
+    }
+    try {
+      return document.querySelector(target);
+    } catch (err) {
+      return null;
+    }
+  }
+
+  function applyPatch(patch) {
+    var kind = Object.keys(patch)[0];
+    var op = patch[kind];
+    var el = findTarget(op.target);
+    if (!el) {
+      return;
+    }
+
+    switch (kind) {
+      case 'Morph':
+        morphChildren(el, fragmentFrom(op.html));
+        break;
+      case 'Append':
+        el.insertAdjacentHTML('beforeend', op.html);
+        break;
+      case 'Prepend':
+        el.insertAdjacentHTML('afterbegin', op.html);
+        break;
+      case 'Replace':
+        el.outerHTML = op.html;
+        break;
+      case 'Remove':
+        el.remove();
+        break;
+      case 'UpdateAttr':
+        el.setAttribute(op.attr, op.value);
+        break;
+      case 'RemoveAttr':
+        el.removeAttribute(op.attr);
+        break;
+      default:
+        console.warn('[philjs-live] unknown patch', kind);
+    }
+  }
+
+  function fragmentFrom(html) {
+    var template = document.createElement('template');
+    template.innerHTML = html;
+    return template.content;
+  }
+
+  // Minimal DOM morph: reuses existing nodes so focus, selection and scroll
+  // position survive re-renders.
+  function morphChildren(from, to) {
+    var oldNodes = Array.prototype.slice.call(from.childNodes);
+    var newNodes = Array.prototype.slice.call(to.childNodes);
+
+    newNodes.forEach(function (newNode, i) {
+      var oldNode = oldNodes[i];
+      if (!oldNode) {
+        from.appendChild(newNode);
+      } else if (oldNode.nodeType !== newNode.nodeType || oldNode.nodeName !== newNode.nodeName) {
+        from.replaceChild(newNode, oldNode);
+      } else if (oldNode.nodeType === Node.TEXT_NODE) {
+        if (oldNode.nodeValue !== newNode.nodeValue) {
+          oldNode.nodeValue = newNode.nodeValue;
+        }
+      } else if (oldNode.nodeType === Node.ELEMENT_NODE) {
+        morphAttributes(oldNode, newNode);
+        if (oldNode !== document.activeElement || !('value' in oldNode)) {
+          morphChildren(oldNode, newNode);
+        }
+      }
+    });
+
+    for (var j = newNodes.length; j < oldNodes.length; j++) {
+      from.removeChild(oldNodes[j]);
+    }
+  }
+
+  function morphAttributes(from, to) {
+    Array.prototype.slice.call(from.attributes).forEach(function (attr) {
+      if (!to.hasAttribute(attr.name)) {
+        from.removeAttribute(attr.name);
+      }
+    });
+    Array.prototype.slice.call(to.attributes).forEach(function (attr) {
+      if (from.getAttribute(attr.name) !== attr.value) {
+        from.setAttribute(attr.name, attr.value);
+      }
+    });
+    if ('value' in from && from !== document.activeElement && from.value !== to.value) {
+      from.value = to.value;
+    }
+  }
+
+  // ---------------------------------------------------------------------------
+  // Event capture
+  // ---------------------------------------------------------------------------
+
+  function binding(el, name) {
+    return el.getAttribute('live:' + name) || el.getAttribute('phx-' + name);
+  }
+
+  function closestBinding(start, name) {
+    var el = start;
+    while (el && el.nodeType === Node.ELEMENT_NODE) {
+      if (binding(el, name)) {
+        return el;
+      }
+      el = el.parentElement;
+    }
+    return null;
+  }
+
+  function targetId(el) {
+    return el.getAttribute('data-live-id') || el.id || null;
+  }
+
+  function phxValues(el) {
+    var values = {};
+    Array.prototype.slice.call(el.attributes).forEach(function (attr) {
+      if (attr.name.indexOf('phx-value-') === 0) {
+        values[attr.name.slice('phx-value-'.length)] = attr.value;
+      }
+    });
+    return values;
+  }
+
+  function formValues(form) {
+    var values = {};
+    new FormData(form).forEach(function (value, key) {
+      values[key] = typeof value === 'string' ? value : value.name;
+    });
+    return values;
+  }
+
+  function pushEvent(eventType, target, value, key) {
+    return send({
+      type: 'Event',
+      topic: config.topic,
+      event: {
+        event_type: eventType,
+        target: target,
+        value: value === undefined ? null : value,
+        key: key || null
+      }
+    });
+  }
+
+  document.addEventListener('click', function (e) {
+    var dismiss = e.target.closest && e.target.closest('[data-live-flash-dismiss]');
+    if (dismiss) {
+      var flash = dismiss.parentElement;
+      if (flash) {
+        flash.remove();
+      }
+      return;
+    }
+
+    var el = closestBinding(e.target, 'click');
+    if (!el) {
+      return;
+    }
+    e.preventDefault();
+    pushEvent(binding(el, 'click'), targetId(el), phxValues(el));
+  });
+
+  function onChange(e) {
+    var el = closestBinding(e.target, 'change');
+    if (!el) {
+      return;
+    }
+    var value = el.tagName === 'FORM' ? formValues(el) : { value: e.target.value };
+    value._target = e.target.name || null;
+    pushEvent(binding(el, 'change'), targetId(el), value);
+  }
+
+  document.addEventListener('input', onChange);
+  document.addEventListener('change', onChange);
+
+  document.addEventListener('submit', function (e) {
+    var el = closestBinding(e.target, 'submit');
+    if (!el) {
+      return;
+    }
+    e.preventDefault();
+    pushEvent(binding(el, 'submit'), targetId(el), formValues(el));
+  });
+
+  ['keydown', 'keyup'].forEach(function (name) {
+    document.addEventListener(name, function (e) {
+      var el = closestBinding(e.target, name);
+      if (el) {
+        pushEvent(binding(el, name), targetId(el), { value: e.target.value }, e.key);
+      }
+    });
+  });
+
+  ['focus', 'blur'].forEach(function (name) {
+    document.addEventListener(name, function (e) {
+      var el = e.target && e.target.nodeType === Node.ELEMENT_NODE ? e.target : null;
+      if (el && binding(el, name)) {
+        pushEvent(binding(el, name), targetId(el), { value: el.value });
+      }
+    }, true);
+  });
+
+  global.PhilJSLive = {
+    connect: connect,
+    disconnect: disconnect,
+    pushEvent: pushEvent
+  };
+
+  if (config.autoConnect) {
+    if (document.readyState === 'loading') {
+      document.addEventListener('DOMContentLoaded', connect);
+    } else {
+      connect();
+    }
+  }
+})(window);
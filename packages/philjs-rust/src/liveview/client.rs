@@ -0,0 +1,128 @@
+//! LiveView JavaScript client
+//!
+//! Generates the browser runtime that connects to a LiveView over WebSocket,
+//! forwards `live:*` / `phx-*` events, applies [`DomPatch`](super::DomPatch)
+//! diffs, sends heartbeats, and reconnects with exponential backoff.
+//!
+//! # Example
+//! ```rust
+//! use philjs::liveview::*;
+//!
+//! let page = "<main data-live-root></main>";
+//! let config = LiveClientConfig::new("/live").heartbeat_interval_ms(15_000);
+//! let html = format!("<body>{}{}</body>", page, client_script_tag(&config));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Unconfigured client runtime source; use [`client_js`] to fill in the config
+pub const CLIENT_JS_TEMPLATE: &str = include_str!("client.js");
+
+const CONFIG_PLACEHOLDER: &str = "__PHILJS_LIVE_CONFIG__";
+
+/// Configuration baked into the generated client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientConfig {
+    /// WebSocket path on the current host
+    pub path: String,
+    /// Topic sent with `Join` and every event (defaults to the page path)
+    pub topic: Option<String>,
+    /// CSS selector of the element `body`-targeted patches apply to
+    pub root: String,
+    /// Interval between heartbeats
+    pub heartbeat_interval_ms: u64,
+    /// Initial reconnect delay; doubled on every failed attempt
+    pub reconnect_base_ms: u64,
+    /// Upper bound for the reconnect delay
+    pub reconnect_max_ms: u64,
+    /// Connect as soon as the DOM is ready
+    pub auto_connect: bool,
+}
+
+impl Default for LiveClientConfig {
+    fn default() -> Self {
+        Self {
+            path: "/live".to_string(),
+            topic: None,
+            root: "[data-live-root]".to_string(),
+            heartbeat_interval_ms: 30_000,
+            reconnect_base_ms: 500,
+            reconnect_max_ms: 30_000,
+            auto_connect: true,
+        }
+    }
+}
+
+impl LiveClientConfig {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn topic(mut self, topic: impl Into<String>) -> Self {
+        self.topic = Some(topic.into());
+        self
+    }
+
+    pub fn root(mut self, selector: impl Into<String>) -> Self {
+        self.root = selector.into();
+        self
+    }
+
+    pub fn heartbeat_interval_ms(mut self, ms: u64) -> Self {
+        self.heartbeat_interval_ms = ms;
+        self
+    }
+
+    pub fn reconnect_backoff_ms(mut self, base: u64, max: u64) -> Self {
+        self.reconnect_base_ms = base;
+        self.reconnect_max_ms = max;
+        self
+    }
+
+    pub fn auto_connect(mut self, auto_connect: bool) -> Self {
+        self.auto_connect = auto_connect;
+        self
+    }
+}
+
+/// Generate the client runtime JavaScript for the given config
+pub fn client_js(config: &LiveClientConfig) -> String {
+    let json = serde_json::to_string(config)
+        .unwrap_or_else(|_| "{}".to_string())
+        .replace("</", "<\\/");
+    // A missing topic is resolved in the browser so one bundle serves every page
+    let config_js = format!(
+        "(function (c) {{ c.topic = c.topic || window.location.pathname; return c; }})({})",
+        json
+    );
+    CLIENT_JS_TEMPLATE.replace(CONFIG_PLACEHOLDER, &config_js)
+}
+
+/// Generate an inline `<script>` tag containing the client runtime
+pub fn client_script_tag(config: &LiveClientConfig) -> String {
+    format!("<script>{}</script>", client_js(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_js_injects_config() {
+        let js = client_js(&LiveClientConfig::new("/socket").heartbeat_interval_ms(1_000));
+        assert!(!js.contains(CONFIG_PLACEHOLDER));
+        assert!(js.contains("\"path\":\"/socket\""));
+        assert!(js.contains("\"heartbeatIntervalMs\":1000"));
+    }
+
+    #[test]
+    fn test_client_script_tag_escapes_closing_tags() {
+        let config = LiveClientConfig::new("/live").root("</script><script>");
+        let tag = client_script_tag(&config);
+        assert_eq!(tag.matches("</script>").count(), 1);
+    }
+}
@@ -0,0 +1,477 @@
+//! Transport-agnostic LiveView endpoint
+//!
+//! [`LiveViewHub`] owns the join handshake, event dispatch, `handle_info`
+//! re-renders, and heartbeat checks shared by every web integration. An
+//! integration only moves text frames between its WebSocket type and a
+//! [`LiveConnection`], and acts on the returned [`Reply`].
+//!
+//! # Example
+//! ```rust,ignore
+//! use philjs::liveview::*;
+//!
+//! let hub = LiveViewHub::new().route("/counter", || Box::new(Counter::default()));
+//!
+//! let (tx, mut info_rx) = tokio::sync::mpsc::unbounded_channel();
+//! let mut conn = hub.connect(socket_id, move |info| {
+//!     let _ = tx.send(info);
+//! });
+//! loop {
+//!     let reply = tokio::select! {
+//!         Some(text) = next_text_frame() => conn.handle_text(&text),
+//!         Some(info) = info_rx.recv() => conn.handle_info(info),
+//!     };
+//!     match reply {
+//!         Reply::None => {}
+//!         Reply::Send(frame) | Reply::Joined { frame, .. } => send(frame).await,
+//!         Reply::Close(frame) => {
+//!             if let Some(frame) = frame {
+//!                 send(frame).await;
+//!             }
+//!             break;
+//!         }
+//!     }
+//! }
+//! // Dropping `conn` terminates the view and unsubscribes it
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use super::{
+    dispatch_ws_message, render_diff, JoinPayload, LiveView, LiveViewRegistry, PubSub, WsMessage,
+    DEFAULT_IDLE_TIMEOUT,
+};
+
+/// Factory creating a fresh view for each connection
+pub type LiveViewFactory = Arc<dyn Fn() -> Box<dyn LiveView> + Send + Sync>;
+
+/// Shortest heartbeat timeout a hub accepts
+pub const MIN_HEARTBEAT_TIMEOUT: Duration = Duration::from_millis(2);
+
+/// Hands published `handle_info` payloads to a connection's transport
+type InfoSink = Box<dyn Fn(serde_json::Value) + Send + Sync>;
+
+/// What the transport should do after a [`LiveConnection`] handled a frame
+#[derive(Debug)]
+pub enum Reply {
+    /// Nothing to send
+    None,
+    /// Send a text frame
+    Send(String),
+    /// The client joined `topic`; send the initial render in `frame`
+    Joined {
+        /// Topic the view was mounted for
+        topic: String,
+        /// Join payload sent by the client
+        payload: JoinPayload,
+        /// Serialized initial diff
+        frame: String,
+    },
+    /// Send the frame, if any, then close the socket
+    Close(Option<String>),
+}
+
+/// Routes LiveView connections to views and delivers server-side events.
+///
+/// Cloning is cheap and shares all state.
+#[derive(Clone)]
+pub struct LiveViewHub {
+    registry: Arc<LiveViewRegistry>,
+    pubsub: Arc<PubSub>,
+    factories: Arc<RwLock<HashMap<String, LiveViewFactory>>>,
+    connections: Arc<RwLock<HashMap<String, InfoSink>>>,
+    heartbeat_timeout: Duration,
+}
+
+impl LiveViewHub {
+    /// Create a hub with its own registry and pub/sub
+    pub fn new() -> Self {
+        Self {
+            registry: Arc::new(LiveViewRegistry::new()),
+            pubsub: Arc::new(PubSub::new()),
+            factories: Arc::new(RwLock::new(HashMap::new())),
+            connections: Arc::new(RwLock::new(HashMap::new())),
+            heartbeat_timeout: DEFAULT_IDLE_TIMEOUT,
+        }
+    }
+
+    /// Share an existing registry (e.g. one with a running sweeper or a
+    /// flash secret)
+    pub fn with_registry(mut self, registry: Arc<LiveViewRegistry>) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Close sockets that have not sent a heartbeat within `timeout`
+    ///
+    /// Timeouts below [`MIN_HEARTBEAT_TIMEOUT`] are raised to it.
+    pub fn with_heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.heartbeat_timeout = timeout.max(MIN_HEARTBEAT_TIMEOUT);
+        self
+    }
+
+    /// Get the configured heartbeat timeout
+    pub fn heartbeat_timeout(&self) -> Duration {
+        self.heartbeat_timeout
+    }
+
+    /// How often transports should call [`LiveConnection::is_alive`]
+    ///
+    /// Half the heartbeat timeout, and never zero, so it can be passed
+    /// straight to `tokio::time::interval`.
+    pub fn heartbeat_interval(&self) -> Duration {
+        (self.heartbeat_timeout / 2).max(Duration::from_millis(1))
+    }
+
+    /// Serve a view for joins on `topic` (usually the page path)
+    pub fn route<F>(self, topic: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn() -> Box<dyn LiveView> + Send + Sync + 'static,
+    {
+        if let Ok(mut factories) = self.factories.write() {
+            factories.insert(topic.into(), Arc::new(factory));
+        }
+        self
+    }
+
+    /// The view registry
+    pub fn registry(&self) -> &Arc<LiveViewRegistry> {
+        &self.registry
+    }
+
+    /// Number of open connections
+    pub fn connection_count(&self) -> usize {
+        self.connections.read().map(|c| c.len()).unwrap_or(0)
+    }
+
+    /// Subscribe a connected socket to an additional topic
+    pub fn subscribe(&self, topic: &str, socket_id: &str) {
+        self.pubsub.subscribe(topic, socket_id);
+    }
+
+    /// Deliver `{ topic, event, payload }` to `handle_info` of every view
+    /// subscribed to `topic`.
+    ///
+    /// Returns the number of connections the event was handed to.
+    pub fn publish(&self, topic: &str, event: &str, payload: serde_json::Value) -> usize {
+        self.publish_info(
+            topic,
+            serde_json::json!({
+                "topic": topic,
+                "event": event,
+                "payload": payload,
+            }),
+        )
+    }
+
+    /// Deliver `info` unchanged to `handle_info` of every view subscribed
+    /// to `topic`
+    pub fn publish_info(&self, topic: &str, info: serde_json::Value) -> usize {
+        let subscribers = self.pubsub.subscribers(topic);
+        let Ok(connections) = self.connections.read() else {
+            return 0;
+        };
+        subscribers
+            .iter()
+            .filter_map(|id| connections.get(id))
+            .map(|sink| sink(info.clone()))
+            .count()
+    }
+
+    /// Open a connection for `socket_id`.
+    ///
+    /// `sink` receives published payloads; the transport passes them back
+    /// to [`LiveConnection::handle_info`] on its own task, so views are
+    /// never re-rendered from the publisher's thread.
+    pub fn connect<F>(&self, socket_id: impl Into<String>, sink: F) -> LiveConnection
+    where
+        F: Fn(serde_json::Value) + Send + Sync + 'static,
+    {
+        let socket_id = socket_id.into();
+        if let Ok(mut connections) = self.connections.write() {
+            connections.insert(socket_id.clone(), Box::new(sink));
+        }
+        LiveConnection {
+            hub: self.clone(),
+            socket_id,
+            topic: None,
+            connected_at: Instant::now(),
+        }
+    }
+}
+
+impl Default for LiveViewHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One client's LiveView session, from [`LiveViewHub::connect`].
+///
+/// Dropping it terminates the view and removes its subscriptions.
+pub struct LiveConnection {
+    hub: LiveViewHub,
+    socket_id: String,
+    /// Topic of the joined view; `None` until the join handshake completes
+    topic: Option<String>,
+    connected_at: Instant,
+}
+
+impl LiveConnection {
+    /// The socket id views see in [`LiveSocket::id`](super::LiveSocket::id)
+    pub fn socket_id(&self) -> &str {
+        &self.socket_id
+    }
+
+    /// Topic of the joined view
+    pub fn topic(&self) -> Option<&str> {
+        self.topic.as_deref()
+    }
+
+    /// Handle a text frame from the client.
+    ///
+    /// The first message must be a `Join` whose topic selects the view;
+    /// events are then dispatched through the registry and answered with
+    /// diffs.
+    pub fn handle_text(&mut self, text: &str) -> Reply {
+        let Ok(message) = serde_json::from_str::<WsMessage>(text) else {
+            return Reply::None;
+        };
+
+        match (&self.topic, message) {
+            (None, WsMessage::Join { topic, payload }) => self.join(topic, payload),
+            (None, WsMessage::Heartbeat) => Reply::None,
+            (None, _) => Reply::Close(Some(join_reply("error", "join required"))),
+            (Some(_), WsMessage::Leave { .. }) => Reply::Close(None),
+            (Some(_), message) => match dispatch_ws_message(&self.hub.registry, &self.socket_id, message) {
+                Some(response) => Reply::Send(serde_json::to_string(&response).unwrap_or_default()),
+                None => Reply::None,
+            },
+        }
+    }
+
+    fn join(&mut self, topic: String, payload: JoinPayload) -> Reply {
+        let factory = self
+            .hub
+            .factories
+            .read()
+            .ok()
+            .and_then(|factories| factories.get(&topic).cloned());
+        let Some(factory) = factory else {
+            return Reply::Close(Some(join_reply("error", "no live view for topic")));
+        };
+
+        let mut view = factory();
+        let mut socket = self.hub.registry.socket(self.socket_id.clone());
+        socket.params = payload.params.clone();
+        socket.restore_flashes();
+        view.mount(&mut socket);
+        let html = view.render();
+        self.hub.registry.register(self.socket_id.clone(), view);
+        self.hub.pubsub.subscribe(&topic, &self.socket_id);
+
+        let diff = render_diff(topic.clone(), &html, &mut socket);
+        self.topic = Some(topic.clone());
+        Reply::Joined {
+            topic,
+            payload,
+            frame: serde_json::to_string(&diff).unwrap_or_default(),
+        }
+    }
+
+    /// Run `handle_info` on the joined view and diff the re-render.
+    ///
+    /// Returns [`Reply::Close`] once the view has been swept.
+    pub fn handle_info(&mut self, info: serde_json::Value) -> Reply {
+        let Some(topic) = self.topic.clone() else {
+            return Reply::None;
+        };
+        let mut socket = self.hub.registry.socket(self.socket_id.clone());
+        let rendered = self.hub.registry.with_view(&self.socket_id, |view| {
+            view.handle_info(info, &mut socket);
+            view.render()
+        });
+        match rendered {
+            Some(html) => Reply::Send(serde_json::to_string(&render_diff(topic, &html, &mut socket)).unwrap_or_default()),
+            None => Reply::Close(None),
+        }
+    }
+
+    /// Whether the client is still sending heartbeats; transports check
+    /// this periodically and close the socket once it turns false
+    ///
+    /// A socket that hasn't joined within the heartbeat timeout of
+    /// connecting is dead too.
+    pub fn is_alive(&self) -> bool {
+        if self.topic.is_none() {
+            return self.connected_at.elapsed() <= self.hub.heartbeat_timeout;
+        }
+        match self.hub.registry.idle_for(&self.socket_id) {
+            Some(idle) => idle <= self.hub.heartbeat_timeout,
+            // Already swept by the registry
+            None => false,
+        }
+    }
+}
+
+impl Drop for LiveConnection {
+    fn drop(&mut self) {
+        if let Ok(mut connections) = self.hub.connections.write() {
+            connections.remove(&self.socket_id);
+        }
+        if let Some(mut view) = self.hub.registry.remove(&self.socket_id) {
+            view.terminate("closed");
+        }
+        self.hub.pubsub.unsubscribe_all(&self.socket_id);
+    }
+}
+
+fn join_reply(status: &str, message: &str) -> String {
+    serde_json::to_string(&WsMessage::Reply {
+        r#ref: "join".to_string(),
+        status: status.to_string(),
+        response: serde_json::json!({ "message": message }),
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::liveview::{FlashType, LiveEvent, LiveSocket};
+    use std::sync::Mutex;
+
+    struct Counter(i32);
+
+    impl LiveView for Counter {
+        fn mount(&mut self, _socket: &mut LiveSocket) {}
+        fn handle_event(&mut self, _event: &LiveEvent, _socket: &mut LiveSocket) {
+            self.0 += 1;
+        }
+        fn handle_info(&mut self, info: serde_json::Value, socket: &mut LiveSocket) {
+            self.0 += info["payload"]["n"].as_i64().unwrap_or(0) as i32;
+            socket.put_flash(FlashType::Info, "Updated");
+        }
+        fn render(&self) -> String {
+            format!("<p>{}</p>", self.0)
+        }
+    }
+
+    fn join(conn: &mut LiveConnection, topic: &str) -> Reply {
+        conn.handle_text(&format!(
+            r#"{{"type":"Join","topic":"{}","payload":{{"url":"/","params":{{}},"session":"u1"}}}}"#,
+            topic
+        ))
+    }
+
+    #[test]
+    fn test_join_and_event() {
+        let hub = LiveViewHub::new().route("/counter", || Box::new(Counter(0)));
+        let mut conn = hub.connect("s1", |_| {});
+
+        match join(&mut conn, "/counter") {
+            Reply::Joined { topic, payload, frame } => {
+                assert_eq!(topic, "/counter");
+                assert_eq!(payload.session, "u1");
+                assert!(frame.contains("<p>0</p>"));
+            }
+            other => panic!("unexpected reply: {:?}", other),
+        }
+        assert_eq!(conn.topic(), Some("/counter"));
+
+        let event = r#"{"type":"Event","topic":"/counter","event":{"event_type":"click","target":null,"value":null,"key":null}}"#;
+        match conn.handle_text(event) {
+            Reply::Send(frame) => assert!(frame.contains("<p>1</p>")),
+            other => panic!("unexpected reply: {:?}", other),
+        }
+        assert!(matches!(conn.handle_text(r#"{"type":"Leave","topic":"/counter"}"#), Reply::Close(None)));
+    }
+
+    #[test]
+    fn test_join_required() {
+        let hub = LiveViewHub::new().route("/counter", || Box::new(Counter(0)));
+        let mut conn = hub.connect("s1", |_| {});
+
+        assert!(matches!(conn.handle_text(r#"{"type":"Heartbeat"}"#), Reply::None));
+        match conn.handle_text(r#"{"type":"Leave","topic":"/counter"}"#) {
+            Reply::Close(Some(frame)) => assert!(frame.contains("join required")),
+            other => panic!("unexpected reply: {:?}", other),
+        }
+        match join(&mut conn, "/missing") {
+            Reply::Close(Some(frame)) => assert!(frame.contains("no live view for topic")),
+            other => panic!("unexpected reply: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_publish_reaches_subscribers() {
+        let hub = LiveViewHub::new().route("/counter", || Box::new(Counter(0)));
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let mut conn = hub.connect("s1", move |info| sink.lock().unwrap().push(info));
+        join(&mut conn, "/counter");
+
+        assert_eq!(hub.publish("/counter", "tick", serde_json::json!({ "n": 2 })), 1);
+        assert_eq!(hub.publish("/other", "tick", serde_json::json!({})), 0);
+
+        let info = received.lock().unwrap().remove(0);
+        assert_eq!(info["event"], "tick");
+        match conn.handle_info(info) {
+            Reply::Send(frame) => {
+                assert!(frame.contains("\"type\":\"Diff\""));
+                assert!(frame.contains("<p>2</p>"));
+                assert!(frame.contains("live-flash"));
+            }
+            other => panic!("unexpected reply: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_drop_cleans_up() {
+        let hub = LiveViewHub::new().route("/counter", || Box::new(Counter(0)));
+        let mut conn = hub.connect("s1", |_| {});
+        join(&mut conn, "/counter");
+        assert!(conn.is_alive());
+        assert_eq!(hub.connection_count(), 1);
+        assert_eq!(hub.registry().len(), 1);
+
+        drop(conn);
+        assert_eq!(hub.connection_count(), 0);
+        assert!(hub.registry().is_empty());
+        assert_eq!(hub.publish("/counter", "tick", serde_json::json!({})), 0);
+    }
+
+    #[test]
+    fn test_swept_view_closes() {
+        let hub = LiveViewHub::new().route("/counter", || Box::new(Counter(0)));
+        let mut conn = hub.connect("s1", |_| {});
+        join(&mut conn, "/counter");
+
+        hub.registry().remove("s1");
+        assert!(!conn.is_alive());
+        assert!(matches!(conn.handle_info(serde_json::json!({})), Reply::Close(None)));
+    }
+
+    #[test]
+    fn test_unjoined_connection_expires() {
+        let hub = LiveViewHub::new().with_heartbeat_timeout(Duration::from_millis(20));
+        let mut conn = hub.connect("s1", |_| {});
+        assert!(conn.is_alive());
+
+        // Heartbeats don't keep a socket that never joins open
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(matches!(conn.handle_text(r#"{"type":"Heartbeat"}"#), Reply::None));
+        assert!(!conn.is_alive());
+    }
+
+    #[test]
+    fn test_heartbeat_timeout_is_clamped() {
+        let hub = LiveViewHub::new().with_heartbeat_timeout(Duration::ZERO);
+        assert_eq!(hub.heartbeat_timeout(), MIN_HEARTBEAT_TIMEOUT);
+        assert_eq!(hub.heartbeat_interval(), Duration::from_millis(1));
+
+        let hub = LiveViewHub::new().with_heartbeat_timeout(Duration::from_secs(30));
+        assert_eq!(hub.heartbeat_interval(), Duration::from_secs(15));
+    }
+}
@@ -11,6 +11,7 @@
 //! fn Counter() -> impl LiveView {
 //!     let count = signal!(0);
 //!
+//!     // Renders `<div data-live-id="0"><h1 data-live-id="1">Count: 0</h1>...`
 //!     live! {
 //!         <div>
 //!             <h1>"Count: " {count}</h1>
@@ -35,12 +36,22 @@
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 
 use crate::reactive::Signal;
 
+pub mod client;
+pub mod hub;
+
+pub use client::{client_js, client_script_tag, LiveClientConfig, CLIENT_JS_TEMPLATE};
+pub use hub::{LiveConnection, LiveViewFactory, LiveViewHub, Reply, MIN_HEARTBEAT_TIMEOUT};
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -75,6 +86,9 @@ pub struct ViewPatch {
     pub patches: Vec<DomPatch>,
     pub title: Option<String>,
     pub events: Vec<PushEvent>,
+    /// Navigate to this URL after applying the patch
+    #[serde(default)]
+    pub redirect: Option<String>,
 }
 
 impl Default for ViewPatch {
@@ -83,6 +97,7 @@ impl Default for ViewPatch {
             patches: Vec::new(),
             title: None,
             events: Vec::new(),
+            redirect: None,
         }
     }
 }
@@ -142,15 +157,79 @@ pub struct LiveSocket {
 
     /// Patch target
     patch: Option<String>,
+
+    /// Signs flashes carried across redirects
+    flash_signer: Option<FlashSigner>,
 }
 
-#[derive(Debug, Clone)]
-struct Flash {
-    flash_type: FlashType,
-    message: String,
+/// Query parameter used to carry flashes across redirects
+pub const FLASH_PARAM: &str = "_flash";
+
+/// Signs and verifies flashes carried across redirects in [`FLASH_PARAM`].
+///
+/// The parameter is client-controlled, so without a valid signature a
+/// crafted link could show arbitrary "server" messages on a trusted page.
+/// The value is `<hex HMAC-SHA256>.<json>`.
+#[derive(Clone)]
+pub struct FlashSigner {
+    key: Arc<[u8]>,
+}
+
+impl FlashSigner {
+    /// Create a signer; use the same secret on every server instance
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: Arc::from(secret.as_ref()),
+        }
+    }
+
+    fn mac(&self, payload: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts any key length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+
+    /// Encode and sign flashes for the redirect URL
+    pub fn sign(&self, flashes: &[Flash]) -> String {
+        let json = serde_json::to_string(flashes).unwrap_or_default();
+        let tag = self.mac(&json).finalize().into_bytes();
+        let hex: String = tag.iter().map(|b| format!("{:02x}", b)).collect();
+        format!("{}.{}", hex, json)
+    }
+
+    /// Decode flashes, or `None` if the signature does not match
+    pub fn verify(&self, value: &str) -> Option<Vec<Flash>> {
+        let (hex, json) = value.split_once('.')?;
+        if hex.len() % 2 != 0 || !hex.is_ascii() {
+            return None;
+        }
+        let tag = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        self.mac(json).verify_slice(&tag).ok()?;
+        serde_json::from_str(json).ok()
+    }
+}
+
+impl std::fmt::Debug for FlashSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlashSigner").finish_non_exhaustive()
+    }
+}
+
+/// DOM id of the container flashes are rendered into
+pub const FLASH_CONTAINER_ID: &str = "live-flash";
+
+/// A flash message shown once on the next render
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Flash {
+    pub flash_type: FlashType,
+    pub message: String,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum FlashType {
     Info,
     Success,
@@ -158,6 +237,18 @@ pub enum FlashType {
     Error,
 }
 
+impl FlashType {
+    /// Lowercase name, used for CSS classes
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlashType::Info => "info",
+            FlashType::Success => "success",
+            FlashType::Warning => "warning",
+            FlashType::Error => "error",
+        }
+    }
+}
+
 impl LiveSocket {
     pub fn new(id: String) -> Self {
         Self {
@@ -168,9 +259,18 @@ impl LiveSocket {
             pending_events: Vec::new(),
             redirect: None,
             patch: None,
+            flash_signer: None,
         }
     }
 
+    /// Carry flashes across redirects, signed with `signer`.
+    ///
+    /// Without a signer flashes are never put into or read from the URL.
+    pub fn with_flash_signer(mut self, signer: FlashSigner) -> Self {
+        self.flash_signer = Some(signer);
+        self
+    }
+
     /// Push an event to the client
     pub fn push_event(&mut self, event: impl Into<String>, payload: serde_json::Value) {
         self.pending_events.push(PushEvent {
@@ -197,14 +297,58 @@ impl LiveSocket {
         });
     }
 
+    /// Flash messages queued for the next render
+    pub fn flashes(&self) -> &[Flash] {
+        &self.flashes
+    }
+
+    /// Get flash messages and clear them
+    pub fn take_flashes(&mut self) -> Vec<Flash> {
+        std::mem::take(&mut self.flashes)
+    }
+
+    /// Drop queued flashes of one type
+    pub fn clear_flash(&mut self, flash_type: FlashType) {
+        self.flashes.retain(|f| f.flash_type != flash_type);
+    }
+
+    /// Restore flashes carried over a redirect in the `_flash` URL parameter.
+    ///
+    /// Integrations call this after populating `params` on join. The
+    /// parameter is dropped unless its signature matches the socket's
+    /// [`FlashSigner`].
+    pub fn restore_flashes(&mut self) {
+        let Some(encoded) = self.params.remove(FLASH_PARAM) else {
+            return;
+        };
+        if let Some(flashes) = self.flash_signer.as_ref().and_then(|signer| signer.verify(&encoded)) {
+            self.flashes.extend(flashes);
+        }
+    }
+
     /// Get pending events and clear them
     pub fn take_pending_events(&mut self) -> Vec<PushEvent> {
         std::mem::take(&mut self.pending_events)
     }
 
-    /// Get redirect target
+    /// Get redirect target.
+    ///
+    /// With a [`FlashSigner`], pending flashes are moved into the target URL
+    /// so they survive the navigation; see
+    /// [`restore_flashes`](Self::restore_flashes).
     pub fn take_redirect(&mut self) -> Option<String> {
-        self.redirect.take()
+        let mut to = self.redirect.take()?;
+        if let Some(signer) = self.flash_signer.as_ref().filter(|_| !self.flashes.is_empty()) {
+            let signed = signer.sign(&self.flashes);
+            self.flashes.clear();
+            let (path, fragment) = match to.find('#') {
+                Some(i) => (to[..i].to_string(), to[i..].to_string()),
+                None => (to.clone(), String::new()),
+            };
+            let sep = if path.contains('?') { '&' } else { '?' };
+            to = format!("{}{}{}={}{}", path, sep, FLASH_PARAM, encode_uri_component(&signed), fragment);
+        }
+        Some(to)
     }
 
     /// Get patch target
@@ -239,23 +383,147 @@ pub trait LiveComponent: Send + Sync {
 // View Instance Manager
 // ============================================================================
 
+/// Default idle period after which a socket without heartbeats is swept
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Lifecycle events emitted by the registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    /// A view was registered for a socket
+    Registered { socket_id: String },
+    /// A view was removed explicitly
+    Removed { socket_id: String },
+    /// A view was terminated because its socket stopped sending heartbeats
+    TimedOut { socket_id: String, idle: Duration },
+}
+
+/// Lifecycle hook callback
+pub type LifecycleHook = Arc<dyn Fn(&LifecycleEvent) + Send + Sync>;
+
+/// Counters describing registry activity
+#[derive(Debug, Default)]
+struct RegistryCounters {
+    registered: AtomicU64,
+    removed: AtomicU64,
+    timed_out: AtomicU64,
+}
+
+/// Snapshot of registry metrics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryMetrics {
+    /// Views currently registered
+    pub active: u64,
+    /// Views registered since creation
+    pub registered: u64,
+    /// Views removed explicitly since creation
+    pub removed: u64,
+    /// Views swept after missing heartbeats since creation
+    pub timed_out: u64,
+}
+
+struct RegisteredView {
+    view: Box<dyn LiveView>,
+    last_heartbeat: Instant,
+}
+
 /// Manages LiveView instances for connected clients
 pub struct LiveViewRegistry {
-    views: RwLock<HashMap<String, Box<dyn LiveView>>>,
+    views: RwLock<HashMap<String, RegisteredView>>,
+    idle_timeout: Duration,
+    hooks: RwLock<Vec<LifecycleHook>>,
+    counters: RegistryCounters,
+    flash_signer: Option<FlashSigner>,
 }
 
 impl LiveViewRegistry {
     pub fn new() -> Self {
         Self {
             views: RwLock::new(HashMap::new()),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            hooks: RwLock::new(Vec::new()),
+            counters: RegistryCounters::default(),
+            flash_signer: None,
+        }
+    }
+
+    /// Sign flashes carried across redirects with `secret`
+    pub fn with_flash_secret(mut self, secret: impl AsRef<[u8]>) -> Self {
+        self.flash_signer = Some(FlashSigner::new(secret));
+        self
+    }
+
+    /// A socket configured with this registry's flash signer
+    pub fn socket(&self, socket_id: impl Into<String>) -> LiveSocket {
+        let socket = LiveSocket::new(socket_id.into());
+        match &self.flash_signer {
+            Some(signer) => socket.with_flash_signer(signer.clone()),
+            None => socket,
+        }
+    }
+
+    /// Set the idle period after which sockets without heartbeats are swept
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Get the configured idle timeout
+    pub fn idle_timeout(&self) -> Duration {
+        self.idle_timeout
+    }
+
+    /// Register a hook called for every lifecycle event
+    pub fn on_lifecycle<F>(&self, hook: F)
+    where
+        F: Fn(&LifecycleEvent) + Send + Sync + 'static,
+    {
+        if let Ok(mut hooks) = self.hooks.write() {
+            hooks.push(Arc::new(hook));
+        }
+    }
+
+    fn emit(&self, event: LifecycleEvent) {
+        let hooks = match self.hooks.read() {
+            Ok(hooks) => hooks.clone(),
+            Err(_) => return,
+        };
+        for hook in hooks {
+            hook(&event);
         }
     }
 
     /// Register a view instance
     pub fn register(&self, socket_id: String, view: Box<dyn LiveView>) {
         if let Ok(mut views) = self.views.write() {
-            views.insert(socket_id, view);
+            views.insert(
+                socket_id.clone(),
+                RegisteredView {
+                    view,
+                    last_heartbeat: Instant::now(),
+                },
+            );
+        } else {
+            return;
+        }
+        self.counters.registered.fetch_add(1, Ordering::Relaxed);
+        self.emit(LifecycleEvent::Registered { socket_id });
+    }
+
+    /// Record a heartbeat for a socket. Returns `false` if the socket is unknown.
+    pub fn touch(&self, socket_id: &str) -> bool {
+        if let Ok(mut views) = self.views.write() {
+            if let Some(entry) = views.get_mut(socket_id) {
+                entry.last_heartbeat = Instant::now();
+                return true;
+            }
         }
+        false
+    }
+
+    /// Time since the last heartbeat for a socket
+    pub fn idle_for(&self, socket_id: &str) -> Option<Duration> {
+        let views = self.views.read().ok()?;
+        views.get(socket_id).map(|entry| entry.last_heartbeat.elapsed())
     }
 
     /// Get a mutable reference to a view
@@ -264,7 +532,7 @@ impl LiveViewRegistry {
         F: FnOnce(&mut Box<dyn LiveView>) -> R,
     {
         if let Ok(mut views) = self.views.write() {
-            views.get_mut(socket_id).map(f)
+            views.get_mut(socket_id).map(|entry| f(&mut entry.view))
         } else {
             None
         }
@@ -272,11 +540,90 @@ impl LiveViewRegistry {
 
     /// Remove a view instance
     pub fn remove(&self, socket_id: &str) -> Option<Box<dyn LiveView>> {
-        if let Ok(mut views) = self.views.write() {
+        let removed = if let Ok(mut views) = self.views.write() {
             views.remove(socket_id)
         } else {
             None
+        }?;
+        self.counters.removed.fetch_add(1, Ordering::Relaxed);
+        self.emit(LifecycleEvent::Removed {
+            socket_id: socket_id.to_string(),
+        });
+        Some(removed.view)
+    }
+
+    /// Terminate and remove every view idle for longer than the idle timeout.
+    ///
+    /// Returns the IDs of the swept sockets.
+    pub fn sweep_idle(&self) -> Vec<String> {
+        let expired: Vec<(String, RegisteredView)> = {
+            let mut views = match self.views.write() {
+                Ok(views) => views,
+                Err(_) => return Vec::new(),
+            };
+            let ids: Vec<String> = views
+                .iter()
+                .filter(|(_, entry)| entry.last_heartbeat.elapsed() > self.idle_timeout)
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.into_iter()
+                .filter_map(|id| views.remove(&id).map(|entry| (id, entry)))
+                .collect()
+        };
+
+        let mut swept = Vec::with_capacity(expired.len());
+        for (socket_id, mut entry) in expired {
+            let idle = entry.last_heartbeat.elapsed();
+            entry.view.terminate("timeout");
+            self.counters.timed_out.fetch_add(1, Ordering::Relaxed);
+            self.emit(LifecycleEvent::TimedOut {
+                socket_id: socket_id.clone(),
+                idle,
+            });
+            swept.push(socket_id);
         }
+        swept
+    }
+
+    /// Number of registered views
+    pub fn len(&self) -> usize {
+        self.views.read().map(|views| views.len()).unwrap_or(0)
+    }
+
+    /// Whether no views are registered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Snapshot of registry metrics
+    pub fn metrics(&self) -> RegistryMetrics {
+        RegistryMetrics {
+            active: self.len() as u64,
+            registered: self.counters.registered.load(Ordering::Relaxed),
+            removed: self.counters.removed.load(Ordering::Relaxed),
+            timed_out: self.counters.timed_out.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Spawn a tokio task that calls [`sweep_idle`](Self::sweep_idle) every `interval`.
+    ///
+    /// The task stops once the last strong reference to the registry is dropped.
+    #[cfg(feature = "tokio")]
+    pub fn spawn_sweeper(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let registry = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match registry.upgrade() {
+                    Some(registry) => {
+                        registry.sweep_idle();
+                    }
+                    None => break,
+                }
+            }
+        })
     }
 }
 
@@ -291,13 +638,16 @@ impl Default for LiveViewRegistry {
 // ============================================================================
 
 /// Compute DOM patches between old and new HTML
+///
+/// Any change yields a single [`DomPatch::Morph`] of the whole view; the
+/// `data-live-id` attributes stamped by `live!` are not used to narrow it.
+/// The client's morph reuses matching nodes, so focus and input state
+/// survive the re-render.
 pub fn diff_html(old: &str, new: &str) -> Vec<DomPatch> {
     if old == new {
         return Vec::new();
     }
 
-    // Simple implementation: full morph for now
-    // Production would use a proper tree diff algorithm
     vec![DomPatch::Morph {
         target: "body".to_string(),
         html: new.to_string(),
@@ -339,6 +689,77 @@ where
         .join("")
 }
 
+/// CSS classes used by [`render_flashes_with`]
+#[derive(Debug, Clone)]
+pub struct FlashClasses {
+    /// Class of the wrapping container
+    pub container: String,
+    /// Class of each flash; `{base} {base}-{type}` is emitted per flash
+    pub item: String,
+    /// Class of the dismiss button
+    pub dismiss: String,
+}
+
+impl Default for FlashClasses {
+    fn default() -> Self {
+        Self {
+            container: "flash-container".to_string(),
+            item: "flash".to_string(),
+            dismiss: "flash-dismiss".to_string(),
+        }
+    }
+}
+
+/// Render flash messages into the flash container using default classes
+///
+/// Render `render_flashes(&[])` in the page layout so later flashes have a
+/// container to replace.
+pub fn render_flashes(flashes: &[Flash]) -> String {
+    render_flashes_with(flashes, &FlashClasses::default())
+}
+
+/// Render flash messages into the flash container
+///
+/// Each flash can be dismissed client-side through its
+/// `data-live-flash-dismiss` button.
+pub fn render_flashes_with(flashes: &[Flash], classes: &FlashClasses) -> String {
+    let items = each(flashes, |flash, _| {
+        let kind = flash.flash_type.as_str();
+        let role = match flash.flash_type {
+            FlashType::Error | FlashType::Warning => "alert",
+            FlashType::Info | FlashType::Success => "status",
+        };
+        format!(
+            r#"<div class="{item} {item}-{kind}" role="{role}" data-flash-type="{kind}"><span>{message}</span><button type="button" class="{dismiss}" data-live-flash-dismiss aria-label="Dismiss">&times;</button></div>"#,
+            item = escape_html(&classes.item),
+            kind = kind,
+            role = role,
+            message = escape_html(&flash.message),
+            dismiss = escape_html(&classes.dismiss),
+        )
+    });
+    format!(
+        r#"<div id="{}" class="{}" data-live-flash>{}</div>"#,
+        FLASH_CONTAINER_ID,
+        escape_html(&classes.container),
+        items
+    )
+}
+
+/// Percent-encode a string for use in a URL query value
+fn encode_uri_component(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
 // ============================================================================
 // Form Helpers
 // ============================================================================
@@ -462,7 +883,7 @@ pub enum WsMessage {
     },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JoinPayload {
     pub url: String,
     pub params: HashMap<String, String>,
@@ -475,33 +896,64 @@ pub async fn handle_ws_message(
     socket_id: &str,
     message: WsMessage,
 ) -> Option<WsMessage> {
+    dispatch_ws_message(registry, socket_id, message)
+}
+
+/// Serialize a full-render diff, draining events, flashes, and redirects from `socket`
+fn render_diff(topic: String, html: &str, socket: &mut LiveSocket) -> WsMessage {
+    // Get previous HTML (would be cached in production)
+    let mut patches = diff_html("", html);
+
+    // Flashes are shown once, then cleared; a redirect carries them along
+    let redirect = socket.take_redirect();
+    let flashes = socket.take_flashes();
+    if !flashes.is_empty() {
+        patches.push(DomPatch::Replace {
+            target: FLASH_CONTAINER_ID.to_string(),
+            html: render_flashes(&flashes),
+        });
+    }
+
+    WsMessage::Diff {
+        topic,
+        diff: ViewPatch {
+            patches,
+            events: socket.take_pending_events(),
+            title: None,
+            redirect,
+        },
+    }
+}
+
+fn dispatch_ws_message(registry: &LiveViewRegistry, socket_id: &str, message: WsMessage) -> Option<WsMessage> {
     match message {
         WsMessage::Event { topic, event } => {
-            let mut socket = LiveSocket::new(socket_id.to_string());
+            registry.touch(socket_id);
+            let mut socket = registry.socket(socket_id);
 
             let new_html = registry.with_view(socket_id, |view| {
                 view.handle_event(&event, &mut socket);
                 view.render()
             })?;
 
-            // Get previous HTML (would be cached in production)
-            let patches = diff_html("", &new_html);
+            Some(render_diff(topic, &new_html, &mut socket))
+        }
 
-            Some(WsMessage::Diff {
-                topic,
-                diff: ViewPatch {
-                    patches,
-                    events: socket.take_pending_events(),
-                    title: None,
-                },
+        WsMessage::Heartbeat => {
+            registry.touch(socket_id);
+            Some(WsMessage::Reply {
+                r#ref: "heartbeat".to_string(),
+                status: "ok".to_string(),
+                response: serde_json::json!({}),
             })
         }
 
-        WsMessage::Heartbeat => Some(WsMessage::Reply {
-            r#ref: "heartbeat".to_string(),
-            status: "ok".to_string(),
-            response: serde_json::json!({}),
-        }),
+        WsMessage::Leave { .. } => {
+            if let Some(mut view) = registry.remove(socket_id) {
+                view.terminate("leave");
+            }
+            None
+        }
 
         _ => None,
     }
@@ -529,14 +981,6 @@ macro_rules! live_view {
     };
 }
 
-/// HTML template macro (simplified)
-#[macro_export]
-macro_rules! live {
-    ($($tt:tt)*) => {
-        format!("{}", stringify!($($tt)*))
-    };
-}
-
 // ============================================================================
 // PubSub
 // ============================================================================
@@ -602,3 +1046,269 @@ impl Default for PubSub {
 
 pub use crate::live;
 pub use crate::live_view;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Dummy {
+        terminated: Arc<Mutex<Option<String>>>,
+    }
+
+    impl LiveView for Dummy {
+        fn mount(&mut self, _socket: &mut LiveSocket) {}
+        fn handle_event(&mut self, _event: &LiveEvent, _socket: &mut LiveSocket) {}
+        fn render(&self) -> String {
+            String::new()
+        }
+        fn terminate(&mut self, reason: &str) {
+            *self.terminated.lock().unwrap() = Some(reason.to_string());
+        }
+    }
+
+    #[test]
+    fn test_take_flashes_clears() {
+        let mut socket = LiveSocket::new("s1".to_string());
+        socket.put_flash(FlashType::Info, "Saved");
+        socket.put_flash(FlashType::Error, "Oops");
+
+        assert_eq!(socket.take_flashes().len(), 2);
+        assert!(socket.flashes().is_empty());
+    }
+
+    #[test]
+    fn test_render_flashes_escapes_and_styles() {
+        let html = render_flashes(&[Flash {
+            flash_type: FlashType::Error,
+            message: "<b>bad</b>".to_string(),
+        }]);
+
+        assert!(html.contains(r#"id="live-flash""#));
+        assert!(html.contains("flash flash-error"));
+        assert!(html.contains(r#"role="alert""#));
+        assert!(html.contains("&lt;b&gt;bad&lt;/b&gt;"));
+    }
+
+    #[test]
+    fn test_flashes_survive_redirect() {
+        let registry = LiveViewRegistry::new().with_flash_secret("secret");
+        let mut socket = registry.socket("s1");
+        socket.put_flash(FlashType::Success, "Created");
+        socket.push_redirect("/posts?page=2");
+
+        let to = socket.take_redirect().unwrap();
+        assert!(to.starts_with("/posts?page=2&_flash="));
+        assert!(socket.flashes().is_empty());
+
+        let encoded = to.split("_flash=").nth(1).unwrap();
+        let decoded = decode_uri_component(encoded);
+        let mut next = registry.socket("s2");
+        next.params.insert(FLASH_PARAM.to_string(), decoded);
+        next.restore_flashes();
+
+        assert_eq!(next.flashes()[0].message, "Created");
+        assert!(!next.params.contains_key(FLASH_PARAM));
+    }
+
+    #[test]
+    fn test_forged_flashes_rejected() {
+        let signer = FlashSigner::new("secret");
+        let flashes = vec![Flash {
+            flash_type: FlashType::Error,
+            message: "Your account is locked".to_string(),
+        }];
+        let signed = signer.sign(&flashes);
+        assert_eq!(signer.verify(&signed), Some(flashes.clone()));
+
+        let unsigned = serde_json::to_string(&flashes).unwrap();
+        let tampered = signed.replace("locked", "closed");
+        let other_key = FlashSigner::new("other").sign(&flashes);
+        for forged in [unsigned, tampered, other_key] {
+            let mut socket = LiveSocket::new("s1".to_string()).with_flash_signer(signer.clone());
+            socket.params.insert(FLASH_PARAM.to_string(), forged);
+            socket.restore_flashes();
+            assert!(socket.flashes().is_empty());
+            assert!(!socket.params.contains_key(FLASH_PARAM));
+        }
+    }
+
+    #[test]
+    fn test_flashes_stay_without_signer() {
+        let mut socket = LiveSocket::new("s1".to_string());
+        socket.put_flash(FlashType::Info, "Saved");
+        socket.push_redirect("/posts");
+
+        assert_eq!(socket.take_redirect().as_deref(), Some("/posts"));
+        assert_eq!(socket.flashes().len(), 1);
+
+        let mut next = LiveSocket::new("s2".to_string());
+        let signed = FlashSigner::new("secret").sign(socket.flashes());
+        next.params.insert(FLASH_PARAM.to_string(), signed);
+        next.restore_flashes();
+        assert!(next.flashes().is_empty());
+    }
+
+    fn decode_uri_component(s: &str) -> String {
+        let bytes = s.as_bytes();
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' {
+                out.push(u8::from_str_radix(&s[i + 1..i + 3], 16).unwrap());
+                i += 3;
+            } else {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_sweep_idle_terminates_and_removes() {
+        let terminated = Arc::new(Mutex::new(None));
+        let registry = LiveViewRegistry::new().with_idle_timeout(Duration::from_millis(0));
+        registry.register(
+            "s1".to_string(),
+            Box::new(Dummy {
+                terminated: terminated.clone(),
+            }),
+        );
+
+        std::thread::sleep(Duration::from_millis(2));
+        let swept = registry.sweep_idle();
+
+        assert_eq!(swept, vec!["s1".to_string()]);
+        assert!(registry.is_empty());
+        assert_eq!(terminated.lock().unwrap().as_deref(), Some("timeout"));
+        assert_eq!(registry.metrics().timed_out, 1);
+    }
+
+    #[test]
+    fn test_touch_keeps_socket_alive() {
+        let registry = LiveViewRegistry::new().with_idle_timeout(Duration::from_secs(60));
+        registry.register(
+            "s1".to_string(),
+            Box::new(Dummy {
+                terminated: Arc::new(Mutex::new(None)),
+            }),
+        );
+
+        assert!(registry.touch("s1"));
+        assert!(!registry.touch("missing"));
+        assert!(registry.sweep_idle().is_empty());
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_lifecycle_hooks() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let registry = LiveViewRegistry::new();
+        let sink = events.clone();
+        registry.on_lifecycle(move |event| sink.lock().unwrap().push(event.clone()));
+
+        registry.register(
+            "s1".to_string(),
+            Box::new(Dummy {
+                terminated: Arc::new(Mutex::new(None)),
+            }),
+        );
+        registry.remove("s1");
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec![
+                LifecycleEvent::Registered { socket_id: "s1".to_string() },
+                LifecycleEvent::Removed { socket_id: "s1".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_live_escapes_text_and_attributes() {
+        let name = "<script>alert('x')</script>";
+        let title = "\"quoted\" & more";
+        let html = live! {
+            <p title={title}>"Tom & Jerry " {name}</p>
+        };
+
+        assert_eq!(
+            html,
+            "<p data-live-id=\"0\" title=\"&quot;quoted&quot; &amp; more\">Tom &amp; Jerry \
+             &lt;script&gt;alert(&#039;x&#039;)&lt;/script&gt;</p>"
+        );
+    }
+
+    #[test]
+    fn test_live_ids_are_stable_across_renders() {
+        let render = |count: i32| {
+            live! {
+                <div>
+                    <h1>"Count: " {count}</h1>
+                    <span>{count * 2}</span>
+                </div>
+            }
+        };
+
+        let (first, second) = (render(1), render(2));
+        assert_eq!(
+            first,
+            "<div data-live-id=\"0\"><h1 data-live-id=\"1\">Count: 1</h1><span data-live-id=\"2\">2</span></div>"
+        );
+        assert_eq!(
+            second,
+            "<div data-live-id=\"0\"><h1 data-live-id=\"1\">Count: 2</h1><span data-live-id=\"2\">4</span></div>"
+        );
+    }
+
+    #[test]
+    fn test_live_event_attributes() {
+        let html = live! {
+            <form phx-change="validate" live:submit="save">
+                <button live:click="increment" phx-value-step="1">"+"</button>
+            </form>
+        };
+
+        assert!(html.contains(" phx-change=\"validate\""));
+        assert!(html.contains(" live:submit=\"save\""));
+        assert!(html.contains(" live:click=\"increment\""));
+        assert!(html.contains(" phx-value-step=\"1\""));
+    }
+
+    #[test]
+    fn test_live_void_elements() {
+        let query = "a&b";
+        let html = live! {
+            <div>
+                <input name="query" value={query} />
+                <br />
+            </div>
+        };
+
+        assert_eq!(
+            html,
+            "<div data-live-id=\"0\"><input data-live-id=\"1\" name=\"query\" value=\"a&amp;b\" />\
+             <br data-live-id=\"2\" /></div>"
+        );
+    }
+
+    #[test]
+    fn test_live_is_exported_from_liveview() {
+        let html = philjs::liveview::live! { <hr /> };
+        assert_eq!(html, "<hr data-live-id=\"0\" />");
+    }
+
+    #[test]
+    fn test_diff_html_morphs_whole_view() {
+        let html = "<p data-live-id=\"0\">1</p>";
+        assert!(diff_html(html, html).is_empty());
+
+        let patches = diff_html(html, "<p data-live-id=\"0\">2</p>");
+        assert!(matches!(
+            patches.as_slice(),
+            [DomPatch::Morph { target, html }] if target == "body" && html == "<p data-live-id=\"0\">2</p>"
+        ));
+    }
+}
@@ -0,0 +1,566 @@
+//! Structured data (JSON-LD) for the document head
+//!
+//! Typed builders for common schema.org types, rendered into a
+//! `<script type="application/ld+json">` tag through the [`MetaContext`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use philjs::meta::*;
+//!
+//! JsonLd::new(
+//!     Article::new("Fine-grained reactivity in Rust")
+//!         .author(Person::new("Ada"))
+//!         .date_published("2024-05-01")
+//!         .image("https://example.com/cover.png"),
+//! );
+//! ```
+//!
+//! [`MetaContext`]: super::MetaContext
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::{with_current, ScriptTag};
+use crate::view::{IntoView, View};
+
+const SCHEMA_CONTEXT: &str = "https://schema.org";
+
+/// A schema.org type that can be serialized as JSON-LD.
+pub trait Schema {
+    /// The schema.org `@type`, e.g. `"Article"`
+    const TYPE: &'static str;
+
+    /// Serialize into a JSON-LD node including `@type`.
+    fn to_json_ld(&self) -> Value;
+}
+
+/// Serialize `value` and tag it with its schema.org type.
+fn typed_node<T: Serialize>(schema_type: &str, value: &T) -> Value {
+    let mut map = Map::new();
+    map.insert("@type".to_string(), Value::String(schema_type.to_string()));
+    if let Ok(Value::Object(fields)) = serde_json::to_value(value) {
+        map.extend(fields.into_iter().filter(|(_, v)| !v.is_null()));
+    }
+    Value::Object(map)
+}
+
+/// Serialize JSON for embedding in a `<script>` element.
+///
+/// `<`, `>` and `&` only ever appear inside JSON strings, so escaping them as
+/// unicode sequences keeps the JSON equivalent while making `</script>` and
+/// `<!--` injection impossible.
+pub fn escape_json_for_script(value: &Value) -> String {
+    serde_json::to_string(value)
+        .unwrap_or_else(|_| "null".to_string())
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029")
+}
+
+// =============================================================================
+// Component
+// =============================================================================
+
+/// Add a JSON-LD structured data block to the document head.
+///
+/// # Example
+/// ```rust
+/// view! {
+///     <JsonLd schema=Organization::new("Acme").url("https://acme.test") />
+/// }
+/// ```
+pub struct JsonLd {
+    value: Value,
+}
+
+impl JsonLd {
+    /// Register a typed schema.
+    pub fn new(schema: impl Schema) -> Self {
+        Self::raw(schema.to_json_ld())
+    }
+
+    /// Register an arbitrary JSON-LD value. `@context` is added when missing.
+    pub fn raw(value: Value) -> Self {
+        let value = with_schema_context(value);
+        with_current(|ctx| {
+            ctx.add_script(ScriptTag {
+                src: None,
+                content: Some(escape_json_for_script(&value)),
+                r#type: Some("application/ld+json".to_string()),
+                r#async: false,
+                defer: false,
+                module: false,
+                nonce: None,
+                integrity: None,
+                crossorigin: None,
+            })
+        });
+        Self { value }
+    }
+
+    /// The JSON-LD value, including `@context`.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Render as a `<script type="application/ld+json">` tag.
+    pub fn to_html(&self) -> String {
+        format!(
+            r#"<script type="application/ld+json">{}</script>"#,
+            escape_json_for_script(&self.value)
+        )
+    }
+}
+
+impl IntoView for JsonLd {
+    fn into_view(self) -> View {
+        View::Empty
+    }
+}
+
+fn with_schema_context(value: Value) -> Value {
+    match value {
+        Value::Object(mut map) => {
+            if !map.contains_key("@context") {
+                let mut ordered = Map::new();
+                ordered.insert("@context".to_string(), Value::String(SCHEMA_CONTEXT.to_string()));
+                ordered.extend(std::mem::take(&mut map));
+                map = ordered;
+            }
+            Value::Object(map)
+        }
+        Value::Array(items) => {
+            let mut map = Map::new();
+            map.insert("@context".to_string(), Value::String(SCHEMA_CONTEXT.to_string()));
+            map.insert("@graph".to_string(), Value::Array(items));
+            Value::Object(map)
+        }
+        other => other,
+    }
+}
+
+// =============================================================================
+// Schema Types
+// =============================================================================
+
+/// schema.org `Person`
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    pub name: String,
+    pub url: Option<String>,
+    pub image: Option<String>,
+}
+
+impl Person {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    pub fn image(mut self, image: impl Into<String>) -> Self {
+        self.image = Some(image.into());
+        self
+    }
+}
+
+impl Schema for Person {
+    const TYPE: &'static str = "Person";
+
+    fn to_json_ld(&self) -> Value {
+        typed_node(Self::TYPE, self)
+    }
+}
+
+/// schema.org `Organization`
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Organization {
+    pub name: String,
+    pub url: Option<String>,
+    pub logo: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub same_as: Vec<String>,
+}
+
+impl Organization {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    pub fn logo(mut self, logo: impl Into<String>) -> Self {
+        self.logo = Some(logo.into());
+        self
+    }
+
+    /// Add a profile URL (social accounts, Wikipedia, ...).
+    pub fn same_as(mut self, url: impl Into<String>) -> Self {
+        self.same_as.push(url.into());
+        self
+    }
+}
+
+impl Schema for Organization {
+    const TYPE: &'static str = "Organization";
+
+    fn to_json_ld(&self) -> Value {
+        typed_node(Self::TYPE, self)
+    }
+}
+
+/// schema.org `Article`
+#[derive(Clone, Debug, Default)]
+pub struct Article {
+    pub headline: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub images: Vec<String>,
+    pub authors: Vec<Person>,
+    pub publisher: Option<Organization>,
+    pub date_published: Option<String>,
+    pub date_modified: Option<String>,
+}
+
+impl Article {
+    pub fn new(headline: impl Into<String>) -> Self {
+        Self {
+            headline: headline.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    pub fn image(mut self, image: impl Into<String>) -> Self {
+        self.images.push(image.into());
+        self
+    }
+
+    pub fn author(mut self, author: Person) -> Self {
+        self.authors.push(author);
+        self
+    }
+
+    pub fn publisher(mut self, publisher: Organization) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+
+    /// ISO 8601 publication date
+    pub fn date_published(mut self, date: impl Into<String>) -> Self {
+        self.date_published = Some(date.into());
+        self
+    }
+
+    /// ISO 8601 modification date
+    pub fn date_modified(mut self, date: impl Into<String>) -> Self {
+        self.date_modified = Some(date.into());
+        self
+    }
+}
+
+impl Schema for Article {
+    const TYPE: &'static str = "Article";
+
+    fn to_json_ld(&self) -> Value {
+        let mut map = Map::new();
+        map.insert("@type".to_string(), Value::String(Self::TYPE.to_string()));
+        map.insert("headline".to_string(), Value::String(self.headline.clone()));
+        if let Some(description) = &self.description {
+            map.insert("description".to_string(), Value::String(description.clone()));
+        }
+        if let Some(url) = &self.url {
+            map.insert("url".to_string(), Value::String(url.clone()));
+        }
+        if !self.images.is_empty() {
+            map.insert("image".to_string(), serde_json::json!(self.images));
+        }
+        if !self.authors.is_empty() {
+            let authors = self.authors.iter().map(Schema::to_json_ld).collect();
+            map.insert("author".to_string(), Value::Array(authors));
+        }
+        if let Some(publisher) = &self.publisher {
+            map.insert("publisher".to_string(), publisher.to_json_ld());
+        }
+        if let Some(date) = &self.date_published {
+            map.insert("datePublished".to_string(), Value::String(date.clone()));
+        }
+        if let Some(date) = &self.date_modified {
+            map.insert("dateModified".to_string(), Value::String(date.clone()));
+        }
+        Value::Object(map)
+    }
+}
+
+/// Availability of a [`Product`] offer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Availability {
+    InStock,
+    OutOfStock,
+    PreOrder,
+    Discontinued,
+}
+
+impl Availability {
+    fn as_url(&self) -> &'static str {
+        match self {
+            Availability::InStock => "https://schema.org/InStock",
+            Availability::OutOfStock => "https://schema.org/OutOfStock",
+            Availability::PreOrder => "https://schema.org/PreOrder",
+            Availability::Discontinued => "https://schema.org/Discontinued",
+        }
+    }
+}
+
+/// schema.org `Offer`
+#[derive(Clone, Debug)]
+pub struct Offer {
+    pub price: String,
+    pub price_currency: String,
+    pub availability: Option<Availability>,
+    pub url: Option<String>,
+}
+
+impl Offer {
+    /// Create an offer; `currency` is an ISO 4217 code such as `"USD"`.
+    pub fn new(price: impl Into<String>, currency: impl Into<String>) -> Self {
+        Self {
+            price: price.into(),
+            price_currency: currency.into(),
+            availability: None,
+            url: None,
+        }
+    }
+
+    pub fn availability(mut self, availability: Availability) -> Self {
+        self.availability = Some(availability);
+        self
+    }
+
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+}
+
+impl Schema for Offer {
+    const TYPE: &'static str = "Offer";
+
+    fn to_json_ld(&self) -> Value {
+        let mut map = Map::new();
+        map.insert("@type".to_string(), Value::String(Self::TYPE.to_string()));
+        map.insert("price".to_string(), Value::String(self.price.clone()));
+        map.insert("priceCurrency".to_string(), Value::String(self.price_currency.clone()));
+        if let Some(availability) = self.availability {
+            map.insert("availability".to_string(), Value::String(availability.as_url().to_string()));
+        }
+        if let Some(url) = &self.url {
+            map.insert("url".to_string(), Value::String(url.clone()));
+        }
+        Value::Object(map)
+    }
+}
+
+/// schema.org `Product`
+#[derive(Clone, Debug, Default)]
+pub struct Product {
+    pub name: String,
+    pub description: Option<String>,
+    pub sku: Option<String>,
+    pub brand: Option<String>,
+    pub images: Vec<String>,
+    pub offers: Vec<Offer>,
+}
+
+impl Product {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn sku(mut self, sku: impl Into<String>) -> Self {
+        self.sku = Some(sku.into());
+        self
+    }
+
+    pub fn brand(mut self, brand: impl Into<String>) -> Self {
+        self.brand = Some(brand.into());
+        self
+    }
+
+    pub fn image(mut self, image: impl Into<String>) -> Self {
+        self.images.push(image.into());
+        self
+    }
+
+    pub fn offer(mut self, offer: Offer) -> Self {
+        self.offers.push(offer);
+        self
+    }
+}
+
+impl Schema for Product {
+    const TYPE: &'static str = "Product";
+
+    fn to_json_ld(&self) -> Value {
+        let mut map = Map::new();
+        map.insert("@type".to_string(), Value::String(Self::TYPE.to_string()));
+        map.insert("name".to_string(), Value::String(self.name.clone()));
+        if let Some(description) = &self.description {
+            map.insert("description".to_string(), Value::String(description.clone()));
+        }
+        if let Some(sku) = &self.sku {
+            map.insert("sku".to_string(), Value::String(sku.clone()));
+        }
+        if let Some(brand) = &self.brand {
+            map.insert(
+                "brand".to_string(),
+                serde_json::json!({ "@type": "Brand", "name": brand }),
+            );
+        }
+        if !self.images.is_empty() {
+            map.insert("image".to_string(), serde_json::json!(self.images));
+        }
+        match self.offers.len() {
+            0 => {}
+            1 => {
+                map.insert("offers".to_string(), self.offers[0].to_json_ld());
+            }
+            _ => {
+                let offers = self.offers.iter().map(Schema::to_json_ld).collect();
+                map.insert("offers".to_string(), Value::Array(offers));
+            }
+        }
+        Value::Object(map)
+    }
+}
+
+/// schema.org `BreadcrumbList`
+#[derive(Clone, Debug, Default)]
+pub struct BreadcrumbList {
+    items: Vec<(String, String)>,
+}
+
+impl BreadcrumbList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a crumb; positions are assigned in insertion order.
+    pub fn item(mut self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        self.items.push((name.into(), url.into()));
+        self
+    }
+}
+
+impl Schema for BreadcrumbList {
+    const TYPE: &'static str = "BreadcrumbList";
+
+    fn to_json_ld(&self) -> Value {
+        let items: Vec<Value> = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, (name, url))| {
+                serde_json::json!({
+                    "@type": "ListItem",
+                    "position": i + 1,
+                    "name": name,
+                    "item": url,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "@type": Self::TYPE,
+            "itemListElement": items,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::with_meta_context;
+
+    #[test]
+    fn test_json_ld_registers_script() {
+        let (_, ctx) = with_meta_context(|| {
+            JsonLd::new(Organization::new("Acme").url("https://acme.test"));
+        });
+
+        let script = &ctx.script_tags[0];
+        assert_eq!(script.r#type.as_deref(), Some("application/ld+json"));
+        let content = script.content.as_deref().unwrap();
+        assert!(content.contains(r#""@context":"https://schema.org""#));
+        assert!(content.contains(r#""@type":"Organization""#));
+    }
+
+    #[test]
+    fn test_json_ld_escapes_script_breakout() {
+        let ld = JsonLd::new(Article::new("</script><script>alert(1)</script>"));
+        let html = ld.to_html();
+
+        assert_eq!(html.matches("</script>").count(), 1);
+        assert!(html.contains("\\u003c/script\\u003e"));
+
+        let json = html
+            .trim_start_matches(r#"<script type="application/ld+json">"#)
+            .trim_end_matches("</script>");
+        let parsed: Value = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed["headline"], "</script><script>alert(1)</script>");
+    }
+
+    #[test]
+    fn test_breadcrumb_positions() {
+        let value = BreadcrumbList::new()
+            .item("Home", "https://example.com/")
+            .item("Blog", "https://example.com/blog")
+            .to_json_ld();
+
+        assert_eq!(value["itemListElement"][1]["position"], 2);
+        assert_eq!(value["itemListElement"][1]["name"], "Blog");
+    }
+
+    #[test]
+    fn test_product_offer() {
+        let value = Product::new("Widget")
+            .offer(Offer::new("9.99", "USD").availability(Availability::InStock))
+            .to_json_ld();
+
+        assert_eq!(value["offers"]["priceCurrency"], "USD");
+        assert_eq!(value["offers"]["availability"], "https://schema.org/InStock");
+    }
+}
@@ -23,24 +23,178 @@
 //!     }
 //! }
 //! ```
+//!
+//! Structured data for search engines is available through [`JsonLd`] and the
+//! typed schema.org builders in [`json_ld`].
 
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
 use std::rc::Rc;
+use std::task::{Context as TaskContext, Poll};
 
-use crate::reactive::Signal;
+use crate::reactive::{Memo, Signal};
 use crate::view::{View, IntoView};
 
+pub mod json_ld;
+pub mod seo;
+pub mod stylesheet;
+
+pub use seo::Seo;
+pub use stylesheet::{set_asset_manifest, AssetManifest, Stylesheet};
+pub use json_ld::{
+    Article, Availability, BreadcrumbList, JsonLd, Offer, Organization, Person, Product, Schema,
+};
+
+// =============================================================================
+// Reactive Values
+// =============================================================================
+
+/// A head tag value that is either fixed or derived from signals.
+///
+/// Dynamic values are read once for SSR and, in the browser, re-applied to the
+/// existing `<head>` tag whenever the signals they read change.
+///
+/// # Example
+/// ```rust
+/// let page = Signal::new("Home".to_string());
+/// Title::new(page.clone());
+/// Title::dynamic(move || format!("{} unread", unread.get()));
+/// ```
+#[derive(Clone)]
+pub enum MetaValue {
+    /// A fixed value
+    Static(String),
+    /// A value recomputed when its reactive dependencies change
+    Dynamic(Rc<dyn Fn() -> String>),
+}
+
+impl MetaValue {
+    /// Create a value from a reactive closure.
+    pub fn dynamic(f: impl Fn() -> String + 'static) -> Self {
+        MetaValue::Dynamic(Rc::new(f))
+    }
+
+    /// Get the current value, tracking reads if in a reactive context.
+    pub fn get(&self) -> String {
+        match self {
+            MetaValue::Static(s) => s.clone(),
+            MetaValue::Dynamic(f) => f(),
+        }
+    }
+
+    /// Whether the value can change after construction.
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self, MetaValue::Dynamic(_))
+    }
+}
+
+impl Default for MetaValue {
+    fn default() -> Self {
+        MetaValue::Static(String::new())
+    }
+}
+
+impl std::fmt::Debug for MetaValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetaValue::Static(s) => f.debug_tuple("Static").field(s).finish(),
+            MetaValue::Dynamic(_) => f.write_str("Dynamic(..)"),
+        }
+    }
+}
+
+impl From<String> for MetaValue {
+    fn from(s: String) -> Self {
+        MetaValue::Static(s)
+    }
+}
+
+impl From<&str> for MetaValue {
+    fn from(s: &str) -> Self {
+        MetaValue::Static(s.to_string())
+    }
+}
+
+impl From<&String> for MetaValue {
+    fn from(s: &String) -> Self {
+        MetaValue::Static(s.clone())
+    }
+}
+
+impl From<Signal<String>> for MetaValue {
+    fn from(signal: Signal<String>) -> Self {
+        MetaValue::dynamic(move || signal.get())
+    }
+}
+
+impl From<Memo<String>> for MetaValue {
+    fn from(memo: Memo<String>) -> Self {
+        MetaValue::dynamic(move || memo.get())
+    }
+}
+
 // =============================================================================
 // Meta Context (for SSR collection)
 // =============================================================================
 
 thread_local! {
-    static META_CONTEXT: RefCell<MetaContext> = RefCell::new(MetaContext::new());
+    /// Stack of active meta contexts. The bottom entry is the ambient context
+    /// used outside any render scope (e.g. on the client); every
+    /// [`with_meta_context`] call pushes its own entry so concurrent and
+    /// nested renders never see each other's head tags.
+    static META_STACK: RefCell<Vec<MetaContext>> = RefCell::new(vec![MetaContext::new()]);
+}
+
+/// Run `f` against the innermost meta context.
+fn with_current<R>(f: impl FnOnce(&mut MetaContext) -> R) -> R {
+    META_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        if stack.is_empty() {
+            stack.push(MetaContext::new());
+        }
+        f(stack.last_mut().unwrap())
+    })
+}
+
+/// Pops the scope pushed by [`with_meta_context`], even if the render panics.
+struct ScopeGuard {
+    depth: usize,
+}
+
+impl ScopeGuard {
+    fn push(context: MetaContext) -> Self {
+        let depth = META_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            stack.push(context);
+            stack.len()
+        });
+        ScopeGuard { depth }
+    }
+
+    fn pop(self) -> MetaContext {
+        let context = META_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            debug_assert_eq!(stack.len(), self.depth, "meta scopes popped out of order");
+            stack.pop().unwrap_or_default()
+        });
+        std::mem::forget(self);
+        context
+    }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        META_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            stack.truncate(self.depth - 1);
+        });
+    }
 }
 
 /// Context for collecting head elements during SSR.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct MetaContext {
     /// Document title
     pub title: Option<String>,
@@ -169,29 +323,67 @@ impl MetaContext {
     }
 }
 
-/// Get the current meta context.
+/// Get a snapshot of the current meta context.
 pub fn use_meta_context() -> MetaContext {
-    META_CONTEXT.with(|ctx| {
-        let borrowed = ctx.borrow();
-        MetaContext {
-            title: borrowed.title.clone(),
-            title_template: borrowed.title_template.clone(),
-            meta_tags: borrowed.meta_tags.clone(),
-            link_tags: borrowed.link_tags.clone(),
-            style_tags: borrowed.style_tags.clone(),
-            script_tags: borrowed.script_tags.clone(),
-            html_attrs: borrowed.html_attrs.clone(),
-            body_attrs: borrowed.body_attrs.clone(),
-        }
-    })
+    with_current(|ctx| ctx.clone())
 }
 
-/// Run a function with meta context, collecting all head elements.
+/// Run a function in a fresh meta context, collecting all head elements.
+///
+/// Each call gets its own scope, so renders running in parallel on a
+/// multi-threaded server, or nested inside each other, are isolated.
+///
+/// # Example
+/// ```rust
+/// let (html, meta) = with_meta_context(|| render_to_string(|| view! { <App /> }));
+/// let head = meta.render_to_string();
+/// ```
 pub fn with_meta_context<R>(f: impl FnOnce() -> R) -> (R, MetaContext) {
-    META_CONTEXT.with(|ctx| ctx.borrow_mut().clear());
+    let guard = ScopeGuard::push(MetaContext::new());
     let result = f();
-    let context = use_meta_context();
-    (result, context)
+    (result, guard.pop())
+}
+
+/// Async version of [`with_meta_context`] for streaming renders.
+///
+/// The scope is entered on every poll and left before yielding, so the future
+/// may hop between worker threads and interleave with other requests.
+pub fn with_meta_context_async<F>(future: F) -> MetaScoped<F>
+where
+    F: Future,
+{
+    MetaScoped {
+        inner: Box::pin(future),
+        context: Some(MetaContext::new()),
+    }
+}
+
+/// Future returned by [`with_meta_context_async`].
+pub struct MetaScoped<F: Future> {
+    inner: Pin<Box<F>>,
+    context: Option<MetaContext>,
+}
+
+impl<F: Future> Future for MetaScoped<F> {
+    type Output = (F::Output, MetaContext);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        let context = self
+            .context
+            .take()
+            .expect("MetaScoped polled after completion");
+        let guard = ScopeGuard::push(context);
+        let poll = self.inner.as_mut().poll(cx);
+        let context = guard.pop();
+
+        match poll {
+            Poll::Ready(output) => Poll::Ready((output, context)),
+            Poll::Pending => {
+                self.context = Some(context);
+                Poll::Pending
+            }
+        }
+    }
 }
 
 // =============================================================================
@@ -235,6 +427,7 @@ impl MetaTag {
 pub struct LinkTag {
     pub rel: String,
     pub href: Option<String>,
+    pub r#as: Option<String>,
     pub r#type: Option<String>,
     pub media: Option<String>,
     pub sizes: Option<String>,
@@ -249,6 +442,9 @@ impl LinkTag {
         if let Some(href) = &self.href {
             attrs.push(format!("href=\"{}\"", escape_attr(href)));
         }
+        if let Some(as_) = &self.r#as {
+            attrs.push(format!("as=\"{}\"", escape_attr(as_)));
+        }
         if let Some(t) = &self.r#type {
             attrs.push(format!("type=\"{}\"", escape_attr(t)));
         }
@@ -369,23 +565,41 @@ impl ScriptTag {
 /// }
 /// ```
 pub struct Title {
-    text: String,
+    text: MetaValue,
 }
 
 impl Title {
-    pub fn new(text: impl Into<String>) -> Self {
+    /// Set the title from a string, signal, or memo.
+    pub fn new(text: impl Into<MetaValue>) -> Self {
         let text = text.into();
-        META_CONTEXT.with(|ctx| ctx.borrow_mut().set_title(text.clone()));
+        with_current(|ctx| ctx.set_title(text.get()));
 
         #[cfg(target_arch = "wasm32")]
         {
-            if let Some(document) = web_sys::window().and_then(|w| w.document()) {
-                document.set_title(&text);
-            }
+            head::bind("title".to_string(), text.clone(), |title| {
+                let template = with_current(|ctx| ctx.title_template.clone());
+                let formatted = match template {
+                    Some(template) => template.replace("%s", title),
+                    None => title.to_string(),
+                };
+                if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+                    document.set_title(&formatted);
+                }
+            });
         }
 
         Self { text }
     }
+
+    /// Set the title from a reactive closure.
+    pub fn dynamic(f: impl Fn() -> String + 'static) -> Self {
+        Self::new(MetaValue::dynamic(f))
+    }
+
+    /// The current title text, before the title template is applied.
+    pub fn text(&self) -> String {
+        self.text.get()
+    }
 }
 
 impl IntoView for Title {
@@ -410,7 +624,7 @@ pub struct TitleTemplate {
 impl TitleTemplate {
     pub fn new(template: impl Into<String>) -> Self {
         let template = template.into();
-        META_CONTEXT.with(|ctx| ctx.borrow_mut().title_template = Some(template.clone()));
+        with_current(|ctx| ctx.title_template = Some(template.clone()));
         Self { template }
     }
 }
@@ -433,6 +647,7 @@ impl IntoView for TitleTemplate {
 /// ```
 pub struct Meta {
     tag: MetaTag,
+    content: MetaValue,
 }
 
 impl Meta {
@@ -445,6 +660,7 @@ impl Meta {
                 charset: None,
                 http_equiv: None,
             },
+            content: MetaValue::default(),
         }
     }
 
@@ -458,11 +674,17 @@ impl Meta {
         self
     }
 
-    pub fn content(mut self, content: impl Into<String>) -> Self {
-        self.tag.content = content.into();
+    /// Set the content from a string, signal, or memo.
+    pub fn content(mut self, content: impl Into<MetaValue>) -> Self {
+        self.content = content.into();
         self
     }
 
+    /// Set the content from a reactive closure.
+    pub fn content_dynamic(self, f: impl Fn() -> String + 'static) -> Self {
+        self.content(MetaValue::dynamic(f))
+    }
+
     pub fn charset(mut self, charset: impl Into<String>) -> Self {
         self.tag.charset = Some(charset.into());
         self
@@ -473,24 +695,17 @@ impl Meta {
         self
     }
 
-    pub fn build(self) -> Self {
-        META_CONTEXT.with(|ctx| ctx.borrow_mut().add_meta(self.tag.clone()));
+    pub fn build(mut self) -> Self {
+        self.tag.content = self.content.get();
+        with_current(|ctx| ctx.add_meta(self.tag.clone()));
 
         #[cfg(target_arch = "wasm32")]
         {
-            // Update DOM
-            if let Some(document) = web_sys::window().and_then(|w| w.document()) {
-                let head = document.head().unwrap();
-                // Remove existing and add new
-                let meta = document.create_element("meta").unwrap();
-                if let Some(name) = &self.tag.name {
-                    meta.set_attribute("name", name).ok();
-                }
-                if let Some(property) = &self.tag.property {
-                    meta.set_attribute("property", property).ok();
-                }
-                meta.set_attribute("content", &self.tag.content).ok();
-                head.append_child(&meta).ok();
+            // Reuse the existing tag for this name/property instead of appending duplicates
+            if let Some(meta) = head::upsert_meta(&self.tag) {
+                head::bind(head::meta_key(&self.tag), self.content.clone(), move |content| {
+                    meta.set_attribute("content", content).ok();
+                });
             }
         }
 
@@ -530,6 +745,7 @@ impl Link {
             tag: LinkTag {
                 rel: rel.into(),
                 href: None,
+                r#as: None,
                 r#type: None,
                 media: None,
                 sizes: None,
@@ -544,6 +760,12 @@ impl Link {
         self
     }
 
+    /// Destination for `rel="preload"` links, e.g. `"style"` or `"font"`.
+    pub fn r#as(mut self, as_: impl Into<String>) -> Self {
+        self.tag.r#as = Some(as_.into());
+        self
+    }
+
     pub fn r#type(mut self, t: impl Into<String>) -> Self {
         self.tag.r#type = Some(t.into());
         self
@@ -570,7 +792,7 @@ impl Link {
     }
 
     pub fn build(self) -> Self {
-        META_CONTEXT.with(|ctx| ctx.borrow_mut().add_link(self.tag.clone()));
+        with_current(|ctx| ctx.add_link(self.tag.clone()));
         self
     }
 }
@@ -603,7 +825,7 @@ impl Style {
             media: None,
             nonce: None,
         };
-        META_CONTEXT.with(|ctx| ctx.borrow_mut().add_style(tag.clone()));
+        with_current(|ctx| ctx.add_style(tag.clone()));
         Self { tag }
     }
 
@@ -707,7 +929,7 @@ impl Script {
     }
 
     pub fn build(self) -> Self {
-        META_CONTEXT.with(|ctx| ctx.borrow_mut().add_script(self.tag.clone()));
+        with_current(|ctx| ctx.add_script(self.tag.clone()));
         self
     }
 }
@@ -762,9 +984,7 @@ impl Html {
     }
 
     pub fn build(self) -> Self {
-        META_CONTEXT.with(|ctx| {
-            ctx.borrow_mut().html_attrs.extend(self.attrs.clone());
-        });
+        with_current(|ctx| ctx.html_attrs.extend(self.attrs.clone()));
         self
     }
 }
@@ -814,9 +1034,7 @@ impl Body {
     }
 
     pub fn build(self) -> Self {
-        META_CONTEXT.with(|ctx| {
-            ctx.borrow_mut().body_attrs.extend(self.attrs.clone());
-        });
+        with_current(|ctx| ctx.body_attrs.extend(self.attrs.clone()));
         self
     }
 }
@@ -833,6 +1051,94 @@ impl IntoView for Body {
     }
 }
 
+// =============================================================================
+// Browser Head Diffing
+// =============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod head {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use super::{MetaTag, MetaValue};
+    use crate::reactive::Effect;
+
+    /// Attribute marking tags managed by PhilJS
+    const MANAGED_ATTR: &str = "data-philjs-meta";
+
+    thread_local! {
+        /// Effects keeping dynamic head values in sync, keyed by tag identity.
+        /// Re-registering a key (e.g. after navigation) drops the previous effect.
+        static HEAD_EFFECTS: RefCell<HashMap<String, Effect>> = RefCell::new(HashMap::new());
+    }
+
+    /// Apply a value now and, if dynamic, whenever its dependencies change.
+    pub(super) fn bind(key: String, value: MetaValue, apply: impl Fn(&str) + 'static) {
+        match value {
+            MetaValue::Static(s) => {
+                HEAD_EFFECTS.with(|effects| effects.borrow_mut().remove(&key));
+                apply(&s);
+            }
+            MetaValue::Dynamic(f) => {
+                let effect = Effect::new(move || apply(&f()));
+                HEAD_EFFECTS.with(|effects| effects.borrow_mut().insert(key, effect));
+            }
+        }
+    }
+
+    /// Identity of a meta tag in the head
+    pub(super) fn meta_key(tag: &MetaTag) -> String {
+        if let Some(name) = &tag.name {
+            format!("meta[name={}]", name)
+        } else if let Some(property) = &tag.property {
+            format!("meta[property={}]", property)
+        } else if let Some(http_equiv) = &tag.http_equiv {
+            format!("meta[http-equiv={}]", http_equiv)
+        } else {
+            "meta[charset]".to_string()
+        }
+    }
+
+    /// Find the `<meta>` matching this tag's identity, creating it if missing.
+    pub(super) fn upsert_meta(tag: &MetaTag) -> Option<web_sys::Element> {
+        let document = web_sys::window()?.document()?;
+        let head = document.head()?;
+
+        let selector = if let Some(name) = &tag.name {
+            format!("meta[name=\"{}\"]", name.replace('"', "\\\""))
+        } else if let Some(property) = &tag.property {
+            format!("meta[property=\"{}\"]", property.replace('"', "\\\""))
+        } else if let Some(http_equiv) = &tag.http_equiv {
+            format!("meta[http-equiv=\"{}\"]", http_equiv.replace('"', "\\\""))
+        } else {
+            "meta[charset]".to_string()
+        };
+
+        let meta = match document.query_selector(&selector).ok().flatten() {
+            Some(existing) => existing,
+            None => {
+                let meta = document.create_element("meta").ok()?;
+                if let Some(name) = &tag.name {
+                    meta.set_attribute("name", name).ok();
+                }
+                if let Some(property) = &tag.property {
+                    meta.set_attribute("property", property).ok();
+                }
+                if let Some(http_equiv) = &tag.http_equiv {
+                    meta.set_attribute("http-equiv", http_equiv).ok();
+                }
+                if let Some(charset) = &tag.charset {
+                    meta.set_attribute("charset", charset).ok();
+                }
+                head.append_child(&meta).ok()?;
+                meta
+            }
+        };
+        meta.set_attribute(MANAGED_ATTR, "").ok();
+        Some(meta)
+    }
+}
+
 // =============================================================================
 // Helpers
 // =============================================================================
@@ -856,7 +1162,7 @@ mod tests {
 
     #[test]
     fn test_meta_context() {
-        META_CONTEXT.with(|ctx| ctx.borrow_mut().clear());
+        with_current(|ctx| ctx.clear());
 
         Title::new("Test Page");
         Meta::new().name("description").content("Test description").build();
@@ -866,6 +1172,79 @@ mod tests {
         assert_eq!(ctx.meta_tags.len(), 1);
     }
 
+    #[test]
+    fn test_dynamic_title_reads_signal() {
+        with_current(|ctx| ctx.clear());
+
+        let page = Signal::new("Inbox".to_string());
+        let title = Title::new(page.clone());
+        assert_eq!(use_meta_context().title, Some("Inbox".to_string()));
+
+        page.set("Drafts".to_string());
+        assert_eq!(title.text(), "Drafts");
+    }
+
+    #[test]
+    fn test_dynamic_meta_content() {
+        with_current(|ctx| ctx.clear());
+
+        let count = Signal::new(3);
+        let c = count.clone();
+        Meta::new()
+            .name("description")
+            .content_dynamic(move || format!("{} items", c.get()))
+            .build();
+
+        let ctx = use_meta_context();
+        assert_eq!(ctx.meta_tags[0].content, "3 items");
+    }
+
+    #[test]
+    fn test_with_meta_context_is_isolated() {
+        let (_, outer) = with_meta_context(|| {
+            Title::new("Outer");
+            let (_, inner) = with_meta_context(|| {
+                Title::new("Inner");
+            });
+            assert_eq!(inner.title, Some("Inner".to_string()));
+        });
+        assert_eq!(outer.title, Some("Outer".to_string()));
+    }
+
+    #[test]
+    fn test_parallel_renders_are_isolated() {
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    let (_, ctx) = with_meta_context(|| {
+                        Title::new(format!("Page {}", i));
+                        Meta::new().name("description").content(format!("Desc {}", i)).build();
+                    });
+                    (i, ctx)
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let (i, ctx) = handle.join().unwrap();
+            assert_eq!(ctx.title, Some(format!("Page {}", i)));
+            assert_eq!(ctx.meta_tags.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_async_scope_collects_across_polls() {
+        let (_, ctx) = futures::executor::block_on(with_meta_context_async(async {
+            Title::new("Before");
+            futures::future::ready(()).await;
+            Meta::new().name("robots").content("noindex").build();
+        }));
+
+        assert_eq!(ctx.title, Some("Before".to_string()));
+        assert_eq!(ctx.meta_tags.len(), 1);
+        assert!(use_meta_context().meta_tags.iter().all(|t| t.content != "noindex"));
+    }
+
     #[test]
     fn test_meta_tag_html() {
         let tag = MetaTag {
@@ -886,6 +1265,7 @@ mod tests {
         let tag = LinkTag {
             rel: "stylesheet".to_string(),
             href: Some("/styles.css".to_string()),
+            r#as: None,
             r#type: None,
             media: None,
             sizes: None,
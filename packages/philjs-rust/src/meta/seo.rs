@@ -0,0 +1,367 @@
+//! High-level SEO component
+//!
+//! [`Seo`] expands a handful of page properties into the full set of title,
+//! description, canonical, Open Graph, and Twitter card tags.
+//!
+//! # Example
+//!
+//! ```rust
+//! use philjs::meta::*;
+//!
+//! view! {
+//!     <TitleTemplate template="%s | My Blog" />
+//! }
+//!
+//! Seo::new()
+//!     .title("Hello World")
+//!     .description("My first post")
+//!     .image("https://example.com/cover.png")
+//!     .canonical("https://example.com/posts/hello-world")
+//!     .build();
+//! ```
+
+use super::{with_current, LinkTag, MetaTag};
+use crate::view::{IntoView, View};
+
+/// Set title, description, canonical URL, and social cards in one place.
+///
+/// Defaults: `og:type` is `website`, and the Twitter card is
+/// `summary_large_image` when an image is set and `summary` otherwise. The
+/// social titles use the formatted title, so an active [`TitleTemplate`]
+/// applies to them as well.
+///
+/// [`TitleTemplate`]: super::TitleTemplate
+#[derive(Clone, Debug, Default)]
+pub struct Seo {
+    title: Option<String>,
+    title_template: Option<String>,
+    description: Option<String>,
+    image: Option<String>,
+    image_alt: Option<String>,
+    canonical: Option<String>,
+    site_name: Option<String>,
+    og_type: Option<String>,
+    locale: Option<String>,
+    twitter_card: Option<String>,
+    twitter_site: Option<String>,
+    twitter_creator: Option<String>,
+    keywords: Vec<String>,
+    robots: Option<String>,
+    extra_og: Vec<(String, String)>,
+    extra_twitter: Vec<(String, String)>,
+}
+
+impl Seo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Title template (e.g. `"%s | My Site"`); falls back to the active `TitleTemplate`.
+    pub fn title_template(mut self, template: impl Into<String>) -> Self {
+        self.title_template = Some(template.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Absolute URL of the social preview image.
+    pub fn image(mut self, image: impl Into<String>) -> Self {
+        self.image = Some(image.into());
+        self
+    }
+
+    pub fn image_alt(mut self, alt: impl Into<String>) -> Self {
+        self.image_alt = Some(alt.into());
+        self
+    }
+
+    /// Canonical URL; also used as `og:url`.
+    pub fn canonical(mut self, url: impl Into<String>) -> Self {
+        self.canonical = Some(url.into());
+        self
+    }
+
+    pub fn site_name(mut self, name: impl Into<String>) -> Self {
+        self.site_name = Some(name.into());
+        self
+    }
+
+    /// Open Graph type, e.g. `"article"`. Defaults to `"website"`.
+    pub fn og_type(mut self, og_type: impl Into<String>) -> Self {
+        self.og_type = Some(og_type.into());
+        self
+    }
+
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Twitter card type, e.g. `"summary"`.
+    pub fn twitter_card(mut self, card: impl Into<String>) -> Self {
+        self.twitter_card = Some(card.into());
+        self
+    }
+
+    /// `@handle` of the site.
+    pub fn twitter_site(mut self, handle: impl Into<String>) -> Self {
+        self.twitter_site = Some(handle.into());
+        self
+    }
+
+    /// `@handle` of the content author.
+    pub fn twitter_creator(mut self, handle: impl Into<String>) -> Self {
+        self.twitter_creator = Some(handle.into());
+        self
+    }
+
+    pub fn keywords(mut self, keywords: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.keywords.extend(keywords.into_iter().map(Into::into));
+        self
+    }
+
+    /// Robots directive, e.g. `"noindex, nofollow"`.
+    pub fn robots(mut self, robots: impl Into<String>) -> Self {
+        self.robots = Some(robots.into());
+        self
+    }
+
+    /// Add an extra Open Graph property; `og:` is prepended when missing.
+    pub fn og(mut self, property: impl Into<String>, content: impl Into<String>) -> Self {
+        let property = property.into();
+        let property = if property.starts_with("og:") {
+            property
+        } else {
+            format!("og:{}", property)
+        };
+        self.extra_og.push((property, content.into()));
+        self
+    }
+
+    /// Add an extra Twitter card tag; `twitter:` is prepended when missing.
+    pub fn twitter(mut self, name: impl Into<String>, content: impl Into<String>) -> Self {
+        let name = name.into();
+        let name = if name.starts_with("twitter:") {
+            name
+        } else {
+            format!("twitter:{}", name)
+        };
+        match name.as_str() {
+            "twitter:card" => self.twitter_card = Some(content.into()),
+            "twitter:site" => self.twitter_site = Some(content.into()),
+            "twitter:creator" => self.twitter_creator = Some(content.into()),
+            _ => self.extra_twitter.push((name, content.into())),
+        }
+        self
+    }
+
+    /// The title with the template applied.
+    pub fn formatted_title(&self, fallback_template: Option<&str>) -> Option<String> {
+        let title = self.title.as_ref()?;
+        let template = self.title_template.as_deref().or(fallback_template);
+        Some(match template {
+            Some(template) => template.replace("%s", title),
+            None => title.clone(),
+        })
+    }
+
+    /// All meta tags this component expands into.
+    pub fn meta_tags(&self, fallback_template: Option<&str>) -> Vec<MetaTag> {
+        let title = self.formatted_title(fallback_template);
+        let mut tags = Vec::new();
+
+        if let Some(description) = &self.description {
+            tags.push(name_tag("description", description));
+        }
+        if !self.keywords.is_empty() {
+            tags.push(name_tag("keywords", &self.keywords.join(", ")));
+        }
+        if let Some(robots) = &self.robots {
+            tags.push(name_tag("robots", robots));
+        }
+
+        // Open Graph
+        if let Some(title) = &title {
+            tags.push(property_tag("og:title", title));
+        }
+        if let Some(description) = &self.description {
+            tags.push(property_tag("og:description", description));
+        }
+        tags.push(property_tag("og:type", self.og_type.as_deref().unwrap_or("website")));
+        if let Some(canonical) = &self.canonical {
+            tags.push(property_tag("og:url", canonical));
+        }
+        if let Some(image) = &self.image {
+            tags.push(property_tag("og:image", image));
+            if let Some(alt) = &self.image_alt {
+                tags.push(property_tag("og:image:alt", alt));
+            }
+        }
+        if let Some(site_name) = &self.site_name {
+            tags.push(property_tag("og:site_name", site_name));
+        }
+        if let Some(locale) = &self.locale {
+            tags.push(property_tag("og:locale", locale));
+        }
+        for (property, content) in &self.extra_og {
+            tags.push(property_tag(property, content));
+        }
+
+        // Twitter
+        let card = self.twitter_card.as_deref().unwrap_or(if self.image.is_some() {
+            "summary_large_image"
+        } else {
+            "summary"
+        });
+        tags.push(name_tag("twitter:card", card));
+        if let Some(title) = &title {
+            tags.push(name_tag("twitter:title", title));
+        }
+        if let Some(description) = &self.description {
+            tags.push(name_tag("twitter:description", description));
+        }
+        if let Some(image) = &self.image {
+            tags.push(name_tag("twitter:image", image));
+            if let Some(alt) = &self.image_alt {
+                tags.push(name_tag("twitter:image:alt", alt));
+            }
+        }
+        if let Some(site) = &self.twitter_site {
+            tags.push(name_tag("twitter:site", site));
+        }
+        if let Some(creator) = &self.twitter_creator {
+            tags.push(name_tag("twitter:creator", creator));
+        }
+        for (name, content) in &self.extra_twitter {
+            tags.push(name_tag(name, content));
+        }
+
+        tags
+    }
+
+    /// The canonical `<link>`, if a canonical URL is set.
+    pub fn canonical_link(&self) -> Option<LinkTag> {
+        self.canonical.as_ref().map(|href| LinkTag {
+            rel: "canonical".to_string(),
+            href: Some(href.clone()),
+            r#as: None,
+            r#type: None,
+            media: None,
+            sizes: None,
+            crossorigin: None,
+            integrity: None,
+        })
+    }
+
+    /// Register every tag with the current meta context.
+    pub fn build(self) -> Self {
+        with_current(|ctx| {
+            if let Some(template) = &self.title_template {
+                ctx.title_template = Some(template.clone());
+            }
+            if let Some(title) = &self.title {
+                ctx.set_title(title.clone());
+            }
+            for tag in self.meta_tags(ctx.title_template.as_deref()) {
+                ctx.add_meta(tag);
+            }
+            if let Some(link) = self.canonical_link() {
+                ctx.link_tags.retain(|l| l.rel != "canonical");
+                ctx.add_link(link);
+            }
+        });
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(title) = &self.title {
+                super::Title::new(title.clone());
+            }
+            for tag in self.meta_tags(with_current(|ctx| ctx.title_template.clone()).as_deref()) {
+                if let Some(meta) = super::head::upsert_meta(&tag) {
+                    meta.set_attribute("content", &tag.content).ok();
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl IntoView for Seo {
+    fn into_view(self) -> View {
+        View::Empty
+    }
+}
+
+fn name_tag(name: &str, content: &str) -> MetaTag {
+    MetaTag {
+        name: Some(name.to_string()),
+        property: None,
+        content: content.to_string(),
+        charset: None,
+        http_equiv: None,
+    }
+}
+
+fn property_tag(property: &str, content: &str) -> MetaTag {
+    MetaTag {
+        name: None,
+        property: Some(property.to_string()),
+        content: content.to_string(),
+        charset: None,
+        http_equiv: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::{with_meta_context, TitleTemplate};
+
+    fn content<'a>(tags: &'a [MetaTag], key: &str) -> Option<&'a str> {
+        tags.iter()
+            .find(|t| t.name.as_deref() == Some(key) || t.property.as_deref() == Some(key))
+            .map(|t| t.content.as_str())
+    }
+
+    #[test]
+    fn test_seo_expands_social_tags() {
+        let (_, ctx) = with_meta_context(|| {
+            Seo::new()
+                .title("Post")
+                .description("About things")
+                .image("https://example.com/a.png")
+                .canonical("https://example.com/post")
+                .build();
+        });
+
+        let tags = &ctx.meta_tags;
+        assert_eq!(content(tags, "og:title"), Some("Post"));
+        assert_eq!(content(tags, "og:url"), Some("https://example.com/post"));
+        assert_eq!(content(tags, "og:type"), Some("website"));
+        assert_eq!(content(tags, "twitter:card"), Some("summary_large_image"));
+        assert_eq!(content(tags, "twitter:description"), Some("About things"));
+        assert_eq!(ctx.link_tags[0].rel, "canonical");
+        assert_eq!(ctx.title, Some("Post".to_string()));
+    }
+
+    #[test]
+    fn test_seo_uses_title_template() {
+        let (_, ctx) = with_meta_context(|| {
+            TitleTemplate::new("%s | Site");
+            Seo::new().title("Home").build();
+        });
+
+        assert_eq!(content(&ctx.meta_tags, "og:title"), Some("Home | Site"));
+        assert_eq!(content(&ctx.meta_tags, "twitter:card"), Some("summary"));
+        assert!(ctx.render_to_string().contains("<title>Home | Site</title>"));
+    }
+}
@@ -0,0 +1,257 @@
+//! Stylesheets resolved through the build manifest
+//!
+//! [`Stylesheet`] maps a source path such as `styles/app.css` to the
+//! content-hashed file emitted by the build, adds a preload hint, and
+//! registers each stylesheet only once per render so component libraries can
+//! declare their own CSS.
+//!
+//! # Example
+//!
+//! ```rust
+//! use philjs::meta::*;
+//!
+//! // At server startup
+//! set_asset_manifest(AssetManifest::from_json(include_str!("../dist/manifest.json"))?);
+//!
+//! // In any component
+//! Stylesheet::new("components/button.css").build();
+//! ```
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use super::{with_current, LinkTag};
+use crate::view::{IntoView, View};
+
+static ASSET_MANIFEST: RwLock<Option<AssetManifest>> = RwLock::new(None);
+
+/// Mapping from source asset paths to their hashed output paths.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssetManifest {
+    /// Public path prefix prepended to resolved files (e.g. `/assets/`)
+    pub base: String,
+    entries: HashMap<String, String>,
+}
+
+impl AssetManifest {
+    pub fn new() -> Self {
+        Self {
+            base: "/".to_string(),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Parse a manifest.
+    ///
+    /// Accepts both flat manifests (`{"app.css": "app.3f2a1c.css"}`) and
+    /// Vite-style manifests (`{"app.css": {"file": "app.3f2a1c.css"}}`).
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let mut manifest = Self::new();
+        if let serde_json::Value::Object(map) = value {
+            for (source, entry) in map {
+                let file = match entry {
+                    serde_json::Value::String(file) => Some(file),
+                    serde_json::Value::Object(obj) => obj
+                        .get("file")
+                        .and_then(|f| f.as_str())
+                        .map(str::to_string),
+                    _ => None,
+                };
+                if let Some(file) = file {
+                    manifest.entries.insert(normalize(&source), file);
+                }
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Set the public path prefix.
+    pub fn base(mut self, base: impl Into<String>) -> Self {
+        self.base = base.into();
+        self
+    }
+
+    /// Add a single entry.
+    pub fn insert(&mut self, source: impl AsRef<str>, file: impl Into<String>) {
+        self.entries.insert(normalize(source.as_ref()), file.into());
+    }
+
+    /// Resolve a source path to its public URL.
+    ///
+    /// Absolute URLs pass through unchanged; unknown paths resolve against
+    /// `base` without hashing.
+    pub fn resolve(&self, path: &str) -> String {
+        if path.contains("://") || path.starts_with("//") {
+            return path.to_string();
+        }
+        let key = normalize(path);
+        let file = self.entries.get(&key).map(String::as_str).unwrap_or(&key);
+        format!("{}/{}", self.base.trim_end_matches('/'), file.trim_start_matches('/'))
+    }
+}
+
+fn normalize(path: &str) -> String {
+    path.trim_start_matches("./").trim_start_matches('/').to_string()
+}
+
+/// Install the manifest used by [`Stylesheet`] for the whole process.
+pub fn set_asset_manifest(manifest: AssetManifest) {
+    if let Ok(mut slot) = ASSET_MANIFEST.write() {
+        *slot = Some(manifest);
+    }
+}
+
+/// Resolve a path through the installed manifest.
+pub fn resolve_asset(path: &str) -> String {
+    match ASSET_MANIFEST.read().ok().as_ref().and_then(|m| m.as_ref()) {
+        Some(manifest) => manifest.resolve(path),
+        None => AssetManifest::new().resolve(path),
+    }
+}
+
+/// Add a manifest-resolved stylesheet and preload hint to the document head.
+///
+/// # Example
+/// ```rust
+/// view! {
+///     <Stylesheet href="styles/app.css" />
+/// }
+/// ```
+pub struct Stylesheet {
+    path: String,
+    media: Option<String>,
+    preload: bool,
+    crossorigin: Option<String>,
+    integrity: Option<String>,
+}
+
+impl Stylesheet {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            media: None,
+            preload: true,
+            crossorigin: None,
+            integrity: None,
+        }
+    }
+
+    pub fn media(mut self, media: impl Into<String>) -> Self {
+        self.media = Some(media.into());
+        self
+    }
+
+    /// Emit a `<link rel="preload" as="style">` hint (default: on).
+    pub fn preload(mut self, preload: bool) -> Self {
+        self.preload = preload;
+        self
+    }
+
+    pub fn crossorigin(mut self, crossorigin: impl Into<String>) -> Self {
+        self.crossorigin = Some(crossorigin.into());
+        self
+    }
+
+    pub fn integrity(mut self, integrity: impl Into<String>) -> Self {
+        self.integrity = Some(integrity.into());
+        self
+    }
+
+    /// The resolved public URL.
+    pub fn href(&self) -> String {
+        resolve_asset(&self.path)
+    }
+
+    fn link(&self, rel: &str, href: &str) -> LinkTag {
+        LinkTag {
+            rel: rel.to_string(),
+            href: Some(href.to_string()),
+            r#as: if rel == "preload" { Some("style".to_string()) } else { None },
+            r#type: None,
+            media: self.media.clone(),
+            sizes: None,
+            crossorigin: self.crossorigin.clone(),
+            integrity: self.integrity.clone(),
+        }
+    }
+
+    pub fn build(self) -> Self {
+        let href = self.href();
+        let preload = self.preload.then(|| self.link("preload", &href));
+        let stylesheet = self.link("stylesheet", &href);
+
+        with_current(|ctx| {
+            let registered = ctx
+                .link_tags
+                .iter()
+                .any(|l| l.rel == "stylesheet" && l.href.as_deref() == Some(href.as_str()));
+            if registered {
+                return;
+            }
+            if let Some(preload) = preload {
+                // Preload hints go first so the browser starts fetching early
+                let at = ctx.link_tags.iter().take_while(|l| l.rel == "preload").count();
+                ctx.link_tags.insert(at, preload);
+            }
+            ctx.add_link(stylesheet);
+        });
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+                let selector = format!("link[rel=\"stylesheet\"][href=\"{}\"]", href.replace('"', "\\\""));
+                if document.query_selector(&selector).ok().flatten().is_none() {
+                    if let (Some(head), Ok(link)) = (document.head(), document.create_element("link")) {
+                        link.set_attribute("rel", "stylesheet").ok();
+                        link.set_attribute("href", &href).ok();
+                        if let Some(media) = &self.media {
+                            link.set_attribute("media", media).ok();
+                        }
+                        head.append_child(&link).ok();
+                    }
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl IntoView for Stylesheet {
+    fn into_view(self) -> View {
+        View::Empty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::with_meta_context;
+
+    #[test]
+    fn test_manifest_formats() {
+        let flat = AssetManifest::from_json(r#"{"styles/app.css": "styles/app.3f2a1c.css"}"#).unwrap();
+        assert_eq!(flat.resolve("styles/app.css"), "/styles/app.3f2a1c.css");
+
+        let vite = AssetManifest::from_json(r#"{"app.css": {"file": "assets/app.9b1e.css"}}"#)
+            .unwrap()
+            .base("/static/");
+        assert_eq!(vite.resolve("/app.css"), "/static/assets/app.9b1e.css");
+        assert_eq!(vite.resolve("missing.css"), "/static/missing.css");
+        assert_eq!(vite.resolve("https://cdn.test/x.css"), "https://cdn.test/x.css");
+    }
+
+    #[test]
+    fn test_stylesheet_preloads_and_dedupes() {
+        let (_, ctx) = with_meta_context(|| {
+            Stylesheet::new("button.css").build();
+            Stylesheet::new("button.css").build();
+            Stylesheet::new("card.css").preload(false).build();
+        });
+
+        let rels: Vec<_> = ctx.link_tags.iter().map(|l| l.rel.as_str()).collect();
+        assert_eq!(rels, vec!["preload", "stylesheet", "stylesheet"]);
+        assert!(ctx.link_tags[0].to_html().contains("as=\"style\""));
+    }
+}
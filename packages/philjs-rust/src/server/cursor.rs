@@ -0,0 +1,164 @@
+//! Opaque pagination cursors
+//!
+//! Keyset paginators (philjs-sqlx's `KeysetQuery`, philjs-seaorm's
+//! `CursorPaginator`) hand clients the key values of the last row on a page
+//! as a cursor. [`CursorCodec`] encodes them as
+//! `base64url(json).base64url(tag)`, where the tag also covers a scope
+//! string naming the listing, so a cursor issued by one listing is rejected
+//! by another.
+//!
+//! # Example
+//! ```rust
+//! use philjs::server::CursorCodec;
+//!
+//! let codec = CursorCodec::signed(b"server secret");
+//! let cursor = codec.encode("posts:created_at,id:desc", &(1_700_000_000, 42)).unwrap();
+//!
+//! let last: (i64, i64) = codec.decode("posts:created_at,id:desc", &cursor).unwrap();
+//! assert_eq!(last, (1_700_000_000, 42));
+//! assert!(codec.decode::<(i64, i64)>("users:id:asc", &cursor).is_none());
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Tag bytes kept in a cursor
+const TAG_LEN: usize = 16;
+
+/// Encodes and verifies opaque pagination cursors
+///
+/// A [`signed`](Self::signed) codec tags cursors with an HMAC, so clients
+/// cannot forge them. An [`unsigned`](Self::unsigned) codec (the default)
+/// tags them with a plain SHA-256 checksum that anyone can recompute: it
+/// catches truncated or hand-edited cursors, but a client can still craft a
+/// valid cursor for any key values. Only leave cursors unsigned when every
+/// key value is safe to expose and to seek to.
+#[derive(Clone, Default)]
+pub struct CursorCodec {
+    key: Option<Arc<[u8]>>,
+}
+
+impl fmt::Debug for CursorCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorCodec")
+            .field("signed", &self.is_signed())
+            .finish()
+    }
+}
+
+impl CursorCodec {
+    /// Sign cursors with an HMAC-SHA256 keyed by `key`
+    pub fn signed(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: Some(Arc::from(key.as_ref())),
+        }
+    }
+
+    /// Checksum cursors without a key; clients can forge these
+    pub fn unsigned() -> Self {
+        Self::default()
+    }
+
+    /// Whether cursors are signed
+    pub fn is_signed(&self) -> bool {
+        self.key.is_some()
+    }
+
+    fn mac(key: &[u8], scope: &str, payload: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(scope.as_bytes());
+        mac.update(b"\n");
+        mac.update(payload.as_bytes());
+        mac
+    }
+
+    fn checksum(scope: &str, payload: &str) -> Vec<u8> {
+        let mut digest = Sha256::new();
+        digest.update(scope.as_bytes());
+        digest.update(b"\n");
+        digest.update(payload.as_bytes());
+        digest.finalize()[..TAG_LEN].to_vec()
+    }
+
+    /// Encode `value` as a cursor for the listing named by `scope`
+    pub fn encode<T: Serialize + ?Sized>(&self, scope: &str, value: &T) -> serde_json::Result<String> {
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(value)?);
+        let tag = match &self.key {
+            Some(key) => Self::mac(key, scope, &payload).finalize().into_bytes()[..TAG_LEN].to_vec(),
+            None => Self::checksum(scope, &payload),
+        };
+        Ok(format!("{}.{}", payload, URL_SAFE_NO_PAD.encode(tag)))
+    }
+
+    /// Decode a cursor from [`encode`](Self::encode) with the same scope
+    ///
+    /// Returns `None` if the cursor is malformed, was issued for another
+    /// scope, or (when signed) was not signed with this key.
+    pub fn decode<T: DeserializeOwned>(&self, scope: &str, cursor: &str) -> Option<T> {
+        let (payload, tag) = cursor.split_once('.')?;
+        let tag = URL_SAFE_NO_PAD.decode(tag).ok()?;
+        if tag.len() != TAG_LEN {
+            return None;
+        }
+        let valid = match &self.key {
+            Some(key) => Self::mac(key, scope, payload).verify_truncated_left(&tag).is_ok(),
+            None => tag == Self::checksum(scope, payload),
+        };
+        if !valid {
+            return None;
+        }
+
+        let json = URL_SAFE_NO_PAD.decode(payload).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_roundtrip() {
+        let codec = CursorCodec::signed("secret");
+        let cursor = codec.encode("posts", &[7, 100]).unwrap();
+        assert_eq!(codec.decode::<Vec<i32>>("posts", &cursor), Some(vec![7, 100]));
+
+        assert!(codec.decode::<Vec<i32>>("users", &cursor).is_none());
+        assert!(CursorCodec::signed("other").decode::<Vec<i32>>("posts", &cursor).is_none());
+        assert!(CursorCodec::unsigned().decode::<Vec<i32>>("posts", &cursor).is_none());
+    }
+
+    #[test]
+    fn test_signed_rejects_forged_and_truncated_tags() {
+        let codec = CursorCodec::signed("secret");
+        let cursor = codec.encode("posts", &[7]).unwrap();
+        let (payload, tag) = cursor.split_once('.').unwrap();
+
+        // Another cursor's payload under this tag
+        let other = codec.encode("posts", &[8]).unwrap();
+        let (forged, _) = other.split_once('.').unwrap();
+        assert!(codec.decode::<Vec<i32>>("posts", &format!("{}.{}", forged, tag)).is_none());
+
+        // A one-byte prefix of the real tag must not pass as a truncated MAC
+        let short = URL_SAFE_NO_PAD.encode(&URL_SAFE_NO_PAD.decode(tag).unwrap()[..1]);
+        assert!(codec.decode::<Vec<i32>>("posts", &format!("{}.{}", payload, short)).is_none());
+        assert!(codec.decode::<Vec<i32>>("posts", payload).is_none());
+    }
+
+    #[test]
+    fn test_unsigned_cursors_can_be_recomputed() {
+        let codec = CursorCodec::unsigned();
+        assert!(!codec.is_signed());
+
+        // Anyone can mint a cursor an unsigned codec accepts
+        let minted = CursorCodec::default().encode("posts", &[1]).unwrap();
+        assert_eq!(codec.decode::<Vec<i32>>("posts", &minted), Some(vec![1]));
+        assert!(codec.decode::<Vec<i32>>("users", &minted).is_none());
+    }
+}
@@ -0,0 +1,145 @@
+//! Form-encoded server function input
+//!
+//! Integrations decode `application/x-www-form-urlencoded` bodies and query
+//! strings with [`url_encoded_to_json`] before handing them to
+//! [`call_server_fn`](super::call_server_fn), so every framework passes
+//! `#[action]` and `#[loader]` functions the same JSON.
+//!
+//! Form values are always strings: `zip=90210` stays `"90210"`, and
+//! `name=true` stays `"true"`. Repeated names (checkbox groups,
+//! multi-selects) are collected into an array in submission order.
+//! Multipart decoders build the same shape with [`insert_field`].
+//!
+//! [`wants_redirect`] decides whether a successful call should redirect a
+//! plain HTML form post back to its page instead of answering with JSON.
+//!
+//! # Example
+//! ```rust
+//! use philjs::server::form::url_encoded_to_json;
+//!
+//! let json = url_encoded_to_json(b"zip=90210&tag=a&tag=b").unwrap();
+//! assert_eq!(json, r#"{"tag":["a","b"],"zip":"90210"}"#);
+//! ```
+
+use serde_json::{Map, Value};
+
+use super::ServerError;
+
+/// Content type of URL-encoded form bodies
+pub const URL_ENCODED: &str = "application/x-www-form-urlencoded";
+
+/// Content type of multipart form bodies
+pub const MULTIPART: &str = "multipart/form-data";
+
+/// Whether a `Content-Type` header names a form body, URL-encoded or multipart
+pub fn is_form(content_type: &str) -> bool {
+    content_type.starts_with(URL_ENCODED) || content_type.starts_with(MULTIPART)
+}
+
+/// Whether a request is a browser submitting a form without JavaScript
+///
+/// Such submissions expect a page, not JSON, so integrations answer a
+/// successful call with a `303 See Other` back to the referring page.
+pub fn wants_redirect(content_type: &str, accept: &str) -> bool {
+    is_form(content_type) && accept.contains("text/html") && !accept.contains("application/json")
+}
+
+/// Insert a form value, collecting repeated names into an array
+pub fn insert_field(object: &mut Map<String, Value>, name: String, value: Value) {
+    match object.get_mut(&name) {
+        Some(Value::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            object.insert(name, value);
+        }
+    }
+}
+
+/// Convert URL-encoded pairs into a JSON object of strings
+///
+/// A single `input` field is taken as the whole JSON input, which lets a
+/// form submit structured input through a hidden field.
+pub fn url_encoded_to_json(encoded: &[u8]) -> Result<String, ServerError> {
+    let pairs: Vec<(String, String)> = serde_urlencoded::from_bytes(encoded)
+        .map_err(|e| ServerError::bad_request(format!("Invalid form data: {}", e)))?;
+
+    if let [(name, value)] = pairs.as_slice() {
+        if name == "input" {
+            return Ok(value.clone());
+        }
+    }
+
+    let mut object = Map::new();
+    for (name, value) in pairs {
+        insert_field(&mut object, name, Value::String(value));
+    }
+    Ok(Value::Object(object).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(encoded: &str) -> Value {
+        serde_json::from_str(&url_encoded_to_json(encoded.as_bytes()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_values_stay_strings() {
+        let value = decode("zip=90210&name=true&nickname=null&quoted=%22hi%22&empty=");
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "zip": "90210",
+                "name": "true",
+                "nickname": "null",
+                "quoted": "\"hi\"",
+                "empty": "",
+            })
+        );
+    }
+
+    #[test]
+    fn test_repeated_fields_collect_into_arrays() {
+        let value = decode("tag=a&name=ada&tag=b&tag=c");
+        assert_eq!(value, serde_json::json!({ "tag": ["a", "b", "c"], "name": "ada" }));
+    }
+
+    #[test]
+    fn test_single_input_field_is_raw_json() {
+        assert_eq!(url_encoded_to_json(b"input=%7B%22id%22%3A7%7D").unwrap(), r#"{"id":7}"#);
+        // Alongside other fields it is an ordinary string
+        assert_eq!(decode("input=7&x=1"), serde_json::json!({ "input": "7", "x": "1" }));
+    }
+
+    #[test]
+    fn test_wants_redirect() {
+        let html = "text/html,application/xhtml+xml";
+        assert!(wants_redirect(URL_ENCODED, html));
+        assert!(wants_redirect("application/x-www-form-urlencoded; charset=utf-8", html));
+        assert!(wants_redirect("multipart/form-data; boundary=XX", html));
+
+        // Script-driven submissions get JSON
+        assert!(!wants_redirect(URL_ENCODED, "application/json, text/html"));
+        assert!(!wants_redirect(MULTIPART, "*/*"));
+        assert!(!wants_redirect("application/json", html));
+        assert!(!wants_redirect("", html));
+    }
+
+    #[test]
+    fn test_deserializes_into_string_fields() {
+        #[derive(serde::Deserialize)]
+        struct Address {
+            zip: String,
+            tags: Vec<String>,
+        }
+
+        let json = url_encoded_to_json(b"zip=02139&tags=x&tags=y").unwrap();
+        let address: Address = serde_json::from_str(&json).unwrap();
+        assert_eq!(address.zip, "02139");
+        assert_eq!(address.tags, ["x", "y"]);
+    }
+}
@@ -495,6 +495,73 @@ pub mod actix_handler {
     }
 }
 
+/// Cloudflare Workers (and other fetch-event runtimes) handler for server
+/// functions
+///
+/// Enabled by the `worker` feature, which also exports `handle_server_fn`
+/// to JavaScript so the Worker shim written by `cargo philjs build
+/// --target=cloudflare` can route `/api/_sf/*` requests to it.
+#[cfg(feature = "worker")]
+pub mod worker_handler {
+    use super::*;
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Headers, Request, Response, ResponseInit};
+
+    /// Path prefix server functions are called on
+    pub const SERVER_FN_PREFIX: &str = "/api/_sf/";
+
+    /// Run the server function a fetch `Request` is addressed to
+    #[wasm_bindgen]
+    pub async fn handle_server_fn(request: Request) -> Result<Response, JsValue> {
+        let url = web_sys::Url::new(&request.url())?;
+        let path = url.pathname();
+        let Some(name) = path.strip_prefix(SERVER_FN_PREFIX) else {
+            return text_response(404, "Server function not found");
+        };
+
+        let body = JsFuture::from(request.array_buffer()?).await?;
+        let body = js_sys::Uint8Array::new(&body).to_vec();
+
+        // The handler future owns its arguments, so the registry lock is
+        // released before awaiting it
+        let call = match get_registry().read() {
+            Ok(registry) => registry.get(name).map(|server_fn| (server_fn.handler)(body)),
+            Err(_) => return text_response(500, "Server function registry poisoned"),
+        };
+        let Some(call) = call else {
+            return text_response(404, "Server function not found");
+        };
+
+        let (status, body) = match call.await {
+            Ok(result) => (200, result),
+            Err(e) => {
+                let status = match e {
+                    ServerError::Unauthorized => 401,
+                    ServerError::NotFound => 404,
+                    ServerError::Validation(_) => 400,
+                    _ => 500,
+                };
+                (status, serde_json::to_vec(&e).unwrap_or_default())
+            }
+        };
+
+        let headers = Headers::new()?;
+        headers.set("Content-Type", "application/json")?;
+        let init = ResponseInit::new();
+        init.set_status(status);
+        init.set_headers(&headers);
+        let body = js_sys::Uint8Array::from(body.as_slice());
+        Response::new_with_opt_buffer_source_and_init(Some(&body), &init)
+    }
+
+    fn text_response(status: u16, body: &str) -> Result<Response, JsValue> {
+        let init = ResponseInit::new();
+        init.set_status(status);
+        Response::new_with_opt_str_and_init(Some(body), &init)
+    }
+}
+
 // =============================================================================
 // Macros
 // =============================================================================
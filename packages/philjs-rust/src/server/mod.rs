@@ -17,8 +17,12 @@
 //! let user = get_user(123).await?;
 //! ```
 
+pub mod cursor;
+pub mod form;
 pub mod functions;
 
+pub use cursor::CursorCodec;
+
 use std::future::Future;
 use std::pin::Pin;
 
@@ -201,9 +205,14 @@ use std::collections::HashMap;
 
 type ServerFnHandler = Box<dyn Fn(String) -> Pin<Box<dyn Future<Output = Result<String, ServerError>> + Send>> + Send + Sync>;
 
-static SERVER_FN_REGISTRY: OnceLock<RwLock<HashMap<&'static str, ServerFnHandler>>> = OnceLock::new();
+struct RegisteredServerFn {
+    method: &'static str,
+    handler: ServerFnHandler,
+}
+
+static SERVER_FN_REGISTRY: OnceLock<RwLock<HashMap<&'static str, RegisteredServerFn>>> = OnceLock::new();
 
-fn get_server_registry() -> &'static RwLock<HashMap<&'static str, ServerFnHandler>> {
+fn get_server_registry() -> &'static RwLock<HashMap<&'static str, RegisteredServerFn>> {
     SERVER_FN_REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
 }
 
@@ -221,17 +230,160 @@ pub fn register_server_fn<F: ServerFn + 'static>() {
         })
     });
 
-    get_server_registry().write().unwrap().insert(F::PATH, handler);
+    get_server_registry().write().unwrap().insert(F::PATH, RegisteredServerFn {
+        method: F::METHOD,
+        handler,
+    });
 }
 
 /// Call a server function by path
 pub async fn call_server_fn(path: &str, input: String) -> Result<String, ServerError> {
-    let registry = get_server_registry().read().unwrap();
+    let future = {
+        let registry = get_server_registry().read().unwrap();
+        let entry = registry.get(path)
+            .ok_or_else(|| ServerError::not_found(format!("Server function not found: {}", path)))?;
+        (entry.handler)(input)
+    };
+
+    future.await
+}
 
-    let handler = registry.get(path)
-        .ok_or_else(|| ServerError::not_found(format!("Server function not found: {}", path)))?;
+/// Paths of every registered server function, sorted
+pub fn registered_server_fns() -> Vec<&'static str> {
+    let mut paths: Vec<_> = get_server_registry().read().unwrap().keys().copied().collect();
+    paths.sort_unstable();
+    paths
+}
 
-    handler(input).await
+/// HTTP method of a registered server function or streaming server function
+pub fn server_fn_method(path: &str) -> Option<&'static str> {
+    if let Some(entry) = get_server_registry().read().unwrap().get(path) {
+        return Some(entry.method);
+    }
+    get_streaming_registry().read().unwrap().get(path).map(|entry| entry.method)
+}
+
+// ============================================================================
+// Streaming Server Functions
+// ============================================================================
+
+/// Stream of serialized items produced by a streaming server function
+pub type ServerFnStream = Pin<Box<dyn futures::Stream<Item = Result<String, ServerError>> + Send>>;
+
+/// Trait for server functions that yield a stream of values
+pub trait StreamingServerFn: Sized {
+    /// The input type (arguments)
+    type Input: Serialize + for<'de> Deserialize<'de>;
+
+    /// The type of each streamed item
+    type Item: Serialize;
+
+    /// The URL path for this server function
+    const PATH: &'static str;
+
+    /// The HTTP method
+    const METHOD: &'static str = "POST";
+
+    /// Start the stream
+    fn run(input: Self::Input) -> Pin<Box<dyn Future<Output = ServerResult<Pin<Box<dyn futures::Stream<Item = ServerResult<Self::Item>> + Send>>>> + Send>>;
+}
+
+type StreamingServerFnHandler = Box<dyn Fn(String) -> Pin<Box<dyn Future<Output = Result<ServerFnStream, ServerError>> + Send>> + Send + Sync>;
+
+struct RegisteredStreamingServerFn {
+    method: &'static str,
+    handler: StreamingServerFnHandler,
+}
+
+static STREAMING_REGISTRY: OnceLock<RwLock<HashMap<&'static str, RegisteredStreamingServerFn>>> = OnceLock::new();
+
+fn get_streaming_registry() -> &'static RwLock<HashMap<&'static str, RegisteredStreamingServerFn>> {
+    STREAMING_REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Register a streaming server function
+pub fn register_streaming_server_fn<F: StreamingServerFn + 'static>() {
+    let handler = Box::new(move |input: String| -> Pin<Box<dyn Future<Output = Result<ServerFnStream, ServerError>> + Send>> {
+        Box::pin(async move {
+            use futures::StreamExt;
+
+            let input: F::Input = serde_json::from_str(&input)
+                .map_err(|e| ServerError::bad_request(format!("Invalid input: {}", e)))?;
+
+            let stream = F::run(input).await?.map(|item| {
+                item.and_then(|item| {
+                    serde_json::to_string(&item)
+                        .map_err(|e| ServerError::new(format!("Serialization error: {}", e)))
+                })
+            });
+            Ok(Box::pin(stream) as ServerFnStream)
+        })
+    });
+
+    get_streaming_registry().write().unwrap().insert(F::PATH, RegisteredStreamingServerFn {
+        method: F::METHOD,
+        handler,
+    });
+}
+
+/// Start a streaming server function by path
+pub async fn call_streaming_server_fn(path: &str, input: String) -> Result<ServerFnStream, ServerError> {
+    let future = {
+        let registry = get_streaming_registry().read().unwrap();
+        let entry = registry.get(path)
+            .ok_or_else(|| ServerError::not_found(format!("Server function not found: {}", path)))?;
+        (entry.handler)(input)
+    };
+
+    future.await
+}
+
+/// Paths of every registered streaming server function, sorted
+pub fn registered_streaming_server_fns() -> Vec<&'static str> {
+    let mut paths: Vec<_> = get_streaming_registry().read().unwrap().keys().copied().collect();
+    paths.sort_unstable();
+    paths
+}
+
+// ============================================================================
+// Request Context
+// ============================================================================
+
+thread_local! {
+    static SERVER_CONTEXT: std::cell::RefCell<Vec<ServerContext>> = std::cell::RefCell::new(Vec::new());
+}
+
+/// The context of the request currently being handled, if any
+pub fn server_context() -> Option<ServerContext> {
+    SERVER_CONTEXT.with(|stack| stack.borrow().last().cloned())
+}
+
+/// Run `future` with `context` available through [`server_context`]
+///
+/// Integrations call this around [`call_server_fn`] so server functions can
+/// read headers, cookies, and the client IP of the current request.
+pub fn with_server_context<F: Future>(context: ServerContext, future: F) -> ServerContextScoped<F> {
+    ServerContextScoped {
+        inner: Box::pin(future),
+        context,
+    }
+}
+
+/// Future returned by [`with_server_context`]
+pub struct ServerContextScoped<F: Future> {
+    inner: Pin<Box<F>>,
+    context: ServerContext,
+}
+
+impl<F: Future> Future for ServerContextScoped<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
+        SERVER_CONTEXT.with(|stack| stack.borrow_mut().push(self.context.clone()));
+        let poll = self.inner.as_mut().poll(cx);
+        SERVER_CONTEXT.with(|stack| stack.borrow_mut().pop());
+        poll
+    }
 }
 
 // ============================================================================
@@ -333,3 +485,67 @@ where
 // ============================================================================
 
 pub use crate::server_fn;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Whoami;
+
+    impl ServerFn for Whoami {
+        type Input = ();
+        type Output = Option<String>;
+        const PATH: &'static str = "/api/whoami";
+
+        fn run(_input: ()) -> Pin<Box<dyn Future<Output = ServerResult<Self::Output>> + Send>> {
+            let ip = server_context().and_then(|ctx| ctx.client_ip);
+            Box::pin(async move { Ok(ip) })
+        }
+    }
+
+    #[test]
+    fn test_server_context_is_scoped_to_call() {
+        register_server_fn::<Whoami>();
+        assert!(registered_server_fns().contains(&"/api/whoami"));
+
+        let mut ctx = ServerContext::new();
+        ctx.client_ip = Some("10.0.0.1".to_string());
+        let output = futures::executor::block_on(with_server_context(
+            ctx,
+            call_server_fn("/api/whoami", "null".to_string()),
+        ));
+
+        assert_eq!(output.unwrap(), "\"10.0.0.1\"");
+        assert!(server_context().is_none());
+        assert_eq!(server_fn_method("/api/whoami"), Some("POST"));
+    }
+
+    struct Countdown;
+
+    impl StreamingServerFn for Countdown {
+        type Input = u32;
+        type Item = u32;
+        const PATH: &'static str = "/api/countdown";
+
+        fn run(from: u32) -> Pin<Box<dyn Future<Output = ServerResult<Pin<Box<dyn futures::Stream<Item = ServerResult<u32>> + Send>>>> + Send>> {
+            Box::pin(async move {
+                let items = (0..=from).rev().map(Ok);
+                Ok(Box::pin(futures::stream::iter(items)) as Pin<Box<dyn futures::Stream<Item = _> + Send>>)
+            })
+        }
+    }
+
+    #[test]
+    fn test_streaming_server_fn() {
+        use futures::StreamExt;
+
+        register_streaming_server_fn::<Countdown>();
+        assert!(registered_streaming_server_fns().contains(&"/api/countdown"));
+
+        let items: Vec<_> = futures::executor::block_on(async {
+            let stream = call_streaming_server_fn("/api/countdown", "2".to_string()).await.unwrap();
+            stream.map(Result::unwrap).collect().await
+        });
+        assert_eq!(items, vec!["2", "1", "0"]);
+    }
+}
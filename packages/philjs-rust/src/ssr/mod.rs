@@ -25,10 +25,23 @@ where
     F: FnOnce() -> V,
     V: IntoView,
 {
+    crate::a11y::reset_ids();
     let view = f().into_view();
     view.to_html()
 }
 
+/// Render a view to an HTML string along with the head elements it registered.
+///
+/// Runs inside its own [`MetaContext`](crate::meta::MetaContext) scope, so
+/// concurrent renders never share `<Title>`/`<Meta>` output.
+pub fn render_to_string_with_meta<F, V>(f: F) -> (String, crate::meta::MetaContext)
+where
+    F: FnOnce() -> V,
+    V: IntoView,
+{
+    crate::meta::with_meta_context(|| render_to_string(f))
+}
+
 /// Render a view to a stream (for streaming SSR).
 ///
 /// # Example
@@ -45,6 +58,7 @@ where
     V: IntoView,
     W: Write,
 {
+    crate::a11y::reset_ids();
     let view = f().into_view();
     write!(writer, "{}", view.to_html())
 }
@@ -86,6 +100,7 @@ where
     use std::sync::Arc;
     use std::sync::atomic::{AtomicUsize, Ordering};
 
+    crate::a11y::reset_ids();
     let view = f().into_view();
     let suspense_id = Arc::new(AtomicUsize::new(0));
 
@@ -33,8 +33,9 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::time::Duration;
 
+use crate::dom::NodeRef;
 use crate::reactive::Signal;
-use crate::view::{View, IntoView};
+use crate::view::{View, IntoView, Reorder};
 
 /// Animation timing function
 #[derive(Clone, Debug, Default)]
@@ -66,6 +67,12 @@ pub struct AnimatedShowConfig {
     pub enter_class: Option<String>,
     /// CSS class to apply during exit animation
     pub exit_class: Option<String>,
+    /// Keyframes for the enter animation; defaults to the preset for
+    /// `enter_class`
+    pub enter_keyframes: Option<Keyframes>,
+    /// Keyframes for the exit animation; defaults to the preset for
+    /// `exit_class`
+    pub exit_keyframes: Option<Keyframes>,
     /// Duration of enter animation in milliseconds
     pub enter_duration_ms: u64,
     /// Duration of exit animation in milliseconds
@@ -78,6 +85,8 @@ pub struct AnimatedShowConfig {
     pub unmount_on_exit: bool,
     /// Initial visibility animation
     pub appear: bool,
+    /// Skip animations when the user prefers reduced motion
+    pub respect_reduced_motion: bool,
 }
 
 impl Default for AnimatedShowConfig {
@@ -85,16 +94,109 @@ impl Default for AnimatedShowConfig {
         Self {
             enter_class: None,
             exit_class: None,
+            enter_keyframes: None,
+            exit_keyframes: None,
             enter_duration_ms: 200,
             exit_duration_ms: 200,
             enter_easing: Easing::EaseOut,
             exit_easing: Easing::EaseIn,
             unmount_on_exit: true,
             appear: false,
+            respect_reduced_motion: true,
         }
     }
 }
 
+/// Keyframes for the Web Animations API, as passed to `Element.animate()`.
+///
+/// Property names use the API's camelCase form, e.g. `backgroundColor`.
+///
+/// # Example
+/// ```rust
+/// let grow = Keyframes::new()
+///     .frame([("transform", "scaleY(0)"), ("opacity", "0")])
+///     .frame([("transform", "scaleY(1)"), ("opacity", "1")]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Keyframes {
+    frames: Vec<Vec<(String, String)>>,
+}
+
+impl Keyframes {
+    /// Create with no frames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a frame of `(property, value)` pairs; frames are spaced evenly.
+    pub fn frame<K, V>(mut self, properties: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.frames
+            .push(properties.into_iter().map(|(k, v)| (k.into(), v.into())).collect());
+        self
+    }
+
+    /// The frames in order.
+    pub fn frames(&self) -> &[Vec<(String, String)>] {
+        &self.frames
+    }
+
+    /// The keyframes of a built-in animation class from [`ANIMATION_CSS`],
+    /// such as `philjs-fade-in`.
+    pub fn preset(class: &str) -> Option<Self> {
+        let fade = |from: &str, to: &str| {
+            Keyframes::new().frame([("opacity", from)]).frame([("opacity", to)])
+        };
+        let shift = |from: (&str, &str), to: (&str, &str)| {
+            Keyframes::new()
+                .frame([("transform", from.0), ("opacity", from.1)])
+                .frame([("transform", to.0), ("opacity", to.1)])
+        };
+        Some(match class {
+            "philjs-fade-in" => fade("0", "1"),
+            "philjs-fade-out" => fade("1", "0"),
+            "philjs-slide-in" => shift(("translateY(-10px)", "0"), ("translateY(0)", "1")),
+            "philjs-slide-out" => shift(("translateY(0)", "1"), ("translateY(10px)", "0")),
+            "philjs-scale-in" => shift(("scale(0.95)", "0"), ("scale(1)", "1")),
+            "philjs-scale-out" => shift(("scale(1)", "1"), ("scale(0.95)", "0")),
+            _ => return None,
+        })
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    fn to_js(&self) -> js_sys::Array {
+        self.frames
+            .iter()
+            .map(|frame| {
+                let object = js_sys::Object::new();
+                for (property, value) in frame {
+                    let _ = js_sys::Reflect::set(&object, &property.into(), &value.into());
+                }
+                object
+            })
+            .collect()
+    }
+}
+
+/// Whether the user asked for reduced motion with the
+/// `prefers-reduced-motion` media query; always `false` outside the browser.
+pub fn prefers_reduced_motion() -> bool {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        web_sys::window()
+            .and_then(|window| window.match_media("(prefers-reduced-motion: reduce)").ok().flatten())
+            .is_some_and(|query| query.matches())
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    {
+        false
+    }
+}
+
 /// Animation state for tracking transitions
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum AnimationState {
@@ -108,9 +210,14 @@ pub enum AnimationState {
     Exiting,
 }
 
+type Callback = Rc<dyn Fn()>;
+
 /// Animated conditional rendering component.
 ///
-/// Shows content with enter/exit animations.
+/// Shows content with enter/exit animations. In the browser the animations
+/// run through the Web Animations API, and exiting content stays in the DOM
+/// until its animation finishes. Classes without [`Keyframes`] fall back to
+/// CSS animations, which is also what server rendering emits.
 pub struct AnimatedShow<W, F, C>
 where
     W: Fn() -> bool + 'static,
@@ -127,6 +234,10 @@ where
     config: AnimatedShowConfig,
     /// Current animation state
     state: Signal<AnimationState>,
+    /// Called when an enter animation completes
+    on_entered: Option<Callback>,
+    /// Called when an exit animation completes and the content is gone
+    on_exited: Option<Callback>,
 }
 
 impl<W, C> AnimatedShow<W, fn() -> View, C>
@@ -142,6 +253,8 @@ where
             fallback: None,
             config: AnimatedShowConfig::default(),
             state: Signal::new(AnimationState::Hidden),
+            on_entered: None,
+            on_exited: None,
         }
     }
 }
@@ -164,6 +277,18 @@ where
         self
     }
 
+    /// Set enter keyframes, used instead of the enter class in the browser
+    pub fn enter_keyframes(mut self, keyframes: Keyframes) -> Self {
+        self.config.enter_keyframes = Some(keyframes);
+        self
+    }
+
+    /// Set exit keyframes, used instead of the exit class in the browser
+    pub fn exit_keyframes(mut self, keyframes: Keyframes) -> Self {
+        self.config.exit_keyframes = Some(keyframes);
+        self
+    }
+
     /// Set animation duration (for both enter and exit)
     pub fn duration_ms(mut self, ms: u64) -> Self {
         self.config.enter_duration_ms = ms;
@@ -195,6 +320,24 @@ where
         self
     }
 
+    /// Set whether to skip animations when the user prefers reduced motion
+    pub fn respect_reduced_motion(mut self, respect: bool) -> Self {
+        self.config.respect_reduced_motion = respect;
+        self
+    }
+
+    /// Run `f` when an enter animation completes
+    pub fn on_entered(mut self, f: impl Fn() + 'static) -> Self {
+        self.on_entered = Some(Rc::new(f));
+        self
+    }
+
+    /// Run `f` when an exit animation completes and the content is removed
+    pub fn on_exited(mut self, f: impl Fn() + 'static) -> Self {
+        self.on_exited = Some(Rc::new(f));
+        self
+    }
+
     /// Set fallback content
     pub fn with_fallback<NF: Fn() -> View + 'static>(self, fallback: NF) -> AnimatedShow<W, NF, C> {
         AnimatedShow {
@@ -203,9 +346,16 @@ where
             fallback: Some(fallback),
             config: self.config,
             state: self.state,
+            on_entered: self.on_entered,
+            on_exited: self.on_exited,
         }
     }
 
+    /// Get the animation configuration
+    pub fn config(&self) -> &AnimatedShowConfig {
+        &self.config
+    }
+
     /// Get the current animation state
     pub fn animation_state(&self) -> Signal<AnimationState> {
         self.state.clone()
@@ -271,11 +421,12 @@ where
         View::Element(crate::view::element::ElementBuilder::new("div")
             .attr("class", &animation_class)
             .attr("data-philjs-animate", if entering { "enter" } else { "exit" })
-            .attr("style", &format!(
+            .attr("style", format!(
                 "animation-duration: {}ms; animation-timing-function: {};",
                 duration,
                 easing.to_css()
             ))
+            .child(content)
             .build())
     }
 }
@@ -287,7 +438,236 @@ where
     C: Fn() -> View + 'static,
 {
     fn into_view(self) -> View {
-        self.render()
+        #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+        {
+            web::drive(self)
+        }
+
+        #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+        {
+            self.render()
+        }
+    }
+}
+
+/// Browser driver: a wrapper that stays mounted while its content is
+/// animated in and out with the Web Animations API.
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod web {
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::Animation;
+
+    use super::*;
+    use crate::dom::mount::render_view;
+    use crate::reactive::batch::untrack;
+    use crate::reactive::effect::watch;
+    use crate::view::Element;
+
+    /// `element.animate(keyframes, options)`; web-sys only binds it behind
+    /// `web_sys_unstable_apis`. `None` where the API is missing.
+    fn animate(element: &web_sys::Element, keyframes: &Keyframes, duration: u64, easing: &Easing) -> Option<Animation> {
+        use js_sys::{Function, Object, Reflect};
+
+        let options = Object::new();
+        let _ = Reflect::set(&options, &"duration".into(), &(duration as f64).into());
+        let _ = Reflect::set(&options, &"easing".into(), &easing.to_css().into());
+        let _ = Reflect::set(&options, &"fill".into(), &"both".into());
+
+        let animate = Reflect::get(element, &"animate".into()).ok()?.dyn_into::<Function>().ok()?;
+        animate
+            .call2(element, &keyframes.to_js(), &options)
+            .ok()?
+            .dyn_into::<Animation>()
+            .ok()
+    }
+
+    struct Driver<W, F, C>
+    where
+        W: Fn() -> bool + 'static,
+        F: Fn() -> View + 'static,
+        C: Fn() -> View + 'static,
+    {
+        show: AnimatedShow<W, F, C>,
+        wrapper: NodeRef,
+        running: RefCell<Option<Animation>>,
+    }
+
+    pub(super) fn drive<W, F, C>(show: AnimatedShow<W, F, C>) -> View
+    where
+        W: Fn() -> bool + 'static,
+        F: Fn() -> View + 'static,
+        C: Fn() -> View + 'static,
+    {
+        let driver = Rc::new(Driver {
+            show,
+            wrapper: NodeRef::new(),
+            running: RefCell::new(None),
+        });
+
+        let visible = untrack(|| (driver.show.when)());
+        let initial = if visible && driver.show.config.appear {
+            AnimationState::Entering
+        } else if visible {
+            AnimationState::Shown
+        } else {
+            AnimationState::Hidden
+        };
+        driver.show.state.set(initial);
+
+        let mut wrapper = Element::new("div")
+            .attr("data-philjs-animated-show", "")
+            .node_ref(driver.wrapper.clone())
+            .child(untrack(|| driver.content(visible)));
+        if !visible && !driver.show.config.unmount_on_exit {
+            wrapper = wrapper.attr("hidden", "");
+        }
+
+        if initial == AnimationState::Entering {
+            // Wait for the wrapper to be mounted
+            let appear = driver.clone();
+            let callback = Closure::once_into_js(move || appear.enter());
+            if let Some(window) = web_sys::window() {
+                let _ = window.request_animation_frame(callback.unchecked_ref());
+            }
+        }
+
+        let source = driver.clone();
+        let effect = watch(
+            move || (source.show.when)(),
+            move |visible, prev| {
+                if prev.is_some() {
+                    untrack(|| if visible { driver.enter() } else { driver.exit() });
+                }
+            },
+        );
+        // Lives as long as the page, like the DOM it animates
+        std::mem::forget(effect);
+
+        wrapper.into()
+    }
+
+    impl<W, F, C> Driver<W, F, C>
+    where
+        W: Fn() -> bool + 'static,
+        F: Fn() -> View + 'static,
+        C: Fn() -> View + 'static,
+    {
+        /// What the wrapper holds: the children, or the fallback once they
+        /// are unmounted.
+        fn content(&self, visible: bool) -> View {
+            if visible || !self.show.config.unmount_on_exit {
+                (self.show.children)()
+            } else if let Some(fallback) = &self.show.fallback {
+                fallback()
+            } else {
+                View::Empty
+            }
+        }
+
+        fn enter(self: &Rc<Self>) {
+            let Some(wrapper) = self.wrapper.get() else {
+                return;
+            };
+            if self.show.state.get_untracked() == AnimationState::Hidden {
+                if self.show.config.unmount_on_exit {
+                    wrapper.set_inner_html("");
+                    render_view(&self.content(true), &wrapper);
+                }
+                let _ = wrapper.remove_attribute("hidden");
+            }
+            self.show.state.set(AnimationState::Entering);
+
+            let this = self.clone();
+            self.play(&wrapper, true, move || {
+                if this.show.state.get_untracked() != AnimationState::Entering {
+                    return;
+                }
+                this.show.state.set(AnimationState::Shown);
+                if let Some(on_entered) = &this.show.on_entered {
+                    on_entered();
+                }
+            });
+        }
+
+        fn exit(self: &Rc<Self>) {
+            let Some(wrapper) = self.wrapper.get() else {
+                return;
+            };
+            if self.show.state.get_untracked() == AnimationState::Hidden {
+                return;
+            }
+            self.show.state.set(AnimationState::Exiting);
+
+            let this = self.clone();
+            let element = wrapper.clone();
+            self.play(&wrapper, false, move || {
+                if this.show.state.get_untracked() != AnimationState::Exiting {
+                    return;
+                }
+                if this.show.config.unmount_on_exit {
+                    element.set_inner_html("");
+                    render_view(&this.content(false), &element);
+                } else {
+                    let _ = element.set_attribute("hidden", "");
+                }
+                this.show.state.set(AnimationState::Hidden);
+                if let Some(on_exited) = &this.show.on_exited {
+                    on_exited();
+                }
+            });
+        }
+
+        /// Animate `wrapper` in or out, then call `done`; immediately when
+        /// motion is reduced or there is nothing to play.
+        fn play(&self, wrapper: &web_sys::Element, entering: bool, done: impl FnOnce() + 'static) {
+            if let Some(animation) = self.running.borrow_mut().take() {
+                animation.cancel();
+            }
+            let _ = wrapper.remove_attribute("class");
+            let _ = wrapper.remove_attribute("style");
+
+            let config = &self.show.config;
+            let (class, keyframes, duration, easing) = if entering {
+                (&config.enter_class, &config.enter_keyframes, config.enter_duration_ms, &config.enter_easing)
+            } else {
+                (&config.exit_class, &config.exit_keyframes, config.exit_duration_ms, &config.exit_easing)
+            };
+            if duration == 0 || (config.respect_reduced_motion && prefers_reduced_motion()) {
+                done();
+                return;
+            }
+
+            let keyframes = keyframes
+                .clone()
+                .or_else(|| class.as_deref().and_then(Keyframes::preset));
+            if let Some(keyframes) = keyframes {
+                if let Some(animation) = animate(wrapper, &keyframes, duration, easing) {
+                    animation.set_onfinish(Some(Closure::once_into_js(done).unchecked_ref()));
+                    *self.running.borrow_mut() = Some(animation);
+                } else {
+                    done();
+                }
+                return;
+            }
+
+            // CSS fallback: apply the class and wait out its duration
+            let Some(class) = class else {
+                done();
+                return;
+            };
+            let _ = wrapper.set_attribute("class", class);
+            let _ = wrapper.set_attribute(
+                "style",
+                &format!("animation-duration: {}ms; animation-timing-function: {};", duration, easing.to_css()),
+            );
+            let callback = Closure::once_into_js(done);
+            if let Some(window) = web_sys::window() {
+                let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                    callback.unchecked_ref(),
+                    duration as i32,
+                );
+            }
+        }
     }
 }
 
@@ -373,6 +753,10 @@ pub const ANIMATION_CSS: &str = r#"
 .philjs-slide-out { animation-name: philjs-slide-out; }
 .philjs-scale-in { animation-name: philjs-scale-in; }
 .philjs-scale-out { animation-name: philjs-scale-out; }
+
+@media (prefers-reduced-motion: reduce) {
+    [data-philjs-animate] { animation: none !important; }
+}
 "#;
 
 // =============================================================================
@@ -415,6 +799,191 @@ impl<C: Fn() -> View + 'static> IntoView for Presence<C> {
     }
 }
 
+// =============================================================================
+// List Reordering (FLIP)
+// =============================================================================
+
+/// An element's box in viewport coordinates
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rect {
+    /// Left edge
+    pub x: f64,
+    /// Top edge
+    pub y: f64,
+    /// Width
+    pub width: f64,
+    /// Height
+    pub height: f64,
+}
+
+/// Configuration for reorder animations
+#[derive(Clone, Debug)]
+pub struct FlipConfig {
+    /// Duration of the move in milliseconds
+    pub duration_ms: u64,
+    /// Easing function for the move
+    pub easing: Easing,
+    /// CSS class added to rows that were just added, e.g. `philjs-fade-in`
+    pub enter_class: Option<String>,
+}
+
+impl Default for FlipConfig {
+    fn default() -> Self {
+        Self {
+            duration_ms: 250,
+            easing: Easing::EaseInOut,
+            enter_class: None,
+        }
+    }
+}
+
+/// Animates a keyed [`For`](crate::view::For) list to its new order with
+/// the FLIP technique: rows are measured before and after the update, moved
+/// back to where they were with a transform, then transitioned into place.
+///
+/// The container's element children must be the list's rows. Removed rows
+/// disappear immediately.
+///
+/// # Example
+/// ```rust
+/// let list = NodeRef::new();
+/// view! {
+///     <ul node_ref=list.clone()>
+///         {For::new(move || items.get(), |item| item.id, |item| view! { <li>{item.name}</li> })
+///             .animate(use_flip(list).duration_ms(300).enter_class("philjs-fade-in"))}
+///     </ul>
+/// }
+/// ```
+#[derive(Clone)]
+pub struct FlipAnimation {
+    container: NodeRef,
+    config: FlipConfig,
+}
+
+/// Create a reorder animation for the list rendered inside `container`.
+pub fn use_flip(container: NodeRef) -> FlipAnimation {
+    FlipAnimation {
+        container,
+        config: FlipConfig::default(),
+    }
+}
+
+impl FlipAnimation {
+    /// Set the move duration
+    pub fn duration_ms(mut self, ms: u64) -> Self {
+        self.config.duration_ms = ms;
+        self
+    }
+
+    /// Set the move easing
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.config.easing = easing;
+        self
+    }
+
+    /// Set the class added to new rows
+    pub fn enter_class(mut self, class: impl Into<String>) -> Self {
+        self.config.enter_class = Some(class.into());
+        self
+    }
+
+    /// The animation configuration
+    pub fn config(&self) -> &FlipConfig {
+        &self.config
+    }
+
+    /// Animate `reorder`; called by [`For::animate`](crate::view::For::animate)
+    /// before the DOM is updated. Does nothing outside the browser.
+    pub fn play(&self, reorder: &Reorder) {
+        #[cfg(feature = "wasm")]
+        {
+            use wasm_bindgen::{closure::Closure, JsCast};
+
+            let Some(container) = self.container.get() else {
+                return;
+            };
+            let before = child_rects(&container);
+            let reorder = reorder.clone();
+            let config = self.config.clone();
+
+            let callback = Closure::once_into_js(move || {
+                let after = child_rects(&container);
+                let children = container.children();
+                let row = |index: usize| {
+                    children
+                        .item(index as u32)
+                        .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+                };
+                let moved: Vec<_> = flip_offsets(&reorder, &before, &after)
+                    .into_iter()
+                    .filter_map(|(index, dx, dy)| Some((row(index)?, dx, dy)))
+                    .collect();
+
+                // Invert: put each row back where it was
+                for (el, dx, dy) in &moved {
+                    let style = el.style();
+                    let _ = style.set_property("transition", "none");
+                    let _ = style.set_property("transform", &format!("translate({}px, {}px)", dx, dy));
+                }
+                // Force a layout so the inverted positions apply before playing
+                let _ = container.get_bounding_client_rect();
+                // Play: transition to the new positions
+                for (el, _, _) in &moved {
+                    let style = el.style();
+                    let _ = style.set_property(
+                        "transition",
+                        &format!("transform {}ms {}", config.duration_ms, config.easing.to_css()),
+                    );
+                    let _ = style.remove_property("transform");
+                }
+                if let Some(class) = &config.enter_class {
+                    for el in reorder.added.iter().filter_map(|&index| row(index)) {
+                        let _ = el.class_list().add_1(class);
+                    }
+                }
+            });
+            if let Some(window) = web_sys::window() {
+                let _ = window.request_animation_frame(callback.unchecked_ref());
+            }
+        }
+
+        #[cfg(not(feature = "wasm"))]
+        let _ = (&self.container, reorder);
+    }
+}
+
+#[cfg(feature = "wasm")]
+fn child_rects(container: &web_sys::Element) -> Vec<Rect> {
+    let children = container.children();
+    (0..children.length())
+        .filter_map(|i| children.item(i))
+        .map(|el| {
+            let rect = el.get_bounding_client_rect();
+            Rect {
+                x: rect.left(),
+                y: rect.top(),
+                width: rect.width(),
+                height: rect.height(),
+            }
+        })
+        .collect()
+}
+
+/// The translation that puts each moved row back at its old position, as
+/// `(new index, dx, dy)`, given row boxes before and after the update.
+/// Rows that didn't move on screen are left out.
+pub fn flip_offsets(reorder: &Reorder, before: &[Rect], after: &[Rect]) -> Vec<(usize, f64, f64)> {
+    reorder
+        .moved
+        .iter()
+        .filter_map(|&(from, to)| {
+            let (first, last) = (before.get(from)?, after.get(to)?);
+            let (dx, dy) = (first.x - last.x, first.y - last.y);
+            (dx != 0.0 || dy != 0.0).then_some((to, dx, dy))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,4 +1000,53 @@ mod tests {
         assert_eq!(Easing::Linear.to_css(), "linear");
         assert_eq!(Easing::EaseInOut.to_css(), "ease-in-out");
     }
+
+    #[test]
+    fn test_keyframes_preset() {
+        let fade_in = Keyframes::preset("philjs-fade-in").unwrap();
+        assert_eq!(fade_in, Keyframes::new().frame([("opacity", "0")]).frame([("opacity", "1")]));
+
+        let slide_out = Keyframes::preset("philjs-slide-out").unwrap();
+        assert_eq!(slide_out.frames()[1], [("transform".to_string(), "translateY(10px)".to_string()), ("opacity".to_string(), "0".to_string())]);
+
+        assert!(Keyframes::preset("my-bounce").is_none());
+        assert!(!prefers_reduced_motion());
+    }
+
+    #[test]
+    fn test_animated_show_renders_content() {
+        let show = fade(|| true, || View::Text(crate::view::text::Text::new("Hello")))
+            .on_entered(|| {});
+        let html = show.into_view().to_html();
+        assert!(html.contains(r#"class="philjs-fade-in""#));
+        assert!(html.contains(r#"data-philjs-animate="enter""#));
+        assert!(html.contains(">Hello</div>"));
+
+        let hidden = fade(|| false, || View::Text(crate::view::text::Text::new("Hello")));
+        assert_eq!(hidden.into_view().to_html(), "");
+    }
+
+    #[test]
+    fn test_flip_offsets() {
+        let rect = |y| Rect { x: 0.0, y, width: 100.0, height: 20.0 };
+        let before = [rect(0.0), rect(20.0), rect(40.0)];
+        let after = [rect(0.0), rect(20.0), rect(40.0)];
+        // [a, b, c] -> [c, a, b]
+        let reorder = Reorder {
+            moved: vec![(0, 1), (1, 2), (2, 0)],
+            ..Default::default()
+        };
+        assert_eq!(
+            flip_offsets(&reorder, &before, &after),
+            [(1, 0.0, -20.0), (2, 0.0, -20.0), (0, 0.0, 40.0)]
+        );
+
+        // A row that ends up in the same place is left alone
+        let reorder = Reorder {
+            moved: vec![(1, 0)],
+            removed: vec![0],
+            ..Default::default()
+        };
+        assert_eq!(flip_offsets(&reorder, &before, &[rect(20.0)]), []);
+    }
 }
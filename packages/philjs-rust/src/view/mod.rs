@@ -19,6 +19,13 @@ pub use children::Children;
 pub use into_view::IntoView;
 pub use view::View;
 
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::reactive::batch::untrack;
+use crate::reactive::Signal;
+
 // ============================================================================
 // Helper Functions (used by view! macro)
 // ============================================================================
@@ -92,44 +99,159 @@ where
     }
 }
 
-/// Iteration component for rendering lists.
-pub struct For<E, K, C> {
+/// How a keyed [`For`] list changed in one update, passed to
+/// [`For::on_reorder`] hooks such as [`animated::FlipAnimation`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Reorder {
+    /// Rows kept but at a new position, as `(from, to)` indices
+    pub moved: Vec<(usize, usize)>,
+    /// Positions of new rows in the updated list
+    pub added: Vec<usize>,
+    /// Positions of removed rows in the previous list
+    pub removed: Vec<usize>,
+}
+
+impl Reorder {
+    /// Whether nothing moved, appeared or disappeared.
+    pub fn is_empty(&self) -> bool {
+        self.moved.is_empty() && self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+type ReorderHook = Rc<dyn Fn(&Reorder)>;
+
+/// A rendered row of a [`For`], reused while its key stays in the list
+struct Row {
+    view: View,
+    index: Signal<usize>,
+}
+
+/// Iteration component for rendering keyed lists.
+///
+/// Each row is rendered once per key and reused when the list changes, so
+/// its state survives reordering. Render closures passed to
+/// [`indexed`](For::indexed) also get the row's position as a signal, which
+/// is updated in place when the row moves.
+///
+/// # Example
+/// ```rust
+/// let todos = signal!(vec![...]);
+/// let list = NodeRef::new();
+///
+/// view! {
+///     <ul node_ref=list.clone()>
+///         {For::indexed(
+///             move || todos.get(),
+///             |todo| todo.id,
+///             |todo, index| view! { <li>{move || index.get() + 1} ". " {todo.title}</li> },
+///         ).animate(use_flip(list))}
+///     </ul>
+/// }
+/// ```
+pub struct For<T, E, K> {
     each: E,
     key: K,
-    children: C,
+    children: Rc<dyn Fn(T, Signal<usize>) -> View>,
+    on_reorder: Option<ReorderHook>,
 }
 
-impl<T, E, K, KV, C, V> For<E, K, C>
+impl<T, E, K, KV> For<T, E, K>
 where
-    T: Clone + 'static,
+    T: 'static,
     E: Fn() -> Vec<T> + 'static,
     K: Fn(&T) -> KV + 'static,
-    KV: std::hash::Hash + Eq,
-    C: Fn(T) -> V + 'static,
-    V: IntoView,
+    KV: std::hash::Hash + Eq + 'static,
 {
     /// Create a new For component.
-    pub fn new(each: E, key: K, children: C) -> Self {
-        For { each, key, children }
+    pub fn new<C, V>(each: E, key: K, children: C) -> Self
+    where
+        C: Fn(T) -> V + 'static,
+        V: IntoView,
+    {
+        Self::indexed(each, key, move |item, _| children(item))
+    }
+
+    /// Create a For component whose render closure also gets the row's
+    /// index as a signal.
+    pub fn indexed<C, V>(each: E, key: K, children: C) -> Self
+    where
+        C: Fn(T, Signal<usize>) -> V + 'static,
+        V: IntoView,
+    {
+        For {
+            each,
+            key,
+            children: Rc::new(move |item, index| children(item, index).into_view()),
+            on_reorder: None,
+        }
+    }
+
+    /// Call `hook` after each update that moves, adds or removes rows; not
+    /// on the first render.
+    pub fn on_reorder(mut self, hook: impl Fn(&Reorder) + 'static) -> Self {
+        self.on_reorder = Some(Rc::new(hook));
+        self
+    }
+
+    /// Animate rows to their new positions when the list reorders.
+    pub fn animate(self, flip: animated::FlipAnimation) -> Self {
+        self.on_reorder(move |reorder| flip.play(reorder))
     }
 }
 
-impl<T, E, K, KV, C, V> IntoView for For<E, K, C>
+impl<T, E, K, KV> IntoView for For<T, E, K>
 where
-    T: Clone + 'static,
+    T: 'static,
     E: Fn() -> Vec<T> + 'static,
     K: Fn(&T) -> KV + 'static,
-    KV: std::hash::Hash + Eq,
-    C: Fn(T) -> V + 'static,
-    V: IntoView,
+    KV: std::hash::Hash + Eq + 'static,
 {
     fn into_view(self) -> View {
+        let For { each, key, children, on_reorder } = self;
+        let rows: RefCell<Vec<(KV, Row)>> = RefCell::new(Vec::new());
+        let rendered = Cell::new(false);
+
         Dynamic::new(move || {
-            let items = (self.each)();
-            let views: Vec<View> = items
-                .into_iter()
-                .map(|item| (self.children)(item).into_view())
+            let items = each();
+            let mut previous: HashMap<KV, (usize, Row)> = rows
+                .borrow_mut()
+                .drain(..)
+                .enumerate()
+                .map(|(from, (key, row))| (key, (from, row)))
                 .collect();
+
+            let mut reorder = Reorder::default();
+            let mut next = Vec::with_capacity(items.len());
+            for (to, item) in items.into_iter().enumerate() {
+                let item_key = key(&item);
+                let row = match previous.remove(&item_key) {
+                    Some((from, row)) => {
+                        if from != to {
+                            reorder.moved.push((from, to));
+                            row.index.set(to);
+                        }
+                        row
+                    }
+                    None => {
+                        reorder.added.push(to);
+                        let index = Signal::new(to);
+                        let view = untrack(|| children(item, index.clone()));
+                        Row { view, index }
+                    }
+                };
+                next.push((item_key, row));
+            }
+            reorder.removed = previous.into_values().map(|(from, _)| from).collect();
+            reorder.removed.sort_unstable();
+
+            let views = next.iter().map(|(_, row)| row.view.clone()).collect();
+            *rows.borrow_mut() = next;
+
+            if rendered.replace(true) && !reorder.is_empty() {
+                if let Some(hook) = &on_reorder {
+                    untrack(|| hook(&reorder));
+                }
+            }
             Fragment::new(views)
         }).into()
     }
@@ -258,4 +380,64 @@ pub mod transition;
 pub mod animated;
 
 pub use transition::{Transition, TransitionConfig, TransitionState, use_transition, DeferredValue, use_deferred_value};
-pub use animated::{AnimatedShow, AnimatedShowConfig, AnimationState, Easing, fade, slide, scale, Presence, ANIMATION_CSS};
+pub use animated::{
+    AnimatedShow, AnimatedShowConfig, AnimationState, Easing, fade, slide, scale, Presence, ANIMATION_CSS,
+    Keyframes, prefers_reduced_motion,
+    FlipAnimation, FlipConfig, Rect, use_flip, flip_offsets,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyed_for_reuses_rows() {
+        let items = Signal::new(vec![1, 2, 3]);
+        let renders = Rc::new(Cell::new(0));
+        let reorders = Rc::new(RefCell::new(Vec::new()));
+        let indexes = Rc::new(RefCell::new(HashMap::new()));
+
+        let view = For::indexed(
+            {
+                let items = items.clone();
+                move || items.get()
+            },
+            |n: &i32| *n,
+            {
+                let renders = renders.clone();
+                let indexes = indexes.clone();
+                move |n: i32, index: Signal<usize>| {
+                    renders.set(renders.get() + 1);
+                    indexes.borrow_mut().insert(n, index.clone());
+                    Dynamic::new(move || format!("{}:{} ", n, index.get()))
+                }
+            },
+        )
+        .on_reorder({
+            let reorders = reorders.clone();
+            move |reorder| reorders.borrow_mut().push(reorder.clone())
+        })
+        .into_view();
+
+        assert_eq!(view.to_html(), "1:0 2:1 3:2 ");
+        assert_eq!(renders.get(), 3);
+        assert!(reorders.borrow().is_empty());
+
+        items.set(vec![3, 1, 4]);
+        assert_eq!(view.to_html(), "3:0 1:1 4:2 ");
+        assert_eq!(renders.get(), 4);
+        assert_eq!(indexes.borrow()[&3].get_untracked(), 0);
+        assert_eq!(
+            reorders.borrow().last(),
+            Some(&Reorder {
+                moved: vec![(2, 0), (0, 1)],
+                added: vec![2],
+                removed: vec![1],
+            })
+        );
+
+        // Re-rendering an unchanged list reports nothing
+        view.to_html();
+        assert_eq!(reorders.borrow().len(), 1);
+    }
+}
@@ -0,0 +1,318 @@
+//! Read replica routing
+//!
+//! A [`DbCluster`] wraps one primary and any number of read replicas. It
+//! implements [`ConnectionTrait`] itself, so ordinary queries route without
+//! call-site changes: plain `SELECT`s go to a replica, everything else
+//! (writes, `SELECT ... FOR UPDATE`, `INSERT ... RETURNING`) and every
+//! transaction goes to the primary.
+//!
+//! ```rust,ignore
+//! let cluster = DbCluster::new(connect(PRIMARY_URL).await?)
+//!     .replica(connect(REPLICA_1_URL).await?)
+//!     .replica(connect(REPLICA_2_URL).await?)
+//!     .strategy(ReplicaStrategy::LeastLoaded);
+//! provide_db_cluster(cluster);
+//!
+//! let db = use_db()?;
+//! let users = Users::find().all(&*db).await?;      // replica
+//! user.into_active_model().update(&*db).await?;    // primary
+//! ```
+//!
+//! Reads that must see a write made moments ago should use
+//! [`DbCluster::primary`] directly, since replicas may lag.
+
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use sea_orm::{
+    AccessMode, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr,
+    ExecResult, IsolationLevel, QueryResult, Statement, TransactionError, TransactionTrait,
+};
+
+/// How reads are spread across replicas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReplicaStrategy {
+    /// Rotate through replicas in order
+    #[default]
+    RoundRobin,
+    /// Pick the replica with the fewest queries in flight
+    LeastLoaded,
+}
+
+/// A read replica and its in-flight query count
+struct Replica {
+    db: DatabaseConnection,
+    in_flight: AtomicUsize,
+}
+
+/// Decrements a replica's in-flight count when the query finishes
+struct InFlight<'a>(&'a AtomicUsize);
+
+impl<'a> InFlight<'a> {
+    fn enter(count: &'a AtomicUsize) -> Self {
+        count.fetch_add(1, Ordering::Relaxed);
+        Self(count)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Whether a statement can safely run on a replica
+///
+/// Only plain `SELECT`s qualify; locking reads need the primary.
+pub fn is_read_only(sql: &str) -> bool {
+    let sql = sql.trim_start().trim_start_matches('(').to_ascii_uppercase();
+    sql.starts_with("SELECT")
+        && !sql.contains(" FOR UPDATE")
+        && !sql.contains(" FOR NO KEY UPDATE")
+        && !sql.contains(" FOR SHARE")
+        && !sql.contains(" FOR KEY SHARE")
+        && !sql.contains(" LOCK IN SHARE MODE")
+}
+
+/// One primary connection plus read replicas
+pub struct DbCluster {
+    primary: DatabaseConnection,
+    replicas: Vec<Replica>,
+    strategy: ReplicaStrategy,
+    next: AtomicUsize,
+}
+
+impl DbCluster {
+    /// Create a cluster with no replicas; everything runs on `primary`
+    pub fn new(primary: DatabaseConnection) -> Self {
+        Self {
+            primary,
+            replicas: Vec::new(),
+            strategy: ReplicaStrategy::default(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Add a read replica
+    pub fn replica(mut self, db: DatabaseConnection) -> Self {
+        self.replicas.push(Replica {
+            db,
+            in_flight: AtomicUsize::new(0),
+        });
+        self
+    }
+
+    /// Set how reads are spread across replicas
+    pub fn strategy(mut self, strategy: ReplicaStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// The primary connection, for writes and read-your-writes queries
+    pub fn primary(&self) -> &DatabaseConnection {
+        &self.primary
+    }
+
+    /// A replica connection chosen by the strategy, or the primary if there are none
+    pub fn reader(&self) -> &DatabaseConnection {
+        match self.pick() {
+            Some(idx) => &self.replicas[idx].db,
+            None => &self.primary,
+        }
+    }
+
+    /// Get the number of replicas
+    pub fn replica_count(&self) -> usize {
+        self.replicas.len()
+    }
+
+    /// Queries currently running on each replica
+    pub fn replica_load(&self) -> Vec<usize> {
+        self.replicas
+            .iter()
+            .map(|r| r.in_flight.load(Ordering::Relaxed))
+            .collect()
+    }
+
+    fn pick(&self) -> Option<usize> {
+        let len = self.replicas.len();
+        if len == 0 {
+            return None;
+        }
+
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % len;
+        match self.strategy {
+            ReplicaStrategy::RoundRobin => Some(start),
+            // Scan from the rotating start so ties still spread out
+            ReplicaStrategy::LeastLoaded => (0..len)
+                .map(|offset| (start + offset) % len)
+                .min_by_key(|&idx| self.replicas[idx].in_flight.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Run a read on the chosen replica, tracking its load
+    async fn on_replica<'a, T, F, Fut>(&'a self, f: F) -> T
+    where
+        F: FnOnce(&'a DatabaseConnection) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        match self.pick() {
+            Some(idx) => {
+                let replica = &self.replicas[idx];
+                let _guard = InFlight::enter(&replica.in_flight);
+                f(&replica.db).await
+            }
+            None => f(&self.primary).await,
+        }
+    }
+}
+
+impl From<DatabaseConnection> for DbCluster {
+    fn from(primary: DatabaseConnection) -> Self {
+        Self::new(primary)
+    }
+}
+
+/// Lets a cluster stand in wherever a `&DatabaseConnection` is expected
+///
+/// Dereferences to the primary, so such callers always get write-safe
+/// behaviour; generic `ConnectionTrait` callers get routing.
+impl Deref for DbCluster {
+    type Target = DatabaseConnection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.primary
+    }
+}
+
+#[async_trait]
+impl ConnectionTrait for DbCluster {
+    fn get_database_backend(&self) -> DbBackend {
+        self.primary.get_database_backend()
+    }
+
+    async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.primary.execute(stmt).await
+    }
+
+    async fn execute_unprepared(&self, sql: &str) -> Result<ExecResult, DbErr> {
+        self.primary.execute_unprepared(sql).await
+    }
+
+    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        if is_read_only(&stmt.sql) {
+            self.on_replica(|db| db.query_one(stmt)).await
+        } else {
+            self.primary.query_one(stmt).await
+        }
+    }
+
+    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        if is_read_only(&stmt.sql) {
+            self.on_replica(|db| db.query_all(stmt)).await
+        } else {
+            self.primary.query_all(stmt).await
+        }
+    }
+
+    fn support_returning(&self) -> bool {
+        self.primary.support_returning()
+    }
+
+    fn is_mock_connection(&self) -> bool {
+        self.primary.is_mock_connection()
+    }
+}
+
+#[async_trait]
+impl TransactionTrait for DbCluster {
+    async fn begin(&self) -> Result<DatabaseTransaction, DbErr> {
+        self.primary.begin().await
+    }
+
+    async fn begin_with_config(
+        &self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<DatabaseTransaction, DbErr> {
+        self.primary.begin_with_config(isolation_level, access_mode).await
+    }
+
+    async fn transaction<F, T, E>(&self, callback: F) -> Result<T, TransactionError<E>>
+    where
+        F: for<'c> FnOnce(
+                &'c DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+            + Send,
+        T: Send,
+        E: std::error::Error + Send,
+    {
+        self.primary.transaction(callback).await
+    }
+
+    async fn transaction_with_config<F, T, E>(
+        &self,
+        callback: F,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<T, TransactionError<E>>
+    where
+        F: for<'c> FnOnce(
+                &'c DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+            + Send,
+        T: Send,
+        E: std::error::Error + Send,
+    {
+        self.primary
+            .transaction_with_config(callback, isolation_level, access_mode)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cluster(replicas: usize) -> DbCluster {
+        (0..replicas).fold(DbCluster::new(DatabaseConnection::Disconnected), |c, _| {
+            c.replica(DatabaseConnection::Disconnected)
+        })
+    }
+
+    #[test]
+    fn test_is_read_only() {
+        assert!(is_read_only(r#"SELECT "users"."id" FROM "users""#));
+        assert!(is_read_only("  (select 1) UNION (select 2)"));
+        assert!(!is_read_only(r#"SELECT * FROM "users" WHERE "id" = $1 FOR UPDATE"#));
+        assert!(!is_read_only(r#"INSERT INTO "users" ("name") VALUES ($1) RETURNING "id""#));
+        assert!(!is_read_only(r#"UPDATE "users" SET "name" = $1"#));
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let cluster = cluster(3);
+        let picks: Vec<_> = (0..6).map(|_| cluster.pick().unwrap()).collect();
+        assert_eq!(picks, vec![0, 1, 2, 0, 1, 2]);
+        assert_eq!(DbCluster::new(DatabaseConnection::Disconnected).pick(), None);
+    }
+
+    #[test]
+    fn test_least_loaded() {
+        let cluster = cluster(3).strategy(ReplicaStrategy::LeastLoaded);
+        let _busy = InFlight::enter(&cluster.replicas[0].in_flight);
+        let _busier = [
+            InFlight::enter(&cluster.replicas[1].in_flight),
+            InFlight::enter(&cluster.replicas[1].in_flight),
+        ];
+
+        assert_eq!(cluster.replica_load(), vec![1, 2, 0]);
+        assert_eq!(cluster.pick(), Some(2));
+
+        drop(_busier);
+        assert_eq!(cluster.replica_load(), vec![1, 0, 0]);
+    }
+}
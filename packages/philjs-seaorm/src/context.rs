@@ -3,10 +3,11 @@
 use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
+use crate::cluster::DbCluster;
 use crate::error::{OrmError, OrmResult};
 
-/// Global database connection storage
-static GLOBAL_DB: once_cell::sync::OnceCell<Arc<DatabaseConnection>> = once_cell::sync::OnceCell::new();
+/// Global database storage
+static GLOBAL_DB: once_cell::sync::OnceCell<Arc<DbCluster>> = once_cell::sync::OnceCell::new();
 
 /// Provide a database connection to the application context
 ///
@@ -24,10 +25,33 @@ static GLOBAL_DB: once_cell::sync::OnceCell<Arc<DatabaseConnection>> = once_cell
 /// }
 /// ```
 pub fn provide_db(db: DatabaseConnection) {
-    let _ = GLOBAL_DB.set(Arc::new(db));
+    provide_db_cluster(DbCluster::new(db));
 }
 
-/// Get the database connection from context
+/// Provide a primary with read replicas to the application context
+///
+/// `use_db()` then routes reads to replicas and writes to the primary.
+///
+/// # Example
+///
+/// ```rust
+/// use philjs_seaorm::prelude::*;
+///
+/// async fn setup() -> OrmResult<()> {
+///     let cluster = DbCluster::new(connect("postgres://primary/mydb").await?)
+///         .replica(connect("postgres://replica/mydb").await?);
+///     provide_db_cluster(cluster);
+///     Ok(())
+/// }
+/// ```
+pub fn provide_db_cluster(cluster: DbCluster) {
+    let _ = GLOBAL_DB.set(Arc::new(cluster));
+}
+
+/// Get the database from context
+///
+/// The handle implements `ConnectionTrait`, routing reads to replicas when a
+/// cluster was provided, and dereferences to the primary connection.
 ///
 /// # Example
 ///
@@ -40,7 +64,7 @@ pub fn provide_db(db: DatabaseConnection) {
 ///     Users::find().all(&*db).await.map_err(Into::into)
 /// }
 /// ```
-pub fn use_db() -> OrmResult<Arc<DatabaseConnection>> {
+pub fn use_db() -> OrmResult<Arc<DbCluster>> {
     GLOBAL_DB
         .get()
         .cloned()
@@ -15,6 +15,13 @@ pub enum OrmError {
     NotFound,
     /// Multiple entities found
     MultipleFound,
+    /// Optimistic lock failed: the row changed since it was read
+    Conflict {
+        /// Entity table name
+        entity: String,
+        /// Version the update expected
+        expected_version: i64,
+    },
     /// Validation error
     Validation(String),
     /// Migration error
@@ -35,6 +42,11 @@ impl fmt::Display for OrmError {
             OrmError::Transaction(msg) => write!(f, "Transaction error: {}", msg),
             OrmError::NotFound => write!(f, "Entity not found"),
             OrmError::MultipleFound => write!(f, "Multiple entities found, expected one"),
+            OrmError::Conflict { entity, expected_version } => write!(
+                f,
+                "Conflict: {} was modified concurrently (expected version {})",
+                entity, expected_version
+            ),
             OrmError::Validation(msg) => write!(f, "Validation error: {}", msg),
             OrmError::Migration(msg) => write!(f, "Migration error: {}", msg),
             OrmError::Context(msg) => write!(f, "Context error: {}", msg),
@@ -112,6 +124,7 @@ impl OrmError {
     pub fn status_code(&self) -> u16 {
         match self {
             OrmError::NotFound => 404,
+            OrmError::Conflict { .. } => 409,
             OrmError::Validation(_) => 422,
             OrmError::Connection(_) => 503,
             _ => 500,
@@ -197,6 +210,11 @@ mod tests {
         assert_eq!(OrmError::NotFound.status_code(), 404);
         assert_eq!(OrmError::Validation("".to_string()).status_code(), 422);
         assert_eq!(OrmError::Connection("".to_string()).status_code(), 503);
+        let conflict = OrmError::Conflict {
+            entity: "posts".to_string(),
+            expected_version: 3,
+        };
+        assert_eq!(conflict.status_code(), 409);
     }
 
     #[test]
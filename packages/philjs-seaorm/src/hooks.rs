@@ -2,22 +2,34 @@
 //!
 //! This module provides hooks that can be attached to entity operations
 //! for automatic validation, auditing, and side effects.
+//!
+//! [`HookedEntity`] runs hooks in registration order around each write:
+//! before hooks may modify the active model or abort by returning an error,
+//! then the write happens, then after hooks run. By default the whole
+//! pipeline shares one transaction, so a failing after hook rolls the write
+//! back too.
 
 use crate::error::{OrmError, OrmResult};
 use async_trait::async_trait;
 use sea_orm::*;
+use std::str::FromStr;
 use std::sync::Arc;
 
 /// Hook that runs before an entity operation
+///
+/// Returning an error aborts the operation.
 #[async_trait]
-pub trait BeforeHook<E: EntityTrait>: Send + Sync {
+pub trait BeforeHook<E: EntityTrait>: Send + Sync
+where
+    E::ActiveModel: Send,
+{
     /// Run before insert
-    async fn before_insert(&self, _model: &E::Model) -> OrmResult<()> {
+    async fn before_insert(&self, _model: &mut E::ActiveModel) -> OrmResult<()> {
         Ok(())
     }
 
     /// Run before update
-    async fn before_update(&self, _model: &E::Model) -> OrmResult<()> {
+    async fn before_update(&self, _model: &mut E::ActiveModel) -> OrmResult<()> {
         Ok(())
     }
 
@@ -51,68 +63,131 @@ pub trait AfterHook<E: EntityTrait>: Send + Sync {
 /// # Example
 ///
 /// ```rust
-/// use philjs_seaorm::hooks::{HookedEntity, BeforeHook, AfterHook};
+/// use philjs_seaorm::hooks::{HookedEntity, BeforeHook, AfterHook, TimestampsHook};
 ///
 /// struct AuditHook;
 ///
 /// #[async_trait]
-/// impl BeforeHook<users::Entity> for AuditHook {
-///     async fn before_insert(&self, model: &users::Model) -> OrmResult<()> {
-///         println!("Inserting user: {}", model.name);
+/// impl AfterHook<users::Entity> for AuditHook {
+///     async fn after_insert(&self, model: &users::Model) -> OrmResult<()> {
+///         println!("Inserted user: {}", model.name);
 ///         Ok(())
 ///     }
 /// }
 ///
 /// let hooked = HookedEntity::<users::Entity>::new(&db)
-///     .with_before_hook(Arc::new(AuditHook))
-///     .with_after_hook(Arc::new(LoggingHook));
+///     .with_before_hook(Arc::new(TimestampsHook::utc()))
+///     .with_after_hook(Arc::new(AuditHook))
+///     .with_after_hook(Arc::new(LoggingHook::new("users")));
+///
+/// let user = hooked.insert(new_user).await?;
 /// ```
-pub struct HookedEntity<E: EntityTrait> {
+pub struct HookedEntity<E: EntityTrait>
+where
+    E::ActiveModel: Send,
+{
     db: DatabaseConnection,
     before_hooks: Vec<Arc<dyn BeforeHook<E>>>,
     after_hooks: Vec<Arc<dyn AfterHook<E>>>,
+    transactional: bool,
 }
 
-impl<E: EntityTrait> HookedEntity<E> {
+impl<E: EntityTrait> HookedEntity<E>
+where
+    E::ActiveModel: Send,
+{
     /// Create a new hooked entity
     pub fn new(db: &DatabaseConnection) -> Self {
         Self {
             db: db.clone(),
             before_hooks: Vec::new(),
             after_hooks: Vec::new(),
+            transactional: true,
         }
     }
 
-    /// Add a before hook
+    /// Add a before hook; hooks run in the order they are added
     pub fn with_before_hook(mut self, hook: Arc<dyn BeforeHook<E>>) -> Self {
         self.before_hooks.push(hook);
         self
     }
 
-    /// Add an after hook
+    /// Add an after hook; hooks run in the order they are added
     pub fn with_after_hook(mut self, hook: Arc<dyn AfterHook<E>>) -> Self {
         self.after_hooks.push(hook);
         self
     }
 
+    /// Whether to wrap each operation and its hooks in a transaction (default on)
+    ///
+    /// Turn off when after hooks have side effects that cannot be rolled back
+    /// and a failure should not undo the write.
+    pub fn transactional(mut self, enabled: bool) -> Self {
+        self.transactional = enabled;
+        self
+    }
+
     /// Insert with hooks
     pub async fn insert(&self, active_model: E::ActiveModel) -> OrmResult<E::Model>
     where
-        E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+        E::ActiveModel: ActiveModelTrait<Entity = E> + ActiveModelBehavior,
+        E::Model: IntoActiveModel<E::ActiveModel>,
+    {
+        if !self.transactional {
+            return self.insert_in(&self.db, active_model).await;
+        }
+        let txn = self.begin().await?;
+        let result = self.insert_in(&txn, active_model).await?;
+        Self::commit(txn).await?;
+        Ok(result)
+    }
+
+    /// Update with hooks
+    pub async fn update(&self, active_model: E::ActiveModel) -> OrmResult<E::Model>
+    where
+        E::ActiveModel: ActiveModelTrait<Entity = E> + ActiveModelBehavior,
+        E::Model: IntoActiveModel<E::ActiveModel>,
+    {
+        if !self.transactional {
+            return self.update_in(&self.db, active_model).await;
+        }
+        let txn = self.begin().await?;
+        let result = self.update_in(&txn, active_model).await?;
+        Self::commit(txn).await?;
+        Ok(result)
+    }
+
+    /// Delete with hooks
+    pub async fn delete(&self, model: E::Model) -> OrmResult<DeleteResult>
+    where
+        E::Model: ModelTrait<Entity = E> + IntoActiveModel<E::ActiveModel>,
+        E::ActiveModel: ActiveModelTrait<Entity = E> + ActiveModelBehavior,
+    {
+        if !self.transactional {
+            return self.delete_in(&self.db, model).await;
+        }
+        let txn = self.begin().await?;
+        let result = self.delete_in(&txn, model).await?;
+        Self::commit(txn).await?;
+        Ok(result)
+    }
+
+    /// Insert with hooks on an existing connection or transaction
+    pub async fn insert_in<C>(&self, conn: &C, mut active_model: E::ActiveModel) -> OrmResult<E::Model>
+    where
+        C: ConnectionTrait,
+        E::ActiveModel: ActiveModelTrait<Entity = E> + ActiveModelBehavior,
+        E::Model: IntoActiveModel<E::ActiveModel>,
     {
-        // Run before hooks
-        let model = active_model.clone().try_into_model().map_err(|e| OrmError::Validation(e.to_string()))?;
         for hook in &self.before_hooks {
-            hook.before_insert(&model).await?;
+            hook.before_insert(&mut active_model).await?;
         }
 
-        // Perform insert
         let result = active_model
-            .insert(&self.db)
+            .insert(conn)
             .await
             .map_err(|e| OrmError::Query(e.to_string()))?;
 
-        // Run after hooks
         for hook in &self.after_hooks {
             hook.after_insert(&result).await?;
         }
@@ -120,24 +195,22 @@ impl<E: EntityTrait> HookedEntity<E> {
         Ok(result)
     }
 
-    /// Update with hooks
-    pub async fn update(&self, active_model: E::ActiveModel) -> OrmResult<E::Model>
+    /// Update with hooks on an existing connection or transaction
+    pub async fn update_in<C>(&self, conn: &C, mut active_model: E::ActiveModel) -> OrmResult<E::Model>
     where
-        E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+        C: ConnectionTrait,
+        E::ActiveModel: ActiveModelTrait<Entity = E> + ActiveModelBehavior,
+        E::Model: IntoActiveModel<E::ActiveModel>,
     {
-        // Run before hooks
-        let model = active_model.clone().try_into_model().map_err(|e| OrmError::Validation(e.to_string()))?;
         for hook in &self.before_hooks {
-            hook.before_update(&model).await?;
+            hook.before_update(&mut active_model).await?;
         }
 
-        // Perform update
         let result = active_model
-            .update(&self.db)
+            .update(conn)
             .await
             .map_err(|e| OrmError::Query(e.to_string()))?;
 
-        // Run after hooks
         for hook in &self.after_hooks {
             hook.after_update(&result).await?;
         }
@@ -145,33 +218,48 @@ impl<E: EntityTrait> HookedEntity<E> {
         Ok(result)
     }
 
-    /// Delete with hooks
-    pub async fn delete(&self, model: E::Model) -> OrmResult<DeleteResult>
+    /// Delete with hooks on an existing connection or transaction
+    pub async fn delete_in<C>(&self, conn: &C, model: E::Model) -> OrmResult<DeleteResult>
     where
+        C: ConnectionTrait,
         E::Model: ModelTrait<Entity = E> + IntoActiveModel<E::ActiveModel>,
+        E::ActiveModel: ActiveModelTrait<Entity = E> + ActiveModelBehavior,
     {
-        // Run before hooks
         for hook in &self.before_hooks {
             hook.before_delete(&model).await?;
         }
 
-        // Perform delete
         let active_model: E::ActiveModel = model.clone().into_active_model();
         let result = active_model
-            .delete(&self.db)
+            .delete(conn)
             .await
             .map_err(|e| OrmError::Query(e.to_string()))?;
 
-        // Run after hooks
         for hook in &self.after_hooks {
             hook.after_delete(&model).await?;
         }
 
         Ok(result)
     }
+
+    async fn begin(&self) -> OrmResult<DatabaseTransaction> {
+        self.db
+            .begin()
+            .await
+            .map_err(|e| OrmError::Transaction(e.to_string()))
+    }
+
+    async fn commit(txn: DatabaseTransaction) -> OrmResult<()> {
+        txn.commit()
+            .await
+            .map_err(|e| OrmError::Transaction(e.to_string()))
+    }
 }
 
 /// Validation hook for models
+///
+/// Validates the active model before inserts and updates; only `Set`
+/// fields are present on partial updates.
 pub struct ValidationHook<F> {
     validator: F,
 }
@@ -187,13 +275,14 @@ impl<F> ValidationHook<F> {
 impl<E, F> BeforeHook<E> for ValidationHook<F>
 where
     E: EntityTrait,
-    F: Fn(&E::Model) -> OrmResult<()> + Send + Sync,
+    E::ActiveModel: Send,
+    F: Fn(&E::ActiveModel) -> OrmResult<()> + Send + Sync,
 {
-    async fn before_insert(&self, model: &E::Model) -> OrmResult<()> {
+    async fn before_insert(&self, model: &mut E::ActiveModel) -> OrmResult<()> {
         (self.validator)(model)
     }
 
-    async fn before_update(&self, model: &E::Model) -> OrmResult<()> {
+    async fn before_update(&self, model: &mut E::ActiveModel) -> OrmResult<()> {
         (self.validator)(model)
     }
 }
@@ -213,13 +302,16 @@ impl LoggingHook {
 }
 
 #[async_trait]
-impl<E: EntityTrait> BeforeHook<E> for LoggingHook {
-    async fn before_insert(&self, _model: &E::Model) -> OrmResult<()> {
+impl<E: EntityTrait> BeforeHook<E> for LoggingHook
+where
+    E::ActiveModel: Send,
+{
+    async fn before_insert(&self, _model: &mut E::ActiveModel) -> OrmResult<()> {
         tracing::debug!("{}: before insert", self.prefix);
         Ok(())
     }
 
-    async fn before_update(&self, _model: &E::Model) -> OrmResult<()> {
+    async fn before_update(&self, _model: &mut E::ActiveModel) -> OrmResult<()> {
         tracing::debug!("{}: before update", self.prefix);
         Ok(())
     }
@@ -248,18 +340,95 @@ impl<E: EntityTrait> AfterHook<E> for LoggingHook {
     }
 }
 
-/// Timestamp hook for automatically updating timestamps
-pub struct TimestampHook;
+/// Produces the current time as a column value
+type Clock = Arc<dyn Fn() -> Value + Send + Sync>;
+
+/// Sets `created_at` on insert and `updated_at` on insert and update
+///
+/// The clock must produce the same value type as the model fields, e.g.
+/// [`TimestampsHook::utc`] for `DateTimeUtc` fields. Explicitly set values
+/// are kept on insert, and entities without a column are skipped.
+pub struct TimestampsHook {
+    created_at: String,
+    updated_at: String,
+    now: Clock,
+}
+
+impl TimestampsHook {
+    /// Use a custom clock
+    pub fn with_clock<F>(now: F) -> Self
+    where
+        F: Fn() -> Value + Send + Sync + 'static,
+    {
+        Self {
+            created_at: "created_at".to_string(),
+            updated_at: "updated_at".to_string(),
+            now: Arc::new(now),
+        }
+    }
+
+    /// Unix seconds, for `i64` fields
+    pub fn unix_seconds() -> Self {
+        Self::with_clock(|| {
+            let secs = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            Value::BigInt(Some(secs as i64))
+        })
+    }
+
+    /// UTC time, for `DateTimeUtc` fields
+    #[cfg(feature = "with-chrono")]
+    pub fn utc() -> Self {
+        Self::with_clock(|| sea_orm::prelude::ChronoUtc::now().into())
+    }
+
+    /// UTC time as a fixed offset, for `DateTimeWithTimeZone` fields
+    #[cfg(feature = "with-chrono")]
+    pub fn fixed_offset() -> Self {
+        Self::with_clock(|| sea_orm::prelude::DateTimeWithTimeZone::from(sea_orm::prelude::ChronoUtc::now()).into())
+    }
+
+    /// Naive UTC time, for `DateTime` fields
+    #[cfg(feature = "with-chrono")]
+    pub fn naive_utc() -> Self {
+        Self::with_clock(|| sea_orm::prelude::ChronoUtc::now().naive_utc().into())
+    }
+
+    /// Use different column names
+    pub fn columns(mut self, created_at: impl Into<String>, updated_at: impl Into<String>) -> Self {
+        self.created_at = created_at.into();
+        self.updated_at = updated_at.into();
+        self
+    }
+
+    fn column<E: EntityTrait>(name: &str) -> Option<E::Column> {
+        E::Column::from_str(name).ok()
+    }
+}
 
 #[async_trait]
-impl<E: EntityTrait> BeforeHook<E> for TimestampHook {
-    async fn before_insert(&self, _model: &E::Model) -> OrmResult<()> {
-        // In practice, you would update created_at and updated_at here
+impl<E: EntityTrait> BeforeHook<E> for TimestampsHook
+where
+    E::ActiveModel: Send,
+{
+    async fn before_insert(&self, model: &mut E::ActiveModel) -> OrmResult<()> {
+        let now = (self.now)();
+        for name in [&self.created_at, &self.updated_at] {
+            if let Some(column) = Self::column::<E>(name) {
+                if model.is_not_set(column) {
+                    model.set(column, now.clone());
+                }
+            }
+        }
         Ok(())
     }
 
-    async fn before_update(&self, _model: &E::Model) -> OrmResult<()> {
-        // In practice, you would update updated_at here
+    async fn before_update(&self, model: &mut E::ActiveModel) -> OrmResult<()> {
+        if let Some(column) = Self::column::<E>(&self.updated_at) {
+            model.set(column, (self.now)());
+        }
         Ok(())
     }
 }
@@ -268,8 +437,60 @@ impl<E: EntityTrait> BeforeHook<E> for TimestampHook {
 mod tests {
     use super::*;
 
+    mod posts {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "posts")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub title: String,
+            pub created_at: i64,
+            pub updated_at: i64,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
     #[test]
     fn test_logging_hook_creation() {
         let _hook = LoggingHook::new("test");
     }
+
+    #[tokio::test]
+    async fn test_timestamps_hook() {
+        let hook = TimestampsHook::with_clock(|| Value::BigInt(Some(100)));
+        let mut post = posts::ActiveModel {
+            title: ActiveValue::Set("Hello".to_string()),
+            created_at: ActiveValue::Set(5),
+            ..Default::default()
+        };
+
+        BeforeHook::<posts::Entity>::before_insert(&hook, &mut post).await.unwrap();
+        assert_eq!(post.created_at, ActiveValue::Set(5));
+        assert_eq!(post.updated_at, ActiveValue::Set(100));
+
+        let hook = TimestampsHook::with_clock(|| Value::BigInt(Some(200)));
+        BeforeHook::<posts::Entity>::before_update(&hook, &mut post).await.unwrap();
+        assert_eq!(post.updated_at, ActiveValue::Set(200));
+    }
+
+    #[tokio::test]
+    async fn test_validation_hook_aborts() {
+        let hook = ValidationHook::new(|post: &posts::ActiveModel| match &post.title {
+            ActiveValue::Set(title) if title.is_empty() => Err(OrmError::Validation("title is required".into())),
+            _ => Ok(()),
+        });
+        let mut post = posts::ActiveModel {
+            title: ActiveValue::Set(String::new()),
+            ..Default::default()
+        };
+
+        let result = BeforeHook::<posts::Entity>::before_insert(&hook, &mut post).await;
+        assert!(matches!(result, Err(OrmError::Validation(_))));
+    }
 }
@@ -47,17 +47,23 @@ pub mod entity;
 pub mod query;
 pub mod migration;
 pub mod context;
+pub mod cluster;
 pub mod error;
 pub mod reactive;
 pub mod hooks;
 pub mod pagination;
+pub mod soft_delete;
+pub mod versioned;
 
 // Re-exports
 pub use entity::{EntityHelpers, Pagination, SortOrder};
 pub use query::{QueryHelpers, FilterBuilder, RelationLoader};
-pub use migration::{Migrator, MigrationStatus};
-pub use context::{provide_db, use_db, DbProvider};
+pub use migration::{Migrator, MigrationStatus, MigrateCommand, MigrationEvent, MigrationDirection};
+pub use context::{provide_db, provide_db_cluster, use_db, DbProvider};
+pub use cluster::{DbCluster, ReplicaStrategy};
 pub use error::{OrmError, OrmResult};
+pub use soft_delete::{SoftDeletable, SoftDeleteExt, SoftDeleteHelpers};
+pub use versioned::{Versioned, VersionedActiveModel};
 
 // Re-export sea-orm types
 pub use sea_orm::{
@@ -77,14 +83,15 @@ pub use sea_query::{Expr, Func, SimpleExpr};
 pub mod prelude {
     pub use crate::entity::{EntityHelpers, Pagination, SortOrder};
     pub use crate::query::{QueryHelpers, FilterBuilder, RelationLoader};
-    pub use crate::context::{provide_db, use_db, DbProvider};
+    pub use crate::context::{provide_db, provide_db_cluster, use_db, DbProvider};
+    pub use crate::cluster::{DbCluster, ReplicaStrategy};
     pub use crate::error::{OrmError, OrmResult};
 
     // Reactive queries
     pub use crate::reactive::{ReactiveEntity, ReactiveQueryBuilder, EntityResource};
 
     // Hooks
-    pub use crate::hooks::{HookedEntity, BeforeHook, AfterHook, ValidationHook, LoggingHook};
+    pub use crate::hooks::{HookedEntity, BeforeHook, AfterHook, ValidationHook, LoggingHook, TimestampsHook};
 
     // Pagination
     pub use crate::pagination::{
@@ -92,6 +99,12 @@ pub mod prelude {
         CursorPaginator, CursorParams, CursorResult, PaginateExt,
     };
 
+    // Soft delete
+    pub use crate::soft_delete::{SoftDeletable, SoftDeleteExt, SoftDeleteHelpers};
+
+    // Optimistic locking
+    pub use crate::versioned::{Versioned, VersionedActiveModel};
+
     pub use sea_orm::{
         entity::*, query::*, ActiveModelTrait, ActiveValue,
         ColumnTrait, EntityTrait, ModelTrait, Set,
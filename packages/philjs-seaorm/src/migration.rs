@@ -1,9 +1,11 @@
 //! Database migration support
 
-use sea_orm::{DatabaseConnection, DbBackend, Statement};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement, TransactionTrait};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
 
 use crate::error::{OrmError, OrmResult};
 
@@ -18,7 +20,122 @@ pub struct MigrationStatus {
     pub pending: bool,
 }
 
+/// Direction a migration runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationDirection {
+    /// Applying
+    Up,
+    /// Rolling back
+    Down,
+}
+
+/// Progress reported while migrating
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum MigrationEvent {
+    /// A migration started
+    Started {
+        /// Migration name
+        name: String,
+        /// Direction
+        direction: MigrationDirection,
+    },
+    /// A migration committed
+    Completed {
+        /// Migration name
+        name: String,
+        /// Direction
+        direction: MigrationDirection,
+        /// Time taken
+        duration_ms: u64,
+    },
+    /// A migration failed and was rolled back
+    Failed {
+        /// Migration name
+        name: String,
+        /// Direction
+        direction: MigrationDirection,
+        /// Error message
+        error: String,
+    },
+}
+
+/// A programmatic migrator action, as run by `cargo philjs db migrate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrateCommand {
+    /// Apply pending migrations, optionally only the next `steps`
+    Up {
+        /// Maximum migrations to apply
+        steps: Option<usize>,
+    },
+    /// Roll back the last `steps` migrations
+    Down {
+        /// Migrations to roll back
+        steps: usize,
+    },
+    /// Roll back and re-apply the last migration
+    Redo,
+    /// Show applied and pending migrations
+    Status,
+    /// Roll back every migration
+    Reset,
+    /// Roll back every migration, then apply all
+    Fresh,
+}
+
+impl MigrateCommand {
+    /// Parse `<action> [--steps N]`, ignoring unknown flags such as `--json`
+    ///
+    /// No action means `up`.
+    pub fn from_args<I, S>(args: I) -> OrmResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut action = None;
+        let mut steps = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            if arg == "--steps" || arg == "-n" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| OrmError::Migration("--steps needs a value".to_string()))?;
+                steps = Some(value.as_ref().parse::<usize>().map_err(|_| {
+                    OrmError::Migration(format!("Invalid --steps value '{}'", value.as_ref()))
+                })?);
+            } else if let Some(value) = arg.strip_prefix("--steps=") {
+                steps = Some(value.parse::<usize>().map_err(|_| {
+                    OrmError::Migration(format!("Invalid --steps value '{}'", value))
+                })?);
+            } else if !arg.starts_with('-') && action.is_none() {
+                action = Some(arg.to_string());
+            }
+        }
+
+        match action.as_deref().unwrap_or("up") {
+            "up" => Ok(MigrateCommand::Up { steps }),
+            "down" => Ok(MigrateCommand::Down { steps: steps.unwrap_or(1) }),
+            "redo" => Ok(MigrateCommand::Redo),
+            "status" => Ok(MigrateCommand::Status),
+            "reset" => Ok(MigrateCommand::Reset),
+            "fresh" => Ok(MigrateCommand::Fresh),
+            other => Err(OrmError::Migration(format!(
+                "Unknown migrate action '{}' (expected up, down, redo, status, reset, or fresh)",
+                other
+            ))),
+        }
+    }
+}
+
+type EventHandler = Arc<dyn Fn(&MigrationEvent) + Send + Sync>;
+
 /// Database migrator
+///
+/// Each migration runs in its own transaction together with its bookkeeping
+/// row, so a failure leaves no partial state behind (except on MySQL, where
+/// DDL commits implicitly).
 pub struct Migrator {
     /// Migrations directory
     migrations_dir: PathBuf,
@@ -26,6 +143,8 @@ pub struct Migrator {
     table_name: String,
     /// Registered migrations
     migrations: Vec<Migration>,
+    /// Progress listener
+    on_event: Option<EventHandler>,
 }
 
 /// Single migration
@@ -61,6 +180,7 @@ impl Default for Migrator {
             migrations_dir: PathBuf::from("migrations"),
             table_name: "_philjs_migrations".to_string(),
             migrations: Vec::new(),
+            on_event: None,
         }
     }
 }
@@ -89,110 +209,258 @@ impl Migrator {
         self
     }
 
+    /// Receive progress events
+    pub fn on_event<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&MigrationEvent) + Send + Sync + 'static,
+    {
+        self.on_event = Some(Arc::new(f));
+        self
+    }
+
+    /// Registered migrations, in run order
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// Load SQL migrations from the migrations directory
+    ///
+    /// Recognizes `<name>/up.sql` + `<name>/down.sql`,
+    /// `<name>.up.sql` + `<name>.down.sql`, and plain `<name>.sql` (no
+    /// rollback). Discovered migrations are appended in name order, so
+    /// prefix names with a timestamp or sequence number.
+    pub fn discover(mut self) -> OrmResult<Self> {
+        let read = |path: &Path| {
+            std::fs::read_to_string(path)
+                .map_err(|e| OrmError::Migration(format!("Failed to read {}: {}", path.display(), e)))
+        };
+        let entries = std::fs::read_dir(&self.migrations_dir).map_err(|e| {
+            OrmError::Migration(format!(
+                "Failed to read migrations directory {}: {}",
+                self.migrations_dir.display(),
+                e
+            ))
+        })?;
+
+        let mut found: BTreeMap<String, (Option<String>, Option<String>)> = BTreeMap::new();
+        for entry in entries {
+            let path = entry.map_err(|e| OrmError::Migration(e.to_string()))?.path();
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+                continue;
+            };
+
+            if path.is_dir() {
+                let up = path.join("up.sql");
+                if up.is_file() {
+                    let down = path.join("down.sql");
+                    let down = if down.is_file() { Some(read(&down)?) } else { None };
+                    found.insert(file_name, (Some(read(&up)?), down));
+                }
+            } else if let Some(name) = file_name.strip_suffix(".up.sql") {
+                found.entry(name.to_string()).or_default().0 = Some(read(&path)?);
+            } else if let Some(name) = file_name.strip_suffix(".down.sql") {
+                found.entry(name.to_string()).or_default().1 = Some(read(&path)?);
+            } else if let Some(name) = file_name.strip_suffix(".sql") {
+                found.entry(name.to_string()).or_default().0 = Some(read(&path)?);
+            }
+        }
+
+        for (name, (up, down)) in found {
+            if self.migrations.iter().any(|m| m.name == name) {
+                continue;
+            }
+            let up = up.ok_or_else(|| {
+                OrmError::Migration(format!("Migration '{}' has a down script but no up script", name))
+            })?;
+            self.migrations.push(Migration { name, up, down });
+        }
+
+        Ok(self)
+    }
+
+    fn emit(&self, event: MigrationEvent) {
+        if let Some(on_event) = &self.on_event {
+            on_event(&event);
+        }
+    }
+
+    /// Parameter placeholder for the backend
+    fn placeholder(backend: DbBackend) -> &'static str {
+        match backend {
+            DbBackend::Postgres => "$1",
+            _ => "?",
+        }
+    }
+
     /// Ensure migrations table exists
     async fn ensure_migrations_table(&self, db: &DatabaseConnection) -> OrmResult<()> {
+        let id = match db.get_database_backend() {
+            DbBackend::Postgres => "id SERIAL PRIMARY KEY",
+            DbBackend::MySql => "id INT AUTO_INCREMENT PRIMARY KEY",
+            DbBackend::Sqlite => "id INTEGER PRIMARY KEY AUTOINCREMENT",
+        };
         let sql = format!(
             r#"
             CREATE TABLE IF NOT EXISTS {} (
-                id SERIAL PRIMARY KEY,
+                {},
                 name VARCHAR(255) NOT NULL UNIQUE,
                 applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
             )
             "#,
-            self.table_name
+            self.table_name, id
         );
 
-        db.execute(Statement::from_string(DbBackend::Postgres, sql))
+        db.execute_unprepared(&sql)
             .await
             .map_err(|e| OrmError::Migration(e.to_string()))?;
 
         Ok(())
     }
 
-    /// Get applied migrations
-    async fn get_applied(&self, db: &DatabaseConnection) -> OrmResult<Vec<String>> {
+    /// Get applied migrations with their timestamps, oldest first
+    async fn get_applied(&self, db: &DatabaseConnection) -> OrmResult<Vec<(String, Option<String>)>> {
+        let backend = db.get_database_backend();
+        let text = match backend {
+            DbBackend::MySql => "CHAR",
+            _ => "TEXT",
+        };
         let sql = format!(
-            "SELECT name FROM {} ORDER BY id",
-            self.table_name
+            "SELECT name, CAST(applied_at AS {}) AS applied_at FROM {} ORDER BY id",
+            text, self.table_name
         );
 
         let rows = db
-            .query_all(Statement::from_string(DbBackend::Postgres, sql))
+            .query_all(Statement::from_string(backend, sql))
             .await
             .map_err(|e| OrmError::Migration(e.to_string()))?;
 
-        let mut names = Vec::new();
+        let mut applied = Vec::new();
         for row in rows {
             if let Ok(name) = row.try_get::<String>("", "name") {
-                names.push(name);
+                let applied_at = row.try_get::<Option<String>>("", "applied_at").ok().flatten();
+                applied.push((name, applied_at));
             }
         }
 
-        Ok(names)
+        Ok(applied)
     }
 
     /// Mark migration as applied
-    async fn mark_applied(&self, db: &DatabaseConnection, name: &str) -> OrmResult<()> {
+    async fn mark_applied<C: ConnectionTrait>(&self, db: &C, name: &str) -> OrmResult<()> {
+        let backend = db.get_database_backend();
         let sql = format!(
-            "INSERT INTO {} (name) VALUES ($1)",
-            self.table_name
+            "INSERT INTO {} (name) VALUES ({})",
+            self.table_name,
+            Self::placeholder(backend)
         );
 
-        db.execute(Statement::from_sql_and_values(
-            DbBackend::Postgres,
-            sql,
-            [name.into()],
-        ))
-        .await
-        .map_err(|e| OrmError::Migration(e.to_string()))?;
+        db.execute(Statement::from_sql_and_values(backend, sql, [name.into()]))
+            .await
+            .map_err(|e| OrmError::Migration(e.to_string()))?;
 
         Ok(())
     }
 
     /// Remove migration from applied
-    async fn mark_unapplied(&self, db: &DatabaseConnection, name: &str) -> OrmResult<()> {
+    async fn mark_unapplied<C: ConnectionTrait>(&self, db: &C, name: &str) -> OrmResult<()> {
+        let backend = db.get_database_backend();
         let sql = format!(
-            "DELETE FROM {} WHERE name = $1",
-            self.table_name
+            "DELETE FROM {} WHERE name = {}",
+            self.table_name,
+            Self::placeholder(backend)
         );
 
-        db.execute(Statement::from_sql_and_values(
-            DbBackend::Postgres,
-            sql,
-            [name.into()],
-        ))
-        .await
-        .map_err(|e| OrmError::Migration(e.to_string()))?;
+        db.execute(Statement::from_sql_and_values(backend, sql, [name.into()]))
+            .await
+            .map_err(|e| OrmError::Migration(e.to_string()))?;
 
         Ok(())
     }
 
+    /// Run one migration and its bookkeeping in a transaction
+    async fn run_one(
+        &self,
+        db: &DatabaseConnection,
+        migration: &Migration,
+        direction: MigrationDirection,
+    ) -> OrmResult<()> {
+        let sql = match direction {
+            MigrationDirection::Up => migration.up.as_str(),
+            MigrationDirection::Down => migration.down.as_deref().ok_or_else(|| {
+                OrmError::Migration(format!("Migration '{}' has no down migration", migration.name))
+            })?,
+        };
+
+        self.emit(MigrationEvent::Started {
+            name: migration.name.clone(),
+            direction,
+        });
+        tracing::info!(name = %migration.name, ?direction, "Running migration");
+        let started = Instant::now();
+
+        let result = async {
+            let txn = db.begin().await?;
+            txn.execute_unprepared(sql).await?;
+            match direction {
+                MigrationDirection::Up => self.mark_applied(&txn, &migration.name).await?,
+                MigrationDirection::Down => self.mark_unapplied(&txn, &migration.name).await?,
+            }
+            txn.commit().await?;
+            Ok::<_, OrmError>(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => {
+                self.emit(MigrationEvent::Completed {
+                    name: migration.name.clone(),
+                    direction,
+                    duration_ms: started.elapsed().as_millis() as u64,
+                });
+                tracing::info!(name = %migration.name, ?direction, "Migration completed");
+                Ok(())
+            }
+            Err(e) => {
+                self.emit(MigrationEvent::Failed {
+                    name: migration.name.clone(),
+                    direction,
+                    error: e.to_string(),
+                });
+                Err(OrmError::Migration(format!(
+                    "Migration '{}' ({:?}) failed: {}",
+                    migration.name, direction, e
+                )))
+            }
+        }
+    }
+
     /// Get migration status
     pub async fn status(&self, db: &DatabaseConnection) -> OrmResult<Vec<MigrationStatus>> {
         self.ensure_migrations_table(db).await?;
-        let applied = self.get_applied(db).await?;
+        let applied: HashMap<String, Option<String>> = self.get_applied(db).await?.into_iter().collect();
 
-        let mut status = Vec::new();
-        for migration in &self.migrations {
-            let is_applied = applied.contains(&migration.name);
-            status.push(MigrationStatus {
-                name: migration.name.clone(),
-                applied_at: if is_applied {
-                    Some("Applied".to_string())
-                } else {
-                    None
+        Ok(self
+            .migrations
+            .iter()
+            .map(|migration| match applied.get(&migration.name) {
+                Some(applied_at) => MigrationStatus {
+                    name: migration.name.clone(),
+                    applied_at: Some(applied_at.clone().unwrap_or_else(|| "Applied".to_string())),
+                    pending: false,
                 },
-                pending: !is_applied,
-            });
-        }
-
-        Ok(status)
+                None => MigrationStatus {
+                    name: migration.name.clone(),
+                    applied_at: None,
+                    pending: true,
+                },
+            })
+            .collect())
     }
 
     /// Get pending migrations
     pub async fn pending(&self, db: &DatabaseConnection) -> OrmResult<Vec<&Migration>> {
         self.ensure_migrations_table(db).await?;
-        let applied = self.get_applied(db).await?;
+        let applied: Vec<String> = self.get_applied(db).await?.into_iter().map(|(name, _)| name).collect();
 
         Ok(self
             .migrations
@@ -203,24 +471,17 @@ impl Migrator {
 
     /// Run all pending migrations
     pub async fn up(&self, db: &DatabaseConnection) -> OrmResult<Vec<String>> {
-        self.ensure_migrations_table(db).await?;
+        self.up_steps(db, None).await
+    }
+
+    /// Run at most `steps` pending migrations (all when `None`)
+    pub async fn up_steps(&self, db: &DatabaseConnection, steps: Option<usize>) -> OrmResult<Vec<String>> {
         let pending = self.pending(db).await?;
 
         let mut applied = Vec::new();
-        for migration in pending {
-            tracing::info!(name = %migration.name, "Running migration");
-
-            db.execute(Statement::from_string(DbBackend::Postgres, migration.up.clone()))
-                .await
-                .map_err(|e| OrmError::Migration(format!(
-                    "Migration '{}' failed: {}",
-                    migration.name, e
-                )))?;
-
-            self.mark_applied(db, &migration.name).await?;
+        for migration in pending.into_iter().take(steps.unwrap_or(usize::MAX)) {
+            self.run_one(db, migration, MigrationDirection::Up).await?;
             applied.push(migration.name.clone());
-
-            tracing::info!(name = %migration.name, "Migration completed");
         }
 
         Ok(applied)
@@ -228,54 +489,46 @@ impl Migrator {
 
     /// Rollback the last migration
     pub async fn down(&self, db: &DatabaseConnection) -> OrmResult<Option<String>> {
+        Ok(self.down_steps(db, 1).await?.pop())
+    }
+
+    /// Rollback the last `steps` migrations, newest first
+    pub async fn down_steps(&self, db: &DatabaseConnection, steps: usize) -> OrmResult<Vec<String>> {
         self.ensure_migrations_table(db).await?;
         let applied = self.get_applied(db).await?;
 
-        if let Some(last_applied) = applied.last() {
+        let mut rolled_back = Vec::new();
+        for (name, _) in applied.iter().rev().take(steps) {
             let migration = self
                 .migrations
                 .iter()
-                .find(|m| &m.name == last_applied)
-                .ok_or_else(|| OrmError::Migration(format!(
-                    "Migration '{}' not found",
-                    last_applied
-                )))?;
-
-            if let Some(ref down_sql) = migration.down {
-                tracing::info!(name = %migration.name, "Rolling back migration");
-
-                db.execute(Statement::from_string(DbBackend::Postgres, down_sql.clone()))
-                    .await
-                    .map_err(|e| OrmError::Migration(format!(
-                        "Rollback of '{}' failed: {}",
-                        migration.name, e
-                    )))?;
+                .find(|m| &m.name == name)
+                .ok_or_else(|| OrmError::Migration(format!("Migration '{}' not found", name)))?;
 
-                self.mark_unapplied(db, &migration.name).await?;
-
-                tracing::info!(name = %migration.name, "Rollback completed");
-
-                return Ok(Some(migration.name.clone()));
-            } else {
-                return Err(OrmError::Migration(format!(
-                    "Migration '{}' has no down migration",
-                    migration.name
-                )));
-            }
+            self.run_one(db, migration, MigrationDirection::Down).await?;
+            rolled_back.push(migration.name.clone());
         }
 
-        Ok(None)
+        Ok(rolled_back)
+    }
+
+    /// Roll back and re-apply the last migration
+    pub async fn redo(&self, db: &DatabaseConnection) -> OrmResult<Option<String>> {
+        let Some(name) = self.down(db).await? else {
+            return Ok(None);
+        };
+        let migration = self
+            .migrations
+            .iter()
+            .find(|m| m.name == name)
+            .ok_or_else(|| OrmError::Migration(format!("Migration '{}' not found", name)))?;
+        self.run_one(db, migration, MigrationDirection::Up).await?;
+        Ok(Some(name))
     }
 
     /// Rollback all migrations
     pub async fn reset(&self, db: &DatabaseConnection) -> OrmResult<Vec<String>> {
-        let mut rolled_back = Vec::new();
-
-        while let Some(name) = self.down(db).await? {
-            rolled_back.push(name);
-        }
-
-        Ok(rolled_back)
+        self.down_steps(db, usize::MAX).await
     }
 
     /// Reset and re-run all migrations
@@ -283,6 +536,76 @@ impl Migrator {
         self.reset(db).await?;
         self.up(db).await
     }
+
+    /// Run a [`MigrateCommand`], returning the migrations it touched
+    ///
+    /// `Status` touches nothing; use [`Migrator::status`] for its output.
+    pub async fn run(&self, db: &DatabaseConnection, command: MigrateCommand) -> OrmResult<Vec<String>> {
+        match command {
+            MigrateCommand::Up { steps } => self.up_steps(db, steps).await,
+            MigrateCommand::Down { steps } => self.down_steps(db, steps).await,
+            MigrateCommand::Redo => Ok(self.redo(db).await?.into_iter().collect()),
+            MigrateCommand::Status => Ok(Vec::new()),
+            MigrateCommand::Reset => self.reset(db).await,
+            MigrateCommand::Fresh => self.fresh(db).await,
+        }
+    }
+
+    /// Entry point for a project's migration binary
+    ///
+    /// `cargo philjs db migrate <action>` runs the project's `migration`
+    /// package with the action and flags as arguments:
+    ///
+    /// ```rust,ignore
+    /// #[tokio::main]
+    /// async fn main() -> OrmResult<()> {
+    ///     let db = philjs_seaorm::connect(&std::env::var("DATABASE_URL").unwrap()).await?;
+    ///     Migrator::new().discover()?.run_cli(&db).await
+    /// }
+    /// ```
+    ///
+    /// With `--json`, events and status are printed as JSON lines.
+    pub async fn run_cli(mut self, db: &DatabaseConnection) -> OrmResult<()> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let json = args.iter().any(|arg| arg == "--json");
+        let command = MigrateCommand::from_args(&args)?;
+
+        if self.on_event.is_none() {
+            self = if json {
+                self.on_event(|event| {
+                    if let Ok(line) = serde_json::to_string(event) {
+                        println!("{}", line);
+                    }
+                })
+            } else {
+                self.on_event(|event| match event {
+                    MigrationEvent::Started { name, direction } => println!("{:?} {} ...", direction, name),
+                    MigrationEvent::Completed { name, duration_ms, .. } => {
+                        println!("  done {} ({} ms)", name, duration_ms)
+                    }
+                    MigrationEvent::Failed { name, error, .. } => eprintln!("  failed {}: {}", name, error),
+                })
+            };
+        }
+
+        if command == MigrateCommand::Status {
+            for status in self.status(db).await? {
+                if json {
+                    println!("{}", serde_json::to_string(&status)?);
+                } else {
+                    let state = status.applied_at.as_deref().unwrap_or("pending");
+                    println!("{:<48} {}", status.name, state);
+                }
+            }
+            return Ok(());
+        }
+
+        let touched = self.run(db, command).await?;
+        if !json && touched.is_empty() {
+            println!("Nothing to migrate");
+        }
+        Ok(())
+    }
 }
 
 /// Migration builder for creating migrations programmatically
@@ -401,4 +724,49 @@ mod tests {
         assert_eq!(migration.name, "test_migration");
         assert!(migration.down.is_some());
     }
+
+    #[test]
+    fn test_migrate_command_from_args() {
+        assert_eq!(MigrateCommand::from_args(Vec::<String>::new()).unwrap(), MigrateCommand::Up { steps: None });
+        assert_eq!(
+            MigrateCommand::from_args(["up", "--steps", "2", "--json"]).unwrap(),
+            MigrateCommand::Up { steps: Some(2) }
+        );
+        assert_eq!(MigrateCommand::from_args(["down"]).unwrap(), MigrateCommand::Down { steps: 1 });
+        assert_eq!(MigrateCommand::from_args(["down", "--steps=3"]).unwrap(), MigrateCommand::Down { steps: 3 });
+        assert_eq!(MigrateCommand::from_args(["redo"]).unwrap(), MigrateCommand::Redo);
+        assert!(MigrateCommand::from_args(["sideways"]).is_err());
+    }
+
+    #[test]
+    fn test_discover() {
+        let dir = std::env::temp_dir().join(format!("philjs-migrations-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("002_posts")).unwrap();
+        std::fs::write(dir.join("001_users.up.sql"), "CREATE TABLE users (id INT)").unwrap();
+        std::fs::write(dir.join("001_users.down.sql"), "DROP TABLE users").unwrap();
+        std::fs::write(dir.join("002_posts/up.sql"), "CREATE TABLE posts (id INT)").unwrap();
+        std::fs::write(dir.join("003_seed.sql"), "INSERT INTO users VALUES (1)").unwrap();
+        std::fs::write(dir.join("README.md"), "not a migration").unwrap();
+
+        let migrator = Migrator::new().migrations_dir(&dir).discover().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<&str> = migrator.migrations().iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["001_users", "002_posts", "003_seed"]);
+        assert_eq!(migrator.migrations()[0].down.as_deref(), Some("DROP TABLE users"));
+        assert!(migrator.migrations()[2].down.is_none());
+    }
+
+    #[test]
+    fn test_migration_event_json() {
+        let event = MigrationEvent::Completed {
+            name: "001_users".to_string(),
+            direction: MigrationDirection::Up,
+            duration_ms: 12,
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"completed","name":"001_users","direction":"up","duration_ms":12}"#
+        );
+    }
 }
@@ -2,7 +2,10 @@
 //!
 //! This module provides utilities for paginating SeaORM entity queries.
 
+use crate::entity::SortOrder;
 use crate::error::{OrmError, OrmResult};
+use crate::soft_delete::{SoftDeletable, SoftDeleteExt};
+use philjs::server::CursorCodec;
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 
@@ -55,6 +58,29 @@ pub struct PaginatedResult<T> {
     pub pagination: PaginationMeta,
 }
 
+impl<T> PaginatedResult<T> {
+    /// Split into `(data, total, page, per_page)`
+    ///
+    /// Matches the arguments of the web integrations' `PaginatedResponse::new`,
+    /// e.g. `PaginatedResponse::new(data, total, page, per_page)` in Rocket.
+    pub fn into_parts(self) -> (Vec<T>, u64, u32, u32) {
+        let page = u32::try_from(self.pagination.page).unwrap_or(u32::MAX);
+        let per_page = u32::try_from(self.pagination.per_page).unwrap_or(u32::MAX);
+        (self.data, self.pagination.total, page, per_page)
+    }
+
+    /// Map items to a different type, e.g. into API DTOs
+    pub fn map<U, F>(self, f: F) -> PaginatedResult<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        PaginatedResult {
+            data: self.data.into_iter().map(f).collect(),
+            pagination: self.pagination,
+        }
+    }
+}
+
 /// Pagination metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaginationMeta {
@@ -166,6 +192,13 @@ pub trait PaginateExt<E: EntityTrait> {
     fn paginate(self, db: &DatabaseConnection, params: PaginationParams) -> Paginator<E>;
 }
 
+impl<E: SoftDeletable> Paginator<E> {
+    /// Paginate only rows that are not soft deleted
+    pub fn active(db: &DatabaseConnection, select: Select<E>) -> Self {
+        Self::new(db, select.active())
+    }
+}
+
 impl<E: EntityTrait> PaginateExt<E> for Select<E> {
     fn paginate(self, db: &DatabaseConnection, params: PaginationParams) -> Paginator<E> {
         Paginator::new(db, self)
@@ -175,7 +208,7 @@ impl<E: EntityTrait> PaginateExt<E> for Select<E> {
 /// Cursor-based pagination parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CursorParams {
-    /// Cursor to start from (usually an ID)
+    /// Opaque cursor from a previous page's `next_cursor`
     pub cursor: Option<String>,
     /// Number of items to fetch
     pub limit: u64,
@@ -201,70 +234,266 @@ pub struct CursorResult<T> {
     pub has_more: bool,
 }
 
+impl<T> CursorResult<T> {
+    /// Map items to a different type, keeping the cursor
+    pub fn map<U, F>(self, f: F) -> CursorResult<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        CursorResult {
+            data: self.data.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            has_more: self.has_more,
+        }
+    }
+}
+
+/// A cursor column value, tagged with its type so it decodes losslessly
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t", content = "v", rename_all = "snake_case")]
+enum CursorValue {
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Char(char),
+    #[cfg(feature = "with-chrono")]
+    Date(String),
+    #[cfg(feature = "with-chrono")]
+    DateTime(String),
+    #[cfg(feature = "with-chrono")]
+    DateTimeUtc(String),
+    #[cfg(feature = "with-chrono")]
+    DateTimeWithTimeZone(String),
+    #[cfg(feature = "with-uuid")]
+    Uuid(String),
+}
+
+impl CursorValue {
+    fn from_value(value: Value) -> OrmResult<Self> {
+        let unsupported = |value: &Value| {
+            OrmError::Validation(format!("Unsupported or null cursor column value: {:?}", value))
+        };
+        Ok(match value {
+            Value::Bool(Some(v)) => Self::Bool(v),
+            Value::TinyInt(Some(v)) => Self::I8(v),
+            Value::SmallInt(Some(v)) => Self::I16(v),
+            Value::Int(Some(v)) => Self::I32(v),
+            Value::BigInt(Some(v)) => Self::I64(v),
+            Value::TinyUnsigned(Some(v)) => Self::U8(v),
+            Value::SmallUnsigned(Some(v)) => Self::U16(v),
+            Value::Unsigned(Some(v)) => Self::U32(v),
+            Value::BigUnsigned(Some(v)) => Self::U64(v),
+            Value::Float(Some(v)) => Self::F32(v),
+            Value::Double(Some(v)) => Self::F64(v),
+            Value::String(Some(v)) => Self::String(*v),
+            Value::Char(Some(v)) => Self::Char(v),
+            #[cfg(feature = "with-chrono")]
+            Value::ChronoDate(Some(v)) => Self::Date(v.to_string()),
+            #[cfg(feature = "with-chrono")]
+            Value::ChronoDateTime(Some(v)) => Self::DateTime(v.to_string()),
+            #[cfg(feature = "with-chrono")]
+            Value::ChronoDateTimeUtc(Some(v)) => Self::DateTimeUtc(v.to_rfc3339()),
+            #[cfg(feature = "with-chrono")]
+            Value::ChronoDateTimeWithTimeZone(Some(v)) => Self::DateTimeWithTimeZone(v.to_rfc3339()),
+            #[cfg(feature = "with-uuid")]
+            Value::Uuid(Some(v)) => Self::Uuid(v.to_string()),
+            other => return Err(unsupported(&other)),
+        })
+    }
+
+    fn into_value(self) -> OrmResult<Value> {
+        #[cfg(any(feature = "with-chrono", feature = "with-uuid"))]
+        let invalid = |e: &dyn std::fmt::Display| OrmError::Validation(format!("Invalid cursor value: {}", e));
+        Ok(match self {
+            Self::Bool(v) => v.into(),
+            Self::I8(v) => v.into(),
+            Self::I16(v) => v.into(),
+            Self::I32(v) => v.into(),
+            Self::I64(v) => v.into(),
+            Self::U8(v) => v.into(),
+            Self::U16(v) => v.into(),
+            Self::U32(v) => v.into(),
+            Self::U64(v) => v.into(),
+            Self::F32(v) => v.into(),
+            Self::F64(v) => v.into(),
+            Self::String(v) => v.into(),
+            Self::Char(v) => v.into(),
+            #[cfg(feature = "with-chrono")]
+            Self::Date(v) => v.parse::<sea_orm::prelude::Date>().map_err(|e| invalid(&e))?.into(),
+            #[cfg(feature = "with-chrono")]
+            Self::DateTime(v) => v.parse::<sea_orm::prelude::DateTime>().map_err(|e| invalid(&e))?.into(),
+            #[cfg(feature = "with-chrono")]
+            Self::DateTimeUtc(v) => v.parse::<sea_orm::prelude::DateTimeUtc>().map_err(|e| invalid(&e))?.into(),
+            #[cfg(feature = "with-chrono")]
+            Self::DateTimeWithTimeZone(v) => v
+                .parse::<sea_orm::prelude::DateTimeWithTimeZone>()
+                .map_err(|e| invalid(&e))?
+                .into(),
+            #[cfg(feature = "with-uuid")]
+            Self::Uuid(v) => v.parse::<sea_orm::prelude::Uuid>().map_err(|e| invalid(&e))?.into(),
+        })
+    }
+}
+
 /// Cursor paginator for infinite scrolling
 ///
+/// Pages by keyset rather than offset, so rows inserted while paging never
+/// shift results. Compound keys such as `(created_at, id)` give a stable
+/// order when the leading column has duplicates; the last column should be
+/// unique and every cursor column non-null.
+///
 /// # Example
 ///
 /// ```rust
 /// use philjs_seaorm::pagination::{CursorPaginator, CursorParams};
 ///
 /// let params = CursorParams { cursor: None, limit: 20 };
-/// let result = CursorPaginator::new(&db, users::Entity::find())
-///     .by_column(users::Column::Id)
+/// let result = CursorPaginator::new(&db, posts::Entity::find())
+///     .by_columns([posts::Column::CreatedAt, posts::Column::Id])
+///     .descending()
+///     .signing_key(secret)
 ///     .paginate(params)
 ///     .await?;
 /// ```
 pub struct CursorPaginator<E: EntityTrait> {
     db: DatabaseConnection,
     select: Select<E>,
-    cursor_column: Option<String>,
+    columns: Vec<E::Column>,
+    order: SortOrder,
+    codec: CursorCodec,
 }
 
 impl<E: EntityTrait> CursorPaginator<E> {
-    /// Create a new cursor paginator
+    /// Create a new cursor paginator, ordered by primary key
     pub fn new(db: &DatabaseConnection, select: Select<E>) -> Self {
         Self {
             db: db.clone(),
             select,
-            cursor_column: None,
+            columns: E::PrimaryKey::iter().map(|key| key.into_column()).collect(),
+            order: SortOrder::Asc,
+            codec: CursorCodec::unsigned(),
         }
     }
 
     /// Set the column to use for cursor
-    pub fn by_column<C: ColumnTrait>(mut self, _col: C) -> Self {
-        self.cursor_column = Some("id".to_string()); // Simplified
+    pub fn by_column(self, col: E::Column) -> Self {
+        self.by_columns([col])
+    }
+
+    /// Use a compound cursor, most significant column first
+    pub fn by_columns(mut self, cols: impl IntoIterator<Item = E::Column>) -> Self {
+        self.columns = cols.into_iter().collect();
         self
     }
 
-    /// Paginate with cursor
-    pub async fn paginate(&self, params: CursorParams) -> OrmResult<CursorResult<E::Model>> {
+    /// Set the sort order
+    pub fn order(mut self, order: SortOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sort newest/largest first
+    pub fn descending(self) -> Self {
+        self.order(SortOrder::Desc)
+    }
+
+    /// Sign cursors with `key` so clients cannot forge them
+    ///
+    /// Without a key, cursors only catch accidental corruption and clients
+    /// can craft one for any column values.
+    pub fn signing_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.codec = CursorCodec::signed(key.into());
+        self
+    }
+
+    /// Identifies the columns and order a cursor belongs to
+    fn shape(&self) -> String {
+        let columns: Vec<&str> = self.columns.iter().map(|col| col.as_str()).collect();
+        format!("{}:{}:{:?}", E::default().table_name(), columns.join(","), self.order)
+    }
+
+    /// Rows strictly after `values` in cursor order
+    ///
+    /// For `(a, b)` ascending: `a > va OR (a = va AND b > vb)`.
+    fn after(&self, values: Vec<Value>) -> OrmResult<Condition> {
+        if values.len() != self.columns.len() {
+            return Err(OrmError::Validation("Invalid pagination cursor".to_string()));
+        }
+        let mut condition = Condition::any();
+        for i in 0..self.columns.len() {
+            let mut branch = Condition::all();
+            for (col, value) in self.columns[..i].iter().zip(&values) {
+                branch = branch.add(col.eq(value.clone()));
+            }
+            let (col, value) = (self.columns[i], values[i].clone());
+            branch = branch.add(match self.order {
+                SortOrder::Asc => col.gt(value),
+                SortOrder::Desc => col.lt(value),
+            });
+            condition = condition.add(branch);
+        }
+        Ok(condition)
+    }
+
+    fn query(&self, cursor: Option<&str>) -> OrmResult<Select<E>> {
         let mut query = self.select.clone();
+        if let Some(cursor) = cursor {
+            let values = self
+                .codec
+                .decode::<Vec<CursorValue>>(&self.shape(), cursor)
+                .ok_or_else(|| OrmError::Validation("Invalid pagination cursor".to_string()))?
+                .into_iter()
+                .map(CursorValue::into_value)
+                .collect::<OrmResult<Vec<_>>>()?;
+            query = query.filter(self.after(values)?);
+        }
+        for col in &self.columns {
+            query = query.order_by(*col, self.order.into());
+        }
+        Ok(query)
+    }
 
-        // Apply cursor if provided
-        if let Some(cursor) = &params.cursor {
-            // In practice, you would parse the cursor and apply it to the query
-            // This is a simplified version
-            let _ = cursor;
+    /// The cursor pointing just past `model`
+    pub fn cursor_for(&self, model: &E::Model) -> OrmResult<String> {
+        let values = self
+            .columns
+            .iter()
+            .map(|col| CursorValue::from_value(model.get(*col)))
+            .collect::<OrmResult<Vec<_>>>()?;
+        Ok(self.codec.encode(&self.shape(), &values)?)
+    }
+
+    /// Paginate with cursor
+    pub async fn paginate(&self, params: CursorParams) -> OrmResult<CursorResult<E::Model>> {
+        if self.columns.is_empty() {
+            return Err(OrmError::Validation("Cursor pagination needs at least one column".to_string()));
         }
 
         // Fetch one extra to determine if there are more items
-        let data = query
+        let mut data = self
+            .query(params.cursor.as_deref())?
             .limit(params.limit + 1)
             .all(&self.db)
             .await
             .map_err(|e| OrmError::Query(e.to_string()))?;
 
         let has_more = data.len() > params.limit as usize;
-        let mut data = data;
-
         if has_more {
             data.pop();
         }
 
-        let next_cursor = if has_more {
-            Some("next_cursor".to_string()) // Simplified
-        } else {
-            None
+        let next_cursor = match data.last() {
+            Some(last) if has_more => Some(self.cursor_for(last)?),
+            _ => None,
         };
 
         Ok(CursorResult {
@@ -275,6 +504,13 @@ impl<E: EntityTrait> CursorPaginator<E> {
     }
 }
 
+impl<E: SoftDeletable> CursorPaginator<E> {
+    /// Page through only rows that are not soft deleted
+    pub fn active(db: &DatabaseConnection, select: Select<E>) -> Self {
+        Self::new(db, select.active())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,4 +535,79 @@ mod tests {
         assert!(meta.has_next);
         assert!(meta.has_prev);
     }
+
+    mod posts {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "posts")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub created_at: i64,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    fn paginator() -> CursorPaginator<posts::Entity> {
+        CursorPaginator::new(&DatabaseConnection::Disconnected, posts::Entity::find())
+            .by_columns([posts::Column::CreatedAt, posts::Column::Id])
+            .descending()
+            .signing_key("secret")
+    }
+
+    #[test]
+    fn test_compound_cursor_query() {
+        let paginator = paginator();
+        let cursor = paginator
+            .cursor_for(&posts::Model { id: 7, created_at: 100 })
+            .unwrap();
+
+        let sql = paginator
+            .query(Some(&cursor))
+            .unwrap()
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.ends_with(
+            r#"WHERE "posts"."created_at" < 100 OR ("posts"."created_at" = 100 AND "posts"."id" < 7) ORDER BY "posts"."created_at" DESC, "posts"."id" DESC"#
+        ));
+    }
+
+    #[test]
+    fn test_cursor_tamper_checks() {
+        let paginator = paginator();
+        let cursor = paginator
+            .cursor_for(&posts::Model { id: 7, created_at: 100 })
+            .unwrap();
+        let (payload, tag) = cursor.split_once('.').unwrap();
+
+        let other = paginator
+            .cursor_for(&posts::Model { id: 1, created_at: 1 })
+            .unwrap();
+        let (forged, _) = other.split_once('.').unwrap();
+        assert!(paginator.query(Some(&format!("{}.{}", forged, tag))).is_err());
+        assert!(paginator.query(Some(payload)).is_err());
+
+        // Cursors only work for the listing that issued them
+        let ascending = paginator.order(SortOrder::Asc);
+        assert!(ascending.query(Some(&cursor)).is_err());
+        let unsigned = CursorPaginator::new(&DatabaseConnection::Disconnected, posts::Entity::find())
+            .by_columns([posts::Column::CreatedAt, posts::Column::Id])
+            .descending();
+        assert!(unsigned.query(Some(&cursor)).is_err());
+    }
+
+    #[test]
+    fn test_paginated_result_into_parts() {
+        let params = PaginationParams::new(2, 10);
+        let result = PaginatedResult {
+            data: vec![1, 2],
+            pagination: PaginationMeta::new(params, 12),
+        };
+        assert_eq!(result.map(|x| x * 10).into_parts(), (vec![10, 20], 12, 2, 10));
+    }
 }
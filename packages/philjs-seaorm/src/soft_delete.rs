@@ -0,0 +1,266 @@
+//! Soft delete support
+//!
+//! Entities opt in by implementing [`SoftDeletable`] and naming their
+//! nullable `deleted_at` column. [`SoftDeleteHelpers`] then mirrors the
+//! [`EntityHelpers`](crate::entity::EntityHelpers) queries with deleted rows
+//! filtered out, and adds `soft_delete`/`restore`.
+//!
+//! ```rust,ignore
+//! impl SoftDeletable for posts::Entity {
+//!     fn deleted_at_column() -> Self::Column {
+//!         posts::Column::DeletedAt
+//!     }
+//! }
+//!
+//! posts::Entity::soft_delete(&db, 42).await?;
+//! let visible = posts::Entity::find_active(&db).await?;
+//! posts::Entity::restore(&db, 42).await?;
+//!
+//! // Paginators respect the flag through the select they are given
+//! let page = Paginator::active(&db, posts::Entity::find()).paginate(params).await?;
+//! ```
+//!
+//! `EntityHelpers` stays unfiltered, since it serves every entity.
+
+use async_trait::async_trait;
+use sea_orm::{
+    entity::*, query::*, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    PrimaryKeyToColumn, PrimaryKeyTrait,
+};
+use sea_query::{Expr, IntoValueTuple, Keyword, SimpleExpr};
+
+use crate::entity::{PaginatedResult, Pagination};
+use crate::error::{OrmError, OrmResult};
+
+/// An entity whose rows are hidden, not removed, on delete
+pub trait SoftDeletable: EntityTrait {
+    /// The nullable timestamp column marking deleted rows
+    fn deleted_at_column() -> Self::Column;
+
+    /// Condition matching rows that are not deleted
+    fn active_condition() -> Condition {
+        Condition::all().add(Self::deleted_at_column().is_null())
+    }
+
+    /// Condition matching deleted rows
+    fn trashed_condition() -> Condition {
+        Condition::all().add(Self::deleted_at_column().is_not_null())
+    }
+}
+
+/// Condition matching the row with primary key `pk`
+fn pk_condition<E, P>(pk: P) -> Condition
+where
+    E: EntityTrait,
+    P: Into<<E::PrimaryKey as PrimaryKeyTrait>::ValueType>,
+{
+    E::PrimaryKey::iter()
+        .zip(pk.into().into_value_tuple())
+        .fold(Condition::all(), |cond, (key, value)| cond.add(key.into_column().eq(value)))
+}
+
+/// Scope a select to live or deleted rows
+pub trait SoftDeleteExt {
+    /// Only rows that are not deleted
+    fn active(self) -> Self;
+
+    /// Only deleted rows
+    fn trashed(self) -> Self;
+}
+
+impl<E: SoftDeletable> SoftDeleteExt for Select<E> {
+    fn active(self) -> Self {
+        self.filter(E::active_condition())
+    }
+
+    fn trashed(self) -> Self {
+        self.filter(E::trashed_condition())
+    }
+}
+
+/// Queries over soft-deletable entities
+#[async_trait]
+pub trait SoftDeleteHelpers: SoftDeletable {
+    /// Select rows that are not deleted
+    fn find_active_query() -> Select<Self> {
+        Self::find().active()
+    }
+
+    /// Find all rows that are not deleted
+    async fn find_active(db: &DatabaseConnection) -> OrmResult<Vec<Self::Model>> {
+        Self::find_active_query()
+            .all(db)
+            .await
+            .map_err(|e| OrmError::Query(e.to_string()))
+    }
+
+    /// Find by primary key, unless deleted
+    async fn find_active_by_pk<P>(db: &DatabaseConnection, pk: P) -> OrmResult<Option<Self::Model>>
+    where
+        P: Into<<<Self as EntityTrait>::PrimaryKey as PrimaryKeyTrait>::ValueType> + Send,
+    {
+        Self::find_by_id(pk)
+            .active()
+            .one(db)
+            .await
+            .map_err(|e| OrmError::Query(e.to_string()))
+    }
+
+    /// Find rows matching `condition` that are not deleted
+    async fn find_active_many(
+        db: &DatabaseConnection,
+        condition: Condition,
+    ) -> OrmResult<Vec<Self::Model>> {
+        Self::find_active_query()
+            .filter(condition)
+            .all(db)
+            .await
+            .map_err(|e| OrmError::Query(e.to_string()))
+    }
+
+    /// Find deleted rows
+    async fn find_trashed(db: &DatabaseConnection) -> OrmResult<Vec<Self::Model>> {
+        Self::find()
+            .trashed()
+            .all(db)
+            .await
+            .map_err(|e| OrmError::Query(e.to_string()))
+    }
+
+    /// Paginate rows that are not deleted
+    async fn find_active_paginated(
+        db: &DatabaseConnection,
+        pagination: &Pagination,
+    ) -> OrmResult<PaginatedResult<Self::Model>> {
+        let total = Self::count_active(db).await?;
+
+        let items = Self::find_active_query()
+            .offset(pagination.offset())
+            .limit(pagination.limit())
+            .all(db)
+            .await
+            .map_err(|e| OrmError::Query(e.to_string()))?;
+
+        Ok(PaginatedResult::new(items, total, pagination))
+    }
+
+    /// Count rows that are not deleted
+    async fn count_active(db: &DatabaseConnection) -> OrmResult<u64> {
+        Self::find_active_query()
+            .count(db)
+            .await
+            .map_err(|e| OrmError::Query(e.to_string()))
+    }
+
+    /// Mark the row with primary key `pk` deleted
+    ///
+    /// Returns the number of rows affected; already deleted rows keep their
+    /// original timestamp.
+    async fn soft_delete<P>(db: &DatabaseConnection, pk: P) -> OrmResult<u64>
+    where
+        P: Into<<<Self as EntityTrait>::PrimaryKey as PrimaryKeyTrait>::ValueType> + Send,
+    {
+        Self::soft_delete_where(db, pk_condition::<Self, P>(pk)).await
+    }
+
+    /// Mark rows matching `condition` deleted
+    async fn soft_delete_where(db: &DatabaseConnection, condition: Condition) -> OrmResult<u64> {
+        let result = Self::update_many()
+            .col_expr(Self::deleted_at_column(), Expr::current_timestamp().into())
+            .filter(condition)
+            .filter(Self::active_condition())
+            .exec(db)
+            .await
+            .map_err(|e| OrmError::Query(e.to_string()))?;
+
+        Ok(result.rows_affected)
+    }
+
+    /// Restore the deleted row with primary key `pk`
+    async fn restore<P>(db: &DatabaseConnection, pk: P) -> OrmResult<u64>
+    where
+        P: Into<<<Self as EntityTrait>::PrimaryKey as PrimaryKeyTrait>::ValueType> + Send,
+    {
+        Self::restore_where(db, pk_condition::<Self, P>(pk)).await
+    }
+
+    /// Restore deleted rows matching `condition`
+    async fn restore_where(db: &DatabaseConnection, condition: Condition) -> OrmResult<u64> {
+        let result = Self::update_many()
+            .col_expr(Self::deleted_at_column(), SimpleExpr::Keyword(Keyword::Null))
+            .filter(condition)
+            .filter(Self::trashed_condition())
+            .exec(db)
+            .await
+            .map_err(|e| OrmError::Query(e.to_string()))?;
+
+        Ok(result.rows_affected)
+    }
+
+    /// Permanently delete deleted rows
+    async fn purge_trashed(db: &DatabaseConnection) -> OrmResult<u64> {
+        let result = Self::delete_many()
+            .filter(Self::trashed_condition())
+            .exec(db)
+            .await
+            .map_err(|e| OrmError::Query(e.to_string()))?;
+
+        Ok(result.rows_affected)
+    }
+}
+
+impl<E: SoftDeletable> SoftDeleteHelpers for E {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::DbBackend;
+
+    mod posts {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "posts")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub title: String,
+            pub deleted_at: Option<String>,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    impl SoftDeletable for posts::Entity {
+        fn deleted_at_column() -> Self::Column {
+            posts::Column::DeletedAt
+        }
+    }
+
+    #[test]
+    fn test_active_and_trashed_scopes() {
+        let active = posts::Entity::find_active_query().build(DbBackend::Postgres).to_string();
+        assert!(active.ends_with(r#"WHERE "posts"."deleted_at" IS NULL"#));
+
+        let trashed = posts::Entity::find()
+            .filter(posts::Column::Title.eq("a"))
+            .trashed()
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(trashed.contains(r#""posts"."deleted_at" IS NOT NULL"#));
+    }
+
+    #[test]
+    fn test_pk_condition() {
+        let sql = posts::Entity::update_many()
+            .col_expr(posts::Column::DeletedAt, SimpleExpr::Keyword(Keyword::Null))
+            .filter(pk_condition::<posts::Entity, _>(7))
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert_eq!(sql, r#"UPDATE "posts" SET "deleted_at" = NULL WHERE "posts"."id" = 7"#);
+    }
+}
@@ -0,0 +1,179 @@
+//! Optimistic locking with a version column
+//!
+//! Entities opt in by implementing [`Versioned`]. Updates made through
+//! [`VersionedActiveModel::update_versioned`] run as
+//! `UPDATE ... SET version = version + 1 WHERE id = ? AND version = ?` and
+//! fail with [`OrmError::Conflict`] when another writer got there first.
+//!
+//! ```rust,ignore
+//! impl Versioned for posts::Entity {
+//!     fn version_column() -> Self::Column {
+//!         posts::Column::Version
+//!     }
+//! }
+//!
+//! let mut post: posts::ActiveModel = post.into();
+//! post.title = Set("Edited".to_string());
+//! match post.update_versioned(&db).await {
+//!     Err(OrmError::Conflict { .. }) => { /* reload and retry, or ask the user */ }
+//!     other => other?,
+//! };
+//! ```
+
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityName,
+    EntityTrait, IntoActiveModel, QueryFilter, Value,
+};
+
+use crate::error::{OrmError, OrmResult};
+
+/// An entity guarded by an integer version column
+pub trait Versioned: EntityTrait {
+    /// The integer column incremented on every update
+    fn version_column() -> Self::Column;
+}
+
+/// The version as an `i64`, for any integer column type
+fn version_number(value: &Value) -> Option<i64> {
+    match value {
+        Value::TinyInt(Some(v)) => Some(*v as i64),
+        Value::SmallInt(Some(v)) => Some(*v as i64),
+        Value::Int(Some(v)) => Some(*v as i64),
+        Value::BigInt(Some(v)) => Some(*v),
+        Value::TinyUnsigned(Some(v)) => Some(*v as i64),
+        Value::SmallUnsigned(Some(v)) => Some(*v as i64),
+        Value::Unsigned(Some(v)) => Some(*v as i64),
+        Value::BigUnsigned(Some(v)) => i64::try_from(*v).ok(),
+        _ => None,
+    }
+}
+
+/// The next version, keeping the column's integer type
+fn next_version(value: &Value) -> Option<Value> {
+    Some(match value {
+        Value::TinyInt(Some(v)) => Value::TinyInt(Some(v.checked_add(1)?)),
+        Value::SmallInt(Some(v)) => Value::SmallInt(Some(v.checked_add(1)?)),
+        Value::Int(Some(v)) => Value::Int(Some(v.checked_add(1)?)),
+        Value::BigInt(Some(v)) => Value::BigInt(Some(v.checked_add(1)?)),
+        Value::TinyUnsigned(Some(v)) => Value::TinyUnsigned(Some(v.checked_add(1)?)),
+        Value::SmallUnsigned(Some(v)) => Value::SmallUnsigned(Some(v.checked_add(1)?)),
+        Value::Unsigned(Some(v)) => Value::Unsigned(Some(v.checked_add(1)?)),
+        Value::BigUnsigned(Some(v)) => Value::BigUnsigned(Some(v.checked_add(1)?)),
+        _ => return None,
+    })
+}
+
+/// Version-checked updates for active models
+#[async_trait]
+pub trait VersionedActiveModel: ActiveModelTrait + ActiveModelBehavior + Send
+where
+    <Self as ActiveModelTrait>::Entity: Versioned,
+{
+    /// The version this model was read at
+    fn current_version(&self) -> OrmResult<Value> {
+        let column = <Self::Entity as Versioned>::version_column();
+        self.get(column).into_value().filter(|v| version_number(v).is_some()).ok_or_else(|| {
+            OrmError::Validation(format!(
+                "Versioned update of {} needs the current integer version set",
+                Self::Entity::default().table_name()
+            ))
+        })
+    }
+
+    /// Set the version column to the next version, returning the previous one
+    fn bump_version(&mut self) -> OrmResult<Value> {
+        let column = <Self::Entity as Versioned>::version_column();
+        let current = self.current_version()?;
+        let next = next_version(&current)
+            .ok_or_else(|| OrmError::Validation("Version column overflowed".to_string()))?;
+        self.set(column, next);
+        Ok(current)
+    }
+
+    /// Update only if the row still has the version this model was read at
+    ///
+    /// Bumps the version automatically. Returns [`OrmError::Conflict`] when
+    /// the row was changed or deleted since it was read.
+    async fn update_versioned(
+        mut self,
+        db: &DatabaseConnection,
+    ) -> OrmResult<<Self::Entity as EntityTrait>::Model>
+    where
+        <Self::Entity as EntityTrait>::Model: IntoActiveModel<Self>,
+    {
+        let column = <Self::Entity as Versioned>::version_column();
+        let expected = self.bump_version()?;
+        let expected_version = version_number(&expected).unwrap_or_default();
+
+        match Self::Entity::update(self).filter(column.eq(expected)).exec(db).await {
+            Ok(model) => Ok(model),
+            Err(DbErr::RecordNotUpdated) => Err(OrmError::Conflict {
+                entity: Self::Entity::default().table_name().to_string(),
+                expected_version,
+            }),
+            Err(e) => Err(OrmError::Query(e.to_string())),
+        }
+    }
+}
+
+impl<A> VersionedActiveModel for A
+where
+    A: ActiveModelTrait + ActiveModelBehavior + Send,
+    <A as ActiveModelTrait>::Entity: Versioned,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::ActiveValue::{NotSet, Set, Unchanged};
+
+    mod posts {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "posts")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub title: String,
+            pub version: i32,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    impl Versioned for posts::Entity {
+        fn version_column() -> Self::Column {
+            posts::Column::Version
+        }
+    }
+
+    #[test]
+    fn test_bump_version() {
+        let mut post = posts::ActiveModel {
+            id: Unchanged(1),
+            title: Set("Edited".to_string()),
+            version: Unchanged(3),
+        };
+
+        assert_eq!(post.bump_version().unwrap(), Value::Int(Some(3)));
+        assert_eq!(post.version, Set(4));
+    }
+
+    #[test]
+    fn test_bump_version_requires_version() {
+        let mut post = posts::ActiveModel {
+            id: Unchanged(1),
+            title: Set("Edited".to_string()),
+            version: NotSet,
+        };
+
+        assert!(matches!(post.bump_version(), Err(OrmError::Validation(_))));
+        assert_eq!(next_version(&Value::TinyInt(Some(i8::MAX))), None);
+    }
+}
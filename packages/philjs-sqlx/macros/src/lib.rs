@@ -0,0 +1,156 @@
+//! PhilJS SQLx Procedural Macros
+//!
+//! Provides `#[derive(Table)]`, which gives a row struct typed column
+//! constants for the query builder in `philjs_sqlx::query`.
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, LitStr};
+
+// ============================================================================
+// TABLE DERIVE - Typed column constants
+// ============================================================================
+
+/// Derive `philjs_sqlx::query::Table` and one typed column constant per field.
+///
+/// The table name defaults to the struct name in snake_case; override it
+/// with `#[table(name = "...")]`. Fields honour `#[sqlx(rename = "...")]`
+/// and `#[sqlx(skip)]`, or the same keys under `#[table(...)]`.
+///
+/// # Example
+/// ```rust
+/// #[derive(sqlx::FromRow, Table)]
+/// #[table(name = "users")]
+/// struct Users {
+///     id: i64,
+///     email: String,
+/// }
+///
+/// let query = Users::select().filter(Users::EMAIL.eq("a@example.com"));
+/// ```
+#[proc_macro_derive(Table, attributes(table))]
+pub fn derive_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_table(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_table(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Table derive only supports structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Table derive only supports structs",
+            ))
+        }
+    };
+
+    let table = attr_options(&input.attrs, "table")?
+        .name
+        .unwrap_or_else(|| snake_case(&name.to_string()));
+
+    let mut consts = Vec::new();
+    let mut columns = Vec::new();
+
+    for field in fields {
+        let mut options = attr_options(&field.attrs, "sqlx")?;
+        options.merge(attr_options(&field.attrs, "table")?);
+        if options.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("named field");
+        let field_name = ident.to_string();
+        let field_name = field_name.trim_start_matches("r#");
+        let column = options.name.unwrap_or_else(|| field_name.to_string());
+        let const_name = format_ident!("{}", field_name.to_uppercase());
+        let ty = &field.ty;
+        let doc = format!("The `{}` column", column);
+
+        consts.push(quote! {
+            #[doc = #doc]
+            pub const #const_name: ::philjs_sqlx::query::Column<Self, #ty> =
+                ::philjs_sqlx::query::Column::new(#column);
+        });
+        columns.push(column);
+    }
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#consts)*
+        }
+
+        impl #impl_generics ::philjs_sqlx::query::Table for #name #ty_generics #where_clause {
+            const NAME: &'static str = #table;
+            const COLUMNS: &'static [&'static str] = &[#(#columns),*];
+        }
+    })
+}
+
+#[derive(Default)]
+struct AttrOptions {
+    name: Option<String>,
+    skip: bool,
+}
+
+impl AttrOptions {
+    fn merge(&mut self, other: AttrOptions) {
+        if other.name.is_some() {
+            self.name = other.name;
+        }
+        self.skip |= other.skip;
+    }
+}
+
+/// Read `name`/`rename` and `skip` from `#[path(...)]` attributes
+///
+/// Unknown keys are ignored, since `#[sqlx(...)]` carries options meant for
+/// `FromRow`.
+fn attr_options(attrs: &[Attribute], path: &str) -> syn::Result<AttrOptions> {
+    let mut options = AttrOptions::default();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident(path)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") || meta.path.is_ident("rename") {
+                let value: LitStr = meta.value()?.parse()?;
+                options.name = Some(value.value());
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if meta.input.peek(syn::Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(options)
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, ch) in name.char_indices() {
+        if ch.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(ch.to_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
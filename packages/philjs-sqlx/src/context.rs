@@ -1,8 +1,10 @@
 //! Context integration for PhilJS
 
 use sqlx::Database;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
-use parking_lot::RwLock;
+use std::task::{Context, Poll};
 
 use crate::pool::DbPool;
 use crate::error::{DbError, DbResult};
@@ -55,7 +57,12 @@ pub fn provide_pool(pool: DbPool<sqlx::Postgres>) {
     let _ = GLOBAL_POOL.set(Arc::new(pool));
 }
 
-/// Get the database pool from context
+/// Get the database from context
+///
+/// Inside [`with_transaction_context`] this is the surrounding transaction,
+/// otherwise the pool given to `provide_pool()`. Acquire a connection from
+/// the handle to run queries, so composed server functions share whichever
+/// one is in effect.
 ///
 /// # Example
 ///
@@ -64,18 +71,195 @@ pub fn provide_pool(pool: DbPool<sqlx::Postgres>) {
 ///
 /// #[server]
 /// async fn get_users() -> Result<Vec<User>, ServerFnError> {
-///     let pool = use_pool()?;
-///     // Use pool...
+///     let db = use_pool()?;
+///     let mut conn = db.acquire().await?;
+///     let users = sqlx::query_as("SELECT * FROM users").fetch_all(&mut *conn).await?;
+///     Ok(users)
 /// }
 /// ```
 #[cfg(feature = "postgres")]
-pub fn use_pool() -> DbResult<DbPool<sqlx::Postgres>> {
+pub fn use_pool() -> DbResult<DbHandle> {
+    if let Some(tx) = philjs::use_context::<SharedTransaction>() {
+        return Ok(DbHandle::Transaction(tx));
+    }
+
     GLOBAL_POOL
         .get()
         .and_then(|p| p.downcast_ref::<DbPool<sqlx::Postgres>>().cloned())
+        .map(DbHandle::Pool)
         .ok_or_else(|| DbError::Context("Database pool not provided. Call provide_pool() first.".to_string()))
 }
 
+/// A transaction shared by everything running inside [`with_transaction_context`]
+#[cfg(feature = "postgres")]
+#[derive(Clone)]
+pub struct SharedTransaction(Arc<tokio::sync::Mutex<Option<sqlx::Transaction<'static, sqlx::Postgres>>>>);
+
+#[cfg(feature = "postgres")]
+impl SharedTransaction {
+    fn new(tx: sqlx::Transaction<'static, sqlx::Postgres>) -> Self {
+        Self(Arc::new(tokio::sync::Mutex::new(Some(tx))))
+    }
+
+    async fn take(&self) -> Option<sqlx::Transaction<'static, sqlx::Postgres>> {
+        self.0.lock().await.take()
+    }
+}
+
+/// The pool, or the transaction in effect, as returned by [`use_pool`]
+#[cfg(feature = "postgres")]
+#[derive(Clone)]
+pub enum DbHandle {
+    /// No transaction in context
+    Pool(DbPool<sqlx::Postgres>),
+    /// Inside `with_transaction_context`
+    Transaction(SharedTransaction),
+}
+
+#[cfg(feature = "postgres")]
+impl DbHandle {
+    /// Get a connection to run queries on
+    ///
+    /// For a transaction this locks it until the connection is dropped, so
+    /// don't hold one across calls to other server functions.
+    pub async fn acquire(&self) -> DbResult<DbConnection<'_>> {
+        match self {
+            DbHandle::Pool(pool) => pool
+                .acquire()
+                .await
+                .map(DbConnection::Pool)
+                .map_err(DbError::from),
+            DbHandle::Transaction(tx) => {
+                tokio::sync::MutexGuard::try_map(tx.0.lock().await, |tx| tx.as_mut())
+                    .map(DbConnection::Transaction)
+                    .map_err(|_| DbError::Transaction("Transaction already finished".to_string()))
+            }
+        }
+    }
+
+    /// Whether queries run inside a transaction
+    pub fn in_transaction(&self) -> bool {
+        matches!(self, DbHandle::Transaction(_))
+    }
+}
+
+/// A connection acquired from a [`DbHandle`]
+#[cfg(feature = "postgres")]
+pub enum DbConnection<'a> {
+    /// Pooled connection
+    Pool(sqlx::pool::PoolConnection<sqlx::Postgres>),
+    /// The shared transaction, locked
+    Transaction(tokio::sync::MappedMutexGuard<'a, sqlx::Transaction<'static, sqlx::Postgres>>),
+}
+
+#[cfg(feature = "postgres")]
+impl std::ops::Deref for DbConnection<'_> {
+    type Target = sqlx::PgConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            DbConnection::Pool(conn) => conn,
+            DbConnection::Transaction(tx) => tx,
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl std::ops::DerefMut for DbConnection<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            DbConnection::Pool(conn) => conn,
+            DbConnection::Transaction(tx) => tx,
+        }
+    }
+}
+
+/// Run `f` in a transaction that `use_pool()` hands to nested server functions
+///
+/// Commits if `f` succeeds and rolls back if it fails, so server functions
+/// composed inside it succeed or fail together. When already inside a
+/// transaction context, `f` joins the outer transaction instead of starting
+/// a new one.
+///
+/// # Example
+///
+/// ```rust
+/// use philjs_sqlx::prelude::*;
+///
+/// with_transaction_context(&pool, |_db| async move {
+///     create_order(order).await?;     // calls use_pool() internally
+///     reserve_stock(items).await?;    // same transaction
+///     Ok(())
+/// }).await?;
+/// ```
+#[cfg(feature = "postgres")]
+pub async fn with_transaction_context<F, Fut, T>(pool: &DbPool<sqlx::Postgres>, f: F) -> DbResult<T>
+where
+    F: FnOnce(DbHandle) -> Fut,
+    Fut: Future<Output = DbResult<T>>,
+{
+    if let Some(outer) = philjs::use_context::<SharedTransaction>() {
+        return f(DbHandle::Transaction(outer)).await;
+    }
+
+    let tx = pool
+        .begin()
+        .await
+        .map_err(|e| DbError::Transaction(e.to_string()))?;
+    let shared = SharedTransaction::new(tx);
+
+    let result = ContextScoped::new(f(DbHandle::Transaction(shared.clone())), shared.clone()).await;
+
+    let Some(tx) = shared.take().await else {
+        return Err(DbError::Transaction(
+            "Transaction was finished inside with_transaction_context".to_string(),
+        ));
+    };
+
+    match result {
+        Ok(value) => {
+            tx.commit()
+                .await
+                .map_err(|e| DbError::Transaction(e.to_string()))?;
+            Ok(value)
+        }
+        Err(e) => {
+            tx.rollback()
+                .await
+                .map_err(|e| DbError::Transaction(e.to_string()))?;
+            Err(e)
+        }
+    }
+}
+
+/// Future with `value` provided to `use_context` on every poll
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+struct ContextScoped<F, V> {
+    inner: Pin<Box<F>>,
+    value: V,
+}
+
+impl<F, V> ContextScoped<F, V> {
+    fn new(inner: F, value: V) -> Self {
+        Self {
+            inner: Box::pin(inner),
+            value,
+        }
+    }
+}
+
+impl<F: Future, V: Clone + Unpin + 'static> Future for ContextScoped<F, V> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let value = self.value.clone();
+        philjs::reactive::context::with_context_scope(|| {
+            philjs::provide_context(value);
+            self.inner.as_mut().poll(cx)
+        })
+    }
+}
+
 /// Database context provider for reactive applications
 pub struct DbProvider<DB: Database> {
     pool: Arc<DbPool<DB>>,
@@ -248,4 +432,21 @@ mod tests {
             assert_eq!(tenant.schema(), Some("tenant_123"));
         }
     }
+
+    #[tokio::test]
+    async fn test_context_survives_await_points() {
+        let seen = ContextScoped::new(
+            async {
+                let before = philjs::use_context::<String>();
+                tokio::task::yield_now().await;
+                let after = philjs::use_context::<String>();
+                (before, after)
+            },
+            "tx-1".to_string(),
+        )
+        .await;
+
+        assert_eq!(seen, (Some("tx-1".to_string()), Some("tx-1".to_string())));
+        assert_eq!(philjs::use_context::<String>(), None);
+    }
 }
@@ -17,8 +17,14 @@ pub enum DbError {
     MultipleRows,
     /// Constraint violation
     Constraint(String),
+    /// Deadlock detected; the transaction can be retried
+    Deadlock(String),
+    /// Serialization failure under concurrent transactions; can be retried
+    SerializationFailure(String),
     /// Serialization error
     Serialization(String),
+    /// Invalid input, such as a malformed pagination cursor
+    Validation(String),
     /// Context error (pool not available)
     Context(String),
     /// Migration error
@@ -40,7 +46,10 @@ impl fmt::Display for DbError {
             DbError::NotFound => write!(f, "Row not found"),
             DbError::MultipleRows => write!(f, "Multiple rows found, expected one"),
             DbError::Constraint(msg) => write!(f, "Constraint violation: {}", msg),
+            DbError::Deadlock(msg) => write!(f, "Deadlock detected: {}", msg),
+            DbError::SerializationFailure(msg) => write!(f, "Serialization failure: {}", msg),
             DbError::Serialization(msg) => write!(f, "Serialization error: {}", msg),
+            DbError::Validation(msg) => write!(f, "Validation error: {}", msg),
             DbError::Context(msg) => write!(f, "Context error: {}", msg),
             DbError::Migration(msg) => write!(f, "Migration error: {}", msg),
             DbError::Timeout => write!(f, "Database operation timed out"),
@@ -62,6 +71,16 @@ impl From<sqlx::Error> for DbError {
             sqlx::Error::PoolTimedOut => DbError::Timeout,
             sqlx::Error::PoolClosed => DbError::PoolExhausted,
             sqlx::Error::Database(db_err) => {
+                match transaction_conflict(db_err.as_ref()) {
+                    Some(TransactionConflict::Deadlock) => {
+                        return DbError::Deadlock(db_err.message().to_string())
+                    }
+                    Some(TransactionConflict::SerializationFailure) => {
+                        return DbError::SerializationFailure(db_err.message().to_string())
+                    }
+                    None => {}
+                }
+
                 // Check for constraint violations
                 if let Some(code) = db_err.code() {
                     let code_str = code.to_string();
@@ -79,6 +98,54 @@ impl From<sqlx::Error> for DbError {
     }
 }
 
+/// Why a transaction lost out to a concurrent one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionConflict {
+    /// Postgres `40P01`, MySQL error `1213`
+    Deadlock,
+    /// SQLSTATE `40001`
+    SerializationFailure,
+}
+
+/// Classify backend errors that are safe to retry in a fresh transaction
+pub fn transaction_conflict(err: &dyn sqlx::error::DatabaseError) -> Option<TransactionConflict> {
+    #[cfg(feature = "mysql")]
+    if let Some(mysql) = err.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+        // ER_LOCK_DEADLOCK; MySQL reports it with SQLSTATE 40001
+        if mysql.number() == 1213 {
+            return Some(TransactionConflict::Deadlock);
+        }
+    }
+
+    classify_sqlstate(err.code()?.as_ref())
+}
+
+fn classify_sqlstate(code: &str) -> Option<TransactionConflict> {
+    match code {
+        "40P01" => Some(TransactionConflict::Deadlock),
+        "40001" => Some(TransactionConflict::SerializationFailure),
+        _ => None,
+    }
+}
+
+impl DbError {
+    /// Convert an error from beginning or committing a transaction
+    ///
+    /// Retryable errors keep their variant; anything else becomes
+    /// [`DbError::Transaction`].
+    pub fn from_transaction(err: sqlx::Error) -> Self {
+        match DbError::from(err) {
+            e if e.is_retryable() => e,
+            e => DbError::Transaction(e.to_string()),
+        }
+    }
+
+    /// Whether a concurrent transaction caused this error
+    pub fn is_transaction_conflict(&self) -> bool {
+        matches!(self, DbError::Deadlock(_) | DbError::SerializationFailure(_))
+    }
+}
+
 impl From<serde_json::Error> for DbError {
     fn from(err: serde_json::Error) -> Self {
         DbError::Serialization(err.to_string())
@@ -137,7 +204,9 @@ impl DbError {
         match self {
             DbError::NotFound => 404,
             DbError::Constraint(_) => 409, // Conflict
+            DbError::Deadlock(_) | DbError::SerializationFailure(_) => 409,
             DbError::Serialization(_) => 400, // Bad Request
+            DbError::Validation(_) => 400, // Bad Request
             DbError::Timeout => 504, // Gateway Timeout
             DbError::PoolExhausted => 503, // Service Unavailable
             _ => 500, // Internal Server Error
@@ -148,7 +217,11 @@ impl DbError {
     pub fn is_retryable(&self) -> bool {
         matches!(
             self,
-            DbError::Timeout | DbError::PoolExhausted | DbError::Connection(_)
+            DbError::Timeout
+                | DbError::PoolExhausted
+                | DbError::Connection(_)
+                | DbError::Deadlock(_)
+                | DbError::SerializationFailure(_)
         )
     }
 
@@ -163,6 +236,18 @@ impl DbError {
     }
 }
 
+/// Convert DbError to a server function error, hiding internals of 5xx errors
+impl From<DbError> for philjs::ServerError {
+    fn from(err: DbError) -> Self {
+        let message = if err.is_client_error() {
+            err.to_string()
+        } else {
+            "Database error".to_string()
+        };
+        philjs::ServerError::new(message).with_status(err.status_code())
+    }
+}
+
 /// Retry configuration for database operations
 #[derive(Clone, Debug)]
 pub struct RetryConfig {
@@ -174,6 +259,8 @@ pub struct RetryConfig {
     pub max_delay_ms: u64,
     /// Multiplier for exponential backoff
     pub backoff_multiplier: f64,
+    /// Fraction of each delay randomized, from 0.0 (none) to 1.0
+    pub jitter: f64,
 }
 
 impl Default for RetryConfig {
@@ -183,6 +270,7 @@ impl Default for RetryConfig {
             initial_delay_ms: 100,
             max_delay_ms: 5000,
             backoff_multiplier: 2.0,
+            jitter: 0.5,
         }
     }
 }
@@ -211,12 +299,48 @@ impl RetryConfig {
         self
     }
 
+    /// Set the backoff multiplier
+    pub fn backoff_multiplier(mut self, multiplier: f64) -> Self {
+        self.backoff_multiplier = multiplier;
+        self
+    }
+
+    /// Set the randomized fraction of each delay
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
     /// Calculate delay for a given attempt
     pub fn delay_for_attempt(&self, attempt: u32) -> u64 {
         let delay = (self.initial_delay_ms as f64)
             * self.backoff_multiplier.powi(attempt as i32);
         (delay as u64).min(self.max_delay_ms)
     }
+
+    /// Delay for a given attempt with jitter applied
+    ///
+    /// Spreads retries of transactions that conflicted with each other so
+    /// they don't collide again.
+    pub fn jittered_delay(&self, attempt: u32) -> std::time::Duration {
+        let delay = self.delay_for_attempt(attempt) as f64;
+        let spread = delay * self.jitter * random_unit();
+        std::time::Duration::from_millis((delay - spread) as u64)
+    }
+}
+
+/// A pseudo-random value in `[0, 1)`, good enough for jitter
+fn random_unit() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
 }
 
 #[cfg(test)]
@@ -237,6 +361,7 @@ mod tests {
         assert_eq!(DbError::NotFound.status_code(), 404);
         assert_eq!(DbError::Timeout.status_code(), 504);
         assert_eq!(DbError::PoolExhausted.status_code(), 503);
+        assert_eq!(DbError::Validation("bad cursor".to_string()).status_code(), 400);
     }
 
     #[test]
@@ -246,6 +371,28 @@ mod tests {
         assert!(!DbError::NotFound.is_retryable());
     }
 
+    #[test]
+    fn test_transaction_conflicts() {
+        assert_eq!(classify_sqlstate("40001"), Some(TransactionConflict::SerializationFailure));
+        assert_eq!(classify_sqlstate("40P01"), Some(TransactionConflict::Deadlock));
+        assert_eq!(classify_sqlstate("23505"), None);
+
+        let err = DbError::Deadlock("deadlock detected".to_string());
+        assert!(err.is_transaction_conflict() && err.is_retryable());
+        assert_eq!(err.status_code(), 409);
+    }
+
+    #[test]
+    fn test_jittered_delay() {
+        let config = RetryConfig::new().jitter(0.5);
+        for attempt in 0..4 {
+            let delay = config.jittered_delay(attempt).as_millis() as u64;
+            let max = config.delay_for_attempt(attempt);
+            assert!(delay <= max && delay >= max / 2, "{} not in {}..={}", delay, max / 2, max);
+        }
+        assert_eq!(RetryConfig::new().jitter(0.0).jittered_delay(1).as_millis(), 200);
+    }
+
     #[test]
     fn test_option_ext() {
         let some: Option<i32> = Some(42);
@@ -37,23 +37,37 @@
 
 #![warn(missing_docs)]
 
+// Lets `#[derive(Table)]` output resolve inside this crate too
+extern crate self as philjs_sqlx;
+
 pub mod pool;
 pub mod query;
 pub mod context;
 pub mod error;
 pub mod reactive;
 pub mod transaction;
+pub mod monitor;
+pub mod pagination;
+pub mod stream;
 
 #[cfg(feature = "migrate")]
 pub mod migration;
 #[cfg(feature = "migrate")]
 pub mod migrate;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 // Re-exports
 pub use pool::{DbPool, PoolConfig, create_pool};
-pub use query::{Query, QueryBuilder, Executor};
+pub use query::{Query, QueryBuilder, Executor, Table, Condition, Select, SqlValue, SqlType, Backend};
+pub use philjs_sqlx_macros::Table;
 pub use context::{provide_pool, use_pool, DbContext};
+#[cfg(feature = "postgres")]
+pub use context::{with_transaction_context, DbHandle, DbConnection};
 pub use error::{DbError, DbResult};
+pub use monitor::{PoolMonitor, PoolMetrics, render_prometheus};
+pub use pagination::{paginate_keyset, CursorKey, KeysetQuery, Page};
+pub use stream::DbStream;
 
 // Re-export sqlx types
 pub use sqlx::{
@@ -74,22 +88,39 @@ pub use sqlx::{Sqlite, SqlitePool, SqliteConnection, SqliteRow};
 /// Prelude - import commonly used items
 pub mod prelude {
     pub use crate::pool::{DbPool, PoolConfig, create_pool};
-    pub use crate::query::{Query, QueryBuilder, Executor};
+    pub use crate::query::{Query, QueryBuilder, Executor, Table, Column, Select};
+    pub use philjs_sqlx_macros::Table;
     pub use crate::context::{provide_pool, use_pool, DbContext};
+    #[cfg(feature = "postgres")]
+    pub use crate::context::{with_transaction_context, DbHandle};
     pub use crate::error::{DbError, DbResult};
 
     // Reactive queries
     pub use crate::reactive::{ReactiveQuery, DbResource, ReactiveQueryBuilder};
+    #[cfg(feature = "postgres")]
+    pub use crate::reactive::{QueryWatch, install_notify_trigger};
+
+    // Monitoring
+    pub use crate::monitor::{PoolMonitor, PoolMetrics};
+
+    // Pagination
+    pub use crate::pagination::{paginate_keyset, CursorKey, KeysetQuery, Page};
+
+    // Streaming
+    pub use crate::stream::DbStream;
 
     // Transactions
     pub use crate::transaction::{
         TransactionHelper, with_transaction, Savepoint,
-        BatchOperation, retry_transaction,
+        BatchOperation, retry_transaction, retry_transaction_with,
     };
 
     #[cfg(feature = "migrate")]
     pub use crate::migrate::{run_migrations, MigrationRunner};
 
+    #[cfg(feature = "testing")]
+    pub use crate::testing::{TestDb, TestTx};
+
     pub use sqlx::{FromRow, Row, query, query_as, query_scalar};
 
     #[cfg(feature = "postgres")]
@@ -3,9 +3,94 @@
 //! This module provides helpers for running and managing database migrations.
 
 use crate::error::{DbError, DbResult};
+use serde::{Deserialize, Serialize};
+use sqlx::migrate::{Migrate, MigrateError};
 use sqlx::{Database, migrate::Migrator, Pool};
 use std::path::Path;
 
+fn migration_error(e: impl std::fmt::Display) -> DbError {
+    DbError::Migration(e.to_string())
+}
+
+/// A migration and whether it has been applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationInfo {
+    /// Version, usually the timestamp prefix of the file name
+    pub version: i64,
+    /// Description taken from the file name
+    pub description: String,
+    /// Whether the migration has been applied
+    pub applied: bool,
+}
+
+/// A migration action, as run by `cargo philjs db`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrateCommand {
+    /// Apply pending migrations
+    Up,
+    /// Revert the last `steps` migrations
+    Down {
+        /// Migrations to revert
+        steps: usize,
+    },
+    /// Revert and re-apply the last migration
+    Redo,
+    /// List applied and pending migrations
+    Status,
+    /// Revert every migration
+    Reset,
+    /// Revert every migration, then apply all
+    Fresh,
+}
+
+impl MigrateCommand {
+    /// Parse `<action> [--steps N]`, ignoring unknown flags such as `--json`
+    ///
+    /// No action means `up`.
+    pub fn from_args<I, S>(args: I) -> DbResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut action = None;
+        let mut steps = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let value = if arg == "--steps" || arg == "-n" {
+                Some(
+                    args.next()
+                        .ok_or_else(|| migration_error("--steps needs a value"))?
+                        .as_ref()
+                        .to_string(),
+                )
+            } else {
+                arg.strip_prefix("--steps=").map(str::to_string)
+            };
+            if let Some(value) = value {
+                steps = Some(value.parse::<usize>().map_err(|_| {
+                    migration_error(format!("Invalid --steps value '{}'", value))
+                })?);
+            } else if !arg.starts_with('-') && action.is_none() {
+                action = Some(arg.to_string());
+            }
+        }
+
+        match action.as_deref().unwrap_or("up") {
+            "up" => Ok(MigrateCommand::Up),
+            "down" => Ok(MigrateCommand::Down { steps: steps.unwrap_or(1) }),
+            "redo" => Ok(MigrateCommand::Redo),
+            "status" => Ok(MigrateCommand::Status),
+            "reset" => Ok(MigrateCommand::Reset),
+            "fresh" => Ok(MigrateCommand::Fresh),
+            other => Err(migration_error(format!(
+                "Unknown migrate action '{}' (expected up, down, redo, status, reset, or fresh)",
+                other
+            ))),
+        }
+    }
+}
+
 /// Migration runner with progress tracking
 ///
 /// # Example
@@ -98,3 +183,123 @@ pub async fn has_pending_migrations<DB: Database>(
     // This is a simplified check - in practice you'd compare with applied migrations
     Ok(!migrator.migrations.is_empty())
 }
+
+impl<'a, DB> MigrationRunner<'a, DB>
+where
+    DB: Database,
+    DB::Connection: Migrate,
+{
+    async fn migrator(&self) -> DbResult<Migrator> {
+        Migrator::new(Path::new(&self.migrations_path))
+            .await
+            .map_err(migration_error)
+    }
+
+    /// Versions of applied migrations, oldest first
+    async fn applied(&self) -> DbResult<Vec<i64>> {
+        let mut conn = self.pool.acquire().await?;
+        conn.ensure_migrations_table().await.map_err(migration_error)?;
+        let mut versions: Vec<i64> = conn
+            .list_applied_migrations()
+            .await
+            .map_err(migration_error)?
+            .into_iter()
+            .map(|m| m.version)
+            .collect();
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    /// Every migration in the directory and whether it has been applied
+    pub async fn status(&self) -> DbResult<Vec<MigrationInfo>> {
+        let migrator = self.migrator().await?;
+        let applied = self.applied().await?;
+        Ok(migrator
+            .iter()
+            .filter(|m| !m.migration_type.is_down_migration())
+            .map(|m| MigrationInfo {
+                version: m.version,
+                description: m.description.to_string(),
+                applied: applied.contains(&m.version),
+            })
+            .collect())
+    }
+
+    /// Revert the last `steps` migrations
+    pub async fn revert_steps(&self, steps: usize) -> DbResult<()> {
+        let applied = self.applied().await?;
+        // Everything newer than the target version is reverted
+        let target = applied
+            .len()
+            .checked_sub(steps.saturating_add(1))
+            .map_or(0, |index| applied[index]);
+        self.revert_to(target).await
+    }
+
+    /// Revert every migration newer than `version`; `0` reverts all
+    pub async fn revert_to(&self, version: i64) -> DbResult<()> {
+        self.migrator()
+            .await?
+            .undo(self.pool, version)
+            .await
+            .map_err(|e| match e {
+                MigrateError::VersionMissing(version) => migration_error(format!(
+                    "Applied migration {} is missing from {}",
+                    version, self.migrations_path
+                )),
+                e => migration_error(e),
+            })
+    }
+
+    /// Run one [`MigrateCommand`]
+    pub async fn run_command(&self, command: MigrateCommand) -> DbResult<()> {
+        match command {
+            MigrateCommand::Up => self.run().await,
+            MigrateCommand::Down { steps } => self.revert_steps(steps).await,
+            MigrateCommand::Redo => {
+                self.revert_steps(1).await?;
+                self.run().await
+            }
+            MigrateCommand::Status => Ok(()),
+            MigrateCommand::Reset => self.revert_to(0).await,
+            MigrateCommand::Fresh => {
+                self.revert_to(0).await?;
+                self.run().await
+            }
+        }
+    }
+
+    /// Run the migration named on the command line
+    ///
+    /// Meant as the `main` of a migration binary, which is what
+    /// `cargo philjs db migrate` runs:
+    ///
+    /// ```rust,ignore
+    /// #[tokio::main]
+    /// async fn main() -> DbResult<()> {
+    ///     let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await?;
+    ///     MigrationRunner::new(&pool, "./migrations").run_cli().await
+    /// }
+    /// ```
+    ///
+    /// With `--json`, status is printed as JSON lines.
+    pub async fn run_cli(&self) -> DbResult<()> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let json = args.iter().any(|arg| arg == "--json");
+        let command = MigrateCommand::from_args(&args)?;
+
+        if command == MigrateCommand::Status {
+            for info in self.status().await? {
+                if json {
+                    println!("{}", serde_json::to_string(&info)?);
+                } else {
+                    let state = if info.applied { "applied" } else { "pending" };
+                    println!("{:<16} {:<40} {}", info.version, info.description, state);
+                }
+            }
+            return Ok(());
+        }
+
+        self.run_command(command).await
+    }
+}
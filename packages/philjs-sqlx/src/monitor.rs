@@ -0,0 +1,335 @@
+//! Connection pool health monitoring
+//!
+//! [`PoolMonitor`] wraps a pool, times connection acquisition, and samples
+//! pool occupancy. The latest [`PoolMetrics`] are available as a plain
+//! snapshot, a PhilJS resource or signal for admin dashboards, and in the
+//! Prometheus text format for metrics endpoints.
+//!
+//! ```rust,ignore
+//! let monitor = PoolMonitor::new(pool.clone()).name("primary");
+//! monitor.spawn_sampler(Duration::from_secs(5));
+//!
+//! // Acquire through the monitor so wait times and timeouts are counted
+//! let mut conn = monitor.acquire().await?;
+//!
+//! // e.g. in an axum handler for GET /metrics
+//! let body = monitor.prometheus();
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sqlx::pool::PoolConnection;
+use sqlx::Database;
+
+use crate::error::{DbError, DbResult};
+use crate::pool::DbPool;
+
+/// A sample of pool health
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PoolMetrics {
+    /// Pool name, used as the `pool` label
+    pub pool: String,
+    /// Connections currently open
+    pub size: u32,
+    /// Open connections not in use
+    pub idle: u32,
+    /// Open connections in use
+    pub active: u32,
+    /// Configured maximum connections
+    pub max_connections: u32,
+    /// Whether the pool has been closed
+    pub closed: bool,
+    /// Acquisitions made through the monitor
+    pub acquires: u64,
+    /// Acquisitions that timed out
+    pub acquire_timeouts: u64,
+    /// Total time spent waiting to acquire
+    pub wait_time_total: Duration,
+    /// Longest single wait to acquire
+    pub wait_time_max: Duration,
+}
+
+impl PoolMetrics {
+    /// Average wait per acquisition
+    pub fn wait_time_avg(&self) -> Duration {
+        match self.acquires {
+            0 => Duration::ZERO,
+            n => self.wait_time_total / n as u32,
+        }
+    }
+
+    /// Fraction of the maximum connections in use
+    pub fn utilization(&self) -> f64 {
+        match self.max_connections {
+            0 => 0.0,
+            max => self.active as f64 / max as f64,
+        }
+    }
+
+    /// Whether the pool is open and not exhausted
+    pub fn healthy(&self) -> bool {
+        !self.closed && (self.idle > 0 || self.size < self.max_connections)
+    }
+
+    /// Render in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        render_prometheus(std::slice::from_ref(self))
+    }
+}
+
+/// Render metrics for several pools in the Prometheus text exposition format
+pub fn render_prometheus(pools: &[PoolMetrics]) -> String {
+    type Metric = (&'static str, &'static str, &'static str, fn(&PoolMetrics) -> String);
+    const METRICS: &[Metric] = &[
+        ("philjs_db_pool_connections", "gauge", "Connections currently open", |m| m.size.to_string()),
+        ("philjs_db_pool_idle_connections", "gauge", "Open connections not in use", |m| m.idle.to_string()),
+        ("philjs_db_pool_active_connections", "gauge", "Open connections in use", |m| m.active.to_string()),
+        ("philjs_db_pool_max_connections", "gauge", "Configured maximum connections", |m| {
+            m.max_connections.to_string()
+        }),
+        ("philjs_db_pool_up", "gauge", "Whether the pool is open", |m| (!m.closed as u8).to_string()),
+        ("philjs_db_pool_acquires_total", "counter", "Connection acquisitions", |m| m.acquires.to_string()),
+        ("philjs_db_pool_acquire_timeouts_total", "counter", "Connection acquisitions that timed out", |m| {
+            m.acquire_timeouts.to_string()
+        }),
+        ("philjs_db_pool_acquire_wait_seconds_total", "counter", "Time spent waiting for connections", |m| {
+            m.wait_time_total.as_secs_f64().to_string()
+        }),
+        ("philjs_db_pool_acquire_wait_seconds_max", "gauge", "Longest wait for a connection", |m| {
+            m.wait_time_max.as_secs_f64().to_string()
+        }),
+    ];
+
+    let mut out = String::new();
+    for (name, kind, help, value) in METRICS {
+        out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n", name, help, name, kind));
+        for metrics in pools {
+            let label = metrics.pool.replace('\\', "\\\\").replace('"', "\\\"");
+            out.push_str(&format!("{}{{pool=\"{}\"}} {}\n", name, label, value(metrics)));
+        }
+    }
+    out
+}
+
+#[derive(Default)]
+struct Counters {
+    acquires: AtomicU64,
+    acquire_timeouts: AtomicU64,
+    wait_nanos_total: AtomicU64,
+    wait_nanos_max: AtomicU64,
+}
+
+/// Samples pool health and times connection acquisition
+pub struct PoolMonitor<DB: Database> {
+    pool: DbPool<DB>,
+    name: String,
+    counters: Arc<Counters>,
+    latest: Arc<Mutex<Option<PoolMetrics>>>,
+}
+
+impl<DB: Database> Clone for PoolMonitor<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            name: self.name.clone(),
+            counters: self.counters.clone(),
+            latest: self.latest.clone(),
+        }
+    }
+}
+
+impl<DB: Database> PoolMonitor<DB> {
+    /// Monitor a pool
+    pub fn new(pool: DbPool<DB>) -> Self {
+        Self {
+            pool,
+            name: "default".to_string(),
+            counters: Arc::new(Counters::default()),
+            latest: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Set the pool name reported in metrics
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Get the monitored pool
+    pub fn pool(&self) -> &DbPool<DB> {
+        &self.pool
+    }
+
+    /// Acquire a connection, recording the wait and any timeout
+    pub async fn acquire(&self) -> DbResult<PoolConnection<DB>> {
+        let started = Instant::now();
+        let result = self.pool.acquire().await.map_err(DbError::from);
+        self.record_acquire(started.elapsed(), matches!(result, Err(DbError::Timeout)));
+        result
+    }
+
+    fn record_acquire(&self, wait: Duration, timed_out: bool) {
+        let nanos = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX);
+        let counters = &self.counters;
+        counters.acquires.fetch_add(1, Ordering::Relaxed);
+        counters.wait_nanos_total.fetch_add(nanos, Ordering::Relaxed);
+        counters.wait_nanos_max.fetch_max(nanos, Ordering::Relaxed);
+        if timed_out {
+            counters.acquire_timeouts.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Take a fresh sample and store it as the latest
+    pub fn sample(&self) -> PoolMetrics {
+        let size = self.pool.size();
+        let idle = self.pool.num_idle() as u32;
+        let counters = &self.counters;
+
+        let metrics = PoolMetrics {
+            pool: self.name.clone(),
+            size,
+            idle,
+            active: size.saturating_sub(idle),
+            max_connections: self.pool.options().get_max_connections(),
+            closed: self.pool.is_closed(),
+            acquires: counters.acquires.load(Ordering::Relaxed),
+            acquire_timeouts: counters.acquire_timeouts.load(Ordering::Relaxed),
+            wait_time_total: Duration::from_nanos(counters.wait_nanos_total.load(Ordering::Relaxed)),
+            wait_time_max: Duration::from_nanos(counters.wait_nanos_max.load(Ordering::Relaxed)),
+        };
+
+        *self.latest.lock().unwrap_or_else(|e| e.into_inner()) = Some(metrics.clone());
+        metrics
+    }
+
+    /// The most recent sample, taking one if none exists yet
+    pub fn latest(&self) -> PoolMetrics {
+        let latest = self.latest.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        latest.unwrap_or_else(|| self.sample())
+    }
+
+    /// Sample every `interval` in a background task until the pool closes
+    pub fn spawn_sampler(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let monitor = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let metrics = monitor.sample();
+                if metrics.closed {
+                    break;
+                }
+                if !metrics.healthy() {
+                    tracing::warn!(
+                        pool = %metrics.pool,
+                        size = metrics.size,
+                        max = metrics.max_connections,
+                        "Database pool exhausted"
+                    );
+                }
+            }
+        })
+    }
+
+    /// The latest sample in the Prometheus text exposition format
+    pub fn prometheus(&self) -> String {
+        self.latest().to_prometheus()
+    }
+
+    /// A signal holding the latest sample
+    ///
+    /// Signals are local to the UI thread; call [`refresh_signal`](Self::refresh_signal)
+    /// to update it, e.g. from an interval effect.
+    pub fn signal(&self) -> philjs::Signal<PoolMetrics> {
+        philjs::Signal::new(self.latest())
+    }
+
+    /// Update a signal from [`signal`](Self::signal) with the latest sample
+    pub fn refresh_signal(&self, signal: &philjs::Signal<PoolMetrics>) {
+        signal.set(self.latest());
+    }
+
+    /// A resource resolving to the latest sample; `refetch()` it to refresh
+    pub fn resource(&self) -> philjs::Resource<PoolMetrics, ()>
+    where
+        DB: 'static,
+    {
+        let monitor = self.clone();
+        philjs::create_resource(
+            || (),
+            move |_| {
+                let metrics = monitor.latest();
+                async move { Ok::<_, String>(metrics) }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics() -> PoolMetrics {
+        PoolMetrics {
+            pool: "primary".to_string(),
+            size: 4,
+            idle: 1,
+            active: 3,
+            max_connections: 4,
+            acquires: 10,
+            acquire_timeouts: 2,
+            wait_time_total: Duration::from_millis(500),
+            wait_time_max: Duration::from_millis(250),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_derived_values() {
+        let metrics = metrics();
+        assert_eq!(metrics.wait_time_avg(), Duration::from_millis(50));
+        assert_eq!(metrics.utilization(), 0.75);
+        assert!(metrics.healthy());
+
+        let exhausted = PoolMetrics { idle: 0, active: 4, ..metrics };
+        assert!(!exhausted.healthy());
+    }
+
+    #[test]
+    fn test_prometheus_format() {
+        let replica = PoolMetrics {
+            pool: "replica".to_string(),
+            ..Default::default()
+        };
+        let text = render_prometheus(&[metrics(), replica]);
+
+        assert!(text.contains("# TYPE philjs_db_pool_connections gauge\n"));
+        assert!(text.contains("philjs_db_pool_connections{pool=\"primary\"} 4\n"));
+        assert!(text.contains("philjs_db_pool_connections{pool=\"replica\"} 0\n"));
+        assert!(text.contains("philjs_db_pool_acquire_timeouts_total{pool=\"primary\"} 2\n"));
+        assert!(text.contains("philjs_db_pool_acquire_wait_seconds_max{pool=\"primary\"} 0.25\n"));
+        assert_eq!(text.matches("# HELP philjs_db_pool_up").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_records_acquires() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(2)
+            .connect_lazy("sqlite::memory:")
+            .unwrap();
+        let monitor = PoolMonitor::new(pool).name("test");
+
+        monitor.record_acquire(Duration::from_millis(30), false);
+        monitor.record_acquire(Duration::from_millis(10), true);
+
+        let metrics = monitor.sample();
+        assert_eq!(metrics.acquires, 2);
+        assert_eq!(metrics.acquire_timeouts, 1);
+        assert_eq!(metrics.wait_time_max, Duration::from_millis(30));
+        assert_eq!(metrics.max_connections, 2);
+        assert_eq!(monitor.latest(), metrics);
+    }
+}
@@ -0,0 +1,316 @@
+//! Cursor (keyset) pagination for raw SQL queries
+//!
+//! Mirrors philjs-seaorm's `CursorPaginator`: pages are fetched with
+//! `WHERE (cols) > (last row's values)` rather than `OFFSET`, so rows
+//! inserted while paging never shift results. Cursors are opaque strings
+//! encoded by [`philjs::server::CursorCodec`]. Set a
+//! [`signing_key`](KeysetQuery::signing_key) so clients cannot forge them;
+//! unsigned cursors only catch accidental corruption, and a client can mint
+//! one for any key values.
+//!
+//! ```rust,ignore
+//! #[derive(sqlx::FromRow, Serialize)]
+//! struct Post { id: i64, created_at: i64, title: String }
+//!
+//! impl CursorKey for Post {
+//!     fn cursor_key(&self) -> Vec<SqlValue> {
+//!         vec![self.created_at.into(), self.id.into()]
+//!     }
+//! }
+//!
+//! let query = KeysetQuery::new("SELECT id, created_at, title FROM posts WHERE author_id = $1")
+//!     .bind(author_id)
+//!     .order_by(["created_at", "id"])
+//!     .descending()
+//!     .signing_key(secret);
+//!
+//! let page: Page<Post> = paginate_keyset(&pool, &query, params.cursor.as_deref(), 20).await?;
+//! ```
+
+use philjs::server::CursorCodec;
+use serde::Serialize;
+use sqlx::Database;
+
+use crate::error::{DbError, DbResult};
+use crate::query::{Backend, BindValue, OrderDirection, SqlValue};
+
+/// A row that can produce the values of its keyset columns
+pub trait CursorKey {
+    /// Values of the ordering columns, in the order given to
+    /// [`KeysetQuery::order_by`]
+    fn cursor_key(&self) -> Vec<SqlValue>;
+}
+
+/// Cursor metadata for a [`Page`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageInfo {
+    /// Items requested per page
+    pub per_page: u32,
+    /// Whether more items follow this page
+    pub has_more: bool,
+    /// Cursor for the next page, if any
+    pub next_cursor: Option<String>,
+}
+
+/// One page of keyset-paginated rows
+///
+/// Serializes as `{ "data": [...], "pagination": { ... } }`, the same
+/// envelope the web integrations' `PaginatedResponse` uses.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Page<T> {
+    /// The rows on this page
+    pub data: Vec<T>,
+    /// Cursor metadata
+    pub pagination: PageInfo,
+}
+
+impl<T> Page<T> {
+    /// Map rows to a different type, keeping the cursor
+    pub fn map<U, F>(self, f: F) -> Page<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Page {
+            data: self.data.into_iter().map(f).collect(),
+            pagination: self.pagination,
+        }
+    }
+}
+
+/// A raw SQL query paginated by keyset
+#[derive(Debug, Clone)]
+pub struct KeysetQuery {
+    sql: String,
+    binds: Vec<SqlValue>,
+    columns: Vec<String>,
+    order: OrderDirection,
+    codec: CursorCodec,
+}
+
+impl KeysetQuery {
+    /// Paginate `sql`, which must not have its own ORDER BY or LIMIT
+    pub fn new(sql: impl Into<String>) -> Self {
+        Self {
+            sql: sql.into(),
+            binds: Vec::new(),
+            columns: vec!["id".to_string()],
+            order: OrderDirection::Asc,
+            codec: CursorCodec::unsigned(),
+        }
+    }
+
+    /// Bind the next parameter of the base query
+    pub fn bind(mut self, value: impl Into<SqlValue>) -> Self {
+        self.binds.push(value.into());
+        self
+    }
+
+    /// Order by these columns, most significant first
+    ///
+    /// The last column must be unique (usually the primary key) so the
+    /// order is total; defaults to `id`.
+    pub fn order_by<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sort newest/largest first
+    pub fn descending(mut self) -> Self {
+        self.order = OrderDirection::Desc;
+        self
+    }
+
+    /// Sign cursors with `key` so clients cannot forge them
+    ///
+    /// Without a key, clients can craft cursors for any key values.
+    pub fn signing_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.codec = CursorCodec::signed(key.into());
+        self
+    }
+
+    /// Check the query can be paginated with a stable order
+    pub fn validate(&self) -> DbResult<()> {
+        let invalid = |msg: String| Err(DbError::Validation(msg));
+
+        if self.columns.is_empty() {
+            return invalid("Keyset pagination needs at least one ordering column".to_string());
+        }
+        for (i, column) in self.columns.iter().enumerate() {
+            if column.is_empty() || !column.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return invalid(format!("Invalid keyset column name: {:?}", column));
+            }
+            if self.columns[..i].contains(column) {
+                return invalid(format!("Keyset column {:?} is listed twice", column));
+            }
+        }
+
+        let sql = self.sql.to_ascii_uppercase();
+        let sql = sql.split_whitespace().collect::<Vec<_>>().join(" ");
+        if sql.contains(" ORDER BY ") || sql.contains(" LIMIT ") || sql.contains(" OFFSET ") {
+            return invalid("Keyset queries must not set ORDER BY, LIMIT or OFFSET".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Identifies the listing a cursor belongs to
+    fn shape(&self) -> String {
+        format!("{}:{}:{:?}", self.sql, self.columns.join(","), self.order)
+    }
+
+    /// Encode the cursor pointing just past a row with these key values
+    pub fn encode_cursor(&self, values: &[SqlValue]) -> DbResult<String> {
+        if values.len() != self.columns.len() || values.iter().any(SqlValue::is_null) {
+            return Err(DbError::Validation(format!(
+                "Cursor needs {} non-null key values, got {:?}",
+                self.columns.len(),
+                values
+            )));
+        }
+
+        Ok(self.codec.encode(&self.shape(), values)?)
+    }
+
+    /// Decode and verify a cursor from [`encode_cursor`](Self::encode_cursor)
+    pub fn decode_cursor(&self, cursor: &str) -> DbResult<Vec<SqlValue>> {
+        self.codec
+            .decode::<Vec<SqlValue>>(&self.shape(), cursor)
+            .filter(|values| values.len() == self.columns.len())
+            .ok_or_else(|| DbError::Validation("Invalid pagination cursor".to_string()))
+    }
+
+    /// Render the page query for `backend`, fetching `limit` rows after `after`
+    pub fn build(&self, backend: Backend, after: Option<Vec<SqlValue>>, limit: u32) -> (String, Vec<SqlValue>) {
+        let mut params = self.binds.clone();
+        let columns: Vec<String> = self.columns.iter().map(|c| backend.quote(c)).collect();
+        let mut sql = format!("SELECT * FROM ({}) AS philjs_keyset", self.sql.trim().trim_end_matches(';'));
+
+        if let Some(values) = after {
+            let placeholders: Vec<String> = values
+                .into_iter()
+                .map(|value| {
+                    params.push(value);
+                    backend.placeholder(params.len())
+                })
+                .collect();
+            let op = match self.order {
+                OrderDirection::Asc => ">",
+                OrderDirection::Desc => "<",
+            };
+            sql.push_str(&format!(
+                " WHERE ({}) {} ({})",
+                columns.join(", "),
+                op,
+                placeholders.join(", ")
+            ));
+        }
+
+        let dir = match self.order {
+            OrderDirection::Asc => "ASC",
+            OrderDirection::Desc => "DESC",
+        };
+        let order: Vec<String> = columns.iter().map(|c| format!("{} {}", c, dir)).collect();
+        sql.push_str(&format!(" ORDER BY {} LIMIT {}", order.join(", "), limit));
+
+        (sql, params)
+    }
+}
+
+/// Fetch one page of `query` starting after `cursor`
+///
+/// Pass `None` for the first page, then each page's `next_cursor`.
+pub async fn paginate_keyset<'e, DB, E, T>(
+    executor: E,
+    query: &KeysetQuery,
+    cursor: Option<&str>,
+    limit: u32,
+) -> DbResult<Page<T>>
+where
+    DB: BindValue,
+    E: sqlx::Executor<'e, Database = DB>,
+    T: for<'r> sqlx::FromRow<'r, DB::Row> + CursorKey + Send + Unpin,
+    for<'q> <DB as sqlx::database::HasArguments<'q>>::Arguments: sqlx::IntoArguments<'q, DB>,
+{
+    query.validate()?;
+    let after = cursor.map(|c| query.decode_cursor(c)).transpose()?;
+
+    // Fetch one extra to determine if there are more items
+    let (sql, params) = query.build(Backend::of::<DB>(), after, limit + 1);
+    let mut data = params
+        .into_iter()
+        .fold(sqlx::query_as::<DB, T>(&sql), |q, value| DB::bind_value(q, value))
+        .fetch_all(executor)
+        .await?;
+
+    let has_more = data.len() > limit as usize;
+    if has_more {
+        data.pop();
+    }
+
+    let next_cursor = match data.last() {
+        Some(last) if has_more => Some(query.encode_cursor(&last.cursor_key())?),
+        _ => None,
+    };
+
+    Ok(Page {
+        data,
+        pagination: PageInfo {
+            per_page: limit,
+            has_more,
+            next_cursor,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::SqlType;
+
+    fn query() -> KeysetQuery {
+        KeysetQuery::new("SELECT id, created_at FROM posts WHERE author_id = $1")
+            .bind(7)
+            .order_by(["created_at", "id"])
+            .descending()
+    }
+
+    #[test]
+    fn test_cursor_roundtrip_and_tamper() {
+        let query = query().signing_key("secret");
+        let values = vec![SqlValue::Int(1_700_000_000), SqlValue::Int(42)];
+        let cursor = query.encode_cursor(&values).unwrap();
+        assert_eq!(query.decode_cursor(&cursor).unwrap(), values);
+
+        let other_key = self::query().signing_key("other");
+        assert!(matches!(other_key.decode_cursor(&cursor), Err(DbError::Validation(_))));
+
+        let ascending = KeysetQuery::new(query.sql.clone()).order_by(["created_at", "id"]);
+        assert!(ascending.decode_cursor(&cursor).is_err());
+        assert!(query.decode_cursor("garbage").is_err());
+        assert!(query.encode_cursor(&[SqlValue::Null(SqlType::Int), SqlValue::Int(1)]).is_err());
+    }
+
+    #[test]
+    fn test_build_after_cursor() {
+        let after = vec![SqlValue::Int(100), SqlValue::Int(5)];
+        let (sql, params) = query().build(Backend::Postgres, Some(after), 21);
+        assert_eq!(
+            sql,
+            r#"SELECT * FROM (SELECT id, created_at FROM posts WHERE author_id = $1) AS philjs_keyset WHERE ("created_at", "id") < ($2, $3) ORDER BY "created_at" DESC, "id" DESC LIMIT 21"#
+        );
+        assert_eq!(params, vec![SqlValue::Int(7), SqlValue::Int(100), SqlValue::Int(5)]);
+    }
+
+    #[test]
+    fn test_validate_ordering() {
+        assert!(query().validate().is_ok());
+        assert!(query().order_by(Vec::<String>::new()).validate().is_err());
+        assert!(query().order_by(["id", "id"]).validate().is_err());
+        assert!(query().order_by(["id; DROP TABLE posts"]).validate().is_err());
+        assert!(KeysetQuery::new("SELECT * FROM posts ORDER BY id").validate().is_err());
+    }
+}
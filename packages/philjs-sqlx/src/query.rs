@@ -461,6 +461,690 @@ impl<DB: Database> DeleteBuilder<DB> {
     }
 }
 
+/// SQL dialect used when rendering typed queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// `$1` placeholders, `"quoted"` identifiers
+    Postgres,
+    /// `?` placeholders, `` `quoted` `` identifiers
+    MySql,
+    /// `?` placeholders, `"quoted"` identifiers
+    Sqlite,
+}
+
+impl Backend {
+    /// The dialect of an sqlx database driver
+    pub fn of<DB: Database>() -> Self {
+        match DB::NAME {
+            "MySQL" => Backend::MySql,
+            "SQLite" => Backend::Sqlite,
+            _ => Backend::Postgres,
+        }
+    }
+
+    /// Quote an identifier
+    pub fn quote(&self, ident: &str) -> String {
+        match self {
+            Backend::MySql => format!("`{}`", ident.replace('`', "``")),
+            _ => format!("\"{}\"", ident.replace('"', "\"\"")),
+        }
+    }
+
+    /// The placeholder for the `n`th (1-based) parameter
+    pub fn placeholder(&self, n: usize) -> String {
+        match self {
+            Backend::Postgres => format!("${}", n),
+            _ => "?".to_string(),
+        }
+    }
+}
+
+/// A value bound as a query parameter
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "t", content = "v", rename_all = "snake_case")]
+pub enum SqlValue {
+    /// SQL NULL, bound with the type of the column it stands in for
+    Null(SqlType),
+    /// Boolean
+    Bool(bool),
+    /// Any integer
+    Int(i64),
+    /// Any float
+    Float(f64),
+    /// Text
+    Text(String),
+    /// Binary
+    Bytes(Vec<u8>),
+    /// UUID
+    #[cfg(feature = "uuid")]
+    Uuid(uuid::Uuid),
+    /// UTC timestamp
+    #[cfg(feature = "chrono")]
+    Timestamp(chrono::DateTime<chrono::Utc>),
+}
+
+impl SqlValue {
+    /// Whether this is a NULL of any type
+    pub fn is_null(&self) -> bool {
+        matches!(self, SqlValue::Null(_))
+    }
+}
+
+/// The type of a [`SqlValue`], carried by NULLs so drivers that type every
+/// parameter (Postgres) accept them in non-text columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SqlType {
+    /// Boolean
+    Bool,
+    /// Any integer
+    Int,
+    /// Any float
+    Float,
+    /// Text
+    Text,
+    /// Binary
+    Bytes,
+    /// UUID
+    #[cfg(feature = "uuid")]
+    Uuid,
+    /// UTC timestamp
+    #[cfg(feature = "chrono")]
+    Timestamp,
+}
+
+/// Rust types that convert to a [`SqlValue`] of a known [`SqlType`]
+pub trait SqlTyped {
+    /// The type a `None` of this is bound as
+    const SQL_TYPE: SqlType;
+}
+
+macro_rules! sql_value_from {
+    ($($ty:ty => $variant:ident $(as $cast:ty)?),* $(,)?) => {
+        $(
+            impl From<$ty> for SqlValue {
+                fn from(value: $ty) -> Self {
+                    SqlValue::$variant(value $(as $cast)?.into())
+                }
+            }
+
+            impl SqlTyped for $ty {
+                const SQL_TYPE: SqlType = SqlType::$variant;
+            }
+        )*
+    };
+}
+
+sql_value_from! {
+    bool => Bool,
+    i8 => Int as i64,
+    i16 => Int as i64,
+    i32 => Int as i64,
+    i64 => Int,
+    u8 => Int as i64,
+    u16 => Int as i64,
+    u32 => Int as i64,
+    f32 => Float as f64,
+    f64 => Float,
+    String => Text,
+    &str => Text,
+    Vec<u8> => Bytes,
+}
+
+#[cfg(feature = "uuid")]
+sql_value_from! { uuid::Uuid => Uuid }
+
+#[cfg(feature = "chrono")]
+sql_value_from! { chrono::DateTime<chrono::Utc> => Timestamp }
+
+impl<T: Into<SqlValue> + SqlTyped> From<Option<T>> for SqlValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(SqlValue::Null(T::SQL_TYPE), Into::into)
+    }
+}
+
+/// A database table with typed columns, usually via `#[derive(Table)]`
+pub trait Table: Sized {
+    /// The table name
+    const NAME: &'static str;
+    /// Every column, in field order
+    const COLUMNS: &'static [&'static str];
+
+    /// Start a SELECT of every column
+    fn select() -> Select<Self> {
+        Select::new()
+    }
+}
+
+/// A column of table `T` holding values of type `V`
+pub struct Column<T, V> {
+    name: &'static str,
+    _phantom: PhantomData<fn() -> (T, V)>,
+}
+
+impl<T, V> Clone for Column<T, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, V> Copy for Column<T, V> {}
+
+impl<T, V> Column<T, V> {
+    /// Create a column reference
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The column name
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn compare(self, op: &'static str, value: impl Into<V>) -> Condition<T>
+    where
+        V: Into<SqlValue>,
+    {
+        Condition::new(ConditionKind::Compare {
+            column: self.name,
+            op,
+            value: value.into().into(),
+        })
+    }
+
+    /// `column = value`
+    pub fn eq(self, value: impl Into<V>) -> Condition<T>
+    where
+        V: Into<SqlValue>,
+    {
+        self.compare("=", value)
+    }
+
+    /// `column <> value`
+    pub fn ne(self, value: impl Into<V>) -> Condition<T>
+    where
+        V: Into<SqlValue>,
+    {
+        self.compare("<>", value)
+    }
+
+    /// `column > value`
+    pub fn gt(self, value: impl Into<V>) -> Condition<T>
+    where
+        V: Into<SqlValue>,
+    {
+        self.compare(">", value)
+    }
+
+    /// `column >= value`
+    pub fn gte(self, value: impl Into<V>) -> Condition<T>
+    where
+        V: Into<SqlValue>,
+    {
+        self.compare(">=", value)
+    }
+
+    /// `column < value`
+    pub fn lt(self, value: impl Into<V>) -> Condition<T>
+    where
+        V: Into<SqlValue>,
+    {
+        self.compare("<", value)
+    }
+
+    /// `column <= value`
+    pub fn lte(self, value: impl Into<V>) -> Condition<T>
+    where
+        V: Into<SqlValue>,
+    {
+        self.compare("<=", value)
+    }
+
+    /// `column LIKE pattern`
+    pub fn like(self, pattern: impl Into<String>) -> Condition<T> {
+        Condition::new(ConditionKind::Compare {
+            column: self.name,
+            op: "LIKE",
+            value: SqlValue::Text(pattern.into()),
+        })
+    }
+
+    /// `column IN (values)`; an empty list matches nothing
+    pub fn is_in<I>(self, values: I) -> Condition<T>
+    where
+        I: IntoIterator,
+        I::Item: Into<V>,
+        V: Into<SqlValue>,
+    {
+        Condition::new(ConditionKind::In {
+            column: self.name,
+            values: values.into_iter().map(|v| v.into().into()).collect(),
+        })
+    }
+
+    /// `column IS NULL`
+    pub fn is_null(self) -> Condition<T> {
+        Condition::new(ConditionKind::Null {
+            column: self.name,
+            negated: false,
+        })
+    }
+
+    /// `column IS NOT NULL`
+    pub fn is_not_null(self) -> Condition<T> {
+        Condition::new(ConditionKind::Null {
+            column: self.name,
+            negated: true,
+        })
+    }
+
+    /// Order by this column ascending
+    pub fn asc(self) -> OrderBy<T> {
+        OrderBy::new(self.name, OrderDirection::Asc)
+    }
+
+    /// Order by this column descending
+    pub fn desc(self) -> OrderBy<T> {
+        OrderBy::new(self.name, OrderDirection::Desc)
+    }
+}
+
+/// An ORDER BY term for table `T`
+pub struct OrderBy<T> {
+    column: &'static str,
+    direction: OrderDirection,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> OrderBy<T> {
+    fn new(column: &'static str, direction: OrderDirection) -> Self {
+        Self {
+            column,
+            direction,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, V> From<Column<T, V>> for OrderBy<T> {
+    fn from(column: Column<T, V>) -> Self {
+        column.asc()
+    }
+}
+
+enum ConditionKind {
+    Compare {
+        column: &'static str,
+        op: &'static str,
+        value: SqlValue,
+    },
+    In {
+        column: &'static str,
+        values: Vec<SqlValue>,
+    },
+    Null {
+        column: &'static str,
+        negated: bool,
+    },
+    All(Vec<ConditionKind>),
+    Any(Vec<ConditionKind>),
+    Not(Box<ConditionKind>),
+}
+
+impl ConditionKind {
+    fn render(&self, backend: Backend, params: &mut Vec<SqlValue>) -> String {
+        match self {
+            ConditionKind::Compare { column, op, value } => {
+                params.push(value.clone());
+                format!("{} {} {}", backend.quote(column), op, backend.placeholder(params.len()))
+            }
+            ConditionKind::In { values, .. } if values.is_empty() => "1 = 0".to_string(),
+            ConditionKind::In { column, values } => {
+                let placeholders: Vec<String> = values
+                    .iter()
+                    .map(|value| {
+                        params.push(value.clone());
+                        backend.placeholder(params.len())
+                    })
+                    .collect();
+                format!("{} IN ({})", backend.quote(column), placeholders.join(", "))
+            }
+            ConditionKind::Null { column, negated } => format!(
+                "{} IS {}NULL",
+                backend.quote(column),
+                if *negated { "NOT " } else { "" }
+            ),
+            ConditionKind::All(parts) => Self::join(parts, " AND ", backend, params),
+            ConditionKind::Any(parts) => Self::join(parts, " OR ", backend, params),
+            ConditionKind::Not(inner) => format!("NOT ({})", inner.render(backend, params)),
+        }
+    }
+
+    fn join(parts: &[ConditionKind], sep: &str, backend: Backend, params: &mut Vec<SqlValue>) -> String {
+        let rendered: Vec<String> = parts
+            .iter()
+            .map(|part| format!("({})", part.render(backend, params)))
+            .collect();
+        rendered.join(sep)
+    }
+}
+
+/// A typed WHERE condition on table `T`
+pub struct Condition<T> {
+    kind: ConditionKind,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Condition<T> {
+    fn new(kind: ConditionKind) -> Self {
+        Self {
+            kind,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Both conditions
+    pub fn and(self, other: Condition<T>) -> Self {
+        Self::new(ConditionKind::All(vec![self.kind, other.kind]))
+    }
+
+    /// Either condition
+    pub fn or(self, other: Condition<T>) -> Self {
+        Self::new(ConditionKind::Any(vec![self.kind, other.kind]))
+    }
+
+    /// Negate the condition
+    pub fn not(self) -> Self {
+        Self::new(ConditionKind::Not(Box::new(self.kind)))
+    }
+}
+
+/// Binds [`SqlValue`]s for a specific sqlx driver
+pub trait BindValue: Database {
+    /// Bind one value to a query
+    fn bind_value<'q, O>(
+        query: sqlx::query::QueryAs<'q, Self, O, <Self as sqlx::database::HasArguments<'q>>::Arguments>,
+        value: SqlValue,
+    ) -> sqlx::query::QueryAs<'q, Self, O, <Self as sqlx::database::HasArguments<'q>>::Arguments>;
+
+    /// Bind one value to a query that returns no typed rows
+    fn bind_query<'q>(
+        query: sqlx::query::Query<'q, Self, <Self as sqlx::database::HasArguments<'q>>::Arguments>,
+        value: SqlValue,
+    ) -> sqlx::query::Query<'q, Self, <Self as sqlx::database::HasArguments<'q>>::Arguments>;
+}
+
+macro_rules! bind_sql_value {
+    ($query:expr, $value:expr) => {
+        match $value {
+            SqlValue::Null(SqlType::Bool) => $query.bind(None::<bool>),
+            SqlValue::Null(SqlType::Int) => $query.bind(None::<i64>),
+            SqlValue::Null(SqlType::Float) => $query.bind(None::<f64>),
+            SqlValue::Null(SqlType::Text) => $query.bind(None::<String>),
+            SqlValue::Null(SqlType::Bytes) => $query.bind(None::<Vec<u8>>),
+            #[cfg(feature = "uuid")]
+            SqlValue::Null(SqlType::Uuid) => $query.bind(None::<uuid::Uuid>),
+            #[cfg(feature = "chrono")]
+            SqlValue::Null(SqlType::Timestamp) => $query.bind(None::<chrono::DateTime<chrono::Utc>>),
+            SqlValue::Bool(v) => $query.bind(v),
+            SqlValue::Int(v) => $query.bind(v),
+            SqlValue::Float(v) => $query.bind(v),
+            SqlValue::Text(v) => $query.bind(v),
+            SqlValue::Bytes(v) => $query.bind(v),
+            #[cfg(feature = "uuid")]
+            SqlValue::Uuid(v) => $query.bind(v),
+            #[cfg(feature = "chrono")]
+            SqlValue::Timestamp(v) => $query.bind(v),
+        }
+    };
+}
+
+macro_rules! impl_bind_value {
+    ($db:ty) => {
+        impl BindValue for $db {
+            fn bind_value<'q, O>(
+                query: sqlx::query::QueryAs<'q, Self, O, <Self as sqlx::database::HasArguments<'q>>::Arguments>,
+                value: SqlValue,
+            ) -> sqlx::query::QueryAs<'q, Self, O, <Self as sqlx::database::HasArguments<'q>>::Arguments> {
+                bind_sql_value!(query, value)
+            }
+
+            fn bind_query<'q>(
+                query: sqlx::query::Query<'q, Self, <Self as sqlx::database::HasArguments<'q>>::Arguments>,
+                value: SqlValue,
+            ) -> sqlx::query::Query<'q, Self, <Self as sqlx::database::HasArguments<'q>>::Arguments> {
+                bind_sql_value!(query, value)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "postgres")]
+impl_bind_value!(sqlx::Postgres);
+#[cfg(feature = "mysql")]
+impl_bind_value!(sqlx::MySql);
+#[cfg(feature = "sqlite")]
+impl_bind_value!(sqlx::Sqlite);
+
+/// A typed SELECT over table `T`
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(sqlx::FromRow, Table)]
+/// #[table(name = "users")]
+/// struct Users {
+///     id: i64,
+///     email: String,
+///     created_at: i64,
+/// }
+///
+/// let users = Users::select()
+///     .filter(Users::EMAIL.like("%@example.com"))
+///     .order_by(Users::CREATED_AT.desc())
+///     .paginate(2, 20)
+///     .fetch_all(&pool)
+///     .await?;
+/// ```
+pub struct Select<T> {
+    columns: Vec<&'static str>,
+    conditions: Vec<ConditionKind>,
+    order_by: Vec<(&'static str, OrderDirection)>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: Table> Default for Select<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Table> Select<T> {
+    /// Select every column of `T`
+    pub fn new() -> Self {
+        Self {
+            columns: T::COLUMNS.to_vec(),
+            conditions: Vec::new(),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Select only the given columns
+    pub fn columns<V>(mut self, columns: impl IntoIterator<Item = Column<T, V>>) -> Self {
+        self.columns = columns.into_iter().map(|c| c.name).collect();
+        self
+    }
+
+    /// Add a WHERE condition; multiple filters are ANDed
+    pub fn filter(mut self, condition: Condition<T>) -> Self {
+        self.conditions.push(condition.kind);
+        self
+    }
+
+    /// Add an ORDER BY term; a bare column sorts ascending
+    pub fn order_by(mut self, order: impl Into<OrderBy<T>>) -> Self {
+        let order = order.into();
+        self.order_by.push((order.column, order.direction));
+        self
+    }
+
+    /// Set LIMIT
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set OFFSET
+    pub fn offset(mut self, offset: i64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Apply pagination (pages start at 1)
+    pub fn paginate(self, page: i64, per_page: i64) -> Self {
+        let offset = (page.max(1) - 1) * per_page;
+        self.limit(per_page).offset(offset)
+    }
+
+    fn where_sql(&self, backend: Backend, params: &mut Vec<SqlValue>) -> String {
+        if self.conditions.is_empty() {
+            return String::new();
+        }
+        let parts: Vec<String> = self
+            .conditions
+            .iter()
+            .map(|c| match c {
+                ConditionKind::Any(_) => format!("({})", c.render(backend, params)),
+                _ => c.render(backend, params),
+            })
+            .collect();
+        format!(" WHERE {}", parts.join(" AND "))
+    }
+
+    /// Render parameterized SQL and its parameters for `backend`
+    pub fn build(&self, backend: Backend) -> (String, Vec<SqlValue>) {
+        let mut params = Vec::new();
+        let columns: Vec<String> = self.columns.iter().map(|c| backend.quote(c)).collect();
+        let mut sql = format!("SELECT {} FROM {}", columns.join(", "), backend.quote(T::NAME));
+
+        sql.push_str(&self.where_sql(backend, &mut params));
+
+        if !self.order_by.is_empty() {
+            let order: Vec<String> = self
+                .order_by
+                .iter()
+                .map(|(column, dir)| {
+                    let dir = match dir {
+                        OrderDirection::Asc => "ASC",
+                        OrderDirection::Desc => "DESC",
+                    };
+                    format!("{} {}", backend.quote(column), dir)
+                })
+                .collect();
+            sql.push_str(&format!(" ORDER BY {}", order.join(", ")));
+        }
+
+        match (self.limit, self.offset) {
+            (Some(limit), offset) => {
+                sql.push_str(&format!(" LIMIT {}", limit));
+                if let Some(offset) = offset {
+                    sql.push_str(&format!(" OFFSET {}", offset));
+                }
+            }
+            (None, Some(offset)) => {
+                // MySQL and SQLite need a LIMIT before OFFSET
+                match backend {
+                    Backend::Postgres => {}
+                    Backend::MySql => sql.push_str(" LIMIT 18446744073709551615"),
+                    Backend::Sqlite => sql.push_str(" LIMIT -1"),
+                }
+                sql.push_str(&format!(" OFFSET {}", offset));
+            }
+            (None, None) => {}
+        }
+
+        (sql, params)
+    }
+
+    /// Render a `COUNT(*)` over the filtered rows for `backend`
+    pub fn build_count(&self, backend: Backend) -> (String, Vec<SqlValue>) {
+        let mut params = Vec::new();
+        let mut sql = format!("SELECT COUNT(*) FROM {}", backend.quote(T::NAME));
+        sql.push_str(&self.where_sql(backend, &mut params));
+        (sql, params)
+    }
+
+    fn query<'q, DB, O>(
+        sql: &'q str,
+        params: Vec<SqlValue>,
+    ) -> sqlx::query::QueryAs<'q, DB, O, <DB as sqlx::database::HasArguments<'q>>::Arguments>
+    where
+        DB: BindValue,
+        O: for<'r> sqlx::FromRow<'r, DB::Row>,
+    {
+        params
+            .into_iter()
+            .fold(sqlx::query_as::<DB, O>(sql), |query, value| DB::bind_value(query, value))
+    }
+
+    /// Fetch all matching rows
+    pub async fn fetch_all<'e, DB, E>(&self, executor: E) -> crate::DbResult<Vec<T>>
+    where
+        DB: BindValue,
+        E: sqlx::Executor<'e, Database = DB>,
+        T: for<'r> sqlx::FromRow<'r, DB::Row> + Send + Unpin,
+        for<'q> <DB as sqlx::database::HasArguments<'q>>::Arguments: sqlx::IntoArguments<'q, DB>,
+    {
+        let (sql, params) = self.build(Backend::of::<DB>());
+        Ok(Self::query::<DB, T>(&sql, params).fetch_all(executor).await?)
+    }
+
+    /// Fetch the first matching row, if any
+    pub async fn fetch_optional<'e, DB, E>(&self, executor: E) -> crate::DbResult<Option<T>>
+    where
+        DB: BindValue,
+        E: sqlx::Executor<'e, Database = DB>,
+        T: for<'r> sqlx::FromRow<'r, DB::Row> + Send + Unpin,
+        for<'q> <DB as sqlx::database::HasArguments<'q>>::Arguments: sqlx::IntoArguments<'q, DB>,
+    {
+        let (sql, params) = self.build(Backend::of::<DB>());
+        Ok(Self::query::<DB, T>(&sql, params).fetch_optional(executor).await?)
+    }
+
+    /// Stream matching rows as they arrive
+    pub fn stream<DB>(&self, pool: &crate::DbPool<DB>) -> crate::stream::DbStream<'static, T>
+    where
+        DB: BindValue,
+        for<'c> &'c crate::DbPool<DB>: sqlx::Executor<'c, Database = DB>,
+        T: for<'r> sqlx::FromRow<'r, DB::Row> + Send + Unpin + 'static,
+        for<'q> <DB as sqlx::database::HasArguments<'q>>::Arguments: sqlx::IntoArguments<'q, DB>,
+    {
+        let (sql, params) = self.build(Backend::of::<DB>());
+        crate::stream::DbStream::fetch(pool, sql, params)
+    }
+
+    /// Count matching rows, ignoring order and pagination
+    pub async fn count<'e, DB, E>(&self, executor: E) -> crate::DbResult<i64>
+    where
+        DB: BindValue,
+        E: sqlx::Executor<'e, Database = DB>,
+        (i64,): for<'r> sqlx::FromRow<'r, DB::Row>,
+        for<'q> <DB as sqlx::database::HasArguments<'q>>::Arguments: sqlx::IntoArguments<'q, DB>,
+    {
+        let (sql, params) = self.build_count(Backend::of::<DB>());
+        let (count,) = Self::query::<DB, (i64,)>(&sql, params).fetch_one(executor).await?;
+        Ok(count)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -528,6 +1212,69 @@ mod tests {
         assert!(sql.contains("WHERE id = $3"));
     }
 
+    #[derive(sqlx::FromRow, crate::Table)]
+    #[table(name = "users")]
+    #[allow(dead_code)]
+    struct Users {
+        id: i64,
+        #[sqlx(rename = "email_address")]
+        email: String,
+        deleted_at: Option<String>,
+        #[sqlx(skip)]
+        cached: bool,
+    }
+
+    #[test]
+    fn test_derived_columns() {
+        assert_eq!(Users::NAME, "users");
+        assert_eq!(Users::COLUMNS, &["id", "email_address", "deleted_at"]);
+        assert_eq!(Users::EMAIL.name(), "email_address");
+    }
+
+    #[test]
+    fn test_typed_select_per_backend() {
+        let query = Users::select()
+            .filter(Users::EMAIL.eq("a@example.com"))
+            .filter(Users::ID.gt(10).or(Users::DELETED_AT.is_null()))
+            .order_by(Users::ID.desc())
+            .paginate(3, 20);
+
+        let (sql, params) = query.build(Backend::Postgres);
+        assert_eq!(
+            sql,
+            r#"SELECT "id", "email_address", "deleted_at" FROM "users" WHERE "email_address" = $1 AND (("id" > $2) OR ("deleted_at" IS NULL)) ORDER BY "id" DESC LIMIT 20 OFFSET 40"#
+        );
+        assert_eq!(params, vec![SqlValue::from("a@example.com"), SqlValue::Int(10)]);
+
+        let (sql, _) = query.build(Backend::MySql);
+        assert!(sql.contains("WHERE `email_address` = ? AND ((`id` > ?)"));
+    }
+
+    #[test]
+    fn test_nulls_carry_their_type() {
+        assert_eq!(SqlValue::from(None::<i32>), SqlValue::Null(SqlType::Int));
+        assert_eq!(SqlValue::from(Some(3_i32)), SqlValue::Int(3));
+        assert!(SqlValue::from(None::<bool>).is_null());
+
+        let (_, params) = Users::select()
+            .filter(Users::DELETED_AT.eq(None::<String>))
+            .build(Backend::Postgres);
+        assert_eq!(params, vec![SqlValue::Null(SqlType::Text)]);
+    }
+
+    #[test]
+    fn test_typed_in_and_count() {
+        let query = Users::select().filter(Users::ID.is_in([1, 2, 3]));
+        let (sql, params) = query.build_count(Backend::Sqlite);
+        assert_eq!(sql, r#"SELECT COUNT(*) FROM "users" WHERE "id" IN (?, ?, ?)"#);
+        assert_eq!(params.len(), 3);
+
+        let (sql, _) = Users::select()
+            .filter(Users::ID.is_in(Vec::<i64>::new()))
+            .build(Backend::Sqlite);
+        assert!(sql.ends_with("WHERE 1 = 0"));
+    }
+
     #[test]
     fn test_delete_builder() {
         let sql = DeleteBuilder::<sqlx::Sqlite>::new("users")
@@ -31,6 +31,8 @@ use std::task::{Context, Poll};
 pub struct ReactiveQuery<'q, DB: Database> {
     sql: &'q str,
     arguments: Vec<String>,
+    channel: String,
+    tags: Vec<String>,
     _phantom: PhantomData<DB>,
 }
 
@@ -40,6 +42,8 @@ impl<'q, DB: Database> ReactiveQuery<'q, DB> {
         Self {
             sql,
             arguments: Vec::new(),
+            channel: DEFAULT_NOTIFY_CHANNEL.to_string(),
+            tags: Vec::new(),
             _phantom: PhantomData,
         }
     }
@@ -50,6 +54,29 @@ impl<'q, DB: Database> ReactiveQuery<'q, DB> {
         self
     }
 
+    /// Set the channel `watch()` listens on
+    pub fn channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = channel.into();
+        self
+    }
+
+    /// Refetch when a notification for `tag` arrives
+    ///
+    /// Without explicit tags, the tables named in the query are used.
+    pub fn depends_on(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// The tags this query is invalidated by
+    pub fn tags(&self) -> Vec<String> {
+        if self.tags.is_empty() {
+            tables_in(self.sql)
+        } else {
+            self.tags.clone()
+        }
+    }
+
     /// Execute the query and return all results
     pub async fn fetch_all<'e, E, T>(&self, executor: E) -> DbResult<Vec<T>>
     where
@@ -67,7 +94,7 @@ impl<'q, DB: Database> ReactiveQuery<'q, DB> {
     pub async fn fetch_one<'e, E, T>(&self, executor: E) -> DbResult<T>
     where
         E: Executor<'e, Database = DB>,
-        T: for<'r> FromRow<'r, DB::Row> + Send + Unpack,
+        T: for<'r> FromRow<'r, DB::Row> + Send + Unpin,
     {
         let query = sqlx::query_as::<_, T>(self.sql);
         query
@@ -90,6 +117,192 @@ impl<'q, DB: Database> ReactiveQuery<'q, DB> {
     }
 }
 
+/// Channel used by `watch()` and [`notify_trigger_sql`] unless overridden
+pub const DEFAULT_NOTIFY_CHANNEL: &str = "philjs_changes";
+
+/// Tables referenced after `FROM` or `JOIN` in a query
+pub fn tables_in(sql: &str) -> Vec<String> {
+    let mut tables: Vec<String> = Vec::new();
+    let mut words = sql.split_whitespace().peekable();
+
+    while let Some(word) = words.next() {
+        if !word.eq_ignore_ascii_case("FROM") && !word.eq_ignore_ascii_case("JOIN") {
+            continue;
+        }
+        let Some(next) = words.peek() else { break };
+        if next.starts_with('(') {
+            continue;
+        }
+        let table = next
+            .trim_end_matches([',', ';', ')'])
+            .replace('"', "")
+            .to_ascii_lowercase();
+        if !table.is_empty() && !tables.contains(&table) {
+            tables.push(table);
+        }
+    }
+
+    tables
+}
+
+/// Whether a notification payload invalidates a query with `tags`
+///
+/// Payloads are either a tag (`users`, optionally `users:42`) or a JSON
+/// object with a `table` or `tag` field. Schema prefixes are ignored.
+pub fn notification_matches(payload: &str, tags: &[String]) -> bool {
+    if tags.is_empty() {
+        return true;
+    }
+
+    let key = match serde_json::from_str::<serde_json::Value>(payload) {
+        Ok(serde_json::Value::Object(map)) => map
+            .get("table")
+            .or_else(|| map.get("tag"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        _ => None,
+    }
+    .unwrap_or_else(|| payload.split(':').next().unwrap_or_default().to_string());
+
+    let unqualified = |name: &str| name.rsplit('.').next().unwrap_or(name).to_ascii_lowercase();
+    let key = unqualified(key.trim());
+    tags.iter().any(|tag| unqualified(tag) == key)
+}
+
+/// SQL installing a trigger that notifies `channel` when `table` changes
+///
+/// The payload is the table name, matching what `watch()` expects.
+pub fn notify_trigger_sql(table: &str, channel: &str) -> String {
+    let trigger = format!("philjs_notify_{}", table.replace(['.', '"'], "_"));
+    format!(
+        "CREATE OR REPLACE FUNCTION philjs_notify_change() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify(TG_ARGV[0], TG_TABLE_NAME);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+DROP TRIGGER IF EXISTS {trigger} ON {table};
+CREATE TRIGGER {trigger} AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON {table}
+    FOR EACH STATEMENT EXECUTE FUNCTION philjs_notify_change('{channel}');"
+    )
+}
+
+#[cfg(feature = "postgres")]
+mod watch {
+    use super::*;
+    use sqlx::postgres::{PgListener, PgPool, Postgres};
+
+    /// Install the change-notification trigger for `table`
+    pub async fn install_notify_trigger(pool: &PgPool, table: &str, channel: &str) -> DbResult<()> {
+        sqlx::Executor::execute(pool, notify_trigger_sql(table, channel).as_str())
+            .await
+            .map_err(|e| DbError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Live results of a watched query
+    ///
+    /// Yields the current rows immediately, then again whenever a matching
+    /// notification arrives. Dropping it stops listening.
+    pub struct QueryWatch<T> {
+        inner: Pin<Box<dyn Stream<Item = DbResult<Vec<T>>> + Send>>,
+    }
+
+    impl<T> Stream for QueryWatch<T> {
+        type Item = DbResult<Vec<T>>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.inner.as_mut().poll_next(cx)
+        }
+    }
+
+    struct WatchState {
+        pool: PgPool,
+        listener: PgListener,
+        sql: String,
+        tags: Vec<String>,
+        started: bool,
+    }
+
+    impl WatchState {
+        /// Wait until the query needs refetching
+        async fn invalidated(&mut self) -> DbResult<()> {
+            loop {
+                match self.listener.try_recv().await {
+                    Ok(Some(notification)) => {
+                        if notification_matches(notification.payload(), &self.tags) {
+                            return Ok(());
+                        }
+                    }
+                    // The connection dropped and will reconnect; changes may
+                    // have been missed, so refetch
+                    Ok(None) => return Ok(()),
+                    Err(e) => return Err(DbError::Connection(e.to_string())),
+                }
+            }
+        }
+    }
+
+    impl<'q> ReactiveQuery<'q, Postgres> {
+        /// Fetch the query now and again whenever its tables change
+        ///
+        /// Listens on the query's channel for NOTIFY payloads naming one of
+        /// its [`tags`](ReactiveQuery::tags); see [`notify_trigger_sql`] for
+        /// a trigger that sends them.
+        ///
+        /// ```rust,ignore
+        /// let mut users = ReactiveQuery::new("SELECT * FROM users")
+        ///     .watch::<User>(&pool)
+        ///     .await?;
+        /// while let Some(rows) = users.next().await {
+        ///     set_users.set(rows?);
+        /// }
+        /// ```
+        pub async fn watch<T>(&self, pool: &PgPool) -> DbResult<QueryWatch<T>>
+        where
+            T: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Send + Unpin + 'static,
+        {
+            let mut listener = PgListener::connect_with(pool)
+                .await
+                .map_err(|e| DbError::Connection(e.to_string()))?;
+            listener
+                .listen(&self.channel)
+                .await
+                .map_err(|e| DbError::Connection(e.to_string()))?;
+
+            let state = WatchState {
+                pool: pool.clone(),
+                listener,
+                sql: self.sql.to_string(),
+                tags: self.tags(),
+                started: false,
+            };
+
+            let inner = futures::stream::unfold(state, |mut state| async move {
+                if state.started {
+                    if let Err(e) = state.invalidated().await {
+                        return Some((Err(e), state));
+                    }
+                }
+                state.started = true;
+
+                let rows = sqlx::query_as::<_, T>(&state.sql)
+                    .fetch_all(&state.pool)
+                    .await
+                    .map_err(|e| DbError::Query(e.to_string()));
+                Some((rows, state))
+            });
+
+            Ok(QueryWatch {
+                inner: Box::pin(inner),
+            })
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub use watch::{install_notify_trigger, QueryWatch};
+
 /// Reactive resource for database queries
 ///
 /// # Example
@@ -331,6 +544,28 @@ mod tests {
         assert!(sql.contains("OFFSET 5"));
     }
 
+    #[test]
+    fn test_tables_in() {
+        assert_eq!(
+            tables_in(r#"SELECT * FROM "users" u JOIN posts p ON p.user_id = u.id"#),
+            vec!["users", "posts"]
+        );
+        assert_eq!(tables_in("SELECT * FROM (SELECT 1) t"), Vec::<String>::new());
+
+        let query = ReactiveQuery::<sqlx::Sqlite>::new("SELECT * FROM users").depends_on("feed");
+        assert_eq!(query.tags(), vec!["feed"]);
+    }
+
+    #[test]
+    fn test_notification_matches() {
+        let tags = vec!["users".to_string()];
+        assert!(notification_matches("users", &tags));
+        assert!(notification_matches("public.users:42", &tags));
+        assert!(notification_matches(r#"{"table":"users","op":"UPDATE"}"#, &tags));
+        assert!(!notification_matches("posts", &tags));
+        assert!(notification_matches("anything", &[]));
+    }
+
     #[test]
     fn test_query_builder_simple() {
         let builder = ReactiveQueryBuilder::new("posts");
@@ -0,0 +1,314 @@
+//! Streaming query results
+//!
+//! [`DbStream`] wraps a row stream so it can feed streaming SSR and
+//! streaming server functions without collecting the result set first.
+//! Rows are only pulled from the database as fast as the response consumes
+//! them: borrowed streams are polled directly, and owned streams run the
+//! query in a task that waits on a bounded channel.
+//!
+//! ```rust,ignore
+//! // Render rows into a suspense boundary as they arrive
+//! let shell = render_to_stream_async(app, StreamingConfig::default());
+//! let rows = Users::select()
+//!     .order_by(Users::ID.asc())
+//!     .stream(&pool)
+//!     .into_html_chunks(0, |user| view! { <li>{user.name}</li> });
+//! let body = shell.chain(rows);
+//!
+//! // Stream rows from a streaming server function
+//! fn run(_: ()) -> ... {
+//!     let pool = POOL.clone();
+//!     Box::pin(async move {
+//!         Ok(DbStream::<Event>::fetch(&pool, "SELECT * FROM events", vec![]).into_server_stream())
+//!     })
+//! }
+//! ```
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::stream::{BoxStream, Stream, StreamExt};
+use philjs::{IntoView, ServerError, ServerResult};
+
+use crate::error::{DbError, DbResult};
+use crate::pool::DbPool;
+use crate::query::{BindValue, SqlValue};
+
+/// Rows buffered ahead of the consumer by [`DbStream::fetch`]
+pub const DEFAULT_STREAM_BUFFER: usize = 32;
+
+/// Most rows rendered into one SSR chunk
+pub const SSR_CHUNK_ROWS: usize = 64;
+
+/// A stream of query results
+pub struct DbStream<'a, T> {
+    inner: BoxStream<'a, DbResult<T>>,
+}
+
+impl<'a, T: 'a> DbStream<'a, T> {
+    /// Wrap a row stream, e.g. from `query_as(...).fetch(&mut conn)`
+    pub fn new<S, E>(stream: S) -> Self
+    where
+        S: Stream<Item = Result<T, E>> + Send + 'a,
+        E: Into<DbError>,
+    {
+        Self {
+            inner: stream.map(|row| row.map_err(Into::into)).boxed(),
+        }
+    }
+
+    /// Transform each row
+    pub fn map<U, F>(self, mut f: F) -> DbStream<'a, U>
+    where
+        F: FnMut(T) -> U + Send + 'a,
+        U: 'a,
+    {
+        DbStream {
+            inner: self.inner.map(move |row| row.map(&mut f)).boxed(),
+        }
+    }
+
+    /// Adapt for a streaming server function
+    pub fn into_server_stream(self) -> Pin<Box<dyn Stream<Item = ServerResult<T>> + Send + 'a>> {
+        Box::pin(self.inner.map(|row| row.map_err(ServerError::from)))
+    }
+
+    /// Render rows into the suspense boundary with the given id
+    ///
+    /// Chunks follow the same protocol as
+    /// [`render_to_stream_async`](philjs::render_to_stream_async): each is a
+    /// `<template>` plus a script that moves it into the boundary, so they
+    /// can be chained after the shell. The first chunk replaces the fallback;
+    /// an error stops the stream and marks the boundary with
+    /// `data-philjs-error`.
+    pub fn into_html_chunks<V, F>(self, boundary: usize, render: F) -> impl Stream<Item = String> + Send + 'a
+    where
+        T: Send,
+        F: FnMut(T) -> V + Send + 'a,
+        V: IntoView,
+    {
+        struct State<'a, T, F> {
+            rows: futures::stream::ReadyChunks<BoxStream<'a, DbResult<T>>>,
+            render: F,
+            chunk: usize,
+            done: bool,
+        }
+
+        let state = State {
+            rows: self.inner.ready_chunks(SSR_CHUNK_ROWS),
+            render,
+            chunk: 0,
+            done: false,
+        };
+
+        futures::stream::unfold(state, move |mut state| async move {
+            if state.done {
+                return None;
+            }
+
+            let Some(batch) = state.rows.next().await else {
+                state.done = true;
+                return Some((resolve_script(boundary, state.chunk), state));
+            };
+
+            let mut html = String::new();
+            let mut error = None;
+            for row in batch {
+                match row {
+                    Ok(row) => html.push_str(&(state.render)(row).into_view().to_html()),
+                    Err(e) => {
+                        error = Some(e);
+                        break;
+                    }
+                }
+            }
+
+            let mut out = rows_chunk(boundary, state.chunk, &html);
+            state.chunk += 1;
+            if let Some(e) = error {
+                tracing::error!(boundary, error = %e, "Database stream failed during SSR");
+                out.push_str(&error_script(boundary, &ServerError::from(e).message));
+                state.done = true;
+            }
+            Some((out, state))
+        })
+    }
+}
+
+impl<T: Send + 'static> DbStream<'static, T> {
+    /// Run a query on the pool and stream its rows
+    pub fn fetch<DB>(pool: &DbPool<DB>, sql: impl Into<String>, params: Vec<SqlValue>) -> Self
+    where
+        DB: BindValue,
+        for<'c> &'c DbPool<DB>: sqlx::Executor<'c, Database = DB>,
+        T: for<'r> sqlx::FromRow<'r, DB::Row> + Unpin,
+        for<'q> <DB as sqlx::database::HasArguments<'q>>::Arguments: sqlx::IntoArguments<'q, DB>,
+    {
+        Self::fetch_buffered(pool, sql, params, DEFAULT_STREAM_BUFFER)
+    }
+
+    /// [`fetch`](Self::fetch) with `buffer` rows read ahead of the consumer
+    ///
+    /// The query stops reading once the buffer is full and is cancelled when
+    /// the stream is dropped.
+    pub fn fetch_buffered<DB>(
+        pool: &DbPool<DB>,
+        sql: impl Into<String>,
+        params: Vec<SqlValue>,
+        buffer: usize,
+    ) -> Self
+    where
+        DB: BindValue,
+        for<'c> &'c DbPool<DB>: sqlx::Executor<'c, Database = DB>,
+        T: for<'r> sqlx::FromRow<'r, DB::Row> + Unpin,
+        for<'q> <DB as sqlx::database::HasArguments<'q>>::Arguments: sqlx::IntoArguments<'q, DB>,
+    {
+        let (tx, rx) = tokio::sync::mpsc::channel(buffer.max(1));
+        let pool = pool.clone();
+        let sql = sql.into();
+
+        tokio::spawn(async move {
+            let query = params
+                .into_iter()
+                .fold(sqlx::query::<DB>(&sql), |query, value| DB::bind_query(query, value));
+            let mut rows = query.fetch(&pool);
+
+            while let Some(row) = rows.next().await {
+                let row = row.and_then(|row| T::from_row(&row)).map_err(DbError::from);
+                let failed = row.is_err();
+                if tx.send(row).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            inner: futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|row| (row, rx)) })
+                .boxed(),
+        }
+    }
+}
+
+impl<T> Stream for DbStream<'_, T> {
+    type Item = DbResult<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+/// Script that moves one template of rows into the boundary
+fn rows_chunk(boundary: usize, chunk: usize, html: &str) -> String {
+    format!(
+        r#"<template id="S:{id}:{n}">{html}</template>
+<script>
+(function(){{
+    var t=document.getElementById("S:{id}:{n}");
+    var f=document.getElementById("F:{id}");
+    if(t&&f){{if(!f.hasAttribute("data-philjs-rows")){{f.setAttribute("data-philjs-rows","");f.textContent="";}}f.appendChild(t.content.cloneNode(true));t.remove();}}
+}})();
+</script>"#,
+        id = boundary,
+        n = chunk,
+        html = html
+    )
+}
+
+/// Script that marks the boundary resolved, clearing the fallback if no rows came
+fn resolve_script(boundary: usize, chunks: usize) -> String {
+    let clear = if chunks == 0 { "f.textContent=\"\";" } else { "" };
+    format!(
+        r#"<script>
+(function(){{
+    var f=document.getElementById("F:{id}");
+    if(f){{{clear}f.setAttribute("data-philjs-resolved","");}}
+}})();
+</script>"#,
+        id = boundary,
+        clear = clear
+    )
+}
+
+/// Script that marks the boundary failed
+fn error_script(boundary: usize, message: &str) -> String {
+    format!(
+        r#"<script>
+(function(){{
+    var f=document.getElementById("F:{id}");
+    if(f){{f.setAttribute("data-philjs-error",{message});}}
+}})();
+</script>"#,
+        id = boundary,
+        message = serde_json::to_string(message).unwrap_or_default().replace("</", "<\\/")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_map_and_server_stream() {
+        let rows = futures::stream::iter(vec![Ok::<_, DbError>(1), Ok(2), Err(DbError::NotFound)]);
+        let items: Vec<_> = DbStream::new(rows).map(|n| n * 10).into_server_stream().collect().await;
+
+        assert_eq!(items[0].as_ref().unwrap(), &10);
+        assert_eq!(items[1].as_ref().unwrap(), &20);
+        assert_eq!(items[2].as_ref().unwrap_err().status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_html_chunks() {
+        let rows = futures::stream::iter(vec![Ok::<_, DbError>("a"), Ok("b")]);
+        let chunks: Vec<_> = DbStream::new(rows)
+            .into_html_chunks(3, |s: &str| s.to_string())
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].contains(r#"<template id="S:3:0">ab</template>"#));
+        assert!(chunks[1].contains("data-philjs-resolved"));
+        assert!(!chunks[1].contains("textContent"));
+    }
+
+    #[tokio::test]
+    async fn test_html_chunks_stop_on_error() {
+        let rows = futures::stream::iter(vec![
+            Ok::<_, DbError>("a"),
+            Err(DbError::Query("syntax error at </script>".to_string())),
+            Ok("b"),
+        ]);
+        let chunks: Vec<_> = DbStream::new(rows)
+            .into_html_chunks(0, |s: &str| s.to_string())
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].contains(r#"data-philjs-error","Database error""#));
+        assert!(!chunks[0].contains("syntax error"));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_fetch_from_pool() {
+        use futures::TryStreamExt;
+
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let rows: Vec<(i64,)> = DbStream::fetch_buffered(
+            &pool,
+            "SELECT value FROM (SELECT 1 AS value UNION ALL SELECT ?) ORDER BY value",
+            vec![SqlValue::Int(2)],
+            1,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert_eq!(rows, vec![(1,), (2,)]);
+    }
+}
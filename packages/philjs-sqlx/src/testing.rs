@@ -0,0 +1,544 @@
+//! Database test harness
+//!
+//! [`TestDb`] gives each test its own database, or its own transaction on a
+//! shared one, with migrations applied and fixtures loaded. Enable the
+//! `testing` feature in `[dev-dependencies]`.
+//!
+//! ```rust,ignore
+//! use philjs_sqlx::testing::TestDb;
+//!
+//! // A fresh database, dropped when the closure finishes (even on panic)
+//! #[tokio::test]
+//! async fn signup_creates_profile() {
+//!     TestDb::<Postgres>::from_env()
+//!         .migrations("./migrations")
+//!         .fixture("tests/fixtures/users.json")
+//!         .isolated()
+//!         .await
+//!         .unwrap()
+//!         .run(|pool| async move {
+//!             signup(&pool, "ada@example.com").await.unwrap();
+//!         })
+//!         .await;
+//! }
+//!
+//! // A transaction on the shared test database, rolled back on drop
+//! #[tokio::test]
+//! async fn rename_user() -> DbResult<()> {
+//!     let mut tx = TestDb::<Postgres>::from_env()
+//!         .fixture("tests/fixtures/users.sql")
+//!         .transactional()
+//!         .await?;
+//!     sqlx::query("UPDATE users SET name = 'Grace'").execute(&mut **tx).await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! SQL fixtures are executed as-is. JSON fixtures are either an array of
+//! `{ "table": ..., "rows": [...] }` objects, loaded in order, or an object
+//! mapping table names to rows, loaded in name order.
+
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use sqlx::{Database, Pool, Transaction};
+
+use crate::error::{DbError, DbResult};
+use crate::query::{Backend, SqlValue};
+
+/// Environment variables consulted by [`TestDb::from_env`], in order
+pub const TEST_DATABASE_URL_VARS: &[&str] = &["TEST_DATABASE_URL", "DATABASE_URL"];
+
+/// Data loaded into a test database
+#[derive(Debug, Clone)]
+enum Fixture {
+    File(PathBuf),
+    Sql(String),
+    Rows { table: String, rows: Vec<serde_json::Value> },
+}
+
+/// Configures and creates a [`TestDb`]
+pub struct TestDbBuilder<DB: Database> {
+    url: String,
+    migrations: Option<PathBuf>,
+    fixtures: Vec<Fixture>,
+    error: Option<DbError>,
+    _phantom: PhantomData<DB>,
+}
+
+impl<DB: Database> TestDbBuilder<DB> {
+    /// Apply the migrations in `path` before loading fixtures
+    pub fn migrations(mut self, path: impl Into<PathBuf>) -> Self {
+        self.migrations = Some(path.into());
+        self
+    }
+
+    /// Load a `.sql` or `.json` fixture file
+    pub fn fixture(mut self, path: impl Into<PathBuf>) -> Self {
+        self.fixtures.push(Fixture::File(path.into()));
+        self
+    }
+
+    /// Execute SQL after migrations
+    pub fn sql(mut self, sql: impl Into<String>) -> Self {
+        self.fixtures.push(Fixture::Sql(sql.into()));
+        self
+    }
+
+    /// Insert rows into `table`; each row serializes to a JSON object
+    pub fn rows<T: Serialize>(mut self, table: impl Into<String>, rows: &[T]) -> Self {
+        match rows.iter().map(serde_json::to_value).collect() {
+            Ok(rows) => self.fixtures.push(Fixture::Rows {
+                table: table.into(),
+                rows,
+            }),
+            Err(e) => self.error = Some(e.into()),
+        }
+        self
+    }
+
+    /// Expand file fixtures into SQL and rows
+    fn resolve_fixtures(&mut self) -> DbResult<Vec<Fixture>> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+
+        let mut resolved = Vec::new();
+        for fixture in &self.fixtures {
+            match fixture {
+                Fixture::File(path) => resolved.extend(read_fixture_file(path)?),
+                other => resolved.push(other.clone()),
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+fn read_fixture_file(path: &Path) -> DbResult<Vec<Fixture>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| DbError::Unknown(format!("Cannot read fixture {}: {}", path.display(), e)))?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("sql") => Ok(vec![Fixture::Sql(contents)]),
+        Some("json") => parse_json_fixture(&contents),
+        _ => Err(DbError::Unknown(format!(
+            "Unsupported fixture {}; use .sql or .json",
+            path.display()
+        ))),
+    }
+}
+
+fn parse_json_fixture(contents: &str) -> DbResult<Vec<Fixture>> {
+    #[derive(serde::Deserialize)]
+    struct TableRows {
+        table: String,
+        rows: Vec<serde_json::Value>,
+    }
+
+    match serde_json::from_str::<serde_json::Value>(contents)? {
+        serde_json::Value::Array(tables) => tables
+            .into_iter()
+            .map(|t| {
+                let t: TableRows = serde_json::from_value(t)?;
+                Ok(Fixture::Rows {
+                    table: t.table,
+                    rows: t.rows,
+                })
+            })
+            .collect(),
+        serde_json::Value::Object(tables) => tables
+            .into_iter()
+            .map(|(table, rows)| {
+                Ok(Fixture::Rows {
+                    table,
+                    rows: serde_json::from_value(rows)?,
+                })
+            })
+            .collect(),
+        _ => Err(DbError::Serialization(
+            "JSON fixtures must be an array or an object".to_string(),
+        )),
+    }
+}
+
+/// The bind for a fixture value, or `None` for a JSON null
+///
+/// A null's column type is unknown, so it is written as a literal `NULL`
+/// for the database to type rather than bound as a parameter.
+fn json_to_sql_value(value: serde_json::Value) -> Option<SqlValue> {
+    Some(match value {
+        serde_json::Value::Null => return None,
+        serde_json::Value::Bool(v) => SqlValue::Bool(v),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(v) => SqlValue::Int(v),
+            None => SqlValue::Float(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(v) => SqlValue::Text(v),
+        nested => SqlValue::Text(nested.to_string()),
+    })
+}
+
+/// The INSERT for one fixture row
+fn insert_statement(backend: Backend, table: &str, row: serde_json::Value) -> DbResult<(String, Vec<SqlValue>)> {
+    let serde_json::Value::Object(row) = row else {
+        return Err(DbError::Serialization(format!(
+            "Fixture rows for {} must be JSON objects",
+            table
+        )));
+    };
+
+    let mut columns = Vec::with_capacity(row.len());
+    let mut placeholders = Vec::with_capacity(row.len());
+    let mut values = Vec::with_capacity(row.len());
+    for (column, value) in row {
+        columns.push(backend.quote(&column));
+        match json_to_sql_value(value) {
+            Some(value) => {
+                values.push(value);
+                placeholders.push(backend.placeholder(values.len()));
+            }
+            None => placeholders.push("NULL".to_string()),
+        }
+    }
+
+    let sql = format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        backend.quote(table),
+        columns.join(", "),
+        placeholders.join(", ")
+    );
+    Ok((sql, values))
+}
+
+/// A unique suffix for per-test database names
+fn unique_suffix() -> String {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    format!("{:012x}", hasher.finish() & 0xffff_ffff_ffff)
+}
+
+/// `url` with its database name made unique
+///
+/// In-memory SQLite URLs are already unique per pool and returned as-is.
+pub fn unique_database_url(url: &str, suffix: &str) -> String {
+    if url.contains(":memory:") {
+        return url.to_string();
+    }
+
+    let (base, query) = match url.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (url, None),
+    };
+    let with_query = |base: String| match query {
+        Some(query) => format!("{}?{}", base, query),
+        None => base,
+    };
+
+    if base.starts_with("sqlite:") {
+        let (stem, ext) = match base.rsplit_once('.') {
+            Some((stem, ext)) if !ext.contains('/') => (stem, format!(".{}", ext)),
+            _ => (base, String::new()),
+        };
+        return with_query(format!("{}_test_{}{}", stem, suffix, ext));
+    }
+
+    let authority_end = base.find("://").map(|i| i + 3).unwrap_or(0);
+    match base[authority_end..].rfind('/') {
+        Some(slash) => {
+            let (prefix, name) = base.split_at(authority_end + slash + 1);
+            let name = if name.is_empty() { "test" } else { name };
+            with_query(format!("{}{}_test_{}", prefix, name, suffix))
+        }
+        None => with_query(format!("{}/test_{}", base, suffix)),
+    }
+}
+
+/// A test database with migrations and fixtures applied
+pub struct TestDb<DB: Database> {
+    pool: Pool<DB>,
+    url: String,
+    owned: bool,
+    torn_down: bool,
+}
+
+impl<DB: Database> TestDb<DB> {
+    /// Configure a test database based on `url`
+    pub fn builder(url: impl Into<String>) -> TestDbBuilder<DB> {
+        TestDbBuilder {
+            url: url.into(),
+            migrations: None,
+            fixtures: Vec::new(),
+            error: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Configure a test database from `TEST_DATABASE_URL` or `DATABASE_URL`
+    pub fn from_env() -> TestDbBuilder<DB> {
+        let url = TEST_DATABASE_URL_VARS
+            .iter()
+            .find_map(|var| std::env::var(var).ok())
+            .unwrap_or_default();
+        let mut builder = Self::builder(url);
+        if builder.url.is_empty() {
+            builder.error = Some(DbError::Context(format!(
+                "Set one of {} to run database tests",
+                TEST_DATABASE_URL_VARS.join(", ")
+            )));
+        }
+        builder
+    }
+
+    /// The test database's pool
+    pub fn pool(&self) -> &Pool<DB> {
+        &self.pool
+    }
+
+    /// The test database's URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl<DB: Database> Drop for TestDb<DB> {
+    fn drop(&mut self) {
+        if self.owned && !self.torn_down {
+            tracing::warn!(
+                url = %self.url,
+                "TestDb dropped without teardown(); the test database was left behind"
+            );
+        }
+    }
+}
+
+/// A transaction on the test database, rolled back when dropped
+pub struct TestTx<DB: Database> {
+    tx: Transaction<'static, DB>,
+    pool: Pool<DB>,
+}
+
+impl<DB: Database> TestTx<DB> {
+    /// The pool the transaction was started on
+    pub fn pool(&self) -> &Pool<DB> {
+        &self.pool
+    }
+}
+
+impl<DB: Database> std::ops::Deref for TestTx<DB> {
+    type Target = Transaction<'static, DB>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.tx
+    }
+}
+
+impl<DB: Database> std::ops::DerefMut for TestTx<DB> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.tx
+    }
+}
+
+macro_rules! impl_test_db {
+    ($db:ty) => {
+        impl TestDbBuilder<$db> {
+            async fn connect(url: &str) -> DbResult<Pool<$db>> {
+                // Each in-memory SQLite connection is a separate database
+                let max = if url.contains(":memory:") { 1 } else { 5 };
+                sqlx::pool::PoolOptions::<$db>::new()
+                    .max_connections(max)
+                    .connect(url)
+                    .await
+                    .map_err(|e| DbError::Connection(e.to_string()))
+            }
+
+            async fn migrate(&self, pool: &Pool<$db>) -> DbResult<()> {
+                if let Some(path) = &self.migrations {
+                    sqlx::migrate::Migrator::new(path.as_path())
+                        .await
+                        .map_err(|e| DbError::Migration(e.to_string()))?
+                        .run(pool)
+                        .await
+                        .map_err(|e| DbError::Migration(e.to_string()))?;
+                }
+                Ok(())
+            }
+
+            async fn load(
+                conn: &mut <$db as Database>::Connection,
+                fixtures: Vec<Fixture>,
+            ) -> DbResult<()> {
+                use crate::query::BindValue;
+                use sqlx::Executor;
+
+                let backend = Backend::of::<$db>();
+                for fixture in fixtures {
+                    match fixture {
+                        Fixture::Sql(sql) => {
+                            conn.execute(sql.as_str()).await?;
+                        }
+                        Fixture::Rows { table, rows } => {
+                            for row in rows {
+                                let (sql, values) = insert_statement(backend, &table, row)?;
+                                values
+                                    .into_iter()
+                                    .fold(sqlx::query::<$db>(&sql), |q, value| {
+                                        <$db as BindValue>::bind_query(q, value)
+                                    })
+                                    .execute(&mut *conn)
+                                    .await?;
+                            }
+                        }
+                        Fixture::File(_) => unreachable!("file fixtures are resolved first"),
+                    }
+                }
+                Ok(())
+            }
+
+            /// Create a fresh database, migrate it, and load fixtures
+            ///
+            /// Call [`TestDb::teardown`] or use [`TestDb::run`] to drop it.
+            pub async fn isolated(mut self) -> DbResult<TestDb<$db>> {
+                use sqlx::migrate::MigrateDatabase;
+
+                let fixtures = self.resolve_fixtures()?;
+                let url = unique_database_url(&self.url, &unique_suffix());
+                let owned = !url.contains(":memory:");
+                if owned {
+                    <$db>::create_database(&url)
+                        .await
+                        .map_err(|e| DbError::Connection(e.to_string()))?;
+                }
+
+                let db = TestDb {
+                    pool: Self::connect(&url).await?,
+                    url,
+                    owned,
+                    torn_down: false,
+                };
+                self.migrate(&db.pool).await?;
+                let mut conn = db.pool.acquire().await?;
+                Self::load(&mut conn, fixtures).await?;
+                drop(conn);
+
+                Ok(db)
+            }
+
+            /// Begin a transaction on the configured database and load
+            /// fixtures into it; nothing outlives the test
+            pub async fn transactional(mut self) -> DbResult<TestTx<$db>> {
+                let fixtures = self.resolve_fixtures()?;
+                let pool = Self::connect(&self.url).await?;
+                self.migrate(&pool).await?;
+
+                let mut tx = pool.begin().await.map_err(DbError::from_transaction)?;
+                Self::load(&mut tx, fixtures).await?;
+                Ok(TestTx { tx, pool })
+            }
+        }
+
+        impl TestDb<$db> {
+            /// Close the pool and drop the database
+            pub async fn teardown(mut self) -> DbResult<()> {
+                use sqlx::migrate::MigrateDatabase;
+
+                self.torn_down = true;
+                self.pool.close().await;
+                if self.owned {
+                    <$db>::drop_database(&self.url)
+                        .await
+                        .map_err(|e| DbError::Connection(e.to_string()))?;
+                }
+                Ok(())
+            }
+
+            /// Run a test body, then tear down even if it panics
+            pub async fn run<F, Fut, T>(self, f: F) -> T
+            where
+                F: FnOnce(Pool<$db>) -> Fut,
+                Fut: std::future::Future<Output = T>,
+            {
+                use futures::FutureExt;
+
+                let result = std::panic::AssertUnwindSafe(f(self.pool.clone()))
+                    .catch_unwind()
+                    .await;
+                if let Err(e) = self.teardown().await {
+                    tracing::warn!(error = %e, "Failed to drop test database");
+                }
+                match result {
+                    Ok(value) => value,
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "postgres")]
+impl_test_db!(sqlx::Postgres);
+#[cfg(feature = "mysql")]
+impl_test_db!(sqlx::MySql);
+#[cfg(feature = "sqlite")]
+impl_test_db!(sqlx::Sqlite);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_database_url() {
+        assert_eq!(
+            unique_database_url("postgres://u:p@localhost:5432/app?sslmode=disable", "ab12"),
+            "postgres://u:p@localhost:5432/app_test_ab12?sslmode=disable"
+        );
+        assert_eq!(unique_database_url("mysql://localhost/", "ab12"), "mysql://localhost/test_test_ab12");
+        assert_eq!(unique_database_url("sqlite://data/app.db", "ab12"), "sqlite://data/app_test_ab12.db");
+        assert_eq!(unique_database_url("sqlite::memory:", "ab12"), "sqlite::memory:");
+    }
+
+    #[test]
+    fn test_json_fixtures() {
+        let ordered = parse_json_fixture(
+            r#"[{"table": "users", "rows": [{"id": 1}]}, {"table": "posts", "rows": []}]"#,
+        )
+        .unwrap();
+        let tables: Vec<_> = ordered
+            .iter()
+            .map(|f| match f {
+                Fixture::Rows { table, .. } => table.as_str(),
+                _ => "",
+            })
+            .collect();
+        assert_eq!(tables, vec!["users", "posts"]);
+
+        assert!(parse_json_fixture(r#"{"users": [{"id": 1}]}"#).is_ok());
+        assert!(parse_json_fixture("42").is_err());
+    }
+
+    #[test]
+    fn test_insert_statement() {
+        let row = serde_json::json!({"id": 1, "name": "Ada", "tags": ["a"], "score": 1.5, "bio": null});
+        let (sql, values) = insert_statement(Backend::Postgres, "users", row).unwrap();
+        assert_eq!(
+            sql,
+            r#"INSERT INTO "users" ("bio", "id", "name", "score", "tags") VALUES (NULL, $1, $2, $3, $4)"#
+        );
+        assert_eq!(
+            values,
+            vec![
+                SqlValue::Int(1),
+                SqlValue::Text("Ada".to_string()),
+                SqlValue::Float(1.5),
+                SqlValue::Text(r#"["a"]"#.to_string()),
+            ]
+        );
+    }
+}
@@ -3,9 +3,10 @@
 //! This module provides ergonomic wrappers around SQLx transactions
 //! with automatic rollback on error.
 
-use crate::error::{DbError, DbResult};
+use crate::error::{DbError, DbResult, RetryConfig};
 use sqlx::{Database, Pool, Transaction};
 use std::future::Future;
+use tracing::Instrument;
 
 /// Transaction helper that automatically commits or rolls back
 ///
@@ -50,13 +51,12 @@ impl<'a, DB: Database> TransactionHelper<'a, DB> {
             .pool
             .begin()
             .await
-            .map_err(|e| DbError::Transaction(e.to_string()))?;
+            .map_err(DbError::from_transaction)?;
 
         match f(&mut tx).await {
             Ok(result) => {
-                tx.commit()
-                    .await
-                    .map_err(|e| DbError::Transaction(e.to_string()))?;
+                // Serializable transactions may only fail at commit
+                tx.commit().await.map_err(DbError::from_transaction)?;
                 Ok(result)
             }
             Err(e) => {
@@ -251,6 +251,9 @@ impl<'a, DB: Database> BatchOperation<'a, DB> {
 
 /// Retry helper for transient transaction errors
 ///
+/// Retries up to `max_retries` times with the default backoff; see
+/// [`retry_transaction_with`].
+///
 /// # Example
 ///
 /// ```rust
@@ -274,17 +277,66 @@ where
     F: Fn(&mut Transaction<'_, DB>) -> Fut,
     Fut: Future<Output = DbResult<T>>,
 {
-    let mut retries = 0;
+    retry_transaction_with(pool, &RetryConfig::new().max_retries(max_retries), f).await
+}
+
+/// Run a transaction, retrying it when it fails for transient reasons
+///
+/// Deadlocks, serialization failures, timeouts and dropped connections are
+/// retried in a fresh transaction after an exponential, jittered backoff,
+/// up to `config.max_retries` times. Other errors are returned immediately.
+/// Each attempt runs in a `transaction_attempt` tracing span.
+///
+/// # Example
+///
+/// ```rust
+/// use philjs_sqlx::error::RetryConfig;
+/// use philjs_sqlx::transaction::retry_transaction_with;
+///
+/// let config = RetryConfig::new().max_retries(5).initial_delay_ms(20);
+/// retry_transaction_with(&pool, &config, |tx| async move {
+///     sqlx::query("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+///         .execute(&mut **tx)
+///         .await?;
+///     transfer(tx, from, to, amount).await
+/// }).await?;
+/// ```
+pub async fn retry_transaction_with<DB, F, T, Fut>(
+    pool: &Pool<DB>,
+    config: &RetryConfig,
+    f: F,
+) -> DbResult<T>
+where
+    DB: Database,
+    F: Fn(&mut Transaction<'_, DB>) -> Fut,
+    Fut: Future<Output = DbResult<T>>,
+{
+    let max_attempts = config.max_retries + 1;
+    let mut attempt = 0;
 
     loop {
-        match with_transaction(pool, &f).await {
+        attempt += 1;
+        let span = tracing::info_span!("transaction_attempt", attempt, max_attempts);
+
+        match with_transaction(pool, &f).instrument(span.clone()).await {
             Ok(result) => return Ok(result),
-            Err(e) if retries < max_retries => {
-                retries += 1;
-                tracing::warn!("Transaction failed, retrying ({}/{}): {}", retries, max_retries, e);
-                tokio::time::sleep(tokio::time::Duration::from_millis(100 * retries as u64)).await;
+            Err(e) if e.is_retryable() && attempt < max_attempts => {
+                let delay = config.jittered_delay(attempt - 1);
+                span.in_scope(|| {
+                    tracing::warn!(
+                        error = %e,
+                        delay_ms = delay.as_millis() as u64,
+                        "Transaction failed, retrying"
+                    )
+                });
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                if e.is_retryable() {
+                    span.in_scope(|| tracing::error!(error = %e, "Transaction failed, retries exhausted"));
+                }
+                return Err(e);
             }
-            Err(e) => return Err(e),
         }
     }
 }
@@ -293,6 +345,37 @@ where
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_retry_only_transient_errors() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let config = RetryConfig::new().max_retries(3).initial_delay_ms(1).jitter(0.0);
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+
+        let result = retry_transaction_with(&pool, &config, |_tx| {
+            let n = attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async move {
+                match n {
+                    0 | 1 => Err(DbError::SerializationFailure("could not serialize access".to_string())),
+                    _ => Ok(n),
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 2);
+
+        attempts.store(0, std::sync::atomic::Ordering::SeqCst);
+        let result: DbResult<()> = retry_transaction_with(&pool, &config, |_tx| {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async { Err(DbError::Constraint("duplicate key".to_string())) }
+        })
+        .await;
+        assert!(matches!(result, Err(DbError::Constraint(_))));
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn test_batch_operation_creation() {
         // This is a placeholder test since we can't easily test with a real database
@@ -48,6 +48,34 @@ mod tests {
         assert!(sql.contains("OFFSET 40"));
     }
 
+    /// Needs a Postgres at `DATABASE_URL`; skipped without one
+    #[cfg(feature = "postgres")]
+    #[tokio::test]
+    async fn test_typed_null_into_integer_column() {
+        use philjs_sqlx::query::BindValue;
+        use philjs_sqlx::{SqlType, SqlValue};
+        use sqlx::{Connection, Row};
+
+        let Ok(url) = std::env::var("DATABASE_URL") else {
+            return;
+        };
+        let mut conn = sqlx::PgConnection::connect(&url).await.unwrap();
+        sqlx::query("CREATE TEMPORARY TABLE philjs_nulls (n BIGINT)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let null = SqlValue::from(None::<i64>);
+        assert_eq!(null, SqlValue::Null(SqlType::Int));
+        <sqlx::Postgres as BindValue>::bind_query(sqlx::query("INSERT INTO philjs_nulls (n) VALUES ($1)"), null)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let row = sqlx::query("SELECT n FROM philjs_nulls").fetch_one(&mut conn).await.unwrap();
+        assert_eq!(row.get::<Option<i64>, _>("n"), None);
+    }
+
     #[test]
     fn test_transaction_helper_creation() {
         // Placeholder for transaction helper tests
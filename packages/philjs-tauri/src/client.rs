@@ -0,0 +1,172 @@
+//! Calling Tauri commands from PhilJS wasm frontends
+//!
+//! Client stubs generated by [`crate::commands::philjs_command`] call
+//! [`invoke`]; it can also be used directly for commands declared elsewhere:
+//!
+//! ```rust,ignore
+//! let version: String = client::invoke("app_version", vec![]).await?;
+//!
+//! // Events from the backend, until `listener` is dropped
+//! let listener = client::listen("download-progress", |percent: u8| progress.set(percent));
+//! ```
+
+use crate::commands::{code, ErrorEnvelope};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    // Present in every Tauri 2 webview, with or without `withGlobalTauri`
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI_INTERNALS__"], js_name = invoke)]
+    async fn tauri_invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI_INTERNALS__"], js_name = transformCallback)]
+    fn transform_callback(callback: &js_sys::Function, once: bool) -> f64;
+
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(message: &str);
+}
+
+/// Serialize one command argument
+pub fn arg<T: Serialize + ?Sized>(name: &str, value: &T) -> Result<serde_json::Value, ErrorEnvelope> {
+    serde_json::to_value(value).map_err(|e| ErrorEnvelope::invalid_arg(name, e.to_string()))
+}
+
+/// Invoke a command with named arguments
+///
+/// Errors thrown by the command are decoded into an [`ErrorEnvelope`];
+/// anything else (an unregistered command, a plain string error) arrives
+/// with code `ipc`.
+pub async fn invoke<T: DeserializeOwned>(
+    cmd: &str,
+    args: Vec<(&str, serde_json::Value)>,
+) -> Result<T, ErrorEnvelope> {
+    let args: serde_json::Map<String, serde_json::Value> =
+        args.into_iter().map(|(name, value)| (name.to_string(), value)).collect();
+    let args = args
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| ErrorEnvelope::new(code::INVALID_ARGS, e.to_string()))?;
+
+    match tauri_invoke(cmd, args).await {
+        Ok(value) => serde_wasm_bindgen::from_value(value).map_err(|e| {
+            ErrorEnvelope::new(
+                code::IPC,
+                format!("unexpected result from command `{}`: {}", cmd, e),
+            )
+        }),
+        Err(error) => Err(decode_error(error)),
+    }
+}
+
+fn decode_error(error: JsValue) -> ErrorEnvelope {
+    if let Ok(envelope) = serde_wasm_bindgen::from_value::<ErrorEnvelope>(error.clone()) {
+        return envelope;
+    }
+    let message = error
+        .as_string()
+        .or_else(|| error.dyn_ref::<js_sys::Error>().map(|e| String::from(e.message())))
+        .unwrap_or_else(|| format!("{:?}", error));
+    ErrorEnvelope::new(code::IPC, message)
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// Emit an event to the backend and every window
+pub async fn emit<T: Serialize + ?Sized>(event: &str, payload: &T) -> Result<(), ErrorEnvelope> {
+    invoke::<()>(
+        "plugin:event|emit",
+        vec![("event", event.into()), ("payload", arg("payload", payload)?)],
+    )
+    .await
+}
+
+#[derive(Deserialize)]
+struct EventMessage<T> {
+    payload: T,
+}
+
+/// A subscription made by [`listen`]; dropping it unsubscribes
+pub struct Listener {
+    event: String,
+    id: Rc<Cell<Option<f64>>>,
+    callback: Option<Closure<dyn FnMut(JsValue)>>,
+}
+
+/// Call `handler` with the payload of every `event` sent by the backend
+///
+/// Payloads that don't deserialize as `T` are logged and skipped.
+pub fn listen<T, F>(event: &str, handler: F) -> Listener
+where
+    T: DeserializeOwned + 'static,
+    F: FnMut(T) + 'static,
+{
+    listen_then(event, handler, || {})
+}
+
+/// Like [`listen`], calling `ready` once the backend will deliver events
+pub fn listen_then<T, F, R>(event: &str, mut handler: F, ready: R) -> Listener
+where
+    T: DeserializeOwned + 'static,
+    F: FnMut(T) + 'static,
+    R: FnOnce() + 'static,
+{
+    let name = event.to_string();
+    let callback = Closure::<dyn FnMut(JsValue)>::new(move |message: JsValue| {
+        match serde_wasm_bindgen::from_value::<EventMessage<T>>(message) {
+            Ok(message) => handler(message.payload),
+            Err(e) => console_warn(&format!("ignoring `{}` event: {}", name, e)),
+        }
+    });
+    let handler_id = transform_callback(callback.as_ref().unchecked_ref(), false);
+
+    let id = Rc::new(Cell::new(None));
+    let registered = Rc::clone(&id);
+    let event_name = event.to_string();
+    wasm_bindgen_futures::spawn_local(async move {
+        let result = invoke::<f64>(
+            "plugin:event|listen",
+            vec![
+                ("event", event_name.into()),
+                ("target", serde_json::json!({ "kind": "Any" })),
+                ("handler", handler_id.into()),
+            ],
+        )
+        .await;
+        match result {
+            Ok(event_id) => {
+                registered.set(Some(event_id));
+                ready();
+            }
+            Err(e) => console_warn(&format!("failed to listen: {}", e)),
+        }
+    });
+
+    Listener {
+        event: event.to_string(),
+        id,
+        callback: Some(callback),
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        let event = std::mem::take(&mut self.event);
+        let id = self.id.get();
+        // Keep the callback alive until the backend stops calling it
+        let callback = self.callback.take();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Some(event_id) = id {
+                let _ = invoke::<()>(
+                    "plugin:event|unlisten",
+                    vec![("event", event.into()), ("eventId", event_id.into())],
+                )
+                .await;
+            }
+            drop(callback);
+        });
+    }
+}
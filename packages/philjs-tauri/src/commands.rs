@@ -1,4 +1,22 @@
 //! Tauri command utilities for PhilJS
+//!
+//! Commands declared with [`philjs_command`] share one error shape with the
+//! frontend, [`ErrorEnvelope`], whether they fail in the command body or
+//! while reading their arguments:
+//!
+//! ```rust,ignore
+//! #[philjs_command]
+//! async fn rename_file(from: PathBuf, to: PathBuf) -> CommandResult<()> {
+//!     tokio::fs::rename(from, to).await?;
+//!     Ok(())
+//! }
+//!
+//! // invoke("rename_file", { from: 1 }) rejects with
+//! // { code: "invalid_args", message: "invalid type: integer `1`, expected path string", field: "from" }
+//! ```
+//!
+//! Calling [`export_typescript`] from the backend's debug build keeps typed
+//! bindings for JS frontends in sync with the Rust signatures.
 
 use serde::{Deserialize, Serialize};
 
@@ -30,30 +48,313 @@ impl From<serde_json::Error> for CommandError {
     }
 }
 
-// Re-export the command macro
+// Re-export the command macros
+pub use philjs_macros::philjs_command;
+#[cfg(not(target_arch = "wasm32"))]
 pub use tauri::command;
 
-/// Helper macro for creating typed commands
-#[macro_export]
-macro_rules! philjs_command {
-    (
-        $(#[$attr:meta])*
-        async fn $name:ident($($arg:ident: $type:ty),*) -> $ret:ty $body:block
-    ) => {
-        $(#[$attr])*
-        #[tauri::command]
-        pub async fn $name($($arg: $type),*) -> Result<$ret, $crate::commands::CommandError> {
-            $body
+// ============================================================================
+// Error Envelope
+// ============================================================================
+
+/// Error codes used by [`ErrorEnvelope`]
+pub mod code {
+    pub const NOT_FOUND: &str = "not_found";
+    pub const INVALID_INPUT: &str = "invalid_input";
+    /// An argument was missing or failed to deserialize
+    pub const INVALID_ARGS: &str = "invalid_args";
+    pub const UNAUTHORIZED: &str = "unauthorized";
+    /// The server holds a newer version of what the call changes
+    pub const CONFLICT: &str = "conflict";
+    pub const INTERNAL: &str = "internal";
+    /// The IPC call itself failed, e.g. the command isn't registered
+    pub const IPC: &str = "ipc";
+}
+
+/// The error every PhilJS command rejects with
+///
+/// Serialized as `{ code, message, field?, details? }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[error("{message}")]
+pub struct ErrorEnvelope {
+    /// Machine-readable error code, see [`code`]
+    pub code: String,
+    pub message: String,
+    /// Argument that failed to deserialize
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// Extra data for the frontend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl ErrorEnvelope {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        ErrorEnvelope {
+            code: code.into(),
+            message: message.into(),
+            field: None,
+            details: None,
         }
-    };
-    (
-        $(#[$attr:meta])*
-        fn $name:ident($($arg:ident: $type:ty),*) -> $ret:ty $body:block
-    ) => {
-        $(#[$attr])*
-        #[tauri::command]
-        pub fn $name($($arg: $type),*) -> Result<$ret, $crate::commands::CommandError> {
-            $body
+    }
+
+    /// An argument that failed to deserialize
+    pub fn invalid_arg(field: impl Into<String>, message: impl Into<String>) -> Self {
+        ErrorEnvelope::new(code::INVALID_ARGS, message).field(field)
+    }
+
+    pub fn field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    pub fn details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn is(&self, code: &str) -> bool {
+        self.code == code
+    }
+}
+
+impl From<CommandError> for ErrorEnvelope {
+    fn from(e: CommandError) -> Self {
+        let code = match &e {
+            CommandError::NotFound(_) => code::NOT_FOUND,
+            CommandError::InvalidInput(_) => code::INVALID_INPUT,
+            CommandError::Unauthorized(_) => code::UNAUTHORIZED,
+            CommandError::Internal(_) => code::INTERNAL,
+        };
+        ErrorEnvelope::new(code, e.to_string())
+    }
+}
+
+impl From<crate::TauriError> for ErrorEnvelope {
+    fn from(e: crate::TauriError) -> Self {
+        ErrorEnvelope::new(code::INTERNAL, e.to_string())
+    }
+}
+
+impl From<std::io::Error> for ErrorEnvelope {
+    fn from(e: std::io::Error) -> Self {
+        CommandError::from(e).into()
+    }
+}
+
+impl From<serde_json::Error> for ErrorEnvelope {
+    fn from(e: serde_json::Error) -> Self {
+        CommandError::from(e).into()
+    }
+}
+
+impl From<anyhow::Error> for ErrorEnvelope {
+    fn from(e: anyhow::Error) -> Self {
+        ErrorEnvelope::new(code::INTERNAL, format!("{:#}", e))
+    }
+}
+
+impl From<String> for ErrorEnvelope {
+    fn from(message: String) -> Self {
+        ErrorEnvelope::new(code::INTERNAL, message)
+    }
+}
+
+impl From<&str> for ErrorEnvelope {
+    fn from(message: &str) -> Self {
+        ErrorEnvelope::new(code::INTERNAL, message)
+    }
+}
+
+// ============================================================================
+// Argument Decoding
+// ============================================================================
+
+/// The arguments of one invocation, read by name
+///
+/// Used by code generated from [`philjs_command`].
+#[derive(Debug, Default)]
+pub struct CommandArgs {
+    args: serde_json::Map<String, serde_json::Value>,
+}
+
+impl CommandArgs {
+    pub fn new(args: serde_json::Map<String, serde_json::Value>) -> Self {
+        CommandArgs { args }
+    }
+
+    /// Read the JSON object a command was invoked with
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_request(command: &str, request: &tauri::ipc::Request<'_>) -> Result<Self, ErrorEnvelope> {
+        match request.body() {
+            tauri::ipc::InvokeBody::Json(serde_json::Value::Object(args)) => Ok(CommandArgs::new(args.clone())),
+            tauri::ipc::InvokeBody::Json(serde_json::Value::Null) => Ok(CommandArgs::default()),
+            _ => Err(ErrorEnvelope::new(
+                code::INVALID_ARGS,
+                format!("command `{}` expects its arguments as a JSON object", command),
+            )),
+        }
+    }
+
+    /// Take and deserialize an argument
+    ///
+    /// A missing argument reads as `null`, so `Option` arguments may be
+    /// left out.
+    pub fn take<T: serde::de::DeserializeOwned>(&mut self, key: &str) -> Result<T, ErrorEnvelope> {
+        let value = self.args.remove(key).unwrap_or(serde_json::Value::Null);
+        let missing = value.is_null();
+        serde_json::from_value(value).map_err(|e| {
+            let message = if missing {
+                format!("missing argument `{}`", key)
+            } else {
+                e.to_string()
+            };
+            ErrorEnvelope::invalid_arg(key, message)
+        })
+    }
+}
+
+// ============================================================================
+// TypeScript Bindings
+// ============================================================================
+
+/// A command's signature, generated by [`philjs_command`] as `{NAME}_COMMAND`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub docs: &'static str,
+    /// Argument names as sent by the frontend, with their TypeScript types
+    pub args: &'static [(&'static str, &'static str)],
+    /// TypeScript type of the successful result
+    pub returns: &'static str,
+}
+
+impl CommandSpec {
+    /// A typed wrapper around `invoke`
+    pub fn to_typescript(&self) -> String {
+        let mut out = String::new();
+        if !self.docs.is_empty() {
+            out.push_str("/**\n");
+            for line in self.docs.lines() {
+                out.push_str(" *");
+                if !line.is_empty() {
+                    out.push(' ');
+                    out.push_str(line);
+                }
+                out.push('\n');
+            }
+            out.push_str(" */\n");
+        }
+
+        // Only trailing nullable arguments can be left out
+        let required = self
+            .args
+            .iter()
+            .rposition(|(_, ty)| !ty.ends_with(" | null"))
+            .map_or(0, |i| i + 1);
+        let params: Vec<String> = self
+            .args
+            .iter()
+            .enumerate()
+            .map(|(i, (name, ty))| {
+                if i < required {
+                    format!("{}: {}", name, ty)
+                } else {
+                    format!("{}?: {}", name, ty)
+                }
+            })
+            .collect();
+        let keys: Vec<&str> = self.args.iter().map(|(name, _)| *name).collect();
+        let function = to_camel_case(self.name);
+
+        out.push_str(&format!(
+            "export function {}({}): Promise<{}> {{\n",
+            function,
+            params.join(", "),
+            self.returns
+        ));
+        out.push_str(&format!(
+            "  return invoke<{}>('{}', {{ {} }});\n}}\n",
+            self.returns,
+            self.name,
+            keys.join(", ")
+        ));
+        out
+    }
+}
+
+/// TypeScript module calling `commands` through `@tauri-apps/api`
+///
+/// Types other than primitives, arrays, maps and `Option` keep their Rust
+/// name and are imported from `./types`.
+pub fn typescript_bindings(commands: &[CommandSpec]) -> String {
+    let mut out = String::from(
+        "// Generated by philjs-tauri. Do not edit.\n\n\
+         import { invoke } from '@tauri-apps/api/core';\n",
+    );
+
+    let mut imports: Vec<&str> = commands
+        .iter()
+        .flat_map(|cmd| cmd.args.iter().map(|(_, ty)| *ty).chain(std::iter::once(cmd.returns)))
+        .flat_map(type_names)
+        .collect();
+    imports.sort_unstable();
+    imports.dedup();
+    if !imports.is_empty() {
+        out.push_str(&format!("import type {{ {} }} from './types';\n", imports.join(", ")));
+    }
+
+    out.push_str(
+        "\n/** Shape of every command error */\n\
+         export interface CommandError {\n  \
+         code: string;\n  \
+         message: string;\n  \
+         field?: string;\n  \
+         details?: unknown;\n\
+         }\n",
+    );
+
+    for command in commands {
+        out.push('\n');
+        out.push_str(&command.to_typescript());
+    }
+    out
+}
+
+/// Write [`typescript_bindings`] to `path`, leaving the file alone if
+/// nothing changed so dev servers don't reload
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export_typescript(path: impl AsRef<std::path::Path>, commands: &[CommandSpec]) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let source = typescript_bindings(commands);
+    if std::fs::read_to_string(path).is_ok_and(|existing| existing == source) {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, source)
+}
+
+/// Names in a TypeScript type that aren't built in
+fn type_names(ty: &str) -> impl Iterator<Item = &str> {
+    const BUILTIN: &[&str] = &["string", "number", "boolean", "null", "void", "unknown", "Record"];
+    ty.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|name| !name.is_empty() && !BUILTIN.contains(name))
+}
+
+fn to_camel_case(s: &str) -> String {
+    let mut result = String::new();
+    let mut capitalize_next = false;
+    for c in s.chars() {
+        if c == '_' || c == '-' {
+            capitalize_next = true;
+        } else if capitalize_next {
+            result.push(c.to_ascii_uppercase());
+            capitalize_next = false;
+        } else {
+            result.push(c);
         }
-    };
+    }
+    result
 }
@@ -0,0 +1,185 @@
+//! Deep links and file-open events for PhilJS Tauri
+//!
+//! Custom-scheme URLs and files opened with the app become navigations in
+//! the embedded PhilJS router. Requires the `deep-link` feature; schemes
+//! are declared under `plugins.deep-link` in `tauri.conf.json`.
+//!
+//! ```rust,ignore
+//! philjs_tauri::Builder::new()
+//!     .deep_links(
+//!         DeepLinks::new()
+//!             .scheme("myapp")
+//!             // myapp://invite/42 -> /teams/join/42
+//!             .map_url(|url| match url.host_str()? {
+//!                 "invite" => Some(format!("/teams/join{}", url.path())),
+//!                 _ => None,
+//!             })
+//!             .map_file(|path| Some(format!("/documents/open?path={}", path.display()))),
+//!     )
+//!     .run()?;
+//! ```
+//!
+//! By default `myapp://settings/profile?tab=2` opens `/settings/profile?tab=2`
+//! and files are ignored. Links that launched the app are delivered once
+//! the frontend calls `use_tauri_navigation`. On Windows and Linux each link
+//! starts a new process unless the single-instance plugin forwards it.
+
+use crate::navigation::{self, Navigation};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Url, Wry};
+
+/// Maps a deep link to a route; `None` ignores it
+pub type UrlMapper = Arc<dyn Fn(&Url) -> Option<String> + Send + Sync>;
+
+/// Maps an opened file to a route; `None` ignores it
+pub type FileMapper = Arc<dyn Fn(&Path) -> Option<String> + Send + Sync>;
+
+/// Which links the app follows, and where they lead
+#[derive(Clone, Default)]
+pub struct DeepLinks {
+    schemes: Vec<String>,
+    map_url: Option<UrlMapper>,
+    map_file: Option<FileMapper>,
+    replace: bool,
+}
+
+impl DeepLinks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Follow links with this scheme; without any, every scheme is followed
+    pub fn scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.schemes.push(scheme.into());
+        self
+    }
+
+    /// Route deep links with `f` instead of the default mapping
+    pub fn map_url<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Url) -> Option<String> + Send + Sync + 'static,
+    {
+        self.map_url = Some(Arc::new(f));
+        self
+    }
+
+    /// Route files opened with the app (double-clicked, dropped on the dock
+    /// icon, passed on the command line)
+    pub fn map_file<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Path) -> Option<String> + Send + Sync + 'static,
+    {
+        self.map_file = Some(Arc::new(f));
+        self
+    }
+
+    /// Replace the current route instead of pushing a history entry
+    pub fn replace(mut self, replace: bool) -> Self {
+        self.replace = replace;
+        self
+    }
+
+    /// The route for `url`, if the app follows it
+    pub fn route(&self, url: &Url) -> Option<String> {
+        if url.scheme() == "file" {
+            let path = url.to_file_path().ok()?;
+            return self.route_file(&path);
+        }
+        if !self.schemes.is_empty() && !self.schemes.iter().any(|s| s == url.scheme()) {
+            return None;
+        }
+        match &self.map_url {
+            Some(map) => map(url),
+            None => Some(default_route(url)),
+        }
+    }
+
+    /// The route for an opened file, if the app follows it
+    pub fn route_file(&self, path: &Path) -> Option<String> {
+        self.map_file.as_ref().and_then(|map| map(path))
+    }
+
+    fn open(&self, app: &AppHandle<Wry>, route: Option<String>) {
+        let Some(path) = route else {
+            return;
+        };
+        let navigation = if self.replace {
+            Navigation::replace(path)
+        } else {
+            Navigation::push(path)
+        };
+        if let Err(e) = navigation::send(app, navigation) {
+            tracing::warn!("{}", e);
+        }
+    }
+}
+
+/// `scheme://host/path?query#fragment` to `/host/path?query#fragment`
+fn default_route(url: &Url) -> String {
+    let mut route = String::new();
+    if let Some(host) = url.host_str() {
+        route.push('/');
+        route.push_str(host);
+    }
+    let path = url.path();
+    if !path.is_empty() && path != "/" {
+        if !path.starts_with('/') {
+            route.push('/');
+        }
+        route.push_str(path);
+    }
+    if route.is_empty() {
+        route.push('/');
+    }
+    if let Some(query) = url.query() {
+        route.push('?');
+        route.push_str(query);
+    }
+    if let Some(fragment) = url.fragment() {
+        route.push('#');
+        route.push_str(fragment);
+    }
+    route
+}
+
+/// Files passed on the command line, which is how Windows and Linux open
+/// documents with an app
+fn launch_files() -> Vec<PathBuf> {
+    std::env::args_os()
+        .skip(1)
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Follow deep links; called by [`crate::Builder::run`]
+pub(crate) fn install(app: &AppHandle<Wry>, links: DeepLinks) {
+    use tauri_plugin_deep_link::DeepLinkExt;
+
+    // Installed apps register through their bundle; this covers `tauri dev`
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        tracing::warn!("failed to register deep link schemes: {}", e);
+    }
+
+    match app.deep_link().get_current() {
+        Ok(Some(urls)) => {
+            for url in &urls {
+                links.open(app, links.route(url));
+            }
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("failed to read launch deep link: {}", e),
+    }
+    for path in launch_files() {
+        links.open(app, links.route_file(&path));
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            links.open(&handle, links.route(&url));
+        }
+    });
+}
@@ -0,0 +1,258 @@
+//! Event bus between the backend and PhilJS components
+//!
+//! Backend tasks push state with [`share`] or the `emit_*` helpers, and
+//! components read it with [`use_tauri_event`], without wiring listeners
+//! by hand:
+//!
+//! ```rust,ignore
+//! // Backend: every change to `progress` reaches the UI
+//! let progress = create_signal(0u8);
+//! events::share(app.handle(), "import-progress", progress.clone());
+//! tauri::async_runtime::spawn(async move {
+//!     for step in 0..=100 {
+//!         progress.set(step);
+//!         sleep(Duration::from_millis(50)).await;
+//!     }
+//! });
+//!
+//! // PhilJS wasm component
+//! let progress = use_tauri_event::<u8>("import-progress");
+//! view! { <progress max="100" value={move || progress.get().unwrap_or(0)} /> }
+//! ```
+//!
+//! Components mounting after a shared signal last changed still get its
+//! value: subscribing asks the backend to resend it.
+
+use serde::{Deserialize, Serialize};
+
+/// Event a component sends when it starts listening, so shared signals
+/// can resend their current value
+pub const SYNC_EVENT: &str = "philjs://events/sync";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncRequest {
+    event: String,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use backend::*;
+
+#[cfg(target_arch = "wasm32")]
+pub use frontend::*;
+
+// ============================================================================
+// Backend
+// ============================================================================
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use super::*;
+    use crate::state::Signal;
+    use crate::TauriError;
+    use serde::de::DeserializeOwned;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex, OnceLock};
+    use tauri::{AppHandle, Emitter, EventId, EventTarget, Listener, Wry};
+
+    fn emit_error(e: tauri::Error) -> TauriError {
+        TauriError::Runtime(format!("event: {}", e))
+    }
+
+    /// Send an event to every window
+    pub fn emit_all<T: Serialize + Clone>(app: &AppHandle<Wry>, event: &str, payload: T) -> Result<(), TauriError> {
+        app.emit(event, payload).map_err(emit_error)
+    }
+
+    /// Send an event to one window, by label
+    pub fn emit_to_window<T: Serialize + Clone>(
+        app: &AppHandle<Wry>,
+        label: &str,
+        event: &str,
+        payload: T,
+    ) -> Result<(), TauriError> {
+        app.emit_to(EventTarget::webview_window(label), event, payload)
+            .map_err(emit_error)
+    }
+
+    /// Send an event to the windows whose label passes `filter`
+    pub fn emit_to_windows<T, F>(app: &AppHandle<Wry>, event: &str, payload: T, filter: F) -> Result<(), TauriError>
+    where
+        T: Serialize + Clone,
+        F: Fn(&str) -> bool,
+    {
+        app.emit_filter(event, payload, |target| match target {
+            EventTarget::AnyLabel { label }
+            | EventTarget::Window { label }
+            | EventTarget::Webview { label }
+            | EventTarget::WebviewWindow { label } => filter(label),
+            _ => false,
+        })
+        .map_err(emit_error)
+    }
+
+    type Resend = Arc<dyn Fn() + Send + Sync>;
+
+    /// Shared signals by event name, for answering sync requests
+    fn shared() -> &'static Mutex<HashMap<String, Resend>> {
+        static SHARED: OnceLock<Mutex<HashMap<String, Resend>>> = OnceLock::new();
+        SHARED.get_or_init(|| Mutex::new(HashMap::new()))
+    }
+
+    /// Emit `signal` as `event` to every window whenever it changes, and to
+    /// components that subscribe later
+    pub fn share<T>(app: &AppHandle<Wry>, event: &str, signal: Signal<T>)
+    where
+        T: Serialize + Clone + Send + Sync + 'static,
+    {
+        let emitter = app.clone();
+        let name = event.to_string();
+        signal.subscribe(move |value| {
+            if let Err(e) = emitter.emit(&name, value) {
+                tracing::warn!("failed to emit `{}`: {}", name, e);
+            }
+        });
+
+        let emitter = app.clone();
+        let name = event.to_string();
+        let resend: Resend = Arc::new(move || {
+            let _ = emitter.emit(&name, signal.get());
+        });
+
+        let mut shared = shared().lock().unwrap();
+        if shared.is_empty() {
+            app.listen_any(SYNC_EVENT, |event| {
+                let Ok(request) = serde_json::from_str::<SyncRequest>(event.payload()) else {
+                    return;
+                };
+                let resend = shared().lock().unwrap().get(&request.event).cloned();
+                if let Some(resend) = resend {
+                    resend();
+                }
+            });
+        }
+        shared.insert(event.to_string(), resend);
+    }
+
+    /// Call `handler` with every `event` from the frontend or other
+    /// backend code; payloads that don't deserialize are logged and skipped
+    pub fn listen<T, F>(app: &AppHandle<Wry>, event: &str, handler: F) -> EventId
+    where
+        T: DeserializeOwned,
+        F: Fn(T) + Send + 'static,
+    {
+        let name = event.to_string();
+        app.listen_any(event, move |event| {
+            match serde_json::from_str::<T>(event.payload()) {
+                Ok(payload) => handler(payload),
+                Err(e) => tracing::warn!("ignoring `{}` event: {}", name, e),
+            }
+        })
+    }
+
+    /// Receive every `event` as a stream, e.g. in a background task
+    ///
+    /// The listener is removed once the receiver is dropped and the next
+    /// event arrives.
+    pub fn stream<T>(app: &AppHandle<Wry>, event: &str) -> tokio::sync::mpsc::UnboundedReceiver<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let id = Arc::new(Mutex::new(None::<EventId>));
+        let registered = Arc::clone(&id);
+        let remover = app.clone();
+        let name = event.to_string();
+
+        let event_id = app.listen_any(event, move |event| {
+            match serde_json::from_str::<T>(event.payload()) {
+                Ok(payload) => {
+                    if tx.send(payload).is_err() {
+                        if let Some(id) = registered.lock().unwrap().take() {
+                            remover.unlisten(id);
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("ignoring `{}` event: {}", name, e),
+            }
+        });
+        *id.lock().unwrap() = Some(event_id);
+        rx
+    }
+}
+
+// ============================================================================
+// Frontend
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod frontend {
+    use super::*;
+    use crate::client;
+    use philjs::{on_cleanup, Signal};
+    use serde::de::DeserializeOwned;
+
+    fn request_sync(event: String) {
+        let request = SyncRequest { event };
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = client::emit(SYNC_EVENT, &request).await;
+        });
+    }
+
+    /// The latest payload of `event`, as a signal; `None` until the first
+    /// one arrives
+    ///
+    /// Stops listening when the component is cleaned up.
+    pub fn use_tauri_event<T>(event: &str) -> Signal<Option<T>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let signal = Signal::new(None);
+        let target = signal.clone();
+        let name = event.to_string();
+        let listener = client::listen_then(
+            event,
+            move |payload: T| target.set(Some(payload)),
+            move || request_sync(name),
+        );
+        on_cleanup(move || drop(listener));
+        signal
+    }
+
+    /// Like [`use_tauri_event`], starting from `initial`
+    pub fn use_tauri_event_or<T>(event: &str, initial: T) -> Signal<T>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let signal = Signal::new(initial);
+        let target = signal.clone();
+        let name = event.to_string();
+        let listener = client::listen_then(
+            event,
+            move |payload: T| target.set(payload),
+            move || request_sync(name),
+        );
+        on_cleanup(move || drop(listener));
+        signal
+    }
+
+    /// Every payload of `event`, as a stream; listening stops when the
+    /// stream is dropped
+    pub fn tauri_event_stream<T>(event: &str) -> impl futures::Stream<Item = T>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let name = event.to_string();
+        let listener = client::listen_then(
+            event,
+            move |payload: T| {
+                let _ = tx.unbounded_send(payload);
+            },
+            move || request_sync(name),
+        );
+        futures::stream::unfold((rx, listener), |(mut rx, listener)| async move {
+            use futures::StreamExt;
+            rx.next().await.map(|item| (item, (rx, listener)))
+        })
+    }
+}
@@ -6,7 +6,12 @@
 //! # Features
 //!
 //! - **Native Integration**: Access system APIs through Tauri commands
-//! - **Type-Safe Commands**: Generate TypeScript bindings from Rust commands
+//! - **Type-Safe Commands**: Call Rust commands from wasm or TypeScript with
+//!   generated, typed clients
+//! - **Shortcuts & Deep Links**: Global shortcuts, custom URL schemes and
+//!   opened files navigate the PhilJS router
+//! - **Offline Sync**: Server function calls made offline are queued and
+//!   replayed when the server is reachable again
 //! - **Hot Reload**: Fast development with HMR support
 //! - **Small Binaries**: Tauri's efficient bundling
 //! - **Cross-Platform**: Windows, macOS, Linux from one codebase
@@ -16,12 +21,15 @@
 //! ```rust,ignore
 //! use philjs_tauri::prelude::*;
 //!
-//! #[tauri::command]
-//! fn greet(name: &str) -> String {
+//! #[philjs_command]
+//! fn greet(name: String) -> String {
 //!     format!("Hello, {}!", name)
 //! }
 //!
 //! fn main() {
+//!     #[cfg(debug_assertions)]
+//!     commands::export_typescript("../src/bindings.ts", &[GREET_COMMAND]).unwrap();
+//!
 //!     philjs_tauri::Builder::new()
 //!         .invoke_handler(tauri::generate_handler![greet])
 //!         .run()
@@ -30,43 +38,95 @@
 //! ```
 
 pub mod commands;
+pub mod events;
+pub mod navigation;
+pub mod shortcuts;
+pub mod state;
+pub mod sync;
+
+// Everything below talks to Tauri directly, so it only exists in the
+// backend; a wasm frontend gets the command client instead.
+#[cfg(target_arch = "wasm32")]
+pub mod client;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod window;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod tray;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod menu;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod dialog;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod clipboard;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod notification;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod fs;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod process;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod shell;
 pub mod updater;
-pub mod state;
+#[cfg(all(not(target_arch = "wasm32"), feature = "secure-store"))]
+pub mod secure_store;
+#[cfg(all(not(target_arch = "wasm32"), feature = "deep-link"))]
+pub mod deep_link;
 
+#[cfg(not(target_arch = "wasm32"))]
 pub mod prelude {
     pub use crate::{
         Builder, PhilJSApp, TauriConfig,
         commands::*,
         window::{Window, WindowBuilder, WindowConfig},
         tray::{TrayIcon, TrayBuilder},
-        menu::{Menu, MenuItem, MenuBuilder},
+        menu::{Menu, MenuAction, MenuBuilder, MenuItem, Predefined, RouteItem},
         dialog::{Dialog, FileDialog, MessageDialog},
         clipboard::Clipboard,
         notification::Notification,
+        state::{create_signal, MaybeSignal, Signal},
+        events::{emit_all, emit_to_window},
+        navigation::navigate,
     };
+    #[cfg(feature = "deep-link")]
+    pub use crate::deep_link::DeepLinks;
+    #[cfg(feature = "sync")]
+    pub use crate::sync::{Resolution, SyncConfig, SyncStatus};
     pub use tauri::{self, AppHandle, Manager, State, Wry};
 }
 
-use std::sync::Arc;
+#[cfg(target_arch = "wasm32")]
+pub mod prelude {
+    pub use crate::{
+        client,
+        commands::*,
+        state::{create_signal, MaybeSignal, Signal},
+        updater::UpdateBanner,
+        events::{tauri_event_stream, use_tauri_event, use_tauri_event_or},
+        navigation::use_tauri_navigation,
+        shortcuts::use_global_shortcut,
+        sync::{use_sync_status, Synced, SyncStatus},
+    };
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 use tauri::{App, AppHandle, Manager, Wry};
 
 /// PhilJS Tauri application builder
+#[cfg(not(target_arch = "wasm32"))]
 pub struct Builder {
     title: String,
     config: TauriConfig,
     setup_hook: Option<Box<dyn FnOnce(&mut App<Wry>) -> Result<(), Box<dyn std::error::Error>> + Send>>,
     invoke_handler: Option<Box<dyn Fn(tauri::Invoke<Wry>) + Send + Sync>>,
+    #[cfg(feature = "updater")]
+    updater: Option<updater::UpdaterConfig>,
+    #[cfg(feature = "deep-link")]
+    deep_links: Option<deep_link::DeepLinks>,
+    #[cfg(feature = "sync")]
+    sync: Option<sync::SyncConfig>,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl Builder {
     pub fn new() -> Self {
         Builder {
@@ -74,6 +134,12 @@ impl Builder {
             config: TauriConfig::default(),
             setup_hook: None,
             invoke_handler: None,
+            #[cfg(feature = "updater")]
+            updater: None,
+            #[cfg(feature = "deep-link")]
+            deep_links: None,
+            #[cfg(feature = "sync")]
+            sync: None,
         }
     }
 
@@ -107,6 +173,28 @@ impl Builder {
         self
     }
 
+    /// Enable the auto-updater; a downloaded update installs on quit
+    #[cfg(feature = "updater")]
+    pub fn updater(mut self, config: updater::UpdaterConfig) -> Self {
+        self.updater = Some(config);
+        self
+    }
+
+    /// Open deep links and files as routes in the PhilJS app
+    #[cfg(feature = "deep-link")]
+    pub fn deep_links(mut self, links: deep_link::DeepLinks) -> Self {
+        self.deep_links = Some(links);
+        self
+    }
+
+    /// Queue server function calls made while offline and replay them
+    /// when the server is back
+    #[cfg(feature = "sync")]
+    pub fn sync(mut self, config: sync::SyncConfig) -> Self {
+        self.sync = Some(config);
+        self
+    }
+
     /// Build and run the application
     pub fn run(self) -> Result<(), TauriError> {
         let mut builder = tauri::Builder::default();
@@ -121,24 +209,74 @@ impl Builder {
             .plugin(tauri_plugin_process::init())
             .plugin(tauri_plugin_os::init());
 
-        // Add setup hook
-        if let Some(setup) = self.setup_hook {
-            builder = builder.setup(move |app| {
-                setup(app)
-            });
+        #[cfg(feature = "updater")]
+        let updater_config = self.updater;
+        #[cfg(feature = "updater")]
+        if updater_config.is_some() {
+            builder = builder.plugin(tauri_plugin_updater::Builder::new().build());
+        }
+
+        #[cfg(all(feature = "global-shortcut", not(any(target_os = "android", target_os = "ios"))))]
+        {
+            builder = builder.plugin(tauri_plugin_global_shortcut::Builder::new().build());
+        }
+
+        #[cfg(feature = "deep-link")]
+        let deep_links = self.deep_links;
+        #[cfg(feature = "deep-link")]
+        if deep_links.is_some() {
+            builder = builder.plugin(tauri_plugin_deep_link::init());
         }
 
+        #[cfg(feature = "sync")]
+        let sync_config = self.sync;
+
+        // Add setup hook
+        let setup_hook = self.setup_hook;
+        builder = builder.setup(move |app| {
+            navigation::install(app.handle());
+            #[cfg(all(feature = "global-shortcut", not(any(target_os = "android", target_os = "ios"))))]
+            shortcuts::install(app.handle());
+            #[cfg(feature = "deep-link")]
+            if let Some(links) = deep_links {
+                deep_link::install(app.handle(), links);
+            }
+            #[cfg(feature = "sync")]
+            if let Some(config) = sync_config {
+                sync::install(app.handle(), config)?;
+            }
+            #[cfg(feature = "updater")]
+            if let Some(config) = updater_config {
+                updater::install(app.handle(), config);
+            }
+            match setup_hook {
+                Some(setup) => setup(app),
+                None => Ok(()),
+            }
+        });
+
         // Add invoke handler
         if let Some(handler) = self.invoke_handler {
             builder = builder.invoke_handler(handler);
         }
 
-        builder
-            .run(tauri::generate_context!())
-            .map_err(|e| TauriError::Runtime(e.to_string()))
+        let app = builder
+            .build(tauri::generate_context!())
+            .map_err(|e| TauriError::Runtime(e.to_string()))?;
+
+        app.run(|_app, event| {
+            #[cfg(feature = "updater")]
+            if let tauri::RunEvent::Exit = event {
+                updater::on_exit(_app);
+            }
+            #[cfg(not(feature = "updater"))]
+            let _ = event;
+        });
+        Ok(())
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl Default for Builder {
     fn default() -> Self {
         Self::new()
@@ -146,11 +284,13 @@ impl Default for Builder {
 }
 
 /// PhilJS Tauri application handle
+#[cfg(not(target_arch = "wasm32"))]
 #[derive(Clone)]
 pub struct PhilJSApp {
     handle: AppHandle<Wry>,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl PhilJSApp {
     pub fn new(handle: AppHandle<Wry>) -> Self {
         PhilJSApp { handle }
@@ -200,6 +340,7 @@ impl PhilJSApp {
 }
 
 /// Tauri configuration
+#[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug, Clone)]
 pub struct TauriConfig {
     /// Window configuration
@@ -212,6 +353,7 @@ pub struct TauriConfig {
     pub single_instance: bool,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl Default for TauriConfig {
     fn default() -> Self {
         TauriConfig {
@@ -239,6 +381,7 @@ pub enum TauriError {
 }
 
 /// Initialize PhilJS Tauri with default settings
+#[cfg(not(target_arch = "wasm32"))]
 pub fn init() -> Builder {
     Builder::new()
 }
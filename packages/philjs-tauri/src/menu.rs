@@ -1,7 +1,45 @@
 //! Menu utilities for PhilJS Tauri
+//!
+//! Labels, checkmarks and enabled states take either a value or a
+//! [`Signal`], and native items follow their signals after the menu is
+//! built:
+//!
+//! ```rust,ignore
+//! let syncing = create_signal(false);
+//!
+//! let menu = Menu::new()
+//!     .item(MenuItem::check("sync", "Sync", syncing.clone()))
+//!     .item(MenuItem::text("sync-now", "Sync now").enabled(syncing.clone()).on_click(sync_now));
+//! ```
+//!
+//! [`MenuBuilder::from_routes`] generates a standard menu bar whose
+//! Navigate menu moves the embedded PhilJS router:
+//!
+//! ```rust,ignore
+//! let signed_in = create_signal(false);
+//!
+//! MenuBuilder::from_routes([
+//!     RouteItem::new("/", "Home"),
+//!     RouteItem::new("/inbox", "Inbox").enabled(signed_in.clone()),
+//!     RouteItem::new("/settings", "Settings").accelerator("CmdOrCtrl+,"),
+//! ])
+//! .build()
+//! .install(app.handle())?;
+//! ```
 
+use crate::navigation;
+use crate::state::{MaybeSignal, Signal};
 use crate::TauriError;
+use std::collections::HashMap;
 use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, Wry};
+
+/// Event emitted to the frontend when a menu item is clicked; the payload
+/// is the item id
+pub const MENU_EVENT: &str = "philjs://menu";
+
+/// Click handler for a menu item
+pub type MenuHandler = Arc<dyn Fn() + Send + Sync>;
 
 /// Menu structure
 pub struct Menu {
@@ -23,13 +61,43 @@ impl Menu {
         self
     }
 
-    pub fn submenu(mut self, label: impl Into<String>, menu: Menu) -> Self {
+    pub fn submenu(mut self, label: impl Into<MaybeSignal<String>>, menu: Menu) -> Self {
         self.items.push(MenuItem::Submenu {
             label: label.into(),
             menu,
         });
         self
     }
+
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    /// Create the native menu, binding items to their signals
+    pub fn build(&self, app: &AppHandle<Wry>) -> Result<NativeMenu, TauriError> {
+        let mut handlers = HashMap::new();
+        let items = build_items(app, &self.items, &mut handlers)?;
+        let refs: Vec<&dyn tauri::menu::IsMenuItem<Wry>> = items.iter().map(NativeItem::as_dyn).collect();
+        let menu = tauri::menu::Menu::with_items(app, &refs).map_err(menu_error)?;
+        Ok(NativeMenu {
+            menu,
+            handlers: MenuHandlers {
+                handlers: Arc::new(handlers),
+            },
+        })
+    }
+
+    /// Build the menu and make it the app's menu bar, replacing any other
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub fn install(&self, app: &AppHandle<Wry>) -> Result<MenuHandlers, TauriError> {
+        let native = self.build(app)?;
+        app.set_menu(native.menu).map_err(menu_error)?;
+        let handlers = native.handlers.clone();
+        app.on_menu_event(move |app, event| {
+            handlers.dispatch(app, event.id().as_ref());
+        });
+        Ok(native.handlers)
+    }
 }
 
 impl Default for Menu {
@@ -42,47 +110,199 @@ impl Default for Menu {
 pub enum MenuItem {
     Text {
         id: String,
-        label: String,
-        enabled: bool,
+        label: MaybeSignal<String>,
+        enabled: MaybeSignal<bool>,
         accelerator: Option<String>,
-        handler: Option<Arc<dyn Fn() + Send + Sync>>,
+        handler: Option<MenuHandler>,
+        action: Option<MenuAction>,
     },
     Check {
         id: String,
-        label: String,
-        checked: bool,
-        enabled: bool,
+        label: MaybeSignal<String>,
+        /// Clicking toggles the signal, if there is one
+        checked: MaybeSignal<bool>,
+        enabled: MaybeSignal<bool>,
+        accelerator: Option<String>,
+        handler: Option<MenuHandler>,
     },
     Separator,
+    /// An item implemented by the OS, like Copy or Quit
+    Predefined(Predefined),
     Submenu {
-        label: String,
+        label: MaybeSignal<String>,
         menu: Menu,
     },
 }
 
+/// Built-in behaviour of a menu item, run after its handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuAction {
+    /// Push a route in the PhilJS app
+    Navigate(String),
+    /// Go back in the focused window's history
+    Back,
+    /// Go forward in the focused window's history
+    Forward,
+    /// Reload the focused window
+    Reload,
+    ToggleFullscreen,
+    CloseWindow,
+    Quit,
+}
+
+/// Menu items provided by the OS
+///
+/// Not every platform has every item; Linux only implements the clipboard
+/// items and About, and shows the others disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Predefined {
+    Undo,
+    Redo,
+    Cut,
+    Copy,
+    Paste,
+    SelectAll,
+    Minimize,
+    Maximize,
+    Fullscreen,
+    CloseWindow,
+    Hide,
+    HideOthers,
+    ShowAll,
+    Services,
+    About,
+    Quit,
+}
+
 impl MenuItem {
-    pub fn text(id: impl Into<String>, label: impl Into<String>) -> Self {
+    pub fn text(id: impl Into<String>, label: impl Into<MaybeSignal<String>>) -> Self {
         MenuItem::Text {
             id: id.into(),
             label: label.into(),
-            enabled: true,
+            enabled: MaybeSignal::Static(true),
             accelerator: None,
             handler: None,
+            action: None,
         }
     }
 
-    pub fn check(id: impl Into<String>, label: impl Into<String>, checked: bool) -> Self {
+    /// An item that navigates the PhilJS app to `path`
+    pub fn route(path: impl Into<String>, label: impl Into<MaybeSignal<String>>) -> Self {
+        let path = path.into();
+        MenuItem::text(format!("navigate:{}", path), label).action(MenuAction::Navigate(path))
+    }
+
+    pub fn predefined(item: Predefined) -> Self {
+        MenuItem::Predefined(item)
+    }
+
+    pub fn check(
+        id: impl Into<String>,
+        label: impl Into<MaybeSignal<String>>,
+        checked: impl Into<MaybeSignal<bool>>,
+    ) -> Self {
         MenuItem::Check {
             id: id.into(),
             label: label.into(),
-            checked,
-            enabled: true,
+            checked: checked.into(),
+            enabled: MaybeSignal::Static(true),
+            accelerator: None,
+            handler: None,
         }
     }
 
     pub fn separator() -> Self {
         MenuItem::Separator
     }
+
+    /// Enable or disable the item; ignored for separators and submenus
+    pub fn enabled(mut self, value: impl Into<MaybeSignal<bool>>) -> Self {
+        if let MenuItem::Text { enabled, .. } | MenuItem::Check { enabled, .. } = &mut self {
+            *enabled = value.into();
+        }
+        self
+    }
+
+    /// Keyboard shortcut, e.g. `CmdOrCtrl+S`
+    pub fn accelerator(mut self, value: impl Into<String>) -> Self {
+        if let MenuItem::Text { accelerator, .. } | MenuItem::Check { accelerator, .. } = &mut self {
+            *accelerator = Some(value.into());
+        }
+        self
+    }
+
+    /// Run `f` when the item is clicked
+    pub fn on_click<F: Fn() + Send + Sync + 'static>(mut self, f: F) -> Self {
+        if let MenuItem::Text { handler, .. } | MenuItem::Check { handler, .. } = &mut self {
+            *handler = Some(Arc::new(f));
+        }
+        self
+    }
+
+    /// Give a text item built-in behaviour
+    pub fn action(mut self, value: MenuAction) -> Self {
+        if let MenuItem::Text { action, .. } = &mut self {
+            *action = Some(value);
+        }
+        self
+    }
+}
+
+/// A route listed in the Navigate menu by [`MenuBuilder::from_routes`]
+pub struct RouteItem {
+    path: String,
+    label: MaybeSignal<String>,
+    enabled: MaybeSignal<bool>,
+    accelerator: Option<String>,
+}
+
+impl RouteItem {
+    pub fn new(path: impl Into<String>, label: impl Into<MaybeSignal<String>>) -> Self {
+        RouteItem {
+            path: path.into(),
+            label: label.into(),
+            enabled: MaybeSignal::Static(true),
+            accelerator: None,
+        }
+    }
+
+    pub fn enabled(mut self, enabled: impl Into<MaybeSignal<bool>>) -> Self {
+        self.enabled = enabled.into();
+        self
+    }
+
+    /// Keyboard shortcut; the first nine routes default to `CmdOrCtrl+1`
+    /// through `CmdOrCtrl+9`
+    pub fn accelerator(mut self, accelerator: impl Into<String>) -> Self {
+        self.accelerator = Some(accelerator.into());
+        self
+    }
+}
+
+/// Accelerators following each platform's conventions
+mod accelerators {
+    #[cfg(target_os = "macos")]
+    pub const BACK: &str = "Cmd+[";
+    #[cfg(not(target_os = "macos"))]
+    pub const BACK: &str = "Alt+Left";
+
+    #[cfg(target_os = "macos")]
+    pub const FORWARD: &str = "Cmd+]";
+    #[cfg(not(target_os = "macos"))]
+    pub const FORWARD: &str = "Alt+Right";
+
+    #[cfg(target_os = "macos")]
+    pub const FULLSCREEN: &str = "Ctrl+Cmd+F";
+    #[cfg(not(target_os = "macos"))]
+    pub const FULLSCREEN: &str = "F11";
+
+    pub const RELOAD: &str = "CmdOrCtrl+R";
+    pub const CLOSE_WINDOW: &str = "CmdOrCtrl+W";
+
+    #[cfg(target_os = "windows")]
+    pub const QUIT: &str = "Alt+F4";
+    #[cfg(not(target_os = "windows"))]
+    pub const QUIT: &str = "CmdOrCtrl+Q";
 }
 
 /// Menu builder
@@ -105,7 +325,7 @@ impl MenuBuilder {
         self
     }
 
-    pub fn submenu(mut self, label: impl Into<String>, menu: Menu) -> Self {
+    pub fn submenu(mut self, label: impl Into<MaybeSignal<String>>, menu: Menu) -> Self {
         self.menu.items.push(MenuItem::Submenu {
             label: label.into(),
             menu,
@@ -113,6 +333,101 @@ impl MenuBuilder {
         self
     }
 
+    /// A menu bar with File, Edit, View and Navigate menus; the Navigate
+    /// menu has Back, Forward and an item per route
+    ///
+    /// On macOS an application menu (About, Hide, Quit) comes first, as the
+    /// OS expects.
+    pub fn from_routes<I>(routes: I) -> Self
+    where
+        I: IntoIterator<Item = RouteItem>,
+    {
+        let mut builder = MenuBuilder::new();
+
+        if cfg!(target_os = "macos") {
+            builder = builder.submenu(
+                "App",
+                Menu::new()
+                    .item(MenuItem::predefined(Predefined::About))
+                    .separator()
+                    .item(MenuItem::predefined(Predefined::Services))
+                    .separator()
+                    .item(MenuItem::predefined(Predefined::Hide))
+                    .item(MenuItem::predefined(Predefined::HideOthers))
+                    .item(MenuItem::predefined(Predefined::ShowAll))
+                    .separator()
+                    .item(MenuItem::predefined(Predefined::Quit)),
+            );
+        }
+
+        let mut file = Menu::new().item(
+            MenuItem::text("file.close", "Close Window")
+                .accelerator(accelerators::CLOSE_WINDOW)
+                .action(MenuAction::CloseWindow),
+        );
+        if !cfg!(target_os = "macos") {
+            let quit = if cfg!(target_os = "windows") { "Exit" } else { "Quit" };
+            file = file.separator().item(
+                MenuItem::text("file.quit", quit)
+                    .accelerator(accelerators::QUIT)
+                    .action(MenuAction::Quit),
+            );
+        }
+
+        let edit = Menu::new()
+            .item(MenuItem::predefined(Predefined::Undo))
+            .item(MenuItem::predefined(Predefined::Redo))
+            .separator()
+            .item(MenuItem::predefined(Predefined::Cut))
+            .item(MenuItem::predefined(Predefined::Copy))
+            .item(MenuItem::predefined(Predefined::Paste))
+            .item(MenuItem::predefined(Predefined::SelectAll));
+
+        let view = Menu::new()
+            .item(
+                MenuItem::text("view.reload", "Reload")
+                    .accelerator(accelerators::RELOAD)
+                    .action(MenuAction::Reload),
+            )
+            .separator()
+            .item(
+                MenuItem::text("view.fullscreen", "Toggle Full Screen")
+                    .accelerator(accelerators::FULLSCREEN)
+                    .action(MenuAction::ToggleFullscreen),
+            );
+
+        let mut navigate = Menu::new()
+            .item(
+                MenuItem::text("navigate.back", "Back")
+                    .accelerator(accelerators::BACK)
+                    .action(MenuAction::Back),
+            )
+            .item(
+                MenuItem::text("navigate.forward", "Forward")
+                    .accelerator(accelerators::FORWARD)
+                    .action(MenuAction::Forward),
+            );
+        for (index, route) in routes.into_iter().enumerate() {
+            if index == 0 {
+                navigate = navigate.separator();
+            }
+            let accelerator = route
+                .accelerator
+                .or_else(|| (index < 9).then(|| format!("CmdOrCtrl+{}", index + 1)));
+            let mut item = MenuItem::route(route.path, route.label).enabled(route.enabled);
+            if let Some(accelerator) = accelerator {
+                item = item.accelerator(accelerator);
+            }
+            navigate = navigate.item(item);
+        }
+
+        builder
+            .submenu("File", file)
+            .submenu("Edit", edit)
+            .submenu("View", view)
+            .submenu("Navigate", navigate)
+    }
+
     pub fn build(self) -> Menu {
         self.menu
     }
@@ -123,3 +438,247 @@ impl Default for MenuBuilder {
         Self::new()
     }
 }
+
+// ============================================================================
+// Native Menus
+// ============================================================================
+
+/// A menu created in the OS, with the handlers of its items
+pub struct NativeMenu {
+    pub menu: tauri::menu::Menu<Wry>,
+    pub handlers: MenuHandlers,
+}
+
+/// What clicking an item does
+#[derive(Clone)]
+struct Dispatch {
+    handler: Option<MenuHandler>,
+    toggle: Option<Signal<bool>>,
+    action: Option<MenuAction>,
+}
+
+/// Click handlers by item id
+#[derive(Clone, Default)]
+pub struct MenuHandlers {
+    handlers: Arc<HashMap<String, Dispatch>>,
+}
+
+impl MenuHandlers {
+    /// Handle a click: toggle bound checkmarks, run the item's handler and
+    /// tell the frontend through [`MENU_EVENT`]
+    ///
+    /// Returns false for ids this menu doesn't know.
+    pub fn dispatch(&self, app: &AppHandle<Wry>, id: &str) -> bool {
+        let Some(dispatch) = self.handlers.get(id) else {
+            return false;
+        };
+        if let Some(checked) = &dispatch.toggle {
+            checked.update(|c| *c = !*c);
+        }
+        if let Some(handler) = &dispatch.handler {
+            handler();
+        }
+        if let Some(action) = &dispatch.action {
+            if let Err(e) = run_action(app, action) {
+                tracing::warn!("menu item `{}`: {}", id, e);
+            }
+        }
+        if let Err(e) = app.emit(MENU_EVENT, id) {
+            tracing::warn!("failed to emit menu event `{}`: {}", id, e);
+        }
+        true
+    }
+}
+
+fn run_action(app: &AppHandle<Wry>, action: &MenuAction) -> Result<(), TauriError> {
+    let window = || {
+        app.get_focused_window()
+            .and_then(|window| app.get_webview_window(window.label()))
+    };
+    let window_error = |e: tauri::Error| TauriError::Window(e.to_string());
+
+    match action {
+        MenuAction::Navigate(path) => navigation::navigate(app, path),
+        MenuAction::Quit => {
+            app.exit(0);
+            Ok(())
+        }
+        action => {
+            let Some(window) = window() else {
+                return Ok(());
+            };
+            match action {
+                MenuAction::Back => window.eval("history.back()"),
+                MenuAction::Forward => window.eval("history.forward()"),
+                MenuAction::Reload => window.reload(),
+                MenuAction::ToggleFullscreen => window
+                    .is_fullscreen()
+                    .and_then(|fullscreen| window.set_fullscreen(!fullscreen)),
+                MenuAction::CloseWindow => window.close(),
+                MenuAction::Navigate(_) | MenuAction::Quit => Ok(()),
+            }
+            .map_err(window_error)
+        }
+    }
+}
+
+enum NativeItem {
+    Text(tauri::menu::MenuItem<Wry>),
+    Check(tauri::menu::CheckMenuItem<Wry>),
+    Predefined(tauri::menu::PredefinedMenuItem<Wry>),
+    Submenu(tauri::menu::Submenu<Wry>),
+}
+
+impl NativeItem {
+    fn as_dyn(&self) -> &dyn tauri::menu::IsMenuItem<Wry> {
+        match self {
+            NativeItem::Text(item) => item,
+            NativeItem::Check(item) => item,
+            NativeItem::Predefined(item) => item,
+            NativeItem::Submenu(item) => item,
+        }
+    }
+}
+
+fn menu_error(e: tauri::Error) -> TauriError {
+    TauriError::Runtime(format!("menu: {}", e))
+}
+
+fn predefined(
+    app: &AppHandle<Wry>,
+    item: Predefined,
+) -> Result<tauri::menu::PredefinedMenuItem<Wry>, TauriError> {
+    use tauri::menu::PredefinedMenuItem as P;
+
+    match item {
+        Predefined::Undo => P::undo(app, None),
+        Predefined::Redo => P::redo(app, None),
+        Predefined::Cut => P::cut(app, None),
+        Predefined::Copy => P::copy(app, None),
+        Predefined::Paste => P::paste(app, None),
+        Predefined::SelectAll => P::select_all(app, None),
+        Predefined::Minimize => P::minimize(app, None),
+        Predefined::Maximize => P::maximize(app, None),
+        Predefined::Fullscreen => P::fullscreen(app, None),
+        Predefined::CloseWindow => P::close_window(app, None),
+        Predefined::Hide => P::hide(app, None),
+        Predefined::HideOthers => P::hide_others(app, None),
+        Predefined::ShowAll => P::show_all(app, None),
+        Predefined::Services => P::services(app, None),
+        Predefined::About => P::about(app, None, None),
+        Predefined::Quit => P::quit(app, None),
+    }
+    .map_err(menu_error)
+}
+
+fn build_items(
+    app: &AppHandle<Wry>,
+    items: &[MenuItem],
+    handlers: &mut HashMap<String, Dispatch>,
+) -> Result<Vec<NativeItem>, TauriError> {
+    let mut native = Vec::with_capacity(items.len());
+
+    for item in items {
+        match item {
+            MenuItem::Text {
+                id,
+                label,
+                enabled,
+                accelerator,
+                handler,
+                action,
+            } => {
+                let text = tauri::menu::MenuItem::with_id(
+                    app,
+                    id.as_str(),
+                    label.get(),
+                    enabled.get(),
+                    accelerator.as_deref(),
+                )
+                .map_err(menu_error)?;
+
+                let target = text.clone();
+                label.subscribe(move |label| {
+                    let _ = target.set_text(label);
+                });
+                let target = text.clone();
+                enabled.subscribe(move |enabled| {
+                    let _ = target.set_enabled(*enabled);
+                });
+
+                handlers.insert(
+                    id.clone(),
+                    Dispatch {
+                        handler: handler.clone(),
+                        toggle: None,
+                        action: action.clone(),
+                    },
+                );
+                native.push(NativeItem::Text(text));
+            }
+            MenuItem::Check {
+                id,
+                label,
+                checked,
+                enabled,
+                accelerator,
+                handler,
+            } => {
+                let check = tauri::menu::CheckMenuItem::with_id(
+                    app,
+                    id.as_str(),
+                    label.get(),
+                    enabled.get(),
+                    checked.get(),
+                    accelerator.as_deref(),
+                )
+                .map_err(menu_error)?;
+
+                let target = check.clone();
+                label.subscribe(move |label| {
+                    let _ = target.set_text(label);
+                });
+                let target = check.clone();
+                enabled.subscribe(move |enabled| {
+                    let _ = target.set_enabled(*enabled);
+                });
+                let target = check.clone();
+                checked.subscribe(move |checked| {
+                    let _ = target.set_checked(*checked);
+                });
+
+                handlers.insert(
+                    id.clone(),
+                    Dispatch {
+                        handler: handler.clone(),
+                        toggle: checked.signal().cloned(),
+                        action: None,
+                    },
+                );
+                native.push(NativeItem::Check(check));
+            }
+            MenuItem::Separator => {
+                let separator = tauri::menu::PredefinedMenuItem::separator(app).map_err(menu_error)?;
+                native.push(NativeItem::Predefined(separator));
+            }
+            MenuItem::Predefined(item) => {
+                native.push(NativeItem::Predefined(predefined(app, *item)?));
+            }
+            MenuItem::Submenu { label, menu } => {
+                let children = build_items(app, &menu.items, handlers)?;
+                let refs: Vec<&dyn tauri::menu::IsMenuItem<Wry>> =
+                    children.iter().map(NativeItem::as_dyn).collect();
+                let submenu = tauri::menu::Submenu::with_items(app, label.get(), true, &refs)
+                    .map_err(menu_error)?;
+
+                let target = submenu.clone();
+                label.subscribe(move |label| {
+                    let _ = target.set_text(label);
+                });
+                native.push(NativeItem::Submenu(submenu));
+            }
+        }
+    }
+
+    Ok(native)
+}
@@ -0,0 +1,156 @@
+//! Router navigation driven from the backend
+//!
+//! Shortcuts, deep links and menus move the PhilJS app to a route with
+//! [`navigate`]. The frontend follows once it calls
+//! [`use_tauri_navigation`] near the root of the app:
+//!
+//! ```rust,ignore
+//! // Backend
+//! navigation::navigate(app.handle(), "/settings")?;
+//!
+//! // PhilJS wasm root component
+//! fn App() -> impl IntoView {
+//!     use_tauri_navigation();
+//!     Router::new(routes![("/" => Home), ("/settings" => Settings)])
+//! }
+//! ```
+//!
+//! Navigations requested before the frontend listens (a deep link that
+//! launched the app, say) are queued and delivered once it does.
+
+use serde::{Deserialize, Serialize};
+
+/// Event carrying a [`Navigation`] to the frontend
+pub const NAVIGATE_EVENT: &str = "philjs://navigate";
+
+/// Event the frontend sends once it follows navigations
+pub const READY_EVENT: &str = "philjs://navigate/ready";
+
+/// A route change requested by the backend
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Navigation {
+    pub path: String,
+    /// Replace the current history entry instead of pushing one
+    #[serde(default)]
+    pub replace: bool,
+}
+
+impl Navigation {
+    pub fn push(path: impl Into<String>) -> Self {
+        Navigation {
+            path: path.into(),
+            replace: false,
+        }
+    }
+
+    pub fn replace(path: impl Into<String>) -> Self {
+        Navigation {
+            path: path.into(),
+            replace: true,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use backend::*;
+
+#[cfg(target_arch = "wasm32")]
+pub use frontend::*;
+
+// ============================================================================
+// Backend
+// ============================================================================
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use super::*;
+    use crate::TauriError;
+    use std::sync::Mutex;
+    use tauri::{AppHandle, Emitter, Listener, Wry};
+
+    /// Navigations waiting for the frontend
+    struct Pending {
+        ready: bool,
+        queue: Vec<Navigation>,
+    }
+
+    static PENDING: Mutex<Pending> = Mutex::new(Pending {
+        ready: false,
+        queue: Vec::new(),
+    });
+
+    /// Start delivering queued navigations when the frontend is ready;
+    /// called by [`crate::Builder::run`]
+    pub(crate) fn install(app: &AppHandle<Wry>) {
+        let emitter = app.clone();
+        app.listen_any(READY_EVENT, move |_| {
+            let queued = {
+                let mut pending = PENDING.lock().unwrap();
+                pending.ready = true;
+                std::mem::take(&mut pending.queue)
+            };
+            for navigation in queued {
+                if let Err(e) = emitter.emit(NAVIGATE_EVENT, navigation) {
+                    tracing::warn!("failed to deliver queued navigation: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Push `path` onto the router's history in every window
+    pub fn navigate(app: &AppHandle<Wry>, path: &str) -> Result<(), TauriError> {
+        send(app, Navigation::push(path))
+    }
+
+    /// Replace the current route with `path` in every window
+    pub fn navigate_replace(app: &AppHandle<Wry>, path: &str) -> Result<(), TauriError> {
+        send(app, Navigation::replace(path))
+    }
+
+    /// Deliver a navigation, or queue it until the frontend is ready
+    pub fn send(app: &AppHandle<Wry>, navigation: Navigation) -> Result<(), TauriError> {
+        {
+            let mut pending = PENDING.lock().unwrap();
+            if !pending.ready {
+                pending.queue.push(navigation);
+                return Ok(());
+            }
+        }
+        app.emit(NAVIGATE_EVENT, navigation)
+            .map_err(|e| TauriError::Runtime(format!("navigate: {}", e)))
+    }
+}
+
+// ============================================================================
+// Frontend
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod frontend {
+    use super::*;
+    use crate::client;
+    use philjs::on_cleanup;
+    use philjs::router::use_navigate;
+
+    /// Follow navigations sent by the backend, until the calling component
+    /// is cleaned up
+    pub fn use_tauri_navigation() {
+        let navigator = use_navigate();
+        let listener = client::listen_then(
+            NAVIGATE_EVENT,
+            move |navigation: Navigation| {
+                if navigation.replace {
+                    navigator.replace(&navigation.path);
+                } else {
+                    navigator.push(&navigation.path);
+                }
+            },
+            || {
+                wasm_bindgen_futures::spawn_local(async {
+                    let _ = client::emit(READY_EVENT, &()).await;
+                });
+            },
+        );
+        on_cleanup(move || drop(listener));
+    }
+}
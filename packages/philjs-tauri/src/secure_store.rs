@@ -0,0 +1,396 @@
+//! Secure storage for PhilJS Tauri
+//!
+//! Keeps tokens and other secrets in the OS credential store (macOS and iOS
+//! Keychain, Windows Credential Manager, the Secret Service on Linux)
+//! instead of config files. Requires the `secure-store` feature; Android
+//! has no supported store yet.
+//!
+//! ```rust,ignore
+//! let store = SecureStore::for_app(app.handle())
+//!     .biometric(Biometric::required("Unlock your account"));
+//!
+//! // Move tokens out of the old plaintext settings file, once
+//! store.migrate_json(config_dir.join("settings.json"), &["access_token", "refresh_token"]).await?;
+//!
+//! store.set("access_token", token).await?;
+//! let token = store.get("access_token").await?; // prompts for Face ID / fingerprint
+//! ```
+//!
+//! Keychain calls block, so every operation runs on Tauri's blocking pool.
+
+use crate::commands::{code, ErrorEnvelope};
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Wry};
+
+/// Secure store errors
+#[derive(Debug, thiserror::Error)]
+pub enum SecureStoreError {
+    #[error("Secure storage unavailable: {0}")]
+    Unavailable(String),
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+    #[error("Credential store error: {0}")]
+    Store(String),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl From<keyring::Error> for SecureStoreError {
+    fn from(e: keyring::Error) -> Self {
+        match e {
+            keyring::Error::NoStorageAccess(_) | keyring::Error::PlatformFailure(_) => {
+                SecureStoreError::Unavailable(e.to_string())
+            }
+            e => SecureStoreError::Store(e.to_string()),
+        }
+    }
+}
+
+impl From<SecureStoreError> for ErrorEnvelope {
+    fn from(e: SecureStoreError) -> Self {
+        let code = match &e {
+            SecureStoreError::AuthenticationFailed(_) => code::UNAUTHORIZED,
+            _ => code::INTERNAL,
+        };
+        ErrorEnvelope::new(code, e.to_string())
+    }
+}
+
+/// When reading a secret asks for biometrics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Biometric {
+    /// Never prompt
+    #[default]
+    Off,
+    /// Prompt where biometrics exist, read without them elsewhere
+    IfAvailable { reason: String },
+    /// Refuse to read where biometrics can't be checked
+    Required { reason: String },
+}
+
+impl Biometric {
+    pub fn if_available(reason: impl Into<String>) -> Self {
+        Biometric::IfAvailable { reason: reason.into() }
+    }
+
+    pub fn required(reason: impl Into<String>) -> Self {
+        Biometric::Required { reason: reason.into() }
+    }
+}
+
+/// What [`SecureStore::migrate_json`] moved
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Migration {
+    /// Keys now in the credential store and removed from the file
+    pub migrated: Vec<String>,
+    /// Keys found in the file that weren't strings and were left alone
+    pub skipped: Vec<String>,
+}
+
+impl Migration {
+    pub fn is_empty(&self) -> bool {
+        self.migrated.is_empty()
+    }
+}
+
+/// Secrets for one service name in the OS credential store
+#[derive(Clone)]
+pub struct SecureStore {
+    service: String,
+    biometric: Biometric,
+    app: Option<AppHandle<Wry>>,
+}
+
+impl SecureStore {
+    /// A store for `service`, usually a reverse-DNS app identifier
+    pub fn new(service: impl Into<String>) -> Self {
+        SecureStore {
+            service: service.into(),
+            biometric: Biometric::Off,
+            app: None,
+        }
+    }
+
+    /// A store named after the app's bundle identifier
+    pub fn for_app(app: &AppHandle<Wry>) -> Self {
+        SecureStore {
+            service: app.config().identifier.clone(),
+            biometric: Biometric::Off,
+            app: Some(app.clone()),
+        }
+    }
+
+    /// Ask for biometrics before reading secrets
+    ///
+    /// Prompts on iOS and Android through the biometric plugin, which the
+    /// app must register; desktop platforms count as unavailable.
+    pub fn biometric(mut self, biometric: Biometric) -> Self {
+        self.biometric = biometric;
+        self
+    }
+
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// Store a secret, replacing any previous value
+    pub async fn set(&self, key: &str, secret: impl Into<String>) -> Result<(), SecureStoreError> {
+        let entry = self.entry(key)?;
+        let secret = secret.into();
+        blocking(move || entry.set_password(&secret).map_err(Into::into)).await
+    }
+
+    /// Read a secret; `None` if there isn't one
+    pub async fn get(&self, key: &str) -> Result<Option<String>, SecureStoreError> {
+        self.authenticate().await?;
+        let entry = self.entry(key)?;
+        blocking(move || match entry.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.into()),
+        })
+        .await
+    }
+
+    /// Remove a secret; returns whether there was one
+    pub async fn delete(&self, key: &str) -> Result<bool, SecureStoreError> {
+        let entry = self.entry(key)?;
+        blocking(move || match entry.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(e.into()),
+        })
+        .await
+    }
+
+    /// Store a value as JSON
+    pub async fn set_json<T: Serialize>(&self, key: &str, value: &T) -> Result<(), SecureStoreError> {
+        self.set(key, serde_json::to_string(value)?).await
+    }
+
+    /// Read a value stored with [`SecureStore::set_json`]
+    pub async fn get_json<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, SecureStoreError> {
+        match self.get(key).await? {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Move string values for `keys` from a plaintext JSON config file into
+    /// the credential store, then rewrite the file without them
+    ///
+    /// Safe to call on every start: a missing file or already-migrated keys
+    /// are no-ops. Keys are written to the store before the file changes,
+    /// so an interrupted migration never loses a secret.
+    pub async fn migrate_json(
+        &self,
+        path: impl AsRef<Path>,
+        keys: &[&str],
+    ) -> Result<Migration, SecureStoreError> {
+        let path = path.as_ref().to_path_buf();
+        let mut config = match tokio::fs::read_to_string(&path).await {
+            Ok(text) => serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&text)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Migration::default()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut migration = Migration::default();
+        for key in keys {
+            match config.get(*key) {
+                Some(serde_json::Value::String(secret)) => {
+                    self.set(key, secret.clone()).await?;
+                    migration.migrated.push(key.to_string());
+                }
+                Some(_) => migration.skipped.push(key.to_string()),
+                None => {}
+            }
+        }
+        if migration.is_empty() {
+            return Ok(migration);
+        }
+
+        for key in &migration.migrated {
+            config.remove(key);
+        }
+        write_atomic(&path, serde_json::to_string_pretty(&config)?.as_bytes()).await?;
+        tracing::info!(
+            "moved {} secret(s) from {} to the credential store",
+            migration.migrated.len(),
+            path.display()
+        );
+        Ok(migration)
+    }
+
+    /// Move a file holding a single secret (e.g. `~/.myapp/token`) into the
+    /// store under `key` and delete the file; returns whether it existed
+    pub async fn migrate_file(&self, path: impl AsRef<Path>, key: &str) -> Result<bool, SecureStoreError> {
+        let path = path.as_ref();
+        let secret = match tokio::fs::read_to_string(path).await {
+            Ok(secret) => secret,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        self.set(key, secret.trim_end()).await?;
+        tokio::fs::remove_file(path).await?;
+        Ok(true)
+    }
+
+    fn entry(&self, key: &str) -> Result<keyring::Entry, SecureStoreError> {
+        // keyring would silently fall back to an in-memory store
+        if cfg!(target_os = "android") {
+            return Err(SecureStoreError::Unavailable(
+                "no credential store on Android".to_string(),
+            ));
+        }
+        Ok(keyring::Entry::new(&self.service, key)?)
+    }
+
+    async fn authenticate(&self) -> Result<(), SecureStoreError> {
+        let (reason, required) = match &self.biometric {
+            Biometric::Off => return Ok(()),
+            Biometric::IfAvailable { reason } => (reason, false),
+            Biometric::Required { reason } => (reason, true),
+        };
+
+        #[cfg(any(target_os = "ios", target_os = "android"))]
+        if let Some(app) = &self.app {
+            use tauri_plugin_biometric::BiometricExt;
+
+            let available = app
+                .biometric()
+                .status()
+                .map(|status| status.is_available)
+                .unwrap_or(false);
+            if available {
+                return app
+                    .biometric()
+                    .authenticate(reason.clone(), Default::default())
+                    .map_err(|e| SecureStoreError::AuthenticationFailed(e.to_string()));
+            }
+        }
+
+        let _ = (reason, &self.app);
+        if required {
+            Err(SecureStoreError::Unavailable(
+                "biometric authentication is not available on this device".to_string(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+async fn blocking<T, F>(f: F) -> Result<T, SecureStoreError>
+where
+    F: FnOnce() -> Result<T, SecureStoreError> + Send + 'static,
+    T: Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| SecureStoreError::Store(e.to_string()))?
+}
+
+/// Replace `path` without leaving a half-written file behind
+async fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut tmp = PathBuf::from(path);
+    tmp.set_extension("tmp");
+    tokio::fs::write(&tmp, contents).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A mock credential store and a fresh directory for config files
+    ///
+    /// Mock credentials don't persist between entries, so these tests check
+    /// the files and the reported [`Migration`] rather than reading back.
+    fn setup(name: &str) -> (SecureStore, PathBuf) {
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+        let dir = std::env::temp_dir().join(format!("philjs-secure-store-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        (SecureStore::new("dev.philjs.test"), dir)
+    }
+
+    fn read_json(path: &Path) -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_migrate_json() {
+        let (store, dir) = setup("json");
+        let path = dir.join("settings.json");
+        let settings = json!({"access_token": "abc", "refresh_token": 42, "theme": "dark"});
+        std::fs::write(&path, settings.to_string()).unwrap();
+
+        let migration = store
+            .migrate_json(&path, &["access_token", "refresh_token", "missing"])
+            .await
+            .unwrap();
+        assert_eq!(
+            migration,
+            Migration {
+                migrated: vec!["access_token".to_string()],
+                skipped: vec!["refresh_token".to_string()],
+            }
+        );
+        // Migrated keys leave the file, non-string values stay
+        assert_eq!(read_json(&path), json!({"refresh_token": 42, "theme": "dark"}));
+        assert!(!path.with_extension("tmp").exists());
+
+        // A second run has nothing left to move
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let migration = store
+            .migrate_json(&path, &["access_token", "refresh_token", "missing"])
+            .await
+            .unwrap();
+        assert!(migration.is_empty());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_migrate_missing_files() {
+        let (store, dir) = setup("missing");
+
+        let migration = store.migrate_json(dir.join("settings.json"), &["access_token"]).await.unwrap();
+        assert_eq!(migration, Migration::default());
+        assert!(!store.migrate_file(dir.join("token"), "access_token").await.unwrap());
+        assert!(!dir.join("settings.json").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_migrate_file() {
+        let (store, dir) = setup("file");
+        let path = dir.join("token");
+        std::fs::write(&path, "abc\n").unwrap();
+
+        assert!(store.migrate_file(&path, "access_token").await.unwrap());
+        assert!(!path.exists());
+        assert!(!store.migrate_file(&path, "access_token").await.unwrap());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_write_atomic() {
+        let (_, dir) = setup("atomic");
+        let path = dir.join("settings.json");
+
+        write_atomic(&path, b"{}").await.unwrap();
+        write_atomic(&path, br#"{"theme":"dark"}"#).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), r#"{"theme":"dark"}"#);
+        assert!(!path.with_extension("tmp").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
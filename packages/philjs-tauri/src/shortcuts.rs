@@ -0,0 +1,209 @@
+//! Global keyboard shortcuts for PhilJS Tauri
+//!
+//! Shortcuts fire even when the app isn't focused. Bind them in the
+//! backend, or from a component with [`use_global_shortcut`]. Requires the
+//! `global-shortcut` feature on desktop platforms.
+//!
+//! ```rust,ignore
+//! // Backend
+//! shortcuts::register(app.handle(), "CmdOrCtrl+Shift+Space", show_quick_entry)?;
+//! shortcuts::navigate(app.handle(), "CmdOrCtrl+Shift+I", "/inbox")?;
+//!
+//! // PhilJS wasm component; released when the component is cleaned up
+//! use_global_shortcut("CmdOrCtrl+Shift+R", move || refresh.set(true));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Event sent to the frontend when a shortcut it registered is pressed
+pub const SHORTCUT_EVENT: &str = "philjs://shortcut";
+
+/// Event the frontend sends to claim a shortcut
+pub const REGISTER_EVENT: &str = "philjs://shortcut/register";
+
+/// Event the frontend sends to release a shortcut
+pub const UNREGISTER_EVENT: &str = "philjs://shortcut/unregister";
+
+/// Payload of the shortcut events
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShortcutPayload {
+    pub accelerator: String,
+}
+
+#[cfg(all(
+    not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")),
+    feature = "global-shortcut"
+))]
+pub use backend::*;
+
+#[cfg(target_arch = "wasm32")]
+pub use frontend::*;
+
+// ============================================================================
+// Backend
+// ============================================================================
+
+#[cfg(all(
+    not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")),
+    feature = "global-shortcut"
+))]
+mod backend {
+    use super::*;
+    use crate::navigation;
+    use crate::TauriError;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use tauri::{AppHandle, Emitter, Listener, Wry};
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+    fn shortcut_error(accelerator: &str, e: impl std::fmt::Display) -> TauriError {
+        TauriError::Runtime(format!("shortcut `{}`: {}", accelerator, e))
+    }
+
+    /// Run `handler` whenever `accelerator` (e.g. `"CmdOrCtrl+Shift+K"`) is
+    /// pressed
+    pub fn register<F>(app: &AppHandle<Wry>, accelerator: &str, handler: F) -> Result<(), TauriError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        app.global_shortcut()
+            .on_shortcut(accelerator, move |_, _, event| {
+                if event.state() == ShortcutState::Pressed {
+                    handler();
+                }
+            })
+            .map_err(|e| shortcut_error(accelerator, e))
+    }
+
+    /// Navigate the PhilJS app to `path` whenever `accelerator` is pressed
+    pub fn navigate(app: &AppHandle<Wry>, accelerator: &str, path: &str) -> Result<(), TauriError> {
+        let target = app.clone();
+        let path = path.to_string();
+        register(app, accelerator, move || {
+            if let Err(e) = navigation::navigate(&target, &path) {
+                tracing::warn!("{}", e);
+            }
+        })
+    }
+
+    pub fn is_registered(app: &AppHandle<Wry>, accelerator: &str) -> bool {
+        match accelerator.parse::<tauri_plugin_global_shortcut::Shortcut>() {
+            Ok(shortcut) => app.global_shortcut().is_registered(shortcut),
+            Err(_) => false,
+        }
+    }
+
+    pub fn unregister(app: &AppHandle<Wry>, accelerator: &str) -> Result<(), TauriError> {
+        app.global_shortcut()
+            .unregister(accelerator)
+            .map_err(|e| shortcut_error(accelerator, e))
+    }
+
+    pub fn unregister_all(app: &AppHandle<Wry>) -> Result<(), TauriError> {
+        app.global_shortcut()
+            .unregister_all()
+            .map_err(|e| shortcut_error("*", e))
+    }
+
+    /// How many components hold each frontend shortcut
+    static CLAIMS: Mutex<Option<HashMap<String, usize>>> = Mutex::new(None);
+
+    /// Register shortcuts on behalf of the frontend; called by
+    /// [`crate::Builder::run`]
+    pub(crate) fn install(app: &AppHandle<Wry>) {
+        let handle = app.clone();
+        app.listen_any(REGISTER_EVENT, move |event| {
+            let Ok(ShortcutPayload { accelerator }) = serde_json::from_str(event.payload()) else {
+                return;
+            };
+            let mut claims = CLAIMS.lock().unwrap();
+            let claims = claims.get_or_insert_with(HashMap::new);
+            if let Some(count) = claims.get_mut(&accelerator) {
+                *count += 1;
+                return;
+            }
+            if is_registered(&handle, &accelerator) {
+                tracing::warn!("shortcut `{}` is already bound in the backend", accelerator);
+                return;
+            }
+
+            let emitter = handle.clone();
+            let payload = ShortcutPayload {
+                accelerator: accelerator.clone(),
+            };
+            match register(&handle, &accelerator, move || {
+                let _ = emitter.emit(SHORTCUT_EVENT, payload.clone());
+            }) {
+                Ok(()) => {
+                    claims.insert(accelerator, 1);
+                }
+                Err(e) => tracing::warn!("{}", e),
+            }
+        });
+
+        let handle = app.clone();
+        app.listen_any(UNREGISTER_EVENT, move |event| {
+            let Ok(ShortcutPayload { accelerator }) = serde_json::from_str(event.payload()) else {
+                return;
+            };
+            let mut claims = CLAIMS.lock().unwrap();
+            let Some(claims) = claims.as_mut() else {
+                return;
+            };
+            // Shortcuts the backend owned are never claimed, so never released
+            let Some(count) = claims.get_mut(&accelerator) else {
+                return;
+            };
+            *count -= 1;
+            if *count == 0 {
+                claims.remove(&accelerator);
+                let _ = unregister(&handle, &accelerator);
+            }
+        });
+    }
+}
+
+// ============================================================================
+// Frontend
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod frontend {
+    use super::*;
+    use crate::client;
+    use philjs::on_cleanup;
+
+    fn send(event: &'static str, accelerator: String) {
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = client::emit(event, &ShortcutPayload { accelerator }).await;
+        });
+    }
+
+    /// Call `callback` whenever `accelerator` is pressed, even while the app
+    /// is in the background
+    ///
+    /// The shortcut is released when the calling component is cleaned up.
+    /// Shortcuts already bound in the backend are left to the backend.
+    pub fn use_global_shortcut<F>(accelerator: &str, mut callback: F)
+    where
+        F: FnMut() + 'static,
+    {
+        let name = accelerator.to_string();
+        let claimed = accelerator.to_string();
+        let listener = client::listen_then(
+            SHORTCUT_EVENT,
+            move |payload: ShortcutPayload| {
+                if payload.accelerator == name {
+                    callback();
+                }
+            },
+            move || send(REGISTER_EVENT, claimed),
+        );
+
+        let released = accelerator.to_string();
+        on_cleanup(move || {
+            drop(listener);
+            send(UNREGISTER_EVENT, released);
+        });
+    }
+}
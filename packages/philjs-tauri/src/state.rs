@@ -43,3 +43,147 @@ impl<T: Default + Send + Sync + 'static> Default for ManagedState<T> {
         ManagedState::new(T::default())
     }
 }
+
+// ============================================================================
+// Signals
+// ============================================================================
+
+type Subscriber<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
+/// Reactive value shared between the backend and native UI
+///
+/// Unlike the frontend's signals these are `Send + Sync`, so background
+/// tasks can set them and tray or menu items follow along.
+pub struct Signal<T> {
+    value: Arc<RwLock<T>>,
+    subscribers: Arc<RwLock<Vec<(usize, Subscriber<T>)>>>,
+    next_id: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl<T: Clone> Signal<T> {
+    pub fn new(initial: T) -> Self {
+        Signal {
+            value: Arc::new(RwLock::new(initial)),
+            subscribers: Arc::new(RwLock::new(Vec::new())),
+            next_id: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn get(&self) -> T {
+        self.value.read().unwrap().clone()
+    }
+
+    pub fn set(&self, value: T) {
+        *self.value.write().unwrap() = value.clone();
+        self.notify(&value);
+    }
+
+    pub fn update<F: FnOnce(&mut T)>(&self, f: F) {
+        let value = {
+            let mut guard = self.value.write().unwrap();
+            f(&mut guard);
+            guard.clone()
+        };
+        self.notify(&value);
+    }
+
+    fn notify(&self, value: &T) {
+        // Clone the list so subscribers can subscribe or unsubscribe
+        let subscribers: Vec<Subscriber<T>> = self
+            .subscribers
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(_, f)| Arc::clone(f))
+            .collect();
+        for subscriber in subscribers {
+            subscriber(value);
+        }
+    }
+
+    /// Call `f` with every new value; returns an id for [`Signal::unsubscribe`]
+    pub fn subscribe<F: Fn(&T) + Send + Sync + 'static>(&self, f: F) -> usize {
+        let id = self.next_id.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.subscribers.write().unwrap().push((id, Arc::new(f)));
+        id
+    }
+
+    pub fn unsubscribe(&self, id: usize) {
+        self.subscribers.write().unwrap().retain(|(sub, _)| *sub != id);
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Signal {
+            value: Arc::clone(&self.value),
+            subscribers: Arc::clone(&self.subscribers),
+            next_id: Arc::clone(&self.next_id),
+        }
+    }
+}
+
+impl<T: Clone + Default> Default for Signal<T> {
+    fn default() -> Self {
+        Signal::new(T::default())
+    }
+}
+
+impl<T: Clone + std::fmt::Debug> std::fmt::Debug for Signal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Signal").field(&self.get()).finish()
+    }
+}
+
+/// Create a new signal
+pub fn create_signal<T: Clone>(initial: T) -> Signal<T> {
+    Signal::new(initial)
+}
+
+/// A fixed value or a signal to follow
+#[derive(Clone)]
+pub enum MaybeSignal<T> {
+    Static(T),
+    Dynamic(Signal<T>),
+}
+
+impl<T: Clone> MaybeSignal<T> {
+    pub fn get(&self) -> T {
+        match self {
+            MaybeSignal::Static(value) => value.clone(),
+            MaybeSignal::Dynamic(signal) => signal.get(),
+        }
+    }
+
+    pub fn signal(&self) -> Option<&Signal<T>> {
+        match self {
+            MaybeSignal::Static(_) => None,
+            MaybeSignal::Dynamic(signal) => Some(signal),
+        }
+    }
+
+    /// Call `f` on changes; does nothing for static values
+    pub fn subscribe<F: Fn(&T) + Send + Sync + 'static>(&self, f: F) {
+        if let MaybeSignal::Dynamic(signal) = self {
+            signal.subscribe(f);
+        }
+    }
+}
+
+impl<T> From<T> for MaybeSignal<T> {
+    fn from(value: T) -> Self {
+        MaybeSignal::Static(value)
+    }
+}
+
+impl<T> From<Signal<T>> for MaybeSignal<T> {
+    fn from(signal: Signal<T>) -> Self {
+        MaybeSignal::Dynamic(signal)
+    }
+}
+
+impl From<&str> for MaybeSignal<String> {
+    fn from(value: &str) -> Self {
+        MaybeSignal::Static(value.to_string())
+    }
+}
@@ -0,0 +1,1044 @@
+//! Offline-first server function calls for PhilJS Tauri
+//!
+//! The backend owns a [`SyncQueue`] (requires the `sync` feature). Calls
+//! made while the server is unreachable are written to the app data
+//! directory through the fs plugin and replayed, in order, once it answers
+//! again. Its [`SyncStatus`] is a [`Signal`] mirrored to the frontend, where
+//! [`use_sync_status`] drives badges:
+//!
+//! ```rust,ignore
+//! philjs_tauri::Builder::new()
+//!     .sync(
+//!         SyncConfig::new("https://api.example.com/api/_sf")
+//!             // The server kept a newer edit: keep theirs
+//!             .on_conflict(|call, _server| Resolution::Discard),
+//!     )
+//!     .run()?;
+//!
+//! // PhilJS wasm component
+//! match sync::call::<_, Note>("save_note", &note).await? {
+//!     Synced::Done(saved) => notes.update(|n| n.push(saved)),
+//!     Synced::Queued(_) => toast("Saved offline, will sync later"),
+//! }
+//!
+//! let status = use_sync_status();
+//! view! { <span class="badge">{move || status.get().badge()}</span> }
+//! ```
+//!
+//! Calls are POSTed as JSON to `{endpoint}/{function}`, the same wire
+//! format as `philjs::server` functions. A 409 or 412 response is a
+//! conflict and goes to the conflict hook; other 4xx responses drop the
+//! call and count it as failed.
+
+use crate::commands::ErrorEnvelope;
+use serde::{Deserialize, Serialize};
+
+/// Event carrying the queue's [`SyncStatus`] to the frontend
+pub const STATUS_EVENT: &str = "philjs://sync/status";
+
+/// Event the frontend sends to make a call through the queue
+pub const CALL_EVENT: &str = "philjs://sync/call";
+
+/// Event answering a [`CALL_EVENT`]
+pub const RESULT_EVENT: &str = "philjs://sync/result";
+
+/// Event the frontend sends when the browser goes on- or offline
+pub const CONNECTIVITY_EVENT: &str = "philjs://sync/connectivity";
+
+/// State of the sync queue, for badges and banners
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    /// Whether the server answered the last request or probe
+    pub online: bool,
+    /// Calls waiting to be replayed
+    pub pending: usize,
+    /// A replay is running
+    pub syncing: bool,
+    /// Calls the server rejected since the app started
+    pub failed: usize,
+    pub last_error: Option<String>,
+}
+
+impl SyncStatus {
+    /// The pending count as badge text; `None` when nothing is waiting
+    pub fn badge(&self) -> Option<String> {
+        match self.pending {
+            0 => None,
+            n if n > 99 => Some("99+".to_string()),
+            n => Some(n.to_string()),
+        }
+    }
+}
+
+/// A server function call waiting in the queue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedCall {
+    pub id: String,
+    pub function: String,
+    pub args: serde_json::Value,
+    /// Unix time in milliseconds
+    pub queued_at: u64,
+    /// Replays that ended in a conflict
+    pub attempts: u32,
+}
+
+/// What happened to a call made through the queue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "camelCase")]
+pub enum CallOutcome {
+    /// The server answered
+    Done { value: serde_json::Value },
+    /// The server was unreachable; the call will be replayed
+    Queued { id: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CallRequest {
+    request: u64,
+    function: String,
+    args: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CallResult {
+    request: u64,
+    result: Result<CallOutcome, ErrorEnvelope>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Connectivity {
+    online: bool,
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "sync"))]
+pub use native::*;
+
+#[cfg(target_arch = "wasm32")]
+pub use frontend::*;
+
+// ============================================================================
+// Backend
+// ============================================================================
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "sync"))]
+mod native {
+    use super::*;
+    use crate::commands::code;
+    use crate::state::Signal;
+    use futures::future::BoxFuture;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use tauri::{AppHandle, Emitter, Listener, Manager, Wry};
+    use tauri_plugin_fs::{FsExt, OpenOptions};
+
+    /// Sync errors
+    #[derive(Debug, thiserror::Error)]
+    pub enum SyncError {
+        #[error("Network error: {0}")]
+        Network(String),
+        #[error("Conflict in `{function}`")]
+        Conflict {
+            function: String,
+            server: serde_json::Value,
+        },
+        #[error("Server rejected `{function}` ({status}): {message}")]
+        Rejected {
+            function: String,
+            status: u16,
+            message: String,
+        },
+        #[error("IO error: {0}")]
+        Io(#[from] std::io::Error),
+        #[error("Serialization error: {0}")]
+        Serialization(#[from] serde_json::Error),
+    }
+
+    impl From<SyncError> for ErrorEnvelope {
+        fn from(e: SyncError) -> Self {
+            let envelope = ErrorEnvelope::new(
+                match &e {
+                    SyncError::Conflict { .. } => code::CONFLICT,
+                    SyncError::Rejected { status: 401 | 403, .. } => code::UNAUTHORIZED,
+                    SyncError::Rejected { status: 404, .. } => code::NOT_FOUND,
+                    SyncError::Rejected { status: 400 | 422, .. } => code::INVALID_INPUT,
+                    _ => code::INTERNAL,
+                },
+                e.to_string(),
+            );
+            match e {
+                SyncError::Conflict { server, .. } => envelope.details(server),
+                _ => envelope,
+            }
+        }
+    }
+
+    /// How to settle a replayed call the server reports a conflict for
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Resolution {
+        /// Send again with these arguments, e.g. merged with the server's
+        Retry(serde_json::Value),
+        /// Drop the call
+        Discard,
+        /// Leave it queued and move on; it's tried again on the next replay
+        Keep,
+    }
+
+    /// Decides conflicts; gets the queued call and the server's response body
+    pub type ConflictHook = Arc<dyn Fn(&QueuedCall, &serde_json::Value) -> Resolution + Send + Sync>;
+
+    /// Sync settings
+    #[derive(Clone)]
+    pub struct SyncConfig {
+        /// Base URL; calls go to `{endpoint}/{function}`
+        pub endpoint: String,
+        /// Queue file name in the app data directory
+        pub file: String,
+        /// How often to check whether the server is reachable
+        pub probe_interval: Duration,
+        /// Conflict retries per call before it counts as failed
+        pub max_attempts: u32,
+        pub headers: Vec<(String, String)>,
+        on_conflict: Option<ConflictHook>,
+    }
+
+    impl SyncConfig {
+        pub fn new(endpoint: impl Into<String>) -> Self {
+            SyncConfig {
+                endpoint: endpoint.into().trim_end_matches('/').to_string(),
+                file: "philjs-sync-queue.json".to_string(),
+                probe_interval: Duration::from_secs(30),
+                max_attempts: 3,
+                headers: Vec::new(),
+                on_conflict: None,
+            }
+        }
+
+        pub fn file(mut self, file: impl Into<String>) -> Self {
+            self.file = file.into();
+            self
+        }
+
+        pub fn probe_interval(mut self, interval: Duration) -> Self {
+            self.probe_interval = interval;
+            self
+        }
+
+        pub fn max_attempts(mut self, attempts: u32) -> Self {
+            self.max_attempts = attempts;
+            self
+        }
+
+        /// Send a header with every call, e.g. `Authorization`
+        pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+            self.headers.push((name.into(), value.into()));
+            self
+        }
+
+        /// Settle conflicts with `f`; without a hook conflicting calls are
+        /// discarded
+        pub fn on_conflict<F>(mut self, f: F) -> Self
+        where
+            F: Fn(&QueuedCall, &serde_json::Value) -> Resolution + Send + Sync + 'static,
+        {
+            self.on_conflict = Some(Arc::new(f));
+            self
+        }
+    }
+
+    /// What a replay did
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct ReplayReport {
+        pub sent: usize,
+        pub discarded: usize,
+        pub failed: usize,
+        /// Calls still queued afterwards
+        pub remaining: usize,
+    }
+
+    /// Queues, persists and replays server function calls for one app
+    ///
+    /// Managed by the app; get it with [`get`].
+    #[derive(Clone)]
+    pub struct SyncQueue {
+        backend: Arc<dyn Backend>,
+        config: Arc<SyncConfig>,
+        queue: Arc<tokio::sync::Mutex<Vec<QueuedCall>>>,
+        replaying: Arc<tokio::sync::Mutex<()>>,
+        status: Signal<SyncStatus>,
+    }
+
+    /// The IO behind a [`SyncQueue`]: the server and the queue file
+    trait Backend: Send + Sync {
+        /// POST a call; unreachable servers and 5xx answers are
+        /// [`SyncError::Network`]
+        fn send<'a>(
+            &'a self,
+            function: &'a str,
+            args: &'a serde_json::Value,
+        ) -> BoxFuture<'a, Result<serde_json::Value, SyncError>>;
+
+        /// Whether the server answers at all
+        fn probe(&self) -> BoxFuture<'_, bool>;
+
+        /// Replace the stored queue
+        fn save(&self, queue: &[QueuedCall]) -> Result<(), SyncError>;
+    }
+
+    /// The server over HTTP and the queue file in the app data directory
+    struct TauriBackend {
+        app: AppHandle<Wry>,
+        config: Arc<SyncConfig>,
+        client: reqwest::Client,
+        path: PathBuf,
+    }
+
+    impl TauriBackend {
+        fn load(&self) -> Result<Vec<QueuedCall>, SyncError> {
+            match self.app.fs().read_to_string(self.path.as_path()) {
+                Ok(text) => Ok(serde_json::from_str(&text)?),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+                Err(e) => Err(e.into()),
+            }
+        }
+    }
+
+    impl Backend for TauriBackend {
+        fn send<'a>(
+            &'a self,
+            function: &'a str,
+            args: &'a serde_json::Value,
+        ) -> BoxFuture<'a, Result<serde_json::Value, SyncError>> {
+            Box::pin(async move {
+                let mut request = self
+                    .client
+                    .post(format!("{}/{}", self.config.endpoint, function))
+                    .json(args);
+                for (name, value) in &self.config.headers {
+                    request = request.header(name.as_str(), value.as_str());
+                }
+
+                let response = request.send().await.map_err(|e| SyncError::Network(e.to_string()))?;
+                let status = response.status().as_u16();
+                let body = response.text().await.map_err(|e| SyncError::Network(e.to_string()))?;
+                let value = || serde_json::from_str(&body).unwrap_or(serde_json::Value::String(body.clone()));
+
+                match status {
+                    200..=299 => Ok(value()),
+                    409 | 412 => Err(SyncError::Conflict {
+                        function: function.to_string(),
+                        server: value(),
+                    }),
+                    400..=499 => Err(SyncError::Rejected {
+                        function: function.to_string(),
+                        status,
+                        message: body,
+                    }),
+                    // The server is up but not handling requests; try later
+                    _ => Err(SyncError::Network(format!("HTTP {}", status))),
+                }
+            })
+        }
+
+        fn probe(&self) -> BoxFuture<'_, bool> {
+            Box::pin(async move {
+                self.client
+                    .head(&self.config.endpoint)
+                    .timeout(Duration::from_secs(5))
+                    .send()
+                    .await
+                    .is_ok()
+            })
+        }
+
+        /// Write through the fs plugin, replacing the old file only once
+        /// the new one is complete
+        fn save(&self, queue: &[QueuedCall]) -> Result<(), SyncError> {
+            let json = serde_json::to_vec(queue)?;
+            let tmp = self.path.with_extension("tmp");
+            let mut file = self.app.fs().open(
+                tmp.as_path(),
+                OpenOptions::new().write(true).create(true).truncate(true).clone(),
+            )?;
+            file.write_all(&json)?;
+            file.sync_all()?;
+            std::fs::rename(&tmp, &self.path)?;
+            Ok(())
+        }
+    }
+
+    impl SyncQueue {
+        fn new(app: AppHandle<Wry>, config: SyncConfig) -> Result<Self, SyncError> {
+            let dir = app
+                .path()
+                .app_data_dir()
+                .map_err(|e| SyncError::Io(std::io::Error::other(e.to_string())))?;
+            std::fs::create_dir_all(&dir)?;
+
+            let config = Arc::new(config);
+            let backend = TauriBackend {
+                path: dir.join(&config.file),
+                app,
+                config: config.clone(),
+                client: reqwest::Client::new(),
+            };
+            let queue = backend.load()?;
+            Ok(Self::with_backend(config, Arc::new(backend), queue))
+        }
+
+        fn with_backend(config: Arc<SyncConfig>, backend: Arc<dyn Backend>, queue: Vec<QueuedCall>) -> Self {
+            let status = Signal::new(SyncStatus {
+                pending: queue.len(),
+                ..SyncStatus::default()
+            });
+            SyncQueue {
+                backend,
+                config,
+                queue: Arc::new(tokio::sync::Mutex::new(queue)),
+                replaying: Arc::new(tokio::sync::Mutex::new(())),
+                status,
+            }
+        }
+
+        /// Current status; also sent to the frontend on every change
+        pub fn status(&self) -> Signal<SyncStatus> {
+            self.status.clone()
+        }
+
+        pub fn is_online(&self) -> bool {
+            self.status.get().online
+        }
+
+        /// A copy of the waiting calls, oldest first
+        pub async fn pending(&self) -> Vec<QueuedCall> {
+            self.queue.lock().await.clone()
+        }
+
+        /// Call `function` now, or queue it if the server is unreachable
+        ///
+        /// Calls made while others are waiting are queued behind them, so
+        /// the server sees them in order.
+        pub async fn call(&self, function: &str, args: serde_json::Value) -> Result<CallOutcome, SyncError> {
+            let waiting = !self.queue.lock().await.is_empty();
+            if self.is_online() && !waiting {
+                match self.send(function, &args).await {
+                    Err(SyncError::Network(e)) => {
+                        tracing::info!("`{}` queued: {}", function, e);
+                        self.set_online(false);
+                    }
+                    result => return result.map(|value| CallOutcome::Done { value }),
+                }
+            }
+            let id = self.enqueue(function, args).await?;
+            Ok(CallOutcome::Queued { id })
+        }
+
+        /// Add a call to the queue without trying it first
+        pub async fn enqueue(&self, function: &str, args: serde_json::Value) -> Result<String, SyncError> {
+            static NEXT: AtomicU64 = AtomicU64::new(0);
+
+            let queued_at = now();
+            let call = QueuedCall {
+                id: format!("{}-{}", queued_at, NEXT.fetch_add(1, Ordering::Relaxed)),
+                function: function.to_string(),
+                args,
+                queued_at,
+                attempts: 0,
+            };
+            let id = call.id.clone();
+
+            let mut queue = self.queue.lock().await;
+            queue.push(call);
+            self.persist(&queue)?;
+            Ok(id)
+        }
+
+        /// Remove a waiting call; returns whether it was queued
+        pub async fn discard(&self, id: &str) -> Result<bool, SyncError> {
+            let mut queue = self.queue.lock().await;
+            let before = queue.len();
+            queue.retain(|call| call.id != id);
+            if queue.len() == before {
+                return Ok(false);
+            }
+            self.persist(&queue)?;
+            Ok(true)
+        }
+
+        /// Send waiting calls in order until the queue is empty or the
+        /// server stops answering
+        pub async fn replay(&self) -> Result<ReplayReport, SyncError> {
+            let Ok(_replaying) = self.replaying.try_lock() else {
+                return Ok(ReplayReport::default());
+            };
+            self.status.update(|s| s.syncing = true);
+            let result = self.replay_queue().await;
+            self.status.update(|s| s.syncing = false);
+            result
+        }
+
+        async fn replay_queue(&self) -> Result<ReplayReport, SyncError> {
+            let mut report = ReplayReport::default();
+            let mut index = 0;
+
+            loop {
+                // Not held across the request, so calls can be queued meanwhile
+                let Some(call) = self.queue.lock().await.get(index).cloned() else {
+                    break;
+                };
+
+                let settled = match self.send(&call.function, &call.args).await {
+                    Ok(_) => {
+                        report.sent += 1;
+                        true
+                    }
+                    Err(SyncError::Network(e)) => {
+                        tracing::info!("replay paused: {}", e);
+                        self.set_online(false);
+                        break;
+                    }
+                    Err(SyncError::Conflict { server, .. }) => {
+                        match self.resolve(&call, &server).await? {
+                            Some(true) => {
+                                report.discarded += 1;
+                                true
+                            }
+                            Some(false) => continue,
+                            None => {
+                                index += 1;
+                                continue;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("dropping queued call: {}", e);
+                        report.failed += 1;
+                        self.status.update(|s| {
+                            s.failed += 1;
+                            s.last_error = Some(e.to_string());
+                        });
+                        true
+                    }
+                };
+
+                if settled {
+                    let mut queue = self.queue.lock().await;
+                    queue.retain(|queued| queued.id != call.id);
+                    self.persist(&queue)?;
+                }
+            }
+
+            report.remaining = self.queue.lock().await.len();
+            Ok(report)
+        }
+
+        /// Apply the conflict hook: `Some(true)` drops the call,
+        /// `Some(false)` retries it with new arguments, `None` keeps it
+        async fn resolve(&self, call: &QueuedCall, server: &serde_json::Value) -> Result<Option<bool>, SyncError> {
+            let resolution = match &self.config.on_conflict {
+                Some(hook) => hook(call, server),
+                None => Resolution::Discard,
+            };
+            match resolution {
+                Resolution::Discard => Ok(Some(true)),
+                Resolution::Keep => Ok(None),
+                Resolution::Retry(_) if call.attempts + 1 >= self.config.max_attempts => {
+                    tracing::warn!("`{}` still conflicts after {} attempts", call.function, call.attempts + 1);
+                    self.status.update(|s| {
+                        s.failed += 1;
+                        s.last_error = Some(format!("Conflict in `{}`", call.function));
+                    });
+                    Ok(Some(true))
+                }
+                Resolution::Retry(args) => {
+                    let mut queue = self.queue.lock().await;
+                    if let Some(queued) = queue.iter_mut().find(|queued| queued.id == call.id) {
+                        queued.args = args;
+                        queued.attempts += 1;
+                    }
+                    self.persist(&queue)?;
+                    Ok(Some(false))
+                }
+            }
+        }
+
+        async fn send(&self, function: &str, args: &serde_json::Value) -> Result<serde_json::Value, SyncError> {
+            let result = self.backend.send(function, args).await;
+            if !matches!(result, Err(SyncError::Network(_))) {
+                self.set_online(true);
+            }
+            result
+        }
+
+        /// Whether the server answers at all
+        pub async fn probe(&self) -> bool {
+            let online = self.backend.probe().await;
+            self.set_online(online);
+            online
+        }
+
+        /// Record connectivity; coming back online starts a replay
+        pub fn set_online(&self, online: bool) {
+            let was_online = self.status.get().online;
+            if was_online == online {
+                return;
+            }
+            self.status.update(|s| s.online = online);
+            if online {
+                let sync = self.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = sync.replay().await {
+                        tracing::warn!("replay failed: {}", e);
+                    }
+                });
+            }
+        }
+
+        fn persist(&self, queue: &[QueuedCall]) -> Result<(), SyncError> {
+            self.backend.save(queue)?;
+            let pending = queue.len();
+            self.status.update(|s| s.pending = pending);
+            Ok(())
+        }
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0)
+    }
+
+    /// Set up the queue for `app`; called by [`crate::Builder`]
+    pub fn install(app: &AppHandle<Wry>, config: SyncConfig) -> Result<SyncQueue, SyncError> {
+        let interval = config.probe_interval;
+        let sync = SyncQueue::new(app.clone(), config)?;
+        app.manage(sync.clone());
+        crate::events::share(app, STATUS_EVENT, sync.status());
+
+        let handler = sync.clone();
+        let emitter = app.clone();
+        app.listen_any(CALL_EVENT, move |event| {
+            let request = match serde_json::from_str::<CallRequest>(event.payload()) {
+                Ok(request) => request,
+                Err(e) => {
+                    tracing::warn!("invalid sync call: {}", e);
+                    return;
+                }
+            };
+            let sync = handler.clone();
+            let emitter = emitter.clone();
+            tauri::async_runtime::spawn(async move {
+                let result = sync
+                    .call(&request.function, request.args)
+                    .await
+                    .map_err(ErrorEnvelope::from);
+                let _ = emitter.emit(
+                    RESULT_EVENT,
+                    CallResult {
+                        request: request.request,
+                        result,
+                    },
+                );
+            });
+        });
+
+        let handler = sync.clone();
+        app.listen_any(CONNECTIVITY_EVENT, move |event| {
+            let Ok(Connectivity { online }) = serde_json::from_str(event.payload()) else {
+                return;
+            };
+            if online {
+                // The browser's opinion isn't proof the server is reachable
+                let sync = handler.clone();
+                tauri::async_runtime::spawn(async move {
+                    sync.probe().await;
+                });
+            } else {
+                handler.set_online(false);
+            }
+        });
+
+        let prober = sync.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                prober.probe().await;
+                tokio::time::sleep(interval).await;
+            }
+        });
+
+        Ok(sync)
+    }
+
+    /// The app's sync queue
+    ///
+    /// Panics if the app was built without [`crate::Builder::sync`].
+    pub fn get(app: &AppHandle<Wry>) -> SyncQueue {
+        app.state::<SyncQueue>().inner().clone()
+    }
+
+    /// Call a server function through the app's queue
+    pub async fn call<A: Serialize>(app: &AppHandle<Wry>, function: &str, args: &A) -> Result<CallOutcome, SyncError> {
+        get(app).call(function, serde_json::to_value(args)?).await
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use serde_json::json;
+        use std::collections::{HashMap, VecDeque};
+        use std::sync::Mutex;
+
+        /// Answers calls from a script; unscripted calls succeed
+        #[derive(Default)]
+        struct Scripted {
+            answers: Mutex<HashMap<String, VecDeque<Result<serde_json::Value, SyncError>>>>,
+            sent: Mutex<Vec<String>>,
+            saved: Mutex<Vec<QueuedCall>>,
+        }
+
+        impl Scripted {
+            fn answer(&self, function: &str, result: Result<serde_json::Value, SyncError>) {
+                self.answers
+                    .lock()
+                    .unwrap()
+                    .entry(function.to_string())
+                    .or_default()
+                    .push_back(result);
+            }
+
+            /// Calls sent so far, as `function(args)`
+            fn sent(&self) -> Vec<String> {
+                self.sent.lock().unwrap().clone()
+            }
+
+            fn saved(&self) -> Vec<serde_json::Value> {
+                self.saved.lock().unwrap().iter().map(|call| call.args.clone()).collect()
+            }
+        }
+
+        impl Backend for Scripted {
+            fn send<'a>(
+                &'a self,
+                function: &'a str,
+                args: &'a serde_json::Value,
+            ) -> BoxFuture<'a, Result<serde_json::Value, SyncError>> {
+                self.sent.lock().unwrap().push(format!("{}({})", function, args));
+                let answer = self
+                    .answers
+                    .lock()
+                    .unwrap()
+                    .get_mut(function)
+                    .and_then(|answers| answers.pop_front());
+                Box::pin(async move { answer.unwrap_or(Ok(serde_json::Value::Null)) })
+            }
+
+            fn probe(&self) -> BoxFuture<'_, bool> {
+                Box::pin(async { true })
+            }
+
+            fn save(&self, queue: &[QueuedCall]) -> Result<(), SyncError> {
+                *self.saved.lock().unwrap() = queue.to_vec();
+                Ok(())
+            }
+        }
+
+        fn queue(config: SyncConfig) -> (SyncQueue, Arc<Scripted>) {
+            let backend = Arc::new(Scripted::default());
+            let sync = SyncQueue::with_backend(Arc::new(config), backend.clone(), Vec::new());
+            // Start online, so sends don't kick off a background replay
+            sync.status.update(|s| s.online = true);
+            (sync, backend)
+        }
+
+        fn conflict(function: &str) -> Result<serde_json::Value, SyncError> {
+            Err(SyncError::Conflict {
+                function: function.to_string(),
+                server: json!("newer"),
+            })
+        }
+
+        #[tokio::test]
+        async fn test_offline_calls_flush_in_order() {
+            let (sync, backend) = queue(SyncConfig::new("http://test"));
+            backend.answer("save", Err(SyncError::Network("unreachable".to_string())));
+
+            assert!(matches!(sync.call("save", json!(1)).await.unwrap(), CallOutcome::Queued { .. }));
+            assert!(!sync.is_online());
+
+            // Offline calls are queued without being tried
+            assert!(matches!(sync.call("save", json!(2)).await.unwrap(), CallOutcome::Queued { .. }));
+            sync.enqueue("delete", json!(3)).await.unwrap();
+            assert_eq!(backend.sent(), ["save(1)"]);
+            assert_eq!(backend.saved(), [json!(1), json!(2), json!(3)]);
+            assert_eq!(sync.status().get().pending, 3);
+
+            let report = sync.replay().await.unwrap();
+            assert_eq!(
+                report,
+                ReplayReport {
+                    sent: 3,
+                    ..ReplayReport::default()
+                }
+            );
+            assert_eq!(backend.sent(), ["save(1)", "save(1)", "save(2)", "delete(3)"]);
+            assert!(backend.saved().is_empty());
+            assert!(sync.is_online());
+            assert_eq!(sync.status().get().pending, 0);
+        }
+
+        #[tokio::test]
+        async fn test_calls_wait_behind_queued_ones() {
+            let (sync, backend) = queue(SyncConfig::new("http://test"));
+            let first = sync.enqueue("a", json!(1)).await.unwrap();
+
+            assert!(matches!(sync.call("b", json!(2)).await.unwrap(), CallOutcome::Queued { .. }));
+            assert!(backend.sent().is_empty());
+            let pending = sync.pending().await;
+            assert_eq!(pending[0].id, first);
+            assert_eq!(pending[1].function, "b");
+
+            sync.replay().await.unwrap();
+            assert_eq!(backend.sent(), ["a(1)", "b(2)"]);
+
+            // Nothing waiting, so the next call goes straight out
+            backend.answer("c", Ok(json!("saved")));
+            assert_eq!(
+                sync.call("c", json!(3)).await.unwrap(),
+                CallOutcome::Done { value: json!("saved") }
+            );
+        }
+
+        #[tokio::test]
+        async fn test_replay_pauses_when_server_drops() {
+            let (sync, backend) = queue(SyncConfig::new("http://test"));
+            for n in 1..=3 {
+                sync.enqueue("save", json!(n)).await.unwrap();
+            }
+            backend.answer("save", Ok(json!("ok")));
+            backend.answer("save", Err(SyncError::Network("HTTP 503".to_string())));
+
+            let report = sync.replay().await.unwrap();
+            assert_eq!(
+                report,
+                ReplayReport {
+                    sent: 1,
+                    remaining: 2,
+                    ..ReplayReport::default()
+                }
+            );
+            assert!(!sync.is_online());
+            assert_eq!(backend.saved(), [json!(2), json!(3)]);
+
+            // The failed call is retried first on the next replay
+            let report = sync.replay().await.unwrap();
+            assert_eq!(report.sent, 2);
+            assert_eq!(report.remaining, 0);
+            assert_eq!(backend.sent(), ["save(1)", "save(2)", "save(2)", "save(3)"]);
+        }
+
+        #[tokio::test]
+        async fn test_rejected_calls_are_dropped() {
+            let (sync, backend) = queue(SyncConfig::new("http://test"));
+            sync.enqueue("bad", json!(1)).await.unwrap();
+            sync.enqueue("good", json!(2)).await.unwrap();
+            backend.answer(
+                "bad",
+                Err(SyncError::Rejected {
+                    function: "bad".to_string(),
+                    status: 422,
+                    message: "invalid".to_string(),
+                }),
+            );
+
+            let report = sync.replay().await.unwrap();
+            assert_eq!(
+                report,
+                ReplayReport {
+                    sent: 1,
+                    failed: 1,
+                    ..ReplayReport::default()
+                }
+            );
+            let status = sync.status().get();
+            assert_eq!(status.failed, 1);
+            assert_eq!(status.last_error.as_deref(), Some("Server rejected `bad` (422): invalid"));
+        }
+
+        #[tokio::test]
+        async fn test_conflicts_retry_until_max_attempts() {
+            let config = SyncConfig::new("http://test")
+                .max_attempts(2)
+                .on_conflict(|call, _server| Resolution::Retry(json!(call.attempts + 10)));
+            let (sync, backend) = queue(config);
+            sync.enqueue("edit", json!(1)).await.unwrap();
+            sync.enqueue("stuck", json!(2)).await.unwrap();
+            backend.answer("edit", conflict("edit"));
+            backend.answer("stuck", conflict("stuck"));
+            backend.answer("stuck", conflict("stuck"));
+
+            let report = sync.replay().await.unwrap();
+            assert_eq!(
+                report,
+                ReplayReport {
+                    sent: 1,
+                    discarded: 1,
+                    ..ReplayReport::default()
+                }
+            );
+            assert_eq!(backend.sent(), ["edit(1)", "edit(10)", "stuck(2)", "stuck(10)"]);
+            let status = sync.status().get();
+            assert_eq!(status.failed, 1);
+            assert_eq!(status.last_error.as_deref(), Some("Conflict in `stuck`"));
+        }
+
+        #[tokio::test]
+        async fn test_kept_conflicts_stay_queued() {
+            let config = SyncConfig::new("http://test").on_conflict(|_call, _server| Resolution::Keep);
+            let (sync, backend) = queue(config);
+            sync.enqueue("a", json!(1)).await.unwrap();
+            sync.enqueue("b", json!(2)).await.unwrap();
+            backend.answer("a", conflict("a"));
+
+            let report = sync.replay().await.unwrap();
+            assert_eq!(
+                report,
+                ReplayReport {
+                    sent: 1,
+                    remaining: 1,
+                    ..ReplayReport::default()
+                }
+            );
+            assert_eq!(backend.sent(), ["a(1)", "b(2)"]);
+            assert_eq!(sync.pending().await[0].function, "a");
+
+            // Sent again on the next replay
+            sync.replay().await.unwrap();
+            assert_eq!(backend.sent(), ["a(1)", "b(2)", "a(1)"]);
+            assert!(sync.pending().await.is_empty());
+        }
+    }
+}
+
+// ============================================================================
+// Frontend
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod frontend {
+    use super::*;
+    use crate::client;
+    use crate::commands::code;
+    use futures::channel::oneshot;
+    use philjs::{on_cleanup, Signal};
+    use serde::de::DeserializeOwned;
+    use std::cell::{Cell, RefCell};
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = window, js_name = addEventListener)]
+        fn add_window_listener(event: &str, callback: &js_sys::Function);
+
+        #[wasm_bindgen(js_namespace = window, js_name = removeEventListener)]
+        fn remove_window_listener(event: &str, callback: &js_sys::Function);
+    }
+
+    /// Result of [`call`]
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Synced<T> {
+        /// The server answered
+        Done(T),
+        /// Queued while offline, with the queued call's id
+        Queued(String),
+    }
+
+    impl<T> Synced<T> {
+        pub fn is_queued(&self) -> bool {
+            matches!(self, Synced::Queued(_))
+        }
+    }
+
+    thread_local! {
+        static NEXT_REQUEST: Cell<u64> = const { Cell::new(0) };
+    }
+
+    /// Call a server function through the backend's queue
+    ///
+    /// Resolves with the server's answer, or with [`Synced::Queued`] when
+    /// the call was saved for later.
+    pub async fn call<A, T>(function: &str, args: &A) -> Result<Synced<T>, ErrorEnvelope>
+    where
+        A: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let request = CallRequest {
+            request: NEXT_REQUEST.with(|next| {
+                let id = next.get();
+                next.set(id + 1);
+                id
+            }),
+            function: function.to_string(),
+            args: client::arg("args", args)?,
+        };
+        let id = request.request;
+
+        let (tx, rx) = oneshot::channel();
+        let tx = RefCell::new(Some(tx));
+        let _listener = client::listen_then(
+            RESULT_EVENT,
+            move |result: CallResult| {
+                if result.request == id {
+                    if let Some(tx) = tx.borrow_mut().take() {
+                        let _ = tx.send(result.result);
+                    }
+                }
+            },
+            move || {
+                wasm_bindgen_futures::spawn_local(async move {
+                    let _ = client::emit(CALL_EVENT, &request).await;
+                });
+            },
+        );
+
+        let outcome = rx
+            .await
+            .map_err(|_| ErrorEnvelope::new(code::IPC, "sync call was dropped"))??;
+        match outcome {
+            CallOutcome::Done { value } => serde_json::from_value(value)
+                .map(Synced::Done)
+                .map_err(|e| ErrorEnvelope::new(code::IPC, format!("unexpected result from `{}`: {}", function, e))),
+            CallOutcome::Queued { id } => Ok(Synced::Queued(id)),
+        }
+    }
+
+    /// The sync queue's status, as a signal
+    ///
+    /// Also tells the backend when the browser goes on- or offline, until
+    /// the calling component is cleaned up.
+    pub fn use_sync_status() -> Signal<SyncStatus> {
+        let status = crate::events::use_tauri_event_or(STATUS_EVENT, SyncStatus::default());
+
+        let report = |online: bool| {
+            move || {
+                wasm_bindgen_futures::spawn_local(async move {
+                    let _ = client::emit(CONNECTIVITY_EVENT, &Connectivity { online }).await;
+                });
+            }
+        };
+        let on_online = Closure::<dyn Fn()>::new(report(true));
+        let on_offline = Closure::<dyn Fn()>::new(report(false));
+        add_window_listener("online", on_online.as_ref().unchecked_ref());
+        add_window_listener("offline", on_offline.as_ref().unchecked_ref());
+
+        on_cleanup(move || {
+            remove_window_listener("online", on_online.as_ref().unchecked_ref());
+            remove_window_listener("offline", on_offline.as_ref().unchecked_ref());
+        });
+        status
+    }
+}
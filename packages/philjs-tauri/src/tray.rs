@@ -1,58 +1,173 @@
 //! System tray utilities for PhilJS Tauri
+//!
+//! Tray menus are declared with [`Menu`] and follow their signals once
+//! built. Requires the `tray` feature.
+//!
+//! ```rust,ignore
+//! let unread = create_signal(0);
+//! let label = create_signal("No new mail".to_string());
+//! unread.subscribe({
+//!     let label = label.clone();
+//!     move |n| label.set(format!("{} unread", n))
+//! });
+//!
+//! let tray = TrayBuilder::new("main")
+//!     .tooltip(label.clone())
+//!     .menu(
+//!         Menu::new()
+//!             .item(MenuItem::text("unread", label).enabled(false))
+//!             .separator()
+//!             .item(MenuItem::text("quit", "Quit").on_click(|| std::process::exit(0))),
+//!     )
+//!     .on_click(show_main_window)
+//!     .build(app.handle())?;
+//! ```
 
+use crate::menu::{Menu, MenuHandler};
+use crate::state::MaybeSignal;
 use crate::TauriError;
-use crate::menu::Menu;
+use std::sync::Arc;
+use tauri::{AppHandle, Wry};
 
-/// System tray icon
+/// A tray icon shown by the OS
+///
+/// Dropping the handle keeps the icon; use [`TrayIcon::remove`].
 pub struct TrayIcon {
     id: String,
-    tooltip: Option<String>,
-    menu: Option<Menu>,
+    #[cfg(feature = "tray")]
+    inner: tauri::tray::TrayIcon<Wry>,
 }
 
 impl TrayIcon {
-    pub fn new(id: impl Into<String>) -> Self {
-        TrayIcon {
-            id: id.into(),
-            tooltip: None,
-            menu: None,
-        }
+    pub fn id(&self) -> &str {
+        &self.id
     }
 
-    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
-        self.tooltip = Some(tooltip.into());
-        self
+    pub fn set_visible(&self, visible: bool) -> Result<(), TauriError> {
+        #[cfg(feature = "tray")]
+        self.inner.set_visible(visible).map_err(tray_error)?;
+        #[cfg(not(feature = "tray"))]
+        let _ = visible;
+        Ok(())
     }
 
-    pub fn menu(mut self, menu: Menu) -> Self {
-        self.menu = Some(menu);
-        self
+    pub fn set_tooltip(&self, tooltip: &str) -> Result<(), TauriError> {
+        #[cfg(feature = "tray")]
+        self.inner.set_tooltip(Some(tooltip)).map_err(tray_error)?;
+        #[cfg(not(feature = "tray"))]
+        let _ = tooltip;
+        Ok(())
+    }
+
+    /// Remove the icon from the tray
+    pub fn remove(self, app: &AppHandle<Wry>) {
+        #[cfg(feature = "tray")]
+        app.remove_tray_by_id(self.id.as_str());
+        #[cfg(not(feature = "tray"))]
+        let _ = app;
     }
 }
 
 /// Tray icon builder
 pub struct TrayBuilder {
-    icon: TrayIcon,
+    id: String,
+    tooltip: Option<MaybeSignal<String>>,
+    menu: Option<Menu>,
+    icon: Option<tauri::image::Image<'static>>,
+    on_click: Option<MenuHandler>,
 }
 
 impl TrayBuilder {
     pub fn new(id: impl Into<String>) -> Self {
         TrayBuilder {
-            icon: TrayIcon::new(id),
+            id: id.into(),
+            tooltip: None,
+            menu: None,
+            icon: None,
+            on_click: None,
         }
     }
 
-    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
-        self.icon.tooltip = Some(tooltip.into());
+    pub fn tooltip(mut self, tooltip: impl Into<MaybeSignal<String>>) -> Self {
+        self.tooltip = Some(tooltip.into());
         self
     }
 
     pub fn menu(mut self, menu: Menu) -> Self {
-        self.icon.menu = Some(menu);
+        self.menu = Some(menu);
         self
     }
 
-    pub fn build(self) -> Result<TrayIcon, TauriError> {
-        Ok(self.icon)
+    /// Icon image; defaults to the app's window icon
+    pub fn icon(mut self, icon: tauri::image::Image<'static>) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Run `f` when the icon itself is left-clicked
+    pub fn on_click<F: Fn() + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.on_click = Some(Arc::new(f));
+        self
+    }
+
+    #[cfg(feature = "tray")]
+    pub fn build(self, app: &AppHandle<Wry>) -> Result<TrayIcon, TauriError> {
+        use tauri::tray::{MouseButton, MouseButtonState, TrayIconEvent};
+
+        let mut builder = tauri::tray::TrayIconBuilder::with_id(self.id.as_str());
+
+        if let Some(icon) = self.icon {
+            builder = builder.icon(icon);
+        } else if let Some(icon) = app.default_window_icon() {
+            builder = builder.icon(icon.clone());
+        }
+        if let Some(tooltip) = &self.tooltip {
+            builder = builder.tooltip(tooltip.get());
+        }
+        if let Some(menu) = &self.menu {
+            let native = menu.build(app)?;
+            let handlers = native.handlers.clone();
+            builder = builder
+                .menu(&native.menu)
+                .show_menu_on_left_click(self.on_click.is_none())
+                .on_menu_event(move |app, event| {
+                    handlers.dispatch(app, event.id().as_ref());
+                });
+        }
+        if let Some(on_click) = self.on_click {
+            builder = builder.on_tray_icon_event(move |_, event| {
+                if let TrayIconEvent::Click {
+                    button: MouseButton::Left,
+                    button_state: MouseButtonState::Up,
+                    ..
+                } = event
+                {
+                    on_click();
+                }
+            });
+        }
+
+        let inner = builder.build(app).map_err(tray_error)?;
+
+        if let Some(tooltip) = &self.tooltip {
+            let target = inner.clone();
+            tooltip.subscribe(move |tooltip| {
+                let _ = target.set_tooltip(Some(tooltip.as_str()));
+            });
+        }
+
+        Ok(TrayIcon { id: self.id, inner })
+    }
+
+    #[cfg(not(feature = "tray"))]
+    pub fn build(self, _app: &AppHandle<Wry>) -> Result<TrayIcon, TauriError> {
+        Err(TauriError::Runtime(
+            "system tray support requires the `tray` feature".to_string(),
+        ))
     }
 }
+
+#[cfg(feature = "tray")]
+fn tray_error(e: tauri::Error) -> TauriError {
+    TauriError::Runtime(format!("tray: {}", e))
+}
@@ -1,25 +1,1040 @@
 //! Auto-updater utilities for PhilJS Tauri
+//!
+//! The backend owns an [`Updater`] (requires the `updater` feature) that
+//! checks a release channel, downloads and verifies signed updates, and
+//! installs them when the app quits. Its status is a [`Signal`] and is
+//! mirrored to the frontend, where [`UpdateBanner`] renders it:
+//!
+//! ```rust,ignore
+//! philjs_tauri::Builder::new()
+//!     .updater(
+//!         UpdaterConfig::new(include_str!("../updater.pub"))
+//!             .endpoint("https://releases.example.com/{{channel}}/{{target}}/{{arch}}/{{current_version}}")
+//!             .channel(Channel::Beta),
+//!     )
+//!     .run()?;
+//!
+//! // Anywhere in the backend
+//! let updater = updater::get(&app);
+//! if updater.check().await?.is_some() {
+//!     updater.download().await?; // installs on quit
+//! }
+//! ```
+//!
+//! Signatures are checked with the minisign public key from the config
+//! before an update is kept; a bad signature leaves the app untouched.
 
-use crate::TauriError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
-/// Check for updates
-pub async fn check() -> Result<Option<Update>, TauriError> {
-    // Would use tauri_plugin_updater
-    Ok(None)
+/// Event carrying the updater's [`UpdateStatus`] to the frontend
+pub const STATUS_EVENT: &str = "philjs://updater/status";
+
+/// Event the frontend sends with an [`UpdateAction`]
+pub const ACTION_EVENT: &str = "philjs://updater/action";
+
+/// Release channel to follow
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Channel {
+    #[default]
+    Stable,
+    Beta,
+    Custom(String),
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Channel::Stable => f.write_str("stable"),
+            Channel::Beta => f.write_str("beta"),
+            Channel::Custom(name) => f.write_str(name),
+        }
+    }
 }
 
 /// Update information
-pub struct Update {
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
     pub version: String,
     pub current_version: String,
     pub date: Option<String>,
+    /// Release notes
     pub body: Option<String>,
+    pub channel: Channel,
+}
+
+/// Where the updater is
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum UpdateStatus {
+    #[default]
+    Idle,
+    Checking,
+    UpToDate,
+    Available(UpdateInfo),
+    #[serde(rename_all = "camelCase")]
+    Downloading {
+        version: String,
+        downloaded: u64,
+        total: Option<u64>,
+    },
+    /// Downloaded and verified; installs when the app quits
+    Ready(UpdateInfo),
+    Installing,
+    #[serde(rename_all = "camelCase")]
+    Failed {
+        message: String,
+        /// The download didn't match its signature
+        signature: bool,
+    },
+}
+
+impl UpdateStatus {
+    /// Download progress from 0.0 to 1.0, if the size is known
+    pub fn progress(&self) -> Option<f64> {
+        match self {
+            UpdateStatus::Downloading {
+                downloaded,
+                total: Some(total),
+                ..
+            } if *total > 0 => Some((*downloaded as f64 / *total as f64).min(1.0)),
+            UpdateStatus::Ready(_) => Some(1.0),
+            _ => None,
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        matches!(
+            self,
+            UpdateStatus::Checking | UpdateStatus::Downloading { .. } | UpdateStatus::Installing
+        )
+    }
+}
+
+/// Requests from the frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UpdateAction {
+    /// Re-send the current status
+    Refresh,
+    Check,
+    Download,
+    /// Install the downloaded update and restart now instead of on quit
+    Restart,
 }
 
-impl Update {
-    /// Download and install the update
-    pub async fn download_and_install(self) -> Result<(), TauriError> {
-        // Would use tauri_plugin_updater
-        Ok(())
+#[cfg(all(not(target_arch = "wasm32"), feature = "updater"))]
+pub use native::*;
+
+#[cfg(target_arch = "wasm32")]
+pub use banner::UpdateBanner;
+
+// ============================================================================
+// Backend
+// ============================================================================
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "updater"))]
+mod native {
+    use super::*;
+    use crate::state::Signal;
+    use crate::TauriError;
+    use futures::future::BoxFuture;
+    use std::sync::{Arc, Mutex};
+    use tauri::{AppHandle, Emitter, Listener, Manager, Wry};
+    use tauri_plugin_updater::UpdaterExt;
+
+    /// Updater settings
+    #[derive(Debug, Clone)]
+    pub struct UpdaterConfig {
+        /// Minisign public key the releases are signed with
+        pub pubkey: String,
+        /// Endpoint templates; `{{channel}}` is replaced with the channel,
+        /// `{{target}}`, `{{arch}}` and `{{current_version}}` by Tauri
+        pub endpoints: Vec<String>,
+        pub channel: Channel,
+        /// Check once the app has started
+        pub check_on_startup: bool,
+    }
+
+    impl UpdaterConfig {
+        pub fn new(pubkey: impl Into<String>) -> Self {
+            UpdaterConfig {
+                pubkey: pubkey.into(),
+                endpoints: Vec::new(),
+                channel: Channel::Stable,
+                check_on_startup: true,
+            }
+        }
+
+        pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+            self.endpoints.push(endpoint.into());
+            self
+        }
+
+        pub fn channel(mut self, channel: Channel) -> Self {
+            self.channel = channel;
+            self
+        }
+
+        pub fn check_on_startup(mut self, check: bool) -> Self {
+            self.check_on_startup = check;
+            self
+        }
+
+        fn urls(&self, channel: &Channel) -> Result<Vec<tauri::Url>, TauriError> {
+            self.endpoints
+                .iter()
+                .map(|endpoint| {
+                    let url = endpoint.replace("{{channel}}", &channel.to_string());
+                    tauri::Url::parse(&url).map_err(|e| TauriError::Runtime(format!("invalid update endpoint `{}`: {}", url, e)))
+                })
+                .collect()
+        }
+    }
+
+    /// Why a download was thrown away
+    #[derive(Debug)]
+    enum DownloadError {
+        /// The download didn't match its signature
+        Signature(TauriError),
+        Failed(TauriError),
+    }
+
+    /// A release that can be downloaded and installed
+    trait Release: Send + Sync + fmt::Debug {
+        /// Download and verify the release, reporting each chunk's size
+        /// and the total size
+        fn download<'a>(
+            &'a self,
+            on_chunk: &'a mut (dyn FnMut(usize, Option<u64>) + Send),
+        ) -> BoxFuture<'a, Result<Vec<u8>, DownloadError>>;
+
+        fn install(&self, bytes: &[u8]) -> Result<(), TauriError>;
+    }
+
+    impl Release for tauri_plugin_updater::Update {
+        fn download<'a>(
+            &'a self,
+            on_chunk: &'a mut (dyn FnMut(usize, Option<u64>) + Send),
+        ) -> BoxFuture<'a, Result<Vec<u8>, DownloadError>> {
+            Box::pin(async move {
+                tauri_plugin_updater::Update::download(self, on_chunk, || {})
+                    .await
+                    .map_err(|e| match e {
+                        tauri_plugin_updater::Error::Minisign(_) => DownloadError::Signature(updater_error(e)),
+                        e => DownloadError::Failed(updater_error(e)),
+                    })
+            })
+        }
+
+        fn install(&self, bytes: &[u8]) -> Result<(), TauriError> {
+            tauri_plugin_updater::Update::install(self, bytes).map_err(updater_error)
+        }
+    }
+
+    /// The IO behind an [`Updater`]: the release server, the frontend and
+    /// the app process
+    trait Backend: Send + Sync {
+        /// Ask `urls` for a release newer than the running app
+        fn check(&self, channel: Channel, urls: Vec<tauri::Url>) -> BoxFuture<'_, Result<Option<Update>, TauriError>>;
+
+        /// Send the status to the frontend
+        fn emit(&self, status: &UpdateStatus);
+
+        fn restart(&self);
+    }
+
+    /// The Tauri updater plugin and the app's windows
+    struct TauriBackend {
+        app: AppHandle<Wry>,
+        config: Arc<UpdaterConfig>,
+    }
+
+    impl Backend for TauriBackend {
+        fn check(&self, channel: Channel, urls: Vec<tauri::Url>) -> BoxFuture<'_, Result<Option<Update>, TauriError>> {
+            Box::pin(async move {
+                let updater = self
+                    .app
+                    .updater_builder()
+                    .pubkey(self.config.pubkey.clone())
+                    .endpoints(urls)
+                    .map_err(updater_error)?
+                    .build()
+                    .map_err(updater_error)?;
+
+                let update = updater.check().await.map_err(updater_error)?;
+                Ok(update.map(|inner| Update {
+                    info: UpdateInfo {
+                        version: inner.version.clone(),
+                        current_version: inner.current_version.clone(),
+                        date: inner.date.map(|date| date.to_string()),
+                        body: inner.body.clone(),
+                        channel,
+                    },
+                    inner: Arc::new(inner),
+                }))
+            })
+        }
+
+        fn emit(&self, status: &UpdateStatus) {
+            let _ = self.app.emit(STATUS_EVENT, status);
+        }
+
+        fn restart(&self) {
+            self.app.restart()
+        }
+    }
+
+    /// A verified download waiting for the app to quit
+    struct Pending {
+        update: Update,
+        bytes: Vec<u8>,
+    }
+
+    /// An update found by [`Updater::check`]
+    #[derive(Debug, Clone)]
+    pub struct Update {
+        pub info: UpdateInfo,
+        inner: Arc<dyn Release>,
+    }
+
+    /// Checks, downloads and installs updates for one app
+    ///
+    /// Managed by the app; get it with [`get`].
+    #[derive(Clone)]
+    pub struct Updater {
+        backend: Arc<dyn Backend>,
+        config: Arc<UpdaterConfig>,
+        channel: Signal<Channel>,
+        status: Signal<UpdateStatus>,
+        available: Arc<Mutex<Option<Update>>>,
+        pending: Arc<Mutex<Option<Pending>>>,
+    }
+
+    impl Updater {
+        fn new(app: AppHandle<Wry>, config: UpdaterConfig) -> Self {
+            let config = Arc::new(config);
+            let backend = TauriBackend {
+                app,
+                config: config.clone(),
+            };
+            Self::with_backend(config, Arc::new(backend))
+        }
+
+        fn with_backend(config: Arc<UpdaterConfig>, backend: Arc<dyn Backend>) -> Self {
+            let status = Signal::new(UpdateStatus::Idle);
+            let emitter = backend.clone();
+            status.subscribe(move |status| emitter.emit(status));
+            Updater {
+                backend,
+                channel: Signal::new(config.channel.clone()),
+                config,
+                status,
+                available: Arc::new(Mutex::new(None)),
+                pending: Arc::new(Mutex::new(None)),
+            }
+        }
+
+        /// Current status; also sent to the frontend on every change
+        pub fn status(&self) -> Signal<UpdateStatus> {
+            self.status.clone()
+        }
+
+        pub fn channel(&self) -> Signal<Channel> {
+            self.channel.clone()
+        }
+
+        /// Follow another channel from the next check
+        pub fn set_channel(&self, channel: Channel) {
+            self.channel.set(channel);
+        }
+
+        /// Ask the channel's endpoints for a newer version
+        pub async fn check(&self) -> Result<Option<Update>, TauriError> {
+            self.status.set(UpdateStatus::Checking);
+            match self.try_check().await {
+                Ok(update) => {
+                    self.status.set(match &update {
+                        Some(update) => UpdateStatus::Available(update.info.clone()),
+                        None => UpdateStatus::UpToDate,
+                    });
+                    *self.available.lock().unwrap() = update.clone();
+                    Ok(update)
+                }
+                Err(e) => Err(self.fail(e, false)),
+            }
+        }
+
+        async fn try_check(&self) -> Result<Option<Update>, TauriError> {
+            let channel = self.channel.get();
+            let urls = self.config.urls(&channel)?;
+            self.backend.check(channel, urls).await
+        }
+
+        /// Download and verify the available update, then keep it to
+        /// install on quit
+        ///
+        /// Checks first if nothing is known to be available.
+        pub async fn download(&self) -> Result<bool, TauriError> {
+            let available = self.available.lock().unwrap().clone();
+            let update = match available {
+                Some(update) => update,
+                None => match self.check().await? {
+                    Some(update) => update,
+                    None => return Ok(false),
+                },
+            };
+
+            let version = update.info.version.clone();
+            let mut downloaded = 0u64;
+            let status = self.status.clone();
+            status.set(UpdateStatus::Downloading {
+                version: version.clone(),
+                downloaded,
+                total: None,
+            });
+
+            let mut on_chunk = |chunk: usize, total: Option<u64>| {
+                downloaded += chunk as u64;
+                status.set(UpdateStatus::Downloading {
+                    version: version.clone(),
+                    downloaded,
+                    total,
+                });
+            };
+            let result = update.inner.download(&mut on_chunk).await;
+
+            match result {
+                Ok(bytes) => {
+                    let info = update.info.clone();
+                    *self.pending.lock().unwrap() = Some(Pending { update, bytes });
+                    self.status.set(UpdateStatus::Ready(info));
+                    Ok(true)
+                }
+                Err(DownloadError::Signature(e)) => Err(self.fail(e, true)),
+                Err(DownloadError::Failed(e)) => Err(self.fail(e, false)),
+            }
+        }
+
+        /// Whether a verified update is waiting for the app to quit
+        pub fn is_pending(&self) -> bool {
+            self.pending.lock().unwrap().is_some()
+        }
+
+        /// Install the downloaded update now and restart
+        pub fn restart(&self) -> Result<(), TauriError> {
+            self.install_pending()?;
+            self.backend.restart();
+            Ok(())
+        }
+
+        /// Install the downloaded update, if any
+        ///
+        /// Called when the app exits; on Windows the installer takes over
+        /// and the process ends here.
+        pub fn install_pending(&self) -> Result<(), TauriError> {
+            let Some(pending) = self.pending.lock().unwrap().take() else {
+                return Ok(());
+            };
+            self.status.set(UpdateStatus::Installing);
+            pending
+                .update
+                .inner
+                .install(&pending.bytes)
+                .map_err(|e| self.fail(e, false))
+        }
+
+        fn handle(&self, action: UpdateAction) {
+            let updater = self.clone();
+            match action {
+                UpdateAction::Refresh => self.backend.emit(&self.status.get()),
+                UpdateAction::Check => {
+                    tauri::async_runtime::spawn(async move {
+                        let _ = updater.check().await;
+                    });
+                }
+                UpdateAction::Download => {
+                    tauri::async_runtime::spawn(async move {
+                        let _ = updater.download().await;
+                    });
+                }
+                UpdateAction::Restart => {
+                    if let Err(e) = updater.restart() {
+                        tracing::error!("failed to install update: {}", e);
+                    }
+                }
+            }
+        }
+
+        fn fail(&self, error: TauriError, signature: bool) -> TauriError {
+            tracing::warn!("updater: {}", error);
+            self.status.set(UpdateStatus::Failed {
+                message: error.to_string(),
+                signature,
+            });
+            error
+        }
+    }
+
+    fn updater_error(e: tauri_plugin_updater::Error) -> TauriError {
+        TauriError::Runtime(format!("updater: {}", e))
+    }
+
+    /// Set up the updater for `app`; called by [`crate::Builder`]
+    pub fn install(app: &AppHandle<Wry>, config: UpdaterConfig) -> Updater {
+        let check_on_startup = config.check_on_startup;
+        let updater = Updater::new(app.clone(), config);
+        app.manage(updater.clone());
+
+        let listener = updater.clone();
+        app.listen_any(ACTION_EVENT, move |event| {
+            match serde_json::from_str::<UpdateAction>(event.payload()) {
+                Ok(action) => listener.handle(action),
+                Err(e) => tracing::warn!("invalid updater action: {}", e),
+            }
+        });
+
+        if check_on_startup {
+            updater.handle(UpdateAction::Check);
+        }
+        updater
+    }
+
+    /// The app's updater
+    ///
+    /// Panics if the app was built without [`crate::Builder::updater`].
+    pub fn get(app: &AppHandle<Wry>) -> Updater {
+        app.state::<Updater>().inner().clone()
+    }
+
+    /// Check for updates
+    pub async fn check(app: &AppHandle<Wry>) -> Result<Option<Update>, TauriError> {
+        get(app).check().await
+    }
+
+    impl Update {
+        /// Download and install the update right away
+        pub async fn download_and_install(self) -> Result<(), TauriError> {
+            let bytes = self
+                .inner
+                .download(&mut |_, _| {})
+                .await
+                .map_err(|(DownloadError::Signature(e) | DownloadError::Failed(e))| e)?;
+            self.inner.install(&bytes)
+        }
+    }
+
+    /// Install a pending update as the app exits
+    pub(crate) fn on_exit(app: &AppHandle<Wry>) {
+        if let Some(updater) = app.try_state::<Updater>() {
+            if let Err(e) = updater.install_pending() {
+                tracing::error!("failed to install update on exit: {}", e);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::collections::VecDeque;
+
+        /// A release served from a script
+        #[derive(Debug, Default)]
+        struct ScriptedRelease {
+            chunks: Vec<usize>,
+            total: Option<u64>,
+            /// Fails the download once its chunks arrived
+            fail: Option<fn(TauriError) -> DownloadError>,
+            install_fails: bool,
+            installed: Mutex<Vec<Vec<u8>>>,
+        }
+
+        impl Release for ScriptedRelease {
+            fn download<'a>(
+                &'a self,
+                on_chunk: &'a mut (dyn FnMut(usize, Option<u64>) + Send),
+            ) -> BoxFuture<'a, Result<Vec<u8>, DownloadError>> {
+                for chunk in &self.chunks {
+                    on_chunk(*chunk, self.total);
+                }
+                let result = match self.fail {
+                    Some(fail) => Err(fail(TauriError::Runtime("rejected".to_string()))),
+                    None => Ok(vec![7; self.chunks.iter().sum()]),
+                };
+                Box::pin(async move { result })
+            }
+
+            fn install(&self, bytes: &[u8]) -> Result<(), TauriError> {
+                if self.install_fails {
+                    return Err(TauriError::Runtime("disk full".to_string()));
+                }
+                self.installed.lock().unwrap().push(bytes.to_vec());
+                Ok(())
+            }
+        }
+
+        /// Answers checks from a script; unscripted checks find nothing
+        #[derive(Default)]
+        struct Scripted {
+            answers: Mutex<VecDeque<Result<Option<Arc<ScriptedRelease>>, String>>>,
+            checked: Mutex<Vec<Vec<String>>>,
+            emitted: Mutex<Vec<UpdateStatus>>,
+            restarts: Mutex<usize>,
+        }
+
+        impl Scripted {
+            fn answer(&self, answer: Result<Option<Arc<ScriptedRelease>>, String>) {
+                self.answers.lock().unwrap().push_back(answer);
+            }
+
+            /// URLs asked by each check
+            fn checked(&self) -> Vec<Vec<String>> {
+                self.checked.lock().unwrap().clone()
+            }
+
+            /// Statuses sent to the frontend since the last call
+            fn emitted(&self) -> Vec<UpdateStatus> {
+                std::mem::take(&mut *self.emitted.lock().unwrap())
+            }
+
+            fn restarts(&self) -> usize {
+                *self.restarts.lock().unwrap()
+            }
+        }
+
+        impl Backend for Scripted {
+            fn check(&self, channel: Channel, urls: Vec<tauri::Url>) -> BoxFuture<'_, Result<Option<Update>, TauriError>> {
+                self.checked
+                    .lock()
+                    .unwrap()
+                    .push(urls.iter().map(ToString::to_string).collect());
+                let answer = self.answers.lock().unwrap().pop_front().unwrap_or(Ok(None));
+                let result = match answer {
+                    Ok(release) => Ok(release.map(|release| Update {
+                        info: info(channel),
+                        inner: release,
+                    })),
+                    Err(message) => Err(TauriError::Runtime(message)),
+                };
+                Box::pin(async move { result })
+            }
+
+            fn emit(&self, status: &UpdateStatus) {
+                self.emitted.lock().unwrap().push(status.clone());
+            }
+
+            fn restart(&self) {
+                *self.restarts.lock().unwrap() += 1;
+            }
+        }
+
+        fn info(channel: Channel) -> UpdateInfo {
+            UpdateInfo {
+                version: "2.0.0".to_string(),
+                current_version: "1.0.0".to_string(),
+                date: None,
+                body: Some("Fixes".to_string()),
+                channel,
+            }
+        }
+
+        fn release(chunks: &[usize], total: Option<u64>) -> Arc<ScriptedRelease> {
+            Arc::new(ScriptedRelease {
+                chunks: chunks.to_vec(),
+                total,
+                ..ScriptedRelease::default()
+            })
+        }
+
+        fn downloading(downloaded: u64, total: Option<u64>) -> UpdateStatus {
+            UpdateStatus::Downloading {
+                version: "2.0.0".to_string(),
+                downloaded,
+                total,
+            }
+        }
+
+        fn failed(message: &str, signature: bool) -> UpdateStatus {
+            UpdateStatus::Failed {
+                message: message.to_string(),
+                signature,
+            }
+        }
+
+        fn scripted(config: UpdaterConfig) -> (Updater, Arc<Scripted>) {
+            let backend = Arc::new(Scripted::default());
+            let updater = Updater::with_backend(Arc::new(config), backend.clone());
+            (updater, backend)
+        }
+
+        fn config() -> UpdaterConfig {
+            UpdaterConfig::new("pubkey").endpoint("https://releases.example.com/{{channel}}/latest.json")
+        }
+
+        #[test]
+        fn test_channel_urls() {
+            let config = UpdaterConfig::new("pubkey")
+                .endpoint("https://releases.example.com/{{channel}}/{{target}}/{{arch}}/{{current_version}}")
+                .endpoint("https://mirror.example.com/latest.json?channel={{channel}}");
+            let urls = |channel: Channel| -> Vec<String> {
+                config.urls(&channel).unwrap().iter().map(ToString::to_string).collect()
+            };
+
+            // Tauri's placeholders are left for the plugin, which fills in
+            // their encoded form
+            assert_eq!(
+                urls(Channel::Stable),
+                [
+                    "https://releases.example.com/stable/%7B%7Btarget%7D%7D/%7B%7Barch%7D%7D/%7B%7Bcurrent_version%7D%7D",
+                    "https://mirror.example.com/latest.json?channel=stable",
+                ]
+            );
+            assert_eq!(
+                urls(Channel::Beta),
+                [
+                    "https://releases.example.com/beta/%7B%7Btarget%7D%7D/%7B%7Barch%7D%7D/%7B%7Bcurrent_version%7D%7D",
+                    "https://mirror.example.com/latest.json?channel=beta",
+                ]
+            );
+            assert_eq!(
+                urls(Channel::Custom("team qa".to_string())),
+                [
+                    "https://releases.example.com/team%20qa/%7B%7Btarget%7D%7D/%7B%7Barch%7D%7D/%7B%7Bcurrent_version%7D%7D",
+                    "https://mirror.example.com/latest.json?channel=team%20qa",
+                ]
+            );
+
+            assert!(UpdaterConfig::new("pubkey").urls(&Channel::Beta).unwrap().is_empty());
+            let error = UpdaterConfig::new("pubkey")
+                .endpoint("releases/{{channel}}")
+                .urls(&Channel::Beta)
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "Runtime error: invalid update endpoint `releases/beta`: relative URL without a base"
+            );
+        }
+
+        #[tokio::test]
+        async fn test_check_statuses() {
+            let (updater, backend) = scripted(config());
+            assert_eq!(updater.status().get(), UpdateStatus::Idle);
+
+            assert!(updater.check().await.unwrap().is_none());
+            assert_eq!(backend.emitted(), [UpdateStatus::Checking, UpdateStatus::UpToDate]);
+
+            backend.answer(Ok(Some(release(&[], None))));
+            let update = updater.check().await.unwrap().unwrap();
+            assert_eq!(update.info, info(Channel::Stable));
+            assert_eq!(
+                backend.emitted(),
+                [UpdateStatus::Checking, UpdateStatus::Available(info(Channel::Stable))]
+            );
+            assert_eq!(
+                backend.checked(),
+                [
+                    ["https://releases.example.com/stable/latest.json"],
+                    ["https://releases.example.com/stable/latest.json"],
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_check_follows_channel() {
+            let (updater, backend) = scripted(config().channel(Channel::Beta));
+            assert_eq!(updater.channel().get(), Channel::Beta);
+
+            updater.check().await.unwrap();
+            updater.set_channel(Channel::Custom("nightly".to_string()));
+            backend.answer(Ok(Some(release(&[], None))));
+            let update = updater.check().await.unwrap().unwrap();
+
+            assert_eq!(update.info.channel, Channel::Custom("nightly".to_string()));
+            assert_eq!(
+                backend.checked(),
+                [
+                    ["https://releases.example.com/beta/latest.json"],
+                    ["https://releases.example.com/nightly/latest.json"],
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_check_failures() {
+            let (updater, backend) = scripted(config());
+            backend.answer(Err("offline".to_string()));
+
+            let error = updater.check().await.unwrap_err();
+            assert_eq!(error.to_string(), "Runtime error: offline");
+            assert_eq!(
+                backend.emitted(),
+                [UpdateStatus::Checking, failed("Runtime error: offline", false)]
+            );
+
+            // A bad endpoint fails before anything is asked
+            let (updater, backend) = scripted(UpdaterConfig::new("pubkey").endpoint("releases/{{channel}}"));
+            assert!(updater.check().await.is_err());
+            assert!(matches!(updater.status().get(), UpdateStatus::Failed { signature: false, .. }));
+            assert!(backend.checked().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_download_progress() {
+            let (updater, backend) = scripted(config());
+            backend.answer(Ok(Some(release(&[40, 60], Some(100)))));
+            updater.check().await.unwrap();
+            backend.emitted();
+
+            assert!(updater.download().await.unwrap());
+            assert_eq!(
+                backend.emitted(),
+                [
+                    downloading(0, None),
+                    downloading(40, Some(100)),
+                    downloading(100, Some(100)),
+                    UpdateStatus::Ready(info(Channel::Stable)),
+                ]
+            );
+            assert!(updater.is_pending());
+            assert_eq!(updater.status().get().progress(), Some(1.0));
+            assert_eq!(downloading(40, Some(100)).progress(), Some(0.4));
+            assert_eq!(downloading(40, None).progress(), None);
+            // The known update is downloaded without checking again
+            assert_eq!(backend.checked().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_download_checks_first() {
+            let (updater, backend) = scripted(config());
+            assert!(!updater.download().await.unwrap());
+            assert_eq!(backend.emitted(), [UpdateStatus::Checking, UpdateStatus::UpToDate]);
+            assert!(!updater.is_pending());
+
+            backend.answer(Ok(Some(release(&[10], None))));
+            assert!(updater.download().await.unwrap());
+            assert_eq!(
+                backend.emitted(),
+                [
+                    UpdateStatus::Checking,
+                    UpdateStatus::Available(info(Channel::Stable)),
+                    downloading(0, None),
+                    downloading(10, None),
+                    UpdateStatus::Ready(info(Channel::Stable)),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_bad_signature_is_not_installed() {
+            let (updater, backend) = scripted(config());
+            let release = Arc::new(ScriptedRelease {
+                chunks: vec![100],
+                total: Some(100),
+                fail: Some(DownloadError::Signature),
+                ..ScriptedRelease::default()
+            });
+            backend.answer(Ok(Some(release.clone())));
+            updater.check().await.unwrap();
+            backend.emitted();
+
+            let error = updater.download().await.unwrap_err();
+            assert_eq!(error.to_string(), "Runtime error: rejected");
+            assert_eq!(
+                backend.emitted(),
+                [
+                    downloading(0, None),
+                    downloading(100, Some(100)),
+                    failed("Runtime error: rejected", true),
+                ]
+            );
+            assert!(!updater.is_pending());
+
+            // Quitting leaves the app untouched
+            updater.install_pending().unwrap();
+            assert!(release.installed.lock().unwrap().is_empty());
+            assert!(backend.emitted().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_failed_download() {
+            let (updater, backend) = scripted(config());
+            backend.answer(Ok(Some(Arc::new(ScriptedRelease {
+                fail: Some(DownloadError::Failed),
+                ..ScriptedRelease::default()
+            }))));
+
+            assert!(updater.download().await.is_err());
+            assert_eq!(updater.status().get(), failed("Runtime error: rejected", false));
+            assert!(!updater.is_pending());
+        }
+
+        #[tokio::test]
+        async fn test_restart_installs_pending_update() {
+            let (updater, backend) = scripted(config());
+            let release = release(&[2, 1], Some(3));
+            backend.answer(Ok(Some(release.clone())));
+            updater.download().await.unwrap();
+            backend.emitted();
+
+            updater.restart().unwrap();
+            assert_eq!(*release.installed.lock().unwrap(), [vec![7u8, 7, 7]]);
+            assert_eq!(backend.emitted(), [UpdateStatus::Installing]);
+            assert_eq!(backend.restarts(), 1);
+            assert!(!updater.is_pending());
+
+            // Quitting afterwards doesn't install it again
+            updater.install_pending().unwrap();
+            assert_eq!(release.installed.lock().unwrap().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_failed_install_does_not_restart() {
+            let (updater, backend) = scripted(config());
+            backend.answer(Ok(Some(Arc::new(ScriptedRelease {
+                chunks: vec![1],
+                install_fails: true,
+                ..ScriptedRelease::default()
+            }))));
+            updater.download().await.unwrap();
+            backend.emitted();
+
+            assert!(updater.restart().is_err());
+            assert_eq!(
+                backend.emitted(),
+                [UpdateStatus::Installing, failed("Runtime error: disk full", false)]
+            );
+            assert_eq!(backend.restarts(), 0);
+        }
+
+        #[tokio::test]
+        async fn test_refresh_resends_status() {
+            let (updater, backend) = scripted(config());
+            updater.check().await.unwrap();
+            backend.emitted();
+
+            updater.handle(UpdateAction::Refresh);
+            assert_eq!(backend.emitted(), [UpdateStatus::UpToDate]);
+        }
+
+        #[test]
+        fn test_status_json() {
+            assert_eq!(
+                serde_json::to_value(downloading(40, Some(100))).unwrap(),
+                serde_json::json!({ "state": "downloading", "version": "2.0.0", "downloaded": 40, "total": 100 })
+            );
+            assert_eq!(
+                serde_json::to_value(failed("bad", true)).unwrap(),
+                serde_json::json!({ "state": "failed", "message": "bad", "signature": true })
+            );
+            assert_eq!(
+                serde_json::to_value(UpdateStatus::Ready(info(Channel::Beta))).unwrap(),
+                serde_json::json!({
+                    "state": "ready",
+                    "version": "2.0.0",
+                    "currentVersion": "1.0.0",
+                    "date": null,
+                    "body": "Fixes",
+                    "channel": "beta",
+                })
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Frontend
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod banner {
+    use super::*;
+    use crate::client;
+    use philjs::{on_cleanup, Dynamic, Element, IntoView, Signal, View};
+
+    fn send(action: UpdateAction) {
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = client::emit(ACTION_EVENT, &action).await;
+        });
+    }
+
+    fn button(label: &str, action: UpdateAction) -> View {
+        Element::new("button")
+            .attr("type", "button")
+            .attr("class", "philjs-update-banner__action")
+            .on("click", move |_| send(action))
+            .child(label.to_string())
+            .into_view()
+    }
+
+    fn banner(state: &str, message: String, actions: Vec<View>) -> View {
+        Element::new("div")
+            .attr("class", "philjs-update-banner")
+            .attr("role", "status")
+            .attr("data-state", state)
+            .child(
+                Element::new("span")
+                    .attr("class", "philjs-update-banner__message")
+                    .child(message),
+            )
+            .children(actions)
+            .into_view()
+    }
+
+    /// Shows the backend updater's status: offers available updates,
+    /// download progress, and a restart once an update is ready
+    ///
+    /// Renders nothing while idle or up to date. Style it through the
+    /// `philjs-update-banner` class and its `data-state` attribute.
+    #[allow(non_snake_case)]
+    pub fn UpdateBanner() -> View {
+        let status = Signal::new(UpdateStatus::Idle);
+
+        let target = status.clone();
+        let listener = client::listen_then(
+            STATUS_EVENT,
+            move |next: UpdateStatus| target.set(next),
+            || send(UpdateAction::Refresh),
+        );
+        on_cleanup(move || drop(listener));
+
+        Dynamic::new(move || match status.get() {
+            UpdateStatus::Available(info) => banner(
+                "available",
+                format!("Version {} is available", info.version),
+                vec![button("Download", UpdateAction::Download)],
+            ),
+            ref downloading @ UpdateStatus::Downloading { ref version, .. } => {
+                let percent = downloading.progress().map(|p| (p * 100.0).round() as u32);
+                let message = match percent {
+                    Some(percent) => format!("Downloading {}… {}%", version, percent),
+                    None => format!("Downloading {}…", version),
+                };
+                let mut progress = Element::new("progress").attr("max", "100");
+                if let Some(percent) = percent {
+                    progress = progress.attr("value", percent.to_string());
+                }
+                banner("downloading", message, vec![progress.into_view()])
+            }
+            UpdateStatus::Ready(info) => banner(
+                "ready",
+                format!("Version {} will be installed when you quit", info.version),
+                vec![button("Restart now", UpdateAction::Restart)],
+            ),
+            UpdateStatus::Installing => banner("installing", "Installing update…".to_string(), Vec::new()),
+            UpdateStatus::Failed { signature: true, .. } => banner(
+                "failed",
+                "The update was rejected because its signature didn't match".to_string(),
+                Vec::new(),
+            ),
+            UpdateStatus::Failed { message, .. } => banner(
+                "failed",
+                format!("Update failed: {}", message),
+                vec![button("Retry", UpdateAction::Check)],
+            ),
+            UpdateStatus::Idle | UpdateStatus::Checking | UpdateStatus::UpToDate => View::Empty,
+        })
+        .into_view()
     }
 }
@@ -0,0 +1,180 @@
+//! Command macro tests: specs and TypeScript bindings
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use philjs_tauri::commands::{
+    export_typescript, philjs_command, typescript_bindings, CommandResult, CommandSpec,
+};
+use serde::Serialize;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Note {
+    id: u64,
+    tag: Option<String>,
+}
+
+#[derive(Default)]
+pub struct Notes(Mutex<Vec<Note>>);
+
+/// Greets someone
+///
+/// Politely.
+#[philjs_command]
+fn greet(user_name: String, excited: Option<bool>) -> String {
+    let mark = if excited.unwrap_or(false) { "!" } else { "." };
+    format!("Hello, {}{}", user_name, mark)
+}
+
+/// Notes with a tag, newest first
+#[philjs_command]
+async fn notes(store: State<'_, Notes>, tag: Option<String>, limit: u32) -> CommandResult<Vec<Note>> {
+    let notes = store.0.lock().unwrap();
+    Ok(notes
+        .iter()
+        .rev()
+        .filter(|note| tag.is_none() || note.tag == tag)
+        .take(limit as usize)
+        .cloned()
+        .collect())
+}
+
+#[philjs_command]
+fn note_counts(by_tag: HashMap<String, Vec<u64>>) -> HashMap<String, usize> {
+    by_tag.into_iter().map(|(tag, ids)| (tag, ids.len())).collect()
+}
+
+#[philjs_command]
+fn ping() {}
+
+const COMMANDS: &[CommandSpec] = &[GREET_COMMAND, NOTES_COMMAND, NOTE_COUNTS_COMMAND, PING_COMMAND];
+
+const BINDINGS: &str = "\
+// Generated by philjs-tauri. Do not edit.
+
+import { invoke } from '@tauri-apps/api/core';
+import type { Note } from './types';
+
+/** Shape of every command error */
+export interface CommandError {
+  code: string;
+  message: string;
+  field?: string;
+  details?: unknown;
+}
+
+/**
+ * Greets someone
+ *
+ * Politely.
+ */
+export function greet(userName: string, excited?: boolean | null): Promise<string> {
+  return invoke<string>('greet', { userName, excited });
+}
+
+/**
+ * Notes with a tag, newest first
+ */
+export function notes(tag: string | null, limit: number): Promise<Note[]> {
+  return invoke<Note[]>('notes', { tag, limit });
+}
+
+export function noteCounts(byTag: Record<string, number[]>): Promise<Record<string, number>> {
+  return invoke<Record<string, number>>('note_counts', { byTag });
+}
+
+export function ping(): Promise<void> {
+  return invoke<void>('ping', {  });
+}
+";
+
+#[test]
+fn test_generated_commands_register() {
+    let _builder = tauri::test::mock_builder()
+        .manage(Notes::default())
+        .invoke_handler(tauri::generate_handler![greet, notes, note_counts, ping]);
+}
+
+#[test]
+fn test_command_specs() {
+    assert_eq!(
+        GREET_COMMAND,
+        CommandSpec {
+            name: "greet",
+            docs: "Greets someone\n\nPolitely.",
+            args: &[("userName", "string"), ("excited", "boolean | null")],
+            returns: "string",
+        }
+    );
+    // State is injected by Tauri, not sent by the frontend
+    assert_eq!(
+        NOTES_COMMAND,
+        CommandSpec {
+            name: "notes",
+            docs: "Notes with a tag, newest first",
+            args: &[("tag", "string | null"), ("limit", "number")],
+            returns: "Note[]",
+        }
+    );
+    assert_eq!(
+        NOTE_COUNTS_COMMAND,
+        CommandSpec {
+            name: "note_counts",
+            docs: "",
+            args: &[("byTag", "Record<string, number[]>")],
+            returns: "Record<string, number>",
+        }
+    );
+    assert_eq!(
+        PING_COMMAND,
+        CommandSpec { name: "ping", docs: "", args: &[], returns: "void" }
+    );
+}
+
+#[test]
+fn test_typescript_bindings() {
+    assert_eq!(typescript_bindings(COMMANDS), BINDINGS);
+}
+
+#[test]
+fn test_typescript_bindings_without_commands() {
+    let bindings = typescript_bindings(&[]);
+    assert!(!bindings.contains("import type"));
+    assert!(bindings.ends_with("  details?: unknown;\n}\n"));
+}
+
+#[test]
+fn test_typescript_imports_are_sorted_and_deduplicated() {
+    let commands = [
+        CommandSpec {
+            name: "move_notes",
+            docs: "",
+            args: &[("notes", "Note[]"), ("to", "Folder | null")],
+            returns: "Page<Note>",
+        },
+        CommandSpec { name: "folders", docs: "", args: &[], returns: "Record<string, Folder>" },
+    ];
+    let bindings = typescript_bindings(&commands);
+    assert!(bindings.contains("import type { Folder, Note, Page } from './types';\n"));
+    assert!(bindings.contains("export function moveNotes(notes: Note[], to?: Folder | null): Promise<Page<Note>>"));
+}
+
+#[test]
+fn test_export_typescript() {
+    let dir = std::env::temp_dir().join(format!("philjs-tauri-bindings-{}", std::process::id()));
+    let path = dir.join("src").join("commands.ts");
+
+    export_typescript(&path, COMMANDS).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), BINDINGS);
+
+    // Unchanged bindings leave the file alone
+    let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+    export_typescript(&path, COMMANDS).unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().modified().unwrap(), modified);
+
+    export_typescript(&path, &[PING_COMMAND]).unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().ends_with("invoke<void>('ping', {  });\n}\n"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
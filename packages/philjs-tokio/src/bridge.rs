@@ -0,0 +1,190 @@
+//! Bridges between channels and PhilJS signals
+//!
+//! Background tasks report progress over channels; these adapters apply
+//! each update to a [`Signal`] so the UI follows along without a manual
+//! polling loop. Signals belong to the UI thread, so the receiving side runs
+//! with `spawn_local` and must be called inside a `LocalSet`. The bridge
+//! task is aborted when the current reactive scope is cleaned up.
+//!
+//! ```rust,ignore
+//! let (tx, rx) = watch(Progress::default());
+//! spawn_task("import", async move { run_import(tx).await });
+//!
+//! let progress = watch_to_signal(rx);
+//! view! { <progress value={move || progress.get().percent} /> }
+//! ```
+
+use std::cell::Cell;
+
+use async_trait::async_trait;
+use futures::Stream;
+use philjs::{Effect, Signal};
+
+use crate::channel::{BroadcastReceiver, BroadcastRecvError, Receiver, WatchReceiver};
+
+/// A receiver that yields a sequence of updates
+#[async_trait]
+pub trait SignalSource<T>: Send {
+    /// Wait for the next update, or `None` once the sender is gone
+    async fn next_update(&mut self) -> Option<T>;
+}
+
+#[async_trait]
+impl<T: Send> SignalSource<T> for Receiver<T> {
+    async fn next_update(&mut self) -> Option<T> {
+        self.recv().await
+    }
+}
+
+#[async_trait]
+impl<T: Clone + Send> SignalSource<T> for BroadcastReceiver<T> {
+    async fn next_update(&mut self) -> Option<T> {
+        loop {
+            match self.recv().await {
+                Ok(value) => return Some(value),
+                // Only the latest state matters to the UI
+                Err(BroadcastRecvError::Lagged(_)) => continue,
+                Err(BroadcastRecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Clone + Send + Sync> SignalSource<T> for WatchReceiver<T> {
+    async fn next_update(&mut self) -> Option<T> {
+        self.wait_for_change().await.ok()
+    }
+}
+
+/// A stream of the updates from a receiver
+pub fn updates<T, S>(source: S) -> impl Stream<Item = T> + Send
+where
+    T: Send,
+    S: SignalSource<T>,
+{
+    futures::stream::unfold(source, |mut source| async move {
+        source.next_update().await.map(|value| (value, source))
+    })
+}
+
+/// A signal starting at `initial` and set to each value received
+///
+/// The signal keeps its last value once the sender is dropped.
+pub fn channel_to_signal<T, S>(mut source: S, initial: T) -> Signal<T>
+where
+    T: 'static,
+    S: SignalSource<T> + 'static,
+{
+    let signal = Signal::new(initial);
+    let target = signal.clone();
+
+    let handle = tokio::task::spawn_local(async move {
+        while let Some(value) = source.next_update().await {
+            target.set(value);
+        }
+    });
+    let abort = handle.abort_handle();
+    philjs::on_cleanup(move || abort.abort());
+
+    signal
+}
+
+/// A signal following a watch channel, starting at its current value
+pub fn watch_to_signal<T>(rx: WatchReceiver<T>) -> Signal<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let initial = rx.get();
+    channel_to_signal(rx, initial)
+}
+
+/// A watch channel that receives every change to `signal`
+///
+/// Background tasks can hold the receiver to react to UI state, e.g. a
+/// search query. The subscription ends when the current reactive scope is
+/// cleaned up.
+pub fn signal_to_watch<T>(signal: &Signal<T>) -> WatchReceiver<T>
+where
+    T: Clone + 'static,
+{
+    let (tx, rx) = tokio::sync::watch::channel(signal.get_untracked());
+    let signal = signal.clone();
+    let initialized = Cell::new(false);
+
+    let effect = Effect::new(move || {
+        // Read on every run so the effect tracks the signal
+        let value = signal.get();
+        if initialized.replace(true) {
+            tx.send_replace(value);
+        }
+    });
+    philjs::on_cleanup(move || drop(effect));
+
+    WatchReceiver::new(rx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::{broadcast, channel, watch};
+    use futures::StreamExt;
+
+    async fn settle() {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_to_signal() {
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let (tx, rx) = watch(1);
+                let signal = watch_to_signal(rx);
+                assert_eq!(signal.get(), 1);
+
+                tx.send(2).unwrap();
+                settle().await;
+                assert_eq!(signal.get(), 2);
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_channel_to_signal_keeps_last_value() {
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let (tx, rx) = channel(4);
+                let signal = channel_to_signal(rx, "idle");
+
+                tx.send("loading").await.unwrap();
+                tx.send("done").await.unwrap();
+                drop(tx);
+                settle().await;
+                assert_eq!(signal.get(), "done");
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_updates_stream() {
+        let (tx, rx) = broadcast(4);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        drop(tx);
+
+        let values: Vec<i32> = updates(rx).collect().await;
+        assert_eq!(values, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_signal_to_watch() {
+        let signal = Signal::new(String::from("a"));
+        let mut rx = signal_to_watch(&signal);
+        assert_eq!(rx.get(), "a");
+
+        signal.set(String::from("b"));
+        assert_eq!(rx.wait_for_change().await.unwrap(), "b");
+    }
+}
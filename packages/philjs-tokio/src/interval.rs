@@ -211,6 +211,9 @@ where
 }
 
 /// Cron-like scheduler (simplified)
+///
+/// For cron expressions, overlap policies, and graceful shutdown, use
+/// [`crate::scheduler::Scheduler`].
 pub struct Scheduler {
     tasks: Vec<ScheduledTask>,
 }
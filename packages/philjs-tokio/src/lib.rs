@@ -9,6 +9,8 @@
 //! - **Channel Utilities**: Typed channels for component communication
 //! - **Timeout Helpers**: Simplified timeout handling
 //! - **Interval Helpers**: Easy periodic task execution
+//! - **Scheduled Jobs**: Cron and interval jobs with overlap control
+//! - **Signal Bridges**: Drive PhilJS signals from channels and back
 //!
 //! ## Quick Start
 //!
@@ -43,14 +45,18 @@ pub mod channel;
 pub mod timeout;
 pub mod interval;
 pub mod sync;
+pub mod bridge;
+pub mod scheduler;
 
 // Re-exports
 pub use runtime::{RuntimeBuilder, RuntimeConfig};
-pub use task::{spawn_task, spawn_blocking_task, TaskHandle, TaskManager};
+pub use task::{spawn_task, spawn_blocking_task, spawn_scoped, TaskHandle, TaskManager};
 pub use channel::{channel, broadcast, watch, Channel, Sender, Receiver};
 pub use timeout::{with_timeout, TimeoutError};
 pub use interval::{spawn_interval, IntervalHandle};
-pub use sync::{Mutex, RwLock, Semaphore};
+pub use sync::{Mutex, RwLock, Semaphore, RateLimiter, KeyedRateLimiter, KeyedSemaphore};
+pub use scheduler::{Job, JobStore, OverlapPolicy, Schedule, Scheduler};
+pub use bridge::{channel_to_signal, watch_to_signal, signal_to_watch, updates, SignalSource};
 
 // Re-export tokio types
 pub use tokio::{
@@ -63,11 +69,13 @@ pub use tokio::{
 /// Prelude - import commonly used items
 pub mod prelude {
     pub use crate::runtime::{RuntimeBuilder, RuntimeConfig};
-    pub use crate::task::{spawn_task, spawn_blocking_task, TaskHandle, TaskManager};
+    pub use crate::task::{spawn_task, spawn_blocking_task, spawn_scoped, TaskHandle, TaskManager};
     pub use crate::channel::{channel, broadcast, watch};
     pub use crate::timeout::{with_timeout, TimeoutError};
     pub use crate::interval::{spawn_interval, IntervalHandle};
-    pub use crate::sync::{Mutex, RwLock, Semaphore};
+    pub use crate::sync::{Mutex, RwLock, Semaphore, RateLimiter, KeyedRateLimiter, KeyedSemaphore};
+    pub use crate::scheduler::{Job, OverlapPolicy, Schedule, Scheduler};
+    pub use crate::bridge::{channel_to_signal, watch_to_signal, signal_to_watch};
 
     pub use tokio::{
         spawn,
@@ -0,0 +1,725 @@
+//! Scheduled jobs
+//!
+//! Run jobs on cron expressions or human-readable intervals, with overlap
+//! control, jitter, persistence hooks, and graceful shutdown.
+//!
+//! ```rust,ignore
+//! use philjs_tokio::scheduler::{Job, OverlapPolicy, Scheduler};
+//!
+//! let mut scheduler = Scheduler::new();
+//!
+//! scheduler.add(
+//!     Job::new("purge-sessions", "0 3 * * *".parse()?, || async {
+//!         purge_expired_sessions().await
+//!     })
+//!     .jitter(Duration::from_secs(60)),
+//! );
+//!
+//! scheduler.add(
+//!     Job::new("sync-feeds", "every 5m".parse()?, || async { sync_feeds().await })
+//!         .overlap(OverlapPolicy::Queue),
+//! );
+//!
+//! // On SIGTERM: stop scheduling and give in-flight runs 30s to finish
+//! scheduler.shutdown(Duration::from_secs(30)).await;
+//! ```
+//!
+//! Cron expressions are evaluated in UTC.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use futures::FutureExt;
+use parking_lot::Mutex;
+use tokio::task::{JoinHandle, JoinSet};
+use tokio_util::sync::CancellationToken;
+
+// ============================================================================
+// Schedules
+// ============================================================================
+
+/// When a job runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    /// On a cron expression
+    Cron(CronSchedule),
+    /// Every fixed interval
+    Every(Duration),
+}
+
+impl Schedule {
+    /// The next run strictly after `now`
+    ///
+    /// Intervals count from the previous run when one is known, so a
+    /// restarted app keeps its cadence; overdue runs happen immediately.
+    /// Missed cron runs are not caught up.
+    pub fn next_after(&self, now: SystemTime, last_run: Option<SystemTime>) -> Option<SystemTime> {
+        match self {
+            Schedule::Cron(cron) => cron.next_after(now),
+            Schedule::Every(period) => match last_run {
+                Some(last) => Some((last + *period).max(now)),
+                None => Some(now + *period),
+            },
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = ScheduleParseError;
+
+    /// Parse a cron expression (`"*/5 * * * *"`, `"@daily"`) or an interval
+    /// (`"every 5m"`, `"1h30m"`, `"2 hours"`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('@') || s.split_whitespace().count() >= 5 {
+            s.parse().map(Schedule::Cron)
+        } else {
+            parse_interval(s).map(Schedule::Every)
+        }
+    }
+}
+
+impl From<Duration> for Schedule {
+    fn from(period: Duration) -> Self {
+        Schedule::Every(period)
+    }
+}
+
+/// Error parsing a schedule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleParseError {
+    message: String,
+}
+
+impl ScheduleParseError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ScheduleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid schedule: {}", self.message)
+    }
+}
+
+impl std::error::Error for ScheduleParseError {}
+
+/// Parse `"every 5m"`, `"1h30m"`, `"90 seconds"`, ...
+fn parse_interval(s: &str) -> Result<Duration, ScheduleParseError> {
+    let lower = s.to_ascii_lowercase();
+    let rest = lower.strip_prefix("every").unwrap_or(&lower).trim();
+    if rest.is_empty() {
+        return Err(ScheduleParseError::new(format!("'{}' has no interval", s)));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut chars = rest.chars().peekable();
+    while chars.peek().is_some() {
+        let number: String = std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_digit())).collect();
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let unit: String = std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_alphabetic())).collect();
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+
+        let n: u64 = number
+            .parse()
+            .map_err(|_| ScheduleParseError::new(format!("expected a number in '{}'", s)))?;
+        let unit_secs = match unit.as_str() {
+            "ms" | "millis" | "millisecond" | "milliseconds" => {
+                total += Duration::from_millis(n);
+                continue;
+            }
+            "s" | "sec" | "secs" | "second" | "seconds" => 1,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3_600,
+            "d" | "day" | "days" => 86_400,
+            "w" | "week" | "weeks" => 604_800,
+            other => return Err(ScheduleParseError::new(format!("unknown unit '{}'", other))),
+        };
+        total += Duration::from_secs(n * unit_secs);
+    }
+
+    if total.is_zero() {
+        return Err(ScheduleParseError::new(format!("'{}' is a zero interval", s)));
+    }
+    Ok(total)
+}
+
+/// A parsed cron expression
+///
+/// Accepts five fields (minute hour day-of-month month day-of-week), six
+/// with a leading seconds field, or `@yearly`, `@monthly`, `@weekly`,
+/// `@daily`, `@hourly`. Fields support `*`, lists, ranges, steps, and
+/// month/day names. As in cron, when both day fields are restricted a day
+/// matching either one fires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    seconds: u64,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+const MONTH_NAMES: &[&str] = &["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+const DAY_NAMES: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+impl FromStr for CronSchedule {
+    type Err = ScheduleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expanded = match s.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other if other.starts_with('@') => {
+                return Err(ScheduleParseError::new(format!("unknown macro '{}'", other)))
+            }
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let (seconds, rest) = match fields.len() {
+            5 => ("0", &fields[..]),
+            6 => (fields[0], &fields[1..]),
+            n => return Err(ScheduleParseError::new(format!("expected 5 or 6 fields, got {}", n))),
+        };
+
+        // Sunday may be written as 0 or 7
+        let mut days_of_week = parse_field(rest[4], 0, 7, DAY_NAMES)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            seconds: parse_field(seconds, 0, 59, &[])?,
+            minutes: parse_field(rest[0], 0, 59, &[])?,
+            hours: parse_field(rest[1], 0, 23, &[])?,
+            days_of_month: parse_field(rest[2], 1, 31, &[])?,
+            months: parse_field(rest[3], 1, 12, MONTH_NAMES)?,
+            days_of_week,
+            any_day_of_month: rest[2] == "*" || rest[2] == "?",
+            any_day_of_week: rest[4] == "*" || rest[4] == "?",
+        })
+    }
+}
+
+/// Parse one cron field into a bitset of allowed values
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, ScheduleParseError> {
+    let value = |s: &str| -> Result<u32, ScheduleParseError> {
+        let lower = s.to_ascii_lowercase();
+        let n = match names.iter().position(|name| *name == lower) {
+            // Month names start at 1, day names at 0
+            Some(i) => i as u32 + min,
+            None => s
+                .parse()
+                .map_err(|_| ScheduleParseError::new(format!("invalid value '{}'", s)))?,
+        };
+        if n < min || n > max {
+            return Err(ScheduleParseError::new(format!("{} is outside {}-{}", n, min, max)));
+        }
+        Ok(n)
+    };
+
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| ScheduleParseError::new(format!("invalid step in '{}'", part)))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = match range {
+            "*" | "?" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // `5/15` means from 5 to the end in steps of 15
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let n = value(range)?;
+                    (n, n)
+                }
+            },
+        };
+        if start > end {
+            return Err(ScheduleParseError::new(format!("empty range '{}'", range)));
+        }
+
+        for n in (start..=end).step_by(step as usize) {
+            bits |= 1 << n;
+        }
+    }
+    Ok(bits)
+}
+
+impl CronSchedule {
+    /// The next matching second strictly after `now`
+    pub fn next_after(&self, now: SystemTime) -> Option<SystemTime> {
+        let start = now.duration_since(UNIX_EPOCH).ok()?.as_secs() + 1;
+        let mut t = start;
+        // Give up on expressions that never match, like Feb 30
+        let limit = start + 5 * 366 * 86_400;
+
+        while t < limit {
+            let days = (t / 86_400) as i64;
+            let (year, month, day) = civil_from_days(days);
+            let secs_of_day = t % 86_400;
+            let (hour, minute, second) = (secs_of_day / 3_600, secs_of_day / 60 % 60, secs_of_day % 60);
+
+            if !has(self.months, month) {
+                let (y, m) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+                t = days_from_civil(y, m, 1) as u64 * 86_400;
+            } else if !self.day_matches(day, weekday(days)) {
+                t = (days as u64 + 1) * 86_400;
+            } else if !has(self.hours, hour as u32) {
+                t = t - secs_of_day % 3_600 + 3_600;
+            } else if !has(self.minutes, minute as u32) {
+                t = t - second + 60;
+            } else if !has(self.seconds, second as u32) {
+                t += 1;
+            } else {
+                return Some(UNIX_EPOCH + Duration::from_secs(t));
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, day: u32, weekday: u32) -> bool {
+        let dom = has(self.days_of_month, day);
+        let dow = has(self.days_of_week, weekday);
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (false, false) => dom || dow,
+            _ => dom && dow,
+        }
+    }
+}
+
+fn has(bits: u64, n: u32) -> bool {
+    bits & (1 << n) != 0
+}
+
+/// Day of week for days since the epoch, Sunday = 0
+fn weekday(days: i64) -> u32 {
+    (days + 4).rem_euclid(7) as u32
+}
+
+/// (year, month, day) for days since the epoch
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Days since the epoch for (year, month, day)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// ============================================================================
+// Jobs
+// ============================================================================
+
+/// What to do when a run is due while the previous one is still going
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlapPolicy {
+    /// Skip the run
+    #[default]
+    Skip,
+    /// Run once the previous run finishes; several missed runs collapse into one
+    Queue,
+    /// Start the run alongside the previous one
+    Concurrent,
+}
+
+type JobFn = Arc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> + Send + Sync>;
+
+/// A named job and its schedule
+pub struct Job {
+    name: String,
+    schedule: Schedule,
+    overlap: OverlapPolicy,
+    jitter: Duration,
+    run: JobFn,
+}
+
+impl Job {
+    /// Create a job; errors returned by `f` are recorded in the [`JobStore`]
+    pub fn new<F, Fut, E>(name: impl Into<String>, schedule: impl Into<Schedule>, f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: fmt::Display,
+    {
+        Self {
+            name: name.into(),
+            schedule: schedule.into(),
+            overlap: OverlapPolicy::default(),
+            jitter: Duration::ZERO,
+            run: Arc::new(move || Box::pin(f().map(|result| result.map_err(|e| e.to_string())))),
+        }
+    }
+
+    /// Set the overlap policy
+    pub fn overlap(mut self, policy: OverlapPolicy) -> Self {
+        self.overlap = policy;
+        self
+    }
+
+    /// Delay each run by a random amount up to `max`
+    ///
+    /// Spreads out jobs that many instances schedule at the same time.
+    pub fn jitter(mut self, max: Duration) -> Self {
+        self.jitter = max;
+        self
+    }
+
+    /// The job's name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The job's schedule
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    fn jitter_delay(&self) -> Duration {
+        use std::hash::{BuildHasher, Hasher};
+
+        if self.jitter.is_zero() {
+            return Duration::ZERO;
+        }
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write(self.name.as_bytes());
+        let unit = (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64;
+        self.jitter.mul_f64(unit)
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+/// A finished run of a job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobRun {
+    /// When the run started
+    pub started_at: SystemTime,
+    /// When the run finished
+    pub finished_at: SystemTime,
+    /// The error, if the run failed
+    pub error: Option<String>,
+}
+
+impl JobRun {
+    /// Whether the run succeeded
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Persists job history so schedules survive restarts
+///
+/// Implement this over a database table to share history between
+/// deployments; [`MemoryJobStore`] is used by default.
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    /// When the job last started, if ever
+    async fn last_run(&self, job: &str) -> Option<SystemTime>;
+
+    /// Record a finished run
+    async fn record_run(&self, job: &str, run: &JobRun);
+}
+
+/// Job history kept in memory
+#[derive(Default)]
+pub struct MemoryJobStore {
+    runs: Mutex<HashMap<String, JobRun>>,
+}
+
+impl MemoryJobStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The latest run of a job
+    pub fn latest(&self, job: &str) -> Option<JobRun> {
+        self.runs.lock().get(job).cloned()
+    }
+}
+
+#[async_trait]
+impl JobStore for MemoryJobStore {
+    async fn last_run(&self, job: &str) -> Option<SystemTime> {
+        self.runs.lock().get(job).map(|run| run.started_at)
+    }
+
+    async fn record_run(&self, job: &str, run: &JobRun) {
+        self.runs.lock().insert(job.to_string(), run.clone());
+    }
+}
+
+// ============================================================================
+// Scheduler
+// ============================================================================
+
+/// Runs jobs on their schedules until shut down
+pub struct Scheduler {
+    store: Arc<dyn JobStore>,
+    shutdown: CancellationToken,
+    jobs: Vec<(String, JoinHandle<()>)>,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler {
+    /// Create a scheduler with in-memory job history
+    pub fn new() -> Self {
+        Self::with_store(Arc::new(MemoryJobStore::new()))
+    }
+
+    /// Create a scheduler that persists job history to `store`
+    pub fn with_store(store: Arc<dyn JobStore>) -> Self {
+        Self {
+            store,
+            shutdown: CancellationToken::new(),
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Start running a job
+    pub fn add(&mut self, job: Job) {
+        let name = job.name.clone();
+        let handle = tokio::spawn(run_job(job, self.store.clone(), self.shutdown.child_token()));
+        self.jobs.push((name, handle));
+    }
+
+    /// Names of the scheduled jobs
+    pub fn job_names(&self) -> Vec<&str> {
+        self.jobs.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Stop scheduling runs and wait up to `grace` for in-flight runs
+    ///
+    /// Runs still going after `grace` are aborted. Returns whether every
+    /// run finished in time.
+    pub async fn shutdown(self, grace: Duration) -> bool {
+        self.shutdown.cancel();
+
+        let handles: Vec<_> = self.jobs.into_iter().map(|(_, handle)| handle).collect();
+        let aborts: Vec<_> = handles.iter().map(|handle| handle.abort_handle()).collect();
+
+        match tokio::time::timeout(grace, futures::future::join_all(handles)).await {
+            Ok(_) => true,
+            Err(_) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Scheduler shutdown timed out; aborting in-flight jobs");
+
+                for abort in aborts {
+                    abort.abort();
+                }
+                false
+            }
+        }
+    }
+}
+
+/// Schedule and run one job until cancelled
+async fn run_job(job: Job, store: Arc<dyn JobStore>, shutdown: CancellationToken) {
+    let job = Arc::new(job);
+    let mut runs = JoinSet::new();
+    let mut last_run = store.last_run(&job.name).await;
+
+    loop {
+        let now = SystemTime::now();
+        let Some(next) = job.schedule.next_after(now, last_run) else {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(job = %job.name, "Schedule never fires again");
+            break;
+        };
+        let delay = next.duration_since(now).unwrap_or_default() + job.jitter_delay();
+
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tokio::time::sleep(delay) => {}
+        }
+        last_run = Some(next);
+
+        // Reap finished runs
+        while let Some(Some(_)) = runs.join_next().now_or_never() {}
+
+        if !runs.is_empty() {
+            match job.overlap {
+                OverlapPolicy::Skip => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(job = %job.name, "Previous run still going; skipping");
+                    continue;
+                }
+                OverlapPolicy::Queue => {
+                    let drained = async { while runs.join_next().await.is_some() {} };
+                    tokio::select! {
+                        _ = shutdown.cancelled() => break,
+                        _ = drained => {}
+                    }
+                }
+                OverlapPolicy::Concurrent => {}
+            }
+        }
+
+        let job = job.clone();
+        let store = store.clone();
+        runs.spawn(async move {
+            let started_at = SystemTime::now();
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(job = %job.name, "Job started");
+
+            let result = (job.run)().await;
+            let run = JobRun {
+                started_at,
+                finished_at: SystemTime::now(),
+                error: result.err(),
+            };
+
+            #[cfg(feature = "tracing")]
+            if let Some(error) = &run.error {
+                tracing::error!(job = %job.name, error = %error, "Job failed");
+            }
+
+            store.record_run(&job.name, &run).await;
+        });
+    }
+
+    // Let in-flight runs finish; shutdown aborts us if they take too long
+    while runs.join_next().await.is_some() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn at(year: i64, month: u32, day: u32, hour: u64, minute: u64) -> SystemTime {
+        let days = days_from_civil(year, month, day) as u64;
+        UNIX_EPOCH + Duration::from_secs(days * 86_400 + hour * 3_600 + minute * 60)
+    }
+
+    #[test]
+    fn test_parse_intervals() {
+        assert_eq!("every 5m".parse(), Ok(Schedule::Every(Duration::from_secs(300))));
+        assert_eq!("1h30m".parse(), Ok(Schedule::Every(Duration::from_secs(5_400))));
+        assert_eq!("every 2 hours".parse(), Ok(Schedule::Every(Duration::from_secs(7_200))));
+        assert_eq!("250ms".parse(), Ok(Schedule::Every(Duration::from_millis(250))));
+        assert!("every".parse::<Schedule>().is_err());
+        assert!("5 fortnights".parse::<Schedule>().is_err());
+    }
+
+    #[test]
+    fn test_parse_cron() {
+        assert!(matches!("*/15 * * * *".parse(), Ok(Schedule::Cron(_))));
+        assert!(matches!("@daily".parse(), Ok(Schedule::Cron(_))));
+        assert!("60 * * * *".parse::<Schedule>().is_err());
+        assert!("* * * *".parse::<CronSchedule>().is_err());
+        assert!("0 0 * * mon-fri/0".parse::<CronSchedule>().is_err());
+    }
+
+    #[test]
+    fn test_cron_next() {
+        // 2024-01-15 was a Monday
+        let now = at(2024, 1, 15, 10, 7);
+
+        let every_15: CronSchedule = "*/15 * * * *".parse().unwrap();
+        assert_eq!(every_15.next_after(now), Some(at(2024, 1, 15, 10, 15)));
+
+        let nightly: CronSchedule = "30 2 * * *".parse().unwrap();
+        assert_eq!(nightly.next_after(now), Some(at(2024, 1, 16, 2, 30)));
+
+        let weekdays: CronSchedule = "0 9 * * mon-fri".parse().unwrap();
+        assert_eq!(weekdays.next_after(at(2024, 1, 19, 10, 0)), Some(at(2024, 1, 22, 9, 0)));
+
+        let leap_day: CronSchedule = "0 0 29 feb *".parse().unwrap();
+        assert_eq!(leap_day.next_after(now), Some(at(2024, 2, 29, 0, 0)));
+        assert_eq!(leap_day.next_after(at(2024, 3, 1, 0, 0)), Some(at(2028, 2, 29, 0, 0)));
+
+        let never: CronSchedule = "0 0 30 feb *".parse().unwrap();
+        assert_eq!(never.next_after(now), None);
+
+        // Either day field may match when both are restricted
+        let either: CronSchedule = "0 0 1 * sun".parse().unwrap();
+        assert_eq!(either.next_after(now), Some(at(2024, 1, 21, 0, 0)));
+    }
+
+    #[test]
+    fn test_interval_keeps_cadence() {
+        let schedule = Schedule::Every(Duration::from_secs(60));
+        let now = at(2024, 1, 1, 12, 0);
+
+        assert_eq!(schedule.next_after(now, None), Some(at(2024, 1, 1, 12, 1)));
+        let last = now - Duration::from_secs(20);
+        assert_eq!(schedule.next_after(now, Some(last)), Some(now + Duration::from_secs(40)));
+        let overdue = now - Duration::from_secs(600);
+        assert_eq!(schedule.next_after(now, Some(overdue)), Some(now));
+    }
+
+    #[tokio::test]
+    async fn test_skip_overlapping_runs_and_shutdown() {
+        let started = Arc::new(AtomicU32::new(0));
+        let store = Arc::new(MemoryJobStore::new());
+        let mut scheduler = Scheduler::with_store(store.clone());
+
+        let counter = started.clone();
+        scheduler.add(Job::new("slow", Duration::from_millis(10), move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(45)).await;
+                Err::<(), _>("boom")
+            }
+        }));
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(scheduler.shutdown(Duration::from_secs(1)).await);
+
+        // Runs take ~45ms and the rest are skipped
+        let runs = started.load(Ordering::SeqCst);
+        assert!((1..=2).contains(&runs), "{} runs", runs);
+        assert_eq!(store.latest("slow").unwrap().error.as_deref(), Some("boom"));
+    }
+}
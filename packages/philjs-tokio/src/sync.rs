@@ -1,13 +1,19 @@
 //! Synchronization primitives
 
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{
     Mutex as TokioMutex, MutexGuard as TokioMutexGuard,
     RwLock as TokioRwLock, RwLockReadGuard, RwLockWriteGuard,
     Semaphore as TokioSemaphore, SemaphorePermit, OwnedSemaphorePermit,
 };
 
+use crate::timeout::TimeoutError;
+
 /// Async mutex wrapper
 pub struct Mutex<T> {
     inner: TokioMutex<T>,
@@ -149,13 +155,13 @@ impl Semaphore {
     /// Acquire a permit
     pub async fn acquire(&self) -> SemaphoreGuard<'_> {
         SemaphoreGuard {
-            inner: self.inner.acquire().await.expect("Semaphore closed"),
+            inner: Some(self.inner.acquire().await.expect("Semaphore closed")),
         }
     }
 
     /// Try to acquire a permit without waiting
     pub fn try_acquire(&self) -> Option<SemaphoreGuard<'_>> {
-        self.inner.try_acquire().ok().map(|permit| SemaphoreGuard { inner: permit })
+        self.inner.try_acquire().ok().map(|permit| SemaphoreGuard { inner: Some(permit) })
     }
 
     /// Acquire an owned permit
@@ -195,8 +201,11 @@ impl Clone for Semaphore {
 }
 
 /// Semaphore guard
+///
+/// Guards from a [`RateLimiter`] hold no permit: spent tokens are not
+/// returned on drop.
 pub struct SemaphoreGuard<'a> {
-    inner: SemaphorePermit<'a>,
+    inner: Option<SemaphorePermit<'a>>,
 }
 
 /// Owned semaphore guard
@@ -204,62 +213,507 @@ pub struct OwnedSemaphoreGuard {
     inner: OwnedSemaphorePermit,
 }
 
-/// Rate limiter based on semaphore
+/// Acquisition counters for [`RateLimiter`], [`KeyedRateLimiter`], and [`KeyedSemaphore`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LimiterStats {
+    /// Successful acquisitions
+    pub acquired: u64,
+    /// Acquisitions that had to wait
+    pub waited: u64,
+    /// Acquisitions refused by `try_acquire` or a timeout
+    pub rejected: u64,
+    /// Total time spent waiting
+    pub wait_time: Duration,
+}
+
+#[derive(Default)]
+struct LimiterCounters {
+    acquired: AtomicU64,
+    waited: AtomicU64,
+    rejected: AtomicU64,
+    wait_nanos: AtomicU64,
+}
+
+impl LimiterCounters {
+    fn record_acquired(&self, wait: Duration) {
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+        if !wait.is_zero() {
+            self.waited.fetch_add(1, Ordering::Relaxed);
+            let nanos = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX);
+            self.wait_nanos.fetch_add(nanos, Ordering::Relaxed);
+        }
+    }
+
+    fn record_rejected(&self) {
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> LimiterStats {
+        LimiterStats {
+            acquired: self.acquired.load(Ordering::Relaxed),
+            waited: self.waited.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            wait_time: Duration::from_nanos(self.wait_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Token bucket rate limiter
+///
+/// Allows `permits_per_interval` acquisitions per interval on average, with
+/// bursts up to the bucket size. Waiters are served in arrival order, and a
+/// waiter that is cancelled or times out takes no tokens. Clones share the
+/// same bucket, so one limiter can throttle an outbound API from every
+/// server function; use [`KeyedRateLimiter`] for a bucket per key.
+///
+/// ```rust,ignore
+/// static GITHUB: Lazy<RateLimiter> = Lazy::new(|| RateLimiter::new(5_000, Duration::from_secs(3_600)));
+///
+/// let _permit = GITHUB.acquire_timeout(Duration::from_secs(2)).await?;
+/// let repos = client.get("https://api.github.com/user/repos").send().await?;
+/// ```
+#[derive(Clone)]
 pub struct RateLimiter {
-    semaphore: Arc<Semaphore>,
-    refill_interval: std::time::Duration,
+    inner: Arc<RateLimiterInner>,
+}
+
+struct RateLimiterInner {
+    burst: f64,
+    per_token: Duration,
+    refill_interval: Duration,
     max_permits: usize,
+    bucket: parking_lot::Mutex<Bucket>,
+    /// Waiters queue here in arrival order; only the head waits on the bucket
+    queue: TokioMutex<()>,
+    counters: Arc<LimiterCounters>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
 }
 
 impl RateLimiter {
-    /// Create a new rate limiter
-    pub fn new(permits_per_interval: usize, interval: std::time::Duration) -> Self {
-        let semaphore = Arc::new(Semaphore::new(permits_per_interval));
-        let semaphore_clone = semaphore.clone();
-        let max_permits = permits_per_interval;
-
-        // Spawn refill task
-        tokio::spawn(async move {
-            let mut ticker = tokio::time::interval(interval);
-            loop {
-                ticker.tick().await;
-                let available = semaphore_clone.available_permits();
-                if available < max_permits {
-                    semaphore_clone.add_permits(max_permits - available);
-                }
-            }
-        });
+    /// Allow `permits_per_interval` acquisitions per `interval`, all of
+    /// which may be used in a burst
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits_per_interval` is zero.
+    pub fn new(permits_per_interval: usize, interval: Duration) -> Self {
+        Self::with_burst(permits_per_interval, interval, permits_per_interval)
+    }
+
+    /// Allow `permits_per_interval` acquisitions per `interval`, at most
+    /// `burst` of them back to back
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits_per_interval` is zero.
+    pub fn with_burst(permits_per_interval: usize, interval: Duration, burst: usize) -> Self {
+        Self::with_counters(permits_per_interval, interval, burst, Arc::default())
+    }
+
+    fn with_counters(
+        permits_per_interval: usize,
+        interval: Duration,
+        burst: usize,
+        counters: Arc<LimiterCounters>,
+    ) -> Self {
+        assert!(permits_per_interval > 0, "RateLimiter needs at least one permit per interval");
+        let burst = burst.max(1) as f64;
 
         Self {
-            semaphore,
-            refill_interval: interval,
-            max_permits,
+            inner: Arc::new(RateLimiterInner {
+                burst,
+                per_token: interval.div_f64(permits_per_interval as f64),
+                refill_interval: interval,
+                max_permits: permits_per_interval,
+                bucket: parking_lot::Mutex::new(Bucket {
+                    tokens: burst,
+                    updated: Instant::now(),
+                }),
+                queue: TokioMutex::new(()),
+                counters,
+            }),
         }
     }
 
-    /// Acquire a permit (waits if rate limited)
+    /// Tokens in the bucket after refilling it up to now
+    fn refill(&self, bucket: &mut Bucket) -> f64 {
+        let now = Instant::now();
+        let refilled = now.duration_since(bucket.updated).as_secs_f64() / self.inner.per_token.as_secs_f64();
+        bucket.tokens = (bucket.tokens + refilled).min(self.inner.burst);
+        bucket.updated = now;
+        bucket.tokens
+    }
+
+    /// Take `n` tokens, or return how long until the bucket holds them
+    fn take(&self, n: u32) -> Result<(), Duration> {
+        let mut bucket = self.inner.bucket.lock();
+        let tokens = self.refill(&mut bucket);
+
+        // Requests larger than the bucket wait for a full one and go into debt
+        let needed = f64::from(n).min(self.inner.burst);
+        if tokens >= needed {
+            bucket.tokens -= f64::from(n);
+            Ok(())
+        } else {
+            Err(self.inner.per_token.mul_f64(needed - tokens))
+        }
+    }
+
+    /// Whether the bucket is full and nobody else holds this limiter
+    fn is_idle(&self) -> bool {
+        Arc::strong_count(&self.inner) == 1 && self.refill(&mut self.inner.bucket.lock()) >= self.inner.burst
+    }
+
+    /// Acquire a permit, waiting for the bucket to refill if needed
     pub async fn acquire(&self) -> SemaphoreGuard<'_> {
-        self.semaphore.acquire().await
+        self.acquire_n(1).await
+    }
+
+    /// Acquire `n` permits at once
+    pub async fn acquire_n(&self, n: u32) -> SemaphoreGuard<'_> {
+        let started = Instant::now();
+        let mut waited = false;
+
+        // Tokens are only taken once the wait is over, so dropping this
+        // future while queued or asleep leaves the bucket untouched
+        let _turn = match self.inner.queue.try_lock() {
+            Ok(turn) => turn,
+            Err(_) => {
+                waited = true;
+                self.inner.queue.lock().await
+            }
+        };
+        while let Err(wait) = self.take(n) {
+            waited = true;
+            tokio::time::sleep(wait).await;
+        }
+
+        self.inner
+            .counters
+            .record_acquired(if waited { started.elapsed() } else { Duration::ZERO });
+        SemaphoreGuard { inner: None }
+    }
+
+    /// Acquire a permit unless that takes longer than `timeout`
+    pub async fn acquire_timeout(&self, timeout: Duration) -> Result<SemaphoreGuard<'_>, TimeoutError> {
+        match tokio::time::timeout(timeout, self.acquire()).await {
+            Ok(guard) => Ok(guard),
+            Err(_) => {
+                self.inner.counters.record_rejected();
+                Err(TimeoutError::new(timeout))
+            }
+        }
     }
 
-    /// Try to acquire without waiting
+    /// Try to acquire a permit without waiting
     pub fn try_acquire(&self) -> Option<SemaphoreGuard<'_>> {
-        self.semaphore.try_acquire()
+        // Don't jump ahead of queued waiters
+        let acquired = self.inner.queue.try_lock().is_ok_and(|_turn| self.take(1).is_ok());
+        if acquired {
+            self.inner.counters.record_acquired(Duration::ZERO);
+            Some(SemaphoreGuard { inner: None })
+        } else {
+            self.inner.counters.record_rejected();
+            None
+        }
     }
 
     /// Get available permits
     pub fn available(&self) -> usize {
-        self.semaphore.available_permits()
+        self.refill(&mut self.inner.bucket.lock()).max(0.0) as usize
     }
 
     /// Get the refill interval
-    pub fn refill_interval(&self) -> std::time::Duration {
-        self.refill_interval
+    pub fn refill_interval(&self) -> Duration {
+        self.inner.refill_interval
     }
 
-    /// Get max permits
+    /// Get max permits per interval
     pub fn max_permits(&self) -> usize {
-        self.max_permits
+        self.inner.max_permits
+    }
+
+    /// Acquisition counters
+    pub fn stats(&self) -> LimiterStats {
+        self.inner.counters.snapshot()
+    }
+}
+
+/// Rate limiters created on demand per key
+///
+/// Throttles each key separately, e.g. at most 10 calls per second per
+/// upstream host or per API token. Keys whose bucket has refilled and that
+/// nobody is waiting on are dropped as new keys arrive, so the key space may
+/// be unbounded.
+///
+/// ```rust,ignore
+/// let per_token = KeyedRateLimiter::new(10, Duration::from_secs(1));
+/// let _permit = per_token.acquire_timeout(api_token.clone(), Duration::from_secs(2)).await?;
+/// client.get(url).bearer_auth(&api_token).send().await?;
+/// ```
+pub struct KeyedRateLimiter<K> {
+    inner: Arc<KeyedRateLimiterInner<K>>,
+}
+
+struct KeyedRateLimiterInner<K> {
+    permits_per_interval: usize,
+    interval: Duration,
+    burst: usize,
+    buckets: parking_lot::Mutex<KeyedBuckets<K>>,
+    counters: Arc<LimiterCounters>,
+}
+
+struct KeyedBuckets<K> {
+    limiters: HashMap<K, RateLimiter>,
+    /// Key count at which idle keys are next dropped
+    prune_at: usize,
+}
+
+/// Fewest keys before idle ones are dropped
+const KEYED_PRUNE_MIN: usize = 64;
+
+impl<K> Clone for KeyedRateLimiter<K> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedRateLimiter<K> {
+    /// Allow `permits_per_interval` acquisitions per `interval` for each
+    /// key, all of which may be used in a burst
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits_per_interval` is zero.
+    pub fn new(permits_per_interval: usize, interval: Duration) -> Self {
+        Self::with_burst(permits_per_interval, interval, permits_per_interval)
+    }
+
+    /// Allow `permits_per_interval` acquisitions per `interval` for each
+    /// key, at most `burst` of them back to back
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits_per_interval` is zero.
+    pub fn with_burst(permits_per_interval: usize, interval: Duration, burst: usize) -> Self {
+        assert!(permits_per_interval > 0, "KeyedRateLimiter needs at least one permit per interval");
+        Self {
+            inner: Arc::new(KeyedRateLimiterInner {
+                permits_per_interval,
+                interval,
+                burst,
+                buckets: parking_lot::Mutex::new(KeyedBuckets {
+                    limiters: HashMap::new(),
+                    prune_at: KEYED_PRUNE_MIN,
+                }),
+                counters: Arc::default(),
+            }),
+        }
+    }
+
+    fn limiter(&self, key: &K) -> RateLimiter {
+        let inner = &self.inner;
+        let mut buckets = inner.buckets.lock();
+        if let Some(limiter) = buckets.limiters.get(key) {
+            return limiter.clone();
+        }
+
+        let limiter = RateLimiter::with_counters(
+            inner.permits_per_interval,
+            inner.interval,
+            inner.burst,
+            inner.counters.clone(),
+        );
+        buckets.limiters.insert(key.clone(), limiter.clone());
+        if buckets.limiters.len() >= buckets.prune_at {
+            buckets.limiters.retain(|_, limiter| !limiter.is_idle());
+            buckets.prune_at = (buckets.limiters.len() * 2).max(KEYED_PRUNE_MIN);
+        }
+        limiter
+    }
+
+    /// Acquire a permit for `key`, waiting for its bucket to refill if needed
+    pub async fn acquire(&self, key: K) -> SemaphoreGuard<'_> {
+        self.limiter(&key).acquire().await;
+        SemaphoreGuard { inner: None }
+    }
+
+    /// Acquire a permit for `key` unless that takes longer than `timeout`
+    pub async fn acquire_timeout(&self, key: K, timeout: Duration) -> Result<SemaphoreGuard<'_>, TimeoutError> {
+        self.limiter(&key).acquire_timeout(timeout).await?;
+        Ok(SemaphoreGuard { inner: None })
+    }
+
+    /// Try to acquire a permit for `key` without waiting
+    pub fn try_acquire(&self, key: K) -> Option<SemaphoreGuard<'_>> {
+        self.limiter(&key).try_acquire()?;
+        Some(SemaphoreGuard { inner: None })
+    }
+
+    /// Permits currently available for `key`
+    pub fn available(&self, key: &K) -> usize {
+        let limiter = self.inner.buckets.lock().limiters.get(key).cloned();
+        limiter.map_or(self.inner.burst.max(1), |limiter| limiter.available())
+    }
+
+    /// Number of keys currently tracked
+    pub fn active_keys(&self) -> usize {
+        self.inner.buckets.lock().limiters.len()
+    }
+
+    /// Acquisition counters across all keys
+    pub fn stats(&self) -> LimiterStats {
+        self.inner.counters.snapshot()
+    }
+}
+
+/// Semaphores created on demand per key
+///
+/// Limits concurrency separately for each key, e.g. at most two requests
+/// in flight per upstream host or per tenant. Idle keys are dropped, so the
+/// key space may be unbounded.
+///
+/// ```rust,ignore
+/// let per_host = KeyedSemaphore::new(2);
+/// let _permit = per_host.acquire(url.host_str().unwrap().to_string()).await;
+/// client.get(url).send().await?;
+/// ```
+pub struct KeyedSemaphore<K> {
+    inner: Arc<KeyedSemaphoreInner<K>>,
+}
+
+struct KeyedSemaphoreInner<K> {
+    permits: usize,
+    semaphores: parking_lot::Mutex<HashMap<K, Arc<TokioSemaphore>>>,
+    counters: LimiterCounters,
+}
+
+impl<K> Clone for KeyedSemaphore<K> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedSemaphore<K> {
+    /// Allow `permits` concurrent holders per key
+    pub fn new(permits: usize) -> Self {
+        Self {
+            inner: Arc::new(KeyedSemaphoreInner {
+                permits,
+                semaphores: parking_lot::Mutex::new(HashMap::new()),
+                counters: LimiterCounters::default(),
+            }),
+        }
+    }
+
+    fn semaphore(&self, key: &K) -> Arc<TokioSemaphore> {
+        self.inner
+            .semaphores
+            .lock()
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(TokioSemaphore::new(self.inner.permits)))
+            .clone()
+    }
+
+    fn permit(&self, key: K, permit: OwnedSemaphorePermit) -> KeyedPermit<K> {
+        KeyedPermit {
+            key,
+            permit: Some(permit),
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Acquire a permit for `key`
+    pub async fn acquire(&self, key: K) -> KeyedPermit<K> {
+        let semaphore = self.semaphore(&key);
+        if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+            self.inner.counters.record_acquired(Duration::ZERO);
+            return self.permit(key, permit);
+        }
+
+        let started = Instant::now();
+        let permit = semaphore.acquire_owned().await.expect("KeyedSemaphore never closes");
+        self.inner.counters.record_acquired(started.elapsed());
+        self.permit(key, permit)
+    }
+
+    /// Acquire a permit for `key`, giving up after `timeout`
+    pub async fn acquire_timeout(&self, key: K, timeout: Duration) -> Result<KeyedPermit<K>, TimeoutError> {
+        match tokio::time::timeout(timeout, self.acquire(key)).await {
+            Ok(permit) => Ok(permit),
+            Err(_) => {
+                self.inner.counters.record_rejected();
+                Err(TimeoutError::new(timeout))
+            }
+        }
+    }
+
+    /// Try to acquire a permit for `key` without waiting
+    pub fn try_acquire(&self, key: K) -> Option<KeyedPermit<K>> {
+        match self.semaphore(&key).try_acquire_owned() {
+            Ok(permit) => {
+                self.inner.counters.record_acquired(Duration::ZERO);
+                Some(self.permit(key, permit))
+            }
+            Err(_) => {
+                self.inner.counters.record_rejected();
+                None
+            }
+        }
+    }
+
+    /// Permits currently available for `key`
+    pub fn available(&self, key: &K) -> usize {
+        self.inner
+            .semaphores
+            .lock()
+            .get(key)
+            .map_or(self.inner.permits, |semaphore| semaphore.available_permits())
+    }
+
+    /// Number of keys with permits held or waiters queued
+    pub fn active_keys(&self) -> usize {
+        self.inner.semaphores.lock().len()
+    }
+
+    /// Acquisition counters
+    pub fn stats(&self) -> LimiterStats {
+        self.inner.counters.snapshot()
+    }
+}
+
+/// A permit from a [`KeyedSemaphore`], released on drop
+pub struct KeyedPermit<K: Hash + Eq> {
+    key: K,
+    permit: Option<OwnedSemaphorePermit>,
+    inner: Arc<KeyedSemaphoreInner<K>>,
+}
+
+impl<K: Hash + Eq> KeyedPermit<K> {
+    /// The key this permit was acquired for
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K: Hash + Eq> Drop for KeyedPermit<K> {
+    fn drop(&mut self) {
+        drop(self.permit.take());
+
+        // Forget the key once nobody holds or waits on its semaphore; new
+        // acquirers clone it under this lock, so the count can't race
+        let mut semaphores = self.inner.semaphores.lock();
+        if semaphores.get(&self.key).is_some_and(|semaphore| Arc::strong_count(semaphore) == 1) {
+            semaphores.remove(&self.key);
+        }
     }
 }
 
@@ -343,6 +797,95 @@ mod tests {
         assert_eq!(semaphore.available_permits(), 1);
     }
 
+    #[tokio::test]
+    async fn test_rate_limiter_bucket() {
+        let limiter = RateLimiter::with_burst(10, Duration::from_millis(100), 2);
+
+        assert!(limiter.try_acquire().is_some());
+        assert!(limiter.try_acquire().is_some());
+        assert!(limiter.try_acquire().is_none());
+
+        // One token refills every 10ms
+        let started = Instant::now();
+        limiter.acquire().await;
+        assert!(started.elapsed() >= Duration::from_millis(5));
+
+        let stats = limiter.stats();
+        assert_eq!((stats.acquired, stats.waited, stats.rejected), (3, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_timeout_does_not_consume() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(60));
+        limiter.acquire().await;
+
+        assert!(limiter.acquire_timeout(Duration::from_millis(10)).await.is_err());
+        assert_eq!(limiter.stats().rejected, 1);
+        assert_eq!(limiter.available(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_cancelled_acquire_keeps_tokens() {
+        let limiter = RateLimiter::new(1, Duration::from_millis(50));
+        assert!(limiter.try_acquire().is_some());
+
+        // Give up while asleep waiting for the refill
+        assert!(tokio::time::timeout(Duration::from_millis(10), limiter.acquire()).await.is_err());
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(limiter.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_rate_limiter_fractional_refill() {
+        // 3 per second is 333.33ms per token, not a truncated 333ms
+        let limiter = RateLimiter::new(3, Duration::from_secs(1));
+        assert_eq!(limiter.inner.per_token, Duration::from_secs(1).div_f64(3.0));
+        assert_eq!(limiter.available(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one permit")]
+    fn test_rate_limiter_rejects_zero_permits() {
+        RateLimiter::new(0, Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_keyed_rate_limiter() {
+        let limiter = KeyedRateLimiter::new(1, Duration::from_secs(60));
+
+        assert!(limiter.try_acquire("token-a").is_some());
+        assert!(limiter.try_acquire("token-a").is_none());
+        assert!(limiter.try_acquire("token-b").is_some());
+        assert!(limiter.acquire_timeout("token-a", Duration::from_millis(10)).await.is_err());
+
+        assert_eq!(limiter.available(&"token-a"), 0);
+        assert_eq!(limiter.available(&"token-c"), 1);
+        assert_eq!(limiter.active_keys(), 2);
+
+        let stats = limiter.stats();
+        assert_eq!((stats.acquired, stats.rejected), (2, 2));
+    }
+
+    #[tokio::test]
+    async fn test_keyed_semaphore() {
+        let semaphore = KeyedSemaphore::new(1);
+
+        let a = semaphore.acquire("api.example.com").await;
+        assert!(semaphore.try_acquire("api.example.com").is_none());
+        assert!(semaphore.try_acquire("cdn.example.com").is_some());
+        assert!(semaphore
+            .acquire_timeout("api.example.com", Duration::from_millis(10))
+            .await
+            .is_err());
+        assert_eq!(semaphore.active_keys(), 1);
+
+        drop(a);
+        assert_eq!(semaphore.active_keys(), 0);
+        assert_eq!(semaphore.available(&"api.example.com"), 1);
+        assert_eq!(semaphore.stats().rejected, 2);
+    }
+
     #[tokio::test]
     async fn test_barrier() {
         let barrier = Arc::new(Barrier::new(2));
@@ -50,6 +50,34 @@ where
     TaskHandle::new(handle)
 }
 
+/// Spawn a task owned by the current reactive scope
+///
+/// The task is aborted when the scope is cleaned up, so background work
+/// started by a component never outlives it.
+///
+/// ```rust,ignore
+/// #[component]
+/// fn Feed() -> impl IntoView {
+///     let posts = create_signal(Vec::new());
+///     spawn_scoped(async move {
+///         // Aborted if <Feed> unmounts before the fetch finishes
+///         let data = fetch_posts().await;
+///         // ...
+///     });
+///     view! { <ul>...</ul> }
+/// }
+/// ```
+pub fn spawn_scoped<F, T>(future: F) -> TaskHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    let handle = tokio::spawn(future);
+    let abort = handle.abort_handle();
+    philjs::on_cleanup(move || abort.abort());
+    TaskHandle::new(handle)
+}
+
 /// Task handle wrapper
 pub struct TaskHandle<T> {
     handle: JoinHandle<T>,
@@ -326,6 +354,31 @@ mod tests {
         assert_eq!(values, vec![1, 2, 3]);
     }
 
+    #[tokio::test]
+    async fn test_spawn_scoped_aborts_on_cleanup() {
+        use philjs::reactive::utils::with_cleanup_scope;
+
+        let handle = with_cleanup_scope(|| {
+            spawn_scoped(async {
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            })
+        });
+
+        assert!(handle.join().await.unwrap_err().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_spawn_scoped_runs_within_scope() {
+        use philjs::reactive::utils::{pop_cleanup_scope, push_cleanup_scope};
+
+        push_cleanup_scope();
+        let handle = spawn_scoped(async { 42 });
+        let result = handle.join().await.unwrap();
+        pop_cleanup_scope();
+
+        assert_eq!(result, 42);
+    }
+
     #[tokio::test]
     async fn test_scoped_task() {
         let task = ScopedTask::new(async { 42 });
@@ -70,16 +70,21 @@ impl<T> Signal<T> {
         self.value.lock().unwrap().clone()
     }
 
+    /// Set the value and redraw the running app
     pub fn set(&self, value: T) {
         *self.value.lock().unwrap() = value;
+        crate::task::request_redraw();
     }
 
+    /// Modify the value in place and redraw the running app
     pub fn update<F>(&self, f: F)
     where
         F: FnOnce(&mut T),
     {
         let mut guard = self.value.lock().unwrap();
         f(&mut *guard);
+        drop(guard);
+        crate::task::request_redraw();
     }
 }
 
@@ -91,6 +96,12 @@ impl<T> Clone for Signal<T> {
     }
 }
 
+impl<T: Default> Default for Signal<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
 /// Create a new signal
 pub fn create_signal<T>(value: T) -> Signal<T> {
     Signal::new(value)
@@ -3,13 +3,14 @@
 //! Terminal UI components that mirror the PhilJS component model.
 
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text as RatatuiText},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
-use crate::render::View;
+use crate::layout::{center, Flex, Justify, SizeHint};
+use crate::render::{text_hint, View};
 
 /// Text component
 pub struct Text {
@@ -87,6 +88,19 @@ impl View for Text {
 
         frame.render_widget(para, area);
     }
+
+    fn size_hint(&self) -> SizeHint {
+        let hint = text_hint(&self.content);
+        if self.wrap {
+            // Wrapped text takes the width it's given and at least its lines
+            let Constraint::Length(lines) = hint.height else {
+                return hint;
+            };
+            SizeHint::new(Constraint::Fill(1), Constraint::Min(lines))
+        } else {
+            hint
+        }
+    }
 }
 
 /// Block/container component with border
@@ -150,39 +164,43 @@ impl<V: View> View for Container<V> {
         frame.render_widget(block, area);
         self.child.render(frame, inner);
     }
+
+    fn size_hint(&self) -> SizeHint {
+        let edges = |a: Borders, b: Borders| self.borders.contains(a) as u16 + self.borders.contains(b) as u16;
+        self.child
+            .size_hint()
+            .grow(edges(Borders::LEFT, Borders::RIGHT), edges(Borders::TOP, Borders::BOTTOM))
+    }
 }
 
 /// Vertical stack layout
-pub struct VStack {
-    children: Vec<Box<dyn View>>,
-    spacing: u16,
-    constraints: Vec<Constraint>,
-}
+pub struct VStack(Flex);
 
 impl VStack {
     pub fn new() -> Self {
-        VStack {
-            children: Vec::new(),
-            spacing: 0,
-            constraints: Vec::new(),
-        }
+        VStack(Flex::column())
     }
 
-    pub fn child<V: View + 'static>(mut self, child: V) -> Self {
-        self.children.push(Box::new(child));
-        self.constraints.push(Constraint::Min(1));
-        self
+    /// Add a child sized by its own hint
+    pub fn child<V: View + 'static>(self, child: V) -> Self {
+        VStack(self.0.child(child))
     }
 
-    pub fn child_sized<V: View + 'static>(mut self, child: V, constraint: Constraint) -> Self {
-        self.children.push(Box::new(child));
-        self.constraints.push(constraint);
-        self
+    pub fn child_sized<V: View + 'static>(self, child: V, constraint: Constraint) -> Self {
+        VStack(self.0.child_sized(child, constraint))
     }
 
-    pub fn spacing(mut self, spacing: u16) -> Self {
-        self.spacing = spacing;
-        self
+    /// Rows between children
+    pub fn spacing(self, spacing: u16) -> Self {
+        VStack(self.0.gap(spacing))
+    }
+
+    pub fn margin(self, margin: u16) -> Self {
+        VStack(self.0.margin(margin))
+    }
+
+    pub fn justify(self, justify: Justify) -> Self {
+        VStack(self.0.justify(justify))
     }
 }
 
@@ -194,55 +212,42 @@ impl Default for VStack {
 
 impl View for VStack {
     fn render(&self, frame: &mut Frame, area: Rect) {
-        if self.children.is_empty() {
-            return;
-        }
-
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(0)
-            .constraints(&self.constraints)
-            .split(area);
+        self.0.render(frame, area);
+    }
 
-        for (i, child) in self.children.iter().enumerate() {
-            if i < chunks.len() {
-                child.render(frame, chunks[i]);
-            }
-        }
+    fn size_hint(&self) -> SizeHint {
+        self.0.size_hint()
     }
 }
 
 /// Horizontal stack layout
-pub struct HStack {
-    children: Vec<Box<dyn View>>,
-    spacing: u16,
-    constraints: Vec<Constraint>,
-}
+pub struct HStack(Flex);
 
 impl HStack {
     pub fn new() -> Self {
-        HStack {
-            children: Vec::new(),
-            spacing: 0,
-            constraints: Vec::new(),
-        }
+        HStack(Flex::row())
     }
 
-    pub fn child<V: View + 'static>(mut self, child: V) -> Self {
-        self.children.push(Box::new(child));
-        self.constraints.push(Constraint::Min(1));
-        self
+    /// Add a child sized by its own hint
+    pub fn child<V: View + 'static>(self, child: V) -> Self {
+        HStack(self.0.child(child))
     }
 
-    pub fn child_sized<V: View + 'static>(mut self, child: V, constraint: Constraint) -> Self {
-        self.children.push(Box::new(child));
-        self.constraints.push(constraint);
-        self
+    pub fn child_sized<V: View + 'static>(self, child: V, constraint: Constraint) -> Self {
+        HStack(self.0.child_sized(child, constraint))
     }
 
-    pub fn spacing(mut self, spacing: u16) -> Self {
-        self.spacing = spacing;
-        self
+    /// Columns between children
+    pub fn spacing(self, spacing: u16) -> Self {
+        HStack(self.0.gap(spacing))
+    }
+
+    pub fn margin(self, margin: u16) -> Self {
+        HStack(self.0.margin(margin))
+    }
+
+    pub fn justify(self, justify: Justify) -> Self {
+        HStack(self.0.justify(justify))
     }
 }
 
@@ -254,21 +259,11 @@ impl Default for HStack {
 
 impl View for HStack {
     fn render(&self, frame: &mut Frame, area: Rect) {
-        if self.children.is_empty() {
-            return;
-        }
-
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .margin(0)
-            .constraints(&self.constraints)
-            .split(area);
+        self.0.render(frame, area);
+    }
 
-        for (i, child) in self.children.iter().enumerate() {
-            if i < chunks.len() {
-                child.render(frame, chunks[i]);
-            }
-        }
+    fn size_hint(&self) -> SizeHint {
+        self.0.size_hint()
     }
 }
 
@@ -291,6 +286,10 @@ impl View for Spacer {
     fn render(&self, _frame: &mut Frame, _area: Rect) {
         // Spacer renders nothing, just takes space
     }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::FILL
+    }
 }
 
 /// Empty view
@@ -298,6 +297,10 @@ pub struct Empty;
 
 impl View for Empty {
     fn render(&self, _frame: &mut Frame, _area: Rect) {}
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::new(Constraint::Length(0), Constraint::Length(0))
+    }
 }
 
 /// Centered content
@@ -313,9 +316,16 @@ impl<V: View> Center<V> {
 
 impl<V: View> View for Center<V> {
     fn render(&self, frame: &mut Frame, area: Rect) {
-        // For now, just render in full area
-        // Real implementation would center the content
-        self.child.render(frame, area);
+        // Fixed-size children are centered; flexible ones get the full area
+        let extent = |constraint: Constraint, available: u16| match constraint {
+            Constraint::Length(n) | Constraint::Max(n) => n.min(available),
+            Constraint::Percentage(p) => (available as u32 * p.min(100) as u32 / 100) as u16,
+            _ => available,
+        };
+        let hint = self.child.size_hint();
+        let width = extent(hint.width, area.width);
+        let height = extent(hint.height, area.height);
+        self.child.render(frame, center(area, width, height));
     }
 }
 
@@ -370,4 +380,10 @@ impl<V: View> View for Padding<V> {
         };
         self.child.render(frame, inner);
     }
+
+    fn size_hint(&self) -> SizeHint {
+        self.child
+            .size_hint()
+            .grow(self.left + self.right, self.top + self.bottom)
+    }
 }
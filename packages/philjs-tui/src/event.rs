@@ -12,6 +12,8 @@ pub enum Event {
     Mouse(MouseEvent),
     /// Terminal resize
     Resize(u16, u16),
+    /// Bracketed paste (requires `TuiConfig::paste`)
+    Paste(String),
     /// Tick (for animations/updates)
     Tick,
 }
@@ -33,6 +35,7 @@ impl EventHandler {
                 CrosstermEvent::Key(key) => Ok(Event::Key(key)),
                 CrosstermEvent::Mouse(mouse) => Ok(Event::Mouse(mouse)),
                 CrosstermEvent::Resize(w, h) => Ok(Event::Resize(w, h)),
+                CrosstermEvent::Paste(text) => Ok(Event::Paste(text)),
                 _ => Ok(Event::Tick),
             }
         } else {
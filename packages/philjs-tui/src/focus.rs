@@ -0,0 +1,262 @@
+//! Keyboard focus for PhilJS TUI
+//!
+//! Views are rebuilt every frame, so focusable widgets claim a slot while
+//! rendering, in render order. A slot keeps the widget's editing state (cursor,
+//! open/closed, ...) between frames and holds the handler that receives key
+//! events while the slot has focus. Tab and Shift-Tab move focus between
+//! slots.
+//!
+//! Overlays such as modals call [`trap`] before rendering their content, so
+//! focus stays on the slots inside them until they close.
+
+use crate::event::Event;
+use crossterm::event::{KeyCode, KeyEventKind};
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Handler = Rc<dyn Fn(&Event) -> bool>;
+
+struct Slot {
+    state: Rc<dyn Any>,
+    handler: Option<Handler>,
+}
+
+struct Trap {
+    /// First slot inside the trap
+    start: usize,
+    /// Gets events no slot inside the trap consumed
+    handler: Handler,
+}
+
+#[derive(Default)]
+struct FocusRing {
+    slots: Vec<Slot>,
+    /// Slots claimed so far this frame
+    claimed: usize,
+    focused: usize,
+    trap: Option<Trap>,
+    /// Focus to go back to once the trap lifts
+    saved: Option<usize>,
+}
+
+impl FocusRing {
+    /// First slot focus can move to
+    fn floor(&self) -> usize {
+        self.trap.as_ref().map_or(0, |trap| trap.start)
+    }
+}
+
+thread_local! {
+    static RING: RefCell<FocusRing> = RefCell::new(FocusRing::default());
+}
+
+/// A claimed focus slot
+pub struct Focusable<S> {
+    index: usize,
+    focused: bool,
+    state: Rc<RefCell<S>>,
+}
+
+impl<S: 'static> Focusable<S> {
+    /// Claim the next slot, reusing its state from the last frame
+    ///
+    /// State is reset when a different kind of widget now renders in
+    /// this position.
+    pub fn claim() -> Self
+    where
+        S: Default,
+    {
+        RING.with(|ring| {
+            let mut ring = ring.borrow_mut();
+            let index = ring.claimed;
+            ring.claimed += 1;
+
+            let existing = ring
+                .slots
+                .get(index)
+                .and_then(|slot| Rc::clone(&slot.state).downcast::<RefCell<S>>().ok());
+            let state = existing.unwrap_or_else(|| {
+                let state = Rc::new(RefCell::new(S::default()));
+                let slot = Slot {
+                    state: state.clone(),
+                    handler: None,
+                };
+                if index < ring.slots.len() {
+                    ring.slots[index] = slot;
+                } else {
+                    ring.slots.push(slot);
+                }
+                state
+            });
+
+            Focusable {
+                index,
+                focused: ring.focused == index,
+                state,
+            }
+        })
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn state(&self) -> Rc<RefCell<S>> {
+        Rc::clone(&self.state)
+    }
+
+    /// Set the handler for events while this slot has focus
+    ///
+    /// The handler returns whether it consumed the event. Slots without a
+    /// handler (e.g. disabled widgets) are skipped by Tab.
+    pub fn on_event<F>(&self, handler: F)
+    where
+        F: Fn(&Event) -> bool + 'static,
+    {
+        RING.with(|ring| {
+            if let Some(slot) = ring.borrow_mut().slots.get_mut(self.index) {
+                slot.handler = Some(Rc::new(handler));
+            }
+        });
+    }
+
+    /// Move focus to this slot
+    pub fn focus(&self) {
+        set_focus(self.index);
+    }
+}
+
+/// Start claiming slots from the first one (called before each frame)
+pub fn begin_frame() {
+    RING.with(|ring| {
+        let mut ring = ring.borrow_mut();
+        ring.claimed = 0;
+        ring.trap = None;
+        for slot in &mut ring.slots {
+            slot.handler = None;
+        }
+    });
+}
+
+/// Keep focus on slots claimed from here on for the rest of this frame
+///
+/// Events no slot inside consumes go to `handler` (e.g. Esc to dismiss).
+/// Focus returns where it was once a frame renders without a trap.
+pub fn trap<F>(handler: F)
+where
+    F: Fn(&Event) -> bool + 'static,
+{
+    RING.with(|ring| {
+        let mut ring = ring.borrow_mut();
+        let start = ring.claimed;
+        if ring.saved.is_none() {
+            ring.saved = Some(ring.focused);
+        }
+        if ring.focused < start {
+            ring.focused = start;
+        }
+        ring.trap = Some(Trap {
+            start,
+            handler: Rc::new(handler),
+        });
+    });
+}
+
+/// Drop slots no longer rendered (called after each frame)
+pub fn end_frame() {
+    RING.with(|ring| {
+        let mut ring = ring.borrow_mut();
+        let claimed = ring.claimed;
+        ring.slots.truncate(claimed);
+        if ring.trap.is_none() {
+            if let Some(saved) = ring.saved.take() {
+                ring.focused = saved;
+            }
+        }
+        if ring.focused >= claimed {
+            ring.focused = claimed.saturating_sub(1).max(ring.floor());
+        }
+    });
+}
+
+/// Index of the focused slot, if anything is focusable
+pub fn focused() -> Option<usize> {
+    RING.with(|ring| {
+        let ring = ring.borrow();
+        (!ring.slots.is_empty()).then_some(ring.focused)
+    })
+}
+
+pub fn set_focus(index: usize) {
+    RING.with(|ring| {
+        let mut ring = ring.borrow_mut();
+        if index < ring.slots.len() && index >= ring.floor() {
+            ring.focused = index;
+        }
+    });
+}
+
+/// Focus the next slot that accepts events
+pub fn focus_next() {
+    step_focus(1);
+}
+
+/// Focus the previous slot that accepts events
+pub fn focus_prev() {
+    step_focus(-1);
+}
+
+fn step_focus(direction: isize) {
+    RING.with(|ring| {
+        let mut ring = ring.borrow_mut();
+        let floor = ring.floor() as isize;
+        let len = ring.slots.len() as isize - floor;
+        let mut index = ring.focused as isize - floor;
+        for _ in 0..len {
+            index = (index + direction).rem_euclid(len);
+            if ring.slots[(floor + index) as usize].handler.is_some() {
+                ring.focused = (floor + index) as usize;
+                return;
+            }
+        }
+    });
+}
+
+/// Send an event to the focused widget
+///
+/// Tab and Shift-Tab move focus if the widget doesn't consume them. Returns
+/// whether the event was handled.
+pub fn dispatch(event: &Event) -> bool {
+    if let Event::Key(key) = event {
+        if key.kind == KeyEventKind::Release {
+            return false;
+        }
+    }
+
+    // Clone the handlers out so they can touch the ring (e.g. to move focus)
+    let (handler, trap) = RING.with(|ring| {
+        let ring = ring.borrow();
+        let handler = ring
+            .slots
+            .get(ring.focused)
+            .filter(|_| ring.focused >= ring.floor())
+            .and_then(|slot| slot.handler.clone());
+        (handler, ring.trap.as_ref().map(|trap| trap.handler.clone()))
+    });
+    if handler.is_some_and(|handler| handler(event)) {
+        return true;
+    }
+
+    match event {
+        Event::Key(key) if key.code == KeyCode::Tab => {
+            focus_next();
+            true
+        }
+        Event::Key(key) if key.code == KeyCode::BackTab => {
+            focus_prev();
+            true
+        }
+        _ => trap.is_some_and(|trap| trap(event)),
+    }
+}
@@ -0,0 +1,1243 @@
+//! Form widgets for PhilJS TUI
+//!
+//! Interactive inputs that take keyboard focus and edit a bound signal:
+//!
+//! ```rust,ignore
+//! let name = create_signal(String::new());
+//! let subscribe = create_signal(false);
+//!
+//! VStack::new()
+//!     .child(
+//!         TextInput::new()
+//!             .label("Name")
+//!             .bind(name.clone())
+//!             .validate(|v: &String| if v.is_empty() { Err("Required".into()) } else { Ok(()) }),
+//!     )
+//!     .child(Checkbox::new("Subscribe").bind(subscribe.clone()))
+//! ```
+//!
+//! Widgets without a bound signal keep their value in their focus slot (see
+//! [`crate::focus`]). Validation errors show once the user has changed the
+//! value.
+
+use crate::app::Signal;
+use crate::event::Event;
+use crate::focus::Focusable;
+use crate::layout::SizeHint;
+use crate::render::View;
+use crate::style::styles;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        block::{Position, Title},
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph,
+    },
+    Frame,
+};
+use std::rc::Rc;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Checks a value, returning the message to show when it's invalid
+pub type Validator<T> = Rc<dyn Fn(&T) -> Result<(), String>>;
+
+type Callback<T> = Rc<dyn Fn(&T)>;
+
+fn check<T>(validator: &Option<Validator<T>>, value: &T) -> Result<(), String> {
+    validator.as_ref().map_or(Ok(()), |validate| validate(value))
+}
+
+/// Bordered block shared by the boxed fields
+fn field_block<'a>(label: Option<&'a str>, focused: bool, error: Option<&'a str>) -> Block<'a> {
+    let border_style = if error.is_some() {
+        styles::error()
+    } else if focused {
+        styles::focused_border()
+    } else {
+        styles::border()
+    };
+
+    let mut block = Block::default().borders(Borders::ALL).border_style(border_style);
+    if let Some(label) = label {
+        block = block.title(label);
+    }
+    if let Some(error) = error {
+        block = block.title(Title::from(Span::styled(error, styles::error())).position(Position::Bottom));
+    }
+    block
+}
+
+// ============================================================================
+// TextInput
+// ============================================================================
+
+#[derive(Default)]
+struct TextInputState {
+    value: Signal<String>,
+    /// Cursor position in chars
+    cursor: usize,
+    /// First visible char, for horizontal scrolling
+    offset: usize,
+    touched: bool,
+}
+
+/// Single-line text field
+///
+/// Editing keys: arrows, Home/End, Backspace/Delete, and the readline
+/// bindings Ctrl-A/E (start/end), Ctrl-B/F (left/right), Alt-B/F and
+/// Ctrl-arrows (by word), Ctrl-W and Alt-Backspace (delete word), Ctrl-U and
+/// Ctrl-K (delete to start/end). Enter submits.
+pub struct TextInput {
+    value: Option<Signal<String>>,
+    label: Option<String>,
+    placeholder: Option<String>,
+    mask: Option<char>,
+    max_length: Option<usize>,
+    disabled: bool,
+    validator: Option<Validator<String>>,
+    on_change: Option<Callback<String>>,
+    on_submit: Option<Callback<String>>,
+    style: Style,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        TextInput {
+            value: None,
+            label: None,
+            placeholder: None,
+            mask: None,
+            max_length: None,
+            disabled: false,
+            validator: None,
+            on_change: None,
+            on_submit: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Two-way bind the text to a signal
+    pub fn bind(mut self, value: Signal<String>) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Hide the text behind `•`
+    pub fn password(self) -> Self {
+        self.mask('•')
+    }
+
+    pub fn mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Maximum length in chars
+    pub fn max_length(mut self, max: usize) -> Self {
+        self.max_length = Some(max);
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn validate<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&String) -> Result<(), String> + 'static,
+    {
+        self.validator = Some(Rc::new(validator));
+        self
+    }
+
+    pub fn on_change<F: Fn(&String) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Rc::new(f));
+        self
+    }
+
+    pub fn on_submit<F: Fn(&String) + 'static>(mut self, f: F) -> Self {
+        self.on_submit = Some(Rc::new(f));
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Whether the bound value passes validation
+    pub fn is_valid(&self) -> bool {
+        self.value
+            .as_ref()
+            .map_or(true, |value| check(&self.validator, &value.get()).is_ok())
+    }
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for TextInput {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let slot = Focusable::<TextInputState>::claim();
+        let state = slot.state();
+        let value = self.value.clone().unwrap_or_else(|| state.borrow().value.clone());
+        let focused = slot.is_focused() && !self.disabled;
+
+        if !self.disabled {
+            let value = value.clone();
+            let state = state.clone();
+            let max_length = self.max_length;
+            let on_change = self.on_change.clone();
+            let on_submit = self.on_submit.clone();
+            slot.on_event(move |event| {
+                let mut text = value.get();
+                let mut state = state.borrow_mut();
+                state.cursor = state.cursor.min(text.chars().count());
+
+                let edit = match event {
+                    Event::Key(key) => edit_line(&mut text, &mut state.cursor, key, max_length),
+                    Event::Paste(pasted) => {
+                        let line: String = pasted.chars().filter(|c| !c.is_control()).collect();
+                        insert_str(&mut text, &mut state.cursor, &line, max_length);
+                        Edit::Changed
+                    }
+                    _ => Edit::Ignored,
+                };
+
+                match edit {
+                    Edit::Changed => {
+                        state.touched = true;
+                        drop(state);
+                        value.set(text.clone());
+                        if let Some(f) = &on_change {
+                            f(&text);
+                        }
+                        true
+                    }
+                    Edit::Moved => true,
+                    Edit::Submit => {
+                        state.touched = true;
+                        drop(state);
+                        if let Some(f) = &on_submit {
+                            f(&text);
+                        }
+                        true
+                    }
+                    Edit::Ignored => false,
+                }
+            });
+        }
+
+        let text = value.get();
+        let mut state = state.borrow_mut();
+        let error = if state.touched { check(&self.validator, &text).err() } else { None };
+        let block = field_block(self.label.as_deref(), focused, error.as_deref());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if text.is_empty() {
+            let placeholder = self.placeholder.as_deref().unwrap_or_default();
+            frame.render_widget(
+                Paragraph::new(placeholder).style(self.style.patch(styles::subtitle())),
+                inner,
+            );
+            if focused {
+                frame.set_cursor(inner.x, inner.y);
+            }
+            return;
+        }
+
+        let chars: Vec<char> = match self.mask {
+            Some(mask) => text.chars().map(|_| mask).collect(),
+            None => text.chars().collect(),
+        };
+        state.cursor = state.cursor.min(chars.len());
+
+        // Scroll so the cursor stays inside the field
+        let width = inner.width as usize;
+        let span_width = |from: usize, to: usize| -> usize {
+            chars[from..to].iter().map(|c| c.width().unwrap_or(0)).sum()
+        };
+        state.offset = state.offset.min(state.cursor);
+        while state.offset < state.cursor && span_width(state.offset, state.cursor) >= width {
+            state.offset += 1;
+        }
+
+        let mut used = 0;
+        let visible: String = chars[state.offset..]
+            .iter()
+            .take_while(|c| {
+                used += c.width().unwrap_or(0);
+                used <= width
+            })
+            .collect();
+
+        let style = if self.disabled {
+            self.style.patch(styles::subtitle())
+        } else {
+            self.style
+        };
+        frame.render_widget(Paragraph::new(visible).style(style), inner);
+
+        if focused {
+            let x = inner.x + span_width(state.offset, state.cursor) as u16;
+            frame.set_cursor(x.min(inner.right().saturating_sub(1)), inner.y);
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::rows(3)
+    }
+}
+
+enum Edit {
+    Changed,
+    Moved,
+    Submit,
+    Ignored,
+}
+
+fn byte_index(text: &str, cursor: usize) -> usize {
+    text.char_indices().nth(cursor).map_or(text.len(), |(i, _)| i)
+}
+
+fn insert_str(text: &mut String, cursor: &mut usize, insert: &str, max_length: Option<usize>) {
+    let room = max_length.map_or(usize::MAX, |max| max.saturating_sub(text.chars().count()));
+    let insert: String = insert.chars().take(room).collect();
+    text.insert_str(byte_index(text, *cursor), &insert);
+    *cursor += insert.chars().count();
+}
+
+/// Start of the word before the cursor
+fn word_start(text: &str, cursor: usize) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = cursor;
+    while i > 0 && !chars[i - 1].is_alphanumeric() {
+        i -= 1;
+    }
+    while i > 0 && chars[i - 1].is_alphanumeric() {
+        i -= 1;
+    }
+    i
+}
+
+/// End of the word after the cursor
+fn word_end(text: &str, cursor: usize) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = cursor;
+    while i < chars.len() && !chars[i].is_alphanumeric() {
+        i += 1;
+    }
+    while i < chars.len() && chars[i].is_alphanumeric() {
+        i += 1;
+    }
+    i
+}
+
+fn remove_range(text: &mut String, from: usize, to: usize) {
+    let (start, end) = (byte_index(text, from), byte_index(text, to));
+    text.replace_range(start..end, "");
+}
+
+fn edit_line(text: &mut String, cursor: &mut usize, key: &KeyEvent, max_length: Option<usize>) -> Edit {
+    let len = text.chars().count();
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+    let moved_to = |to: usize, cursor: &mut usize| {
+        *cursor = to;
+        Edit::Moved
+    };
+
+    match key.code {
+        KeyCode::Enter => Edit::Submit,
+
+        // Movement
+        KeyCode::Home | KeyCode::Char('a') if ctrl || key.code == KeyCode::Home => moved_to(0, cursor),
+        KeyCode::End | KeyCode::Char('e') if ctrl || key.code == KeyCode::End => moved_to(len, cursor),
+        KeyCode::Left if ctrl || alt => moved_to(word_start(text, *cursor), cursor),
+        KeyCode::Right if ctrl || alt => moved_to(word_end(text, *cursor), cursor),
+        KeyCode::Char('b') if alt => moved_to(word_start(text, *cursor), cursor),
+        KeyCode::Char('f') if alt => moved_to(word_end(text, *cursor), cursor),
+        KeyCode::Left | KeyCode::Char('b') if ctrl || key.code == KeyCode::Left => {
+            moved_to(cursor.saturating_sub(1), cursor)
+        }
+        KeyCode::Right | KeyCode::Char('f') if ctrl || key.code == KeyCode::Right => {
+            moved_to((*cursor + 1).min(len), cursor)
+        }
+
+        // Deletion
+        KeyCode::Backspace if alt || ctrl => {
+            let start = word_start(text, *cursor);
+            remove_range(text, start, *cursor);
+            *cursor = start;
+            Edit::Changed
+        }
+        KeyCode::Char('w') if ctrl => {
+            let start = word_start(text, *cursor);
+            remove_range(text, start, *cursor);
+            *cursor = start;
+            Edit::Changed
+        }
+        KeyCode::Char('u') if ctrl => {
+            remove_range(text, 0, *cursor);
+            *cursor = 0;
+            Edit::Changed
+        }
+        KeyCode::Char('k') if ctrl => {
+            remove_range(text, *cursor, len);
+            Edit::Changed
+        }
+        KeyCode::Backspace if *cursor > 0 => {
+            remove_range(text, *cursor - 1, *cursor);
+            *cursor -= 1;
+            Edit::Changed
+        }
+        KeyCode::Delete | KeyCode::Char('d') if (ctrl || key.code == KeyCode::Delete) && *cursor < len => {
+            remove_range(text, *cursor, *cursor + 1);
+            Edit::Changed
+        }
+        // Swallow no-op edits at the ends of the text
+        KeyCode::Backspace | KeyCode::Delete => Edit::Moved,
+
+        KeyCode::Char(c) if !ctrl && !alt => {
+            insert_str(text, cursor, c.encode_utf8(&mut [0; 4]), max_length);
+            Edit::Changed
+        }
+        _ => Edit::Ignored,
+    }
+}
+
+// ============================================================================
+// Select
+// ============================================================================
+
+#[derive(Default)]
+struct SelectState {
+    selected: Signal<usize>,
+    open: bool,
+    highlighted: usize,
+    touched: bool,
+}
+
+/// Dropdown picking one of several options
+///
+/// Enter or Space opens the list; arrows move, Enter or Space picks, Esc
+/// closes. While closed, Left/Right step through the options. Typing a
+/// letter jumps to the next option starting with it.
+pub struct Select {
+    options: Vec<String>,
+    selected: Option<Signal<usize>>,
+    label: Option<String>,
+    max_visible: u16,
+    disabled: bool,
+    validator: Option<Validator<usize>>,
+    on_change: Option<Callback<usize>>,
+    style: Style,
+    /// Defaults to the theme's `highlight`
+    highlight_style: Option<Style>,
+}
+
+impl Select {
+    pub fn new<I, S>(options: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Select {
+            options: options.into_iter().map(Into::into).collect(),
+            selected: None,
+            label: None,
+            max_visible: 8,
+            disabled: false,
+            validator: None,
+            on_change: None,
+            style: Style::default(),
+            highlight_style: None,
+        }
+    }
+
+    /// Two-way bind the selected index to a signal
+    pub fn bind(mut self, selected: Signal<usize>) -> Self {
+        self.selected = Some(selected);
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Rows shown when the list is open
+    pub fn max_visible(mut self, rows: u16) -> Self {
+        self.max_visible = rows.max(1);
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn validate<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&usize) -> Result<(), String> + 'static,
+    {
+        self.validator = Some(Rc::new(validator));
+        self
+    }
+
+    pub fn on_change<F: Fn(&usize) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Rc::new(f));
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = Some(style);
+        self
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.selected
+            .as_ref()
+            .map_or(true, |selected| check(&self.validator, &selected.get()).is_ok())
+    }
+}
+
+impl View for Select {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let slot = Focusable::<SelectState>::claim();
+        let state = slot.state();
+        let selected = self.selected.clone().unwrap_or_else(|| state.borrow().selected.clone());
+        let focused = slot.is_focused() && !self.disabled;
+        let count = self.options.len();
+
+        if !self.disabled && count > 0 {
+            let selected = selected.clone();
+            let state = state.clone();
+            let options = self.options.clone();
+            let on_change = self.on_change.clone();
+            slot.on_event(move |event| {
+                let Event::Key(key) = event else {
+                    return false;
+                };
+                let mut state = state.borrow_mut();
+                let current = selected.get().min(count - 1);
+
+                let pick = |index: usize, state: &mut SelectState| {
+                    state.open = false;
+                    state.touched = true;
+                    if index != selected.get() {
+                        selected.set(index);
+                        if let Some(f) = &on_change {
+                            f(&index);
+                        }
+                    }
+                };
+                let jump = |from: usize, c: char| {
+                    let c = c.to_lowercase().next().unwrap_or(c);
+                    (1..=count).map(|step| (from + step) % count).find(|&i| {
+                        options[i].chars().next().and_then(|f| f.to_lowercase().next()) == Some(c)
+                    })
+                };
+
+                if state.open {
+                    match key.code {
+                        KeyCode::Up => state.highlighted = state.highlighted.checked_sub(1).unwrap_or(count - 1),
+                        KeyCode::Down => state.highlighted = (state.highlighted + 1) % count,
+                        KeyCode::Home => state.highlighted = 0,
+                        KeyCode::End => state.highlighted = count - 1,
+                        KeyCode::Enter | KeyCode::Char(' ') => {
+                            let index = state.highlighted;
+                            pick(index, &mut *state);
+                        }
+                        KeyCode::Esc => state.open = false,
+                        KeyCode::Tab | KeyCode::BackTab => {
+                            // Close and let focus move on
+                            state.open = false;
+                            return false;
+                        }
+                        KeyCode::Char(c) => match jump(state.highlighted, c) {
+                            Some(i) => state.highlighted = i,
+                            None => return false,
+                        },
+                        _ => return false,
+                    }
+                    return true;
+                }
+
+                match key.code {
+                    KeyCode::Enter | KeyCode::Char(' ') => {
+                        state.open = true;
+                        state.highlighted = current;
+                    }
+                    KeyCode::Left => pick(current.checked_sub(1).unwrap_or(count - 1), &mut *state),
+                    KeyCode::Right => pick((current + 1) % count, &mut *state),
+                    KeyCode::Char(c) => match jump(current, c) {
+                        Some(i) => pick(i, &mut *state),
+                        None => return false,
+                    },
+                    _ => return false,
+                }
+                true
+            });
+        }
+
+        let mut state = state.borrow_mut();
+        if !focused {
+            state.open = false;
+        }
+        let index = selected.get();
+        let error = if state.touched { check(&self.validator, &index).err() } else { None };
+        let block = field_block(self.label.as_deref(), focused, error.as_deref());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let current = self.options.get(index).map(String::as_str).unwrap_or_default();
+        let arrow = if state.open { "▴" } else { "▾" };
+        let text_width = (inner.width as usize).saturating_sub(2);
+        let style = if self.disabled {
+            self.style.patch(styles::subtitle())
+        } else {
+            self.style
+        };
+        let line = Line::from(vec![
+            Span::styled(format!("{:<width$}", current, width = text_width), style),
+            Span::styled(format!(" {}", arrow), styles::subtitle()),
+        ]);
+        frame.render_widget(Paragraph::new(line), inner);
+
+        if !state.open {
+            return;
+        }
+
+        // The open list draws over whatever follows the field, below it when
+        // there's room and above it otherwise
+        let screen = frame.size();
+        let height = (count as u16).min(self.max_visible) + 2;
+        let below = screen.bottom().saturating_sub(area.bottom());
+        let y = if below >= height || below >= area.top().saturating_sub(screen.top()) {
+            area.bottom()
+        } else {
+            area.top().saturating_sub(height)
+        };
+        let popup = Rect::new(area.x, y, area.width, height).intersection(screen);
+
+        let items: Vec<ListItem> = self.options.iter().map(|o| ListItem::new(o.as_str())).collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).border_style(styles::focused_border()))
+            .style(self.style)
+            .highlight_style(self.highlight_style.unwrap_or_else(styles::highlight))
+            .highlight_symbol("> ");
+        let mut list_state = ListState::default();
+        list_state.select(Some(state.highlighted));
+
+        frame.render_widget(Clear, popup);
+        frame.render_stateful_widget(list, popup, &mut list_state);
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::rows(3)
+    }
+}
+
+// ============================================================================
+// Checkbox
+// ============================================================================
+
+#[derive(Default)]
+struct CheckboxState {
+    checked: Signal<bool>,
+    touched: bool,
+}
+
+/// Labelled on/off toggle; Space or Enter toggles
+pub struct Checkbox {
+    label: String,
+    checked: Option<Signal<bool>>,
+    disabled: bool,
+    validator: Option<Validator<bool>>,
+    on_change: Option<Callback<bool>>,
+    style: Style,
+}
+
+impl Checkbox {
+    pub fn new(label: impl Into<String>) -> Self {
+        Checkbox {
+            label: label.into(),
+            checked: None,
+            disabled: false,
+            validator: None,
+            on_change: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Two-way bind the checked state to a signal
+    pub fn bind(mut self, checked: Signal<bool>) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn validate<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&bool) -> Result<(), String> + 'static,
+    {
+        self.validator = Some(Rc::new(validator));
+        self
+    }
+
+    pub fn on_change<F: Fn(&bool) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Rc::new(f));
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.checked
+            .as_ref()
+            .map_or(true, |checked| check(&self.validator, &checked.get()).is_ok())
+    }
+}
+
+impl View for Checkbox {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let slot = Focusable::<CheckboxState>::claim();
+        let state = slot.state();
+        let checked = self.checked.clone().unwrap_or_else(|| state.borrow().checked.clone());
+        let focused = slot.is_focused() && !self.disabled;
+
+        if !self.disabled {
+            let checked = checked.clone();
+            let state = state.clone();
+            let on_change = self.on_change.clone();
+            slot.on_event(move |event| match event {
+                Event::Key(key) if matches!(key.code, KeyCode::Char(' ') | KeyCode::Enter) => {
+                    let value = !checked.get();
+                    checked.set(value);
+                    state.borrow_mut().touched = true;
+                    if let Some(f) = &on_change {
+                        f(&value);
+                    }
+                    true
+                }
+                _ => false,
+            });
+        }
+
+        let value = checked.get();
+        let mark = if value { "[x] " } else { "[ ] " };
+        let style = if self.disabled {
+            self.style.patch(styles::subtitle())
+        } else if focused {
+            self.style.patch(styles::focused_border()).add_modifier(Modifier::BOLD)
+        } else {
+            self.style
+        };
+
+        let mut spans = vec![Span::styled(mark, style), Span::styled(self.label.as_str(), style)];
+        if state.borrow().touched {
+            if let Err(error) = check(&self.validator, &value) {
+                spans.push(Span::styled(format!("  {}", error), styles::error()));
+            }
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::rows(1)
+    }
+}
+
+// ============================================================================
+// Slider
+// ============================================================================
+
+#[derive(Default)]
+struct SliderState {
+    value: Signal<f64>,
+    touched: bool,
+}
+
+/// Numeric value on a track
+///
+/// Left/Right (or h/l) step, PageDown/PageUp move ten steps, Home/End jump
+/// to the ends.
+pub struct Slider {
+    value: Option<Signal<f64>>,
+    label: Option<String>,
+    min: f64,
+    max: f64,
+    step: f64,
+    disabled: bool,
+    format: Option<Rc<dyn Fn(f64) -> String>>,
+    validator: Option<Validator<f64>>,
+    on_change: Option<Callback<f64>>,
+    style: Style,
+}
+
+impl Slider {
+    pub fn new(min: f64, max: f64) -> Self {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        Slider {
+            value: None,
+            label: None,
+            min,
+            max,
+            step: if max > min { (max - min) / 100.0 } else { 1.0 },
+            disabled: false,
+            format: None,
+            validator: None,
+            on_change: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Two-way bind the value to a signal
+    pub fn bind(mut self, value: Signal<f64>) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn step(mut self, step: f64) -> Self {
+        if step > 0.0 {
+            self.step = step;
+        }
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Format the value shown after the track
+    pub fn format<F: Fn(f64) -> String + 'static>(mut self, f: F) -> Self {
+        self.format = Some(Rc::new(f));
+        self
+    }
+
+    pub fn validate<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&f64) -> Result<(), String> + 'static,
+    {
+        self.validator = Some(Rc::new(validator));
+        self
+    }
+
+    pub fn on_change<F: Fn(&f64) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Rc::new(f));
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.value
+            .as_ref()
+            .map_or(true, |value| check(&self.validator, &value.get()).is_ok())
+    }
+
+    fn display(&self, value: f64) -> String {
+        if let Some(format) = &self.format {
+            return format(value);
+        }
+        let decimals = (-self.step.log10().floor()).max(0.0) as usize;
+        format!("{:.*}", decimals, value)
+    }
+}
+
+/// Snap to the nearest step and clamp to the range
+fn snap(value: f64, min: f64, max: f64, step: f64) -> f64 {
+    let steps = ((value - min) / step).round();
+    (min + steps * step).clamp(min, max)
+}
+
+impl View for Slider {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let slot = Focusable::<SliderState>::claim();
+        let state = slot.state();
+        let value = self.value.clone().unwrap_or_else(|| state.borrow().value.clone());
+        let focused = slot.is_focused() && !self.disabled;
+        let (min, max, step) = (self.min, self.max, self.step);
+
+        if !self.disabled {
+            let value = value.clone();
+            let state = state.clone();
+            let on_change = self.on_change.clone();
+            slot.on_event(move |event| {
+                let Event::Key(key) = event else {
+                    return false;
+                };
+                let current = snap(value.get(), min, max, step);
+                let next = match key.code {
+                    KeyCode::Left | KeyCode::Char('h') => current - step,
+                    KeyCode::Right | KeyCode::Char('l') => current + step,
+                    KeyCode::PageDown => current - step * 10.0,
+                    KeyCode::PageUp => current + step * 10.0,
+                    KeyCode::Home => min,
+                    KeyCode::End => max,
+                    _ => return false,
+                };
+                let next = snap(next, min, max, step);
+                if next != value.get() {
+                    value.set(next);
+                    state.borrow_mut().touched = true;
+                    if let Some(f) = &on_change {
+                        f(&next);
+                    }
+                }
+                true
+            });
+        }
+
+        let current = snap(value.get(), min, max, step);
+        let style = if self.disabled {
+            self.style.patch(styles::subtitle())
+        } else {
+            self.style
+        };
+        let track_style = if focused { styles::focused_border() } else { styles::border() };
+
+        let label = self.label.as_deref().map(|l| format!("{} ", l)).unwrap_or_default();
+        let shown = format!(" {}", self.display(current));
+        let track = (area.width as usize).saturating_sub(label.width() + shown.width()).max(2);
+        let ratio = if max > min { (current - min) / (max - min) } else { 0.0 };
+        let knob = ((track - 1) as f64 * ratio).round() as usize;
+
+        let mut spans = vec![
+            Span::styled(label, style),
+            Span::styled("━".repeat(knob), track_style),
+            Span::styled("●", if focused { styles::title() } else { style }),
+            Span::styled("─".repeat(track - 1 - knob), styles::subtitle()),
+            Span::styled(shown, style),
+        ];
+        if state.borrow().touched {
+            if let Err(error) = check(&self.validator, &current) {
+                spans.push(Span::styled(format!("  {}", error), styles::error()));
+            }
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::rows(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_signal;
+    use crate::components::VStack;
+    use crate::testing::TestApp;
+    use std::cell::RefCell;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::CONTROL)
+    }
+
+    fn alt(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::ALT)
+    }
+
+    /// Apply keys to `text` with the cursor at `cursor`
+    fn edit(text: &str, cursor: usize, keys: &[KeyEvent]) -> (String, usize) {
+        let (mut text, mut cursor) = (text.to_string(), cursor);
+        for key in keys {
+            edit_line(&mut text, &mut cursor, key, None);
+        }
+        (text, cursor)
+    }
+
+    #[test]
+    fn test_cursor_moves_by_char_over_multibyte_text() {
+        let typed = [
+            key(KeyCode::Char('h')),
+            key(KeyCode::Char('é')),
+            key(KeyCode::Char('日')),
+            key(KeyCode::Char('🎉')),
+        ];
+        assert_eq!(edit("", 0, &typed), ("hé日🎉".to_string(), 4));
+
+        let keys = [key(KeyCode::Left), key(KeyCode::Left), key(KeyCode::Char('x'))];
+        assert_eq!(edit("hé日🎉", 4, &keys), ("héx日🎉".to_string(), 3));
+
+        assert_eq!(edit("hé日🎉", 2, &[key(KeyCode::Backspace)]), ("h日🎉".to_string(), 1));
+        assert_eq!(edit("hé日🎉", 2, &[key(KeyCode::Delete)]), ("hé🎉".to_string(), 2));
+        assert_eq!(edit("hé日🎉", 3, &[ctrl(KeyCode::Char('d'))]), ("hé日".to_string(), 3));
+
+        // Movement stops at the ends
+        assert_eq!(edit("日本", 2, &[key(KeyCode::Right)]).1, 2);
+        assert_eq!(edit("日本", 0, &[key(KeyCode::Left)]).1, 0);
+        assert_eq!(edit("日本", 1, &[key(KeyCode::End)]).1, 2);
+        assert_eq!(edit("日本", 1, &[key(KeyCode::Home)]).1, 0);
+        assert_eq!(edit("日本", 1, &[ctrl(KeyCode::Char('e'))]).1, 2);
+        assert_eq!(edit("日本", 1, &[ctrl(KeyCode::Char('a'))]).1, 0);
+        assert_eq!(edit("日本", 1, &[ctrl(KeyCode::Char('b'))]).1, 0);
+        assert_eq!(edit("日本", 1, &[ctrl(KeyCode::Char('f'))]).1, 2);
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        let text = "héllo wörld, 日本";
+        assert_eq!(word_start(text, 15), 13);
+        assert_eq!(word_start(text, 13), 6);
+        assert_eq!(word_start(text, 8), 6);
+        assert_eq!(word_start(text, 6), 0);
+        assert_eq!(word_start(text, 0), 0);
+
+        assert_eq!(word_end(text, 0), 5);
+        assert_eq!(word_end(text, 5), 11);
+        assert_eq!(word_end(text, 11), 15);
+        assert_eq!(word_end(text, 15), 15);
+        assert_eq!(word_end("  ", 0), 2);
+    }
+
+    #[test]
+    fn test_word_movement_keys() {
+        let text = "héllo wörld, 日本";
+        assert_eq!(edit(text, 15, &[ctrl(KeyCode::Left)]).1, 13);
+        assert_eq!(edit(text, 15, &[alt(KeyCode::Left), alt(KeyCode::Char('b'))]).1, 6);
+        assert_eq!(edit(text, 0, &[alt(KeyCode::Char('f'))]).1, 5);
+        assert_eq!(edit(text, 0, &[ctrl(KeyCode::Right), alt(KeyCode::Right)]).1, 11);
+    }
+
+    #[test]
+    fn test_deletion_keys() {
+        let text = "héllo wörld";
+        assert_eq!(edit(text, 11, &[ctrl(KeyCode::Char('w'))]), ("héllo ".to_string(), 6));
+        assert_eq!(edit(text, 8, &[alt(KeyCode::Backspace)]), ("héllo rld".to_string(), 6));
+        assert_eq!(edit(text, 6, &[ctrl(KeyCode::Backspace)]), ("wörld".to_string(), 0));
+        assert_eq!(edit(text, 6, &[ctrl(KeyCode::Char('u'))]), ("wörld".to_string(), 0));
+        assert_eq!(edit(text, 5, &[ctrl(KeyCode::Char('k'))]), ("héllo".to_string(), 5));
+    }
+
+    #[test]
+    fn test_edit_outcomes() {
+        let outcome = |text: &str, cursor: usize, key: KeyEvent| {
+            let (mut text, mut cursor) = (text.to_string(), cursor);
+            edit_line(&mut text, &mut cursor, &key, None)
+        };
+
+        assert!(matches!(outcome("a", 1, key(KeyCode::Enter)), Edit::Submit));
+        assert!(matches!(outcome("a", 1, key(KeyCode::Left)), Edit::Moved));
+        assert!(matches!(outcome("a", 1, key(KeyCode::Char('b'))), Edit::Changed));
+        // No-op deletions are swallowed rather than passed on
+        assert!(matches!(outcome("a", 0, key(KeyCode::Backspace)), Edit::Moved));
+        assert!(matches!(outcome("a", 1, key(KeyCode::Delete)), Edit::Moved));
+        // Unbound shortcuts are left for the app
+        assert!(matches!(outcome("a", 1, ctrl(KeyCode::Char('x'))), Edit::Ignored));
+        assert!(matches!(outcome("a", 1, alt(KeyCode::Char('x'))), Edit::Ignored));
+        assert!(matches!(outcome("a", 1, key(KeyCode::Esc)), Edit::Ignored));
+    }
+
+    #[test]
+    fn test_max_length_counts_chars() {
+        let (mut text, mut cursor) = ("日本".to_string(), 2);
+        edit_line(&mut text, &mut cursor, &key(KeyCode::Char('語')), Some(3));
+        edit_line(&mut text, &mut cursor, &key(KeyCode::Char('!')), Some(3));
+        assert_eq!((text.as_str(), cursor), ("日本語", 3));
+
+        let (mut text, mut cursor) = ("ab".to_string(), 1);
+        insert_str(&mut text, &mut cursor, "éèê", Some(4));
+        assert_eq!((text.as_str(), cursor), ("aéèb", 3));
+    }
+
+    #[test]
+    fn test_text_input_events() {
+        let name = create_signal(String::new());
+        let submitted = Rc::new(RefCell::new(Vec::new()));
+        let (bound, log) = (name.clone(), submitted.clone());
+        let mut app = TestApp::new(20, 3, move || {
+            let log = log.clone();
+            TextInput::new()
+                .bind(bound.clone())
+                .on_submit(move |value: &String| log.borrow_mut().push(value.clone()))
+        });
+
+        app.type_text("日本");
+        app.press(KeyCode::Left);
+        app.paste("é\n");
+        assert_eq!(name.get(), "日é本");
+        assert!(app.contains("│日é本"));
+
+        assert!(app.press(KeyCode::Enter));
+        assert_eq!(*submitted.borrow(), ["日é本"]);
+        assert!(!app.press_with(KeyCode::Char('x'), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    fn test_select_keys() {
+        let selected = create_signal(0);
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let (bound, log) = (selected.clone(), changes.clone());
+        let mut app = TestApp::new(20, 10, move || {
+            let log = log.clone();
+            VStack::new().child(
+                Select::new(["Apple", "Banana", "Cherry", "Blueberry"])
+                    .bind(bound.clone())
+                    .on_change(move |index: &usize| log.borrow_mut().push(*index)),
+            )
+        });
+        assert!(app.contains("Apple"));
+
+        // Closed: Left/Right step and wrap
+        app.press(KeyCode::Right);
+        assert_eq!(selected.get(), 1);
+        app.press(KeyCode::Left);
+        app.press(KeyCode::Left);
+        assert_eq!(selected.get(), 3);
+        app.press(KeyCode::Right);
+        assert_eq!(selected.get(), 0);
+
+        // Letters jump to the next match, wrapping, case-insensitively
+        app.press(KeyCode::Char('b'));
+        assert_eq!(selected.get(), 1);
+        app.press(KeyCode::Char('B'));
+        assert_eq!(selected.get(), 3);
+        app.press(KeyCode::Char('b'));
+        assert_eq!(selected.get(), 1);
+        assert!(!app.press(KeyCode::Char('z')));
+        assert_eq!(*changes.borrow(), [1, 0, 3, 0, 1, 3, 1]);
+
+        // Open: arrows move the highlight without changing the value
+        app.press(KeyCode::Enter);
+        assert!(app.contains("> Banana"));
+        app.press(KeyCode::Down);
+        app.press(KeyCode::Down);
+        app.press(KeyCode::Down);
+        assert!(app.contains("> Apple"));
+        app.press(KeyCode::Up);
+        assert!(app.contains("> Blueberry"));
+        assert_eq!(selected.get(), 1);
+
+        // Esc closes without picking
+        app.press(KeyCode::Esc);
+        assert!(!app.contains("> "));
+        assert_eq!(selected.get(), 1);
+
+        app.press(KeyCode::Char(' '));
+        app.press(KeyCode::End);
+        app.press(KeyCode::Char('c'));
+        assert!(app.contains("> Cherry"));
+        app.press(KeyCode::Enter);
+        assert_eq!(selected.get(), 2);
+        assert!(!app.contains("> "));
+
+        // Picking the current value again is not a change
+        app.press(KeyCode::Enter);
+        app.press(KeyCode::Enter);
+        assert_eq!(*changes.borrow(), [1, 0, 3, 0, 1, 3, 1, 2]);
+    }
+
+    #[test]
+    fn test_select_tab_closes_list() {
+        let mut app = TestApp::new(20, 10, || {
+            VStack::new()
+                .child(Select::new(["One", "Two"]))
+                .child(Checkbox::new("Next"))
+        });
+        app.press(KeyCode::Enter);
+        assert!(app.contains("> One"));
+
+        // Tab passes through to move focus
+        assert!(app.press(KeyCode::Tab));
+        assert!(!app.contains("> One"));
+        assert!(app.press(KeyCode::Char(' ')));
+        assert!(app.contains("[x] Next"));
+    }
+
+    #[test]
+    fn test_slider_keys() {
+        let value = create_signal(0.0);
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let (bound, log) = (value.clone(), changes.clone());
+        let mut app = TestApp::new(30, 1, move || {
+            let log = log.clone();
+            Slider::new(10.0, 0.0)
+                .step(1.0)
+                .label("Vol")
+                .bind(bound.clone())
+                .on_change(move |v: &f64| log.borrow_mut().push(*v))
+        });
+
+        app.press(KeyCode::Right);
+        app.press(KeyCode::Char('l'));
+        app.press(KeyCode::Char('h'));
+        assert_eq!(value.get(), 1.0);
+        assert!(app.snapshot().ends_with(" 1"));
+
+        app.press(KeyCode::PageUp);
+        assert_eq!(value.get(), 10.0);
+        app.press(KeyCode::PageDown);
+        assert_eq!(value.get(), 0.0);
+        app.press(KeyCode::End);
+        assert_eq!(value.get(), 10.0);
+        app.press(KeyCode::Home);
+        assert_eq!(value.get(), 0.0);
+
+        // Handled at the ends, but nothing changes
+        assert!(app.press(KeyCode::Left));
+        assert_eq!(value.get(), 0.0);
+        assert!(!app.press(KeyCode::Char('x')));
+        assert_eq!(*changes.borrow(), [1.0, 2.0, 1.0, 10.0, 0.0, 10.0, 0.0]);
+
+        // Off-step values snap before stepping
+        value.set(2.4);
+        app.press(KeyCode::Right);
+        assert_eq!(value.get(), 3.0);
+    }
+
+    #[test]
+    fn test_slider_display() {
+        let value = create_signal(2.5);
+        let bound = value.clone();
+        let mut app = TestApp::new(20, 1, move || Slider::new(0.0, 5.0).step(0.5).bind(bound.clone()));
+        assert!(app.snapshot().ends_with(" 2.5"));
+        app.press(KeyCode::Left);
+        assert!(app.snapshot().ends_with(" 2.0"));
+    }
+}
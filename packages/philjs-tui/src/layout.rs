@@ -1,6 +1,11 @@
 //! Layout utilities for PhilJS TUI
 
 pub use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+/// How a [`Flex`] places children that don't fill it
+pub use ratatui::layout::Flex as Justify;
+
+use crate::render::View;
+use ratatui::Frame;
 
 /// Create a centered area within a parent area
 pub fn center(area: Rect, width: u16, height: u16) -> Rect {
@@ -70,3 +75,541 @@ pub fn sidebar_layout(area: Rect, sidebar_width: u16) -> (Rect, Rect) {
         .split(area);
     (chunks[0], chunks[1])
 }
+
+// ============================================================================
+// Size Hints
+// ============================================================================
+
+/// Space a view asks for when placed in a [`Flex`] or [`Grid`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeHint {
+    pub width: Constraint,
+    pub height: Constraint,
+}
+
+impl SizeHint {
+    /// Share whatever space is left
+    pub const FILL: SizeHint = SizeHint {
+        width: Constraint::Fill(1),
+        height: Constraint::Fill(1),
+    };
+
+    pub const fn new(width: Constraint, height: Constraint) -> Self {
+        SizeHint { width, height }
+    }
+
+    /// A fixed number of rows at any width
+    pub const fn rows(rows: u16) -> Self {
+        SizeHint::new(Constraint::Fill(1), Constraint::Length(rows))
+    }
+
+    /// The constraint along a layout direction
+    pub fn along(&self, direction: Direction) -> Constraint {
+        match direction {
+            Direction::Horizontal => self.width,
+            Direction::Vertical => self.height,
+        }
+    }
+
+    /// Grow both axes, e.g. for borders or padding
+    pub fn grow(self, width: u16, height: u16) -> Self {
+        SizeHint::new(grow(self.width, width), grow(self.height, height))
+    }
+}
+
+impl Default for SizeHint {
+    fn default() -> Self {
+        SizeHint::FILL
+    }
+}
+
+fn grow(constraint: Constraint, by: u16) -> Constraint {
+    match constraint {
+        Constraint::Length(n) => Constraint::Length(n.saturating_add(by)),
+        Constraint::Min(n) => Constraint::Min(n.saturating_add(by)),
+        Constraint::Max(n) => Constraint::Max(n.saturating_add(by)),
+        other => other,
+    }
+}
+
+/// Total of fixed-size constraints plus gaps, or `None` if any of them
+/// flexes
+fn fixed_sum(constraints: impl IntoIterator<Item = Constraint>, gap: u16) -> Option<u16> {
+    let mut total = 0u16;
+    let mut count = 0u16;
+    for constraint in constraints {
+        let Constraint::Length(n) = constraint else {
+            return None;
+        };
+        total = total.saturating_add(n);
+        count += 1;
+    }
+    Some(total.saturating_add(gap.saturating_mul(count.saturating_sub(1))))
+}
+
+/// Largest fixed-size constraint, or `None` if any of them flexes
+fn fixed_max(constraints: impl IntoIterator<Item = Constraint>) -> Option<u16> {
+    constraints.into_iter().try_fold(0, |max, constraint| match constraint {
+        Constraint::Length(n) => Some(max.max(n)),
+        _ => None,
+    })
+}
+
+/// View with its size hint overridden
+pub struct SizedView<V: View> {
+    child: V,
+    width: Option<Constraint>,
+    height: Option<Constraint>,
+}
+
+impl<V: View> View for SizedView<V> {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        self.child.render(frame, area);
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let hint = self.child.size_hint();
+        SizeHint::new(self.width.unwrap_or(hint.width), self.height.unwrap_or(hint.height))
+    }
+}
+
+/// Sizing modifiers for any view
+///
+/// ```rust,ignore
+/// HStack::new()
+///     .child(Sidebar.width(Constraint::Length(24)))
+///     .child(Content.width(Constraint::Percentage(70)))
+/// ```
+pub trait SizeExt: View + Sized {
+    fn width(self, width: Constraint) -> SizedView<Self> {
+        SizedView { child: self, width: Some(width), height: None }
+    }
+
+    fn height(self, height: Constraint) -> SizedView<Self> {
+        SizedView { child: self, width: None, height: Some(height) }
+    }
+
+    fn size(self, width: Constraint, height: Constraint) -> SizedView<Self> {
+        SizedView { child: self, width: Some(width), height: Some(height) }
+    }
+}
+
+impl<V: View> SizeExt for V {}
+
+// ============================================================================
+// Flex
+// ============================================================================
+
+/// Row or column of views, sized by their hints or explicit constraints
+///
+/// Children that ask for a fixed size get it; the rest share what's left.
+/// Nested stacks report the sum of fixed-size children, so they size
+/// themselves inside their parent.
+pub struct Flex {
+    direction: Direction,
+    children: Vec<(Box<dyn View>, Option<Constraint>)>,
+    gap: u16,
+    margin: u16,
+    justify: Justify,
+}
+
+impl Flex {
+    pub fn new(direction: Direction) -> Self {
+        Flex {
+            direction,
+            children: Vec::new(),
+            gap: 0,
+            margin: 0,
+            justify: Justify::Start,
+        }
+    }
+
+    /// Children side by side
+    pub fn row() -> Self {
+        Self::new(Direction::Horizontal)
+    }
+
+    /// Children top to bottom
+    pub fn column() -> Self {
+        Self::new(Direction::Vertical)
+    }
+
+    /// Add a child sized by its own hint
+    pub fn child<V: View + 'static>(mut self, child: V) -> Self {
+        self.children.push((Box::new(child), None));
+        self
+    }
+
+    /// Add a child with an explicit constraint along the main axis
+    pub fn child_sized<V: View + 'static>(mut self, child: V, constraint: Constraint) -> Self {
+        self.children.push((Box::new(child), Some(constraint)));
+        self
+    }
+
+    /// Cells between children
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Cells around all children
+    pub fn margin(mut self, margin: u16) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Where children go when they don't fill the main axis
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    fn constraints(&self) -> Vec<Constraint> {
+        self.children
+            .iter()
+            .map(|(child, constraint)| constraint.unwrap_or_else(|| child.size_hint().along(self.direction)))
+            .collect()
+    }
+}
+
+impl View for Flex {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if self.children.is_empty() {
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(self.direction)
+            .constraints(self.constraints())
+            .spacing(self.gap)
+            .margin(self.margin)
+            .flex(self.justify)
+            .split(area);
+
+        for ((child, _), chunk) in self.children.iter().zip(chunks.iter()) {
+            child.render(frame, *chunk);
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let cross = match self.direction {
+            Direction::Horizontal => Direction::Vertical,
+            Direction::Vertical => Direction::Horizontal,
+        };
+        let main = fixed_sum(self.constraints(), self.gap).map_or(Constraint::Fill(1), Constraint::Length);
+        let cross = fixed_max(self.children.iter().map(|(child, _)| child.size_hint().along(cross)))
+            .map_or(Constraint::Fill(1), Constraint::Length);
+
+        let hint = match self.direction {
+            Direction::Horizontal => SizeHint::new(main, cross),
+            Direction::Vertical => SizeHint::new(cross, main),
+        };
+        hint.grow(self.margin * 2, self.margin * 2)
+    }
+}
+
+// ============================================================================
+// Grid
+// ============================================================================
+
+/// Views laid out in rows and columns, filled left to right
+///
+/// Row heights default to the tallest fixed-height child in the row, or a
+/// share of the leftover space if any child in it flexes.
+pub struct Grid {
+    columns: Vec<Constraint>,
+    rows: Vec<Constraint>,
+    children: Vec<Box<dyn View>>,
+    row_gap: u16,
+    column_gap: u16,
+}
+
+impl Grid {
+    /// Equal-width columns
+    pub fn new(columns: usize) -> Self {
+        Self::with_columns(vec![Constraint::Fill(1); columns.max(1)])
+    }
+
+    /// Columns with their own constraints
+    pub fn with_columns(columns: impl IntoIterator<Item = Constraint>) -> Self {
+        let mut columns: Vec<Constraint> = columns.into_iter().collect();
+        if columns.is_empty() {
+            columns.push(Constraint::Fill(1));
+        }
+        Grid {
+            columns,
+            rows: Vec::new(),
+            children: Vec::new(),
+            row_gap: 0,
+            column_gap: 0,
+        }
+    }
+
+    /// Explicit row heights; rows past the end are sized automatically
+    pub fn rows(mut self, rows: impl IntoIterator<Item = Constraint>) -> Self {
+        self.rows = rows.into_iter().collect();
+        self
+    }
+
+    pub fn child<V: View + 'static>(mut self, child: V) -> Self {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    /// Same gap between rows and columns
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.row_gap = gap;
+        self.column_gap = gap;
+        self
+    }
+
+    pub fn row_gap(mut self, gap: u16) -> Self {
+        self.row_gap = gap;
+        self
+    }
+
+    pub fn column_gap(mut self, gap: u16) -> Self {
+        self.column_gap = gap;
+        self
+    }
+
+    fn row_constraints(&self) -> Vec<Constraint> {
+        self.children
+            .chunks(self.columns.len())
+            .enumerate()
+            .map(|(i, row)| {
+                self.rows.get(i).copied().unwrap_or_else(|| {
+                    fixed_max(row.iter().map(|child| child.size_hint().height))
+                        .map_or(Constraint::Fill(1), Constraint::Length)
+                })
+            })
+            .collect()
+    }
+}
+
+impl View for Grid {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if self.children.is_empty() {
+            return;
+        }
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(self.row_constraints())
+            .spacing(self.row_gap)
+            .split(area);
+
+        for (row, row_area) in self.children.chunks(self.columns.len()).zip(rows.iter()) {
+            let cells = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(self.columns.clone())
+                .spacing(self.column_gap)
+                .split(*row_area);
+            for (child, cell) in row.iter().zip(cells.iter()) {
+                child.render(frame, *cell);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let width = fixed_sum(self.columns.iter().copied(), self.column_gap);
+        let height = fixed_sum(self.row_constraints(), self.row_gap);
+        SizeHint::new(
+            width.map_or(Constraint::Fill(1), Constraint::Length),
+            height.map_or(Constraint::Fill(1), Constraint::Length),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::render_to_string;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Log = Rc<RefCell<Vec<Rect>>>;
+
+    /// Records the area it's given
+    struct Probe {
+        hint: SizeHint,
+        log: Log,
+    }
+
+    impl View for Probe {
+        fn render(&self, _frame: &mut Frame, area: Rect) {
+            self.log.borrow_mut().push(area);
+        }
+
+        fn size_hint(&self) -> SizeHint {
+            self.hint
+        }
+    }
+
+    fn probe(log: &Log, hint: SizeHint) -> Probe {
+        Probe { hint, log: log.clone() }
+    }
+
+    fn fixed(width: u16, height: u16) -> SizeHint {
+        SizeHint::new(Constraint::Length(width), Constraint::Length(height))
+    }
+
+    #[test]
+    fn test_helpers() {
+        let area = Rect::new(0, 0, 80, 24);
+        assert_eq!(center(area, 20, 10), Rect::new(30, 7, 20, 10));
+        assert_eq!(center(Rect::new(10, 5, 20, 4), 30, 2), Rect::new(10, 6, 20, 2));
+
+        assert_eq!(
+            horizontal_split(area, 4),
+            [Rect::new(0, 0, 20, 24), Rect::new(20, 0, 20, 24), Rect::new(40, 0, 20, 24), Rect::new(60, 0, 20, 24)]
+        );
+        assert_eq!(vertical_split(Rect::new(0, 0, 10, 20), 2), [Rect::new(0, 0, 10, 10), Rect::new(0, 10, 10, 10)]);
+        assert_eq!(header_layout(area, 3), (Rect::new(0, 0, 80, 3), Rect::new(0, 3, 80, 21)));
+        assert_eq!(footer_layout(area, 1), (Rect::new(0, 0, 80, 23), Rect::new(0, 23, 80, 1)));
+        assert_eq!(sidebar_layout(area, 24), (Rect::new(0, 0, 24, 24), Rect::new(24, 0, 56, 24)));
+    }
+
+    #[test]
+    fn test_size_hints() {
+        assert_eq!(SizeHint::rows(3).along(Direction::Vertical), Constraint::Length(3));
+        assert_eq!(SizeHint::rows(3).along(Direction::Horizontal), Constraint::Fill(1));
+
+        let grown = SizeHint::new(Constraint::Min(2), Constraint::Max(4)).grow(2, 1);
+        assert_eq!(grown, SizeHint::new(Constraint::Min(4), Constraint::Max(5)));
+        assert_eq!(SizeHint::rows(1).grow(2, 2), SizeHint::new(Constraint::Fill(1), Constraint::Length(3)));
+
+        let sized = "abc".width(Constraint::Length(10));
+        assert_eq!(sized.size_hint(), fixed(10, 1));
+        assert_eq!("abc\nde".height(Constraint::Min(4)).size_hint(), SizeHint::new(Constraint::Length(3), Constraint::Min(4)));
+    }
+
+    #[test]
+    fn test_column_rects() {
+        let log = Log::default();
+        let column = Flex::column()
+            .child(probe(&log, SizeHint::rows(3)))
+            .child(probe(&log, SizeHint::FILL))
+            .child(probe(&log, SizeHint::rows(1)));
+        render_to_string(&column, 20, 10);
+        assert_eq!(
+            *log.borrow(),
+            [Rect::new(0, 0, 20, 3), Rect::new(0, 3, 20, 6), Rect::new(0, 9, 20, 1)]
+        );
+
+        // The fill child gives up the gaps and margin
+        log.borrow_mut().clear();
+        render_to_string(&column.gap(1).margin(1), 20, 10);
+        assert_eq!(
+            *log.borrow(),
+            [Rect::new(1, 1, 18, 3), Rect::new(1, 5, 18, 2), Rect::new(1, 8, 18, 1)]
+        );
+    }
+
+    #[test]
+    fn test_row_rects() {
+        let log = Log::default();
+        let row = Flex::row()
+            .child(probe(&log, fixed(4, 1)))
+            .child_sized(probe(&log, SizeHint::FILL), Constraint::Length(6))
+            .justify(Justify::Center);
+        render_to_string(&row, 20, 3);
+        assert_eq!(*log.borrow(), [Rect::new(5, 0, 4, 3), Rect::new(9, 0, 6, 3)]);
+
+        log.borrow_mut().clear();
+        render_to_string(&row.justify(Justify::End).gap(2), 20, 3);
+        assert_eq!(*log.borrow(), [Rect::new(8, 0, 4, 3), Rect::new(14, 0, 6, 3)]);
+    }
+
+    #[test]
+    fn test_flex_size_hint() {
+        let log = Log::default();
+
+        // Fixed children add up along the main axis and take the largest
+        // size across it
+        let row = Flex::row()
+            .child(probe(&log, fixed(4, 1)))
+            .child(probe(&log, fixed(6, 3)))
+            .gap(2);
+        assert_eq!(row.size_hint(), fixed(12, 3));
+        assert_eq!(row.margin(1).size_hint(), fixed(14, 5));
+
+        let column = Flex::column()
+            .child(probe(&log, SizeHint::rows(2)))
+            .child(probe(&log, SizeHint::FILL));
+        assert_eq!(column.size_hint(), SizeHint::FILL);
+        assert_eq!(Flex::column().child(probe(&log, SizeHint::rows(2))).size_hint(), SizeHint::rows(2));
+    }
+
+    #[test]
+    fn test_nested_stack_sizes_itself() {
+        let log = Log::default();
+        let column = Flex::column()
+            .child(Flex::row().child(probe(&log, fixed(3, 2))).child(probe(&log, SizeHint::rows(2))))
+            .child(probe(&log, SizeHint::FILL));
+        render_to_string(&column, 10, 6);
+        assert_eq!(
+            *log.borrow(),
+            [Rect::new(0, 0, 3, 2), Rect::new(3, 0, 7, 2), Rect::new(0, 2, 10, 4)]
+        );
+    }
+
+    #[test]
+    fn test_grid_rects() {
+        let log = Log::default();
+        let grid = Grid::new(2)
+            .child(probe(&log, SizeHint::rows(2)))
+            .child(probe(&log, SizeHint::rows(1)))
+            .child(probe(&log, SizeHint::rows(3)));
+        assert_eq!(grid.size_hint(), SizeHint::new(Constraint::Fill(1), Constraint::Length(5)));
+
+        // Rows are as tall as their tallest child
+        render_to_string(&grid, 20, 5);
+        assert_eq!(
+            *log.borrow(),
+            [Rect::new(0, 0, 10, 2), Rect::new(10, 0, 10, 2), Rect::new(0, 2, 10, 3)]
+        );
+
+        log.borrow_mut().clear();
+        let grid = grid.gap(1);
+        assert_eq!(grid.size_hint().height, Constraint::Length(6));
+        render_to_string(&grid, 21, 6);
+        assert_eq!(
+            *log.borrow(),
+            [Rect::new(0, 0, 10, 2), Rect::new(11, 0, 10, 2), Rect::new(0, 3, 10, 3)]
+        );
+    }
+
+    #[test]
+    fn test_grid_columns_and_rows() {
+        let log = Log::default();
+        let grid = Grid::with_columns([Constraint::Length(5), Constraint::Fill(1)])
+            .rows([Constraint::Length(4)])
+            .child(probe(&log, SizeHint::rows(1)))
+            .child(probe(&log, SizeHint::FILL))
+            .child(probe(&log, SizeHint::rows(2)));
+        assert_eq!(grid.size_hint(), SizeHint::new(Constraint::Fill(1), Constraint::Length(6)));
+
+        render_to_string(&grid, 20, 6);
+        assert_eq!(
+            *log.borrow(),
+            [Rect::new(0, 0, 5, 4), Rect::new(5, 0, 15, 4), Rect::new(0, 4, 5, 2)]
+        );
+        assert_eq!(
+            Grid::with_columns([Constraint::Length(5), Constraint::Length(7)]).column_gap(1).size_hint().width,
+            Constraint::Length(13)
+        );
+    }
+
+    #[test]
+    fn test_renders_text_in_place() {
+        let column = Flex::column()
+            .child("top")
+            .child_sized("mid", Constraint::Length(2))
+            .child("bot");
+        assert_eq!(render_to_string(&column, 10, 5), "top\nmid\n\nbot\n");
+
+        let row = Flex::row().child("ab").child("cd").gap(1).margin(1);
+        assert_eq!(render_to_string(&row, 10, 3), "\n ab cd\n");
+    }
+}
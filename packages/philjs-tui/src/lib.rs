@@ -57,7 +57,13 @@ pub mod components;
 pub mod layout;
 pub mod style;
 pub mod event;
+pub mod focus;
+pub mod form;
+pub mod overlay;
 pub mod render;
+pub mod task;
+pub mod testing;
+pub mod theme;
 pub mod widgets;
 
 pub mod prelude {
@@ -68,15 +74,29 @@ pub mod prelude {
         style::*,
         event::*,
         widgets::*,
+        form::*,
+        overlay::{Modal, Confirm, Toast, ToastKind, use_toast},
+        task::{spawn, request_redraw},
+        theme::{Theme, ThemeProvider, use_theme, set_theme, token},
     };
     pub use ratatui::style::{Color, Modifier, Style};
 }
 
 use std::io::{self, Stdout};
 use std::time::Duration;
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{
+    backend::CrosstermBackend,
+    widgets::{Paragraph, Widget},
+    Terminal, TerminalOptions, Viewport,
+};
+use futures::StreamExt;
+use task::AppMessage;
+use tokio::time::MissedTickBehavior;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event,
+        EventStream, KeyCode, KeyEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -92,6 +112,20 @@ pub struct TuiConfig {
     pub paste: bool,
     /// Frame rate limit
     pub frame_rate: u32,
+    /// Draw in this many rows below the cursor instead of taking over the
+    /// screen, e.g. for progress output in a CLI
+    pub inline: Option<u16>,
+}
+
+impl TuiConfig {
+    /// Inline mode, `height` rows tall, without mouse capture
+    pub fn inline(height: u16) -> Self {
+        TuiConfig {
+            inline: Some(height),
+            mouse: false,
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for TuiConfig {
@@ -101,6 +135,7 @@ impl Default for TuiConfig {
             mouse: true,
             paste: false,
             frame_rate: 60,
+            inline: None,
         }
     }
 }
@@ -117,13 +152,26 @@ impl TuiApp {
     pub fn new(config: TuiConfig) -> Result<Self, TuiError> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
+        if config.inline.is_none() {
+            execute!(stdout, EnterAlternateScreen)?;
+        }
         if config.mouse {
             execute!(stdout, EnableMouseCapture)?;
         }
+        if config.paste {
+            execute!(stdout, EnableBracketedPaste)?;
+        }
 
         let backend = CrosstermBackend::new(stdout);
-        let terminal = Terminal::new(backend)?;
+        let terminal = match config.inline {
+            Some(height) => Terminal::with_options(
+                backend,
+                TerminalOptions {
+                    viewport: Viewport::Inline(height),
+                },
+            )?,
+            None => Terminal::new(backend)?,
+        };
 
         Ok(TuiApp {
             terminal,
@@ -156,16 +204,43 @@ impl TuiApp {
         self.terminal.draw(f)?;
         Ok(())
     }
+
+    /// Apply a message from a task; returns whether to quit
+    fn handle_message(&mut self, message: AppMessage) -> Result<bool, TuiError> {
+        match message {
+            AppMessage::Redraw => {}
+            AppMessage::Print(text) => self.print_above(&text)?,
+            AppMessage::Quit => return Ok(true),
+        }
+        Ok(false)
+    }
+
+    /// Print lines above the inline viewport, where they scroll with the
+    /// terminal's normal output (inline mode only)
+    pub fn print_above(&mut self, text: &str) -> Result<(), TuiError> {
+        if self.config.inline.is_none() {
+            return Ok(());
+        }
+        let lines = text.lines().count().max(1) as u16;
+        self.terminal.insert_before(lines, |buf| {
+            Paragraph::new(text).render(buf.area, buf);
+        })?;
+        Ok(())
+    }
 }
 
 impl Drop for TuiApp {
     fn drop(&mut self) {
         let _ = disable_raw_mode();
-        let _ = execute!(
-            self.terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        );
+        let _ = execute!(self.terminal.backend_mut(), DisableMouseCapture, DisableBracketedPaste);
+        if self.config.inline.is_some() {
+            // Leave the last frame in the scrollback and continue below it
+            let bottom = self.terminal.get_frame().size().bottom();
+            let _ = self.terminal.set_cursor(0, bottom.saturating_sub(1));
+            println!();
+        } else {
+            let _ = execute!(self.terminal.backend_mut(), LeaveAlternateScreen);
+        }
         let _ = self.terminal.show_cursor();
     }
 }
@@ -180,35 +255,73 @@ where
 }
 
 /// Run a TUI application with custom config
+///
+/// Starts a single-threaded tokio runtime for the event loop and any tasks
+/// spawned with [`task::spawn`].
 pub fn run_with_config<F, V>(app: F, config: TuiConfig) -> Result<(), TuiError>
+where
+    F: Fn() -> V + 'static,
+    V: render::View,
+{
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    tokio::task::LocalSet::new().block_on(&runtime, run_async(app, config))
+}
+
+/// Run a TUI application on an existing runtime
+///
+/// Must run inside a `tokio::task::LocalSet` for [`task::spawn`] to work.
+pub async fn run_async<F, V>(app: F, config: TuiConfig) -> Result<(), TuiError>
 where
     F: Fn() -> V + 'static,
     V: render::View,
 {
     let mut tui = TuiApp::new(config.clone())?;
-    let tick_rate = config.tick_rate;
+    let (_messages, mut messages) = task::install();
+    let mut events = EventStream::new();
+    let mut ticks = tokio::time::interval(config.tick_rate);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
     loop {
         // Render
         let view = app();
-        tui.draw(|frame| {
-            view.render(frame, frame.area());
-        })?;
+        tui.draw(|frame| render::render_root(&view, frame))?;
 
-        // Handle events
-        if event::poll(tick_rate)? {
-            match event::read()? {
-                Event::Key(key) if key.kind == KeyEventKind::Press => {
-                    match key.code {
-                        KeyCode::Char('q') => break,
-                        KeyCode::Esc => break,
-                        _ => {}
+        // Wait for input, a tick, or a message from a task
+        tokio::select! {
+            event = events.next() => match event {
+                // Give the focused widget the first look
+                Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+                    if !focus::dispatch(&crate::event::Event::Key(key)) {
+                        match key.code {
+                            KeyCode::Char('q') => break,
+                            KeyCode::Esc => break,
+                            _ => {}
+                        }
                     }
                 }
-                Event::Resize(_, _) => {
-                    // Terminal resized, will redraw on next iteration
+                Some(Ok(Event::Paste(text))) => {
+                    focus::dispatch(&crate::event::Event::Paste(text));
+                }
+                Some(Ok(_)) => {
+                    // Resizes and the rest just redraw
+                }
+                Some(Err(err)) => return Err(err.into()),
+                None => break,
+            },
+            _ = ticks.tick() => {}
+            Some(message) = messages.recv() => {
+                if tui.handle_message(message)? {
+                    break;
                 }
-                _ => {}
+            }
+        }
+
+        // Fold redraw requests that piled up into the next frame
+        while let Ok(message) = messages.try_recv() {
+            if tui.handle_message(message)? {
+                tui.quit();
             }
         }
 
@@ -0,0 +1,747 @@
+//! Overlays for PhilJS TUI
+//!
+//! Modals, confirmation dialogs and toasts draw above the main view, after
+//! it has rendered. Modals are part of the view tree and shown while their
+//! `open` signal is true:
+//!
+//! ```rust,ignore
+//! let confirm_delete = create_signal(false);
+//!
+//! VStack::new()
+//!     .child(FileList)
+//!     .child(
+//!         Confirm::new(confirm_delete.clone(), "Delete file?")
+//!             .message("This can't be undone.")
+//!             .destructive()
+//!             .on_confirm(|| delete_selected()),
+//!     )
+//! ```
+//!
+//! Toasts are imperative and can be raised from anywhere, including async
+//! tasks:
+//!
+//! ```rust,ignore
+//! use_toast().success("Saved");
+//! ```
+//!
+//! An open modal traps focus: Tab cycles through its own widgets, keys don't
+//! reach the view behind it, and Esc dismisses it.
+
+use crate::app::Signal;
+use crate::event::Event;
+use crate::focus::{self, Focusable};
+use crate::layout::{center, Constraint, SizeHint};
+use crate::render::View;
+use crate::style::styles;
+use crate::theme::{self, token};
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+
+type Layer = Box<dyn FnOnce(&mut Frame)>;
+
+thread_local! {
+    /// Overlays opened while rendering the current frame
+    static LAYERS: RefCell<Vec<Layer>> = RefCell::new(Vec::new());
+}
+
+/// Queue an overlay, drawn later with the theme in effect now
+fn push_layer(layer: impl FnOnce(&mut Frame) + 'static) {
+    let theme = theme::current();
+    LAYERS.with(|layers| {
+        layers
+            .borrow_mut()
+            .push(Box::new(move |frame| theme::scoped(theme, || layer(frame))))
+    });
+}
+
+/// Draw the overlays opened this frame, then toasts (called by the runtime
+/// after the main view)
+pub fn render(frame: &mut Frame) {
+    // Layers can open further layers (a modal inside a modal)
+    loop {
+        let layers = LAYERS.with(|layers| std::mem::take(&mut *layers.borrow_mut()));
+        if layers.is_empty() {
+            break;
+        }
+        for layer in layers {
+            layer(frame);
+        }
+    }
+    render_toasts(frame);
+}
+
+/// Size hint for views that only draw as overlays
+fn no_space() -> SizeHint {
+    SizeHint::new(Constraint::Length(0), Constraint::Length(0))
+}
+
+/// Dim everything behind a dialog and clear its box
+fn draw_backdrop(frame: &mut Frame, area: Rect) {
+    let screen = frame.size();
+    frame
+        .buffer_mut()
+        .set_style(screen, Style::default().add_modifier(Modifier::DIM));
+    frame.render_widget(Clear, area);
+    frame.buffer_mut().set_style(area, styles::surface());
+}
+
+/// Pick a dialog size from a content hint, capped to most of the screen
+fn dialog_area(screen: Rect, content: SizeHint, width: Option<u16>, height: Option<u16>) -> Rect {
+    let extent = |fixed: Option<u16>, hint: Constraint, available: u16| {
+        let max = available.saturating_mul(4) / 5;
+        match (fixed, hint) {
+            (Some(n), _) => n,
+            // Content plus the border
+            (None, Constraint::Length(n)) => n.saturating_add(2).min(max),
+            (None, _) => max,
+        }
+    };
+    let width = extent(width, content.width, screen.width);
+    let height = extent(height, content.height, screen.height);
+    center(screen, width, height)
+}
+
+/// Handler for the trap around a dialog: Esc dismisses, Ctrl-C still
+/// reaches the app, and everything else stays inside
+fn trap_keys(dismiss: Option<Rc<dyn Fn()>>) -> impl Fn(&Event) -> bool {
+    move |event| match event {
+        Event::Key(key) if key.code == KeyCode::Esc => {
+            if let Some(dismiss) = &dismiss {
+                dismiss();
+            }
+            true
+        }
+        Event::Key(key) if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) => false,
+        _ => true,
+    }
+}
+
+// ============================================================================
+// Modal
+// ============================================================================
+
+/// Dialog box with arbitrary content, shown while `open` is true
+///
+/// Takes no space in the layout it's placed in.
+pub struct Modal {
+    open: Signal<bool>,
+    content: Rc<dyn View>,
+    title: Option<String>,
+    width: Option<u16>,
+    height: Option<u16>,
+    dismissible: bool,
+    on_close: Option<Rc<dyn Fn()>>,
+}
+
+impl Modal {
+    pub fn new<V: View + 'static>(open: Signal<bool>, content: V) -> Self {
+        Modal {
+            open,
+            content: Rc::new(content),
+            title: None,
+            width: None,
+            height: None,
+            dismissible: true,
+            on_close: None,
+        }
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Fixed outer size; defaults to the content's size hint
+    pub fn size(mut self, width: u16, height: u16) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    /// Whether Esc closes the modal (default true)
+    pub fn dismissible(mut self, dismissible: bool) -> Self {
+        self.dismissible = dismissible;
+        self
+    }
+
+    /// Called when the user dismisses the modal
+    pub fn on_close<F: Fn() + 'static>(mut self, f: F) -> Self {
+        self.on_close = Some(Rc::new(f));
+        self
+    }
+}
+
+impl View for Modal {
+    fn render(&self, _frame: &mut Frame, _area: Rect) {
+        if !self.open.get() {
+            return;
+        }
+
+        let open = self.open.clone();
+        let content = Rc::clone(&self.content);
+        let title = self.title.clone();
+        let (width, height) = (self.width, self.height);
+        let dismiss = self.dismissible.then(|| {
+            let on_close = self.on_close.clone();
+            Rc::new(move || {
+                open.set(false);
+                if let Some(f) = &on_close {
+                    f();
+                }
+            }) as Rc<dyn Fn()>
+        });
+
+        push_layer(move |frame| {
+            let area = dialog_area(frame.size(), content.size_hint(), width, height);
+            draw_backdrop(frame, area);
+
+            let mut block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(styles::focused_border())
+                .style(styles::surface());
+            if let Some(title) = &title {
+                block = block.title(Span::styled(title.as_str(), styles::title()));
+            }
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+
+            focus::trap(trap_keys(dismiss));
+            content.render(frame, inner);
+        });
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        no_space()
+    }
+}
+
+// ============================================================================
+// Confirm
+// ============================================================================
+
+#[derive(Default)]
+struct ConfirmState {
+    /// Whether the confirm button (rather than cancel) is selected
+    confirm_selected: bool,
+}
+
+/// Yes/no dialog, shown while `open` is true
+///
+/// Left/Right (or Tab) pick a button and Enter activates it; `y` and `n`
+/// answer directly. Esc cancels. Cancel is selected when the dialog opens.
+pub struct Confirm {
+    open: Signal<bool>,
+    title: String,
+    message: Option<String>,
+    confirm_label: String,
+    cancel_label: String,
+    destructive: bool,
+    on_confirm: Option<Rc<dyn Fn()>>,
+    on_cancel: Option<Rc<dyn Fn()>>,
+}
+
+impl Confirm {
+    pub fn new(open: Signal<bool>, title: impl Into<String>) -> Self {
+        Confirm {
+            open,
+            title: title.into(),
+            message: None,
+            confirm_label: "OK".to_string(),
+            cancel_label: "Cancel".to_string(),
+            destructive: false,
+            on_confirm: None,
+            on_cancel: None,
+        }
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn confirm_label(mut self, label: impl Into<String>) -> Self {
+        self.confirm_label = label.into();
+        self
+    }
+
+    pub fn cancel_label(mut self, label: impl Into<String>) -> Self {
+        self.cancel_label = label.into();
+        self
+    }
+
+    /// Show the confirm button in the error color
+    pub fn destructive(mut self) -> Self {
+        self.destructive = true;
+        self
+    }
+
+    pub fn on_confirm<F: Fn() + 'static>(mut self, f: F) -> Self {
+        self.on_confirm = Some(Rc::new(f));
+        self
+    }
+
+    pub fn on_cancel<F: Fn() + 'static>(mut self, f: F) -> Self {
+        self.on_cancel = Some(Rc::new(f));
+        self
+    }
+}
+
+impl View for Confirm {
+    fn render(&self, _frame: &mut Frame, _area: Rect) {
+        if !self.open.get() {
+            return;
+        }
+
+        let open = self.open.clone();
+        let answer = {
+            let on_confirm = self.on_confirm.clone();
+            let on_cancel = self.on_cancel.clone();
+            Rc::new(move |confirmed: bool| {
+                open.set(false);
+                let callback = if confirmed { &on_confirm } else { &on_cancel };
+                if let Some(f) = callback {
+                    f();
+                }
+            })
+        };
+        let title = self.title.clone();
+        let message = self.message.clone().unwrap_or_default();
+        let confirm_label = format!(" {} ", self.confirm_label);
+        let cancel_label = format!(" {} ", self.cancel_label);
+        let confirm_token = if self.destructive { token::ERROR } else { token::PRIMARY };
+
+        push_layer(move |frame| {
+            let screen = frame.size();
+            let text_width = message.width().max(title.width()).max(confirm_label.width() + cancel_label.width() + 4);
+            let width = (text_width as u16 + 6).min(screen.width.saturating_mul(4) / 5).max(24);
+            let message_rows = if message.is_empty() {
+                0
+            } else {
+                (message.width() as u16).div_ceil(width.saturating_sub(4).max(1)) + 1
+            };
+            let area = center(screen, width, message_rows + 4);
+            draw_backdrop(frame, area);
+
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(styles::focused_border())
+                .style(styles::surface())
+                .title(Span::styled(title.as_str(), styles::title()));
+            let inner = block.inner(area).inner(&Margin::new(1, 0));
+            frame.render_widget(block, area);
+
+            let cancel = {
+                let answer = Rc::clone(&answer);
+                Rc::new(move || answer(false)) as Rc<dyn Fn()>
+            };
+            focus::trap(trap_keys(Some(cancel)));
+
+            let slot = Focusable::<ConfirmState>::claim();
+            let state = slot.state();
+            {
+                let state = Rc::clone(&state);
+                let answer = Rc::clone(&answer);
+                slot.on_event(move |event| {
+                    let Event::Key(key) = event else {
+                        return false;
+                    };
+                    let mut state = state.borrow_mut();
+                    match key.code {
+                        KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab => {
+                            state.confirm_selected = !state.confirm_selected;
+                        }
+                        KeyCode::Enter | KeyCode::Char(' ') => {
+                            let confirmed = std::mem::take(&mut state.confirm_selected);
+                            drop(state);
+                            answer(confirmed);
+                        }
+                        KeyCode::Char('y') => {
+                            state.confirm_selected = false;
+                            drop(state);
+                            answer(true);
+                        }
+                        KeyCode::Char('n') => {
+                            state.confirm_selected = false;
+                            drop(state);
+                            answer(false);
+                        }
+                        _ => return false,
+                    }
+                    true
+                });
+            }
+
+            if !message.is_empty() {
+                let message_area = Rect { height: message_rows.saturating_sub(1), ..inner };
+                frame.render_widget(Paragraph::new(message.as_str()).wrap(Wrap { trim: true }), message_area);
+            }
+
+            let selected = state.borrow().confirm_selected;
+            let button = |label: &str, active: bool, color: Color| {
+                if active {
+                    Span::styled(label.to_string(), styles::selected().bg(color))
+                } else {
+                    Span::styled(label.to_string(), Style::default().fg(color))
+                }
+            };
+            let buttons = Line::from(vec![
+                button(&cancel_label, !selected, theme::color(token::TEXT)),
+                Span::raw("  "),
+                button(&confirm_label, selected, theme::color(confirm_token)),
+            ]);
+            let buttons_area = Rect {
+                y: inner.bottom().saturating_sub(1),
+                height: 1,
+                ..inner
+            };
+            frame.render_widget(Paragraph::new(buttons).alignment(Alignment::Right), buttons_area);
+        });
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        no_space()
+    }
+}
+
+// ============================================================================
+// Toasts
+// ============================================================================
+
+/// Toast severity, which sets its color and icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    fn color(self) -> Color {
+        theme::color(match self {
+            ToastKind::Info => token::INFO,
+            ToastKind::Success => token::SUCCESS,
+            ToastKind::Warning => token::WARNING,
+            ToastKind::Error => token::ERROR,
+        })
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            ToastKind::Info => "ℹ",
+            ToastKind::Success => "✓",
+            ToastKind::Warning => "!",
+            ToastKind::Error => "✗",
+        }
+    }
+}
+
+/// A timed notification
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub kind: ToastKind,
+    pub duration: Duration,
+}
+
+impl Toast {
+    pub fn new(message: impl Into<String>, kind: ToastKind) -> Self {
+        Toast {
+            message: message.into(),
+            kind,
+            duration: Duration::from_secs(3),
+        }
+    }
+
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
+/// Toasts on screen, with when they expire
+static TOASTS: Mutex<Vec<(Toast, Instant)>> = Mutex::new(Vec::new());
+
+/// Most toasts shown at once; older ones wait their turn
+const MAX_VISIBLE_TOASTS: usize = 4;
+
+/// Handle for raising toasts
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Toaster;
+
+/// Get a handle for raising toasts
+///
+/// Toasts stack in the bottom-right corner and disappear after their
+/// duration. Safe to call from any thread.
+pub fn use_toast() -> Toaster {
+    Toaster
+}
+
+impl Toaster {
+    pub fn show(&self, toast: Toast) {
+        TOASTS.lock().unwrap().push((toast, Instant::now()));
+        crate::task::request_redraw();
+    }
+
+    pub fn info(&self, message: impl Into<String>) {
+        self.show(Toast::new(message, ToastKind::Info));
+    }
+
+    pub fn success(&self, message: impl Into<String>) {
+        self.show(Toast::new(message, ToastKind::Success));
+    }
+
+    pub fn warning(&self, message: impl Into<String>) {
+        self.show(Toast::new(message, ToastKind::Warning));
+    }
+
+    pub fn error(&self, message: impl Into<String>) {
+        self.show(Toast::new(message, ToastKind::Error));
+    }
+
+    /// Remove every toast
+    pub fn clear(&self) {
+        TOASTS.lock().unwrap().clear();
+        crate::task::request_redraw();
+    }
+}
+
+fn render_toasts(frame: &mut Frame) {
+    draw_toasts(frame, &mut TOASTS.lock().unwrap(), Instant::now());
+}
+
+/// Drop toasts that expired by `now` and draw the rest
+fn draw_toasts(frame: &mut Frame, toasts: &mut Vec<(Toast, Instant)>, now: Instant) {
+    // A toast's clock starts when it's first shown, not while it's queued
+    for (_, shown) in toasts.iter_mut().skip(MAX_VISIBLE_TOASTS) {
+        *shown = now;
+    }
+    toasts.retain(|(toast, shown)| now.duration_since(*shown) < toast.duration);
+
+    let screen = frame.size();
+    let mut bottom = screen.bottom();
+    for (toast, _) in toasts.iter().take(MAX_VISIBLE_TOASTS) {
+        let width = (toast.message.width() as u16 + 6).min(screen.width.saturating_sub(2)).min(50);
+        let text_width = width.saturating_sub(4).max(1);
+        let height = (toast.message.width() as u16).div_ceil(text_width).max(1) + 2;
+        if bottom < screen.top() + height {
+            break;
+        }
+        bottom -= height;
+        let area = Rect::new(screen.right().saturating_sub(width + 1), bottom, width, height);
+
+        let color = toast.kind.color();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color))
+            .style(styles::surface());
+        let text = Line::from(vec![
+            Span::styled(format!("{} ", toast.kind.icon()), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::raw(toast.message.as_str()),
+        ]);
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(text).wrap(Wrap { trim: true }).block(block), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_signal;
+    use crate::components::VStack;
+    use crate::form::Checkbox;
+    use crate::testing::{buffer_to_string, TestApp};
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::cell::Cell;
+
+    /// A checkbox behind a modal holding two more
+    fn modal_app(
+        behind: &Signal<bool>,
+        open: &Signal<bool>,
+        inside: [&Signal<bool>; 2],
+        dismissible: bool,
+        closed: &Rc<Cell<u32>>,
+    ) -> TestApp<impl Fn() -> VStack> {
+        let (behind, open, closed) = (behind.clone(), open.clone(), closed.clone());
+        let (a, b) = (inside[0].clone(), inside[1].clone());
+        TestApp::new(30, 10, move || {
+            let closed = closed.clone();
+            VStack::new().child(Checkbox::new("Behind").bind(behind.clone())).child(
+                Modal::new(
+                    open.clone(),
+                    VStack::new()
+                        .child(Checkbox::new("A").bind(a.clone()))
+                        .child(Checkbox::new("B").bind(b.clone())),
+                )
+                .title("Pick")
+                .dismissible(dismissible)
+                .on_close(move || closed.set(closed.get() + 1)),
+            )
+        })
+    }
+
+    #[test]
+    fn test_modal_traps_focus() {
+        let (behind, open) = (create_signal(false), create_signal(false));
+        let (a, b) = (create_signal(false), create_signal(false));
+        let closed = Rc::new(Cell::new(0));
+        let mut app = modal_app(&behind, &open, [&a, &b], true, &closed);
+        assert!(!app.contains("Pick"));
+
+        open.set(true);
+        app.render();
+        assert!(app.contains("Pick") && app.contains("[ ] A"));
+
+        // Focus starts inside, and Tab cycles without leaving
+        app.press(KeyCode::Char(' '));
+        assert!(a.get());
+        app.press(KeyCode::Tab);
+        app.press(KeyCode::Tab);
+        app.press(KeyCode::Char(' '));
+        assert!(!a.get());
+        app.press(KeyCode::BackTab);
+        app.press(KeyCode::Char(' '));
+        assert!(b.get());
+        assert!(!behind.get());
+
+        // Keys the dialog doesn't use stop at it, except Ctrl-C
+        assert!(app.press(KeyCode::Char('x')));
+        assert!(!app.press_with(KeyCode::Char('c'), KeyModifiers::CONTROL));
+
+        // Esc closes it and focus goes back where it was
+        assert!(app.press(KeyCode::Esc));
+        assert!(!open.get());
+        assert_eq!(closed.get(), 1);
+        assert!(!app.contains("Pick"));
+        app.press(KeyCode::Char(' '));
+        assert!(behind.get());
+    }
+
+    #[test]
+    fn test_undismissible_modal() {
+        let (behind, open) = (create_signal(false), create_signal(true));
+        let (a, b) = (create_signal(false), create_signal(false));
+        let closed = Rc::new(Cell::new(0));
+        let mut app = modal_app(&behind, &open, [&a, &b], false, &closed);
+
+        assert!(app.press(KeyCode::Esc));
+        assert!(open.get() && app.contains("Pick"));
+        assert_eq!(closed.get(), 0);
+
+        // Closing it from code lifts the trap
+        open.set(false);
+        app.render();
+        app.press(KeyCode::Char(' '));
+        assert!(behind.get());
+    }
+
+    #[test]
+    fn test_confirm_keys() {
+        let open = create_signal(true);
+        let answers = Rc::new(RefCell::new(Vec::new()));
+        let (shown, log) = (open.clone(), answers.clone());
+        let mut app = TestApp::new(40, 12, move || {
+            let (yes, no) = (log.clone(), log.clone());
+            Confirm::new(shown.clone(), "Delete file?")
+                .message("This can't be undone.")
+                .confirm_label("Delete")
+                .on_confirm(move || yes.borrow_mut().push("confirm"))
+                .on_cancel(move || no.borrow_mut().push("cancel"))
+        });
+        assert!(app.contains("Delete file?") && app.contains("This can't be undone."));
+        assert!(app.contains("Cancel    Delete"));
+
+        let mut answer = |keys: &[KeyCode]| {
+            open.set(true);
+            app.render();
+            for &key in keys {
+                app.press(key);
+            }
+            assert!(!open.get());
+            assert!(!app.contains("Delete file?"));
+            answers.borrow_mut().pop().unwrap()
+        };
+
+        // Cancel is selected when the dialog opens
+        assert_eq!(answer(&[KeyCode::Enter]), "cancel");
+        assert_eq!(answer(&[KeyCode::Right, KeyCode::Enter]), "confirm");
+        // Tab moves between the buttons rather than out of the dialog
+        assert_eq!(answer(&[KeyCode::Tab, KeyCode::Char(' ')]), "confirm");
+        assert_eq!(answer(&[KeyCode::Right, KeyCode::Left, KeyCode::Enter]), "cancel");
+        assert_eq!(answer(&[KeyCode::Char('y')]), "confirm");
+        assert_eq!(answer(&[KeyCode::Right, KeyCode::Char('n')]), "cancel");
+        assert_eq!(answer(&[KeyCode::Right, KeyCode::Esc]), "cancel");
+        // Reopening starts from Cancel again
+        assert_eq!(answer(&[KeyCode::Enter]), "cancel");
+    }
+
+    /// Draw `toasts` as of `now` into a `width` by `height` screen
+    fn draw(toasts: &mut Vec<(Toast, Instant)>, now: Instant, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| draw_toasts(frame, toasts, now)).unwrap();
+        buffer_to_string(terminal.backend().buffer())
+    }
+
+    #[test]
+    fn test_toasts_expire() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut toasts = vec![
+            (Toast::new("Saved", ToastKind::Success).duration(Duration::from_secs(1)), start),
+            (Toast::new("Failed", ToastKind::Error), start),
+        ];
+
+        // Stacked up from the bottom-right corner, oldest lowest
+        let screen = draw(&mut toasts, at(500), 30, 8);
+        let rows: Vec<&str> = screen.lines().collect();
+        assert_eq!(rows[3], format!("{}│✗ Failed  │", " ".repeat(17)));
+        assert_eq!(rows[6], format!("{}│✓ Saved  │", " ".repeat(18)));
+
+        let screen = draw(&mut toasts, at(1000), 30, 8);
+        assert!(!screen.contains("Saved") && screen.contains("Failed"));
+        assert_eq!(toasts.len(), 1);
+
+        let screen = draw(&mut toasts, at(3000), 30, 8);
+        assert!(screen.trim().is_empty());
+        assert!(toasts.is_empty());
+    }
+
+    #[test]
+    fn test_queued_toasts_wait_their_turn() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut toasts: Vec<_> = (0..6)
+            .map(|i| (Toast::new(format!("t{}", i), ToastKind::Info).duration(Duration::from_secs(1)), start))
+            .collect();
+        let shown = |screen: &str| -> Vec<usize> { (0..6).filter(|i| screen.contains(&format!("t{}", i))).collect() };
+
+        assert_eq!(shown(&draw(&mut toasts, at(500), 30, 14)), [0, 1, 2, 3]);
+        // The queued toasts' clocks start once they're on screen
+        assert_eq!(shown(&draw(&mut toasts, at(1000), 30, 14)), [4, 5]);
+        assert_eq!(shown(&draw(&mut toasts, at(1900), 30, 14)), [4, 5]);
+        assert!(shown(&draw(&mut toasts, at(2000), 30, 14)).is_empty());
+    }
+
+    #[test]
+    fn test_toasts_that_dont_fit_wait() {
+        let start = Instant::now();
+        let mut toasts = vec![
+            (Toast::new("first", ToastKind::Info), start),
+            (Toast::new("second", ToastKind::Warning), start),
+        ];
+        let screen = draw(&mut toasts, start, 30, 5);
+        assert!(screen.contains("first") && !screen.contains("second"));
+        assert_eq!(toasts.len(), 2);
+    }
+}
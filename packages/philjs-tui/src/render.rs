@@ -1,10 +1,35 @@
 //! Rendering traits for PhilJS TUI
 
-use ratatui::{layout::Rect, Frame};
+use crate::layout::SizeHint;
+use ratatui::{
+    layout::{Constraint, Rect},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
 
 /// Trait for renderable views
 pub trait View {
     fn render(&self, frame: &mut Frame, area: Rect);
+
+    /// Space this view asks for inside a stack or grid
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::FILL
+    }
+}
+
+/// Render a whole frame: the root view, then overlays, tracking focus
+pub(crate) fn render_root<V: View>(view: &V, frame: &mut Frame) {
+    crate::focus::begin_frame();
+    view.render(frame, frame.size());
+    crate::overlay::render(frame);
+    crate::focus::end_frame();
+}
+
+/// Size of plain text: its widest line by its line count
+pub(crate) fn text_hint(text: &str) -> SizeHint {
+    let width = text.lines().map(|line| line.width()).max().unwrap_or(0);
+    let height = text.lines().count().max(1);
+    SizeHint::new(Constraint::Length(width as u16), Constraint::Length(height as u16))
 }
 
 /// Implement View for tuples (for composing multiple views)
@@ -22,6 +47,13 @@ impl<V: View> View for Option<V> {
             v.render(frame, area);
         }
     }
+
+    fn size_hint(&self) -> SizeHint {
+        match self {
+            Some(v) => v.size_hint(),
+            None => SizeHint::new(Constraint::Length(0), Constraint::Length(0)),
+        }
+    }
 }
 
 /// Implement View for Vec
@@ -39,6 +71,10 @@ impl View for &str {
         use ratatui::widgets::Paragraph;
         frame.render_widget(Paragraph::new(*self), area);
     }
+
+    fn size_hint(&self) -> SizeHint {
+        text_hint(self)
+    }
 }
 
 /// Implement View for String
@@ -47,6 +83,10 @@ impl View for String {
         use ratatui::widgets::Paragraph;
         frame.render_widget(Paragraph::new(self.as_str()), area);
     }
+
+    fn size_hint(&self) -> SizeHint {
+        text_hint(self)
+    }
 }
 
 /// A boxed view for dynamic dispatch
@@ -56,4 +96,8 @@ impl View for BoxedView {
     fn render(&self, frame: &mut Frame, area: Rect) {
         self.0.render(frame, area);
     }
+
+    fn size_hint(&self) -> SizeHint {
+        self.0.size_hint()
+    }
 }
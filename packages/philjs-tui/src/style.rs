@@ -2,7 +2,7 @@
 
 pub use ratatui::style::{Color, Modifier, Style, Stylize};
 
-/// Common color palette
+/// Base colors of the default dark theme
 pub mod colors {
     use super::Color;
 
@@ -24,56 +24,48 @@ pub fn style() -> Style {
     Style::default()
 }
 
-/// Common styles
+/// Common styles, resolved from the current [`crate::theme`]
 pub mod styles {
     use super::*;
+    use crate::theme::{self, token};
 
     pub fn title() -> Style {
-        Style::default()
-            .fg(colors::PRIMARY)
-            .add_modifier(Modifier::BOLD)
+        theme::style(token::TITLE)
     }
 
     pub fn subtitle() -> Style {
-        Style::default()
-            .fg(colors::TEXT_MUTED)
+        theme::style(token::MUTED)
     }
 
     pub fn highlight() -> Style {
-        Style::default()
-            .bg(colors::PRIMARY)
-            .fg(Color::Black)
+        theme::style(token::HIGHLIGHT)
     }
 
     pub fn selected() -> Style {
-        Style::default()
-            .bg(colors::PRIMARY)
-            .fg(Color::Black)
-            .add_modifier(Modifier::BOLD)
+        theme::style(token::SELECTED)
     }
 
     pub fn error() -> Style {
-        Style::default()
-            .fg(colors::ERROR)
+        theme::style(token::ERROR)
     }
 
     pub fn success() -> Style {
-        Style::default()
-            .fg(colors::SUCCESS)
+        theme::style(token::SUCCESS)
     }
 
     pub fn warning() -> Style {
-        Style::default()
-            .fg(colors::WARNING)
+        theme::style(token::WARNING)
     }
 
     pub fn border() -> Style {
-        Style::default()
-            .fg(colors::BORDER)
+        theme::style(token::BORDER)
     }
 
     pub fn focused_border() -> Style {
-        Style::default()
-            .fg(colors::PRIMARY)
+        theme::style(token::FOCUS)
+    }
+
+    pub fn surface() -> Style {
+        theme::style(token::SURFACE)
     }
 }
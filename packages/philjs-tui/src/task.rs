@@ -0,0 +1,108 @@
+//! Async tasks for PhilJS TUI
+//!
+//! The event loop runs on a single-threaded tokio runtime, so components can
+//! start async work (HTTP requests, database queries, ...) without blocking
+//! input and write the result into a signal:
+//!
+//! ```rust,ignore
+//! let users = create_signal(Vec::new());
+//!
+//! let target = users.clone();
+//! spawn(async move {
+//!     let rows = sqlx::query_as::<_, User>("SELECT * FROM users").fetch_all(&pool).await;
+//!     target.set(rows.unwrap_or_default());
+//! });
+//! ```
+//!
+//! Setting a signal asks the loop to redraw, so results show up as soon as
+//! they land rather than on the next tick.
+
+use std::future::Future;
+use std::sync::Mutex;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+/// Messages to the running event loop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppMessage {
+    /// Render a new frame
+    Redraw,
+    /// Print a line above the inline viewport
+    Print(String),
+    /// Leave the event loop
+    Quit,
+}
+
+static MESSAGES: Mutex<Option<UnboundedSender<AppMessage>>> = Mutex::new(None);
+
+/// Open the message channel for a running app
+///
+/// The channel closes when the returned guard drops.
+pub(crate) fn install() -> (MessageGuard, UnboundedReceiver<AppMessage>) {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    *MESSAGES.lock().unwrap() = Some(tx);
+    (MessageGuard(()), rx)
+}
+
+pub(crate) struct MessageGuard(());
+
+impl Drop for MessageGuard {
+    fn drop(&mut self) {
+        MESSAGES.lock().unwrap().take();
+    }
+}
+
+/// Send a message to the running app; returns false if no app is running
+pub fn send(message: AppMessage) -> bool {
+    MESSAGES
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|tx| tx.send(message).is_ok())
+}
+
+/// Ask the app to render a new frame
+pub fn request_redraw() {
+    send(AppMessage::Redraw);
+}
+
+/// Print above the app in inline mode, like a log line that stays in the
+/// scrollback; ignored in full-screen mode
+pub fn println(text: impl Into<String>) {
+    send(AppMessage::Print(text.into()));
+}
+
+/// Ask the app to exit after the current frame
+pub fn quit() {
+    send(AppMessage::Quit);
+}
+
+/// Run a future alongside the event loop, redrawing when it finishes
+///
+/// Futures don't need to be `Send`; they run on the UI thread between
+/// events. Must be called from inside [`crate::run`] (or [`crate::run_async`]
+/// on a `LocalSet`).
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    tokio::task::spawn_local(async move {
+        let output = future.await;
+        request_redraw();
+        output
+    })
+}
+
+/// Run blocking work on tokio's blocking pool, redrawing when it finishes
+pub fn spawn_blocking<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let output = f();
+        request_redraw();
+        output
+    })
+}
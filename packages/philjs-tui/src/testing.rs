@@ -0,0 +1,213 @@
+//! Headless rendering for tests
+//!
+//! Renders views into ratatui's `TestBackend` so widget behavior can be
+//! checked without a terminal:
+//!
+//! ```rust,ignore
+//! use philjs_tui::testing::TestApp;
+//!
+//! let name = create_signal(String::new());
+//! let bound = name.clone();
+//! let mut app = TestApp::new(30, 3, move || TextInput::new().label("Name").bind(bound.clone()));
+//!
+//! app.type_text("Ada");
+//! assert_eq!(name.get(), "Ada");
+//! assert_eq!(
+//!     app.snapshot(),
+//!     "┌Name────────────────────────┐\n\
+//!      │Ada                         │\n\
+//!      └────────────────────────────┘"
+//! );
+//! ```
+//!
+//! Focus and widget state live per thread, so tests running in parallel
+//! don't see each other's widgets.
+
+use crate::event::Event;
+use crate::focus;
+use crate::render::{render_root, View};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use unicode_width::UnicodeWidthStr;
+
+/// An app rendered into an in-memory buffer
+pub struct TestApp<F> {
+    terminal: Terminal<TestBackend>,
+    app: F,
+}
+
+impl<F, V> TestApp<F>
+where
+    F: Fn() -> V,
+    V: View,
+{
+    /// Create the app and render its first frame
+    pub fn new(width: u16, height: u16, app: F) -> Self {
+        let terminal = Terminal::new(TestBackend::new(width, height)).expect("test backend never fails");
+        let mut test = TestApp { terminal, app };
+        test.render();
+        test
+    }
+
+    /// Render a frame from the current state
+    pub fn render(&mut self) -> &mut Self {
+        let view = (self.app)();
+        self.terminal
+            .draw(|frame| render_root(&view, frame))
+            .expect("test backend never fails");
+        self
+    }
+
+    /// Send an event to the focused widget, then render
+    ///
+    /// Returns whether anything handled it.
+    pub fn send(&mut self, event: Event) -> bool {
+        let handled = focus::dispatch(&event);
+        self.render();
+        handled
+    }
+
+    /// Press a key with no modifiers
+    pub fn press(&mut self, code: KeyCode) -> bool {
+        self.press_with(code, KeyModifiers::NONE)
+    }
+
+    pub fn press_with(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        self.send(Event::Key(KeyEvent::new(code, modifiers)))
+    }
+
+    /// Type each character of `text`
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.press(KeyCode::Char(c));
+        }
+    }
+
+    /// Paste `text` in one event
+    pub fn paste(&mut self, text: &str) -> bool {
+        self.send(Event::Paste(text.to_string()))
+    }
+
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.terminal.backend_mut().resize(width, height);
+        self.terminal.autoresize().expect("test backend never fails");
+        self.render();
+    }
+
+    /// The last rendered frame
+    pub fn buffer(&self) -> &Buffer {
+        self.terminal.backend().buffer()
+    }
+
+    /// The last rendered frame as text, one line per row with trailing
+    /// spaces trimmed
+    pub fn snapshot(&self) -> String {
+        buffer_to_string(self.buffer())
+    }
+
+    /// Whether any row of the last frame contains `text`
+    pub fn contains(&self, text: &str) -> bool {
+        self.snapshot().lines().any(|line| line.contains(text))
+    }
+}
+
+/// Render a single view and return the frame as text
+pub fn render_to_string<V: View>(view: &V, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("test backend never fails");
+    terminal
+        .draw(|frame| render_root(view, frame))
+        .expect("test backend never fails");
+    buffer_to_string(terminal.backend().buffer())
+}
+
+/// A buffer's symbols, row by row; styles are dropped
+pub fn buffer_to_string(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut rows = Vec::with_capacity(area.height as usize);
+    for y in area.top()..area.bottom() {
+        let mut row = String::new();
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            // Wide characters fill the cells after them
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let symbol = buffer.get(x, y).symbol();
+            skip = symbol.width().saturating_sub(1);
+            row.push_str(symbol);
+        }
+        rows.push(row.trim_end().to_string());
+    }
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_signal;
+    use crate::components::VStack;
+    use crate::form::{Checkbox, TextInput};
+
+    #[test]
+    fn test_render_to_string_trims_rows() {
+        assert_eq!(render_to_string(&"Hello\nWorld", 10, 3), "Hello\nWorld\n");
+    }
+
+    #[test]
+    fn test_wide_characters() {
+        assert_eq!(render_to_string(&"日本語!", 10, 1), "日本語!");
+    }
+
+    #[test]
+    fn test_typing_into_bound_input() {
+        let name = create_signal(String::new());
+        let bound = name.clone();
+        let mut app = TestApp::new(30, 3, move || TextInput::new().label("Name").bind(bound.clone()));
+
+        app.type_text("Ada");
+        assert_eq!(name.get(), "Ada");
+        assert_eq!(app.buffer().get(1, 1).symbol(), "A");
+        assert_eq!(
+            app.snapshot(),
+            format!("┌Name{}┐\n│Ada{}│\n└{}┘", "─".repeat(24), " ".repeat(25), "─".repeat(28))
+        );
+
+        app.press(KeyCode::Backspace);
+        assert_eq!(name.get(), "Ad");
+        assert!(app.contains("│Ad "));
+    }
+
+    #[test]
+    fn test_tab_moves_focus() {
+        let first = create_signal(false);
+        let second = create_signal(false);
+        let (a, b) = (first.clone(), second.clone());
+        let mut app = TestApp::new(20, 2, move || {
+            VStack::new()
+                .child(Checkbox::new("One").bind(a.clone()))
+                .child(Checkbox::new("Two").bind(b.clone()))
+        });
+        assert_eq!(app.snapshot(), "[ ] One\n[ ] Two");
+
+        assert!(app.press(KeyCode::Char(' ')));
+        assert!(app.press(KeyCode::Tab));
+        assert!(app.press(KeyCode::Enter));
+        assert!(app.press(KeyCode::BackTab));
+        assert!(app.press(KeyCode::Char(' ')));
+
+        assert!(!first.get());
+        assert!(second.get());
+        assert_eq!(app.snapshot(), "[ ] One\n[x] Two");
+    }
+
+    #[test]
+    fn test_resize_rerenders() {
+        let mut app = TestApp::new(10, 1, || "abcdefghij");
+        assert_eq!(app.snapshot(), "abcdefghij");
+
+        app.resize(4, 2);
+        assert_eq!(app.buffer().area.width, 4);
+        assert_eq!(app.snapshot(), "abcd\n");
+    }
+}
@@ -0,0 +1,295 @@
+//! Theming for PhilJS TUI
+//!
+//! Widgets ask for styles by name (`primary`, `surface`, `error`, `focus`,
+//! ...) instead of hard-coding colors. Names resolve against the nearest
+//! [`ThemeProvider`] above the widget, falling back to the app theme:
+//!
+//! ```rust,ignore
+//! // Switch the whole app at runtime
+//! set_theme(Theme::light());
+//!
+//! // Restyle one subtree; other tokens inherit from the parent theme
+//! ThemeProvider::new(DangerZone)
+//!     .token(token::FOCUS, Style::default().fg(Color::Red))
+//! ```
+//!
+//! Overrides cascade: a provider's style is patched onto the inherited one,
+//! so overriding only the foreground keeps the inherited background and
+//! modifiers.
+
+use crate::app::Signal;
+use crate::layout::SizeHint;
+use crate::render::View;
+use crate::style::colors;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    Frame,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+/// Names of the built-in style tokens
+pub mod token {
+    /// Body text
+    pub const TEXT: &str = "text";
+    /// Secondary text, placeholders, disabled widgets
+    pub const MUTED: &str = "muted";
+    /// Headings and dialog titles
+    pub const TITLE: &str = "title";
+    /// Accent color
+    pub const PRIMARY: &str = "primary";
+    /// Background of dialogs, toasts and popups
+    pub const SURFACE: &str = "surface";
+    pub const BORDER: &str = "border";
+    /// Border or marker of the focused widget
+    pub const FOCUS: &str = "focus";
+    /// Highlighted row in a list
+    pub const HIGHLIGHT: &str = "highlight";
+    /// Selected item or active button
+    pub const SELECTED: &str = "selected";
+    pub const ERROR: &str = "error";
+    pub const SUCCESS: &str = "success";
+    pub const WARNING: &str = "warning";
+    pub const INFO: &str = "info";
+}
+
+/// Named styles
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    pub name: String,
+    styles: HashMap<String, Style>,
+}
+
+impl Theme {
+    pub fn new(name: impl Into<String>) -> Self {
+        Theme {
+            name: name.into(),
+            styles: HashMap::new(),
+        }
+    }
+
+    /// Set a token's style
+    pub fn style(mut self, token: impl Into<String>, style: Style) -> Self {
+        self.styles.insert(token.into(), style);
+        self
+    }
+
+    /// A token's style; unknown tokens get the terminal default
+    pub fn get(&self, token: &str) -> Style {
+        self.styles.get(token).copied().unwrap_or_default()
+    }
+
+    /// A token's foreground color
+    pub fn color(&self, token: &str) -> Color {
+        self.get(token).fg.unwrap_or(Color::Reset)
+    }
+
+    /// Patch `overrides` onto this theme's styles
+    pub fn merge(&self, overrides: &[(String, Style)]) -> Theme {
+        let mut theme = self.clone();
+        for (token, style) in overrides {
+            let merged = theme.get(token).patch(*style);
+            theme.styles.insert(token.clone(), merged);
+        }
+        theme
+    }
+
+    /// Light text on the terminal's background (the default)
+    pub fn dark() -> Self {
+        Theme::palette(
+            "dark",
+            Palette {
+                text: colors::TEXT,
+                muted: colors::TEXT_MUTED,
+                primary: colors::PRIMARY,
+                on_primary: Color::Black,
+                surface: Color::Rgb(30, 30, 36),
+                border: colors::BORDER,
+                error: colors::ERROR,
+                success: colors::SUCCESS,
+                warning: colors::WARNING,
+                info: colors::INFO,
+            },
+        )
+    }
+
+    /// Dark text for light terminal backgrounds
+    pub fn light() -> Self {
+        Theme::palette(
+            "light",
+            Palette {
+                text: Color::Black,
+                muted: Color::Gray,
+                primary: Color::Blue,
+                on_primary: Color::White,
+                surface: Color::Rgb(238, 238, 242),
+                border: Color::DarkGray,
+                error: Color::Red,
+                success: Color::Green,
+                warning: Color::Rgb(175, 95, 0),
+                info: Color::Blue,
+            },
+        )
+    }
+
+    /// Bright colors, bold focus and underlined selection, for low vision
+    /// and monochrome-ish terminals
+    pub fn high_contrast() -> Self {
+        Theme::palette(
+            "high-contrast",
+            Palette {
+                text: Color::White,
+                muted: Color::White,
+                primary: Color::LightYellow,
+                on_primary: Color::Black,
+                surface: Color::Black,
+                border: Color::White,
+                error: Color::LightRed,
+                success: Color::LightGreen,
+                warning: Color::LightYellow,
+                info: Color::LightCyan,
+            },
+        )
+        .style(token::FOCUS, Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD))
+        .style(
+            token::SELECTED,
+            Style::default()
+                .bg(Color::LightYellow)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        )
+    }
+
+    fn palette(name: &str, p: Palette) -> Self {
+        Theme::new(name)
+            .style(token::TEXT, Style::default().fg(p.text))
+            .style(token::MUTED, Style::default().fg(p.muted))
+            .style(token::TITLE, Style::default().fg(p.primary).add_modifier(Modifier::BOLD))
+            .style(token::PRIMARY, Style::default().fg(p.primary))
+            .style(token::SURFACE, Style::default().bg(p.surface).fg(p.text))
+            .style(token::BORDER, Style::default().fg(p.border))
+            .style(token::FOCUS, Style::default().fg(p.primary))
+            .style(token::HIGHLIGHT, Style::default().bg(p.primary).fg(p.on_primary))
+            .style(
+                token::SELECTED,
+                Style::default().bg(p.primary).fg(p.on_primary).add_modifier(Modifier::BOLD),
+            )
+            .style(token::ERROR, Style::default().fg(p.error))
+            .style(token::SUCCESS, Style::default().fg(p.success))
+            .style(token::WARNING, Style::default().fg(p.warning))
+            .style(token::INFO, Style::default().fg(p.info))
+    }
+}
+
+/// Base colors the built-in themes derive their tokens from
+struct Palette {
+    text: Color,
+    muted: Color,
+    primary: Color,
+    on_primary: Color,
+    surface: Color,
+    border: Color,
+    error: Color,
+    success: Color,
+    warning: Color,
+    info: Color,
+}
+
+// ============================================================================
+// App Theme and Providers
+// ============================================================================
+
+/// The app-wide theme, as a signal
+///
+/// Setting it redraws the app with the new theme.
+pub fn use_theme() -> Signal<Arc<Theme>> {
+    static THEME: OnceLock<Signal<Arc<Theme>>> = OnceLock::new();
+    THEME.get_or_init(|| Signal::new(Arc::new(Theme::dark()))).clone()
+}
+
+/// Replace the app-wide theme
+pub fn set_theme(theme: Theme) {
+    use_theme().set(Arc::new(theme));
+}
+
+thread_local! {
+    /// Themes of the providers being rendered, innermost last
+    static STACK: RefCell<Vec<Arc<Theme>>> = RefCell::new(Vec::new());
+}
+
+/// The theme in effect where a view is rendering
+pub fn current() -> Arc<Theme> {
+    STACK
+        .with(|stack| stack.borrow().last().cloned())
+        .unwrap_or_else(|| use_theme().get())
+}
+
+/// Resolve a style token
+pub fn style(token: &str) -> Style {
+    current().get(token)
+}
+
+/// Resolve a token's foreground color
+pub fn color(token: &str) -> Color {
+    current().color(token)
+}
+
+/// Run `f` with `theme` in effect, e.g. to draw an overlay with the theme
+/// captured where it was declared
+pub fn scoped<R>(theme: Arc<Theme>, f: impl FnOnce() -> R) -> R {
+    STACK.with(|stack| stack.borrow_mut().push(theme));
+    let result = f();
+    STACK.with(|stack| stack.borrow_mut().pop());
+    result
+}
+
+/// Applies a theme, or token overrides, to a subtree
+pub struct ThemeProvider<V: View> {
+    child: V,
+    theme: Option<Signal<Theme>>,
+    overrides: Vec<(String, Style)>,
+}
+
+impl<V: View> ThemeProvider<V> {
+    /// Inherit the surrounding theme
+    pub fn new(child: V) -> Self {
+        ThemeProvider {
+            child,
+            theme: None,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Use this theme for the subtree instead, switchable at runtime
+    pub fn theme(mut self, theme: Signal<Theme>) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Patch a token's style for the subtree
+    pub fn token(mut self, token: impl Into<String>, style: Style) -> Self {
+        self.overrides.push((token.into(), style));
+        self
+    }
+}
+
+impl<V: View> View for ThemeProvider<V> {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let base = match &self.theme {
+            Some(theme) => Arc::new(theme.get()),
+            None => current(),
+        };
+        let theme = if self.overrides.is_empty() {
+            base
+        } else {
+            Arc::new(base.merge(&self.overrides))
+        };
+        scoped(theme, || self.child.render(frame, area));
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.child.size_hint()
+    }
+}
@@ -1,9 +1,11 @@
 //! Additional widgets for PhilJS TUI
 
+use crate::layout::SizeHint;
 use crate::render::View;
+use crate::theme::{self, token};
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
+    layout::{Constraint, Rect},
+    style::Style,
     widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph, Table, Row, Cell},
     Frame,
 };
@@ -13,7 +15,8 @@ pub struct ProgressBar {
     value: f64,
     label: Option<String>,
     style: Style,
-    gauge_style: Style,
+    /// Defaults to the theme's `primary`
+    gauge_style: Option<Style>,
 }
 
 impl ProgressBar {
@@ -22,7 +25,7 @@ impl ProgressBar {
             value: value.clamp(0.0, 1.0),
             label: None,
             style: Style::default(),
-            gauge_style: Style::default().fg(Color::Cyan),
+            gauge_style: None,
         }
     }
 
@@ -37,7 +40,7 @@ impl ProgressBar {
     }
 
     pub fn gauge_style(mut self, style: Style) -> Self {
-        self.gauge_style = style;
+        self.gauge_style = Some(style);
         self
     }
 }
@@ -50,12 +53,16 @@ impl View for ProgressBar {
 
         let gauge = Gauge::default()
             .block(Block::default())
-            .gauge_style(self.gauge_style)
+            .gauge_style(self.gauge_style.unwrap_or_else(|| theme::style(token::PRIMARY)))
             .ratio(self.value)
             .label(label);
 
         frame.render_widget(gauge, area);
     }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::rows(1)
+    }
 }
 
 /// Selectable list widget
@@ -64,7 +71,8 @@ pub struct SelectList {
     selected: usize,
     title: Option<String>,
     style: Style,
-    highlight_style: Style,
+    /// Defaults to the theme's `highlight`
+    highlight_style: Option<Style>,
 }
 
 impl SelectList {
@@ -74,7 +82,7 @@ impl SelectList {
             selected: 0,
             title: None,
             style: Style::default(),
-            highlight_style: Style::default().bg(Color::Cyan).fg(Color::Black),
+            highlight_style: None,
         }
     }
 
@@ -89,7 +97,7 @@ impl SelectList {
     }
 
     pub fn highlight_style(mut self, style: Style) -> Self {
-        self.highlight_style = style;
+        self.highlight_style = Some(style);
         self
     }
 
@@ -126,7 +134,7 @@ impl View for SelectList {
         let list = List::new(items)
             .block(block)
             .style(self.style)
-            .highlight_style(self.highlight_style)
+            .highlight_style(self.highlight_style.unwrap_or_else(|| theme::style(token::HIGHLIGHT)))
             .highlight_symbol("> ");
 
         let mut state = ListState::default();
@@ -140,7 +148,8 @@ impl View for SelectList {
 pub struct Spinner {
     frames: Vec<&'static str>,
     current: usize,
-    style: Style,
+    /// Defaults to the theme's `primary`
+    style: Option<Style>,
 }
 
 impl Spinner {
@@ -148,7 +157,7 @@ impl Spinner {
         Spinner {
             frames: vec!["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
             current: 0,
-            style: Style::default().fg(Color::Cyan),
+            style: None,
         }
     }
 
@@ -160,7 +169,7 @@ impl Spinner {
         Spinner {
             frames: vec!["-", "\\", "|", "/"],
             current: 0,
-            style: Style::default().fg(Color::Cyan),
+            style: None,
         }
     }
 
@@ -169,7 +178,7 @@ impl Spinner {
     }
 
     pub fn style(mut self, style: Style) -> Self {
-        self.style = style;
+        self.style = Some(style);
         self
     }
 }
@@ -183,9 +192,14 @@ impl Default for Spinner {
 impl View for Spinner {
     fn render(&self, frame: &mut Frame, area: Rect) {
         let text = self.frames[self.current];
-        let paragraph = Paragraph::new(text).style(self.style);
+        let style = self.style.unwrap_or_else(|| theme::style(token::PRIMARY));
+        let paragraph = Paragraph::new(text).style(style);
         frame.render_widget(paragraph, area);
     }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::new(Constraint::Length(1), Constraint::Length(1))
+    }
 }
 
 /// Input field widget
@@ -194,7 +208,8 @@ pub struct Input {
     cursor: usize,
     placeholder: Option<String>,
     style: Style,
-    cursor_style: Style,
+    /// Defaults to the theme's `selected`
+    cursor_style: Option<Style>,
     focused: bool,
 }
 
@@ -205,7 +220,7 @@ impl Input {
             cursor: 0,
             placeholder: None,
             style: Style::default(),
-            cursor_style: Style::default().bg(Color::White).fg(Color::Black),
+            cursor_style: None,
             focused: false,
         }
     }
@@ -276,7 +291,7 @@ impl View for Input {
         };
 
         let style = if self.value.is_empty() && self.placeholder.is_some() {
-            self.style.fg(Color::DarkGray)
+            self.style.patch(theme::style(token::MUTED))
         } else {
             self.style
         };
@@ -284,4 +299,8 @@ impl View for Input {
         let paragraph = Paragraph::new(display).style(style);
         frame.render_widget(paragraph, area);
     }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::rows(1)
+    }
 }